@@ -4,6 +4,11 @@ xSwarm Assistant - Developer-centric AI for managing multiple software projects.
 
 from importlib.metadata import version, PackageNotFoundError
 
+# Errors are cheap to import, and the one part of the API callers match on (see errors.py)
+from .errors import (
+    ConfigError, MemoryStoreError, SchedulerError, UnknownJobError, VoiceError, XswarmError,
+)
+
 # Lazy imports to avoid slow startup from heavy dependencies (openai, anthropic, torch)
 # These are only imported when actually accessed
 def __getattr__(name):
@@ -29,4 +34,7 @@ try:
 except PackageNotFoundError:
     __version__ = "0.0.0"
 
-__all__ = ["Config", "ChatEngine", "ChatEngineConfig", "ChatMessage", "MessageRole"]
+__all__ = [
+    "Config", "ChatEngine", "ChatEngineConfig", "ChatMessage", "MessageRole",
+    "XswarmError", "ConfigError", "SchedulerError", "UnknownJobError", "MemoryStoreError", "VoiceError",
+]
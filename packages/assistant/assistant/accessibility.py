@@ -0,0 +1,141 @@
+"""
+Accessibility Stream - The dashboard as plain lines, for screen readers and braille displays.
+
+A full-screen TUI redraws regions in place, which screen readers can't
+follow. This stream writes what the dashboard shows as one plain line per
+event, oldest first:
+
+    14:02 You: what's next today?
+    14:02 Jarvis: Your dentist appointment is at 4.
+    14:05 Warning: Inbox sync failed
+    14:06 Mic: wake word only
+
+- `xswarm --a11y` replaces the TUI: the dashboard runs headless, lines go
+  to stdout and each line typed on stdin is sent as a chat message
+- `xswarm --a11y FILE` keeps the TUI and also writes the lines to FILE
+  (a file or FIFO read from another terminal)
+- config.a11y_output does the same without the flag ("-" for stdout)
+
+Lines have no ANSI codes, box drawing or emoji; the level is a word
+instead of a color. Streamed replies are written once, when complete.
+"""
+
+import logging
+import re
+import sys
+import threading
+import unicodedata
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, Optional, TextIO, Union
+
+logger = logging.getLogger(__name__)
+
+STDOUT = "-"
+
+LEVELS = {"error": "Error", "warning": "Warning", "success": "Done", "info": "Info"}
+
+_ANSI = re.compile(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07]*\x07")
+_STREAMING = ("▌", "...")  # Typing indicator and placeholder while a reply streams in
+
+
+def plain(text: str) -> str:
+    """
+    `text` without ANSI codes, emoji, box drawing or status symbols, on one
+    line: "✓ Synced 3 events" -> "Synced 3 events".
+    """
+    text = _ANSI.sub("", text or "")
+    kept = []
+    for char in text:
+        category = unicodedata.category(char)
+        if char in "\n\r\t":
+            kept.append(" ")
+        elif category in ("So", "Sk", "Cf", "Cc", "Co", "Mn") and char not in "°":
+            continue  # Emoji, symbols, variation selectors, control characters
+        elif "─" <= char <= "▟":
+            continue  # Box drawing and block elements (bars, borders)
+        else:
+            kept.append(char)
+    return " ".join("".join(kept).split())
+
+
+class AccessibleStream:
+    """Writes dashboard events to a text stream as plain, timestamped lines."""
+
+    def __init__(self, out: TextIO, replaces_tui: bool = False,
+                 clock: Callable[[], datetime] = datetime.now):
+        self.out = out
+        self.replaces_tui = replaces_tui  # Writing to the terminal: no TUI, input comes from stdin
+        self.clock = clock
+        self._lock = threading.Lock()
+        self._last_chat: Optional[tuple] = None
+        self._mic: Optional[str] = None
+
+    @classmethod
+    def open(cls, target: Union[str, Path]) -> "AccessibleStream":
+        """ "-" for stdout (instead of the TUI), otherwise a file to append to (alongside it)."""
+        if str(target) == STDOUT:
+            return cls(sys.stdout, replaces_tui=True)
+        path = Path(target).expanduser()
+        path.parent.mkdir(parents=True, exist_ok=True)
+        return cls(open(path, "a", buffering=1, encoding="utf-8"))
+
+    def write(self, text: str) -> None:
+        line = plain(text)
+        if not line:
+            return
+        with self._lock:
+            try:
+                self.out.write(f"{self.clock():%H:%M} {line}\n")
+                self.out.flush()
+            except (OSError, ValueError) as e:
+                logger.debug(f"Accessibility stream closed: {e}")
+
+    def activity(self, message: str, level: str = "info") -> None:
+        self.write(f"{LEVELS.get(level, 'Info')}: {message}")
+
+    def chat(self, sender: str, text: str) -> None:
+        """A chat message, skipped while it's still streaming in and when repeated."""
+        stripped = (text or "").strip()
+        if sender.lower() in ("thinking", "debug") or not stripped or stripped.endswith(_STREAMING):
+            return
+        if (sender, stripped) == self._last_chat:
+            return
+        self._last_chat = (sender, stripped)
+        self.write(f"{'You' if sender == 'User' else sender}: {stripped}")
+
+    def mic(self, label: str) -> None:
+        """Mic state ("listening", "wake word only", "mic off"), when it changes."""
+        if label != self._mic:
+            self._mic = label
+            self.write(f"Mic: {label}")
+
+    def close(self) -> None:
+        if self.out is not sys.stdout:
+            with self._lock:
+                self.out.close()
+
+
+_stream: Optional[AccessibleStream] = None
+
+
+def get_a11y_stream() -> Optional[AccessibleStream]:
+    """The accessibility stream, or None when it's off."""
+    return _stream
+
+
+def set_a11y_stream(stream: Optional[AccessibleStream]) -> None:
+    global _stream
+    _stream = stream
+
+
+def mirror_activity(message: str, level: str = "info") -> None:
+    """Write an activity-feed entry to the accessibility stream, if it's on."""
+    if _stream is not None:
+        _stream.activity(message, level)
+
+
+def mirror_chat(sender: str, text: str) -> None:
+    """Write a chat message to the accessibility stream, if it's on."""
+    if _stream is not None:
+        _stream.chat(sender, text)
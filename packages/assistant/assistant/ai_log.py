@@ -0,0 +1,309 @@
+"""
+AI Log - Every request to a language model, kept so it can be audited.
+
+Each call the assistant makes to a model - the chat, the voice replies,
+the thinking engine, memory's fact extraction and recall - is recorded
+with log_ai_call(): provider, model, what it was for, tokens in and out,
+latency and any error. How much of the content is kept is config.ai_log:
+
+- "off": nothing is recorded
+- "metadata": no content, only how long each message was
+- "truncated" (default): the first config.ai_log_chars of the system
+  prompt, each message and the reply
+- "full": everything that was sent and received
+
+Content is redacted before it's stored (redaction.py; in strict mode
+identifiers become keyed hashes, so the same address can be followed
+through the log), and every call notes what the redactor found in the full
+request - {"email": 2, "phone": 1} - whatever the level, so personal
+details sent to a provider show up even in metadata mode.
+
+`xswarm dev ai log` lists calls (--provider, --purpose, --since) with a
+total of tokens and personal details per provider; --show ID prints one
+call's content, and the interaction it was part of (its thread in `xswarm
+dev events query`, see correlation.py). Calls are kept for retention_days["ai_log"] (30 days).
+
+Like record_event(), log_ai_call() does nothing until a log is set up
+(the dashboard does, so tests and scripts don't write to it).
+
+Storage: ~/.xswarm/ai_log.db (format upgrades in MIGRATIONS)
+"""
+
+import json
+import logging
+import re
+import sqlite3
+import threading
+import time
+from collections import Counter
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Union
+
+from .correlation import current_id
+from .migrations import Migration, migrate_sqlite
+from .redaction import get_redactor
+
+logger = logging.getLogger(__name__)
+
+LEVELS = ("off", "metadata", "truncated", "full")
+DEFAULT_CHARS = 200
+
+_SCHEMA = """
+CREATE TABLE IF NOT EXISTS calls (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts REAL NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    purpose TEXT NOT NULL,
+    input_tokens INTEGER,
+    output_tokens INTEGER,
+    latency REAL,
+    error TEXT NOT NULL DEFAULT '',
+    data TEXT NOT NULL DEFAULT '{}'
+);
+CREATE INDEX IF NOT EXISTS calls_ts ON calls (ts);
+"""
+
+# Format changes to ai_log.db, oldest first (see migrations.py)
+MIGRATIONS = [
+    Migration(1, "calls table", lambda db: db.executescript(_SCHEMA)),
+]
+
+_MASK = re.compile(r"\[(\w+)(?::[0-9a-f]{8})?\]")
+
+
+@dataclass
+class AICall:
+    """One logged request and its reply."""
+    provider: str
+    model: str
+    purpose: str
+    time: datetime
+    input_tokens: Optional[int] = None
+    output_tokens: Optional[int] = None
+    latency: Optional[float] = None  # Seconds
+    error: str = ""
+    data: Dict[str, Any] = field(default_factory=dict)  # sensitive, system, messages, reply (per the level)
+    id: Optional[int] = None
+
+    def line(self) -> str:
+        tokens = "? tok" if self.input_tokens is None else f"{self.input_tokens}→{self.output_tokens or 0} tok"
+        latency = f"{self.latency:.1f}s" if self.latency is not None else "-"
+        sensitive = ", ".join(f"{name}×{n}" for name, n in sorted(self.data.get("sensitive", {}).items()))
+        flags = (f"  ⚠ {sensitive}" if sensitive else "") + (f"  ✗ {self.error}" if self.error else "")
+        return (f"{self.id:>5}  {self.time:%Y-%m-%d %H:%M}  {self.provider:<9} {self.model:<28} {self.purpose:<9}"
+                f" {tokens:>14} {latency:>6}{flags}")
+
+
+def text_of(content: Any) -> str:
+    """A message's content as text: content blocks joined, tool calls and results named."""
+    if isinstance(content, str):
+        return content
+    parts = []
+    for block in content or []:
+        if not isinstance(block, dict):
+            parts.append(str(block))
+        elif block.get("type") == "tool_use":
+            parts.append(f"[tool call {block.get('name')}: {json.dumps(block.get('input', {}), ensure_ascii=False)}]")
+        elif block.get("type") == "tool_result":
+            parts.append(f"[tool result: {text_of(block.get('content', ''))}]")
+        else:
+            parts.append(block.get("text", ""))
+    return "\n".join(part for part in parts if part)
+
+
+def usage_of(response: Any) -> Dict[str, Optional[int]]:
+    """Tokens in and out from an Anthropic, OpenAI, Ollama or LM Studio response (JSON or SDK object)."""
+    usage = response.get("usage") if isinstance(response, dict) else getattr(response, "usage", None)
+    if isinstance(response, dict) and usage is None and "prompt_eval_count" in response:
+        usage = {"input_tokens": response.get("prompt_eval_count"), "output_tokens": response.get("eval_count")}
+    if usage is None:
+        return {"input_tokens": None, "output_tokens": None}
+
+    def get(*names):
+        for name in names:
+            value = usage.get(name) if isinstance(usage, dict) else getattr(usage, name, None)
+            if value is not None:
+                return int(value)
+        return None
+    return {"input_tokens": get("input_tokens", "prompt_tokens"),
+            "output_tokens": get("output_tokens", "completion_tokens")}
+
+
+def sensitive_counts(texts: List[str]) -> Dict[str, int]:
+    """What the redactor masks in `texts`, by kind ({"email": 2})."""
+    redactor, found = get_redactor(), Counter()
+    for text in texts:
+        if text:
+            found.update(_MASK.findall(redactor(text)))
+            found.subtract(_MASK.findall(text))  # Already masked before it was sent
+    return {name: n for name, n in found.items() if n > 0}
+
+
+class AILog:
+    """SQLite log of model calls (":memory:" for a throwaway one)."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "ai_log.db"
+
+    def __init__(self, path: Union[Path, str, None] = None, level: str = "truncated", chars: int = DEFAULT_CHARS):
+        if level not in LEVELS:
+            raise ValueError(f"Unknown ai_log level '{level}' (use {', '.join(LEVELS)})")
+        self.path = path or self.DEFAULT_PATH
+        self.level = level
+        self.chars = chars
+        if self.path != ":memory:":
+            Path(self.path).parent.mkdir(parents=True, exist_ok=True)
+        self._lock = threading.Lock()
+        self._db = sqlite3.connect(str(self.path), check_same_thread=False)
+        try:
+            self.migrated = migrate_sqlite(self._db, self.path, "ai log", MIGRATIONS)
+        except Exception:
+            self._db.close()
+            raise
+
+    @classmethod
+    def from_config(cls, config, path: Union[Path, str, None] = None) -> "AILog":
+        return cls(path, getattr(config, "ai_log", "truncated"), getattr(config, "ai_log_chars", DEFAULT_CHARS))
+
+    def _content(self, text: str) -> Any:
+        """`text` as kept at this level: its length, the redacted start, or all of it redacted."""
+        if self.level == "metadata":
+            return len(text)
+        text = get_redactor()(text)
+        if self.level == "truncated" and len(text) > self.chars:
+            return text[:self.chars] + f"… (+{len(text) - self.chars} chars)"
+        return text
+
+    def record(self, provider: str, model: str, purpose: str, request: Dict[str, Any], reply: str = "",
+               usage: Optional[Dict[str, Optional[int]]] = None, latency: Optional[float] = None,
+               error: str = "", at: Optional[datetime] = None, thread: Optional[str] = None) -> AICall:
+        """Log one call; `request` is the request body, `thread` the interaction (the current one by default)."""
+        messages = [(m.get("role", "?"), text_of(m.get("content", ""))) for m in request.get("messages", [])]
+        # Anthropic takes the system prompt beside the messages, OpenAI-style APIs as the first message
+        system = "\n".join([text_of(request.get("system") or "")] + [t for role, t in messages if role == "system"])
+        system, messages = system.strip(), [(role, t) for role, t in messages if role != "system"]
+        data = {"sensitive": sensitive_counts([system] + [text for _, text in messages])}
+        if system:
+            data["system"] = self._content(system)
+        data["messages"] = [{"role": role, "content": self._content(text)} for role, text in messages]
+        data["reply"] = self._content(reply or "")
+        thread = thread or current_id()
+        if thread:
+            data["thread"] = thread
+        usage = usage or {}
+        call = AICall(provider, model or "?", purpose, at or datetime.now(), usage.get("input_tokens"),
+                      usage.get("output_tokens"), latency, get_redactor()(error or "")[:300], data)
+        with self._lock:
+            cursor = self._db.execute(
+                "INSERT INTO calls (ts, provider, model, purpose, input_tokens, output_tokens, latency, error, data)"
+                " VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                (call.time.timestamp(), provider, call.model, purpose, call.input_tokens, call.output_tokens,
+                 latency, call.error, json.dumps(data, ensure_ascii=False)))
+            self._db.commit()
+        call.id = cursor.lastrowid
+        return call
+
+    def query(self, since: Optional[datetime] = None, provider: Optional[str] = None, purpose: Optional[str] = None,
+              limit: Optional[int] = None) -> List[AICall]:
+        """Calls matching the filters, oldest first (the newest `limit`)."""
+        where, args = [], []
+        for column, value in (("provider", provider), ("purpose", purpose)):
+            if value:
+                where.append(f"{column} = ?")
+                args.append(value)
+        if since is not None:
+            where.append("ts >= ?")
+            args.append(since.timestamp())
+        sql = "SELECT id, ts, provider, model, purpose, input_tokens, output_tokens, latency, error, data FROM calls"
+        if where:
+            sql += " WHERE " + " AND ".join(where)
+        sql += " ORDER BY ts DESC, id DESC"
+        if limit:
+            sql += f" LIMIT {int(limit)}"
+        with self._lock:
+            rows = self._db.execute(sql, args).fetchall()
+        return [AICall(provider, model, purpose, datetime.fromtimestamp(ts), tokens_in, tokens_out, latency, error,
+                       json.loads(data), id)
+                for id, ts, provider, model, purpose, tokens_in, tokens_out, latency, error, data in reversed(rows)]
+
+    def get(self, call_id: int) -> Optional[AICall]:
+        with self._lock:
+            row = self._db.execute(
+                "SELECT id, ts, provider, model, purpose, input_tokens, output_tokens, latency, error, data"
+                " FROM calls WHERE id = ?", (call_id,)).fetchone()
+        if row is None:
+            return None
+        id, ts, provider, model, purpose, tokens_in, tokens_out, latency, error, data = row
+        return AICall(provider, model, purpose, datetime.fromtimestamp(ts), tokens_in, tokens_out, latency, error,
+                      json.loads(data), id)
+
+    def prune(self, before: datetime, dry_run: bool = False) -> int:
+        """Delete calls older than `before` (only count them with dry_run); returns how many."""
+        with self._lock:
+            if dry_run:
+                return self._db.execute("SELECT COUNT(*) FROM calls WHERE ts < ?", (before.timestamp(),)).fetchone()[0]
+            cursor = self._db.execute("DELETE FROM calls WHERE ts < ?", (before.timestamp(),))
+            self._db.commit()
+            return cursor.rowcount
+
+    def close(self) -> None:
+        with self._lock:
+            self._db.close()
+
+
+def summarize(calls: List[AICall]) -> List[str]:
+    """Per provider: calls, tokens, and the personal details sent."""
+    lines = []
+    for provider in sorted({call.provider for call in calls}):
+        mine = [call for call in calls if call.provider == provider]
+        sent = Counter()
+        for call in mine:
+            sent.update(call.data.get("sensitive", {}))
+        tokens_in = sum(call.input_tokens or 0 for call in mine)
+        tokens_out = sum(call.output_tokens or 0 for call in mine)
+        details = ", ".join(f"{name} ×{n}" for name, n in sorted(sent.items())) or "none"
+        lines.append(f"{provider}: {len(mine)} calls, {tokens_in} tokens in, {tokens_out} out; "
+                     f"personal details sent: {details}")
+    return lines
+
+
+def format_call(call: AICall) -> str:
+    """One call in full, as kept: header, system prompt, messages, reply."""
+    lines = [call.line() + (f"  (thread {call.data['thread']})" if "thread" in call.data else "")]
+
+    def show(label: str, content: Any) -> None:
+        shown = f"({content} chars, not kept)" if isinstance(content, int) else content
+        lines.append(f"--- {label}\n{shown}")
+    if "system" in call.data:
+        show("system", call.data["system"])
+    for message in call.data.get("messages", []):
+        show(message["role"], message["content"])
+    show("reply", call.data.get("reply", ""))
+    return "\n".join(lines)
+
+
+_log: Optional[AILog] = None
+
+
+def get_ai_log() -> Optional[AILog]:
+    return _log
+
+
+def set_ai_log(log: Optional[AILog]) -> None:
+    global _log
+    _log = log
+
+
+def log_ai_call(provider: str, model: str, purpose: str, request: Dict[str, Any], reply: str = "",
+                response: Any = None, started: Optional[float] = None, error: str = "") -> None:
+    """Record a model call in the AI log, if one is set up; `started` is time.monotonic() before the call."""
+    if _log is None or _log.level == "off":
+        return
+    try:
+        latency = time.monotonic() - started if started is not None else None
+        _log.record(provider, model, purpose, request, reply, usage_of(response) if response is not None else None,
+                    latency, error)
+    except Exception as e:
+        logger.debug(f"Could not log {provider} call: {e}")
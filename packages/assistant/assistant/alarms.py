@@ -0,0 +1,338 @@
+"""
+Alarms - Wake-up alarms that ring until you're up.
+
+Unlike reminders (a spoken line before an event, subject to quiet hours),
+an alarm rings at an exact time and keeps ringing:
+
+    xswarm dev alarm add 06:30 --days weekdays --label "Gym"
+    xswarm dev alarm add 7am                      # Once, at the next 7:00
+    xswarm dev alarm list
+    xswarm dev alarm remove ID
+
+or "wake me up at half past six on weekdays" (the set_alarm tool).
+
+- Ringing: a beep pattern on the media stream (volume.py), from
+  config.alarm_start_volume up to full over config.alarm_ramp_seconds.
+  The beeps leave gaps so the mic still hears you over them. It stops by
+  itself after config.alarm_ring_minutes.
+- Snooze by voice: "five more minutes", "snooze" (config.alarm_snooze_minutes).
+  Dismiss: "stop", "I'm up", "turn it off". No wake word needed while ringing.
+- Briefing: BRIEFING_LEAD before it rings, today's calendar and next tasks
+  are gathered (announcements.briefing_context); they're read out once the
+  alarm is dismissed. --no-briefing turns that off.
+- Recurrence: days of the week (--days weekdays, weekends, daily, mon,wed,fri);
+  without days an alarm rings once and then turns itself off.
+
+Quiet hours and do not disturb don't apply - that's what an alarm is for.
+The next ring time (including a snooze) is saved, so an alarm survives the
+daemon restarting overnight: restarted mid-ring it rings on; if it was down
+through the whole ring window the alarm counts as missed and moves to its
+next day.
+
+Storage: ~/.xswarm/alarms/alarms.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Dict, List, Optional, Set, Tuple
+
+from .capabilities import register_capability
+from .dates import NUMBER_WORDS, WEEKDAY_ALIASES, parse_time_expression
+
+logger = logging.getLogger(__name__)
+
+BRIEFING_LEAD = timedelta(minutes=2)  # The wake-up briefing is gathered this long before ringing
+WAKE_BRIEFING = "{greeting}. It's {time} on {weekday}, {date}. {briefing}"  # announcements.render placeholders
+DAY_NAMES = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"]
+DAY_SETS = {
+    "daily": list(range(7)), "everyday": list(range(7)), "every day": list(range(7)),
+    "weekdays": list(range(5)), "weekends": [5, 6],
+}
+
+_SNOOZE = re.compile(r"\b(snooze|more minutes?|few more|not yet|later)\b")
+_MINUTES = re.compile(r"\b(\d{1,2}|[a-z]+)\s+(?:more\s+)?min(?:ute)?s?\b")
+_DISMISS = re.compile(r"\b(stop|dismiss|turn (?:it |that )?off|shut (?:it )?off|i'?m up|i am up|i'?m awake|"
+                      r"i am awake|enough|cancel|alarm off)\b")
+
+
+class AlarmError(ValueError):
+    """A time, set of days or alarm id that doesn't work."""
+
+
+def parse_days(text: Optional[str]) -> List[int]:
+    """Weekday numbers (Monday 0) from "weekdays", "weekends", "daily" or "mon,wed,fri"; empty means once."""
+    text = (text or "").strip().lower()
+    if not text or text == "once":
+        return []
+    if text in DAY_SETS:
+        return list(DAY_SETS[text])
+    days: Set[int] = set()
+    for part in re.split(r"[,\s]+|\band\b", text):
+        if not part:
+            continue
+        if part not in WEEKDAY_ALIASES and part.rstrip("s") not in WEEKDAY_ALIASES:
+            raise AlarmError(f"Unknown day '{part}' (weekdays, weekends, daily or mon,wed,fri)")
+        days.add(WEEKDAY_ALIASES.get(part, WEEKDAY_ALIASES.get(part.rstrip("s"))))
+    return sorted(days)
+
+
+def describe_days(days: List[int]) -> str:
+    for name in ("daily", "weekdays", "weekends"):
+        if sorted(days) == DAY_SETS[name]:
+            return name
+    return ",".join(DAY_NAMES[day] for day in days) if days else "once"
+
+
+@dataclass
+class Alarm:
+    time: str  # "HH:MM"
+    days: List[int] = field(default_factory=list)  # Weekdays it rings on (Monday 0); empty: once
+    label: str = ""
+    briefing: bool = True
+    enabled: bool = True
+    id: str = ""
+    next_ring: Optional[str] = None  # When it next rings (a snooze moves it)
+    snoozes: int = 0  # Since it last started ringing
+
+    def __post_init__(self):
+        if not self.id:
+            self.id = uuid.uuid4().hex[:8]
+
+    @property
+    def recurring(self) -> bool:
+        return bool(self.days)
+
+    def occurrence_after(self, after: datetime) -> datetime:
+        """The first time it's set for after `after`, on one of its days."""
+        hour, minute = (int(part) for part in self.time.split(":"))
+        candidate = after.replace(hour=hour, minute=minute, second=0, microsecond=0)
+        if candidate <= after:
+            candidate += timedelta(days=1)
+        while self.days and candidate.weekday() not in self.days:
+            candidate += timedelta(days=1)
+        return candidate
+
+    def ring_time(self) -> Optional[datetime]:
+        return datetime.fromisoformat(self.next_ring) if self.enabled and self.next_ring else None
+
+    def describe(self) -> str:
+        name = f" ({self.label})" if self.label else ""
+        return f"{self.time} {describe_days(self.days)}{name}"
+
+
+# ==============================================================================
+# STORE
+# ==============================================================================
+
+class AlarmStore:
+    """Alarms, in one JSON file (the CLI edits it while the dashboard runs)."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "alarms"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "alarms.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+        path = self._path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load alarms: {e}")
+        self._data = {"alarms": []}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            with open(self._path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save alarms: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    def all(self) -> List[Alarm]:
+        return [Alarm(**raw) for raw in self._load()["alarms"]]
+
+    def add(self, time: str, days: Optional[str] = None, label: str = "", briefing: bool = True,
+            now: Optional[datetime] = None) -> Alarm:
+        """An alarm at a time of day ("06:30", "7am", "half past six") on some days (see parse_days)."""
+        hhmm = parse_time_expression(time)
+        if hhmm is None:
+            raise AlarmError(f"Not a time of day: {time!r}")
+        alarm = Alarm(hhmm, parse_days(days), label.strip(), briefing)
+        alarm.next_ring = alarm.occurrence_after(now or datetime.now()).isoformat()
+        self._load()["alarms"].append(asdict(alarm))
+        self._save()
+        return alarm
+
+    def remove(self, alarm_id: str) -> Alarm:
+        """Remove by id (a unique prefix is enough)."""
+        data = self._load()
+        matches = [raw for raw in data["alarms"] if raw["id"].startswith(alarm_id)]
+        if len(matches) != 1:
+            raise AlarmError(f"No alarm '{alarm_id}'" if not matches
+                             else f"'{alarm_id}' matches {len(matches)} alarms")
+        data["alarms"].remove(matches[0])
+        self._save()
+        return Alarm(**matches[0])
+
+    def update(self, alarm: Alarm) -> None:
+        data = self._load()
+        for i, raw in enumerate(data["alarms"]):
+            if raw["id"] == alarm.id:
+                data["alarms"][i] = asdict(alarm)
+                self._save()
+                return
+
+
+# ==============================================================================
+# RINGING
+# ==============================================================================
+
+class AlarmClock:
+    """
+    When alarms ring, how loud, and what snoozing or dismissing does. The
+    dashboard's alarms job calls tick() every couple of seconds and plays
+    ring_tone() at volume() for whatever is ringing().
+    """
+
+    def __init__(self, store: Optional[AlarmStore] = None, start_volume: float = 0.2, ramp_seconds: float = 60.0,
+                 ring_minutes: float = 10.0, snooze_minutes: float = 9.0):
+        self.store = store or AlarmStore()
+        self.start_volume = max(0.0, min(1.0, start_volume))
+        self.ramp_seconds = max(0.0, ramp_seconds)
+        self.ring_minutes = ring_minutes
+        self.snooze_minutes = snooze_minutes
+        self._prepared: Set[str] = set()  # Alarms whose briefing was gathered for the coming ring
+        self._rang: Set[str] = set()  # Alarms that started ringing in this process
+
+    @classmethod
+    def from_config(cls, config, store: Optional[AlarmStore] = None) -> "AlarmClock":
+        return cls(store, getattr(config, "alarm_start_volume", 0.2), getattr(config, "alarm_ramp_seconds", 60.0),
+                   getattr(config, "alarm_ring_minutes", 10.0), getattr(config, "alarm_snooze_minutes", 9.0))
+
+    def tick(self, now: datetime) -> List[Tuple[str, Alarm]]:
+        """
+        What changed since the last tick: ("prepare", alarm) shortly before it
+        rings, ("ring", alarm) when it starts, ("timeout", alarm) when it rang
+        unanswered for ring_minutes, ("missed", alarm) when the whole ring
+        window passed while the assistant wasn't running.
+        """
+        self.store.reload()  # `xswarm dev alarm` edits the file
+        window = timedelta(minutes=self.ring_minutes)
+        events = []
+        for alarm in self.store.all():
+            ring_at = alarm.ring_time()
+            if ring_at is None:
+                continue
+            if now < ring_at:
+                if now >= ring_at - BRIEFING_LEAD and alarm.briefing and alarm.id not in self._prepared:
+                    self._prepared.add(alarm.id)
+                    events.append(("prepare", alarm))
+            elif now - ring_at < window:
+                if alarm.id not in self._rang:
+                    self._rang.add(alarm.id)
+                    events.append(("ring", alarm))
+            else:
+                events.append(("timeout" if alarm.id in self._rang else "missed", alarm))
+                self._finish(alarm, now)
+        return events
+
+    def ringing(self, now: datetime) -> List[Alarm]:
+        window = timedelta(minutes=self.ring_minutes)
+        return [alarm for alarm in self.store.all()
+                if alarm.ring_time() is not None and timedelta(0) <= now - alarm.ring_time() < window]
+
+    def volume(self, alarm: Alarm, now: datetime) -> float:
+        """From start_volume up to 1.0 over ramp_seconds of ringing."""
+        ring_at = alarm.ring_time()
+        if ring_at is None or self.ramp_seconds == 0:
+            return 1.0
+        progress = max(0.0, min(1.0, (now - ring_at).total_seconds() / self.ramp_seconds))
+        return self.start_volume + (1.0 - self.start_volume) * progress
+
+    def snooze(self, now: datetime, minutes: Optional[float] = None) -> List[Alarm]:
+        """Ring the ringing alarms again in `minutes` (default snooze_minutes); their briefing waits."""
+        snoozed = self.ringing(now)
+        for alarm in snoozed:
+            alarm.next_ring = (now + timedelta(minutes=minutes or self.snooze_minutes)).replace(microsecond=0).isoformat()
+            alarm.snoozes += 1
+            self._rang.discard(alarm.id)
+            self.store.update(alarm)
+        return snoozed
+
+    def dismiss(self, now: datetime) -> List[Alarm]:
+        """Stop the ringing alarms: recurring ones move to their next day, one-offs turn off."""
+        dismissed = self.ringing(now)
+        for alarm in dismissed:
+            self._finish(alarm, now)
+        return dismissed
+
+    def _finish(self, alarm: Alarm, now: datetime) -> None:
+        if alarm.recurring:
+            alarm.next_ring = alarm.occurrence_after(now).isoformat()
+        else:
+            alarm.enabled = False
+            alarm.next_ring = None
+        alarm.snoozes = 0
+        self._rang.discard(alarm.id)
+        self._prepared.discard(alarm.id)
+        self.store.update(alarm)
+
+
+# ==============================================================================
+# VOICE
+# ==============================================================================
+
+def parse_alarm_command(text: str) -> Optional[Tuple[str, Optional[int]]]:
+    """
+    ("snooze", minutes or None) for "five more minutes" / "snooze for ten
+    minutes", ("dismiss", None) for "stop" / "I'm up"; None otherwise.
+    Only meaningful while an alarm rings.
+    """
+    lowered = text.lower().replace("’", "'")
+    if _SNOOZE.search(lowered):
+        match = _MINUTES.search(lowered)
+        if match:
+            word = match.group(1)
+            minutes = int(word) if word.isdigit() else NUMBER_WORDS.get(word)
+            if minutes and word not in ("a", "an"):
+                return ("snooze", minutes)
+        return ("snooze", None)
+    if _DISMISS.search(lowered):
+        return ("dismiss", None)
+    return None
+
+
+def ring_tone(sample_rate: int = 24000):
+    """About a second of alarm beeps (float32, peak 1.0), gaps included."""
+    import numpy as np
+
+    from .earcons import _note
+
+    beep, gap = _note(880.0, 0.12, "tone", sample_rate), _note(0.0, 0.08, "tone", sample_rate)
+    return np.concatenate([beep, gap, beep, gap, beep, gap, beep, _note(0.0, 0.2, "tone", sample_rate)])
+
+
+register_capability("Alarms", "Wake-up alarms that ring until you snooze or stop them",
+                    ["wake me up at half past six on weekdays", "five more minutes", "I'm up"],
+                    keywords=("wake", "snooze", "morning"))
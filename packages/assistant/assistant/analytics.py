@@ -0,0 +1,170 @@
+"""
+Usage Analytics - How the assistant gets used, from the event history.
+
+Built from the events the dashboard records (events.py):
+
+- conversations per day: turns (utterances and typed messages) with less
+  than CONVERSATION_GAP between them count as one conversation
+- response latency: time from a turn to the first answer (reply events)
+- reminders delivered vs missed: delivered when a reminder reached the
+  desktop or speech, missed when quiet hours or preferences held it back
+- most used commands: tool calls by name
+- messages per day: new texts, emails and voicemails from the inbox
+
+Shown as charts on the Status tab (UsageWidget, and messages per day in a
+BarChart) and exported with
+`xswarm dev analytics --period week --format json|csv`.
+"""
+
+import csv
+import io
+import json
+from collections import Counter
+from dataclasses import dataclass, field
+from datetime import date, datetime, time, timedelta
+from typing import Any, Dict, List, Optional, Tuple
+
+from .charts import bar_length
+from .events import INBOX_TYPES
+
+CONVERSATION_GAP = timedelta(minutes=5)
+PERIODS = {"day": 1, "week": 7}
+TOP_COMMANDS = 5
+CSV_FIELDS = ["date", "conversations", "turns", "avg_latency_s", "reminders_delivered", "reminders_missed",
+              "messages"]
+
+
+@dataclass
+class DayUsage:
+    """One day's numbers."""
+    day: date
+    conversations: int = 0
+    turns: int = 0
+    latencies: List[float] = field(default_factory=list)
+    reminders_delivered: int = 0
+    reminders_missed: int = 0
+    messages: int = 0
+
+    @property
+    def avg_latency(self) -> Optional[float]:
+        return sum(self.latencies) / len(self.latencies) if self.latencies else None
+
+    def row(self) -> Dict[str, Any]:
+        latency = self.avg_latency
+        return {
+            "date": self.day.isoformat(),
+            "conversations": self.conversations,
+            "turns": self.turns,
+            "avg_latency_s": round(latency, 2) if latency is not None else None,
+            "reminders_delivered": self.reminders_delivered,
+            "reminders_missed": self.reminders_missed,
+            "messages": self.messages,
+        }
+
+
+@dataclass
+class UsageReport:
+    """Usage per day over a period, plus the most used commands."""
+    period: str
+    days: List[DayUsage]
+    commands: List[Tuple[str, int]]
+
+    @property
+    def conversations(self) -> int:
+        return sum(d.conversations for d in self.days)
+
+    @property
+    def avg_latency(self) -> Optional[float]:
+        latencies = [seconds for d in self.days for seconds in d.latencies]
+        return sum(latencies) / len(latencies) if latencies else None
+
+    @property
+    def reminders(self) -> Tuple[int, int]:
+        """(delivered, missed)"""
+        return sum(d.reminders_delivered for d in self.days), sum(d.reminders_missed for d in self.days)
+
+    def to_json(self) -> str:
+        latency = self.avg_latency
+        delivered, missed = self.reminders
+        return json.dumps({
+            "period": self.period,
+            "start": self.days[0].day.isoformat(),
+            "end": self.days[-1].day.isoformat(),
+            "conversations": self.conversations,
+            "avg_latency_s": round(latency, 2) if latency is not None else None,
+            "reminders_delivered": delivered,
+            "reminders_missed": missed,
+            "days": [d.row() for d in self.days],
+            "commands": [{"name": name, "count": count} for name, count in self.commands],
+        }, indent=2)
+
+    def to_csv(self) -> str:
+        """One row per day (the commands are in the JSON export)."""
+        out = io.StringIO()
+        writer = csv.DictWriter(out, fieldnames=CSV_FIELDS, lineterminator="\n")
+        writer.writeheader()
+        for day in self.days:
+            writer.writerow({k: "" if v is None else v for k, v in day.row().items()})
+        return out.getvalue()
+
+    def summary(self) -> str:
+        """ "avg reply 1.8s · reminders 12 delivered, 2 missed" """
+        latency = self.avg_latency
+        delivered, missed = self.reminders
+        reply = f"avg reply {latency:.1f}s" if latency is not None else "no replies yet"
+        return f"{reply} · reminders {delivered} delivered, {missed} missed"
+
+    def bars(self, width: int = 20) -> List[Tuple[DayUsage, int]]:
+        """Each day with its bar length, the busiest day filling `width`."""
+        peak = max(d.conversations for d in self.days)
+        return [(day, bar_length(day.conversations, peak, width)) for day in self.days]
+
+    def chart(self, width: int = 20) -> List[str]:
+        """Conversations per day as bars: "Wed 14  ██████░░░░  6" """
+        return [f"{day.day:%a %d}  {'█' * filled}{'░' * (width - filled)}  {day.conversations}"
+                for day, filled in self.bars(width)]
+
+    def lines(self) -> List[str]:
+        start, end = self.days[0].day, self.days[-1].day
+        span = f"{start:%a %d %b}" if start == end else f"{start:%a %d %b} - {end:%a %d %b}"
+        lines = [f"📊 Usage {span}: {self.conversations} conversation(s)", ""]
+        lines += self.chart()
+        lines += ["", self.summary()]
+        if self.commands:
+            lines.append("Top commands: " + ", ".join(f"{name} ({count})" for name, count in self.commands))
+        return lines
+
+
+def build_report(store, period: str = "week", today: Optional[date] = None) -> UsageReport:
+    """Usage for the last day or week (`period`, see PERIODS) up to and including `today`."""
+    if period not in PERIODS:
+        raise ValueError(f"Unknown period '{period}' (use {' or '.join(PERIODS)})")
+    today = today or date.today()
+    first = today - timedelta(days=PERIODS[period] - 1)
+    days = {first + timedelta(days=i): DayUsage(first + timedelta(days=i)) for i in range(PERIODS[period])}
+    events = store.query(["turn", "reply", "tool", "reminder", *INBOX_TYPES], since=datetime.combine(first, time()),
+                         until=datetime.combine(today + timedelta(days=1), time()))
+
+    commands: Counter = Counter()
+    last_turn: Optional[datetime] = None
+    for event in events:
+        usage = days.get(event.time.date())
+        if usage is None:
+            continue
+        if event.type == "turn":
+            usage.turns += 1
+            if last_turn is None or event.time - last_turn > CONVERSATION_GAP or last_turn.date() != event.time.date():
+                usage.conversations += 1
+            last_turn = event.time
+        elif event.type == "reply" and event.data.get("latency") is not None:
+            usage.latencies.append(float(event.data["latency"]))
+        elif event.type == "tool":
+            commands[event.data.get("name") or event.summary] += 1
+        elif event.type == "reminder":
+            if event.data.get("delivered"):
+                usage.reminders_delivered += 1
+            else:
+                usage.reminders_missed += 1
+        elif event.type in INBOX_TYPES:
+            usage.messages += 1
+    return UsageReport(period, list(days.values()), commands.most_common(TOP_COMMANDS))
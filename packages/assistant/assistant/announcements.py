@@ -0,0 +1,241 @@
+"""
+Scheduled Announcements - "Speak this at 9am".
+
+Text the assistant says out loud at a time of day or on a cron schedule:
+
+    xswarm dev announce add 09:00 "{greeting}! {briefing}"
+    xswarm dev announce add "30 17 * * 1-5" "Time to wrap up for the day" --priority high
+    xswarm dev announce list
+    xswarm dev announce remove ID
+
+The schedule is "HH:MM" (every day) or a cron expression (scheduler.py's
+CronSchedule: "0 9 * * 1-5", "@hourly"). Placeholders in the text are
+filled in when it's spoken:
+
+    {greeting}   good morning / good afternoon / good evening
+    {time}       the time, e.g. 09:00 (spoken as words)
+    {weekday}    e.g. Friday
+    {date}       e.g. October 16
+    {events}     today's calendar, e.g. "Standup at 09:30 and Dentist at 16:00"
+    {tasks}      the next tasks on the list
+    {news}       the newest unread feed items (news.py), which are then marked read
+    {holidays}   holidays in the coming week (holidays.py), e.g. "Thursday is Thanksgiving."
+    {briefing}   events, tasks, holidays and news as sentences
+
+The dashboard's announcements job (every minute) speaks due announcements
+through the voice orchestrator's announce(), so quiet hours, do not disturb,
+category preferences and meetings apply as they do to every unprompted
+announcement (notifications.py). Each fires once per scheduled time; one
+missed by more than GRACE_MINUTES (the assistant wasn't running) is skipped
+rather than said late.
+
+Storage: ~/.xswarm/announcements/announcements.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional
+
+from .notifications import PRIORITY_ORDER
+from .scheduler import CronSchedule
+from .verbalize import join_words
+
+logger = logging.getLogger(__name__)
+
+GRACE_MINUTES = 10  # A scheduled time missed by longer than this is skipped
+MAX_TASKS = 3  # Tasks named in {tasks}
+_HHMM = re.compile(r"^([01]?\d|2[0-3]):([0-5]\d)$")
+
+
+class AnnouncementError(ValueError):
+    """A schedule, priority or announcement id that doesn't work."""
+
+
+def parse_schedule(schedule: str) -> CronSchedule:
+    """"HH:MM" (daily) or a cron expression, as a CronSchedule."""
+    schedule = schedule.strip()
+    match = _HHMM.match(schedule)
+    try:
+        return CronSchedule(f"{int(match.group(2))} {int(match.group(1))} * * *" if match else schedule)
+    except ValueError as e:
+        raise AnnouncementError(f"Not a time (HH:MM) or cron expression: {schedule!r} ({e})")
+
+
+@dataclass
+class Announcement:
+    schedule: str
+    text: str
+    priority: str = "normal"
+    tags: List[str] = field(default_factory=list)
+    id: str = ""
+    created_at: str = ""
+    last_fired: Optional[str] = None  # The scheduled minute it last fired for (or was skipped)
+
+    def __post_init__(self):
+        if not self.id:
+            self.id = uuid.uuid4().hex[:8]
+        if not self.created_at:
+            self.created_at = datetime.now().replace(second=0, microsecond=0).isoformat()
+
+    def next_time(self, after: Optional[datetime] = None) -> datetime:
+        """The first scheduled time after `after` (default: the last firing, or creation)."""
+        after = after or datetime.fromisoformat(self.last_fired or self.created_at)
+        return parse_schedule(self.schedule).next_after(after)
+
+
+# ==============================================================================
+# TEMPLATES
+# ==============================================================================
+
+
+def briefing_context(now: datetime, events: Iterable[Any] = (), tasks: Iterable[Any] = (),
+                     news: str = "", holidays: str = "") -> Dict[str, str]:
+    """
+    Values for the placeholders, from today's planner events, next tasks,
+    news (news.news_briefing) and coming holidays (holidays.upcoming_holidays).
+    """
+    greeting = "good morning" if now.hour < 12 else "good afternoon" if now.hour < 18 else "good evening"
+    agenda = []
+    for event in sorted(events, key=lambda e: e.start_time):
+        start = str(event.start_time)
+        agenda.append(f"{event.title} at {start[11:16]}" if len(start) >= 16 else event.title)
+    todo = [task.title for task in list(tasks)[:MAX_TASKS]]
+    events_text = join_words(agenda) if agenda else "nothing on the calendar"
+    tasks_text = join_words(todo) if todo else "nothing on the list"
+    if agenda:
+        briefing = f"Today you have {events_text}."
+    else:
+        briefing = "There's nothing on the calendar today."
+    if todo:
+        briefing += f" Next up: {tasks_text}."
+    if holidays:
+        briefing += f" {holidays}"
+    if news:
+        briefing += f" {news}"
+    return {
+        "greeting": greeting.capitalize(),
+        "time": now.strftime("%H:%M"),
+        "weekday": now.strftime("%A"),
+        "date": f"{now.strftime('%B')} {now.day}",
+        "events": events_text,
+        "tasks": tasks_text,
+        "news": news or "no news",
+        "holidays": holidays or "no holidays coming up",
+        "briefing": briefing,
+    }
+
+
+class _Placeholders(dict):
+    def __missing__(self, key: str) -> str:
+        return "{" + key + "}"  # Unknown placeholders are left as written
+
+
+def render(text: str, context: Dict[str, str]) -> str:
+    try:
+        return text.format_map(_Placeholders(context))
+    except (ValueError, IndexError):
+        return text  # Stray braces: say it as written
+
+
+# ==============================================================================
+# STORE
+# ==============================================================================
+
+class AnnouncementStore:
+    """Scheduled announcements, in one JSON file (the CLI edits it while the dashboard runs)."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "announcements"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "announcements.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+        path = self._path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load announcements: {e}")
+        self._data = {"announcements": []}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            with open(self._path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save announcements: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    def all(self) -> List[Announcement]:
+        return [Announcement(**raw) for raw in self._load()["announcements"]]
+
+    def add(self, schedule: str, text: str, priority: str = "normal", tags: Iterable[str] = ()) -> Announcement:
+        parse_schedule(schedule)
+        if priority not in [p.value for p in PRIORITY_ORDER]:
+            raise AnnouncementError(f"Unknown priority '{priority}' (low, normal, high, emergency)")
+        if not text.strip():
+            raise AnnouncementError("Nothing to say")
+        announcement = Announcement(schedule.strip(), text.strip(), priority, list(tags))
+        self._load()["announcements"].append(asdict(announcement))
+        self._save()
+        return announcement
+
+    def remove(self, announcement_id: str) -> Announcement:
+        """Remove by id (a unique prefix is enough)."""
+        data = self._load()
+        matches = [raw for raw in data["announcements"] if raw["id"].startswith(announcement_id)]
+        if len(matches) != 1:
+            raise AnnouncementError(f"No announcement '{announcement_id}'" if not matches
+                                    else f"'{announcement_id}' matches {len(matches)} announcements")
+        data["announcements"].remove(matches[0])
+        self._save()
+        return Announcement(**matches[0])
+
+    def due(self, now: datetime) -> List[Announcement]:
+        """Announcements whose time has come (marked fired); times missed by over GRACE_MINUTES are skipped."""
+        now = now.replace(second=0, microsecond=0)
+        due, changed = [], False
+        for raw in self._load()["announcements"]:
+            announcement = Announcement(**raw)
+            try:
+                scheduled = announcement.next_time()
+            except (AnnouncementError, ValueError) as e:
+                logger.warning(f"Announcement {announcement.id}: {e}")
+                continue
+            if scheduled > now:
+                continue
+            latest = scheduled
+            while True:  # The most recent scheduled time not after now
+                following = announcement.next_time(latest)
+                if following > now:
+                    break
+                latest = following
+            raw["last_fired"] = latest.isoformat()
+            changed = True
+            if now - latest <= timedelta(minutes=GRACE_MINUTES):
+                due.append(Announcement(**raw))
+            else:
+                logger.info(f"Skipped announcement {announcement.id} missed at {latest:%Y-%m-%d %H:%M}")
+        if changed:
+            self._save()
+        return due
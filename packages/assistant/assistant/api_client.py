@@ -0,0 +1,456 @@
+"""
+API Client - Shared HTTP layer for calls to the xSwarm server.
+
+Wraps httpx.AsyncClient with:
+- Retries with exponential backoff (idempotent requests only, plus any request
+  whose connection was never made - a read, write or protocol error may have
+  been processed, so a POST that hit one isn't sent again)
+- A circuit breaker per endpoint, so a dead server fails fast instead of
+  stalling every poll
+- Error classification (ApiErrorKind) so callers and the UI can tell
+  "server down" apart from "bad request", "sign-in expired", "plan limit
+  reached", "these fields are wrong" and "down for maintenance" - from the
+  status and the server's JSON error body ({"error", "code", "fields"})
+
+Clients (inbox, call screening, memory) build an ApiClient and call
+server routes from endpoints.py with call(), or get/post/put/delete with a
+path. Non-2xx responses raise ApiError, which subclasses
+httpx.HTTPError so existing `except httpx.HTTPError` handlers keep working.
+explain(error) turns one into what the dashboard shows and the assistant
+says: what happened and what to do about it. Each response's Date header
+goes to the clock skew tracker (clock_skew.py).
+"""
+
+import asyncio
+import logging
+import os
+import random
+import re
+import time
+from dataclasses import dataclass
+from datetime import datetime
+from enum import Enum
+from typing import Any, Callable, Collection, Dict, Optional
+
+import httpx
+
+from .clock_skew import get_clock_skew
+from .endpoints import Route
+
+logger = logging.getLogger(__name__)
+
+
+class ApiErrorKind(str, Enum):
+    SERVER_DOWN = "server_down"      # Connection failed or circuit open
+    TIMEOUT = "timeout"
+    SERVER_ERROR = "server_error"    # 5xx
+    RATE_LIMITED = "rate_limited"    # 429
+    AUTH = "auth"                    # 401/403
+    AUTH_EXPIRED = "auth_expired"    # 401 for an expired or invalidated token
+    QUOTA_EXCEEDED = "quota_exceeded"  # 402, or 429 for a plan limit rather than the request rate
+    VALIDATION = "validation"        # 422, or 400 naming the fields at fault
+    MAINTENANCE = "maintenance"      # 503 while the server is down for maintenance
+    NOT_FOUND = "not_found"          # 404
+    BAD_REQUEST = "bad_request"      # Any other 4xx
+
+
+# Worth retrying, and counted against the endpoint's circuit breaker
+TRANSIENT_KINDS = {ApiErrorKind.SERVER_DOWN, ApiErrorKind.TIMEOUT, ApiErrorKind.SERVER_ERROR, ApiErrorKind.RATE_LIMITED}
+# Sending the same request again will never work. The rest (auth, quota, maintenance) will once
+# the user or time fixes things, so queued changes are kept for them
+PERMANENT_KINDS = {ApiErrorKind.BAD_REQUEST, ApiErrorKind.VALIDATION, ApiErrorKind.NOT_FOUND}
+
+IDEMPOTENT_METHODS = {"GET", "HEAD", "PUT", "DELETE", "OPTIONS"}
+
+USER_MESSAGES = {
+    ApiErrorKind.SERVER_DOWN: "Server unreachable",
+    ApiErrorKind.TIMEOUT: "Server timed out",
+    ApiErrorKind.SERVER_ERROR: "Server error",
+    ApiErrorKind.RATE_LIMITED: "Server is rate limiting requests",
+    ApiErrorKind.AUTH: "Not authorized - check your API token",
+    ApiErrorKind.AUTH_EXPIRED: "Server sign-in expired",
+    ApiErrorKind.QUOTA_EXCEEDED: "Plan limit reached",
+    ApiErrorKind.VALIDATION: "Server rejected some fields",
+    ApiErrorKind.MAINTENANCE: "Server down for maintenance",
+    ApiErrorKind.NOT_FOUND: "Not found on server",
+    ApiErrorKind.BAD_REQUEST: "Server rejected the request",
+}
+
+
+class ApiError(httpx.HTTPError):
+    """A classified failure talking to the server."""
+
+    def __init__(self, kind: ApiErrorKind, endpoint: str, message: str = "",
+                 status_code: Optional[int] = None, retry_after: Optional[float] = None,
+                 fields: Optional[Dict[str, str]] = None, code: str = ""):
+        self.kind = kind
+        self.endpoint = endpoint
+        self.status_code = status_code
+        self.retry_after = retry_after
+        self.detail = message
+        self.fields = fields or {}  # Field -> what's wrong with it (VALIDATION)
+        self.code = code  # The server's error code, e.g. "TIER_LIMIT_EXCEEDED"
+        super().__init__(f"{endpoint}: {self.user_message}" + (f" ({message})" if message else ""))
+
+    @property
+    def transient(self) -> bool:
+        return self.kind in TRANSIENT_KINDS
+
+    @property
+    def permanent(self) -> bool:
+        return self.kind in PERMANENT_KINDS
+
+    @property
+    def user_message(self) -> str:
+        text = USER_MESSAGES[self.kind]
+        if self.status_code and self.kind not in (ApiErrorKind.SERVER_DOWN, ApiErrorKind.TIMEOUT):
+            text += f" ({self.status_code})"
+        return text
+
+
+def classify_status(status_code: int) -> Optional[ApiErrorKind]:
+    """Map an HTTP status to an error kind (None for success)."""
+    if status_code < 400:
+        return None
+    if status_code == 429:
+        return ApiErrorKind.RATE_LIMITED
+    if status_code in (401, 403):
+        return ApiErrorKind.AUTH
+    if status_code == 402:
+        return ApiErrorKind.QUOTA_EXCEEDED
+    if status_code == 404:
+        return ApiErrorKind.NOT_FOUND
+    if status_code == 422:
+        return ApiErrorKind.VALIDATION
+    if status_code >= 500:
+        return ApiErrorKind.SERVER_ERROR
+    return ApiErrorKind.BAD_REQUEST
+
+
+def classify_response(status_code: int, body: Dict[str, Any]) -> Optional[ApiErrorKind]:
+    """classify_status, refined by the server's JSON error body."""
+    kind = classify_status(status_code)
+    code = str(body.get("code") or "").lower()
+    text = f"{code} {body.get('error') or ''} {body.get('message') or ''}".lower()
+    if status_code == 401 and ("expired" in text or "invalidated" in text):
+        return ApiErrorKind.AUTH_EXPIRED
+    if status_code == 429 and ("quota" in text or "limit_exceeded" in code):
+        return ApiErrorKind.QUOTA_EXCEEDED
+    if status_code == 400 and _error_fields(body):
+        return ApiErrorKind.VALIDATION
+    if status_code == 503 and "maintenance" in text:
+        return ApiErrorKind.MAINTENANCE
+    return kind
+
+
+def _error_fields(body: Dict[str, Any]) -> Dict[str, str]:
+    """Which fields the server objected to: {"fields": {...}}, {"details": [{"field", "message"}]}
+    or "Missing required fields: a, b"."""
+    fields = body.get("fields")
+    if isinstance(fields, dict):
+        return {str(name): str(problem) for name, problem in fields.items()}
+    details = body.get("details") or body.get("errors")
+    if isinstance(details, list):
+        return {str(d.get("field") or d.get("path")): str(d.get("message") or "is invalid")
+                for d in details if isinstance(d, dict) and (d.get("field") or d.get("path"))}
+    missing = re.match(r"Missing required fields?: (.+)", str(body.get("error") or ""))
+    if missing:
+        return {name.strip(): "is required" for name in missing.group(1).split(",") if name.strip()}
+    return {}
+
+
+def explain(error: ApiError) -> str:
+    """What went wrong and what to do about it, for the activity log and the assistant to say."""
+    kind = error.kind
+    wait = f" in about {max(1, round(error.retry_after / 60))} min" if error.retry_after else ""
+    if kind == ApiErrorKind.SERVER_DOWN:
+        return "Can't reach the server - working offline, will retry"
+    if kind == ApiErrorKind.TIMEOUT:
+        return "The server is slow to answer - will retry"
+    if kind == ApiErrorKind.SERVER_ERROR:
+        return f"The server had a problem ({error.status_code}) - will retry"
+    if kind == ApiErrorKind.RATE_LIMITED:
+        pause = f" for {error.retry_after:.0f}s" if error.retry_after else ""
+        return f"Too many requests to the server - slowing down{pause}"
+    if kind == ApiErrorKind.AUTH_EXPIRED:
+        return "Your server sign-in expired - set a new api_token in config.yaml (or XSWARM_API_TOKEN)"
+    if kind == ApiErrorKind.AUTH:
+        return "The server refused access - check api_token in config.yaml (or XSWARM_API_TOKEN)"
+    if kind == ApiErrorKind.QUOTA_EXCEEDED:
+        return f"{error.detail or 'Your plan limit is reached'} - upgrade your plan, or wait for the limit to reset"
+    if kind == ApiErrorKind.VALIDATION:
+        problems = "; ".join(f"{name} {problem}" for name, problem in error.fields.items())
+        return f"The server didn't accept that: {problems or error.detail or 'invalid fields'}"
+    if kind == ApiErrorKind.MAINTENANCE:
+        return f"The server is down for maintenance - back{wait or ' soon'}; changes are kept until then"
+    if kind == ApiErrorKind.NOT_FOUND:
+        return "That's no longer on the server - it may have been deleted"
+    return f"The server rejected the request: {error.detail or error.status_code}"
+
+
+def classify_exception(exc: Exception) -> ApiErrorKind:
+    """Map an httpx transport exception to an error kind."""
+    if isinstance(exc, getattr(httpx, "TimeoutException", ())):
+        return ApiErrorKind.TIMEOUT
+    return ApiErrorKind.SERVER_DOWN
+
+
+def never_sent(exc: Exception) -> bool:
+    """Whether the request certainly didn't reach the server: the connection itself failed."""
+    return isinstance(exc, (httpx.ConnectError, httpx.ConnectTimeout))
+
+
+def endpoint_key(method: str, path: str) -> str:
+    """Collapse ids out of a path so breakers are per route, e.g. 'POST /api/inbox/:id/reply'."""
+    path = path.split("?", 1)[0]
+    segments = [":id" if re.search(r"\d", seg) else seg for seg in path.strip("/").split("/")]
+    return f"{method.upper()} /{'/'.join(segments)}"
+
+
+@dataclass
+class ApiPolicy:
+    """Retry and circuit-breaker settings (see Config.api_*)."""
+    max_attempts: int = 3
+    backoff_base: float = 0.5      # Seconds before the first retry
+    backoff_max: float = 8.0
+    circuit_threshold: int = 5     # Consecutive transient failures before opening
+    circuit_reset: float = 30.0    # Seconds an open circuit waits before a trial request
+
+    @classmethod
+    def from_config(cls, config=None) -> "ApiPolicy":
+        defaults = cls()
+        return cls(
+            max_attempts=max(1, int(getattr(config, "api_max_attempts", defaults.max_attempts))),
+            backoff_base=float(getattr(config, "api_backoff_base", defaults.backoff_base)),
+            backoff_max=float(getattr(config, "api_backoff_max", defaults.backoff_max)),
+            circuit_threshold=max(1, int(getattr(config, "api_circuit_threshold", defaults.circuit_threshold))),
+            circuit_reset=float(getattr(config, "api_circuit_reset", defaults.circuit_reset)),
+        )
+
+    def backoff(self, attempt: int) -> float:
+        """Delay before retry number `attempt` (1-based), with up to 10% jitter."""
+        delay = min(self.backoff_max, self.backoff_base * (2 ** (attempt - 1)))
+        return delay * (1 + random.random() * 0.1)
+
+
+class CircuitBreaker:
+    """
+    Closed -> open after repeated transient failures -> half-open after a cooldown.
+
+    Half-open lets a single trial request through; the others fail fast until
+    it succeeds (closed) or fails (open again). A trial that never reports back
+    gives up its slot after another cooldown.
+    """
+
+    CLOSED = "closed"
+    OPEN = "open"
+    HALF_OPEN = "half_open"
+
+    def __init__(self, threshold: int = 5, reset_after: float = 30.0, clock: Callable[[], float] = time.monotonic):
+        self.threshold = threshold
+        self.reset_after = reset_after
+        self.clock = clock
+        self.failures = 0
+        self.opened_at: Optional[float] = None
+        self.trial_at: Optional[float] = None  # When the half-open trial request went out
+
+    @property
+    def state(self) -> str:
+        if self.opened_at is None:
+            return self.CLOSED
+        if self.clock() - self.opened_at >= self.reset_after:
+            return self.HALF_OPEN
+        return self.OPEN
+
+    def allow(self) -> bool:
+        """False while open; half-open lets one trial request through (this call takes its slot)."""
+        state = self.state
+        if state == self.CLOSED:
+            return True
+        if state == self.OPEN:
+            return False
+        now = self.clock()
+        if self.trial_at is not None and now - self.trial_at < self.reset_after:
+            return False
+        self.trial_at = now
+        return True
+
+    def retry_in(self) -> float:
+        if self.opened_at is None:
+            return 0.0
+        return max(0.0, self.reset_after - (self.clock() - self.opened_at))
+
+    def record_success(self) -> None:
+        self.failures = 0
+        self.opened_at = None
+        self.trial_at = None
+
+    def record_failure(self) -> None:
+        self.failures += 1
+        if self.state == self.HALF_OPEN or self.failures >= self.threshold:
+            self.opened_at = self.clock()
+        self.trial_at = None
+
+
+class ApiHealth:
+    """Process-wide view of server health, for the dashboard status."""
+
+    def __init__(self):
+        self.last_error: Optional[ApiError] = None
+        self.last_error_at: Optional[datetime] = None
+        self.last_success_at: Optional[datetime] = None
+
+    @property
+    def ok(self) -> bool:
+        return self.last_error is None
+
+    def record_success(self) -> None:
+        self.last_error = None
+        self.last_success_at = datetime.now()
+
+    def record_failure(self, error: ApiError) -> None:
+        self.last_error = error
+        self.last_error_at = datetime.now()
+
+
+_health = ApiHealth()
+_breakers: Dict[str, CircuitBreaker] = {}
+
+
+def get_api_health() -> ApiHealth:
+    return _health
+
+
+def get_breaker(server_url: str, endpoint: str, policy: ApiPolicy) -> CircuitBreaker:
+    """Breakers are shared by every client talking to the same server endpoint."""
+    key = f"{server_url} {endpoint}"
+    if key not in _breakers:
+        _breakers[key] = CircuitBreaker(policy.circuit_threshold, policy.circuit_reset)
+    return _breakers[key]
+
+
+class ApiClient:
+    """Async HTTP client for the xSwarm server with retries and circuit breaking."""
+
+    def __init__(self, server_url: str = "http://localhost:3000", api_token: Optional[str] = None,
+                 timeout: float = 10.0, policy: Optional[ApiPolicy] = None, client=None, sleep=asyncio.sleep):
+        self.server_url = server_url.rstrip("/")
+        self.api_token = api_token or os.getenv("XSWARM_API_TOKEN")
+        self.policy = policy or ApiPolicy()
+        self.sleep = sleep
+        headers = {}
+        if self.api_token:
+            headers["Authorization"] = f"Bearer {self.api_token}"
+        self.client = client or httpx.AsyncClient(base_url=self.server_url, headers=headers, timeout=timeout)
+
+    async def close(self):
+        await self.client.aclose()
+
+    def breaker(self, method: str, path: str, key: Optional[str] = None) -> CircuitBreaker:
+        return get_breaker(self.server_url, key or endpoint_key(method, path), self.policy)
+
+    async def request(self, method: str, path: str, ok_statuses: Collection[int] = (),
+                      idempotent: Optional[bool] = None, retry: bool = True, key: Optional[str] = None,
+                      **kwargs) -> Any:
+        """
+        Send a request, retrying transient failures.
+
+        Returns the response for 2xx (or any status in ok_statuses); raises ApiError otherwise.
+        Non-idempotent requests are only retried when they never reached the server.
+        Pass retry=False for probes (e.g. health checks) that should fail fast.
+        `key` names the route for breakers and errors (default: endpoint_key guesses it from the path).
+        """
+        method = method.upper()
+        endpoint = key or endpoint_key(method, path)
+        breaker = self.breaker(method, path, endpoint)
+        if idempotent is None:
+            idempotent = method in IDEMPOTENT_METHODS
+
+        attempt = 0
+        while True:
+            attempt += 1
+            if not breaker.allow():
+                error = ApiError(ApiErrorKind.SERVER_DOWN, endpoint, f"circuit open, retry in {breaker.retry_in():.0f}s")
+                _health.record_failure(error)
+                raise error
+
+            error, reached_server = await self._attempt(method, path, endpoint, ok_statuses, kwargs)
+            if not isinstance(error, ApiError):
+                breaker.record_success()
+                _health.record_success()
+                return error  # The response
+
+            if error.transient:
+                breaker.record_failure()
+            else:
+                breaker.record_success()  # It answered: up, if not happy with the request
+            _health.record_failure(error)
+
+            retryable = error.transient and (idempotent or not reached_server)
+            if not retryable or not retry or attempt >= self.policy.max_attempts or breaker.state == breaker.OPEN:
+                raise error
+
+            delay = error.retry_after if error.retry_after is not None else self.policy.backoff(attempt)
+            logger.debug(f"{endpoint} failed ({error.kind.value}), retry {attempt} in {delay:.1f}s")
+            await self.sleep(min(delay, self.policy.backoff_max))
+
+    async def _attempt(self, method, path, endpoint, ok_statuses, kwargs):
+        """One request. Returns (response_or_ApiError, reached_server)."""
+        sent = time.time()
+        try:
+            response = await self.client.request(method, path, **kwargs)
+        except httpx.HTTPError as e:
+            # Only a failed connect means the request never left; after a read/write/protocol
+            # error or a read timeout the server may have processed it
+            return ApiError(classify_exception(e), endpoint, str(e) or type(e).__name__), not never_sent(e)
+        get_clock_skew().observe_date(response.headers.get("Date"), sent, time.time())
+
+        if classify_status(response.status_code) is None or response.status_code in ok_statuses:
+            return response, True
+        body = _error_body(response)
+        kind = classify_response(response.status_code, body)
+        retry_after = None
+        if kind in (ApiErrorKind.RATE_LIMITED, ApiErrorKind.MAINTENANCE):
+            try:
+                retry_after = float(response.headers.get("Retry-After", ""))
+            except ValueError:
+                pass
+        return ApiError(kind, endpoint, _error_detail(response, body), response.status_code, retry_after,
+                        _error_fields(body), str(body.get("code") or "")), True
+
+    async def call(self, route: Route, **kwargs):
+        """Send a route from endpoints.py, e.g. call(INBOX_REPLY(item_id=id), json=...)."""
+        if route.idempotent is not None:
+            kwargs.setdefault("idempotent", route.idempotent)
+        return await self.request(route.method, route.path, key=route.key, **kwargs)
+
+    async def get(self, path: str, **kwargs):
+        return await self.request("GET", path, **kwargs)
+
+    async def post(self, path: str, **kwargs):
+        return await self.request("POST", path, **kwargs)
+
+    async def put(self, path: str, **kwargs):
+        return await self.request("PUT", path, **kwargs)
+
+    async def delete(self, path: str, **kwargs):
+        return await self.request("DELETE", path, **kwargs)
+
+
+def _error_body(response) -> Dict[str, Any]:
+    """The JSON error body, or {} when there isn't one."""
+    try:
+        body = response.json()
+    except Exception:
+        return {}
+    return body if isinstance(body, dict) else {}
+
+
+def _error_detail(response, body: Dict[str, Any]) -> str:
+    """The server's `error` field (or `message`, when `error` is just a code), else a short text snippet."""
+    error, message = body.get("error"), body.get("message")
+    if message and (not error or re.fullmatch(r"[a-z_]+", str(error))):
+        return str(message)
+    if error:
+        return str(error)
+    return (getattr(response, "text", "") or "")[:200]
@@ -0,0 +1,226 @@
+"""
+Appointment Cache - The calendar around today, answered from memory.
+
+Dashboard panels, reminders, the notification policy's meeting check,
+conflict checks and "am I free Thursday afternoon?" all ask for the
+appointments of a day or a stretch of time, some of them every few
+seconds. The cache holds everything from WINDOW_DAYS before today to
+WINDOW_DAYS after it - planner events with their recurring occurrences,
+and server appointments made on other devices (the calendar mirror, via
+CalendarSync.remote_events) - grouped by day and in an IntervalIndex
+(calendar_core.py), so none of those reads touch the planner file, the
+mirror database or the network.
+
+It stays fresh without a timer: the planner, the calendar sync and the
+mirror keep a revision that every write, changed file load, push and pull
+bumps. A read that finds a revision changed, or a new day (which moves
+the window), fetches the window again first. The dashboard prefetches
+after each calendar sync and server change, so that fetch rarely lands
+on a voice query. Days outside the window are read from the sources.
+
+    am I free Thursday afternoon?  -> "No - Thursday afternoon you have the dentist at two o'clock."
+    am I busy tomorrow at 3?       -> "No, you're free tomorrow at three o'clock."
+"""
+
+import logging
+import re
+import threading
+from collections import defaultdict
+from datetime import date, datetime, time as clock_time, timedelta
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from .calendar_core import IntervalIndex, find_conflicts
+from .capabilities import register_capability
+from .categories import has_tag
+from .dates import (
+    PART_OF_DAY_TIMES, WORKDAY_END, WORKDAY_START, parse_natural_date, parse_natural_datetime, parse_time_expression,
+)
+from .verbalize import verbalize_date, verbalize_time
+
+logger = logging.getLogger(__name__)
+
+WINDOW_DAYS = 30  # Either side of today
+MAX_NAMED = 3  # Appointments read out in a "you're busy" answer
+
+_AVAILABILITY = re.compile(
+    r"^\s*(?:(?:so|and|hey|okay)\s+)?(?:am\s+i|are\s+we|is\s+my\s+calendar)\s+"
+    r"(?P<state>free|busy|available|clear|booked)\b(?P<when>.*?)[\s?.!]*$",
+    re.IGNORECASE,
+)
+_BUSY_WORDS = ("busy", "booked")
+_SKIP = {"this", "in", "the"}  # Around a part of the day: "in the afternoon", "this evening"
+
+
+class AppointmentCache:
+    """Planner and server appointments from WINDOW_DAYS before today to WINDOW_DAYS after it."""
+
+    def __init__(self, planner, sync=None, window_days: int = WINDOW_DAYS,
+                 today: Callable[[], date] = date.today):
+        self.planner = planner
+        self.sync = sync  # CalendarSync for server appointments; None without one
+        self.window_days = window_days
+        self._today = today
+        self._lock = threading.Lock()
+        self._key: Optional[Tuple[Any, ...]] = None  # (today, planner revision, sync revision) when fetched
+        self._days: Dict[str, List[Any]] = {}
+        self._index = IntervalIndex([])
+        self.first: Optional[date] = None
+        self.last: Optional[date] = None
+        self.hits = self.misses = self.fetches = 0
+
+    def _remote(self, day: date, days: int = 1, tag: Optional[str] = None) -> Optional[List[Any]]:
+        """Server appointments from the mirror ([] without calendar sync, None when it can't be read)."""
+        if self.sync is None:
+            return []
+        try:
+            return self.sync.remote_events(day, tag, days=days)
+        except Exception as e:
+            logger.debug(f"Could not read the calendar mirror: {e}")
+            return None
+
+    def _fresh(self) -> None:
+        """Fetch the window again if a source changed or the day moved on (with the lock held)."""
+        today = self._today()
+        key = (today, self.planner.revision, self.sync.revision if self.sync is not None else None)
+        if key == self._key:
+            return
+        first, last = today - timedelta(days=self.window_days), today + timedelta(days=self.window_days)
+        events = list(self.planner.get_calendar_events(first.isoformat(), last.isoformat()))
+        remote = self._remote(first, (last - first).days + 1)
+        if remote is None:
+            key = None  # Try the mirror again on the next read
+        days = defaultdict(list)
+        for event in sorted(events + (remote or []), key=lambda e: e.start_time):
+            days[event.start_time[:10]].append(event)
+        self._days, self._index = dict(days), IntervalIndex(events + (remote or []))
+        self.first, self.last, self._key = first, last, key
+        self.fetches += 1
+
+    def _covers(self, day: date) -> bool:
+        return self.first is not None and self.first <= day <= self.last
+
+    def prefetch(self) -> None:
+        """Fetch the window now if it's out of date, so the next read doesn't wait for it."""
+        with self._lock:
+            self._fresh()
+
+    def events(self, day: Optional[date] = None, tag: Optional[str] = None) -> List[Any]:
+        """The appointments starting on `day` (today), planner and server ones together, by start time."""
+        day = day or self._today()
+        with self._lock:
+            self._fresh()
+            if self._covers(day):
+                self.hits += 1
+                return [e for e in self._days.get(day.isoformat(), []) if has_tag(e.tags, tag)]
+            self.misses += 1
+        events = self.planner.get_calendar_events(day.isoformat(), day.isoformat(), tag=tag)
+        return sorted(events + (self._remote(day, tag=tag) or []), key=lambda e: e.start_time)
+
+    def between(self, start: datetime, end: datetime) -> List[Any]:
+        """The busy, timed appointments sharing some time with start-end, by start time."""
+        with self._lock:
+            self._fresh()
+            # Appointments start at most a day before the range they overlap, bar multi-day ones
+            if self._covers(start.date() - timedelta(days=1)) and self._covers(end.date()):
+                self.hits += 1
+                return self._index.overlapping(start, end)
+        days = (end.date() - start.date()).days + 2
+        nearby = [e for offset in range(days) for e in self.events(start.date() - timedelta(days=1 - offset))]
+        return IntervalIndex(nearby).overlapping(start, end)
+
+    def conflicts(self, event: Any) -> List[Any]:
+        """The busy appointments overlapping `event` (see calendar_core.find_conflicts)."""
+        day = date.fromisoformat(event.start_time[:10])
+        with self._lock:
+            self._fresh()
+            if self._covers(day):
+                self.hits += 1
+                return find_conflicts(event, self._index)
+        return find_conflicts(event, self.events(day))
+
+    def is_free(self, start: datetime, end: datetime) -> bool:
+        return not self.between(start, end)
+
+
+_cache: Optional[AppointmentCache] = None
+
+
+def get_appointment_cache() -> AppointmentCache:
+    """Get the cache of the planner and calendar sync tools.py has (a new one when either is replaced)."""
+    global _cache
+    from .tools import get_calendar_sync, get_planner_data
+    planner, sync = get_planner_data(), get_calendar_sync()
+    if _cache is None or _cache.planner is not planner or _cache.sync is not sync:
+        _cache = AppointmentCache(planner, sync)
+    return _cache
+
+
+# ==============================================================================
+# "AM I FREE THURSDAY AFTERNOON?"
+# ==============================================================================
+
+def _at(day: date, hhmm: str) -> datetime:
+    if hhmm == "24:00":
+        return datetime.combine(day + timedelta(days=1), clock_time.min)
+    return datetime.combine(day, clock_time.fromisoformat(hhmm))
+
+
+def parse_availability_question(text: str, now: datetime) -> Optional[Tuple[datetime, datetime, str, bool]]:
+    """
+    (start, end, the time as said back, asked "busy?") for "am I free
+    Thursday afternoon?", "are we busy tomorrow at 3", "am I free tonight".
+    A part of the day covers its PART_OF_DAY_TIMES, a time the hour from
+    it, and a day alone the working day. None when it isn't one.
+    """
+    match = _AVAILABILITY.match(text or "")
+    if not match:
+        return None
+    words = [w.strip(",").lower() for w in match.group("when").split()]
+    part = next((w for w in words if w in PART_OF_DAY_TIMES), None)
+    rest = " ".join(w for w in words if w != part and w not in _SKIP)
+    today = now.date()
+    day, hour = (today if part == "tonight" or not rest else parse_natural_date(rest, today=today)), None
+    if day is None:
+        when = parse_natural_datetime(rest, default_time="00:01", today=today)
+        if when is not None and when == parse_natural_datetime(rest, default_time="00:02", today=today):
+            day, hour = when.date(), when
+        elif parse_time_expression(rest):
+            day, hour = today, _at(today, parse_time_expression(rest))
+        else:
+            return None
+    spoken_day = verbalize_date(day, today)
+    if hour is not None:
+        start, end, said = hour, hour + timedelta(hours=1), f"{spoken_day} at {verbalize_time(hour)}"
+    elif part is not None:
+        start, end = _at(day, PART_OF_DAY_TIMES[part][0]), _at(day, PART_OF_DAY_TIMES[part][1])
+        evening = part in ("night", "tonight")
+        said = ("tonight" if evening and day == today else f"this {part}" if day == today
+                else f"{spoken_day} night" if evening else f"{spoken_day} {part}")
+    else:
+        start, end, said = _at(day, WORKDAY_START), _at(day, WORKDAY_END), spoken_day
+    return max(start, now) if day == today else start, end, said, match.group("state").lower() in _BUSY_WORDS
+
+
+def answer_availability_question(text: str, cache: Optional[AppointmentCache] = None,
+                                 now: Optional[datetime] = None) -> Optional[str]:
+    """The answer to "am I free Thursday afternoon?" from the cache, or None when `text` doesn't ask one."""
+    now = now or datetime.now()
+    question = parse_availability_question(text, now)
+    if question is None:
+        return None
+    start, end, said, asked_busy = question
+    if end <= now:
+        return f"{said[:1].upper()}{said[1:]} is already over."
+    busy = (cache or get_appointment_cache()).between(start, end)
+    if not busy:
+        return f"{'No' if asked_busy else 'Yes'}, you're free {said}."
+    named = [f"{e.title} at {verbalize_time(datetime.fromisoformat(e.start_time))}" for e in busy[:MAX_NAMED]]
+    if len(busy) > MAX_NAMED:
+        named.append(f"{len(busy) - MAX_NAMED} more")
+    listed = named[0] if len(named) == 1 else ", ".join(named[:-1]) + " and " + named[-1]
+    return f"{'Yes' if asked_busy else 'No'} - {said} you have {listed}."
+
+
+register_capability("Availability", "Say whether you're free at a time, straight from the calendar",
+                    ["am I free Thursday afternoon?", "am I busy tomorrow at 3"], category="Planning",
+                    keywords=("free", "busy", "calendar", "schedule"))
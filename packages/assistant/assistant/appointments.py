@@ -0,0 +1,190 @@
+"""
+Appointments - Ask for missing details instead of guessing when scheduling.
+
+"Schedule the dentist" has a title but no time; "book something tomorrow
+at 3" has a time but no title. Rather than filling the gap with a default
+(9am, "Meeting"), the dashboard runs a clarification flow (flows.py) that
+asks only for what's missing:
+
+    "schedule the dentist on friday"
+    -> "What time should I schedule the dentist on Friday?"
+    <- "no idea, you pick"
+    -> "Shall I put the dentist at nine o'clock on Friday?"
+    <- "yes"
+    -> "Added: 'Dentist' on Friday Oct 23 at 09:00"
+
+The appointment is created (through the add_calendar_event tool, so tags,
+travel-mode times and the usual summary apply) only once the title and
+time are given or the user accepts the suggested default. A day that isn't
+mentioned is the next time the clock reaches the chosen time, and an end
+said at the close ("... until 3", "... through the afternoon") is kept;
+without one the tool infers the length (durations.py). Requests with
+everything filled in go to the AI as usual.
+"""
+
+import re
+from dataclasses import dataclass
+from datetime import date, datetime, timedelta
+from typing import Any, Dict, List, Optional
+
+from .capabilities import register_capability
+from .dates import ambiguous_readings, parse_end_time, parse_natural_datetime, parse_time_expression
+from .dialogue import get_dialogue_state
+from .flows import Flow, Step
+from .verbalize import verbalize_date, verbalize_time
+
+_SCHEDULE_INTENT = re.compile(
+    r"^\s*(?:(?:please|can\s+you|could\s+you)\s+)?(?P<verb>schedule|book|set\s+up)\b(?P<rest>.*?)(?:\s+please)?\s*[.!?]*\s*$",
+    re.IGNORECASE,
+)
+# Planning the day rather than adding an event ("schedule my tasks" is optimize_day's job)
+_NOT_AN_EVENT = re.compile(r"^\s*(?:my|the|today'?s|all)?\s*(?:day|tasks?|week|morning|afternoon|evening|habits?)\b",
+                           re.IGNORECASE)
+
+# Words that name the kind of thing rather than what it is: "schedule a meeting" has no title yet
+GENERIC_TITLES = {"meeting", "appointment", "appt", "event", "something", "it", "one", "a time", "time",
+                  "call", "slot"}
+ARTICLES = ("the ", "a ", "an ", "my ", "our ")
+CONNECTORS = {"on", "at", "for", "this", "next", "in", "from"}
+# Read as times by dates.py ("lunch" is 12:00) but, leading the sentence, they're what's being booked
+MEALS = {"breakfast", "brunch", "lunch", "lunchtime", "dinner", "supper", "tea", "coffee", "drinks"}
+
+DEFAULT_TIME = "09:00"  # Offered (never assumed) when the user has no preference
+END_LEADS = {"until", "till", "til", "through", "thru"}  # "... until 3": the end, not part of the "when"
+
+
+@dataclass
+class ScheduleRequest:
+    """What a scheduling request said, and what it left out."""
+    title: str = ""  # As stored: "Dentist"
+    phrase: str = ""  # As said: "the dentist"
+    day: Optional[date] = None
+    time: Optional[str] = None  # "HH:MM"
+    end: str = ""  # As said: "until 3", "through the afternoon"
+
+    @property
+    def missing(self) -> List[str]:
+        return [slot for slot, value in (("title", self.title), ("time", self.time)) if not value]
+
+
+def _title(phrase: str) -> str:
+    lowered = phrase.lower()
+    for article in ARTICLES:
+        if lowered.startswith(article):
+            phrase = phrase[len(article):]
+            break
+    return phrase[:1].upper() + phrase[1:] if phrase.lower() not in GENERIC_TITLES else ""
+
+
+def parse_schedule_request(text: str, today: Optional[date] = None) -> Optional[ScheduleRequest]:
+    """
+    Split "schedule the dentist friday at four" into title and when. None
+    when `text` isn't a scheduling request, or names a date that could be
+    two different days (the AI asks about those). "book" and "set up" only
+    count with a day or time ("book the dentist tomorrow").
+    """
+    match = _SCHEDULE_INTENT.match(text or "")
+    if not match or _NOT_AN_EVENT.match(match.group("rest")):
+        return None
+    today = today or date.today()
+    words = match.group("rest").split()
+    request = ScheduleRequest()
+    for i, word in enumerate(words):
+        tail = " ".join(words[i:]).strip(",.")
+        if word.lower() in END_LEADS and parse_end_time(tail, datetime.combine(today, datetime.min.time())):
+            request.end, words = tail, words[:i]
+            break
+    # The longest tail of the sentence that reads as a day and/or time is the "when"
+    for i in range(len(words)):
+        tail = " ".join(words[i:]).strip(",.")
+        if words[i].lower().strip(",.") in MEALS:
+            continue
+        if ambiguous_readings(tail, today=today):
+            return None
+        when = parse_natural_datetime(tail, default_time="00:01", today=today)
+        if when is not None:
+            timed = when == parse_natural_datetime(tail, default_time="00:02", today=today)
+            request.day, request.time = when.date(), f"{when:%H:%M}" if timed else None
+        else:
+            request.time = parse_time_expression(tail)
+        if request.day or request.time:
+            words = words[:i]
+            break
+    if not (request.day or request.time) and match.group("verb").lower() != "schedule":
+        return None  # "book a flight", "set up my email": only "schedule" is sure to mean the calendar
+    while words and words[-1].lower().strip(",") in CONNECTORS:
+        words = words[:-1]
+    request.phrase = " ".join(words).strip(" ,")
+    request.title = _title(request.phrase)
+    return request
+
+
+def needs_details(text: str, today: Optional[date] = None) -> Optional[ScheduleRequest]:
+    """The parsed request when `text` asks to schedule something but leaves out its title or time."""
+    request = parse_schedule_request(text, today)
+    return request if request is not None and request.missing else None
+
+
+def resolve_day(time_of_day: str, now: datetime) -> date:
+    """The next day the clock reaches `time_of_day`: today, or tomorrow if it's passed."""
+    hour, minute = map(int, time_of_day.split(":"))
+    at = now.replace(hour=hour, minute=minute, second=0, microsecond=0)
+    return (at if at > now else at + timedelta(days=1)).date()
+
+
+def appointment_flow(request: ScheduleRequest, add_event=None, now: Optional[datetime] = None) -> Flow:
+    """
+    Ask for the request's missing title and time, then add the event.
+    `add_event(title, day, start_time)` defaults to the add_calendar_event
+    tool and returns its result line.
+    """
+    now = now or datetime.now()
+
+    def subject(values: Dict[str, Any]) -> str:
+        if values.get("title"):  # Just given, so say it back as given
+            return values["title"]
+        return request.phrase if request.title else "it"
+
+    def spoken_day(day: date) -> str:
+        said = verbalize_date(day, today=now.date())
+        return said if said in ("today", "tomorrow") else f"on {said}"
+
+    def when(values: Dict[str, Any]) -> str:
+        return f" {spoken_day(request.day)}" if request.day else ""
+
+    def default_prompt(values: Dict[str, Any]) -> str:
+        clock = datetime.strptime(DEFAULT_TIME, "%H:%M").time()
+        day = request.day or resolve_day(DEFAULT_TIME, now)
+        return f"Shall I put {subject(values)} at {verbalize_time(clock)} {spoken_day(day)}?"
+
+    def complete(values: Dict[str, Any]) -> str:
+        title = values.get("title") or request.title
+        time_of_day = values.get("time") or request.time or DEFAULT_TIME
+        day = request.day or resolve_day(time_of_day, now)
+        if add_event is None:
+            from .tools import add_calendar_event
+            result = add_calendar_event(title, day.isoformat(), start_time=time_of_day, end_time=request.end)
+            get_dialogue_state().note_call("add_calendar_event", {"title": title}, result)  # "invite Bob too"
+        else:
+            result = add_event(title, day.isoformat(), time_of_day)
+        if not result.startswith("✓"):
+            raise ValueError(result.lstrip("✗⏸ "))
+        return result.lstrip("✓ ")
+
+    steps = []
+    if "title" in request.missing:
+        steps.append(Step("title", "What should I call it?"))
+    if "time" in request.missing:
+        steps += [
+            Step("time", lambda values: f"What time should I schedule {subject(values)}{when(values)}?",
+                 slot="time", optional=True, next=lambda values: None if values["time"] else "use_default"),
+            Step("use_default", default_prompt, slot="yes_no",
+                 next=lambda values: None if values["use_default"] else "time"),
+        ]
+    return Flow("schedule", steps, on_complete=complete,
+                on_cancel=lambda _values: "Okay, I won't schedule it.")
+
+
+register_capability("Scheduling", "Book appointments, asking for the time or title when it's missing",
+                    ["schedule the dentist on friday", "book something tomorrow at 3"], category="Planning",
+                    keywords=("appointment", "book", "meeting"))
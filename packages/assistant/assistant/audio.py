@@ -12,9 +12,15 @@ import numpy as np
 import torch
 import threading
 import random
-from typing import Callable, Optional, Dict
+from typing import Callable, Optional, Dict, List, Any
 from queue import Queue, Empty
 
+from .audio_bus import FrameQueue
+from .audio_devices import BluetoothCheck, check_bluetooth, restore_a2dp
+from .audio_frame import AudioFrame
+from .audio_mixer import OutputMixer
+from .resample import AudioFormat, StreamResampler
+
 logger = logging.getLogger(__name__)
 
 # ==============================================================================
@@ -26,13 +32,31 @@ class AudioIO:
     Audio I/O manager using sounddevice.
     Provides real-time audio input/output with frame-based processing.
     """
-    def __init__(self, sample_rate: int = 24000, frame_size: int = 1920, channels: int = 1, log_callback: Optional[Callable[[str], None]] = None):
+    def __init__(self, sample_rate: int = 24000, frame_size: int = 1920, channels: int = 1, log_callback: Optional[Callable[[str], None]] = None,
+                 playback_rate: Optional[int] = None, bluetooth_fallback: bool = False):
+        """
+        Args:
+            sample_rate: Capture rate, and the default rate of audio passed to play_audio()
+            frame_size: Samples per frame at sample_rate
+            playback_rate: Output stream rate; None uses the output device's native rate
+                           (play_audio resamples to it - see resample.py)
+            bluetooth_fallback: Capture from the built-in mic instead of a Bluetooth headset's
+                                (which would put it in low-quality HFP - see audio_devices.py)
+        """
         self.sample_rate = sample_rate
+        # Until start_output() negotiates with the device, playback runs at the capture rate
+        self.playback_rate = sample_rate
+        self._requested_playback_rate = playback_rate
+        self.device_output_rate: Optional[int] = None
+        self._playback_resamplers: Dict[int, StreamResampler] = {}
+        self._media_resamplers: Dict[int, StreamResampler] = {}
         self.frame_size = frame_size
         self.channels = channels
         self.log_callback = log_callback
-        self.input_queue: Queue = Queue()
-        self.output_queue: Queue = Queue()
+        # Bounded, drop-oldest queues (see audio_bus.py) - the audio callbacks must never block
+        self.input_queue = FrameQueue("mic/reader", capacity=50)
+        self.output_queue = FrameQueue("playback", capacity=100)
+        self.mixer = OutputMixer(sample_rate)  # Speech/earcon/media gain and ducking (volume.py)
         self.input_stream: Optional[sd.InputStream] = None
         self.output_stream: Optional[sd.OutputStream] = None
         
@@ -62,16 +86,32 @@ class AudioIO:
             self.log(f"⚠️ Error querying audio devices: {e}")
             self.input_device_index = None
 
+        # Recording from a Bluetooth headset's mic drops it into the call (HFP) profile
+        self.bluetooth = BluetoothCheck()
+        self.bluetooth_rerouted = False
+        try:
+            self.bluetooth = check_bluetooth(list(sd.query_devices()), self.input_device_index)
+        except Exception as e:
+            logger.debug(f"Bluetooth profile check failed: {e}")
+        if self.bluetooth.hfp and bluetooth_fallback and self.bluetooth.builtin_index is not None:
+            self.input_device_index = self.bluetooth.builtin_index
+            self.bluetooth_rerouted = True
+            restore_a2dp(self.bluetooth)
+        if self.bluetooth.hfp:
+            logger.warning(self.bluetooth.warning(self.bluetooth_rerouted))  # The dashboard shows it
+
         # Log default output device
         try:
             default_out = sd.query_devices(kind='output')
             self.log(f"🔊 Default Output Device: {default_out['name']} (Index {default_out['index']})")
+            self.device_output_rate = int(default_out['default_samplerate'])
         except Exception as e:
             self.log(f"⚠️ Error querying output device: {e}")
 
         # Buffer state for callback
         self.current_chunk = None
         self.chunk_pos = 0
+        self._flush_speech = False  # Set by stop_speech(), honoured by the playback callback
 
     def log(self, msg: str):
         if self.log_callback:
@@ -79,7 +119,14 @@ class AudioIO:
         else:
             logger.debug(msg)
 
-    def start_input(self, callback: Optional[Callable] = None):
+    def start_input(self, callback: Optional[Callable] = None, shared_frames: bool = False):
+        """
+        Start capturing from the mic.
+
+        Args:
+            callback: Called with each frame (float32 ndarray, read-only)
+            shared_frames: Pass the AudioFrame itself, so consumers share its cached conversions
+        """
         def audio_callback(indata, frames, time, status):
             if status:
                 self.log(f"⚠️ Audio Status: {status}")
@@ -90,17 +137,19 @@ class AudioIO:
                     # Output is playing - ignore mic input to prevent feedback
                     return
                 
-                audio = np.ascontiguousarray(indata[:, 0], dtype=np.float32)
+                # PortAudio reuses indata, so this is the one copy; every consumer shares it read-only
+                frame = AudioFrame.from_buffer(indata[:, 0], self.sample_rate)
+                audio = frame.samples
                 
                 # DEBUG: Check for signal
-                rms = np.sqrt(np.mean(audio**2))
-                if rms == 0.0:
+                if frame.rms() == 0.0:
                     self.log(f"⚠️ Absolute Silence (RMS=0.0) - Check Permissions/Mute")
                 
-                self.input_queue.put(audio)
+                if callback is None:
+                    self.input_queue.put_nowait(audio)  # Only read_frame() consumers drain this queue
                 if callback:
                     try:
-                        callback(audio)
+                        callback(frame if shared_frames else audio)
                     except Exception as e:
                         self.log(f"❌ Error in audio callback: {e}")
             except Exception as e:
@@ -116,8 +165,25 @@ class AudioIO:
         )
         self.input_stream.start()
 
+    @property
+    def input_format(self) -> AudioFormat:
+        """Format of captured frames (consumers convert from this, see resample.FormatNegotiator)."""
+        return AudioFormat(self.sample_rate)
+
+    @property
+    def playback_frame_size(self) -> int:
+        """frame_size worth of time at the playback rate."""
+        return int(round(self.frame_size * self.playback_rate / self.sample_rate))
+
     def start_output(self):
-        self.output_queue = Queue(maxsize=100)
+        self.playback_rate = self._requested_playback_rate or self.device_output_rate or self.sample_rate
+        if self.playback_rate != self.sample_rate:
+            self.log(f"🔊 Playback at device rate {self.playback_rate}Hz (resampling from {self.sample_rate}Hz)")
+        self._playback_resamplers.clear()
+        self._media_resamplers.clear()
+        self.output_queue.clear()
+        self.mixer.sample_rate = self.playback_rate
+        self.mixer.clear()
         # Initialize buffer state
         self.current_chunk = None
         self.chunk_pos = 0
@@ -135,6 +201,11 @@ class AudioIO:
                     queue_size = self.output_queue.qsize()
                     logging.debug(f"🔊 Callback #{self._callback_count}, Queue: {queue_size}, Chunk: {self.current_chunk is not None}")
                 
+                if self._flush_speech:
+                    self._flush_speech = False
+                    self.current_chunk = None
+                    self.output_queue.clear()
+
                 # Fill output buffer by piecing together chunks from queue
                 needed = frames
                 output = np.zeros(frames, dtype=np.float32)
@@ -176,6 +247,9 @@ class AudioIO:
                     if self.chunk_pos >= len(self.current_chunk):
                         self.current_chunk = None
                 
+                # Speech at its volume, earcons and (ducked) media on top, whatever the pre-buffering did
+                filled = self.mixer.mix(output, speech=filled)
+
                 # Write to output
                 outdata[:] = output.reshape(-1, 1)
                 
@@ -200,20 +274,36 @@ class AudioIO:
         # For now, we rely on the queue.
         
         self.output_stream = sd.OutputStream(
-            samplerate=self.sample_rate,
+            samplerate=self.playback_rate,
             channels=self.channels,
-            blocksize=self.frame_size, # Match Moshi frame size (1920) for stability
+            blocksize=self.playback_frame_size, # Match Moshi frame duration (80ms) for stability
             latency=0.1, # 100ms hardware latency
             callback=audio_callback
         )
         self.output_stream.start()
 
-    def play_audio(self, audio: np.ndarray):
+    def play_audio(self, audio: np.ndarray, sample_rate: Optional[int] = None):
+        """
+        Queue audio for playback.
+
+        Args:
+            audio: float32 samples
+            sample_rate: Rate of `audio` (default: sample_rate); resampled to the playback rate
+        """
         if len(audio) == 0:
             return
         
         # Ensure float32
         audio = np.asarray(audio, dtype=np.float32)
+
+        source_rate = sample_rate or self.sample_rate
+        if source_rate != self.playback_rate:
+            resampler = self._playback_resamplers.get(source_rate)
+            if resampler is None:
+                resampler = self._playback_resamplers[source_rate] = StreamResampler(source_rate, self.playback_rate)
+            audio = resampler.process(audio)
+            if len(audio) == 0:
+                return
         
         # Check for scaling issues (int16 treated as float32)
         max_val = np.max(np.abs(audio))
@@ -235,13 +325,53 @@ class AudioIO:
 
         if not audio.flags['C_CONTIGUOUS']:
             audio = np.ascontiguousarray(audio)
-            
-        num_frames = int(np.ceil(len(audio) / self.frame_size))
+
+        # Read-only (shared) audio can be queued as views; a caller might still modify a writable array
+        shared = not audio.flags.writeable
+        frame_size = self.playback_frame_size
+        num_frames = int(np.ceil(len(audio) / frame_size))
         for i in range(num_frames):
-            start = i * self.frame_size
-            end = min((i + 1) * self.frame_size, len(audio))
-            chunk = audio[start:end].copy()
-            self.output_queue.put(chunk)
+            start = i * frame_size
+            end = min((i + 1) * frame_size, len(audio))
+            chunk = audio[start:end] if shared else audio[start:end].copy()
+            self.output_queue.put_nowait(chunk)  # Drops the oldest chunk if playback can't keep up
+
+    def stop_speech(self):
+        """Drop the speech queued for playback (barge-in); earcons and media keep playing."""
+        self.output_queue.clear()
+        self._flush_speech = True  # The chunk mid-playback goes too, on the callback's thread
+
+    def play_cue(self, audio: np.ndarray, gain: float = 1.0):
+        """
+        Mix a short sound (an earcon, at playback_rate) over whatever is
+        playing, at `gain` times the earcon volume.
+        """
+        self._play_over("earcons", np.asarray(audio, dtype=np.float32) * gain)
+
+    def play_media(self, audio: np.ndarray, sample_rate: Optional[int] = None):
+        """
+        Mix other audio (float32, at `sample_rate`, default sample_rate) over
+        whatever is playing, at the media volume; ducked while speech plays.
+        """
+        audio = np.asarray(audio, dtype=np.float32)
+        source_rate = sample_rate or self.sample_rate
+        if len(audio) and source_rate != self.playback_rate:
+            # Kept per rate, so media passed through in chunks resamples as one stream
+            resampler = self._media_resamplers.get(source_rate)
+            if resampler is None:
+                resampler = self._media_resamplers[source_rate] = StreamResampler(source_rate, self.playback_rate)
+            audio = resampler.process(audio)
+        self._play_over("media", audio)
+
+    def _play_over(self, stream: str, audio: np.ndarray):
+        if len(audio) == 0:
+            return
+        if self.output_stream and not self.output_stream.active:
+            try:
+                self.output_stream.start()
+            except Exception as e:
+                self.log(f"❌ Failed to restart output stream: {e}")
+        self.mixer.add(stream, audio)
 
     def read_frame(self, timeout: float = 0.1) -> Optional[np.ndarray]:
         try:
@@ -249,6 +379,10 @@ class AudioIO:
         except:
             return None
 
+    def audio_stats(self) -> List[Dict[str, Any]]:
+        """Backpressure counters for the playback and mic reader queues."""
+        return [self.output_queue.stats(), self.input_queue.stats()]
+
     def stop(self):
         if self.input_stream:
             self.input_stream.stop()
@@ -321,15 +455,17 @@ class HybridVAD:
             model, utils = torch.hub.load(repo_or_dir='snakers4/silero-vad', model='silero_vad', force_reload=False, onnx=False)
             self._silero_model = model
             self._silero_utils = utils
-            if torch.backends.mps.is_available():
-                self._silero_model = self._silero_model.to('mps')
-            elif torch.cuda.is_available():
-                self._silero_model = self._silero_model.to('cuda')
+            from .compute import BACKENDS, get_compute_manager
+            get_compute_manager().register("vad", BACKENDS, move=self._move_silero)
             self._silero_model.eval()
         except Exception as e:
             logger.debug(f"Failed to load Silero VAD: {e}")
             self._silero_model = "disabled"
 
+    def _move_silero(self, backend: str):
+        from .compute import TORCH_DEVICES
+        self._silero_model = self._silero_model.to(TORCH_DEVICES[backend])
+
     def _check_amplitude(self, audio: np.ndarray) -> bool:
         energy = np.sqrt(np.mean(audio ** 2))
         return energy > self.amplitude_threshold
@@ -0,0 +1,155 @@
+"""
+Audio Bus - Bounded frame queues with explicit backpressure.
+
+Audio frames are produced in real time (PortAudio callbacks, the Moshi
+decode loop) and must never block the producer, so every queue between
+stages is a FrameQueue:
+
+- bounded: when a consumer falls behind, the OLDEST frame is dropped and
+  counted (stale audio is worth less than current audio)
+- non-blocking put: producers never wait on slow consumers
+- critical vs non-critical: non-critical consumers (the visualizer) are
+  automatically downsampled while they lag - they receive every Nth frame,
+  with N doubling while the queue is over half full and recovering once
+  the consumer catches up
+
+AudioBroadcast fans one stream out to several named subscribers, each with
+its own FrameQueue, so one slow subscriber never holds back the others.
+stats() on either feeds the dashboard's audio lag indicator.
+"""
+
+import threading
+from collections import deque
+from queue import Empty
+from typing import Any, Dict, List, Optional
+
+# Most a non-critical subscriber is downsampled (every 8th frame)
+MAX_STRIDE = 8
+
+
+class FrameQueue:
+    """Bounded, drop-oldest frame queue with per-queue counters (Queue-compatible API)."""
+
+    def __init__(self, name: str, capacity: int, critical: bool = True):
+        self.name = name
+        self.capacity = capacity
+        self.critical = critical
+        self._frames: deque = deque()
+        self._cond = threading.Condition()
+        self.received = 0   # Frames offered by the producer
+        self.dropped = 0    # Frames discarded because the consumer lagged
+        self.skipped = 0    # Frames not delivered due to downsampling (non-critical only)
+        self.max_lag = 0    # Deepest the queue has been
+        self.stride = 1     # Deliver every Nth frame (non-critical only)
+        self._offset = 0
+
+    def put_nowait(self, frame: Any) -> None:
+        with self._cond:
+            self.received += 1
+            if not self.critical:
+                self._offset = (self._offset + 1) % self.stride
+                if self._offset:
+                    self.skipped += 1
+                    return
+                self._adapt_stride()
+
+            if len(self._frames) >= self.capacity:
+                self._frames.popleft()
+                self.dropped += 1
+            self._frames.append(frame)
+            self.max_lag = max(self.max_lag, len(self._frames))
+            self._cond.notify()
+
+    # Producers must never block, so put() is the same as put_nowait()
+    def put(self, frame: Any, block: bool = True, timeout: Optional[float] = None) -> None:
+        self.put_nowait(frame)
+
+    def _adapt_stride(self) -> None:
+        depth = len(self._frames)
+        if depth > self.capacity // 2:
+            self.stride = min(self.stride * 2, MAX_STRIDE)
+        elif depth == 0 and self.stride > 1:
+            self.stride //= 2
+
+    def get(self, block: bool = True, timeout: Optional[float] = None) -> Any:
+        with self._cond:
+            if block and not self._frames:
+                self._cond.wait_for(lambda: self._frames, timeout)
+            if not self._frames:
+                raise Empty
+            return self._frames.popleft()
+
+    def get_nowait(self) -> Any:
+        return self.get(block=False)
+
+    def drain_latest(self) -> Optional[Any]:
+        """Discard everything queued except the newest frame, and return it (None if empty)."""
+        with self._cond:
+            if not self._frames:
+                return None
+            latest = self._frames[-1]
+            self._frames.clear()
+            return latest
+
+    def qsize(self) -> int:
+        return len(self._frames)
+
+    def empty(self) -> bool:
+        return not self._frames
+
+    def clear(self) -> None:
+        with self._cond:
+            self._frames.clear()
+
+    def stats(self) -> Dict[str, Any]:
+        return {
+            "name": self.name,
+            "critical": self.critical,
+            "lag": len(self._frames),
+            "capacity": self.capacity,
+            "max_lag": self.max_lag,
+            "received": self.received,
+            "dropped": self.dropped,
+            "skipped": self.skipped,
+            "stride": self.stride,
+        }
+
+
+class AudioBroadcast:
+    """One producer, many subscribers, each with its own bounded queue."""
+
+    def __init__(self, name: str):
+        self.name = name
+        self._subscribers: Dict[str, FrameQueue] = {}
+        self._lock = threading.Lock()
+
+    def subscribe(self, name: str, capacity: int, critical: bool = True) -> FrameQueue:
+        """Queue receiving every frame published from now on (same queue if already subscribed)."""
+        with self._lock:
+            if name not in self._subscribers:
+                self._subscribers[name] = FrameQueue(f"{self.name}/{name}", capacity, critical)
+            return self._subscribers[name]
+
+    def unsubscribe(self, name: str) -> None:
+        with self._lock:
+            self._subscribers.pop(name, None)
+
+    def publish(self, frame: Any) -> None:
+        with self._lock:
+            subscribers = list(self._subscribers.values())
+        for queue in subscribers:
+            queue.put_nowait(frame)
+
+    def stats(self) -> List[Dict[str, Any]]:
+        with self._lock:
+            return [q.stats() for q in self._subscribers.values()]
+
+
+def summarize(stats: List[Dict[str, Any]]) -> Dict[str, int]:
+    """Worst lag (as % of capacity) and total drops on critical queues, for the footer."""
+    critical = [s for s in stats if s["critical"]]
+    return {
+        "lag_percent": max((100 * s["lag"] // max(s["capacity"], 1) for s in critical), default=0),
+        "dropped": sum(s["dropped"] for s in critical),
+        "downsampled": max((s["stride"] for s in stats if not s["critical"]), default=1),
+    }
@@ -0,0 +1,155 @@
+"""
+Audio Devices - Spot a Bluetooth headset's mic forcing the low-quality call profile.
+
+A Bluetooth headset plays over A2DP (high quality, playback only). As soon
+as anything records from its mic it switches to the hands-free profile
+(HFP/HSP): the mic drops to 8 or 16kHz narrowband, playback degrades with
+it, and speech recognition gets noticeably worse.
+
+check_bluetooth() looks at the capture device AudioIO picked:
+
+- Linux: `pactl list cards` - a bluez card whose active profile is a
+  headset/handsfree one is in HFP (rerouting switches it back to A2DP)
+- macOS: the Bluetooth devices `system_profiler` lists as connected,
+  matched against the device name
+- anywhere: name hints (AirPods, Buds, headset...)
+
+Recording from a Bluetooth input device means HFP. With
+config.bluetooth_mic_fallback, AudioIO captures from the built-in mic
+instead and the headset stays in A2DP for playback; otherwise the
+dashboard warns once voice is up.
+"""
+
+import json
+import logging
+import platform
+import re
+import subprocess
+from dataclasses import dataclass
+from typing import Any, Dict, Iterable, List, Optional, Set
+
+logger = logging.getLogger(__name__)
+
+BLUETOOTH_HINTS = ("airpods", "bluetooth", "headset", "buds", "beats", "bose", "jabra", "wh-1000", "wf-1000",
+                   "bluez", "hands-free", "handsfree")
+BUILTIN_HINTS = ("built-in", "macbook", "internal", "hda intel", "realtek", "analog")
+VIRTUAL_INPUTS = ("default", "pulse", "pipewire", "sysdefault", "jack", "blackhole", "soundflower", "loopback",
+                  "zoomaudio", "teams", "virtual", "aggregate")
+HFP_PROFILES = re.compile(r"headset|handsfree|hsp|hfp", re.IGNORECASE)
+
+
+@dataclass
+class LinuxCard:
+    name: str  # e.g. bluez_card.AA_BB_CC_DD_EE_FF
+    profile: str  # Active profile, e.g. headset-head-unit
+    a2dp: Optional[str] = None  # Its A2DP profile, e.g. a2dp-sink
+
+
+@dataclass
+class BluetoothCheck:
+    headset: Optional[str] = None  # The Bluetooth device the mic records from
+    card: Optional[LinuxCard] = None  # Its PulseAudio/PipeWire card (Linux)
+    builtin_index: Optional[int] = None  # A non-Bluetooth mic to capture from instead
+    builtin_name: Optional[str] = None
+
+    @property
+    def hfp(self) -> bool:
+        return self.headset is not None
+
+    def warning(self, rerouted: bool = False) -> Optional[str]:
+        if not self.hfp:
+            return None
+        if rerouted:
+            return (f"🎧 Recording from {self.builtin_name} instead of {self.headset}'s mic, "
+                    "so the headset keeps its high-quality audio")
+        fix = ("set bluetooth_mic_fallback to record from " + self.builtin_name if self.builtin_name
+               else "use a wired or built-in mic")
+        return (f"🎧 {self.headset} is in headset (HFP) mode - its mic is low quality and speech recognition "
+                f"suffers; {fix}")
+
+
+def _run(command: List[str]) -> str:
+    try:
+        return subprocess.run(command, capture_output=True, text=True, timeout=5).stdout
+    except (OSError, subprocess.SubprocessError) as e:
+        logger.debug(f"{command[0]} failed: {e}")
+        return ""
+
+
+def linux_bluetooth_cards(output: Optional[str] = None) -> Dict[str, LinuxCard]:
+    """Bluetooth card description -> card, from `pactl list cards`."""
+    output = _run(["pactl", "list", "cards"]) if output is None else output
+    cards: Dict[str, LinuxCard] = {}
+    for card in re.split(r"\n(?=Card #)", output):
+        name = re.search(r"Name: (bluez_card\.\S+)", card)
+        description = re.search(r'device\.description = "([^"]+)"', card)
+        profile = re.search(r"Active Profile: (\S+)", card)
+        if name and description and profile:
+            a2dp = re.search(r"^\s+(a2dp[-_]sink\S*): ", card, re.MULTILINE)
+            cards[description.group(1)] = LinuxCard(name.group(1), profile.group(1), a2dp and a2dp.group(1))
+    return cards
+
+
+def restore_a2dp(check: BluetoothCheck) -> bool:
+    """Switch a Linux headset card back to A2DP once nothing records from it."""
+    if check.card is None or not check.card.a2dp:
+        return False
+    try:
+        subprocess.run(["pactl", "set-card-profile", check.card.name, check.card.a2dp],
+                       check=True, capture_output=True, timeout=5)
+        return True
+    except (OSError, subprocess.SubprocessError) as e:
+        logger.warning(f"Couldn't switch {check.headset} to A2DP: {e}")
+        return False
+
+
+def macos_bluetooth_devices(output: Optional[str] = None) -> Set[str]:
+    """Names of connected Bluetooth devices, from `system_profiler SPBluetoothDataType -json`."""
+    output = _run(["system_profiler", "SPBluetoothDataType", "-json"]) if output is None else output
+    try:
+        data = json.loads(output or "{}")
+    except ValueError:
+        return set()
+    names = set()
+    for controller in data.get("SPBluetoothDataType", []):
+        for entry in controller.get("device_connected", []) or []:
+            names.update(entry.keys())
+    return names
+
+
+def is_bluetooth(name: str, known: Iterable[str] = ()) -> bool:
+    lowered = name.lower()
+    return any(device.lower() in lowered for device in known if device) or any(h in lowered for h in BLUETOOTH_HINTS)
+
+
+def _builtin_input(devices: List[Dict[str, Any]], known: Iterable[str]) -> Optional[int]:
+    candidates = [i for i, device in enumerate(devices)
+                  if device.get("max_input_channels", 0) > 0 and not is_bluetooth(device["name"], known)
+                  and not any(v in device["name"].lower() for v in VIRTUAL_INPUTS)]
+    preferred = [i for i in candidates if any(h in devices[i]["name"].lower() for h in BUILTIN_HINTS)]
+    return (preferred or candidates or [None])[0]
+
+
+def check_bluetooth(devices: List[Dict[str, Any]], input_index: Optional[int],
+                    known: Optional[Iterable[str]] = None,
+                    linux_cards: Optional[Dict[str, LinuxCard]] = None) -> BluetoothCheck:
+    """Whether capturing from devices[input_index] puts a Bluetooth headset in HFP, and a mic to use instead."""
+    system = platform.system()
+    if linux_cards is None:
+        linux_cards = linux_bluetooth_cards() if system == "Linux" else {}
+    if known is None:
+        known = macos_bluetooth_devices() if system == "Darwin" else ()
+    known = set(known) | set(linux_cards)
+    check = BluetoothCheck()
+    name = devices[input_index]["name"] if input_index is not None and 0 <= input_index < len(devices) else ""
+    hfp_cards = {description: card for description, card in linux_cards.items() if HFP_PROFILES.search(card.profile)}
+    if hfp_cards:
+        # PulseAudio/PipeWire: the PortAudio device is usually just "default"; the card says it all
+        check.headset, check.card = next(iter(hfp_cards.items()))
+    elif name and is_bluetooth(name, known):
+        check.headset = name
+    if check.hfp:
+        check.builtin_index = _builtin_input(devices, known)
+        if check.builtin_index is not None:
+            check.builtin_name = devices[check.builtin_index]["name"]
+    return check
@@ -0,0 +1,85 @@
+"""
+Audio Frame - One immutable buffer shared by every consumer.
+
+A mic frame fans out to the Moshi encoder, the visualizer, the user
+transcriber and wake word detection every 10-20ms. Rather than each
+consumer copying and converting it, the frame is copied once out of the
+PortAudio buffer, marked read-only and passed by reference; derived forms
+(16-bit PCM bytes for Vosk, RMS for the visualizer, other sample rates -
+see resample.py) are computed once per frame and cached.
+
+Consumers accept either an AudioFrame or a plain ndarray (pcm16_bytes and
+frame_samples handle both), so existing callers keep working.
+
+Benchmark: python packages/assistant/assistant/scripts/bench_audio_fanout.py
+"""
+
+from typing import Dict, Optional, Union
+
+import numpy as np
+
+
+class AudioFrame:
+    """Read-only float32 samples plus lazily cached conversions."""
+
+    __slots__ = ("samples", "sample_rate", "_pcm16", "_rms", "_converted")
+
+    def __init__(self, samples: np.ndarray, sample_rate: Optional[int] = None):
+        # No copy when the input is already contiguous float32 (the common case)
+        samples = np.ascontiguousarray(samples, dtype=np.float32)
+        samples.flags.writeable = False
+        self.samples = samples
+        self.sample_rate = sample_rate
+        self._pcm16: Optional[bytes] = None
+        self._rms: Optional[float] = None
+        self._converted: Optional[Dict[int, "AudioFrame"]] = None
+
+    @classmethod
+    def from_buffer(cls, buffer: np.ndarray, sample_rate: Optional[int] = None) -> "AudioFrame":
+        """Copy out of a buffer the producer will reuse (e.g. PortAudio's indata). The only copy a frame gets."""
+        return cls(np.array(buffer, dtype=np.float32, copy=True), sample_rate)
+
+    def __len__(self) -> int:
+        return len(self.samples)
+
+    def pcm16(self) -> bytes:
+        """16-bit little-endian PCM, as Vosk wants it (converted once, shared)."""
+        if self._pcm16 is None:
+            self._pcm16 = _to_pcm16(self.samples)
+        return self._pcm16
+
+    def rms(self) -> float:
+        if self._rms is None:
+            self._rms = float(np.sqrt(np.mean(np.square(self.samples)))) if len(self.samples) else 0.0
+        return self._rms
+
+    def converted(self, sample_rate: int) -> Optional["AudioFrame"]:
+        """This frame resampled to `sample_rate`, if a consumer already asked for it."""
+        return self._converted.get(sample_rate) if self._converted else None
+
+    def cache_converted(self, sample_rate: int, frame: "AudioFrame") -> None:
+        if self._converted is None:
+            self._converted = {}
+        self._converted[sample_rate] = frame
+
+
+AudioLike = Union[AudioFrame, np.ndarray]
+
+
+def frame_samples(audio: AudioLike) -> np.ndarray:
+    """The sample array, without copying."""
+    return audio.samples if isinstance(audio, AudioFrame) else audio
+
+
+def pcm16_bytes(audio: AudioLike) -> bytes:
+    """16-bit PCM bytes for a frame (cached) or a raw float/int16 array."""
+    if isinstance(audio, AudioFrame):
+        return audio.pcm16()
+    if audio.dtype in (np.float32, np.float64):
+        return _to_pcm16(audio)
+    return audio.tobytes()
+
+
+def _to_pcm16(samples: np.ndarray) -> bytes:
+    scaled = np.multiply(samples, 32767, dtype=np.float32)
+    return scaled.astype(np.int16).tobytes()
@@ -0,0 +1,135 @@
+"""
+Audio Mixer - Per-stream gain and ducking for everything AudioIO plays.
+
+The playback callback builds each block from three streams:
+
+- speech: the assistant's voice, from AudioIO's output queue
+- earcons: listening cues (earcons.py), mixed over speech rather than
+  queued behind it
+- media: anything else passed through (AudioIO.play_media), played in
+  order so a stream fed in chunks stays continuous
+
+Every stream is scaled by volume.VolumeSettings.gain(), read per block so
+"speak louder" takes effect mid-sentence. While a block has speech in it,
+media is ducked to duck_level, ramping down over DUCK_ATTACK and back up
+over DUCK_RELEASE so the gaps between speech chunks don't pump. Earcons
+are short and meant to be heard, so they aren't ducked.
+"""
+
+import threading
+from collections import deque
+from typing import Callable, Dict, Optional
+
+import numpy as np
+
+from .volume import VolumeSettings, get_volume_settings
+
+ONE_SHOT_STREAMS = ("earcons", "media")  # Sounds added whole, rather than from the output queue
+QUEUED_STREAMS = ("media",)  # One sound after another; earcons overlap
+DUCKED_STREAMS = ("media",)
+
+DUCK_ATTACK = 0.05  # Seconds to duck when speech starts
+DUCK_RELEASE = 0.5  # Seconds to come back up after it stops
+
+
+class OutputMixer:
+    """Mixes one-shot sounds over speech, with per-stream gain and ducking."""
+
+    def __init__(self, sample_rate: int = 24000,
+                 settings: Optional[Callable[[], VolumeSettings]] = None):
+        self.sample_rate = sample_rate
+        self.settings = settings or get_volume_settings
+        self._sounds: Dict[str, deque] = {stream: deque() for stream in ONE_SHOT_STREAMS}  # [samples, position]
+        self._lock = threading.Lock()
+        self.duck = 1.0  # Current duck gain, 1.0 when not ducking
+
+    def add(self, stream: str, samples: np.ndarray) -> None:
+        """Start a sound on a one-shot stream (float32 at sample_rate)."""
+        if stream not in self._sounds:
+            raise ValueError(f"Unknown stream '{stream}' (one of {', '.join(ONE_SHOT_STREAMS)})")
+        if len(samples):
+            with self._lock:
+                self._sounds[stream].append([np.asarray(samples, dtype=np.float32), 0])
+
+    def clear(self) -> None:
+        with self._lock:
+            for sounds in self._sounds.values():
+                sounds.clear()
+        self.duck = 1.0
+
+    def active(self, stream: Optional[str] = None) -> bool:
+        """True while anything (or anything on `stream`) is still playing."""
+        streams = [stream] if stream else ONE_SHOT_STREAMS
+        return any(self._sounds[name] for name in streams)
+
+    def _duck_ramp(self, count: int, speaking: bool, duck_level: float) -> np.ndarray:
+        """Per-sample duck gains for the next `count` samples."""
+        target = duck_level if speaking else 1.0
+        seconds = DUCK_ATTACK if target < self.duck else DUCK_RELEASE
+        step = count / max(1.0, seconds * self.sample_rate) * (1.0 - duck_level)
+        if target < self.duck:
+            end = max(target, self.duck - step)
+        else:
+            end = min(target, self.duck + step)
+        ramp = np.linspace(self.duck, end, count, dtype=np.float32)
+        self.duck = end
+        return ramp
+
+    def mix(self, output: np.ndarray, speech: int = 0) -> int:
+        """
+        Mix a playback block in place. `output[:speech]` holds speech from the
+        output queue; it's scaled to the speech volume, and the one-shot
+        streams are added over the whole block (clipped). Returns how many
+        samples have sound in them.
+        """
+        settings = self.settings()
+        if speech:
+            output[:speech] *= settings.gain("speech")
+        touched = speech
+        ramp = self._duck_ramp(len(output), speech > 0, settings.duck_level)
+        with self._lock:
+            for stream, sounds in self._sounds.items():
+                if not sounds:
+                    continue
+                gain = settings.gain(stream)
+                if stream in QUEUED_STREAMS:
+                    chunks = [self._next_queued(sounds, len(output))]
+                else:
+                    chunks = self._next_overlapping(stream, len(output))
+                for chunk in chunks:
+                    chunk = chunk * gain
+                    if stream in DUCKED_STREAMS:
+                        chunk *= ramp[:len(chunk)]
+                    output[:len(chunk)] += chunk
+                    touched = max(touched, len(chunk))
+        if touched:
+            np.clip(output, -1.0, 1.0, out=output)
+        return touched
+
+    def _next_queued(self, sounds: deque, count: int) -> np.ndarray:
+        """Up to `count` samples continuing through the queued sounds in order."""
+        parts = []
+        while sounds and count > 0:
+            sound = sounds[0]
+            samples, position = sound
+            part = samples[position:position + count]
+            parts.append(part)
+            count -= len(part)
+            sound[1] = position + len(part)
+            if sound[1] >= len(samples):
+                sounds.popleft()
+        return np.concatenate(parts) if parts else np.zeros(0, dtype=np.float32)
+
+    def _next_overlapping(self, stream: str, count: int) -> list:
+        """The next `count` samples of each sound on `stream`, all starting now."""
+        chunks = []
+        still_playing = deque()
+        for sound in self._sounds[stream]:
+            samples, position = sound
+            chunks.append(samples[position:position + count])
+            sound[1] = position + len(chunks[-1])
+            if sound[1] < len(samples):
+                still_playing.append(sound)
+        self._sounds[stream] = still_playing
+        return chunks
+
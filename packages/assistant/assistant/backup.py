@@ -0,0 +1,269 @@
+"""
+Backup - All local state in one encrypted file, and putting it back.
+
+`xswarm dev backup create FILE` packs these components into one archive:
+
+- config: ~/.config/xswarm/config.yaml
+- memory: semantic memory (~/.xswarm/memory) and the user profile
+- personas: the persona bundles (--personas-dir)
+- wake_word: the wake word model (config.wake_word_model)
+- events: activity and inbox history (~/.xswarm/events.db)
+- transcripts: saved chat sessions (~/.xswarm/chat_history)
+- planner: calendar, tasks and follow-ups (~/.xswarm/planning, ~/.xswarm/followups)
+
+`--only memory,events` backs up (or restores) just those components, so
+`xswarm dev backup restore FILE --only memory` brings back memory and
+leaves everything else as it is. SQLite databases are copied with
+SQLite's backup API, so a running assistant doesn't leave them half-written.
+
+Restoring replaces a component outright: what's there now is moved aside
+first (no stale files survive next to the restored ones) and only deleted
+once everything is written; if writing fails, it's all put back.
+
+Format: "XSWB" + version byte, a 16-byte salt and a 12-byte nonce, then a
+tar.gz encrypted with AES-256-GCM under a key derived from the passphrase
+(scrypt). The tar starts with manifest.json: format version, app version,
+creation time and the files of each component. The passphrase comes from
+$XSWARM_BACKUP_PASSPHRASE or is asked for.
+"""
+
+import io
+import json
+import logging
+import os
+import shutil
+import sqlite3
+import tarfile
+import tempfile
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, List, Optional, Sequence, Tuple
+
+logger = logging.getLogger(__name__)
+
+MAGIC = b"XSWB"
+FORMAT_VERSION = 1  # Bumped when the layout changes; newer backups are refused
+PASSPHRASE_ENV = "XSWARM_BACKUP_PASSPHRASE"
+MANIFEST = "manifest.json"
+_SALT, _NONCE = 16, 12
+_JOURNALS = ("-wal", "-shm", "-journal")  # SQLite's, replayed onto a database restored next to them
+
+
+class BackupError(Exception):
+    """A backup that can't be written or read (wrong passphrase, damaged or too new)."""
+
+
+@dataclass(frozen=True)
+class Component:
+    name: str
+    description: str
+    paths: Tuple[Path, ...]  # Files or directories; stored under "<name>/<path name>/"
+
+
+def components(config, personas_dir: Optional[Path] = None, home: Optional[Path] = None,
+               config_path: Optional[Path] = None) -> Dict[str, Component]:
+    """What gets backed up, in order, and where it lives on this machine."""
+    home = home or Path.home()
+    state = home / ".xswarm"
+    config_path = config_path or home / ".config" / "xswarm" / "config.yaml"
+    found = [
+        Component("config", "Settings", (config_path,)),
+        Component("memory", "Memory and user profile", (state / "memory", state / "user_profile")),
+        Component("personas", "Persona bundles", (personas_dir,) if personas_dir else ()),
+        Component("wake_word", "Wake word model", (Path(config.wake_word_model),)),
+        Component("events", "Activity and inbox history", (state / "events.db",)),
+        Component("transcripts", "Chat transcripts", (state / "chat_history",)),
+        Component("planner", "Calendar, tasks and follow-ups", (state / "planning", state / "followups")),
+    ]
+    return {component.name: component for component in found}
+
+
+def select(available: Dict[str, Component], only: Optional[Sequence[str]]) -> List[Component]:
+    """The components named in `only` ("memory,events" or a list), or all of them."""
+    names = [n.strip() for value in only or [] for n in value.split(",") if n.strip()]
+    unknown = [n for n in names if n not in available]
+    if unknown:
+        raise BackupError(f"Unknown component '{unknown[0]}' (use {', '.join(available)})")
+    return [available[n] for n in names] if names else list(available.values())
+
+
+def _key(passphrase: str, salt: bytes) -> bytes:
+    from cryptography.hazmat.primitives.kdf.scrypt import Scrypt
+    return Scrypt(salt=salt, length=32, n=2 ** 15, r=8, p=1).derive(passphrase.encode())
+
+
+def encrypt(data: bytes, passphrase: str) -> bytes:
+    from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+    salt, nonce = os.urandom(_SALT), os.urandom(_NONCE)
+    header = MAGIC + bytes([FORMAT_VERSION])
+    return header + salt + nonce + AESGCM(_key(passphrase, salt)).encrypt(nonce, data, header)
+
+
+def decrypt(blob: bytes, passphrase: str) -> bytes:
+    from cryptography.exceptions import InvalidTag
+    from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+    if not blob.startswith(MAGIC) or len(blob) < len(MAGIC) + 1 + _SALT + _NONCE:
+        raise BackupError("Not an xswarm backup")
+    version = blob[len(MAGIC)]
+    if version > FORMAT_VERSION:
+        raise BackupError(f"Backup format {version} is newer than this xswarm understands (update first)")
+    header, rest = blob[:len(MAGIC) + 1], blob[len(MAGIC) + 1:]
+    salt, nonce, ciphertext = rest[:_SALT], rest[_SALT:_SALT + _NONCE], rest[_SALT + _NONCE:]
+    try:
+        return AESGCM(_key(passphrase, salt)).decrypt(nonce, ciphertext, header)
+    except InvalidTag:
+        raise BackupError("Wrong passphrase, or the backup is damaged") from None
+
+
+def _files(path: Path) -> List[Path]:
+    if path.is_file():
+        return [path]
+    if path.is_dir():
+        return sorted(p for p in path.rglob("*") if p.is_file() and "__pycache__" not in p.parts)
+    return []
+
+
+def _sqlite_copy(path: Path) -> bytes:
+    """A consistent copy of a SQLite database that may be open elsewhere."""
+    with tempfile.TemporaryDirectory() as tmp:
+        copy = Path(tmp) / path.name
+        source, target = sqlite3.connect(str(path)), sqlite3.connect(str(copy))
+        try:
+            source.backup(target)
+        finally:
+            target.close()
+            source.close()
+        return copy.read_bytes()
+
+
+def _read(path: Path) -> bytes:
+    if path.suffix == ".db":
+        try:
+            return _sqlite_copy(path)
+        except sqlite3.Error as e:
+            logger.debug(f"{path} is not SQLite ({e}), copying as is")
+    return path.read_bytes()
+
+
+def create_backup(target: Path, passphrase: str, selected: List[Component],
+                  app_version: str = "", now: Optional[datetime] = None) -> dict:
+    """Write the components to `target`; returns the manifest."""
+    if not passphrase:
+        raise BackupError("A passphrase is needed to encrypt the backup")
+    manifest = {
+        "format_version": FORMAT_VERSION,
+        "app_version": app_version,
+        "created_at": (now or datetime.now()).isoformat(timespec="seconds"),
+        "components": {},
+    }
+    buffer = io.BytesIO()
+    with tarfile.open(fileobj=buffer, mode="w:gz") as tar:
+        members = []
+        for component in selected:
+            files, size = [], 0
+            for root in component.paths:
+                for path in _files(root):
+                    name = f"{component.name}/{root.name}"
+                    if path != root:
+                        name += "/" + path.relative_to(root).as_posix()
+                    data = _read(path)
+                    members.append((name, data))
+                    files.append(name)
+                    size += len(data)
+            manifest["components"][component.name] = {"files": files, "bytes": size}
+        _add(tar, MANIFEST, json.dumps(manifest, indent=2).encode())
+        for name, data in members:
+            _add(tar, name, data)
+    target.parent.mkdir(parents=True, exist_ok=True)
+    target.write_bytes(encrypt(buffer.getvalue(), passphrase))
+    target.chmod(0o600)
+    return manifest
+
+
+def _add(tar: tarfile.TarFile, name: str, data: bytes) -> None:
+    info = tarfile.TarInfo(name)
+    info.size = len(data)
+    info.mode = 0o600
+    tar.addfile(info, io.BytesIO(data))
+
+
+def read_manifest(source: Path, passphrase: str) -> dict:
+    return _open(source, passphrase)[0]
+
+
+def _open(source: Path, passphrase: str) -> Tuple[dict, Dict[str, bytes]]:
+    try:
+        blob = source.read_bytes()
+    except OSError as e:
+        raise BackupError(f"Can't read {source}: {e.strerror}") from None
+    with tarfile.open(fileobj=io.BytesIO(decrypt(blob, passphrase)), mode="r:gz") as tar:
+        contents = {member.name: tar.extractfile(member).read() for member in tar.getmembers() if member.isfile()}
+    if MANIFEST not in contents:
+        raise BackupError("Backup has no manifest")
+    return json.loads(contents.pop(MANIFEST)), contents
+
+
+def _planned_writes(component: Component, contents: Dict[str, bytes]) -> List[Tuple[Path, bytes]]:
+    """Where each of the component's files in the backup goes; BackupError for any outside its folder."""
+    writes = []
+    for root in component.paths:
+        prefix = f"{component.name}/{root.name}"
+        for name in (n for n in contents if n == prefix or n.startswith(prefix + "/")):
+            relative = name[len(prefix):].lstrip("/")
+            path = root / relative if relative else root
+            # Member names come from the archive; never write outside the component's folder
+            if relative and not path.resolve().is_relative_to(root.resolve()):
+                raise BackupError(f"Refusing to restore {name} outside {root}")
+            writes.append((path, contents[name]))
+    return writes
+
+
+def _replaced(root: Path) -> List[Path]:
+    """The root and, for a database, its journals."""
+    return [root] + ([root.with_name(root.name + journal) for journal in _JOURNALS] if root.suffix == ".db" else [])
+
+
+def _remove(path: Path) -> None:
+    if path.is_dir() and not path.is_symlink():
+        shutil.rmtree(path)
+    else:
+        path.unlink(missing_ok=True)
+
+
+def restore_backup(source: Path, passphrase: str, selected: List[Component]) -> Dict[str, int]:
+    """
+    Put the selected components back where they live on this machine,
+    replacing what's there. Returns files restored per component (only
+    components that are in the backup). All or nothing: if a write fails,
+    every component is left as it was.
+    """
+    manifest, contents = _open(source, passphrase)
+    included = [c for c in selected if c.name in manifest.get("components", {})]
+    writes = {component.name: _planned_writes(component, contents) for component in included}
+
+    touched: List[Path] = []
+    aside: Dict[Path, Path] = {}  # Original -> where it waits until the restore is done
+    try:
+        for component in included:
+            for root in component.paths:
+                for path in _replaced(root):
+                    touched.append(path)
+                    if path.exists() or path.is_symlink():
+                        holder = Path(tempfile.mkdtemp(prefix=f".{path.name}.", suffix=".before-restore",
+                                                       dir=path.parent))
+                        aside[path] = holder / path.name
+                        path.replace(aside[path])
+            for path, data in writes[component.name]:
+                path.parent.mkdir(parents=True, exist_ok=True)
+                path.write_bytes(data)
+    except BaseException:
+        for path in reversed(touched):
+            _remove(path)
+            if path in aside:
+                aside[path].replace(path)
+                aside[path].parent.rmdir()
+        raise
+    for kept in aside.values():
+        shutil.rmtree(kept.parent)
+    return {name: len(files) for name, files in writes.items()}
@@ -0,0 +1,345 @@
+"""
+Booking Emails - Flight, shipping and reservation details from confirmation emails.
+
+Airlines, shops and booking sites mark up their confirmation emails with
+schema.org JSON-LD (the markup mail clients use for trip cards): a
+FlightReservation, LodgingReservation, ParcelDelivery... When an email
+body has it, the details come from there. Plain-text emails fall back to
+a few patterns for the common formats: a flight ("Flight UA 523",
+"Confirmation code: K7X2QP", "Terminal 3", "Seat 14C") or a shipment
+("Tracking number: 1Z999AA10123456784", "Arriving Tuesday"). Without a
+booking reference or tracking number an email isn't treated as one of
+these - the generic rules in email_tasks.py still look at it.
+
+Each confirmation becomes a richer proposal than the generic rules make:
+
+- the title names the thing ("Flight UA 523 SFO → DEN", "Check in: Hotel
+  Monaco"), and the place becomes the event's location
+- the details (flight number, confirmation code, terminal, gate, seat,
+  tracking number...) go in the event's description as "Key: value" lines
+- the reminder goes off when it's useful for that kind of thing: 3 hours
+  before a flight, an hour before a train or a table
+- a delivery becomes a reminder task for the day it's expected
+
+Times with a UTC offset are converted to home time, the way planner times
+are stored (timezones.py); times without one are taken as written.
+"""
+
+import json
+import logging
+import re
+from dataclasses import dataclass, field
+from datetime import date, datetime
+from typing import Dict, Iterable, List, Optional
+
+from .dates import get_date_settings
+
+logger = logging.getLogger(__name__)
+
+# Kind: (what the notice calls it, reminder minutes before, default length in minutes)
+KINDS = {
+    "flight": ("Flight confirmation", 180, 120),
+    "train": ("Train ticket", 60, 120),
+    "bus": ("Bus ticket", 60, 120),
+    "rental": ("Car rental", 60, 30),
+    "lodging": ("Hotel reservation", 120, 60),
+    "restaurant": ("Reservation", 60, 90),
+    "event": ("Ticket", 90, 120),
+    "parcel": ("Delivery", 0, 0),
+}
+
+_LD_JSON = re.compile(r"<script[^>]*type\s*=\s*[\"']?application/ld\+json[\"']?[^>]*>(.*?)</script>", re.I | re.S)
+_FLIGHT = re.compile(r"\b(?i:flight)\s*(?i:number|no\.?|#)?\s*:?\s*(?P<airline>[A-Z][A-Z0-9]|[A-Z0-9][A-Z])\s?"
+                     r"(?P<number>\d{1,4})\b")
+_BOOKING = re.compile(r"\b(?i:confirmation|booking reference|booking|record locator|reservation|PNR)"
+                      r"(?:\s+(?i:code|number|no\.?|#))?\s*(?:(?i:is)\s+|[:#]\s*)(?P<code>[A-Z0-9]{5,8})\b")
+_TERMINAL = re.compile(r"\b(?i:terminal)\s*:?\s*(?P<value>[A-Z]?\d{1,2}[A-Z]?|[A-Z])\b")
+_GATE = re.compile(r"\b(?i:gate)\s*:?\s*(?P<value>[A-Z]?\d{1,3}[A-Z]?)\b")
+_SEAT = re.compile(r"\b(?i:seat)\s*:?\s*(?P<value>\d{1,2}[A-K])\b")
+_ROUTE = re.compile(r"\b(?P<origin>[A-Z]{3})\s*(?:→|->|–|—|-|\b(?i:to)\b)\s*(?P<destination>[A-Z]{3})\b")
+_TRACKING = re.compile(r"\b(?i:tracking)(?:\s+(?i:number|no\.?|#|id))?\s*(?:(?i:is)\s+|[:#]\s*)"
+                       r"(?P<number>[A-Z0-9]{10,34})\b")
+_CARRIER = re.compile(r"\b(UPS|FedEx|USPS|DHL|Royal Mail|Canada Post|Amazon Logistics|OnTrac)\b", re.I)
+_ARRIVAL = re.compile(r"\b(?:arriv\w*|deliver\w*|expected|estimated|out for delivery)\b", re.I)
+
+
+@dataclass
+class Confirmation:
+    """One booking or shipment found in an email."""
+    kind: str  # A KINDS key
+    title: str
+    start: datetime
+    end: Optional[datetime] = None
+    has_time: bool = True
+    location: str = ""
+    details: Dict[str, str] = field(default_factory=dict)  # Flight, Confirmation, Terminal, ...
+
+    @property
+    def notice_name(self) -> str:
+        return KINDS[self.kind][0]
+
+    @property
+    def reminder_minutes(self) -> int:
+        return KINDS[self.kind][1]
+
+    @property
+    def minutes(self) -> int:
+        return KINDS[self.kind][2]
+
+    def description(self) -> str:
+        """The details as "Key: value" lines, for the event description."""
+        return "\n".join(f"{key}: {value}" for key, value in self.details.items() if value)
+
+
+def _type(entry: dict) -> str:
+    kind = entry.get("@type") or ""
+    if isinstance(kind, list):
+        kind = kind[0] if kind else ""
+    return str(kind).rsplit("/", 1)[-1]
+
+
+def _name(value) -> str:
+    if isinstance(value, dict):
+        return str(value.get("name") or value.get("iataCode") or "").strip()
+    return str(value or "").strip()
+
+
+def _code(value) -> str:
+    """An airport or station's code, else its name."""
+    if isinstance(value, dict):
+        return str(value.get("iataCode") or value.get("name") or "").strip()
+    return str(value or "").strip()
+
+
+def _address(value) -> str:
+    if not isinstance(value, dict):
+        return ""
+    address = value.get("address")
+    if isinstance(address, dict):
+        parts = [address.get("streetAddress"), address.get("addressLocality")]
+        address = ", ".join(str(p) for p in parts if p)
+    name = _name(value)
+    return ", ".join(p for p in (name, str(address or "").strip()) if p)
+
+
+def _when(value) -> Optional[datetime]:
+    """An ISO date or datetime as naive home time."""
+    if not value:
+        return None
+    try:
+        parsed = datetime.fromisoformat(str(value).replace("Z", "+00:00"))
+    except ValueError:
+        return None
+    if parsed.tzinfo is not None:
+        home = get_date_settings().home_zone()
+        parsed = (parsed.astimezone(home) if home else parsed.astimezone()).replace(tzinfo=None)
+    return parsed
+
+
+def _has_time(value) -> bool:
+    return "T" in str(value or "")
+
+
+def _ld_entries(html: str) -> Iterable[dict]:
+    for block in _LD_JSON.findall(html or ""):
+        try:
+            data = json.loads(block.strip())
+        except ValueError:
+            logger.debug("Skipped unreadable JSON-LD in an email")
+            continue
+        for entry in data if isinstance(data, list) else [data]:
+            if isinstance(entry, dict) and isinstance(entry.get("@graph"), list):
+                yield from (e for e in entry["@graph"] if isinstance(e, dict))
+            elif isinstance(entry, dict):
+                yield entry
+
+
+def _trips(reservation: dict) -> List[dict]:
+    trips = reservation.get("reservationFor") or {}
+    return [t for t in (trips if isinstance(trips, list) else [trips]) if isinstance(t, dict)]
+
+
+def _flight(reservation: dict) -> Iterable[Confirmation]:
+    for flight in _trips(reservation):
+        start = _when(flight.get("departureTime"))
+        if not start:
+            continue
+        airline = flight.get("airline") or {}
+        airline_code = airline.get("iataCode", "") if isinstance(airline, dict) else ""
+        number = str(flight.get("flightNumber") or "")
+        if airline_code and not number.startswith(airline_code):
+            number = f"{airline_code} {number}"
+        origin, destination = _code(flight.get("departureAirport")), _code(flight.get("arrivalAirport"))
+        route = f"{origin} → {destination}" if origin and destination else ""
+        seat = reservation.get("airplaneSeat")
+        yield Confirmation("flight", " ".join(p for p in ("Flight", number, route) if p), start,
+                           _when(flight.get("arrivalTime")), location=_address(flight.get("departureAirport")),
+                           details={"Flight": number, "Confirmation": reservation.get("reservationNumber"),
+                                    "Route": route, "Terminal": flight.get("departureTerminal"),
+                                    "Gate": flight.get("departureGate"), "Seat": seat,
+                                    "Passenger": _name(reservation.get("underName"))})
+
+
+def _seat(reservation: dict) -> Optional[str]:
+    ticket = reservation.get("reservedTicket")
+    seat = ticket.get("ticketedSeat") if isinstance(ticket, dict) else None
+    return seat.get("seatNumber") if isinstance(seat, dict) else None
+
+
+def _ground_trip(reservation: dict, kind: str) -> Iterable[Confirmation]:
+    prefix = "train" if kind == "train" else "bus"
+    for trip in _trips(reservation):
+        start = _when(trip.get("departureTime"))
+        if not start:
+            continue
+        number = str(trip.get(f"{prefix}Number") or "")
+        origin = _name(trip.get("departureStation") or trip.get("departureBusStop"))
+        destination = _name(trip.get("arrivalStation") or trip.get("arrivalBusStop"))
+        route = f"{origin} → {destination}" if origin and destination else ""
+        yield Confirmation(kind, " ".join(p for p in (kind.title(), number, route) if p), start,
+                           _when(trip.get("arrivalTime")), location=origin,
+                           details={kind.title(): number, "Confirmation": reservation.get("reservationNumber"),
+                                    "Route": route, "Platform": trip.get("departurePlatform"),
+                                    "Seat": _seat(reservation)})
+
+
+def _lodging(reservation: dict) -> Iterable[Confirmation]:
+    hotel = (_trips(reservation) or [{}])[0]
+    checkin = reservation.get("checkinTime") or reservation.get("checkinDate")
+    start = _when(checkin)
+    if not start:
+        return
+    checkout = _when(reservation.get("checkoutTime") or reservation.get("checkoutDate"))
+    yield Confirmation("lodging", f"Check in: {_name(hotel) or 'hotel'}", start, has_time=_has_time(checkin),
+                       location=_address(hotel),
+                       details={"Hotel": _name(hotel), "Confirmation": reservation.get("reservationNumber"),
+                                "Check-out": f"{checkout:%a %b %d %H:%M}" if checkout else None,
+                                "Guests": reservation.get("numAdults")})
+
+
+def _restaurant(reservation: dict) -> Iterable[Confirmation]:
+    place = (_trips(reservation) or [{}])[0]
+    start = _when(reservation.get("startTime"))
+    if start:
+        yield Confirmation("restaurant", f"Reservation: {_name(place) or 'table'}", start,
+                           _when(reservation.get("endTime")), location=_address(place),
+                           details={"Restaurant": _name(place), "Confirmation": reservation.get("reservationNumber"),
+                                    "Party size": reservation.get("partySize")})
+
+
+def _event(reservation: dict) -> Iterable[Confirmation]:
+    ticket = reservation.get("reservedTicket")
+    for event in _trips(reservation):
+        start = _when(event.get("startDate"))
+        if not start:
+            continue
+        yield Confirmation("event", _name(event) or "Event", start, _when(event.get("endDate")),
+                           has_time=_has_time(event.get("startDate")), location=_address(event.get("location")),
+                           details={"Confirmation": reservation.get("reservationNumber"),
+                                    "Ticket": ticket.get("ticketNumber") if isinstance(ticket, dict) else None,
+                                    "Seat": _seat(reservation)})
+
+
+def _rental(reservation: dict) -> Iterable[Confirmation]:
+    start = _when(reservation.get("pickupTime"))
+    if not start:
+        return
+    car = (_trips(reservation) or [{}])[0]
+    company = _name(car.get("rentalCompany") or car.get("brand")) or _name(car)
+    dropoff = _when(reservation.get("dropoffTime"))
+    yield Confirmation("rental", f"Car pickup: {company or 'rental car'}", start,
+                       location=_address(reservation.get("pickupLocation")),
+                       details={"Company": company, "Confirmation": reservation.get("reservationNumber"),
+                                "Drop-off": f"{dropoff:%a %b %d %H:%M}" if dropoff else None})
+
+
+def _parcel(delivery: dict) -> Iterable[Confirmation]:
+    expected = delivery.get("expectedArrivalUntil") or delivery.get("expectedArrivalFrom")
+    start = _when(expected)
+    if not start:
+        return
+    order = delivery.get("partOfOrder") if isinstance(delivery.get("partOfOrder"), dict) else {}
+    item = _name(delivery.get("itemShipped"))
+    merchant = _name(order.get("merchant") or order.get("seller"))
+    carrier = _name(delivery.get("carrier") or delivery.get("provider"))
+    yield Confirmation("parcel", f"Delivery: {item or merchant or 'package'}", start, has_time=False,
+                       details={"Carrier": carrier, "Tracking": delivery.get("trackingNumber"),
+                                "Order": order.get("orderNumber"), "From": merchant})
+
+
+_MARKUP = {
+    "FlightReservation": _flight,
+    "TrainReservation": lambda r: _ground_trip(r, "train"),
+    "BusReservation": lambda r: _ground_trip(r, "bus"),
+    "LodgingReservation": _lodging,
+    "FoodEstablishmentReservation": _restaurant,
+    "EventReservation": _event,
+    "RentalCarReservation": _rental,
+    "ParcelDelivery": _parcel,
+}
+
+
+def from_markup(html: str) -> List[Confirmation]:
+    """Confirmations in an email's schema.org JSON-LD."""
+    found = []
+    for entry in _ld_entries(html):
+        parse = _MARKUP.get(_type(entry))
+        if parse:
+            try:
+                found.extend(parse(entry))
+            except (AttributeError, TypeError, ValueError) as e:
+                logger.debug(f"Skipped malformed {_type(entry)} markup: {e}")
+    return found
+
+
+def _first_date(text: str, today: date, near: Optional[re.Pattern] = None):
+    from .email_tasks import _sentences, find_dates
+
+    for sentence in _sentences(text):
+        if near is not None and not near.search(sentence):
+            continue
+        dates = find_dates(sentence, today)
+        if dates:
+            return dates[0][:2]
+    return None, False
+
+
+def _text_flight(text: str, today: date) -> Optional[Confirmation]:
+    flight, booking = _FLIGHT.search(text), _BOOKING.search(text)
+    if not flight or not booking:
+        return None
+    start, has_time = _first_date(text, today, re.compile(r"\b(?:flight|depart\w*)\b", re.I))
+    if not start or not has_time:
+        return None
+    number = f"{flight.group('airline')} {flight.group('number')}"
+    route = _ROUTE.search(text)
+    route_text = f"{route.group('origin')} → {route.group('destination')}" if route else ""
+    details = {"Flight": number, "Confirmation": booking.group("code"), "Route": route_text}
+    for label, pattern in (("Terminal", _TERMINAL), ("Gate", _GATE), ("Seat", _SEAT)):
+        match = pattern.search(text)
+        details[label] = match.group("value") if match else None
+    return Confirmation("flight", " ".join(p for p in ("Flight", number, route_text) if p), start,
+                        location=route.group("origin") if route else "", details=details)
+
+
+def _text_parcel(text: str, today: date, sender: str) -> Optional[Confirmation]:
+    tracking = _TRACKING.search(text)
+    if not tracking:
+        return None
+    start, _ = _first_date(text, today, _ARRIVAL)
+    if not start:
+        return None
+    carrier = _CARRIER.search(text)
+    return Confirmation("parcel", f"Delivery: {sender}", start.replace(hour=0, minute=0), has_time=False,
+                        details={"Carrier": carrier.group(1) if carrier else None,
+                                 "Tracking": tracking.group("number"), "From": sender})
+
+
+def parse_confirmations(item, today: date) -> List[Confirmation]:
+    """Upcoming bookings and deliveries in an email: schema.org markup first, then text formats."""
+    content = item.content or ""
+    found = from_markup(content)
+    if not found:
+        text = f"{item.subject or ''}\n{re.sub(r'<[^>]+>', ' ', content)}"
+        found = [c for c in (_text_flight(text, today), _text_parcel(text, today, item.sender)) if c]
+    return [c for c in found if c.start.date() >= today]
@@ -0,0 +1,244 @@
+"""
+Bookmarks - Parts of a conversation the user asked to keep ("remember this").
+
+    "remember this as the contract terms"          -> the last DEFAULT_TURNS turns, labelled
+    "bookmark the last 6 messages about the lease" -> the last six
+    "pin that"                                      -> labelled from what was said
+    "what did I bookmark about the contract?"       -> read back the best matches
+
+Every message shown in the chat (typed or spoken, the user's and the
+persona's) is noted as a turn (ChatHistory.add_message calls note_turn), so
+"this" is the conversation just before the request - the request itself is
+left out. A bookmark keeps the turns word for word with its label, and is
+pinned: it lives with the memory store, not the chat transcripts, so
+transcript retention (retention.py) never deletes it.
+
+Finding one matches the query's words against the label (which counts
+most) and the turns, plurals and all. The find_bookmarks tool does the same
+for the model, forget_bookmark deletes one ("undo that" brings it back),
+and `xswarm dev memory list` shows them with the remembered facts.
+
+Storage: ~/.xswarm/memory/bookmarks.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from collections import deque
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Deque, Dict, List, Optional, Tuple
+
+from .capabilities import register_capability
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_TURNS = 4  # "remember this": the last two exchanges
+RECENT_LIMIT = 40  # Turns kept to bookmark from
+LABEL_WORDS = 6  # A label made up from what was said is this many words at most
+NOT_TURNS = {"system", "debug", "thinking"}  # Chat senders that aren't the conversation
+NUMBERS = {"two": 2, "three": 3, "four": 4, "five": 5, "six": 6, "eight": 8, "ten": 10}
+STOPWORDS = {"the", "a", "an", "and", "or", "of", "to", "in", "on", "for", "about", "my", "i", "we", "it",
+             "is", "was", "that", "this", "with", "what", "you", "me"}
+
+_REQUEST = re.compile(
+    r"^\s*(?:(?:ok(?:ay)?|hey|please|can\s+you|could\s+you)[,\s]+)*"
+    r"(?:(?:bookmark|remember|pin|save|flag|keep)\s+(?:this|that|these|those|the\s+last\s+(?P<count>\d+|"
+    + "|".join(NUMBERS) + r")\s+(?P<unit>turns?|messages?|lines?|exchanges?))"
+    r"|(?:add|make|create)\s+a\s+bookmark)"
+    r"(?:\s+(?:conversation|exchange|bit|part|discussion|chat))?"
+    r"(?:\s*,?\s*(?:as|about|under|called|labell?ed|re|regarding|and\s+(?:call|label)\s+it|label\s+it)\s+"
+    r"[\"']?(?P<label>.+?)[\"']?)?[\s.!]*(?:,?\s*please)?[\s.!]*$",
+    re.IGNORECASE,
+)
+_QUERY = re.compile(
+    r"^\s*(?:what\s+(?:did|have)\s+i\s+(?:bookmark(?:ed)?|pin(?:ned)?|save[d]?)"
+    r"|(?:show|list|find|read|give)\s+(?:me\s+)?(?:my\s+|the\s+)?bookmarks?"
+    r"|what\s+(?:are\s+my|bookmarks\s+do\s+i\s+have)(?:\s+bookmarks)?)"
+    r"(?:\s+(?:about|on|for|regarding|re|called)\s+(?P<query>.+?))?\s*[?.!]*$",
+    re.IGNORECASE,
+)
+_WORD = re.compile(r"[a-z0-9']+")
+
+
+@dataclass
+class Bookmark:
+    label: str
+    turns: List[Dict[str, str]]  # [{"sender": "User", "text": "..."}, ...], oldest first
+    created_at: str = ""
+    persona: Optional[str] = None
+    id: str = field(default_factory=lambda: f"bm_{uuid.uuid4().hex[:8]}")
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Bookmark":
+        return cls(**{k: v for k, v in data.items() if k in cls.__dataclass_fields__})
+
+    def transcript(self, you: str = "You") -> List[str]:
+        """The turns as lines, the user's as `you`."""
+        return [f"{you if t['sender'].lower() == 'user' else t['sender']}: {t['text']}" for t in self.turns]
+
+
+def parse_bookmark_request(text: str) -> Optional[Tuple[int, str]]:
+    """(turns, label) for "remember this as X" and friends; the label is "" when none was given."""
+    match = _REQUEST.match(text or "")
+    if not match:
+        return None
+    count = match.group("count")
+    turns = DEFAULT_TURNS
+    if count:
+        turns = int(count) if count.isdigit() else NUMBERS[count.lower()]
+        if match.group("unit").lower().startswith("exchange"):
+            turns *= 2
+    label = re.sub(r"^(?:the|my|our)\s+", "", (match.group("label") or "").strip(), flags=re.IGNORECASE)
+    return max(1, turns), label
+
+
+def parse_bookmark_query(text: str) -> Optional[str]:
+    """The words to look for in "what did I bookmark about X?" ("" for every bookmark), or None."""
+    match = _QUERY.match(text or "")
+    return (match.group("query") or "").strip() if match else None
+
+
+def _stems(text: str) -> set:
+    words = (w.strip("'") for w in _WORD.findall(text.lower()))
+    return {re.sub(r"(?<=\w\w\w)(?:'s|s)$", "", w) for w in words if w and w not in STOPWORDS}
+
+
+# Recent turns, fed by the chat view
+_recent: Deque[Dict[str, str]] = deque(maxlen=RECENT_LIMIT)
+
+
+def note_turn(sender: str, text: str, replace_last: bool = False) -> None:
+    """A message shown in the chat; `replace_last` for a reply still streaming in."""
+    if sender.lower() in NOT_TURNS:
+        return
+    text = text.rstrip(" ▌").strip()
+    if replace_last and _recent and _recent[-1]["sender"] == sender:
+        _recent.pop()
+    if text and text != "...":
+        _recent.append({"sender": sender, "text": text})
+
+
+def recent_turns(count: int = DEFAULT_TURNS) -> List[Dict[str, str]]:
+    """The last `count` turns before the bookmark request (asking to bookmark isn't part of it)."""
+    turns = list(_recent)
+    while turns and turns[-1]["sender"].lower() == "user" and parse_bookmark_request(turns[-1]["text"]):
+        turns.pop()
+    return turns[-count:] if count > 0 else []
+
+
+def clear_recent() -> None:
+    _recent.clear()
+
+
+def default_label(turns: List[Dict[str, str]]) -> str:
+    """A label from what the user said last, for "remember this" without one."""
+    said = [t["text"] for t in turns if t["sender"].lower() == "user"] or [t["text"] for t in turns]
+    words = said[-1].split() if said else []
+    label = " ".join(words[:LABEL_WORDS]).rstrip("?.!,")
+    return label + ("…" if len(words) > LABEL_WORDS else "") if label else "conversation"
+
+
+class BookmarkStore:
+    """Pinned bookmarks, kept with the memory store."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "memory"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._bookmarks: Optional[List[Bookmark]] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "bookmarks.json"
+
+    def bookmarks(self) -> List[Bookmark]:
+        """Every bookmark, oldest first."""
+        if self._bookmarks is None:
+            self._bookmarks = []
+            path = self._path()
+            if path.exists():
+                try:
+                    raw = json.loads(path.read_text(encoding="utf-8"))
+                    self._bookmarks = [Bookmark.from_dict(b) for b in raw.get("bookmarks", [])]
+                except Exception as e:
+                    logger.warning(f"Failed to load bookmarks: {e}")
+        return self._bookmarks
+
+    def _save(self) -> None:
+        try:
+            self._path().write_text(json.dumps({"bookmarks": [asdict(b) for b in self.bookmarks()]},
+                                               indent=2, ensure_ascii=False), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save bookmarks: {e}")
+
+    def reload(self) -> None:
+        self._bookmarks = None
+
+    def add(self, label: str, turns: List[Dict[str, str]], persona: Optional[str] = None) -> Bookmark:
+        if not turns:
+            raise ValueError("There's nothing in the conversation to bookmark yet")
+        persona = persona or next((t["sender"] for t in turns if t["sender"].lower() != "user"), None)
+        bookmark = Bookmark(label.strip() or default_label(turns), [dict(t) for t in turns],
+                            datetime.now().isoformat(timespec="seconds"), persona)
+        self.bookmarks().append(bookmark)
+        self._save()
+        return bookmark
+
+    def find(self, query: str = "", limit: Optional[int] = None) -> List[Bookmark]:
+        """Bookmarks matching `query`'s words, best first (the label counts triple); all, newest first, for ""."""
+        wanted = _stems(query)
+        if not wanted:
+            found = self.bookmarks()[::-1]
+        else:
+            scored = []
+            for order, bookmark in enumerate(self.bookmarks()):
+                label = len(wanted & _stems(bookmark.label))
+                said = len(wanted & _stems(" ".join(t["text"] for t in bookmark.turns)))
+                if label or said:
+                    scored.append((3 * label + said, order, bookmark))
+            found = [b for _, _, b in sorted(scored, key=lambda s: (s[0], s[1]), reverse=True)]
+        return found[:limit] if limit else found
+
+    def remove(self, match: str) -> List[Bookmark]:
+        """Delete the bookmarks whose id or label contains `match`."""
+        needle = match.strip().lower()
+        if not needle:
+            return []
+        removed = [b for b in self.bookmarks() if needle in b.id or needle in b.label.lower()]
+        if removed:
+            self._bookmarks = [b for b in self.bookmarks() if b not in removed]
+            self._save()
+        return removed
+
+    def restore(self, bookmarks: List[Dict[str, Any]]) -> int:
+        """Put removed bookmarks back (undo); returns how many were missing."""
+        known = {b.id for b in self.bookmarks()}
+        back = [Bookmark.from_dict(b) for b in bookmarks if b.get("id") not in known]
+        if back:
+            self._bookmarks = sorted(self.bookmarks() + back, key=lambda b: b.created_at)
+            self._save()
+        return len(back)
+
+
+_store: Optional[BookmarkStore] = None
+
+
+def get_bookmark_store() -> BookmarkStore:
+    """Get the global bookmark store."""
+    global _store
+    if _store is None:
+        _store = BookmarkStore()
+    return _store
+
+
+def set_bookmark_store(store: Optional[BookmarkStore]) -> None:
+    global _store
+    _store = store
+
+
+register_capability("Bookmarks", "Keep part of our conversation under a label and find it again later",
+                    ["remember this as the contract terms", "what did I bookmark about the contract?"],
+                    requires=("memory_enabled",), keywords=("bookmark", "remember this", "pin", "label"))
@@ -0,0 +1,98 @@
+"""
+Buffers - Caps on what the dashboard keeps in memory, and how much it is.
+
+A session can run for weeks, so everything the dashboard accumulates is a
+ring buffer (a deque with a maxlen) that drops its oldest entries:
+
+    activity      activity feed messages
+    chat          chat transcript messages on screen (the full history
+                  is on disk, see chat_history.py)
+    chart         a chart's samples at full resolution (charts.History)
+    chart_minutes a chart's samples older than an hour, one per minute
+
+config.buffer_caps changes them, e.g. {"activity": 1000}. The Status tab
+shows what each holds and roughly how much memory that takes
+(buffer_usage), so a leak shows up as a number instead of a slow machine.
+"""
+
+import logging
+import sys
+from collections import deque
+from typing import Any, Dict, Iterable, List, Mapping, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+CAPS: Dict[str, int] = {"activity": 500, "chat": 1000, "chart": 720, "chart_minutes": 1440}
+
+
+def ring(cap: int, items: Iterable = ()) -> deque:
+    return deque(items, maxlen=max(1, cap))
+
+
+def approx_size(value: Any, _seen: Optional[set] = None) -> int:
+    """Bytes an object takes with everything it contains (strings, lists, dicts), roughly."""
+    seen = set() if _seen is None else _seen
+    if id(value) in seen:
+        return 0
+    seen.add(id(value))
+    size = sys.getsizeof(value)
+    if isinstance(value, dict):
+        size += sum(approx_size(key, seen) + approx_size(item, seen) for key, item in value.items())
+    elif isinstance(value, (list, tuple, set, frozenset, deque)):
+        size += sum(approx_size(item, seen) for item in value)
+    elif hasattr(value, "__dict__"):
+        size += approx_size(vars(value), seen)
+    return size
+
+
+def human_bytes(size: float) -> str:
+    for unit in ("B", "KB", "MB"):
+        if size < 1024:
+            return f"{size:.0f} {unit}"
+        size /= 1024
+    return f"{size:.1f} GB"
+
+
+def buffer_usage(buffers: Mapping[str, Tuple[Any, int]]) -> List[Tuple[str, int, int, int]]:
+    """(name, entries, cap, bytes) for each named (buffer, cap)."""
+    return [(name, len(buffer), cap, approx_size(buffer)) for name, (buffer, cap) in buffers.items()]
+
+
+def usage_line(rows: List[Tuple[str, int, int, int]]) -> str:
+    """The Status tab's line: feed 200/500 · chat 84/1000 · ~310 KB."""
+    parts = [f"{name} {count}/{cap}" for name, count, cap, _size in rows]
+    return " · ".join(parts + [f"~{human_bytes(sum(row[3] for row in rows))}"])
+
+
+class BufferCaps:
+    """The caps in CAPS, with config.buffer_caps on top."""
+
+    def __init__(self, overrides: Optional[Mapping[str, Any]] = None):
+        overrides = dict(overrides or {})
+        unknown = sorted(set(overrides) - set(CAPS))
+        if unknown:
+            logger.warning(f"Ignoring unknown buffer cap(s) in config.buffer_caps: {', '.join(unknown)}")
+        self.caps = {name: max(1, int(overrides.get(name, cap))) for name, cap in CAPS.items()}
+
+    @classmethod
+    def from_config(cls, config=None) -> "BufferCaps":
+        return cls(getattr(config, "buffer_caps", None))
+
+    def __getitem__(self, name: str) -> int:
+        return self.caps[name]
+
+
+_caps: Optional[BufferCaps] = None
+
+
+def get_buffer_caps() -> BufferCaps:
+    """The caps built from the loaded Config (the defaults until set_buffer_caps is called)."""
+    global _caps
+    if _caps is None:
+        _caps = BufferCaps()
+    return _caps
+
+
+def set_buffer_caps(caps: Optional[BufferCaps]) -> None:
+    global _caps
+    _caps = caps
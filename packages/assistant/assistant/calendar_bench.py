@@ -0,0 +1,225 @@
+"""
+Calendar Bench - Load benchmarks for the scheduling code, with baselines.
+
+`xswarm dev calendar-bench` generates synthetic calendars of SIZES events
+(RECURRING_SHARE of them repeating daily to yearly, some all-day or "show as
+free") and times the scans the planner runs on them:
+
+- expand_recurrence: every event expanded over a year (calendar_core.py)
+- find_conflicts: each event of every day checked against the rest of that
+  day, through an IntervalIndex built per day (calendar_core.py)
+- free_slots: the working-day gaps of every day (evening_review.py)
+
+Each is run `repeat` times on the same calendar and the best time kept.
+Between sizes the report shows the growth exponent: time ~ n^k, so k near 1
+is linear and near 2 quadratic - the number to watch as the scans change.
+
+`--save FILE` writes the results as a JSON baseline and `--compare FILE`
+checks a run against one: the command exits 1 when any benchmark is more
+than `--max-regression` (default 25%) slower at a size both have. Timings
+depend on the machine, so compare against a baseline saved on the same one.
+The calendars come from a fixed seed, so every run times the same work.
+"""
+
+import json
+import math
+import random
+import time
+from collections import defaultdict
+from dataclasses import asdict, dataclass, field
+from datetime import date, datetime, timedelta
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional, Sequence
+
+from .calendar_core import IntervalIndex, expand_recurrence, find_conflicts
+
+SIZES = (100, 500, 2000)
+REPEAT = 3
+RECURRING_SHARE = 0.4
+RECURRENCES = ("daily", "weekly", "weekly", "biweekly", "monthly", "yearly")
+WINDOW_DAYS = 365  # Expanded over, from START
+START = date(2026, 1, 5)
+WORK_START, WORK_END = "08:00", "18:00"
+MAX_REGRESSION = 0.25
+
+
+class BenchError(ValueError):
+    """An unknown benchmark or an unreadable baseline."""
+
+
+@dataclass
+class BenchResult:
+    name: str
+    size: int  # Events in the calendar
+    seconds: float  # Best of the runs
+    items: int  # Occurrences, events checked or days handled
+
+    @property
+    def per_item_us(self) -> float:
+        return self.seconds / max(self.items, 1) * 1e6
+
+
+@dataclass
+class BenchReport:
+    results: List[BenchResult] = field(default_factory=list)
+    regressions: List[str] = field(default_factory=list)  # Set by compare()
+
+    def result(self, name: str, size: int) -> Optional[BenchResult]:
+        return next((r for r in self.results if r.name == name and r.size == size), None)
+
+    def exponents(self, name: str) -> List[float]:
+        """The growth exponent k (time ~ n^k) between each size and the next."""
+        runs = sorted((r for r in self.results if r.name == name), key=lambda r: r.size)
+        return [math.log(b.seconds / a.seconds) / math.log(b.size / a.size)
+                for a, b in zip(runs, runs[1:]) if a.seconds > 0 and b.seconds > 0 and b.size > a.size]
+
+    def compare(self, baseline: "BenchReport", max_regression: float = MAX_REGRESSION) -> List[str]:
+        """The benchmarks slower than `baseline` by more than `max_regression`, as lines to print."""
+        self.regressions = []
+        for result in self.results:
+            before = baseline.result(result.name, result.size)
+            if before and before.seconds > 0 and result.seconds > before.seconds * (1 + max_regression):
+                self.regressions.append(f"{result.name} at {result.size}: {before.seconds * 1000:.1f} ms -> "
+                                        f"{result.seconds * 1000:.1f} ms ({result.seconds / before.seconds - 1:+.0%})")
+        return self.regressions
+
+    def lines(self, baseline: Optional["BenchReport"] = None) -> List[str]:
+        lines = []
+        for name in dict.fromkeys(r.name for r in self.results):
+            lines.append(name)
+            for result in (r for r in self.results if r.name == name):
+                before = baseline.result(name, result.size) if baseline else None
+                change = (f"  ({result.seconds / before.seconds - 1:+.0%} vs baseline)"
+                          if before and before.seconds > 0 else "")
+                lines.append(f"  {result.size:>6} events  {result.seconds * 1000:9.1f} ms  {result.items:>9} items  "
+                             f"{result.per_item_us:8.2f} µs/item{change}")
+            exponents = self.exponents(name)
+            if exponents:
+                lines.append(f"  growth: n^{' then n^'.join(f'{k:.2f}' for k in exponents)}")
+        return lines
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"results": [asdict(r) for r in self.results], "regressions": self.regressions}
+
+    def save(self, path: Path) -> None:
+        path.write_text(json.dumps(self.to_dict(), indent=2), encoding="utf-8")
+
+    @classmethod
+    def load(cls, path: Path) -> "BenchReport":
+        try:
+            raw = json.loads(path.read_text(encoding="utf-8"))
+            return cls([BenchResult(**r) for r in raw["results"]])
+        except (OSError, ValueError, KeyError, TypeError) as e:
+            raise BenchError(f"Can't read the baseline {path}: {e}")
+
+
+def synthetic_events(count: int, seed: int = 0, start: date = START) -> List[Dict[str, Any]]:
+    """`count` calendar events over the 90 days from `start`, the same ones for the same seed."""
+    rng = random.Random(seed)
+    events = []
+    for i in range(count):
+        day = start + timedelta(days=rng.randrange(90))
+        event = {"id": f"bench-{i}", "title": f"Event {i}", "recurrence": "none", "recurrence_end": None,
+                 "busy": rng.random() > 0.1}
+        if rng.random() < 0.05:
+            event.update(start_time=day.isoformat(), end_time=day.isoformat())  # All day
+        else:
+            begin = datetime.combine(day, datetime.min.time()) + timedelta(minutes=rng.randrange(7 * 4, 20 * 4) * 15)
+            event.update(start_time=begin.isoformat(),
+                         end_time=(begin + timedelta(minutes=rng.choice((15, 30, 30, 45, 60, 90, 120)))).isoformat())
+        if rng.random() < RECURRING_SHARE:
+            event["recurrence"] = rng.choice(RECURRENCES)
+            if rng.random() < 0.5:
+                event["recurrence_end"] = (day + timedelta(days=rng.randrange(30, WINDOW_DAYS))).isoformat()
+        events.append(event)
+    return events
+
+
+def _occurrences(events: List[Dict[str, Any]], start: date = START) -> List[Dict[str, Any]]:
+    end = (start + timedelta(days=WINDOW_DAYS)).isoformat()
+    occurrences = list(events)
+    for event in events:
+        occurrences += expand_recurrence(event, start.isoformat(), end)
+    return occurrences
+
+
+def _by_day(events: List[Dict[str, Any]]) -> Dict[str, List[Dict[str, Any]]]:
+    days = defaultdict(list)
+    for event in _occurrences(events):
+        days[event["start_time"][:10]].append(event)
+    return days
+
+
+class _DayPlanner:
+    """The two PlannerData reads free_slots() makes, from prepared days."""
+
+    def __init__(self, days: Dict[str, List[Dict[str, Any]]]):
+        from .planner import CalendarEvent
+        self.days = {day: [CalendarEvent(**{k: v for k, v in e.items() if not k.startswith("_")}) for e in events]
+                     for day, events in days.items()}
+
+    def get_calendar_events(self, start_date: str, end_date: str):
+        return self.days.get(start_date, [])
+
+    def get_habits(self):
+        return []
+
+
+def _bench_expand(events: List[Dict[str, Any]]) -> Callable[[], int]:
+    return lambda: len(_occurrences(events))
+
+
+def _bench_conflicts(events: List[Dict[str, Any]]) -> Callable[[], int]:
+    days = _by_day(events)
+
+    def run() -> int:
+        checks = 0
+        for day_events in days.values():
+            index = IntervalIndex(day_events)
+            for event in day_events:
+                find_conflicts(event, index)
+                checks += 1
+        return checks
+    return run
+
+
+def _bench_free_slots(events: List[Dict[str, Any]]) -> Callable[[], int]:
+    from .evening_review import free_slots
+    planner = _DayPlanner(_by_day(events))
+
+    def run() -> int:
+        for day in planner.days:
+            free_slots(planner, date.fromisoformat(day), WORK_START, WORK_END)
+        return len(planner.days)
+    return run
+
+
+# Each prepares its input outside the timing and returns what's timed
+BENCHMARKS: Dict[str, Callable[[List[Dict[str, Any]]], Callable[[], int]]] = {
+    "expand_recurrence": _bench_expand,
+    "find_conflicts": _bench_conflicts,
+    "free_slots": _bench_free_slots,
+}
+
+
+def run_benchmarks(sizes: Sequence[int] = SIZES, repeat: int = REPEAT, names: Optional[Sequence[str]] = None,
+                   on_result: Optional[Callable[[BenchResult], None]] = None) -> BenchReport:
+    """Time each benchmark (all, or `names`) on a calendar of each size."""
+    names = list(names or BENCHMARKS)
+    unknown = [n for n in names if n not in BENCHMARKS]
+    if unknown:
+        raise BenchError(f"Unknown benchmark '{unknown[0]}' (use {', '.join(BENCHMARKS)})")
+    report = BenchReport()
+    for size in sorted(sizes):
+        events = synthetic_events(size)
+        for name in names:
+            timed = BENCHMARKS[name](events)
+            best, items = math.inf, 0
+            for _ in range(max(repeat, 1)):
+                began = time.perf_counter()
+                items = timed()
+                best = min(best, time.perf_counter() - began)
+            report.results.append(BenchResult(name, size, best, items))
+            if on_result:
+                on_result(report.results[-1])
+    return report
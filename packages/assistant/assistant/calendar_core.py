@@ -0,0 +1,301 @@
+"""
+Calendar Core - The pure scheduling logic, reusable outside the assistant.
+
+Recurrence expansion and conflict detection (with an interval index for
+checking many events against the same calendar) live here, and date parsing
+in dates.py. Neither touches files, the network or config, and neither imports
+anything beyond the standard library, so the same code runs in a browser
+under Pyodide (Python compiled to WebAssembly):
+
+    xswarm dev core-bundle xswarm_core.zip
+
+zips CORE_MODULES into an `xswarm_core` package that a web page loads with
+pyodide.unpackArchive(), then `from xswarm_core import calendar_core, dates`.
+check_core() refuses to bundle a module that has picked up another import.
+
+Parity comes from shared cases: tests/fixtures/core/*.json hold inputs and
+expected outputs, run here by tests/assistant/test_calendar_core.py; a web
+dashboard runs the same files against the bundle.
+
+Events are dicts with the CalendarEvent fields (start_time/end_time as
+naive ISO datetimes, recurrence, recurrence_end, busy, exceptions,
+skip_holidays);
+CalendarEvent objects work too wherever only fields are read.
+
+A recurring event's exceptions change single occurrences, keyed by the
+date each was due: {"2026-10-20": {"cancelled": True}} skips one and
+{"2026-10-27": {"start_time": "2026-10-28T10:00:00"}} moves one (any of
+OCCURRENCE_FIELDS can change). Each occurrence has its own id,
+"<series id>@<date due>", which is how one is edited without the rest.
+
+An event's visibility says what integrations outside the assistant (the
+local API, MCP clients, group scheduling) see of it: "public" everything,
+"busy" only the time it takes, as "Busy", and "private" nothing at all.
+Events without one take the user's default, and an unknown value is "busy".
+"""
+
+import ast
+import re
+import sys
+from bisect import bisect_left
+from datetime import date, datetime, timedelta
+from pathlib import Path
+from typing import Any, Collection, Dict, Iterable, List, Optional, Tuple, Union
+
+from .dates import add_months, add_years
+
+CORE_MODULES = ("dates", "calendar_core")
+MAX_OCCURRENCES = 1000  # Safety limit when expanding one event
+RECURRENCE_STEPS = {"daily": timedelta(days=1), "weekly": timedelta(weeks=1), "biweekly": timedelta(weeks=2)}
+# What an exception can change about one occurrence
+OCCURRENCE_FIELDS = ("title", "start_time", "end_time", "description", "location", "attendees", "busy",
+                     "reminder_minutes", "tags", "visibility")
+VISIBILITIES = ("public", "busy", "private")
+BUSY_TITLE = "Busy"
+# All a "busy" event shares
+BUSY_FIELDS = ("id", "start_time", "end_time", "busy", "visibility")
+_OCCURRENCE_ID = re.compile(r"^(?P<series>.+)@(?P<day>\d{4}-\d{2}-\d{2})$")
+
+
+def _field(event: Any, name: str, default: Any = None) -> Any:
+    return event.get(name, default) if isinstance(event, dict) else getattr(event, name, default)
+
+
+# ==============================================================================
+# RECURRENCE
+# ==============================================================================
+
+def occurrence_id(series_id: str, day: str) -> str:
+    """The id of a recurring event's occurrence due on `day` (YYYY-MM-DD)."""
+    return f"{series_id}@{day}"
+
+
+def split_occurrence_id(event_id: str) -> Tuple[str, Optional[str]]:
+    """(series id, date due) for an occurrence id; (event_id, None) for any other id."""
+    match = _OCCURRENCE_ID.match(event_id or "")
+    return (match.group("series"), match.group("day")) if match else (event_id, None)
+
+
+def apply_exception(occurrence: Dict[str, Any], exception: Optional[Dict[str, Any]]) -> Optional[Dict[str, Any]]:
+    """An occurrence with its exception's changes, or None when the exception cancels it."""
+    if not exception:
+        return occurrence
+    if exception.get("cancelled"):
+        return None
+    return {**occurrence, **{k: v for k, v in exception.items() if k in OCCURRENCE_FIELDS}}
+
+
+def expand_recurrence(event: Dict[str, Any], start_date: str, end_date: str,
+                      holidays: Collection[str] = ()) -> List[Dict[str, Any]]:
+    """
+    The occurrences of a recurring event between two dates (YYYY-MM-DD,
+    inclusive), not counting the original. Each is a copy of the event with
+    its own id and start/end, marked _is_recurring_instance with
+    _original_id, and changed by its exception: cancelled ones are left
+    out and moved ones count on the day they moved to. Without
+    recurrence_end, an event repeats for a year. An event with
+    skip_holidays leaves out the occurrences due on `holidays` (YYYY-MM-DD
+    days off) that have no exception.
+    """
+    recurrence = event.get("recurrence", "none")
+    if recurrence not in RECURRENCE_STEPS and recurrence not in ("monthly", "yearly"):
+        return []
+
+    event_start = datetime.fromisoformat(event["start_time"])
+    duration = datetime.fromisoformat(event["end_time"]) - event_start
+    recurrence_end = (date.fromisoformat(event["recurrence_end"]) if event.get("recurrence_end")
+                      else add_years(event_start.date(), 1))
+    query_start, query_end = date.fromisoformat(start_date), date.fromisoformat(end_date)
+    exceptions = event.get("exceptions") or {}
+    skipped = holidays if event.get("skip_holidays") else ()
+    # An occurrence due after the range may have been moved into it
+    last_due = max([query_end, *(date.fromisoformat(day) for day in exceptions)])
+
+    instances = []
+    current = event_start
+    for iteration in range(1, MAX_OCCURRENCES + 1):
+        current_date = current.date()
+        if current_date > recurrence_end or current_date > last_due:
+            break
+        due = current_date.isoformat()
+        if current_date != event_start.date() and (query_start <= current_date <= query_end or due in exceptions) \
+                and (due not in skipped or due in exceptions):
+            instance = event.copy()
+            instance["id"] = occurrence_id(event["id"], due)
+            instance["start_time"] = current.isoformat()
+            instance["end_time"] = (current + duration).isoformat()
+            instance["_is_recurring_instance"] = True
+            instance["_original_id"] = event["id"]
+            instance = apply_exception(instance, exceptions.get(due))
+            if instance is not None and query_start <= date.fromisoformat(instance["start_time"][:10]) <= query_end:
+                instances.append(instance)
+
+        if recurrence in RECURRENCE_STEPS:
+            current = current + RECURRENCE_STEPS[recurrence]
+        elif recurrence == "monthly":
+            # Counted from the first occurrence so short months don't drift the day:
+            # Jan 31 -> Feb 28 -> Mar 31
+            current = add_months(event_start, iteration)
+        else:
+            # Feb 29 -> Feb 28 outside leap years, back to Feb 29 when there is one
+            current = add_years(event_start, iteration)
+    return sorted(instances, key=lambda instance: instance["start_time"]) if exceptions else instances
+
+
+# ==============================================================================
+# CONFLICTS
+# ==============================================================================
+
+def overlaps(a_start: str, a_end: str, b_start: str, b_end: str) -> bool:
+    """Two time ranges share some time (touching end to start doesn't count)."""
+    return (datetime.fromisoformat(a_start) < datetime.fromisoformat(b_end)
+            and datetime.fromisoformat(b_start) < datetime.fromisoformat(a_end))
+
+
+def _blocks_time(event: Any) -> bool:
+    """Busy and timed: all-day events (a date, no time) and "show as free" ones never conflict."""
+    return bool(_field(event, "busy", True)) and "T" in _field(event, "start_time", "")
+
+
+class IntervalIndex:
+    """
+    The time-blocking events of a calendar, indexed for overlap queries: sorted
+    by start, with the latest end under each node of an implicit binary tree
+    (an interval tree over the sorted array). A query costs O(log n + matches)
+    instead of a pass over every event, so build one to check many events
+    against the same calendar and pass it to find_conflicts() for the list.
+    """
+
+    def __init__(self, events: Iterable[Any]):
+        entries = sorted(((datetime.fromisoformat(_field(e, "start_time")),
+                           datetime.fromisoformat(_field(e, "end_time")), i, e)
+                          for i, e in enumerate(events) if _blocks_time(e)), key=lambda entry: (entry[0], entry[2]))
+        self._starts = [entry[0] for entry in entries]
+        self._events = [entry[3] for entry in entries]
+        self._leaves = 1
+        while self._leaves < len(entries):
+            self._leaves *= 2
+        self._max_end = [datetime.min] * (2 * self._leaves)  # Node k's children are 2k and 2k+1; leaves from _leaves
+        for i, entry in enumerate(entries):
+            self._max_end[self._leaves + i] = entry[1]
+        for node in range(self._leaves - 1, 0, -1):
+            self._max_end[node] = max(self._max_end[2 * node], self._max_end[2 * node + 1])
+
+    def __len__(self) -> int:
+        return len(self._events)
+
+    def overlapping(self, start: datetime, end: datetime) -> List[Any]:
+        """The events sharing some time with start-end (touching doesn't count), by start time."""
+        before_end = bisect_left(self._starts, end)  # Only events starting before `end` can overlap
+        found = []
+        stack = [(1, 0, self._leaves)]
+        while stack:
+            node, low, high = stack.pop()
+            if low >= before_end or self._max_end[node] <= start:
+                continue  # Nothing under this node starts early enough and ends late enough
+            if node >= self._leaves:
+                found.append(self._events[low])
+                continue
+            middle = (low + high) // 2
+            stack.append((2 * node + 1, middle, high))
+            stack.append((2 * node, low, middle))  # Left first, so matches come out in start order
+        return found
+
+
+def find_conflicts(event: Any, others: Union[List[Any], IntervalIndex]) -> List[Any]:
+    """
+    The busy events among `others` that overlap `event`, by start time.
+    All-day events (a date, no time), "show as free" events and the event
+    itself (same id) never conflict. `others` can be an IntervalIndex of
+    them, built once to check many events against.
+    """
+    if not _blocks_time(event):
+        return []
+    start, end = datetime.fromisoformat(_field(event, "start_time")), datetime.fromisoformat(_field(event, "end_time"))
+    if isinstance(others, IntervalIndex):
+        candidates = others.overlapping(start, end)
+    else:  # One query: a single pass is cheaper than building the index
+        candidates = [other for other in others if _blocks_time(other)
+                      and datetime.fromisoformat(_field(other, "start_time")) < end
+                      and start < datetime.fromisoformat(_field(other, "end_time"))]
+    conflicts = [other for other in candidates if _field(other, "id") != _field(event, "id")]
+    return sorted(conflicts, key=lambda other: _field(other, "start_time"))
+
+
+# ==============================================================================
+# SHARING
+# ==============================================================================
+
+def visibility(event: Any, default: str = "public") -> str:
+    """The event's visibility, or `default` without one; anything unknown is "busy"."""
+    value = str(_field(event, "visibility", "") or default).strip().lower()
+    return value if value in VISIBILITIES else "busy"
+
+
+def shared_view(event: Dict[str, Any], default: str = "public") -> Optional[Dict[str, Any]]:
+    """What an outside integration may see of `event`: all of it, its time as "Busy", or None (private)."""
+    level = visibility(event, default)
+    if level == "private":
+        return None
+    if level == "public":
+        return dict(event)
+    return {**{name: event[name] for name in BUSY_FIELDS if name in event}, "title": BUSY_TITLE}
+
+
+def busy_times(events: Iterable[Any], default: str = "public") -> List[Tuple[str, str]]:
+    """
+    The (start, end) stretches the non-private busy, timed events take,
+    overlapping and touching ones merged, by start time: free/busy without
+    a word about what any of it is.
+    """
+    blocks: List[List[datetime]] = []
+    for event in sorted((e for e in events if _blocks_time(e) and visibility(e, default) != "private"),
+                        key=lambda e: datetime.fromisoformat(_field(e, "start_time"))):
+        start = datetime.fromisoformat(_field(event, "start_time"))
+        end = datetime.fromisoformat(_field(event, "end_time"))
+        if blocks and start <= blocks[-1][1]:
+            blocks[-1][1] = max(blocks[-1][1], end)
+        else:
+            blocks.append([start, end])
+    return [(start.isoformat(), end.isoformat()) for start, end in blocks]
+
+
+# ==============================================================================
+# BUNDLE
+# ==============================================================================
+
+def check_core(directory: Path = Path(__file__).parent) -> List[str]:
+    """Imports in CORE_MODULES that aren't the standard library or each other (empty when it's all pure)."""
+    problems = []
+    for name in CORE_MODULES:
+        tree = ast.parse((directory / f"{name}.py").read_text(encoding="utf-8"))
+        for node in ast.walk(tree):
+            if isinstance(node, ast.Import):
+                modules = [alias.name for alias in node.names]
+            elif isinstance(node, ast.ImportFrom):
+                if node.level:
+                    if (node.module or "").split(".")[0] not in CORE_MODULES:
+                        problems.append(f"{name}.py: from {'.' * node.level}{node.module or ''} import ...")
+                    continue
+                modules = [node.module or ""]
+            else:
+                continue
+            problems.extend(f"{name}.py: import {module}" for module in modules
+                            if module.split(".")[0] not in sys.stdlib_module_names)
+    return problems
+
+
+def write_bundle(path: Path, directory: Path = Path(__file__).parent) -> List[str]:
+    """Zip CORE_MODULES as the `xswarm_core` package for Pyodide; returns the files written."""
+    import zipfile
+
+    problems = check_core(directory)
+    if problems:
+        raise ValueError("Not pure: " + "; ".join(problems))
+    names = ["xswarm_core/__init__.py"]
+    with zipfile.ZipFile(path, "w", zipfile.ZIP_DEFLATED) as bundle:
+        bundle.writestr(names[0], '"""xSwarm calendar core (dates, calendar_core), built by `xswarm dev core-bundle`."""\n')
+        for name in CORE_MODULES:
+            names.append(f"xswarm_core/{name}.py")
+            bundle.write(directory / f"{name}.py", names[-1])
+    return names
@@ -0,0 +1,142 @@
+"""
+Calendar Live - React to server calendar changes as they happen.
+
+The calendar_sync job pulls the server calendar every 15 minutes. With
+config.calendar_live on (the default) the dashboard also keeps a WebSocket
+open to the server's /api/calendar/subscribe. Whenever the user's
+appointments or reminders change there - created from the phone, another
+computer, a text - the server sends a hint, the change is pulled into the
+calendar mirror (calendar_mirror.py), and the Today panel and reminder
+checks see it within about a second.
+
+Hints only say that something changed: several arriving together cause one
+pull, and whatever was missed while disconnected is pulled right after
+reconnecting (reconnects back off from 1 to 60 seconds). A server with
+subscriptions turned off answers 503, and the dashboard keeps polling.
+Other hints on the same socket (e.g. {"type": "command"}, see
+remote_commands.py) go to on_hint.
+"""
+
+import asyncio
+import json
+import logging
+from typing import Any, Awaitable, Callable, Dict, Optional
+from urllib.parse import urlencode
+
+from . import endpoints
+from .supervisor import get_task_supervisor
+
+logger = logging.getLogger(__name__)
+
+MIN_BACKOFF = 1.0
+MAX_BACKOFF = 60.0
+DISABLED_STATUSES = {404, 426, 503}  # The server doesn't offer subscriptions
+
+
+def _status(error: Exception) -> Optional[int]:
+    """HTTP status of a refused WebSocket handshake (old and new websockets APIs)."""
+    status = getattr(error, "status_code", None)
+    if status is None:
+        status = getattr(getattr(error, "response", None), "status_code", None)
+    return status
+
+
+def _websocket_connect(url: str, headers: dict):
+    import websockets
+    if int(websockets.__version__.split(".")[0]) >= 14:
+        return websockets.connect(url, additional_headers=headers)
+    return websockets.connect(url, extra_headers=headers)
+
+
+class CalendarSubscription:
+    """Calls on_change (once per burst of changes) while subscribed to the server calendar."""
+
+    def __init__(self, server_url: str, user_id: str, on_change: Callable[[], Awaitable[None]],
+                 api_token: Optional[str] = None, connect=_websocket_connect, sleep=asyncio.sleep,
+                 on_hint: Optional[Callable[[Dict[str, Any]], None]] = None):
+        self.server_url = server_url.rstrip("/")
+        self.user_id = user_id
+        self.on_change = on_change
+        self.api_token = api_token
+        self.connect = connect
+        self.sleep = sleep
+        self.on_hint = on_hint  # Called with hints other than calendar changes
+        self.connected = False
+        self.disabled = False  # The server turned subscriptions down
+        self.changes = 0  # Hints received
+        self._changed = asyncio.Event()
+        self._task: Optional[asyncio.Task] = None
+
+    @classmethod
+    def from_config(cls, config, user_id: str, on_change, on_hint=None) -> "CalendarSubscription":
+        return cls(getattr(config, "server_url", "http://localhost:3000"), user_id, on_change,
+                   getattr(config, "api_token", None), on_hint=on_hint)
+
+    @property
+    def url(self) -> str:
+        base = self.server_url.replace("https://", "wss://", 1).replace("http://", "ws://", 1)
+        return f"{base}{endpoints.CALENDAR_SUBSCRIBE.path()}?{urlencode({'user_id': self.user_id})}"
+
+    def handle(self, message) -> None:
+        """A message from the server."""
+        try:
+            data = json.loads(message)
+        except (TypeError, ValueError):
+            return  # "pong" and the like
+        if not isinstance(data, dict):
+            return
+        if data.get("type") == "calendar_change":
+            self.changes += 1
+            self._changed.set()
+        elif data.get("type") != "subscribed" and self.on_hint is not None:
+            self.on_hint(data)
+
+    async def _listen(self) -> None:
+        delay = MIN_BACKOFF
+        headers = {"Authorization": f"Bearer {self.api_token}"} if self.api_token else {}
+        while True:
+            try:
+                async with self.connect(self.url, headers) as socket:
+                    self.connected, delay = True, MIN_BACKOFF
+                    self._changed.set()  # Catch up on anything missed while disconnected
+                    async for message in socket:
+                        self.handle(message)
+            except asyncio.CancelledError:
+                raise
+            except Exception as e:
+                if _status(e) in DISABLED_STATUSES:
+                    logger.info("Server doesn't offer calendar subscriptions; polling instead")
+                    self.disabled = True
+                    return
+                logger.debug(f"Calendar subscription dropped: {e}")
+            finally:
+                self.connected = False
+            await self.sleep(delay)
+            delay = min(delay * 2, MAX_BACKOFF)
+
+    async def _deliver(self) -> None:
+        while True:
+            await self._changed.wait()
+            self._changed.clear()  # Hints arriving during the pull cause one more
+            try:
+                await self.on_change()
+            except Exception as e:
+                logger.warning(f"Handling a calendar change failed: {e}")
+
+    async def run(self) -> None:
+        """Subscribe until cancelled (or until the server says it doesn't offer subscriptions)."""
+        deliver = asyncio.ensure_future(self._deliver())
+        try:
+            await self._listen()
+        finally:
+            deliver.cancel()
+
+    def start(self) -> None:
+        """Subscribe in the background (supervised, restarted if it fails)."""
+        if self._task is None:
+            self._task = get_task_supervisor().spawn("calendar subscription", self.run)
+
+    def stop(self) -> None:
+        if self._task is not None:
+            self._task.cancel()
+            self._task = None
@@ -0,0 +1,212 @@
+"""
+Calendar Mirror - A local copy of the server's appointments and reminders.
+
+The server journals every appointment and reminder write (its
+calendar_changes table, GET /api/calendar/changes). The mirror keeps the
+position it has applied up to (its cursor) and pull() asks only for what
+changed after it, a page at a time. Each page is applied together with the
+new cursor in one SQLite transaction, so an interrupted pull resumes from
+the last whole page and never leaves half of one behind.
+
+Because the copy is on disk, startup reads it straight away instead of
+downloading the calendar, and it keeps answering while the server is
+unreachable; the next pull catches up on everything missed. A cursor the
+server doesn't recognize (a reset or restored server database) empties the
+mirror and starts over from 0.
+
+Pulled by the calendar_sync job after it pushes the planner calendar, and
+as soon as the server reports a change (calendar_live.py).
+
+Storage: ~/.xswarm/calendar_sync/mirror.db (format upgrades in MIGRATIONS)
+"""
+
+import json
+import logging
+import sqlite3
+import threading
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple, Union
+
+from .migrations import Migration, migrate_sqlite
+
+logger = logging.getLogger(__name__)
+
+PAGE_SIZE = 500
+MAX_PAGES = 40  # Per pull; the rest waits for the next one
+
+_SCHEMA = """
+CREATE TABLE IF NOT EXISTS appointments (
+    id TEXT PRIMARY KEY,
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    status TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS reminders (
+    id TEXT PRIMARY KEY,
+    due_time TEXT,
+    completed INTEGER NOT NULL DEFAULT 0,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT);
+"""
+
+# Format changes to mirror.db, oldest first (see migrations.py)
+MIGRATIONS = [
+    Migration(1, "appointments, reminders and meta tables", lambda db: db.executescript(_SCHEMA)),
+]
+
+
+@dataclass
+class PullReport:
+    """What a pull() applied."""
+    updated: int = 0  # Appointments/reminders added or changed
+    deleted: int = 0
+    pages: int = 0
+    reset: bool = False  # The server didn't know the cursor; the mirror started over
+    complete: bool = True  # False when MAX_PAGES ran out before catching up
+
+    def summary(self) -> str:
+        if not (self.updated or self.deleted or self.reset):
+            return "Calendar mirror up to date"
+        line = f"Calendar mirror: {self.updated} updated, {self.deleted} removed"
+        if self.reset:
+            line += " (resynced from scratch)"
+        return line
+
+
+class CalendarMirror:
+    """SQLite mirror of one user's server calendar, updated by delta pulls."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "calendar_sync" / "mirror.db"
+
+    def __init__(self, client=None, user_id: str = "default-user", path: Union[Path, str, None] = None):
+        self.client = client  # SchedulerClient; only needed to pull
+        self.user_id = user_id
+        self.path = path or self.DEFAULT_PATH
+        if self.path != ":memory:":
+            Path(self.path).parent.mkdir(parents=True, exist_ok=True)
+        self.revision = 0  # Bumped whenever the appointments may have changed (appointment_cache.py)
+        self._lock = threading.Lock()
+        self._db = sqlite3.connect(str(self.path), check_same_thread=False)
+        try:
+            self.migrated = migrate_sqlite(self._db, self.path, "calendar mirror", MIGRATIONS)
+        except Exception:
+            self._db.close()
+            raise
+
+    def close(self) -> None:
+        self._db.close()
+
+    # --------------------------------------------------------------------------
+    # Cursor
+    # --------------------------------------------------------------------------
+
+    def _meta(self, key: str) -> Optional[str]:
+        row = self._db.execute("SELECT value FROM meta WHERE key = ?", (key,)).fetchone()
+        return row[0] if row else None
+
+    def _set_meta(self, key: str, value: str) -> None:
+        self._db.execute("INSERT OR REPLACE INTO meta (key, value) VALUES (?, ?)", (key, value))
+
+    @property
+    def cursor(self) -> int:
+        """The last journal position applied (0 before the first pull)."""
+        with self._lock:
+            return int(self._meta("cursor") or 0)
+
+    # --------------------------------------------------------------------------
+    # Pulling
+    # --------------------------------------------------------------------------
+
+    async def pull(self, max_pages: int = MAX_PAGES) -> PullReport:
+        """Apply the server's changes since the cursor. Raises ApiError when the server can't be reached."""
+        report = PullReport(complete=False)
+        for _ in range(max_pages):
+            page = await self.client.changes(self.user_id, since=self.cursor, limit=PAGE_SIZE)
+            if page.get("reset"):
+                if report.reset:
+                    raise RuntimeError("Server reset the calendar cursor twice in one pull")
+                logger.info("Server didn't recognize the calendar cursor; resyncing the mirror")
+                self.clear()
+                report.reset = True
+                continue
+            updated, deleted = self.apply(page.get("changes", []), int(page.get("cursor", self.cursor)))
+            report.updated += updated
+            report.deleted += deleted
+            report.pages += 1
+            if not page.get("has_more"):
+                report.complete = True
+                break
+        return report
+
+    def apply(self, changes: List[Dict[str, Any]], cursor: int) -> Tuple[int, int]:
+        """Apply one page of changes and move the cursor, all or nothing. Returns (updated, deleted)."""
+        updated = deleted = 0
+        with self._lock, self._db:  # One transaction: committed together or rolled back
+            for change in changes:
+                table = {"appointment": "appointments", "reminder": "reminders"}.get(change.get("entity"))
+                if table is None:
+                    continue  # An entity this version doesn't mirror
+                if change.get("op") == "delete":
+                    deleted += self._db.execute(f"DELETE FROM {table} WHERE id = ?", (change["id"],)).rowcount
+                    continue
+                data = change["data"]
+                if table == "appointments":
+                    self._db.execute(
+                        "INSERT OR REPLACE INTO appointments (id, start_time, end_time, status, data) VALUES (?, ?, ?, ?, ?)",
+                        (change["id"], data.get("start_time") or "", data.get("end_time") or "",
+                         data.get("status") or "scheduled", json.dumps(data)))
+                else:
+                    self._db.execute(
+                        "INSERT OR REPLACE INTO reminders (id, due_time, completed, data) VALUES (?, ?, ?, ?)",
+                        (change["id"], data.get("due_time"), int(bool(data.get("completed"))), json.dumps(data)))
+                updated += 1
+            self._set_meta("cursor", str(cursor))
+        if updated or deleted:
+            self.revision += 1
+        return updated, deleted
+
+    def clear(self) -> None:
+        """Forget everything, cursor included."""
+        with self._lock, self._db:
+            self._db.execute("DELETE FROM appointments")
+            self._db.execute("DELETE FROM reminders")
+            self._db.execute("DELETE FROM meta WHERE key = 'cursor'")
+        self.revision += 1
+
+    # --------------------------------------------------------------------------
+    # Reading
+    # --------------------------------------------------------------------------
+
+    def appointments(self, start: Optional[str] = None, end: Optional[str] = None,
+                     status: Optional[str] = "scheduled") -> List[Dict[str, Any]]:
+        """Mirrored appointments starting in [start, end) (ISO times, either optional), by start time."""
+        where, args = [], []
+        # datetime() compares times with different UTC offsets correctly, like the server's queries
+        if start:
+            where.append("datetime(start_time) >= datetime(?)")
+            args.append(start)
+        if end:
+            where.append("datetime(start_time) < datetime(?)")
+            args.append(end)
+        if status:
+            where.append("status = ?")
+            args.append(status)
+        sql = "SELECT data FROM appointments"
+        if where:
+            sql += " WHERE " + " AND ".join(where)
+        with self._lock:
+            rows = self._db.execute(sql + " ORDER BY datetime(start_time)", args).fetchall()
+        return [json.loads(data) for data, in rows]
+
+    def reminders(self, completed: Optional[bool] = False) -> List[Dict[str, Any]]:
+        """Mirrored reminders (all of them when completed is None), by due time."""
+        sql, args = "SELECT data FROM reminders", []
+        if completed is not None:
+            sql += " WHERE completed = ?"
+            args.append(int(completed))
+        with self._lock:
+            rows = self._db.execute(sql + " ORDER BY due_time IS NULL, due_time", args).fetchall()
+        return [json.loads(data) for data, in rows]
@@ -0,0 +1,247 @@
+"""
+Call Screening - Watch screened calls live and decide what happens to them.
+
+Calls to the Boss number from unknown callers are answered by the server,
+which asks the caller what the call is about and streams the transcription
+into its `screened_calls` table. This module polls those calls for the
+dashboard and sends the user's decision back:
+
+- accept:    forward the call to the user's phone
+- decline:   the assistant speaks the user's message and hangs up
+- voicemail: the caller leaves a message; transcripts are searchable
+
+Voicemails are mirrored locally so search works offline.
+
+Storage: ~/.xswarm/voicemail/voicemails.json
+"""
+
+import json
+import logging
+from dataclasses import dataclass, asdict
+from pathlib import Path
+from typing import Optional, List, Dict, Any
+
+import httpx
+
+from . import endpoints
+from .api_client import ApiClient, ApiError, ApiPolicy, explain
+
+logger = logging.getLogger(__name__)
+
+
+DECISIONS = {"accept": "accepted", "decline": "declined", "voicemail": "voicemail"}
+
+
+@dataclass
+class ScreenedCall:
+    """An inbound call the assistant is screening."""
+    id: str  # Twilio CallSid
+    caller: str
+    status: str  # screening, waiting
+    purpose: str = ""
+    caller_name: Optional[str] = None
+    created_at: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "ScreenedCall":
+        known = {k: data.get(k) for k in cls.__dataclass_fields__ if k in data}
+        known["purpose"] = known.get("purpose") or ""
+        return cls(**known)
+
+    @property
+    def display_name(self) -> str:
+        return self.caller_name or self.caller
+
+
+@dataclass
+class Voicemail:
+    """A recorded voicemail with its transcript."""
+    id: str
+    call_sid: str
+    caller: str
+    created_at: str
+    transcript: Optional[str] = None
+    purpose: Optional[str] = None
+    duration: int = 0
+    recording_url: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Voicemail":
+        known = {k: data.get(k) for k in cls.__dataclass_fields__ if k in data}
+        known["duration"] = int(known.get("duration") or 0)
+        return cls(**known)
+
+    def matches(self, query: str) -> bool:
+        """True when every word of the query appears in transcript, caller or purpose."""
+        haystack = " ".join(filter(None, [self.transcript, self.caller, self.purpose])).lower()
+        return all(term in haystack for term in query.lower().split())
+
+
+class VoicemailStore:
+    """
+    Local voicemail mirror.
+    Follows the PlannerData single-file pattern.
+    """
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "voicemail"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _voicemail_path(self) -> Path:
+        return self.storage_dir / "voicemails.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+
+        path = self._voicemail_path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load voicemails: {e}")
+
+        self._data = {"voicemails": []}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            with open(self._voicemail_path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save voicemails: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    def merge_remote(self, remote: List[Dict[str, Any]]) -> int:
+        """Merge voicemails from the server. Returns the number of new ones."""
+        data = self._load()
+        by_id = {v["id"]: v for v in data["voicemails"]}
+        added = sum(1 for v in remote if v["id"] not in by_id)
+        for v in remote:
+            by_id[v["id"]] = asdict(Voicemail.from_dict(v))
+        data["voicemails"] = list(by_id.values())
+        self._save()
+        return added
+
+    def search(self, query: str = "", limit: int = 20) -> List[Voicemail]:
+        """Voicemails newest first, filtered to those matching every query word."""
+        voicemails = [Voicemail.from_dict(v) for v in self._load()["voicemails"]]
+        if query.strip():
+            voicemails = [v for v in voicemails if v.matches(query)]
+        return sorted(voicemails, key=lambda v: v.created_at, reverse=True)[:limit]
+
+
+class CallScreeningClient:
+    """Async HTTP client for the server call screening API."""
+
+    def __init__(self, server_url: str = "http://localhost:3000", api_token: Optional[str] = None,
+                 timeout: float = 10.0, policy: Optional[ApiPolicy] = None):
+        self.client = ApiClient(server_url, api_token, timeout, policy)
+
+    async def close(self):
+        await self.client.close()
+
+    async def active_calls(self, user_id: str) -> List[Dict[str, Any]]:
+        response = await self.client.call(endpoints.CALL_SCREENING(), params={"user_id": user_id})
+        return response.json().get("calls", [])
+
+    async def decide(self, call_sid: str, decision: str, message: Optional[str] = None) -> Dict[str, Any]:
+        payload = {"decision": decision}
+        if message:
+            payload["message"] = message
+        response = await self.client.call(endpoints.CALL_SCREEN(call_sid=call_sid), json=payload)
+        return response.json()
+
+    async def voicemails(self, user_id: str, query: Optional[str] = None) -> List[Dict[str, Any]]:
+        params = {"user_id": user_id, "limit": 200}
+        if query:
+            params["q"] = query
+        response = await self.client.call(endpoints.VOICEMAIL(), params=params)
+        return response.json().get("voicemails", [])
+
+
+class CallScreeningManager:
+    """Tracks calls being screened and relays the user's decisions."""
+
+    def __init__(self, config=None, user_id: str = "default-user", store: Optional[VoicemailStore] = None):
+        server_url = getattr(config, "server_url", "http://localhost:3000")
+        api_token = getattr(config, "api_token", None)
+        self.user_id = user_id
+        self.store = store or VoicemailStore()
+        self.client = CallScreeningClient(server_url, api_token, policy=ApiPolicy.from_config(config))
+        self.active_calls: List[ScreenedCall] = []
+
+    async def poll(self) -> List[ScreenedCall]:
+        """Refresh active calls. Returns calls that were not active before."""
+        try:
+            remote = await self.client.active_calls(self.user_id)
+        except httpx.HTTPError as e:
+            logger.debug(f"Call screening poll failed: {e}")
+            return []
+        known = {c.id for c in self.active_calls}
+        self.active_calls = [ScreenedCall.from_dict(c) for c in remote]
+        return [c for c in self.active_calls if c.id not in known]
+
+    def get_call(self, call_id: Optional[str] = None) -> Optional[ScreenedCall]:
+        """Find an active call by id prefix, or the most recent one."""
+        if not self.active_calls:
+            return None
+        if not call_id:
+            return self.active_calls[0]
+        return next((c for c in self.active_calls if c.id.startswith(call_id)), None)
+
+    async def decide(self, action: str, call_id: Optional[str] = None, message: Optional[str] = None) -> str:
+        """Apply accept/decline/voicemail to a screened call. Returns a status line."""
+        decision = DECISIONS.get(action)
+        if not decision:
+            return f"✗ Unknown action '{action}'. Use accept, decline or voicemail."
+        call = self.get_call(call_id)
+        if not call:
+            return "✗ No call is being screened right now"
+        try:
+            await self.client.decide(call.id, decision, message)
+        except ApiError as e:
+            return f"✗ {explain(e)}"
+        self.active_calls = [c for c in self.active_calls if c.id != call.id]
+        if decision == "accepted":
+            return f"✓ Connecting {call.display_name} to your phone"
+        if decision == "declined":
+            return f"✓ Declined call from {call.display_name}"
+        return f"✓ Sent {call.display_name} to voicemail"
+
+    async def sync_voicemail(self) -> int:
+        """Pull voicemails into the local store. Returns the number of new ones."""
+        try:
+            remote = await self.client.voicemails(self.user_id)
+        except httpx.HTTPError as e:
+            logger.debug(f"Voicemail sync failed: {e}")
+            return 0
+        return self.store.merge_remote(remote)
+
+    async def close(self):
+        await self.client.close()
+
+
+def format_voicemails(voicemails: List[Voicemail]) -> str:
+    """Plain-text voicemail listing for chat tools."""
+    if not voicemails:
+        return "No voicemails found."
+    lines = []
+    for vm in voicemails:
+        when = vm.created_at[:16].replace("T", " ")
+        transcript = vm.transcript or "(transcription pending)"
+        lines.append(f"📞 {when}  {vm.caller} ({vm.duration}s)")
+        if vm.purpose:
+            lines.append(f"   Purpose: {vm.purpose}")
+        lines.append(f"   {transcript}")
+    return "\n".join(lines)
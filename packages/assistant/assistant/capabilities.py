@@ -0,0 +1,161 @@
+"""
+Capabilities - What xswarm can do, as each feature module declares it.
+
+Every feature registers itself where it's implemented, next to the code:
+
+    register_capability("Timers", "Kitchen timers and a stopwatch",
+                        ["set a pasta timer for 9 minutes", "how long is left on the pasta timer"],
+                        category="Everyday")
+
+and lists the config fields it needs (`requires`) when it does nothing
+without them, e.g. ("emergency_phrase", "emergency_contacts"). Help is
+built from the registry, so it only ever describes what's implemented:
+
+- asked "what can you do?" (voice or chat), answer_capability_question()
+  names what's set up, with an example, and "what can you do with timers?"
+  or "how do I use lists?" gives that feature's examples (or what to set
+  up first)
+- the Help tab lists everything, searchable (search())
+
+FEATURE_MODULES are imported before the registry is read, so help is
+complete without the dashboard having loaded them; a new module that
+registers capabilities is added there.
+"""
+
+import importlib
+import logging
+import re
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Sequence, Tuple
+
+logger = logging.getLogger(__name__)
+
+FEATURE_MODULES = (
+    "timers", "lists", "alarms", "quick_math", "volume", "undo", "events", "reminders", "evening_review",
+    "flows", "appointments", "appointment_cache", "occurrences", "holidays", "scheduling_preferences", "tutorial",
+    "emergency", "household", "web_search", "news", "pronunciation", "bookmarks", "memory_digest", "tools",
+    "share_notes", "voice_config",
+)
+CATEGORIES = ("Everyday", "Planning", "Messages", "Information", "Safety", "Settings")
+MAX_SPOKEN = 8  # Capability names read out for "what can you do?"
+
+_ASK = re.compile(
+    r"^\W*(?:so\s+|and\s+|okay\s+)?(?:"
+    r"what\s+(?:else\s+|things\s+|stuff\s+)?(?:can|could)\s+(?:you|i)\s+(?:do|ask(?:\s+you)?|say|help(?:\s+me)?\s+with)"
+    r"|what\s+are\s+you\s+able\s+to\s+do|what\s+do\s+you\s+do|what\s+are\s+your\s+(?:skills|features|capabilities)"
+    r"|how\s+(?:do|can)\s+i\s+use(?:\s+you)?|how\s+does\s+(?P<works>.+?)\s+work|help(?:\s+me)?"
+    r")(?P<topic>\s+.+?)?\W*$",
+    re.IGNORECASE)
+_ABOUT = re.compile(r"^\s*(?:with|for|about|using|on|to\s+use)\s+", re.IGNORECASE)
+_FILLER = re.compile(r"\b(?:the|a|an|my|your|some|please|feature|features|thing|things|here)\b", re.IGNORECASE)
+
+
+@dataclass(frozen=True)
+class Capability:
+    name: str
+    summary: str
+    examples: Tuple[str, ...]  # Things to say, as the user would say them
+    requires: Tuple[str, ...] = ()  # Config fields that must be set
+    category: str = "Everyday"
+    keywords: Tuple[str, ...] = ()  # Other words people look it up by
+
+    def missing(self, config) -> List[str]:
+        """The required config fields that aren't set."""
+        return [name for name in self.requires if not _is_set(getattr(config, name, None))]
+
+    def available(self, config) -> bool:
+        return config is None or not self.missing(config)
+
+    def words(self) -> str:
+        return " ".join((self.name, self.summary, *self.examples, *self.keywords)).lower()
+
+
+_capabilities: Dict[str, Capability] = {}
+_loaded = False
+
+
+def _is_set(value) -> bool:
+    if isinstance(value, str):
+        return value.strip().lower() not in ("", "none", "off")
+    return bool(value)
+
+
+def register_capability(name: str, summary: str, examples: Sequence[str], requires: Sequence[str] = (),
+                        category: str = "Everyday", keywords: Sequence[str] = ()) -> None:
+    """Register (or replace) what a feature does and how to ask for it."""
+    if category not in CATEGORIES:
+        raise ValueError(f"Capability '{name}': category must be one of {', '.join(CATEGORIES)}")
+    if not examples:
+        raise ValueError(f"Capability '{name}' needs at least one example")
+    _capabilities[name.lower()] = Capability(name, summary, tuple(examples), tuple(requires), category,
+                                             tuple(keywords))
+
+
+def capabilities() -> List[Capability]:
+    """Everything registered, by category then name (feature modules are loaded first)."""
+    global _loaded
+    if not _loaded:
+        _loaded = True
+        for module in FEATURE_MODULES:
+            try:
+                importlib.import_module(f"{__package__}.{module}")
+            except Exception as e:
+                logger.warning(f"Capabilities from {module} unavailable: {e}")
+    return sorted(_capabilities.values(), key=lambda c: (CATEGORIES.index(c.category), c.name.lower()))
+
+
+def search(query: str, config=None) -> List[Capability]:
+    """
+    Capabilities matching every word of `query` (name, summary, examples,
+    keywords), best first; everything for an empty query. Ones `config`
+    hasn't set up yet come last.
+    """
+    words = [w for w in re.findall(r"[\w%]+", _FILLER.sub(" ", query or "").lower()) if len(w) > 1]
+    found = []
+    for capability in capabilities():
+        text = capability.words()
+        if all(w in text or w.rstrip("s") in text for w in words):
+            in_name = sum(w.rstrip("s") in capability.name.lower() for w in words)
+            found.append((not capability.available(config), -in_name, capability))
+    return [c for *_, c in sorted(found, key=lambda item: item[:2])]
+
+
+def describe_capability(capability: Capability, config=None) -> str:
+    """One feature, spoken: what it does and how to ask, or what it needs first."""
+    missing = capability.missing(config) if config is not None else []
+    if missing:
+        fields = ", ".join(name.replace("_", " ") for name in missing)
+        return (f"{capability.name} needs setting up first: {fields} in the config. "
+                "The Help tab has the details.")
+    examples = [f"'{e}'" for e in capability.examples[:2]]
+    return f"{capability.name}: {capability.summary[0].lower()}{capability.summary[1:]}. Try {' or '.join(examples)}."
+
+
+def describe_capabilities(config=None) -> str:
+    """The answer to "what can you do?": what's set up, one example, and how to hear more."""
+    ready = [c for c in capabilities() if c.available(config)]
+    if not ready:
+        return "Nothing's set up yet. The Help tab shows what I can do."
+    names = [c.name.lower() for c in ready[:MAX_SPOKEN]]
+    more = " and more" if len(ready) > MAX_SPOKEN else ""
+    listed = ", ".join(names[:-1]) + f" and {names[-1]}" if len(names) > 1 and not more else ", ".join(names)
+    waiting = len(capabilities()) - len(ready)
+    setup = f" {waiting} more need setting up first." if waiting else ""
+    return (f"I can help with {listed}{more}. For example, say '{ready[0].examples[0]}'. "
+            f"Ask 'what can you do with {names[0]}?' for more, or open the Help tab.{setup}")
+
+
+def answer_capability_question(text: str, config=None) -> Optional[str]:
+    """
+    The answer when `text` asks what xswarm can do ("what can you do?",
+    "how do I use timers?", "help with lists"), or None. A topic that
+    matches nothing is left to the AI ("help me with my essay").
+    """
+    match = _ASK.match(text or "")
+    if not match:
+        return None
+    topic = _ABOUT.sub("", match.group("topic") or match.group("works") or "")
+    if not topic or not _FILLER.sub("", topic).strip(" ?.!"):
+        return describe_capabilities(config)
+    found = search(topic, config)
+    return describe_capability(found[0], config) if found else None
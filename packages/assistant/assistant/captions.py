@@ -0,0 +1,56 @@
+"""
+Live captions - What the user is saying, shown while they say it.
+
+The transcriber (transcription.UserTranscriber) passes Vosk's partial
+hypotheses on as they change, through on_text(text, is_final=False). The
+voice orchestrator hands partials and final transcripts to its
+caption_callback, and the dashboard shows them on the line under the chat:
+
+    🎤 remind me to call the                 dimmed, while the user speaks
+    🎤 remind me to call the dentist         the final transcript, for FINAL_HOLD seconds
+
+The final transcript still goes to the chat as before; the caption line is
+only ever one utterance. While a PIN is asked for it shows dots, and
+config.live_captions turns it off.
+"""
+
+import time
+from typing import Optional, Tuple
+
+FINAL_HOLD = 3.0  # Seconds the final transcript stays on the line
+MAX_CHARS = 100  # Long utterances show their last words
+
+
+class Captions:
+    """The caption line's state: the utterance in progress, or the last one for a moment after."""
+
+    def __init__(self, hold: float = FINAL_HOLD):
+        self.hold = hold
+        self.text = ""
+        self.is_final = False
+        self._final_at = 0.0
+
+    def partial(self, text: str) -> None:
+        """A new hypothesis for the utterance in progress ("" when it's gone back to nothing)."""
+        text = " ".join(text.split())
+        if self.is_final and not text:
+            return  # The recognizer resetting after a final result; keep showing that
+        self.text, self.is_final = text, False
+
+    def final(self, text: str, now: Optional[float] = None) -> None:
+        self.text, self.is_final = " ".join(text.split()), True
+        self._final_at = time.monotonic() if now is None else now
+
+    def clear(self) -> None:
+        self.text, self.is_final = "", False
+
+    def line(self, now: Optional[float] = None) -> Optional[Tuple[str, bool]]:
+        """(text, is_final) to show, or None when the line should be hidden."""
+        now = time.monotonic() if now is None else now
+        if not self.text or (self.is_final and now - self._final_at >= self.hold):
+            return None
+        text = self.text
+        if len(text) > MAX_CHARS:
+            tail = text[-MAX_CHARS:]
+            text = "…" + (tail.split(" ", 1)[1] if " " in tail else tail)
+        return text, self.is_final
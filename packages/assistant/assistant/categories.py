@@ -0,0 +1,127 @@
+"""
+Categories - Tags for tasks, commitments and calendar events.
+
+Items carry free-form tags; the common categories below get keyword
+inference so "book the dentist" lands in health without the user saying so.
+
+Tags come from, in order of precedence:
+- Explicit phrasing: "#work", "work: call Bob", "(health)", "a personal reminder"
+- Keyword inference: "dentist" -> health, "standup" -> work
+
+Per-category notification preferences (config.category_notifications) are
+enforced by notifications.NotificationPolicy, e.g.
+    {"work": {"weekends": false, "hours": "08:00-18:00"}, "finance": {"muted": true}}
+"""
+
+import re
+from typing import Iterable, List, Optional, Tuple, Union
+
+CATEGORIES = ("work", "personal", "health", "family", "finance", "errands")
+
+CATEGORY_KEYWORDS = {
+    "work": [
+        "meeting", "standup", "stand-up", "client", "sprint", "presentation", "office",
+        "colleague", "coworker", "interview", "deploy", "release", "code review", "1:1", "one-on-one",
+    ],
+    "health": [
+        "doctor", "dentist", "gym", "workout", "yoga", "therapy", "therapist", "medication",
+        "meds", "pharmacy", "checkup", "check-up", "physio", "prescription", "vet",
+    ],
+    "family": [
+        "mom", "dad", "kids", "son", "daughter", "wife", "husband", "grandma", "grandpa",
+        "school pickup", "parent-teacher", "anniversary", "family",
+    ],
+    "finance": [
+        "bank", "tax", "taxes", "rent", "bill", "bills", "invoice", "budget", "insurance",
+        "mortgage", "pay", "payment",
+    ],
+    "errands": [
+        "groceries", "grocery", "dry cleaning", "post office", "shopping", "car wash",
+        "oil change", "pick up", "drop off",
+    ],
+    "personal": ["birthday", "haircut", "hobby", "vacation", "friend", "friends"],
+}
+
+_NAMES = "|".join(CATEGORIES)
+_HASHTAG = re.compile(r"(?<![\w#])#([A-Za-z][\w-]*)")
+_PREFIX = re.compile(rf"^\s*({_NAMES})\s*[:\-]\s+", re.IGNORECASE)
+_PAREN = re.compile(rf"\s*\(({_NAMES})\)", re.IGNORECASE)
+_PHRASE = re.compile(
+    rf"\b({_NAMES})\s+(?:reminder|task|appointment|meeting|event|errand|call)s?\b|\bfor\s+(work)\b",
+    re.IGNORECASE,
+)
+_KEYWORD_PATTERNS = {
+    category: re.compile(r"(?<![\w-])(" + "|".join(re.escape(k) for k in keywords) + r")(?![\w-])", re.IGNORECASE)
+    for category, keywords in CATEGORY_KEYWORDS.items()
+}
+
+
+def normalize_tags(tags: Union[str, Iterable[str], None]) -> List[str]:
+    """Tags from a comma-separated string or list: lowercase, no '#', no duplicates."""
+    if not tags:
+        return []
+    if isinstance(tags, str):
+        tags = tags.split(",")
+    result = []
+    for tag in tags:
+        tag = tag.strip().lstrip("#").lower()
+        if tag and tag not in result:
+            result.append(tag)
+    return result
+
+
+def infer_categories(text: str) -> List[str]:
+    """Categories suggested by keywords in the text (may be empty)."""
+    return [category for category, pattern in _KEYWORD_PATTERNS.items() if pattern.search(text or "")]
+
+
+def extract_categories(text: str) -> Tuple[List[str], str]:
+    """
+    Pull tags out of a spoken or typed title.
+
+    Returns (tags, cleaned_title). Explicit markers (#tag, "work:", "(health)")
+    are removed from the title; phrasing like "personal reminder" is kept.
+    Keyword inference is only used when nothing explicit was said.
+    """
+    text = text or ""
+    tags = [m.group(1) for m in _HASHTAG.finditer(text)]
+    # Leading/trailing hashtags are markers; mid-sentence ones are words ("about #taxes")
+    words = text.split()
+    while words and _HASHTAG.fullmatch(words[0]):
+        words.pop(0)
+    while words and _HASHTAG.fullmatch(words[-1]):
+        words.pop()
+    cleaned = _HASHTAG.sub(r"\1", " ".join(words))
+
+    prefix = _PREFIX.match(cleaned)
+    if prefix:
+        tags.append(prefix.group(1))
+        cleaned = cleaned[prefix.end():]
+
+    tags += [m.group(1) for m in _PAREN.finditer(cleaned)]
+    cleaned = _PAREN.sub("", cleaned)
+    tags += [m.group(1) or m.group(2) for m in _PHRASE.finditer(cleaned)]
+
+    cleaned = " ".join(cleaned.split())
+    tags = normalize_tags(tags)
+    return (tags or infer_categories(cleaned)), cleaned
+
+
+def resolve_tags(tags: Union[str, Iterable[str], None], title: str) -> Tuple[List[str], str]:
+    """Explicit tags win; otherwise extract them from the title. Returns (tags, title)."""
+    explicit = normalize_tags(tags)
+    if explicit:
+        return explicit, title
+    return extract_categories(title)
+
+
+def has_tag(item_tags: Optional[Iterable[str]], tag: Optional[str]) -> bool:
+    """True if no filter is set or the item carries the tag."""
+    if not tag:
+        return True
+    return tag.lstrip("#").lower() in (item_tags or [])
+
+
+def format_tags(tags: Optional[Iterable[str]]) -> str:
+    """Tags as ' #work #health' for list output (empty when untagged)."""
+    return "".join(f" #{t}" for t in tags or [])
@@ -0,0 +1,176 @@
+"""
+Chaos - Simulated provider outages and slow calls, for exercising failover.
+
+    xswarm --chaos "ai=0.3,delay=0.5-3"          # the live assistant, this run only
+    xswarm dev replay --chaos outage=anthropic scenario.yaml
+    chaos: {outage: anthropic, ai.delay: 2}       # in a replay scenario (replay.py)
+
+A spec is comma-separated key=value pairs:
+
+- ai / stt / tts=P: each call on that path fails with probability P
+- delay=S or LO-HI: seconds added before every call; ai.delay=... (and
+  stt., tts.) for one path only
+- outage=NAME+NAME: calls to these always fail - a path, or a provider
+  such as anthropic or ollama (the start of a ladder rung's name)
+- seed=N: the same failures on every run
+
+Where it strikes:
+
+- ai: every rung of BudgetedAI (latency.py), so the next rung answers,
+  fillers are spoken when a delay passes the soft budget, and LatencyError
+  is raised when the whole ladder is down
+- stt: UserTranscriber's final transcripts (transcription.py) are late or
+  lost, as with a recognizer that gave up
+- tts: VoiceOrchestrator.speak_text treats Moshi as unreachable, so only
+  cached phrases (speech_cache.py) are still said
+
+Off unless asked for on the command line - never from config.yaml - and
+every injected failure is logged with 🐒.
+"""
+
+import asyncio
+import logging
+import random
+import time
+from collections import Counter
+from dataclasses import dataclass, field
+from typing import Any, Dict, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+PATHS = ("ai", "stt", "tts")
+
+
+class ChaosError(RuntimeError):
+    """A failure injected by chaos mode."""
+
+
+def _rate(path: str, value: str) -> float:
+    try:
+        rate = float(value)
+    except ValueError:
+        rate = -1.0
+    if not 0 <= rate <= 1:
+        raise ValueError(f"Chaos {path}={value}: the chance a call fails is 0 to 1")
+    return rate
+
+
+def _seconds(value: str) -> Tuple[float, float]:
+    low, _, high = str(value).partition("-")
+    try:
+        low, high = float(low), float(high or low)
+    except ValueError:
+        low = high = -1.0
+    if low < 0 or high < low:
+        raise ValueError(f"Chaos delay '{value}' must be seconds or a LO-HI range")
+    return low, high
+
+
+@dataclass
+class Chaos:
+    """What fails and how slow it gets; an empty Chaos changes nothing."""
+    rates: Dict[str, float] = field(default_factory=dict)  # Path -> chance a call fails
+    delays: Dict[str, Tuple[float, float]] = field(default_factory=dict)  # Path ("" for all) -> seconds range
+    outages: Tuple[str, ...] = ()
+    seed: Optional[int] = None
+
+    def __post_init__(self):
+        self._random = random.Random(self.seed)
+        self.injected: Counter = Counter()  # "ai errors", "tts delays"...
+
+    @classmethod
+    def parse(cls, spec: Any) -> "Chaos":
+        """From "ai=0.3,delay=1-2,outage=anthropic" or the same as a dict; ValueError names what's wrong."""
+        if isinstance(spec, dict):
+            pairs = [(str(k), "+".join(v) if isinstance(v, list) else str(v)) for k, v in spec.items()]
+        else:
+            pairs = []
+            for part in filter(None, (p.strip() for p in str(spec or "").split(","))):
+                key, sep, value = part.partition("=")
+                if not sep:
+                    raise ValueError(f"Chaos setting '{part}' needs a value (e.g. ai=0.3)")
+                pairs.append((key.strip(), value.strip()))
+
+        chaos = cls()
+        for key, value in pairs:
+            key = key.lower()
+            path = key[:-len(".delay")] if key.endswith(".delay") else ""
+            if key in PATHS:
+                chaos.rates[key] = _rate(key, value)
+            elif key == "delay" or path in PATHS:
+                chaos.delays[path] = _seconds(value)
+            elif key == "outage":
+                chaos.outages += tuple(name.strip().lower() for name in value.split("+") if name.strip())
+            elif key == "seed":
+                try:
+                    chaos.seed = int(value)
+                except ValueError:
+                    raise ValueError(f"Chaos seed '{value}' must be a whole number")
+            else:
+                raise ValueError(f"Unknown chaos setting '{key}' (use ai, stt, tts, delay, outage, seed)")
+        chaos._random = random.Random(chaos.seed)
+        return chaos
+
+    @property
+    def active(self) -> bool:
+        return bool(self.outages or any(self.rates.values()) or any(high for _, high in self.delays.values()))
+
+    def describe(self) -> str:
+        """One line for the activity feed and log, e.g. "ai fails 30%, delay 1-2s, outage anthropic"."""
+        parts = [f"{path} fails {rate:.0%}" for path, rate in self.rates.items() if rate]
+        for path, (low, high) in self.delays.items():
+            parts.append(f"{path + ' ' if path else ''}delay {low:g}{f'-{high:g}' if high != low else ''}s")
+        if self.outages:
+            parts.append(f"outage {', '.join(self.outages)}")
+        return ", ".join(parts) or "off"
+
+    def summary(self) -> str:
+        """What was injected so far, e.g. "ai errors ×3, tts delays ×1"."""
+        return ", ".join(f"{kind} ×{n}" for kind, n in sorted(self.injected.items())) or "nothing injected"
+
+    def is_out(self, path: str, name: str = "") -> bool:
+        name = name.lower()
+        return any(outage in (path, name) or name.startswith(outage + ":") for outage in self.outages)
+
+    def delay(self, path: str) -> float:
+        low, high = self.delays.get(path, self.delays.get("", (0.0, 0.0)))
+        return self._random.uniform(low, high) if high else 0.0
+
+    def _strike(self, path: str, name: str, waited: float) -> None:
+        if waited:
+            self.injected[f"{path} delays"] += 1
+            logger.info(f"🐒 {path} {name} delayed {waited:.2f}s")
+        if self.is_out(path, name) or self._random.random() < self.rates.get(path, 0.0):
+            self.injected[f"{path} errors"] += 1
+            logger.warning(f"🐒 {path} {name} failed (chaos mode)")
+            raise ChaosError(f"simulated {name or path} outage")
+
+    async def disrupt(self, path: str, name: str = "") -> None:
+        """Wait out an injected delay, then raise ChaosError if this call is to fail. Nothing when off."""
+        if not self.active:
+            return
+        waited = self.delay(path)
+        if waited:
+            await asyncio.sleep(waited)
+        self._strike(path, name, waited)
+
+    def disrupt_sync(self, path: str, name: str = "") -> None:
+        """disrupt() for worker threads (the transcriber's)."""
+        if not self.active:
+            return
+        waited = self.delay(path)
+        if waited:
+            time.sleep(waited)
+        self._strike(path, name, waited)
+
+
+_chaos = Chaos()
+
+
+def get_chaos() -> Chaos:
+    return _chaos
+
+
+def set_chaos(chaos: Chaos) -> None:
+    global _chaos
+    _chaos = chaos
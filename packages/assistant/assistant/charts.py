@@ -0,0 +1,107 @@
+"""
+Charts - Sparklines and bar charts drawn with text, for the dashboard.
+
+Values that change over time read better as a shape than as one number:
+
+- History: the last few samples of a value (reply latency, CPU usage),
+  older ones optionally kept one per minute
+- sparkline(): one block per sample, "▁▂▅█▃"
+- bar_rows(): a label, bar length and value per row, scaled so the
+  largest value fills the width
+
+The Sparkline and BarChart widgets (dashboard_widgets.py) draw these with
+the theme's colors on the Status tab: xswarm's CPU, reply latency and
+messages per day (analytics.py).
+"""
+
+import time
+from collections import deque
+from typing import Iterable, List, Optional, Sequence, Tuple
+
+BLOCKS = "▁▂▃▄▅▆▇█"
+HISTORY_SIZE = 60  # Samples kept per sparkline
+RECENT_SECONDS = 3600  # With a per-minute tier, samples older than this are averaged to one a minute
+MINUTE = 60
+
+
+class History:
+    """
+    The last `size` samples of a value, oldest first. With `minutes`, the
+    samples older than an hour (or pushed out by newer ones) aren't dropped
+    but averaged to one per minute, the last `minutes` of those kept - a
+    day of CPU in a few thousand floats instead of one per second.
+    """
+
+    def __init__(self, size: int = HISTORY_SIZE, minutes: int = 0):
+        self.size = size
+        self._samples: deque = deque()
+        self._minutes: Optional[deque] = deque(maxlen=minutes) if minutes else None  # (minute, mean, count)
+
+    def add(self, value: float, at: Optional[float] = None) -> None:
+        at = at if at is not None else time.time()
+        self._samples.append((at, float(value)))
+        cutoff = at - RECENT_SECONDS if self._minutes is not None else float("-inf")
+        while len(self._samples) > self.size or self._samples[0][0] < cutoff:
+            old_at, old_value = self._samples.popleft()
+            if self._minutes is not None:
+                self._downsample(old_at, old_value)
+
+    def _downsample(self, at: float, value: float) -> None:
+        minute = at - at % MINUTE
+        if self._minutes and self._minutes[-1][0] == minute:
+            _, mean, count = self._minutes[-1]
+            self._minutes[-1] = (minute, mean + (value - mean) / (count + 1), count + 1)
+        else:
+            self._minutes.append((minute, value, 1))
+
+    @property
+    def values(self) -> List[float]:
+        older = [mean for _, mean, _ in self._minutes] if self._minutes else []
+        return older + [value for _, value in self._samples]
+
+    @property
+    def last(self) -> Optional[float]:
+        return self._samples[-1][1] if self._samples else None
+
+    @property
+    def mean(self) -> Optional[float]:
+        return sum(self.values) / len(self) if len(self) else None
+
+    def __len__(self) -> int:
+        return len(self._samples) + (len(self._minutes) if self._minutes else 0)
+
+
+def sparkline(values: Iterable[float], width: Optional[int] = None,
+              low: Optional[float] = None, high: Optional[float] = None) -> str:
+    """
+    "▁▂▅█▃" for the values, scaled between `low` and `high` (their own
+    range when not given). With a width, the newest `width` values are shown,
+    right-aligned.
+    """
+    values = list(values)
+    if width is not None:
+        values = values[-width:] if width > 0 else []
+    if not values:
+        return " " * (width or 0)
+    low = min(values) if low is None else low
+    high = max(values) if high is None else high
+    span = high - low
+    top = len(BLOCKS) - 1
+    blocks = ""
+    for value in values:
+        level = round((value - low) / span * top) if span > 0 else 0
+        blocks += BLOCKS[max(0, min(top, level))]
+    return blocks.rjust(width or 0)
+
+
+def bar_length(value: float, peak: float, width: int) -> int:
+    """How much of `width` a bar for `value` fills when `peak` fills all of it."""
+    if peak <= 0 or value <= 0:
+        return 0
+    return max(0, min(width, round(width * value / peak)))
+
+
+def bar_rows(items: Sequence[Tuple[str, float]], width: int = 20) -> List[Tuple[str, int, float]]:
+    """(label, bar length, value) for each (label, value), the largest filling `width`."""
+    peak = max([value for _, value in items] + [0])
+    return [(label, bar_length(value, peak, width), value) for label, value in items]
@@ -11,12 +11,14 @@ Features:
 - Visible thinking/reasoning in chat history
 - Streaming responses
 - Memory integration
+- A window of recent turns, older ones kept as a synopsis (see working_memory.py)
 
 IMPORTANT: OAuth tokens from Claude Code require the EXACT system prompt
 "You are Claude Code, Anthropic's official CLI for Claude." - no additions.
 Persona/agenda must be injected via a preamble in the first user message.
 """
 
+import time
 from dataclasses import dataclass, field
 from datetime import datetime
 from typing import Optional, List, Dict, Any, AsyncGenerator, Callable
@@ -24,6 +26,7 @@ from enum import Enum
 
 import httpx
 
+from .ai_log import log_ai_call
 from .auth import (
     AnthropicAuth,
     get_anthropic_client_headers,
@@ -32,9 +35,11 @@ from .auth import (
     CLAUDE_CODE_SYSTEM_PROMPT
 )
 from .personas.config import PersonaConfig
+from .personas.mood import get_mood_model
 from .memory import PersistentChatHistory, EnhancedMemoryAgent, UserProfile
 from .planner import PlannerData, PlanningSession
-from .tools import set_planner_data, registry as tool_registry
+from .tools import set_planner_data, set_user_profile, registry as tool_registry
+from .working_memory import Window, WorkingMemory
 
 
 # Default persona preamble for when no persona is set
@@ -148,6 +153,9 @@ class ChatEngine:
         # Conversation history (in-memory for this session)
         self.messages: List[ChatMessage] = []
 
+        # The latest turns go to the API; older ones are folded into a synopsis (config.working_memory_*)
+        self.working_memory = WorkingMemory(app_config, summarize=self._synopsis_ai())
+
         # Agenda/goals for this session
         self.agenda: Optional[str] = None
 
@@ -159,6 +167,7 @@ class ChatEngine:
 
         # User profile for persistent facts (always in context)
         self.user_profile = UserProfile()
+        set_user_profile(self.user_profile)
 
         # Planning system for daily planning and habit tracking
         self.planner = PlannerData()
@@ -173,6 +182,9 @@ class ChatEngine:
         if self.chat_history:
             self.chat_history.start_session()
 
+        # A new session starts the persona at its resting mood
+        get_mood_model(self.persona).reset()
+
     def set_persona(self, persona: PersonaConfig) -> None:
         """Set or change the active persona."""
         self.persona = persona
@@ -184,6 +196,29 @@ class ChatEngine:
     def clear_history(self) -> None:
         """Clear conversation history."""
         self.messages = []
+        self.working_memory.reset()
+
+    def set_working_memory(self, turns: Optional[int] = None, tokens: Optional[int] = None) -> Window:
+        """Change how many turns (and tokens) of this conversation the AI sees; None keeps the current value."""
+        return self.working_memory.set_window(turns, tokens)
+
+    def _synopsis_ai(self):
+        """The configured AI's chat, for the working memory synopsis (None: list earlier requests instead)."""
+        if self.app_config is None:
+            return None
+        try:
+            from .voice import AIClient
+            client = AIClient(self.app_config, "working_memory")
+        except Exception:
+            return None
+        return client.chat if client.is_available() else None
+
+    async def _update_working_memory(self) -> None:
+        """Fold turns that left the window into the synopsis, kept as the saved session's summary too."""
+        if await self.working_memory.update(self.messages):
+            session = self.chat_history.current_session if self.chat_history else None
+            if session is not None:
+                session.summary = self.working_memory.synopsis
 
     def end_session(self) -> None:
         """End the current session (saves to persistent storage)."""
@@ -216,6 +251,11 @@ class ChatEngine:
         else:
             parts.append("You are a helpful AI assistant. Be concise, accurate, and helpful.")
 
+        # Add the persona's mood (drifts with the conversation - personas/mood.py)
+        mood_line = get_mood_model(self.persona).prompt_line()
+        if mood_line:
+            parts.append(mood_line)
+
         # Add user profile (persistent facts about the user - always in context)
         if self.user_profile:
             user_context = self.user_profile.get_context_string()
@@ -391,27 +431,34 @@ class ChatEngine:
         For OAuth: Injects persona preamble into first user message.
         For API keys: Returns messages as-is (persona is in system prompt).
         Also injects recalled memory context into the latest user message.
+        Only the working memory window is sent, its synopsis of earlier turns
+        leading the first message.
         """
         api_messages = []
         is_first_user_message = True
         last_user_msg_index = None
+        visible, synopsis = self.working_memory.visible(self.messages)
+        earlier = f"<earlier_conversation>\n{synopsis}\n</earlier_conversation>\n\n" if synopsis else ""
 
         # First pass: build messages and track last user message
-        for i, msg in enumerate(self.messages):
+        for i, msg in enumerate(visible):
             api_msg = msg.to_api_message()
             if not api_msg:
                 continue
 
-            # For OAuth, inject preamble into first user message
-            if requires_system_prompt(self.auth) and msg.role == MessageRole.USER and is_first_user_message:
-                preamble = self._build_persona_preamble()
-                api_msg["content"] = f"{preamble}\n\nUser message: {api_msg['content']}"
+            if msg.role == MessageRole.USER and is_first_user_message:
                 is_first_user_message = False
-
-                # In debug mode, show the persona preamble (only once)
-                if self.config.debug and self.on_system_prompt and not self._preamble_shown:
-                    self.on_system_prompt(preamble)
-                    self._preamble_shown = True
+                # For OAuth, inject preamble into first user message
+                if requires_system_prompt(self.auth):
+                    preamble = self._build_persona_preamble()
+                    api_msg["content"] = f"{preamble}\n\n{earlier}User message: {api_msg['content']}"
+
+                    # In debug mode, show the persona preamble (only once)
+                    if self.config.debug and self.on_system_prompt and not self._preamble_shown:
+                        self.on_system_prompt(preamble)
+                        self._preamble_shown = True
+                elif earlier:
+                    api_msg["content"] = f"{earlier}{api_msg['content']}"
 
             if msg.role == MessageRole.USER:
                 last_user_msg_index = len(api_messages)
@@ -426,6 +473,13 @@ class ChatEngine:
             # Clear after use (only inject once)
             self._pending_memory_context = None
 
+        # For OAuth the system prompt is fixed, so the mood goes with the latest message
+        mood_line = get_mood_model(self.persona).prompt_line() if requires_system_prompt(self.auth) else ""
+        if mood_line and last_user_msg_index is not None:
+            api_messages[last_user_msg_index]["content"] = (
+                f"<mood>{mood_line}</mood>\n\n{api_messages[last_user_msg_index]['content']}"
+            )
+
         return api_messages
 
     def _parse_thinking(self, content: str) -> tuple[Optional[str], str]:
@@ -483,6 +537,8 @@ class ChatEngine:
             # Update request with current messages
             request_body["messages"] = current_messages
             request_body["stream"] = True
+            started = time.monotonic()
+            usage = {}
 
             async with client.stream(
                 "POST",
@@ -494,6 +550,8 @@ class ChatEngine:
                 if response.status_code != 200:
                     error_text = await response.aread()
                     error_msg = f"API error: {error_text.decode()[:200]}"
+                    log_ai_call("anthropic", request_body.get("model", ""), "chat", request_body,
+                                started=started, error=error_msg)
                     self.messages.append(ChatMessage(
                         role=MessageRole.ASSISTANT,
                         content=error_msg
@@ -518,8 +576,8 @@ class ChatEngine:
                             event_type = event.get("type", "")
 
                             if event_type == "message_start":
-                                # Message started
-                                pass
+                                # Message started; its usage has the input tokens
+                                usage.update(event.get("message", {}).get("usage", {}))
 
                             elif event_type == "content_block_start":
                                 block = event.get("content_block", {})
@@ -559,10 +617,14 @@ class ChatEngine:
                             elif event_type == "message_delta":
                                 delta = event.get("delta", {})
                                 stop_reason = delta.get("stop_reason")
+                                usage.update(event.get("usage", {}))
 
                         except json.JSONDecodeError:
                             pass
 
+            log_ai_call("anthropic", request_body.get("model", ""), "chat", request_body,
+                        full_text, {"usage": usage}, started)
+
             # Process any text response
             if full_text:
                 thinking, main_response = self._parse_thinking(full_text)
@@ -682,6 +744,11 @@ class ChatEngine:
             for fact in extracted_facts:
                 self.user_profile.add_fact(fact["category"], fact["fact"])
 
+        # Nudge the persona's mood by how the message reads; it tunes the reply (temperature)
+        mood = get_mood_model(self.persona)
+        mood.observe(user_message)
+        response_config = mood.shape(self.config)
+
         # Trigger memory agent (async, non-blocking)
         if self.memory_agent:
             recalled = await self.memory_agent.on_message("user", user_message)
@@ -700,12 +767,13 @@ class ChatEngine:
             return
 
         # Build API messages with persona preamble if needed (for OAuth)
+        await self._update_working_memory()
         api_messages = self._prepare_api_messages()
 
         # Build request
         request_body = {
             "model": self.config.model,
-            "max_tokens": self.config.max_tokens,
+            "max_tokens": response_config.max_tokens,
             "system": self._build_system_prompt(),
             "messages": api_messages,
             "stream": should_stream
@@ -716,8 +784,8 @@ class ChatEngine:
         if tool_schemas:
             request_body["tools"] = tool_schemas
 
-        if self.config.temperature != 1.0:
-            request_body["temperature"] = self.config.temperature
+        if response_config.temperature != 1.0:
+            request_body["temperature"] = response_config.temperature
 
         try:
             async with httpx.AsyncClient(timeout=60.0) as client:
@@ -729,6 +797,7 @@ class ChatEngine:
                         yield text_chunk
                 else:
                     # Non-streaming response
+                    started = time.monotonic()
                     response = await client.post(
                         f"{ANTHROPIC_API_URL}/v1/messages",
                         headers=headers,
@@ -737,6 +806,8 @@ class ChatEngine:
 
                     if response.status_code != 200:
                         error_msg = f"API error: {response.text[:200]}"
+                        log_ai_call("anthropic", request_body.get("model", ""), "chat", request_body,
+                                    started=started, error=error_msg)
                         self.messages.append(ChatMessage(
                             role=MessageRole.ASSISTANT,
                             content=error_msg
@@ -746,6 +817,8 @@ class ChatEngine:
 
                     data = response.json()
                     content = data.get("content", [{}])[0].get("text", "")
+                    log_ai_call("anthropic", request_body.get("model", ""), "chat", request_body, content, data,
+                                started)
 
                     # Parse thinking
                     thinking, main_response = self._parse_thinking(content)
@@ -0,0 +1,128 @@
+"""
+Clock Skew - Notice when this machine's clock is wrong, and correct for it.
+
+Event reminders and meeting prep fire from the local clock, so a clock that
+has drifted makes them early or late. Every server response carries a Date
+header: ApiClient hands each one to the ClockSkew tracker, which takes the
+server's time at the midpoint of the request as the truth. Date headers
+only have whole seconds, so the offset is the median of the last
+SAMPLES readings and anything under COMPENSATE_SECONDS is treated as
+noise. With config.ntp_server set (e.g. "pool.ntp.org"; off by default,
+it's a request to a third party) the clock_check job also asks an NTP
+server, which is more precise and is preferred while its reading is fresh.
+
+corrected_now() is datetime.now() plus the offset; reminder and prep checks
+use it. When the offset passes config.clock_skew_warn_seconds the dashboard
+warns once (and says so again when it's back in sync).
+"""
+
+import logging
+import socket
+import struct
+import time
+from collections import deque
+from datetime import datetime, timedelta
+from email.utils import parsedate_to_datetime
+from statistics import median
+from typing import Callable, Optional
+
+logger = logging.getLogger(__name__)
+
+SAMPLES = 15
+COMPENSATE_SECONDS = 2.0  # Smaller offsets are within a Date header's rounding
+NTP_FRESH_SECONDS = 6 * 60 * 60
+NTP_EPOCH_OFFSET = 2208988800  # Seconds from 1900 (NTP) to 1970 (Unix)
+
+
+def sntp_offset(server: str, timeout: float = 3.0, port: int = 123,
+                clock: Callable[[], float] = time.time, sock_factory=None) -> float:
+    """
+    Seconds to add to the local clock to match `server` (one SNTP query).
+    Raises OSError when the server can't be reached.
+    """
+    request = b"\x23" + 47 * b"\0"  # Version 4, client mode
+    sock = (sock_factory or (lambda: socket.socket(socket.AF_INET, socket.SOCK_DGRAM)))()
+    try:
+        sock.settimeout(timeout)
+        sent = clock()
+        sock.sendto(request, (server, port))
+        data, _ = sock.recvfrom(512)
+        received = clock()
+    finally:
+        sock.close()
+    if len(data) < 48:
+        raise OSError(f"Short NTP reply from {server}")
+
+    def timestamp(offset: int) -> float:
+        seconds, fraction = struct.unpack("!II", data[offset:offset + 8])
+        return seconds - NTP_EPOCH_OFFSET + fraction / 2 ** 32
+
+    server_received, server_sent = timestamp(32), timestamp(40)
+    return ((server_received - sent) + (server_sent - received)) / 2
+
+
+class ClockSkew:
+    """Estimated local clock offset from server Date headers and NTP."""
+
+    def __init__(self, clock: Callable[[], float] = time.time):
+        self.clock = clock
+        self._samples: deque = deque(maxlen=SAMPLES)
+        self.ntp_offset: Optional[float] = None
+        self.ntp_at: Optional[float] = None
+
+    def observe_date(self, header: Optional[str], sent: float, received: float) -> None:
+        """A server Date header, for a request sent and answered at these local times (time.time())."""
+        if not header:
+            return
+        try:
+            server = parsedate_to_datetime(header).timestamp()
+        except (TypeError, ValueError, IndexError):
+            return
+        # The server stamped it somewhere in the round trip; a whole-second header averages +0.5s
+        self._samples.append(server + 0.5 - (sent + received) / 2)
+
+    def observe_ntp(self, offset: float) -> None:
+        self.ntp_offset, self.ntp_at = offset, self.clock()
+
+    @property
+    def source(self) -> Optional[str]:
+        """"ntp", "server" or None when there's nothing to go on."""
+        if self.ntp_at is not None and self.clock() - self.ntp_at < NTP_FRESH_SECONDS:
+            return "ntp"
+        return "server" if self._samples else None
+
+    @property
+    def offset(self) -> float:
+        """Seconds the local clock is behind (negative: ahead); 0 when unknown or within noise."""
+        source = self.source
+        if source is None:
+            return 0.0
+        offset = self.ntp_offset if source == "ntp" else median(self._samples)
+        return offset if abs(offset) >= COMPENSATE_SECONDS else 0.0
+
+    def now(self) -> datetime:
+        """The local time, corrected by the offset."""
+        return datetime.now() + timedelta(seconds=self.offset)
+
+    def warning(self, threshold: float) -> Optional[str]:
+        """What to tell the user when the offset is past `threshold` seconds, else None."""
+        offset = self.offset
+        if abs(offset) < threshold:
+            return None
+        amount = f"{abs(offset) / 60:.0f} minutes" if abs(offset) >= 120 else f"{abs(offset):.0f} seconds"
+        direction = "behind" if offset > 0 else "ahead"
+        by = "an NTP server" if self.source == "ntp" else "the server"
+        return (f"This computer's clock is {amount} {direction} ({by}); reminders are adjusted, "
+                f"but turn on automatic time sync to fix it")
+
+
+_skew = ClockSkew()
+
+
+def get_clock_skew() -> ClockSkew:
+    return _skew
+
+
+def corrected_now() -> datetime:
+    """datetime.now() corrected for a drifted local clock."""
+    return _skew.now()
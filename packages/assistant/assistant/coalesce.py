@@ -0,0 +1,63 @@
+"""
+Coalescing - The same event over and over shown once, with a count.
+
+A subsystem that fails in a loop reports it every time:
+"Voice bridge error: connection refused" once a second buries everything
+else in the activity feed, and a desktop notification that repeats is
+worse. A Coalescer tells a repeat from something new: the same key
+seen again less than COALESCE_SECONDS after it was last seen.
+
+- the activity feed keeps one entry and updates its count and last-seen
+  time ("× 37, last 12:03:05") instead of adding another; repeats aren't
+  written to the event history or the accessible stream again either
+- notifications (notifications.py) with the same channel, title and
+  message aren't delivered again; the next one after a quiet spell says
+  how many were held back. Emergencies always go out.
+
+The window slides: an error repeating every second stays one entry for as
+long as it keeps repeating.
+"""
+
+import time
+from collections import OrderedDict
+from dataclasses import dataclass
+from typing import Callable, Hashable
+
+COALESCE_SECONDS = 60.0
+MAX_KEYS = 256  # Distinct events remembered; the least recent forgotten first
+
+
+@dataclass
+class Repeat:
+    count: int  # Times seen in this run of repeats, the first included
+    first_seen: float
+    last_seen: float
+    earlier: int = 0  # Repeats in the previous run (the ones not shown, for notifications)
+
+    @property
+    def new(self) -> bool:
+        return self.count == 1
+
+
+class Coalescer:
+    """Counts runs of the same key seen less than `window` seconds apart."""
+
+    def __init__(self, window: float = COALESCE_SECONDS, clock: Callable[[], float] = time.monotonic):
+        self.window = window
+        self.clock = clock
+        self._seen: "OrderedDict[Hashable, Repeat]" = OrderedDict()
+
+    def note(self, key: Hashable) -> Repeat:
+        """Record that `key` happened now; its Repeat, new (count 1) when not seen within the window."""
+        now = self.clock()
+        repeat = self._seen.get(key)
+        if repeat is None or now - repeat.last_seen >= self.window:
+            repeat = Repeat(1, now, now, earlier=repeat.count - 1 if repeat else 0)
+        else:
+            repeat.count += 1
+            repeat.last_seen = now
+        self._seen[key] = repeat
+        self._seen.move_to_end(key)
+        while len(self._seen) > MAX_KEYS:
+            self._seen.popitem(last=False)
+        return repeat
@@ -0,0 +1,372 @@
+"""
+Code Chunks - Split source files at function/class boundaries for the project index.
+
+Fixed-size windows cut functions in half and lose what they belong to, so
+project_docs.py indexes source files by definition instead: each function,
+method, class, struct, impl or trait becomes its own chunk, tagged with its
+symbol ("RetryPolicy.backoff"), kind and language. Definitions longer than
+MAX_CHUNK_LINES are split into their members (a class into its methods), or
+into windows that all keep the symbol. Lines outside any definition (imports,
+constants) are windowed as before. Markdown is split by heading, with the
+heading trail as its symbol.
+
+Parsers, best first:
+- tree-sitter, when installed (pip install voice-assistant[code]), for every
+  language in LANGUAGES
+- Python's own ast for .py files
+- an indentation heuristic: definition keywords per language, ending at the
+  closing brace/"end" at the same indentation or the next definition
+
+Used by project_docs.py: chunk_file(path, text) -> [Chunk].
+"""
+
+import ast
+import logging
+import re
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional
+
+logger = logging.getLogger(__name__)
+
+CHUNK_LINES = 40  # Window for lines outside definitions
+MAX_CHUNK_LINES = 80  # Longer definitions are split into members or windows
+# Comment/doc/attribute lines directly above a definition belong to it
+COMMENT_PREFIXES = ("//", "/*", "*", "#", "@", "--")
+CHUNKER_VERSION = 2  # Bump to reindex files chunked by an older version
+
+LANGUAGES = {
+    ".py": "python", ".rs": "rust", ".js": "javascript", ".mjs": "javascript", ".jsx": "javascript",
+    ".ts": "typescript", ".tsx": "tsx", ".go": "go", ".java": "java", ".kt": "kotlin", ".rb": "ruby",
+    ".php": "php", ".swift": "swift", ".c": "c", ".h": "c", ".cc": "cpp", ".cpp": "cpp", ".hpp": "cpp",
+    ".cs": "csharp", ".md": "markdown", ".mdx": "markdown",
+}
+
+# tree-sitter node types that are definitions, per language
+TS_DEFINITIONS = {
+    "python": {"function_definition", "class_definition"},
+    "rust": {"function_item", "struct_item", "enum_item", "trait_item", "impl_item", "mod_item", "macro_definition"},
+    "javascript": {"function_declaration", "generator_function_declaration", "class_declaration",
+                   "method_definition", "lexical_declaration"},
+    "go": {"function_declaration", "method_declaration", "type_declaration"},
+    "java": {"class_declaration", "interface_declaration", "enum_declaration", "method_declaration",
+             "constructor_declaration"},
+    "kotlin": {"class_declaration", "object_declaration", "function_declaration"},
+    "ruby": {"method", "singleton_method", "class", "module"},
+    "php": {"function_definition", "class_declaration", "interface_declaration", "trait_declaration",
+            "method_declaration"},
+    "swift": {"function_declaration", "class_declaration", "protocol_declaration"},
+    "c": {"function_definition", "struct_specifier", "enum_specifier"},
+    "cpp": {"function_definition", "class_specifier", "struct_specifier", "namespace_definition"},
+    "csharp": {"class_declaration", "interface_declaration", "struct_declaration", "method_declaration",
+               "constructor_declaration", "namespace_declaration"},
+}
+TS_DEFINITIONS["typescript"] = TS_DEFINITIONS["tsx"] = TS_DEFINITIONS["javascript"] | {
+    "interface_declaration", "type_alias_declaration", "enum_declaration", "abstract_class_declaration"}
+# Nodes wrapping a definition (decorators, export): the range is theirs, name and kind the definition's
+TS_WRAPPERS = {"decorated_definition", "export_statement"}
+
+# Heuristic fallback: a definition starts on a line matching one of these (group "name")
+_NAME = r"(?P<name>[A-Za-z_$][\w$]*)"
+HEURISTIC_DEFINITIONS = {
+    "python": [r"(async\s+)?def\s+" + _NAME, r"class\s+" + _NAME],
+    "rust": [r"(pub(\([^)]*\))?\s+)?(const\s+)?(async\s+)?(unsafe\s+)?(extern\s+\"\w+\"\s+)?fn\s+" + _NAME,
+             r"(pub(\([^)]*\))?\s+)?(struct|enum|trait|mod|union)\s+" + _NAME,
+             r"(unsafe\s+)?impl(<[^>]*>)?\s+(?P<name>[\w:<>, ]+?)\s*(where\b.*)?\{?$"],
+    "javascript": [r"(export\s+)?(default\s+)?(async\s+)?function\*?\s+" + _NAME,
+                   r"(export\s+)?(default\s+)?(abstract\s+)?class\s+" + _NAME,
+                   r"(export\s+)?(const|let|var)\s+" + _NAME + r"\s*=\s*(async\s+)?(function\b|\([^)]*\)\s*=>|\w+\s*=>)",
+                   r"(static\s+)?(async\s+)?(get\s+|set\s+)?(?!if\b|for\b|while\b|switch\b|catch\b|return\b)"
+                   + _NAME + r"\s*\([^)]*\)\s*\{$"],
+    "go": [r"func\s+(\([^)]*\)\s*)?" + _NAME, r"type\s+" + _NAME + r"\s+(struct|interface)"],
+    "java": [r"((public|private|protected|static|final|abstract|sealed)\s+)*(class|interface|enum|record)\s+" + _NAME,
+             r"((public|private|protected|static|final|abstract|synchronized)\s+)+[\w<>\[\], ]+\s+" + _NAME + r"\s*\("],
+    "ruby": [r"def\s+(self\.)?" + _NAME, r"(class|module)\s+" + _NAME],
+}
+for _lang in ("typescript", "tsx"):
+    HEURISTIC_DEFINITIONS[_lang] = HEURISTIC_DEFINITIONS["javascript"] + [
+        r"(export\s+)?(interface|enum|type)\s+" + _NAME]
+HEURISTIC_DEFINITIONS["kotlin"] = [r"((private|public|internal|data|open|abstract)\s+)*(class|object|interface)\s+" + _NAME,
+                                   r"((private|public|internal|override|suspend)\s+)*fun\s+" + _NAME]
+HEURISTIC_DEFINITIONS["csharp"] = HEURISTIC_DEFINITIONS["java"]
+HEURISTIC_DEFINITIONS["php"] = [r"((public|private|protected|static|abstract|final)\s+)*function\s+" + _NAME,
+                                r"((abstract|final)\s+)?(class|interface|trait)\s+" + _NAME]
+HEURISTIC_DEFINITIONS["swift"] = [r"((public|private|internal|static|override)\s+)*func\s+" + _NAME,
+                                  r"((public|private|internal|final)\s+)*(class|struct|enum|protocol|extension)\s+" + _NAME]
+_COMPILED = {lang: [re.compile(r"^\s*" + p) for p in patterns] for lang, patterns in HEURISTIC_DEFINITIONS.items()}
+
+# tree-sitter node type -> kind, first substring match wins
+_NODE_KINDS = [("method", "method"), ("constructor", "method"), ("function", "function"), ("lexical", "function"),
+               ("class", "class"), ("struct", "struct"), ("enum", "enum"), ("trait", "trait"), ("impl", "impl"),
+               ("interface", "interface"), ("protocol", "interface"), ("type", "type"), ("mod", "module"),
+               ("namespace", "module"), ("object", "class"), ("macro", "macro")]
+# Functions directly inside these are methods
+MEMBER_OF = {"class", "impl", "trait", "interface", "struct"}
+# Heuristic: the last of these keywords before the name gives the kind (none: a function/method)
+_KEYWORD_KINDS = {"def": "function", "fn": "function", "func": "function", "fun": "function",
+                  "function": "function", "class": "class", "object": "class", "record": "class",
+                  "struct": "struct", "union": "struct", "enum": "enum", "trait": "trait", "impl": "impl",
+                  "extension": "impl", "mod": "module", "module": "module", "interface": "interface",
+                  "protocol": "interface", "type": "type"}
+
+
+@dataclass
+class Chunk:
+    line: int  # 1-based, inclusive
+    end_line: int
+    text: str
+    symbol: str = ""  # "RetryPolicy.backoff", "Setup > Install"; "" outside definitions
+    kind: str = ""  # function, method, class, struct, impl, ..., section
+    language: str = ""
+
+    @property
+    def label(self) -> str:
+        """Kind and symbol ("method RetryPolicy.backoff"), for citations."""
+        return f"{self.kind} {self.symbol}".strip() if self.symbol else ""
+
+
+@dataclass
+class Definition:
+    """A definition's line range (1-based, inclusive) and its members."""
+    start: int
+    end: int
+    name: str
+    kind: str
+    children: List["Definition"] = field(default_factory=list)
+
+
+def _node_kind(node_type: str) -> str:
+    return next((kind for key, kind in _NODE_KINDS if key in node_type), "definition")
+
+
+def _keyword_kind(prefix: str) -> str:
+    keywords = [word for word in re.findall(r"\w+", prefix) if word in _KEYWORD_KINDS]
+    return _KEYWORD_KINDS[keywords[-1]] if keywords else "function"
+
+
+def windows(lines: List[str], first: int, size: int = CHUNK_LINES):
+    """(first line, last line, text) windows of lines starting at line `first`, blank edge lines trimmed."""
+    for start in range(0, len(lines), size):
+        window = lines[start:start + size]
+        filled = [i for i, line in enumerate(window) if line.strip()]
+        if filled:
+            top, bottom = filled[0], filled[-1]
+            yield first + start + top, first + start + bottom, "\n".join(window[top:bottom + 1])
+
+
+# ------------------------------------------------------------------------------
+# Parsers: text -> [Definition]
+# ------------------------------------------------------------------------------
+
+_ts_parsers: Dict[str, object] = {}
+
+
+def _tree_sitter_parser(language: str):
+    """A tree-sitter parser for the language, or None (not installed / no grammar)."""
+    if language not in _ts_parsers:
+        try:
+            from tree_sitter_language_pack import get_parser
+            _ts_parsers[language] = get_parser(language)
+        except Exception:  # ImportError, or a grammar the pack doesn't have
+            _ts_parsers[language] = None
+    return _ts_parsers[language]
+
+
+def _ts_name(node) -> str:
+    if node.type == "impl_item":
+        trait, kind = node.child_by_field_name("trait"), node.child_by_field_name("type")
+        target = kind.text.decode() if kind else "?"
+        return f"{trait.text.decode()} for {target}" if trait else target
+    named = node.child_by_field_name("name")
+    if named is not None:
+        return named.text.decode()
+    # C/C++ functions: the name is inside the declarator; Go types: inside the type_spec
+    stack = [c for c in (node.child_by_field_name("declarator"), *node.named_children) if c is not None]
+    while stack:
+        child = stack.pop(0)
+        if child.type in ("identifier", "field_identifier", "type_identifier", "property_identifier"):
+            return child.text.decode()
+        if child.type in ("type_spec", "variable_declarator") or "declarator" in child.type:
+            named = child.child_by_field_name("name")
+            if named is not None:
+                return named.text.decode()
+            stack[:0] = child.named_children
+    return ""
+
+
+def _ts_is_definition(node, types) -> bool:
+    if node.type == "lexical_declaration":  # const retry = async () => ..., not const LIMIT = 3
+        return b"=>" in node.text or b"function" in node.text
+    return node.type in types
+
+
+def _ts_definitions(node, types, parent_kind: str = "") -> List[Definition]:
+    found = []
+    for child in node.named_children:
+        target = child
+        if child.type in TS_WRAPPERS:
+            # Keep the decorators/export in the range, take the name and kind from what they wrap
+            target = child.child_by_field_name("definition") or child.child_by_field_name("declaration")
+        if target is not None and _ts_is_definition(target, types):
+            kind = _node_kind(target.type)
+            if kind == "function" and parent_kind in MEMBER_OF:
+                kind = "method"
+            found.append(Definition(child.start_point[0] + 1, child.end_point[0] + 1, _ts_name(target), kind,
+                                    _ts_definitions(target, types, kind)))
+        else:
+            found.extend(_ts_definitions(child, types, parent_kind))
+    return found
+
+
+def _python_definitions(body, in_class: bool = False) -> List[Definition]:
+    found = []
+    for node in body:
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef, ast.ClassDef)):
+            start = min([d.lineno for d in node.decorator_list] + [node.lineno])
+            is_class = isinstance(node, ast.ClassDef)
+            kind = "class" if is_class else "method" if in_class else "function"
+            found.append(Definition(start, node.end_lineno, node.name, kind,
+                                    _python_definitions(node.body, is_class)))
+    return found
+
+
+def _indent(line: str) -> int:
+    return len(line) - len(line.lstrip())
+
+
+def _heuristic_definitions(lines: List[str], language: str) -> List[Definition]:
+    patterns = _COMPILED.get(language, [])
+    closer = "end" if language == "ruby" else "}"
+    starts = []
+    for number, line in enumerate(lines):
+        for pattern in patterns:
+            match = pattern.match(line)
+            if match:
+                kind = _keyword_kind(line[:match.start("name")])
+                starts.append((number, _indent(line), match.group("name").strip(), kind))
+                break
+
+    flat = []
+    for number, indent, name, kind in starts:
+        end = len(lines) - 1
+        opening = lines[number].rstrip()
+        if language != "python" and (opening.endswith(";") or opening.count("{") and
+                                     opening.count("{") == opening.count("}")):
+            end = number  # Declared on one line: "struct Unit;", "fn id(x: T) -> T { x }"
+        for later in range(number + 1, len(lines) if end != number else number):
+            text = lines[later]
+            if not text.strip():
+                continue
+            if language == "python":
+                if _indent(text) <= indent:
+                    end = later - 1
+                    break
+            elif _indent(text) == indent and text.strip().startswith(closer):
+                end = later
+                break
+            elif _indent(text) < indent:
+                end = later - 1
+                break
+        while end > number and not lines[end].strip():
+            end -= 1
+        flat.append(Definition(number + 1, end + 1, name, kind))
+
+    # Nest by range: a definition inside another's lines is its member
+    roots: List[Definition] = []
+    stack: List[Definition] = []
+    for definition in flat:
+        while stack and definition.start > stack[-1].end:
+            stack.pop()
+        if stack and definition.kind == "function" and stack[-1].kind in MEMBER_OF:
+            definition.kind = "method"
+        (stack[-1].children if stack else roots).append(definition)
+        stack.append(definition)
+    return roots
+
+
+def definitions(text: str, language: str) -> List[Definition]:
+    """Top-level definitions (with members) by the best parser available."""
+    if language in TS_DEFINITIONS:
+        parser = _tree_sitter_parser(language)
+        if parser is not None:
+            try:
+                return _ts_definitions(parser.parse(text.encode()).root_node, TS_DEFINITIONS[language])
+            except Exception as e:
+                logger.debug(f"tree-sitter failed on {language}: {e}")
+    if language == "python":
+        try:
+            return _python_definitions(ast.parse(text).body)
+        except (SyntaxError, ValueError):
+            pass  # Python 2, templates... the heuristic copes
+    return _heuristic_definitions(text.splitlines(), language)
+
+
+# ------------------------------------------------------------------------------
+# Chunking
+# ------------------------------------------------------------------------------
+
+def _chunk_range(lines: List[str], start: int, end: int, symbol: str, kind: str, language: str,
+                 size: int = CHUNK_LINES) -> List[Chunk]:
+    return [Chunk(first, last, text, symbol, kind, language)
+            for first, last, text in windows(lines[start - 1:end], start, size)]
+
+
+def _leading_comments(lines: List[str], gap_start: int, start: int) -> int:
+    """Where a definition starts once the comments/attributes right above it are counted in."""
+    while start > gap_start and lines[start - 2].strip().startswith(COMMENT_PREFIXES):
+        start -= 1
+    return start
+
+
+def _chunk_definitions(lines: List[str], start: int, end: int, found: List[Definition], language: str,
+                       parent: Optional[Definition] = None, prefix: str = "") -> List[Chunk]:
+    """Chunks for lines start..end: each definition whole, or split into members; gaps windowed."""
+    chunks = []
+    position = start
+    gap_symbol, gap_kind = (prefix.rstrip("."), parent.kind) if parent else ("", "")
+    for definition in sorted(found, key=lambda d: d.start):
+        if definition.start < position or definition.end > end:
+            continue  # Overlapping (a heuristic misread); its lines go to the neighbours
+        first = _leading_comments(lines, position, definition.start)
+        chunks.extend(_chunk_range(lines, position, first - 1, gap_symbol, gap_kind, language))
+        symbol = prefix + definition.name
+        if definition.end - first < MAX_CHUNK_LINES:
+            chunks.extend(_chunk_range(lines, first, definition.end, symbol, definition.kind, language,
+                                       size=MAX_CHUNK_LINES))
+        elif definition.children:
+            chunks.extend(_chunk_definitions(lines, first, definition.end, definition.children,
+                                             language, definition, symbol + "."))
+        else:
+            chunks.extend(_chunk_range(lines, first, definition.end, symbol, definition.kind, language))
+        position = definition.end + 1
+    chunks.extend(_chunk_range(lines, position, end, gap_symbol, gap_kind, language))
+    return chunks
+
+
+def _markdown_chunks(lines: List[str]) -> List[Chunk]:
+    chunks = []
+    trail: List[str] = []
+    section_start, symbol = 1, ""
+    in_fence = False
+    for number, line in enumerate(lines + ["# "], 1):  # The sentinel closes the last section
+        if line.lstrip().startswith(("```", "~~~")):
+            in_fence = not in_fence
+        heading = None if in_fence else re.match(r"^(#{1,6})\s+(.*?)\s*#*\s*$", line)
+        if heading:
+            chunks.extend(_chunk_range(lines, section_start, number - 1, symbol, "section" if symbol else "",
+                                       "markdown"))
+            level = len(heading.group(1))
+            trail = trail[:level - 1] + [heading.group(2)]
+            symbol, section_start = " > ".join(t for t in trail if t), number
+    return chunks
+
+
+def chunk_file(path: str, text: str) -> List[Chunk]:
+    """A file's chunks: by definition for source code, by heading for Markdown, else by window."""
+    suffix = "." + path.rsplit(".", 1)[-1].lower() if "." in path.rsplit("/", 1)[-1] else ""
+    language = LANGUAGES.get(suffix, "")
+    lines = text.splitlines()
+    if language == "markdown":
+        return _markdown_chunks(lines)
+    if not language:
+        return _chunk_range(lines, 1, len(lines), "", "", "")
+    return _chunk_definitions(lines, 1, len(lines), definitions(text, language), language)
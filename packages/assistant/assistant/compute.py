@@ -0,0 +1,223 @@
+"""
+Compute - Which backend (Metal, CUDA or CPU) each model runs on, and moving them.
+
+Models register when they load, with the backends they support and, if
+they can move while running, a `move(backend)` callback:
+
+    voice  Moshi LM (MLX, voice server)   metal, cpu          on restart
+    stt    Vosk transcriber               cpu
+    vad    Silero VAD (torch)             metal, cuda, cpu    live
+
+Placement comes from config.model_placement ({"voice": "cpu"}), falling
+back to config.device for every model; "auto" picks the first available of
+metal, cuda, cpu that the model supports. A backend that isn't available
+here falls back to CPU with a warning.
+
+When the user's own work needs the GPU - free VRAM below
+config.gpu_reserve_gb, or the GPU busier than config.gpu_busy_percent -
+check() moves the config.gpu_yield_models that can move to the CPU, and
+back once the GPU has been calm for CALM_CHECKS checks in a row. The
+dashboard's compute job runs it; the control socket's "compute" command
+reports placements and VRAM (`xswarm dev compute`) and moves a model by
+hand.
+"""
+
+import logging
+from dataclasses import asdict, dataclass
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+METAL, CUDA, CPU = "metal", "cuda", "cpu"
+BACKENDS = (METAL, CUDA, CPU)  # "auto" preference order
+ALIASES = {"mps": METAL, "rocm": CUDA}  # config.device's names
+TORCH_DEVICES = {METAL: "mps", CUDA: "cuda", CPU: "cpu"}
+CALM_CHECKS = 3  # Calm checks in a row before yielded models return to the GPU
+
+
+class ComputeError(RuntimeError):
+    """A model can't be placed or moved where asked."""
+
+
+def normalize_backend(name: str) -> str:
+    name = ALIASES.get((name or "auto").lower(), (name or "auto").lower())
+    if name != "auto" and name not in BACKENDS:
+        raise ComputeError(f"Unknown backend '{name}' (use metal, cuda, cpu or auto)")
+    return name
+
+
+def _configured(name: str, setting: str) -> str:
+    try:
+        return normalize_backend(name)
+    except ComputeError as e:
+        logger.warning(f"{setting}: {e} - using auto")
+        return "auto"
+
+
+def available_backends() -> List[str]:
+    """The backends this machine has, GPU first; CPU always."""
+    found = []
+    try:
+        import torch
+        if torch.backends.mps.is_available():
+            found.append(METAL)
+        if torch.cuda.is_available():
+            found.append(CUDA)
+    except Exception:
+        pass
+    if METAL not in found:
+        try:
+            import mlx.core as mx
+            if mx.metal.is_available():
+                found.append(METAL)
+        except Exception:
+            pass
+    return [backend for backend in BACKENDS if backend in found] + [CPU]
+
+
+@dataclass
+class Placement:
+    name: str
+    backend: str
+    supported: Tuple[str, ...]
+    live: bool  # Can move while running (otherwise placement applies on restart)
+    yielded: bool = False  # Moved to the CPU to leave the GPU to the user
+
+
+class ComputeManager:
+    """Places models on backends and moves them off the GPU when the user needs it."""
+
+    def __init__(self, default: str = "auto", placement: Optional[Dict[str, str]] = None,
+                 yield_models: Iterable[str] = ("stt", "vad"), gpu_reserve_gb: float = 2.0,
+                 gpu_busy_percent: float = 90.0, available: Optional[List[str]] = None,
+                 probe: Optional[Callable[[], Any]] = None):
+        self.default = _configured(default, "device")
+        self.placement = {name: _configured(backend, f"model_placement.{name}")
+                          for name, backend in (placement or {}).items()}
+        self.yield_models = set(yield_models)
+        self.gpu_reserve_gb = gpu_reserve_gb
+        self.gpu_busy_percent = gpu_busy_percent
+        self._available = available
+        self.probe = probe  # () -> hardware.GPUCapability; default detect_gpu_capability
+        self.models: Dict[str, Placement] = {}
+        self._movers: Dict[str, Callable[[str], None]] = {}
+        self._calm = 0
+
+    @classmethod
+    def from_config(cls, config) -> "ComputeManager":
+        return cls(default=getattr(config, "device", "auto"),
+                   placement=getattr(config, "model_placement", {}) or {},
+                   yield_models=getattr(config, "gpu_yield_models", ("stt", "vad")),
+                   gpu_reserve_gb=float(getattr(config, "gpu_reserve_gb", 2.0)),
+                   gpu_busy_percent=float(getattr(config, "gpu_busy_percent", 90.0)))
+
+    @property
+    def available(self) -> List[str]:
+        if self._available is None:
+            self._available = available_backends()
+        return self._available
+
+    def resolve(self, name: str, supported: Iterable[str]) -> str:
+        """Where a model with these backends should run, per its placement."""
+        supported = tuple(supported)
+        wanted = self.placement.get(name, self.default)
+        usable = [backend for backend in BACKENDS if backend in supported and backend in self.available]
+        if not usable:
+            raise ComputeError(f"{name} supports {', '.join(supported)}; none is available here")
+        if wanted == "auto":
+            return usable[0]
+        if wanted not in usable:
+            fallback = CPU if CPU in usable else usable[0]
+            logger.warning(f"{name} can't run on {wanted} here, using {fallback}")
+            return fallback
+        return wanted
+
+    def register(self, name: str, supported: Iterable[str], move: Optional[Callable[[str], None]] = None) -> str:
+        """Place a model as it loads; `move(backend)` (called now) lets it move later. Returns the backend."""
+        supported = tuple(supported)
+        backend = self.resolve(name, supported)
+        if move:
+            move(backend)
+            self._movers[name] = move
+        self.models[name] = Placement(name, backend, supported, live=move is not None)
+        logger.info(f"🖥  {name} on {backend}")
+        return backend
+
+    def switch(self, name: str, backend: str) -> Placement:
+        """Move a running model; ComputeError if it can't go there (or can't move without a restart)."""
+        placement = self.models.get(name)
+        if placement is None:
+            raise ComputeError(f"No model '{name}' is loaded ({', '.join(self.models) or 'none yet'})")
+        backend = self.resolve(name, placement.supported) if backend == "auto" else normalize_backend(backend)
+        if backend not in placement.supported or backend not in self.available:
+            raise ComputeError(f"{name} can't run on {backend}")
+        if backend != placement.backend:
+            if not placement.live:
+                raise ComputeError(f"{name} moves on restart - set model_placement.{name}: {backend}")
+            self._movers[name](backend)
+            placement.backend = backend
+            logger.info(f"🖥  {name} moved to {backend}")
+        placement.yielded = False
+        return placement
+
+    def gpu(self) -> Optional[Any]:
+        """The GPU's current state (hardware.GPUCapability), or None without a GPU."""
+        try:
+            if self.probe is None:
+                from .hardware import detect_gpu_capability
+                self.probe = detect_gpu_capability
+            gpu = self.probe()
+        except Exception as e:
+            logger.debug(f"GPU probe failed: {e}")
+            return None
+        return gpu if gpu is not None and gpu.device_type != "cpu" else None
+
+    def check(self) -> List[str]:
+        """Yield the GPU to the user's work or take it back; returns what moved."""
+        gpu = self.gpu()
+        if gpu is None:
+            return []
+        pressed = gpu.vram_free_gb < self.gpu_reserve_gb or gpu.util_percent >= self.gpu_busy_percent
+        self._calm = 0 if pressed else self._calm + 1
+        moved = []
+        for placement in list(self.models.values()):
+            if pressed and not placement.yielded and placement.name in self.yield_models \
+                    and placement.live and placement.backend != CPU and CPU in placement.supported:
+                self.switch(placement.name, CPU)
+                placement.yielded = True
+                moved.append(f"{placement.name} → cpu (GPU needed: {gpu.vram_free_gb:.1f}GB free, "
+                             f"{gpu.util_percent:.0f}% busy)")
+            elif placement.yielded and self._calm >= CALM_CHECKS:
+                backend = self.resolve(placement.name, placement.supported)
+                self.switch(placement.name, backend)
+                moved.append(f"{placement.name} → {backend} (GPU free again)")
+        return moved
+
+    def report(self) -> Dict[str, Any]:
+        """Backends, the GPU's VRAM and each model's placement (the control socket's "compute")."""
+        gpu = self.gpu()
+        return {
+            "backends": self.available,
+            "gpu": None if gpu is None else {
+                "name": gpu.device_name, "type": gpu.device_type, "total_gb": round(gpu.vram_total_gb, 1),
+                "used_gb": round(gpu.vram_used_gb, 1), "free_gb": round(gpu.vram_free_gb, 1),
+                "busy_percent": round(float(gpu.util_percent), 1),
+            },
+            "models": [asdict(placement) for placement in self.models.values()],
+        }
+
+
+_manager: Optional[ComputeManager] = None
+
+
+def get_compute_manager() -> ComputeManager:
+    """Get the global compute manager (defaults until startup installs one from the config)."""
+    global _manager
+    if _manager is None:
+        _manager = ComputeManager()
+    return _manager
+
+
+def set_compute_manager(manager: ComputeManager) -> None:
+    global _manager
+    _manager = manager
@@ -6,17 +6,29 @@ Supports MPS (Mac M3), ROCm/CUDA (AMD/NVIDIA), and CPU fallback.
 import logging
 import yaml
 from pathlib import Path
-from typing import Literal, Optional, List
+from typing import Any, Literal, Optional, List, Dict
 from pydantic import BaseModel
 
+from .errors import ConfigError
+
 logger = logging.getLogger(__name__)
 
+# Set at startup from the hardware and command line rather than the file; reload() keeps them
+RUNTIME_FIELDS = {"is_debug_mode", "moshi_quality", "thinking_mode", "thinking_model", "embedding_mode", "text_only"}
+
+_loaded_path: Optional[Path] = None  # The file load_from_file() last read, for reload()
+
 
 class Config(BaseModel):
     """Application configuration"""
 
     # Device settings
     device: str = "auto"  # auto, mps, cuda, cpu
+    # Per-model backends and yielding the GPU - see compute.py
+    model_placement: Dict[str, str] = {}  # e.g. {"voice": "metal", "vad": "cpu"}; others follow device
+    gpu_yield_models: List[str] = ["stt", "vad"]  # Moved to the CPU while the user's work needs the GPU
+    gpu_reserve_gb: float = 2.0  # Free VRAM below this means the GPU is needed
+    gpu_busy_percent: float = 90.0  # ... as does the GPU being this busy
 
     # Audio settings
     sample_rate: int = 24000
@@ -29,9 +41,67 @@ class Config(BaseModel):
     wake_word: str | List[str] = "jarvis"  # Default, overridden by persona
     wake_word_model: Path = Path.home() / ".cache" / "vosk" / "vosk-model-small-en-us-0.15"
     wake_word_sensitivity: float = 0.7  # 0.0-1.0
+    require_wake_word: bool = False  # Ignore speech without the wake word...
+    follow_up_window: float = 8.0  # ...except replies this many seconds after an answer (0 = off) - see conversation_window.py
+    live_captions: bool = True  # Show the user's words under the chat while they speak - see captions.py
+    privacy_tray_icon: bool = False  # Mic state in the menu bar/tray too (needs the "tray" extra) - see privacy.py
+    control_socket: bool = True  # Local socket for `xswarm tray` - see control.py
+    local_api: bool = False  # REST API on 127.0.0.1 for scripts and `xswarm mcp` - see local_api.py
+    local_api_port: int = 8766
+    # What the local API, MCP clients and group scheduling see of events without their own visibility:
+    # "public" (everything), "busy" (the time only) or "private" (nothing) - see calendar_core.py
+    shared_visibility: str = "public"
+    # Read-only ICS feed of xswarm's events for calendar apps: "local" (served by the local API),
+    # "server" (uploaded, for Google Calendar and phones) or "" (off) - see ics_feed.py
+    ics_feed: str = ""
+    # Companion server for paired phone/web clients (pair with ctrl+y) - see pairing.py
+    companion_enabled: bool = False  # Listen from startup; otherwise only after pairing in this session
+    companion_host: str = "0.0.0.0"
+    companion_port: int = 8765
+    # Offer permessage-deflate on the companion and Media Streams WebSockets - see ws_framing.py
+    ws_compression: bool = True
+    # Turns of a conversation sent to the AI, and about how many tokens of them; older turns are summarized
+    # into a rolling synopsis instead of dropped (0 = no limit) - see working_memory.py
+    working_memory_turns: int = 20
+    working_memory_tokens: int = 8000
+    # Opus for Media Streams clients that offer it (needs the "opus" extra) - see opus_transport.py
+    opus_bitrate: int = 24000  # Bits per second
+    opus_jitter_ms: int = 60  # Audio held to reorder late packets (rounded to 20ms frames)
+    # Matrix bridge: chat from any Matrix client over E2EE (needs the "matrix" extra) - see matrix.py
+    matrix_enabled: bool = False
+    matrix_homeserver: str = "https://matrix.org"
+    matrix_user_id: Optional[str] = None  # The assistant's own account, e.g. "@my-xswarm:matrix.org"
+    matrix_allowed_users: List[str] = []  # Who may chat with it, e.g. ["@me:matrix.org"]; others are ignored
+    a11y_output: Optional[str] = None  # Plain-line event stream: "-" replaces the TUI, a path mirrors it - see accessibility.py
 
     # Server settings
     server_url: str = "http://localhost:3000"
+    # Look for signed releases of the xswarm binary and show "⬆ version" in the status bar (see self_update.py)
+    update_check: bool = True
+    update_channel: str = "stable"  # Release channel `xswarm self-update` installs from, e.g. "beta"
+    # Feature flags set locally, winning over the channel and the server, e.g. {"local_llm": false} - see flags.py
+    feature_flags: Dict[str, bool] = {}
+    # Redacted reports of unhandled errors in ~/.xswarm/crash_reports (see crash_report.py)
+    crash_reports: bool = True
+    crash_upload: bool = False  # Opt in to sending them to the server (only then does a report leave the machine)
+    # The log file (~/.xswarm/logs/xswarm.log) rotates past this size or age; archives are gzipped (log_rotation.py)
+    log_max_mb: float = 10.0
+    log_rotate_hours: float = 24.0
+    log_total_mb: float = 100.0  # The log and its archives together; the oldest archives go first
+    # Seconds a startup stage may take before it's a warning, e.g. {"models": 90} (see startup_profile.BUDGETS)
+    startup_budgets: Dict[str, float] = {}
+    # Entries the activity feed, chat and charts keep before dropping the oldest, e.g. {"activity": 1000} (buffers.py)
+    buffer_caps: Dict[str, int] = {}
+    # Retry/circuit-breaker behavior for server API calls (see api_client.ApiPolicy)
+    api_max_attempts: int = 3
+    api_backoff_base: float = 0.5  # Seconds; doubles each retry
+    api_backoff_max: float = 8.0
+    api_circuit_threshold: int = 5  # Consecutive failures before an endpoint fails fast
+    api_circuit_reset: float = 30.0  # Seconds before a failed endpoint is tried again
+    calendar_cache_seconds: float = 30.0  # Repeated calendar reads served locally this long, then revalidated (scheduler_client.py)
+    calendar_live: bool = True  # Subscribe to server calendar changes instead of waiting for calendar_sync (calendar_live.py)
+    clock_skew_warn_seconds: float = 60.0  # Warn when the local clock is off by more than this (clock_skew.py)
+    ntp_server: str = ""  # e.g. "pool.ntp.org" to also check the clock against NTP; off by default
 
     # Memory settings
     api_token: Optional[str] = None
@@ -39,8 +109,36 @@ class Config(BaseModel):
 
     # Voice configuration
     voice_enabled: bool = False  # Voice disabled by default
+    text_only: bool = False  # Skip voice entirely: no Moshi server, model downloads or audio devices
     moshi_quality: str = "q4"
     moshi_mode: str = "local"
+    # Cap on memory for all voice models (Moshi LM + codec + transcriber), e.g. 6.0.
+    # The largest Moshi variant that fits is used (see model_memory.select_voice_variant)
+    voice_model_memory_gb: Optional[float] = None
+    voice_mmap_weights: bool = True  # Lazily load weights from the checkpoint file (lower peak RAM)
+    # Listening cues (wake, done listening, error) in the persona's style - see earcons.py
+    earcons_enabled: bool = True
+    earcon_volume: float = 0.3  # Relative to speech
+    # Recordings of common utterances, played instantly and offline - see speech_cache.py
+    speech_cache: bool = True
+    speech_cache_mb: int = 50
+    speech_cache_after: int = 2  # Times a short text is said before it's kept too
+    # Throwaway STT/LLM/TTS passes so the first turn after a quiet spell isn't cold - see warmup.py
+    voice_warmup: bool = True  # Skipped on battery / in low power regardless
+    voice_warmup_idle_minutes: float = 30.0
+    # Playback levels, changed with "speak louder" / the set_volume tool - see volume.py
+    volume: float = 1.0  # Master, 0-1
+    speech_volume: float = 1.0  # Streams go up to 1.5 (boosted, clipped at full scale)
+    media_volume: float = 0.8
+    duck_level: float = 0.25  # Media drops to this fraction while the assistant speaks
+    whisper_level: float = 0.35  # Speech drops to this fraction for announcements whispered in a meeting
+    # Wake-up alarms (`xswarm dev alarm`, "wake me at 6:30") - see alarms.py
+    alarm_start_volume: float = 0.2  # Rises to full over alarm_ramp_seconds
+    alarm_ramp_seconds: float = 60.0
+    alarm_ring_minutes: float = 10.0  # Rings this long unanswered, then gives up
+    alarm_snooze_minutes: float = 9.0  # "Snooze" without a number of minutes
+    # Sums and unit/currency conversions answered without the model - see quick_math.py
+    exchange_rates: bool = True  # Refresh currency rates daily (open.er-api.com, no key)
     
     # Persona settings
     default_persona: Optional[str] = "Jarvis"  # Default to Jarvis persona
@@ -51,6 +149,10 @@ class Config(BaseModel):
     # UI Theme settings
     theme_base_color: str = "#8899aa"  # Base color for shade palette generation
     # Can be: hex color ("#8899aa"), or preset name ("blue-gray", "slate", "cyan", etc.)
+    # Dashboard keys: a preset ("default", "vi", "emacs") plus per-action overrides,
+    # e.g. {"next_tab": "ctrl+j, down", "copy_logs": ""} - see keymap.py (press ? for the active keys)
+    keymap_preset: str = "default"
+    keymap: Dict[str, Any] = {}
 
     # Service selection settings (auto-selected based on GPU capability)
     moshi_quality: str = "auto"  # "auto", "bf16", "q8", "q4", or "cloud"
@@ -77,6 +179,24 @@ class Config(BaseModel):
     # Local AI settings
     local_ai_provider: str = "disabled"  # disabled, ollama, lmstudio
     local_ai_model: str = ""  # Model name for local provider
+    local_ai_url: str = ""  # Default: localhost:11434 (Ollama) / localhost:1234 (LM Studio)
+    # Replies the user waits for - see latency.py
+    voice_latency_budget: float = 1.5  # Seconds without an answer before a filler ("Let me check.") is spoken
+    ai_hard_limit: float = 6.0  # Seconds before falling back to the smaller model, then the local one
+    # Conversation state machine - see conversation_state.py
+    barge_in: bool = True  # Talking over the assistant stops its reply (best with headphones)
+    barge_in_threshold: float = 0.02  # Mic RMS that counts as the user talking
+    # Record from the built-in mic while a Bluetooth headset plays, so it stays out of
+    # the low-quality call (HFP) profile - see audio_devices.py
+    bluetooth_mic_fallback: bool = False
+    thinking_timeout: float = 8.0  # Seconds to wait for a reply before listening again
+    interrupt_timeout: float = 3.0  # Seconds an interruption waits for the user to finish
+    # What may be said aloud - see speech_filter.py (a persona's speech_filter: block overrides these)
+    speech_profanity: str = "mask"  # mask (said as "bleep"), remove, off
+    speech_pii: bool = True  # Phone numbers, emails, card numbers and secrets aren't read out
+    speech_max_words: int = 150  # Longer replies stop to ask "Shall I continue?" (0 = no limit)
+    # Per channel (speech, call), winning over the persona's, e.g. {"call": {"max_words": 60}}
+    speech_filter_channels: Dict[str, Dict[str, Any]] = {}
 
     # Network Mode
     network_role: str = "standalone"  # standalone, master, slave
@@ -96,11 +216,179 @@ class Config(BaseModel):
     # Subscription features
     subscription_tier: str = "free"  # free, premium, enterprise
     has_phone_subscription: bool = False  # User purchased phone number add-on
+    quota_allow_overage: bool = False  # Paid tiers may go past their phone/SMS limits and be billed (see quota.py)
+
+    # Quiet hours - enforced for all notifications by notifications.NotificationPolicy
+    quiet_hours_enabled: bool = False
+    quiet_hours_start: str = "22:00"  # HH:MM local time
+    quiet_hours_end: str = "07:00"  # HH:MM local time (may be earlier than start)
+    # Per-channel minimum priority allowed during quiet hours, e.g. {"desktop": "high"}
+    # Channels: speech, desktop, suggestions, sms, call, companion, push. Default is emergency only.
+    quiet_hours_channels: Dict[str, str] = {}
+    # Do not disturb: quiet hours until turned off (tray menu, see tray.py)
+    do_not_disturb: bool = False
+    # While a calendar event shown as busy is under way, hold spoken announcements until it ends
+    # and whisper high-priority ones (events added "show as free" don't count)
+    meeting_quiet: bool = True
+
+    # ntfy.sh/Pushover pushes for selected events (see push.py). Providers, e.g.
+    # {"ntfy": {"server": "https://ntfy.sh", "token": "tk_..."}, "pushover": {"token": "...", "user": "...", "per_hour": 10}}
+    push_providers: Dict[str, Dict[str, Any]] = {}
+    # Event class (missed_reminder, error, task_done) -> targets, e.g. {"error": ["ntfy:my-alerts", "pushover:phone"]}
+    push_routes: Dict[str, List[str]] = {}
+    # Failed pushes retried after push_retry_seconds, doubling up to push_retry_max_seconds, then kept as dead letters
+    push_retry_attempts: int = 6
+    push_retry_seconds: float = 30.0
+    push_retry_max_seconds: float = 3600.0
+    long_job_seconds: float = 300  # Background jobs running this long record a "task" event when done (0 = never)
+
+    # Commands the server may ask this assistant to run (see remote_commands.py): speak, refresh_config, call
+    remote_commands: List[str] = ["speak", "refresh_config"]
+
+    # Days of activity and inbox history kept for `dev events` and "what did I miss?" (see events.py)
+    event_history_days: int = 90
+    # Days kept per data class (transcripts, audio, events, ai_log, logs, exports; 0 = forever), e.g. {"audio": 7}
+    # - deleted by the daily retention job, see retention.py
+    retention_days: Dict[str, int] = {}
+    retention_dry_run: bool = False  # The job only reports what it would delete (`xswarm dev retention`)
+
+    # Per-category notification preferences (see categories.py), e.g.
+    # {"work": {"weekends": false, "hours": "08:00-18:00"}, "finance": {"muted": true}}
+    category_notifications: Dict[str, Dict[str, Any]] = {}
+    # Reminder wording per category ("default" = all) and channel (see message_templates.py), e.g.
+    # {"default": {"speech": "Heads up: {{ title }} starts {{ when }}."}, "work": {"desktop_title": "Work"}}
+    reminder_templates: Dict[str, Dict[str, str]] = {}
+    # Event reminder channels besides the activity feed (see reminders.py): desktop, speech
+    reminder_methods: List[str] = ["desktop", "speech"]
+    # Leave out a channel acknowledged less than reminder_min_ack_rate of the time over 30 days
+    # (`xswarm dev reminders effectiveness`); the best one is always kept
+    reminder_methods_auto: bool = False
+    reminder_min_ack_rate: float = 0.2
+
+    # Emergency phrase (see emergency.py): said on its own, it texts/calls these contacts after a spoken
+    # countdown that "cancel" stops, repeating until one answers. "" = off. Contacts: [{"name", "phone"}]
+    emergency_phrase: str = ""
+    emergency_contacts: List[Dict[str, str]] = []
+    emergency_methods: List[str] = ["sms", "call"]
+    emergency_confirm_seconds: int = 10
+    emergency_repeat_minutes: float = 5.0
+    emergency_location: str = ""  # Sent with the alert, e.g. a home address
+
+    # People notes can be sent to ("send Alice the notes", see share_notes.py), e.g.
+    # {"Alice Chen": {"email": "alice@example.com", "phone": "+15551234567"}}
+    contacts: Dict[str, Dict[str, str]] = {}
+    # Wording of the shared notes email: Jinja "subject" and "body", e.g. {"subject": "Notes: {{ topic }}"}
+    share_templates: Dict[str, str] = {}
+
+    # Pre-meeting prep briefs (see meeting_prep.py) for events with attendees or a project
+    meeting_prep_minutes: int = 10  # How long before the start; 0 = off
+    meeting_prep_spoken: bool = False  # Read the brief aloud as well as showing it
+    # Per-category overrides, e.g. {"work": {"minutes": 20, "spoken": true}, "personal": {"enabled": false}}
+    meeting_prep_categories: Dict[str, Dict[str, Any]] = {}
+
+    # Evening review (see evening_review.py): started at this time (HH:MM) unless done already; "" = only when asked
+    evening_review_time: str = "20:00"
+    # Weekly memory digest (see memory_digest.py): saved on this day at this time (HH:MM); "" = only when asked
+    memory_digest_day: str = "sunday"
+    memory_digest_time: str = "18:00"
+    memory_digest_dir: str = ""  # A Markdown vault folder (e.g. in Obsidian); default ~/.xswarm/digests
+    memory_digest_email: bool = False  # Email it as well (to USER_EMAIL)
+    # Working hours tomorrow's time blocks are planned into
+    workday_start: str = "09:00"
+    workday_end: str = "18:00"
+
+    # Confirmation levels (see confirmation.py) by action class or tool name, e.g.
+    # {"delete": "explicit_yes", "run_command": "pin"}. Levels: silent, verbal, explicit_yes, pin
+    confirmation_levels: Dict[str, str] = {}
+    confirmation_pin_hash: Optional[str] = None  # sha256 of the spoken PIN; set via set_confirmation_pin
+    # Household profiles with capability scopes (see household.py), e.g.
+    # {"Emma": {"allow": ["read", "create"], "deny": ["communicate"], "cloud_ai": false, "speakers": ["emma"]}}
+    household_profiles: Dict[str, Dict[str, Any]] = {}
+    household_start_profile: str = ""  # Active profile at startup; "" = the owner
+    household_owner_speakers: List[str] = []  # Speaker identification labels for the owner
+    # Lock screen (ctrl+o) for shared/visible screens - see session_lock.py
+    lock_idle_minutes: float = 0  # Lock after this long without a key press, click or utterance (0 = never)
+    lock_unlock_with: str = "pin"  # "pin" (the confirmation PIN) or "account" (login password, needs python-pam)
+    # Masking of phone numbers, emails and credentials in logs, copied logs and memory - see redaction.py
+    redaction_enabled: bool = True
+    redaction_strict: bool = False  # Replace identifiers with a keyed hash ("[phone:1f9c0a2e]") instead of "[phone]"
+    # Extra name -> regex to redact, e.g. {"employee_id": "EMP-\\d{6}"}; "" turns a built-in off ({"phone": ""})
+    redaction_patterns: Dict[str, str] = {}
+    # Log of every request to a language model, for `xswarm dev ai log` (see ai_log.py):
+    # "off", "metadata" (no content), "truncated" (the first ai_log_chars of each message) or "full"
+    ai_log: str = "truncated"
+    ai_log_chars: int = 200
+    # Confidence (0-1, see intents.py) a call acting on an existing item needs before it runs without
+    # asking "did you want me to...?", by action class or tool name, e.g. {"delete": 0.9, "modify": 0}
+    intent_confidence_thresholds: Dict[str, float] = {}
+
+    # How numeric dates like 04/05/2025 are read (see dates.py): auto (system locale), mdy or dmy
+    date_order: str = "auto"
+    confirm_ambiguous_dates: bool = True  # Ask which day was meant when both readings are valid
+    fiscal_year_start_month: int = 1  # 1-12; "Q1"/"end of the quarter"/"fiscal year" count from this month
+    # How times are spoken (see verbalize.py): auto (system locale), 12h ("half past two") or 24h ("fourteen thirty")
+    clock_format: str = "auto"
+    # Home timezone planner times are stored in (see timezones.py); None = the OS zone
+    timezone: Optional[str] = None  # IANA name, e.g. "Europe/London"
+    travel_timezone: Optional[str] = None  # Travel mode: show/speak/enter times in this zone (set_travel_mode tool)
+    # Holidays warned about, briefed and skipped by skip_holidays series (see holidays.py): US, GB, CA, AU or "" (none)
+    holiday_region: str = "US"
+    custom_holidays: Dict[str, str] = {}  # Own days off: {"07-14": "Company picnic", "2026-12-28": "Office closed"}
+    # Minutes an event lasts when no length or end is said, by title keyword (see durations.py), e.g.
+    # {"lunch": 45, "1:1": 25}; your own past events of the same kind take precedence once there are a few
+    event_durations: Dict[str, int] = {}
+
+    # Event locations (see geocoding.py): geocoder is "nominatim" or "none"
+    geocoder: str = "nominatim"
+    geocoder_url: Optional[str] = None  # Self-hosted Nominatim; None = nominatim.openstreetmap.org
+    map_provider: str = "openstreetmap"  # Map links: openstreetmap, google or apple
+    travel_speed_kmh: float = 30.0  # Average door-to-door speed for travel-time conflicts
+
+    # Web search tool (see web_search.py): off by default since queries leave the machine.
+    # "none", "searxng" (web_search_url, e.g. a self-hosted instance) or "brave" (web_search_key)
+    web_search: str = "none"
+    web_search_url: Optional[str] = None
+    web_search_key: Optional[str] = None  # BRAVE_API_KEY in .env (debug mode)
+    web_search_results: int = 5  # Results the model answers from
+
+    # Project document search (see project_docs.py): Meilisearch holding the indexed project folders
+    meilisearch_url: str = "http://localhost:7700"
+    meilisearch_key: Optional[str] = None  # MEILI_MASTER_KEY in .env (debug mode); profiles get keys made from it
+    index_profile: Optional[str] = None  # Whose index this is on a shared Meilisearch; None = the login name
+    # Left out of the index besides .gitignore'd files, e.g. ["*.min.js", "fixtures/"] (.gitignore syntax)
+    project_ignore: List[str] = []
+    project_watch: bool = False  # Reindex saved files within seconds (see project_watch.py)
+    project_watch_debounce: float = 2.0  # Seconds a file must be quiet before it's reindexed
+
+    # Resource governor (see governor.py): defer indexing, memory consolidation and sync jobs
+    # while the machine is busy or xswarm (incl. the voice server) exceeds these ceilings
+    governor_cpu_ceiling: float = 50.0  # xswarm CPU, % of one core
+    governor_rss_ceiling_mb: Optional[float] = 8192
+    governor_busy_cpu_percent: float = 80.0  # Whole-machine CPU that counts as busy
+    power_mode: str = "auto"  # auto (low power on battery or when hot), performance, low_power - see power.py
+
+    # Settings for skill plugins (see skill_plugins.py), by plugin, checked against each one's schema, e.g.
+    # {"weather_uk": {"units": "metric"}}. Plugins are enabled with `xswarm dev plugins enable`
+    plugin_settings: Dict[str, Dict[str, Any]] = {}
+
+    # User Lua scripts hooked into transcripts, notifications and events (see scripting.py)
+    scripts_enabled: bool = False
+    scripts_dir: Optional[str] = None  # Default ~/.xswarm/scripts
+    script_timeout_ms: int = 100  # CPU time per hook call
+
+    # Per-job overrides for the background job scheduler (see scheduler.py), e.g.
+    # {"document_indexing": {"enabled": false}, "calendar_sync": {"cron": "*/30 7-22 * * *"}}
+    jobs: Dict[str, Dict[str, Any]] = {}
 
     class Config:
         """Pydantic configuration"""
         arbitrary_types_allowed = True
 
+    @property
+    def voice_active(self) -> bool:
+        """Whether to start the voice pipeline (voice enabled and not in text-only mode)."""
+        return self.voice_enabled and not self.text_only
+
     def detect_device(self):
         """
         Detect best available device for PyTorch.
@@ -178,13 +466,19 @@ class Config(BaseModel):
             if os.getenv("GROQ_API_KEY"):
                 config.groq_api_key = os.getenv("GROQ_API_KEY")
 
+            if os.getenv("MEILI_MASTER_KEY"):
+                config.meilisearch_key = os.getenv("MEILI_MASTER_KEY")
+
+            if os.getenv("BRAVE_API_KEY"):
+                config.web_search_key = os.getenv("BRAVE_API_KEY")
+
         except ImportError:
             pass  # python-dotenv not installed
 
         return config
 
     @classmethod
-    def load_from_file(cls, config_path: Optional[Path] = None) -> "Config":
+    def load_from_file(cls, config_path: Optional[Path] = None, strict: bool = False) -> "Config":
         """
         Load configuration from file (YAML or JSON).
         Prioritizes:
@@ -194,30 +488,37 @@ class Config(BaseModel):
 
         Args:
             config_path: Optional custom config path.
+            strict: Raise ConfigError for a missing custom path or an unreadable
+                file, instead of falling back to the defaults (for library use).
 
         Returns:
             Config: Loaded configuration
         """
+        global _loaded_path
         # 1. Try custom path
         if config_path and config_path.exists():
-            return cls._load_from_path(config_path)
+            _loaded_path = config_path
+            return cls._load_from_path(config_path, strict)
+        if config_path and strict:
+            raise ConfigError(f"No config file at {config_path}")
 
         # 2. Try project root config.json
         root_config = Path("config.json")
         if root_config.exists():
             logger.debug(f"Loading config from project root: {root_config.absolute()}")
-            return cls._load_from_json_root(root_config)
+            return cls._load_from_json_root(root_config, strict)
 
         # 3. Try default user config
         default_path = cls.get_config_path()
         if default_path.exists():
-            return cls._load_from_path(default_path)
+            _loaded_path = default_path
+            return cls._load_from_path(default_path, strict)
 
         # 4. Return default
         return cls()
 
     @classmethod
-    def _load_from_path(cls, path: Path) -> "Config":
+    def _load_from_path(cls, path: Path, strict: bool = False) -> "Config":
         """Helper to load from YAML/JSON based on extension"""
         try:
             with open(path, "r") as f:
@@ -239,11 +540,13 @@ class Config(BaseModel):
 
             return cls(**data)
         except Exception as e:
+            if strict:
+                raise ConfigError(f"Can't load config from {path}: {e}") from e
             logger.debug(f"Error loading config from {path}: {e}")
             return cls()
 
     @classmethod
-    def _load_from_json_root(cls, path: Path) -> "Config":
+    def _load_from_json_root(cls, path: Path, strict: bool = False) -> "Config":
         """Load from the specific project root config.json structure"""
         try:
             import json
@@ -251,6 +554,8 @@ class Config(BaseModel):
                 data = json.load(f)
             return cls._map_root_json_to_config(data)
         except Exception as e:
+            if strict:
+                raise ConfigError(f"Can't load config from {path}: {e}") from e
             logger.debug(f"Error loading root config.json: {e}")
             return cls()
 
@@ -290,12 +595,30 @@ class Config(BaseModel):
 
         return cls(**config_data)
 
-    def save_to_file(self, config_path: Optional[Path] = None):
+    def reload(self) -> List[str]:
+        """
+        Re-read the config file into this object, which the app's modules share,
+        and return the names of the fields that changed. Settings made at startup
+        (RUNTIME_FIELDS) and API keys the file leaves empty are kept.
+        """
+        fresh = self.load_from_file(_loaded_path)
+        changed = []
+        for name in self.__fields__:
+            value = getattr(fresh, name)
+            if name in RUNTIME_FIELDS or (name.endswith("_key") and not value):
+                continue
+            if getattr(self, name) != value:
+                setattr(self, name, value)
+                changed.append(name)
+        return changed
+
+    def save_to_file(self, config_path: Optional[Path] = None, strict: bool = False):
         """
         Save configuration to YAML file.
 
         Args:
             config_path: Optional custom config path. If None, uses ~/.config/xswarm/config.yaml
+            strict: Raise ConfigError when the file can't be written, instead of logging it
         """
         if config_path is None:
             config_path = self.get_config_path()
@@ -311,6 +634,8 @@ class Config(BaseModel):
                 yaml.safe_dump(data, f, default_flow_style=False, sort_keys=False)
             logger.debug(f"Configuration saved to {config_path}")
         except Exception as e:
+            if strict:
+                raise ConfigError(f"Can't save config to {config_path}: {e}") from e
             logger.debug(f"Error saving config to {config_path}: {e}")
 
     @staticmethod
@@ -0,0 +1,333 @@
+"""
+Confirmation Policy - How sure we must be before a tool runs.
+
+Every tool call is classified into an action class and each class maps to
+a confirmation level:
+
+- silent:       just do it
+- verbal:       do it, then say what was done (so a mishearing is noticed
+                and can be undone)
+- explicit_yes: hold the action until the user answers "yes"
+- pin:          hold the action until the user says their PIN
+
+Defaults are in DEFAULT_LEVELS; config.confirmation_levels overrides them
+per user, by action class or by tool name, e.g.
+    {"delete": "explicit_yes", "run_command": "pin"}
+
+Several deletes in quick succession ("delete all my reminders") count as
+bulk_delete, which requires an explicit yes by default. PIN-level actions
+fall back to an explicit yes when no PIN is configured.
+
+Calls that would otherwise run without asking are also held when they act
+on an item the user's words don't clearly point at (intents.py): below the
+class's confidence threshold (DEFAULT_CONFIDENCE_THRESHOLDS, overridden by
+config.intent_confidence_thresholds) the user is asked "Did you want me to
+cancel the 3pm standup?" first.
+"""
+
+import hashlib
+import logging
+import re
+import time
+from dataclasses import dataclass, field
+from enum import Enum
+from typing import Any, Callable, Dict, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+class ConfirmationLevel(str, Enum):
+    SILENT = "silent"
+    VERBAL = "verbal"
+    EXPLICIT_YES = "explicit_yes"
+    PIN = "pin"
+
+
+LEVEL_ORDER = [ConfirmationLevel.SILENT, ConfirmationLevel.VERBAL, ConfirmationLevel.EXPLICIT_YES, ConfirmationLevel.PIN]
+
+DEFAULT_LEVELS = {
+    "read": ConfirmationLevel.SILENT,
+    "create": ConfirmationLevel.SILENT,
+    "modify": ConfirmationLevel.SILENT,
+    "complete": ConfirmationLevel.VERBAL,
+    "delete": ConfirmationLevel.VERBAL,  # Undoable for 5 minutes (see undo.py)
+    "communicate": ConfirmationLevel.VERBAL,
+    "bulk_delete": ConfirmationLevel.EXPLICIT_YES,
+    "memory_purge": ConfirmationLevel.EXPLICIT_YES,
+    "system": ConfirmationLevel.EXPLICIT_YES,
+    "settings": ConfirmationLevel.EXPLICIT_YES,
+}
+
+# Minimum intents.score_call confidence to act without asking; riskier classes need more
+DEFAULT_CONFIDENCE_THRESHOLDS = {
+    "read": 0.0,
+    "create": 0.0,
+    "modify": 0.5,
+    "complete": 0.6,
+    "delete": 0.7,
+    "communicate": 0.7,
+    "bulk_delete": 0.8,
+    "memory_purge": 0.8,
+    "system": 0.8,
+    "settings": 0.8,
+}
+
+# Tools whose name doesn't say what they do
+TOOL_ACTION_CLASSES = {
+    "run_command": "system",
+    "forget_facts": "memory_purge",
+    "set_confirmation_level": "settings",
+    "set_confirmation_pin": "settings",
+    "check_off": "complete",
+    "mark_planning_done": "complete",
+    "quick_add": "create",
+    "capture_idea": "create",
+    "confirm_followup": "create",
+    "dismiss_followup": "modify",
+    "undo_last_action": "modify",
+    "schedule_task": "modify",
+    "reschedule_task": "modify",
+    "rollover_incomplete": "modify",
+    "optimize_day": "modify",
+    "sync_calendar_to_server": "modify",
+    "retry_failed_notifications": "communicate",
+    "screen_call": "communicate",
+    "invite_event_attendees": "communicate",
+    "send_email": "communicate",
+    "make_call": "communicate",
+    "switch_persona": "modify",
+    "change_theme": "modify",
+    "end_travel_mode": "modify",
+}
+
+_PREFIX_CLASSES = [
+    (("delete_", "remove_"), "delete"),
+    (("complete_",), "complete"),
+    (("add_", "create_", "log_"), "create"),
+    (("update_", "set_"), "modify"),
+    (("send_", "reply_"), "communicate"),
+]
+
+# More destructive calls than this within BULK_WINDOW seconds count as bulk_delete
+BULK_THRESHOLD = 2
+BULK_WINDOW = 60.0
+
+# How long a held action waits for its yes/PIN
+PENDING_TIMEOUT = 120.0
+
+_YES = re.compile(r"^\s*(?:yes|yeah|yep|yup|confirm(?:ed)?|do it|go ahead)(?:[,\s]+(?:please|do it|confirm(?:ed)?|go ahead|delete (?:it|them)))*\s*[.!]*\s*$", re.IGNORECASE)
+_NO = re.compile(r"^\s*(?:no|nope|cancel|stop|don'?t|never ?mind|abort)\b", re.IGNORECASE)
+_SPOKEN_DIGITS = {"zero": "0", "oh": "0", "one": "1", "two": "2", "three": "3", "four": "4",
+                  "five": "5", "six": "6", "seven": "7", "eight": "8", "nine": "9"}
+
+
+def classify_action(tool_name: str) -> str:
+    """Action class for a tool (see DEFAULT_LEVELS)."""
+    if tool_name in TOOL_ACTION_CLASSES:
+        return TOOL_ACTION_CLASSES[tool_name]
+    for prefixes, action_class in _PREFIX_CLASSES:
+        if tool_name.startswith(prefixes):
+            return action_class
+    return "read"
+
+
+def hash_pin(pin: str) -> str:
+    """Config stores only a hash of the PIN (config.confirmation_pin_hash)."""
+    return hashlib.sha256(pin.strip().encode()).hexdigest()
+
+
+def spoken_pin(text: str) -> str:
+    """Digits in an utterance, accepting words ("four two one one") as well as numerals."""
+    digits = [_SPOKEN_DIGITS.get(word, word) for word in re.findall(r"[a-z]+|\d+", text.lower())]
+    return "".join(d for d in digits if d.isdigit())
+
+
+def _level(value) -> Optional[ConfirmationLevel]:
+    try:
+        return ConfirmationLevel(value)
+    except ValueError:
+        logger.warning(f"Unknown confirmation level '{value}'")
+        return None
+
+
+@dataclass
+class PendingAction:
+    """A tool call held until the user confirms it."""
+    tool_name: str
+    args: Dict[str, Any]
+    level: ConfirmationLevel
+    description: str
+    created_at: float = field(default_factory=time.monotonic)
+    attempts: int = 0
+    registry: Any = field(default=None, repr=False)  # ToolRegistry to run it with once confirmed
+    question: str = ""  # Asked instead of the generic prompt ("Did you want me to cancel the 3pm standup?")
+
+    def prompt(self) -> str:
+        if self.question:
+            return self.question
+        if self.level == ConfirmationLevel.PIN:
+            return f"To {self.description}, say your PIN (or 'cancel')."
+        return f"Should I {self.description}? Say 'yes' to confirm or 'no' to cancel."
+
+
+@dataclass
+class Resolution:
+    """Outcome of feeding an utterance to a pending confirmation."""
+    status: str  # "confirmed", "declined", "retry", "expired"
+    action: Optional[PendingAction] = None
+    message: str = ""
+
+
+class ConfirmationPolicy:
+    """Maps tool calls to confirmation levels and holds actions awaiting a yes/PIN."""
+
+    MAX_PIN_ATTEMPTS = 3
+
+    def __init__(self, config=None, clock: Callable[[], float] = time.monotonic):
+        self.config = config
+        self.clock = clock
+        self.pending: Optional[PendingAction] = None
+        self._recent_deletes: List[float] = []
+        self._bulk_confirmed_at: Optional[float] = None
+        # Set by the UI to say/show what a verbal-level action did
+        self.on_verbal: Optional[Callable[[str], None]] = None
+
+    @property
+    def pin_hash(self) -> Optional[str]:
+        return getattr(self.config, "confirmation_pin_hash", None)
+
+    def _override(self, key: str) -> Optional[ConfirmationLevel]:
+        overrides = getattr(self.config, "confirmation_levels", None) or {}
+        return _level(overrides[key]) if key in overrides else None
+
+    def class_level(self, action_class: str) -> ConfirmationLevel:
+        """Configured level for an action class."""
+        return self._override(action_class) or DEFAULT_LEVELS.get(action_class, ConfirmationLevel.SILENT)
+
+    def level_for(self, tool_name: str) -> ConfirmationLevel:
+        """Configured level for a tool: tool override, then its class."""
+        return self._override(tool_name) or self.class_level(classify_action(tool_name))
+
+    def confidence_threshold(self, tool_name: str) -> float:
+        """Confidence a call needs to run without asking: tool override, then its class."""
+        overrides = getattr(self.config, "intent_confidence_thresholds", None) or {}
+        action_class = classify_action(tool_name)
+        for key in (tool_name, action_class):
+            if key in overrides:
+                try:
+                    return float(overrides[key])
+                except (TypeError, ValueError):
+                    logger.warning(f"Unknown confidence threshold '{overrides[key]}' for {key}")
+        if action_class == "delete" and self._in_burst():
+            action_class = "bulk_delete"
+        return DEFAULT_CONFIDENCE_THRESHOLDS.get(action_class, 0.0)
+
+    def required_level(self, tool_name: str) -> ConfirmationLevel:
+        """Level this particular call needs, escalating bursts of deletes to bulk_delete."""
+        level = self.level_for(tool_name)
+        if classify_action(tool_name) == "delete" and self._in_burst():
+            level = max(level, self.class_level("bulk_delete"), key=LEVEL_ORDER.index)
+        if classify_action(tool_name) == "settings" and self.pin_hash:
+            level = ConfirmationLevel.PIN  # Once a PIN is set, only the PIN holder can relax the policy
+        if level == ConfirmationLevel.PIN and not self.pin_hash:
+            level = ConfirmationLevel.EXPLICIT_YES
+        return level
+
+    def _in_burst(self) -> bool:
+        """True once enough deletes happened recently, until the user confirms the burst."""
+        now = self.clock()
+        self._recent_deletes = [t for t in self._recent_deletes if now - t < BULK_WINDOW]
+        if self._bulk_confirmed_at is not None and now - self._bulk_confirmed_at < BULK_WINDOW:
+            return False
+        return len(self._recent_deletes) >= BULK_THRESHOLD
+
+    def record_executed(self, tool_name: str, level: ConfirmationLevel, result: Any) -> None:
+        """After a call ran: count deletes toward a burst and read back verbal-level results."""
+        if classify_action(tool_name) == "delete":
+            self._recent_deletes.append(self.clock())
+        if level == ConfirmationLevel.VERBAL and self.on_verbal:
+            try:
+                self.on_verbal(str(result))
+            except Exception as e:
+                logger.debug(f"Verbal confirmation failed: {e}")
+
+    def hold(self, tool_name: str, args: Dict[str, Any], level: ConfirmationLevel,
+             registry: Any = None, question: str = "") -> PendingAction:
+        """Park a call until the user confirms. Replaces any earlier pending action."""
+        self.pending = PendingAction(tool_name, dict(args), level, describe_call(tool_name, args),
+                                     created_at=self.clock(), registry=registry, question=question)
+        return self.pending
+
+    def has_pending(self) -> bool:
+        if self.pending and self.clock() - self.pending.created_at > PENDING_TIMEOUT:
+            self.pending = None
+        return self.pending is not None
+
+    def resolve(self, text: str) -> Optional[Resolution]:
+        """
+        Interpret the user's reply to a pending confirmation.
+
+        Returns None when there is nothing pending or the reply is unrelated
+        (the pending action is then dropped, never executed by accident).
+        """
+        if self.pending is None:
+            return None
+        action = self.pending
+        if self.clock() - action.created_at > PENDING_TIMEOUT:
+            self.pending = None
+            return Resolution("expired", action, f"Confirmation timed out - I didn't {action.description}.")
+
+        if _NO.match(text):
+            self.pending = None
+            return Resolution("declined", action, f"OK, I won't {action.description}.")
+
+        if action.level == ConfirmationLevel.PIN:
+            pin = spoken_pin(text)
+            if pin and hash_pin(pin) == self.pin_hash:
+                return self._confirm(action)
+            if pin:
+                action.attempts += 1
+                if action.attempts >= self.MAX_PIN_ATTEMPTS:
+                    self.pending = None
+                    return Resolution("declined", action, f"Wrong PIN too many times - I won't {action.description}.")
+                return Resolution("retry", action, "That PIN didn't match. Try again or say 'cancel'.")
+        elif _YES.match(text):
+            return self._confirm(action)
+
+        # Anything else moves the conversation on without confirming
+        self.pending = None
+        return None
+
+    def _confirm(self, action: PendingAction) -> Resolution:
+        self.pending = None
+        if classify_action(action.tool_name) == "delete":
+            self._bulk_confirmed_at = self.clock()  # The rest of this burst goes ahead
+        return Resolution("confirmed", action)
+
+    def set_pin(self, pin: Optional[str]) -> None:
+        if self.config is not None:
+            self.config.confirmation_pin_hash = hash_pin(pin) if pin else None
+
+
+def describe_call(tool_name: str, args: Dict[str, Any]) -> str:
+    """Human phrasing of a tool call for confirmation prompts, e.g. "delete task (tsk-12)"."""
+    words = tool_name.replace("_", " ")
+    details = ", ".join(f"{v}" for v in args.values() if v not in ("", None, False))
+    return f"{words} ({details})" if details else words
+
+
+_policy: Optional[ConfirmationPolicy] = None
+
+
+def get_confirmation_policy() -> ConfirmationPolicy:
+    """Get the global confirmation policy (defaults until configured)."""
+    global _policy
+    if _policy is None:
+        _policy = ConfirmationPolicy()
+    return _policy
+
+
+def set_confirmation_policy(policy: ConfirmationPolicy) -> None:
+    """Install the policy built from the loaded Config (called at startup)."""
+    global _policy
+    _policy = policy
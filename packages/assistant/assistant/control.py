@@ -0,0 +1,166 @@
+"""
+Control Socket - Drive the running assistant from outside the TUI.
+
+The dashboard listens on a Unix socket (~/.config/xswarm/control.sock,
+owner-only) while it runs (config.control_socket). Clients such as the tray
+companion (`xswarm tray`, see tray.py) send one JSON object per line and
+get one back:
+
+    {"command": "mute"}               -> {"ok": true, "message": "✓ Mic muted", "muted": true}
+    {"command": "dnd", "on": false}   -> {"ok": true, "message": "✓ Do not disturb off", "dnd": false}
+    {"command": "nope"}               -> {"ok": false, "error": "Unknown command 'nope'"}
+
+Commands (handled by the dashboard):
+
+- status: mic state, muted, do not disturb, next appointment
+- mute: toggle the mic, or set it with "on": true/false
+- dnd: toggle do not disturb (see notifications.py), or set it with "on"
+- next: the next appointment
+- show: bring attention to the dashboard
+- compute: where each model runs and the GPU's VRAM; moves one with
+  "model" and "backend" (see compute.py)
+- quit: exit the assistant
+
+Scripts outside this package should use the xswarm-client package
+(packages/client), which speaks this protocol with a stable API.
+"""
+
+import asyncio
+import json
+import logging
+import os
+import socket
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Awaitable, Callable, Dict, Optional, Union
+
+logger = logging.getLogger(__name__)
+
+TIMEOUT = 3.0  # Seconds a client waits for the assistant
+
+Reply = Dict[str, Any]
+Handler = Callable[[Dict[str, Any]], Union[Reply, Awaitable[Reply]]]
+
+
+class ControlError(Exception):
+    """The assistant isn't running or didn't answer."""
+
+
+def socket_path() -> Path:
+    return Path.home() / ".config" / "xswarm" / "control.sock"
+
+
+def next_appointment(planner, now: Optional[datetime] = None) -> str:
+    """ "Dentist · today 16:00", or a note that nothing is coming up this week."""
+    from .timezones import format_dual
+
+    now = now or datetime.now()
+    upcoming = []
+    for event in planner.get_upcoming_events(days=7):
+        if "T" not in event.start_time:
+            continue  # All-day
+        start = datetime.fromisoformat(event.start_time)
+        if start >= now:
+            upcoming.append((start, event))
+    if not upcoming:
+        return "Nothing in the next 7 days"
+    start, event = min(upcoming, key=lambda item: item[0])
+    if start.date() == now.date():
+        day = "today"
+    elif start.date() == now.date() + timedelta(days=1):
+        day = "tomorrow"
+    else:
+        day = start.strftime("%a %d %b")
+    return f"{event.title} · {day} {format_dual(start)}"
+
+
+class ControlServer:
+    """Answers control commands on the local socket with the given handlers."""
+
+    def __init__(self, handlers: Dict[str, Handler], path: Optional[Path] = None):
+        self.handlers = handlers
+        self.path = path or socket_path()
+        self._server: Optional[asyncio.AbstractServer] = None
+
+    async def start(self) -> bool:
+        """Listen on the socket; False (logged) when it can't be created."""
+        try:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            if self.path.exists():
+                self.path.unlink()  # Left behind by an assistant that didn't exit cleanly
+            self._server = await asyncio.start_unix_server(self._serve, path=str(self.path))
+            os.chmod(self.path, 0o600)
+        except (OSError, NotImplementedError, AttributeError) as e:
+            logger.warning(f"Could not open the control socket at {self.path}: {e}")
+            self._server = None
+            return False
+        logger.info(f"Control socket listening at {self.path}")
+        return True
+
+    async def handle(self, request: Dict[str, Any]) -> Reply:
+        command = request.get("command")
+        handler = self.handlers.get(command)
+        if handler is None:
+            return {"ok": False, "error": f"Unknown command '{command}'"}
+        try:
+            reply = handler(request)
+            if asyncio.iscoroutine(reply):
+                reply = await reply
+        except Exception as e:
+            logger.warning(f"Control command '{command}' failed: {e}")
+            return {"ok": False, "error": str(e)}
+        return {"ok": True, **(reply or {})}
+
+    async def _serve(self, reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        try:
+            while True:
+                line = await reader.readline()
+                if not line:
+                    break
+                try:
+                    request = json.loads(line)
+                    if not isinstance(request, dict):
+                        raise ValueError("expected an object")
+                except ValueError as e:
+                    reply = {"ok": False, "error": f"Bad request: {e}"}
+                else:
+                    reply = await self.handle(request)
+                writer.write((json.dumps(reply) + "\n").encode())
+                await writer.drain()
+        except (ConnectionError, asyncio.IncompleteReadError):
+            pass
+        finally:
+            writer.close()
+
+    def close(self) -> None:
+        if self._server is not None:
+            self._server.close()
+            self._server = None
+        try:
+            self.path.unlink()
+        except OSError:
+            pass
+
+
+def send_command(command: str, path: Optional[Path] = None, timeout: float = TIMEOUT, **args) -> Reply:
+    """Send one command to the running assistant (blocking) and return its reply."""
+    path = path or socket_path()
+    try:
+        with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as sock:
+            sock.settimeout(timeout)
+            sock.connect(str(path))
+            sock.sendall((json.dumps({"command": command, **args}) + "\n").encode())
+            data = b""
+            while not data.endswith(b"\n"):
+                chunk = sock.recv(4096)
+                if not chunk:
+                    break
+                data += chunk
+    except (FileNotFoundError, ConnectionRefusedError):
+        raise ControlError("xSwarm isn't running")
+    except OSError as e:
+        raise ControlError(f"No answer from xSwarm: {e}")
+    try:
+        return json.loads(data)
+    except ValueError:
+        raise ControlError("No answer from xSwarm")
@@ -0,0 +1,194 @@
+"""
+Conversation State - The voice conversation as one explicit state machine.
+
+    IDLE --start--> LISTENING --user_done--> THINKING --reply_audio--> SPEAKING
+                        ^                        |                      |    |
+                        +-------- timeout -------+                      |    |
+                        +------------------- reply_done ----------------+    |
+                        |                                                user_speech
+                        |                                                    v
+                        +-------- timeout --------- INTERRUPTED <-----------+
+                                                        |
+                                                    user_done --> THINKING
+
+Any state goes to ERROR on `error` (RECOVER returns to LISTENING) and to
+IDLE on `stop`. An event the current state has no transition for is
+ignored (fire() returns False), so a late reply_done or a duplicate start
+can't leave the conversation somewhere impossible.
+
+Barge-in: the user starting to talk while the assistant speaks is
+`user_speech` from SPEAKING (ignored when config.barge_in is off). INTERRUPTED flushes queued playback and drops
+Moshi's audio until the user finishes (then THINKING) or goes quiet for
+interrupt_timeout (back to LISTENING).
+
+Events come from the conversation loop: reply_audio() for each loud chunk
+of Moshi output, observe_user() with the mic VAD (edges become user_speech
+/ user_done). tick() turns time into events - THINKING without a reply for
+thinking_timeout, SPEAKING with no audio for speaking_tail (the reply is
+over), ERROR for error_recovery - and VoiceBridgeOrchestrator runs it as the
+supervised "conversation state" task. Every transition is kept in history
+and reported to the task supervisor (TaskSupervisor.states), which the
+control socket's status shows.
+"""
+
+import asyncio
+import logging
+import threading
+import time
+from collections import deque
+from dataclasses import dataclass
+from enum import Enum
+from typing import Callable, Deque, Dict, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+HISTORY = 50  # Transitions kept for debugging
+
+
+class ConversationState(Enum):
+    IDLE = "idle"
+    LISTENING = "listening"
+    THINKING = "thinking"
+    SPEAKING = "speaking"
+    INTERRUPTED = "interrupted"
+    ERROR = "error"
+
+
+class Event(str, Enum):
+    START = "start"
+    STOP = "stop"
+    USER_SPEECH = "user_speech"  # The user started talking
+    USER_DONE = "user_done"  # ... and stopped
+    REPLY_AUDIO = "reply_audio"  # Moshi is producing sound
+    REPLY_DONE = "reply_done"
+    TIMEOUT = "timeout"
+    ERROR = "error"
+    RECOVER = "recover"
+
+
+S, E = ConversationState, Event
+TRANSITIONS: Dict[Tuple[ConversationState, Event], ConversationState] = {
+    (S.IDLE, E.START): S.LISTENING,
+    (S.LISTENING, E.USER_DONE): S.THINKING,
+    (S.LISTENING, E.REPLY_AUDIO): S.SPEAKING,  # Full duplex: Moshi may answer before the VAD says the user is done
+    (S.THINKING, E.REPLY_AUDIO): S.SPEAKING,
+    (S.THINKING, E.USER_SPEECH): S.LISTENING,  # The user wasn't finished
+    (S.THINKING, E.TIMEOUT): S.LISTENING,
+    (S.SPEAKING, E.REPLY_DONE): S.LISTENING,
+    (S.SPEAKING, E.USER_SPEECH): S.INTERRUPTED,
+    (S.INTERRUPTED, E.USER_DONE): S.THINKING,
+    (S.INTERRUPTED, E.TIMEOUT): S.LISTENING,
+    (S.ERROR, E.RECOVER): S.LISTENING,
+    (S.ERROR, E.START): S.LISTENING,
+}
+for _state in S:
+    if _state != S.IDLE:
+        TRANSITIONS[(_state, E.STOP)] = S.IDLE
+        TRANSITIONS.setdefault((_state, E.ERROR), S.ERROR)
+TRANSITIONS.pop((S.ERROR, E.ERROR))
+
+
+@dataclass(frozen=True)
+class Transition:
+    old: ConversationState
+    new: ConversationState
+    event: Event
+    reason: str
+    at: float
+
+
+class ConversationMachine:
+    """The conversation's state, changed only by events with a transition from it."""
+
+    def __init__(self, on_transition: Optional[Callable[[Transition], None]] = None, name: str = "conversation",
+                 thinking_timeout: float = 8.0, interrupt_timeout: float = 3.0, speaking_tail: float = 0.6,
+                 error_recovery: float = 2.0, barge_in: bool = True, supervisor=None,
+                 clock: Callable[[], float] = time.monotonic):
+        self.name = name
+        self.thinking_timeout = thinking_timeout
+        self.interrupt_timeout = interrupt_timeout
+        self.speaking_tail = speaking_tail  # Quiet that ends a reply
+        self.error_recovery = error_recovery
+        self.barge_in = barge_in  # False: the user talking while the assistant speaks is ignored
+        self.supervisor = supervisor  # Default: the global task supervisor
+        self.clock = clock
+        self.listeners: List[Callable[[Transition], None]] = [on_transition] if on_transition else []
+        self.state = ConversationState.IDLE
+        self.since = clock()
+        self.history: Deque[Transition] = deque(maxlen=HISTORY)
+        self.ignored = 0  # Events with no transition from the state they arrived in
+        self._user_speaking = False
+        self._last_audio = 0.0
+        self._lock = threading.RLock()
+
+    @classmethod
+    def from_config(cls, config, on_transition=None) -> "ConversationMachine":
+        return cls(on_transition, thinking_timeout=float(getattr(config, "thinking_timeout", 8.0)),
+                   interrupt_timeout=float(getattr(config, "interrupt_timeout", 3.0)),
+                   barge_in=bool(getattr(config, "barge_in", True)))
+
+    def add_listener(self, listener: Callable[[Transition], None]) -> None:
+        self.listeners.append(listener)
+
+    def fire(self, event: Event, reason: str = "") -> bool:
+        """Apply an event; False (and nothing changes) when the state has no transition for it."""
+        with self._lock:
+            new = TRANSITIONS.get((self.state, Event(event)))
+            if new is None:
+                self.ignored += 1
+                logger.debug(f"{self.name}: {Event(event).value} ignored while {self.state.value}")
+                return False
+            now = self.clock()
+            transition = Transition(self.state, new, Event(event), reason, now)
+            self.state, self.since = new, now
+            self.history.append(transition)
+        if transition.old != transition.new:
+            logger.debug(f"{self.name}: {transition.old.value} -> {new.value} ({transition.event.value})")
+        self._report(transition)
+        for listener in list(self.listeners):
+            try:
+                listener(transition)
+            except Exception as e:
+                logger.debug(f"{self.name} listener failed: {e}")
+        return True
+
+    def _report(self, transition: Transition) -> None:
+        from .supervisor import get_task_supervisor
+        supervisor = self.supervisor or get_task_supervisor()
+        supervisor.report_state(self.name, transition.new.value, transition.reason or transition.event.value)
+
+    def reply_audio(self, loud: bool) -> None:
+        """A chunk of Moshi output: loud chunks are the reply going on (silence is just the duplex stream)."""
+        if loud:
+            self._last_audio = self.clock()
+            if self.state != ConversationState.SPEAKING:
+                self.fire(Event.REPLY_AUDIO)
+
+    def observe_user(self, speaking: bool) -> None:
+        """The mic VAD's current verdict; its edges are user_speech / user_done."""
+        if speaking != self._user_speaking:
+            self._user_speaking = speaking
+            if speaking and self.state == ConversationState.SPEAKING and not self.barge_in:
+                return
+            self.fire(Event.USER_SPEECH if speaking else Event.USER_DONE)
+
+    def tick(self) -> bool:
+        """Fire the timeout-driven event that is due, if any."""
+        elapsed = self.clock() - self.since
+        if self.state == ConversationState.THINKING and elapsed >= self.thinking_timeout:
+            return self.fire(Event.TIMEOUT, "no reply")
+        if self.state == ConversationState.INTERRUPTED and elapsed >= self.interrupt_timeout:
+            return self.fire(Event.TIMEOUT, "user went quiet")
+        if (self.state == ConversationState.SPEAKING
+                and self.clock() - max(self._last_audio, self.since) >= self.speaking_tail):
+            return self.fire(Event.REPLY_DONE)
+        if self.state == ConversationState.ERROR and elapsed >= self.error_recovery:
+            return self.fire(Event.RECOVER)
+        return False
+
+    async def run(self, user_speaking: Callable[[], bool], interval: float = 0.1) -> None:
+        """Feed the VAD and timeouts in (runs supervised while the conversation does)."""
+        while True:
+            self.observe_user(bool(user_speaking()))
+            self.tick()
+            await asyncio.sleep(interval)
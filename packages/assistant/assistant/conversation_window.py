@@ -0,0 +1,108 @@
+"""
+Follow-up Window - Answer the assistant without saying the wake word again.
+
+    "Jarvis, what's on tomorrow?"   -> "Standup at 9 and the dentist at 4."
+    "move the dentist to 5"         -> acted on: within the window
+    ... 8 seconds of silence ...
+    "what's the weather"            -> ignored: no wake word
+
+When utterances need the wake word (config.require_wake_word, or a replay
+scenario's `wake_word:`), the window opens each time the assistant finishes
+speaking and stays open for config.follow_up_window seconds (0 turns it
+off). A reply within it is acted on as if it had started with the wake
+word; the window then closes until the next answer reopens it. Silence
+lets it run out, and "thanks, that's all" closes it straight away.
+
+The dashboard shows the time left on the audio visualizer.
+"""
+
+import re
+import time
+from typing import Callable, List, Optional
+
+DEFAULT_SECONDS = 8.0
+
+_DISMISS = re.compile(
+    r"^\s*(?:(?:ok(?:ay)?|great|perfect|cool|no)[,.!\s]+)*"
+    r"(?:(?:thanks|thank\s+you|cheers)\b[,.!\s]*(?:\w+[,.!\s]*)?)?"
+    r"(?:(?:that'?s|that\s+is)\s+(?:all|it|everything)(?:\s+for\s+now)?|i'?m\s+(?:done|good|all\s+set)|"
+    r"all\s+done|nothing\s+else|(?:good)?bye)?[,.!\s]*$",
+    re.IGNORECASE)
+_CLOSING = re.compile(
+    r"\b(?:thanks|thank\s+you|cheers|that'?s\s+(?:all|it|everything)|that\s+is\s+(?:all|it)|"
+    r"i'?m\s+(?:done|good|all\s+set)|all\s+done|nothing\s+else|(?:good)?bye)\b",
+    re.IGNORECASE)
+
+
+def strip_wake_word(text: str, wake_words: List[str]) -> Optional[str]:
+    """
+    The utterance with its wake word removed, or None if no wake word was
+    said (matching is per word, like WakeWordDetector).
+    """
+    words = text.split()
+    lowered = [w.strip(",.!?").lower() for w in words]
+    for wake_word in wake_words:
+        parts = wake_word.split()
+        for i in range(len(lowered) - len(parts) + 1):
+            if lowered[i:i + len(parts)] == parts:
+                return " ".join(words[:i] + words[i + len(parts):]).strip(" ,")
+    return None
+
+
+def is_dismissal(text: str) -> bool:
+    """True for "thanks, that's all", "that's it", "thank you Jarvis", "I'm done"..."""
+    return bool(_CLOSING.search(text or "")) and bool(_DISMISS.match(text or ""))
+
+
+class FollowUpWindow:
+    """The few seconds after an answer when no wake word is needed."""
+
+    def __init__(self, seconds: float = DEFAULT_SECONDS, clock: Callable[[], float] = time.monotonic):
+        self.seconds = seconds
+        self.clock = clock
+        self.opened_at: Optional[float] = None
+
+    @classmethod
+    def from_config(cls, config, clock: Callable[[], float] = time.monotonic) -> "FollowUpWindow":
+        seconds = getattr(config, "follow_up_window", DEFAULT_SECONDS)
+        return cls(max(0.0, float(DEFAULT_SECONDS if seconds is None else seconds)), clock)
+
+    def open(self) -> None:
+        """The assistant just finished answering: start (or restart) the window."""
+        if self.seconds > 0:
+            self.opened_at = self.clock()
+
+    def close(self) -> None:
+        self.opened_at = None
+
+    def remaining(self) -> float:
+        """Seconds left, 0 when closed."""
+        if self.opened_at is None:
+            return 0.0
+        left = self.seconds - (self.clock() - self.opened_at)
+        if left <= 0:
+            self.opened_at = None  # Ran out in silence
+            return 0.0
+        return left
+
+    @property
+    def is_open(self) -> bool:
+        return self.remaining() > 0
+
+    def admit(self, text: str, wake_words: List[str]) -> Optional[str]:
+        """
+        What to act on from an utterance: the text after the wake word, the
+        whole text as a follow-up while the window is open, or None to
+        ignore it (no wake word, or a dismissal like "thanks, that's all").
+        The wake word on its own opens the window for what comes next.
+        """
+        command = strip_wake_word(text, wake_words)
+        if command is None and not self.is_open:
+            return None
+        if command == "":
+            self.open()
+            return None
+        self.close()  # Reopened when the assistant answers
+        if is_dismissal(text if command is None else command):
+            return None
+        return text if command is None else command
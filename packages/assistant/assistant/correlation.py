@@ -0,0 +1,104 @@
+"""
+Correlation - Which events were set off by the same interaction.
+
+An interaction starts when the user is heard (past the wake word) or types,
+or when new messages come in, and gets a short ID. Everything it sets off
+carries that ID: the turn (what speech-to-text heard), the AI log entries,
+the reply, tool calls, activity-feed entries and notifications. The
+activity feed groups them into one collapsible thread instead of
+interleaving them with everything else, and
+
+    xswarm dev events query --thread 3fa9c1
+
+lists one thread from the event history (events.py).
+
+The ID is a context variable, so tasks started while handling an
+interaction (asyncio.create_task, asyncio.to_thread) carry it without it
+being passed around. Callbacks arriving from elsewhere - the voice bridge
+answering, a worker thread - pick the latest interaction up again with
+resume(), if it started within THREAD_WINDOW. Callbacks run with
+handle(), so an ID they set doesn't stay behind in their caller.
+"""
+
+import time
+import uuid
+from contextlib import contextmanager
+from contextvars import ContextVar, copy_context
+from dataclasses import dataclass
+from typing import Any, Callable, Collection, Dict, Iterator, List, Optional, Sequence, Tuple
+
+THREAD_WINDOW = 120.0  # Seconds a late callback still belongs to the latest interaction
+
+
+@dataclass(frozen=True)
+class Interaction:
+    id: str
+    source: str  # voice, chat, inbox
+    started: float  # time.monotonic()
+
+
+_current: ContextVar[Optional[Interaction]] = ContextVar("interaction", default=None)
+_latest: Optional[Interaction] = None
+
+
+def begin(source: str, now: Optional[float] = None) -> str:
+    """A new interaction for the rest of this task (and what it starts); returns its ID."""
+    global _latest
+    _latest = Interaction(uuid.uuid4().hex[:6], source, time.monotonic() if now is None else now)
+    _current.set(_latest)
+    return _latest.id
+
+
+@contextmanager
+def interaction(source: str) -> Iterator[str]:
+    """begin() for a block only, e.g. one inbox sync."""
+    token = _current.set(None)
+    try:
+        yield begin(source)
+    finally:
+        _current.reset(token)
+
+
+def current_id() -> Optional[str]:
+    """The ID of the interaction being handled, if any."""
+    current = _current.get()
+    return current.id if current else None
+
+
+def resume(now: Optional[float] = None) -> Optional[str]:
+    """Carry on the latest interaction in a callback that lost it, unless it's older than THREAD_WINDOW."""
+    if _current.get() is None and _latest is not None:
+        if (time.monotonic() if now is None else now) - _latest.started <= THREAD_WINDOW:
+            _current.set(_latest)
+    return current_id()
+
+
+def handle(source: Optional[str], callback: Callable[..., Any], *args: Any) -> Any:
+    """Run a callback in a context of its own: a new interaction from source, or the latest resumed (None)."""
+    def run():
+        if source:
+            begin(source)
+        else:
+            resume()
+        return callback(*args)
+    return copy_context().run(run)
+
+
+def thread_rows(entries: Sequence[Dict[str, Any]], collapsed: Collection[str] = ()) -> List[Tuple[Dict[str, Any], str]]:
+    """
+    Feed entries ({"thread": ID or None, ...}) grouped by thread, each group
+    where its first entry was, with a marker: "" for an entry on its own,
+    "▾" / "▸ +N" for the first of an open / collapsed thread, "└" for the rest.
+    """
+    groups: Dict[Any, List[Dict[str, Any]]] = {}
+    for n, entry in enumerate(entries):
+        groups.setdefault(entry.get("thread") or n, []).append(entry)
+    rows = []
+    for key, group in groups.items():
+        if len(group) == 1:
+            rows.append((group[0], ""))
+        elif key in collapsed:
+            rows.append((group[0], f"▸ +{len(group) - 1}"))
+        else:
+            rows += [(group[0], "▾")] + [(entry, "└") for entry in group[1:]]
+    return rows
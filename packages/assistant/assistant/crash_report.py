@@ -0,0 +1,288 @@
+"""
+Crash reports - What the assistant was doing when it failed, kept locally.
+
+An error nothing handled - in the main thread, another thread, an asyncio
+task or the dashboard - is saved as a report:
+
+- the error and its stack trace (file, line, function and code only; no
+  variable values), redacted like the logs (redaction.py) with the home
+  folder shortened to ~
+- the last LOG_TAIL lines of the log file, redacted
+- the state of each subsystem the task supervisor knows (supervisor.py),
+  with the ones it gave up on
+- the version, platform and feature flags (flags.py)
+
+Fatal errors Python can't catch (a crash in native code) are written by
+faulthandler to fatal.log and turned into a report at the next start.
+
+    xswarm dev crash-report list
+    xswarm dev crash-report show 3fa9c1
+    xswarm dev crash-report send 3fa9c1     # upload one report, asked for by hand
+
+Nothing leaves the machine unless the user opts in with
+config.crash_upload: then the crash_upload job sends the reports not sent
+yet (POST /api/crash-reports) at start and every few hours. The dashboard
+mentions a crash from the last run either way.
+
+Storage: ~/.xswarm/crash_reports/<id>.json (the newest MAX_REPORTS kept)
+"""
+
+import faulthandler
+import json
+import logging
+import platform
+import sys
+import threading
+import traceback
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+from . import __version__, endpoints
+from .redaction import Redactor, get_redactor
+
+logger = logging.getLogger(__name__)
+
+REPORTS_DIR = Path.home() / ".xswarm" / "crash_reports"
+LOG_TAIL = 100  # Lines of the log file kept in a report
+MAX_REPORTS = 50
+
+
+@dataclass
+class CrashReport:
+    id: str
+    time: str  # ISO, local time
+    where: str  # main, thread <name>, task <name>, dashboard, fatal
+    error: str  # "KeyError: 'voice'"
+    stack: List[str] = field(default_factory=list)
+    log_tail: List[str] = field(default_factory=list)
+    subsystems: Dict[str, str] = field(default_factory=dict)
+    flags: Dict[str, bool] = field(default_factory=dict)
+    version: str = __version__
+    platform: str = ""
+    uploaded_at: Optional[str] = None
+    seen: bool = False  # Mentioned by the dashboard after the crash
+
+    def summary(self) -> str:
+        sent = "  ↑ sent" if self.uploaded_at else ""
+        return f"{self.id}  {self.time[:19].replace('T', ' ')}  v{self.version:<8} {self.where:<14} {self.error}{sent}"
+
+    def text(self) -> str:
+        lines = [f"Crash {self.id} at {self.time[:19].replace('T', ' ')} in {self.where}",
+                 f"xSwarm {self.version} on {self.platform}", "", self.error, ""] + self.stack
+        if self.subsystems:
+            lines += ["", "Subsystems:"] + [f"  {name}: {state}" for name, state in sorted(self.subsystems.items())]
+        if self.flags:
+            flags = ", ".join(f"{name}={'on' if on else 'off'}" for name, on in sorted(self.flags.items()))
+            lines += ["", f"Flags: {flags}"]
+        if self.log_tail:
+            lines += ["", f"Log (last {len(self.log_tail)} lines):"] + [f"  {line}" for line in self.log_tail]
+        if self.uploaded_at:
+            lines += ["", f"Sent to the server {self.uploaded_at[:19].replace('T', ' ')}"]
+        return "\n".join(lines)
+
+
+def subsystem_states() -> Dict[str, str]:
+    """What the task supervisor knows: each subsystem's last state, and the ones it gave up on."""
+    from .supervisor import get_task_supervisor
+
+    supervisor = get_task_supervisor()
+    states = {name: f"{report.state} ({report.reason})" if report.reason else report.state
+              for name, report in supervisor.states.items()}
+    states.update({name: f"failed after {failure.restarts} restarts: {failure.error}"
+                   for name, failure in supervisor.failed.items()})
+    return states
+
+
+def flag_states() -> Dict[str, bool]:
+    from .flags import get_feature_flags
+
+    return {flag.name: on for flag, on, _source in get_feature_flags().rows()}
+
+
+def log_file() -> Optional[Path]:
+    """The file the root logger writes to, if any."""
+    for handler in logging.getLogger().handlers:
+        if isinstance(handler, logging.FileHandler):
+            return Path(handler.baseFilename)
+    return None
+
+
+class CrashReporter:
+    """Saves crash reports to `directory`; uploads them only when `upload` (config.crash_upload) is on."""
+
+    def __init__(self, directory: Path = REPORTS_DIR, redactor: Optional[Redactor] = None, upload: bool = False,
+                 log_path: Optional[Path] = None, states: Callable[[], Dict[str, str]] = subsystem_states,
+                 flags: Callable[[], Dict[str, bool]] = flag_states, clock: Callable[[], datetime] = datetime.now):
+        self.directory = directory
+        self.redactor = redactor
+        self.upload = upload
+        self.log_path = log_path
+        self.states = states
+        self.flags = flags
+        self.clock = clock
+        self._seen_errors: set = set()  # One report per distinct error and stack a run
+        self._fatal_file = None
+
+    @classmethod
+    def from_config(cls, config=None) -> "CrashReporter":
+        return cls(upload=bool(getattr(config, "crash_upload", False)))
+
+    @property
+    def fatal_path(self) -> Path:
+        return self.directory / "fatal.log"
+
+    def _redact(self, text: str) -> str:
+        text = (self.redactor or get_redactor())(text)
+        return text.replace(str(Path.home()), "~")
+
+    def _log_tail(self) -> List[str]:
+        path = self.log_path or log_file()
+        try:
+            lines = path.read_text(encoding="utf-8", errors="replace").splitlines()[-LOG_TAIL:] if path else []
+        except OSError:
+            return []
+        return [self._redact(line) for line in lines]
+
+    def capture(self, error: BaseException, where: str = "main") -> Optional[CrashReport]:
+        """Save a report for an unhandled error (None when this one was already reported this run). Never raises."""
+        try:
+            stack = traceback.format_exception(type(error), error, error.__traceback__)
+            lines = [self._redact(line) for chunk in stack[:-1] for line in chunk.rstrip("\n").splitlines()]
+            message = self._redact(traceback.format_exception_only(type(error), error)[-1].strip())
+            signature = (where.split()[0], message, tuple(lines))
+            if signature in self._seen_errors:
+                return None
+            self._seen_errors.add(signature)
+            return self._save_new(where, message, lines)
+        except Exception as e:  # A broken reporter must not hide the crash
+            logger.error(f"Couldn't write a crash report: {e}")
+            return None
+
+    def _save_new(self, where: str, error: str, stack: List[str]) -> CrashReport:
+        try:
+            states = self.states()
+            flags = self.flags()
+        except Exception:
+            states, flags = {}, {}
+        report = CrashReport(uuid.uuid4().hex[:6], self.clock().isoformat(timespec="seconds"), where, error, stack,
+                             self._log_tail(), states, flags, platform=f"{platform.system()} {platform.machine()}")
+        self.save(report)
+        self._prune()
+        logger.error(f"Crash report {report.id} saved: {error}")
+        return report
+
+    def save(self, report: CrashReport) -> None:
+        self.directory.mkdir(parents=True, exist_ok=True)
+        path = self.directory / f"{report.id}.json"
+        path.write_text(json.dumps(asdict(report), indent=2, ensure_ascii=False), encoding="utf-8")
+
+    def _prune(self) -> None:
+        for report in self.reports()[MAX_REPORTS:]:
+            (self.directory / f"{report.id}.json").unlink(missing_ok=True)
+
+    def reports(self) -> List[CrashReport]:
+        """Saved reports, newest first."""
+        reports = []
+        for path in self.directory.glob("*.json"):
+            try:
+                reports.append(CrashReport(**json.loads(path.read_text(encoding="utf-8"))))
+            except (OSError, ValueError, TypeError) as e:
+                logger.warning(f"Skipping unreadable crash report {path.name}: {e}")
+        return sorted(reports, key=lambda report: report.time, reverse=True)
+
+    def get(self, report_id: str) -> CrashReport:
+        """A report by its ID or the start of it; KeyError when none or several match."""
+        found = [report for report in self.reports() if report.id.startswith(report_id.strip())]
+        if len(found) != 1:
+            raise KeyError(f"No crash report {report_id}" if not found else f"{report_id} matches several reports")
+        return found[0]
+
+    def unseen(self) -> List[CrashReport]:
+        """Reports the dashboard hasn't mentioned yet (e.g. the crash that ended the last run), marked seen."""
+        reports = [report for report in self.reports() if not report.seen]
+        for report in reports:
+            report.seen = True
+            self.save(report)
+        return reports
+
+    # ----- Uploading (config.crash_upload, or `dev crash-report send`) -----
+
+    async def send(self, report: CrashReport, client, user_id: str) -> None:
+        await client.call(endpoints.CRASH_REPORTS(), json={"user_id": user_id, "report": asdict(report)})
+        report.uploaded_at = self.clock().isoformat(timespec="seconds")
+        self.save(report)
+
+    async def upload_pending(self, client, user_id: str) -> int:
+        """Send the reports not sent yet, when the user opted in (crash_upload job); how many were sent."""
+        if not self.upload:
+            return 0
+        pending = [report for report in self.reports() if not report.uploaded_at]
+        for report in pending:
+            await self.send(report, client, user_id)
+        return len(pending)
+
+    # ----- Hooks -----
+
+    def install(self) -> None:
+        """Report unhandled errors in any thread, and have faulthandler record fatal ones for the next start."""
+        previous_hook, previous_thread_hook = sys.excepthook, threading.excepthook
+
+        def excepthook(kind, error, tb):
+            if not issubclass(kind, KeyboardInterrupt):
+                self.capture(error.with_traceback(tb), "main")
+            previous_hook(kind, error, tb)
+
+        def thread_excepthook(args):
+            if args.exc_value is not None and not issubclass(args.exc_type, SystemExit):
+                self.capture(args.exc_value, f"thread {args.thread.name if args.thread else '?'}")
+            previous_thread_hook(args)
+
+        sys.excepthook = excepthook
+        threading.excepthook = thread_excepthook
+        self.collect_fatal()
+        try:
+            self.directory.mkdir(parents=True, exist_ok=True)
+            self._fatal_file = open(self.fatal_path, "a", encoding="utf-8")
+            faulthandler.enable(self._fatal_file)
+        except OSError as e:
+            logger.warning(f"Fatal errors won't be reported: {e}")
+
+    def loop_exception_handler(self, loop, context: Dict[str, Any]) -> None:
+        """asyncio's handler for errors no task awaited: report them, then log them as asyncio would."""
+        error = context.get("exception")
+        if isinstance(error, Exception):
+            task = context.get("task") or context.get("future")
+            name = task.get_name() if hasattr(task, "get_name") else "asyncio"
+            self.capture(error, f"task {name}")
+        loop.default_exception_handler(context)
+
+    def collect_fatal(self) -> Optional[CrashReport]:
+        """The report for a fatal error faulthandler wrote during the last run, if there was one."""
+        try:
+            dump = self.fatal_path.read_text(encoding="utf-8", errors="replace").strip()
+        except OSError:
+            return None
+        self.fatal_path.write_text("", encoding="utf-8")
+        if not dump:
+            return None
+        lines = [self._redact(line) for line in dump.splitlines()]
+        return self._save_new("fatal", lines[0], lines[1:])
+
+
+_reporter: Optional[CrashReporter] = None
+
+
+def get_crash_reporter() -> CrashReporter:
+    """The reporter built from the loaded Config (local reports only until set_crash_reporter is called)."""
+    global _reporter
+    if _reporter is None:
+        _reporter = CrashReporter()
+    return _reporter
+
+
+def set_crash_reporter(reporter: Optional[CrashReporter]) -> None:
+    global _reporter
+    _reporter = reporter
@@ -5,9 +5,13 @@ Consolidates dashboard logic, screens, and theme management.
 
 import asyncio
 import datetime
+import logging
 import math
 import random
 import re
+import sys
+import threading
+import time
 import colorsys
 from dataclasses import dataclass
 from pathlib import Path
@@ -16,13 +20,13 @@ from typing import Optional, Tuple, List
 from textual.app import App, ComposeResult
 from textual.containers import Container, Vertical, Horizontal, Grid, ScrollableContainer
 from textual.widgets import Static, Label, Button, RadioButton, RadioSet, Input, Tree, Select, Switch, Header, Footer
-from textual.screen import Screen
+from textual.screen import ModalScreen, Screen
 from textual.reactive import reactive
 from textual.binding import Binding
-from textual.events import MouseScrollDown, MouseScrollUp
+from textual.events import Key, MouseDown, MouseScrollDown, MouseScrollUp, Resize
 import pyperclip
 from rich.text import Text
-from typing import Optional, List, Any, cast
+from typing import Optional, List, Any, Dict, cast
 
 from .voice import VoiceBridgeOrchestrator, ConversationState
 from .memory import MemoryManager
@@ -36,18 +40,110 @@ from .dashboard_widgets import (
     AudioVisualizer,
     WorkerDashboard,
     ScheduleWidget,
+    FollowUpWidget,
+    ListsWidget,
+    UsageWidget,
+    Sparkline,
+    BarChart,
+    CallScreeningWidget,
+    InboxWidget,
+    HelpWidget,
     ProjectDashboard,
+    CaptionLine,
     ChatHistory,
     ExpandableInput
 )
 
+from .clock_skew import corrected_now, get_clock_skew, sntp_offset
 from .config import Config
 from .personas.manager import PersonaManager
+from .personas.mood import get_mood_model
+from .personas.phrases import get_phrase_bank
 from .voice import VoiceBridgeOrchestrator, ConversationState
 from .memory import MemoryManager, PersistentChatHistory
 from .thinking import DeepThinkingEngine
 from .chat_engine import ChatEngine, ChatEngineConfig
 from .auth import AnthropicAuth
+from .notifications import (
+    NotificationPolicy, add_notification_hook, current_meeting, set_notification_policy, send_desktop_notification,
+)
+from .undo import is_undo_request
+from .volume import VolumeSettings, parse_volume_request, set_volume_settings
+from .quick_math import answer_quick_question, get_rate_cache
+from .appointment_cache import answer_availability_question, get_appointment_cache
+from .capabilities import answer_capability_question
+from .skill_plugins import SkillPlugins, get_skill_plugins, set_skill_plugins
+from .scripting import ScriptHooks, get_script_hooks, set_script_hooks
+from .lists import get_list_store, parse_list_command, sync_lists
+from .occurrences import match_skip
+from .news import NewsReader, get_news_store, news_briefing
+from .message_templates import MessageTemplates, set_message_templates
+from .emergency import Emergency, EmergencyError, EmergencySettings
+from .household import Household, HouseholdError, get_household, set_household
+from .tutorial import MicMeter, TutorialHooks, TutorialProgress, get_tutorial_hooks, set_tutorial_hooks, tutorial_flow
+from .reminders import METHOD_NAMES, METHODS, ReminderReceipts, adjust_methods, effectiveness
+from .timers import Timer, get_timer_registry, parse_timer_command
+from .evening_review import is_review_request
+from .share_notes import parse_share_request
+from .flows import Flow, flow_for_request
+from .conversation_window import FollowUpWindow, strip_wake_word
+from .alarms import WAKE_BRIEFING, AlarmClock, parse_alarm_command, ring_tone
+from .control import ControlServer, next_appointment
+from .events import (
+    EventStore, add_event_hook, add_event_listener, get_event_store, is_missed_request, missed_since, record_event,
+    remove_event_listener, set_event_store, summarize_missed,
+)
+from .push import DeliveryQueue, FailedPush, PushError, PushRouter
+from .privacy import MicState, TrayIndicator, get_privacy_monitor
+from .accessibility import get_a11y_stream, mirror_activity
+from .dialogue import get_dialogue_state
+from .intents import hear
+from .chaos import get_chaos
+from . import correlation
+from .latency import get_latency_metrics
+from .audio_bus import summarize as summarize_audio_stats
+from .model_loading import LoadProgress
+from .confirmation import ConfirmationLevel, ConfirmationPolicy, get_confirmation_policy, set_confirmation_policy
+from .analytics import build_report
+from .keymap import Keymap
+from .palette import MAX_SHOWN, PaletteEntry, filter_entries, palette_entries
+from .session_lock import SessionLock
+from .matrix import MatrixBridge, MatrixError, set_matrix_bridge
+from .pairing import CompanionServer, DeviceRegistry, PairingError, lan_address, pairing_uri, qr_text, set_companion_server
+from .redaction import Redactor, redact, set_redactor
+from .ai_log import AILog, set_ai_log
+from .speech_filter import SpeechFilter, get_speech_filter, set_speech_filter
+from .layout import TABS, SizeClass, size_class, tab_label
+from .governor import ResourceGovernor, get_resource_governor, set_resource_governor
+from .power import Profile, get_power_manager
+from .prosody import strip as strip_speech_hints
+from .captions import Captions
+from .dates import DateSettings, set_date_settings
+from .holidays import HolidayCalendar, set_holiday_calendar, upcoming_holidays
+from .durations import DurationDefaults, set_duration_defaults
+from .memory_digest import build_digest, digest_cron, is_digest_request, save_digest
+from .memory_sync import SyncState, key_offer, sync_memory
+from .voice_config import ConfigChanger, persona_choices, set_config_changer
+from .ws_framing import compression, get_bandwidth_meter
+from .ics_feed import publish as publish_feed
+from .bookmarks import get_bookmark_store, parse_bookmark_query, parse_bookmark_request, recent_turns
+from .buffers import BufferCaps, buffer_usage, get_buffer_caps, ring, set_buffer_caps, usage_line
+from .coalesce import Coalescer
+from .scheduling_preferences import SchedulingPreferences, parse_preference, set_scheduling_preferences
+from .timezones import find_timezone, system_timezone, travel_suggestion
+from .geocoding import LocationSettings, set_location_settings
+from .project_docs import ProjectDocs, get_project_docs, set_project_docs
+from .web_search import WebSearch, WebSearchError, set_web_search
+from .project_watch import ProjectWatcher, set_project_watcher
+from .quota import QuotaManager, set_quota_manager
+from .scheduler import JobStateStore, Scheduler
+from .compute import get_compute_manager
+from .supervisor import SubsystemFailure, TaskSupervisor, get_task_supervisor, set_task_supervisor
+from .self_update import UPDATE_CHECK_HOURS, SelfUpdater
+from .flags import FeatureFlags, set_feature_flags
+from .flags import enabled as flag_enabled
+from .crash_report import get_crash_reporter
+from .startup_profile import get_startup_profile
 
 
 # ==============================================================================
@@ -64,6 +160,11 @@ class ColorPalette:
     shade_5: str  # Lightest
 
 
+def _strip_context_hint(text: str) -> str:
+    """Remove the "[Context: ...]" prefixes (side pane, what "it" is) added to input."""
+    return re.sub(r"^(?:\[Context: [^\]]*\]\s*)+", "", text)
+
+
 def hex_to_rgb(hex_color: str) -> Tuple[int, int, int]:
     """Convert hex color to RGB tuple."""
     hex_color = hex_color.lstrip('#')
@@ -297,6 +398,41 @@ class SettingsScreen(Screen):
                     id="memory-switch"
                 )
 
+            # Date format (how 04/05/2025 is read)
+            with Vertical(classes="settings-row"):
+                yield Label("Date Format:", classes="settings-label")
+                yield Select(
+                    options=[
+                        ("Auto (system locale)", "auto"),
+                        ("Month first (04/05 = April 5)", "mdy"),
+                        ("Day first (04/05 = 4 May)", "dmy"),
+                    ],
+                    value=self.config.date_order if self.config.date_order in ("auto", "mdy", "dmy") else "auto",
+                    id="date-order-select"
+                )
+
+            # Home timezone (planner times are stored in it; travel mode shows another)
+            with Vertical(classes="settings-row"):
+                yield Label("Home Timezone:", classes="settings-label")
+                yield Input(
+                    value=self.config.timezone or system_timezone() or "",
+                    placeholder="e.g. America/New_York, London",
+                    id="timezone-input"
+                )
+
+            # Clock (how times are spoken)
+            with Vertical(classes="settings-row"):
+                yield Label("Spoken Times:", classes="settings-label")
+                yield Select(
+                    options=[
+                        ("Auto (system locale)", "auto"),
+                        ("12-hour (half past two)", "12h"),
+                        ("24-hour (fourteen thirty)", "24h"),
+                    ],
+                    value=self.config.clock_format if self.config.clock_format in ("auto", "12h", "24h") else "auto",
+                    id="clock-format-select"
+                )
+
             # Buttons
             with Vertical(id="button-container"):
                 yield Button("Save", variant="primary", id="save-button")
@@ -328,6 +464,9 @@ class SettingsScreen(Screen):
         server_url_input = self.query_one("#server-url-input", Input)
         api_token_input = self.query_one("#api-token-input", Input)
         memory_switch = self.query_one("#memory-switch", Switch)
+        date_order_select = self.query_one("#date-order-select", Select)
+        clock_format_select = self.query_one("#clock-format-select", Select)
+        timezone_input = self.query_one("#timezone-input", Input)
 
         # Update config
         self.config.default_persona = str(persona_select.value) if persona_select.value else None
@@ -336,6 +475,12 @@ class SettingsScreen(Screen):
         self.config.server_url = server_url_input.value
         self.config.api_token = api_token_input.value if api_token_input.value else None
         self.config.memory_enabled = memory_switch.value
+        self.config.date_order = str(date_order_select.value)
+        self.config.clock_format = str(clock_format_select.value)
+        if timezone_input.value.strip():
+            # Unknown names keep the previous zone rather than silently falling back to local time
+            self.config.timezone = find_timezone(timezone_input.value) or self.config.timezone
+        set_date_settings(DateSettings.from_config(self.config))
 
         # Save to file
         self.config.save_to_file()
@@ -523,6 +668,8 @@ class WizardScreen(Screen):
         self.config.wake_word = wake_word_input.value
         self.config.server_url = server_url_input.value
         self.config.memory_enabled = True
+        # Pin home to the OS zone now, so planner times keep their meaning if the machine travels
+        self.config.timezone = self.config.timezone or system_timezone()
 
         # Save to file
         self.config.save_to_file()
@@ -531,6 +678,247 @@ class WizardScreen(Screen):
         self.dismiss(self.config)
 
 
+class KeymapHelpScreen(ModalScreen):
+    """The active keymap (keymap.py), shown with ?. Any of escape, q or ? closes it."""
+
+    BINDINGS = [
+        Binding("escape", "close", "Close"),
+        Binding("q", "close", "Close"),
+        Binding("question_mark", "close", "Close"),
+    ]
+
+    CSS = """
+    KeymapHelpScreen {
+        align: center middle;
+    }
+
+    #keymap-help {
+        width: auto;
+        max-width: 90%;
+        height: auto;
+        max-height: 90%;
+        border: solid $primary;
+        background: $surface;
+        padding: 1 2;
+        overflow-y: auto;
+    }
+    """
+
+    def __init__(self, keymap: Keymap):
+        super().__init__()
+        self.keymap = keymap
+
+    def compose(self) -> ComposeResult:
+        lines = self.keymap.help_lines() + ["", "Change keys with keymap_preset / keymap in config.yaml"]
+        yield Static("\n".join(lines), id="keymap-help", markup=False)
+
+    def action_close(self) -> None:
+        self.dismiss()
+
+
+class CommandPaletteScreen(ModalScreen):
+    """
+    Every dashboard action and skill, fuzzy-searched as you type (palette.py).
+    Up/down pick, enter chooses, escape closes; dismisses with the chosen entry.
+    """
+
+    BINDINGS = [
+        Binding("escape", "close", "Close"),
+        Binding("up", "move(-1)", "Up", show=False),
+        Binding("down", "move(1)", "Down", show=False),
+    ]
+
+    CSS = """
+    CommandPaletteScreen {
+        align: center top;
+    }
+
+    #palette {
+        width: 80%;
+        max-width: 100;
+        height: auto;
+        margin-top: 2;
+        border: solid $primary;
+        background: $surface;
+        padding: 0 1;
+    }
+
+    #palette-list {
+        height: auto;
+    }
+    """
+
+    def __init__(self, entries: List[PaletteEntry]):
+        super().__init__()
+        self.entries = entries
+        self.shown = entries
+        self.selected = 0
+
+    def compose(self) -> ComposeResult:
+        with Vertical(id="palette"):
+            yield Input(placeholder="Type a command or something to say...", id="palette-input")
+            yield Static(self._render_list(), id="palette-list")
+
+    def on_mount(self) -> None:
+        self.query_one("#palette-input", Input).focus()
+
+    def _render_list(self) -> Text:
+        if not self.shown:
+            return Text("Nothing matches.", style="dim")
+        result = Text()
+        first = min(max(0, self.selected - MAX_SHOWN + 1), max(0, len(self.shown) - MAX_SHOWN))
+        for index, entry in enumerate(self.shown[first:first + MAX_SHOWN], start=first):
+            selected = index == self.selected
+            result.append("▶ " if selected else "  ", style="bold" if selected else "")
+            result.append(entry.title, style="bold" if selected else "")
+            if entry.detail:
+                result.append(f"  {entry.detail}", style="dim")
+            result.append("\n")
+        return result
+
+    def on_input_changed(self, event: Input.Changed) -> None:
+        event.stop()  # Not a Help tab search
+        self.shown = filter_entries(self.entries, event.value)
+        self.selected = 0
+        self.query_one("#palette-list", Static).update(self._render_list())
+
+    def on_input_submitted(self, event: Input.Submitted) -> None:
+        event.stop()
+        if self.shown:
+            self.dismiss(self.shown[self.selected])
+
+    def action_move(self, step: int) -> None:
+        if self.shown:
+            self.selected = (self.selected + step) % len(self.shown)
+            self.query_one("#palette-list", Static).update(self._render_list())
+
+    def action_close(self) -> None:
+        self.dismiss(None)
+
+
+class PairingScreen(ModalScreen):
+    """
+    The pairing code (and QR code, with the qrcode package) for a companion
+    client, plus the devices already paired. Closes by itself once a device
+    pairs or the code expires; escape cancels the offer.
+    """
+
+    BINDINGS = [Binding("escape", "close", "Close")]
+
+    CSS = """
+    PairingScreen {
+        align: center middle;
+    }
+
+    #pairing {
+        width: auto;
+        max-width: 90%;
+        height: auto;
+        max-height: 95%;
+        border: solid $primary;
+        background: $surface;
+        padding: 1 2;
+        overflow-y: auto;
+    }
+    """
+
+    def __init__(self, registry: DeviceRegistry, offer, uri: str):
+        super().__init__()
+        self.registry = registry
+        self.offer = offer
+        self.uri = uri
+        self._known = {device.id for device in registry.devices()}
+
+    def compose(self) -> ComposeResult:
+        lines = ["Pair a phone or web client", ""]
+        qr = qr_text(self.uri)
+        if qr:
+            lines += [qr, ""]
+        lines += [f"Code: {self.offer.code[:3]} {self.offer.code[3:]}", self.uri,
+                  f"Valid until {self.offer.expires_at:%H:%M} · {', '.join(self.offer.scopes)}", ""]
+        devices = self.registry.devices()
+        lines.append("Paired devices:" if devices else "No paired devices yet")
+        lines += [f"  {d.name} ({d.id}) · last seen {d.last_seen or 'never'}" for d in devices]
+        if devices:
+            lines.append("Revoke one with: xswarm dev devices revoke NAME")
+        yield Static("\n".join(lines), id="pairing", markup=False)
+
+    def on_mount(self) -> None:
+        self.set_interval(1.0, self._check_done)
+
+    def _check_done(self) -> None:
+        paired = [d for d in self.registry.devices() if d.id not in self._known]
+        if paired:
+            self.app.update_activity(f"✓ Paired {paired[0].name}", "success")
+            self.dismiss()
+        elif self.registry.offer is not self.offer:
+            self.app.update_activity("Pairing cancelled after too many wrong codes", "warning")
+            self.dismiss()
+        elif datetime.datetime.now() >= self.offer.expires_at:
+            self.registry.cancel_pairing()
+            self.app.update_activity("Pairing code expired", "warning")
+            self.dismiss()
+
+    def action_close(self) -> None:
+        self.registry.cancel_pairing()
+        self.dismiss()
+
+
+class LockScreen(ModalScreen):
+    """
+    Covers the whole dashboard while it's locked (session_lock.py). Only the
+    PIN or account password gets past it; closes itself once unlocked.
+    """
+
+    CSS = """
+    LockScreen {
+        align: center middle;
+        background: $background 100%;
+    }
+
+    #lock-box {
+        width: 44;
+        height: auto;
+        border: solid $primary;
+        padding: 1 2;
+    }
+
+    #lock-title {
+        width: 100%;
+        text-align: center;
+        text-style: bold;
+    }
+
+    #lock-message {
+        width: 100%;
+        height: 1;
+        color: $warning;
+    }
+    """
+
+    def __init__(self, lock: SessionLock):
+        super().__init__()
+        self.lock = lock
+
+    def compose(self) -> ComposeResult:
+        with Vertical(id="lock-box"):
+            yield Static("🔒 xSwarm is locked", id="lock-title")
+            yield Input(placeholder=self.lock.prompt, password=True, id="unlock-input")
+            yield Static("", id="lock-message")
+
+    def on_mount(self) -> None:
+        self.query_one("#unlock-input", Input).focus()
+
+    def on_input_submitted(self, event: Input.Submitted) -> None:
+        event.stop()  # Never reaches the chat handler
+        result = self.lock.unlock(event.value)
+        event.input.value = ""
+        if result.ok:
+            self.dismiss()
+        else:
+            self.query_one("#lock-message", Static).update(result.message)
+
+
 class VoiceVizDemoScreen(Screen):
     """
     Demo screen showing all 6 voice visualization styles.
@@ -680,40 +1068,14 @@ class VoiceAssistantApp(App):
 
     TITLE = "Voice Assistant"
 
-    # Key bindings
-    BINDINGS = [
-        ("q", "quit", "Quit"),  # Only works when not in input field
-        Binding("ctrl+q", "quit", "Quit", priority=True),
-        Binding("ctrl+c", "quit", "Quit", priority=True),  # User requested CTRL-C to exit
-        ("ctrl+l", "copy_logs", "Copy Logs"), # Rebound copy logs to CTRL-L
-        # Navigation bindings
-        ("j", "nav_down", "Next Tab"),
-        ("k", "nav_up", "Previous Tab"),
-        ("down", "nav_down", "Next Tab"),
-        ("up", "nav_up", "Previous Tab"),
-        ("right", "focus_content", "Focus Content"),
-        ("left", "focus_sidebar", "Focus Sidebar"),
-        ("h", "focus_sidebar", "Focus Sidebar"),
-        ("l", "focus_content", "Focus Content"),
-        Binding("escape", "escape_handler", "Cancel/Back", priority=True),
-        # Tab key zone cycling (priority=True to override default focus chain)
-        Binding("tab", "cycle_focus_zone", "Next Pane", priority=True),
-        Binding("shift+tab", "cycle_focus_zone_reverse", "Prev Pane", priority=True),
-        # Quick tab access (new order: Chat first, Settings second)
-        ("1", "goto_chat", "Chat"),
-        ("2", "goto_schedule", "Schedule"),
-        ("3", "goto_projects", "Projects"),
-        ("4", "goto_settings", "Settings"),
-        ("5", "goto_status", "Status"),
-        ("6", "goto_tools", "Tools"),
-        ("7", "goto_workers", "Workers"),
-    ]
+    # Key bindings come from the configured keymap (keymap.py), set up in __init__
+    ENABLE_COMMAND_PALETTE = False  # Textual's own; ours (Ctrl+P) also lists skills (palette.py)
 
     # Reactive state
     state = reactive("idle")  # idle, listening, speaking, thinking
     amplitude = reactive(0.0)
     current_persona_name = reactive("Default")  # Current persona name
-    active_tab = reactive("chat")  # chat, settings, status, tools, projects, schedule, workers (new order)
+    active_tab = reactive("chat")  # chat, settings, status, tools, projects, schedule, workers, inbox (new order)
     _focus_zone = reactive("sidebar")  # "sidebar" | "content" - tracks which zone has focus
 
     # Reactive theme colors - automatically update UI when changed
@@ -742,6 +1104,94 @@ class VoiceAssistantApp(App):
     def __init__(self, config: Config, personas_dir: Path, voice_server_process=None, voice_queues=None):
         super().__init__()
         self.config = config
+        # Timed from here until on_mount (startup_profile.py)
+        self.startup = get_startup_profile()
+        self.startup.start("dashboard")
+        # Dashboard keys from the configured preset and overrides (keymap.py)
+        self.keymap = Keymap.from_config(config)
+        for key, command, description, priority in self.keymap.bindings():
+            self._bindings.bind(key, command, description, priority=priority)
+        # Quiet hours apply to every notification channel from here on
+        set_notification_policy(NotificationPolicy(config, meetings=self._current_meeting))
+        # Risky tool calls wait for a spoken yes/PIN; verbal-level ones are read back
+        set_confirmation_policy(ConfirmationPolicy(config))
+        get_confirmation_policy().on_verbal = self._announce_verbal_confirmation
+        # Background jobs (scheduler tasks, inbox/calendar sync) back off while the machine is busy
+        set_resource_governor(ResourceGovernor.from_config(config))
+        # Numeric dates (04/05/2025) read in the user's day/month order
+        set_date_settings(DateSettings.from_config(config))
+        # How much the feed, chat and charts keep before dropping the oldest (buffers.py)
+        set_buffer_caps(BufferCaps.from_config(config))
+        self._activity_repeats = Coalescer()  # Repeated feed messages shown once with a count
+        # Holidays for booking warnings, briefings and skip_holidays series
+        set_holiday_calendar(HolidayCalendar.from_config(config))
+        # Event lengths by kind when none is said ("lunch with Sam at noon")
+        set_duration_defaults(DurationDefaults.from_config(config))
+        set_scheduling_preferences(SchedulingPreferences.from_config(config))
+        # Speech/earcon/media levels the audio mixer plays at ("speak louder" changes them)
+        set_volume_settings(VolumeSettings.from_config(config))
+        # Geocoder, map links and travel speed for event locations
+        set_location_settings(LocationSettings.from_config(config))
+        # Phone numbers, emails and credentials masked in logs, copied logs and memory
+        set_redactor(Redactor.from_config(config))
+        # Phone minutes and text messages counted against the subscription tier (synced by quota_sync)
+        set_quota_manager(QuotaManager.from_config(config))
+        # Experimental subsystems on or off by channel, server override and config (synced by flag_sync)
+        set_feature_flags(FeatureFlags.from_config(config))
+        # Project folders indexed into Meilisearch for "where do we ... in project X?" (ask_project tool)
+        from .voice import AIClient
+        set_project_docs(ProjectDocs.from_config(config, AIClient(config, "project_docs")))
+        # Web search for the web_search tool (off unless config.web_search names a provider)
+        try:
+            set_web_search(WebSearch.from_config(config))
+        except WebSearchError as e:
+            logging.warning(f"Web search disabled: {e}")
+        # User wording for reminders per category and channel; broken templates are logged and skipped
+        set_message_templates(MessageTemplates.from_config(config))
+        # Who's using the assistant and what their profile may do; a bad config leaves just the owner
+        try:
+            set_household(Household.from_config(config))
+        except HouseholdError as e:
+            logging.warning(f"Household profiles disabled: {e}")
+        # Enabled WebAssembly skill plugins: their intents, tools (AI tool registry) and help entries
+        from .tools import registry as ai_tool_registry
+        set_skill_plugins(SkillPlugins.from_config(config))
+        get_skill_plugins().load(ai_tool_registry)
+        # User Lua scripts (config.scripts_enabled) hooked into transcripts, notifications and events
+        set_script_hooks(ScriptHooks.from_config(config))
+        get_script_hooks().on_disabled = lambda message: self._on_ui_thread(self.update_activity, f"⚠ {message}",
+                                                                            "warning")
+        add_notification_hook(get_script_hooks().before_notification)
+        add_event_hook(get_script_hooks().on_event)
+        # Activity and inbox history for `dev events` and "what did I miss?"
+        try:
+            set_event_store(EventStore.from_config(config))
+        except Exception as e:
+            logging.warning(f"Event history unavailable, keeping this session's in memory: {e}")
+            set_event_store(EventStore(":memory:"))
+        # Every request to a language model, for `xswarm dev ai log` (config.ai_log)
+        if config.ai_log != "off":
+            try:
+                set_ai_log(AILog.from_config(config))
+            except Exception as e:
+                logging.warning(f"AI log unavailable: {e}")
+        # Swearing, personal details and long replies, on their way to the voice (config.speech_*)
+        set_speech_filter(SpeechFilter.from_config(config))
+        # Missed reminders, errors and finished long jobs pushed to ntfy/Pushover (config.push_routes)
+        try:
+            self.push_router = PushRouter.from_config(
+                config, queue=DeliveryQueue.from_config(config, on_dead=self._on_push_dead))
+        except PushError as e:
+            logging.warning(f"Push notifications off: {e}")
+            self.push_router = None
+        if self.push_router:
+            add_event_listener(self.push_router.on_event)
+        from .tools import set_push_router
+        set_push_router(self.push_router)
+        # Crashed background tasks (audio forwarder, listeners, scheduler) restart and report here
+        set_task_supervisor(TaskSupervisor(on_crash=self._on_task_crash, on_failed=self._on_subsystem_failed))
+        # All periodic background work runs on this scheduler (jobs registered in _setup_jobs)
+        self.job_scheduler = Scheduler(config=config, store=JobStateStore())
         self.personas_dir = personas_dir
         self.voice_server_process = voice_server_process
         self.voice_queues = voice_queues
@@ -753,7 +1203,7 @@ class VoiceAssistantApp(App):
         self.moshi_client: Optional[object] = None  # Deprecated: kept for backwards compatibility
         self.audio_io: Optional[object] = None
         self.audio_buffer = []  # Buffer for capturing audio during listening
-        self.chat_history = []  # Store chat messages (user + assistant) - legacy list
+        self.chat_history = ring(get_buffer_caps()["chat"])  # Store chat messages (user + assistant) - legacy list
         self.memory_manager: Optional[MemoryManager] = None  # Memory manager for persistence
         self.persistent_chat_history: Optional[PersistentChatHistory] = None  # New file-based persistence
         self.user_id = "local-user"  # Default user ID
@@ -798,7 +1248,7 @@ class VoiceAssistantApp(App):
             self._theme_palette = self._load_theme(config.theme_base_color)
 
         # Initialize tool registry
-        from .tools import ToolRegistry, ThemeChangeTool, send_email_tool, make_call_tool
+        from .tools import ToolRegistry, ThemeChangeTool, send_email_tool, make_call_tool, get_undo_log
         self.tool_registry = ToolRegistry()
         # Register theme change tool (bound to this app instance)
         self.tool_registry.register_tool(ThemeChangeTool.create_tool(self))
@@ -806,10 +1256,14 @@ class VoiceAssistantApp(App):
         self.tool_registry.register_tool(send_email_tool)
         # Register phone tools
         self.tool_registry.register_tool(make_call_tool)
+        # "Set quiet hours from 10pm to 7am": checked, saved and applied to this config (voice_config.py)
+        config_changer = ConfigChanger(config, personas=lambda: persona_choices(self.persona_manager),
+                                       undo_log=get_undo_log())
+        config_changer.on_change("voice", lambda values: self.switch_persona(values["default_persona"]))
+        set_config_changer(config_changer)
 
         # Keyboard navigation state (new order: Chat first, Settings second)
-        self._nav_buttons = ["tab-chat", "tab-schedule", "tab-projects", "tab-settings",
-                            "tab-status", "tab-tools", "tab-workers"]
+        self._nav_buttons = [f"tab-{tab}" for tab, _icon, _name in TABS]
         self._focused_nav_index = 0  # Track which nav button has keyboard focus
 
         # Chat engine for text-based AI conversations (fallback when voice is disabled)
@@ -818,6 +1272,73 @@ class VoiceAssistantApp(App):
         self._initial_welcome_shown = False
         self._ui_fully_initialized = False  # Set True after initial persona selector setup
         self._current_chat_task: Optional[asyncio.Task] = None  # For cancellation
+        # Memory starts loading on mount; voice init waits on it instead of starting a second one
+        self._memory_task: Optional[asyncio.Task] = None
+
+        # Unified inbox sync (created lazily on first sync)
+        self.inbox_manager = None
+        # Active "draft a reply" conversation, if any (see reply_drafts.py)
+        self.reply_workflow = None
+        # Active "send Alice the notes" conversation, if any (see share_notes.py)
+        self.share_workflow = None
+        # Active end-of-day review conversation, if any (see evening_review.py)
+        self.evening_review = None
+        # Active guided dialog, if any (see flows.py)
+        self.active_flow = None
+        # First-use tutorial (see tutorial.py): the mic level it checks, and when the wake word was last heard
+        self.mic_meter = MicMeter()
+        self._wake_word_at = 0.0
+        self._welcomed = asyncio.Event()  # Set once the welcome message is in, so the tutorial doesn't race it
+        # Seconds after an answer when a reply needs no wake word (config.require_wake_word)
+        self.follow_up = FollowUpWindow.from_config(config)
+        # The user's words while they speak, under the chat (config.live_captions)
+        self.captions = Captions()
+        # When the unanswered user turn started (time.monotonic), for reply latency in analytics.py
+        self._turn_started: Optional[float] = None
+        # Lock screen for shared/visible screens (session_lock.py)
+        self.session_lock = SessionLock(config)
+        # Layout for the terminal size (layout.py), set on mount and on resize
+        self.size_class: Optional[SizeClass] = None
+        # Menu-bar/tray mic indicator (config.privacy_tray_icon), started on mount
+        self.privacy_tray: Optional[TrayIndicator] = None
+        # Local socket for `xswarm tray` (config.control_socket), opened on mount
+        self.control_server: Optional[ControlServer] = None
+        # REST API on localhost for scripts and `xswarm mcp` (config.local_api), opened on mount
+        self.local_api = None
+        # Paired phone/web clients (pairing.py): listening on mount with config.companion_enabled, else once pairing
+        self.devices = DeviceRegistry()
+        self.companion_server: Optional[CompanionServer] = None
+        self.matrix_bridge: Optional[MatrixBridge] = None
+        self._audio_drops_reported = 0
+        self._audio_drops_reported_at = float("-inf")
+        # Screened inbound calls (created lazily on first poll)
+        self.call_screening = None
+        # Follow-up detection over the user's side of the conversation
+        self.followup_detector = None
+        self.email_task_detector = None
+        self._recent_utterances: List[str] = []
+        # Last server API failure kind shown in the activity feed (None = healthy)
+        self._api_error_kind: Optional[str] = None
+        # Event occurrences already reminded about (instance keys, see scheduler_client)
+        self._reminded_events: set = set()
+        self.reminder_receipts = ReminderReceipts()
+        self._reminder_methods: List[str] = list(config.reminder_methods)
+        self._prepared_events: set = set()  # Event instances already given a prep brief
+        self.calendar_subscription = None  # Live server calendar changes (calendar_live.py)
+        self.remote_commands = None  # Commands queued by the server (remote_commands.py)
+        self._clock_warned = False  # Clock drift warning shown (clock_skew.py)
+        self._update_shown = ""  # Newer release already announced (self_update.py)
+        self._push_dead_reported = False  # Dead-letter pushes from earlier sessions mentioned (push.py)
+        self.alarm_clock = AlarmClock.from_config(config)  # Wake-up alarms (alarms.py)
+        self._alarm_briefings: dict = {}  # Alarm id -> briefing gathered before it rang
+        self._news_reader: Optional[NewsReader] = None  # Made by the first news job run
+        self.emergency: Optional[Emergency] = None  # Emergency phrase and alerts (emergency.py)
+        try:
+            settings = EmergencySettings.from_config(config)
+            if settings is not None:
+                self.emergency = Emergency(settings, config)
+        except EmergencyError as e:
+            logging.warning(f"Emergency phrase disabled: {e}")
 
     def _load_theme(self, theme_input: str):
         """
@@ -897,10 +1418,18 @@ class VoiceAssistantApp(App):
     def update_activity(self, message: str, msg_type: str = "info") -> None:
         """
         Update activity feed with new message.
+
+        The same message again within a minute of the last (see coalesce.py)
+        only bumps the count on its entry.
         """
+        repeat = self._activity_repeats.note((message, msg_type))
+        if repeat.new:
+            record_event("activity", message, {"level": msg_type})
+            mirror_activity(message, msg_type)
         try:
             feed = self.query_one(ActivityFeed)
-            feed.add_message(message, msg_type)
+            if repeat.new or not feed.count_repeat(message, msg_type, repeat.count):
+                feed.add_message(message, msg_type, thread=correlation.current_id())
             
             # Removed toast notifications per user request
         except Exception:
@@ -929,6 +1458,314 @@ class VoiceAssistantApp(App):
             return True  # Indicate we handled something
         return False  # Nothing to cancel
 
+    def action_undo(self) -> None:
+        """Undo the last destructive action (delete, complete, forget)."""
+        from .tools import undo_last
+        result = undo_last()
+        self.update_activity(result, "success" if result.startswith("✓") else "warning")
+
+    def action_volume(self, level: str) -> None:
+        """Turn the master volume up or down a step."""
+        from .tools import set_volume
+        result = set_volume("master", level)
+        self.update_activity(result, "success" if result.startswith("✓") else "warning")
+
+    async def _handle_volume_utterance(self, text: str) -> None:
+        """Answer "speak louder" / "volume to 60%" directly instead of sending it to the model."""
+        from .tools import set_volume
+        request = parse_volume_request(text)
+        result = set_volume(request.stream, request.level)
+        self.update_activity(result, "success" if result.startswith("✓") else "warning")
+        await self._say_to_user(result.lstrip("✓✗ "))
+
+    async def _handle_plugin_utterance(self, text: str) -> None:
+        """Let the skill plugin whose intent matched answer, off the event loop - see skill_plugins.py."""
+        answer = await asyncio.to_thread(get_skill_plugins().answer, text)
+        if answer:
+            await self._say_to_user(answer)
+
+    async def _handle_timer_utterance(self, text: str) -> None:
+        """Start, cancel or check a timer, or work the stopwatch, without the model - see timers.py."""
+        registry = get_timer_registry()
+        command = parse_timer_command(text, registry)
+        if command is None:
+            return
+        reply = registry.apply(command, corrected_now())
+        self._update_timers()
+        self.update_activity(f"⏲ {reply}", "info")
+        await self._say_to_user(reply)
+
+    async def _handle_list_utterance(self, text: str) -> None:
+        """Add to, read, check off or clear a named list without the model - see lists.py."""
+        store = get_list_store()
+        command = parse_list_command(text, store)
+        if command is None:
+            return
+        reply = store.apply(command)
+        self.update_activity(f"📝 {reply}", "info")
+        await self._say_to_user(reply)
+        if command.action != "read":
+            asyncio.create_task(self._sync_lists())  # Other devices see it now, not at the next list_sync
+
+    async def _handle_skip_utterance(self, text: str) -> None:
+        """Skip one occurrence of a recurring meeting ("skip next week's standup") - see occurrences.py."""
+        from .tools import delete_calendar_event
+        from .verbalize import verbalize_date
+        occurrence = match_skip(text)
+        if occurrence is None:
+            return
+        result = delete_calendar_event(occurrence.id)
+        if not result.startswith("✓"):
+            self.update_activity(result, "warning")
+            await self._say_to_user(f"I couldn't skip the {occurrence.title}.")
+            return
+        self.update_activity(f"📅 {result.lstrip('✓ ')}", "success")
+        try:
+            self.query_one(ScheduleWidget).refresh()
+        except Exception:
+            pass  # Not mounted in this layout
+        day = datetime.date.fromisoformat(occurrence.start_time[:10])
+        await self._say_to_user(f"Okay, no {occurrence.title} {verbalize_date(day)}. The rest of the series stays.")
+
+    async def _handle_bookmark_utterance(self, text: str) -> None:
+        """Keep the last few turns under a label ("remember this as the contract terms") - see bookmarks.py."""
+        if not self.config.memory_enabled:
+            await self._say_to_user("Memory is turned off, so I can't keep bookmarks.")
+            return
+        turns, label = parse_bookmark_request(text)
+        try:
+            bookmark = get_bookmark_store().add(label, recent_turns(turns))
+        except ValueError as e:
+            self.update_activity(f"✗ {e}", "warning")
+            await self._say_to_user("There's nothing in our conversation to bookmark yet.")
+            return
+        self.update_activity(f"🔖 Bookmarked {len(bookmark.turns)} turns as '{bookmark.label}'", "success")
+        await self._say_to_user(f"Bookmarked as '{bookmark.label}'.")
+
+    async def _handle_bookmark_query(self, text: str) -> None:
+        """Read back the best bookmark for "what did I bookmark about the contract?"."""
+        from .tools import find_bookmarks
+        query = parse_bookmark_query(text)
+        result = find_bookmarks(query, limit=1)
+        found = get_bookmark_store().find(query)
+        if not found:
+            await self._say_to_user(result)
+            return
+        self.update_activity(f"🔖 {result}", "info")
+        more = f" {len(found) - 1} more are in the activity feed." if len(found) > 1 else ""
+        await self._say_to_user(f"Under '{found[0].label}': " + " ".join(found[0].transcript()) + more)
+
+    async def _handle_preference_utterance(self, text: str) -> None:
+        """Remember "I never take meetings before 10" as a scheduling preference - see scheduling_preferences.py."""
+        from .tools import add_scheduling_preference
+        result = add_scheduling_preference(text)
+        if not result.startswith("✓"):
+            return
+        self.update_activity(f"🗓 {result.lstrip('✓ ')}", "success")
+        await self._say_to_user(f"Got it - {result.split(': ', 1)[1]}. I'll keep that in mind when suggesting times.")
+
+    def _update_timers(self) -> None:
+        """Count running timers down in the footer and announce the ones that finished."""
+        registry = get_timer_registry()
+        now = corrected_now()
+        for timer in registry.finished(now):
+            asyncio.create_task(self._timer_done(timer, now))
+        try:
+            self.query_one(CyberpunkFooter).timers = tuple(registry.countdown(now))
+        except Exception:
+            pass
+
+    def _on_voice_caption(self, text: str, is_final: bool) -> None:
+        """A partial or final transcript from the voice bridge (transcriber thread) for the caption line."""
+        self._on_ui_thread(self._show_caption, text, is_final)
+
+    def _show_caption(self, text: str, is_final: bool) -> None:
+        text = "••••" if text and self._awaiting_pin() else text
+        if is_final:
+            self.captions.final(text)
+            self.set_timer(self.captions.hold, self._refresh_caption)
+        else:
+            self.captions.partial(text)
+        self._refresh_caption()
+
+    def _refresh_caption(self) -> None:
+        try:
+            self.query_one(CaptionLine).caption = self.captions.line()
+        except Exception:
+            pass
+
+    def _update_mood(self) -> None:
+        """Show the persona's mood in the footer; it eases back to rest between messages."""
+        try:
+            self.query_one(CyberpunkFooter).mood = get_mood_model().current().label
+        except Exception:
+            pass
+
+    async def _timer_done(self, timer: Timer, now: datetime.datetime) -> None:
+        if (now - timer.end_time).total_seconds() > 60:
+            text = f"Your {timer.label} went off at {timer.end_time:%H:%M} while I wasn't running."
+        else:
+            text = f"Your {timer.label} is done."
+            audio_io = getattr(self.voice_orchestrator, "audio_io", None)
+            if audio_io is not None:
+                audio_io.play_media(ring_tone(audio_io.sample_rate))
+        self.update_activity(f"⏲ {text}", "info")
+        record_event("reminder", text, {"timer": timer.name, "seconds": timer.seconds})
+        await self._say_to_user(text)
+
+    def _user_active(self, source: str) -> Optional[datetime.datetime]:
+        """The user said or typed something: when they last did before (for "what did I miss?")."""
+        record_event("turn", source, {"source": source})
+        self._turn_started = time.monotonic()
+        self.session_lock.touch()
+        try:
+            return get_event_store().touch()
+        except Exception as e:
+            logging.debug(f"Could not record user activity: {e}")
+            return None
+
+    def _note_reply(self) -> None:
+        """The assistant started answering: record how long the user waited."""
+        if self._turn_started is not None:
+            latency = time.monotonic() - self._turn_started
+            self._turn_started = None
+            record_event("reply", f"{latency:.1f}s", {"latency": round(latency, 3)})
+            try:
+                self.query_one("#latency-chart", Sparkline).add(latency)
+            except Exception:
+                pass
+
+    def _load_chart_history(self) -> None:
+        """Start the latency sparkline from recent replies, and fill in messages per day."""
+        try:
+            chart = self.query_one("#latency-chart", Sparkline)
+            for event in get_event_store().query(["reply"], limit=chart.history.size):
+                chart.history.add(event.data.get("latency", 0), at=event.time.timestamp())
+            chart.refresh()
+        except Exception as e:
+            logging.debug(f"Could not load reply history: {e}")
+        self._update_message_chart()
+
+    def _update_message_chart(self) -> None:
+        """New texts, emails and voicemails per day this week (analytics.py)."""
+        try:
+            report = build_report(get_event_store(), "week")
+            chart = self.query_one("#messages-chart", BarChart)
+            chart.set_items([(f"{day.day:%a %d}", day.messages) for day in report.days])
+        except Exception as e:
+            logging.debug(f"Could not update the messages chart: {e}")
+
+    async def _handle_missed_utterance(self, last_active: Optional[datetime.datetime]) -> None:
+        """Answer "what did I miss?" from the event history instead of sending it to the model."""
+        summary = summarize_missed(get_event_store().query(since=missed_since(last_active)))
+        self.update_activity(f"🕘 {summary}", "info")
+        await self._say_to_user(summary)
+
+    async def _handle_digest_utterance(self) -> None:
+        """Answer "what did we discuss this week?" from the weekly digest (see memory_digest.py)."""
+        digest = await asyncio.to_thread(build_digest)
+        if not digest.is_empty():
+            self.update_activity(f"🗒 {digest.markdown()}", "info")
+        await self._say_to_user(digest.spoken())
+
+    async def _scheduled_memory_digest(self) -> None:
+        """memory_digest job: save the week's digest to the vault folder, and email it when asked to."""
+        digest = await asyncio.to_thread(build_digest)
+        if digest.is_empty():
+            return
+        path = await asyncio.to_thread(save_digest, digest, self.config.memory_digest_dir or None)
+        self.update_activity(f"🗒 Weekly digest saved to {path}", "success")
+        if self.config.memory_digest_email:
+            from .tools import send_email_handler
+            result = await send_email_handler(f"Weekly digest {digest.week}", digest.markdown())
+            if not result.get("success"):
+                self.update_activity(f"✗ Weekly digest not emailed: {result.get('message')}", "warning")
+
+    async def _handle_undo_utterance(self) -> None:
+        """Answer an "undo that" request directly instead of sending it to the model."""
+        from .tools import undo_last
+        result = undo_last()
+        self.update_activity(result, "success" if result.startswith("✓") else "warning")
+        await self._say_to_user(result.lstrip("✓✗ "))
+
+    def _continue_utterance(self, text: str) -> bool:
+        """A "yes" or "no" after "Shall I continue?" on a long spoken reply (speech_filter.py)."""
+        more = get_speech_filter().answer(text)
+        if more is None:
+            return False
+        asyncio.create_task(self._handle_continue_utterance(more))
+        return True
+
+    async def _handle_continue_utterance(self, more: bool) -> None:
+        """The next part of the reply (already on screen in full), or leave the rest unsaid."""
+        if self.voice_orchestrator:
+            part = get_speech_filter().next_part() if more else get_phrase_bank().pick("acknowledgment") or "Okay."
+            await self.voice_orchestrator.speak_text(part, filtered=True)
+
+    def _announce_verbal_confirmation(self, result: str) -> None:
+        """Read back what a verbal-level tool did so a misheard command gets noticed."""
+        self.update_activity(result, "success" if result.startswith("✓") else "warning")
+        if self.voice_orchestrator:
+            asyncio.create_task(self.voice_orchestrator.speak_text(result.lstrip("✓✗ ")))
+
+    async def _handle_confirmation_utterance(self, text: str) -> bool:
+        """
+        Feed the user's reply to a held tool call (see confirmation.py).
+
+        Returns False when the reply was about something else, so it goes on
+        to the model as usual.
+        """
+        policy = get_confirmation_policy()
+        resolution = policy.resolve(text)
+        if resolution is None:
+            return False
+
+        if resolution.status == "confirmed":
+            action = resolution.action
+            tool_registry = action.registry or self.tool_registry
+            outcome = await tool_registry.execute_tool(action.tool_name, action.args, confirmed=True)
+            result = outcome["result"] if outcome["success"] else f"✗ {outcome['message']}"
+            self.update_activity(result, "success" if str(result).startswith("✓") else "warning")
+            await self._say_to_user(str(result).lstrip("✓✗ "))
+        else:
+            self.update_activity(resolution.message, "warning")
+            await self._say_to_user(resolution.message)
+        return True
+
+    async def _handle_confirmation_or_chat(self, text: str, last_active: Optional[datetime.datetime] = None) -> None:
+        """Spoken reply while a confirmation is pending; anything else is a normal utterance."""
+        if not await self._handle_confirmation_utterance(text):
+            if is_undo_request(text):
+                await self._handle_undo_utterance()
+            elif parse_volume_request(text):
+                await self._handle_volume_utterance(text)
+            elif parse_timer_command(text, get_timer_registry()):
+                await self._handle_timer_utterance(text)
+            elif parse_list_command(text, get_list_store()):
+                await self._handle_list_utterance(text)
+            elif parse_bookmark_request(text):
+                await self._handle_bookmark_utterance(text)
+            elif parse_bookmark_query(text) is not None:
+                await self._handle_bookmark_query(text)
+            elif match_skip(text):
+                await self._handle_skip_utterance(text)
+            elif parse_preference(text):
+                await self._handle_preference_utterance(text)
+            elif is_missed_request(text):
+                await self._handle_missed_utterance(last_active)
+            elif is_digest_request(text):
+                await self._handle_digest_utterance()
+            elif answer_quick_question(text):
+                await self._say_to_user(answer_quick_question(text))
+            elif answer_availability_question(text):
+                await self._say_to_user(answer_availability_question(text))
+            else:
+                await self._detect_followups(text)
+
+    def _awaiting_pin(self) -> bool:
+        policy = get_confirmation_policy()
+        return policy.has_pending() and policy.pending.level == ConfirmationLevel.PIN
+
     def action_escape_handler(self) -> None:
         """Handle Escape: cancel chat if running, otherwise focus sidebar."""
         # First try to cancel any running chat
@@ -938,6 +1775,128 @@ class VoiceAssistantApp(App):
             # No chat running, focus sidebar
             self.action_focus_sidebar()
 
+    async def on_event(self, event) -> None:
+        # Any key press or click means someone is at the screen (idle lock)
+        if isinstance(event, (Key, MouseDown)):
+            self.session_lock.touch()
+        await super().on_event(event)
+
+    def action_lock(self) -> None:
+        """Cover the dashboard until the PIN or account password is entered (ctrl+o)."""
+        if self.session_lock.locked:
+            return
+        reason = self.session_lock.unavailable()
+        if reason:
+            self.update_activity(f"⚠ {reason}", "warning")
+            return
+        self.session_lock.lock()
+        self.push_screen(LockScreen(self.session_lock))
+
+    def check_action(self, action: str, parameters: tuple) -> Optional[bool]:
+        """While locked no app action runs (priority keys included); only the lock screen's input unlocks."""
+        if self.session_lock.locked:
+            return False
+        return super().check_action(action, parameters)
+
+    def _check_idle_lock(self) -> None:
+        if self.session_lock.idle_expired():
+            self.action_lock()
+
+    def action_pair_device(self) -> None:
+        """Show a pairing code for a phone or web client (ctrl+y), starting the companion server if needed."""
+        if isinstance(self.screen, (PairingScreen, LockScreen)):
+            return
+        asyncio.create_task(self._show_pairing())
+
+    async def _show_pairing(self) -> None:
+        if self.companion_server is None and not await self._start_companion_server():
+            self.update_activity(f"⚠ Can't pair: port {self.config.companion_port} is unavailable", "warning")
+            return
+        try:
+            offer = self.devices.start_pairing()
+        except PairingError as e:
+            self.update_activity(f"⚠ {e}", "warning")
+            return
+        self.push_screen(PairingScreen(self.devices, offer, pairing_uri(lan_address(), self.config.companion_port, offer.code)))
+
+    async def _start_companion_server(self) -> bool:
+        """Listen for paired companions (pairing.py); False when the port can't be opened."""
+        server = CompanionServer(self.devices, self._on_companion_message, lambda: self._control_status(None),
+                                 host=self.config.companion_host, port=self.config.companion_port,
+                                 on_violation=lambda message: self.update_activity(message, "warning"),
+                                 on_sync_join=self._offer_sync_key, compression=compression(self.config))
+        if not await server.start():
+            return False
+        self.companion_server = server
+        set_companion_server(server)
+        return True
+
+    def _offer_sync_key(self, private, public_key: str, code: str) -> Dict[str, Any]:
+        """Another of the user's machines joins memory sync with the pairing code (the first join creates the key)."""
+        state = SyncState.load()
+        if state is None:
+            state = SyncState.create()
+            state.save()
+        self.update_activity("⇄ Another machine joined memory sync - facts, lists and preferences will stay in step",
+                             "success")
+        return key_offer(state, private, public_key, code)
+
+    def _start_matrix_bridge(self) -> None:
+        """Chat from Matrix clients (config.matrix_enabled, see matrix.py)."""
+        try:
+            bridge = MatrixBridge.from_config(self.config, self._on_matrix_message)
+        except ImportError:
+            self.update_activity("⚠ Matrix bridge needs the matrix extra (pip install 'voice-assistant[matrix]')", "warning")
+            return
+        except MatrixError as e:
+            self.update_activity(f"⚠ {e}", "warning")
+            return
+        if bridge:
+            self.matrix_bridge = bridge
+            set_matrix_bridge(bridge)
+            get_task_supervisor().spawn("matrix bridge", bridge.run)
+
+    def _on_companion_message(self, text: str, device) -> None:
+        """A text message from a paired device: handled like typed chat, the reply goes back to it."""
+        self._on_remote_message(text, f"📱 Message from {device.name}")
+
+    def _on_matrix_message(self, text: str, sender: str, room_id: str) -> None:
+        """A Matrix message from an allowed user: handled like typed chat, the reply goes back to the room."""
+        self._on_remote_message(text, f"💬 Matrix message from {sender}")
+
+    def _on_remote_message(self, text: str, activity: str) -> None:
+        try:
+            chat_history_widget = self.query_one("#chat-history-widget", ChatHistory)
+        except Exception:
+            chat_history_widget = None
+        if chat_history_widget:
+            chat_history_widget.add_message("User", "••••" if self._awaiting_pin() else text)
+        self.update_activity(activity)
+        asyncio.create_task(self._detect_followups(text))
+        self._current_chat_task = asyncio.create_task(self._process_chat_message(text, chat_history_widget))
+
+    def action_keymap_help(self) -> None:
+        """Show the active keys (?)."""
+        if not isinstance(self.screen, (KeymapHelpScreen, LockScreen)):
+            self.push_screen(KeymapHelpScreen(self.keymap))
+
+    def action_command_palette(self) -> None:
+        """Search and run any action or skill (Ctrl+P)."""
+        if not isinstance(self.screen, (CommandPaletteScreen, LockScreen)):
+            self.push_screen(CommandPaletteScreen(palette_entries(self.keymap, self.config)), self._run_palette_entry)
+
+    def _run_palette_entry(self, entry: Optional[PaletteEntry]) -> None:
+        """Run the chosen action, or put the chosen phrase in the chat input."""
+        if entry is None:
+            return
+        if entry.action:
+            self.call_later(self.run_action, entry.action)
+            return
+        self.action_goto_chat()
+        chat_input = self.query_one("#chat-input", ExpandableInput)
+        chat_input.value = entry.say
+        chat_input.focus()
+
     def action_copy_logs(self) -> None:
         """Copy activity logs to clipboard."""
         try:
@@ -947,7 +1906,7 @@ class VoiceAssistantApp(App):
                              for msg in activity_feed.messages])
 
             if logs:
-                pyperclip.copy(logs)
+                pyperclip.copy(redact(logs))
                 self.update_activity("Activity logs copied to clipboard!", "success")
             else:
                 self.update_activity("No logs to copy.", "warning")
@@ -960,7 +1919,7 @@ class VoiceAssistantApp(App):
         with NoScrollContainer(id="main-layout"):
             # LEFT COLUMN - Visualizer (top) + Tabs (bottom)
             with Vertical(id="left-column"):
-                # Voice visualizer - always show, but animation controlled by voice_enabled
+                # Voice visualizer - always show, but animation controlled by config.voice_active
                 viz_panel = VoiceVisualizerPanel(
                     visualization_style=VisualizationStyle.TRON_BARS
                 )
@@ -970,13 +1929,8 @@ class VoiceAssistantApp(App):
 
                 # Tab buttons below visualizer (new order: Chat first, Settings second)
                 with Vertical(id="sidebar"):
-                    yield Button(" 💬  Chat", id="tab-chat", classes="tab-button")
-                    yield Button(" 📅  Schedule", id="tab-schedule", classes="tab-button")
-                    yield Button(" 📁  Projects", id="tab-projects", classes="tab-button")
-                    yield Button(" ⚙️   Settings", id="tab-settings", classes="tab-button")
-                    yield Button(" 📊  Status", id="tab-status", classes="tab-button")
-                    yield Button(" 🔧  Tools", id="tab-tools", classes="tab-button")
-                    yield Button(" 💻  Workers", id="tab-workers", classes="tab-button")
+                    for tab, icon, name in TABS:
+                        yield Button(tab_label(icon, name, SizeClass.WIDE), id=f"tab-{tab}", classes="tab-button")
 
             # RIGHT COLUMN - Content area
             with Container(id="content-area"):
@@ -984,6 +1938,13 @@ class VoiceAssistantApp(App):
                 with Container(id="content-status", classes="content-pane active-pane") as status_pane:
                     status_pane.border_title = "◉ Status"
                     yield Button("📋 Copy Logs", id="btn-copy-logs", classes="action-button copy-logs-btn")
+                    yield UsageWidget(id="usage-widget")
+                    with Container(id="stats-charts"):
+                        yield Sparkline("CPU", unit="%", high=100, id="cpu-chart")
+                        yield Sparkline("Reply", unit="s", decimals=1, id="latency-chart")
+                        yield Sparkline("Net", unit=" KB/s", decimals=1, id="bandwidth-chart")
+                        yield BarChart("Messages / day", id="messages-chart")
+                        yield Static("", id="buffer-usage")
                     yield ActivityFeed(id="activity")
 
                 # Settings content
@@ -1220,6 +2181,7 @@ class VoiceAssistantApp(App):
                 with Container(id="content-chat", classes="content-pane") as chat_pane:
                     chat_pane.border_title = "◇ Chat"
                     yield ChatHistory(id="chat-history-widget")
+                    yield CaptionLine(id="chat-captions")
                     yield ExpandableInput(placeholder="Type a message... (Shift+Enter for newline)", id="chat-input")
 
                 # Projects content
@@ -1231,6 +2193,8 @@ class VoiceAssistantApp(App):
                 # Schedule content
                 with Container(id="content-schedule", classes="content-pane") as schedule_pane:
                     schedule_pane.border_title = "◷ Schedule"
+                    yield FollowUpWidget(id="followup-widget")
+                    yield ListsWidget(id="lists-widget")
                     yield ScheduleWidget(id="schedule-widget")
                     yield ExpandableInput(placeholder="Add meeting, change time...", id="schedule-input", classes="pane-input")
 
@@ -1238,6 +2202,18 @@ class VoiceAssistantApp(App):
                 with Container(id="content-workers", classes="content-pane") as workers_pane:
                     workers_pane.border_title = "⬡ Workers"
                     yield WorkerDashboard(id="workers-dashboard")
+
+                # Inbox content
+                with Container(id="content-inbox", classes="content-pane") as inbox_pane:
+                    inbox_pane.border_title = "✉ Inbox"
+                    yield CallScreeningWidget(id="call-screening-widget")
+                    yield InboxWidget(id="inbox-widget")
+
+                # Help content
+                with Container(id="content-help", classes="content-pane") as help_pane:
+                    help_pane.border_title = "? Help"
+                    yield Input(placeholder="Search what I can do...", id="help-search")
+                    yield HelpWidget(id="help-widget")
         # Footer outside main-layout to span full width at bottom
         yield CyberpunkFooter(id="footer")
 
@@ -1280,7 +2256,7 @@ class VoiceAssistantApp(App):
         with open("/tmp/xswarm_debug.log", "a") as f:
             f.write("DEBUG: on_mount() - before initialize_memory()\n")
             f.flush()
-        asyncio.create_task(self.initialize_memory())
+        self._memory_task = asyncio.create_task(self.initialize_memory())
 
         # Initialize chat engine in background so first message is instant
         asyncio.create_task(self._init_chat_engine_background())
@@ -1297,6 +2273,43 @@ class VoiceAssistantApp(App):
             f.write("DEBUG: on_mount() - after scheduling voice initialization\n")
             f.flush()
         
+        self._setup_remote_commands()
+        self._setup_calendar_sync()
+        self._setup_jobs()
+        self._setup_startup_profile()
+        # The machine is in another zone than home: offer travel mode
+        suggestion = travel_suggestion()
+        if suggestion:
+            self.update_activity(suggestion, "warning")
+        self._apply_layout(self.size.width, self.size.height)
+        for problem in self.keymap.problems:
+            self.update_activity(f"⚠ {problem} - see keymap.py", "warning")
+        for problem in get_skill_plugins().problems:
+            self.update_activity(f"⚠ Plugin not loaded: {problem}", "warning")
+        for problem in get_script_hooks().problems:
+            self.update_activity(f"⚠ Script not loaded: {problem}", "warning")
+        if get_chaos().active:
+            self.update_activity(f"🐒 Chaos mode: {get_chaos().describe()}", "warning")
+        if self.session_lock.idle_timeout:
+            self.set_interval(10.0, self._check_idle_lock)
+        # UI refresh timers (not background jobs)
+        self.set_interval(2.0, self._update_audio_health)
+        self.set_interval(1.0, self._update_timers)
+        self.set_interval(10.0, self._update_mood)
+        self._setup_privacy_indicators()
+        self._setup_accessible_stream()
+        if self.config.control_socket:
+            asyncio.create_task(self._start_control_socket())
+        if self.config.local_api or self.config.ics_feed == "local":
+            asyncio.create_task(self._start_local_api())
+        if self.config.companion_enabled:
+            asyncio.create_task(self._start_companion_server())
+        self._start_matrix_bridge()
+        self.set_interval(5.0, self._update_resource_usage)
+        self.call_later(self._apply_power_profile, get_power_manager().profile)
+        self._load_chart_history()
+        self.set_interval(60.0, self._update_message_chart)
+
         # Manually trigger tab highlighting on startup
         self.watch_active_tab(self.active_tab)
 
@@ -1306,6 +2319,26 @@ class VoiceAssistantApp(App):
         # Show immediate welcome message with persona name (before chat engine loads)
         self.call_later(self._show_initial_welcome)
 
+    def on_resize(self, event: Resize) -> None:
+        """The terminal was resized (SIGWINCH): switch layouts when the size class changes."""
+        self._apply_layout(event.size.width, event.size.height)
+
+    def _apply_layout(self, width: int, height: int) -> None:
+        """Collapse or restore panels for a `width` x `height` terminal (see layout.py)."""
+        size = size_class(width, height)
+        if size == self.size_class:
+            return
+        self.size_class = size
+        for other in SizeClass:
+            self.set_class(other == size, other.css_class)
+        for tab, icon, name in TABS:
+            try:
+                self.query_one(f"#tab-{tab}", Button).label = tab_label(icon, name, size)
+            except Exception:
+                pass  # Not composed yet
+        # Repaint everything, so nothing from the old layout is left behind
+        self.screen.refresh(layout=True)
+
     def _focus_chat_input(self) -> None:
         """Focus the chat input widget. Called on startup and when switching to chat pane."""
         try:
@@ -1353,6 +2386,32 @@ class VoiceAssistantApp(App):
         with open("/tmp/xswarm_debug.log", "a") as f:
             f.write(f"DEBUG: Voice initialization completed: {success}\n")
             f.flush()
+        await self._finish_startup()
+        if not self.startup.exit_when_done:
+            await self._maybe_start_tutorial()
+
+    async def _maybe_start_tutorial(self) -> None:
+        """Start the first-use tutorial (see tutorial.py) unless it has run before or something else is going on."""
+        set_tutorial_hooks(TutorialHooks(
+            wake_words=self._wake_words(),
+            wake_word_heard=lambda: time.monotonic() - self._wake_word_at < 15,
+            mic=self.mic_meter if self.voice_initialized else None,
+        ))
+        progress = TutorialProgress()
+        if not progress.should_offer:
+            return
+        try:
+            await asyncio.wait_for(self._welcomed.wait(), timeout=30)
+        except asyncio.TimeoutError:
+            pass
+        busy = ((self.reply_workflow and self.reply_workflow.is_active) or self._flow_is_active()
+                or (self.share_workflow and self.share_workflow.is_active)
+                or get_confirmation_policy().has_pending())
+        if busy:
+            return  # Offered again next start
+        progress.mark("started")
+        get_tutorial_hooks().progress = progress
+        await self._start_flow(tutorial_flow())
 
     def add_dummy_chat_messages(self):
         """Add dummy chat messages for demonstration"""
@@ -1598,6 +2657,1143 @@ class VoiceAssistantApp(App):
         """Jump to Workers tab (7)."""
         self._goto_tab_by_index(6)
 
+    def action_goto_inbox(self) -> None:
+        """Jump to Inbox tab (8)."""
+        self._goto_tab_by_index(7)
+
+    def action_goto_help(self) -> None:
+        """Jump to Help tab (9)."""
+        self._goto_tab_by_index(8)
+
+    def on_input_changed(self, event: Input.Changed) -> None:
+        """Filter the Help tab as the search is typed."""
+        if event.input.id == "help-search":
+            self.query_one("#help-widget", HelpWidget).search_text = event.value
+
+    def _setup_jobs(self) -> None:
+        """Register the dashboard's background work on the job scheduler and start it."""
+        jobs = self.job_scheduler
+        if self.config.crash_reports:
+            self._setup_crash_reports()
+            if self.config.crash_upload:
+                jobs.add_job("crash_upload", self._upload_crash_reports, interval=6 * 60 * 60, jitter=60,
+                             run_at_start=True, description="Send crash reports to the server (config.crash_upload)")
+        jobs.add_job("inbox_sync", lambda: self._sync_inbox(raise_errors=True), interval=60, jitter=10,
+                     run_at_start=True, description="Sync inbox and voicemail with the server")
+        jobs.add_job("call_screening", self._poll_call_screening, interval=2,
+                     description="Refresh calls being screened")
+        jobs.add_job("calendar_sync", self._background_calendar_sync, interval=15 * 60, jitter=60,
+                     description="Mirror the calendar to the server and pull its changes")
+        jobs.add_job("event_reminders", self._check_event_reminders, cron="* * * * *",
+                     description="Remind about today's events")
+        jobs.add_job("reminder_methods", self._adjust_reminder_methods, cron="0 4 * * *", run_at_start=True,
+                     description="Leave out reminder channels that get ignored (reminder_methods_auto)")
+        jobs.add_job("meeting_prep", self._check_meeting_prep, cron="* * * * *",
+                     description="Prepare briefs before meetings")
+        jobs.add_job("announcements", self._speak_announcements, cron="* * * * *",
+                     description="Say scheduled announcements (`xswarm dev announce`)")
+        jobs.add_job("news", self._fetch_news, cron="* * * * *",
+                     description="Fetch RSS/Atom feeds on their schedules for the briefing (`xswarm dev news`)")
+        jobs.add_job("alarms", self._ring_alarms, interval=2,
+                     description="Ring wake-up alarms (`xswarm dev alarm`) until snoozed or dismissed")
+        if self.emergency:
+            jobs.add_job("emergency", self._tick_emergency, interval=2, run_at_start=True,
+                         description="Send, repeat and follow up emergency alerts until a contact answers")
+        if self.config.evening_review_time:
+            hour, minute = self.config.evening_review_time.split(":")
+            jobs.add_job("evening_review", self._scheduled_evening_review, cron=f"{int(minute)} {int(hour)} * * *",
+                         description="Start the end-of-day review")
+        if self.config.memory_digest_time and self.config.memory_enabled:
+            jobs.add_job("memory_digest", self._scheduled_memory_digest,
+                         cron=digest_cron(self.config.memory_digest_day, self.config.memory_digest_time),
+                         description="Save the weekly digest of conversations, facts and reminders")
+        jobs.add_job("geocode_locations", self._geocode_locations, interval=10 * 60, jitter=60,
+                     description="Look up map coordinates for event locations")
+        jobs.add_job("retention", self._apply_retention, cron="30 3 * * *",
+                     description="Delete transcripts, recordings, events and logs past their retention")
+        jobs.add_job("list_sync", lambda: self._sync_lists(raise_errors=True), interval=5 * 60, jitter=30,
+                     run_at_start=True, description="Sync shopping and todo lists with the server")
+        jobs.add_job("memory_sync", self._sync_memory, interval=5 * 60, jitter=30, run_at_start=True,
+                     description="Sync facts, lists and preferences with the user's other machines (encrypted)")
+        if self.config.ics_feed == "server":
+            jobs.add_job("ics_feed", lambda: publish_feed(self.config, self.user_id), interval=10 * 60, jitter=30,
+                         run_at_start=True, description="Upload the calendar feed for calendar apps when it changed")
+        jobs.add_job("flag_sync", self._sync_flags, interval=15 * 60, jitter=60, run_at_start=True,
+                     description="Fetch this user's feature flag overrides from the server")
+        jobs.add_job("quota_sync", self._sync_quota, interval=15 * 60, jitter=60, run_at_start=True,
+                     description="Report phone and SMS usage to the server and refresh what's left")
+        jobs.add_job("document_indexing", self._index_project_docs, interval=6 * 60 * 60, jitter=5 * 60,
+                     description="Index active projects' folders for project questions")
+        jobs.add_job("clock_check", self._check_clock, interval=30 * 60, jitter=60, run_at_start=True,
+                     description="Compare the local clock with the server (and NTP) and warn when it drifts")
+        if self.remote_commands:
+            jobs.add_job("remote_commands", self._poll_remote_commands, interval=60, jitter=10, run_at_start=True,
+                         description="Run commands the server queued for this assistant (config.remote_commands)")
+        if self.config.exchange_rates:
+            jobs.add_job("exchange_rates", self._refresh_exchange_rates, interval=12 * 60 * 60, jitter=10 * 60,
+                         run_at_start=True, description="Fetch currency exchange rates for offline conversions")
+        if self.config.speech_cache:
+            jobs.add_job("speech_cache", self._warm_speech_cache, interval=10 * 60, jitter=60,
+                         description="Record common phrases for instant, offline playback while nothing is said")
+        if self.config.voice_warmup:
+            jobs.add_job("warmup", self._warm_voice_pipeline, interval=60, jitter=10,
+                         description="Warm the voice models after loading and after long quiet spells")
+        if self.config.gpu_yield_models:
+            jobs.add_job("compute", self._check_compute, interval=30, jitter=5,
+                         description="Move models off the GPU while the user's own work needs it (config.gpu_yield_models)")
+        if self.config.update_check:
+            jobs.add_job("update_check", self._check_for_update, interval=UPDATE_CHECK_HOURS * 60 * 60,
+                         jitter=10 * 60, run_at_start=True,
+                         description="Look for a newer signed release of xswarm and show it in the status bar")
+        if self.push_router:
+            jobs.add_job("push_retry", self._retry_pushes, interval=60, run_at_start=True,
+                         description="Resend failed ntfy/Pushover pushes whose retry time has come")
+        jobs.start()
+        if self.config.project_watch and get_project_docs() is not None:
+            # Saved files reindexed within seconds instead of waiting for document_indexing
+            from .tools import get_planner_data
+            watcher = ProjectWatcher(get_project_docs(), get_planner_data(),
+                                     debounce=self.config.project_watch_debounce)
+            set_project_watcher(watcher)
+            watcher.start()
+
+    async def _sync_inbox(self, raise_errors: bool = False) -> None:
+        """Push local inbox status changes and pull new messages from the server."""
+        try:
+            if self.inbox_manager is None:
+                from .inbox import InboxManager
+                from .tools import get_inbox_store
+                self.inbox_manager = InboxManager(self.config, store=get_inbox_store(),
+                                                  events=get_event_store())
+            with correlation.interaction("inbox"):  # New messages and what they set off, one thread
+                result = await self.inbox_manager.sync()
+                if result["added"]:
+                    self.update_activity(f"📥 {result['added']} new inbox message(s)")
+                    send_desktop_notification("xSwarm Inbox", f"{result['added']} new message(s)")
+                    await self._propose_email_tasks(self.inbox_manager.new_items)
+            if self.call_screening is not None:
+                new_voicemails = await self.call_screening.sync_voicemail()
+                if new_voicemails:
+                    self.update_activity(f"📞 {new_voicemails} new voicemail(s)")
+        except Exception:
+            # Server unreachable - local inbox keeps working (the job scheduler records the failure)
+            if raise_errors:
+                raise
+
+    async def _propose_email_tasks(self, items) -> None:
+        """Propose events and deadlines from new email (email_tasks.py); confirmed in the Schedule pane."""
+        try:
+            if self.email_task_detector is None:
+                from .email_tasks import EmailTaskDetector
+                from .voice import AIClient
+                self.email_task_detector = EmailTaskDetector(AIClient(self.config, "email_tasks"))
+            from .email_tasks import notice
+            from .tools import get_followup_store, get_planner_data
+            for item in items:
+                proposals = await self.email_task_detector.detect(item, get_planner_data())
+                for proposal in get_followup_store().add(proposals):
+                    self.update_activity(notice(item, proposal))
+        except Exception:
+            pass  # Detection is best-effort
+
+    async def _background_calendar_sync(self) -> None:
+        """Mirror the calendar to the server and pull its changes back (calendar_sync job; deferred while the machine is busy)."""
+        from .tools import get_calendar_sync, sync_calendar_to_server
+        result = await sync_calendar_to_server()
+        if result.startswith("✗") and "not configured" not in result:
+            raise RuntimeError(result.lstrip("✗ "))
+        sync = get_calendar_sync()
+        if sync is not None:
+            await sync.pull()
+        await asyncio.to_thread(get_appointment_cache().prefetch)
+
+    async def _geocode_locations(self) -> None:
+        """Store coordinates for event locations (geocode_locations job; off when config.geocoder is "none")."""
+        from .geocoding import geocode_events, get_location_settings
+        from .tools import get_planner_data
+        geocoder = get_location_settings().make_geocoder()
+        if geocoder is None:
+            return
+        located = await geocode_events(get_planner_data(), geocoder)
+        if located:
+            self._refresh_schedule_widget()
+
+    async def _sync_lists(self, raise_errors: bool = False) -> None:
+        """Push list changes and pull other devices' (list_sync job, and after each change)."""
+        try:
+            result = await sync_lists(self.config, self.user_id)
+            if result["changed"]:
+                logging.debug(f"Lists: {result['changed']} item(s) changed on another device")
+        except Exception:
+            # Server unreachable - lists keep working locally and the changes stay queued
+            if raise_errors:
+                raise
+
+    def _setup_crash_reports(self) -> None:
+        """Report errors no task awaited, and mention a crash that ended the last run (crash_report.py)."""
+        reporter = get_crash_reporter()
+        asyncio.get_running_loop().set_exception_handler(reporter.loop_exception_handler)
+        crashes = reporter.unseen()
+        if crashes:
+            where = "" if len(crashes) == 1 else f" ({len(crashes)} reports)"
+            self.update_activity(f"✗ xSwarm crashed last time{where}: {crashes[0].error} - "
+                                 f"`xswarm dev crash-report show {crashes[0].id}`", "error")
+
+    def _setup_startup_profile(self) -> None:
+        """Warn in the activity feed about startup stages over budget, and time the server's first answer."""
+        self.startup.finish("dashboard")
+        for stage in self.startup.over_budget():
+            self._on_startup_over_budget(stage)
+        self.startup.on_over = self._on_startup_over_budget
+        asyncio.create_task(self._time_server_handshake())
+
+    def _on_startup_over_budget(self, stage) -> None:
+        self.update_activity(f"⚠ Slow start: {stage.name} took {stage.seconds:.1f}s (budget {stage.budget:g}s)"
+                             " - `xswarm --profile-startup` for the breakdown", "warning")
+
+    async def _time_server_handshake(self) -> None:
+        """The "server" startup stage: until the xSwarm server first answers GET /health."""
+        from .api_client import ApiClient
+
+        client = ApiClient(self.config.server_url, self.config.api_token)
+        try:
+            with self.startup.stage("server"):
+                await client.get("/health", timeout=10.0, retry=False)
+        except Exception as e:
+            logging.debug(f"Server handshake failed: {e}")
+        finally:
+            await client.close()
+
+    async def _finish_startup(self) -> None:
+        """Startup is over once voice and the chat engine are ready: log the breakdown, quit with --profile-startup."""
+        await self._welcomed.wait()
+        self.startup.done()
+        if self.startup.exit_when_done:
+            self.exit()
+
+    def _handle_exception(self, error: Exception) -> None:
+        """An error in the dashboard itself (Textual shuts the app down after this): keep a crash report."""
+        if self.config.crash_reports:
+            get_crash_reporter().capture(error, "dashboard")
+        super()._handle_exception(error)
+
+    async def _upload_crash_reports(self) -> None:
+        """Send crash reports not sent yet (crash_upload job, only with config.crash_upload)."""
+        from .api_client import ApiClient, ApiPolicy
+
+        client = ApiClient(self.config.server_url, self.config.api_token, policy=ApiPolicy.from_config(self.config))
+        try:
+            sent = await get_crash_reporter().upload_pending(client, self.user_id)
+        finally:
+            await client.close()
+        if sent:
+            logging.info(f"Sent {sent} crash report(s) to the server")
+
+    async def _sync_memory(self) -> None:
+        """Exchange encrypted memory changes with the user's other machines (memory_sync job)."""
+        result = await sync_memory(self.config, self.user_id)
+        if result and result["applied"]:
+            logging.info(f"Memory sync: {result['applied']} change(s) from another machine")
+            self._refresh_schedule_widget()
+
+    async def _sync_flags(self) -> None:
+        """Refresh the server's feature flag overrides for this user (flag_sync job)."""
+        from .api_client import ApiClient, ApiPolicy
+        from .flags import get_feature_flags
+
+        client = ApiClient(self.config.server_url, self.config.api_token, policy=ApiPolicy.from_config(self.config))
+        try:
+            changed = await get_feature_flags().sync(client, self.user_id)
+        finally:
+            await client.close()
+        if changed:
+            self.update_activity(f"⚑ Feature flags changed: {', '.join(changed)} (`xswarm dev config flags`)", "info")
+
+    async def _sync_quota(self) -> None:
+        """Report metered usage and refresh the remaining counts (quota_sync job)."""
+        from .quota import sync_quota
+        reported = await sync_quota(self.config)
+        if reported:
+            logging.debug(f"Reported usage to the server: {reported}")
+
+    async def _index_project_docs(self) -> None:
+        """Reindex changed files in active projects' folders (document_indexing job)."""
+        from .tools import get_planner_data
+        docs = get_project_docs()
+        if docs is None:
+            return
+        for name, report in (await docs.index_all(get_planner_data())).items():
+            if report.files or report.removed:
+                logging.debug(f"Indexed {name}: {report.summary()}")
+
+    async def _apply_retention(self) -> None:
+        """Delete data past its retention period (retention job; only reported with retention_dry_run)."""
+        from .retention import RetentionEngine
+        engine = RetentionEngine.from_config(self.config, events=get_event_store())
+        report = await asyncio.to_thread(engine.run)
+        if report.total():
+            self.update_activity(f"🗑️ {report.summary()}")
+        for error in report.errors:
+            self.update_activity(f"Retention failed for {error}", "warning")
+
+    def _setup_calendar_sync(self) -> None:
+        """Make the planner calendar available to the sync_calendar_to_server tool."""
+        try:
+            from .calendar_mirror import CalendarMirror
+            from .scheduler_client import CalendarSync, SchedulerClient
+            from .tools import get_planner_data, set_calendar_sync
+            client = SchedulerClient.from_config(self.config)
+            add_event_listener(client.on_event)  # Inbox activity invalidates its cached calendar reads
+            mirror = CalendarMirror(client, self.user_id)
+            set_calendar_sync(CalendarSync(get_planner_data(), client, self.user_id, mirror=mirror))
+        except Exception:
+            return
+        if self.config.calendar_live:
+            from .calendar_live import CalendarSubscription
+            self.calendar_subscription = CalendarSubscription.from_config(self.config, self.user_id, self._calendar_changed,
+                                                                          on_hint=self._on_server_hint)
+            self.calendar_subscription.start()
+
+    def _setup_remote_commands(self) -> None:
+        """Run the commands the server queues for this assistant (those config.remote_commands allows)."""
+        from .remote_commands import CommandRunner
+        self.remote_commands = CommandRunner.from_config(self.config, {
+            "speak": self._remote_speak,
+            "refresh_config": self._remote_refresh_config,
+            "call": self._remote_call,
+        })
+
+    def _on_server_hint(self, hint: dict) -> None:
+        """A hint on the calendar subscription socket other than a calendar change."""
+        if hint.get("type") == "command" and self.remote_commands:
+            asyncio.ensure_future(self.job_scheduler.run_now("remote_commands"))
+
+    async def _poll_remote_commands(self) -> None:
+        """Run queued server commands (remote_commands job, and right away when the server hints)."""
+        for outcome in await self.remote_commands.poll():
+            if outcome.status == "rejected" and not outcome.repeated:
+                self.update_activity(f"⚠ Refused a {outcome.command.type} command from the server: {outcome.result}",
+                                     "warning")
+            elif outcome.status == "failed" and not outcome.repeated:
+                self.update_activity(f"✗ Server {outcome.command.type} command failed: {outcome.result}", "warning")
+
+    async def _remote_speak(self, args: dict) -> str:
+        from .remote_commands import CommandFailed
+        text = str(args.get("text") or "").strip()
+        if not text:
+            raise CommandFailed("nothing to say (args.text is empty)")
+        self.update_activity(f"📣 {text}", "info")
+        if self.voice_orchestrator is None:
+            return "shown; voice is off"
+        if await self.voice_orchestrator.announce(text, priority=str(args.get("priority") or "normal")):
+            return "spoken"
+        return "shown; not spoken now (quiet hours, a meeting or do not disturb)"
+
+    async def _remote_refresh_config(self, args: dict) -> str:
+        changed = await asyncio.to_thread(self.config.reload)
+        set_volume_settings(VolumeSettings.from_config(self.config))
+        if not changed:
+            return "no changes"
+        self.update_activity(f"⚙ Settings reloaded from the config file: {', '.join(changed)}", "info")
+        return f"reloaded: {', '.join(changed)}"
+
+    async def _remote_call(self, args: dict) -> str:
+        from .remote_commands import CommandFailed
+        from .tools import make_call_handler
+        message = str(args.get("message") or "").strip()
+        if not message:
+            raise CommandFailed("no message to call about (args.message is empty)")
+        result = await make_call_handler(message, priority=str(args.get("priority") or "normal"))
+        if not result.get("success"):
+            raise CommandFailed(result.get("message") or "call not placed")
+        self.update_activity(f"📞 Calling you for the server: {message[:60]}", "info")
+        return result.get("message") or "call placed"
+
+    async def _calendar_changed(self) -> None:
+        """The server calendar changed (calendar subscription): pull it, refresh Today, check reminders."""
+        from .tools import get_calendar_sync
+        sync = get_calendar_sync()
+        if sync is None:
+            return
+        sync.client.invalidate()
+        report = await sync.pull()
+        if report is not None and (report.updated or report.deleted):
+            await asyncio.to_thread(get_appointment_cache().prefetch)
+            try:
+                self.query_one(ScheduleWidget).refresh()
+            except Exception:
+                pass  # Not mounted in this layout
+            await self._check_event_reminders()
+
+    async def _check_event_reminders(self) -> None:
+        """Remind the user of today's events reminder_minutes before they start, honoring category prefs (and meetings)."""
+        try:
+            from .reminders import deliver_reminder, due_reminders
+            now = corrected_now()  # A drifted local clock would remind early or late
+            if self.voice_orchestrator and self.voice_orchestrator.held_announcements:
+                if await self.voice_orchestrator.announce_held(now):
+                    self.update_activity("🔔 Meeting over - saying what came up during it", "info")
+            events = get_appointment_cache().events()
+            for reminder in due_reminders(events, now, self._reminded_events):
+                delivered = await deliver_reminder(
+                    reminder, activity=self.update_activity, announcer=self.voice_orchestrator,
+                    methods=self._reminder_methods,
+                    on_click=lambda clicked: self.reminder_receipts.acknowledge(clicked.key, "desktop"))
+                self.reminder_receipts.sent(reminder, delivered, now)
+                record_event("reminder", reminder.message("desktop"), {
+                    "title": reminder.title, "key": reminder.key,
+                    "channels": [channel for channel in METHODS if delivered[channel]],
+                    "delivered": delivered["desktop"] or delivered["speech"]})
+        except Exception:
+            pass  # Reminders are best-effort
+
+    async def _speak_announcements(self) -> None:
+        """Say announcements scheduled for now (announcements job), honoring quiet hours, DND and meetings."""
+        from .announcements import AnnouncementStore, briefing_context, render
+        from .tools import get_planner_data
+        store = AnnouncementStore()  # Re-read each minute: `xswarm dev announce` edits the file
+        now = corrected_now()
+        due = store.due(now)
+        if not due:
+            return
+        planner = get_planner_data()
+        # Only take news (marking it read) when an announcement says it
+        wants_news = any("{news}" in a.text or "{briefing}" in a.text for a in due)
+        context = briefing_context(now, get_appointment_cache().events(), planner.get_tasks(status="next"),
+                                   news_briefing() if wants_news else "", upcoming_holidays(now.date()))
+        for announcement in due:
+            text = render(announcement.text, context)
+            self.update_activity(f"📣 {text}", "info")
+            spoken = False
+            if self.voice_orchestrator is not None:
+                spoken = await self.voice_orchestrator.announce(text, announcement.priority, announcement.tags)
+            record_event("reminder", text, {"announcement": announcement.id, "delivered": spoken})
+
+    async def _fetch_news(self) -> None:
+        """Fetch due feeds and summarize their new items (news job)."""
+        if self._news_reader is None:
+            from .voice import AIClient
+            self._news_reader = NewsReader(get_news_store(), AIClient(self.config, "news"))
+        new = await self._news_reader.fetch_due(corrected_now())
+        if new:
+            self.update_activity(f"📰 {len(new)} new news item(s)", "info")
+
+    async def _ring_alarms(self) -> None:
+        """Start, ring and time out alarms (alarms job), gathering each one's briefing just before it rings."""
+        now = corrected_now()
+        for event, alarm in self.alarm_clock.tick(now):
+            if event == "prepare":
+                self._alarm_briefings[alarm.id] = self._wake_briefing(now)
+            elif event == "ring":
+                self.update_activity(f"⏰ Alarm {alarm.describe()} - say \"five more minutes\" or \"I'm up\"", "info")
+                record_event("reminder", f"Alarm {alarm.time}", {"alarm": alarm.id, "snoozes": alarm.snoozes})
+            elif event == "timeout":
+                self._alarm_briefings.pop(alarm.id, None)
+                self.update_activity(f"⏰ Alarm {alarm.describe()} rang unanswered for "
+                                     f"{self.alarm_clock.ring_minutes:g} minutes", "warning")
+            elif event == "missed":
+                self.update_activity(f"⏰ Missed the {alarm.describe()} alarm (the assistant wasn't running)", "warning")
+        ringing = self.alarm_clock.ringing(now)
+        audio_io = getattr(self.voice_orchestrator, "audio_io", None)
+        if ringing and audio_io is not None:
+            volume = max(self.alarm_clock.volume(alarm, now) for alarm in ringing)
+            audio_io.play_media(ring_tone(audio_io.sample_rate) * volume)
+
+    def _wake_briefing(self, now) -> str:
+        from .announcements import briefing_context, render
+        from .tools import get_planner_data
+        try:
+            planner = get_planner_data()
+            context = briefing_context(now, get_appointment_cache().events(), planner.get_tasks(status="next"),
+                                       news_briefing(), upcoming_holidays(now.date()))
+        except Exception as e:
+            logging.debug(f"Wake-up briefing without the planner: {e}")
+            context = briefing_context(now, news=news_briefing())
+        return render(WAKE_BRIEFING, context)
+
+    def _reminder_ack_utterance(self, text: str) -> bool:
+        """ "Got it" right after a spoken reminder: noted as seen (see reminders.ReminderReceipts), not sent on."""
+        if get_confirmation_policy().has_pending():
+            return False  # "Okay" answers the question asked since
+        reminder = self.reminder_receipts.heard(text, corrected_now())
+        if reminder is None:
+            return False
+        self.update_activity(f"✓ Reminder for {reminder.title} acknowledged", "info")
+        return True
+
+    def _adjust_reminder_methods(self) -> None:
+        """Leave out reminder channels the user keeps ignoring (reminder_methods job, config.reminder_methods_auto)."""
+        if not self.config.reminder_methods_auto:
+            self._reminder_methods = list(self.config.reminder_methods)
+            return
+        stats = effectiveness(get_event_store(), corrected_now())
+        methods = adjust_methods(self.config.reminder_methods, stats, self.config.reminder_min_ack_rate)
+        for channel in set(self._reminder_methods) - set(methods):
+            self.update_activity(f"🔔 Reminders: stopped {METHOD_NAMES[channel]}, acknowledged "
+                                 f"{stats[channel].rate:.0%} of the time", "info")
+        for channel in set(methods) - set(self._reminder_methods):
+            self.update_activity(f"🔔 Reminders: trying {METHOD_NAMES[channel]} again", "info")
+        self._reminder_methods = methods
+
+    def _emergency_utterance(self, text: str) -> bool:
+        """The emergency phrase, or cancelling/standing down an alert; no wake word needed."""
+        if self.emergency is None or not self.emergency.wants(text, self._wake_words()):
+            return False
+        asyncio.create_task(self._handle_emergency_utterance(text))
+        return True
+
+    async def _handle_emergency_utterance(self, text: str) -> None:
+        reply = await self.emergency.heard(text, corrected_now(), self._wake_words())
+        if reply:
+            self.update_activity(f"🚨 {reply}", "warning")
+            await self._say_to_user(reply)  # Not an announcement: quiet hours and DND don't hold it
+
+    async def _tick_emergency(self) -> None:
+        """Count down, send and repeat the emergency alert (emergency job)."""
+        for note in await self.emergency.tick(corrected_now()):
+            self.update_activity(f"🚨 {note}", "warning")
+            await self._say_to_user(note)
+
+    def _household_utterance(self, text: str) -> bool:
+        """Switching household profiles ("this is Emma") or the PIN that asked for; no wake word needed."""
+        reply = get_household().heard(text, get_confirmation_policy().pin_hash)
+        if reply is None:
+            return False
+        self.update_activity(f"👤 {reply}", "info")
+        asyncio.create_task(self._say_to_user(reply))
+        return True
+
+    def _alarm_utterance(self, text: str) -> bool:
+        """Snooze or dismiss a ringing alarm ("five more minutes", "I'm up"); no wake word needed."""
+        command = parse_alarm_command(text) if self.alarm_clock.ringing(corrected_now()) else None
+        if command is None:
+            return False
+        asyncio.create_task(self._handle_alarm_command(*command))
+        return True
+
+    async def _handle_alarm_command(self, action: str, minutes: Optional[int]) -> None:
+        now = corrected_now()
+        if action == "snooze":
+            minutes = minutes or self.alarm_clock.snooze_minutes
+            if self.alarm_clock.snooze(now, minutes):
+                self.update_activity(f"⏰ Snoozed until {now + datetime.timedelta(minutes=minutes):%H:%M}", "info")
+                await self._say_to_user(f"Okay, {minutes:g} more minutes.")
+            return
+        dismissed = self.alarm_clock.dismiss(now)
+        if not dismissed:
+            return
+        self.update_activity(f"⏰ Alarm {dismissed[0].describe()} off", "success")
+        briefings = [self._alarm_briefings.pop(alarm.id, None) or self._wake_briefing(now)
+                     for alarm in dismissed if alarm.briefing]
+        # Read out whatever quiet hours say: the user just got up
+        await self._say_to_user(briefings[0] if briefings else "Alarm off.")
+
+    def _current_meeting(self, now):
+        """The busy calendar event under way (the notification policy holds announcements during it)."""
+        return current_meeting(get_appointment_cache().events(), now)
+
+    async def _check_meeting_prep(self) -> None:
+        """Offer a prep brief (participants, recent messages, project notes) before meetings, per category prefs."""
+        try:
+            from .meeting_prep import PrepSettings, build_brief, deliver_prep, due_preps
+            from .tools import get_inbox_store, get_planner_data, get_user_profile
+            planner = get_planner_data()
+            now = corrected_now()
+            settings = PrepSettings.from_config(self.config)
+            for event, rule, minutes in due_preps(planner.get_todays_events(), now, self._prepared_events, settings):
+                brief = build_brief(event, planner, inbox=get_inbox_store(), profile=get_user_profile(),
+                                    history=self.persistent_chat_history, now=now, minutes=minutes)
+                if brief.items:
+                    await deliver_prep(brief, rule, activity=self.update_activity, announcer=self.voice_orchestrator)
+        except Exception:
+            pass  # Prep is best-effort
+
+    async def _check_clock(self) -> None:
+        """Measure clock drift (clock_check job) and warn when it passes config.clock_skew_warn_seconds."""
+        skew = get_clock_skew()
+        if self.config.ntp_server:
+            try:
+                skew.observe_ntp(await asyncio.to_thread(sntp_offset, self.config.ntp_server))
+            except OSError as e:
+                logging.debug(f"NTP check against {self.config.ntp_server} failed: {e}")
+        warning = skew.warning(self.config.clock_skew_warn_seconds)
+        if warning and not self._clock_warned:
+            self.update_activity(f"⚠ {warning}", "warning")
+        elif not warning and self._clock_warned:
+            self.update_activity("✓ Clock back in sync", "success")
+        self._clock_warned = warning is not None
+
+    async def _check_for_update(self) -> None:
+        """Look for a newer release (update_check job): "⬆ version" by the version, announced once."""
+        from . import __version__
+
+        updater = SelfUpdater.from_config(self.config)
+        try:
+            release = await updater.check()
+        finally:
+            await updater.client.close()
+        newer = release.version if release.newer_than(__version__) else ""
+        if newer and newer != self._update_shown:
+            self.update_activity(f"⬆ xswarm {newer} is out - run `xswarm self-update` to install it", "info")
+        self._update_shown = newer
+        self.query_one(CyberpunkFooter).update_available = newer
+
+    async def _warm_speech_cache(self) -> None:
+        """Pre-record a few missing common phrases (speech_cache job) while the conversation is quiet."""
+        bridge = self.voice_orchestrator
+        if bridge is None or bridge.speech_cache is None or bridge.state == ConversationState.THINKING:
+            return
+        if bridge._current_mic_amplitude > 0.05 or bridge._current_moshi_amplitude > 0.05:
+            return  # Someone is talking
+        cached = await bridge.warm_speech_cache()
+        if cached:
+            logging.info(f"🗣️ Cached {cached} common phrase(s) for instant playback")
+
+    async def _warm_voice_pipeline(self) -> None:
+        """Run a throwaway STT/LLM/TTS pass (warmup job) when due and nobody is talking - see warmup.py."""
+        bridge = self.voice_orchestrator
+        if bridge is None or not self.voice_initialized or bridge.state not in (ConversationState.IDLE,
+                                                                                ConversationState.LISTENING):
+            return
+        if bridge._current_mic_amplitude > 0.05 or bridge._current_moshi_amplitude > 0.05:
+            return  # Someone is talking
+        results = await bridge.warmup.maybe_run()
+        failed = [str(result) for result in results or [] if result.error]
+        if failed:
+            self.update_activity(f"⚠ Voice warm-up: {', '.join(failed)}", "warning")
+
+    async def _refresh_exchange_rates(self) -> None:
+        """Cache today's exchange rates (exchange_rates job) so "100 dollars in euros" works offline."""
+        count = await get_rate_cache().refresh()
+        logging.info(f"Exchange rates updated ({count} currencies)")
+
+    async def _check_compute(self) -> None:
+        """Yield the GPU to the user's work, or take it back (compute job) - see compute.py."""
+        for move in await asyncio.to_thread(get_compute_manager().check):
+            self.update_activity(f"🖥 {move}", "info")
+
+    async def _retry_pushes(self) -> None:
+        """Resend due failed pushes (push_retry job); on the first run, mention dead letters left from before."""
+        if not self._push_dead_reported:
+            self._push_dead_reported = True
+            dead = self.push_router.queue.dead()
+            if dead:
+                self.update_activity(f"⚠ {len(dead)} push notification(s) couldn't be delivered; "
+                                     "`xswarm dev push failed` lists them, `xswarm dev push retry` resends", "warning")
+        sent, _ = await asyncio.to_thread(self.push_router.retry_due)
+        if sent:
+            logging.info(f"Resent {sent} failed push(es)")
+
+    def _on_push_dead(self, entry: FailedPush) -> None:
+        # A warning, not an error: errors are pushed themselves, and the provider is what's failing
+        self._on_ui_thread(self.update_activity,
+                           f"⚠ Gave up pushing \"{entry.push.message[:60]}\" to {entry.target} after "
+                           f"{entry.attempts} tries ({entry.error}); `xswarm dev push retry {entry.id}` resends it",
+                           "warning")
+
+    def _report_api_health(self) -> None:
+        """Tell the user when server calls start or stop failing, and what to do about it (api_client.explain)."""
+        from .api_client import ApiErrorKind, explain, get_api_health
+        error = get_api_health().last_error
+        kind = error.kind.value if error else None
+        if kind == self._api_error_kind:
+            return
+        self._api_error_kind = kind
+        if error is None:
+            self.update_activity("✓ Server connection restored", "success")
+        elif error.transient or error.kind == ApiErrorKind.MAINTENANCE:
+            self.update_activity(f"⚠ {explain(error)}", "warning")
+        else:
+            self.update_activity(f"✗ {explain(error)}", "error")
+
+    def _setup_privacy_indicators(self) -> None:
+        """Footer badge (and tray icon) for the mic state; activity-log entries for audio leaving the machine."""
+        monitor = get_privacy_monitor()
+        monitor.listeners.append(self._on_privacy_change)
+        monitor.on_egress = lambda message: self._on_ui_thread(self.update_activity, message, "warning")
+        if self.config.privacy_tray_icon:
+            tray = TrayIndicator(monitor)
+            if tray.start():
+                self.privacy_tray = tray
+            else:
+                self.update_activity("⚠ Tray icon unavailable (pip install voice-assistant[tray])", "warning")
+        self._update_mic_state()
+        self.set_interval(1.0, self._update_mic_state)
+
+    def _setup_accessible_stream(self) -> None:
+        """Mirror mic changes to the accessibility stream; read chat from stdin when it replaces the TUI."""
+        stream = get_a11y_stream()
+        if stream is None:
+            return
+        get_privacy_monitor().listeners.append(lambda monitor: stream.mic(monitor.summary()))
+        stream.mic(get_privacy_monitor().summary())
+        if stream.replaces_tui:
+            stream.write("xSwarm is running. Type a message and press Enter; type quit to exit.")
+            # A daemon thread, so a blocked readline() never holds up exit
+            threading.Thread(target=self._read_accessible_input, name="a11y-input", daemon=True).start()
+
+    def _read_accessible_input(self) -> None:
+        for line in sys.stdin:
+            self._on_ui_thread(self._on_accessible_input, line.strip())
+        self._on_ui_thread(self.action_quit)  # stdin closed
+
+    def _on_accessible_input(self, text: str) -> None:
+        """A line typed in accessibility mode: sent as chat, like the chat input."""
+        if not text:
+            return
+        if text.lower() in ("quit", "exit"):
+            self.action_quit()
+            return
+        try:
+            chat_history_widget = self.query_one("#chat-history-widget", ChatHistory)
+        except Exception:
+            chat_history_widget = None
+        if chat_history_widget:
+            chat_history_widget.add_message("User", "••••" if self._awaiting_pin() else text)
+        asyncio.create_task(self._detect_followups(text))
+        self._current_chat_task = asyncio.create_task(self._process_chat_message(text, chat_history_widget))
+
+    def _on_ui_thread(self, callback, *args) -> None:
+        try:
+            self.call_from_thread(callback, *args)
+        except RuntimeError:
+            callback(*args)  # Already on the app's thread
+
+    def _update_mic_state(self) -> None:
+        """What happens to speech right now: nothing, wake word only, or acted on."""
+        if not self.voice_orchestrator or not getattr(self.voice_orchestrator, "_running", False):
+            state = MicState.OFF
+        elif getattr(self.voice_orchestrator, "mic_muted", False):
+            state = MicState.OFF
+        elif self._needs_wake_word() and not self.follow_up.is_open:
+            state = MicState.WAKE_WORD
+        else:
+            state = MicState.STREAMING
+        get_privacy_monitor().set_mic_state(state)
+
+    async def _start_control_socket(self) -> None:
+        """Let the tray companion (and other local clients) control the assistant - see control.py."""
+        server = ControlServer({
+            "status": self._control_status,
+            "mute": self._control_mute,
+            "dnd": self._control_dnd,
+            "next": lambda _request: {"message": self._next_appointment()},
+            "show": self._control_show,
+            "compute": self._control_compute,
+            "quit": self._control_quit,
+        })
+        if await server.start():
+            self.control_server = server
+
+    async def _start_local_api(self) -> None:
+        """Serve the local REST API (and through it `xswarm mcp`) - see local_api.py."""
+        from .ics_feed import serve_feed
+        from .local_api import LocalApi
+        api = LocalApi({
+            "list_appointments": self._api_appointments,
+            "free_busy": self._api_free_busy,
+            "create_reminder": self._api_create_reminder,
+            "speak": self._api_speak,
+            "set_speaker": self._api_set_speaker,
+        }, port=self.config.local_api_port, feed=lambda token: serve_feed(self.config, token))
+        if await api.start():
+            self.local_api = api
+
+    async def _api_appointments(self, query) -> list:
+        from .local_api import shared_appointments
+        from .tools import get_planner_data
+        events = get_planner_data().get_upcoming_events(days=max(1, min(query.days, 366)))
+        return shared_appointments(events, self.config.shared_visibility)
+
+    async def _api_free_busy(self, query) -> list:
+        from .local_api import shared_busy_times
+        # The same days as /appointments (today to `days` ahead), server appointments too
+        start = datetime.datetime.combine(datetime.date.today(), datetime.time.min)
+        end = start + datetime.timedelta(days=max(1, min(query.days, 366)) + 1)
+        events = await asyncio.to_thread(get_appointment_cache().between, start, end)
+        return shared_busy_times(events, self.config.shared_visibility)
+
+    async def _api_create_reminder(self, request):
+        from .dates import parse_natural_datetime
+        from .local_api import LocalApiError, ReminderCreated
+        from .tools import get_calendar_sync
+        sync = get_calendar_sync()
+        if sync is None:
+            raise LocalApiError(503, "The server calendar isn't set up")
+        try:
+            due = datetime.datetime.fromisoformat(request.when)
+        except ValueError:
+            due = parse_natural_datetime(request.when)
+        if due is None:
+            raise LocalApiError(400, f"Couldn't read '{request.when}' as a date and time")
+        if due.tzinfo is None:
+            due = due.astimezone()
+        try:
+            reminder = await sync.client.create_reminder(self.user_id, request.title, due.isoformat(),
+                                                         description=request.description)
+        except Exception as e:
+            raise LocalApiError(502, f"The server didn't take the reminder: {e}")
+        self.update_activity(f"⏰ Reminder set from the local API: {request.title}", "info")
+        return ReminderCreated(reminder["id"], reminder["title"], reminder["due_time"])
+
+    async def _api_speak(self, request):
+        from .local_api import LocalApiError, SpeakResult
+        from .remote_commands import CommandFailed
+        try:
+            message = await self._remote_speak({"text": request.text, "priority": request.priority})
+        except CommandFailed as e:
+            raise LocalApiError(400, str(e))
+        return SpeakResult(message == "spoken", message)
+
+    async def _api_set_speaker(self, request):
+        from .local_api import LocalApiError, SpeakerResult
+        household = get_household()
+        before = household.active
+        if household.identify(request.speaker) is None:
+            raise LocalApiError(404, f"No household profile lists the speaker '{request.speaker}'")
+        if household.active is not before:
+            self.update_activity(f"👤 {household.active.name}'s profile (speaker identified)", "info")
+        return SpeakerResult(household.active.name, household.active is not before)
+
+    def _next_appointment(self) -> str:
+        from .tools import get_planner_data
+        return next_appointment(get_planner_data())
+
+    def _control_status(self, _request) -> dict:
+        monitor = get_privacy_monitor()
+        return {
+            "mic": monitor.mic_state.value,
+            "muted": bool(getattr(self.voice_orchestrator, "mic_muted", False)),
+            "voice": bool(self.voice_orchestrator),
+            "dnd": self.config.do_not_disturb,
+            "egress": monitor.destinations(),
+            "next": self._next_appointment(),
+            "latency": get_latency_metrics().summary(),
+            "bandwidth": get_bandwidth_meter().summary(),
+            "power": get_power_manager().profile.name,
+            "profile": get_household().active.name,
+            "states": {name: report.state for name, report in get_task_supervisor().states.items()},
+            "message": f"xSwarm: {monitor.summary()}",
+        }
+
+    def _control_mute(self, request) -> dict:
+        if not self.voice_orchestrator:
+            raise RuntimeError("Voice isn't running")
+        muted = request.get("on")
+        muted = not self.voice_orchestrator.mic_muted if muted is None else bool(muted)
+        self.voice_orchestrator.set_mic_muted(muted)
+        self._update_mic_state()
+        message = "✓ Mic muted" if muted else "✓ Mic on"
+        self.update_activity(message, "info")
+        return {"message": message, "muted": muted}
+
+    def _control_dnd(self, request) -> dict:
+        on = request.get("on")
+        on = not self.config.do_not_disturb if on is None else bool(on)
+        self.config.do_not_disturb = on
+        try:
+            self.config.save_to_file()
+        except Exception as e:
+            logging.warning(f"Could not save do not disturb: {e}")
+        message = "✓ Do not disturb on" if on else "✓ Do not disturb off"
+        self.update_activity(message, "info")
+        return {"message": message, "dnd": on}
+
+    async def _control_compute(self, request) -> dict:
+        manager = get_compute_manager()
+        if request.get("model"):
+            placement = manager.switch(request["model"], request.get("backend") or "auto")
+            self.update_activity(f"🖥 {placement.name} moved to {placement.backend}", "info")
+        report = await asyncio.to_thread(manager.report)
+        return {**report, "message": ", ".join(f"{m['name']}: {m['backend']}" for m in report["models"]) or "No models loaded"}
+
+    def _control_show(self, _request) -> dict:
+        """The tray's "Open dashboard" while it's already open in a terminal: ring its bell."""
+        self.bell()
+        self.update_activity("👋 Dashboard requested from the tray", "info")
+        return {"message": "✓ Dashboard is open"}
+
+    def _control_quit(self, _request) -> dict:
+        self.call_later(self.action_quit)  # After the reply is sent
+        return {"message": "✓ Quitting xSwarm"}
+
+    def _on_privacy_change(self, monitor) -> None:
+        def show() -> None:
+            try:
+                footer = self.query_one(CyberpunkFooter)
+                footer.mic_state = monitor.mic_state.value
+                footer.audio_egress = tuple(monitor.destinations())
+            except Exception:
+                pass
+        self._on_ui_thread(show)
+
+    def _update_audio_health(self) -> None:
+        """Show audio queue lag in the footer and log when frames start being dropped."""
+        if not self.voice_orchestrator or not hasattr(self.voice_orchestrator, "get_audio_stats"):
+            return
+        stats = self.voice_orchestrator.get_audio_stats()
+        health = summarize_audio_stats(stats)
+        try:
+            self.query_one(CyberpunkFooter).audio_health = health
+        except Exception:
+            pass
+
+        # At most one activity line per 30s so a struggling pipeline doesn't flood the feed
+        new_drops = health["dropped"] - self._audio_drops_reported
+        if new_drops > 0 and time.monotonic() - self._audio_drops_reported_at >= 30:
+            worst = max((s for s in stats if s["critical"]), key=lambda s: s["dropped"])
+            self.update_activity(f"⚠ Audio lagging: dropped {new_drops} frames (worst: {worst['name']})", "warning")
+            self._audio_drops_reported = health["dropped"]
+            self._audio_drops_reported_at = time.monotonic()
+
+    def _on_task_crash(self, name: str, error: BaseException, delay: float) -> None:
+        """A supervised background task crashed and will be restarted."""
+        self.update_activity(f"✗ {name} crashed ({type(error).__name__}: {error}) - restarting in {delay:.0f}s", "error")
+
+    def _on_subsystem_failed(self, failure: SubsystemFailure) -> None:
+        """A supervised task kept crashing and was given up on - keep it visible in the footer."""
+        self.update_activity(f"✗ {failure.name} stopped after {failure.restarts} restarts: {failure.error}", "error")
+        try:
+            footer = self.query_one(CyberpunkFooter)
+            footer.failed_subsystems = tuple(f.name for f in get_task_supervisor().failures())
+        except Exception:
+            pass
+
+    def _update_resource_usage(self) -> None:
+        """Show xswarm's CPU/RAM in the header and note when background jobs are being throttled."""
+        governor = get_resource_governor()
+        was_throttled = governor.throttled
+        governor.sample()
+        power = get_power_manager()
+        profile = power.sample()
+        if profile:
+            self._apply_power_profile(profile)
+            self.update_activity(f"{profile.label} mode: {', '.join(power.reasons) or 'back on power'}", "info")
+        try:
+            visualizer = self.query_one("#visualizer", VoiceVisualizerPanel)
+            visualizer.border_title = f"xSwarm Assistant ─ {governor.status_line()} · {power.status_line()}"
+        except Exception:
+            pass
+        try:
+            self.query_one("#cpu-chart", Sparkline).add(governor.usage.cpu_percent)
+            self.query_one("#bandwidth-chart", Sparkline).add(get_bandwidth_meter().rate() / 1024)
+            self.query_one("#buffer-usage", Static).update(f" Memory   {usage_line(self._buffer_usage())}")
+        except Exception:
+            pass
+        if governor.throttled and not was_throttled:
+            self.update_activity(f"⏸ Pausing background jobs: {', '.join(governor.reasons)}", "warning")
+        elif was_throttled and not governor.throttled:
+            self.update_activity("▶ Background jobs resumed")
+
+    def _buffer_usage(self) -> list:
+        """What the feed, chat and charts hold against their caps, and its size (buffers.py)."""
+        caps = get_buffer_caps()
+        chart_cap = caps["chart"] + caps["chart_minutes"]
+        feed = self.query_one("#activity", ActivityFeed)
+        return buffer_usage({
+            "feed": (feed.messages, feed.messages.maxlen),
+            "chat": (self.query_one("#chat-history-widget", ChatHistory)._messages, caps["chat"]),
+            "cpu": (self.query_one("#cpu-chart", Sparkline).history, chart_cap),
+            "reply": (self.query_one("#latency-chart", Sparkline).history, chart_cap),
+            "net": (self.query_one("#bandwidth-chart", Sparkline).history, chart_cap),
+        })
+
+    def _apply_power_profile(self, profile: Profile) -> None:
+        """Visualizer frame rate and transcriber polling for the power profile (see power.py)."""
+        try:
+            self.query_one("#visualizer", VoiceVisualizerPanel).set_fps(profile.visualizer_fps)
+        except Exception:
+            pass
+        transcriber = getattr(self.voice_orchestrator, "user_transcriber", None)
+        if transcriber is not None:
+            transcriber.poll_interval = profile.transcribe_poll
+
+    async def _poll_call_screening(self) -> None:
+        """Refresh calls being screened and bring new ones to the user's attention."""
+        try:
+            if self.call_screening is None:
+                from .call_screening import CallScreeningManager
+                from .tools import get_voicemail_store, set_call_screening
+                self.call_screening = CallScreeningManager(self.config, self.user_id, store=get_voicemail_store())
+                set_call_screening(self.call_screening)
+            new_calls = await self.call_screening.poll()
+        except Exception:
+            return  # Server unreachable
+        finally:
+            self._report_api_health()
+
+        for call in new_calls:
+            self.update_activity(f"📞 Screening call from {call.display_name} - see Inbox (y/n/v)")
+            send_desktop_notification("Incoming call", f"Screening {call.display_name}", priority="high")
+            if self.voice_orchestrator:
+                await self.voice_orchestrator.announce(f"Incoming call from {call.display_name}. I'm screening it.", priority="high")
+        if new_calls:
+            self.action_goto_inbox()
+            try:
+                self.query_one("#call-screening-widget").focus()
+            except Exception:
+                pass
+
+    async def _detect_followups(self, text: str) -> None:
+        """Propose tasks/commitments the user just made; confirmed in the Schedule pane."""
+        try:
+            if self.followup_detector is None:
+                from .followups import FollowUpDetector
+                from .voice import AIClient
+                self.followup_detector = FollowUpDetector(AIClient(self.config, "followups"))
+            context = "\n".join(self._recent_utterances)
+            self._recent_utterances = (self._recent_utterances + [text])[-10:]
+
+            proposals = await self.followup_detector.detect(text, context)
+            if not proposals:
+                return
+            from .tools import get_followup_store
+            for proposal in get_followup_store().add(proposals):
+                self.update_activity(f"📝 Follow-up: {proposal.describe()} - confirm in Schedule")
+        except Exception:
+            pass  # Detection is best-effort
+
+    def decide_screened_call(self, action: str, message: Optional[str] = None) -> None:
+        """Accept, decline or send the current screened call to voicemail."""
+        async def _decide():
+            if self.call_screening is None:
+                return
+            result = await self.call_screening.decide(action, message=message)
+            self.update_activity(result, "error" if result.startswith("✗") else "info")
+
+        asyncio.create_task(_decide())
+
+    def draft_inbox_reply(self, item_id: str) -> None:
+        """Draft a reply to an inbox item and ask the user to confirm before sending."""
+        async def _draft():
+            if not get_household().allows_class("communicate"):
+                self.update_activity(f"✗ {get_household().active.name}'s profile can't send replies", "error")
+                return
+            if self.inbox_manager is None:
+                await self._sync_inbox()
+            if self.inbox_manager is None:
+                self.update_activity("✗ Could not draft a reply (server unreachable?)", "error")
+                return
+            from .reply_drafts import ReplyWorkflow
+            from .voice import budgeted_ai
+            ai = budgeted_ai(self.config, on_filler=self._speak_filler, purpose="reply_drafts")
+            self.reply_workflow = ReplyWorkflow(ai, self.persona_manager, self.inbox_manager)
+            response = await self.reply_workflow.start(item_id)
+            await self._say_to_user(response)
+            if self.reply_workflow.is_active:
+                self.update_activity("✎ Reply drafted - say or type 'send it', an edit, or 'cancel'")
+
+        asyncio.create_task(_draft())
+
+    async def _handle_reply_utterance(self, text: str) -> None:
+        """Feed a user utterance into the active reply draft."""
+        response = await self.reply_workflow.handle(text)
+        await self._say_to_user(response)
+        session = self.reply_workflow.session
+        if session.state == "sent":
+            self.update_activity(f"✓ Reply sent to {session.sender}")
+        elif session.state == "cancelled":
+            self.update_activity("✗ Reply discarded")
+
+    async def _start_share_notes(self, text: str) -> None:
+        """Summarize a conversation and ask the user to confirm before emailing it (see share_notes.py)."""
+        if not get_household().allows_class("communicate"):
+            self.update_activity(f"✗ {get_household().active.name}'s profile can't send messages", "error")
+            return
+        from .share_notes import ShareNotesWorkflow
+        from .tools import get_mailer
+        from .voice import budgeted_ai
+        ai = budgeted_ai(self.config, on_filler=self._speak_filler, purpose="share_notes")
+        self.share_workflow = ShareNotesWorkflow(ai, self.persona_manager, self.persistent_chat_history,
+                                                 get_mailer(), self.config)
+        await self._say_to_user(await self.share_workflow.start(parse_share_request(text)))
+        if self.share_workflow.is_active:
+            self.update_activity("✎ Notes ready - say or type 'send it', an edit, or 'cancel'")
+
+    async def _handle_share_utterance(self, text: str) -> None:
+        """Feed a user utterance into the active notes share."""
+        await self._say_to_user(await self.share_workflow.handle(text))
+        session = self.share_workflow.session
+        if session.state == "sent":
+            self.update_activity(f"✓ Notes sent to {session.contact.name}")
+        elif session.state == "cancelled":
+            self.update_activity("✗ Notes discarded")
+
+    async def _start_evening_review(self) -> None:
+        """Begin the end-of-day review conversation (see evening_review.py)."""
+        from .evening_review import EveningReview
+        from .tools import get_planner_data
+        self.evening_review = EveningReview(get_planner_data(), work_start=self.config.workday_start,
+                                            work_end=self.config.workday_end)
+        await self._say_to_user(self.evening_review.start())
+        if self.evening_review.is_active:
+            self.update_activity("🌙 Evening review - answer by voice or text, or say 'cancel'")
+
+    async def _scheduled_evening_review(self) -> None:
+        """evening_review job: start the review unless it's done, something else is in progress, or it's quiet hours."""
+        from .notifications import get_notification_policy
+        from .tools import get_planner_data
+        busy = ((self.reply_workflow and self.reply_workflow.is_active) or self._flow_is_active()
+                or (self.share_workflow and self.share_workflow.is_active)
+                or get_confirmation_policy().has_pending())
+        if busy or get_planner_data().was_review_done_today():
+            return
+        if not get_notification_policy().check("speech").allowed:
+            self.update_activity("🌙 Evening review skipped (quiet hours) - say 'evening review' to start it")
+            return
+        await self._start_evening_review()
+
+    async def _handle_review_utterance(self, text: str) -> None:
+        """Feed a user utterance into the active evening review."""
+        response = self.evening_review.handle(text)
+        await self._say_to_user(response)
+        if self.evening_review.state == "done":
+            self.update_activity("✓ Evening review done - journal saved")
+        elif self.evening_review.state == "cancelled":
+            self.update_activity("✗ Evening review stopped")
+
+    async def _start_flow(self, flow: Flow) -> None:
+        """Begin a guided dialog (see flows.py)."""
+        from .flows import FlowRun
+        self.active_flow = FlowRun(flow)
+        await self._say_to_user(self.active_flow.start())
+        if self.active_flow.is_active:
+            self.update_activity("💬 Answer by voice or text, or say 'cancel'")
+
+    def _flow_is_active(self) -> bool:
+        """True while a guided dialog awaits an answer; reports one that timed out."""
+        if self.active_flow is None:
+            return False
+        if self.active_flow.expired():
+            self.update_activity("⌛ Guided question timed out")
+            self.active_flow = None
+            return False
+        return self.active_flow.is_active
+
+    async def _handle_flow_utterance(self, text: str) -> None:
+        """Feed a user utterance into the active guided dialog."""
+        response = self.active_flow.handle(text)
+        await self._say_to_user(response)
+        if self.active_flow.state == "cancelled":
+            self.update_activity("✗ Cancelled")
+
+    async def _speak_filler(self, text: str) -> None:
+        """Fill a slow AI reply ("Let me check.") when voice is on; typed chat shows its cursor instead."""
+        if self.voice_orchestrator:
+            await self.voice_orchestrator.speak_text(text)
+
+    async def _say_to_user(self, text: str) -> None:
+        """Show assistant output (reply drafts, undo results) in chat and read it aloud when voice is active."""
+        self._note_reply()
+        persona = self.persona_manager.get_current_persona()
+        try:
+            chat_widget = self.query_one("#chat-history-widget", ChatHistory)
+            chat_widget.add_message(persona.name if persona else "Assistant", strip_speech_hints(text))
+        except Exception:
+            pass
+        if self.voice_orchestrator:
+            await self.voice_orchestrator.speak_text(text)
+
     def handle_oauth_button(self, button_id: str, button: Button) -> None:
         """Handle OAuth connector button clicks (mock functionality)"""
         # Extract service name from button ID (e.g., "oauth-gmail-btn" -> "gmail")
@@ -1786,6 +3982,8 @@ class VoiceAssistantApp(App):
             # STEP 2: Signal threads to stop (but don't wait)
             if hasattr(self, '_processing_thread_stop'):
                 self._processing_thread_stop.set()
+            self.job_scheduler.stop()
+            get_task_supervisor().cancel_all()
 
             # STEP 3: Stop audio I/O immediately
             if hasattr(self, 'audio_io') and self.audio_io:
@@ -1794,6 +3992,25 @@ class VoiceAssistantApp(App):
                 except:
                     pass
 
+            if self.privacy_tray:
+                self.privacy_tray.stop()
+            if self.control_server:
+                self.control_server.close()
+            if self.local_api:
+                self.local_api.close()
+            if self.companion_server:
+                self.companion_server.close()
+                set_companion_server(None)
+            if self.matrix_bridge:
+                set_matrix_bridge(None)
+            if self.push_router:
+                remove_event_listener(self.push_router.on_event)
+                self.push_router.close()
+            monitor = get_privacy_monitor()
+            if self._on_privacy_change in monitor.listeners:
+                monitor.listeners.remove(self._on_privacy_change)
+            monitor.set_mic_state(MicState.OFF)
+
             # STEP 4: Stop voice components
             if hasattr(self, 'voice_orchestrator') and self.voice_orchestrator:
                 try:
@@ -1817,11 +4034,11 @@ class VoiceAssistantApp(App):
     async def initialize_memory(self):
         """Initialize memory manager for conversation history"""
         try:
-            self.memory_manager = MemoryManager(
-                server_url=self.config.server_url if hasattr(self.config, 'server_url') else "http://localhost:3000",
-                max_history=100
-            )
-            await self.memory_manager.initialize()
+            with self.startup.stage("memory"):
+                self.memory_manager = MemoryManager(
+                    server_url=getattr(self.config, "server_url", "http://localhost:3000"), max_history=100
+                )
+                await self.memory_manager.initialize()
             self.update_activity("✅ Memory system initialized")
         except Exception as e:
             self.update_activity(f"⚠️  Memory init failed: {e}, using chat history only")
@@ -1834,10 +4051,12 @@ class VoiceAssistantApp(App):
         Returns:
             True if initialization successful, False otherwise
         """
-        # Check if voice is enabled in config
-        voice_enabled = getattr(self.config, 'voice_enabled', True)  # Default to True for backwards compatibility
-        if not voice_enabled:
-            self.update_activity("ℹ️  Voice disabled in config - skipping voice initialization")
+        # Text-only users (or voice disabled in config) get chat, calendar and memory without voice
+        if not self.config.voice_active:
+            if self.config.text_only:
+                self.update_activity("ℹ️  Text-only mode - voice is off")
+            else:
+                self.update_activity("ℹ️  Voice disabled in config - skipping voice initialization")
             self.voice_initialized = False
             return False
             
@@ -1848,7 +4067,9 @@ class VoiceAssistantApp(App):
             self.update_activity("Initializing voice bridge...")
             self.update_activity(f"DEBUG: Voice Queues: {bool(self.voice_queues)}")
             
-            # Ensure memory manager is initialized first
+            # Ensure memory manager is initialized first (on_mount already started it)
+            if self._memory_task:
+                await self._memory_task
             if not self.memory_manager:
                 await self.initialize_memory()
 
@@ -1863,15 +4084,21 @@ class VoiceAssistantApp(App):
                 moshi_quality=moshi_quality,
                 voice_queues=self.voice_queues,
                 log_callback=self.update_activity,
-                text_callback=self._on_voice_text
+                text_callback=self._on_voice_text,
+                caption_callback=self._on_voice_caption if self.config.live_captions else None
             )
-            # Initialize Moshi models
-            await self.voice_orchestrator.initialize()
+            # Wait for Moshi models in the background; the footer shows load progress
+            server_alive = self.voice_server_process.is_alive if self.voice_server_process else None
+            await self.voice_orchestrator.initialize(on_progress=self._on_voice_load_progress, server_alive=server_alive)
             # Register state change callback
             self.voice_orchestrator.on_state_change(self._on_voice_state_change)
             # Mark as initialized
             self.voice_initialized = True
             self.update_activity("✅ Voice bridge initialized successfully")
+            audio_io = getattr(self.voice_orchestrator, "audio_io", None)
+            if audio_io is not None and audio_io.bluetooth.hfp:
+                self.update_activity(audio_io.bluetooth.warning(audio_io.bluetooth_rerouted),
+                                     "info" if audio_io.bluetooth_rerouted else "warning")
             
             # Update footer voice status
             try:
@@ -1896,7 +4123,8 @@ class VoiceAssistantApp(App):
             # This eliminates dual-timer race condition that caused freezes
 
             # Auto-start conversation for microphone visualization and greeting
-            await self.voice_orchestrator.start_conversation()
+            with self.startup.stage("microphone"):
+                await self.voice_orchestrator.start_conversation()
             self.update_activity("🎙️  Microphone active - speak naturally, I'm listening...")
 
             # Generate and play startup greeting
@@ -1917,8 +4145,27 @@ class VoiceAssistantApp(App):
                 logging.error(f"Stack trace:\n{error_details}")
                 self.update_activity(f"❌ Voice initialization failed: {e}")
             self.voice_initialized = False
+            try:
+                footer = self.query_one(CyberpunkFooter)
+                footer.voice_loading = None
+                footer.voice_status = "disconnected"
+            except Exception:
+                pass
             return False
 
+    def _on_voice_load_progress(self, progress: LoadProgress) -> None:
+        """Show model loading progress in the footer (cleared once voice is ready)."""
+        try:
+            footer = self.query_one(CyberpunkFooter)
+            footer.voice_loading = None if progress.done or progress.error else progress
+        except Exception:
+            pass
+        if progress.error:
+            self.update_activity(f"❌ Voice model loading failed: {progress.error}")
+        elif progress.stage != getattr(self, "_voice_load_stage", None):
+            self.update_activity(f"⏳ {progress.label}...")
+        self._voice_load_stage = progress.stage
+
     async def generate_greeting_with_voice_bridge(self):
         """Generate and play startup greeting using VoiceBridgeOrchestrator"""
         if not self.voice_orchestrator:
@@ -1927,7 +4174,8 @@ class VoiceAssistantApp(App):
         try:
             # Get current persona
             persona = self.persona_manager.get_current_persona()
-            greeting_text = f"Hello, I am {persona.name}. How can I help you today?"
+            greeting_text = (self._canned_greeting(getattr(self.config, "user_name", None))
+                             or f"Hello, I am {persona.name}. How can I help you today?")
             
             # Use voice bridge to synthesize speech
             # Note: VoiceBridgeOrchestrator doesn't have a direct 'synthesize' method exposed publicly
@@ -1944,7 +4192,9 @@ class VoiceAssistantApp(App):
     def _on_voice_state_change(self, state: ConversationState):
         """Handle voice state changes from bridge"""
         # Map bridge state to app state
-        # Bridge states: IDLE, LISTENING, THINKING, SPEAKING, ERROR
+        # Bridge states: IDLE, LISTENING, THINKING, SPEAKING, INTERRUPTED, ERROR
+        if self.state == "speaking" and state.value.lower() != "speaking":
+            self.follow_up.open()  # Finished answering: the user can reply without the wake word
         self.state = state.value.lower()
         
         # Update visualizer state
@@ -1952,7 +4202,7 @@ class VoiceAssistantApp(App):
             visualizer = self.query_one("#visualizer", VoiceVisualizerPanel)
             # Map state to visualizer state
             # Map state to visualizer state
-            if self.state == "listening":
+            if self.state in ("listening", "interrupted"):
                 visualizer.connection_amplitude = 1.0 # Active
             elif self.state == "speaking":
                 visualizer.connection_amplitude = 1.0 # Active
@@ -1990,24 +4240,97 @@ class VoiceAssistantApp(App):
             display_text = text
             for prefix in ["[Context: Projects pane] ", "[Context: Schedule pane] "]:
                 display_text = display_text.replace(prefix, "")
+            if self._awaiting_pin():
+                display_text = "••••"  # Never show a PIN in the transcript
             chat_history_widget.add_message("User", display_text)
 
+        # Look for commitments ("I'll send that by Friday") in the background
+        asyncio.create_task(self._detect_followups(event.value))
+
         # Schedule async work for LATER - don't block the UI thread at all
         # Store the task so it can be cancelled with Escape
         def start_chat():
             self._current_chat_task = asyncio.create_task(self._process_chat_message(text, chat_history_widget))
         self.call_later(start_chat)
 
+    def _script_transcript(self, text: str, source: str) -> Optional[str]:
+        """What the user said after the on_transcript scripts (scripting.py), or None if one dropped it."""
+        said = _strip_context_hint(text)
+        hooked = get_script_hooks().on_transcript(said, source)
+        return None if hooked is None else text[:len(text) - len(said)] + hooked
+
     async def _process_chat_message(self, text: str, chat_history_widget) -> None:
         """Process chat message asynchronously after UI has updated."""
-        if self.voice_orchestrator:
-            await self.voice_orchestrator.send_text(text)
+        correlation.begin("chat")  # This task and what it starts: one thread in the activity feed
+        hear(_strip_context_hint(text))  # What tool calls from this turn are checked against (intents.py)
+        last_active = self._user_active("chat")
+        if (self._emergency_utterance(_strip_context_hint(text)) or self._alarm_utterance(_strip_context_hint(text))
+                or self._household_utterance(_strip_context_hint(text))):
+            return
+        text = self._script_transcript(text, "chat")
+        if text is None:
+            return
+        if self.reply_workflow and self.reply_workflow.is_active:
+            await self._handle_reply_utterance(text)
+        elif self.share_workflow and self.share_workflow.is_active:
+            await self._handle_share_utterance(_strip_context_hint(text))
+        elif self.evening_review and self.evening_review.is_active:
+            await self._handle_review_utterance(_strip_context_hint(text))
+        elif self._flow_is_active():
+            await self._handle_flow_utterance(_strip_context_hint(text))
+        elif self._continue_utterance(_strip_context_hint(text)):
+            pass
+        elif get_confirmation_policy().has_pending() and await self._handle_confirmation_utterance(_strip_context_hint(text)):
+            pass
+        elif is_undo_request(text):
+            await self._handle_undo_utterance()
+        elif parse_volume_request(_strip_context_hint(text)):
+            await self._handle_volume_utterance(_strip_context_hint(text))
+        elif parse_timer_command(_strip_context_hint(text), get_timer_registry()):
+            await self._handle_timer_utterance(_strip_context_hint(text))
+        elif parse_list_command(_strip_context_hint(text), get_list_store()):
+            await self._handle_list_utterance(_strip_context_hint(text))
+        elif parse_bookmark_request(_strip_context_hint(text)):
+            await self._handle_bookmark_utterance(_strip_context_hint(text))
+        elif parse_bookmark_query(_strip_context_hint(text)) is not None:
+            await self._handle_bookmark_query(_strip_context_hint(text))
+        elif match_skip(_strip_context_hint(text)):
+            await self._handle_skip_utterance(_strip_context_hint(text))
+        elif parse_preference(_strip_context_hint(text)):
+            await self._handle_preference_utterance(_strip_context_hint(text))
+        elif is_missed_request(_strip_context_hint(text)):
+            await self._handle_missed_utterance(last_active)
+        elif is_digest_request(_strip_context_hint(text)):
+            await self._handle_digest_utterance()
+        elif answer_quick_question(_strip_context_hint(text)):
+            await self._say_to_user(answer_quick_question(_strip_context_hint(text)))
+        elif answer_availability_question(_strip_context_hint(text)):
+            await self._say_to_user(answer_availability_question(_strip_context_hint(text)))
+        elif answer_capability_question(_strip_context_hint(text), self.config):
+            await self._say_to_user(answer_capability_question(_strip_context_hint(text), self.config))
+        elif get_skill_plugins().match(_strip_context_hint(text)):
+            await self._handle_plugin_utterance(_strip_context_hint(text))
+        elif is_review_request(_strip_context_hint(text)):
+            await self._start_evening_review()
+        elif parse_share_request(_strip_context_hint(text)):
+            await self._start_share_notes(_strip_context_hint(text))
+        elif flow_for_request(_strip_context_hint(text)):
+            await self._start_flow(flow_for_request(_strip_context_hint(text)))
+        elif self.voice_orchestrator:
+            await self.voice_orchestrator.send_text(get_dialogue_state().with_context(text))
         else:
-            await self._handle_chat_text(text, chat_history_widget)
+            await self._handle_chat_text(get_dialogue_state().with_context(text), chat_history_widget)
 
     async def _handle_chat_text(self, text: str, chat_history_widget: Optional[ChatHistory]) -> None:
         """Handle chat via ChatEngine (text mode fallback)."""
         try:
+            if not get_household().active.cloud_ai:
+                # ChatEngine only talks to the cloud model, which this profile may not spend
+                if chat_history_widget:
+                    name = get_household().active.name
+                    chat_history_widget.add_message(
+                        "System", f"{name}'s profile only uses a local model, and typed chat needs the cloud one.")
+                return
             # Don't wait for chat engine - it initializes in background
             # If not ready yet, show a message and return immediately
             if not self.chat_engine:
@@ -2037,6 +4360,7 @@ class VoiceAssistantApp(App):
             in_thinking_block = False
 
             async for chunk in self.chat_engine.send_message(text):
+                self._note_reply()
                 response_text += chunk
 
                 # Check if we're entering or in a thinking block
@@ -2102,6 +4426,8 @@ class VoiceAssistantApp(App):
                 persona=persona_name
             )
             self.persistent_chat_history.start_session()
+            from .tools import set_chat_history
+            set_chat_history(self.persistent_chat_history)  # Past conversations for meeting prep
 
             # Note: Previous session messages are NOT displayed in chat pane
             # Instead, context is sent to AI via get_context_for_injection() and UserProfile
@@ -2136,12 +4462,15 @@ class VoiceAssistantApp(App):
     async def _init_chat_engine_background(self) -> None:
         """Initialize chat engine in background - doesn't block first message."""
         try:
-            await self._init_chat_engine()
+            with self.startup.stage("chat_engine"):
+                await self._init_chat_engine()
             # Generate smart welcome message after engine is ready
             await self._generate_welcome_message()
         except Exception as e:
             with open("/tmp/xswarm_debug.log", "a") as f:
                 f.write(f"ERROR: Background chat init failed: {e}\n")
+        finally:
+            self._welcomed.set()
 
     async def _generate_welcome_message(self) -> None:
         """Generate a contextual welcome message based on memory and time of day.
@@ -2166,13 +4495,18 @@ class VoiceAssistantApp(App):
             if self.chat_engine and self.chat_engine.user_profile:
                 user_profile_context = self.chat_engine.user_profile.get_context_string()
 
-            # Generate AI-powered welcome message
-            welcome = await self._generate_ai_welcome(
-                persona_name=persona_name,
-                user_name=user_name,
-                context=context,
-                user_profile=user_profile_context
-            )
+            # Nothing earlier to mention: the persona's canned greeting, no model call (personas/phrases.py)
+            welcome = None
+            if not context.get("recent_messages") or context.get("is_new_day"):
+                welcome = self._canned_greeting(user_name)
+            # Otherwise an AI-powered welcome message
+            if not welcome:
+                welcome = await self._generate_ai_welcome(
+                    persona_name=persona_name,
+                    user_name=user_name,
+                    context=context,
+                    user_profile=user_profile_context
+                )
 
             # Only show welcome if AI generated one
             if welcome:
@@ -2186,6 +4520,12 @@ class VoiceAssistantApp(App):
             with open("/tmp/xswarm_debug.log", "a") as f:
                 f.write(f"DEBUG: Could not generate welcome: {e}\n")
 
+    def _canned_greeting(self, user_name: Optional[str]) -> Optional[str]:
+        """A greeting from the current persona's phrase bank, for the time of day."""
+        hour = datetime.datetime.now().hour
+        time_of_day = "morning" if hour < 12 else "afternoon" if hour < 17 else "evening"
+        return get_phrase_bank().pick("greeting", user=user_name, time_of_day=time_of_day)
+
     async def _generate_ai_welcome(
         self,
         persona_name: str,
@@ -2271,11 +4611,97 @@ Just output the welcome message, nothing else:"""
         # The persona name is already shown in the chat, so no need for hardcoded greeting
         return ""
 
+    def _wake_words(self) -> List[str]:
+        wake_word = self.config.wake_word
+        return [w.lower().strip() for w in ([wake_word] if isinstance(wake_word, str) else wake_word) if w.strip()]
+
+    def _needs_wake_word(self) -> bool:
+        """Speech is ignored without the wake word (config.require_wake_word, unless the wake_word flag is off)."""
+        return self.config.require_wake_word and flag_enabled("wake_word")
+
     def _on_voice_text(self, sender: str, text: str):
-        """Handle text output from voice bridge"""
+        """Handle text output from voice bridge: what the user said starts an interaction, an answer joins it"""
+        source = "voice" if sender == "User" else None  # Threads in the activity feed (correlation.py)
+        correlation.handle(source, self._handle_voice_text, sender, text)
+
+    def _handle_voice_text(self, sender: str, text: str):
         try:
+            if sender == "Moshi":
+                self.follow_up.open()  # Counts from the end of the answer
+                self._note_reply()
+            elif sender == "User" and (self._emergency_utterance(text) or self._alarm_utterance(text)
+                                       or self._household_utterance(text) or self._reminder_ack_utterance(text)):
+                self.query_one("#chat-history-widget", ChatHistory).add_message(sender, text)
+                return
+            if sender == "User":
+                text = self._script_transcript(text, "voice")
+                if text is None:
+                    logging.debug("Ignored (dropped by a script)")
+                    return
+            if sender == "User" and self._needs_wake_word():
+                if strip_wake_word(text, self._wake_words()) is not None:
+                    self._wake_word_at = time.monotonic()  # The tutorial's wake word step (tutorial.py)
+                # Needs the wake word, unless it's a reply within the follow-up window
+                command = self.follow_up.admit(text, self._wake_words())
+                if not command:
+                    logging.debug(f"Ignored (no wake word, or a dismissal): {text}")
+                    return
+                text = command
             chat_history = self.query_one("#chat-history-widget", ChatHistory)
-            chat_history.add_message(sender, text)
+            chat_history.add_message(sender, "••••" if sender == "User" and self._awaiting_pin() else text)
+            if sender == "User" and not self._awaiting_pin():
+                hear(text)
+            last_active = self._user_active("voice") if sender == "User" else None
+
+            # Spoken confirmations/edits for a pending reply draft
+            if sender == "User" and self.reply_workflow and self.reply_workflow.is_active:
+                asyncio.create_task(self._handle_reply_utterance(text))
+            elif sender == "User" and self.share_workflow and self.share_workflow.is_active:
+                asyncio.create_task(self._handle_share_utterance(text))
+            elif sender == "User" and self.evening_review and self.evening_review.is_active:
+                asyncio.create_task(self._handle_review_utterance(text))
+            elif sender == "User" and is_review_request(text):
+                asyncio.create_task(self._start_evening_review())
+            elif sender == "User" and parse_share_request(text):
+                asyncio.create_task(self._start_share_notes(text))
+            elif sender == "User" and self._flow_is_active():
+                asyncio.create_task(self._handle_flow_utterance(text))
+            elif sender == "User" and flow_for_request(text):
+                asyncio.create_task(self._start_flow(flow_for_request(text)))
+            elif sender == "User" and self._continue_utterance(text):
+                pass
+            elif sender == "User" and get_confirmation_policy().has_pending():
+                asyncio.create_task(self._handle_confirmation_or_chat(text, last_active))
+            elif sender == "User" and is_undo_request(text):
+                asyncio.create_task(self._handle_undo_utterance())
+            elif sender == "User" and parse_volume_request(text):
+                asyncio.create_task(self._handle_volume_utterance(text))
+            elif sender == "User" and parse_timer_command(text, get_timer_registry()):
+                asyncio.create_task(self._handle_timer_utterance(text))
+            elif sender == "User" and parse_list_command(text, get_list_store()):
+                asyncio.create_task(self._handle_list_utterance(text))
+            elif sender == "User" and parse_bookmark_request(text):
+                asyncio.create_task(self._handle_bookmark_utterance(text))
+            elif sender == "User" and parse_bookmark_query(text) is not None:
+                asyncio.create_task(self._handle_bookmark_query(text))
+            elif sender == "User" and match_skip(text):
+                asyncio.create_task(self._handle_skip_utterance(text))
+            elif sender == "User" and parse_preference(text):
+                asyncio.create_task(self._handle_preference_utterance(text))
+            elif sender == "User" and is_missed_request(text):
+                asyncio.create_task(self._handle_missed_utterance(last_active))
+            elif sender == "User" and is_digest_request(text):
+                asyncio.create_task(self._handle_digest_utterance())
+            elif sender == "User" and answer_quick_question(text):
+                asyncio.create_task(self._say_to_user(answer_quick_question(text)))
+            elif sender == "User" and answer_availability_question(text):
+                asyncio.create_task(self._say_to_user(answer_availability_question(text)))
+            elif sender == "User" and answer_capability_question(text, self.config):
+                asyncio.create_task(self._say_to_user(answer_capability_question(text, self.config)))
+            elif sender == "User" and get_skill_plugins().match(text):
+                asyncio.create_task(self._handle_plugin_utterance(text))
+            elif sender == "User":
+                asyncio.create_task(self._detect_followups(text))
             
             # Update visualizer when Moshi speaks
             if sender == "Moshi":
@@ -2291,6 +4717,7 @@ Just output the welcome message, nothing else:"""
         # Return dict with mic_amplitude and connection_amplitude
         if self.voice_orchestrator:
             mic_amp = getattr(self.voice_orchestrator, '_current_mic_amplitude', 0.0)
+            self.mic_meter.sample(mic_amp * 2.0)  # As the waveform shows it
             moshi_amp = getattr(self.voice_orchestrator, '_current_moshi_amplitude', 0.0)
             
             # DUPLEX: Both mic and moshi are independent and simultaneous
@@ -2310,7 +4737,9 @@ Just output the welcome message, nothing else:"""
             
             return {
                 "mic_amplitude": mic_amp * 2.0,  # Bottom waveform (always show when mic active)
-                "connection_amplitude": conn_amp  # Top circular viz (always show when moshi active)
+                "connection_amplitude": conn_amp,  # Top circular viz (always show when moshi active)
+                # Time left to reply without the wake word
+                "follow_up": self.follow_up.remaining() if self._needs_wake_word() else 0.0,
             }
             return {"mic_amplitude": 0.0, "connection_amplitude": 0.0}
 
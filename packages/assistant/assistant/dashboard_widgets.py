@@ -9,6 +9,9 @@ Includes:
 - ProjectDashboard
 - WorkerDashboard
 - ScheduleWidget
+- FollowUpWidget
+- CallScreeningWidget
+- InboxWidget
 - StatusWidget
 - AudioVisualizer
 """
@@ -36,7 +39,18 @@ from textual.events import Key
 from textual.message import Message
 
 # Import from sibling package
+from .accessibility import mirror_chat
+from .bookmarks import note_turn
+from .buffers import get_buffer_caps, ring
+from .matrix import relay_chat
+from .pairing import forward_chat
+from .charts import History, bar_rows, sparkline
+from .correlation import thread_rows
+from .dates import get_date_settings
+from .geocoding import event_map_link, travel_conflicts
 from .hardware import GPUCapability, detect_gpu_capability
+from .model_loading import render_bar
+from .timezones import format_dual, home_timezone_name, to_display
 
 
 class PanelBase(Static, can_focus=True):
@@ -373,6 +387,7 @@ class VoiceVisualizerPanel(Static):
 
         # Persona name (rendered above divider line)
         self.persona_name = "JARVIS"  # Default persona name
+        self.follow_up_remaining: float = 0.0  # Seconds left to reply without the wake word (conversation_window.py)
 
         # Data callback - app provides this to let widget pull real-time data
         self.data_callback: Optional[Callable[[], Any]] = None  # Set by app after initialization
@@ -385,6 +400,15 @@ class VoiceVisualizerPanel(Static):
             if not self.simulation_mode:
                 self._animation_timer = self.set_interval(1 / self.fps, self._update_animation)
 
+    def set_fps(self, fps: int):
+        """Change the animation rate (the power profile's visualizer_fps), restarting a running timer."""
+        if fps == self.fps:
+            return
+        self.fps = fps
+        if self.is_animating and self._animation_timer:
+            self._animation_timer.stop()
+            self._animation_timer = self.set_interval(1 / self.fps, self._update_animation)
+
     def stop_animation(self):
         """Stop the visualization animation."""
         if self.is_animating and self._animation_timer:
@@ -414,6 +438,7 @@ class VoiceVisualizerPanel(Static):
                 conn_amp = data.get("connection_amplitude")
                 if conn_amp is not None:
                     self.connection_amplitude = conn_amp
+                self.follow_up_remaining = data.get("follow_up", 0.0)
             except Exception:
                 pass  # Callback failed, use existing data
 
@@ -927,6 +952,9 @@ class VoiceVisualizerPanel(Static):
 
         # Render persona name above divider line (centered)
         persona_text = f"◈ {self.persona_name} ◈"
+        if self.follow_up_remaining > 0:
+            # Listening for a reply without the wake word
+            persona_text += f" ⋯ {math.ceil(self.follow_up_remaining)}s"
         persona_padding = (content_width - len(persona_text)) // 2
         persona_line = " " * persona_padding + persona_text
         result.append(persona_line + "\n", style=shade_5)
@@ -1010,27 +1038,39 @@ class ActivityFeed(Static, can_focus=True):
     - Line numbers
     - Terminal prompt style
     - Message type indicators
-    - Keyboard navigation (left/escape returns to sidebar)
+    - Messages from one interaction grouped in a thread (correlation.py);
+      older threads collapse when a new one starts
+    - Keyboard navigation (up/down pick a thread, enter/space opens or
+      collapses it, left/escape returns to sidebar)
     """
 
-    def __init__(self, max_messages: int = 100, **kwargs):
+    def __init__(self, max_messages: Optional[int] = None, **kwargs):
         super().__init__(**kwargs)
-        self.messages = deque(maxlen=max_messages)
+        self.messages = ring(max_messages or get_buffer_caps()["activity"])
         self._message_counter = 0
+        self.collapsed: set = set()  # Thread IDs shown as their first message only
+        self.selected_thread: Optional[str] = None
 
-    def add_message(self, message: str, msg_type: str = "info"):
+    def add_message(self, message: str, msg_type: str = "info", thread: Optional[str] = None,
+                    at: Optional[datetime] = None):
         """
         Add a message to the activity feed.
 
         Args:
             message: The message text
             msg_type: Type of message (info, success, warning, error, system)
+            thread: The interaction it belongs to, if any
+            at: When it happened (now unless replaying, see timeline.py)
 
         Returns:
             int: The message ID (for tracking/updating later)
         """
-        timestamp = datetime.now().strftime("%H:%M:%S.%f")[:-3]  # Include milliseconds
+        timestamp = (at or datetime.now()).strftime("%H:%M:%S.%f")[:-3]  # Include milliseconds
         self._message_counter += 1
+        if thread and thread not in self._threads():
+            self.collapsed.update(self._threads())
+        if len(self.messages) == self.messages.maxlen:
+            self.collapsed &= set(self._threads())  # Forget threads that scrolled out of the feed
 
         # DEBUG: Log to file for diagnosis
         try:
@@ -1043,11 +1083,30 @@ class ActivityFeed(Static, can_focus=True):
             "id": self._message_counter,
             "timestamp": timestamp,
             "message": message,
-            "type": msg_type
+            "type": msg_type,
+            "thread": thread
         })
         self.refresh()
         return self._message_counter
 
+    def count_repeat(self, message: str, msg_type: str, count: int, at: Optional[datetime] = None) -> bool:
+        """
+        The same message came again (see coalesce.py): show the count and
+        when it was last seen on its latest entry instead of adding another.
+        False when that entry has already scrolled out of the feed.
+        """
+        for msg in reversed(self.messages):
+            if msg["message"] == message and msg["type"] == msg_type:
+                msg["count"] = count
+                msg["last_seen"] = (at or datetime.now()).strftime("%H:%M:%S")
+                self.refresh()
+                return True
+        return False
+
+    def _threads(self) -> List[str]:
+        """Thread IDs in the feed, oldest first."""
+        return list(dict.fromkeys(msg["thread"] for msg in self.messages if msg.get("thread")))
+
     def update_last_message(self, message: str, msg_type: str = None):
         """Update the last message instead of adding a new one (useful for progress updates)"""
         if not self.messages:
@@ -1061,8 +1120,7 @@ class ActivityFeed(Static, can_focus=True):
 
         # Update last message in place
         self.messages[-1] = {
-            "id": self.messages[-1]["id"],  # Keep same ID
-            "timestamp": self.messages[-1]["timestamp"],  # Keep original timestamp
+            **self.messages[-1],  # Keep same ID, original timestamp and thread
             "message": message,
             "type": msg_type
         }
@@ -1079,8 +1137,7 @@ class ActivityFeed(Static, can_focus=True):
 
                 # Update message in place
                 self.messages[i] = {
-                    "id": msg["id"],  # Keep same ID
-                    "timestamp": msg["timestamp"],  # Keep original timestamp
+                    **msg,  # Keep same ID, original timestamp and thread
                     "message": message,
                     "type": msg_type
                 }
@@ -1106,8 +1163,8 @@ class ActivityFeed(Static, can_focus=True):
         else:
             return "info"
 
-    def _format_message(self, msg: dict) -> Text:
-        """Format a single message with subtle grayscale shades"""
+    def _format_message(self, msg: dict, marker: str = "") -> Text:
+        """Format a single message with subtle grayscale shades (marker: its place in a thread)"""
         result = Text()
 
         # Use dynamic theme colors if available, otherwise fallback to defaults
@@ -1141,6 +1198,12 @@ class ActivityFeed(Static, can_focus=True):
         # Timestamp
         result.append(f"[{msg['timestamp']}] ", style=shade_3)  # shade-3 (medium)
 
+        # Thread marker: "▾" / "▸ +3" opens a thread, "└" continues it
+        if marker:
+            selected = msg.get("thread") == self.selected_thread and marker != "└"
+            result.append(f"{'  ' if marker == '└' else ''}{marker} ",
+                          style=f"reverse {shade_4}" if selected else shade_3)
+
         # Type indicator
         result.append(f"{indicator} ", style=color)
 
@@ -1155,6 +1218,8 @@ class ActivityFeed(Static, can_focus=True):
             text_style = shade_4  # shade-4 (light)
 
         result.append(msg["message"], style=text_style)
+        if msg.get("count", 1) > 1:
+            result.append(f"  ×{msg['count']}, last {msg['last_seen']}", style=shade_3)
 
         return result
 
@@ -1177,19 +1242,32 @@ class ActivityFeed(Static, can_focus=True):
             # Show messages that fit in available height
             visible_messages = list(self.messages)
 
-            for msg in visible_messages:
+            for msg, marker in thread_rows(visible_messages, self.collapsed):
                 # Format message
-                msg_text = self._format_message(msg)
+                msg_text = self._format_message(msg, marker)
                 result.append(msg_text)
                 result.append("\n")
 
         return result
 
     def on_key(self, event: Key) -> None:
-        """Handle keyboard navigation. Left/Escape returns to sidebar."""
+        """Handle keyboard navigation. Up/Down pick a thread, Enter/Space toggle it, Left/Escape return to sidebar."""
         if event.key in ("left", "escape"):
             self.app.action_focus_sidebar()
             event.stop()
+        elif event.key in ("up", "down"):
+            threads = [msg["thread"] for msg, marker in thread_rows(self.messages, self.collapsed)
+                       if marker not in ("", "└")]
+            if threads:
+                at = threads.index(self.selected_thread) if self.selected_thread in threads else len(threads)
+                at = max(at - 1, 0) if event.key == "up" else min(at + 1, len(threads) - 1)
+                self.selected_thread = threads[at]
+                self.refresh()
+            event.stop()
+        elif event.key in ("enter", "space") and self.selected_thread:
+            self.collapsed ^= {self.selected_thread}
+            self.refresh()
+            event.stop()
 
 
 class CyberpunkActivityFeed(Static):
@@ -1299,6 +1377,9 @@ class CyberpunkFooter(Static):
     Cyberpunk-styled footer with project and system status.
 
     Features:
+    - Privacy badge: mic state and audio leaving the machine (privacy.py)
+    - Timer and stopwatch countdowns (timers.py)
+    - The persona's mood (personas/mood.py)
     - GPU status (sufficient/insufficient)
     - Number of projects
     - Project progress with color coding
@@ -1331,6 +1412,21 @@ class CyberpunkFooter(Static):
     
     # Voice server connection status
     voice_status = reactive(None)  # None, "connected", "disconnected"
+    # Moshi model load progress (model_loading.LoadProgress) while voice starts in the background
+    voice_loading = reactive(None)
+    # Audio pipeline backpressure (audio_bus.summarize), None until voice is running
+    audio_health = reactive(None)
+    # Background subsystems the task supervisor gave up restarting (supervisor.py)
+    failed_subsystems = reactive(())
+    # Privacy badge (privacy.py): mic state, and where audio is leaving the machine for
+    mic_state = reactive("off")  # off, wake_word, streaming
+    audio_egress = reactive(())
+    # Running timers and the stopwatch (timers.py), e.g. ("pasta 8:42", "stopwatch 1:23")
+    timers = reactive(())
+    # The persona's mood (personas/mood.py), e.g. "cheerful"; empty hides it
+    mood = reactive("")
+    # A newer release of xswarm (self_update.py), e.g. "0.33.0"; empty hides it
+    update_available = reactive("")
 
     # Theme colors dictionary (set dynamically by app)
     theme_colors = None
@@ -1383,6 +1479,26 @@ class CyberpunkFooter(Static):
         # Start directly with content (no inner border/box)
         result.append("▓▒░ ", style=f"bold {primary}")
 
+        # Privacy badge - always first, so it's never truncated away
+        if self.mic_state == "streaming":
+            result.append("● MIC LIVE", style="bold red")
+        elif self.mic_state == "wake_word":
+            result.append("◐ MIC WAKE WORD", style="bold yellow")
+        else:
+            result.append("○ MIC OFF", style=shade_4)
+        if self.audio_egress:
+            result.append(f" ☁ audio → {', '.join(self.audio_egress)}", style="bold #3c8ce6")
+        result.append(" │ ", style=shade_3)
+
+        # Timer countdowns, right after the badge so they stay visible
+        if self.timers:
+            result.append(f"⏲ {' · '.join(self.timers)}", style=f"bold {primary}")
+            result.append(" │ ", style=shade_3)
+
+        if self.mood:
+            result.append(f"☺ {self.mood}", style=shade_4)
+            result.append(" │ ", style=shade_3)
+
         # 1. AI Capability Score - FIRST ITEM (most important for AI workloads)
         if self.gpu_capability:
             gpu = self.gpu_capability
@@ -1413,11 +1529,27 @@ class CyberpunkFooter(Static):
             result.append(f" {gpu.util_percent:.0f}%", style=f"{grade_color}")
             result.append(" │ ", style=shade_3)
 
+        # Voice model loading progress bar (until voice is ready)
+        if self.voice_loading:
+            result.append("🎙️ ", style=shade_4)
+            result.append(render_bar(self.voice_loading.fraction), style=f"bold {primary}")
+            result.append(f" {self.voice_loading.fraction:.0%} {self.voice_loading.label}", style=shade_4)
+            result.append(" │ ", style=shade_3)
         # Voice Server Status (if available)
-        if hasattr(self, 'voice_status') and self.voice_status:
+        elif hasattr(self, 'voice_status') and self.voice_status:
             status_icon = "🎙️" if self.voice_status == "connected" else "🔇"
             status_color = "green" if self.voice_status == "connected" else "red"
             result.append(status_icon, style=f"bold {status_color}")
+            if self.audio_health and (self.audio_health["lag_percent"] >= 50 or self.audio_health["dropped"]):
+                lag_color = "red" if self.audio_health["lag_percent"] >= 80 else "yellow"
+                result.append(f" lag {self.audio_health['lag_percent']}%", style=lag_color)
+                if self.audio_health["dropped"]:
+                    result.append(f" ↓{self.audio_health['dropped']}", style=shade_4)
+            result.append(" │ ", style=shade_3)
+
+        # Subsystems that crashed too often to restart
+        if self.failed_subsystems:
+            result.append(f"✖ {', '.join(self.failed_subsystems)} down", style="bold red")
             result.append(" │ ", style=shade_3)
 
         # 2. Version Number
@@ -1426,6 +1558,8 @@ class CyberpunkFooter(Static):
             from . import __version__
             app_version = __version__
             result.append(f"v{app_version}", style=f"bold {primary}")
+            if self.update_available:
+                result.append(f" ⬆ {self.update_available}", style="bold yellow")
             result.append(" │ ", style=shade_3)
         except Exception:
             pass  # Skip if version not available
@@ -2031,6 +2165,11 @@ class ProjectDashboard(Static, can_focus=True):
             if folders:
                 for folder in folders:
                     result.append(f"   {folder}\n", style=shade_4)
+                from .project_watch import get_project_watcher
+                watcher = get_project_watcher()
+                index_status = watcher.status_line(proj.id) if watcher else ""
+                if index_status:
+                    result.append(f"   {index_status}\n", style=shade_4)
 
             # Next action (most important for GTD)
             if proj.next_action:
@@ -2115,11 +2254,13 @@ class ScheduleWidget(Static, can_focus=True):
     Schedule/Calendar widget showing today's schedule and upcoming events.
     Pulls from planner calendar events and tasks.
     Auto-refreshes every 5 seconds to pick up changes.
+    Keys: c cycles a category filter (work, personal, ...; habits are hidden while filtered).
     """
 
     def __init__(self, **kwargs):
         super().__init__(**kwargs)
         self._last_data_hash: Optional[str] = None
+        self.category_filter: Optional[str] = None
 
     def on_mount(self) -> None:
         """Start auto-refresh timer when mounted."""
@@ -2160,12 +2301,13 @@ class ScheduleWidget(Static, can_focus=True):
             return None
 
     def _get_todays_events(self):
-        """Get today's calendar events."""
+        """Get today's calendar events, with server appointments made on other devices."""
         planner = self._get_planner_data()
         if not planner:
             return []
         try:
-            return planner.get_todays_events()
+            from .appointment_cache import get_appointment_cache
+            return get_appointment_cache().events(tag=self.category_filter)
         except Exception:
             return []
 
@@ -2175,7 +2317,7 @@ class ScheduleWidget(Static, can_focus=True):
         if not planner:
             return []
         try:
-            return planner.get_upcoming_events(days=days)
+            return planner.get_upcoming_events(days=days, tag=self.category_filter)
         except Exception:
             return []
 
@@ -2200,15 +2342,16 @@ class ScheduleWidget(Static, can_focus=True):
                 if t.completed_at and t.completed_at[:10] == today_date
             ]
 
-            return inbox + tasks + scheduled + completed_today
+            from .categories import has_tag
+            return [t for t in inbox + tasks + scheduled + completed_today if has_tag(t.tags, self.category_filter)]
         except Exception:
             return []
 
     def _get_habits_due_today(self):
         """Get all habits for today (both due and completed)."""
         planner = self._get_planner_data()
-        if not planner:
-            return []
+        if not planner or self.category_filter:
+            return []  # Habits aren't categorized
         try:
             today = datetime.now().date()
             today_str = today.isoformat()
@@ -2256,19 +2399,36 @@ class ScheduleWidget(Static, can_focus=True):
         today_str = now.strftime("%A, %B %d")
         today_date = now.date().isoformat()
 
+        settings = get_date_settings()
+        travelling = bool(settings.travel_timezone)
+
         # Top padding for visual breathing room
         result.append("\n")
+        if travelling:
+            result.append(f" 🧳 Travel mode: {settings.travel_timezone}", style=f"bold {primary}")
+            result.append(f"  (home {home_timezone_name(settings)})\n\n", style=shade_3)
+        if self.category_filter:
+            result.append(f" Showing #{self.category_filter} only", style=f"bold {primary}")
+            result.append("  [c] next category\n\n", style=shade_3)
 
         # Collect all items: (time, type, title, duration, done, id, priority, task_obj)
         scheduled_items = []
         unscheduled_items = []
         completed_items = []
+        event_places = {}  # event id -> (location, map link), shown under the event
 
-        # 1. Calendar events (always scheduled)
-        for event in self._get_todays_events():
+        # 1. Calendar events (always scheduled; in travel mode at local time, with home time after)
+        todays_events = self._get_todays_events()
+        for event in todays_events:
             try:
-                time = event.start_time[11:16] if "T" in event.start_time else "00:00"
-                scheduled_items.append((time, "event", event.title, 60, False, event.id, "high", None))
+                if event.location:
+                    event_places[event.id] = (event.location, event_map_link(event))
+                start = datetime.fromisoformat(event.start_time)
+                time = to_display(start).strftime("%H:%M") if "T" in event.start_time else "00:00"
+                title = f"{event.title} ({event.participant_summary()})" if event.attendees else event.title
+                if travelling and "T" in event.start_time and time != start.strftime("%H:%M"):
+                    title += f" · {start:%H:%M} home"
+                scheduled_items.append((time, "event", title, 60, False, event.id, "high", None))
             except Exception:
                 continue
 
@@ -2360,6 +2520,14 @@ class ScheduleWidget(Static, can_focus=True):
                     result.append(f"{time_str}{type_icon}", style=shade_4)
                     result.append(f"{title}", style="white")
                     result.append(f"{dur_str}\n", style="dim")
+                if item_type == "event" and item_id in event_places:
+                    # Clickable in terminals that support links
+                    place, url = event_places[item_id]
+                    result.append(f"       📍 {place}\n", style=f"{shade_4} link {url}")
+
+        # Back-to-back events in different places without time to get there
+        for conflict in travel_conflicts(todays_events):
+            result.append(f" ⚠ {conflict.text}\n", style="yellow")
 
         result.append("\n")
 
@@ -2446,19 +2614,638 @@ class ScheduleWidget(Static, can_focus=True):
                         day_name = event_datetime.strftime("%A %m/%d")
                         result.append(f"\n {day_name}\n", style=f"bold {shade_4}")
 
-                    time_part = event_datetime.strftime("%H:%M")
+                    time_part = format_dual(event_datetime) if travelling else event_datetime.strftime("%H:%M")
                     result.append(f"   {time_part} ", style="white")
-                    result.append(f"{event.title}\n", style="white")
+                    result.append(event.title, style="white")
+                    if event.attendees:
+                        result.append(f"  {event.participant_summary()}", style=shade_4)
+                    result.append("\n")
                 except Exception:
                     continue
 
         return result
 
+    def cycle_category_filter(self) -> None:
+        """Step the filter through all, then each category."""
+        from .categories import CATEGORIES
+        options = [None, *CATEGORIES]
+        self.category_filter = options[(options.index(self.category_filter) + 1) % len(options)]
+        self.refresh()
+
     def on_key(self, event: Key) -> None:
-        """Handle keyboard navigation. Left/Escape returns to sidebar."""
+        """Handle keyboard navigation. Left/Escape returns to sidebar, c filters by category."""
         if event.key in ("left", "escape"):
             self.app.action_focus_sidebar()
             event.stop()
+        elif event.key == "c":
+            self.cycle_category_filter()
+            event.stop()
+
+
+class FollowUpWidget(Static, can_focus=True):
+    """
+    Confirmation list for follow-ups detected in conversation.
+    Keys: up/down select, y add to planner, n dismiss, a add all.
+    Hidden when nothing is pending.
+    """
+
+    selected_index = reactive(0)
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self._last_data_hash: Optional[str] = None
+        self._items: list = []
+
+    def on_mount(self) -> None:
+        """Start auto-refresh timer when mounted."""
+        self._check_for_updates()
+        self.set_interval(3.0, self._check_for_updates)
+
+    def _check_for_updates(self) -> None:
+        """Reload pending follow-ups and refresh if anything changed."""
+        try:
+            from .tools import get_followup_store
+            store = get_followup_store()
+            store.reload()
+            self._items = store.pending()
+            data_hash = ":".join(f.id for f in self._items)
+            if data_hash != self._last_data_hash:
+                self._last_data_hash = data_hash
+                self.selected_index = min(self.selected_index, max(0, len(self._items) - 1))
+                self.display = bool(self._items)
+                self.refresh()
+        except Exception:
+            pass
+
+    def _resolve(self, accept: bool, all_items: bool = False) -> None:
+        from .tools import get_followup_store, get_planner_data
+        store = get_followup_store()
+        if all_items:
+            targets = self._items
+        elif 0 <= self.selected_index < len(self._items):
+            targets = [self._items[self.selected_index]]
+        else:
+            targets = []
+        for item in targets:
+            if accept:
+                result = store.accept(item.id, get_planner_data())
+                if result and hasattr(self.app, "update_activity"):
+                    self.app.update_activity(result)
+            else:
+                store.dismiss(item.id)
+        self._check_for_updates()
+
+    def render(self) -> Text:
+        """Render pending follow-ups."""
+        result = Text()
+
+        theme = getattr(self, 'theme_colors', None)
+        if theme:
+            primary = theme["primary"]
+            shade_3 = theme["shade_3"]
+            shade_4 = theme["shade_4"]
+        else:
+            primary = "cyan"
+            shade_3 = "#4d5966"
+            shade_4 = "#6b7a8a"
+
+        if not self._items:
+            return result
+
+        result.append("\n")
+        result.append(" FOLLOW-UPS", style=f"bold {primary}")
+        result.append(f"  {len(self._items)} to confirm\n", style=shade_4)
+        for index, item in enumerate(self._items[:8]):
+            selected = index == self.selected_index
+            cursor = "▶" if selected else " "
+            icon = "🤝" if item.kind == "commitment" else "☐"
+            result.append(f" {cursor} {icon} ", style=primary if selected else shade_4)
+            result.append(f"{item.describe()}\n", style="white" if selected else shade_4)
+            if selected:
+                result.append(f"     “{item.source[:70]}”\n", style=shade_3)
+        result.append("\n [y] add  [n] dismiss  [a] add all\n", style=shade_3)
+        return result
+
+    def on_key(self, event: Key) -> None:
+        """Handle navigation and confirmation."""
+        if event.key in ("left", "escape"):
+            self.app.action_focus_sidebar()
+            event.stop()
+        elif event.key in ("down", "j"):
+            if self._items:
+                self.selected_index = min(self.selected_index + 1, len(self._items) - 1)
+                self.refresh()
+            event.stop()
+        elif event.key in ("up", "k"):
+            self.selected_index = max(self.selected_index - 1, 0)
+            self.refresh()
+            event.stop()
+        elif event.key in ("y", "enter"):
+            self._resolve(accept=True)
+            event.stop()
+        elif event.key == "n":
+            self._resolve(accept=False)
+            event.stop()
+        elif event.key == "a":
+            self._resolve(accept=True, all_items=True)
+            event.stop()
+
+
+class ListsWidget(Static, can_focus=True):
+    """
+    Shopping/todo lists (see lists.py), one at a time.
+    Keys: up/down select, space/x check off, d remove, c clear checked, [ ] switch list.
+    Hidden when there are no lists.
+    """
+
+    selected_index = reactive(0)
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self._last_data_hash: Optional[str] = None
+        self._names: list = []
+        self._list = ""
+        self._items: list = []
+
+    def on_mount(self) -> None:
+        """Start auto-refresh timer when mounted."""
+        self._check_for_updates()
+        self.set_interval(3.0, self._check_for_updates)
+
+    def _check_for_updates(self) -> None:
+        """Reload lists from disk (voice and sync change them) and refresh if anything changed."""
+        try:
+            from .lists import get_list_store
+            store = get_list_store()
+            store.reload()
+            self._names = store.names()
+            if self._list not in self._names:
+                self._list = self._names[0] if self._names else ""
+            self._items = store.items(self._list) if self._list else []
+            data_hash = self._list + ":" + ":".join(f"{i.id}:{i.done}" for i in self._items)
+            if data_hash != self._last_data_hash:
+                self._last_data_hash = data_hash
+                self.selected_index = min(self.selected_index, max(0, len(self._items) - 1))
+                self.display = bool(self._names)
+                self.refresh()
+        except Exception:
+            pass
+
+    def _selected(self):
+        if 0 <= self.selected_index < len(self._items):
+            return self._items[self.selected_index]
+        return None
+
+    def _switch(self, step: int) -> None:
+        if self._names:
+            index = self._names.index(self._list) if self._list in self._names else 0
+            self._list = self._names[(index + step) % len(self._names)]
+            self.selected_index = 0
+            self._check_for_updates()
+
+    def render(self) -> Text:
+        """Render the current list."""
+        result = Text()
+
+        theme = getattr(self, 'theme_colors', None)
+        if theme:
+            primary = theme["primary"]
+            shade_3 = theme["shade_3"]
+            shade_4 = theme["shade_4"]
+        else:
+            primary = "cyan"
+            shade_3 = "#4d5966"
+            shade_4 = "#6b7a8a"
+
+        if not self._names:
+            return result
+
+        open_count = sum(1 for item in self._items if not item.done)
+        result.append("\n")
+        result.append(f" {self._list.upper()} LIST", style=f"bold {primary}")
+        result.append(f"  {open_count} to go", style=shade_4)
+        if len(self._names) > 1:
+            result.append(f"  ({self._names.index(self._list) + 1}/{len(self._names)})", style=shade_3)
+        result.append("\n")
+        for index, item in enumerate(self._items[:12]):
+            selected = index == self.selected_index
+            cursor = "▶" if selected else " "
+            result.append(f" {cursor} {'☑' if item.done else '☐'} ", style=primary if selected else shade_4)
+            style = shade_3 if item.done else ("white" if selected else shade_4)
+            result.append(f"{item.text}\n", style=f"strike {style}" if item.done else style)
+        if len(self._items) > 12:
+            result.append(f"   … {len(self._items) - 12} more\n", style=shade_3)
+        result.append("\n [x] check off  [d] remove  [c] clear checked  [ ] switch list\n", style=shade_3)
+        return result
+
+    def on_key(self, event: Key) -> None:
+        """Handle navigation and check-off."""
+        from .lists import get_list_store
+        item = self._selected()
+        if event.key in ("left", "escape"):
+            self.app.action_focus_sidebar()
+            event.stop()
+        elif event.key in ("down", "j"):
+            if self._items:
+                self.selected_index = min(self.selected_index + 1, len(self._items) - 1)
+                self.refresh()
+            event.stop()
+        elif event.key in ("up", "k"):
+            self.selected_index = max(self.selected_index - 1, 0)
+            self.refresh()
+            event.stop()
+        elif event.key in ("space", "x", "enter"):
+            if item:
+                get_list_store().set_done(self._list, item.text, done=not item.done)
+                self._check_for_updates()
+            event.stop()
+        elif event.key == "d":
+            if item:
+                get_list_store().remove(self._list, item.text)
+                self._check_for_updates()
+            event.stop()
+        elif event.key == "c":
+            get_list_store().clear(self._list, done_only=True)
+            self._check_for_updates()
+            event.stop()
+        elif event.key in ("left_square_bracket", "right_square_bracket"):
+            self._switch(-1 if event.key == "left_square_bracket" else 1)
+            event.stop()
+
+
+class UsageWidget(Static):
+    """
+    This week's usage from the event history (see analytics.py): conversations
+    per day as bars, reply latency, reminders and the most used commands.
+    """
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self._report = None
+
+    def on_mount(self) -> None:
+        self._reload()
+        self.set_interval(60.0, self._reload)
+
+    def _reload(self) -> None:
+        try:
+            from .analytics import build_report
+            from .events import get_event_store
+            self._report = build_report(get_event_store(), "week")
+            self.refresh()
+        except Exception:
+            pass
+
+    def render(self) -> Text:
+        result = Text()
+        theme = getattr(self, 'theme_colors', None)
+        if theme:
+            primary = theme["primary"]
+            shade_3 = theme["shade_3"]
+            shade_4 = theme["shade_4"]
+        else:
+            primary = "cyan"
+            shade_3 = "#4d5966"
+            shade_4 = "#6b7a8a"
+
+        report = self._report
+        if report is None:
+            return result
+        result.append(" USAGE", style=f"bold {primary}")
+        result.append(f"  {report.conversations} conversation(s) this week\n", style=shade_4)
+        width = 24
+        for day, filled in report.bars(width):
+            result.append(f" {day.day:%a %d}  ", style=shade_4)
+            result.append("█" * filled, style=primary)
+            result.append("░" * (width - filled), style=shade_3)
+            result.append(f"  {day.conversations}\n", style="white")
+        result.append(f" {report.summary()}\n", style=shade_4)
+        if report.commands:
+            top = ", ".join(f"{name} ({count})" for name, count in report.commands[:3])
+            result.append(f" top: {top}\n", style=shade_3)
+        return result
+
+
+class Sparkline(Static):
+    """
+    A value over time as a one-line sparkline (see charts.py), with the
+    latest sample: " CPU    ▁▂▃▅▇▆▅▃  12%".
+    """
+
+    def __init__(self, label: str, unit: str = "", high: Optional[float] = None,
+                 decimals: int = 0, **kwargs):
+        super().__init__(**kwargs)
+        self.label = label
+        self.unit = unit
+        self.high = high  # Fixed top of the scale (100 for percentages), else the samples' own
+        self.decimals = decimals
+        caps = get_buffer_caps()
+        self.history = History(caps["chart"], caps["chart_minutes"])
+
+    def add(self, value: float) -> None:
+        self.history.add(value)
+        self.refresh()
+
+    def render(self) -> Text:
+        result = Text()
+        theme = getattr(self, 'theme_colors', None)
+        primary = theme["primary"] if theme else "cyan"
+        shade_4 = theme["shade_4"] if theme else "#6b7a8a"
+
+        width = max(8, self.size.width - 20)
+        low = 0 if self.high is not None else None
+        result.append(f" {self.label:<8}", style=shade_4)
+        result.append(sparkline(self.history.values, width, low=low, high=self.high), style=primary)
+        last = self.history.last
+        value = "–" if last is None else f"{last:.{self.decimals}f}{self.unit}"
+        result.append(f"  {value}", style="bold white")
+        return result
+
+
+class BarChart(Static):
+    """Labelled horizontal bars, the largest filling the width (see charts.bar_rows)."""
+
+    def __init__(self, title: str = "", **kwargs):
+        super().__init__(**kwargs)
+        self.title = title
+        self.items: List[tuple] = []
+
+    def set_items(self, items: List[tuple]) -> None:
+        """(label, value) pairs, top to bottom."""
+        self.items = list(items)
+        self.refresh()
+
+    def render(self) -> Text:
+        result = Text()
+        theme = getattr(self, 'theme_colors', None)
+        primary = theme["primary"] if theme else "cyan"
+        shade_3 = theme["shade_3"] if theme else "#4d5966"
+        shade_4 = theme["shade_4"] if theme else "#6b7a8a"
+
+        if self.title:
+            result.append(f" {self.title}\n", style=f"bold {primary}")
+        width = 24
+        for label, filled, value in bar_rows(self.items, width):
+            result.append(f" {label}  ", style=shade_4)
+            result.append("█" * filled, style=primary)
+            result.append("░" * (width - filled), style=shade_3)
+            result.append(f"  {value:g}\n", style="white")
+        return result
+
+
+class CallScreeningWidget(Static, can_focus=True):
+    """
+    Live view of a call the assistant is screening.
+    Shows the caller's stated purpose as it is transcribed.
+    Keys: y accept, n decline, v voicemail. Hidden when no call is active.
+    """
+
+    def on_mount(self) -> None:
+        """Start refresh timer when mounted."""
+        self._refresh_calls()
+        self.set_interval(1.0, self._refresh_calls)
+
+    def _calls(self) -> list:
+        manager = getattr(self.app, "call_screening", None)
+        return manager.active_calls if manager else []
+
+    def _refresh_calls(self) -> None:
+        self.display = bool(self._calls())
+        self.refresh()
+
+    def render(self) -> Text:
+        """Render the active screened call(s)."""
+        result = Text()
+
+        theme = getattr(self, 'theme_colors', None)
+        if theme:
+            primary = theme["primary"]
+            shade_3 = theme["shade_3"]
+            shade_4 = theme["shade_4"]
+        else:
+            primary = "cyan"
+            shade_3 = "#4d5966"
+            shade_4 = "#6b7a8a"
+
+        calls = self._calls()
+        if not calls:
+            return result
+
+        call = calls[0]
+        result.append("\n")
+        result.append(" 📞 SCREENING CALL", style=f"bold {primary}")
+        if len(calls) > 1:
+            result.append(f"  (+{len(calls) - 1} waiting)", style=shade_4)
+        result.append("\n")
+        result.append(f" {call.display_name}\n", style="bold white")
+        status = "asking purpose…" if call.status == "screening" else "on hold"
+        result.append(f" {status}\n", style=shade_4)
+        result.append(f" “{call.purpose or '…'}”\n", style="white")
+        result.append("\n [y] accept  [n] decline  [v] voicemail\n", style=shade_3)
+        return result
+
+    def on_key(self, event: Key) -> None:
+        """Handle screening decisions."""
+        actions = {"y": "accept", "n": "decline", "v": "voicemail"}
+        if event.key in ("left", "escape"):
+            self.app.action_focus_sidebar()
+            event.stop()
+        elif event.key in actions and hasattr(self.app, "decide_screened_call"):
+            self.app.decide_screened_call(actions[event.key])
+            event.stop()
+
+
+class InboxWidget(Static, can_focus=True):
+    """
+    Unified inbox showing inbound SMS, email and voice messages.
+    Keys: up/down select, enter/r mark read, a archive, d draft an AI reply.
+    Auto-refreshes every 5 seconds from the local inbox mirror.
+    """
+
+    selected_index = reactive(0)
+
+    def __init__(self, **kwargs):
+        super().__init__(**kwargs)
+        self._last_data_hash: Optional[str] = None
+        self._items: list = []
+
+    def on_mount(self) -> None:
+        """Start auto-refresh timer when mounted."""
+        self._check_for_updates()
+        self.set_interval(5.0, self._check_for_updates)
+
+    def _check_for_updates(self) -> None:
+        """Reload inbox from disk and refresh if anything changed."""
+        try:
+            from .tools import get_inbox_store
+            store = get_inbox_store()
+            store.reload()
+            self._items = store.get_items()
+            data_hash = ":".join(f"{i.id}:{i.status}" for i in self._items[:50])
+            if data_hash != self._last_data_hash:
+                self._last_data_hash = data_hash
+                self.selected_index = min(self.selected_index, max(0, len(self._items) - 1))
+                self.refresh()
+        except Exception:
+            pass
+
+    def _selected_item(self):
+        if 0 <= self.selected_index < len(self._items):
+            return self._items[self.selected_index]
+        return None
+
+    def _set_selected_status(self, status: str) -> None:
+        item = self._selected_item()
+        if not item:
+            return
+        from .tools import get_inbox_store
+        get_inbox_store().set_status(item.id, status)
+        self._check_for_updates()
+
+    def render(self) -> Text:
+        """Render inbox list with selected item preview."""
+        result = Text()
+
+        theme = getattr(self, 'theme_colors', None)
+        if theme:
+            primary = theme["primary"]
+            shade_3 = theme["shade_3"]
+            shade_4 = theme["shade_4"]
+        else:
+            primary = "cyan"
+            shade_3 = "#4d5966"
+            shade_4 = "#6b7a8a"
+
+        unread = sum(1 for i in self._items if i.status == "unread")
+        result.append("\n")
+        result.append(f" INBOX", style=f"bold {primary}")
+        result.append(f"  {unread} unread\n", style=shade_4)
+        result.append(" " + "─" * 40 + "\n", style=shade_3)
+
+        if not self._items:
+            result.append("\n  No messages yet.\n", style=shade_4)
+            return result
+
+        for index, item in enumerate(self._items[:30]):
+            selected = index == self.selected_index
+            cursor = "▶" if selected else " "
+            weight = "bold " if item.status == "unread" else ""
+            color = "white" if item.status in ("unread", "read") else shade_4
+            when = item.received_at[5:16].replace("T", " ")
+            result.append(f" {cursor} {item.icon} ", style=primary if selected else shade_4)
+            result.append(f"{item.sender[:20]:<20} ", style=f"{weight}{color}")
+            result.append(f"{item.preview[:40]}", style=color)
+            result.append(f"  {when}", style=shade_3)
+            if item.status == "replied":
+                result.append(" ↩", style=shade_4)
+            result.append("\n")
+
+        item = self._selected_item()
+        if item:
+            result.append("\n " + "─" * 40 + "\n", style=shade_3)
+            if item.subject:
+                result.append(f" {item.subject}\n", style=f"bold {shade_4}")
+            result.append(f" {item.content[:500]}\n", style="white")
+            if item.reply_text:
+                result.append(f"\n ↩ {item.reply_text[:300]}\n", style=shade_4)
+            result.append("\n [r] read  [a] archive  [d] draft reply\n", style=shade_3)
+
+        return result
+
+    def on_key(self, event: Key) -> None:
+        """Handle keyboard navigation and item actions."""
+        if event.key in ("left", "escape"):
+            self.app.action_focus_sidebar()
+            event.stop()
+        elif event.key in ("down", "j"):
+            if self._items:
+                self.selected_index = min(self.selected_index + 1, len(self._items) - 1)
+                self.refresh()
+            event.stop()
+        elif event.key in ("up", "k"):
+            self.selected_index = max(self.selected_index - 1, 0)
+            self.refresh()
+            event.stop()
+        elif event.key in ("enter", "r"):
+            self._set_selected_status("read")
+            event.stop()
+        elif event.key == "a":
+            self._set_selected_status("archived")
+            event.stop()
+        elif event.key == "d":
+            item = self._selected_item()
+            if item and hasattr(self.app, "draft_inbox_reply"):
+                self.app.draft_inbox_reply(item.id)
+            event.stop()
+
+
+class HelpWidget(Static, can_focus=True):
+    """
+    Everything xswarm can do, from the capabilities registry (capabilities.py),
+    grouped by category and filtered by the search box above it. Features that
+    need config first are listed last with the fields to set.
+    Keys: up/down scroll.
+    """
+
+    search_text = reactive("")
+
+    def render(self) -> Text:
+        """Render matching capabilities with their examples."""
+        from .capabilities import search
+
+        result = Text()
+        theme = getattr(self, 'theme_colors', None)
+        if theme:
+            primary = theme["primary"]
+            shade_3 = theme["shade_3"]
+            shade_4 = theme["shade_4"]
+        else:
+            primary = "cyan"
+            shade_3 = "#4d5966"
+            shade_4 = "#6b7a8a"
+
+        config = getattr(self.app, "config", None)
+        found = search(self.search_text, config)
+        result.append("\n")
+        result.append(" WHAT I CAN DO", style=f"bold {primary}")
+        result.append(f"  {len(found)} shown  ·  ask 'what can you do?' any time\n", style=shade_4)
+        result.append(" " + "─" * 40 + "\n", style=shade_3)
+
+        if not found:
+            result.append(f"\n  Nothing matches '{self.search_text}'.\n", style=shade_4)
+            return result
+
+        category = None  # Headings only for the full list; search results come best first
+        for capability in found:
+            missing = capability.missing(config) if config is not None else []
+            if not missing and not self.search_text.strip() and capability.category != category:
+                category = capability.category
+                result.append(f"\n {category.upper()}\n", style=f"bold {shade_4}")
+            elif missing and category != "setup":
+                category = "setup"
+                result.append("\n NEEDS SETTING UP\n", style=f"bold {shade_4}")
+            result.append(f"  {capability.name}", style=f"bold {'white' if not missing else shade_4}")
+            result.append(f"  {capability.summary}\n", style=shade_4)
+            for example in capability.examples:
+                result.append(f"     \"{example}\"\n", style=primary if not missing else shade_3)
+            if missing:
+                result.append(f"     set {', '.join(missing)} in the config\n", style=shade_3)
+        return result
+
+    def watch_search_text(self, _text: str) -> None:
+        self.scroll_home(animate=False)
+
+    def on_key(self, event: Key) -> None:
+        """Scroll, or hand focus back to the sidebar."""
+        if event.key in ("left", "escape"):
+            self.app.action_focus_sidebar()
+            event.stop()
+        elif event.key in ("down", "j"):
+            self.scroll_down()
+            event.stop()
+        elif event.key in ("up", "k"):
+            self.scroll_up()
+            event.stop()
 
 
 class StatusWidget(Static):
@@ -2551,8 +3338,8 @@ class AudioVisualizer(Static):
         # Check if voice is enabled in the app config
         try:
             app = self.app
-            if hasattr(app, 'config') and hasattr(app.config, 'voice_enabled'):
-                if not app.config.voice_enabled:
+            if hasattr(app, 'config') and hasattr(app.config, 'voice_active'):
+                if not app.config.voice_active:
                     # Voice disabled - don't start animation
                     return
         except Exception:
@@ -3138,6 +3925,37 @@ class CyberpunkVisualizer(Static):
 
 # END OF FILE
 
+class CaptionLine(Static):
+    """
+    Live captions under the chat: the user's words while they speak, then
+    the final transcript for a moment (see captions.py). Hidden otherwise.
+    """
+
+    DEFAULT_CSS = """
+    CaptionLine {
+        width: 100%;
+        height: auto;
+        max-height: 2;
+        padding: 0 2;
+        display: none;
+    }
+    """
+
+    caption = reactive(None)  # (text, is_final) or None
+
+    def watch_caption(self, caption) -> None:
+        self.display = caption is not None
+
+    def render(self) -> Text:
+        result = Text()
+        if self.caption is None:
+            return result
+        text, is_final = self.caption
+        result.append("🎤 ", style="dim")
+        result.append(text, style="yellow" if is_final else "dim italic yellow")
+        return result
+
+
 class ChatHistory(TextArea, can_focus=True):
     """
     Displays the conversation history between User and Moshi.
@@ -3173,7 +3991,7 @@ class ChatHistory(TextArea, can_focus=True):
             **kwargs
         )
         # Store messages for reconstruction
-        self._messages: List[tuple] = []  # [(sender, text), ...]
+        self._messages = ring(get_buffer_caps()["chat"])  # [(sender, text), ...], the oldest dropped (buffers.py)
         self._last_assistant_response: str = ""
         # Streaming optimization: track if we're in a streaming update
         self._streaming_sender: str = ""
@@ -3198,6 +4016,10 @@ class ChatHistory(TextArea, can_focus=True):
     def add_message(self, sender: str, text: str):
         """Add a message to the chat history."""
         self._messages.append((sender, text))
+        mirror_chat(sender, text)
+        forward_chat(sender, text)
+        relay_chat(sender, text)
+        note_turn(sender, text)
 
         # Track assistant responses for easy copy
         if sender.lower() not in ["user", "system", "debug"]:
@@ -3225,6 +4047,10 @@ class ChatHistory(TextArea, can_focus=True):
         """
         if self._messages:
             self._messages[-1] = (sender, text)
+        mirror_chat(sender, text)
+        forward_chat(sender, text)
+        relay_chat(sender, text)
+        note_turn(sender, text, replace_last=True)
 
         # Track assistant responses
         if sender.lower() not in ["user", "system", "debug"]:
@@ -0,0 +1,798 @@
+"""
+Natural Dates - Turn spoken dates and times into calendar values.
+
+Shared by the calendar/task tools (tools._parse_natural_date) and follow-up
+deadlines (followups.resolve_deadline), so "friday" means the same day
+everywhere.
+
+Dates (parse_natural_date):
+- today, tonight, tomorrow, the day after tomorrow, yesterday
+- weekdays: "friday", "next friday", "this friday" (the coming one, never
+  today), "friday next week"
+- relative: "in 3 days", "in a couple of weeks", "two months from now",
+  "in 1 year" (calendar months: Jan 31 + 1 month is Feb 28/29)
+- periods: end of day/week/month/year, this weekend, next week, next month
+- quarters and fiscal years: "end of the quarter", "end of next quarter",
+  "next quarter", "Q3", "end of Q2 2027", "end of the fiscal year"
+  (quarters follow config.fiscal_year_start_month; FY2027 is the fiscal
+  year that ends in 2027)
+- days of a month: "the 3rd", "next month on the 3rd", "the 3rd of next
+  month", "March 3", "3rd of March 2027"
+- numeric: "2026-10-16", "2026/10/16", "3/14", "14.03.2027"
+
+Numeric day/month dates are read in the configured order (DateSettings:
+config.date_order, "auto" follows the system locale, so "04/05/2025" is
+April 5 in the US and 4 May in the UK). When both readings are real dates
+and differ, ambiguous_readings() returns both so the caller can ask which
+was meant (config.confirm_ambiguous_dates); a reading only one order allows
+("25/12") is used whatever the setting.
+
+Times (parse_time_expression), always returned as 24h "HH:MM":
+- "16:00", "4pm", "4:30 p.m.", "noon", "midnight"
+- "half past three", "a quarter to 5", "ten past four", "twenty to six"
+- "three o'clock", "three thirty", "four forty-five in the afternoon"
+- "first thing" (start of the workday), "lunchtime", "end of day"/"close
+  of business" (end of the workday) - "first thing Monday" works as a whole
+
+Ends (parse_end_time), given the start: "until 3", "till half past four",
+"to 5pm", "until end of day", "through the afternoon", "all evening".
+
+A bare hour with no am/pm ("at four", "half past three") is read as a
+working-day time: 7-11 are mornings, 12 is noon, 1-6 are afternoons.
+"in the morning"/"in the evening"/"tonight" override that. Clock times
+without am/pm ("09:00", "3:30", "4.30") are taken as 24h times.
+
+Nothing here raises on bad input: unparseable text returns None.
+
+The other direction (a datetime back into words for speech) is verbalize.py.
+"""
+
+import logging
+import os
+import re
+from dataclasses import dataclass, field
+from datetime import date, datetime, timedelta, tzinfo
+from typing import Any, List, Mapping, Optional, Tuple
+from zoneinfo import ZoneInfo, ZoneInfoNotFoundError
+
+logger = logging.getLogger(__name__)
+
+MAX_INPUT_LENGTH = 200  # Longer "dates" are sentences; don't spend time on them
+
+WEEKDAYS = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"]
+WEEKDAY_ALIASES = {
+    "mon": 0, "tue": 1, "tues": 1, "wed": 2, "thu": 3, "thur": 3, "thurs": 3,
+    "fri": 4, "sat": 5, "sun": 6,
+    **{name: i for i, name in enumerate(WEEKDAYS)},
+}
+
+MONTHS = ["january", "february", "march", "april", "may", "june", "july", "august",
+          "september", "october", "november", "december"]
+MONTH_ALIASES = {
+    **{name: i + 1 for i, name in enumerate(MONTHS)},
+    **{name[:3]: i + 1 for i, name in enumerate(MONTHS)},
+    "sept": 9,
+}
+
+NUMBER_WORDS = {
+    "zero": 0, "oh": 0, "a": 1, "an": 1, "one": 1, "two": 2, "three": 3, "four": 4, "five": 5,
+    "six": 6, "seven": 7, "eight": 8, "nine": 9, "ten": 10, "eleven": 11, "twelve": 12,
+    "thirteen": 13, "fourteen": 14, "fifteen": 15, "sixteen": 16, "seventeen": 17,
+    "eighteen": 18, "nineteen": 19, "twenty": 20, "thirty": 30, "forty": 40, "fifty": 50,
+    "couple": 2, "few": 3,
+}
+
+ORDINAL_WORDS = {
+    "first": 1, "second": 2, "third": 3, "fourth": 4, "fifth": 5, "sixth": 6, "seventh": 7,
+    "eighth": 8, "ninth": 9, "tenth": 10, "eleventh": 11, "twelfth": 12, "thirteenth": 13,
+    "fourteenth": 14, "fifteenth": 15, "sixteenth": 16, "seventeenth": 17, "eighteenth": 18,
+    "nineteenth": 19, "twentieth": 20, "thirtieth": 30,
+}
+
+DATE_ORDERS = ("mdy", "dmy")
+# Territories that write the month first; everywhere else day/month (and C/POSIX keep the old mdy)
+MONTH_FIRST_TERRITORIES = {"US", "PH", "FM", "MH", "PW", "AS", "GU", "MP", "PR", "VI", "UM"}
+CLOCK_FORMATS = ("12h", "24h")
+# Where times are usually said on a 12-hour clock ("half past two" rather than "fourteen thirty")
+TWELVE_HOUR_TERRITORIES = MONTH_FIRST_TERRITORIES | {"GB", "IE", "CA", "AU", "NZ", "IN", "PK", "BD", "EG", "SA", "MY"}
+
+# The planner's default working day (PlannerData.auto_schedule_tasks)
+WORKDAY_START = "09:00"
+WORKDAY_END = "18:00"
+NAMED_TIMES = {
+    "first thing": WORKDAY_START, "start of day": WORKDAY_START,
+    "lunch": "12:00", "lunchtime": "12:00",
+    "end of day": WORKDAY_END, "eod": WORKDAY_END, "close of business": WORKDAY_END, "cob": WORKDAY_END,
+    "end of business": WORKDAY_END, "eob": WORKDAY_END, "close of play": WORKDAY_END,
+}
+# Parts of the day said with a date ("tomorrow morning"): (from, to, time used when the time given is outside)
+PART_OF_DAY_TIMES = {
+    "morning": ("05:00", "12:00", "09:00"),
+    "afternoon": ("12:00", "17:00", "14:00"),
+    "evening": ("17:00", "24:00", "19:00"),
+    "night": ("17:00", "24:00", "19:00"),
+    "tonight": ("17:00", "24:00", "19:00"),
+}
+
+UNIT_DAYS = {"day": 1, "days": 1, "week": 7, "weeks": 7, "fortnight": 14, "fortnights": 14}
+UNIT_MONTHS = {"month": 1, "months": 1, "year": 12, "years": 12}
+
+# Words that lead an end: "until 3", "through the afternoon", "all evening"
+END_WORDS = {"until", "till", "til", "to", "through", "thru", "all", "ending", "ends", "finishing", "at", "the"}
+
+# Filler that carries no date/time information
+FILLER = {"on", "at", "by", "the", "of", "for", "around", "about", "before", "until", "due", "from", "starting"}
+
+# After _tokens ("p.m." -> "pm"); "in the morning" etc. are stripped word by word
+AM_WORDS = ("am", "morning")
+PM_WORDS = ("pm", "afternoon", "evening", "night", "tonight")
+
+
+# ==============================================================================
+# DATE ORDER
+# ==============================================================================
+
+class AmbiguousDateError(ValueError):
+    """A numeric date that reads as two different days; ask the user which."""
+
+    def __init__(self, text: str, readings: List[date]):
+        self.text = text
+        self.readings = readings
+        self.question = clarifying_question(text, readings)
+        super().__init__(f"'{text}' is ambiguous. {self.question}")
+
+
+def system_date_order(environ: Optional[Mapping[str, str]] = None) -> str:
+    """mdy or dmy from the locale environment (LC_ALL, LC_TIME, LANG), e.g. en_GB.UTF-8 -> dmy."""
+    environ = os.environ if environ is None else environ
+    for name in ("LC_ALL", "LC_TIME", "LANG"):
+        value = environ.get(name)
+        if value:
+            match = re.match(r"[a-z]{2,3}_([A-Z]{2})", value)
+            if not match:
+                return "mdy"  # C, POSIX, plain "en"
+            return "mdy" if match.group(1) in MONTH_FIRST_TERRITORIES else "dmy"
+    return "mdy"
+
+
+def system_clock_format(environ: Optional[Mapping[str, str]] = None) -> str:
+    """12h or 24h from the locale environment, e.g. en_US -> 12h, de_DE -> 24h."""
+    environ = os.environ if environ is None else environ
+    for name in ("LC_ALL", "LC_TIME", "LANG"):
+        value = environ.get(name)
+        if value:
+            match = re.match(r"[a-z]{2,3}_([A-Z]{2})", value)
+            if not match:
+                return "12h"
+            return "12h" if match.group(1) in TWELVE_HOUR_TERRITORIES else "24h"
+    return "12h"
+
+
+@dataclass
+class DateSettings:
+    """
+    How dates are read and spoken (config.date_order, confirm_ambiguous_dates,
+    fiscal_year_start_month, clock_format, timezone, travel_timezone).
+    """
+    order: str = "auto"  # mdy, dmy or auto (system locale)
+    ask_when_ambiguous: bool = True
+    fiscal_start_month: int = 1  # Month Q1 and the fiscal year start in
+    clock: str = "auto"  # 12h, 24h or auto (system locale); used by verbalize.py
+    timezone: Optional[str] = None  # Home zone (IANA name) planner times are in; None = system local time
+    travel_timezone: Optional[str] = None  # Travel mode: show, speak and enter times in this zone (timezones.py)
+    config: Optional[Any] = field(default=None, repr=False, compare=False)  # Saved when travel mode changes
+
+    @classmethod
+    def from_config(cls, config) -> "DateSettings":
+        fiscal_start = getattr(config, "fiscal_year_start_month", 1)
+        return cls(order=getattr(config, "date_order", "auto") or "auto",
+                   ask_when_ambiguous=getattr(config, "confirm_ambiguous_dates", True),
+                   fiscal_start_month=fiscal_start if fiscal_start in range(1, 13) else 1,
+                   clock=getattr(config, "clock_format", "auto") or "auto",
+                   timezone=getattr(config, "timezone", None) or None,
+                   travel_timezone=getattr(config, "travel_timezone", None) or None,
+                   config=config)
+
+    def resolved_order(self) -> str:
+        order = self.order.strip().lower()
+        return order if order in DATE_ORDERS else system_date_order()
+
+    def resolved_clock(self) -> str:
+        clock = self.clock.strip().lower()
+        return clock if clock in CLOCK_FORMATS else system_clock_format()
+
+    def home_zone(self) -> Optional[tzinfo]:
+        """The configured home zone, or None for system local time (also when the name is unknown)."""
+        return _zone(self.timezone)
+
+    def travel_zone(self) -> Optional[tzinfo]:
+        """The travel mode zone, or None when not travelling."""
+        return _zone(self.travel_timezone)
+
+    def display_zone(self) -> Optional[tzinfo]:
+        """The zone times are shown and spoken in: the travel zone while travelling, else home."""
+        return self.travel_zone() or self.home_zone()
+
+
+def _zone(name: Optional[str]) -> Optional[tzinfo]:
+    if not name:
+        return None
+    try:
+        return ZoneInfo(name)
+    except (ZoneInfoNotFoundError, ValueError):
+        logger.warning(f"Unknown timezone {name!r}, using local time")
+        return None
+
+
+_settings: Optional[DateSettings] = None
+
+
+def get_date_settings() -> DateSettings:
+    """Get the global date settings (locale defaults until configured)."""
+    global _settings
+    if _settings is None:
+        _settings = DateSettings()
+    return _settings
+
+
+def set_date_settings(settings: DateSettings) -> None:
+    """Install the settings built from the loaded Config (called at startup and on settings save)."""
+    global _settings
+    _settings = settings
+
+
+def _order(date_order: Optional[str]) -> str:
+    return date_order if date_order in DATE_ORDERS else get_date_settings().resolved_order()
+
+
+def describe_date(day: date) -> str:
+    """ "Saturday April 5, 2025" """
+    return f"{day.strftime('%A')} {MONTHS[day.month - 1].title()} {day.day}, {day.year}"
+
+
+def clarifying_question(text: str, readings: List[date]) -> str:
+    """What to ask when `text` could mean several days."""
+    options = " or ".join(describe_date(day) for day in readings)
+    return f"Did you mean {options}?"
+
+
+# ==============================================================================
+# TOKENS
+# ==============================================================================
+
+def _tokens(text: str) -> List[str]:
+    """
+    Lowercase words with punctuation removed; numbers and "3rd"/"4:30" stay
+    whole, and so does a minus sign, so "in -3 days" isn't read as 3 days ahead.
+    """
+    text = text.lower().replace("o'clock", " oclock").replace("o’clock", " oclock")
+    text = re.sub(r"(\d)\s*(a\.?m\.?|p\.?m\.?)(?![a-z])", r"\1 \2", text)  # "4pm" -> "4 pm"
+    text = re.sub(r"\b(a|p)\.m\.?", r"\1m", text)
+    text = re.sub(r"-(?!\d)", " ", text).replace(",", " ")  # "twenty-five", but not 2026-10-16 or -3
+    text = re.sub(r"(?<!\d)\.|\.(?!\d)", " ", text)  # Sentence dots, but not 14.03.2027 or 4.30
+    text = re.sub(r"[^\w:/.\-\s]", " ", text)
+    return text.split()
+
+
+def _number(words: List[str]) -> Optional[int]:
+    """A cardinal from one or two words: "3", "three", "twenty five"."""
+    if len(words) == 1:
+        word = words[0]
+        if word.isdigit() and len(word) <= 4:
+            return int(word)
+        return NUMBER_WORDS.get(word)
+    if len(words) == 2 and words[0] in ("twenty", "thirty", "forty", "fifty"):
+        ones = NUMBER_WORDS.get(words[1])
+        if ones is not None and 1 <= ones <= 9:
+            return NUMBER_WORDS[words[0]] + ones
+    return None
+
+
+def _ordinal(words: List[str]) -> Optional[int]:
+    """A day of the month: "3", "3rd", "third", "twenty first"."""
+    if len(words) == 1:
+        match = re.fullmatch(r"(\d{1,2})(?:st|nd|rd|th)?", words[0])
+        if match:
+            return int(match.group(1))
+        return ORDINAL_WORDS.get(words[0])
+    if len(words) == 2 and words[0] in ("twenty", "thirty"):
+        ones = ORDINAL_WORDS.get(words[1])
+        if ones is not None and 1 <= ones <= 9:
+            return NUMBER_WORDS[words[0]] + ones
+    return None
+
+
+# ==============================================================================
+# DATES
+# ==============================================================================
+
+def add_months(day: date, months: int) -> date:
+    """
+    Same day of the month `months` later (or earlier), clamped to the
+    month's last day: Jan 31 + 1 month = Feb 28/29. Works on datetimes too.
+    Raises ValueError/OverflowError past year 1-9999, like date arithmetic.
+    """
+    index = day.month - 1 + months
+    year, month = day.year + index // 12, index % 12 + 1
+    if not 1 <= year <= 9999:
+        raise OverflowError(f"year {year} is out of range")
+    return day.replace(year=year, month=month, day=min(day.day, month_length(year, month)))
+
+
+def add_years(day: date, years: int) -> date:
+    """Same day `years` later; Feb 29 becomes Feb 28 outside leap years."""
+    return add_months(day, 12 * years)
+
+
+def month_length(year: int, month: int) -> int:
+    if month == 12:
+        return 31
+    return (date(year, month + 1, 1) - timedelta(days=1)).day
+
+
+def _month_end(day: date) -> date:
+    return day.replace(day=month_length(day.year, day.month))
+
+
+def _quarter_start(today: date, fiscal_start: int) -> date:
+    """First day of the (fiscal) quarter `today` is in."""
+    into_quarter = (today.month - fiscal_start) % 3
+    return add_months(today.replace(day=1), -into_quarter)
+
+
+def _fiscal_year_start(today: date, fiscal_start: int) -> date:
+    """First day of the fiscal year `today` is in."""
+    return add_months(today.replace(day=1), -((today.month - fiscal_start) % 12))
+
+
+def _fiscal_year_named(year: int, fiscal_start: int) -> date:
+    """Start of FY`year` - the fiscal year that ends in `year`."""
+    return date(year if fiscal_start == 1 else year - 1, fiscal_start, 1)
+
+
+def _parse_period_words(phrase: str, words: List[str], today: date) -> Optional[date]:
+    """Quarters and fiscal/calendar years ("end of next quarter", "q3 2027", "end of the fiscal year")."""
+    fiscal_start = get_date_settings().fiscal_start_month
+
+    if phrase in ("end of year", "end of this year", "eoy", "year end"):
+        return date(today.year, 12, 31)
+    if phrase == "end of next year":
+        return date(today.year + 1, 12, 31)
+
+    # "this quarter" / "next quarter" / "end of (this|next) quarter"
+    match = re.fullmatch(r"(?:(start|beginning|end) of )?(this |next |following )?(?:fiscal )?quarter"
+                         r"(?: end)?|eoq", phrase)
+    if match:
+        edge, which = match.group(1), (match.group(2) or "this ").strip()
+        if phrase == "eoq" or phrase.endswith("quarter end"):
+            edge = "end"
+        start = _quarter_start(today, fiscal_start)
+        if which != "this":
+            start = add_months(start, 3)
+        elif edge is None:
+            edge = "end"  # "by this quarter" means before it's over
+        return _month_end(add_months(start, 2)) if edge == "end" else start
+
+    # "q3", "end of q2", "start of q4 2027", "q1 fy2027"
+    match = re.fullmatch(r"(?:(start|beginning|end) of )?q([1-4])(?: (?:fy)?(\d{4})|(?: fy(\d{4})))?", phrase)
+    if match:
+        edge, quarter = match.group(1), int(match.group(2))
+        year = match.group(3) or match.group(4)
+        if year:
+            start = add_months(_fiscal_year_named(int(year), fiscal_start), 3 * (quarter - 1))
+        else:
+            # The next Q<n> that isn't over yet
+            start = add_months(_fiscal_year_start(today, fiscal_start), 3 * (quarter - 1))
+            if _month_end(add_months(start, 2)) < today:
+                start = add_months(start, 12)
+        return _month_end(add_months(start, 2)) if edge == "end" else start
+
+    # "end of the fiscal year", "next fiscal year", "fy2027", "end of fy2027"
+    match = re.fullmatch(r"(?:(start|beginning|end) of )?(?:(this|next|following) )?(?:fiscal year|fy)"
+                         r"(?: end)?(?: ?(\d{4}))?|eofy", phrase)
+    if match:
+        edge, which, year = match.group(1), match.group(2) or "this", match.group(3)
+        if phrase == "eofy" or phrase.endswith("year end"):
+            edge = "end"
+        if year:
+            start = _fiscal_year_named(int(year), fiscal_start)
+        else:
+            start = _fiscal_year_start(today, fiscal_start)
+            if which != "this":
+                start = add_months(start, 12)
+            elif edge is None:
+                edge = "end"
+        return add_months(start, 12) - timedelta(days=1) if edge == "end" else start
+    return None
+
+
+def _next_weekday(today: date, weekday: int) -> date:
+    """The coming `weekday`, never today ("friday" said on a Friday is next week's)."""
+    return today + timedelta(days=(weekday - today.weekday()) % 7 or 7)
+
+
+def _next_day_of_month(today: date, day: int) -> Optional[date]:
+    """The next date (from today on) that falls on this day of the month."""
+    if not 1 <= day <= 31:
+        return None
+    year, month = today.year, today.month
+    for _ in range(13):
+        if day <= month_length(year, month):
+            candidate = date(year, month, day)
+            if candidate >= today:
+                return candidate
+        year, month = (year + 1, 1) if month == 12 else (year, month + 1)
+    return None
+
+
+def _month_day(month: int, day: int, year: Optional[int], today: date) -> Optional[date]:
+    """A calendar day; without a year, the next time it comes round."""
+    if year is not None:
+        try:
+            return date(year, month, day)
+        except ValueError:
+            return None  # Feb 30th, Feb 29th outside a leap year...
+    for candidate_year in range(today.year, today.year + 9):  # Feb 29th waits for a leap year
+        try:
+            candidate = date(candidate_year, month, day)
+        except ValueError:
+            continue
+        if candidate >= today:
+            return candidate
+    return None
+
+
+def _year(word: str) -> Optional[int]:
+    return int(word) if re.fullmatch(r"\d{4}", word) and 1900 <= int(word) <= 9999 else None
+
+
+def _numeric_readings(phrase: str, today: date, order: str) -> Optional[List[date]]:
+    """
+    The days a numeric date could be, the `order` reading first (empty if
+    neither is a real day). None when `phrase` isn't a numeric date.
+    """
+    match = re.fullmatch(r"(\d{4})([/.-])(\d{1,2})\2(\d{1,2})", phrase)
+    if match:  # Year first is always year-month-day
+        day = _month_day(int(match.group(3)), int(match.group(4)), int(match.group(1)), today)
+        return [day] if day else []
+
+    match = re.fullmatch(r"(\d{1,2})([/.-])(\d{1,2})(?:\2(\d{4}|\d{2}))?", phrase)
+    if not match:
+        return None
+    first, second, year = int(match.group(1)), int(match.group(3)), match.group(4)
+    year = None if year is None else int(year) + (2000 if len(year) == 2 else 0)
+    pairs = [(first, second), (second, first)] if order == "mdy" else [(second, first), (first, second)]
+    readings = []
+    for month, day in pairs:
+        reading = _month_day(month, day, year, today) if 1 <= month <= 12 else None
+        if reading and reading not in readings:
+            readings.append(reading)
+    return readings
+
+
+def _parse_date_words(words: List[str], today: date, order: str = "mdy") -> Optional[date]:
+    if not words:
+        return None
+    phrase = " ".join(w for w in words if w != "the")  # "end of the week", "the day after tomorrow"
+
+    if phrase in ("today", "tonight", "eod", "end of day", "end of today", "this evening", "this morning",
+                  "this afternoon"):
+        return today
+    if phrase in ("tomorrow", "tmrw", "tmr", "tomorrow morning", "tomorrow afternoon",
+                  "tomorrow evening", "tomorrow night"):
+        return today + timedelta(days=1)
+    if phrase in ("day after tomorrow", "a day after tomorrow", "overmorrow"):
+        return today + timedelta(days=2)
+    if phrase == "yesterday":
+        return today - timedelta(days=1)
+    if phrase in ("end of week", "end of this week", "eow", "this week"):
+        return today + timedelta(days=(4 - today.weekday()) % 7)
+    if phrase in ("weekend", "this weekend"):
+        return today + timedelta(days=(5 - today.weekday()) % 7)
+    if phrase == "next weekend":
+        return today + timedelta(days=7 - today.weekday() + 5)
+    if phrase in ("next week", "start of next week", "beginning of next week"):
+        return today + timedelta(days=7 - today.weekday())
+    if phrase in ("end of next week",):
+        return today + timedelta(days=7 - today.weekday() + 4)
+    if phrase in ("end of month", "end of this month", "eom", "month end"):
+        return _month_end(today)
+    if phrase in ("next month", "start of next month", "beginning of next month"):
+        return add_months(today.replace(day=1), 1)
+    if phrase == "end of next month":
+        return _month_end(add_months(today.replace(day=1), 1))
+    if phrase in ("next year", "start of next year", "beginning of next year"):
+        return date(today.year + 1, 1, 1)
+    period = _parse_period_words(phrase, words, today)
+    if period is not None:
+        return period
+
+    # Numeric: ISO, "3/14", "14.03.2027" (in the configured order when it could be either)
+    if len(words) == 1:
+        readings = _numeric_readings(phrase, today, order)
+        if readings is not None:
+            return readings[0] if readings else None
+
+    # Weekdays: "friday", "next friday", "this friday", "coming friday", "friday next week"
+    if words[0] in ("next", "this", "coming") and len(words) == 2 and words[1] in WEEKDAY_ALIASES:
+        return _next_weekday(today, WEEKDAY_ALIASES[words[1]])
+    if len(words) == 1 and words[0] in WEEKDAY_ALIASES:
+        return _next_weekday(today, WEEKDAY_ALIASES[words[0]])
+    if len(words) == 3 and words[0] in WEEKDAY_ALIASES and words[1:] == ["next", "week"]:
+        return today + timedelta(days=7 - today.weekday() + WEEKDAY_ALIASES[words[0]])
+
+    # Relative: "in 3 days", "two weeks from now", "in a couple of months"
+    relative = words[1:] if words[0] == "in" else words[:-2] if words[-2:] == ["from", "now"] else None
+    if relative:
+        relative = [w for w in relative if w != "of"]
+        if relative and relative[0] == "a" and len(relative) > 2:
+            relative = relative[1:]  # "a couple weeks", "a few days"
+        if len(relative) >= 2:
+            amount, unit = _number(relative[:-1]), relative[-1]
+            if amount is not None and amount <= 1000:
+                if unit in UNIT_DAYS:
+                    return today + timedelta(days=amount * UNIT_DAYS[unit])
+                if unit in UNIT_MONTHS:
+                    return add_months(today, amount * UNIT_MONTHS[unit])
+
+    # Day of next month: "next month on the 3rd", "the 3rd of next month", "3rd next month"
+    rest = None
+    if words[:2] == ["next", "month"]:
+        rest = words[2:]
+    elif words[-2:] == ["next", "month"]:
+        rest = words[:-2]
+    if rest is not None:
+        day = _ordinal([w for w in rest if w not in FILLER])
+        start = add_months(today.replace(day=1), 1)
+        if day is not None and 1 <= day <= month_length(start.year, start.month):
+            return start.replace(day=day)
+        return None
+
+    # Month and day: "march 3", "march 3rd 2027", "3rd of march", "3 march"
+    content = [w for w in words if w not in FILLER]
+    year = _year(content[-1]) if len(content) >= 3 else None
+    if year is not None:
+        content = content[:-1]
+    if len(content) >= 2:
+        if content[0] in MONTH_ALIASES:
+            day = _ordinal(content[1:])
+            if day is not None:
+                return _month_day(MONTH_ALIASES[content[0]], day, year, today)
+        if content[-1] in MONTH_ALIASES:
+            day = _ordinal(content[:-1])
+            if day is not None:
+                return _month_day(MONTH_ALIASES[content[-1]], day, year, today)
+
+    # Bare day of the month: "the 3rd", "on the twenty first"
+    if content and (words[0] == "the" or re.fullmatch(r"\d{1,2}(st|nd|rd|th)", content[0])):
+        day = _ordinal(content)
+        if day is not None:
+            return _next_day_of_month(today, day)
+    return None
+
+
+def parse_natural_date(text: str, today: Optional[date] = None, date_order: Optional[str] = None) -> Optional[date]:
+    """
+    A spoken/written date relative to `today`, or None if it isn't one.
+    Numeric dates use `date_order` (mdy/dmy), default the configured order.
+    """
+    if not isinstance(text, str) or len(text) > MAX_INPUT_LENGTH:
+        return None
+    return _try_date(_tokens(text), today or date.today(), _order(date_order))
+
+
+def ambiguous_readings(text: str, today: Optional[date] = None, date_order: Optional[str] = None) -> List[date]:
+    """
+    Both days a date like "04/05/2025" or "4/5 at 3pm" could be (the
+    configured order's first), or [] when the text reads the same either way.
+    """
+    order = _order(date_order)
+    readings: List[date] = []
+    for candidate_order in sorted(DATE_ORDERS, key=lambda o: o != order):
+        when = parse_natural_datetime(text, today=today, date_order=candidate_order)
+        if when is not None and when.date() not in readings:
+            readings.append(when.date())
+    return readings if len(readings) > 1 else []
+
+
+# ==============================================================================
+# TIMES
+# ==============================================================================
+
+def _meridiem(words: List[str]) -> Tuple[List[str], Optional[str]]:
+    """Split trailing "pm"/"in the morning"/"tonight" off a time phrase."""
+    meridiem = None
+    while words:
+        if words[-1] in AM_WORDS:
+            meridiem = meridiem or "am"
+        elif words[-1] in PM_WORDS:
+            meridiem = meridiem or "pm"
+        elif words[-1] not in ("in", "the", "at", "this", "of"):
+            break
+        words = words[:-1]
+    return words, meridiem
+
+
+def _apply_meridiem(hour: int, meridiem: Optional[str], bare: bool) -> Optional[int]:
+    """24h hour from a 12h hour; `bare` hours without am/pm get the working-day guess."""
+    if meridiem == "am":
+        return 0 if hour == 12 else hour if hour <= 12 else None
+    if meridiem == "pm":
+        return 12 if hour == 12 else hour + 12 if hour < 12 else None
+    if bare and 1 <= hour <= 6:
+        return hour + 12
+    return hour
+
+
+def _hour(words: List[str]) -> Optional[int]:
+    if words == ["noon"] or words == ["midday"]:
+        return 12
+    if words == ["midnight"]:
+        return 0
+    hour = _number(words)
+    return hour if hour is not None and 0 <= hour <= 23 else None
+
+
+def _parse_time_words(words: List[str]) -> Optional[str]:
+    words = [w for w in words if w not in ("at", "around", "about", "by", "for", "from")]
+    words, meridiem = _meridiem(words)
+    if words and words[-1] == "oclock":
+        words = words[:-1]
+    if not words:
+        return None
+
+    named = " ".join(w for w in words if w != "the")  # "end of the day"; "in the morning" is already gone
+    if named in NAMED_TIMES:
+        return NAMED_TIMES[named]
+    phrase = " ".join(words)
+    if phrase in ("noon", "midday") and meridiem != "am":
+        return "12:00"
+    if phrase == "midnight":
+        return "00:00"
+
+    # "16:00", "4:30", "4.30", "09:30:00"
+    match = re.fullmatch(r"(\d{1,2})[:.](\d{2})(?::\d{2})?", phrase)
+    if match:
+        hour, minute = int(match.group(1)), int(match.group(2))
+        if meridiem:
+            if not 1 <= hour <= 12:
+                return None
+            hour = _apply_meridiem(hour, meridiem, bare=False)
+        return f"{hour:02d}:{minute:02d}" if hour is not None and hour <= 23 and minute <= 59 else None
+
+    # "half past three", "a quarter to five", "ten past four", "twenty five to six"
+    for link, sign in (("past", 1), ("after", 1), ("to", -1), ("till", -1), ("before", -1)):
+        if link in words:
+            i = words.index(link)
+            amount = [w for w in words[:i] if w not in ("a", "minutes", "minute", "mins")]
+            if amount == ["half"]:
+                minutes = 30
+            elif amount == ["quarter"]:
+                minutes = 15
+            else:
+                minutes = _number(amount)
+            hour = _hour(words[i + 1:])
+            if minutes is None or hour is None or not 1 <= minutes <= 59 or (sign < 0 and minutes == 30):
+                return None
+            if hour > 12 and meridiem:
+                return None
+            if 1 <= hour <= 12:
+                hour = _apply_meridiem(hour, meridiem, bare=words[i + 1:] not in (["noon"], ["midday"]))
+            total = (hour * 60 + sign * minutes) % (24 * 60)
+            return f"{total // 60:02d}:{total % 60:02d}"
+
+    # "four", "4", "three thirty", "four forty five", "7 15"
+    hour = _hour(words[:1])
+    if hour is None:
+        return None
+    minutes = 0
+    if len(words) > 1:
+        rest = words[1:]
+        if rest[0] == "oh" and len(rest) == 2:  # "nine oh five"
+            rest = rest[1:]
+            minutes = _number(rest)
+            if minutes is None or minutes > 9:
+                return None
+        else:
+            minutes = _number(rest)
+            if minutes is None or not 0 <= minutes <= 59:
+                return None
+    if hour > 12:
+        return f"{hour:02d}:{minutes:02d}" if not meridiem else None
+    if hour == 0:
+        return f"00:{minutes:02d}" if meridiem != "pm" else None
+    hour = _apply_meridiem(hour, meridiem, bare=True)
+    return f"{hour:02d}:{minutes:02d}"
+
+
+def parse_time_expression(text: str) -> Optional[str]:
+    """A spoken/written time of day as 24h "HH:MM", or None if it isn't one."""
+    if not isinstance(text, str) or len(text) > MAX_INPUT_LENGTH:
+        return None
+    try:
+        return _parse_time_words(_tokens(text))
+    except (ValueError, OverflowError):
+        return None
+
+
+# ==============================================================================
+# DATE + TIME
+# ==============================================================================
+
+def parse_natural_datetime(text: str, default_time: str = "09:00", today: Optional[date] = None,
+                           date_order: Optional[str] = None) -> Optional[datetime]:
+    """
+    A date with an optional time in either order: "friday at half past
+    three", "at 4pm tomorrow", "next month on the 3rd". The time defaults
+    to `default_time`; None when the date part isn't understood.
+    """
+    if not isinstance(text, str) or len(text) > MAX_INPUT_LENGTH:
+        return None
+    today = today or date.today()
+    order = _order(date_order)
+    words = _tokens(text)
+    default = parse_time_expression(default_time) or "09:00"
+
+    day = _try_date(words, today, order)
+    if day is not None:
+        return _combine(day, default, words)
+
+    # Split into a date part and a time part, either way round
+    for i in range(len(words) - 1, 0, -1):
+        for date_words, time_words in ((words[:i], words[i:]), (words[i:], words[:i])):
+            day = _try_date(date_words, today, order)
+            if day is None:
+                continue
+            time_of_day = _parse_time_words(time_words)
+            if time_of_day is not None:
+                return _combine(day, time_of_day, [])
+    return None
+
+
+def _try_date(words: List[str], today: date, order: str) -> Optional[date]:
+    while words and words[0] in ("on", "by", "before", "until", "due", "for", "starting"):
+        words = words[1:]
+    while words and words[-1] in ("at", "on"):
+        words = words[:-1]
+    try:
+        return _parse_date_words(words, today, order)
+    except (ValueError, OverflowError):
+        return None  # Arithmetic past year 9999 and the like
+
+
+def parse_end_time(text: str, start: datetime) -> Optional[datetime]:
+    """
+    When an event starting at `start` ends, from "until 3", "till half past
+    four", "to 5pm" or "through the afternoon": the first such time after
+    `start` ("until 9" from 7pm is 9pm, "until 1am" from 10pm the next
+    day), or the end of the part of the day named. None when `text` isn't
+    an end.
+    """
+    if not isinstance(text, str) or len(text) > MAX_INPUT_LENGTH:
+        return None
+    words = _tokens(text)
+    while words and words[0] in END_WORDS:
+        words = words[1:]
+    if len(words) == 1 and words[0] in PART_OF_DAY_TIMES:
+        hour, minute = map(int, PART_OF_DAY_TIMES[words[0]][1].split(":"))
+        end = datetime.combine(start.date(), datetime.min.time()) + timedelta(hours=hour, minutes=minute)
+        return end if end > start else None
+    time_of_day = _parse_time_words(words) if words else None
+    if time_of_day is None:
+        return None
+    hour, minute = map(int, time_of_day.split(":"))
+    end = start.replace(hour=hour, minute=minute, second=0, microsecond=0)
+    candidates = [end, end + timedelta(days=1)]
+    if hour < 12 and _meridiem(list(words))[1] is None:
+        candidates.append(end + timedelta(hours=12))  # "until 9" in the evening
+    return min(c for c in candidates if c > start)
+
+
+def _combine(day: date, time_of_day: str, words: List[str]) -> datetime:
+    # "tomorrow evening" with a default time that isn't in the evening: the evening
+    if words and words[-1] in PART_OF_DAY_TIMES:
+        start, end, usual = PART_OF_DAY_TIMES[words[-1]]
+        if not start <= time_of_day < end:
+            time_of_day = usual
+    hour, minute = map(int, time_of_day.split(":"))
+    return datetime(day.year, day.month, day.day, hour, minute)
@@ -0,0 +1,215 @@
+"""
+Dialogue - What "it" means in a follow-up.
+
+    "book the dentist friday at 3"   -> add_calendar_event(...)
+    "move it to 4 instead"           -> update_calendar_event(event_id=<dentist>, start_time="16:00")
+    "invite Bob too"                 -> update_calendar_event(event_id=<dentist>, attendees="<theirs>, Bob")
+
+DialogueState keeps the item the conversation is about (the focus): the
+appointment, task, reminder, habit... the last tool call acted on or
+created. It is used before anything runs:
+
+- context_hint() names the focus for the model, in the same "[Context: ...]"
+  prefix the dashboard uses for its side panes.
+- resolve_args() fills a call's missing or pronoun target ("it", "that")
+  with the focus, and turns "too"/"as well" into adding attendees rather
+  than replacing them. ToolRegistry.execute_tool applies it to every call,
+  typed or spoken.
+
+An utterance that doesn't refer back ("it", "that", "instead", "too"...)
+changes the topic and clears the focus, as do "new topic", "never mind"
+and FOCUS_TTL without mentioning it. intents.hear() reports each utterance.
+"""
+
+import logging
+import re
+import time
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Optional
+
+from .confirmation import classify_action
+from .intents import TARGET_KEYS, current_utterance, describe_target, lookup_target
+
+logger = logging.getLogger(__name__)
+
+FOCUS_TTL = 300.0  # Seconds the focus survives without being mentioned
+
+_REFERS_BACK = re.compile(
+    r"\b(?:it|its|that|this|them|those|the same|instead|too|also|as well|again)\b", re.IGNORECASE)
+_ADDS = re.compile(r"\b(?:too|also|as well)\b", re.IGNORECASE)
+_RESET = re.compile(
+    r"^\s*(?:(?:ok(?:ay)?|right)[,\s]+)?(?:new topic|something else|change of subject|different question|"
+    r"never ?mind|forget (?:it|that))\b", re.IGNORECASE)
+_PRONOUN_ARGS = {"it", "that", "this", "that one", "this one", "the same", "same"}
+
+# Create tools, and the id key of what they create
+CREATED_KEYS = {
+    "add_calendar_event": "event_id",
+    "add_recurring_meeting": "event_id",
+    "add_task": "task_id",
+    "add_habit": "habit_id",
+    "add_goal": "goal_id",
+    "add_commitment": "commitment_id",
+    "add_project": "project_id",
+}
+
+
+@dataclass
+class Focus:
+    """The item the conversation is about."""
+    key: str  # One of intents.TARGET_KEYS: "event_id", "task_id", ...
+    item_id: str
+    title: str
+    touched_at: float = 0.0
+
+
+def refers_back(text: str) -> bool:
+    """True when `text` points at something already mentioned ("move it", "invite Bob too")."""
+    return bool(_REFERS_BACK.search(text or "")) and not _RESET.match(text or "")
+
+
+def _newest(key: str, title: str, planner) -> Optional[str]:
+    """Id of the most recently added item called `title` (creates don't return their id)."""
+    if key == "event_id":
+        items = [(e.id, e.title) for e in planner.get_calendar_events(expand_recurring=False)]
+    elif key == "task_id":
+        items = [(t.id, t.title) for t in planner.get_tasks()]
+    elif key == "habit_id":
+        items = [(h.id, h.name) for h in planner.get_habits()]
+    elif key == "goal_id":
+        items = [(g.id, g.name) for g in planner.get_goals()]
+    elif key == "commitment_id":
+        items = [(c.id, c.description) for c in planner.get_commitments(include_completed=True)]
+    else:
+        items = [(p.id, p.name) for p in planner.get_projects()]
+    matches = [item_id for item_id, name in items if name.strip().lower() == title.strip().lower()]
+    return matches[-1] if matches else None
+
+
+class DialogueState:
+    """Short-term memory of the item "it" refers to."""
+
+    def __init__(self, clock: Callable[[], float] = time.monotonic):
+        self.clock = clock
+        self.focus: Optional[Focus] = None
+
+    def _planner(self):
+        from .tools import get_planner_data
+        return get_planner_data()
+
+    def heard(self, text: str) -> None:
+        """A new utterance: unless it refers back, the topic changed."""
+        if not refers_back(text):
+            self.focus = None  # Whatever comes next sets the focus again
+
+    def _current(self) -> Optional[Focus]:
+        """The focus, unless it went unmentioned too long."""
+        if self.focus is not None and self.clock() - self.focus.touched_at > FOCUS_TTL:
+            self.focus = None
+        return self.focus
+
+    def clear(self) -> None:
+        self.focus = None
+
+    def focus_on(self, key: str, item_id: str, title: str) -> None:
+        """Make an item the one "it" refers to."""
+        self.focus = Focus(key, item_id, title, self.clock())
+
+    def is_focus(self, args: Dict[str, Any]) -> bool:
+        """True when a call's arguments point at the focus."""
+        focus = self._current()
+        return focus is not None and focus.item_id in (args.get(focus.key), args.get("item_id"))
+
+    def note_call(self, tool_name: str, args: Dict[str, Any], result: Any) -> None:
+        """After a call succeeded: focus on what it created or acted on; forget what it deleted."""
+        if not str(result).startswith("✓"):
+            return
+        try:
+            planner = self._planner()
+            if tool_name in CREATED_KEYS:
+                key = CREATED_KEYS[tool_name]
+                title = str(args.get("title") or args.get("name") or args.get("description") or "")
+                item_id = _newest(key, title, planner) if title else None
+                if item_id:
+                    self.focus_on(key, item_id, title)
+                return
+            for key in TARGET_KEYS:
+                item_id = args.get(key)
+                if not item_id or not isinstance(item_id, str):
+                    continue
+                if classify_action(tool_name) == "delete":
+                    if self.focus is not None and self.focus.item_id == item_id:
+                        self.clear()
+                    return
+                target = lookup_target(key, item_id, planner)
+                if target is not None:
+                    self.focus_on(key, item_id, target.title)
+                return
+        except Exception as e:
+            logger.debug(f"Could not note the focus of {tool_name}: {e}")
+
+    def resolve_args(self, tool_name: str, args: Dict[str, Any], params) -> Dict[str, Any]:
+        """
+        `args` with "it" resolved: a missing or pronoun id (one of the tool's
+        `params`) becomes the focus when it's the same kind of thing, and
+        attendees said with "too" are added to the focus's existing ones.
+        """
+        focus = self._current()
+        if focus is None:
+            return args
+        if focus.key in params:
+            key = focus.key
+        elif "item_id" in params and focus.key in ("task_id", "habit_id"):
+            key = "item_id"
+        else:
+            return args  # The call is about a different kind of thing
+        resolved = dict(args)
+        value = resolved.get(key)
+        if not value or (isinstance(value, str) and value.strip().lower() in _PRONOUN_ARGS):
+            resolved[key] = focus.item_id
+            logger.info(f"Resolved '{value or ''}' in {tool_name} to {focus.key} {focus.item_id} ({focus.title})")
+        if resolved[key] != focus.item_id:
+            return resolved
+        focus.touched_at = self.clock()
+
+        utterance = current_utterance()
+        if focus.key == "event_id" and resolved.get("attendees") and utterance and _ADDS.search(utterance.text):
+            event = self._planner().get_calendar_event(focus.item_id)
+            existing = list(event.attendees or []) if event else []
+            added = [a.strip() for a in str(resolved["attendees"]).split(",") if a.strip()]
+            known = {a.lower() for a in existing}
+            resolved["attendees"] = ", ".join(existing + [a for a in added if a.lower() not in known])
+        return resolved
+
+    def context_hint(self) -> str:
+        """ "[Context: 'it' is the 3pm Dentist (event_id evt-12)]", or "" with nothing in focus."""
+        focus = self._current()
+        if focus is None:
+            return ""
+        try:
+            target = lookup_target(focus.key, focus.item_id, self._planner())
+        except Exception as e:
+            logger.debug(f"Could not look up the focus: {e}")
+            return ""
+        if target is None:
+            self.clear()  # Deleted elsewhere
+            return ""
+        described = describe_target(target).replace("[", "(").replace("]", ")")
+        return f"[Context: 'it' is {described} ({focus.key} {focus.item_id})]"
+
+    def with_context(self, text: str) -> str:
+        """`text` for the model, prefixed with what "it" refers to when it refers back."""
+        hint = self.context_hint()
+        return f"{hint} {text}" if hint else text
+
+
+_state = DialogueState()
+
+
+def get_dialogue_state() -> DialogueState:
+    return _state
+
+
+def set_dialogue_state(state: DialogueState) -> None:
+    global _state
+    _state = state
@@ -0,0 +1,122 @@
+"""
+Durations - How long an event takes when nobody said.
+
+"Lunch with Sam at noon" used to be an hour like everything else. Now the
+length comes from, in order:
+
+1. History: the user's own past events of the same kind, averaged over
+   the last HISTORY_SIZE (rounded to ROUND_TO minutes) once there are
+   MIN_HISTORY of them - "~45 min, like your past lunches"
+2. config.event_durations, by keyword: {"lunch": 45, "1:1": 25}
+3. KEYWORD_MINUTES, by keyword: coffee 30, standup 15, dinner 90, ...
+4. DEFAULT_MINUTES
+
+An event's kind is the first keyword (config.event_durations first, then
+KEYWORD_MINUTES) its title mentions, else the whole title: "Lunch with Sam"
+and "Team lunch" are both lunches, while "Therapy" is only like other
+"Therapy" events. An end said instead ("until 3", "through the afternoon")
+is dates.parse_end_time's job and wins over all of this.
+"""
+
+import re
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import Any, Dict, Iterable, List, Optional
+
+DEFAULT_MINUTES = 60
+HISTORY_SIZE = 10  # Most recent past events averaged
+MIN_HISTORY = 2  # Past events needed before history counts
+ROUND_TO = 5
+MAX_MINUTES = 24 * 60
+KEYWORD_MINUTES = {
+    "standup": 15, "stand-up": 15, "check-in": 15,
+    "coffee": 30, "call": 30, "1:1": 30, "one-on-one": 30, "doctor": 30,
+    "breakfast": 45, "haircut": 45,
+    "lunch": 60, "meeting": 60, "interview": 60, "dentist": 60, "gym": 60, "workout": 60, "class": 60,
+    "therapy": 50,
+    "dinner": 90, "drinks": 90,
+    "movie": 150, "workshop": 180,
+}
+
+
+@dataclass
+class Duration:
+    minutes: int
+    kind: str  # "lunch", or the title lowercased when no keyword matched
+    source: str  # history, config, keyword or default
+    samples: int = 0  # Past events averaged (history)
+
+    @property
+    def note(self) -> str:
+        """" (~45 min, like your past lunches)" when learned from history, else empty."""
+        if self.source != "history":
+            return ""
+        return f" (~{self.minutes} min, like your past {_plural(self.kind)})"
+
+
+def _plural(kind: str) -> str:
+    if kind not in KEYWORD_MINUTES:
+        return f"'{kind}' events"
+    return kind + ("es" if kind.endswith(("ch", "sh", "s", "x")) else "s")
+
+
+def _mentions(title: str, keyword: str) -> bool:
+    return re.search(rf"(?<![\w-]){re.escape(keyword)}(?![\w-])", title) is not None
+
+
+@dataclass
+class DurationDefaults:
+    """Event lengths by keyword (config.event_durations over KEYWORD_MINUTES)."""
+    overrides: Dict[str, int] = field(default_factory=dict)
+
+    @classmethod
+    def from_config(cls, config) -> "DurationDefaults":
+        overrides = getattr(config, "event_durations", None) or {}
+        return cls({str(k).lower(): int(v) for k, v in overrides.items() if 0 < int(v) <= MAX_MINUTES})
+
+    def kind(self, title: str) -> str:
+        lowered = " ".join(title.lower().split())
+        for keyword in [*self.overrides, *KEYWORD_MINUTES]:
+            if _mentions(lowered, keyword):
+                return keyword
+        return lowered
+
+    def infer(self, title: str, history: Iterable[Any] = (), now: Optional[datetime] = None) -> Duration:
+        """How long an event called `title` probably takes; `history` is the calendar's events (any order)."""
+        now = now or datetime.now()
+        kind = self.kind(title)
+        lengths: List[float] = []
+        for event in sorted(history, key=lambda e: e.start_time, reverse=True):
+            if "T" not in event.start_time or datetime.fromisoformat(event.start_time) >= now:
+                continue
+            minutes = (datetime.fromisoformat(event.end_time)
+                       - datetime.fromisoformat(event.start_time)).total_seconds() / 60
+            if 0 < minutes <= MAX_MINUTES and self.kind(event.title) == kind:
+                lengths.append(minutes)
+                if len(lengths) == HISTORY_SIZE:
+                    break
+        if len(lengths) >= MIN_HISTORY:
+            average = max(ROUND_TO, round(sum(lengths) / len(lengths) / ROUND_TO) * ROUND_TO)
+            return Duration(average, kind, "history", len(lengths))
+        if kind in self.overrides:
+            return Duration(self.overrides[kind], kind, "config")
+        if kind in KEYWORD_MINUTES:
+            return Duration(KEYWORD_MINUTES[kind], kind, "keyword")
+        return Duration(DEFAULT_MINUTES, kind, "default")
+
+
+_defaults: Optional[DurationDefaults] = None
+
+
+def get_duration_defaults() -> DurationDefaults:
+    """Get the global duration defaults (KEYWORD_MINUTES only until configured)."""
+    global _defaults
+    if _defaults is None:
+        _defaults = DurationDefaults()
+    return _defaults
+
+
+def set_duration_defaults(defaults: DurationDefaults) -> None:
+    """Install the defaults built from the loaded Config (called at startup)."""
+    global _defaults
+    _defaults = defaults
@@ -0,0 +1,147 @@
+"""
+Earcons - Short sounds for when the assistant starts and stops listening.
+
+Three cues, synthesized rather than shipped as sound files:
+
+- wake: listening started (the wake word triggered / the conversation began)
+- listening_end: the user finished speaking and the assistant is on it
+- error: something went wrong
+
+Each persona picks a style in its theme.yaml:
+
+    earcons:
+      style: chime      # beep (HAL), chime (JARVIS), blip (default), none
+      volume: 0.8       # Relative to the other personas' cues
+
+Cues are mixed over whatever AudioIO is playing (audio_mixer.OutputMixer)
+at config.earcon_volume, so a chime neither waits behind nor cuts off a
+reply. VoiceBridgeOrchestrator plays them on state changes
+(cue_for_transition); config.earcons_enabled turns them off.
+"""
+
+import logging
+from typing import Dict, List, Optional, Tuple
+
+import numpy as np
+
+logger = logging.getLogger(__name__)
+
+CUES = ("wake", "listening_end", "error")
+DEFAULT_STYLE = "blip"
+
+# (frequency Hz, seconds) per note; frequency 0 is a rest
+STYLES: Dict[str, Dict[str, List[Tuple[float, float]]]] = {
+    # HAL 9000: a plain, flat console beep
+    "beep": {
+        "wake": [(880.0, 0.12)],
+        "listening_end": [(660.0, 0.10)],
+        "error": [(440.0, 0.15), (0.0, 0.05), (440.0, 0.15)],
+    },
+    # JARVIS: a two-note bell, rising to listen and falling when done
+    "chime": {
+        "wake": [(784.0, 0.10), (1175.0, 0.28)],
+        "listening_end": [(1175.0, 0.08), (784.0, 0.22)],
+        "error": [(523.0, 0.14), (415.0, 0.32)],
+    },
+    # Everyone else: a short, soft blip
+    "blip": {
+        "wake": [(1000.0, 0.07)],
+        "listening_end": [(750.0, 0.07)],
+        "error": [(300.0, 0.12), (0.0, 0.04), (300.0, 0.12)],
+    },
+    "none": {},
+}
+
+FADE_SECONDS = 0.005  # Ramps at note edges, so tones don't click
+
+
+def _note(frequency: float, seconds: float, style: str, sample_rate: int) -> np.ndarray:
+    count = max(1, int(round(seconds * sample_rate)))
+    if frequency <= 0:
+        return np.zeros(count, dtype=np.float32)
+    t = np.arange(count, dtype=np.float32) / sample_rate
+    tone = np.sin(2 * np.pi * frequency * t)
+    if style == "chime":
+        # Bell: a quieter octave partial, decaying away
+        tone = (tone + 0.35 * np.sin(4 * np.pi * frequency * t)) * np.exp(-t * 6.0 / seconds)
+    elif style == "blip":
+        tone = tone * np.exp(-t * 4.0 / seconds)
+    fade = min(count // 2, max(1, int(FADE_SECONDS * sample_rate)))
+    ramp = np.linspace(0.0, 1.0, fade, dtype=np.float32)
+    tone[:fade] *= ramp
+    tone[count - fade:] *= ramp[::-1]
+    return tone.astype(np.float32)
+
+
+def synthesize(style: str, cue: str, sample_rate: int = 24000) -> np.ndarray:
+    """A cue in a style, as float32 samples peaking at 1.0 (empty for "none")."""
+    if style not in STYLES:
+        logger.warning(f"Unknown earcon style '{style}' - using {DEFAULT_STYLE}")
+        style = DEFAULT_STYLE
+    notes = STYLES[style].get(cue, [])
+    if not notes:
+        return np.zeros(0, dtype=np.float32)
+    audio = np.concatenate([_note(frequency, seconds, style, sample_rate) for frequency, seconds in notes])
+    peak = float(np.max(np.abs(audio)))
+    return (audio / peak).astype(np.float32) if peak > 0 else audio
+
+
+def cue_for_transition(old: str, new: str) -> Optional[str]:
+    """The cue for a conversation state change ("idle" -> "listening" is wake), if any."""
+    if new == "error" and old != "error":
+        return "error"
+    if new == "listening" and old == "idle":
+        return "wake"
+    if new == "thinking" and old in ("listening", "interrupted"):
+        return "listening_end"
+    return None
+
+
+class EarconPlayer:
+    """Plays the current persona's cues through an AudioIO."""
+
+    def __init__(self, audio_io, config=None, persona=None):
+        self.audio_io = audio_io
+        self.config = config
+        self.persona = persona
+        self._cache: Dict[Tuple[str, str, int], np.ndarray] = {}
+
+    @property
+    def enabled(self) -> bool:
+        return bool(getattr(self.config, "earcons_enabled", True))
+
+    def style(self) -> str:
+        settings = getattr(self.persona, "earcons", None)
+        return getattr(settings, "style", None) or DEFAULT_STYLE
+
+    def gain(self) -> float:
+        """The persona's own cue volume (the mixer applies config.earcon_volume on top)."""
+        settings = getattr(self.persona, "earcons", None)
+        return float(getattr(settings, "volume", 1.0))
+
+    def samples(self, cue: str) -> np.ndarray:
+        rate = int(getattr(self.audio_io, "playback_rate", 24000))
+        key = (self.style(), cue, rate)
+        if key not in self._cache:
+            self._cache[key] = synthesize(key[0], cue, rate)
+        return self._cache[key]
+
+    def play(self, cue: str) -> bool:
+        """Mix a cue into playback; False when off, silent or there's nothing to play through."""
+        if cue not in CUES or not self.enabled or self.audio_io is None:
+            return False
+        samples = self.samples(cue)
+        gain = self.gain()
+        if not len(samples) or gain <= 0:
+            return False
+        try:
+            self.audio_io.play_cue(samples, gain=gain)
+        except Exception as e:
+            logger.debug(f"Could not play the {cue} earcon: {e}")
+            return False
+        return True
+
+    def on_state_change(self, old: str, new: str) -> Optional[str]:
+        """Play the cue for a state change; returns the cue played."""
+        cue = cue_for_transition(old, new)
+        return cue if cue and self.play(cue) else None
@@ -0,0 +1,293 @@
+"""
+Email Tasks - Propose appointments and reminders found in incoming email.
+
+A new email that pins something to a date ("Your flight UA 523 departs
+October 23 at 9:40 AM", "Please submit the form by March 3") becomes a
+follow-up proposal (followups.py) instead of staying buried in the inbox:
+
+- confirmations (flights, reservations, appointments, meeting invites)
+  with a time become calendar events: "Flight confirmation detected - add
+  to calendar?"; without a time, a reminder task due that day
+- requests with a deadline ("by", "before", "due") become tasks due then
+
+Dates and times go through the same parser as the calendar tools
+(dates.py), relative to the day the email arrived. When an AI client is
+available, emails the rules found something in - or that mention a date
+the rules couldn't use - are sent to it to extract the details (title,
+time, place) and drop false alarms like newsletters or receipts for past
+events, the way FollowUpDetector reviews conversation.
+
+Flight, shipping and reservation confirmations with a booking reference
+or schema.org markup are read by booking_emails.py instead: they become
+events with the details (flight number, confirmation code, terminal) in
+the description and a reminder timed for the kind of thing, and skip the
+LLM review.
+
+Nothing is added until the user confirms it in the Schedule pane (or with
+confirm_followup). Proposals matching an event or open task already in
+the planner are skipped, and an email is never proposed twice.
+"""
+
+import json
+import logging
+import re
+import uuid
+from datetime import date, datetime, timedelta
+from typing import Iterable, List, Optional, Tuple
+
+from .booking_emails import Confirmation, parse_confirmations
+from .dates import parse_natural_datetime, parse_time_expression
+from .followups import ACTION_VERBS, ProposedFollowUp
+
+logger = logging.getLogger(__name__)
+
+# Confirmation kinds: (label, what the notice calls it, pattern, default length in minutes)
+CONFIRMATIONS = [
+    ("Flight", "Flight confirmation",
+     re.compile(r"\b(?:flight|boarding pass|e-?ticket|itinerary|departs|departure)\b", re.I), 120),
+    ("Reservation", "Reservation", re.compile(r"\b(?:reservation|booking|check-?in|table for)\b", re.I), 90),
+    ("Appointment", "Appointment", re.compile(r"\b(?:appointment|your visit|consultation)\b", re.I), 60),
+    ("Meeting", "Meeting invite", re.compile(r"\b(?:invitation|invited|meeting|interview|webinar)\b", re.I), 60),
+]
+DEADLINE = re.compile(r"\b(?:by|before|no later than|due|deadline)\b", re.I)
+MAX_PER_EMAIL = 3
+PAST_DATE_DAYS = 183  # A date without a year further ahead than this is taken to be one that has passed
+
+_MONTH = (r"(?:january|february|march|april|may|june|july|august|september|october|november|december|"
+          r"jan|feb|mar|apr|jun|jul|aug|sept|sep|oct|nov|dec)\.?")
+_WEEKDAY = r"(?:monday|tuesday|wednesday|thursday|friday|saturday|sunday|mon|tues|tue|wed|thurs|thu|fri|sat|sun)\.?"
+_TIME = r"\d{1,2}(?::\d{2})?\s*[ap]\.?m\.?|\d{1,2}:\d{2}|noon"
+DATE_PATTERN = re.compile(
+    rf"\b(?:{_WEEKDAY},?\s+)?(?P<date>{_MONTH}\s+\d{{1,2}}(?:st|nd|rd|th)?(?:,?\s+\d{{4}})?|"
+    rf"\d{{1,2}}(?:st|nd|rd|th)?\s+(?:of\s+)?{_MONTH}(?:,?\s+\d{{4}})?|"
+    rf"\d{{4}}-\d{{2}}-\d{{2}}|\d{{1,2}}/\d{{1,2}}(?:/\d{{2,4}})?|today|tonight|tomorrow|"
+    rf"(?:next\s+|this\s+)?(?:monday|tuesday|wednesday|thursday|friday|saturday|sunday))\b"
+    rf"(?:,?\s*(?:at|@|from)?\s*(?P<time>{_TIME})(?!\w))?",
+    re.IGNORECASE,
+)
+TIME_PATTERN = re.compile(rf"(?<![\w:])(?P<time>{_TIME})(?!\w)", re.IGNORECASE)
+_SUBJECT_PREFIX = re.compile(r"^\s*(?:(?:re|fwd?|fw)\s*:\s*)+", re.IGNORECASE)
+_STOPWORDS = {"the", "a", "an", "your", "to", "for", "of", "on", "at", "with", "and", "in", "is", "from"}
+
+
+def _received(item) -> Optional[date]:
+    try:
+        return datetime.fromisoformat(str(item.received_at).replace("Z", "+00:00")).date()
+    except (TypeError, ValueError):
+        return None
+
+
+def _sentences(text: str) -> List[str]:
+    return [s.strip() for s in re.split(r"(?<=[.!?])\s+(?=[A-Z])|\n+", text or "") if s.strip()]
+
+
+def _label(text: str) -> Optional[Tuple[str, int]]:
+    for label, _, pattern, minutes in CONFIRMATIONS:
+        if pattern.search(text):
+            return label, minutes
+    return None
+
+
+def notice(item, proposal: ProposedFollowUp) -> str:
+    """ "Flight confirmation detected - add to calendar? ..." for the activity feed."""
+    parsed = parse_confirmations(item, _received(item) or date.today()) if proposal.detected_by == "parser" else []
+    what = next((c.notice_name for c in parsed if c.title == proposal.title), None) or next(
+        (name for _, name, pattern, _ in CONFIRMATIONS
+         if pattern.search(f"{item.subject or ''}\n{item.content or ''}")), "Deadline")
+    question = "add to calendar?" if proposal.kind == "event" else "add a reminder?"
+    return f"📧 {what} detected from {item.sender} - {question} {proposal.describe()} (confirm in Schedule)"
+
+
+def _subject(item) -> str:
+    subject = _SUBJECT_PREFIX.sub("", item.subject or "").strip(" -:")
+    return subject or f"Email from {item.sender}"
+
+
+def find_dates(sentence: str, today: date) -> List[Tuple[datetime, bool, int]]:
+    """(when, has a time, where it starts) for every date in a sentence that's today or later."""
+    found = []
+    for match in DATE_PATTERN.finditer(sentence):
+        time_text = match.group("time")
+        if not time_text:
+            nearby = TIME_PATTERN.search(sentence)  # "9:40 AM on October 23"
+            time_text = nearby.group("time") if nearby else None
+        when = parse_natural_datetime(match.group("date"), today=today)
+        if when is None or when.date() < today:
+            continue
+        if not re.search(r"\d{4}", match.group("date")) and (when.date() - today).days > PAST_DATE_DAYS:
+            continue  # "October 2" in an email on October 16 is about the past, not next year
+        time_of_day = parse_time_expression(time_text) if time_text else None
+        if time_of_day:
+            hour, minute = map(int, time_of_day.split(":"))
+            when = when.replace(hour=hour, minute=minute)
+        found.append((when, bool(time_of_day), match.start()))
+    return found
+
+
+def _deadline_title(sentence: str, item) -> str:
+    match = re.search(r"(?:please\s+)?(?P<action>\b[a-z][\w' ]*?)\s+(?:by|before|no later than)\b", sentence, re.I)
+    if match:
+        action = match.group("action").strip()
+        words = action.split()
+        if words and words[0].lower() == "please":
+            words = words[1:]
+        if words and words[0].lower() in ACTION_VERBS and len(words) >= 2:
+            return " ".join(words)[:1].upper() + " ".join(words)[1:]
+    return f"{_subject(item)} ({item.sender})"
+
+
+def _proposal(item, title: str, kind: str, confidence: float, detected_by: str = "rules", **fields) -> ProposedFollowUp:
+    return ProposedFollowUp(id=uuid.uuid4().hex[:8], title=title, source=f"Email from {item.sender}: {_subject(item)}",
+                            kind=kind, confidence=confidence, detected_by=detected_by, source_id=item.id, **fields)
+
+
+def confirmation_proposal(item, confirmation: Confirmation) -> ProposedFollowUp:
+    """An event (or, without a time, a reminder task) carrying a parsed confirmation's details."""
+    when = confirmation.start
+    if not confirmation.has_time:
+        return _proposal(item, confirmation.title, "task", 0.9, "parser", due_date=when.date().isoformat(),
+                         details=confirmation.description())
+    end = confirmation.end if confirmation.end and confirmation.end > when else (
+        when + timedelta(minutes=confirmation.minutes))
+    return _proposal(item, confirmation.title, "event", 0.95, "parser", due_date=when.date().isoformat(),
+                     start_time=when.isoformat(timespec="minutes"), end_time=end.isoformat(timespec="minutes"),
+                     location=confirmation.location or None, details=confirmation.description(),
+                     reminder_minutes=confirmation.reminder_minutes)
+
+
+def detect_email_proposals(item, today: Optional[date] = None) -> List[ProposedFollowUp]:
+    """Rule pass: appointments, reminders and deadlines in one email."""
+    if item.channel != "email":
+        return []
+    today = today or _received(item) or date.today()
+    parsed = parse_confirmations(item, today)
+    if parsed:
+        return [confirmation_proposal(item, confirmation) for confirmation in parsed]
+    text = f"{item.subject or ''}\n{item.content or ''}"
+    confirmation = _label(text)
+    proposals: List[ProposedFollowUp] = []
+    seen = set()
+    for sentence in _sentences(text):
+        dates = find_dates(sentence, today)
+        if not dates:
+            continue
+        when, has_time, start = dates[0]
+        if DEADLINE.search(sentence[:start]):
+            proposal = _proposal(item, _deadline_title(sentence, item), "task", 0.7, due_date=when.date().isoformat())
+        elif confirmation and has_time:
+            label, minutes = confirmation
+            subject = _subject(item)
+            title = subject if label.lower() in subject.lower() else f"{label}: {subject}"
+            proposal = _proposal(item, title, "event", 0.8, due_date=when.date().isoformat(),
+                                 start_time=when.isoformat(timespec="minutes"),
+                                 end_time=(when + timedelta(minutes=minutes)).isoformat(timespec="minutes"))
+        elif confirmation:
+            label, _ = confirmation
+            subject = _subject(item)
+            title = subject if label.lower() in subject.lower() else f"{label}: {subject}"
+            proposal = _proposal(item, title, "task", 0.6, due_date=when.date().isoformat())
+        else:
+            continue
+        key = (proposal.title.lower(), proposal.due_date)
+        if key in seen:
+            continue
+        seen.add(key)
+        proposals.append(proposal)
+        if len(proposals) >= MAX_PER_EMAIL:
+            break
+    return proposals
+
+
+def _words(text: str) -> set:
+    return {w for w in re.findall(r"[a-z0-9]+", (text or "").lower()) if w not in _STOPWORDS}
+
+
+def _similar(a: str, b: str) -> bool:
+    first, second = _words(a), _words(b)
+    if not first or not second:
+        return False
+    return len(first & second) / min(len(first), len(second)) >= 0.5
+
+
+def is_duplicate(proposal: ProposedFollowUp, planner) -> bool:
+    """True when the planner already has this event (same day, same start or a similar title) or open task."""
+    if not proposal.due_date:
+        return False
+    if proposal.kind == "event":
+        for event in planner.get_calendar_events(start_date=proposal.due_date, end_date=proposal.due_date):
+            if event.start_time[:16] == (proposal.start_time or "")[:16] or _similar(event.title, proposal.title):
+                return True
+    for task in planner.get_tasks():
+        if task.status != "complete" and task.due_date == proposal.due_date and _similar(task.title, proposal.title):
+            return True
+    return False
+
+
+class EmailTaskDetector:
+    """Rule + LLM detection of appointments and deadlines in new email."""
+
+    def __init__(self, ai_client=None):
+        self.ai = ai_client
+
+    async def detect(self, item, planner=None, today: Optional[date] = None) -> List[ProposedFollowUp]:
+        """Proposals for one email, minus ones already in `planner`."""
+        if item.channel != "email":
+            return []
+        today = today or _received(item) or date.today()
+        proposals = detect_email_proposals(item, today)
+        mentions_date = bool(DATE_PATTERN.search(f"{item.subject or ''} {item.content or ''}"))
+        parsed = any(p.detected_by == "parser" for p in proposals)
+        if self.ai is not None and self.ai.is_available() and not parsed and (proposals or mentions_date):
+            try:
+                proposals = await self._review(item, today)
+            except Exception as e:
+                logger.debug(f"Email task LLM review failed, using rules only: {e}")
+        if planner is not None:
+            proposals = [p for p in proposals if not is_duplicate(p, planner)]
+        return proposals
+
+    async def _review(self, item, today: date) -> List[ProposedFollowUp]:
+        messages = [
+            {
+                "role": "system",
+                "content": (
+                    "You find things in an email the recipient should put on their calendar or to-do list: "
+                    "confirmed flights, reservations, appointments and meetings, and things they are asked to do "
+                    "by a deadline. Ignore marketing, newsletters, and anything already in the past. "
+                    f"The email arrived on {today.strftime('%A %Y-%m-%d')}. "
+                    'Respond with only a JSON array of objects: {"kind": "event" or "task", "title": short title, '
+                    '"date": YYYY-MM-DD, "time": HH:MM (24h) or null, "minutes": length or null, '
+                    '"location": place or null}. Return [] if there are none.'
+                ),
+            },
+            {
+                "role": "user",
+                "content": f"From: {item.sender}\nSubject: {item.subject or ''}\n\n{(item.content or '')[:4000]}",
+            },
+        ]
+        response = await self.ai.chat(messages, max_tokens=500)
+        match = re.search(r"\[.*\]", response, re.DOTALL)
+        if not match:
+            raise ValueError(f"No JSON array in email task review: {response[:100]}")
+        return list(self._parse_review(json.loads(match.group(0)), item, today))
+
+    @staticmethod
+    def _parse_review(items: Iterable[dict], item, today: date) -> Iterable[ProposedFollowUp]:
+        for entry in items:
+            title = (entry.get("title") or "").strip()
+            try:
+                day = date.fromisoformat(str(entry.get("date")))
+            except ValueError:
+                continue
+            if not title or day < today:
+                continue
+            time_of_day = parse_time_expression(str(entry.get("time") or ""))
+            if entry.get("kind") == "event" and time_of_day:
+                start = datetime.combine(day, datetime.strptime(time_of_day, "%H:%M").time())
+                minutes = int(entry.get("minutes") or 60)
+                yield _proposal(item, title, "event", 0.9, "llm", due_date=day.isoformat(),
+                                start_time=start.isoformat(timespec="minutes"),
+                                end_time=(start + timedelta(minutes=minutes)).isoformat(timespec="minutes"),
+                                location=entry.get("location") or None)
+            else:
+                yield _proposal(item, title, "task", 0.9, "llm", due_date=day.isoformat())
@@ -0,0 +1,306 @@
+"""
+Emergency - A spoken phrase that texts and calls the user's emergency contacts.
+
+With config.emergency_phrase (e.g. "red alert red alert") and
+config.emergency_contacts set, saying the phrase raises an alert through the
+server: every contact gets a text and/or a call (config.emergency_methods)
+saying who needs help, when, and where (config.emergency_location), and the
+rounds repeat every config.emergency_repeat_minutes until one of them
+answers (replies OK, or presses 1 on the call).
+
+A false alarm scares people, so the phrase is guarded:
+- only the phrase on its own counts, spoken or typed ("red alert red alert",
+  or after the wake word), never a sentence mentioning it ("what's my
+  emergency phrase?", "if I say red alert...")
+- it must be at least MIN_PHRASE_WORDS words, not all the same word
+  (validate_phrase)
+- it starts a countdown (config.emergency_confirm_seconds) said out loud
+  whatever quiet hours or do not disturb say: "cancel", "stop" or "false
+  alarm" ends it with nobody contacted, "send it now" skips the wait.
+  Silence sends: someone who can't answer still gets help.
+
+The server only texts and calls contacts it has saved for the signed-in
+user, so the contacts are saved there (EMERGENCY_CONTACTS) before the first
+alert. Once sent, "I'm okay" or "cancel the alert" stands it down and texts the
+contacts an all-clear. An active alert is kept in ~/.xswarm/emergency.json,
+so a restart carries on repeating it.
+"""
+
+import json
+import logging
+import re
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional
+
+from . import endpoints
+from .capabilities import register_capability
+from .events import record_event
+
+logger = logging.getLogger(__name__)
+
+METHODS = ("sms", "call")
+MIN_PHRASE_WORDS = 3
+STATUS_SECONDS = 30  # How often an active alert is checked for an answer
+RETRY_SECONDS = 15  # After failing to reach the server
+
+_PHONE = re.compile(r"^\+\d{7,15}$")
+_CANCEL = re.compile(
+    r"\b(?:cancel|stop|abort|false\s+alarm|never\s*mind|don'?t\s+send|no\s+no|"
+    r"i'?m\s+(?:ok(?:ay)?|fine|alright|all\s+right))\b", re.IGNORECASE)
+_SEND_NOW = re.compile(r"^\W*(?:send\s+(?:it\s+)?now|send\s+it|do\s+it\s+now|hurry|yes\s+send)\W*$", re.IGNORECASE)
+_STAND_DOWN = re.compile(
+    r"\b(?:i'?m\s+(?:ok(?:ay)?|fine|alright|all\s+right|safe)|false\s+alarm|all\s+clear|"
+    r"(?:cancel|stop)\s+(?:the\s+)?(?:emergency|alert|alarm))\b", re.IGNORECASE)
+
+
+class EmergencyError(Exception):
+    """The emergency settings can't be used."""
+
+
+def normalize(text: str) -> str:
+    """Lowercase words only: "Red alert, RED ALERT!" -> "red alert red alert"."""
+    return " ".join(re.sub(r"[^\w\s']", " ", (text or "").lower()).split())
+
+
+def validate_phrase(phrase: str) -> Optional[str]:
+    """Why a phrase is too easy to say by accident, or None."""
+    words = normalize(phrase).split()
+    if len(words) < MIN_PHRASE_WORDS:
+        return f"the emergency phrase needs at least {MIN_PHRASE_WORDS} words"
+    if len(set(words)) < 2:
+        return "the emergency phrase can't be one word repeated"
+    return None
+
+
+@dataclass
+class EmergencyContact:
+    name: str
+    phone: str  # E.164, e.g. +15551234567
+
+
+@dataclass
+class EmergencySettings:
+    phrase: str = ""
+    contacts: List[EmergencyContact] = field(default_factory=list)
+    methods: List[str] = field(default_factory=lambda: list(METHODS))
+    confirm_seconds: int = 10
+    repeat_minutes: float = 5.0
+    location: str = ""
+    user_name: str = ""
+
+    @classmethod
+    def from_config(cls, config) -> Optional["EmergencySettings"]:
+        """None when no phrase is set (the default); EmergencyError when it's set but unusable."""
+        phrase = normalize(getattr(config, "emergency_phrase", "") or "")
+        if not phrase:
+            return None
+        problem = validate_phrase(phrase)
+        if problem:
+            raise EmergencyError(problem)
+        contacts = []
+        for raw in getattr(config, "emergency_contacts", None) or []:
+            phone = re.sub(r"[^\d+]", "", str(raw.get("phone", "")))
+            if not _PHONE.match(phone):
+                raise EmergencyError(f"Emergency contact {raw.get('name') or raw}: the phone number must look "
+                                     "like +15551234567")
+            contacts.append(EmergencyContact(str(raw.get("name") or phone), phone))
+        if not contacts:
+            raise EmergencyError("emergency_phrase is set but emergency_contacts is empty")
+        methods = list(getattr(config, "emergency_methods", None) or METHODS)
+        unknown = [m for m in methods if m not in METHODS]
+        if unknown or not methods:
+            raise EmergencyError(f"Unknown emergency method '{unknown[0] if unknown else ''}' "
+                                 f"(use {' and/or '.join(METHODS)})")
+        return cls(phrase, contacts, methods, max(0, int(getattr(config, "emergency_confirm_seconds", 10))),
+                   max(1.0, float(getattr(config, "emergency_repeat_minutes", 5.0))),
+                   getattr(config, "emergency_location", "") or "", getattr(config, "user_name", None) or "")
+
+    def matches(self, text: str, wake_words: Iterable[str] = ()) -> bool:
+        """The phrase on its own (said once or more, after a wake word or not)."""
+        said = normalize(text)
+        for wake in sorted((normalize(w) for w in wake_words), key=len, reverse=True):
+            if wake and said.startswith(wake + " "):
+                said = said[len(wake) + 1:]
+                break
+        repeats = said.count(self.phrase)
+        return repeats > 0 and said == " ".join([self.phrase] * repeats)
+
+    def names(self) -> str:
+        names = [c.name for c in self.contacts]
+        return names[0] if len(names) == 1 else ", ".join(names[:-1]) + " and " + names[-1]
+
+    def message(self, now: datetime) -> str:
+        """What the contacts are sent: who, when and where."""
+        who = self.user_name or "Your contact"
+        text = f"EMERGENCY: {who} said their emergency phrase at {now:%H:%M} on {now:%A} {now.day} {now:%B}."
+        if self.location:
+            from .geocoding import map_link
+            text += f" Location: {self.location} {map_link(self.location)}"
+        return text
+
+
+class Emergency:
+    """
+    The emergency flow: idle -> confirming (countdown) -> active (rounds
+    repeating) -> idle. heard() takes the user's words, tick() (every couple
+    of seconds) sends, repeats and checks for answers. Both return what to
+    tell the user.
+    """
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "emergency.json"
+
+    def __init__(self, settings: EmergencySettings, config=None, client=None, path: Optional[Path] = None):
+        self.settings = settings
+        self.config = config
+        self.client = client
+        self.path = Path(path) if path else self.DEFAULT_PATH
+        self.state = "idle"
+        self.deadline: Optional[datetime] = None
+        self.alert_id: Optional[str] = None
+        self.next_round: Optional[datetime] = None
+        self.next_check: Optional[datetime] = None
+        self.contacts_saved = False  # On the server, which only alerts saved contacts
+        self._resume()
+
+    def _resume(self) -> None:
+        try:
+            saved = json.loads(self.path.read_text(encoding="utf-8"))
+        except (OSError, ValueError):
+            return
+        if saved.get("alert_id"):
+            self.state, self.alert_id = "active", saved["alert_id"]
+            self.next_round = datetime.fromisoformat(saved.get("next_round") or datetime.min.isoformat())
+            self.next_check = datetime.min  # Check for an answer straight away
+
+    def _save(self) -> None:
+        try:
+            if self.state == "active":
+                self.path.parent.mkdir(parents=True, exist_ok=True)
+                self.path.write_text(json.dumps({"alert_id": self.alert_id, "next_round": self.next_round.isoformat()}),
+                                     encoding="utf-8")
+            elif self.path.exists():
+                self.path.unlink()
+        except OSError as e:
+            logger.warning(f"Could not save the emergency alert state: {e}")
+
+    def wants(self, text: str, wake_words: Iterable[str] = ()) -> bool:
+        """Whether heard() should take `text` instead of the rest of the assistant."""
+        if self.state == "confirming":
+            return bool(_CANCEL.search(text) or _SEND_NOW.match(text) or self.settings.matches(text, wake_words))
+        if self.state == "active" and _STAND_DOWN.search(text):
+            return True
+        return self.settings.matches(text, wake_words)
+
+    async def heard(self, text: str, now: datetime, wake_words: Iterable[str] = ()) -> Optional[str]:
+        """The reply to something the user said or typed, or None when it isn't for the emergency flow."""
+        if self.state == "confirming":
+            if _CANCEL.search(text):
+                self.state, self.deadline = "idle", None
+                record_event("activity", "Emergency countdown cancelled", {"level": "warning"})
+                return "Cancelled. Nobody was contacted."
+            if _SEND_NOW.match(text) or self.settings.matches(text, wake_words):
+                self.deadline = now
+                return "Sending now."
+            return None
+        if self.state == "active" and _STAND_DOWN.search(text):
+            return await self._stand_down()
+        if not self.settings.matches(text, wake_words):
+            return None
+        if self.state == "active":
+            return f"The alert is still going out to {self.settings.names()}."
+        self.state, self.deadline = "confirming", now + timedelta(seconds=self.settings.confirm_seconds)
+        how = {("sms",): "Texting", ("call",): "Calling"}.get(tuple(self.settings.methods), "Texting and calling")
+        return (f"Emergency. {how} {self.settings.names()} in {self.settings.confirm_seconds} seconds. "
+                "Say cancel to stop.")
+
+    async def tick(self, now: datetime) -> List[str]:
+        """Send when the countdown runs out, then repeat and watch for an answer."""
+        if self.state == "confirming" and now >= self.deadline:
+            return [await self._raise(now)]
+        if self.state != "active" or now < self.next_check:
+            return []
+        self.next_check = now + timedelta(seconds=STATUS_SECONDS)
+        try:
+            if now >= self.next_round:
+                body = await self._call(endpoints.EMERGENCY_REPEAT(alert_id=self.alert_id), json={})
+                if body.get("results"):
+                    self.next_round = now + timedelta(minutes=self.settings.repeat_minutes)
+                    self._save()
+                    return [f"Sent the emergency alert to {self.settings.names()} again "
+                            f"(round {body['alert'].get('rounds')})"]
+            else:
+                body = await self._call(endpoints.EMERGENCY_ALERT(alert_id=self.alert_id))
+        except Exception as e:
+            logger.warning(f"Emergency alert check failed: {e}")
+            self.next_check = now + timedelta(seconds=RETRY_SECONDS)
+            return []
+        alert = body.get("alert", {})
+        if alert.get("status") == "acknowledged":
+            self._finish()
+            who = alert.get("acknowledged_by") or "A contact"
+            record_event("activity", f"Emergency alert answered by {who}", {"level": "success"})
+            return [f"{who} got your emergency alert and is on it."]
+        if alert.get("status") == "cancelled":
+            self._finish()
+        return []
+
+    async def _raise(self, now: datetime) -> str:
+        settings = self.settings
+        contacts = [{"name": c.name, "phone": c.phone} for c in settings.contacts]
+        payload = {"message": settings.message(now), "methods": settings.methods, "contacts": contacts}
+        try:
+            if not self.contacts_saved:
+                await self._call(endpoints.EMERGENCY_CONTACTS(), json={"contacts": contacts})
+                self.contacts_saved = True
+            body = await self._call(endpoints.EMERGENCY_ALERTS(), json=payload)
+        except Exception as e:
+            logger.error(f"Emergency alert not sent: {e}")
+            self.deadline = now + timedelta(seconds=RETRY_SECONDS)  # Still confirming: tried again shortly
+            return f"I couldn't send the emergency alert ({e}). Trying again in {RETRY_SECONDS} seconds."
+        self.state, self.alert_id = "active", body["alert"]["id"]
+        self.next_round = now + timedelta(minutes=settings.repeat_minutes)
+        self.next_check = now + timedelta(seconds=STATUS_SECONDS)
+        self._save()
+        failed = [r.get("name") or r.get("phone") for r in body.get("results", [])
+                  if all(r.get(m) != "sent" for m in settings.methods)]
+        record_event("activity", f"Emergency alert sent to {settings.names()}", {"level": "warning"})
+        note = f" Couldn't reach {', '.join(failed)}." if failed else ""
+        return (f"Emergency alert sent to {settings.names()}.{note} I'll keep trying every "
+                f"{settings.repeat_minutes:g} minutes until one of them answers. Say \"I'm okay\" to call it off.")
+
+    async def _stand_down(self) -> str:
+        who = self.settings.user_name or "Your contact"
+        try:
+            await self._call(endpoints.EMERGENCY_CANCEL(alert_id=self.alert_id), json={
+                "message": f"All clear: {who} says they're okay. Sorry for the worry."})
+        except Exception as e:
+            logger.warning(f"Could not cancel the emergency alert: {e}")
+            return (f"I couldn't reach the server to call off the alert ({e}). "
+                    f"Please tell {self.settings.names()} yourself.")
+        self._finish()
+        record_event("activity", "Emergency alert called off", {"level": "warning"})
+        return f"Okay. I've called off the alert and told {self.settings.names()} you're okay."
+
+    def _finish(self) -> None:
+        self.state, self.alert_id, self.deadline = "idle", None, None
+        self._save()
+
+    async def _call(self, route, **kwargs) -> Dict[str, Any]:
+        from .api_client import ApiClient, ApiPolicy
+
+        client = self.client or ApiClient(getattr(self.config, "server_url", "http://localhost:3000"),
+                                          getattr(self.config, "api_token", None),
+                                          policy=ApiPolicy.from_config(self.config))
+        try:
+            return (await client.call(route, **kwargs)).json()
+        finally:
+            if client is not self.client:
+                await client.close()
+
+
+register_capability("Emergency alert", "Say your emergency phrase on its own and I text and call your "
+                    "emergency contacts after a short countdown", ["cancel", "I'm okay"],
+                    requires=("emergency_phrase", "emergency_contacts"), category="Safety",
+                    keywords=("sos", "help", "emergency", "contacts"))
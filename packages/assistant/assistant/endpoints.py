@@ -0,0 +1,127 @@
+"""
+Endpoints - The xSwarm server routes the assistant calls, in one place.
+
+Each Endpoint is a method and a path template with {placeholders}:
+
+    INBOX_REPLY = Endpoint("POST", "/api/inbox/{item_id}/reply")
+
+Calling it with the path parameters gives a Route, which ApiClient.call()
+sends:
+
+    await client.call(INBOX_REPLY(item_id=item.id), json={"text": text})
+
+Parameters are percent-encoded (an id with "/", "?" or a space stays one
+path segment), a missing or unexpected one raises TypeError before anything
+is sent, and the route's key ("POST /api/inbox/:item_id/reply") names the
+circuit breaker, so ids never leak into breaker names.
+
+Every endpoint here must match a route in packages/server/src/index.js;
+tests/assistant/test_endpoints.py checks that.
+"""
+
+import re
+from dataclasses import dataclass
+from typing import Optional, Tuple
+from urllib.parse import quote
+
+_PLACEHOLDER = re.compile(r"\{(\w+)\}")
+
+
+@dataclass(frozen=True)
+class Route:
+    """An endpoint with its parameters filled in."""
+    method: str
+    path: str
+    key: str  # Circuit breaker / error name, e.g. "POST /api/inbox/:item_id/reply"
+    idempotent: Optional[bool] = None  # None: by method (see api_client.IDEMPOTENT_METHODS)
+
+
+@dataclass(frozen=True)
+class Endpoint:
+    method: str
+    template: str
+    idempotent: Optional[bool] = None  # True for POSTs the server dedupes, so they can be retried
+
+    @property
+    def params(self) -> Tuple[str, ...]:
+        return tuple(_PLACEHOLDER.findall(self.template))
+
+    @property
+    def key(self) -> str:
+        return self.method + " " + _PLACEHOLDER.sub(lambda m: ":" + m.group(1), self.template)
+
+    def path(self, **params) -> str:
+        """The template with each parameter percent-encoded; TypeError for missing or unknown ones."""
+        missing = [name for name in self.params if params.get(name) in (None, "")]
+        unknown = sorted(set(params) - set(self.params))
+        if missing or unknown:
+            problems = [f"missing {', '.join(missing)}"] if missing else []
+            problems += [f"unexpected {', '.join(unknown)}"] if unknown else []
+            raise TypeError(f"{self.key}: {'; '.join(problems)}")
+        return _PLACEHOLDER.sub(lambda m: quote(str(params[m.group(1)]), safe=""), self.template)
+
+    def __call__(self, **params) -> Route:
+        return Route(self.method, self.path(**params), self.key, self.idempotent)
+
+
+# Calendar (scheduler_client.py, calendar_live.py)
+CALENDAR_TODAY = Endpoint("GET", "/api/calendar/today")
+CALENDAR_CHANGES = Endpoint("GET", "/api/calendar/changes")
+CALENDAR_SUBSCRIBE = Endpoint("GET", "/api/calendar/subscribe")
+APPOINTMENTS = Endpoint("GET", "/api/calendar/appointments")
+APPOINTMENTS_BATCH = Endpoint("POST", "/api/calendar/appointments/batch")
+APPOINTMENTS_BATCH_DELETE = Endpoint("POST", "/api/calendar/appointments/batch-delete")
+APPOINTMENT_PARTICIPANTS = Endpoint("GET", "/api/calendar/appointments/{appointment_id}/participants")
+APPOINTMENT_INVITATIONS = Endpoint("POST", "/api/calendar/appointments/{appointment_id}/invitations")
+REMINDERS = Endpoint("POST", "/api/calendar/reminders")
+REMINDERS_BATCH = Endpoint("PUT", "/api/calendar/reminders/batch")
+
+# Calendar feed for calendar apps (ics_feed.py)
+ICS_FEED_PUBLISH = Endpoint("PUT", "/api/calendar/feed")
+ICS_FEED_DELETE = Endpoint("DELETE", "/api/calendar/feed")
+
+# Remote commands (remote_commands.py)
+COMMANDS = Endpoint("GET", "/api/commands")
+COMMAND_ACK = Endpoint("POST", "/api/commands/{command_id}/ack", idempotent=True)
+
+# Inbox (inbox.py)
+INBOX = Endpoint("GET", "/api/inbox")
+INBOX_ITEM = Endpoint("PUT", "/api/inbox/{item_id}")
+INBOX_DRAFT = Endpoint("POST", "/api/inbox/{item_id}/draft")
+INBOX_REPLY = Endpoint("POST", "/api/inbox/{item_id}/reply")
+
+# Lists (lists.py)
+LISTS = Endpoint("GET", "/api/lists")
+LIST_ITEMS = Endpoint("PUT", "/api/lists/items")
+
+# Emergency alerts (emergency.py)
+EMERGENCY_CONTACTS = Endpoint("PUT", "/api/emergency/contacts")
+EMERGENCY_ALERTS = Endpoint("POST", "/api/emergency/alerts")
+EMERGENCY_ALERT = Endpoint("GET", "/api/emergency/alerts/{alert_id}")
+EMERGENCY_REPEAT = Endpoint("POST", "/api/emergency/alerts/{alert_id}/repeat")
+EMERGENCY_CANCEL = Endpoint("POST", "/api/emergency/alerts/{alert_id}/cancel")
+
+# Calls (call_screening.py)
+CALL_SCREENING = Endpoint("GET", "/api/calls/screening")
+CALL_SCREEN = Endpoint("POST", "/api/calls/{call_sid}/screen")
+VOICEMAIL = Endpoint("GET", "/api/voicemail")
+
+# Account (quota.py)
+IDENTITY = Endpoint("GET", "/api/identity")
+USAGE = Endpoint("POST", "/api/usage")
+
+# Crash reports, sent only with config.crash_upload (crash_report.py)
+CRASH_REPORTS = Endpoint("POST", "/api/crash-reports", idempotent=True)
+
+# Encrypted memory sync between the user's machines (memory_sync.py)
+SYNC_PUSH = Endpoint("POST", "/api/sync/blobs")
+SYNC_PULL = Endpoint("GET", "/api/sync/blobs")
+
+# Feature flags (flags.py)
+FLAGS = Endpoint("GET", "/api/flags")
+
+# Releases (self_update.py)
+RELEASE_LATEST = Endpoint("GET", "/api/releases/latest")
+RELEASE_ASSET = Endpoint("GET", "/api/releases/{version}/{platform}")
+
+ALL = tuple(value for value in list(globals().values()) if isinstance(value, Endpoint))
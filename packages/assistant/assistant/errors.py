@@ -0,0 +1,52 @@
+"""
+Errors - What the assistant's core raises, for code using it as a library.
+
+Config, the scheduler, memory and voice used to raise bare ValueError and
+RuntimeError (or log and carry on), so a script built on the package
+(xswarm_client.core, a notebook) couldn't tell a bad config file from a
+busy voice server without matching message text. Each now raises its own
+error, all under XswarmError:
+
+    XswarmError
+    ├── ConfigError        config files, settings (voice_config.SettingError), model specs
+    ├── SchedulerError     cron expressions and job definitions
+    │   └── UnknownJobError  no job by that name (also a KeyError)
+    ├── MemoryStoreError   the memory database can't be opened
+    ├── VoiceError         the voice pipeline and the AI client behind it
+    └── migrations.SchemaTooNew / MigrationFailed
+
+(MemoryStoreError because Python's own MemoryError is taken.) Each also
+subclasses the built-in error it replaces, so `except ValueError` around
+a cron expression keeps working. All are re-exported from the package
+(`from assistant import ConfigError`).
+
+The application itself (main.py, the dashboard) still catches broadly and
+reports what went wrong - these are for callers that want to decide.
+"""
+
+
+class XswarmError(Exception):
+    """Anything the assistant's core raises on purpose."""
+
+
+class ConfigError(XswarmError, ValueError):
+    """A config file that can't be read or written, or a setting it can't take."""
+
+
+class SchedulerError(XswarmError, ValueError):
+    """A job or cron expression the scheduler can't use."""
+
+
+class UnknownJobError(SchedulerError, KeyError):
+    """No job registered under that name."""
+
+    def __str__(self) -> str:
+        return f"Unknown job '{self.args[0]}'" if self.args else "Unknown job"
+
+
+class MemoryStoreError(XswarmError, RuntimeError):
+    """The memory database isn't available (libsql missing, or the file can't be opened)."""
+
+
+class VoiceError(XswarmError, RuntimeError):
+    """The voice pipeline or its AI client isn't ready, or lost the microphone."""
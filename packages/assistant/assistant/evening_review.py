@@ -0,0 +1,287 @@
+"""
+Evening Review - End-of-day review and tomorrow planning as a conversation.
+
+Flow:
+1. Review: what got done today (tasks, habits) and what is still open.
+2. Carry over: the user says which open tasks move to tomorrow ("all",
+   "none", "the report and the slides"); the rest lose today's date and
+   stay in Next Actions.
+3. Plan: time blocks for tomorrow are proposed in the free slots between
+   meetings and habits. The user keeps them, skips them, or moves one
+   ("report at two"). Kept blocks are pinned (PlannerData.set_day_plan),
+   so tomorrow's auto-scheduling leaves them where they are.
+4. Journal: a short note on the day - the user's words plus what got done
+   - is recorded (PlannerData.add_journal_entry) and read back in the next
+   morning briefing.
+
+"cancel" ends the review at any step; changes already made are kept.
+
+Like reply_drafts.ReplyWorkflow this is transport-agnostic: the dashboard
+feeds it utterances (typed or transcribed) and speaks/displays what it
+returns. It starts from the evening_review job at config.evening_review_time
+or when the user asks ("let's do the evening review").
+"""
+
+import logging
+import re
+from datetime import date, datetime, timedelta
+from typing import Iterable, List, Optional, Tuple
+
+from .capabilities import register_capability
+from .dates import parse_time_expression
+from .planner import PlannerData, Task, TimeBlock
+from .verbalize import join_words
+
+logger = logging.getLogger(__name__)
+
+_REVIEW_INTENT = re.compile(
+    r"^\s*(?:(?:let'?s|can\s+we|please|start)\s+)?(?:do\s+)?(?:the\s+|my\s+|an\s+)?"
+    r"(?:evening\s+review|end[\s-]of[\s-](?:the[\s-])?day(?:\s+review)?|review\s+(?:my|the)\s+day|"
+    r"plan(?:\s+for)?\s+tomorrow)(?:\s+please)?\s*[.!?]*\s*$",
+    re.IGNORECASE,
+)
+
+CANCEL_PHRASES = ("cancel", "stop", "never mind", "nevermind", "forget it", "not now", "later")
+ALL_PHRASES = ("all", "all of them", "everything", "yes", "yes all", "all of it", "carry them all over")
+NONE_PHRASES = ("none", "no", "nothing", "none of them", "no thanks", "drop them", "leave them")
+KEEP_PHRASES = ("yes", "yeah", "keep it", "keep them", "sounds good", "looks good", "ok", "okay",
+                "perfect", "great", "that works", "do it", "save it")
+SKIP_PHRASES = ("no", "skip", "skip it", "no thanks", "don't", "no plan", "nope", "nothing", "no note")
+ORDINALS = {"first": 0, "second": 1, "third": 2, "fourth": 3, "fifth": 4, "sixth": 5, "last": -1}
+FILLER_WORDS = {"the", "and", "for", "with", "to", "a", "an", "at", "on", "of", "my", "move", "put",
+                "carry", "over", "keep", "them", "that", "this", "one", "ones", "task", "please", "just"}
+
+# Reserved like PlannerData.auto_schedule_tasks does, so plans agree with tomorrow's schedule
+HABIT_BLOCKS = {"morning": (480, 540), "afternoon": (840, 900), "evening": (1140, 1200)}
+
+MAX_REVIEW_ITEMS = 8  # Open tasks read out
+MAX_BLOCKS = 5  # Time blocks proposed for tomorrow
+MIN_BLOCK_MINUTES = 15
+
+
+def is_review_request(text: str) -> bool:
+    """True for short requests like "let's do the evening review" or "plan tomorrow"."""
+    return bool(_REVIEW_INTENT.match(text or ""))
+
+
+def _normalize(text: str) -> str:
+    text = re.sub(r"[^\w\s':]", " ", text.lower())
+    return " ".join(text.split())
+
+
+def _words(text: str) -> set:
+    return {w for w in _normalize(text).split() if len(w) >= 3 and w not in FILLER_WORDS}
+
+
+def _minutes(hhmm: str) -> int:
+    hours, minutes = hhmm.split(":")
+    return int(hours) * 60 + int(minutes)
+
+
+def _hhmm(minutes: int) -> str:
+    return f"{minutes // 60:02d}:{minutes % 60:02d}"
+
+
+def match_items(text: str, titles: List[str]) -> List[int]:
+    """
+    Indexes of the titles an utterance names, by significant words ("the
+    report and the slides") or position ("the first and the last").
+    """
+    said = _normalize(text).split()
+    picked = {ORDINALS[w] % len(titles) for w in said if w in ORDINALS and titles}
+    words = _words(text)
+    picked |= {i for i, title in enumerate(titles) if _words(title) & words}
+    return sorted(picked)
+
+
+def free_slots(planner: PlannerData, day: date, work_start: str, work_end: str) -> List[Tuple[int, int]]:
+    """Gaps (minutes since midnight) in the working day around events and habit times."""
+    busy = []
+    for event in planner.get_calendar_events(day.isoformat(), day.isoformat()):
+        if "T" not in event.start_time:
+            continue  # All-day events don't block time
+        start, end = datetime.fromisoformat(event.start_time), datetime.fromisoformat(event.end_time)
+        busy.append((start.hour * 60 + start.minute, end.hour * 60 + end.minute if end.date() == day else 24 * 60))
+    for habit in planner.get_habits():
+        due = habit.frequency in ("daily", "weekly") or (habit.frequency == "weekdays" and day.weekday() < 5)
+        if due and habit.preferred_time in HABIT_BLOCKS:
+            busy.append(HABIT_BLOCKS[habit.preferred_time])
+
+    slots, current = [], _minutes(work_start)
+    for start, end in sorted(busy):
+        if start - current >= MIN_BLOCK_MINUTES:
+            slots.append((current, min(start, _minutes(work_end))))
+        current = max(current, end)
+    if _minutes(work_end) - current >= MIN_BLOCK_MINUTES:
+        slots.append((current, _minutes(work_end)))
+    return [(start, end) for start, end in slots if end - start >= MIN_BLOCK_MINUTES]
+
+
+def propose_blocks(tasks: Iterable[Task], slots: List[Tuple[int, int]], limit: int = MAX_BLOCKS) -> List[TimeBlock]:
+    """First-fit the tasks, in order, into the free slots."""
+    used = [start for start, _ in slots]
+    blocks = []
+    for task in tasks:
+        duration = task.duration_min or 30
+        for i, (_, end) in enumerate(slots):
+            if used[i] + duration <= end:
+                blocks.append(TimeBlock(task_id=task.id, title=task.title, start=_hhmm(used[i]), minutes=duration))
+                used[i] += duration
+                break
+        if len(blocks) >= limit:
+            break
+    return sorted(blocks, key=lambda b: b.start)
+
+
+class EveningReview:
+    """Drives one evening review from user utterances."""
+
+    def __init__(self, planner: PlannerData, now: Optional[datetime] = None,
+                 work_start: str = "09:00", work_end: str = "18:00"):
+        self.planner = planner
+        self.now = now or datetime.now()
+        self.today = self.now.date()
+        self.tomorrow = self.today + timedelta(days=1)
+        self.work_start = work_start
+        self.work_end = work_end
+        self.state = "idle"  # idle, carry_over, plan, journal, done, cancelled
+        self.done_titles: List[str] = []
+        self.open_tasks: List[Task] = []
+        self.carried: List[Task] = []
+        self.blocks: List[TimeBlock] = []
+        self.plan_saved = False
+
+    @property
+    def is_active(self) -> bool:
+        return self.state in ("carry_over", "plan", "journal")
+
+    # --------------------------------------------------------------------------
+    # Steps
+    # --------------------------------------------------------------------------
+
+    def start(self) -> str:
+        """Begin the review. Returns the text to speak/display."""
+        today = self.today.isoformat()
+        tasks = self.planner.get_tasks()
+        done = [t.title for t in tasks if t.status == "complete" and (t.completed_at or "")[:10] == today]
+        habits = [h.name for h in self.planner.get_habits() if h.last_completed == today]
+        self.done_titles = done + habits
+        self.open_tasks = [
+            t for t in tasks
+            if t.status not in ("complete", "someday")
+            and (t.status == "scheduled" or (t.due_date and t.due_date <= today))
+        ][:MAX_REVIEW_ITEMS]
+
+        if self.done_titles:
+            opening = f"Today you got through {len(self.done_titles)}: {join_words(self.done_titles)}."
+        else:
+            opening = "Nothing got checked off today - that happens."
+        if not self.open_tasks:
+            return f"{opening} Nothing is left open. {self._start_plan()}"
+
+        self.state = "carry_over"
+        return (f"{opening} Still open: {join_words([t.title for t in self.open_tasks])}. "
+                f"{self._carry_prompt()}")
+
+    def handle(self, utterance: str) -> str:
+        """Handle a user utterance while the review is active."""
+        if not self.is_active:
+            return ""
+        normalized = _normalize(utterance)
+        if normalized in CANCEL_PHRASES:
+            self.state = "cancelled"
+            return "Okay, stopping the review here."
+        if self.state == "carry_over":
+            return self._handle_carry_over(normalized)
+        if self.state == "plan":
+            return self._handle_plan(utterance, normalized)
+        return self._handle_journal(utterance, normalized)
+
+    def _carry_prompt(self) -> str:
+        return "Which should I carry over to tomorrow - all, none, or name them?"
+
+    def _handle_carry_over(self, normalized: str) -> str:
+        if normalized in ALL_PHRASES:
+            chosen = list(range(len(self.open_tasks)))
+        elif normalized in NONE_PHRASES:
+            chosen = []
+        else:
+            chosen = match_items(normalized, [t.title for t in self.open_tasks])
+            if not chosen:
+                return f"I didn't catch which ones. {self._carry_prompt()}"
+
+        self.carried = [self.open_tasks[i] for i in chosen]
+        for task in self.open_tasks:
+            if task in self.carried:
+                self.planner.update_task(task.id, due_date=self.tomorrow.isoformat(), scheduled_time=None, status="next")
+            else:
+                # Not for tomorrow: off today's schedule, back in Next Actions without a date
+                self.planner.update_task(task.id, due_date="", scheduled_time=None, status="next")
+        lead = f"Carrying over {join_words([t.title for t in self.carried])}." if self.carried \
+            else "Okay, nothing carries over."
+        return f"{lead} {self._start_plan()}"
+
+    def _start_plan(self) -> str:
+        tomorrow = self.tomorrow.isoformat()
+        carried_ids = {t.id for t in self.carried}
+        others = [t for t in self.planner.get_tasks()
+                  if t.id not in carried_ids and t.status in ("inbox", "next", "scheduled")]
+        due = [t for t in others if t.due_date == tomorrow]
+        rest = sorted((t for t in others if not t.due_date), key=lambda t: t.gtd_score(), reverse=True)
+        self.blocks = propose_blocks(self.carried + due + rest,
+                                     free_slots(self.planner, self.tomorrow, self.work_start, self.work_end))
+        if not self.blocks:
+            return f"There's nothing to plan for tomorrow. {self._start_journal()}"
+        self.state = "plan"
+        return f"Here's a plan for tomorrow: {self._read_blocks()}. {self._plan_prompt()}"
+
+    def _read_blocks(self) -> str:
+        return ", ".join(f"{b.start} {b.title}" for b in self.blocks)
+
+    def _plan_prompt(self) -> str:
+        return "Keep it, skip it, or move something - like 'report at 2pm'?"
+
+    def _handle_plan(self, utterance: str, normalized: str) -> str:
+        if normalized in KEEP_PHRASES:
+            self.planner.set_day_plan(self.tomorrow.isoformat(), self.blocks)
+            self.plan_saved = True
+            return f"Saved tomorrow's plan. {self._start_journal()}"
+        if normalized in SKIP_PHRASES:
+            return f"Okay, no plan - tomorrow will be scheduled as it comes. {self._start_journal()}"
+
+        # "move the report to 2pm", "slides at half past ten"
+        parts = re.split(r"\s+(?:at|to)\s+", utterance.strip())
+        name, when = (" ".join(parts[:-1]), parts[-1]) if len(parts) >= 2 else (utterance, None)
+        new_time = parse_time_expression(when) if when else None
+        picked = match_items(name, [b.title for b in self.blocks])
+        if new_time is None or len(picked) != 1:
+            return f"Sorry, I didn't follow. {self._plan_prompt()}"
+        block = self.blocks[picked[0]]
+        block.start = new_time
+        self.blocks.sort(key=lambda b: b.start)
+        return f"Moved {block.title} to {new_time}. Now it's {self._read_blocks()}. {self._plan_prompt()}"
+
+    def _start_journal(self) -> str:
+        self.state = "journal"
+        return "Last thing: anything you'd like me to note about today?"
+
+    def _handle_journal(self, utterance: str, normalized: str) -> str:
+        note = "" if normalized in SKIP_PHRASES else utterance.strip().rstrip(".")
+        self.planner.add_journal_entry(f"{note}. {self.summary}" if note else self.summary, self.today.isoformat())
+        self.planner.mark_review_done()
+        self.state = "done"
+        first = f" Tomorrow starts with {self.blocks[0].title} at {self.blocks[0].start}." if self.plan_saved else ""
+        return f"{'Noted.' if note else 'All set.'}{first} Good night."
+
+    @property
+    def summary(self) -> str:
+        """One line for the journal: "Finished 3 (Report, Slides, Run); carried over Email."."""
+        finished = f"Finished {len(self.done_titles)} ({', '.join(self.done_titles)})" if self.done_titles \
+            else "Nothing finished"
+        carried = f"; carried over {', '.join(t.title for t in self.carried)}" if self.carried else ""
+        return f"{finished}{carried}."
+
+
+register_capability("Evening review", "Wrap up the day: what got done, what moves to tomorrow, and a plan for it",
+                    ["let's do the evening review", "plan tomorrow"], category="Planning",
+                    keywords=("end of day", "tomorrow"))
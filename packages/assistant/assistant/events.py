@@ -0,0 +1,329 @@
+"""
+Event History - Everything the assistant reported, kept and searchable.
+
+Activity-feed entries and new inbox messages scroll away once shown. The
+EventStore keeps them in a local SQLite database, indexed on type and time:
+
+- sms, email, voice: new inbox messages (recorded by InboxManager.sync)
+- activity: activity-feed entries, with their level (info, success,
+  warning, error)
+- turn: something the user said or typed ({"source": "voice" or "chat"})
+- reply: the first answer to a turn ({"latency": seconds})
+- tool: a tool call that ran ({"name": ...})
+- reminder: an event reminder ({"title", "key", "channels" it reached,
+  "delivered"}: False when quiet hours or preferences kept it to the
+  activity feed)
+- reminder_ack: the user reacted to a reminder ({"key", "title", "channel",
+  "seconds"}, see reminders.ReminderReceipts)
+- task: a background job that ran for config.long_job_seconds or more
+  finished ({"name", "duration"})
+
+Events set off by the same interaction (a spoken or typed request, new
+messages coming in) share its ID, the event's thread (see correlation.py).
+
+Anything can add events with record_event(), which does nothing until a
+store is set up (the dashboard does, so tests and scripts don't write to
+your history). Listeners added with add_event_listener() see each event
+as it's recorded (push.py forwards some to the user's phone). Usage analytics are built from the same events (analytics.py).
+Hooks added with add_event_hook() see each event first and can change it
+or drop it (user scripts, see scripting.py).
+
+They can be searched with `xswarm dev events query --type sms --since
+yesterday` (or `--thread ID` for one interaction), and "what did I miss?" summarizes what came in since the user
+last said or typed anything (EventStore.touch). Events older than
+config.event_history_days (or retention_days["events"], see retention.py)
+are pruned when the store opens.
+
+Storage: ~/.xswarm/events.db (format upgrades in MIGRATIONS)
+"""
+
+import json
+import logging
+import re
+import sqlite3
+import threading
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional, Union
+
+from .capabilities import register_capability
+from .correlation import current_id
+from .migrations import Migration, migrate_sqlite
+
+logger = logging.getLogger(__name__)
+
+INBOX_TYPES = ("sms", "email", "voice")
+EVENT_TYPES = INBOX_TYPES + ("activity", "turn", "reply", "tool", "reminder", "reminder_ack", "task")
+KEEP_DAYS = 90
+MISSED_DEFAULT = timedelta(hours=24)  # "What did I miss?" before anything was said
+MISSED_MAX = timedelta(days=7)
+
+_SCHEMA = """
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    type TEXT NOT NULL,
+    ts REAL NOT NULL,
+    summary TEXT NOT NULL,
+    data TEXT NOT NULL DEFAULT '{}'
+);
+CREATE INDEX IF NOT EXISTS events_type_ts ON events (type, ts);
+CREATE INDEX IF NOT EXISTS events_ts ON events (ts);
+CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT);
+"""
+
+# Format changes to events.db, oldest first (see migrations.py)
+MIGRATIONS = [
+    Migration(1, "events and meta tables", lambda db: db.executescript(_SCHEMA)),
+    Migration(2, "thread column", lambda db: db.executescript(
+        "ALTER TABLE events ADD COLUMN thread TEXT; CREATE INDEX IF NOT EXISTS events_thread ON events (thread);")),
+]
+
+_MISSED_INTENT = re.compile(
+    r"\b(?:what\s+(?:did|have)\s+i\s+miss(?:ed)?|did\s+i\s+miss\s+anything|"
+    r"(?:anything\s+(?:new\s+)?|what\s+happened\s+)while\s+i\s+was\s+(?:away|out|gone)|"
+    r"catch\s+me\s+up[.!?\s]*$)",
+    re.IGNORECASE,
+)
+
+_RELATIVE = re.compile(
+    r"^(\d+)\s*(m|mins?|minutes?|h|hrs?|hours?|d|days?|w|weeks?)(?:\s+ago)?$", re.IGNORECASE)
+_UNITS = {"m": "minutes", "h": "hours", "d": "days", "w": "weeks"}
+_WEEKDAYS = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"]
+
+
+def is_missed_request(text: str) -> bool:
+    """True for "what did I miss?", "anything new while I was away?", "catch me up"..."""
+    return bool(_MISSED_INTENT.search(text or ""))
+
+
+def parse_since(text: str, now: Optional[datetime] = None) -> datetime:
+    """
+    Start of a period: "yesterday", "today", "monday" (the last one),
+    "last week", "2h", "3 days ago" or an ISO date/time. ValueError otherwise.
+    """
+    now = now or datetime.now()
+    phrase = " ".join((text or "").lower().split())
+    midnight = now.replace(hour=0, minute=0, second=0, microsecond=0)
+    if phrase == "today":
+        return midnight
+    if phrase == "yesterday":
+        return midnight - timedelta(days=1)
+    if phrase == "last week":
+        return now - timedelta(weeks=1)
+    if phrase in _WEEKDAYS:
+        days = (now.weekday() - _WEEKDAYS.index(phrase)) % 7 or 7
+        return midnight - timedelta(days=days)
+    match = _RELATIVE.match(phrase)
+    if match:
+        return now - timedelta(**{_UNITS[match.group(2)[0]]: int(match.group(1))})
+    try:
+        return datetime.fromisoformat(text.strip())
+    except ValueError:
+        raise ValueError(f"Unknown time '{text}' (try yesterday, monday, 2h, 3 days or 2026-10-01)")
+
+
+@dataclass
+class Event:
+    """One recorded event."""
+    type: str
+    time: datetime
+    summary: str
+    data: Dict[str, Any] = field(default_factory=dict)
+    id: Optional[int] = None
+    thread: Optional[str] = None  # The interaction that set it off (correlation.py)
+
+    def line(self) -> str:
+        return f"{self.time:%Y-%m-%d %H:%M}  {self.thread or '-':<6}  {self.type:<8}  {self.summary}"
+
+
+class EventStore:
+    """SQLite event history (":memory:" for a throwaway store)."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "events.db"
+
+    def __init__(self, path: Union[Path, str, None] = None, keep_days: Optional[int] = KEEP_DAYS):
+        self.path = path or self.DEFAULT_PATH
+        if self.path != ":memory:":
+            Path(self.path).parent.mkdir(parents=True, exist_ok=True)
+        self._lock = threading.Lock()
+        # Shared by the UI thread and background jobs, serialized by the lock
+        self._db = sqlite3.connect(str(self.path), check_same_thread=False)
+        try:
+            self.migrated = migrate_sqlite(self._db, self.path, "events", MIGRATIONS)
+        except Exception:
+            self._db.close()
+            raise
+        if keep_days:
+            self.prune(datetime.now() - timedelta(days=keep_days))
+
+    @classmethod
+    def from_config(cls, config, path: Union[Path, str, None] = None) -> "EventStore":
+        days = (getattr(config, "retention_days", None) or {}).get("events")
+        return cls(path, getattr(config, "event_history_days", KEEP_DAYS) if days is None else days)
+
+    def record(self, type: str, summary: str, data: Optional[Dict[str, Any]] = None,
+               at: Optional[datetime] = None, thread: Optional[str] = None) -> Event:
+        event = Event(type, at or datetime.now(), summary, data or {}, thread=thread)
+        with self._lock:
+            cursor = self._db.execute(
+                "INSERT INTO events (type, ts, summary, data, thread) VALUES (?, ?, ?, ?, ?)",
+                (type, event.time.timestamp(), summary, json.dumps(event.data, ensure_ascii=False), thread))
+            self._db.commit()
+        event.id = cursor.lastrowid
+        return event
+
+    def query(self, types: Optional[Iterable[str]] = None, since: Optional[datetime] = None,
+              until: Optional[datetime] = None, limit: Optional[int] = None,
+              thread: Optional[str] = None) -> List[Event]:
+        """Events of `types` (all when None) between `since` and `until`, oldest first (the newest `limit`)."""
+        where, args = [], []
+        types = list(types or [])
+        if types:
+            where.append(f"type IN ({', '.join('?' for _ in types)})")
+            args.extend(types)
+        if since is not None:
+            where.append("ts >= ?")
+            args.append(since.timestamp())
+        if until is not None:
+            where.append("ts < ?")
+            args.append(until.timestamp())
+        if thread is not None:
+            where.append("thread = ?")
+            args.append(thread)
+        sql = "SELECT id, type, ts, summary, data, thread FROM events"
+        if where:
+            sql += " WHERE " + " AND ".join(where)
+        sql += " ORDER BY ts DESC, id DESC"
+        if limit:
+            sql += f" LIMIT {int(limit)}"
+        with self._lock:
+            rows = self._db.execute(sql, args).fetchall()
+        return [Event(type, datetime.fromtimestamp(ts), summary, json.loads(data), id, thread)
+                for id, type, ts, summary, data, thread in reversed(rows)]
+
+    def prune(self, before: datetime, dry_run: bool = False) -> int:
+        """Delete events older than `before` (only count them with dry_run); returns how many."""
+        with self._lock:
+            if dry_run:
+                return self._db.execute("SELECT COUNT(*) FROM events WHERE ts < ?", (before.timestamp(),)).fetchone()[0]
+            cursor = self._db.execute("DELETE FROM events WHERE ts < ?", (before.timestamp(),))
+            self._db.commit()
+        return cursor.rowcount
+
+    def touch(self, now: Optional[datetime] = None) -> Optional[datetime]:
+        """The user just said or typed something; returns when they last did before this."""
+        now = now or datetime.now()
+        with self._lock:
+            row = self._db.execute("SELECT value FROM meta WHERE key = 'last_active'").fetchone()
+            self._db.execute("INSERT OR REPLACE INTO meta (key, value) VALUES ('last_active', ?)", (now.isoformat(),))
+            self._db.commit()
+        return datetime.fromisoformat(row[0]) if row else None
+
+    def close(self) -> None:
+        with self._lock:
+            self._db.close()
+
+
+def _names(events: List[Event]) -> str:
+    senders = []
+    for event in events:
+        sender = event.data.get("sender")
+        if sender and sender not in senders:
+            senders.append(sender)
+    if not senders:
+        return ""
+    shown = senders[:3] + ([f"{len(senders) - 3} others"] if len(senders) > 3 else [])
+    return " from " + (", ".join(shown[:-1]) + " and " + shown[-1] if len(shown) > 1 else shown[0])
+
+
+def summarize_missed(events: List[Event]) -> str:
+    """ "While you were away: 2 texts from Bob and Alice, 1 email from Dana. 1 alert: Calendar sync failed." """
+    parts = []
+    for type, one, many in (("sms", "text", "texts"), ("email", "email", "emails"),
+                            ("voice", "voicemail", "voicemails")):
+        matching = [e for e in events if e.type == type]
+        if matching:
+            parts.append(f"{len(matching)} {one if len(matching) == 1 else many}{_names(matching)}")
+    alerts = [e for e in events if e.type == "activity" and e.data.get("level") in ("warning", "error")]
+    if not parts and not alerts:
+        return "Nothing new while you were away."
+    text = "While you were away: " + (", ".join(parts) if parts else "no new messages") + "."
+    if alerts:
+        latest = alerts[-1].summary.lstrip("⚠✗ ").rstrip(".")
+        text += f" 1 alert: {latest}." if len(alerts) == 1 else f" {len(alerts)} alerts, the latest: {latest}."
+    return text
+
+
+def missed_since(last_active: Optional[datetime], now: Optional[datetime] = None) -> datetime:
+    """Where "what did I miss?" starts: when the user was last active, within MISSED_MAX."""
+    now = now or datetime.now()
+    if last_active is None:
+        return now - MISSED_DEFAULT
+    return max(last_active, now - MISSED_MAX)
+
+
+_store: Optional[EventStore] = None
+_listeners: List[Callable[[Event], None]] = []
+_hooks: List[Callable[[Event], Optional[Event]]] = []
+
+
+def get_event_store() -> EventStore:
+    global _store
+    if _store is None:
+        _store = EventStore()
+    return _store
+
+
+def set_event_store(store: Optional[EventStore]) -> None:
+    global _store
+    _store = store
+
+
+def add_event_listener(listener: Callable[[Event], None]) -> None:
+    """Call `listener` with every event passed to record_event()."""
+    _listeners.append(listener)
+
+
+def remove_event_listener(listener: Callable[[Event], None]) -> None:
+    if listener in _listeners:
+        _listeners.remove(listener)
+
+
+def add_event_hook(hook: Callable[[Event], Optional[Event]]) -> None:
+    """Call `hook` with every event before it's recorded: it returns the event (changed or not), or None to drop it."""
+    _hooks.append(hook)
+
+
+def remove_event_hook(hook: Callable[[Event], Optional[Event]]) -> None:
+    if hook in _hooks:
+        _hooks.remove(hook)
+
+
+def record_event(type: str, summary: str, data: Optional[Dict[str, Any]] = None) -> None:
+    """
+    Add an event to the history, if one is set up (see set_event_store), and pass it to listeners.
+    It joins the thread of the interaction being handled, if any (correlation.py).
+    """
+    event = Event(type, datetime.now(), summary, data or {}, thread=current_id())
+    for hook in list(_hooks):
+        try:
+            event = hook(event)
+        except Exception as e:
+            logger.debug(f"Event hook failed on {type} event: {e}")
+        if event is None:
+            return
+    if _store is not None:
+        try:
+            event = _store.record(event.type, event.summary, event.data, thread=event.thread)
+        except Exception as e:
+            logger.debug(f"Could not record {type} event: {e}")
+    for listener in list(_listeners):
+        try:
+            listener(event)
+        except Exception as e:
+            logger.debug(f"Event listener failed on {type} event: {e}")
+
+
+register_capability("Catch up", "What happened while you were away: messages, reminders and finished jobs",
+                    ["what did I miss?", "catch me up"], category="Information", keywords=("missed", "away"))
@@ -0,0 +1,136 @@
+"""
+Feature flags - Subsystems switched on or off at runtime, per user.
+
+Every flag is declared in FLAGS with what it gates. Whether it's on is
+decided in layers, the last that has a say winning:
+
+1. the flag's default - or on for the release channels it lists
+   (config.update_channel), which is how a subsystem ships dark to
+   stable users while beta users try it
+2. the server's overrides for this user (GET /api/flags, see
+   routes/flags.js), refreshed by the flag_sync job and kept on disk so
+   they apply from the next start even while the server is unreachable
+3. config.feature_flags, e.g. {"local_llm": false} - the user's own say
+
+    xswarm dev config flags      # each flag, on or off, and which layer decided
+
+Code asks `enabled("wake_word")` where the subsystem is used, so most
+changes apply at once (speech_cache is read when voice starts); an unknown
+name is a KeyError, so a typo fails loudly instead of leaving a subsystem
+off.
+
+Storage: ~/.xswarm/flags.json (the server's last overrides)
+"""
+
+import json
+import logging
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, List, Mapping, Optional, Tuple
+
+from . import endpoints
+
+logger = logging.getLogger(__name__)
+
+FLAGS_PATH = Path.home() / ".xswarm" / "flags.json"
+
+
+@dataclass(frozen=True)
+class Flag:
+    name: str
+    description: str
+    default: bool = False
+    channels: Tuple[str, ...] = ()  # Release channels it's on for, whatever the default
+
+
+FLAGS: Dict[str, Flag] = {flag.name: flag for flag in (
+    Flag("wake_word", "Wait for the wake word when config.require_wake_word is set (wake_word.py)", True),
+    Flag("local_llm", "Fall back to a model on this machine (config.local_ai_provider)", True),
+    Flag("speech_cache", "Play recorded common phrases instead of synthesizing them (speech_cache.py)", True),
+)}
+
+
+class FeatureFlags:
+    """The flags' state for one user: defaults, the release channel, server overrides, local config."""
+
+    def __init__(self, channel: str = "stable", local: Optional[Mapping[str, Any]] = None,
+                 path: Optional[Path] = None, flags: Optional[Dict[str, Flag]] = None):
+        self.flags = FLAGS if flags is None else flags
+        self.channel = channel
+        self.path = path
+        self.local = self._known(local or {}, "config.feature_flags")
+        self.server: Dict[str, bool] = {}
+        if path is not None and path.exists():
+            try:
+                self.server = self._known(json.loads(path.read_text(encoding="utf-8")), "the saved server flags")
+            except (OSError, ValueError) as e:
+                logger.warning(f"Couldn't read {path}: {e}")
+
+    @classmethod
+    def from_config(cls, config=None, path: Optional[Path] = FLAGS_PATH) -> "FeatureFlags":
+        return cls(getattr(config, "update_channel", "stable"), getattr(config, "feature_flags", None), path)
+
+    def _known(self, values: Mapping[str, Any], where: str) -> Dict[str, bool]:
+        unknown = sorted(set(values) - set(self.flags))
+        if unknown:
+            logger.warning(f"Ignoring unknown feature flag(s) in {where}: {', '.join(unknown)}")
+        return {name: bool(value) for name, value in values.items() if name in self.flags}
+
+    def state(self, name: str) -> Tuple[bool, str]:
+        """Whether a flag is on, and which layer said so: default, channel, server or config."""
+        flag = self.flags.get(name)
+        if flag is None:
+            raise KeyError(f"Unknown feature flag '{name}'")
+        if name in self.local:
+            return self.local[name], "config"
+        if name in self.server:
+            return self.server[name], "server"
+        if self.channel in flag.channels:
+            return True, "channel"
+        return flag.default, "default"
+
+    def enabled(self, name: str) -> bool:
+        return self.state(name)[0]
+
+    def rows(self) -> List[Tuple[Flag, bool, str]]:
+        """Every flag with its state and where that came from, by name."""
+        return [(flag, *self.state(name)) for name, flag in sorted(self.flags.items())]
+
+    def set_server(self, overrides: Mapping[str, Any]) -> List[str]:
+        """Replace the server's overrides (and save them); the flags whose state changed."""
+        before = {name: self.enabled(name) for name in self.flags}
+        self.server = self._known(overrides, "the server's flags")
+        if self.path is not None:
+            try:
+                self.path.parent.mkdir(parents=True, exist_ok=True)
+                self.path.write_text(json.dumps(self.server, indent=2, sort_keys=True), encoding="utf-8")
+            except OSError as e:
+                logger.warning(f"Couldn't save the server's feature flags: {e}")
+        return [name for name in self.flags if self.enabled(name) != before[name]]
+
+    async def sync(self, client, user_id: str) -> List[str]:
+        """Fetch this user's overrides from the server (flag_sync job); the flags that changed."""
+        response = await client.call(endpoints.FLAGS(), params={"user_id": user_id})
+        return self.set_server(response.json().get("flags") or {})
+
+
+_flags: Optional[FeatureFlags] = None
+
+
+def get_feature_flags() -> FeatureFlags:
+    """The flags built from the loaded Config, or only the defaults before startup."""
+    global _flags
+    if _flags is None:
+        _flags = FeatureFlags()
+    return _flags
+
+
+def set_feature_flags(flags: Optional[FeatureFlags]) -> None:
+    """Install the flags built from the loaded Config (called at startup)."""
+    global _flags
+    _flags = flags
+
+
+def enabled(name: str) -> bool:
+    """Whether a feature flag is on (see FLAGS)."""
+    return get_feature_flags().enabled(name)
@@ -0,0 +1,354 @@
+"""
+Flows - Declarative multi-turn dialogs (setup questions, guided capture).
+
+A Flow is a small state machine of Steps. Each step asks a question, parses
+the answer into a typed slot and validates it, then moves to the next step
+(fixed or chosen from the answers so far). When the last step is answered
+the flow's on_complete runs with the collected values and its reply is the
+flow's last word.
+
+    Flow("new_task", [
+        Step("title", "What's the task?"),
+        Step("due", "When is it due?", slot="date", optional=True),
+    ], on_complete=lambda values: f"Added {values['title']}.")
+
+Slot types: text, yes_no, number, time ("HH:MM"), date (datetime.date),
+choice (one of Step.choices). A bad answer re-asks with a hint, up to
+Flow.max_retries times; optional steps accept "skip".
+
+At any step "cancel" / "never mind" ends the flow, and "repeat" asks the
+question again. A flow left unanswered for Flow.timeout seconds lapses, so
+a later unrelated sentence isn't taken as an answer.
+
+Like reply_drafts.ReplyWorkflow, a FlowRun is transport-agnostic: the
+dashboard feeds it utterances (typed or transcribed) and speaks/displays
+what it returns. Flows are registered by name with the phrases that start
+them (register_flow / match_flow); "add a task" starts the built-in
+new_task flow, and flow_for_request also picks up scheduling requests that
+leave out a title or time (appointments.py).
+"""
+
+import logging
+import re
+import time
+from dataclasses import dataclass, field
+from datetime import date
+from typing import Any, Callable, Dict, List, Optional, Sequence, Tuple, Union
+
+from .capabilities import register_capability
+from .dates import (
+    NUMBER_WORDS,
+    ORDINAL_WORDS,
+    ambiguous_readings,
+    clarifying_question,
+    parse_natural_date,
+    parse_time_expression,
+)
+from .dialogue import get_dialogue_state
+from .verbalize import verbalize_date
+
+logger = logging.getLogger(__name__)
+
+CANCEL_PHRASES = ("cancel", "never mind", "nevermind", "forget it", "stop", "quit", "exit", "cancel that")
+REPEAT_PHRASES = ("repeat", "repeat that", "say that again", "what", "pardon", "come again", "sorry what")
+SKIP_PHRASES = ("skip", "skip it", "pass", "none", "nothing", "no thanks", "doesn't matter", "don't care",
+                "you pick", "you choose", "whenever", "any time", "anytime", "no idea", "not sure", "i don't know")
+YES_WORDS = ("yes", "yeah", "yep", "yup", "sure", "ok", "okay", "correct", "right", "please", "absolutely", "definitely")
+NO_WORDS = ("no", "nope", "nah", "not", "don't", "negative")
+
+SLOT_TYPES = ("text", "yes_no", "number", "time", "date", "choice")
+DEFAULT_TIMEOUT = 120.0  # Seconds without an answer before a flow lapses
+DEFAULT_RETRIES = 2  # Re-asks after an unusable answer before giving up
+
+Prompt = Union[str, Callable[[Dict[str, Any]], str]]
+
+
+@dataclass
+class Step:
+    """One question in a flow."""
+    name: str  # Key for the answer in FlowRun.values
+    prompt: Prompt  # Question, or a function of the values so far
+    slot: str = "text"  # One of SLOT_TYPES
+    choices: Sequence[str] = ()  # For slot="choice"
+    optional: bool = False  # "skip" stores None
+    # Extra check on a parsed answer: returns a hint to re-ask with, or None when it's fine
+    validate: Optional[Callable[[Any, Dict[str, Any]], Optional[str]]] = None
+    # Step name to go to next (None = the following step), or a function of
+    # the values returning one (None = finish)
+    next: Union[None, str, Callable[[Dict[str, Any]], Optional[str]]] = None
+
+    def __post_init__(self):
+        if self.slot not in SLOT_TYPES:
+            raise ValueError(f"Unknown slot type: {self.slot}")
+
+
+@dataclass
+class Flow:
+    """A named multi-turn dialog."""
+    name: str
+    steps: List[Step]
+    on_complete: Callable[[Dict[str, Any]], str]  # Does the work; returns the closing reply
+    intro: str = ""  # Said before the first question
+    on_cancel: Optional[Callable[[Dict[str, Any]], str]] = None
+    timeout: float = DEFAULT_TIMEOUT
+    max_retries: int = DEFAULT_RETRIES
+
+    def step(self, name: str) -> Step:
+        for step in self.steps:
+            if step.name == name:
+                return step
+        raise KeyError(f"Flow {self.name} has no step {name}")
+
+
+def _normalize(text: str) -> str:
+    text = re.sub(r"[^\w\s':/.-]", " ", (text or "").lower())
+    return " ".join(text.split()).strip(" .")
+
+
+# ------------------------------------------------------------------------------
+# Slot parsing
+# ------------------------------------------------------------------------------
+
+def _parse_number(text: str) -> Optional[float]:
+    match = re.search(r"-?\d+(?:\.\d+)?", text)
+    if match:
+        value = float(match.group())
+        return int(value) if value.is_integer() else value
+    words = text.split()
+    for i, word in enumerate(words):
+        if word in NUMBER_WORDS and word not in ("a", "an", "oh"):
+            value = NUMBER_WORDS[word]
+            if value >= 20 and i + 1 < len(words) and 1 <= NUMBER_WORDS.get(words[i + 1], 0) <= 9:
+                value += NUMBER_WORDS[words[i + 1]]
+            return value
+    return None
+
+
+def _parse_choice(text: str, choices: Sequence[str]) -> Tuple[Optional[str], Optional[str]]:
+    exact = [c for c in choices if _normalize(c) == text]
+    if exact:
+        return exact[0], None
+    words = text.split()
+    for word in words:
+        position = ORDINAL_WORDS.get(word) or (int(word) if word.isdigit() else None)
+        if position and 1 <= position <= len(choices):
+            return choices[position - 1], None
+    if "last" in words and choices:
+        return choices[-1], None
+    matches = [c for c in choices if re.search(rf"\b{re.escape(_normalize(c))}\b", text)]
+    if len(matches) == 1:
+        return matches[0], None
+    if len(matches) > 1:
+        return None, f"Did you mean {' or '.join(matches)}?"
+    return None, f"Please pick one of: {', '.join(choices)}."
+
+
+def parse_slot(step: Step, utterance: str, today: Optional[date] = None) -> Tuple[Any, Optional[str]]:
+    """
+    Parse an answer for `step` as (value, None), or (None, hint) when it
+    isn't usable.
+    """
+    text = _normalize(utterance)
+    if step.slot == "text":
+        value = " ".join((utterance or "").split()).strip()
+        return (value, None) if value else (None, "I didn't catch that.")
+    if step.slot == "yes_no":
+        for word in text.split():  # The first yes or no word decides ("no, that's fine" is a no)
+            if word in YES_WORDS or word in NO_WORDS:
+                return word in YES_WORDS, None
+        return None, "Please answer yes or no."
+    if step.slot == "number":
+        value = _parse_number(text)
+        return (value, None) if value is not None else (None, "I need a number.")
+    if step.slot == "time":
+        value = parse_time_expression(text)
+        return (value, None) if value else (None, "I didn't get a time - try something like 'half past two'.")
+    if step.slot == "date":
+        readings = ambiguous_readings(text, today=today)
+        if readings:
+            return None, clarifying_question(text, readings)
+        value = parse_natural_date(text, today=today)
+        return (value, None) if value else (None, "I didn't get a date - try something like 'next Friday'.")
+    return _parse_choice(text, step.choices)
+
+
+# ------------------------------------------------------------------------------
+# Running a flow
+# ------------------------------------------------------------------------------
+
+class FlowRun:
+    """
+    One conversation through a Flow.
+
+    state: idle -> waiting -> done | cancelled | failed | expired
+    """
+
+    def __init__(self, flow: Flow, values: Optional[Dict[str, Any]] = None,
+                 clock: Callable[[], float] = time.monotonic, today: Optional[date] = None):
+        self.flow = flow
+        self.values: Dict[str, Any] = dict(values or {})
+        self.clock = clock
+        self.today = today
+        self.state = "idle"
+        self.current: Optional[Step] = None
+        self.retries = 0
+        self.last_activity = clock()
+
+    @property
+    def is_active(self) -> bool:
+        return self.state == "waiting"
+
+    def _prompt(self) -> str:
+        prompt = self.current.prompt
+        return prompt(self.values) if callable(prompt) else prompt
+
+    def start(self) -> str:
+        """Ask the first question."""
+        if not self.flow.steps:
+            return self._complete()
+        self.state = "waiting"
+        self.current = self.flow.steps[0]
+        self.last_activity = self.clock()
+        return " ".join(part for part in (self.flow.intro, self._prompt()) if part)
+
+    def expired(self) -> bool:
+        """True (and the flow lapses) when the current question has gone unanswered for flow.timeout seconds."""
+        if self.is_active and self.flow.timeout and self.clock() - self.last_activity > self.flow.timeout:
+            self.state = "expired"
+            logger.info(f"Flow {self.flow.name} timed out at step {self.current.name}")
+        return self.state == "expired"
+
+    def handle(self, utterance: str) -> str:
+        """Answer the current question; returns what to say next."""
+        if not self.is_active:
+            return ""
+        self.last_activity = self.clock()
+        text = _normalize(utterance)
+        if text in CANCEL_PHRASES:
+            self.state = "cancelled"
+            return self.flow.on_cancel(self.values) if self.flow.on_cancel else "Okay, cancelled."
+        if text in REPEAT_PHRASES:
+            return self._prompt()
+
+        step = self.current
+        if step.optional and text in SKIP_PHRASES:
+            value, hint = None, None
+        else:
+            value, hint = parse_slot(step, utterance, self.today)
+            if hint is None and step.validate:
+                hint = step.validate(value, self.values)
+        if hint is not None:
+            self.retries += 1
+            if self.retries > self.flow.max_retries:
+                self.state = "failed"
+                return "Let's leave that for now - start again whenever you're ready."
+            return f"{hint} {self._prompt()}"
+
+        self.values[step.name] = value
+        self.retries = 0
+        following = step.next(self.values) if callable(step.next) else step.next
+        if following is None and step.next is None:
+            index = self.flow.steps.index(step)
+            following = self.flow.steps[index + 1].name if index + 1 < len(self.flow.steps) else None
+        if following is None:
+            return self._complete()
+        self.current = self.flow.step(following)
+        return self._prompt()
+
+    def _complete(self) -> str:
+        try:
+            reply = self.flow.on_complete(self.values)
+        except Exception as e:
+            logger.error(f"Flow {self.flow.name} failed to complete: {e}")
+            self.state = "failed"
+            return f"Sorry, that didn't work: {e}"
+        self.state = "done"
+        return reply
+
+
+# ------------------------------------------------------------------------------
+# Registry
+# ------------------------------------------------------------------------------
+
+@dataclass
+class FlowEntry:
+    """A registered flow: how to build it and what starts it."""
+    name: str
+    factory: Callable[[], Flow]
+    triggers: List[re.Pattern] = field(default_factory=list)
+
+
+_flows: Dict[str, FlowEntry] = {}
+
+
+def register_flow(name: str, factory: Callable[[], Flow], triggers: Sequence[str] = ()) -> None:
+    """Register a flow factory; `triggers` are regexes matched against the whole utterance."""
+    _flows[name] = FlowEntry(name, factory, [re.compile(rf"^\s*(?:{t})\s*[.!?]*\s*$", re.IGNORECASE)
+                                             for t in triggers])
+
+
+def get_flow(name: str) -> Optional[Flow]:
+    entry = _flows.get(name)
+    return entry.factory() if entry else None
+
+
+def match_flow(text: str) -> Optional[str]:
+    """The name of the flow `text` asks to start, if any."""
+    for entry in _flows.values():
+        if any(pattern.match(text or "") for pattern in entry.triggers):
+            return entry.name
+    return None
+
+
+def flow_for_request(text: str) -> Optional[Flow]:
+    """
+    The flow to run for an utterance: a registered flow it starts, or a
+    clarification for a scheduling request with details missing
+    (appointments.py). None when the AI should handle it.
+    """
+    name = match_flow(text)
+    if name:
+        return get_flow(name)
+    from .appointments import appointment_flow, needs_details
+    request = needs_details(text)
+    return appointment_flow(request) if request else None
+
+
+# ------------------------------------------------------------------------------
+# Built-in flows
+# ------------------------------------------------------------------------------
+
+def new_task_flow(planner=None, today: Optional[date] = None) -> Flow:
+    """Guided task capture: title, due date, length."""
+
+    def complete(values: Dict[str, Any]) -> str:
+        target = planner
+        if target is None:
+            from .tools import get_planner_data
+            target = get_planner_data()
+        due = values.get("due")
+        minutes = values.get("minutes")
+        task = target.add_task(
+            values["title"], due_date=due.isoformat() if due else None, duration_min=int(minutes or 30),
+            status="next",
+        )
+        get_dialogue_state().focus_on("task_id", task.id, task.title)  # "...and make it high priority"
+        when = f", due {verbalize_date(due, today=today or date.today())}" if due else ""
+        return f"Added {task.title}{when}."
+
+    def check_minutes(value, _values) -> Optional[str]:
+        return None if 5 <= value <= 8 * 60 else "Somewhere between 5 minutes and 8 hours, please."
+
+    return Flow("new_task", [
+        Step("title", "What's the task?"),
+        Step("due", "When is it due? Say 'skip' if it isn't.", slot="date", optional=True),
+        Step("minutes", "Roughly how many minutes will it take?", slot="number", optional=True,
+             validate=check_minutes),
+    ], on_complete=complete, on_cancel=lambda _values: "Okay, no task added.")
+
+
+register_flow("new_task", new_task_flow,
+              triggers=[r"(?:please\s+)?(?:add|create|new|capture)\s+(?:a\s+)?(?:new\s+)?task(?:\s+please)?"])
+
+register_capability("New task", "Add a task step by step, with a question for each detail",
+                    ["add a task", "new task"], category="Planning", keywords=("todo", "capture"))
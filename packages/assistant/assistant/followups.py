@@ -0,0 +1,337 @@
+"""
+Follow-up Detection - Spot commitments made in conversation.
+
+When the user says something like "I'll send that to Sarah by Friday", a
+follow-up is proposed instead of silently forgotten. Detection is hybrid:
+
+1. Rules: first-person commitment phrases ("I'll", "I need to", "remind me
+   to") plus deadline phrases ("by Friday", "tomorrow", "end of week").
+2. LLM: when rules fire (or a softer trigger appears) and an AI client is
+   available, the utterance and recent context are sent for confirmation and
+   cleanup, which also drops false positives like "I'll think about it".
+
+Proposals are queued for confirmation in the dashboard; nothing is added to
+the planner until the user accepts it. Appointments and deadlines found in
+incoming email are proposed the same way (email_tasks.py), as tasks or
+calendar events.
+
+Storage: ~/.xswarm/followups/followups.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from dataclasses import dataclass, asdict
+from datetime import date, datetime
+from pathlib import Path
+from typing import Optional, List, Dict
+
+from .categories import infer_categories
+from .dates import parse_natural_date
+
+logger = logging.getLogger(__name__)
+
+
+# First-person commitment openers; the action follows the match
+COMMITMENT_PATTERN = re.compile(
+    r"\b(?:i'll|i will|i'm going to|i am going to|i'm gonna|i need to|i have to|"
+    r"i've got to|i must|i promise to|let me|remind me to|i should)\s+(?P<action>.+)",
+    re.IGNORECASE,
+)
+
+# Hedges and non-actions that look like commitments
+HEDGE_PATTERN = re.compile(
+    r"^(?:think about|see|try to remember|be right back|be there|let you know if|"
+    r"check if i can|maybe|probably|possibly|just|say|tell you what|know)\b",
+    re.IGNORECASE,
+)
+
+# Softer phrasing worth an LLM look even without a rule match
+SOFT_TRIGGERS = re.compile(r"\b(?:by|before|until|deadline|follow up|get back to|owe)\b", re.IGNORECASE)
+
+DEADLINE_PATTERN = re.compile(
+    r"\s*\b(?:(?:by|before|on|until)\s+)?(?P<when>today|tonight|tomorrow|"
+    r"end of (?:the )?(?:day|week|month)|eod|eow|this week|next week|this weekend|next month|"
+    r"(?:the )?day after tomorrow|in (?:a|an|a couple of|a few|\d+|two|three|four|five|six) (?:days?|weeks?)|"
+    r"(?:next |this )?(?:monday|tuesday|wednesday|thursday|friday|saturday|sunday)|"
+    r"\d{4}-\d{2}-\d{2})\b",
+    re.IGNORECASE,
+)
+
+RECIPIENT_PATTERN = re.compile(r"\b(?:to|for|with)\s+(?P<who>[A-Z][a-z]+(?:\s+[A-Z][a-z]+)?)")
+
+ACTION_VERBS = {
+    "send", "email", "call", "text", "finish", "review", "write", "draft", "submit",
+    "book", "schedule", "pay", "fix", "follow", "get", "share", "update", "prepare",
+    "ping", "reply", "check", "order", "buy", "file", "sign",
+}
+
+MIN_CONFIDENCE = 0.6
+
+
+def resolve_deadline(phrase: str, today: Optional[date] = None) -> Optional[str]:
+    """Turn a deadline phrase into a YYYY-MM-DD date (same grammar as the calendar tools, see dates.py)."""
+    day = parse_natural_date(phrase, today)
+    return day.isoformat() if day else None
+
+
+@dataclass
+class ProposedFollowUp:
+    """A follow-up detected in conversation, awaiting confirmation."""
+    id: str
+    title: str
+    source: str  # The utterance it came from
+    kind: str = "task"  # task, commitment, event
+    due_date: Optional[str] = None  # YYYY-MM-DD (events: the day they're on)
+    to_whom: Optional[str] = None
+    confidence: float = 0.0
+    detected_by: str = "rules"  # rules, llm, parser (booking_emails.py)
+    status: str = "pending"  # pending, accepted, dismissed
+    created_at: str = ""
+    start_time: Optional[str] = None  # Events: ISO datetimes
+    end_time: Optional[str] = None
+    location: Optional[str] = None
+    source_id: Optional[str] = None  # Inbox item it came from (email_tasks.py)
+    details: Optional[str] = None  # "Key: value" lines from a parsed confirmation (booking_emails.py)
+    reminder_minutes: Optional[int] = None  # Events: how long before to remind, when not the default
+
+    def __post_init__(self):
+        if not self.created_at:
+            self.created_at = datetime.now().isoformat()
+
+    def describe(self) -> str:
+        parts = [self.title]
+        if self.start_time:
+            parts.append(f"📅 {datetime.fromisoformat(self.start_time):%a %b %d %H:%M}")
+            return " ".join(parts)
+        if self.to_whom:
+            parts.append(f"→ {self.to_whom}")
+        if self.due_date:
+            parts.append(f"(due {self.due_date})")
+        return " ".join(parts)
+
+
+def _clean_action(action: str) -> str:
+    action = re.split(r"[.!?;]|\b(?:and then|but|because|so that)\b", action, maxsplit=1)[0]
+    action = DEADLINE_PATTERN.sub("", action)
+    action = re.sub(r"\s+", " ", action).strip(" ,")
+    return action[:1].upper() + action[1:] if action else action
+
+
+def detect_followups(text: str, today: Optional[date] = None) -> List[ProposedFollowUp]:
+    """Rule pass: find first-person commitments in a user utterance."""
+    proposals = []
+    for sentence in re.split(r"(?<=[.!?])\s+|\n", text):
+        match = COMMITMENT_PATTERN.search(sentence)
+        if not match:
+            continue
+        raw_action = match.group("action")
+        if HEDGE_PATTERN.match(raw_action):
+            continue
+
+        title = _clean_action(raw_action)
+        if len(title.split()) < 2 and title.lower() not in ACTION_VERBS:
+            continue
+
+        deadline = DEADLINE_PATTERN.search(raw_action)
+        recipient = RECIPIENT_PATTERN.search(raw_action)
+        due_date = resolve_deadline(deadline.group("when"), today) if deadline else None
+
+        confidence = 0.5
+        if title.split()[0].lower() in ACTION_VERBS:
+            confidence += 0.2
+        if due_date:
+            confidence += 0.2
+        if recipient:
+            confidence += 0.1
+
+        if confidence < MIN_CONFIDENCE:
+            continue
+
+        proposals.append(ProposedFollowUp(
+            id=uuid.uuid4().hex[:8],
+            title=title,
+            source=sentence.strip(),
+            kind="commitment" if recipient and due_date else "task",
+            due_date=due_date,
+            to_whom=recipient.group("who") if recipient else None,
+            confidence=round(min(confidence, 1.0), 2),
+        ))
+    return proposals
+
+
+class FollowUpDetector:
+    """Hybrid rule + LLM follow-up detection."""
+
+    def __init__(self, ai_client=None):
+        self.ai = ai_client
+
+    def _needs_review(self, text: str, candidates: List[ProposedFollowUp]) -> bool:
+        return bool(candidates) or bool(SOFT_TRIGGERS.search(text) and COMMITMENT_PATTERN.search(text) is None)
+
+    async def detect(self, utterance: str, context: str = "", today: Optional[date] = None) -> List[ProposedFollowUp]:
+        """Detect follow-ups in a user utterance, confirming with the LLM when available."""
+        candidates = detect_followups(utterance, today)
+        if self.ai is None or not self.ai.is_available() or not self._needs_review(utterance, candidates):
+            return candidates
+
+        try:
+            reviewed = await self._review(utterance, context, today)
+        except Exception as e:
+            logger.debug(f"Follow-up LLM review failed, using rules only: {e}")
+            return candidates
+        return reviewed
+
+    async def _review(self, utterance: str, context: str, today: Optional[date]) -> List[ProposedFollowUp]:
+        today = today or date.today()
+        messages = [
+            {
+                "role": "system",
+                "content": (
+                    "You extract commitments the user made about things THEY will do. "
+                    "Ignore vague intentions, things others will do, and past actions. "
+                    f"Today is {today.strftime('%A %Y-%m-%d')}. "
+                    'Respond with only a JSON array of objects: {"title": imperative task title, '
+                    '"to_whom": person or null, "due_date": YYYY-MM-DD or null}. '
+                    "Return [] if there are none."
+                ),
+            },
+            {
+                "role": "user",
+                "content": f"Recent conversation:\n{context[-2000:]}\n\nUser just said:\n{utterance}",
+            },
+        ]
+        response = await self.ai.chat(messages, max_tokens=400)
+        match = re.search(r"\[.*\]", response, re.DOTALL)
+        if not match:
+            raise ValueError(f"No JSON array in follow-up review: {response[:100]}")
+        items = json.loads(match.group(0))
+
+        proposals = []
+        for item in items:
+            title = (item.get("title") or "").strip()
+            if not title:
+                continue
+            due = item.get("due_date")
+            if due and not re.fullmatch(r"\d{4}-\d{2}-\d{2}", str(due)):
+                due = resolve_deadline(str(due), today)
+            to_whom = item.get("to_whom") or None
+            proposals.append(ProposedFollowUp(
+                id=uuid.uuid4().hex[:8],
+                title=title,
+                source=utterance.strip(),
+                kind="commitment" if to_whom and due else "task",
+                due_date=due,
+                to_whom=to_whom,
+                confidence=0.9,
+                detected_by="llm",
+            ))
+        return proposals
+
+
+class FollowUpStore:
+    """
+    Pending follow-up proposals awaiting user confirmation.
+    Follows the PlannerData single-file pattern.
+    """
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "followups"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _followups_path(self) -> Path:
+        return self.storage_dir / "followups.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+
+        path = self._followups_path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load follow-ups: {e}")
+
+        self._data = {"followups": []}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            with open(self._followups_path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save follow-ups: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    def pending(self) -> List[ProposedFollowUp]:
+        return [ProposedFollowUp(**f) for f in self._load()["followups"] if f["status"] == "pending"]
+
+    def get(self, followup_id: str) -> Optional[ProposedFollowUp]:
+        for raw in self._load()["followups"]:
+            if raw["id"].startswith(followup_id):
+                return ProposedFollowUp(**raw)
+        return None
+
+    def add(self, proposals: List[ProposedFollowUp]) -> List[ProposedFollowUp]:
+        """
+        Queue proposals, skipping ones already pending with the same title
+        and ones proposed before from the same email. Returns those added.
+        """
+        data = self._load()
+        existing = {f["title"].lower() for f in data["followups"] if f["status"] == "pending"}
+        from_sources = {(f.get("source_id"), f["title"].lower()) for f in data["followups"] if f.get("source_id")}
+        added = []
+        for proposal in proposals:
+            if proposal.title.lower() in existing or (proposal.source_id, proposal.title.lower()) in from_sources:
+                continue
+            existing.add(proposal.title.lower())
+            data["followups"].append(asdict(proposal))
+            added.append(proposal)
+        if added:
+            self._save()
+        return added
+
+    def _set_status(self, followup_id: str, status: str) -> Optional[ProposedFollowUp]:
+        for raw in self._load()["followups"]:
+            if raw["id"].startswith(followup_id) and raw["status"] == "pending":
+                raw["status"] = status
+                self._save()
+                return ProposedFollowUp(**raw)
+        return None
+
+    def accept(self, followup_id: str, planner) -> Optional[str]:
+        """Create the task or commitment in the planner. Returns a status line."""
+        proposal = self._set_status(followup_id, "accepted")
+        if not proposal:
+            return None
+        tags = infer_categories(f"{proposal.title} {proposal.source}")
+        if proposal.kind == "event":
+            description = f"{proposal.details}\n{proposal.source}" if proposal.details else proposal.source
+            event = planner.add_calendar_event(proposal.title, proposal.start_time, proposal.end_time,
+                                               description=description, location=proposal.location or "",
+                                               reminder_minutes=proposal.reminder_minutes or 15, tags=tags)
+            return f"✓ Added to calendar: {event.title} ({datetime.fromisoformat(event.start_time):%a %b %d %H:%M})"
+        if proposal.kind == "commitment":
+            planner.add_commitment(proposal.title, proposal.to_whom, proposal.due_date, tags=tags)
+            return f"✓ Commitment to {proposal.to_whom}: {proposal.title} (due {proposal.due_date})"
+        notes = proposal.source if proposal.source_id else f"From conversation: \"{proposal.source}\""
+        if proposal.details:
+            notes = f"{proposal.details}\n{notes}"
+        planner.add_task(proposal.title, due_date=proposal.due_date, notes=notes, tags=tags)
+        due = f" (due {proposal.due_date})" if proposal.due_date else ""
+        return f"✓ Task added: {proposal.title}{due}"
+
+    def dismiss(self, followup_id: str) -> Optional[ProposedFollowUp]:
+        return self._set_status(followup_id, "dismissed")
@@ -0,0 +1,256 @@
+"""
+Geocoding - Turn event locations into coordinates, map links and travel times.
+
+When a calendar event has a location, the geocode_locations job (dashboard
+_setup_jobs, every 10 minutes, deferred while the machine is busy) looks it
+up with the configured geocoder and stores latitude/longitude on the event.
+Coordinates then feed:
+
+- map links (map_link) in the Schedule tab and, via calendar sync, in the
+  server's invitation emails
+- travel-time conflicts (travel_conflicts): back-to-back events in
+  different places without time to get between them, from real distances
+  rather than a fixed buffer
+
+Geocoders are pluggable (config.geocoder):
+- "nominatim": OpenStreetMap's Nominatim (config.geocoder_url for a
+  self-hosted instance); results are cached on disk, misses included, so
+  each place is looked up once
+- "none": no lookups; map links fall back to a search for the text
+
+Anything with an async geocode(query) -> Optional[GeoPoint] works (see
+StaticGeocoder for the shape). Settings are installed at startup with
+set_location_settings(LocationSettings.from_config(config)).
+
+Storage: ~/.xswarm/geocode_cache.json
+"""
+
+import json
+import logging
+import math
+from dataclasses import dataclass
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+from urllib.parse import quote_plus
+
+logger = logging.getLogger(__name__)
+
+NOMINATIM_URL = "https://nominatim.openstreetmap.org"
+USER_AGENT = "xswarm-assistant (https://xswarm.ai)"  # Nominatim's usage policy requires one
+
+MAP_PROVIDERS = ("openstreetmap", "google", "apple")
+
+# Travel estimates: straight-line distance stretched for roads, at city speed, plus getting out the door
+ROAD_FACTOR = 1.3
+DEFAULT_SPEED_KMH = 30.0
+DEPARTURE_MINUTES = 5
+SAME_PLACE_KM = 0.2  # Closer than this counts as the same building
+
+
+@dataclass(frozen=True)
+class GeoPoint:
+    latitude: float
+    longitude: float
+    label: str = ""  # What the geocoder called it ("Main St, Springfield, ...")
+
+
+@dataclass
+class TravelConflict:
+    """Two consecutive events too far apart for the gap between them."""
+    before: Any  # CalendarEvent
+    after: Any
+    gap_minutes: int
+    travel_minutes: int
+    distance_km: float
+
+    @property
+    def text(self) -> str:
+        return (f"{self.before.title} → {self.after.title}: ~{self.travel_minutes} min to travel "
+                f"{self.distance_km:.1f} km, {max(self.gap_minutes, 0)} min between them")
+
+
+class StaticGeocoder:
+    """Fixed places (tests, offline use): {"office": (51.5, -0.12)}; names match case-insensitively."""
+
+    def __init__(self, places: Dict[str, Tuple[float, float]]):
+        self.places = {name.strip().lower(): point for name, point in places.items()}
+
+    async def geocode(self, query: str) -> Optional[GeoPoint]:
+        point = self.places.get(query.strip().lower())
+        return GeoPoint(point[0], point[1], query.strip()) if point else None
+
+
+class NominatimGeocoder:
+    """OpenStreetMap Nominatim search (one result, best match first)."""
+
+    def __init__(self, base_url: str = NOMINATIM_URL, timeout: float = 10.0):
+        self.base_url = base_url.rstrip("/")
+        self.timeout = timeout
+
+    async def geocode(self, query: str) -> Optional[GeoPoint]:
+        import httpx
+
+        async with httpx.AsyncClient(timeout=self.timeout, headers={"User-Agent": USER_AGENT}) as client:
+            response = await client.get(f"{self.base_url}/search", params={"q": query, "format": "json", "limit": 1})
+            response.raise_for_status()
+            results = response.json()
+        if not results:
+            return None
+        best = results[0]
+        return GeoPoint(float(best["lat"]), float(best["lon"]), best.get("display_name", ""))
+
+
+class CachedGeocoder:
+    """Wraps a geocoder with a JSON cache; misses are cached too so unknown places aren't retried."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "geocode_cache.json"
+
+    def __init__(self, inner, path: Optional[Path] = None):
+        self.inner = inner
+        self.path = Path(path) if path else self.DEFAULT_PATH
+        self._cache: Optional[Dict[str, Optional[List]]] = None
+
+    def _load(self) -> Dict[str, Optional[List]]:
+        if self._cache is None:
+            try:
+                self._cache = json.loads(self.path.read_text())
+            except (OSError, ValueError):
+                self._cache = {}
+        return self._cache
+
+    async def geocode(self, query: str) -> Optional[GeoPoint]:
+        key = " ".join(query.lower().split())
+        cache = self._load()
+        if key in cache:
+            hit = cache[key]
+            return GeoPoint(*hit) if hit else None
+        point = await self.inner.geocode(query)  # Errors propagate uncached, so they're retried
+        cache[key] = [point.latitude, point.longitude, point.label] if point else None
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps(cache, indent=2))
+        return point
+
+
+@dataclass
+class LocationSettings:
+    """config.geocoder, geocoder_url, map_provider and travel_speed_kmh."""
+    geocoder: str = "nominatim"
+    geocoder_url: Optional[str] = None
+    map_provider: str = "openstreetmap"
+    speed_kmh: float = DEFAULT_SPEED_KMH
+
+    @classmethod
+    def from_config(cls, config) -> "LocationSettings":
+        provider = getattr(config, "map_provider", "openstreetmap")
+        return cls(geocoder=(getattr(config, "geocoder", "nominatim") or "none").lower(),
+                   geocoder_url=getattr(config, "geocoder_url", None),
+                   map_provider=provider if provider in MAP_PROVIDERS else "openstreetmap",
+                   speed_kmh=getattr(config, "travel_speed_kmh", DEFAULT_SPEED_KMH) or DEFAULT_SPEED_KMH)
+
+    def make_geocoder(self) -> Optional[Any]:
+        """The configured geocoder (cached), or None when geocoding is off."""
+        if self.geocoder == "nominatim":
+            return CachedGeocoder(NominatimGeocoder(self.geocoder_url or NOMINATIM_URL))
+        if self.geocoder != "none":
+            logger.warning(f"Unknown geocoder {self.geocoder!r}, geocoding is off")
+        return None
+
+
+_settings: Optional[LocationSettings] = None
+
+
+def get_location_settings() -> LocationSettings:
+    """Get the global location settings (defaults until configured)."""
+    global _settings
+    if _settings is None:
+        _settings = LocationSettings()
+    return _settings
+
+
+def set_location_settings(settings: LocationSettings) -> None:
+    """Install the settings built from the loaded Config (called at startup)."""
+    global _settings
+    _settings = settings
+
+
+def map_link(location: str, latitude: Optional[float] = None, longitude: Optional[float] = None,
+             provider: Optional[str] = None) -> str:
+    """A map URL: a pin when coordinates are known, else a search for the location text."""
+    provider = provider or get_location_settings().map_provider
+    if latitude is not None and longitude is not None:
+        return {
+            "google": f"https://www.google.com/maps/search/?api=1&query={latitude},{longitude}",
+            "apple": f"https://maps.apple.com/?ll={latitude},{longitude}&q={quote_plus(location)}",
+        }.get(provider, f"https://www.openstreetmap.org/?mlat={latitude}&mlon={longitude}#map=17/{latitude}/{longitude}")
+    return {
+        "google": f"https://www.google.com/maps/search/?api=1&query={quote_plus(location)}",
+        "apple": f"https://maps.apple.com/?q={quote_plus(location)}",
+    }.get(provider, f"https://www.openstreetmap.org/search?query={quote_plus(location)}")
+
+
+def event_map_link(event, provider: Optional[str] = None) -> Optional[str]:
+    """Map link for a CalendarEvent's location, or None without one."""
+    if not event.location:
+        return None
+    return map_link(event.location, event.latitude, event.longitude, provider)
+
+
+def distance_km(a: GeoPoint, b: GeoPoint) -> float:
+    """Great-circle (haversine) distance."""
+    lat1, lon1, lat2, lon2 = map(math.radians, (a.latitude, a.longitude, b.latitude, b.longitude))
+    h = math.sin((lat2 - lat1) / 2) ** 2 + math.cos(lat1) * math.cos(lat2) * math.sin((lon2 - lon1) / 2) ** 2
+    return 2 * 6371.0 * math.asin(math.sqrt(h))
+
+
+def travel_minutes(a: GeoPoint, b: GeoPoint, speed_kmh: Optional[float] = None) -> int:
+    """Rough door-to-door time between two points (0 for the same place)."""
+    speed_kmh = speed_kmh or get_location_settings().speed_kmh
+    km = distance_km(a, b)
+    if km < SAME_PLACE_KM:
+        return 0
+    return DEPARTURE_MINUTES + math.ceil(km * ROAD_FACTOR / max(speed_kmh, 1.0) * 60)
+
+
+def event_point(event) -> Optional[GeoPoint]:
+    if event.latitude is None or event.longitude is None:
+        return None
+    return GeoPoint(event.latitude, event.longitude, event.location)
+
+
+def travel_conflicts(events: Iterable[Any], speed_kmh: Optional[float] = None) -> List[TravelConflict]:
+    """
+    Consecutive geocoded events (by start time, same day) where getting from
+    one to the next takes longer than the gap. Events without coordinates
+    are skipped rather than guessed at.
+    """
+    located = sorted((e for e in events if event_point(e)), key=lambda e: e.start_time)
+    conflicts = []
+    for before, after in zip(located, located[1:]):
+        if before.start_time[:10] != after.start_time[:10]:
+            continue
+        gap = datetime.fromisoformat(after.start_time) - datetime.fromisoformat(before.end_time)
+        needed = travel_minutes(event_point(before), event_point(after), speed_kmh)
+        if needed and gap < timedelta(minutes=needed):
+            conflicts.append(TravelConflict(
+                before=before, after=after, gap_minutes=int(gap.total_seconds() // 60),
+                travel_minutes=needed, distance_km=distance_km(event_point(before), event_point(after)),
+            ))
+    return conflicts
+
+
+async def geocode_events(planner, geocoder, limit: int = 20) -> int:
+    """
+    Look up coordinates for events that have a location but none yet.
+    Returns how many were located; places the geocoder doesn't know are
+    left without coordinates (and, with CachedGeocoder, not asked again).
+    """
+    located = 0
+    pending = [e for e in planner.get_calendar_events(expand_recurring=False)
+               if e.location and e.latitude is None]
+    for event in pending[:limit]:
+        point = await geocoder.geocode(event.location)
+        if point:
+            planner.set_event_coordinates(event.id, point.latitude, point.longitude)
+            located += 1
+    return located
@@ -0,0 +1,202 @@
+"""
+Resource Governor - Keep xswarm's background work out of the user's way.
+
+Samples xswarm's own CPU and resident memory (the app plus child processes
+such as the voice server) and the machine's overall load. While the machine
+is busy, or xswarm is over its own ceilings, non-critical background jobs
+are deferred:
+
+- document indexing
+- memory consolidation
+- calendar sync / checks, planning sync
+- inbox sync
+
+Everything else (reminders, call screening, user-initiated tools) always
+runs. A deferred job runs anyway once it has waited MAX_DEFER seconds, so a
+machine that's busy all day still gets its calendar synced.
+
+Throttling uses hysteresis: it starts above a ceiling and ends once usage
+drops below RELEASE_RATIO of it, so jobs don't flap at the boundary.
+
+Config (see config.py): governor_cpu_ceiling, governor_rss_ceiling_mb,
+governor_busy_cpu_percent.
+"""
+
+import logging
+import os
+import time
+from dataclasses import dataclass
+from typing import Callable, Dict, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+# Background jobs that may be deferred while the machine is busy
+NON_CRITICAL_JOBS = {
+    "document_indexing",
+    "memory_consolidation",
+    "calendar_check",
+    "calendar_sync",
+    "planning_sync",
+    "inbox_sync",
+    "geocode_locations",
+}
+
+MAX_DEFER = 30 * 60     # Seconds a job can be held back before it runs regardless
+RELEASE_RATIO = 0.8     # Stop throttling once usage is below 80% of the ceiling that started it
+SYSTEM_MEMORY_BUSY = 90.0  # System RAM % that counts as busy
+
+
+@dataclass
+class ResourceUsage:
+    cpu_percent: float = 0.0           # xswarm incl. child processes, % of one core
+    rss_mb: float = 0.0                # xswarm incl. child processes
+    system_cpu_percent: float = 0.0    # Whole machine, % of all cores
+    system_memory_percent: float = 0.0
+
+
+class _PsutilSampler:
+    """Measures this process tree and the machine with psutil (keeps Process objects for CPU deltas)."""
+
+    def __init__(self):
+        self._processes: Dict[int, object] = {}
+
+    def __call__(self) -> ResourceUsage:
+        import psutil
+
+        root = psutil.Process(os.getpid())
+        try:
+            tree = [root] + root.children(recursive=True)
+        except psutil.Error:
+            tree = [root]
+
+        cpu = rss = 0.0
+        alive = set()
+        for proc in tree:
+            # Reuse the Process object so cpu_percent() measures since the last sample
+            proc = self._processes.setdefault(proc.pid, proc)
+            alive.add(proc.pid)
+            try:
+                cpu += proc.cpu_percent(interval=None)
+                rss += proc.memory_info().rss
+            except psutil.Error:
+                continue
+        self._processes = {pid: p for pid, p in self._processes.items() if pid in alive}
+
+        return ResourceUsage(
+            cpu_percent=cpu,
+            rss_mb=rss / (1024 * 1024),
+            system_cpu_percent=psutil.cpu_percent(interval=None),
+            system_memory_percent=psutil.virtual_memory().percent,
+        )
+
+
+class ResourceGovernor:
+    """Decides whether non-critical background jobs may run right now."""
+
+    def __init__(
+        self,
+        cpu_ceiling: float = 50.0,
+        rss_ceiling_mb: Optional[float] = 8192,
+        busy_cpu_percent: float = 80.0,
+        sampler: Optional[Callable[[], ResourceUsage]] = None,
+        clock: Callable[[], float] = time.monotonic,
+        sample_interval: float = 5.0,
+    ):
+        self.cpu_ceiling = cpu_ceiling
+        self.rss_ceiling_mb = rss_ceiling_mb
+        self.busy_cpu_percent = busy_cpu_percent
+        self._sampler = sampler or _PsutilSampler()
+        self._clock = clock
+        self.sample_interval = sample_interval
+        self.usage = ResourceUsage()
+        self._sampled_at: Optional[float] = None
+        self._reasons: List[str] = []
+        self._deferred_since: Dict[str, float] = {}
+        self.deferrals: Dict[str, int] = {}  # Times each job was held back
+
+    @classmethod
+    def from_config(cls, config) -> "ResourceGovernor":
+        return cls(
+            cpu_ceiling=getattr(config, "governor_cpu_ceiling", 50.0),
+            rss_ceiling_mb=getattr(config, "governor_rss_ceiling_mb", 8192),
+            busy_cpu_percent=getattr(config, "governor_busy_cpu_percent", 80.0),
+        )
+
+    def sample(self) -> ResourceUsage:
+        """Measure usage now and update the throttle state."""
+        try:
+            self.usage = self._sampler()
+        except Exception as e:
+            logger.debug(f"Resource sampling failed: {e}")
+            return self.usage
+        self._sampled_at = self._clock()
+        self._reasons = self._evaluate(self.usage, releasing=bool(self._reasons))
+        return self.usage
+
+    def _evaluate(self, usage: ResourceUsage, releasing: bool) -> List[str]:
+        # Already throttled: keep going until usage is clearly back under each ceiling
+        scale = RELEASE_RATIO if releasing else 1.0
+        checks: List[Tuple[bool, str]] = [
+            (usage.system_cpu_percent > self.busy_cpu_percent * scale, f"machine busy (CPU {usage.system_cpu_percent:.0f}%)"),
+            (usage.system_memory_percent > SYSTEM_MEMORY_BUSY * scale, f"machine low on memory ({usage.system_memory_percent:.0f}% used)"),
+            (usage.cpu_percent > self.cpu_ceiling * scale, f"xswarm CPU {usage.cpu_percent:.0f}%"),
+            (self.rss_ceiling_mb is not None and usage.rss_mb > self.rss_ceiling_mb * scale, f"xswarm memory {usage.rss_mb:.0f}MB"),
+        ]
+        return [reason for over, reason in checks if over]
+
+    def _refresh(self) -> None:
+        if self._sampled_at is None or self._clock() - self._sampled_at >= self.sample_interval:
+            self.sample()
+
+    @property
+    def throttled(self) -> bool:
+        return bool(self._reasons)
+
+    @property
+    def reasons(self) -> List[str]:
+        return list(self._reasons)
+
+    def allow(self, job: str) -> bool:
+        """Whether `job` may run now. Records a deferral when it may not."""
+        if job not in NON_CRITICAL_JOBS:
+            return True
+        self._refresh()
+        now = self._clock()
+        if not self.throttled:
+            self._deferred_since.pop(job, None)
+            return True
+
+        since = self._deferred_since.setdefault(job, now)
+        if now - since >= MAX_DEFER:
+            logger.info(f"Running {job} despite load: deferred for {int(now - since)}s")
+            self._deferred_since.pop(job, None)
+            return True
+
+        if since == now:
+            logger.debug(f"Deferring {job}: {', '.join(self._reasons)}")
+        self.deferrals[job] = self.deferrals.get(job, 0) + 1
+        return False
+
+    def status_line(self) -> str:
+        """Compact usage for the dashboard header, e.g. "⚙ 4% CPU · 612MB" (+ "⏸ throttled")."""
+        line = f"⚙ {self.usage.cpu_percent:.0f}% CPU · {self.usage.rss_mb:.0f}MB"
+        if self.throttled:
+            line += " ⏸ throttled"
+        return line
+
+
+_governor: Optional[ResourceGovernor] = None
+
+
+def get_resource_governor() -> ResourceGovernor:
+    """Get the global resource governor (defaults until configured)."""
+    global _governor
+    if _governor is None:
+        _governor = ResourceGovernor()
+    return _governor
+
+
+def set_resource_governor(governor: ResourceGovernor) -> None:
+    """Install the governor built from the loaded Config (called at startup)."""
+    global _governor
+    _governor = governor
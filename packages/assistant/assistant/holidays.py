@@ -0,0 +1,245 @@
+"""
+Holidays - Public holidays and observances, bundled per region.
+
+REGIONS holds the rules for each region config.holiday_region names (US,
+GB, CA, AU; "" turns holidays off), so nothing is fetched:
+
+    "12-25"           a fixed date
+    "11/Thu/4"        the 4th Thursday of November (-1 is the last one)
+    "05/Mon/<=24"     the last Monday on or before May 24 (Victoria Day)
+    "easter-2"        days from Easter Sunday (Good Friday)
+
+A holiday is either a day off or an observance (Mother's Day, Halloween).
+A fixed day off that falls on a weekend is also off on the weekday it's
+observed on: the nearest one in the US, the next free one elsewhere
+(Christmas on a Saturday, Boxing Day on a Sunday: Monday and Tuesday).
+config.custom_holidays adds the user's own days off, by "MM-DD" every
+year or "YYYY-MM-DD" once.
+
+The calendar is used three ways:
+- add_calendar_event and add_recurring_meeting warn "⚠ That's Thanksgiving"
+- the wake-up briefing and announcements say what's coming ({holidays})
+- recurring events with skip_holidays leave out occurrences due on a day
+  off (calendar_core.expand_recurrence), e.g. a work standup
+"""
+
+import logging
+import re
+from dataclasses import dataclass
+from datetime import date, timedelta
+from functools import lru_cache
+from typing import Dict, List, Optional, Set, Tuple
+
+from .capabilities import register_capability
+from .verbalize import verbalize_date
+
+logger = logging.getLogger(__name__)
+
+UPCOMING_DAYS = 7  # Holidays the briefing mentions, from today
+WEEKDAY_NAMES = ("Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun")
+OFF, OBSERVANCE = True, False
+
+_FIXED = re.compile(r"^(?P<month>\d{2})-(?P<day>\d{2})$")
+_NTH = re.compile(r"^(?P<month>\d{2})/(?P<weekday>[A-Z][a-z]{2})/(?:(?P<n>-?\d)|<=(?P<before>\d{2}))$")
+_EASTER = re.compile(r"^easter(?P<offset>[+-]\d+)?$")
+
+REGIONS: Dict[str, List[Tuple[str, str, bool]]] = {
+    "US": [
+        ("New Year's Day", "01-01", OFF),
+        ("Martin Luther King Jr. Day", "01/Mon/3", OFF),
+        ("Valentine's Day", "02-14", OBSERVANCE),
+        ("Presidents' Day", "02/Mon/3", OFF),
+        ("Easter", "easter", OBSERVANCE),
+        ("Mother's Day", "05/Sun/2", OBSERVANCE),
+        ("Memorial Day", "05/Mon/-1", OFF),
+        ("Father's Day", "06/Sun/3", OBSERVANCE),
+        ("Juneteenth", "06-19", OFF),
+        ("Independence Day", "07-04", OFF),
+        ("Labor Day", "09/Mon/1", OFF),
+        ("Columbus Day", "10/Mon/2", OBSERVANCE),
+        ("Halloween", "10-31", OBSERVANCE),
+        ("Veterans Day", "11-11", OBSERVANCE),
+        ("Thanksgiving", "11/Thu/4", OFF),
+        ("Christmas Eve", "12-24", OBSERVANCE),
+        ("Christmas Day", "12-25", OFF),
+        ("New Year's Eve", "12-31", OBSERVANCE),
+    ],
+    "GB": [
+        ("New Year's Day", "01-01", OFF),
+        ("Mothering Sunday", "easter-21", OBSERVANCE),
+        ("Good Friday", "easter-2", OFF),
+        ("Easter", "easter", OBSERVANCE),
+        ("Easter Monday", "easter+1", OFF),
+        ("Early May bank holiday", "05/Mon/1", OFF),
+        ("Spring bank holiday", "05/Mon/-1", OFF),
+        ("Father's Day", "06/Sun/3", OBSERVANCE),
+        ("Summer bank holiday", "08/Mon/-1", OFF),
+        ("Halloween", "10-31", OBSERVANCE),
+        ("Bonfire Night", "11-05", OBSERVANCE),
+        ("Christmas Day", "12-25", OFF),
+        ("Boxing Day", "12-26", OFF),
+    ],
+    "CA": [
+        ("New Year's Day", "01-01", OFF),
+        ("Good Friday", "easter-2", OFF),
+        ("Easter", "easter", OBSERVANCE),
+        ("Mother's Day", "05/Sun/2", OBSERVANCE),
+        ("Victoria Day", "05/Mon/<=24", OFF),
+        ("Father's Day", "06/Sun/3", OBSERVANCE),
+        ("Canada Day", "07-01", OFF),
+        ("Civic Holiday", "08/Mon/1", OBSERVANCE),
+        ("Labour Day", "09/Mon/1", OFF),
+        ("National Day for Truth and Reconciliation", "09-30", OBSERVANCE),
+        ("Thanksgiving", "10/Mon/2", OFF),
+        ("Halloween", "10-31", OBSERVANCE),
+        ("Remembrance Day", "11-11", OBSERVANCE),
+        ("Christmas Day", "12-25", OFF),
+        ("Boxing Day", "12-26", OFF),
+    ],
+    "AU": [
+        ("New Year's Day", "01-01", OFF),
+        ("Australia Day", "01-26", OFF),
+        ("Good Friday", "easter-2", OFF),
+        ("Easter", "easter", OBSERVANCE),
+        ("Easter Monday", "easter+1", OFF),
+        ("Anzac Day", "04-25", OFF),
+        ("Mother's Day", "05/Sun/2", OBSERVANCE),
+        ("King's Birthday", "06/Mon/2", OFF),
+        ("Father's Day", "09/Sun/1", OBSERVANCE),
+        ("Christmas Day", "12-25", OFF),
+        ("Boxing Day", "12-26", OFF),
+    ],
+}
+REGION_ALIASES = {"USA": "US", "UK": "GB", "ENGLAND": "GB", "CANADA": "CA", "AUSTRALIA": "AU"}
+
+
+@dataclass(frozen=True)
+class Holiday:
+    day: date
+    name: str
+    day_off: bool = True
+
+
+def easter(year: int) -> date:
+    """Easter Sunday (Gregorian, the anonymous algorithm)."""
+    a, b, c = year % 19, year // 100, year % 100
+    d, e = divmod(b, 4)
+    g = (8 * b + 13) // 25
+    h = (19 * a + b - d - g + 15) % 30
+    i, k = divmod(c, 4)
+    shift = (32 + 2 * e + 2 * i - h - k) % 7
+    m = (a + 11 * h + 19 * shift) // 433
+    month = (h + shift - 7 * m + 90) // 25
+    return date(year, month, (h + shift - 7 * m + 33 * month + 19) % 32)
+
+
+def rule_date(rule: str, year: int) -> date:
+    """The day a rule ("12-25", "11/Thu/4", "05/Mon/<=24", "easter-2") falls on in `year`."""
+    fixed, nth, from_easter = _FIXED.match(rule), _NTH.match(rule), _EASTER.match(rule)
+    if fixed:
+        return date(year, int(fixed.group("month")), int(fixed.group("day")))
+    if from_easter:
+        return easter(year) + timedelta(days=int(from_easter.group("offset") or 0))
+    if not nth:
+        raise ValueError(f"Unknown holiday rule: {rule}")
+    month, weekday = int(nth.group("month")), WEEKDAY_NAMES.index(nth.group("weekday"))
+    if nth.group("before"):
+        latest = date(year, month, int(nth.group("before")))
+        return latest - timedelta(days=(latest.weekday() - weekday) % 7)
+    n = int(nth.group("n"))
+    if n > 0:
+        first = date(year, month, 1)
+        return first + timedelta(days=(weekday - first.weekday()) % 7 + 7 * (n - 1))
+    last = date(year + month // 12, month % 12 + 1, 1) - timedelta(days=1)
+    return last - timedelta(days=(last.weekday() - weekday) % 7 + 7 * (-n - 1))
+
+
+class HolidayCalendar:
+    """The holidays of one region (None for none) plus the user's own days off."""
+
+    def __init__(self, region: Optional[str] = "US", custom: Optional[Dict[str, str]] = None):
+        region = (region or "").strip().upper()
+        self.region = REGION_ALIASES.get(region, region) or None
+        if self.region and self.region not in REGIONS:
+            logger.warning(f"No holidays for region {region!r} (known: {', '.join(REGIONS)})")
+            self.region = None
+        self.custom = dict(custom or {})
+
+    @classmethod
+    def from_config(cls, config) -> "HolidayCalendar":
+        return cls(getattr(config, "holiday_region", "US"), getattr(config, "custom_holidays", None))
+
+    def year(self, year: int) -> List[Holiday]:
+        """Every holiday of `year`, observed days included, by date."""
+        return list(_year(self.region, tuple(sorted(self.custom.items())), year))
+
+    def between(self, start: date, end: date) -> List[Holiday]:
+        """The holidays from `start` to `end` (inclusive), by date."""
+        return [h for year in range(start.year, end.year + 1) for h in self.year(year) if start <= h.day <= end]
+
+    def on(self, day: date) -> List[Holiday]:
+        return self.between(day, day)
+
+    def days_off(self, start: date, end: date) -> Set[str]:
+        """The days off from `start` to `end`, as YYYY-MM-DD."""
+        return {h.day.isoformat() for h in self.between(start, end) if h.day_off}
+
+
+@lru_cache(maxsize=32)
+def _year(region: Optional[str], custom: Tuple[Tuple[str, str], ...], year: int) -> Tuple[Holiday, ...]:
+    holidays = [Holiday(rule_date(rule, year), name, day_off) for name, rule, day_off in REGIONS.get(region, [])]
+    for when, name in custom:
+        try:
+            day = date.fromisoformat(when) if len(when) == 10 else rule_date(when, year)
+        except ValueError:
+            logger.warning(f"Skipping custom holiday {when!r}: not MM-DD or YYYY-MM-DD")
+            continue
+        if day.year == year:
+            holidays.append(Holiday(day, name))
+    # A fixed day off on a weekend is also off on a weekday
+    taken = {h.day for h in holidays if h.day_off}
+    for name, rule, day_off in REGIONS.get(region, []):
+        day = rule_date(rule, year)
+        if not day_off or not _FIXED.match(rule) or day.weekday() < 5:
+            continue
+        if region == "US":
+            observed = day - timedelta(days=1) if day.weekday() == 5 else day + timedelta(days=1)
+        else:
+            observed = day + timedelta(days=7 - day.weekday())
+            while observed in taken or observed.weekday() >= 5:
+                observed += timedelta(days=1)
+        taken.add(observed)
+        holidays.append(Holiday(observed, f"{name} (observed)"))
+    return tuple(sorted(holidays, key=lambda h: h.day))
+
+
+def upcoming_holidays(today: date, calendar: Optional[HolidayCalendar] = None, days: int = UPCOMING_DAYS) -> str:
+    """"Thursday is Thanksgiving." for the holidays in the coming `days`; empty when there are none."""
+    holidays = (calendar or get_holiday_calendar()).between(today, today + timedelta(days=days))
+    said = [f"{verbalize_date(h.day, today)} is {h.name}" for h in holidays]
+    if not said:
+        return ""
+    text = said[0] if len(said) == 1 else ", ".join(said[:-1]) + " and " + said[-1]
+    return f"{text[:1].upper()}{text[1:]}."
+
+
+_calendar: Optional[HolidayCalendar] = None
+
+
+def get_holiday_calendar() -> HolidayCalendar:
+    """Get the global holiday calendar (US holidays until configured)."""
+    global _calendar
+    if _calendar is None:
+        _calendar = HolidayCalendar()
+    return _calendar
+
+
+def set_holiday_calendar(calendar: HolidayCalendar) -> None:
+    """Install the calendar built from the loaded Config (called at startup)."""
+    global _calendar
+    _calendar = calendar
+
+
+register_capability("Holidays", "Warn about booking on a holiday, mention upcoming ones, skip them for work meetings",
+                    ["what holidays are coming up?", "don't have standup on holidays"], category="Planning",
+                    keywords=("thanksgiving", "christmas", "bank holiday", "day off"))
@@ -0,0 +1,227 @@
+"""
+Household - Profiles for the people sharing the assistant, each with a capability scope.
+
+The account holder is the owner profile (named config.user_name, or
+"owner") and can do everything. config.household_profiles adds the rest of
+the household, e.g.
+
+    {"Emma": {"allow": ["read", "create"], "cloud_ai": false, "speakers": ["emma"]}}
+
+- allow: action classes (confirmation.py: read, create, modify, complete,
+  delete, communicate, ...) or tool names the profile may use; left out, it
+  may use everything not denied
+- deny: classes or tool names it may not use, whatever allow says
+- cloud_ai: false keeps its questions on the local model so they don't
+  spend the AI provider budget (latency.py skips the cloud rungs; typed
+  chat, which only has a cloud model, is refused)
+- speakers: labels a speaker identification source reports for this person
+  (the owner's are config.household_owner_speakers)
+
+ToolRegistry.execute_tool asks the active profile first (refusal()) and only
+then applies the confirmation policy, so a scoped-out call is refused
+before anyone is asked to confirm it. Drafting a text or email reply needs
+the "communicate" class too. Timers, alarms and the emergency phrase aren't
+tools and work for everyone.
+
+Switching profiles:
+- by voice: "this is Emma", "I'm Alex", "switch to Emma's profile". Moving
+  to a profile that may do something the current one can't (back to the
+  owner, say) takes the confirmation PIN (set_confirmation_pin); with no
+  PIN set it can't be done by voice. Moving to a narrower profile is free.
+- by speaker identification: whatever identifies the voice posts the
+  speaker's label (local API POST /speaker) and identify() switches to the
+  profile that lists it, without a PIN.
+
+The active profile isn't saved: xswarm starts as
+config.household_start_profile (the owner by default).
+"""
+
+import logging
+import re
+import time
+from dataclasses import dataclass, field
+from typing import Any, Callable, Dict, List, Optional, Set
+
+from .capabilities import register_capability
+from .confirmation import DEFAULT_LEVELS, PENDING_TIMEOUT, classify_action, hash_pin, spoken_pin
+
+logger = logging.getLogger(__name__)
+
+OWNER = "owner"
+ACTION_CLASSES = tuple(DEFAULT_LEVELS)
+MAX_PIN_ATTEMPTS = 3
+
+_SWITCH = re.compile(
+    r"^\W*(?:this\s+is|it'?s|i\s+am|i'?m|switch\s+(?:profile\s+)?to(?:\s+the)?)\s+([\w' ]+?)"
+    r"(?:'s)?(?:\s+profile)?\W*$", re.IGNORECASE)
+
+
+class HouseholdError(Exception):
+    """The household profiles in the config can't be used."""
+
+
+@dataclass
+class Profile:
+    name: str
+    allow: Optional[Set[str]] = None  # None: everything not denied
+    deny: Set[str] = field(default_factory=set)
+    cloud_ai: bool = True
+    speakers: List[str] = field(default_factory=list)
+    owner: bool = False
+
+    def allows(self, tool_name: str) -> bool:
+        """Whether this profile may run the tool: its name, then its action class."""
+        if self.owner:
+            return True
+        action_class = tool_name if tool_name in ACTION_CLASSES else classify_action(tool_name)
+        if tool_name in self.deny or (action_class in self.deny and not (self.allow and tool_name in self.allow)):
+            return False
+        return self.allow is None or tool_name in self.allow or action_class in self.allow
+
+    def covers(self, other: "Profile") -> bool:
+        """Whether this profile may do everything `other` may (so switching to `other` needs no PIN)."""
+        if self.owner or self is other:
+            return True
+        if other.owner or (other.cloud_ai and not self.cloud_ai):
+            return False
+        named = set(ACTION_CLASSES) | (self.allow or set()) | self.deny | (other.allow or set()) | other.deny
+        return all(self.allows(name) or not other.allows(name) for name in named)
+
+
+@dataclass
+class PendingSwitch:
+    profile: Profile
+    created_at: float
+    attempts: int = 0
+
+
+class Household:
+    """The profiles and which one is active."""
+
+    def __init__(self, profiles: Optional[List[Profile]] = None, start: str = "",
+                 clock: Callable[[], float] = time.monotonic):
+        self.profiles = profiles or [Profile(OWNER, owner=True)]
+        self.owner = next(p for p in self.profiles if p.owner)
+        self.active = self.find(start) or self.owner
+        self.pending: Optional[PendingSwitch] = None
+        self.clock = clock
+
+    @classmethod
+    def from_config(cls, config) -> "Household":
+        profiles = [Profile(getattr(config, "user_name", None) or OWNER, owner=True,
+                            speakers=[str(s).lower() for s in getattr(config, "household_owner_speakers", None) or []])]
+        for name, raw in (getattr(config, "household_profiles", None) or {}).items():
+            allow = raw.get("allow")
+            for key in ("allow", "deny"):
+                unknown = [s for s in raw.get(key) or [] if not re.fullmatch(r"[a-z_]+", str(s))]
+                if unknown:
+                    raise HouseholdError(f"Household profile '{name}': '{unknown[0]}' in {key} is neither an "
+                                         f"action class ({', '.join(ACTION_CLASSES)}) nor a tool name")
+            if name.lower() in (p.name.lower() for p in profiles) or name.lower() == OWNER:
+                raise HouseholdError(f"Household profile '{name}' is defined twice (or clashes with the owner)")
+            profiles.append(Profile(name, set(allow) if allow is not None else None, set(raw.get("deny") or []),
+                                    bool(raw.get("cloud_ai", True)), [str(s).lower() for s in raw.get("speakers") or []]))
+        start = getattr(config, "household_start_profile", "") or ""
+        household = cls(profiles, start)
+        if start and household.find(start) is None:
+            raise HouseholdError(f"household_start_profile '{start}' isn't one of the household profiles")
+        return household
+
+    def find(self, name: str) -> Optional[Profile]:
+        """The profile by name (any case); "owner" always names the owner."""
+        name = (name or "").strip().lower()
+        if name == OWNER:
+            return next(p for p in self.profiles if p.owner)
+        return next((p for p in self.profiles if p.name.lower() == name), None)
+
+    def refusal(self, tool_name: str) -> Optional[str]:
+        """Why the active profile can't run this tool, or None when it can."""
+        if self.active.allows(tool_name):
+            return None
+        return f"{self.active.name}'s profile isn't allowed to {tool_name.replace('_', ' ')} - ask {self.owner.name}."
+
+    def allows_class(self, action_class: str) -> bool:
+        """Whether the active profile may do this kind of thing at all (flows that aren't tool calls)."""
+        return self.active.allows(action_class)
+
+    def identify(self, speaker: str) -> Optional[Profile]:
+        """Switch to the profile that lists this speaker label; None when nobody does."""
+        label = (speaker or "").strip().lower()
+        profile = next((p for p in self.profiles if label and label in p.speakers), None)
+        if profile is not None and profile is not self.active:
+            self._switch(profile, "speaker identified")
+        return profile
+
+    def heard(self, text: str, pin_hash: Optional[str]) -> Optional[str]:
+        """
+        The reply to a profile switch ("this is Emma") or to the PIN it asked
+        for; None when the text is neither (a pending PIN request is then dropped).
+        """
+        if self.pending is not None:
+            return self._resolve_pin(text, pin_hash)
+        match = _SWITCH.match(text or "")
+        profile = self.find(match.group(1)) if match else None
+        if profile is None:
+            return None
+        if profile is self.active:
+            return f"You're already on {profile.name}'s profile."
+        if self.active.covers(profile):
+            self._switch(profile, "switched by voice")
+            return f"Hi {profile.name}. Switched to your profile."
+        if not pin_hash:
+            return (f"Switching to {profile.name}'s profile needs the confirmation PIN, and none is set. "
+                    f"{self.owner.name} can set one.")
+        self.pending = PendingSwitch(profile, self.clock())
+        return f"Say the PIN to switch to {profile.name}'s profile."
+
+    def _resolve_pin(self, text: str, pin_hash: Optional[str]) -> Optional[str]:
+        pending = self.pending
+        if self.clock() - pending.created_at > PENDING_TIMEOUT:
+            self.pending = None
+            return None
+        pin = spoken_pin(text)
+        if not pin:
+            self.pending = None  # Anything else moves the conversation on
+            return None
+        if pin_hash and hash_pin(pin) == pin_hash:
+            self.pending = None
+            self._switch(pending.profile, "switched by voice with the PIN")
+            return f"Hi {pending.profile.name}. Switched to your profile."
+        pending.attempts += 1
+        if pending.attempts >= MAX_PIN_ATTEMPTS:
+            self.pending = None
+            return f"Wrong PIN too many times. Staying on {self.active.name}'s profile."
+        return "That PIN didn't match. Try again."
+
+    def _switch(self, profile: Profile, how: str) -> None:
+        from .events import record_event
+        previous, self.active = self.active, profile
+        logger.info(f"Household profile: {previous.name} -> {profile.name} ({how})")
+        record_event("activity", f"Profile switched to {profile.name} ({how})", {"level": "info"})
+
+    def describe(self) -> Dict[str, Any]:
+        """The active profile and its scope, for status displays."""
+        active = self.active
+        return {"profile": active.name, "owner": active.owner, "cloud_ai": active.cloud_ai,
+                "allow": sorted(active.allow) if active.allow is not None else None, "deny": sorted(active.deny)}
+
+
+_household: Optional[Household] = None
+
+
+def get_household() -> Household:
+    """Get the household (just the owner until startup installs one from the config)."""
+    global _household
+    if _household is None:
+        _household = Household()
+    return _household
+
+
+def set_household(household: Optional[Household]) -> None:
+    global _household
+    _household = household
+
+
+register_capability("Household profiles", "A profile for each person in the house, limited to what they may do",
+                    ["this is Emma", "switch to the owner profile"], requires=("household_profiles",),
+                    category="Settings", keywords=("family", "kids", "profile", "users"))
@@ -0,0 +1,220 @@
+"""
+ICS Feed - The calendar made with xswarm, for Apple or Google Calendar to subscribe to.
+
+Events created through the assistant (the planner calendar, CalendarEvent)
+are published as a read-only iCalendar feed, so they show up in the
+user's usual calendar app without two-way sync. config.ics_feed picks
+where the feed lives:
+
+- "local":  served by the local API (local_api.py, started for it) at
+            http://127.0.0.1:<local_api_port>/calendar/<token>.ics - for a
+            calendar app on this machine
+- "server": uploaded by the "ics_feed" job when it changes
+            (PUT /api/calendar/feed) and served at
+            <server_url>/ics/<token>.ics - for Google Calendar and phones
+- "":       off (the default)
+
+The token in the URL is the only thing keeping the feed private: anyone
+with the URL can read it. `xswarm dev ics url` prints it, and `xswarm dev
+ics regenerate` makes a new token (the server drops the old feed with the
+next upload), after which calendars subscribed to the old URL stop
+updating. `xswarm dev ics unpublish` deletes the uploaded feed.
+
+The feed covers PAST_DAYS back to FUTURE_DAYS ahead, recurring events as
+their occurrences (holidays and single-occurrence changes applied, as in
+the dashboard). Events are shared at their visibility (calendar_core.py):
+a "busy" one appears as "Busy" with its time only, a "private" one not at
+all, and events without one follow config.shared_visibility. Times are
+"floating" (no time zone), so they show at the same clock time the
+assistant has them.
+
+Storage: ~/.xswarm/ics_feed_token (owner-only)
+"""
+
+import hashlib
+import logging
+import secrets
+from dataclasses import asdict
+from datetime import date, datetime, timedelta, timezone
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional
+
+from . import endpoints
+from .calendar_core import shared_view, visibility
+from .local_api import load_token
+
+logger = logging.getLogger(__name__)
+
+FEED_TOKEN_PATH = Path.home() / ".xswarm" / "ics_feed_token"
+MODES = ("local", "server")
+PAST_DAYS = 30
+FUTURE_DAYS = 365
+PRODUCT_ID = "-//xswarm//xswarm assistant//EN"
+LINE_OCTETS = 75  # RFC 5545 lines fold after this many bytes
+
+_published: Dict[str, str] = {}  # Token -> digest of the last feed uploaded under it
+
+
+def feed_token(rotate: bool = False, create: bool = True, path: Optional[Path] = None) -> Optional[str]:
+    """The feed URL's secret, made on first use; `rotate` replaces it."""
+    return load_token(path or FEED_TOKEN_PATH, rotate=rotate, create=create)
+
+
+def feed_url(config, token: str) -> str:
+    if getattr(config, "ics_feed", "") == "server":
+        return f"{getattr(config, 'server_url', 'http://localhost:3000').rstrip('/')}/ics/{token}.ics"
+    return f"http://127.0.0.1:{getattr(config, 'local_api_port', 8766)}/calendar/{token}.ics"
+
+
+# ------------------------------------------------------------------------------
+# iCalendar text
+# ------------------------------------------------------------------------------
+
+def escape(text: str) -> str:
+    """A TEXT value: backslashes, semicolons, commas and newlines escaped."""
+    return (str(text).replace("\\", "\\\\").replace(";", "\\;").replace(",", "\\,")
+            .replace("\r\n", "\\n").replace("\n", "\\n"))
+
+
+def fold(line: str) -> str:
+    """One content line, folded into CRLF + space continuations of at most LINE_OCTETS bytes."""
+    parts, current = [], ""
+    for char in line:
+        limit = LINE_OCTETS if not parts else LINE_OCTETS - 1  # Continuations start with a space
+        if len((current + char).encode("utf-8")) > limit:
+            parts.append(current)
+            current = ""
+        current += char
+    parts.append(current)
+    return "\r\n ".join(parts)
+
+
+def _local_time(value: str) -> str:
+    """A floating DATE-TIME: the event's clock time, without a zone."""
+    return datetime.fromisoformat(value).replace(tzinfo=None).strftime("%Y%m%dT%H%M%S")
+
+
+def _utc(moment: datetime) -> str:
+    return moment.astimezone(timezone.utc).strftime("%Y%m%dT%H%M%SZ")
+
+
+def vevent(event: Dict[str, Any], stamp: datetime, confidential: bool = False) -> List[str]:
+    """The VEVENT lines for one (shared view of an) event or occurrence."""
+    lines = ["BEGIN:VEVENT", f"UID:{event['id']}@xswarm", f"DTSTAMP:{_utc(stamp)}",
+             f"DTSTART:{_local_time(event['start_time'])}", f"DTEND:{_local_time(event['end_time'])}",
+             f"SUMMARY:{escape(event.get('title') or '')}"]
+    if event.get("description"):
+        lines.append(f"DESCRIPTION:{escape(event['description'])}")
+    if event.get("location"):
+        lines.append(f"LOCATION:{escape(event['location'])}")
+    if event.get("latitude") is not None and event.get("longitude") is not None:
+        lines.append(f"GEO:{event['latitude']};{event['longitude']}")
+    if event.get("tags"):
+        lines.append(f"CATEGORIES:{','.join(escape(tag) for tag in event['tags'])}")
+    if not event.get("busy", True):
+        lines.append("TRANSP:TRANSPARENT")
+    if confidential:
+        lines.append("CLASS:CONFIDENTIAL")
+    if event.get("created_at"):
+        try:
+            lines.append(f"CREATED:{_utc(datetime.fromisoformat(event['created_at']))}")
+        except ValueError:
+            pass
+    lines.append("END:VEVENT")
+    return lines
+
+
+def build_feed(events: Iterable[Any], default_visibility: str = "public", name: str = "xswarm",
+               now: Optional[datetime] = None) -> str:
+    """The VCALENDAR text for `events` (CalendarEvent objects or dicts), as outside apps may see them."""
+    stamp = now or datetime.now(timezone.utc)
+    lines = ["BEGIN:VCALENDAR", "VERSION:2.0", f"PRODID:{PRODUCT_ID}", "CALSCALE:GREGORIAN", "METHOD:PUBLISH",
+             f"X-WR-CALNAME:{escape(name)}", "REFRESH-INTERVAL;VALUE=DURATION:PT1H", "X-PUBLISHED-TTL:PT1H"]
+    for event in events:
+        raw = event if isinstance(event, dict) else asdict(event)
+        view = shared_view(raw, default_visibility)
+        if view is not None:
+            lines += vevent(view, stamp, confidential=visibility(raw, default_visibility) == "busy")
+    lines.append("END:VCALENDAR")
+    return "\r\n".join(fold(line) for line in lines) + "\r\n"
+
+
+def feed_events(planner, today: Optional[date] = None) -> List[Any]:
+    """The planner events and occurrences the feed covers."""
+    today = today or date.today()
+    return planner.get_calendar_events((today - timedelta(days=PAST_DAYS)).isoformat(),
+                                       (today + timedelta(days=FUTURE_DAYS)).isoformat())
+
+
+def render(config, planner=None, today: Optional[date] = None, now: Optional[datetime] = None) -> str:
+    """The feed as it stands now."""
+    if planner is None:
+        from .tools import get_planner_data
+        planner = get_planner_data()
+    return build_feed(feed_events(planner, today), getattr(config, "shared_visibility", "public") or "public",
+                      now=now)
+
+
+def serve_feed(config, token: str, planner=None, token_path: Optional[Path] = None) -> Optional[str]:
+    """The local API's /calendar/<token>.ics: the feed, or None when `token` isn't the feed's."""
+    expected = feed_token(create=False, path=token_path)
+    if getattr(config, "ics_feed", "") != "local" or not expected \
+            or not secrets.compare_digest(token.encode(), expected.encode()):
+        return None
+    return render(config, planner)
+
+
+def digest(ics: str) -> str:
+    """What the feed says, leaving out the stamp that changes with every render."""
+    body = "\n".join(line for line in ics.splitlines() if not line.startswith("DTSTAMP:"))
+    return hashlib.sha256(body.encode("utf-8")).hexdigest()
+
+
+# ------------------------------------------------------------------------------
+# Publishing
+# ------------------------------------------------------------------------------
+
+async def publish(config, user_id: str = "local-user", client=None, planner=None, force: bool = False,
+                  token_path: Optional[Path] = None) -> Optional[bool]:
+    """
+    The ics_feed job: upload the feed when it changed since the last upload
+    (or `force`). Returns True when uploaded, False when unchanged, None when
+    the feed isn't published through the server. Errors are left to the caller.
+    """
+    from .api_client import ApiClient, ApiPolicy
+
+    if getattr(config, "ics_feed", "") != "server":
+        return None
+    token = feed_token(path=token_path)
+    ics = render(config, planner)
+    if not force and _published.get(token) == digest(ics):
+        return False
+    own_client = client is None
+    if own_client:
+        client = ApiClient(getattr(config, "server_url", "http://localhost:3000"), getattr(config, "api_token", None),
+                           policy=ApiPolicy.from_config(config))
+    try:
+        await client.call(endpoints.ICS_FEED_PUBLISH(), json={"user_id": user_id, "token": token, "ics": ics})
+    finally:
+        if own_client:
+            await client.close()
+    _published.clear()
+    _published[token] = digest(ics)
+    return True
+
+
+async def unpublish(config, user_id: str = "local-user", client=None) -> bool:
+    """Delete the uploaded feed; True when there was one."""
+    from .api_client import ApiClient, ApiPolicy
+
+    own_client = client is None
+    if own_client:
+        client = ApiClient(getattr(config, "server_url", "http://localhost:3000"), getattr(config, "api_token", None),
+                           policy=ApiPolicy.from_config(config))
+    try:
+        response = await client.call(endpoints.ICS_FEED_DELETE(), params={"user_id": user_id})
+    finally:
+        if own_client:
+            await client.close()
+    _published.clear()
+    return bool(response.json().get("deleted"))
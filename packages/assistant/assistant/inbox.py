@@ -0,0 +1,357 @@
+"""
+Unified Inbox - Inbound SMS, email and voice messages in one place.
+
+The server records every inbound message in its `inbox_items` table. This
+module keeps a local mirror so the dashboard and chat tools work offline,
+and queues status changes (read/replied/archived) until they can be synced
+back to the server API. Replies are sent through the server, which delivers
+them over the original channel (see reply_drafts.py for the voice flow).
+
+Items can be named by their full id or by a prefix only one of them starts
+with (as format_inbox shows them); a prefix several share is an InboxError.
+
+Storage: ~/.xswarm/inbox/inbox.json
+"""
+
+import json
+import logging
+from dataclasses import dataclass, asdict, field
+from datetime import datetime
+from pathlib import Path
+from typing import Optional, List, Dict, Any
+
+import httpx
+
+from . import endpoints
+from .api_client import ApiClient, ApiError, ApiPolicy
+from .correlation import current_id
+
+logger = logging.getLogger(__name__)
+
+
+INBOX_STATUSES = ("unread", "read", "replied", "archived")
+CHANNEL_ICONS = {"sms": "💬", "email": "📧", "voice": "📞"}
+
+
+class InboxError(ValueError):
+    """An item id that names more than one message."""
+
+
+@dataclass
+class InboxItem:
+    """A single inbound communication."""
+    id: str
+    channel: str  # sms, email, voice
+    sender: str
+    content: str
+    received_at: str
+    status: str = "unread"
+    subject: Optional[str] = None
+    reply_text: Optional[str] = None
+    replied_at: Optional[str] = None
+    updated_at: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "InboxItem":
+        known = {k: data.get(k) for k in cls.__dataclass_fields__ if k in data}
+        return cls(**known)
+
+    @property
+    def icon(self) -> str:
+        return CHANNEL_ICONS.get(self.channel, "•")
+
+    @property
+    def preview(self) -> str:
+        """One-line preview for list views."""
+        text = self.subject or self.content
+        text = " ".join(text.split())
+        return text[:60] + "…" if len(text) > 60 else text
+
+
+@dataclass
+class PendingUpdate:
+    """A local status change waiting to be pushed to the server."""
+    item_id: str
+    status: Optional[str] = None
+    reply_text: Optional[str] = None
+    queued_at: str = field(default_factory=lambda: datetime.now().isoformat())
+
+
+class InboxStore:
+    """
+    Local inbox mirror with an outbound change queue.
+    Follows the PlannerData single-file pattern.
+    """
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "inbox"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _inbox_path(self) -> Path:
+        return self.storage_dir / "inbox.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+
+        path = self._inbox_path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load inbox: {e}")
+
+        self._data = {"items": [], "pending": [], "last_synced_at": None}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            with open(self._inbox_path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save inbox: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    # --------------------------------------------------------------------------
+    # Queries
+    # --------------------------------------------------------------------------
+
+    def get_items(
+        self,
+        status: Optional[str] = None,
+        channel: Optional[str] = None,
+        include_archived: bool = False,
+    ) -> List[InboxItem]:
+        """Items newest first, archived hidden unless requested."""
+        items = [InboxItem.from_dict(i) for i in self._load()["items"]]
+        if status:
+            items = [i for i in items if i.status == status]
+        elif not include_archived:
+            items = [i for i in items if i.status != "archived"]
+        if channel:
+            items = [i for i in items if i.channel == channel]
+        return sorted(items, key=lambda i: i.received_at, reverse=True)
+
+    def _find(self, item_id: str) -> Optional[Dict[str, Any]]:
+        """The stored item with this id, or the only one it's a prefix of; InboxError if several."""
+        item_id = (item_id or "").strip()
+        if not item_id:
+            return None
+        items = self._load()["items"]
+        exact = next((raw for raw in items if raw["id"] == item_id), None)
+        if exact is not None:
+            return exact
+        matches = [raw for raw in items if raw["id"].startswith(item_id)]
+        if len(matches) > 1:
+            raise InboxError(f"'{item_id}' matches {len(matches)} inbox items - give more of the id")
+        return matches[0] if matches else None
+
+    def get_item(self, item_id: str) -> Optional[InboxItem]:
+        raw = self._find(item_id)
+        return InboxItem.from_dict(raw) if raw else None
+
+    def unread_count(self) -> int:
+        return sum(1 for i in self._load()["items"] if i.get("status") == "unread")
+
+    @property
+    def last_synced_at(self) -> Optional[str]:
+        return self._load().get("last_synced_at")
+
+    @property
+    def pending(self) -> List[PendingUpdate]:
+        return [PendingUpdate(**p) for p in self._load()["pending"]]
+
+    # --------------------------------------------------------------------------
+    # Mutations
+    # --------------------------------------------------------------------------
+
+    def merge_remote(self, remote_items: List[Dict[str, Any]], synced_at: Optional[str] = None) -> int:
+        """
+        Merge items fetched from the server.
+
+        Local status wins for items that still have a pending update, so a
+        change made offline is not overwritten before it reaches the server.
+        Returns the number of new items.
+        """
+        data = self._load()
+        by_id = {i["id"]: i for i in data["items"]}
+        pending_ids = {p["item_id"] for p in data["pending"]}
+        added = 0
+
+        for remote in remote_items:
+            item = asdict(InboxItem.from_dict(remote))
+            existing = by_id.get(item["id"])
+            if existing is None:
+                added += 1
+            elif item["id"] in pending_ids:
+                item["status"] = existing["status"]
+                item["reply_text"] = existing.get("reply_text")
+            by_id[item["id"]] = item
+
+        data["items"] = list(by_id.values())
+        data["last_synced_at"] = synced_at or datetime.now().isoformat()
+        self._save()
+        return added
+
+    def set_status(self, item_id: str, status: str, reply_text: Optional[str] = None) -> Optional[InboxItem]:
+        """Change item status locally and queue the change for sync."""
+        if status not in INBOX_STATUSES:
+            raise ValueError(f"Invalid status '{status}'. Expected one of: {', '.join(INBOX_STATUSES)}")
+
+        data = self._load()
+        raw = self._find(item_id)
+        if raw is None:
+            return None
+        raw["status"] = status
+        raw["updated_at"] = datetime.now().isoformat()
+        if reply_text is not None:
+            raw["reply_text"] = reply_text
+            raw["replied_at"] = raw["updated_at"]
+
+        # Collapse with any earlier queued change for the same item
+        data["pending"] = [p for p in data["pending"] if p["item_id"] != raw["id"]]
+        data["pending"].append(asdict(PendingUpdate(raw["id"], status, reply_text)))
+        self._save()
+        return InboxItem.from_dict(raw)
+
+    def clear_pending(self, item_ids: List[str]) -> None:
+        data = self._load()
+        data["pending"] = [p for p in data["pending"] if p["item_id"] not in item_ids]
+        self._save()
+
+
+class InboxClient:
+    """Async HTTP client for the server inbox API."""
+
+    def __init__(self, server_url: str = "http://localhost:3000", api_token: Optional[str] = None,
+                 timeout: float = 10.0, policy: Optional[ApiPolicy] = None):
+        self.client = ApiClient(server_url, api_token, timeout, policy)
+
+    async def close(self):
+        await self.client.close()
+
+    async def fetch(self, since: Optional[str] = None) -> Dict[str, Any]:
+        """The signed-in user's items (the server takes the user from the API token)."""
+        params = {"limit": 200}
+        if since:
+            params["since"] = since
+        response = await self.client.call(endpoints.INBOX(), params=params)
+        return response.json()
+
+    async def push_update(self, update: PendingUpdate) -> bool:
+        payload = {k: v for k, v in (("status", update.status), ("reply_text", update.reply_text)) if v is not None}
+        # 404 means deleted server-side; nothing left to sync
+        await self.client.call(endpoints.INBOX_ITEM(item_id=update.item_id), json=payload, ok_statuses=(404,))
+        return True
+
+    async def draft_reply(self, item_id: str, user_name: Optional[str] = None, instructions: Optional[str] = None) -> str:
+        payload = {"user_name": user_name or "the user"}
+        if instructions:
+            payload["instructions"] = instructions
+        response = await self.client.call(endpoints.INBOX_DRAFT(item_id=item_id), json=payload)
+        return response.json().get("draft", "")
+
+    async def send_reply(self, item_id: str, text: str) -> Dict[str, Any]:
+        response = await self.client.call(endpoints.INBOX_REPLY(item_id=item_id), json={"text": text})
+        return response.json()
+
+
+class InboxManager:
+    """High-level inbox: local store plus two-way server sync."""
+
+    def __init__(self, config=None, store: Optional[InboxStore] = None, events=None):
+        server_url = getattr(config, "server_url", "http://localhost:3000")
+        api_token = getattr(config, "api_token", None)
+        self.store = store or InboxStore()
+        self.events = events  # EventStore that new messages are recorded in (events.py), if any
+        self.new_items: List[InboxItem] = []  # What the last sync brought in, oldest first
+        self.last_error: Optional[ApiError] = None  # Why the last reply couldn't be sent (api_client.explain)
+        self.client = InboxClient(server_url, api_token, policy=ApiPolicy.from_config(config))
+
+    async def sync(self) -> Dict[str, int]:
+        """Push queued status changes, then pull new/updated items."""
+        pushed = []
+        for update in self.store.pending:
+            try:
+                await self.client.push_update(update)
+                pushed.append(update.item_id)
+            except ApiError as e:
+                if not e.permanent:  # Down, signed out, over quota: keep it queued for later
+                    logger.debug(f"Inbox push failed for {update.item_id}: {e}")
+                    break
+                # The server will never accept this update; drop it rather than block the queue
+                logger.warning(f"Inbox update for {update.item_id} rejected: {e}")
+                pushed.append(update.item_id)
+        if pushed:
+            self.store.clear_pending(pushed)
+
+        added = 0
+        known = {i.id for i in self.store.get_items(include_archived=True)}
+        try:
+            result = await self.client.fetch(since=self.store.last_synced_at)
+            added = self.store.merge_remote(result.get("items", []), result.get("synced_at"))
+        except httpx.HTTPError as e:
+            logger.debug(f"Inbox fetch failed: {e}")
+        self.new_items = [item for item in reversed(self.store.get_items(include_archived=True))
+                          if item.id not in known] if added else []
+        if self.events is not None:
+            for item in self.new_items:
+                self.events.record(item.channel, f"{item.icon} {item.sender}: {item.preview}",
+                                   {"id": item.id, "sender": item.sender, "subject": item.subject},
+                                   thread=current_id())
+
+        return {"pushed": len(pushed), "added": added, "pending": len(self.store.pending)}
+
+    async def draft_reply(self, item_id: str, instructions: Optional[str] = None) -> Optional[str]:
+        item = self.store.get_item(item_id)
+        if not item:
+            return None
+        try:
+            return await self.client.draft_reply(item.id, instructions=instructions)
+        except httpx.HTTPError as e:
+            logger.debug(f"Inbox draft failed: {e}")
+            return None
+
+    async def send_reply(self, item_id: str, text: str) -> bool:
+        """Send a confirmed reply over the item's channel and mark it replied."""
+        item = self.store.get_item(item_id)
+        if not item:
+            return False
+        try:
+            await self.client.send_reply(item.id, text)
+        except httpx.HTTPError as e:
+            logger.debug(f"Inbox reply send failed: {e}")
+            self.last_error = e if isinstance(e, ApiError) else None
+            return False
+        self.last_error = None
+        self.store.set_status(item.id, "replied", reply_text=text)
+        # Server already recorded the reply; don't push it again
+        self.store.clear_pending([item.id])
+        return True
+
+    async def close(self):
+        await self.client.close()
+
+
+def format_inbox(items: List[InboxItem], limit: int = 20) -> str:
+    """Plain-text inbox listing for chat tools and the CLI."""
+    if not items:
+        return "Inbox is empty."
+    lines = []
+    for item in items[:limit]:
+        marker = "●" if item.status == "unread" else " "
+        when = item.received_at[:16].replace("T", " ")
+        lines.append(f"{marker} {item.icon} [{item.id[:8]}] {when}  {item.sender}: {item.preview} ({item.status})")
+    if len(items) > limit:
+        lines.append(f"  … {len(items) - limit} more")
+    return "\n".join(lines)
@@ -0,0 +1,254 @@
+"""
+Intents - How sure we are that a tool call is what the user asked for.
+
+The AI turns an utterance into tool calls. Before a call that acts on an
+existing item runs, score_call rates how well the utterance supports it:
+
+- target: the item the call acts on (event_id, task_id, ...) should be
+  named in what was said. "delete the standup" or "cancel my 3pm" ground
+  delete_calendar_event(<standup at 15:00>); "cancel my meeting" only names
+  the kind of thing; an utterance that mentions nothing about it grounds
+  it least.
+- hearing: the speech-to-text confidence for the utterance, when known.
+
+When the score is under the threshold for the call's action class
+(confirmation.DEFAULT_CONFIDENCE_THRESHOLDS, overridden by class or tool
+name in config.intent_confidence_thresholds), ToolRegistry.execute_tool
+holds the call and asks "Did you want me to cancel the 3pm standup?" -
+yes runs it, anything else drops it. Riskier classes need more confidence.
+
+The dashboard (and replay.py) report each user utterance with hear(); calls
+made more than UTTERANCE_TTL after it (background jobs) aren't scored.
+"""
+
+import logging
+import re
+import time
+from dataclasses import dataclass
+from datetime import date, datetime
+from typing import Any, Callable, Dict, List, Optional, Set, Tuple
+
+from .confirmation import classify_action, describe_call
+from .dates import get_date_settings, parse_time_expression
+from .verbalize import verbalize_date
+
+logger = logging.getLogger(__name__)
+
+UTTERANCE_TTL = 90.0  # Seconds an utterance stays the context for tool calls
+
+# How well an utterance names a call's target
+NAMED = 1.0  # Most of its title words
+AT_TIME = 0.9  # Its time ("cancel my 3pm")
+REFERRED = 0.75  # "it", "that", "too" - resolved from earlier in the conversation (dialogue.py)
+PARTLY_NAMED = 0.7  # Some of its title words
+KIND_ONLY = 0.5  # "my meeting", "the task"
+UNMENTIONED = 0.3
+
+TARGET_KEYS = ("event_id", "task_id", "item_id", "habit_id", "goal_id", "commitment_id", "project_id")
+REFERRING_WORDS = {"it", "that", "this", "them", "those", "these", "one"}
+KIND_WORDS = {"meeting", "meetings", "event", "events", "appointment", "appointments", "call", "task", "tasks",
+              "todo", "habit", "habits", "thing", "reminder", "reminders", "project", "goal", "commitment"}
+STOP_WORDS = {"the", "and", "for", "with", "my", "a", "an", "to", "of", "on", "at", "in"}
+
+
+@dataclass
+class Utterance:
+    text: str
+    confidence: Optional[float] = None  # Speech-to-text confidence, 0-1
+    heard_at: float = 0.0
+
+
+@dataclass
+class Target:
+    """The existing item a tool call acts on."""
+    kind: str  # "event", "task", "habit", "goal", "commitment", "project"
+    title: str
+    start: Optional[datetime] = None  # Events and scheduled tasks
+
+
+@dataclass
+class CallScore:
+    """How sure we are about one tool call."""
+    confidence: float
+    question: str  # "Did you want me to cancel the 3pm standup?"
+    reason: str = ""
+
+
+_current: Optional[Utterance] = None
+_clock: Callable[[], float] = time.monotonic
+
+
+def hear(text: str, confidence: Optional[float] = None) -> None:
+    """Record what the user just said, as the context for the tool calls it leads to."""
+    from .dialogue import get_dialogue_state
+    global _current
+    _current = Utterance(text or "", confidence, _clock())
+    get_dialogue_state().heard(_current.text)  # A new topic forgets what "it" was
+
+
+def current_utterance() -> Optional[Utterance]:
+    """The latest utterance, if it was recent enough to explain a tool call."""
+    if _current is None or _clock() - _current.heard_at > UTTERANCE_TTL:
+        return None
+    return _current
+
+
+def _words(text: str) -> List[str]:
+    return re.sub(r"[^\w\s:]", " ", (text or "").lower().replace("-", "")).split()
+
+
+def _times(words: List[str]) -> Set[Tuple[int, int]]:
+    """Clock times mentioned, as (hour on a 12h dial, minute), so "three" matches 15:00."""
+    found = set()
+    for size in (3, 2, 1):
+        for i in range(len(words) - size + 1):
+            value = parse_time_expression(" ".join(words[i:i + size]))
+            if value:
+                hour, minute = map(int, value.split(":"))
+                found.add((hour % 12, minute))
+    return found
+
+
+def grounding(text: str, target: Target) -> Tuple[float, str]:
+    """How well `text` names `target`, with the reason."""
+    words = _words(text)
+    title_words = [w for w in _words(target.title) if w not in STOP_WORDS]
+    said = set(words)
+    if title_words:
+        matched = sum(1 for w in title_words if w in said or (len(w) > 4 and any(s.startswith(w[:5]) for s in said)))
+        if matched * 2 >= len(title_words):
+            return NAMED, "named"
+    else:
+        matched = 0
+    if target.start is not None and (target.start.hour % 12, target.start.minute) in _times(words):
+        return AT_TIME, "time"
+    if said & REFERRING_WORDS:
+        return REFERRED, "referred"
+    if matched:
+        return PARTLY_NAMED, "partly named"
+    if said & KIND_WORDS:
+        return KIND_ONLY, "kind only"
+    return UNMENTIONED, "not mentioned"
+
+
+# ------------------------------------------------------------------------------
+# Targets
+# ------------------------------------------------------------------------------
+
+def _datetime(value: Optional[str]) -> Optional[datetime]:
+    """A stored start ("2026-10-16T15:00:00", or "15:00" today for scheduled tasks) as shown to the user."""
+    from .timezones import to_display
+    if not value:
+        return None
+    try:
+        moment = datetime.fromisoformat(value) if "T" in value else \
+            datetime.combine(date.today(), datetime.strptime(value, "%H:%M").time())
+    except ValueError:
+        return None
+    return to_display(moment)
+
+
+def lookup_target(key: str, item_id: str, planner) -> Optional[Target]:
+    """The item an id argument (`key` is one of TARGET_KEYS) names, or None if not found."""
+    if key == "event_id":
+        event = planner.get_calendar_event(item_id)
+        return Target("event", event.title, _datetime(event.start_time)) if event else None
+    if key in ("task_id", "item_id"):
+        task = planner.get_task(item_id)
+        if task:
+            return Target("task", task.title, _datetime(task.scheduled_time))
+        if key == "task_id":
+            return None
+    if key in ("habit_id", "item_id"):
+        habit = planner.get_habit(item_id)
+        return Target("habit", habit.name) if habit else None
+    if key == "goal_id":
+        goal = planner.get_goal(item_id)
+        return Target("goal", goal.name) if goal else None
+    if key == "commitment_id":
+        commitment = next((c for c in planner.get_commitments(include_completed=True) if c.id == item_id), None)
+        return Target("commitment", commitment.description) if commitment else None
+    if key == "project_id":
+        project = next((p for p in planner.get_projects() if p.id == item_id), None)
+        return Target("project", project.name) if project else None
+    return None
+
+
+def find_target(tool_name: str, args: Dict[str, Any], planner) -> Optional[Target]:
+    """The existing item `args` point at, or None (nothing targeted, or not found)."""
+    if classify_action(tool_name) == "create":
+        return None  # project_id etc. on a create only files the new item
+    for key in TARGET_KEYS:
+        item_id = args.get(key)
+        if item_id and isinstance(item_id, str):
+            return lookup_target(key, item_id, planner)
+    return None
+
+
+def _clock_time(moment: datetime) -> str:
+    """ "3pm" / "3:30pm", or "15:00" on a 24-hour clock."""
+    if get_date_settings().resolved_clock() == "24h":
+        return f"{moment:%H:%M}"
+    hour = moment.hour % 12 or 12
+    minutes = f":{moment.minute:02d}" if moment.minute else ""
+    return f"{hour}{minutes}{'am' if moment.hour < 12 else 'pm'}"
+
+
+def describe_target(target: Target, today: Optional[date] = None) -> str:
+    """ "the 3pm standup", "the standup tomorrow at 9am", "the task 'Write report'"."""
+    if target.kind == "event" and target.start is not None:
+        today = today or date.today()
+        if target.start.date() == today:
+            return f"the {_clock_time(target.start)} {target.title}"
+        return f"the {target.title} {verbalize_date(target.start.date(), today=today)} at {_clock_time(target.start)}"
+    if target.kind == "event":
+        return f"the {target.title}"
+    return f"the {target.kind} '{target.title}'"
+
+
+def question_for(tool_name: str, args: Dict[str, Any], target: Target, today: Optional[date] = None) -> str:
+    """ "Did you want me to cancel the 3pm standup?" """
+    thing = describe_target(target, today)
+    action_class = classify_action(tool_name)
+    if action_class == "delete":
+        phrase = f"{'cancel' if target.kind == 'event' else 'delete'} {thing}"
+    elif action_class == "complete":
+        phrase = f"mark {thing} done"
+    elif tool_name in ("reschedule_task", "schedule_task") or "start_time" in args or "new_time" in args:
+        phrase = f"move {thing}"
+    elif action_class == "modify":
+        phrase = f"change {thing}"
+    else:
+        phrase = f"{describe_call(tool_name, {})} for {thing}"
+    return f"Did you want me to {phrase}?"
+
+
+def score_call(tool_name: str, args: Dict[str, Any], planner=None,
+               utterance: Optional[Utterance] = None) -> Optional[CallScore]:
+    """
+    Confidence that a call is what the latest utterance asked for, or None
+    when there's nothing to check: no recent utterance, or the call doesn't
+    act on an existing item.
+    """
+    utterance = utterance or current_utterance()
+    if utterance is None or not utterance.text.strip():
+        return None
+    if planner is None:
+        from .tools import get_planner_data
+        planner = get_planner_data()
+    try:
+        target = find_target(tool_name, args, planner)
+    except Exception as e:
+        logger.debug(f"Could not look up the target of {tool_name}: {e}")
+        return None
+    if target is None:
+        return None
+    confidence, reason = grounding(utterance.text, target)
+    if confidence < REFERRED:
+        from .dialogue import get_dialogue_state, refers_back
+        if refers_back(utterance.text) and get_dialogue_state().is_focus(args):
+            confidence, reason = REFERRED, "referred"  # "invite Bob too", about what we were just discussing
+    if utterance.confidence is not None:
+        confidence *= max(0.0, min(1.0, utterance.confidence))
+        reason += f", heard with {utterance.confidence:.0%} confidence"
+    return CallScore(round(confidence, 2), question_for(tool_name, args, target), reason)
@@ -0,0 +1,208 @@
+"""
+Keymap - Which keys do what in the dashboard, from config.
+
+Every dashboard shortcut is a named action (ACTIONS). A preset picks the
+keys for all of them, and config can rebind single actions:
+
+    keymap_preset: vi          # default, vi or emacs
+    keymap:
+      next_tab: "ctrl+j, down"  # comma-separated keys
+      copy_logs: ""             # unbound
+
+- default: arrows plus j/k/h/l, digits for tabs
+- vi: j/k/h/l only, u undo, y copy logs, +/- volume
+- emacs: ctrl+n/ctrl+p tabs, ctrl+f/ctrl+b panes, ctrl+g cancel, alt+w copy logs,
+  alt+x command palette (ctrl+p elsewhere)
+
+`?` (help, in every preset) shows the active keymap, generated from the
+same table (KeymapHelpScreen in dashboard.py). The command palette lists
+every action whether it has a key or not (palette.py).
+"""
+
+import logging
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass(frozen=True)
+class Action:
+    name: str  # Key in config.keymap
+    command: str  # Textual action it runs
+    description: str
+    group: str
+
+
+ACTIONS: List[Action] = [
+    Action("help", "keymap_help", "Show keys", "General"),
+    Action("command_palette", "command_palette", "Command palette", "General"),
+    Action("quit", "quit", "Quit", "General"),
+    Action("cancel", "escape_handler", "Cancel / back", "General"),
+    Action("lock", "lock", "Lock the screen", "General"),
+    Action("pair_device", "pair_device", "Pair a phone or web client", "General"),
+    Action("undo", "undo", "Undo last delete/complete/forget", "General"),
+    Action("copy_logs", "copy_logs", "Copy activity log", "General"),
+    Action("volume_up", "volume('up')", "Louder", "General"),
+    Action("volume_down", "volume('down')", "Quieter", "General"),
+    Action("next_tab", "nav_down", "Next tab", "Navigation"),
+    Action("previous_tab", "nav_up", "Previous tab", "Navigation"),
+    Action("focus_content", "focus_content", "Focus content", "Navigation"),
+    Action("focus_sidebar", "focus_sidebar", "Focus sidebar", "Navigation"),
+    Action("next_pane", "cycle_focus_zone", "Next pane", "Navigation"),
+    Action("previous_pane", "cycle_focus_zone_reverse", "Previous pane", "Navigation"),
+    Action("goto_chat", "goto_chat", "Chat", "Tabs"),
+    Action("goto_schedule", "goto_schedule", "Schedule", "Tabs"),
+    Action("goto_projects", "goto_projects", "Projects", "Tabs"),
+    Action("goto_settings", "goto_settings", "Settings", "Tabs"),
+    Action("goto_status", "goto_status", "Status", "Tabs"),
+    Action("goto_tools", "goto_tools", "Tools", "Tabs"),
+    Action("goto_workers", "goto_workers", "Workers", "Tabs"),
+    Action("goto_inbox", "goto_inbox", "Inbox", "Tabs"),
+    Action("goto_help", "goto_help", "Help", "Tabs"),
+]
+ACTION_NAMES = {action.name: action for action in ACTIONS}
+
+# Handled before the focused widget, so they work while typing in the chat input
+PRIORITY_ACTIONS = {"cancel", "lock", "next_pane", "previous_pane", "command_palette"}
+
+_TABS = {f"goto_{tab}": [str(i)] for i, tab in enumerate(
+    ["chat", "schedule", "projects", "settings", "status", "tools", "workers", "inbox", "help"], start=1)}
+
+PRESETS: Dict[str, Dict[str, List[str]]] = {
+    "default": {
+        "help": ["question_mark"],
+        "command_palette": ["ctrl+p"],
+        "quit": ["q", "ctrl+q", "ctrl+c"],
+        "cancel": ["escape"],
+        "lock": ["ctrl+o"],
+        "pair_device": ["ctrl+y"],
+        "undo": ["ctrl+z"],
+        "copy_logs": ["ctrl+l"],
+        "volume_up": ["ctrl+up"],
+        "volume_down": ["ctrl+down"],
+        "next_tab": ["j", "down"],
+        "previous_tab": ["k", "up"],
+        "focus_content": ["l", "right"],
+        "focus_sidebar": ["h", "left"],
+        "next_pane": ["tab"],
+        "previous_pane": ["shift+tab"],
+        **_TABS,
+    },
+    "vi": {
+        "help": ["question_mark"],
+        "command_palette": ["ctrl+p"],
+        "quit": ["q", "ctrl+q", "ctrl+c"],
+        "cancel": ["escape"],
+        "lock": ["ctrl+o"],
+        "pair_device": ["ctrl+y"],
+        "undo": ["u"],
+        "copy_logs": ["y"],
+        "volume_up": ["plus"],
+        "volume_down": ["minus"],
+        "next_tab": ["j"],
+        "previous_tab": ["k"],
+        "focus_content": ["l"],
+        "focus_sidebar": ["h"],
+        "next_pane": ["tab"],
+        "previous_pane": ["shift+tab"],
+        **_TABS,
+    },
+    "emacs": {
+        "help": ["question_mark", "f1"],
+        "command_palette": ["alt+x"],
+        "quit": ["ctrl+q", "ctrl+c"],
+        "cancel": ["ctrl+g", "escape"],
+        "lock": ["ctrl+o"],
+        "pair_device": ["ctrl+y"],
+        "undo": ["ctrl+z"],
+        "copy_logs": ["alt+w"],
+        "volume_up": ["ctrl+up"],
+        "volume_down": ["ctrl+down"],
+        "next_tab": ["ctrl+n", "down"],
+        "previous_tab": ["ctrl+p", "up"],
+        "focus_content": ["ctrl+f", "right"],
+        "focus_sidebar": ["ctrl+b", "left"],
+        "next_pane": ["tab"],
+        "previous_pane": ["shift+tab"],
+        **{name: [f"alt+{keys[0]}"] for name, keys in _TABS.items()},
+    },
+}
+
+_CHARACTERS = {"?": "question_mark", "+": "plus", "-": "minus"}  # Typed in config as the character
+_KEY_NAMES = {"question_mark": "?", "plus": "+", "minus": "-", "up": "↑", "down": "↓",
+              "left": "←", "right": "→", "escape": "Esc", "tab": "Tab"}
+
+
+def parse_keys(value) -> List[str]:
+    """ "ctrl+j, down" or ["ctrl+j", "down"] -> ["ctrl+j", "down"]; "" unbinds."""
+    if isinstance(value, str):
+        value = value.split(",")
+    keys = [key.strip().lower() for key in value or [] if key and key.strip()]
+    return [_CHARACTERS.get(key, key) for key in keys]
+
+
+def key_label(key: str) -> str:
+    """How a key is shown in help: "ctrl+q" -> "Ctrl+Q", "down" -> "↓"."""
+    *modifiers, last = key.split("+")
+    if last in _KEY_NAMES:
+        last = _KEY_NAMES[last]
+    elif modifiers or len(last) > 1:
+        last = last.capitalize()  # Ctrl+Q, F1 - a lone letter stays as typed
+    return "+".join([modifier.capitalize() for modifier in modifiers] + [last])
+
+
+class Keymap:
+    """The keys bound to each action: a preset, with per-action overrides."""
+
+    def __init__(self, preset: str = "default", overrides: Optional[Dict[str, object]] = None):
+        self.problems: List[str] = []  # Config mistakes, shown once in the activity feed
+        if preset not in PRESETS:
+            self.problems.append(f"Unknown keymap preset '{preset}' (use {', '.join(PRESETS)})")
+            preset = "default"
+        self.preset = preset
+        self._keys: Dict[str, List[str]] = {name: list(keys) for name, keys in PRESETS[preset].items()}
+
+        for name, value in (overrides or {}).items():
+            if name not in ACTION_NAMES:
+                self.problems.append(f"Unknown keymap action '{name}'")
+                continue
+            keys = parse_keys(value)
+            # A key moved to this action stops doing whatever it did before
+            for other, bound in self._keys.items():
+                if other != name:
+                    self._keys[other] = [key for key in bound if key not in keys]
+            self._keys[name] = keys
+
+        for problem in self.problems:
+            logger.warning(problem)
+
+    @classmethod
+    def from_config(cls, config) -> "Keymap":
+        return cls(getattr(config, "keymap_preset", "default") or "default", getattr(config, "keymap", {}) or {})
+
+    def keys(self, action: str) -> List[str]:
+        return list(self._keys.get(action, []))
+
+    def bindings(self) -> List[Tuple[str, str, str, bool]]:
+        """(key, Textual action, description, priority) for every bound key."""
+        result = []
+        for action in ACTIONS:
+            for key in self._keys.get(action.name, []):
+                priority = action.name in PRIORITY_ACTIONS or (action.name == "quit" and key.startswith("ctrl+"))
+                result.append((key, action.command, action.description, priority))
+        return result
+
+    def help_lines(self) -> List[str]:
+        """The active keys by group, for the `?` overlay: "  j / ↓        Next tab"."""
+        lines = [f"Keys ({self.preset})"]
+        group = None
+        for action in ACTIONS:
+            keys = self._keys.get(action.name, [])
+            if not keys:
+                continue
+            if action.group != group:
+                group = action.group
+                lines += ["", group]
+            lines.append(f"  {' / '.join(key_label(key) for key in keys):<18} {action.description}")
+        return lines
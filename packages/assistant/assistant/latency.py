@@ -0,0 +1,202 @@
+"""
+Latency - A time budget for AI calls the user is waiting on, and what to do when it runs out.
+
+BudgetedAI wraps the `chat(messages, max_tokens)` call path with a ladder
+of rungs, fastest-acceptable last:
+
+    primary model  ->  the provider's small model  ->  local model (Ollama/LM Studio)
+
+Each turn has two limits (config.voice_latency_budget, config.ai_hard_limit):
+
+- soft: no answer by then and a filler is spoken once ("Let me check."),
+  so the user hears something within the budget; the call keeps going
+- hard: a rung that hasn't answered (or has failed) is abandoned and the
+  next rung is asked, with its own hard limit
+
+LatencyError is raised when the last rung fails too, so callers keep their
+own fallbacks (reply_drafts.py drafts on the server instead). Every call's
+time, timeouts, errors and fillers go into LatencyMetrics per rung, shown in
+the control socket's status (ControlClient.status().latency in
+xswarm-client) and logged when a turn degrades.
+
+Fillers are among the speech cache's common phrases, so they play at once.
+In the low-power profile (power.py) small rungs are asked first. A
+household profile without cloud AI (household.py) gets the local rungs only.
+Chaos mode (chaos.py) fails or slows rungs on purpose to exercise all this.
+"""
+
+import asyncio
+import logging
+import random
+import time
+from collections import deque
+from dataclasses import dataclass
+from typing import Any, Awaitable, Callable, Deque, Dict, List, Optional
+
+from .chaos import get_chaos
+from .errors import VoiceError
+from .personas.phrases import get_phrase_bank
+
+logger = logging.getLogger(__name__)
+
+FILLERS = ("Let me check.", "One moment.", "Just a second.")
+WINDOW = 200  # Latencies kept per rung for percentiles
+
+Chat = Callable[[list, int], Awaitable[str]]
+
+
+class LatencyError(TimeoutError):
+    """Every rung of the ladder timed out or failed."""
+
+
+@dataclass(frozen=True)
+class LatencyBudget:
+    soft: float = 1.5  # Seconds to first audio before a filler is spoken
+    hard: float = 6.0  # Seconds before a rung is abandoned for the next
+
+    @classmethod
+    def from_config(cls, config=None) -> "LatencyBudget":
+        return cls(float(getattr(config, "voice_latency_budget", cls.soft)),
+                   float(getattr(config, "ai_hard_limit", cls.hard)))
+
+
+@dataclass
+class Rung:
+    name: str  # e.g. "anthropic:claude-3-5-haiku-20241022"
+    chat: Chat
+    small: bool = False  # A smaller model, asked first in the low-power profile
+    local: bool = False  # Runs on this machine: the only kind a household profile without cloud_ai may use
+
+
+class LatencyMetrics:
+    """Per-rung call counts, outcomes and recent latencies."""
+
+    def __init__(self):
+        self.rungs: Dict[str, Dict[str, Any]] = {}
+        self.turns = 0
+        self.fillers = 0
+        self.degraded = 0  # Turns answered by a rung after the first
+
+    def _rung(self, name: str) -> Dict[str, Any]:
+        return self.rungs.setdefault(name, {"calls": 0, "ok": 0, "timeouts": 0, "errors": 0,
+                                            "latencies": deque(maxlen=WINDOW)})
+
+    def record(self, name: str, outcome: str, seconds: float) -> None:
+        rung = self._rung(name)
+        rung["calls"] += 1
+        rung[outcome] += 1
+        if outcome == "ok":
+            rung["latencies"].append(seconds)
+
+    def summary(self) -> Dict[str, Any]:
+        rungs = {}
+        for name, rung in self.rungs.items():
+            latencies: Deque[float] = rung["latencies"]
+            rungs[name] = {key: rung[key] for key in ("calls", "ok", "timeouts", "errors")}
+            rungs[name]["p50"] = round(percentile(latencies, 50), 3) if latencies else None
+            rungs[name]["p95"] = round(percentile(latencies, 95), 3) if latencies else None
+        return {"turns": self.turns, "fillers": self.fillers, "degraded": self.degraded, "rungs": rungs}
+
+
+def percentile(values, pct: float) -> float:
+    ordered = sorted(values)
+    index = min(len(ordered) - 1, max(0, int(round(pct / 100 * (len(ordered) - 1)))))
+    return ordered[index]
+
+
+_metrics = LatencyMetrics()
+
+
+def get_latency_metrics() -> LatencyMetrics:
+    return _metrics
+
+
+class BudgetedAI:
+    """An AI client (chat / is_available) that keeps to a LatencyBudget by climbing down a ladder of rungs."""
+
+    def __init__(self, ladder: List[Rung], budget: Optional[LatencyBudget] = None,
+                 on_filler: Optional[Callable[[str], Awaitable[None]]] = None,
+                 metrics: Optional[LatencyMetrics] = None, clock: Callable[[], float] = time.monotonic):
+        self.ladder = ladder
+        self.budget = budget or LatencyBudget()
+        self.on_filler = on_filler  # None: nobody is listening (background calls), no filler
+        self.metrics = metrics or get_latency_metrics()
+        self.clock = clock
+
+    def rungs(self) -> List[Rung]:
+        """
+        The ladder in the order it's climbed now: small rungs first in low
+        power, local rungs only for a household profile without cloud AI.
+        """
+        from .household import get_household
+        from .power import get_power_manager
+        ladder = self.ladder if get_household().active.cloud_ai else [rung for rung in self.ladder if rung.local]
+        if not get_power_manager().profile.small_models:
+            return ladder
+        return [rung for rung in ladder if rung.small] + [rung for rung in ladder if not rung.small]
+
+    def is_available(self) -> bool:
+        return bool(self.ladder)
+
+    def without_filler(self) -> "BudgetedAI":
+        """The same ladder for background calls nobody is waiting to hear."""
+        return BudgetedAI(self.ladder, self.budget, None, self.metrics, self.clock)
+
+    async def _filler(self) -> None:
+        self.metrics.fillers += 1
+        try:
+            await self.on_filler(get_phrase_bank().pick("filler") or random.choice(FILLERS))
+        except Exception as e:
+            logger.debug(f"Filler failed: {e}")
+
+    @staticmethod
+    async def _ask(rung: Rung, messages: list, max_tokens: int) -> str:
+        await get_chaos().disrupt("ai", rung.name)
+        return await rung.chat(messages, max_tokens)
+
+    async def chat(self, messages: list, max_tokens: int = 1024) -> str:
+        if not self.ladder:
+            raise VoiceError("AI client not initialized")
+        self.metrics.turns += 1
+        turn_start = self.clock()
+        filler_due = self.on_filler is not None
+        filler_task = None
+        problems = []
+        rungs = self.rungs()
+        if not rungs:
+            raise LatencyError("Only a local model may answer for this household profile, and none is set up")
+        try:
+            for index, rung in enumerate(rungs):
+                rung_start = self.clock()
+                task = asyncio.ensure_future(self._ask(rung, messages, max_tokens))
+                while True:
+                    now = self.clock()
+                    deadline = rung_start + self.budget.hard
+                    wake_at = min(deadline, turn_start + self.budget.soft) if filler_due else deadline
+                    done, _ = await asyncio.wait({task}, timeout=max(0.0, wake_at - now))
+                    if done:
+                        break
+                    if filler_due and self.clock() >= turn_start + self.budget.soft:
+                        filler_due = False
+                        filler_task = asyncio.ensure_future(self._filler())
+                    elif self.clock() >= deadline:
+                        task.cancel()
+                        break
+
+                seconds = self.clock() - rung_start
+                if not task.done() or task.cancelled():
+                    self.metrics.record(rung.name, "timeouts", seconds)
+                    problems.append(f"{rung.name} took over {self.budget.hard:g}s")
+                elif task.exception() is not None:
+                    self.metrics.record(rung.name, "errors", seconds)
+                    problems.append(f"{rung.name}: {task.exception()}")
+                else:
+                    self.metrics.record(rung.name, "ok", seconds)
+                    if index:
+                        self.metrics.degraded += 1
+                        logger.info(f"⏱ Answered by {rung.name} after {'; '.join(problems)}")
+                    return task.result()
+            raise LatencyError("; ".join(problems))
+        finally:
+            if filler_task is not None:
+                await filler_task  # The filler finishes before the answer is said
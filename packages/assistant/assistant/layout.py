@@ -0,0 +1,61 @@
+"""
+Responsive Layout - Fit the dashboard to the terminal it's in.
+
+The full layout (visualizer and tab column on the left, content on the
+right) needs about 120x34. Smaller terminals get a size class, set as a
+CSS class on the app (see styles.tcss) so panels collapse instead of
+overlapping:
+
+- wide: everything
+- medium: narrower tab column, shorter visualizer, the Status charts hidden
+- compact (80x24 and below): one column - the visualizer is hidden, the
+  tabs become an icon row above the content, and the mic state stays in
+  the footer
+
+The class is recomputed on every resize (Textual turns SIGWINCH into a
+Resize event); the dashboard only relayouts when it changes.
+"""
+
+from enum import Enum
+from typing import List, Tuple
+
+# Below these the layout steps down (columns, rows)
+MEDIUM_BELOW = (120, 34)
+COMPACT_BELOW = (90, 26)
+
+# (tab, icon, name), in sidebar order
+TABS: List[Tuple[str, str, str]] = [
+    ("chat", "💬", "Chat"),
+    ("schedule", "📅", "Schedule"),
+    ("projects", "📁", "Projects"),
+    ("settings", "⚙️ ", "Settings"),
+    ("status", "📊", "Status"),
+    ("tools", "🔧", "Tools"),
+    ("workers", "💻", "Workers"),
+    ("inbox", "📥", "Inbox"),
+    ("help", "❓", "Help"),
+]
+
+
+class SizeClass(Enum):
+    WIDE = "wide"
+    MEDIUM = "medium"
+    COMPACT = "compact"
+
+    @property
+    def css_class(self) -> str:
+        return f"-{self.value}"
+
+
+def size_class(width: int, height: int) -> SizeClass:
+    """Which layout fits a `width` x `height` terminal."""
+    if width < COMPACT_BELOW[0] or height < COMPACT_BELOW[1]:
+        return SizeClass.COMPACT
+    if width < MEDIUM_BELOW[0] or height < MEDIUM_BELOW[1]:
+        return SizeClass.MEDIUM
+    return SizeClass.WIDE
+
+
+def tab_label(icon: str, name: str, size: SizeClass) -> str:
+    """Sidebar button text: " 📅  Schedule", or just the icon in the compact tab row."""
+    return icon if size == SizeClass.COMPACT else f" {icon}  {name}"
@@ -0,0 +1,453 @@
+"""
+Lists - Named shopping and todo lists, by voice.
+
+Handled in the dashboard before the model, like timers:
+
+    "add milk and eggs to the shopping list"  -> two items on "shopping"
+    "what's on my todo list?"                 -> reads the open items
+    "cross milk off the shopping list"        -> checks milk off
+    "I got the eggs"                          -> checks off eggs, if a list has them
+    "take bread off the shopping list"        -> removes it
+    "clear the checked items from the shopping list" / "clear the shopping list"
+
+A list exists once something is added to it; its name is what comes before
+"list" ("grocery" is the shopping list, "to-do" the todo list).
+
+Every change is stamped with its time and queued; the "list_sync" job pushes
+the queue (PUT /api/lists/items) and pulls what other devices changed
+(GET /api/lists). The newest change to an item wins. Removed items stay as
+tombstones until the server has them, so the removal syncs too. Once
+memory sync is set up (memory_sync.py), lists travel encrypted with it
+instead and list_sync does nothing.
+
+Shown in the Schedule pane (ListsWidget) and by `xswarm dev list`, which
+also exports a list to Markdown.
+
+Storage: ~/.xswarm/lists/lists.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Dict, List, Optional
+
+from . import endpoints
+from .api_client import ApiError
+from .capabilities import register_capability
+from .verbalize import join_words
+
+logger = logging.getLogger(__name__)
+
+PUSH_BATCH = 500  # Server's limit per PUT (lib/lists.js MAX_BATCH)
+MAX_ITEM_LENGTH = 200
+
+ALIASES = {"grocery": "shopping", "groceries": "shopping", "shop": "shopping", "to-do": "todo",
+           "to do": "todo", "todos": "todo", "to-dos": "todo", "things to do": "todo"}
+
+
+def list_name(raw: str) -> str:
+    """Canonical list name: "my Grocery list" -> "shopping", "To-Do" -> "todo"."""
+    name = re.sub(r"[^\w\s-]", "", raw.lower()).strip()
+    name = re.sub(r"^(?:the|my|our|a)\s+", "", name)
+    name = re.sub(r"\s*\blist$", "", name).strip()
+    if name in ("the", "my", "our", "a"):
+        return ""
+    return ALIASES.get(name, name)
+
+
+def describe_list(name: str) -> str:
+    return f"{name} list"
+
+
+def _clean_item(text: str) -> str:
+    text = re.sub(r"^(?:some|a|an|the|more)\s+", "", text.strip(" ,.!?\"'"), flags=re.I)
+    return text[:MAX_ITEM_LENGTH]
+
+
+def split_items(text: str) -> List[str]:
+    """ "milk, eggs and bread" -> ["milk", "eggs", "bread"]."""
+    parts = re.split(r"\s*,\s*(?:and\s+)?|\s+and\s+", text.strip())
+    return [item for item in (_clean_item(part) for part in parts) if item]
+
+
+def _now() -> str:
+    # UTC, so changes made on machines in different time zones order correctly
+    return datetime.now(timezone.utc).isoformat(timespec="seconds")
+
+
+@dataclass
+class ListItem:
+    list: str
+    text: str
+    done: bool = False
+    deleted: bool = False
+    updated_at: str = field(default_factory=_now)
+    id: str = field(default_factory=lambda: uuid.uuid4().hex[:12])
+
+    @classmethod
+    def from_dict(cls, data: Dict) -> "ListItem":
+        return cls(list=list_name(data["list"]), text=data["text"], done=bool(data.get("done")),
+                   deleted=bool(data.get("deleted")), updated_at=data.get("updated_at") or _now(),
+                   id=data["id"])
+
+
+@dataclass
+class ListCommand:
+    action: str  # add, done, undone, remove, read, clear, clear_done
+    list: str
+    items: List[str] = field(default_factory=list)
+
+
+def _matches(item: ListItem, text: str) -> bool:
+    a, b = item.text.casefold(), text.casefold()
+    return a == b or a.rstrip("s") == b.rstrip("s")
+
+
+class ListStore:
+    """Local lists with an outbound change queue. Follows the InboxStore single-file pattern."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "lists"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "lists.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+        path = self._path()
+        if path.exists():
+            try:
+                self._data = json.loads(path.read_text(encoding="utf-8"))
+                return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load lists: {e}")
+        self._data = {"items": [], "pending": [], "last_synced_at": None}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            self._path().write_text(json.dumps(self._data, indent=2, ensure_ascii=False), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save lists: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    # --------------------------------------------------------------------------
+    # Queries
+    # --------------------------------------------------------------------------
+
+    def _all(self) -> List[ListItem]:
+        return [ListItem.from_dict(raw) for raw in self._load()["items"]]
+
+    def names(self) -> List[str]:
+        """Lists with anything on them, in the order they were started."""
+        return list(dict.fromkeys(item.list for item in self._all() if not item.deleted))
+
+    def items(self, name: str) -> List[ListItem]:
+        name = list_name(name)
+        return [item for item in self._all() if item.list == name and not item.deleted]
+
+    def find(self, name: str, text: str) -> Optional[ListItem]:
+        """The item on `name` that `text` names: exact (ignoring case and plurals), else a partial match."""
+        candidates = self.items(name)
+        for item in candidates:
+            if _matches(item, text):
+                return item
+        partial = [item for item in candidates
+                   if text.casefold() in item.text.casefold() or item.text.casefold() in text.casefold()]
+        return partial[0] if len(partial) == 1 else None
+
+    def list_with(self, text: str) -> Optional[str]:
+        """The only list with an open item matching `text`, if there is just one."""
+        names = [name for name in self.names()
+                 if any(_matches(item, text) and not item.done for item in self.items(name))]
+        return names[0] if len(names) == 1 else None
+
+    @property
+    def pending(self) -> List[ListItem]:
+        queued = set(self._load()["pending"])
+        return [item for item in self._all() if item.id in queued]
+
+    @property
+    def last_synced_at(self) -> Optional[str]:
+        return self._load().get("last_synced_at")
+
+    # --------------------------------------------------------------------------
+    # Changes (each is queued for the server)
+    # --------------------------------------------------------------------------
+
+    def _put(self, item: ListItem, queue: bool = True) -> ListItem:
+        data = self._load()
+        for index, raw in enumerate(data["items"]):
+            if raw["id"] == item.id:
+                data["items"][index] = asdict(item)
+                break
+        else:
+            data["items"].append(asdict(item))
+        if queue and item.id not in data["pending"]:
+            data["pending"].append(item.id)
+        return item
+
+    def _change(self, item: ListItem, **changes) -> ListItem:
+        for key, value in changes.items():
+            setattr(item, key, value)
+        item.updated_at = _now()
+        return self._put(item)
+
+    def add(self, name: str, texts: List[str]) -> Dict[str, List[str]]:
+        """Add items; ones already on the list are left (or reopened, if checked off)."""
+        name, result = list_name(name), {"added": [], "already": []}
+        for text in texts:
+            text = _clean_item(text)
+            if not text:
+                continue
+            existing = self.find(name, text)
+            if existing and _matches(existing, text) and not existing.done:
+                result["already"].append(existing.text)
+            elif existing and _matches(existing, text):
+                self._change(existing, done=False)
+                result["added"].append(existing.text)
+            else:
+                self._put(ListItem(list=name, text=text))
+                result["added"].append(text)
+        self._save()
+        return result
+
+    def set_done(self, name: str, text: str, done: bool = True) -> Optional[ListItem]:
+        item = self.find(name, text)
+        if item:
+            self._change(item, done=done)
+            self._save()
+        return item
+
+    def remove(self, name: str, text: str) -> Optional[ListItem]:
+        item = self.find(name, text)
+        if item:
+            self._change(item, deleted=True)
+            self._save()
+        return item
+
+    def clear(self, name: str, done_only: bool = False) -> int:
+        cleared = [item for item in self.items(name) if item.done or not done_only]
+        for item in cleared:
+            self._change(item, deleted=True)
+        self._save()
+        return len(cleared)
+
+    # --------------------------------------------------------------------------
+    # Sync
+    # --------------------------------------------------------------------------
+
+    def clear_pending(self, item_ids: List[str]) -> None:
+        """The server has these; forget removed ones for good."""
+        data, sent = self._load(), set(item_ids)
+        data["pending"] = [i for i in data["pending"] if i not in sent]
+        data["items"] = [r for r in data["items"] if not (r["id"] in sent and r.get("deleted"))]
+        self._save()
+
+    def merge_remote(self, items: List[Dict], synced_at: Optional[str]) -> int:
+        """Take the server's items where they're newer than ours. Returns how many changed here."""
+        data = self._load()
+        local = {raw["id"]: raw for raw in data["items"]}
+        changed = 0
+        for raw in items:
+            try:
+                theirs = ListItem.from_dict(raw)
+            except (KeyError, TypeError):
+                continue
+            ours = local.get(theirs.id)
+            if ours is not None and ours["updated_at"] >= theirs.updated_at:
+                continue
+            changed += 1
+            if theirs.id in data["pending"]:
+                data["pending"].remove(theirs.id)
+            if theirs.deleted:
+                data["items"] = [r for r in data["items"] if r["id"] != theirs.id]
+            else:
+                self._put(theirs, queue=False)
+        if synced_at:
+            data["last_synced_at"] = synced_at
+        self._save()
+        return changed
+
+    # --------------------------------------------------------------------------
+    # Voice
+    # --------------------------------------------------------------------------
+
+    def apply(self, command: ListCommand) -> str:
+        """Carry out a spoken request and say what happened."""
+        label = describe_list(command.list)
+        if command.action == "add":
+            result = self.add(command.list, command.items)
+            replies = []
+            if result["added"]:
+                replies.append(f"Added {join_words(result['added'])} to the {label}.")
+            if result["already"]:
+                replies.append(f"Already on it: {join_words(result['already'])}.")
+            return " ".join(replies) or "I didn't catch what to add."
+        if command.action == "read":
+            items = self.items(command.list)
+            todo = [item.text for item in items if not item.done]
+            got = [item.text for item in items if item.done]
+            if not items:
+                return f"The {label} is empty."
+            reply = f"The {label} has {join_words(todo)}." if todo else f"Everything on the {label} is checked off."
+            if todo and got:
+                reply += f" Checked off: {join_words(got)}."
+            return reply
+        if command.action in ("clear", "clear_done"):
+            count = self.clear(command.list, done_only=command.action == "clear_done")
+            if not count:
+                return f"Nothing to clear on the {label}."
+            what = "checked-off item" if command.action == "clear_done" else "item"
+            return f"Cleared {count} {what}{'s' if count != 1 else ''} from the {label}."
+
+        done, missing = [], []
+        for text in command.items:
+            if command.action == "remove":
+                item = self.remove(command.list, text)
+            else:
+                item = self.set_done(command.list, text, done=command.action == "done")
+            (done if item else missing).append(item.text if item else text)
+        replies = []
+        if done:
+            verb = {"done": "Checked off", "undone": "Unchecked", "remove": "Took"}[command.action]
+            tail = f" off the {label}" if command.action == "remove" else ""
+            replies.append(f"{verb} {join_words(done)}{tail}.")
+        if missing:
+            verb = "isn't" if len(missing) == 1 else "aren't"
+            replies.append(f"{join_words(missing).capitalize()} {verb} on the {label}.")
+        return " ".join(replies)
+
+
+# ==============================================================================
+# PARSING
+# ==============================================================================
+
+_LIST = r"(?:the\s+|my\s+|our\s+)?(?P<list>[a-z][\w\s-]{0,24}?)\s*list"
+_ITEMS = r"(?P<items>.+?)"
+_PATTERNS = [
+    ("add", rf"(?:add|put|stick|write(?:\s+down)?)\s+{_ITEMS}\s+(?:to|on|onto|in)\s+{_LIST}"),
+    ("read", rf"(?:what's|whats|what is|what are|what's left|what is left)\s+(?:on|in)\s+{_LIST}"),
+    ("read", rf"(?:read(?:\s+me)?|show(?:\s+me)?|tell\s+me|go\s+through)\s+(?:out\s+)?{_LIST}"),
+    ("clear_done", rf"(?:clear|remove|delete)\s+(?:the\s+|all\s+(?:the\s+)?)?(?:done|checked(?:[\s-]off)?|ticked"
+                   rf"(?:[\s-]off)?|completed|finished)\s+(?:items|things|ones)\s+(?:from|off|on)\s+{_LIST}"),
+    ("clear", rf"(?:clear|empty|wipe)\s+(?:out\s+)?{_LIST}"),
+    ("remove", rf"(?:remove|delete|drop|take)\s+{_ITEMS}\s+(?:off|from|off\s+of)\s+{_LIST}"),
+    ("undone", rf"(?:uncheck|untick|unmark)\s+{_ITEMS}(?:\s+(?:on|from)\s+{_LIST})?"),
+    ("done", rf"(?:check|tick|cross|mark)\s+(?:off\s+)?{_ITEMS}(?:\s+off)?(?:\s+as\s+(?:done|bought|got))?"
+             rf"(?:\s+(?:on|from|off)\s+{_LIST})?"),
+    ("done", rf"i(?:'ve|\s+have)?\s+(?:got|bought|picked\s+up|finished|done)\s+{_ITEMS}"),
+]
+_LEAD = re.compile(r"^(?:(?:hey|ok|okay|so|and|please|can you|could you|would you)\b[\s,]*)+")
+
+
+def parse_list_command(text: str, store: Optional[ListStore] = None) -> Optional[ListCommand]:
+    """
+    Recognize a list request. Checking off or unchecking without naming the
+    list needs `store` to find which list has the item; anything that isn't
+    clearly about a list returns None so the model handles it.
+    """
+    cleaned = _LEAD.sub("", text.strip().lower()).strip(" .!?")
+    cleaned = re.sub(r"\s+(?:please|for me|thanks)$", "", cleaned)
+    for action, pattern in _PATTERNS:
+        match = re.fullmatch(pattern, cleaned)
+        if not match:
+            continue
+        raw_list = match.groupdict().get("list")
+        name = list_name(raw_list) if raw_list else ""
+        items = split_items(match.group("items")) if "items" in match.groupdict() else []
+        if "items" in match.groupdict() and not items:
+            return None
+        if not name and store is not None and action in ("done", "undone"):
+            found = {store.list_with(item) for item in items} if action == "done" else \
+                {n for n in store.names() for item in items if store.find(n, item)}
+            name = found.pop() if len(found) == 1 else ""
+        elif not name and store is not None and raw_list is not None and len(store.names()) == 1:
+            name = store.names()[0]  # "add eggs to the list" with only one list
+        if not name:
+            return None
+        return ListCommand(action, name, items)
+    return None
+
+
+def to_markdown(store: ListStore, name: str) -> str:
+    """A list as a Markdown checklist."""
+    name = list_name(name)
+    lines = [f"# {describe_list(name).capitalize()}", ""]
+    lines += [f"- [{'x' if item.done else ' '}] {item.text}" for item in store.items(name)]
+    return "\n".join(lines) + "\n"
+
+
+async def sync_lists(config, user_id: str = "local-user", store: Optional[ListStore] = None, client=None) -> Dict[str, int]:
+    """The list_sync job: push queued changes, then pull other devices'. Errors are left to the caller."""
+    from .api_client import ApiClient, ApiPolicy
+    from .memory_sync import is_enabled
+
+    store = store or get_list_store()
+    if is_enabled():  # memory_sync carries them, encrypted
+        return {"pushed": 0, "changed": 0, "pending": len(store.pending)}
+    own_client = client is None
+    if own_client:
+        client = ApiClient(getattr(config, "server_url", "http://localhost:3000"), getattr(config, "api_token", None),
+                           policy=ApiPolicy.from_config(config))
+    try:
+        pushed = 0
+        pending = store.pending
+        for start in range(0, len(pending), PUSH_BATCH):
+            batch = pending[start:start + PUSH_BATCH]
+            try:
+                await client.call(endpoints.LIST_ITEMS(),
+                                  json={"user_id": user_id, "items": [asdict(item) for item in batch]})
+            except ApiError as e:
+                if not e.permanent:  # Down, signed out, over quota: keep it queued for later
+                    raise
+                # The server will never accept this batch; drop it rather than block the queue
+                logger.warning(f"List changes rejected: {e}")
+            store.clear_pending([item.id for item in batch])
+            pushed += len(batch)
+
+        params = {"user_id": user_id}
+        if store.last_synced_at:
+            params["since"] = store.last_synced_at
+        body = (await client.call(endpoints.LISTS(), params=params)).json()
+        changed = store.merge_remote(body.get("items", []), body.get("synced_at"))
+        return {"pushed": pushed, "changed": changed, "pending": len(store.pending)}
+    finally:
+        if own_client:
+            await client.close()
+
+
+_store: Optional[ListStore] = None
+
+
+def get_list_store() -> ListStore:
+    """Get the global list store."""
+    global _store
+    if _store is None:
+        _store = ListStore()
+    return _store
+
+
+def set_list_store(store: Optional[ListStore]) -> None:
+    global _store
+    _store = store
+
+
+register_capability("Lists", "Shopping, todo and any other lists, ticked off as you go",
+                    ["add milk and eggs to the shopping list", "what's on my todo list?",
+                     "cross milk off the shopping list", "clear the checked items from the shopping list"],
+                    keywords=("groceries", "grocery", "todo", "to-do"))
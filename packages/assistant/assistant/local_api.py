@@ -0,0 +1,401 @@
+"""
+Local API - A REST API on localhost for scripts and local tools.
+
+Off by default (config.local_api). When on, the dashboard serves it on
+127.0.0.1:config.local_api_port while it runs:
+
+    GET  /appointments?days=7   upcoming calendar events (CalendarEvent, planner.py)
+    GET  /free-busy?days=7      the busy times in them, merged, without what they are
+    POST /reminders             {"title", "when", "description"}: a server reminder (SMS/email when due)
+    POST /speak                 {"text", "priority"}: say it out loud (quiet hours and meetings apply)
+    POST /speaker               {"speaker"}: who speaker identification heard; switches household profile
+    GET  /openapi.json          the OpenAPI schema (no token needed)
+    GET  /calendar/<token>.ics  the ICS feed, when config.ics_feed is "local" (ics_feed.py; the token in the URL)
+
+Everything else needs `Authorization: Bearer <token>`. The token is made on
+first start at ~/.xswarm/local_api_token (owner-only); `xswarm dev api
+token` prints it and `--rotate` replaces it (read on every request, so the
+old one stops working at once).
+
+Both calendar endpoints share events at their visibility (calendar_core.py):
+a "busy" one is listed as "Busy" with its time only, a "private" one not at
+all, and events without one follow config.shared_visibility.
+
+Operations are declared once, in OPERATIONS: method, path, and request and
+response dataclasses. The OpenAPI schema is generated from the dataclasses,
+and `xswarm mcp` (mcp_server.py) offers the same operations as MCP tools by
+calling this API, so the three can't drift apart. Errors are JSON
+{"error": ...} with 400 (bad request), 401 (token), 404, 413 (body over
+MAX_BODY), 502 (the server behind it failed) or 503 (not available now).
+"""
+
+import asyncio
+import dataclasses
+import json
+import logging
+import os
+import secrets
+import tempfile
+import typing
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Awaitable, Callable, Dict, List, Optional, Tuple
+from urllib.parse import parse_qsl, urlsplit
+
+from .calendar_core import busy_times, shared_view
+from .planner import CalendarEvent
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_PORT = 8766
+MAX_BODY = 64 * 1024
+TIMEOUT = 10.0  # Seconds to read a request
+TOKEN_PATH = Path.home() / ".xswarm" / "local_api_token"
+
+
+class LocalApiError(Exception):
+    """A handler refusing a request; sent as {"error": message} with `status`."""
+
+    def __init__(self, status: int, message: str):
+        super().__init__(message)
+        self.status = status
+
+
+# ==============================================================================
+# OPERATIONS
+# ==============================================================================
+
+@dataclass
+class AppointmentsQuery:
+    days: int = 7  # How many days ahead, from today
+
+
+@dataclass
+class BusyTime:
+    start_time: str  # ISO 8601
+    end_time: str
+
+
+@dataclass
+class ReminderRequest:
+    title: str
+    when: str  # "tomorrow at 9am", "friday at half past three" or an ISO 8601 datetime
+    description: str = ""
+
+
+@dataclass
+class ReminderCreated:
+    id: str
+    title: str
+    due_time: str  # ISO 8601
+
+
+@dataclass
+class SpeakRequest:
+    text: str
+    priority: str = "normal"  # low, normal, high or emergency
+
+
+@dataclass
+class SpeakResult:
+    spoken: bool  # False when only shown: voice off, quiet hours, a meeting or do not disturb
+    message: str
+
+
+@dataclass
+class SpeakerRequest:
+    speaker: str  # A label from config.household_profiles[...].speakers or household_owner_speakers
+
+
+@dataclass
+class SpeakerResult:
+    profile: str  # The active household profile afterwards
+    switched: bool
+
+
+@dataclass
+class Operation:
+    name: str  # Also the MCP tool name
+    method: str
+    path: str
+    summary: str
+    request: type
+    response: type
+    many: bool = False  # The response is a list of `response`
+
+
+OPERATIONS = [
+    Operation("list_appointments", "GET", "/appointments", "Upcoming calendar events",
+              AppointmentsQuery, CalendarEvent, many=True),
+    Operation("free_busy", "GET", "/free-busy", "Busy times in the coming days, without what they are",
+              AppointmentsQuery, BusyTime, many=True),
+    Operation("create_reminder", "POST", "/reminders", "Create a reminder the server sends by SMS/email when due",
+              ReminderRequest, ReminderCreated),
+    Operation("speak", "POST", "/speak", "Say something out loud through the assistant",
+              SpeakRequest, SpeakResult),
+    Operation("set_speaker", "POST", "/speaker",
+              "Report who speaker identification heard, switching to their household profile",
+              SpeakerRequest, SpeakerResult),
+]
+
+Handler = Callable[[Any], Awaitable[Any]]
+
+
+# ==============================================================================
+# SCHEMAS
+# ==============================================================================
+
+def _public_fields(cls: type) -> List[dataclasses.Field]:
+    return [f for f in dataclasses.fields(cls) if not f.name.startswith("_")]
+
+
+def type_schema(hint: Any) -> Dict[str, Any]:
+    """JSON schema for a type hint (str, int, float, bool, Optional, List, Dict, dataclasses)."""
+    origin, args = typing.get_origin(hint), typing.get_args(hint)
+    if origin is typing.Union:
+        others = [arg for arg in args if arg is not type(None)]
+        schema = type_schema(others[0]) if len(others) == 1 else {"anyOf": [type_schema(arg) for arg in others]}
+        return {**schema, "nullable": True} if type(None) in args else schema
+    if origin in (list, List):
+        return {"type": "array", "items": type_schema(args[0]) if args else {}}
+    if origin in (dict, Dict):
+        return {"type": "object", "additionalProperties": type_schema(args[1]) if args else {}}
+    if dataclasses.is_dataclass(hint):
+        return object_schema(hint)
+    return {str: {"type": "string"}, int: {"type": "integer"}, float: {"type": "number"},
+            bool: {"type": "boolean"}}.get(hint, {})
+
+
+def object_schema(cls: type) -> Dict[str, Any]:
+    """JSON schema for a dataclass; fields without a default are required, _private ones are left out."""
+    hints = typing.get_type_hints(cls)
+    fields = _public_fields(cls)
+    schema: Dict[str, Any] = {"type": "object",
+                              "properties": {f.name: type_schema(hints[f.name]) for f in fields}}
+    required = [f.name for f in fields
+                if f.default is dataclasses.MISSING and f.default_factory is dataclasses.MISSING]
+    if required:
+        schema["required"] = required
+    return schema
+
+
+def openapi(port: int = DEFAULT_PORT) -> Dict[str, Any]:
+    """The OpenAPI document for OPERATIONS."""
+    from . import __version__
+
+    paths: Dict[str, Any] = {}
+    for op in OPERATIONS:
+        response = {"$ref": f"#/components/schemas/{op.response.__name__}"}
+        operation: Dict[str, Any] = {
+            "operationId": op.name,
+            "summary": op.summary,
+            "responses": {
+                "200": {"description": "OK", "content": {"application/json": {
+                    "schema": {"type": "array", "items": response} if op.many else response}}},
+                "default": {"description": "Error", "content": {"application/json": {
+                    "schema": {"$ref": "#/components/schemas/Error"}}}},
+            },
+        }
+        if op.method == "GET":
+            hints = typing.get_type_hints(op.request)
+            operation["parameters"] = [{"name": f.name, "in": "query", "schema": type_schema(hints[f.name])}
+                                       for f in _public_fields(op.request)]
+        else:
+            operation["requestBody"] = {"required": True, "content": {"application/json": {
+                "schema": {"$ref": f"#/components/schemas/{op.request.__name__}"}}}}
+        paths.setdefault(op.path, {})[op.method.lower()] = operation
+
+    schemas = {"Error": {"type": "object", "properties": {"error": {"type": "string"}}, "required": ["error"]}}
+    for op in OPERATIONS:
+        for cls in (op.request, op.response):
+            schemas[cls.__name__] = object_schema(cls)
+    return {
+        "openapi": "3.0.3",
+        "info": {"title": "xSwarm local API", "version": __version__},
+        "servers": [{"url": f"http://127.0.0.1:{port}"}],
+        "security": [{"bearer": []}],
+        "paths": paths,
+        "components": {"schemas": schemas,
+                       "securitySchemes": {"bearer": {"type": "http", "scheme": "bearer"}}},
+    }
+
+
+def parse_request(cls: type, data: Dict[str, Any]) -> Any:
+    """Build a request dataclass from JSON (or query strings); LocalApiError 400 when it doesn't fit."""
+    if not isinstance(data, dict):
+        raise LocalApiError(400, "Expected a JSON object")
+    hints = typing.get_type_hints(cls)
+    values = {}
+    for f in _public_fields(cls):
+        if f.name not in data:
+            if f.default is dataclasses.MISSING and f.default_factory is dataclasses.MISSING:
+                raise LocalApiError(400, f"Missing field: {f.name}")
+            continue
+        value, hint = data[f.name], hints[f.name]
+        try:
+            if hint is int and not isinstance(value, bool):
+                value = int(value)
+            elif hint is float:
+                value = float(value)
+            elif hint is bool and isinstance(value, str):
+                value = value.lower() in ("1", "true", "yes")
+        except (TypeError, ValueError):
+            raise LocalApiError(400, f"{f.name} must be a number")
+        if hint is str and not isinstance(value, str):
+            raise LocalApiError(400, f"{f.name} must be a string")
+        values[f.name] = value
+    return cls(**values)
+
+
+def shared_appointments(events: List[CalendarEvent], default: str = "public") -> List[CalendarEvent]:
+    """`events` as outside integrations may see them: private ones left out, busy-only ones as "Busy"."""
+    shared = (shared_view(dataclasses.asdict(event), default) for event in events)
+    return [CalendarEvent(**view) for view in shared if view is not None]
+
+
+def shared_busy_times(events: List[Any], default: str = "public") -> List[BusyTime]:
+    return [BusyTime(start, end) for start, end in busy_times(events, default)]
+
+
+def to_json(value: Any) -> Any:
+    if isinstance(value, list):
+        return [to_json(item) for item in value]
+    if dataclasses.is_dataclass(value):
+        return {key: item for key, item in dataclasses.asdict(value).items() if not key.startswith("_")}
+    return value
+
+
+# ==============================================================================
+# TOKEN
+# ==============================================================================
+
+def load_token(path: Optional[Path] = None, rotate: bool = False, create: bool = True) -> Optional[str]:
+    """The API token, made (owner-only) if there isn't one yet or `rotate`; None when missing and not `create`."""
+    path = path or TOKEN_PATH
+    if path.exists() and not rotate:
+        return path.read_text(encoding="utf-8").strip()
+    if not create and not rotate:
+        return None
+    token = secrets.token_urlsafe(32)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    # Written owner-only from the start (mkstemp) and swapped in whole, so it's never readable by others
+    fd, temp = tempfile.mkstemp(dir=path.parent, prefix=f".{path.name}.")
+    try:
+        with os.fdopen(fd, "w", encoding="utf-8") as file:
+            file.write(token + "\n")
+        os.replace(temp, path)
+    except BaseException:
+        Path(temp).unlink(missing_ok=True)
+        raise
+    return token
+
+
+# ==============================================================================
+# SERVER
+# ==============================================================================
+
+REASONS = {200: "OK", 400: "Bad Request", 401: "Unauthorized", 404: "Not Found", 405: "Method Not Allowed",
+           413: "Payload Too Large", 500: "Internal Server Error", 502: "Bad Gateway", 503: "Service Unavailable"}
+
+
+class LocalApi:
+    """Serves OPERATIONS over HTTP on localhost with the given handlers (operation name -> async handler)."""
+
+    def __init__(self, handlers: Dict[str, Handler], port: int = DEFAULT_PORT, token_path: Optional[Path] = None,
+                 host: str = "127.0.0.1", feed: Optional[Callable[[str], Optional[str]]] = None):
+        self.handlers = handlers
+        self.feed = feed  # Token from the URL -> the ICS feed, None for a wrong one (ics_feed.serve_feed)
+        self.host = host
+        self.port = port
+        self.token_path = token_path or TOKEN_PATH
+        self._server: Optional[asyncio.AbstractServer] = None
+
+    async def start(self) -> bool:
+        """Listen; False (logged) when the port can't be opened."""
+        try:
+            load_token(self.token_path)
+            self._server = await asyncio.start_server(self._serve, self.host, self.port)
+        except OSError as e:
+            logger.warning(f"Could not open the local API on port {self.port}: {e}")
+            self._server = None
+            return False
+        if not self.port:
+            self.port = self._server.sockets[0].getsockname()[1]
+        logger.info(f"Local API listening on http://{self.host}:{self.port}")
+        return True
+
+    def _authorized(self, headers: Dict[str, str]) -> bool:
+        token = load_token(self.token_path, create=False)
+        return bool(token) and secrets.compare_digest(headers.get("authorization", ""), f"Bearer {token}")
+
+    async def handle(self, method: str, target: str, headers: Dict[str, str], body: bytes) -> Tuple[int, Any]:
+        """(status, JSON payload) for one request; the ICS feed is a str payload."""
+        url = urlsplit(target)
+        if method == "GET" and url.path == "/openapi.json":
+            return 200, openapi(self.port)
+        if method == "GET" and self.feed is not None and url.path.startswith("/calendar/") \
+                and url.path.endswith(".ics"):
+            ics = await asyncio.to_thread(self.feed, url.path[len("/calendar/"):-len(".ics")])
+            return (200, ics) if ics is not None else (404, {"error": "No such feed"})
+        matching = [op for op in OPERATIONS if op.path == url.path.rstrip("/")]
+        if not matching:
+            return 404, {"error": f"No such endpoint: {url.path}"}
+        op = next((op for op in matching if op.method == method), None)
+        if op is None:
+            return 405, {"error": f"{url.path} takes {', '.join(op.method for op in matching)}"}
+        if not self._authorized(headers):
+            return 401, {"error": "Missing or wrong token (Authorization: Bearer ..., see `xswarm dev api token`)"}
+        handler = self.handlers.get(op.name)
+        if handler is None:
+            return 503, {"error": f"{op.name} isn't available right now"}
+        try:
+            if method == "GET":
+                data = dict(parse_qsl(url.query))
+            else:
+                try:
+                    data = json.loads(body or b"{}")
+                except ValueError:
+                    raise LocalApiError(400, "Body isn't valid JSON")
+            return 200, to_json(await handler(parse_request(op.request, data)))
+        except LocalApiError as e:
+            return e.status, {"error": str(e)}
+        except Exception as e:
+            logger.warning(f"Local API {op.name} failed: {e}")
+            return 500, {"error": str(e)}
+
+    async def _serve(self, reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        try:
+            try:
+                request_line = await asyncio.wait_for(reader.readline(), TIMEOUT)
+                method, target, _ = request_line.decode("latin-1").split(" ", 2)
+                headers = {}
+                while True:
+                    line = await asyncio.wait_for(reader.readline(), TIMEOUT)
+                    if line in (b"\r\n", b"\n", b""):
+                        break
+                    name, _, value = line.decode("latin-1").partition(":")
+                    headers[name.strip().lower()] = value.strip()
+                length = int(headers.get("content-length") or 0)
+                if length > MAX_BODY:
+                    status, payload = 413, {"error": f"Body over {MAX_BODY} bytes"}
+                else:
+                    body = await asyncio.wait_for(reader.readexactly(length), TIMEOUT) if length else b""
+                    status, payload = await self.handle(method.upper(), target, headers, body)
+            except (ValueError, asyncio.TimeoutError, asyncio.IncompleteReadError):
+                status, payload = 400, {"error": "Bad request"}
+            if isinstance(payload, str):
+                data, content_type = payload.encode("utf-8"), "text/calendar; charset=utf-8"
+            else:
+                data, content_type = json.dumps(payload).encode(), "application/json"
+            writer.write(f"HTTP/1.1 {status} {REASONS.get(status, 'Error')}\r\nContent-Type: {content_type}\r\n"
+                         f"Content-Length: {len(data)}\r\nConnection: close\r\n\r\n".encode() + data)
+            await writer.drain()
+        except ConnectionError:
+            pass
+        finally:
+            writer.close()
+
+    def close(self) -> None:
+        if self._server is not None:
+            self._server.close()
+            self._server = None
@@ -0,0 +1,123 @@
+"""
+Log rotation - The log file rotated, compressed and kept under a size cap.
+
+The assistant logs to ~/.xswarm/logs/xswarm.log, appending across runs.
+RotatingLog starts a new file when the current one passes
+config.log_max_mb or is older than config.log_rotate_hours, whichever
+comes first; the old one is gzipped to xswarm-<date>-<time>.log.gz next
+to it. After each rotation the oldest archives are deleted until the
+folder is under config.log_total_mb (the live log counts too).
+
+    xswarm dev logs prune            # apply the cap now: which archives go
+    xswarm dev logs prune --dry-run  # only list them
+
+Archives past the logs retention period (retention.py) are deleted by the
+retention job as well, whatever their size.
+"""
+
+import gzip
+import logging
+import logging.handlers
+import os
+import shutil
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import List, Optional, Tuple
+
+LOG_DIR = Path.home() / ".xswarm" / "logs"
+LOG_NAME = "xswarm.log"
+MAX_MB = 10.0  # Rotate past this size
+ROTATE_HOURS = 24.0  # ...or this age
+TOTAL_MB = 100.0  # The live log and its archives together
+MB = 1024 * 1024
+
+
+def archives(directory: Path, name: str = LOG_NAME) -> List[Path]:
+    """The compressed logs rotated out of `name`, oldest first."""
+    stem = Path(name).stem
+    return sorted(directory.glob(f"{stem}-*.log.gz"), key=lambda path: path.name[:-len(".log.gz")])
+
+
+def prune(directory: Path, total_mb: float = TOTAL_MB, name: str = LOG_NAME,
+          dry_run: bool = False) -> List[Tuple[Path, int]]:
+    """Delete the oldest archives until the folder's logs fit in `total_mb`; (path, bytes) of each deleted."""
+    live = directory / name
+    old = [(path, path.stat().st_size) for path in archives(directory, name)]
+    used = sum(size for _, size in old) + (live.stat().st_size if live.exists() else 0)
+    deleted = []
+    for path, size in old:
+        if used <= total_mb * MB:
+            break
+        if not dry_run:
+            path.unlink(missing_ok=True)
+        deleted.append((path, size))
+        used -= size
+    return deleted
+
+
+class RotatingLog(logging.handlers.RotatingFileHandler):
+    """A log file rotated by size or age into gzipped archives, kept under a total cap."""
+
+    def __init__(self, path: Path = LOG_DIR / LOG_NAME, max_mb: float = MAX_MB,
+                 rotate_hours: float = ROTATE_HOURS, total_mb: float = TOTAL_MB):
+        path.parent.mkdir(parents=True, exist_ok=True)
+        super().__init__(path, mode="a", maxBytes=int(max_mb * MB), encoding="utf-8", delay=False)
+        self.rotate_seconds = rotate_hours * 3600
+        self.total_mb = total_mb
+        self.opened_at = self._started(path)
+
+    @staticmethod
+    def _started(path: Path) -> float:
+        """When the current file was started: the time on its first line (the log format starts with it)."""
+        try:
+            with open(path, encoding="utf-8", errors="replace") as log:
+                first = log.readline()
+        except OSError:
+            return time.time()
+        try:
+            return datetime.strptime(first[:19], "%Y-%m-%d %H:%M:%S").timestamp()
+        except ValueError:
+            return time.time()
+
+    def configure(self, config) -> None:
+        """Apply config.log_max_mb, log_rotate_hours and log_total_mb (known once config is loaded)."""
+        self.maxBytes = int(float(getattr(config, "log_max_mb", MAX_MB)) * MB)
+        self.rotate_seconds = float(getattr(config, "log_rotate_hours", ROTATE_HOURS)) * 3600
+        self.total_mb = float(getattr(config, "log_total_mb", TOTAL_MB))
+
+    def shouldRollover(self, record) -> bool:
+        if self.rotate_seconds and time.time() - self.opened_at >= self.rotate_seconds:
+            return bool(self.stream and self.stream.tell())
+        return bool(super().shouldRollover(record))
+
+    def doRollover(self) -> None:
+        if self.stream:
+            self.stream.close()
+            self.stream = None
+        path = Path(self.baseFilename)
+        if path.exists() and path.stat().st_size:
+            stamp = f"{path.stem}-{datetime.now():%Y%m%d-%H%M%S}"
+            archive, n = path.with_name(f"{stamp}.log.gz"), 1
+            while archive.exists():  # Rotated twice within a second
+                archive, n = path.with_name(f"{stamp}-{n}.log.gz"), n + 1
+            try:
+                with open(path, "rb") as source, gzip.open(archive, "wb") as target:
+                    shutil.copyfileobj(source, target)
+                os.remove(path)
+            except OSError:
+                archive.unlink(missing_ok=True)  # Keep appending to the file rather than lose it
+        self.stream = self._open()
+        self.opened_at = time.time()
+        try:
+            prune(path.parent, self.total_mb, path.name)
+        except OSError:
+            pass
+
+
+def find_rotating_log() -> Optional[RotatingLog]:
+    """The root logger's RotatingLog, if main() installed one."""
+    for handler in logging.getLogger().handlers:
+        if isinstance(handler, RotatingLog):
+            return handler
+    return None
@@ -29,7 +29,7 @@ import asyncio
 import signal
 import sys
 from pathlib import Path
-from typing import Optional, cast
+from typing import List, Optional, cast
 import argparse
 import atexit
 import logging
@@ -192,7 +192,6 @@ class VoiceAssistant:
         from .memory import MemoryManager
         from .wake_word import WakeWordDetector
         from .dashboard import VoiceAssistantApp
-        from .scheduler import Scheduler
 
         # 1. Load personas
         if not self.personas_dir.exists():
@@ -271,15 +270,23 @@ class VoiceAssistant:
         # 3. Initialize dashboard (TUI)
         self.app = VoiceAssistantApp(self.config, self.personas_dir, voice_server_process=self.voice_server_process, voice_queues=self.voice_queues)
 
-        # 4. Initialize Scheduler (connects to Thinking Engine)
-        # Note: Thinking Engine is created inside VoiceAssistantApp, so we access it there
-        if hasattr(self.app, 'thinking_engine'):
-            self.scheduler = Scheduler(self.app.thinking_engine)
-            self.scheduler.start()
+        # 4. Background job scheduler (owned by the app, started on mount inside the TUI's event loop)
+        self.scheduler = self.app.job_scheduler
 
     async def run(self):
         """Run the application"""
         self.is_running = True
+        from .accessibility import get_a11y_stream
+        stream = get_a11y_stream()
+        if stream and stream.replaces_tui:
+            # Accessibility mode: no alt-screen or redraws, the stream is the interface
+            try:
+                await self.app.run_async(headless=True)
+            except KeyboardInterrupt:
+                logger.debug("Shutting down...")
+            finally:
+                await self.cleanup()
+            return
 
         # Aggressively clean up terminal state before TUI starts
         # This prevents stray characters from appearing after splash screen
@@ -347,17 +354,1370 @@ async def show_wizard(personas_dir: Path) -> "Config":
     return cast(Config, await wizard_app.run_async())
 
 
+def run_inbox_command(config_path: Optional[Path] = None) -> int:
+    """Sync the unified inbox with the server and print it (no TUI)."""
+    from .config import Config
+    from .events import EventStore
+    from .inbox import InboxManager, format_inbox
+
+    config = Config.load_from_file(config_path)
+    manager = InboxManager(config, events=EventStore.from_config(config))
+
+    async def _sync():
+        try:
+            return await manager.sync()
+        finally:
+            await manager.close()
+
+    result = asyncio.run(_sync())
+    items = manager.store.get_items()
+    print(f"Inbox: {manager.store.unread_count()} unread, {len(items)} total "
+          f"({result['pending']} change(s) waiting to sync)")
+    print(format_inbox(items, limit=50))
+    return 0
+
+
+def run_undo_command(list_only: bool = False) -> int:
+    """Undo the last destructive action (or list what can be undone), no TUI."""
+    from .tools import get_undo_log, list_undoable_actions, undo_last
+
+    if list_only:
+        print(list_undoable_actions())
+        log = get_undo_log()
+        done = [e for e in log.entries() if e.undone_at]
+        if done:
+            print("Recently undone:")
+            for entry in done[:10]:
+                print(f"  {entry.description} (undone {entry.undone_at[11:16]})")
+        return 0
+
+    result = undo_last()
+    print(result)
+    return 0 if result.startswith("✓") else 1
+
+
+def _headless_job_scheduler(config) -> "Scheduler":
+    """Job scheduler with the jobs that can run without the TUI (for `dev jobs`)."""
+    from .calendar_mirror import CalendarMirror
+    from .events import EventStore
+    from .geocoding import LocationSettings, geocode_events
+    from .ics_feed import publish as publish_feed
+    from .inbox import InboxManager
+    from .lists import sync_lists
+    from .memory_sync import sync_memory
+    from .project_docs import ProjectDocs
+    from .quota import sync_quota
+    from .retention import RetentionEngine
+    from .scheduler import JobStateStore, Scheduler
+    from .scheduler_client import CalendarSync, SchedulerClient
+    from .tools import get_planner_data
+
+    scheduler = Scheduler(config=config, store=JobStateStore())
+
+    async def inbox_sync():
+        manager = InboxManager(config, events=EventStore.from_config(config))
+        try:
+            await manager.sync()
+        finally:
+            await manager.close()
+
+    async def calendar_sync():
+        client = SchedulerClient.from_config(config)
+        mirror = CalendarMirror(client)
+        try:
+            sync = CalendarSync(get_planner_data(), client, mirror=mirror)
+            await sync.push()
+            await sync.pull()
+        finally:
+            mirror.close()
+            await client.close()
+
+    async def geocode_locations():
+        geocoder = LocationSettings.from_config(config).make_geocoder()
+        if geocoder is not None:
+            await geocode_events(get_planner_data(), geocoder)
+
+    # Same names and schedules as the dashboard registers, so status lines up
+    scheduler.add_job("inbox_sync", inbox_sync, interval=60, jitter=10,
+                      description="Sync inbox and voicemail with the server")
+    scheduler.add_job("calendar_sync", calendar_sync, interval=15 * 60, jitter=60,
+                      description="Mirror the calendar to the server and pull its changes")
+    scheduler.add_job("geocode_locations", geocode_locations, interval=10 * 60, jitter=60,
+                      description="Look up map coordinates for event locations")
+    scheduler.add_job("retention", lambda: RetentionEngine.from_config(config).run(), cron="30 3 * * *",
+                      description="Delete transcripts, recordings, events and logs past their retention")
+    scheduler.add_job("list_sync", lambda: sync_lists(config), interval=5 * 60, jitter=30,
+                      description="Sync shopping and todo lists with the server")
+    scheduler.add_job("memory_sync", lambda: sync_memory(config), interval=5 * 60, jitter=30,
+                      description="Sync facts, lists and preferences with the user's other machines (encrypted)")
+    if config.ics_feed == "server":
+        scheduler.add_job("ics_feed", lambda: publish_feed(config), interval=10 * 60, jitter=30,
+                          description="Upload the calendar feed for calendar apps when it changed")
+    scheduler.add_job("quota_sync", lambda: sync_quota(config), interval=15 * 60, jitter=60,
+                      description="Report phone and SMS usage to the server and refresh what's left")
+    scheduler.add_job("document_indexing", lambda: ProjectDocs.from_config(config).index_all(get_planner_data()),
+                      interval=6 * 60 * 60, jitter=5 * 60,
+                      description="Index active projects' folders for project questions")
+    return scheduler
+
+
+def run_jobs_command(action: str, name: Optional[str] = None, config_path: Optional[Path] = None) -> int:
+    """List background jobs with their last-run status, or run one now (no TUI)."""
+    from .config import Config
+    from .scheduler import format_job_table
+
+    config = Config.load_from_file(config_path)
+    scheduler = _headless_job_scheduler(config)
+
+    if action == "list":
+        rows = scheduler.list_tasks()
+        # Jobs only the dashboard registers (e.g. event reminders) are known from their saved status
+        for job_name, state in scheduler.store.all().items():
+            if job_name not in scheduler.tasks:
+                rows.append(dict(state, name=job_name, schedule=f"{state.get('schedule', '?')} (dashboard)"))
+        print(format_job_table(rows))
+        return 0
+
+    if name not in scheduler.tasks:
+        known = scheduler.store.all()
+        if name in known:
+            print(f"✗ {name} only runs inside the dashboard")
+        else:
+            print(f"✗ Unknown job '{name}'. Run `xswarm dev jobs list` to see jobs.")
+        return 1
+
+    task = asyncio.run(scheduler.run_now(name))
+    if task.last_status == "ok":
+        print(f"✓ {name} finished in {task.last_duration:.1f}s")
+        return 0
+    print(f"✗ {name} {task.last_status}: {task.last_error}")
+    return 1
+
+
+def run_replay_command(scenario_paths: List[Path], config_path: Optional[Path] = None,
+                       chaos_spec: Optional[str] = None) -> int:
+    """Replay voice scenarios with mocked AI/TTS and print their transcripts. 1 if any failed."""
+    from .chaos import Chaos
+    from .config import Config
+    from .replay import ReplayRunner, Scenario, ScenarioError, VoskFileTranscriber
+
+    try:
+        chaos = Chaos.parse(chaos_spec) if chaos_spec else None
+    except ValueError as e:
+        print(f"✗ {e}")
+        return 1
+    config = Config.load_from_file(config_path)
+    transcriber = None
+    failed = 0
+    for path in scenario_paths:
+        try:
+            scenario = Scenario.load(path)
+            if transcriber is None and any(turn.audio for turn in scenario.turns):
+                transcriber = VoskFileTranscriber(config.wake_word_model)
+            result = asyncio.run(ReplayRunner(scenario, transcriber, base_config=config, chaos=chaos).run())
+        except ScenarioError as e:
+            print(f"✗ {path}: {e}")
+            failed += 1
+            continue
+        print("\n".join(result.lines()))
+        if not result.passed:
+            failed += 1
+    if len(scenario_paths) > 1:
+        print(f"\n{len(scenario_paths) - failed}/{len(scenario_paths)} scenarios passed")
+    return 1 if failed else 0
+
+
+def run_events_command(types: Optional[List[str]], since: Optional[str], limit: int,
+                       config_path: Optional[Path] = None, thread: Optional[str] = None) -> int:
+    """Print recorded activity and inbox events, oldest first (see events.py)."""
+    from .config import Config
+    from .events import EVENT_TYPES, EventStore, parse_since
+    from .redaction import Redactor
+
+    config = Config.load_from_file(config_path)
+    types = [t.strip() for value in types or [] for t in value.split(",") if t.strip()]
+    unknown = [t for t in types if t not in EVENT_TYPES]
+    if unknown:
+        print(f"✗ Unknown event type '{unknown[0]}' (types: {', '.join(EVENT_TYPES)})")
+        return 1
+    try:
+        start = parse_since(since) if since else None
+    except ValueError as e:
+        print(f"✗ {e}")
+        return 1
+    events = EventStore.from_config(config).query(types, since=start, limit=limit, thread=thread)
+    if not events:
+        print("No events")
+        return 0
+    redactor = Redactor.from_config(config)
+    print("\n".join(redactor(event.line()) for event in events))
+    return 0
+
+
+def run_events_replay_command(when: Optional[str], thread: Optional[str], tui: bool,
+                              config_path: Optional[Path] = None) -> int:
+    """What happened around a moment, interaction by interaction, with the time each step took (see timeline.py)."""
+    from .ai_log import AILog
+    from .config import Config
+    from .events import EventStore
+    from .redaction import Redactor
+    from .timeline import build_timelines, parse_range
+
+    config = Config.load_from_file(config_path)
+    try:
+        start, end = parse_range(when) if when else (None, None)
+    except ValueError as e:
+        print(f"✗ {e}")
+        return 1
+    if start is None and thread is None:
+        print("✗ Give a time (3:04pm, 9am..9:30am, 2h) or --thread ID")
+        return 1
+    ai_log = AILog(AILog.DEFAULT_PATH) if AILog.DEFAULT_PATH.exists() else None
+    try:
+        timelines = build_timelines(EventStore.from_config(config), start, end, ai_log, thread)
+    finally:
+        if ai_log is not None:
+            ai_log.close()
+    if not timelines:
+        print("No events")
+        return 0
+    redactor = Redactor.from_config(config)
+    if tui:
+        show_timelines(timelines, redactor)
+        return 0
+    print("\n".join(redactor(line) for timeline in timelines for line in timeline.lines()))
+    return 0
+
+
+def show_timelines(timelines, redactor) -> None:
+    """Replayed interactions in the dashboard's activity feed, threads collapsible (q quits)."""
+    from textual.app import App, ComposeResult
+
+    from .dashboard_widgets import ActivityFeed
+
+    class TimelineApp(App):
+        BINDINGS = [("q", "quit", "Quit")]
+
+        def compose(self) -> ComposeResult:
+            yield ActivityFeed(max_messages=10000, id="activity")
+
+        def on_mount(self) -> None:
+            feed = self.query_one(ActivityFeed)
+            for timeline in timelines:
+                for step in timeline.steps:
+                    took = f" ({step.took:.2f}s)" if step.took is not None else ""
+                    feed.add_message(redactor(f"{step.stage}: {step.summary}{took}"), step.level,
+                                     thread=timeline.thread, at=step.time)
+            feed.focus()
+
+    TimelineApp().run()
+
+
+def run_ai_log_command(since: Optional[str], provider: Optional[str], purpose: Optional[str], limit: int,
+                       show: Optional[int], config_path: Optional[Path] = None) -> int:
+    """Model calls from the AI log, with tokens and personal details sent per provider (see ai_log.py)."""
+    from .ai_log import AILog, format_call, summarize
+    from .config import Config
+    from .events import parse_since
+
+    config = Config.load_from_file(config_path)
+    if not AILog.DEFAULT_PATH.exists():
+        print(f"No AI calls logged yet (config.ai_log is '{config.ai_log}')")
+        return 0
+    log = AILog(AILog.DEFAULT_PATH)
+    try:
+        if show is not None:
+            call = log.get(show)
+            if call is None:
+                print(f"✗ No AI call {show}")
+                return 1
+            print(format_call(call))
+            return 0
+        try:
+            start = parse_since(since) if since else None
+        except ValueError as e:
+            print(f"✗ {e}")
+            return 1
+        calls = log.query(start, provider, purpose, limit)
+        if not calls:
+            print("No AI calls")
+            return 0
+        print("\n".join(call.line() for call in calls))
+        print()
+        print("\n".join(summarize(calls)))
+    finally:
+        log.close()
+    return 0
+
+
+def run_analytics_command(period: str, output_format: str, output: Optional[Path] = None,
+                          config_path: Optional[Path] = None) -> int:
+    """Usage analytics for the last day or week, as text or exported as JSON/CSV (see analytics.py)."""
+    from .analytics import build_report
+    from .config import Config
+    from .events import EventStore
+
+    config = Config.load_from_file(config_path)
+    report = build_report(EventStore.from_config(config), period)
+    if output_format == "json":
+        text = report.to_json() + "\n"
+    elif output_format == "csv":
+        text = report.to_csv()
+    else:
+        text = "\n".join(report.lines()) + "\n"
+    if output:
+        output.write_text(text, encoding="utf-8")
+        print(f"✓ Wrote {period} usage to {output}")
+    else:
+        print(text, end="")
+    return 0
+
+
+def run_devices_command(action: str, name: Optional[str] = None) -> int:
+    """List paired companion devices or revoke one (see pairing.py)."""
+    from .pairing import DeviceRegistry
+
+    registry = DeviceRegistry()
+    if action == "list":
+        devices = registry.devices(include_revoked=True)
+        if not devices:
+            print("No paired devices (pair one with ctrl+y in the dashboard)")
+        for device in devices:
+            state = f"revoked {device.revoked_at}" if device.revoked_at else f"last seen {device.last_seen or 'never'}"
+            print(f"  {device.id}  {device.name:<20} {','.join(device.scopes):<22} paired {device.paired_at}, {state}")
+        return 0
+    device = registry.revoke(name or "")
+    if device is None:
+        print(f"✗ No paired device '{name}' (see `xswarm dev devices list`)")
+        return 1
+    print(f"✓ Revoked {device.name} ({device.id})")
+    return 0
+
+
+def run_sync_command(action: str, uri: Optional[str] = None, config_path: Optional[Path] = None) -> int:
+    """Join, run or leave the encrypted memory sync between the user's machines (see memory_sync.py)."""
+    import socket
+
+    from .memory_sync import SYNC_PATH, SyncState, join, sync_memory
+
+    state = SyncState.load()
+    if action == "join":
+        if state is not None:
+            print("✗ This machine already syncs (`xswarm dev sync leave` first)")
+            return 1
+        try:
+            state = asyncio.run(join(uri or "", socket.gethostname()))
+        except Exception as e:  # A wrong code, the other machine unreachable or not pairing
+            print(f"✗ Couldn't join: {e}")
+            return 1
+        print(f"✓ Joined memory sync {state.group}; the first sync merges this machine's memory with the others'")
+        action = "now"
+    if action == "leave":
+        if state is None:
+            print("Not syncing")
+            return 0
+        SYNC_PATH.unlink()
+        print("✓ Left memory sync and deleted the key here (lists sync through the server again)")
+        return 0
+    if state is None:
+        print("Not syncing (start pairing with ctrl+y on a machine, then `xswarm dev sync join URI` on this one)")
+        return 0 if action == "status" else 1
+    if action == "status":
+        print(f"  group {state.group}, this machine {state.device}, {len(state.known)} record(s) synced, "
+              f"last sync {state.last_synced_at or 'never'}")
+        return 0
+    from .config import Config
+    try:
+        result = asyncio.run(sync_memory(Config.load_from_file(config_path)))
+    except Exception as e:
+        print(f"✗ Sync failed: {e}")
+        return 1
+    print(f"✓ Synced: sent {result['pushed']}, applied {result['applied']} from other machines")
+    return 0
+
+
+def run_announce_command(action: str, schedule: Optional[str] = None, text: Optional[str] = None,
+                         priority: str = "normal", tags: Optional[List[str]] = None,
+                         announcement_id: Optional[str] = None) -> int:
+    """Add, list or remove scheduled announcements (see announcements.py)."""
+    from datetime import datetime
+
+    from .announcements import AnnouncementError, AnnouncementStore
+
+    store = AnnouncementStore()
+    try:
+        if action == "add":
+            announcement = store.add(schedule or "", text or "", priority, tags or [])
+            print(f"✓ Added {announcement.id}: next at {announcement.next_time(datetime.now()):%a %Y-%m-%d %H:%M}")
+        elif action == "remove":
+            announcement = store.remove(announcement_id or "")
+            print(f"✓ Removed {announcement.id} ({announcement.schedule}: {announcement.text})")
+        else:
+            announcements = store.all()
+            if not announcements:
+                print("No scheduled announcements (add one with `xswarm dev announce add 09:00 \"Good morning\"`)")
+            for announcement in announcements:
+                extra = "".join(f" #{tag}" for tag in announcement.tags)
+                if announcement.priority != "normal":
+                    extra += f" [{announcement.priority}]"
+                print(f"  {announcement.id}  {announcement.schedule:<16} next {announcement.next_time(datetime.now()):%a %H:%M}"
+                      f"  {announcement.text}{extra}")
+    except AnnouncementError as e:
+        print(f"✗ {e}")
+        return 1
+    return 0
+
+
+def run_alarm_command(action: str, time: Optional[str] = None, days: Optional[str] = None, label: str = "",
+                      briefing: bool = True, alarm_id: Optional[str] = None) -> int:
+    """Add, list or remove wake-up alarms (see alarms.py)."""
+    from .alarms import AlarmError, AlarmStore
+
+    store = AlarmStore()
+    try:
+        if action == "add":
+            alarm = store.add(time or "", days, label, briefing)
+            print(f"✓ Added {alarm.id}: {alarm.describe()}, next at {alarm.ring_time():%a %Y-%m-%d %H:%M}")
+        elif action == "remove":
+            alarm = store.remove(alarm_id or "")
+            print(f"✓ Removed {alarm.id} ({alarm.describe()})")
+        else:
+            alarms = store.all()
+            if not alarms:
+                print("No alarms (add one with `xswarm dev alarm add 06:30 --days weekdays`)")
+            for alarm in alarms:
+                ring_at = alarm.ring_time()
+                when = f"next {ring_at:%a %H:%M}" if ring_at else "off"
+                if ring_at and alarm.snoozes:
+                    when += f" (snoozed {alarm.snoozes}x)"
+                extra = "" if alarm.briefing else "  no briefing"
+                print(f"  {alarm.id}  {alarm.describe():<28} {when}{extra}")
+    except AlarmError as e:
+        print(f"✗ {e}")
+        return 1
+    return 0
+
+
+def run_list_command(action: str, name: Optional[str] = None, items: Optional[List[str]] = None,
+                     checked_only: bool = False, output: Optional[Path] = None,
+                     config_path: Optional[Path] = None) -> int:
+    """Show, edit, export or sync the shopping/todo lists (see lists.py)."""
+    from .lists import ListStore, describe_list, list_name, sync_lists, to_markdown
+
+    store = ListStore()
+    name = list_name(name or "")
+    if action in ("add", "check", "uncheck", "remove", "clear", "export") and not name:
+        print("✗ Which list? e.g. `xswarm dev list add shopping milk eggs`")
+        return 1
+    if action == "add":
+        result = store.add(name, items or [])
+        print(f"✓ Added {len(result['added'])} to the {describe_list(name)}"
+              + (f" ({', '.join(result['already'])} already on it)" if result["already"] else ""))
+    elif action in ("check", "uncheck", "remove"):
+        missing = []
+        for text in items or []:
+            if action == "remove":
+                item = store.remove(name, text)
+            else:
+                item = store.set_done(name, text, done=action == "check")
+            if not item:
+                missing.append(text)
+        if missing:
+            print(f"✗ Not on the {describe_list(name)}: {', '.join(missing)}")
+            return 1
+        verb = {"check": "Checked off", "uncheck": "Unchecked", "remove": "Removed"}[action]
+        print(f"✓ {verb} {len(items or [])} on the {describe_list(name)}")
+    elif action == "clear":
+        print(f"✓ Cleared {store.clear(name, done_only=checked_only)} from the {describe_list(name)}")
+    elif action == "export":
+        markdown = to_markdown(store, name)
+        if output:
+            output.write_text(markdown, encoding="utf-8")
+            print(f"✓ Wrote the {describe_list(name)} to {output}")
+        else:
+            print(markdown, end="")
+    elif action == "sync":
+        from .config import Config
+        try:
+            result = asyncio.run(sync_lists(Config.load_from_file(config_path), store=store))
+        except Exception as e:
+            print(f"✗ Sync failed: {e} ({len(store.pending)} change(s) still waiting)")
+            return 1
+        print(f"✓ Synced: sent {result['pushed']}, {result['changed']} changed elsewhere")
+    else:
+        names = [name] if name else store.names()
+        if not names:
+            print('No lists (start one with `xswarm dev list add shopping milk` or "add milk to the shopping list")')
+        for list_ in names:
+            print(to_markdown(store, list_))
+        if store.pending:
+            print(f"({len(store.pending)} change(s) waiting to sync)")
+    return 0
+
+
+def run_news_command(action: str, url: Optional[str] = None, name: str = "", schedule: Optional[str] = None,
+                     feed: Optional[str] = None, config_path: Optional[Path] = None) -> int:
+    """Follow, list, fetch or unfollow RSS/Atom feeds for the briefing (see news.py)."""
+    from datetime import datetime
+
+    from .news import DEFAULT_SCHEDULE, NewsError, NewsReader, NewsStore
+
+    store = NewsStore()
+    try:
+        if action == "add":
+            added = store.add_feed(url or "", name, schedule or DEFAULT_SCHEDULE)
+            print(f"✓ Following {added.name or added.url} ({added.id}), fetched {added.schedule}")
+        elif action == "remove":
+            removed = store.remove_feed(feed or "")
+            print(f"✓ Unfollowed {removed.name or removed.url}")
+        elif action == "fetch":
+            from .config import Config
+            from .voice import AIClient
+            config = Config.load_from_file(config_path)
+            reader = NewsReader(store, AIClient(config, "news"))
+            now = datetime.now()
+            for target in store.feeds():
+                try:
+                    new = asyncio.run(reader.fetch(target, now))
+                    asyncio.run(reader.summarize(new))
+                    print(f"✓ {target.name or target.url}: {len(new)} new")
+                except Exception as e:
+                    print(f"✗ {target.name or target.url}: {e}")
+        elif action == "unread":
+            names = {f.id: f.name or f.url for f in store.feeds()}
+            items = store.unread()
+            if not items:
+                print("Nothing unread")
+            for item in items:
+                print(f"  {item.published[:10] or '':<10}  {names.get(item.feed_id, '?')}: {item.title}")
+                if item.summary:
+                    print(f"              {item.summary}")
+        else:
+            feeds = store.feeds()
+            if not feeds:
+                print("No feeds (follow one with `xswarm dev news add URL --name NAME`)")
+            for target in feeds:
+                unread = len(store.unread(target.id))
+                state = f"✗ {target.error}" if target.error else f"last fetched {target.last_fetched or 'never'}"
+                print(f"  {target.id}  {target.name or '(untitled)'}  {target.schedule}  {unread} unread  {state}")
+                print(f"            {target.url}")
+    except NewsError as e:
+        print(f"✗ {e}")
+        return 1
+    return 0
+
+
+def run_push_command(event_class: str, config_path: Optional[Path] = None) -> int:
+    """Send a test push for an event class to its configured targets (see push.py)."""
+    from .config import Config
+    from .push import EVENT_CLASSES, PushError, PushRouter
+
+    if event_class not in EVENT_CLASSES:
+        print(f"✗ Unknown event class '{event_class}' (classes: {', '.join(EVENT_CLASSES)})")
+        return 1
+    try:
+        router = PushRouter.from_config(Config.load_from_file(config_path))
+    except PushError as e:
+        print(f"✗ {e}")
+        return 1
+    results = router.send_now(event_class, "Test push from xswarm") if router else {}
+    if not results:
+        print(f"✗ No targets for {event_class} (set config.push_routes)")
+        return 1
+    for target, error in results.items():
+        print(f"✗ {target}: {error}" if error else f"✓ Sent to {target}")
+    return 1 if any(results.values()) else 0
+
+
+def run_push_queue_command(action: str, ids: Optional[List[str]] = None, config_path: Optional[Path] = None) -> int:
+    """List, resend or discard pushes that couldn't be delivered (see push.py)."""
+    from .config import Config
+    from .push import DeliveryQueue, PushError, PushRouter
+
+    config = Config.load_from_file(config_path)
+    queue = DeliveryQueue.from_config(config)
+    if action == "failed":
+        entries = queue.dead() + queue.pending()
+        if not entries:
+            print("✓ No failed pushes")
+        for entry in entries:
+            print(entry.line())
+        return 0
+    if action == "discard":
+        print(f"✓ Discarded {queue.discard(ids)} failed push(es)")
+        return 0
+
+    entries = queue.retry(ids)
+    if not entries:
+        print("✓ No failed pushes to resend")
+        return 1 if ids else 0
+    try:
+        router = PushRouter.from_config(config, queue=queue)
+    except PushError as e:
+        print(f"✗ {e}")
+        return 1
+    if router is None:
+        print(f"✗ {len(entries)} push(es) queued, but no push routes are configured (config.push_routes)")
+        return 1
+    sent, failed = router.retry_due()
+    print(f"✓ Resent {sent} of {len(entries)} failed push(es)")
+    if failed:
+        print(f"✗ {failed} failed again; `xswarm dev push failed` shows why")
+    return 1 if failed else 0
+
+
+def run_matrix_command(action: str, config_path: Optional[Path] = None) -> int:
+    """Log the assistant's Matrix account in (or out) for the Matrix bridge (see matrix.py)."""
+    import getpass
+
+    from .config import Config
+    from .matrix import CREDENTIALS_PATH, MatrixError, login
+
+    if action == "logout":
+        if not CREDENTIALS_PATH.exists():
+            print("Not logged in to Matrix")
+            return 0
+        CREDENTIALS_PATH.unlink()
+        print("✓ Forgot the Matrix access token (the device stays listed in your Matrix account until removed there)")
+        return 0
+    config = Config.load_from_file(config_path)
+    if not config.matrix_user_id:
+        print("✗ Set matrix_user_id (and matrix_homeserver) in the config first")
+        return 1
+    password = getpass.getpass(f"Password for {config.matrix_user_id}: ")
+    try:
+        credentials = asyncio.run(login(config.matrix_homeserver, config.matrix_user_id, password))
+    except ImportError:
+        print("✗ The Matrix bridge needs the matrix extra: pip install 'voice-assistant[matrix]'")
+        return 1
+    except MatrixError as e:
+        print(f"✗ {e}")
+        return 1
+    print(f"✓ Logged in as {credentials['user_id']} (device {credentials['device_id']})")
+    if not config.matrix_enabled or not config.matrix_allowed_users:
+        print("  Set matrix_enabled and matrix_allowed_users in the config to start the bridge")
+    return 0
+
+
+def run_quota_command(sync: bool, config_path: Optional[Path] = None) -> int:
+    """Phone minutes and text messages used this month, optionally synced with the server first (see quota.py)."""
+    from .config import Config
+    from .quota import QuotaManager, sync_quota
+
+    config = Config.load_from_file(config_path)
+    manager = QuotaManager.from_config(config)
+    if sync:
+        try:
+            reported = asyncio.run(sync_quota(config, manager))
+        except Exception as e:
+            print(f"✗ Sync failed: {e}")
+            return 1
+        print(f"✓ Synced with {config.server_url}" + (" (reported " + ", ".join(
+            f"{amount:g} {resource}" for resource, amount in reported.items()) + ")" if reported else ""))
+    print(manager.summary())
+    return 0
+
+
+def run_flags_command(sync: bool, config_path: Optional[Path] = None) -> int:
+    """Each feature flag, on or off and which layer decided, optionally refreshed from the server (see flags.py)."""
+    from .api_client import ApiClient, ApiPolicy
+    from .config import Config
+    from .flags import FeatureFlags
+
+    config = Config.load_from_file(config_path)
+    flags = FeatureFlags.from_config(config)
+    if sync:
+        async def fetch():
+            client = ApiClient(config.server_url, config.api_token, policy=ApiPolicy.from_config(config))
+            try:
+                return await flags.sync(client, "local-user")
+            finally:
+                await client.close()
+        try:
+            changed = asyncio.run(fetch())
+        except Exception as e:
+            print(f"✗ Sync failed: {e}")
+            return 1
+        print(f"✓ Synced with {config.server_url}" + (f" (changed: {', '.join(changed)})" if changed else ""))
+    print(f"Release channel: {flags.channel}")
+    for flag, on, source in flags.rows():
+        print(f"  {'on ' if on else 'off'}  {flag.name:<14} {f'({source})':<10} {flag.description}")
+    return 0
+
+
+def run_crash_report_command(action: str, report_id: Optional[str] = None, config_path: Optional[Path] = None) -> int:
+    """List, show or send the saved crash reports (see crash_report.py)."""
+    from .api_client import ApiClient, ApiError, ApiPolicy
+    from .config import Config
+    from .crash_report import CrashReporter
+
+    reporter = CrashReporter()
+    if action == "list":
+        reports = reporter.reports()
+        for report in reports:
+            print(report.summary())
+        if not reports:
+            print("No crash reports")
+        return 0
+    try:
+        report = reporter.get(report_id)
+    except KeyError as e:
+        print(f"✗ {e.args[0]}")
+        return 1
+    if action == "show":
+        print(report.text())
+        return 0
+
+    config = Config.load_from_file(config_path)
+
+    async def send():
+        client = ApiClient(config.server_url, config.api_token, policy=ApiPolicy.from_config(config))
+        try:
+            await reporter.send(report, client, "local-user")
+        finally:
+            await client.close()
+    try:
+        asyncio.run(send())
+    except ApiError as e:
+        print(f"✗ {e.user_message}")
+        return 1
+    print(f"✓ Sent crash report {report.id} to {config.server_url}")
+    return 0
+
+
+def run_search_command(query: str, config_path: Optional[Path] = None) -> int:
+    """Run a query through the configured web search provider and print what the model would see (see web_search.py)."""
+    from .config import Config
+    from .web_search import WebSearch, WebSearchError
+
+    try:
+        search = WebSearch.from_config(Config.load_from_file(config_path))
+        if search is None:
+            print("✗ Web search is off (set web_search to searxng or brave in the config)")
+            return 1
+        print(asyncio.run(search.answer_context(query)))
+    except WebSearchError as e:
+        print(f"✗ {e}")
+        return 1
+    return 0
+
+
+def run_reminders_preview_command(title: str, minutes: int, category: Optional[str] = None,
+                                  config_path: Optional[Path] = None) -> int:
+    """Show a sample reminder on every channel with the configured templates (see message_templates.py)."""
+    from .config import Config
+    from .message_templates import CHANNELS, DEFAULT_CATEGORY, MessageTemplates, sample_context
+
+    templates = MessageTemplates.from_config(Config.load_from_file(config_path))
+    categories = [category] if category else sorted(templates.sources, key=lambda c: c != DEFAULT_CATEGORY)
+    for name in categories or [DEFAULT_CATEGORY]:
+        context = sample_context(title, minutes, name)
+        print(f"{name}:")
+        for channel in CHANNELS:
+            picked = templates.pick(channel, context["tags"])
+            source = "built-in" if picked is None else f"{picked} template"
+            print(f"  {channel:<14} {templates.render(channel, context)}  ({source})")
+    for name, channel, problem in templates.errors:
+        print(f"✗ {name}.{channel}: {problem} - using the built-in wording")
+    return 1 if templates.errors else 0
+
+
+def run_reminders_effectiveness_command(days: int, config_path: Optional[Path] = None) -> int:
+    """How often reminders on each channel get acknowledged, from the event history (see reminders.py)."""
+    from .config import Config
+    from .events import EventStore
+    from .reminders import METHOD_NAMES, adjust_methods, describe_effectiveness, effectiveness
+
+    config = Config.load_from_file(config_path)
+    store = EventStore.from_config(config)
+    try:
+        stats = effectiveness(store, days=days)
+    finally:
+        store.close()
+    print(f"Reminders over the last {days} days:")
+    for channel, channel_stats in stats.items():
+        rate = f"{channel_stats.rate:.0%}" if channel_stats.rate is not None else "-"
+        print(f"  {METHOD_NAMES[channel]:<23} {channel_stats.acknowledged:>4} of {channel_stats.sent:<4} "
+              f"acknowledged  {rate}")
+    print(describe_effectiveness(stats))
+    methods = adjust_methods(config.reminder_methods, stats, config.reminder_min_ack_rate)
+    if methods != [m for m in config.reminder_methods if m in stats]:
+        state = "leaves out" if config.reminder_methods_auto else "would leave out (reminder_methods_auto is off)"
+        dropped = [METHOD_NAMES[m] for m in config.reminder_methods if m in stats and m not in methods]
+        print(f"Auto-adjust {state}: {', '.join(dropped)}")
+    return 0
+
+
+def run_api_token_command(rotate: bool) -> int:
+    """Print the local API token, replacing it first with --rotate (see local_api.py)."""
+    from .local_api import TOKEN_PATH, load_token
+
+    token = load_token(rotate=rotate)
+    print(token)
+    print(f"{'Replaced' if rotate else 'Stored'} in {TOKEN_PATH}; send it as 'Authorization: Bearer <token>'",
+          file=sys.stderr)
+    return 0
+
+
+def run_ics_command(action: str, output: Optional[Path] = None, config_path: Optional[Path] = None) -> int:
+    """The calendar feed for calendar apps: its URL, a new token, upload/delete or export it (see ics_feed.py)."""
+    from .config import Config
+    from .ics_feed import MODES, feed_token, feed_url, publish, render, unpublish
+
+    config = Config.load_from_file(config_path)
+    if action == "export":
+        ics = render(config)
+        if output:
+            output.write_text(ics, encoding="utf-8")
+            print(f"✓ Wrote the feed to {output}")
+        else:
+            print(ics, end="")
+        return 0
+    if action == "unpublish":
+        try:
+            deleted = asyncio.run(unpublish(config))
+        except Exception as e:
+            print(f"✗ Couldn't delete the feed: {e}")
+            return 1
+        print("✓ Deleted the uploaded feed" if deleted else "No feed was uploaded")
+        return 0
+    if config.ics_feed not in MODES:
+        print('✗ The feed is off: set ics_feed to "local" (this machine) or "server" (Google Calendar, phones)')
+        return 1
+    token = feed_token(rotate=action == "regenerate")
+    if action in ("regenerate", "publish") and config.ics_feed == "server":
+        try:
+            asyncio.run(publish(config, force=True))
+        except Exception as e:
+            print(f"✗ Upload failed: {e} (the ics_feed job tries again)")
+            return 1
+    print(feed_url(config, token))
+    if action == "regenerate":
+        print("New token: calendars subscribed to the old URL stop updating", file=sys.stderr)
+    return 0
+
+
+def run_mcp_command(config_path: Optional[Path] = None) -> int:
+    """MCP server on stdio, calling the running assistant's local API (see mcp_server.py)."""
+    from .config import Config
+    from .local_api import load_token
+    from .mcp_server import McpServer, api_caller
+
+    config = Config.load_from_file(config_path)
+    token = load_token(create=False)
+    if not config.local_api or token is None:
+        print("✗ Turn on local_api in the config and start xSwarm first", file=sys.stderr)
+        return 1
+    McpServer(api_caller(f"http://127.0.0.1:{config.local_api_port}", token)).run()
+    return 0
+
+
+def run_self_update_command(check: bool, rollback: bool, config_path: Optional[Path] = None) -> int:
+    """Install the latest signed release of the binary, say whether there is one, or roll back (see self_update.py)."""
+    from . import __version__
+    from .api_client import ApiError
+    from .config import Config
+    from .self_update import SelfUpdater, UpdateError
+
+    updater = SelfUpdater.from_config(Config.load_from_file(config_path))
+
+    async def go() -> str:
+        try:
+            if rollback:
+                updater.rollback()
+                running = updater.restart_daemon()
+                return "✓ Rolled back" + (" and restarted xswarm" if running else " - restart xswarm to use it")
+            if check:
+                release = await updater.check()
+                if not release.newer_than(__version__):
+                    return f"✓ xswarm {__version__} is up to date"
+                notes = f"\n{release.notes.strip()}" if release.notes.strip() else ""
+                return f"⬆ xswarm {release.version} is out (you have {__version__}) - run `xswarm self-update`{notes}"
+            return "✓ " + await updater.update()
+        finally:
+            await updater.client.close()
+
+    try:
+        print(asyncio.run(go()))
+    except (UpdateError, OSError) as e:
+        print(f"✗ {e}", file=sys.stderr)
+        return 1
+    except ApiError as e:
+        print(f"✗ {e.user_message}", file=sys.stderr)
+        return 1
+    return 0
+
+
+def run_core_bundle_command(path: Path) -> int:
+    """Zip the pure calendar core for Pyodide (see calendar_core.py)."""
+    from .calendar_core import write_bundle
+
+    try:
+        names = write_bundle(path)
+    except (OSError, ValueError) as e:
+        print(f"✗ {e}")
+        return 1
+    print(f"✓ Wrote {path} ({', '.join(names)})")
+    return 0
+
+
+def run_speech_cache_command(clear: bool) -> int:
+    """Show what the speech cache holds, or empty it (see speech_cache.py)."""
+    from .speech_cache import SpeechCache
+
+    cache = SpeechCache()
+    if clear:
+        print(f"✓ Deleted {cache.clear()} cached utterance(s)")
+        return 0
+    stats = cache.stats()
+    print(f"{stats['entries']} utterance(s), {stats['bytes'] / 1e6:.1f} of {stats['max_bytes'] / 1e6:.0f} MB, "
+          f"played {stats['hits']} time(s) ({cache.directory})")
+    return 0
+
+
+def run_pronounce_command(action: str, word: Optional[str] = None, spoken: Optional[str] = None,
+                          text: Optional[str] = None, persona: Optional[str] = None) -> int:
+    """Add to, list, remove from or try the pronunciation lexicon (see pronunciation.py)."""
+    from .pronunciation import PronunciationLexicon, respell
+
+    lexicon = PronunciationLexicon()
+    whose = f" for {persona}" if persona else ""
+    if action == "add":
+        try:
+            lexicon.add(word or "", spoken or "", persona)
+        except ValueError as e:
+            print(f"✗ {e}")
+            return 1
+        print(f"✓ '{word}' is said \"{respell(spoken)}\"{whose}")
+    elif action == "remove":
+        if not lexicon.remove(word or "", persona):
+            print(f"✗ '{word}' isn't in the lexicon{whose}")
+            return 1
+        print(f"✓ '{word}' is said as spelled again{whose}")
+    elif action == "test":
+        print(lexicon.apply(text or "", persona))
+    else:
+        entries = lexicon.entries(persona)
+        if not entries:
+            print(f"No pronunciations{whose} (add one with `xswarm dev tts pronounce add \"Siobhan\" \"shiv-AWN\"`)")
+        for entry, said in sorted(entries.items(), key=lambda item: item[0].lower()):
+            shown = said if respell(said) == said else f"{said} → {respell(said)}"
+            print(f"  {entry:<24} {shown}")
+        print(f"({lexicon.path})")
+    return 0
+
+
+def run_memory_command(search: Optional[str] = None) -> int:
+    """What the assistant remembers: facts about the user, then bookmarked conversations (see bookmarks.py)."""
+    from .bookmarks import get_bookmark_store
+    from .memory import UserProfile
+
+    needle = (search or "").strip().lower()
+    facts = [f for f in UserProfile().facts if needle in f.fact.lower()]
+    bookmarks = get_bookmark_store().find(needle)
+    print(f"Facts ({len(facts)}):")
+    for fact in facts:
+        print(f"  [{fact.category}] {fact.fact}")
+    print(f"Bookmarks ({len(bookmarks)}):")
+    for bookmark in bookmarks:
+        print(f"  {bookmark.id}  '{bookmark.label}'  {bookmark.created_at.replace('T', ' ')[:16]}")
+        for line in bookmark.transcript():
+            print(f"      {line}")
+    if not facts and not bookmarks:
+        print(f"Nothing remembered{' about ' + repr(search) if needle else ''}.")
+    return 0
+
+
+def run_memory_digest_command(days: int, save: bool, config_path: Optional[Path] = None) -> int:
+    """The weekly digest (see memory_digest.py), saved to config.memory_digest_dir with --save."""
+    from .config import Config
+    from .memory_digest import build_digest, save_digest
+
+    digest = build_digest(days=days)
+    print(digest.markdown(), end="")
+    if save:
+        config = Config.load_from_file(config_path)
+        print(f"✓ Saved to {save_digest(digest, config.memory_digest_dir or None)}")
+    return 0
+
+
+def run_compute_command(move: Optional[List[str]], config_path: Optional[Path] = None) -> int:
+    """Where each model runs and the GPU's VRAM, from the running assistant; or move a model (see compute.py)."""
+    from .compute import ComputeError, ComputeManager
+    from .config import Config
+    from .control import ControlError, send_command
+
+    args = {"model": move[0], "backend": move[1]} if move else {}
+    try:
+        reply = send_command("compute", **args)
+    except ControlError:
+        if move:
+            print("❌ The assistant isn't running - set model_placement in the config instead")
+            return 1
+        reply = {"ok": True, **ComputeManager.from_config(Config.load_from_file(config_path)).report()}
+    if not reply.get("ok"):
+        print(f"❌ {reply.get('error')}")
+        return 1
+    print(f"Backends: {', '.join(reply['backends'])}")
+    gpu = reply.get("gpu")
+    if gpu:
+        print(f"{gpu['name']}: {gpu['used_gb']:.1f} of {gpu['total_gb']:.1f} GB used, {gpu['busy_percent']:.0f}% busy")
+    for model in reply["models"]:
+        notes = [note for note, on in (("yielded to the user", model["yielded"]), ("moves on restart", not model["live"])) if on]
+        print(f"  {model['name']:6} {model['backend']:6} (supports {', '.join(model['supported'])})"
+              + (f" - {', '.join(notes)}" if notes else ""))
+    if not reply["models"]:
+        print("  No models loaded (the assistant isn't running)")
+    return 0
+
+
+def run_voice_selftest_command(corpus: Optional[Path], quality: Optional[str], whisper: str,
+                               max_wer: Optional[float], max_latency: Optional[float], as_json: bool,
+                               config_path: Optional[Path] = None) -> int:
+    """Read a prompt corpus with the cached voice model, transcribe it with Whisper and score WER and latency.
+    1 if any prompt failed (see voice_selftest.py)."""
+    import json
+
+    from .compute import CPU, METAL, ComputeManager
+    from .config import Config
+    from .model_memory import MOSHI_VARIANTS
+    from .voice_selftest import MoshiSynthesizer, SelftestError, VoiceSelftest, WhisperTranscriber, load_corpus
+
+    config = Config.load_from_file(config_path)
+    quality = quality or (config.moshi_quality if config.moshi_quality in MOSHI_VARIANTS else "q4")
+    device = "cpu" if ComputeManager.from_config(config).resolve("voice", (METAL, CPU)) == CPU else "gpu"
+
+    async def run():
+        prompts = load_corpus(corpus, max_wer, max_latency)
+        transcriber = WhisperTranscriber(whisper, api_key=config.openai_api_key)
+        synthesizer = MoshiSynthesizer(quality, device=device, mmap_weights=config.voice_mmap_weights)
+        if not as_json:
+            print(f"🧪 {len(prompts)} prompt(s), {quality} voice model on {device}, Whisper ({transcriber.backend})")
+        await synthesizer.start()
+        try:
+            on_result = None if as_json else lambda result: print(result.line())
+            return await VoiceSelftest(prompts, synthesizer, transcriber, on_result=on_result).run()
+        finally:
+            await synthesizer.stop()
+
+    try:
+        report = asyncio.run(run())
+    except SelftestError as e:
+        if as_json:
+            print(json.dumps({"passed": False, "error": str(e)}))
+        else:
+            print(f"✗ {e}")
+        return 1
+    if as_json:
+        print(json.dumps(report.to_dict(), indent=2))
+    else:
+        print(report.lines()[-1])
+    return 0 if report.passed else 1
+
+
+def run_calendar_bench_command(sizes: Optional[str], repeat: int, only: Optional[List[str]], save: Optional[Path],
+                               compare: Optional[Path], max_regression: float, as_json: bool) -> int:
+    """Time recurrence expansion, conflict detection and free slots on synthetic calendars; 1 if slower than
+    the baseline (see calendar_bench.py)."""
+    import json
+
+    from .calendar_bench import SIZES, BenchError, BenchReport, run_benchmarks
+
+    try:
+        counts = [int(n) for n in sizes.split(",")] if sizes else list(SIZES)
+        baseline = BenchReport.load(compare) if compare else None
+        if not as_json:
+            print(f"⏱ Calendars of {', '.join(map(str, counts))} events, best of {repeat}")
+        on_result = None if as_json else lambda r: print(f"  {r.name} at {r.size}: {r.seconds * 1000:.1f} ms")
+        report = run_benchmarks(counts, repeat, only, on_result=on_result)
+    except (BenchError, ValueError) as e:
+        print(f"✗ {e}")
+        return 1
+    regressions = report.compare(baseline, max_regression) if baseline else []
+    if save:
+        report.save(save)
+    if as_json:
+        print(json.dumps(report.to_dict(), indent=2))
+    else:
+        print("\n".join(report.lines(baseline)))
+        if save:
+            print(f"✓ Saved the baseline to {save}")
+        for regression in regressions:
+            print(f"✗ Slower: {regression}")
+    return 1 if regressions else 0
+
+
+def run_opus_bench_command(seconds: float, bitrate: Optional[int], link_mbps: float, as_json: bool,
+                           config_path: Optional[Path] = None) -> int:
+    """Per-frame latency of Opus against raw float audio on a simulated LAN link; 1 if Opus isn't at parity
+    (see opus_transport.py)."""
+    import json
+    from dataclasses import asdict
+
+    from .config import Config
+    from .opus_transport import OpusCodec, OpusUnavailable, run_bench
+
+    config = Config.load_from_file(config_path)
+    try:
+        codec = OpusCodec(bitrate=bitrate or config.opus_bitrate)
+    except OpusUnavailable as e:
+        print(f"✗ {e}")
+        return 1
+    result = run_bench(codec, seconds, link_mbps)
+    if as_json:
+        print(json.dumps({**asdict(result), "parity": result.parity}, indent=2))
+    else:
+        print(f"⏱ {seconds:g}s of speech at {codec.bitrate // 1000} kbps, 20ms frames")
+        print("\n".join(result.lines()))
+    return 0 if result.parity else 1
+
+
+def run_project_command(action: str, name: Optional[str], question: Optional[str] = None,
+                        language: Optional[str] = None, config_path: Optional[Path] = None) -> int:
+    """Index a project's folders into Meilisearch, ask a question about them or summarize one of its documents,
+    or keep them indexed as files change (see project_docs.py, project_watch.py)."""
+    from .config import Config
+    from .project_docs import ProjectDocs, ProjectDocsError, find_project
+    from .tools import get_planner_data
+    from .voice import AIClient
+
+    config = Config.load_from_file(config_path)
+    project = find_project(get_planner_data(), name) if name else None
+    docs = ProjectDocs.from_config(config, AIClient(config, "project_docs"))  # Answers, and summaries of long documents
+    if action == "watch" and not name:
+        return _watch_projects(docs, config)
+    if project is None:
+        print(f"✗ No project '{name}' (add its folders with the add_project_folder tool)")
+        return 1
+    try:
+        if action == "index":
+            if not project.folders:
+                print(f"✗ {project.name} has no folders to index")
+                return 1
+            report = asyncio.run(docs.index_project(project))
+            print(f"✓ {project.name}: {report.summary()}")
+            return 1 if report.missing_folders else 0
+        if action == "watch":
+            return _watch_projects(docs, config, project)
+        if action == "summarize":
+            print(asyncio.run(docs.summarize(project, question or "")).render())
+            return 0
+        print(asyncio.run(docs.ask(project, question or "", language or "")).render())
+    except ProjectDocsError as e:
+        print(f"✗ {e}")
+        return 1
+    return 0
+
+
+def run_project_key_command(revoke: bool, config_path: Optional[Path] = None) -> int:
+    """This profile's Meilisearch index and API key, or revoke the key (see project_docs.MeiliKeys)."""
+    from .config import Config
+    from .project_docs import INDEX_NAME, MeiliKeys, ProjectDocsError, profile_name
+
+    config = Config.load_from_file(config_path)
+    profile = profile_name(config)
+    print(f"Profile: {profile}")
+    print(f"Index:   {INDEX_NAME}_{profile}")
+    if not config.meilisearch_key:
+        print("Key:     none (no meilisearch_key: profiles share an unprotected Meilisearch)")
+        return 0
+    keys = MeiliKeys(config.meilisearch_url, config.meilisearch_key)
+    entry = keys.stored(profile)
+    if not revoke:
+        print(f"Key:     {entry['uid']} (limited to this index)" if entry else "Key:     made on first index or search")
+        return 0
+    try:
+        revoked = asyncio.run(keys.revoke(profile))
+    except ProjectDocsError as e:
+        print(f"✗ {e}")
+        return 1
+    print("✓ Key revoked; a new one is made on next use" if revoked else "No key to revoke")
+    return 0
+
+
+def _watch_projects(docs, config, project=None) -> int:
+    """Reindex files as they're saved until Ctrl+C; every active project, or just `project`."""
+    from .project_watch import ProjectWatcher
+    from .tools import get_planner_data
+
+    def on_update(updated, report):
+        print(f"✓ {updated.name}: {report.summary()}")
+
+    watcher = ProjectWatcher(docs, get_planner_data(), debounce=config.project_watch_debounce,
+                             on_update=on_update, only=project.id if project else None)
+    watcher.refresh_roots()
+    if not watcher.roots:
+        print("✗ No project folders to watch")
+        return 1
+    for folder, watched in watcher.roots.items():
+        print(f"Watching {watched.name}: {folder}")
+    try:
+        asyncio.run(watcher.run())
+    except KeyboardInterrupt:
+        pass
+    return 0
+
+
+def run_logs_prune_command(dry_run: bool, config_path: Optional[Path] = None) -> int:
+    """Delete the oldest rotated logs until they fit in config.log_total_mb (see log_rotation.py)."""
+    from .config import Config
+    from .log_rotation import LOG_DIR, prune
+
+    config = Config.load_from_file(config_path)
+    deleted = prune(LOG_DIR, config.log_total_mb, dry_run=dry_run)
+    for path, size in deleted:
+        print(f"{'Would delete' if dry_run else 'Deleted'} {path.name} ({size / 1024:.0f} KB)")
+    freed = sum(size for _, size in deleted) / (1024 * 1024)
+    if deleted:
+        print(f"{'Would free' if dry_run else 'Freed'} {freed:.1f} MB")
+    else:
+        print(f"Logs in {LOG_DIR} fit in {config.log_total_mb:g} MB - nothing to delete")
+    return 0
+
+
+def run_retention_command(apply: bool, verbose: bool, config_path: Optional[Path] = None) -> int:
+    """What is past its retention period, deleted with --apply (see retention.py)."""
+    from .config import Config
+    from .retention import RetentionEngine
+
+    config = Config.load_from_file(config_path)
+    engine = RetentionEngine.from_config(config)
+    for problem in engine.policy.problems:
+        print(f"✗ {problem}")
+    report = engine.run(apply=apply)
+    print("\n".join(report.lines(verbose)))
+    if not apply and report.total():
+        print("Run with --apply to delete these.")
+    return 1 if report.errors else 0
+
+
+def _backup_passphrase(confirm: bool) -> str:
+    """$XSWARM_BACKUP_PASSPHRASE, or asked for (twice when creating a backup)."""
+    import getpass
+    from .backup import PASSPHRASE_ENV, BackupError
+
+    passphrase = os.environ.get(PASSPHRASE_ENV)
+    if passphrase:
+        return passphrase
+    passphrase = getpass.getpass("Backup passphrase: ")
+    if confirm and getpass.getpass("Repeat passphrase: ") != passphrase:
+        raise BackupError("Passphrases don't match")
+    return passphrase
+
+
+def run_plugins_command(action: str, name: Optional[str] = None, grants: Optional[List[str]] = None,
+                        config_path: Optional[Path] = None) -> int:
+    """List, enable or disable WebAssembly skill plugins (see skill_plugins.py)."""
+    from .config import Config
+    from .skill_plugins import Grants, PluginError, SkillPlugins
+
+    plugins = SkillPlugins.from_config(Config.load_from_file(config_path))
+    if action == "list":
+        manifests, problems = plugins.discover()
+        if not manifests and not problems:
+            print(f"No plugins in {plugins.directory} (try copying examples/plugins/coin_flip there)")
+        for manifest in manifests:
+            state = "enabled" if plugins.state.enabled(manifest.name) else "disabled"
+            print(f"  {manifest.name:<16} {manifest.version:<8} {state:<9} {manifest.description}")
+            examples = [e for intent in manifest.intents for e in intent.examples]
+            if examples:
+                print(f"      say: {', '.join(examples)}")
+            if manifest.tools:
+                print(f"      tools: {', '.join(t.name for t in manifest.tools)}")
+            approved = plugins.state.granted(manifest.name)
+            for permission, targets in manifest.permissions.items():
+                granted = "granted" if permission in approved else "not granted"
+                if Grants.asked(manifest, [permission]).beyond(approved):
+                    granted = "asks for more than was granted - enable it again"
+                print(f"      {permission}: {', '.join(targets)} ({granted})")
+        for problem in problems:
+            print(f"✗ {problem}")
+        return 0
+    try:
+        if action == "enable":
+            manifest = plugins.enable(name or "", grants or [])
+            access = ", ".join(f"{g} ({', '.join(manifest.permissions[g])})" for g in sorted(set(grants or [])))
+            print(f"✓ Enabled {manifest.name}" + (f" with {access}" if access else " (no network or file access)"))
+            ungranted = sorted(set(manifest.permissions) - set(grants or []))
+            if ungranted:
+                print(f"  It asks for {', '.join(ungranted)} access too: add --grant {' --grant '.join(ungranted)}")
+        else:
+            plugins.disable(name or "")
+            print(f"✓ Disabled {name}")
+    except PluginError as e:
+        print(f"✗ {e}")
+        return 1
+    print("  Restart the assistant to apply")
+    return 0
+
+
+def run_backup_command(action: str, path: Path, only: Optional[List[str]], personas_dir: Path,
+                       assume_yes: bool = False, config_path: Optional[Path] = None) -> int:
+    """Create or restore an encrypted backup of local state (see backup.py)."""
+    from . import __version__
+    from .backup import BackupError, components, create_backup, read_manifest, restore_backup, select
+    from .config import Config
+
+    config = Config.load_from_file(config_path)
+    available = components(config, personas_dir, config_path=config_path or Config.get_config_path())
+    try:
+        selected = select(available, only)
+        if action == "create":
+            manifest = create_backup(path, _backup_passphrase(confirm=True), selected, __version__)
+            for name, info in manifest["components"].items():
+                print(f"  {name:<12} {len(info['files'])} files")
+            print(f"✓ Backed up to {path}")
+            return 0
+
+        passphrase = _backup_passphrase(confirm=False)
+        manifest = read_manifest(path, passphrase)
+        included = [c for c in selected if c.name in manifest["components"]]
+        if not included:
+            print(f"✗ {path} has none of: {', '.join(c.name for c in selected)}")
+            return 1
+        print(f"Backup from {manifest['created_at']} (xswarm {manifest.get('app_version') or '?'})")
+        for component in included:
+            print(f"  {component.name:<12} {component.description}")
+        if not assume_yes and input("Replace these on this machine? Quit xswarm first. [y/N] ").strip().lower() != "y":
+            print("Nothing restored")
+            return 1
+        restored = restore_backup(path, passphrase, included)
+        print(f"✓ Restored {', '.join(f'{name} ({count} files)' for name, count in restored.items())}")
+        return 0
+    except BackupError as e:
+        print(f"✗ {e}")
+        return 1
+
+
+def run_tray_command() -> int:
+    """Menu-bar/tray quick actions for the running assistant (see tray.py)."""
+    from .tray import TrayCompanion
+
+    if not TrayCompanion().run():
+        print("✗ Could not show the tray (needs pystray and Pillow: pip install voice-assistant[tray])")
+        return 1
+    return 0
+
+
 def main():
     """CLI entry point"""
     # Configure logging to file to prevent TUI corruption
     # Must be inside main() to avoid multiprocessing pickle issues
+    # Appended across runs, rotated and compressed by size and age - see log_rotation.py
+    from .log_rotation import RotatingLog
+    log_handler = RotatingLog()
     logging.basicConfig(
         level=logging.DEBUG,
         format='%(asctime)s - %(name)s - %(levelname)s - %(message)s',
-        handlers=[
-            logging.FileHandler('/tmp/xswarm_main.log', mode='w')
-        ]
+        handlers=[log_handler]
     )
+    # Phone numbers, emails and credentials are masked before reaching the log file
+    from .redaction import install_log_redaction
+    install_log_redaction()
+    # Each startup stage timed from here against its budget - see startup_profile.py
+    from .startup_profile import StartupProfile, set_startup_profile
+    profile = StartupProfile()
+    set_startup_profile(profile)
 
     parser = argparse.ArgumentParser(
         description="xSwarm Voice Assistant - Interactive TUI with flexible persona system",
@@ -367,6 +1727,62 @@ Examples:
   %(prog)s                    # Launch interactive TUI
   %(prog)s --debug            # Launch with debug logging
   %(prog)s --config /path     # Use custom config file
+  %(prog)s --text-only        # Chat, calendar and memory without voice
+  %(prog)s --profile-startup  # Time each startup stage against its budget, print the breakdown and exit
+  %(prog)s --inbox            # Print unified inbox and exit
+  %(prog)s --a11y             # Screen-reader mode: plain lines on stdout instead of the TUI
+  %(prog)s --a11y FILE        # Keep the TUI and mirror its events to FILE as plain lines
+  %(prog)s --chaos "ai=0.3,delay=1-3"  # Failover drill: AI/STT/TTS calls fail or lag at random
+  %(prog)s tray               # Menu-bar/tray quick actions for the running assistant
+  %(prog)s mcp                # MCP tools (appointments, reminders, speak) for editors and chat apps
+  %(prog)s self-update        # Install the latest signed release and restart the daemon (--check, --rollback)
+  %(prog)s dev undo           # Undo the last delete/complete/forget (5 minute window)
+  %(prog)s dev jobs list      # Background jobs, schedules and last-run status
+  %(prog)s dev jobs run NAME  # Run a background job now
+  %(prog)s dev replay FILE    # Replay a voice scenario with mocked AI and TTS
+  %(prog)s dev replay --chaos outage=anthropic FILE  # ...with the provider down, to check the fallbacks
+  %(prog)s dev events query --type sms --since yesterday  # Search past activity and messages
+  %(prog)s dev events replay 3:04pm     # What happened then, step by step with latencies
+  %(prog)s dev ai log --since monday [--show 42]  # What was sent to which AI provider, and the tokens used
+  %(prog)s dev analytics --period week --format csv       # Usage report (text, JSON or CSV)
+  %(prog)s dev retention --verbose  # What is past its retention period (--apply deletes it)
+  %(prog)s dev backup create FILE   # Encrypted backup of config, memory, personas, history...
+  %(prog)s dev backup restore FILE --only memory  # Restore just some components
+  %(prog)s dev announce add 09:00 "{greeting}! {briefing}"  # Say this every day at 9 (quiet hours apply)
+  %(prog)s dev alarm add 06:30 --days weekdays  # Wake-up alarm; "five more minutes" snoozes it
+  %(prog)s dev news add https://blog.rust-lang.org/feed.xml --name "the Rust blog"  # News in the briefing
+  %(prog)s dev list add shopping milk eggs  # Named lists; "what's on my shopping list?" reads one
+  %(prog)s dev list export shopping --output shopping.md  # A list as a Markdown checklist
+  %(prog)s dev devices list         # Paired phone/web clients (pair with ctrl+y in the dashboard)
+  %(prog)s dev devices revoke NAME  # Disconnect a paired client for good
+  %(prog)s dev sync join "xswarm://pair?..."  # Share memory with another machine (ctrl+y shows the URI there)
+  %(prog)s dev sync status          # Encrypted memory sync: group, last sync (also: now, leave)
+  %(prog)s dev push test error      # Send a test ntfy/Pushover push for an event class
+  %(prog)s dev push retry [ID]      # Resend pushes that couldn't be delivered (`dev push failed` lists them)
+  %(prog)s dev matrix login         # Log the assistant's Matrix account in for the Matrix bridge
+  %(prog)s dev search "rust 1.90"   # Try the web_search provider (off unless config.web_search is set)
+  %(prog)s dev quota --sync         # Phone minutes and texts used this month, refreshed from the server
+  %(prog)s dev crash-report list     # Saved crash reports (show ID for one; send ID uploads it)
+  %(prog)s dev logs prune [--dry-run]  # Delete the oldest rotated logs until they fit in log_total_mb
+  %(prog)s dev config flags [--sync]  # Feature flags: on or off, and whether the channel, server or config said so
+  %(prog)s dev project index NAME   # Index a project's repo and docs folders into Meilisearch
+  %(prog)s dev project ask NAME "where do we configure retries?"  # Answer from them, citing files
+  %(prog)s dev project summarize NAME architecture  # A document's summary, kept from indexing
+  %(prog)s dev project watch [NAME]   # Keep the index current as files are saved
+  %(prog)s dev project key --revoke   # Replace this profile's Meilisearch key
+  %(prog)s dev api token [--rotate]   # The local REST API's token (turn it on with local_api)
+  %(prog)s dev ics url                # Calendar feed URL to subscribe to (set ics_feed; also regenerate, export)
+  %(prog)s dev core-bundle FILE       # Zip the date/recurrence/conflict logic for a web page (Pyodide)
+  %(prog)s dev speech-cache [--clear] # Recorded common phrases (instant, offline playback)
+  %(prog)s dev tts pronounce add "Siobhan" "shiv-AWN"  # How to say a name (--persona NAME for one persona)
+  %(prog)s dev memory list [--search lease]  # Remembered facts and bookmarked conversations
+  %(prog)s dev memory digest [--save]  # The last week's conversations, facts, reminders and tasks
+  %(prog)s dev compute [--move vad cpu]  # Where each model runs, GPU memory; move a model
+  %(prog)s dev voice-selftest [--json]   # Read a prompt corpus, score it with Whisper (WER, latency)
+  %(prog)s dev calendar-bench --save bench.json  # Time the calendar scans; --compare bench.json later
+  %(prog)s dev opus-bench [--link 100]  # Opus call audio against raw PCM: bandwidth and per-frame latency
+  %(prog)s dev plugins list           # Skill plugins in ~/.xswarm/plugins, what they ask for and are granted
+  %(prog)s dev plugins enable NAME --grant network  # Turn one on, letting it reach the hosts it names
 
 Configuration:
   All settings are configured interactively in the TUI.
@@ -399,6 +1815,290 @@ Configuration:
         action="store_true",
         help="Enable debug logging"
     )
+    parser.add_argument(
+        "--text-only",
+        action="store_true",
+        help="Skip voice entirely (no Moshi models or audio devices) for this run"
+    )
+    parser.add_argument(
+        "--profile-startup",
+        action="store_true",
+        help="Start up, print how long each stage took against its budget, then exit"
+    )
+    parser.add_argument(
+        "--inbox",
+        action="store_true",
+        help="Sync and print the unified inbox (SMS, email, voice), then exit"
+    )
+
+    parser.add_argument(
+        "--a11y",
+        nargs="?",
+        const="-",
+        metavar="FILE",
+        help="Screen-reader/braille output: plain lines on stdout instead of the TUI, or to FILE alongside it"
+    )
+    parser.add_argument(
+        "--chaos",
+        metavar="SPEC",
+        help="Development: inject provider failures and latency, e.g. 'ai=0.3,tts=0.1,delay=1-3,outage=anthropic' "
+             "(see assistant/chaos.py)"
+    )
+
+    subparsers = parser.add_subparsers(dest="command")
+    subparsers.add_parser("tray", help="Menu-bar/tray icon: mute mic, do not disturb, next appointment, quit")
+    subparsers.add_parser("mcp", help="MCP server on stdio for the running assistant (needs config.local_api)")
+    update_parser = subparsers.add_parser("self-update", help="Install the latest signed release of the xswarm binary")
+    update_parser.add_argument("--check", action="store_true", help="Only say whether there's a newer release")
+    update_parser.add_argument("--rollback", action="store_true", help="Go back to the binary before the last update")
+    dev_parser = subparsers.add_parser("dev", help="Developer and maintenance commands")
+    dev_commands = dev_parser.add_subparsers(dest="dev_command", required=True)
+    undo_parser = dev_commands.add_parser("undo", help="Undo the last destructive action (within 5 minutes)")
+    undo_parser.add_argument("--list", action="store_true", help="Show undoable actions instead of undoing")
+    jobs_parser = dev_commands.add_parser("jobs", help="List background jobs or run one now")
+    jobs_commands = jobs_parser.add_subparsers(dest="jobs_command", required=True)
+    jobs_commands.add_parser("list", help="Show jobs, schedules and last-run status")
+    jobs_run_parser = jobs_commands.add_parser("run", help="Run a job immediately")
+    jobs_run_parser.add_argument("name", help="Job name (see `dev jobs list`)")
+    replay_parser = dev_commands.add_parser("replay", help="Replay voice scenarios (YAML) through the pipeline")
+    replay_parser.add_argument("scenarios", nargs="+", type=Path, help="Scenario files (see assistant/replay.py)")
+    replay_parser.add_argument("--chaos", metavar="SPEC", dest="replay_chaos",
+                               help="Simulated outages for every scenario (see chaos.py)")
+    events_parser = dev_commands.add_parser("events", help="Search recorded activity and inbox events")
+    events_commands = events_parser.add_subparsers(dest="events_command", required=True)
+    events_query_parser = events_commands.add_parser("query", help="List events, oldest first")
+    events_query_parser.add_argument("--type", action="append", dest="types",
+                                     help="sms, email, voice or activity (repeat or comma-separate)")
+    events_query_parser.add_argument("--since", help="yesterday, monday, 2h, '3 days ago' or an ISO date")
+    events_query_parser.add_argument("--limit", type=int, default=100, help="Most recent events to show (default 100)")
+    events_query_parser.add_argument("--thread", metavar="ID",
+                                     help="Only what one interaction set off (the second column)")
+    events_replay_parser = events_commands.add_parser(
+        "replay", help="Rebuild the interactions around a time, with how long each step took")
+    events_replay_parser.add_argument("when", nargs="?", help="3:04pm, 'yesterday 9am..9:30am', 2h or an ISO date")
+    events_replay_parser.add_argument("--thread", metavar="ID", dest="replay_thread", help="One interaction only")
+    events_replay_parser.add_argument("--tui", action="store_true", help="Show it in the activity feed")
+    ai_parser = dev_commands.add_parser("ai", help="Requests to language models (the AI log)")
+    ai_commands = ai_parser.add_subparsers(dest="ai_command", required=True)
+    ai_log_parser = ai_commands.add_parser("log", help="List logged model calls, oldest first")
+    ai_log_parser.add_argument("--since", help="yesterday, monday, 2h, '3 days ago' or an ISO date")
+    ai_log_parser.add_argument("--provider", help="anthropic, openai, ollama or lmstudio")
+    ai_log_parser.add_argument("--purpose", help="chat, voice, thinking, memory, news...")
+    ai_log_parser.add_argument("--limit", type=int, default=50, help="Most recent calls to show (default 50)")
+    ai_log_parser.add_argument("--show", type=int, metavar="ID", help="One call's messages and reply, as kept")
+    analytics_parser = dev_commands.add_parser("analytics", help="Usage report: conversations, latency, reminders, commands")
+    analytics_parser.add_argument("--period", choices=["day", "week"], default="week")
+    analytics_parser.add_argument("--format", choices=["text", "json", "csv"], default="text", dest="output_format")
+    analytics_parser.add_argument("--output", type=Path, help="Write to this file instead of printing")
+    retention_parser = dev_commands.add_parser("retention", help="Show (or delete) data past its retention period")
+    retention_parser.add_argument("--apply", action="store_true", help="Delete it instead of only reporting")
+    retention_parser.add_argument("--verbose", action="store_true", help="List every file and session")
+    announce_parser = dev_commands.add_parser("announce", help="Scheduled announcements (\"speak this at 9am\")")
+    announce_commands = announce_parser.add_subparsers(dest="announce_command", required=True)
+    announce_add_parser = announce_commands.add_parser("add", help="Say TEXT at a time of day or on a cron schedule")
+    announce_add_parser.add_argument("schedule", help='HH:MM (daily) or a cron expression, e.g. "0 9 * * 1-5"')
+    announce_add_parser.add_argument("text", help="What to say; {greeting}, {briefing}, {events}, {tasks}, {time}...")
+    announce_add_parser.add_argument("--priority", default="normal", choices=["low", "normal", "high", "emergency"],
+                                     help="Against quiet hours and do not disturb (default normal)")
+    announce_add_parser.add_argument("--tag", action="append", dest="tags", help="Category, for its notification prefs")
+    announce_commands.add_parser("list", help="Show scheduled announcements and when each is next said")
+    announce_remove_parser = announce_commands.add_parser("remove", help="Stop a scheduled announcement")
+    announce_remove_parser.add_argument("announcement_id", metavar="ID", help="Its id (see `dev announce list`)")
+    alarm_parser = dev_commands.add_parser("alarm", help="Wake-up alarms with snooze and a morning briefing")
+    alarm_commands = alarm_parser.add_subparsers(dest="alarm_command", required=True)
+    alarm_add_parser = alarm_commands.add_parser("add", help="Ring at a time of day, once or on some weekdays")
+    alarm_add_parser.add_argument("time", help='e.g. 06:30, 7am, "half past six"')
+    alarm_add_parser.add_argument("--days", help="weekdays, weekends, daily or mon,wed,fri (default: once)")
+    alarm_add_parser.add_argument("--label", default="", help="Shown when it rings")
+    alarm_add_parser.add_argument("--no-briefing", dest="briefing", action="store_false",
+                                  help="Don't read today's calendar and tasks once it's dismissed")
+    alarm_commands.add_parser("list", help="Show alarms and when each next rings")
+    alarm_remove_parser = alarm_commands.add_parser("remove", help="Delete an alarm")
+    alarm_remove_parser.add_argument("alarm_id", metavar="ID", help="Its id (see `dev alarm list`)")
+    news_parser = dev_commands.add_parser("news", help="RSS/Atom feeds read out in the briefing")
+    news_commands = news_parser.add_subparsers(dest="news_command", required=True)
+    news_add_parser = news_commands.add_parser("add", help="Follow a feed")
+    news_add_parser.add_argument("url", help="The feed's RSS or Atom URL")
+    news_add_parser.add_argument("--name", default="", help='Said in the briefing, e.g. "the Rust blog" (default: its title)')
+    news_add_parser.add_argument("--schedule", help='When to fetch: HH:MM or cron, e.g. "0 6 * * *" (default: @hourly)')
+    news_commands.add_parser("list", help="Show followed feeds, their schedules and unread counts")
+    news_commands.add_parser("fetch", help="Fetch every feed now")
+    news_commands.add_parser("unread", help="Show unread items, newest first")
+    news_remove_parser = news_commands.add_parser("remove", help="Unfollow a feed")
+    news_remove_parser.add_argument("feed", metavar="ID", help="Its id, name or URL (see `dev news list`)")
+    list_parser = dev_commands.add_parser("list", help="Shopping/todo lists: show, edit, export or sync")
+    list_commands = list_parser.add_subparsers(dest="list_command", required=True)
+    list_show_parser = list_commands.add_parser("show", help="Print every list, or one")
+    list_show_parser.add_argument("name", nargs="?", help="e.g. shopping, todo")
+    for action, help_text in (("add", "Add items to a list (starting it if it's new)"),
+                              ("check", "Check items off"), ("uncheck", "Put checked items back"),
+                              ("remove", "Take items off a list")):
+        item_parser = list_commands.add_parser(action, help=help_text)
+        item_parser.add_argument("name", help="The list, e.g. shopping")
+        item_parser.add_argument("items", nargs="+", metavar="ITEM")
+    list_clear_parser = list_commands.add_parser("clear", help="Empty a list")
+    list_clear_parser.add_argument("name", help="The list, e.g. shopping")
+    list_clear_parser.add_argument("--checked", dest="checked_only", action="store_true",
+                                   help="Only remove the checked-off items")
+    list_export_parser = list_commands.add_parser("export", help="Write a list as a Markdown checklist")
+    list_export_parser.add_argument("name", help="The list, e.g. shopping")
+    list_export_parser.add_argument("--output", type=Path, help="File to write (default: print it)")
+    list_commands.add_parser("sync", help="Send list changes to the server and fetch other devices'")
+    devices_parser = dev_commands.add_parser("devices", help="Paired phone/web clients: list or revoke")
+    devices_commands = devices_parser.add_subparsers(dest="devices_command", required=True)
+    devices_commands.add_parser("list", help="Show paired devices, their scopes and when they were last seen")
+    devices_revoke_parser = devices_commands.add_parser("revoke", help="Revoke a device's access")
+    devices_revoke_parser.add_argument("name", help="Device name or id (see `dev devices list`)")
+    sync_parser = dev_commands.add_parser("sync", help="Encrypted memory sync with the user's other machines")
+    sync_commands = sync_parser.add_subparsers(dest="sync_command", required=True)
+    sync_commands.add_parser("status", help="Whether this machine syncs, and when it last did")
+    sync_join_parser = sync_commands.add_parser("join", help="Get the key from a machine showing a pairing code")
+    sync_join_parser.add_argument("uri", help="The xswarm://pair?... URI shown by ctrl+y on the other machine")
+    sync_commands.add_parser("now", help="Send this machine's changes and apply the others' now")
+    sync_commands.add_parser("leave", help="Stop syncing and delete the key here")
+    push_parser = dev_commands.add_parser("push", help="ntfy/Pushover pushes for selected events")
+    push_commands = push_parser.add_subparsers(dest="push_command", required=True)
+    push_test_parser = push_commands.add_parser("test", help="Send a test push to an event class's targets")
+    push_test_parser.add_argument("event_class", help="missed_reminder, error or task_done")
+    push_commands.add_parser("failed", help="List pushes waiting for a retry or given up on")
+    push_retry_parser = push_commands.add_parser("retry", help="Resend pushes given up on (or the given ones) now")
+    push_retry_parser.add_argument("ids", nargs="*", help="Failed push ids (default: all given up on)")
+    push_discard_parser = push_commands.add_parser("discard", help="Forget pushes given up on (or the given ones)")
+    push_discard_parser.add_argument("ids", nargs="*", help="Failed push ids (default: all given up on)")
+    matrix_parser = dev_commands.add_parser("matrix", help="Matrix bridge account: log in or out")
+    matrix_parser.add_argument("matrix_command", choices=["login", "logout"])
+    search_parser = dev_commands.add_parser("search", help="Try the web search the web_search tool uses")
+    search_parser.add_argument("query", nargs="+", help="What to search for")
+    reminders_parser = dev_commands.add_parser("reminders",
+                                               help="Reminder wording, and how often reminders get acknowledged")
+    reminders_commands = reminders_parser.add_subparsers(dest="reminders_command", required=True)
+    reminders_preview_parser = reminders_commands.add_parser(
+        "preview", help="Show a sample reminder on every channel per category, and any broken templates")
+    reminders_preview_parser.add_argument("--title", default="Standup", help="The sample event (default Standup)")
+    reminders_preview_parser.add_argument("--minutes", type=int, default=10, help="Minutes until it starts (default 10)")
+    reminders_preview_parser.add_argument("--category", help="Only this category (default: each with templates)")
+    reminders_effect_parser = reminders_commands.add_parser(
+        "effectiveness", help="How often reminders on each channel get acknowledged")
+    reminders_effect_parser.add_argument("--days", type=int, default=30, help="Over this many days (default 30)")
+    quota_parser = dev_commands.add_parser("quota", help="Phone minutes and text messages used this month")
+    quota_parser.add_argument("--sync", action="store_true", help="Report usage to the server and refresh first")
+    project_parser = dev_commands.add_parser("project", help="Project repo/docs search: index a project or ask about it")
+    project_commands = project_parser.add_subparsers(dest="project_command", required=True)
+    project_index_parser = project_commands.add_parser("index", help="Index the project's folders into Meilisearch")
+    project_index_parser.add_argument("name", help="Project name or id")
+    project_ask_parser = project_commands.add_parser("ask", help="Answer a question from the indexed files, with sources")
+    project_ask_parser.add_argument("name", help="Project name or id")
+    project_ask_parser.add_argument("question")
+    project_ask_parser.add_argument("--language", help="Only search files in this language (rust, python, markdown...)")
+    project_summarize_parser = project_commands.add_parser("summarize", help="Summarize one of the project's documents")
+    project_summarize_parser.add_argument("name", help="Project name or id")
+    project_summarize_parser.add_argument("question", metavar="document", help='Which document, e.g. "architecture"')
+    project_watch_parser = project_commands.add_parser("watch", help="Reindex files within seconds of a save (Ctrl+C stops)")
+    project_watch_parser.add_argument("name", nargs="?", help="Project name or id (default: every active project)")
+    project_key_parser = project_commands.add_parser("key", help="This profile's Meilisearch index and API key")
+    project_key_parser.add_argument("--revoke", action="store_true", help="Delete the key (a new one is made on next use)")
+    config_parser = dev_commands.add_parser("config", help="Runtime configuration: feature flags")
+    config_commands = config_parser.add_subparsers(dest="config_command", required=True)
+    config_flags_parser = config_commands.add_parser("flags", help="Each feature flag, on or off, and what decided it")
+    config_flags_parser.add_argument("--sync", action="store_true", help="Fetch the server's overrides first")
+    logs_parser = dev_commands.add_parser("logs", help="Rotated log files in ~/.xswarm/logs")
+    logs_commands = logs_parser.add_subparsers(dest="logs_command", required=True)
+    logs_prune_parser = logs_commands.add_parser("prune", help="Delete the oldest archives until under log_total_mb")
+    logs_prune_parser.add_argument("--dry-run", action="store_true", help="Only list what would be deleted")
+    crash_parser = dev_commands.add_parser("crash-report", help="Saved reports of unhandled errors and crashes")
+    crash_commands = crash_parser.add_subparsers(dest="crash_command", required=True)
+    crash_commands.add_parser("list", help="Saved crash reports, newest first")
+    crash_show_parser = crash_commands.add_parser("show", help="A report: error, stack, subsystems and log tail")
+    crash_show_parser.add_argument("report_id", help="The report (see `dev crash-report list`)")
+    crash_send_parser = crash_commands.add_parser("send",
+                                                  help="Send one report to the server (even without crash_upload)")
+    crash_send_parser.add_argument("report_id", help="The report")
+    api_parser = dev_commands.add_parser("api", help="Local REST API (config.local_api)")
+    api_commands = api_parser.add_subparsers(dest="api_command", required=True)
+    api_token_parser = api_commands.add_parser("token", help="Print the API token")
+    api_token_parser.add_argument("--rotate", action="store_true", help="Replace it first (the old one stops working)")
+    ics_parser = dev_commands.add_parser("ics", help="ICS feed of xswarm's events for calendar apps (config.ics_feed)")
+    ics_commands = ics_parser.add_subparsers(dest="ics_command", required=True)
+    ics_commands.add_parser("url", help="Print the feed URL to subscribe to")
+    ics_commands.add_parser("regenerate", help="Make a new secret URL (the old one stops working)")
+    ics_commands.add_parser("publish", help="Upload the feed now (ics_feed = \"server\")")
+    ics_commands.add_parser("unpublish", help="Delete the uploaded feed from the server")
+    ics_export_parser = ics_commands.add_parser("export", help="Write the feed to a file")
+    ics_export_parser.add_argument("--output", type=Path, help="File to write (default: print it)")
+    core_bundle_parser = dev_commands.add_parser("core-bundle",
+                                                 help="Zip the pure calendar core (dates, recurrence, conflicts) for Pyodide")
+    core_bundle_parser.add_argument("path", type=Path, metavar="FILE")
+    speech_cache_parser = dev_commands.add_parser("speech-cache", help="Recorded common phrases (config.speech_cache)")
+    speech_cache_parser.add_argument("--clear", action="store_true", help="Delete them all (they're recorded again)")
+    tts_parser = dev_commands.add_parser("tts", help="Text-to-speech: how words are said")
+    tts_commands = tts_parser.add_subparsers(dest="tts_command", required=True)
+    pronounce_parser = tts_commands.add_parser("pronounce", help="The pronunciation lexicon (~/.xswarm/pronunciations.yaml)")
+    pronounce_commands = pronounce_parser.add_subparsers(dest="pronounce_command", required=True)
+    pronounce_add_parser = pronounce_commands.add_parser("add", help="Say WORD as SPOKEN")
+    pronounce_add_parser.add_argument("word", help='A name or word as written, e.g. "Siobhan"')
+    pronounce_add_parser.add_argument("spoken", help='A respelling ("shiv-AWN") or IPA between slashes ("/ʃɪˈvɔːn/")')
+    pronounce_list_parser = pronounce_commands.add_parser("list", help="Show the words and how each is said")
+    pronounce_remove_parser = pronounce_commands.add_parser("remove", help="Go back to saying WORD as spelled")
+    pronounce_remove_parser.add_argument("word")
+    pronounce_test_parser = pronounce_commands.add_parser("test", help="Show TEXT the way it will be read aloud")
+    pronounce_test_parser.add_argument("text")
+    for sub_parser in (pronounce_add_parser, pronounce_list_parser, pronounce_remove_parser, pronounce_test_parser):
+        sub_parser.add_argument("--persona", help="Only for this persona (its entries win over the shared ones)")
+    memory_parser = dev_commands.add_parser("memory", help="What the assistant remembers: facts and bookmarks")
+    memory_commands = memory_parser.add_subparsers(dest="memory_command", required=True)
+    memory_list_parser = memory_commands.add_parser("list", help="Show remembered facts and bookmarked conversations")
+    memory_list_parser.add_argument("--search", metavar="WORDS", help="Only what mentions these words")
+    memory_digest_parser = memory_commands.add_parser("digest", help="The weekly digest: what was discussed and done")
+    memory_digest_parser.add_argument("--days", type=int, default=7, help="How many days back (default 7)")
+    memory_digest_parser.add_argument("--save", action="store_true", help="Write it to the digest folder too")
+    compute_parser = dev_commands.add_parser("compute", help="Model placement (Metal/CUDA/CPU) and GPU memory")
+    compute_parser.add_argument("--move", nargs=2, metavar=("MODEL", "BACKEND"),
+                                help="Move a running model, e.g. vad cpu (backend: metal, cuda, cpu, auto)")
+    selftest_parser = dev_commands.add_parser("voice-selftest",
+                                              help="Voice quality regression: Whisper WER and latency over a corpus")
+    selftest_parser.add_argument("--corpus", type=Path, metavar="FILE", help="YAML prompts (default: built-in corpus)")
+    selftest_parser.add_argument("--quality", choices=["bf16", "q8", "q4"], help="Voice model (default: as configured)")
+    selftest_parser.add_argument("--whisper", choices=["auto", "local", "api"], default="auto",
+                                 help="local faster-whisper or OpenAI's API (auto: local if installed)")
+    selftest_parser.add_argument("--max-wer", type=float, help="Word error rate a prompt may reach (default 0.25)")
+    selftest_parser.add_argument("--max-latency", type=float, help="Seconds to the first sound (default 3.0)")
+    selftest_parser.add_argument("--json", action="store_true", help="Print the report as JSON")
+    bench_parser = dev_commands.add_parser("calendar-bench",
+                                           help="Load benchmarks: recurrence, conflicts and free slots on big calendars")
+    bench_parser.add_argument("--sizes", help="Events per calendar, comma-separated (default 100,500,2000)")
+    bench_parser.add_argument("--repeat", type=int, default=3, help="Runs of each, the best kept (default 3)")
+    bench_parser.add_argument("--only", action="append", choices=["expand_recurrence", "find_conflicts", "free_slots"],
+                              help="Just this benchmark (repeatable)")
+    bench_parser.add_argument("--save", type=Path, metavar="FILE", help="Write the results as a JSON baseline")
+    bench_parser.add_argument("--compare", type=Path, metavar="FILE", help="Fail if slower than this baseline")
+    bench_parser.add_argument("--max-regression", type=float, default=0.25,
+                              help="How much slower counts as a regression (default 0.25: 25%%)")
+    bench_parser.add_argument("--json", action="store_true", help="Print the report as JSON")
+    opus_parser = dev_commands.add_parser("opus-bench", help="Opus against raw PCM call audio: bandwidth and latency")
+    opus_parser.add_argument("--seconds", type=float, default=5.0, help="Audio to send (default 5)")
+    opus_parser.add_argument("--bitrate", type=int, help="Bits per second (default: config.opus_bitrate)")
+    opus_parser.add_argument("--link", type=float, default=100.0, help="Simulated link in Mbit/s (default 100)")
+    opus_parser.add_argument("--json", action="store_true", help="Print the result as JSON")
+    backup_parser = dev_commands.add_parser("backup", help="Encrypted backup of all local state, or restore one")
+    backup_commands = backup_parser.add_subparsers(dest="backup_command", required=True)
+    for name, help_text in [("create", "Write a backup to FILE"), ("restore", "Restore from FILE")]:
+        backup_action_parser = backup_commands.add_parser(name, help=help_text)
+        backup_action_parser.add_argument("path", type=Path, metavar="FILE")
+        backup_action_parser.add_argument("--only", action="append",
+                                          help="config, memory, personas, wake_word, events, transcripts, planner "
+                                               "(repeat or comma-separate)")
+        if name == "restore":
+            backup_action_parser.add_argument("--yes", action="store_true", help="Restore without asking")
+
+    plugins_parser = dev_commands.add_parser("plugins", help="WebAssembly skill plugins: list, enable or disable")
+    plugins_commands = plugins_parser.add_subparsers(dest="plugins_command", required=True)
+    plugins_commands.add_parser("list", help="Show installed plugins, what they ask for and what's granted")
+    plugins_enable_parser = plugins_commands.add_parser("enable", help="Enable a plugin, granting access it asks for")
+    plugins_enable_parser.add_argument("name", help="The plugin (see `dev plugins list`)")
+    plugins_enable_parser.add_argument("--grant", dest="grants", action="append", choices=["network", "files"],
+                                       help="Let it reach the hosts or folders its manifest names (repeatable)")
+    plugins_disable_parser = plugins_commands.add_parser("disable", help="Disable a plugin and drop its grants")
+    plugins_disable_parser.add_argument("name", help="The plugin")
 
     from . import __version__
     parser.add_argument(
@@ -409,9 +2109,111 @@ Configuration:
 
     args = parser.parse_args()
 
+    if args.inbox:
+        sys.exit(run_inbox_command(args.config))
+    if args.command == "tray":
+        sys.exit(run_tray_command())
+    if args.command == "mcp":
+        sys.exit(run_mcp_command(args.config))
+    if args.command == "self-update":
+        sys.exit(run_self_update_command(args.check, args.rollback, args.config))
+    if args.command == "dev" and args.dev_command == "api":
+        sys.exit(run_api_token_command(args.rotate))
+    if args.command == "dev" and args.dev_command == "ics":
+        sys.exit(run_ics_command(args.ics_command, getattr(args, "output", None), args.config))
+    if args.command == "dev" and args.dev_command == "undo":
+        sys.exit(run_undo_command(args.list))
+    if args.command == "dev" and args.dev_command == "jobs":
+        sys.exit(run_jobs_command(args.jobs_command, getattr(args, "name", None), args.config))
+    if args.command == "dev" and args.dev_command == "replay":
+        sys.exit(run_replay_command(args.scenarios, args.config, args.replay_chaos or args.chaos))
+    if args.command == "dev" and args.dev_command == "events":
+        if args.events_command == "replay":
+            sys.exit(run_events_replay_command(args.when, args.replay_thread, args.tui, args.config))
+        sys.exit(run_events_command(args.types, args.since, args.limit, args.config, args.thread))
+    if args.command == "dev" and args.dev_command == "ai":
+        sys.exit(run_ai_log_command(args.since, args.provider, args.purpose, args.limit, args.show, args.config))
+    if args.command == "dev" and args.dev_command == "analytics":
+        sys.exit(run_analytics_command(args.period, args.output_format, args.output, args.config))
+    if args.command == "dev" and args.dev_command == "retention":
+        sys.exit(run_retention_command(args.apply, args.verbose, args.config))
+    if args.command == "dev" and args.dev_command == "announce":
+        sys.exit(run_announce_command(args.announce_command, getattr(args, "schedule", None),
+                                      getattr(args, "text", None), getattr(args, "priority", "normal"),
+                                      getattr(args, "tags", None), getattr(args, "announcement_id", None)))
+    if args.command == "dev" and args.dev_command == "alarm":
+        sys.exit(run_alarm_command(args.alarm_command, getattr(args, "time", None), getattr(args, "days", None),
+                                   getattr(args, "label", ""), getattr(args, "briefing", True),
+                                   getattr(args, "alarm_id", None)))
+    if args.command == "dev" and args.dev_command == "news":
+        sys.exit(run_news_command(args.news_command, getattr(args, "url", None), getattr(args, "name", ""),
+                                  getattr(args, "schedule", None), getattr(args, "feed", None), args.config))
+    if args.command == "dev" and args.dev_command == "list":
+        sys.exit(run_list_command(args.list_command, getattr(args, "name", None), getattr(args, "items", None),
+                                  getattr(args, "checked_only", False), getattr(args, "output", None), args.config))
+    if args.command == "dev" and args.dev_command == "devices":
+        sys.exit(run_devices_command(args.devices_command, getattr(args, "name", None)))
+    if args.command == "dev" and args.dev_command == "sync":
+        sys.exit(run_sync_command(args.sync_command, getattr(args, "uri", None), args.config))
+    if args.command == "dev" and args.dev_command == "push":
+        if args.push_command == "test":
+            sys.exit(run_push_command(args.event_class, args.config))
+        sys.exit(run_push_queue_command(args.push_command, getattr(args, "ids", None), args.config))
+    if args.command == "dev" and args.dev_command == "matrix":
+        sys.exit(run_matrix_command(args.matrix_command, args.config))
+    if args.command == "dev" and args.dev_command == "search":
+        sys.exit(run_search_command(" ".join(args.query), args.config))
+    if args.command == "dev" and args.dev_command == "reminders":
+        if args.reminders_command == "effectiveness":
+            sys.exit(run_reminders_effectiveness_command(args.days, args.config))
+        sys.exit(run_reminders_preview_command(args.title, args.minutes, args.category, args.config))
+    if args.command == "dev" and args.dev_command == "logs":
+        sys.exit(run_logs_prune_command(args.dry_run, args.config))
+    if args.command == "dev" and args.dev_command == "crash-report":
+        sys.exit(run_crash_report_command(args.crash_command, getattr(args, "report_id", None), args.config))
+    if args.command == "dev" and args.dev_command == "config":
+        sys.exit(run_flags_command(args.sync, args.config))
+    if args.command == "dev" and args.dev_command == "quota":
+        sys.exit(run_quota_command(args.sync, args.config))
+    if args.command == "dev" and args.dev_command == "project":
+        if args.project_command == "key":
+            sys.exit(run_project_key_command(args.revoke, args.config))
+        sys.exit(run_project_command(args.project_command, args.name, getattr(args, "question", None),
+                                     getattr(args, "language", None), args.config))
+    if args.command == "dev" and args.dev_command == "core-bundle":
+        sys.exit(run_core_bundle_command(args.path))
+    if args.command == "dev" and args.dev_command == "speech-cache":
+        sys.exit(run_speech_cache_command(args.clear))
+    if args.command == "dev" and args.dev_command == "tts":
+        sys.exit(run_pronounce_command(args.pronounce_command, getattr(args, "word", None),
+                                       getattr(args, "spoken", None), getattr(args, "text", None), args.persona))
+    if args.command == "dev" and args.dev_command == "memory":
+        if args.memory_command == "digest":
+            sys.exit(run_memory_digest_command(args.days, args.save, args.config))
+        sys.exit(run_memory_command(args.search))
+    if args.command == "dev" and args.dev_command == "compute":
+        sys.exit(run_compute_command(args.move, args.config))
+    if args.command == "dev" and args.dev_command == "voice-selftest":
+        sys.exit(run_voice_selftest_command(args.corpus, args.quality, args.whisper, args.max_wer, args.max_latency,
+                                            args.json, args.config))
+    if args.command == "dev" and args.dev_command == "calendar-bench":
+        sys.exit(run_calendar_bench_command(args.sizes, args.repeat, args.only, args.save, args.compare,
+                                            args.max_regression, args.json))
+    if args.command == "dev" and args.dev_command == "opus-bench":
+        sys.exit(run_opus_bench_command(args.seconds, args.bitrate, args.link, args.json, args.config))
+    if args.command == "dev" and args.dev_command == "backup":
+        sys.exit(run_backup_command(args.backup_command, args.path, args.only, args.personas_dir,
+                                    getattr(args, "yes", False), args.config))
+
+    if args.command == "dev" and args.dev_command == "plugins":
+        sys.exit(run_plugins_command(args.plugins_command, getattr(args, "name", None), getattr(args, "grants", None),
+                                     args.config))
+
     # Show splash screen immediately (before heavy imports)
     # This clears any stray output and shows the logo while loading
-    show_splash()
+    # (not in accessibility mode, where stdout is plain lines for a screen reader)
+    if args.a11y != "-":
+        show_splash()
 
     # Suppress ALL stdout/stderr during imports to prevent stray output
     # This prevents any library from printing during initialization
@@ -458,7 +2260,6 @@ Configuration:
         from .personas import PersonaManager
         from .memory import MemoryManager
         from .wake_word import WakeWordDetector
-        from .scheduler import Scheduler
 
         # GPU detection and service selection
         from .hardware import detect_gpu_capability, select_services
@@ -472,28 +2273,47 @@ Configuration:
 
     # Load or create config
     config_path = args.config if args.config else None
-    config = Config.load_from_file(config_path)
+    with profile.stage("config"):
+        config = Config.load_from_file(config_path)
+    profile.set_budgets(config.startup_budgets)
+    log_handler.configure(config)
+    profile.exit_when_done = args.profile_startup
 
     # Set debug mode flag
     config.is_debug_mode = args.debug
 
-    # Voice disabled by default until voice interface is complete
-    # TODO: Re-enable when Moshi voice server is production-ready
-    config.voice_enabled = False
-
     # Load API keys from .env if in debug mode
     if args.debug:
         config = Config.load_env_keys(config)
 
     # Apply service selection to config
+    config_quality = config.moshi_quality
     config.moshi_quality = service_config.moshi_quality
     config.thinking_mode = service_config.thinking_mode
     config.thinking_model = service_config.thinking_model
     config.embedding_mode = service_config.embedding_mode
 
+    # Which backend each model runs on, and yielding the GPU - see compute.py
+    from .compute import ComputeManager, get_compute_manager, set_compute_manager
+    set_compute_manager(ComputeManager.from_config(config))
+    # Unhandled errors and fatal crashes kept as redacted reports, sent only with crash_upload - see crash_report.py
+    if config.crash_reports:
+        from .crash_report import CrashReporter, set_crash_reporter
+        crash_reporter = CrashReporter.from_config(config)
+        set_crash_reporter(crash_reporter)
+        crash_reporter.install()
+    # Battery/thermal-aware profile (smaller models, slower polling) - see power.py
+    from .power import PowerManager, set_power_manager
+    power = PowerManager.from_config(config)
+    power.sample(force=True)
+    set_power_manager(power)
+
     # Check if first run (no config file exists)
     # Skip wizard in debug mode for faster local testing
-    if not args.debug and not (args.config or Config.get_config_path().exists()):
+    a11y_target = args.a11y or config.a11y_output
+    if a11y_target == "-" and not (args.config or Config.get_config_path().exists()):
+        config = Config()  # The wizard is full-screen: defaults, changed later with the settings tools
+    elif not args.debug and not (args.config or Config.get_config_path().exists()):
         logger.info("First run - showing wizard")
         try:
             # Show wizard in TUI
@@ -507,18 +2327,73 @@ Configuration:
     elif args.debug and not (args.config or Config.get_config_path().exists()):
         config = Config()
 
+    # Text-only mode: set in config (text_only: true) or per run with --text-only
+    if args.text_only:
+        config.text_only = True
+
+    # Failover drills: simulated provider failures for this run only (see chaos.py)
+    if args.chaos:
+        from .chaos import Chaos, set_chaos
+        try:
+            set_chaos(Chaos.parse(args.chaos))
+        except ValueError as e:
+            print(f"✗ {e}", file=sys.stderr)
+            sys.exit(1)
+
+    # Stored data is upgraded to this version's formats (backed up first) before anything opens it;
+    # data from a newer xswarm is left alone and startup stops
+    from .migrations import MigrationFailed, SchemaTooNew, migrate_stores
+    try:
+        for upgraded in migrate_stores(config):
+            logger.info(f"Upgraded {upgraded}")
+    except (SchemaTooNew, MigrationFailed) as e:
+        print(f"✗ {e}", file=sys.stderr)
+        sys.exit(1)
+
+    # Plain-line event stream for screen readers (--a11y or a11y_output) - see accessibility.py
+    if a11y_target:
+        from .accessibility import AccessibleStream, set_a11y_stream
+        try:
+            set_a11y_stream(AccessibleStream.open(a11y_target))
+        except OSError as e:
+            print(f"✗ Could not open accessibility output {a11y_target}: {e}", file=sys.stderr)
+            sys.exit(1)
+
     # Start Moshi voice server BEFORE Textual to avoid multiprocessing issues
-    # The server runs MLX inference in a separate process for proper Metal GPU utilization
+    # The server runs MLX inference in a separate process for proper Metal GPU utilization.
+    # Spawning returns immediately - models download/load in the background while the
+    # TUI is already usable, with progress shown in the footer (see model_loading.py)
     voice_server_process = None
     voice_queues = None
 
     logger.debug(f"Moshi Mode: {service_config.moshi_mode}")
-    if service_config.moshi_mode == "local" and config.voice_enabled:
-        logger.debug(f"Starting voice server (quality={service_config.moshi_quality})...")
+    if service_config.moshi_mode == "local" and config.voice_active:
+        # Explicitly configured quality is the most we'll load; the memory cap can step it down
+        from .model_memory import MOSHI_VARIANTS, select_voice_variant
+        requested = config_quality if config_quality in MOSHI_VARIANTS else service_config.moshi_quality
+        smallest = power.profile.voice_variant
+        if smallest and list(MOSHI_VARIANTS).index(requested) < list(MOSHI_VARIANTS).index(smallest):
+            logger.info(f"Using the {smallest} voice model: {power.status_line()}")
+            requested = smallest
+        voice_variant = select_voice_variant(requested, config.voice_model_memory_gb)
+        if voice_variant is None:
+            logger.warning(f"No voice model fits voice_model_memory_gb={config.voice_model_memory_gb:g} - voice disabled")
+        elif voice_variant != requested:
+            logger.info(f"Using {voice_variant} voice model to stay under {config.voice_model_memory_gb:g}GB")
+    else:
+        voice_variant = None
+
+    if voice_variant:
+        config.moshi_quality = voice_variant
+        logger.debug(f"Starting voice server (quality={voice_variant})...")
         try:
+            from .compute import CPU, METAL
             from .voice_server import start_server_process
+            voice_device = "cpu" if get_compute_manager().register("voice", (METAL, CPU)) == CPU else "gpu"
             # Unpack the tuple returned by start_server_process
-            process, c2s, s2c, status = start_server_process(quality=service_config.moshi_quality)
+            with profile.stage("voice_server"):
+                process, c2s, s2c, status = start_server_process(
+                    quality=voice_variant, mmap_weights=config.voice_mmap_weights, device=voice_device)
             voice_server_process = process
             voice_queues = (c2s, s2c, status)
             logger.debug("Voice server process started")
@@ -541,7 +2416,7 @@ Configuration:
         'enabled': config.tunnel_enabled,
         'sendgrid_enabled': config.sendgrid_enabled,
         'has_phone_subscription': config.has_phone_subscription,
-        'voice_enabled': config.voice_enabled,
+        'voice_enabled': config.voice_active,
         'moshi_mode': service_config.moshi_mode,
         'webhook_server_port': config.webhook_server_port,
         'voice_server_port': config.voice_server_port,
@@ -555,7 +2430,8 @@ Configuration:
     # This catches any output from libraries that write directly to stdout/stderr
     sys.stdout.flush()
     sys.stderr.flush()
-    os.system('clear' if os.name != 'nt' else 'cls')
+    if a11y_target != "-":
+        os.system('clear' if os.name != 'nt' else 'cls')
 
     # Set up signal handler for clean exit (no traceback on Ctrl+C)
     # Use KeyboardInterrupt instead of sys.exit() to avoid threading shutdown issues
@@ -575,6 +2451,8 @@ Configuration:
     try:
         asyncio.run(assistant.initialize())
         asyncio.run(assistant.run())
+        if args.profile_startup:
+            print("\n".join(profile.lines()))
     except (KeyboardInterrupt, SystemExit):
         # Clean exit on Ctrl+C - no traceback needed
         pass
@@ -583,6 +2461,9 @@ Configuration:
         if args.debug:
             import traceback
             logger.debug(traceback.format_exc())
+        if config.crash_reports:
+            from .crash_report import get_crash_reporter
+            get_crash_reporter().capture(e, "main")
         sys.exit(1)
     finally:
         # Suppress any exceptions during cleanup
@@ -0,0 +1,215 @@
+"""
+Matrix Bridge - Chat with the assistant from any Matrix client, end-to-end encrypted.
+
+The assistant logs in as its own Matrix account (config.matrix_user_id,
+`xswarm dev matrix login` once) and keeps its encryption keys in
+~/.xswarm/matrix/store, so it can read and send messages in encrypted
+rooms. It needs the "matrix" extra (matrix-nio with libolm).
+
+Only config.matrix_allowed_users can talk to it: their invites are
+accepted, everyone else's invites and messages are ignored. A message is
+handled like typed chat (same persona, tools, confirmations and
+conversation memory), and the reply goes back to the room it came from.
+Messages sent before the bridge started are skipped, so a restart doesn't
+answer old history.
+
+Replies are encrypted to the room members' devices without interactive
+verification; verify the assistant's device from your client if you want
+the shield to go green.
+
+Storage: ~/.xswarm/matrix/credentials.json (access token, mode 0600)
+"""
+
+import asyncio
+import json
+import logging
+import os
+import time
+from pathlib import Path
+from typing import Callable, Dict, Iterable, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+MATRIX_DIR = Path.home() / ".xswarm" / "matrix"
+CREDENTIALS_PATH = MATRIX_DIR / "credentials.json"
+STORE_DIR = MATRIX_DIR / "store"
+SYNC_TIMEOUT_MS = 30_000
+
+
+class MatrixError(Exception):
+    """The bridge can't start: not logged in, nobody allowed, or the login failed."""
+
+
+def load_credentials(path: Path = CREDENTIALS_PATH) -> Optional[Dict[str, str]]:
+    try:
+        return json.loads(Path(path).read_text())
+    except (OSError, ValueError):
+        return None
+
+
+def save_credentials(credentials: Dict[str, str], path: Path = CREDENTIALS_PATH) -> None:
+    path = Path(path)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(credentials, indent=2))
+    os.chmod(path, 0o600)
+
+
+def _client_config():
+    from nio import AsyncClientConfig
+
+    return AsyncClientConfig(encryption_enabled=True, store_sync_tokens=True)
+
+
+async def login(homeserver: str, user_id: str, password: str, device_name: str = "xswarm",
+                path: Path = CREDENTIALS_PATH, store_dir: Path = STORE_DIR) -> Dict[str, str]:
+    """Log in with a password once and keep the access token; the password isn't stored."""
+    from nio import AsyncClient, LoginResponse
+
+    Path(store_dir).mkdir(parents=True, exist_ok=True)
+    client = AsyncClient(homeserver, user_id, store_path=str(store_dir), config=_client_config())
+    try:
+        response = await client.login(password, device_name=device_name)
+    finally:
+        await client.close()
+    if not isinstance(response, LoginResponse):
+        raise MatrixError(f"Matrix login failed: {getattr(response, 'message', response)}")
+    credentials = {"homeserver": homeserver, "user_id": response.user_id,
+                   "device_id": response.device_id, "access_token": response.access_token}
+    save_credentials(credentials, path)
+    return credentials
+
+
+def open_client(credentials: Dict[str, str], store_dir: Path = STORE_DIR):
+    """An encryption-enabled nio client restored from saved credentials."""
+    from nio import AsyncClient
+
+    Path(store_dir).mkdir(parents=True, exist_ok=True)
+    client = AsyncClient(credentials["homeserver"], credentials["user_id"], device_id=credentials["device_id"],
+                         store_path=str(store_dir), config=_client_config())
+    client.restore_login(credentials["user_id"], credentials["device_id"], credentials["access_token"])
+    return client
+
+
+class MatrixBridge:
+    """Passes allowed users' Matrix messages to the assistant and its replies back to their room."""
+
+    def __init__(self, client, allowed_users: Iterable[str], on_message: Callable[[str, str, str], None],
+                 started_ms: Optional[int] = None):
+        self.client = client
+        self.allowed_users = set(allowed_users)
+        self.on_message = on_message  # (text, sender, room_id)
+        self.started_ms = int(time.time() * 1000) if started_ms is None else started_ms
+        self.reply_room: Optional[str] = None  # Where the assistant's replies go; None after local chat
+        self._incoming: Optional[str] = None
+        self._last_reply: Optional[Tuple[str, str]] = None
+        self._callbacks_added = False
+
+    @classmethod
+    def from_config(cls, config, on_message: Callable[[str, str, str], None],
+                    credentials_path: Path = CREDENTIALS_PATH) -> Optional["MatrixBridge"]:
+        """The bridge when config.matrix_enabled, else None. Raises MatrixError when it can't start."""
+        if not getattr(config, "matrix_enabled", False):
+            return None
+        allowed = list(getattr(config, "matrix_allowed_users", None) or [])
+        if not allowed:
+            raise MatrixError("Set config.matrix_allowed_users to who may chat with the assistant")
+        credentials = load_credentials(credentials_path)
+        if not credentials:
+            raise MatrixError("Not logged in to Matrix - run `xswarm dev matrix login`")
+        return cls(open_client(credentials), allowed, on_message)
+
+    async def handle_invite(self, room_id: str, sender: str) -> bool:
+        """Join rooms allowed users invite the assistant to."""
+        if sender not in self.allowed_users:
+            logger.info(f"Ignored Matrix invite to {room_id} from {sender}")
+            return False
+        await self.client.join(room_id)
+        logger.info(f"Joined Matrix room {room_id} (invited by {sender})")
+        return True
+
+    def handle_message(self, room_id: str, sender: str, body: str, timestamp_ms: int) -> bool:
+        """Pass on a text message from an allowed user; True when it was."""
+        text = (body or "").strip()
+        if sender == self.client.user_id or not text or timestamp_ms < self.started_ms:
+            return False
+        if sender not in self.allowed_users:
+            logger.debug(f"Ignored Matrix message from {sender}")
+            return False
+        self._incoming = room_id
+        self.on_message(text, sender, room_id)
+        return True
+
+    def relay(self, sender: str, text: str) -> None:
+        """A chat message was shown: send finished replies to the room the last user message came from."""
+        if sender == "User":
+            self.reply_room, self._incoming = self._incoming, None
+            self._last_reply = None
+            return
+        stripped = (text or "").strip()
+        if (self.reply_room is None or sender.lower() in ("thinking", "debug") or not stripped
+                or stripped.endswith(("▌", "..."))):
+            return
+        if (sender, stripped) == self._last_reply:
+            return
+        self._last_reply = (sender, stripped)
+        try:
+            asyncio.get_running_loop().create_task(self.send(self.reply_room, stripped))
+        except RuntimeError:
+            logger.debug("Matrix reply dropped: not on the event loop")
+
+    async def send(self, room_id: str, text: str) -> bool:
+        try:
+            await self.client.room_send(room_id, "m.room.message", {"msgtype": "m.text", "body": text},
+                                        ignore_unverified_devices=True)
+            return True
+        except Exception as e:
+            logger.warning(f"Could not send to Matrix room {room_id}: {e}")
+            return False
+
+    def _add_callbacks(self) -> None:
+        from nio import InviteMemberEvent, MegolmEvent, RoomMessageText
+
+        async def on_text(room, event):
+            self.handle_message(room.room_id, event.sender, event.body, event.server_timestamp)
+
+        async def on_invite(room, event):
+            if event.membership == "invite" and event.state_key == self.client.user_id:
+                await self.handle_invite(room.room_id, event.sender)
+
+        async def on_undecryptable(room, event):
+            logger.warning(f"Could not decrypt a Matrix message from {event.sender} in {room.room_id}")
+
+        self.client.add_event_callback(on_text, RoomMessageText)
+        self.client.add_event_callback(on_invite, InviteMemberEvent)
+        self.client.add_event_callback(on_undecryptable, MegolmEvent)
+        self._callbacks_added = True
+
+    async def run(self) -> None:
+        """Sync until cancelled (run under the task supervisor, which restarts it on errors)."""
+        if not self._callbacks_added:
+            self._add_callbacks()
+        if self.client.should_upload_keys:
+            await self.client.keys_upload()
+        await self.client.sync_forever(timeout=SYNC_TIMEOUT_MS, full_state=True)
+
+    async def close(self) -> None:
+        await self.client.close()
+
+
+_bridge: Optional[MatrixBridge] = None
+
+
+def get_matrix_bridge() -> Optional[MatrixBridge]:
+    """The running Matrix bridge, or None when it's off."""
+    return _bridge
+
+
+def set_matrix_bridge(bridge: Optional[MatrixBridge]) -> None:
+    global _bridge
+    _bridge = bridge
+
+
+def relay_chat(sender: str, text: str) -> None:
+    """Called for every chat message shown; replies to Matrix messages go back to their room."""
+    if _bridge is not None:
+        _bridge.relay(sender, text)
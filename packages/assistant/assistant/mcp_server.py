@@ -0,0 +1,115 @@
+"""
+MCP Server - The local API's operations as tools for MCP clients.
+
+`xswarm mcp` speaks the Model Context Protocol over stdio (one JSON-RPC
+message per line) so an MCP client - an editor, a desktop chat app - can
+list appointments, create reminders and have the assistant speak:
+
+    {"mcpServers": {"xswarm": {"command": "xswarm", "args": ["mcp"]}}}
+
+Each tool is an operation from local_api.OPERATIONS, with the request
+dataclass's schema as its input schema, and each call goes to the running
+assistant's local API (config.local_api must be on) with the token from
+~/.xswarm/local_api_token. The MCP server holds no state of its own.
+"""
+
+import json
+import sys
+from typing import Any, Callable, Dict, Optional, TextIO
+from urllib.error import HTTPError, URLError
+from urllib.parse import urlencode
+from urllib.request import Request, urlopen
+
+from .local_api import OPERATIONS, Operation, object_schema
+
+PROTOCOL_VERSION = "2024-11-05"
+TIMEOUT = 15.0
+
+Call = Callable[[Operation, Dict[str, Any]], Any]
+
+
+class ToolError(Exception):
+    """A tool call failed; the message goes back to the client as the result text."""
+
+
+def api_caller(base_url: str, token: str, opener=urlopen) -> Call:
+    """Calls operations on the local API at base_url."""
+
+    def call(op: Operation, arguments: Dict[str, Any]) -> Any:
+        url, data = base_url.rstrip("/") + op.path, None
+        if op.method == "GET":
+            if arguments:
+                url += "?" + urlencode(arguments)
+        else:
+            data = json.dumps(arguments).encode()
+        request = Request(url, data=data, method=op.method,
+                          headers={"Authorization": f"Bearer {token}", "Content-Type": "application/json"})
+        try:
+            with opener(request, timeout=TIMEOUT) as response:
+                return json.loads(response.read())
+        except HTTPError as e:
+            try:
+                message = json.loads(e.read()).get("error") or e.reason
+            except (ValueError, AttributeError):
+                message = e.reason
+            raise ToolError(f"{message} (HTTP {e.code})")
+        except (URLError, OSError):
+            raise ToolError("xSwarm isn't running with its local API on (config.local_api)")
+
+    return call
+
+
+class McpServer:
+    """Answers MCP requests with OPERATIONS as tools, calling them through `call`."""
+
+    def __init__(self, call: Call):
+        self.call = call
+        self.tools = {op.name: op for op in OPERATIONS}
+
+    def _tools_list(self) -> Dict[str, Any]:
+        return {"tools": [{"name": op.name, "description": op.summary, "inputSchema": object_schema(op.request)}
+                          for op in OPERATIONS]}
+
+    def _tools_call(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        op = self.tools.get(params.get("name"))
+        if op is None:
+            return {"content": [{"type": "text", "text": f"Unknown tool '{params.get('name')}'"}], "isError": True}
+        try:
+            result = self.call(op, params.get("arguments") or {})
+        except ToolError as e:
+            return {"content": [{"type": "text", "text": str(e)}], "isError": True}
+        return {"content": [{"type": "text", "text": json.dumps(result, indent=2, ensure_ascii=False)}]}
+
+    def handle(self, message: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        """The response to one JSON-RPC message (None for notifications)."""
+        if "id" not in message:
+            return None
+        method, params = message.get("method"), message.get("params") or {}
+        if method == "initialize":
+            from . import __version__
+            result = {"protocolVersion": PROTOCOL_VERSION, "capabilities": {"tools": {}},
+                      "serverInfo": {"name": "xswarm", "version": __version__}}
+        elif method == "ping":
+            result = {}
+        elif method == "tools/list":
+            result = self._tools_list()
+        elif method == "tools/call":
+            result = self._tools_call(params)
+        else:
+            return {"jsonrpc": "2.0", "id": message["id"],
+                    "error": {"code": -32601, "message": f"Method not found: {method}"}}
+        return {"jsonrpc": "2.0", "id": message["id"], "result": result}
+
+    def run(self, stdin: TextIO = sys.stdin, stdout: TextIO = sys.stdout) -> None:
+        for line in stdin:
+            if not line.strip():
+                continue
+            try:
+                message = json.loads(line)
+            except ValueError:
+                response = {"jsonrpc": "2.0", "id": None, "error": {"code": -32700, "message": "Parse error"}}
+            else:
+                response = self.handle(message) if isinstance(message, dict) else None
+            if response is not None:
+                stdout.write(json.dumps(response) + "\n")
+                stdout.flush()
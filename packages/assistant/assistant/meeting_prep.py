@@ -0,0 +1,288 @@
+"""
+Meeting Prep - A short brief on who you're about to meet and what's going on.
+
+N minutes before an event with attendees or a project, the dashboard's
+meeting_prep job (every minute) gathers:
+
+- memories: the last conversation that mentioned each participant
+  (PersistentChatHistory) and profile facts about them (UserProfile)
+- recent messages from participants (inbox: email, SMS, voicemail)
+- project notes: the event's project, its health and next action, and
+  open tasks with notes
+
+and offers it on screen (activity feed and desktop notification) and, when
+configured, spoken ("Prep for Pricing review in ten minutes: you last
+spoke to Alice about the pricing change.").
+
+Configured per category (config.meeting_prep_categories, see
+categories.py), falling back to meeting_prep_minutes / meeting_prep_spoken:
+
+    {"work": {"minutes": 20, "spoken": true}, "personal": {"enabled": false}}
+
+Events with nothing found get no brief. The prepare_for_meeting tool
+builds the same brief on demand.
+"""
+
+import logging
+import re
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from typing import Any, Callable, Dict, Iterable, List, Optional, Set, Tuple
+
+from .notifications import send_desktop_notification
+from .scheduler_client import instance_key
+from .verbalize import verbalize_relative
+
+logger = logging.getLogger(__name__)
+
+MESSAGE_WINDOW = timedelta(days=30)  # Inbox messages older than this aren't "recent"
+MAX_ITEMS_PER_PERSON = 3
+MAX_PROJECT_TASKS = 3
+SPOKEN_ITEMS = 2  # The rest stay on screen
+SNIPPET_WORDS = 12
+
+
+@dataclass
+class PrepRule:
+    """Prep settings for one event after category overrides."""
+    enabled: bool = True
+    minutes: int = 10
+    spoken: bool = False
+
+
+@dataclass
+class PrepSettings:
+    """config.meeting_prep_minutes, meeting_prep_spoken and meeting_prep_categories."""
+    minutes: int = 10  # 0 = off unless a category turns it on
+    spoken: bool = False
+    categories: Dict[str, Dict[str, Any]] = field(default_factory=dict)
+
+    @classmethod
+    def from_config(cls, config) -> "PrepSettings":
+        return cls(minutes=getattr(config, "meeting_prep_minutes", 10),
+                   spoken=getattr(config, "meeting_prep_spoken", False),
+                   categories=dict(getattr(config, "meeting_prep_categories", None) or {}))
+
+    def rule_for(self, tags: Iterable[str]) -> PrepRule:
+        """The first tag with an override wins; untagged events use the defaults."""
+        for tag in tags or ():
+            override = self.categories.get(tag)
+            if override is not None:
+                minutes = int(override.get("minutes", self.minutes))
+                return PrepRule(enabled=bool(override.get("enabled", True)) and minutes > 0,
+                                minutes=minutes, spoken=bool(override.get("spoken", self.spoken)))
+        return PrepRule(enabled=self.minutes > 0, minutes=self.minutes, spoken=self.spoken)
+
+
+@dataclass
+class PrepItem:
+    """One thing worth knowing before the meeting."""
+    kind: str  # "message", "conversation", "fact", "project", "task"
+    text: str  # On-screen line
+    spoken: str = ""  # Phrase for the spoken brief ("" = screen only)
+
+
+@dataclass
+class PrepBrief:
+    """Everything gathered for one event."""
+    event: Any  # CalendarEvent
+    items: List[PrepItem]
+    minutes: Optional[int] = None  # Until the start, when sent ahead of it
+
+    @property
+    def title(self) -> str:
+        when = f" in {self.minutes} minute{'s' if self.minutes != 1 else ''}" if self.minutes else ""
+        return f"Prep for {self.event.title}{when}"
+
+    @property
+    def text(self) -> str:
+        if not self.items:
+            return f"{self.title}: nothing on file about the participants or project."
+        return "\n".join([f"{self.title}:"] + [f"  • {item.text}" for item in self.items])
+
+    @property
+    def spoken(self) -> str:
+        """e.g. "Prep for Pricing review in ten minutes: you last spoke to Alice about the pricing change." """
+        when = f" {verbalize_relative(timedelta(minutes=self.minutes))}" if self.minutes else ""
+        phrases = [item.spoken for item in self.items if item.spoken]
+        if not phrases:
+            return f"Prep for {self.event.title}{when} is on screen."
+        more = " The rest is on screen." if len(phrases) > SPOKEN_ITEMS or len(phrases) < len(self.items) else ""
+        return f"Prep for {self.event.title}{when}: {'; '.join(phrases[:SPOKEN_ITEMS])}.{more}"
+
+
+# ------------------------------------------------------------------------------
+# Participants
+# ------------------------------------------------------------------------------
+
+def participant_name(attendee: str) -> str:
+    """What to call an attendee: "alice.smith@x.com" -> "Alice", "Bob Jones" -> "Bob"."""
+    attendee = attendee.strip()
+    if "@" in attendee:
+        local = re.split(r"[._+-]", attendee.split("@", 1)[0])[0]
+        return local.title() if local.isalpha() else attendee
+    if attendee.lstrip("+").replace(" ", "").replace("-", "").isdigit():
+        return attendee
+    return attendee.split()[0] if attendee.split() else attendee
+
+
+def participant_terms(attendee: str) -> List[str]:
+    """Lowercase strings that identify an attendee in senders and text (the full value, then the name)."""
+    attendee = attendee.strip().lower()
+    terms = [attendee] if attendee else []
+    name = participant_name(attendee).lower()
+    if name != attendee and len(name) >= 3:
+        terms.append(name)
+    return terms
+
+
+def _mentions(text: str, terms: List[str]) -> bool:
+    lowered = (text or "").lower()
+    return any(re.search(rf"(?<![\w@.]){re.escape(term)}(?![\w@])", lowered) for term in terms)
+
+
+def _snippet(text: str) -> str:
+    words = " ".join(text.split()).split(" ")
+    return " ".join(words[:SNIPPET_WORDS]) + ("…" if len(words) > SNIPPET_WORDS else "")
+
+
+def _day(value: str) -> str:
+    moment = datetime.fromisoformat(value.replace("Z", "+00:00"))
+    return f"{moment:%b} {moment.day}"
+
+
+# ------------------------------------------------------------------------------
+# Sources
+# ------------------------------------------------------------------------------
+
+def message_items(attendee: str, inbox, now: datetime) -> List[PrepItem]:
+    """The latest inbox message from the attendee within MESSAGE_WINDOW."""
+    terms, name = participant_terms(attendee), participant_name(attendee)
+    cutoff = (now - MESSAGE_WINDOW).isoformat()
+    for message in inbox.get_items(include_archived=True):  # Newest first
+        if message.received_at[:19] < cutoff[:19]:
+            break
+        if not _mentions(message.sender, terms):
+            continue
+        about = message.subject or _snippet(message.content)
+        verb = {"email": "emailed", "sms": "texted", "voice": "left a voicemail"}.get(message.channel, "wrote")
+        return [PrepItem("message", f"{message.icon} {name} {verb} {_day(message.received_at)}: {about}",
+                         f"{name} last {verb} about {about}")]
+    return []
+
+
+def conversation_items(attendee: str, history) -> List[PrepItem]:
+    """The most recent thing said in conversation that mentions the attendee."""
+    terms, name = participant_terms(attendee), participant_name(attendee)
+    found = []
+    for term in terms:
+        found += [m for m in history.search_all_sessions(term, max_results=50) if _mentions(m["content"], terms)]
+    if not found:
+        return []
+    # Prefer what the user said over the assistant's replies
+    latest = max(found, key=lambda m: (m["role"] == "user", m.get("timestamp") or ""))
+    snippet = _snippet(latest["content"])
+    when = f" {_day(latest['timestamp'])}" if latest.get("timestamp") else ""
+    return [PrepItem("conversation", f"💬 Last talked about {name}{when}: \"{snippet}\"",
+                     f"you last spoke about {name}: {snippet}")]
+
+
+def fact_items(attendee: str, profile) -> List[PrepItem]:
+    """Profile facts that mention the attendee ("Alice is the CFO")."""
+    terms = participant_terms(attendee)
+    return [PrepItem("fact", f"👤 {fact.fact}", "")
+            for fact in profile.facts if _mentions(fact.fact, terms)][:MAX_ITEMS_PER_PERSON]
+
+
+def project_items(event, planner) -> List[PrepItem]:
+    """The event's project: health, next action and open tasks with notes."""
+    if not event.project_id:
+        return []
+    project = next((p for p in planner.get_projects() if p.id == event.project_id), None)
+    if project is None:
+        return []
+    status = f"{project.health}" + (f" - {project.health_reason}" if project.health_reason else "")
+    line = f"📁 {project.name} ({status})" + (f"; next: {project.next_action}" if project.next_action else "")
+    items = [PrepItem("project", line, f"{project.name} is {project.health}"
+                      + (f", next up is {project.next_action}" if project.next_action else ""))]
+    open_tasks = [t for t in planner.get_tasks() if t.project_id == project.id and t.status != "complete" and t.notes]
+    items += [PrepItem("task", f"📌 {t.title}: {_snippet(t.notes)}") for t in open_tasks[:MAX_PROJECT_TASKS]]
+    return items
+
+
+def build_brief(event, planner, inbox=None, profile=None, history=None,
+                now: Optional[datetime] = None, minutes: Optional[int] = None) -> PrepBrief:
+    """
+    Gather a brief for `event`. Each source is optional; one that fails is
+    logged and skipped so the rest still arrive.
+    """
+    now = now or datetime.now()
+    items: List[PrepItem] = []
+
+    def gather(source: Callable[[], List[PrepItem]]) -> None:
+        try:
+            items.extend(source())
+        except Exception as e:
+            logger.debug(f"Meeting prep source failed: {e}")
+
+    for attendee in event.attendees or []:
+        if inbox is not None:
+            gather(lambda: message_items(attendee, inbox, now))
+        if history is not None:
+            gather(lambda: conversation_items(attendee, history))
+        if profile is not None:
+            gather(lambda: fact_items(attendee, profile))
+    gather(lambda: project_items(event, planner))
+    return PrepBrief(event=event, items=items, minutes=minutes)
+
+
+# ------------------------------------------------------------------------------
+# Scheduling and delivery
+# ------------------------------------------------------------------------------
+
+def due_preps(events: Iterable[Any], now: datetime, prepared: Set[str],
+              settings: PrepSettings) -> List[Tuple[Any, PrepRule, int]]:
+    """
+    Events with attendees or a project whose prep window has opened, as
+    (event, rule, minutes until start). Keys are added to `prepared`, so
+    each event instance is prepared once.
+    """
+    due = []
+    for event in events:
+        if not (event.attendees or event.project_id) or "T" not in event.start_time:
+            continue
+        rule = settings.rule_for(event.tags)
+        key = instance_key(event)
+        start = datetime.fromisoformat(event.start_time)
+        if not rule.enabled or key in prepared or not start - timedelta(minutes=rule.minutes) <= now < start:
+            continue
+        prepared.add(key)
+        due.append((event, rule, max(1, round((start - now).total_seconds() / 60))))
+    return due
+
+
+async def deliver_prep(
+    brief: PrepBrief,
+    rule: PrepRule,
+    activity: Optional[Callable[[str], None]] = None,
+    notify: Callable[..., bool] = send_desktop_notification,
+    announcer: Optional[Any] = None,
+) -> Dict[str, bool]:
+    """
+    Show the brief in the activity feed and a desktop notification, and read
+    it aloud when the rule says so. Returns which channels delivered it.
+    """
+    delivered = {"activity": False, "desktop": False, "speech": False}
+    tags = list(brief.event.tags or [])
+    if activity:
+        for line in brief.text.splitlines():
+            activity(f"📝 {line.strip()}")
+        delivered["activity"] = True
+    body = "\n".join(item.text for item in brief.items)
+    delivered["desktop"] = bool(notify(brief.title, body, tags=tags))
+    if rule.spoken and announcer is not None:
+        try:
+            delivered["speech"] = bool(await announcer.announce(brief.spoken, tags=tags))
+        except Exception as e:
+            logger.debug(f"Spoken meeting prep failed: {e}")
+    return delivered
@@ -20,12 +20,19 @@ import json
 import logging
 import asyncio
 import hashlib
+import time
 import httpx
 from typing import List, Optional, Dict, Any, TYPE_CHECKING
 from dataclasses import dataclass, field, asdict
 from datetime import datetime, timedelta
 from pathlib import Path
 
+from .ai_log import log_ai_call
+from .api_client import ApiClient, ApiPolicy
+from .errors import ConfigError, MemoryStoreError, XswarmError
+from .migrations import Migration, migrate_sqlite
+from .redaction import redact
+
 # Lazy import for openai - checked on first use
 _openai_checked = False
 _openai_available = False
@@ -114,21 +121,21 @@ class ModelConfig:
     @classmethod
     def parse(cls, config_string: str) -> "ModelConfig":
         if not config_string or not isinstance(config_string, str):
-            raise ValueError("config_string must be a non-empty string")
+            raise ConfigError("config_string must be a non-empty string")
         config_string = config_string.strip().strip('"').strip("'")
         if ":" not in config_string:
-            raise ValueError(f"Invalid model config format: '{config_string}'. Expected: PROVIDER:model-name")
+            raise ConfigError(f"Invalid model config format: '{config_string}'. Expected: PROVIDER:model-name")
         parts = config_string.split(":", 1)
         if len(parts) != 2:
-            raise ValueError(f"Invalid model config format: '{config_string}'. Expected: PROVIDER:model-name")
+            raise ConfigError(f"Invalid model config format: '{config_string}'. Expected: PROVIDER:model-name")
         provider, model = parts
         provider = provider.strip().upper()
         model = model.strip()
         if not provider or not model:
-            raise ValueError("Provider and model name cannot be empty")
+            raise ConfigError("Provider and model name cannot be empty")
         valid_providers = {"ANTHROPIC", "OPENAI", "OLLAMA"}
         if provider not in valid_providers:
-            raise ValueError(f"Unknown provider: '{provider}'. Valid: {', '.join(sorted(valid_providers))}")
+            raise ConfigError(f"Unknown provider: '{provider}'. Valid: {', '.join(sorted(valid_providers))}")
         return cls(provider=provider, model=model)
 
     def __str__(self) -> str:
@@ -154,8 +161,8 @@ def load_thinking_config(debug_mode: bool = False) -> Optional[Dict[str, ModelCo
             config_string = defaults[level]
         try:
             config[level] = ModelConfig.parse(config_string)
-        except ValueError as e:
-            raise ValueError(f"Invalid configuration for {env_var}: {e}") from e
+        except ConfigError as e:
+            raise ConfigError(f"Invalid configuration for {env_var}: {e}") from e
     return config
 
 
@@ -279,23 +286,21 @@ class Embedder:
 
 class MemoryStorageClient:
     """Async HTTP client for memory server (Storage/Retrieval)."""
-    def __init__(self, server_url: str = "http://localhost:3000", api_token: Optional[str] = None, timeout: float = 10.0):
+    def __init__(self, server_url: str = "http://localhost:3000", api_token: Optional[str] = None,
+                 timeout: float = 10.0, policy: Optional[ApiPolicy] = None):
         self.server_url = server_url.rstrip("/")
-        self.api_token = api_token or os.getenv("XSWARM_API_TOKEN")
         self.timeout = timeout
-        headers = {}
-        if self.api_token:
-            headers["Authorization"] = f"Bearer {self.api_token}"
-        self.client = httpx.AsyncClient(base_url=self.server_url, headers=headers, timeout=timeout)
+        self.client = ApiClient(self.server_url, api_token, timeout, policy)
+        self.api_token = self.client.api_token
 
     async def close(self):
-        await self.client.aclose()
+        await self.client.close()
 
     async def health_check(self) -> bool:
         try:
             # Use a short timeout for health check to avoid blocking startup
-            response = await self.client.get("/health", timeout=1.0)
-            return response.status_code == 200
+            await self.client.get("/health", timeout=1.0, retry=False)
+            return True
         except Exception:
             return False
 
@@ -309,7 +314,6 @@ class MemoryStorageClient:
         }
         try:
             response = await self.client.post("/memory/store", json=payload)
-            response.raise_for_status()
             return response.json()
         except httpx.HTTPError as e:
             logger.debug(f"Error storing message: {e}")
@@ -321,7 +325,6 @@ class MemoryStorageClient:
             params["query"] = query
         try:
             response = await self.client.get("/memory/retrieve", params=params)
-            response.raise_for_status()
             return response.json().get("messages", [])
         except httpx.HTTPError as e:
             logger.debug(f"Error retrieving context: {e}")
@@ -330,7 +333,6 @@ class MemoryStorageClient:
     async def clear_history(self, user_id: str) -> bool:
         try:
             response = await self.client.delete(f"/memory/history/{user_id}")
-            response.raise_for_status()
             return True
         except httpx.HTTPError as e:
             logger.debug(f"Error clearing history: {e}")
@@ -378,6 +380,8 @@ class MemoryManager:
         logger.debug("Using local memory cache (embedded libsql)")
 
     async def store_message(self, user_id: str, message: str, role: str = "user", metadata: Optional[Dict] = None):
+        # Indexed and embedded on the server, so phone numbers and passwords read aloud are masked first
+        message = redact(message)
         if self._server_available:
             try:
                 await self.client.store_message(user_id, message, role, metadata)
@@ -896,6 +900,12 @@ class PersistentChatHistory:
         )
         return sorted_sessions[:limit]
 
+    def get_session(self, session_id: str) -> Optional[ChatSession]:
+        """A session by id: the current one, or one loaded from disk."""
+        if self.current_session and self.current_session.session_id == session_id:
+            return self.current_session
+        return self._load_session(session_id)
+
     def get_context_for_injection(self) -> str:
         """
         Get formatted context from recent sessions for memory injection.
@@ -1295,6 +1305,10 @@ class UserProfile:
         except Exception as e:
             logger.warning(f"Failed to save user profile: {e}")
 
+    def reload(self) -> None:
+        """Forget the cached facts, so changes written by another instance (memory sync) are seen."""
+        self._facts = None
+
     @property
     def facts(self) -> List[UserFact]:
         """Get all facts (loads from disk if needed)."""
@@ -1345,6 +1359,24 @@ class UserProfile:
                 return True
         return False
 
+    def remove_facts_matching(self, text: str) -> List[UserFact]:
+        """Remove every fact containing `text` (all facts if empty). Returns the removed facts."""
+        needle = text.lower().strip()
+        removed = [f for f in self.facts if needle in f.fact.lower()]
+        if removed:
+            self._facts = [f for f in self._facts if f not in removed]
+            self._save_facts()
+        return removed
+
+    def restore_facts(self, facts: List[Dict[str, Any]]) -> int:
+        """Add back previously removed facts (skipping ones present again). Returns how many."""
+        known = {f.fact.lower().strip() for f in self.facts}
+        restored = [UserFact(**f) for f in facts if f["fact"].lower().strip() not in known]
+        if restored:
+            self._facts.extend(restored)
+            self._save_facts()
+        return len(restored)
+
     def get_facts_by_category(self, category: str) -> List[UserFact]:
         """Get all facts in a category."""
         return [f for f in self.facts if f.category == category]
@@ -1513,6 +1545,8 @@ JSON response:"""
         """Call local AI (Ollama or LMStudio) for fact extraction."""
         import httpx
 
+        request = {"messages": [{"role": "user", "content": prompt}]}
+        started = time.monotonic()
         try:
             if config.local_ai_provider == "ollama":
                 # Ollama API
@@ -1528,6 +1562,8 @@ JSON response:"""
                     )
                     if response.status_code == 200:
                         data = response.json()
+                        log_ai_call("ollama", config.local_ai_model or "llama3.1:7b", "memory", request,
+                                    data.get("response", ""), data, started)
                         return data.get("response", "")
 
             elif config.local_ai_provider == "lmstudio":
@@ -1543,7 +1579,10 @@ JSON response:"""
                     )
                     if response.status_code == 200:
                         data = response.json()
-                        return data.get("choices", [{}])[0].get("message", {}).get("content", "")
+                        reply = data.get("choices", [{}])[0].get("message", {}).get("content", "")
+                        log_ai_call("lmstudio", config.local_ai_model or "local-model", "memory", request,
+                                    reply, data, started)
+                        return reply
 
         except Exception as e:
             logger.debug(f"Local AI call failed: {e}")
@@ -1595,6 +1634,7 @@ JSON response:"""
                 with open("/tmp/xswarm_debug.log", "a") as f:
                     f.write(f"DEBUG: Cloud AI request to {ANTHROPIC_API_URL}/v1/messages with model {model}\n")
 
+                started = time.monotonic()
                 response = await client.post(
                     f"{ANTHROPIC_API_URL}/v1/messages",
                     headers=headers,
@@ -1606,8 +1646,12 @@ JSON response:"""
 
                 if response.status_code == 200:
                     data = response.json()
-                    return data.get("content", [{}])[0].get("text", "")
+                    reply = data.get("content", [{}])[0].get("text", "")
+                    log_ai_call("anthropic", model, "memory", request_body, reply, data, started)
+                    return reply
                 else:
+                    log_ai_call("anthropic", model, "memory", request_body, started=started,
+                                error=f"status {response.status_code}")
                     with open("/tmp/xswarm_debug.log", "a") as f:
                         f.write(f"DEBUG: Cloud AI error: {response.text[:500]}\n")
                     logger.debug(f"Cloud AI returned status {response.status_code}: {response.text[:200]}")
@@ -1820,22 +1864,23 @@ Only include memories with relevance >= 0.6. Return [] if none are relevant.
 Be selective - only include memories that add meaningful context."""
 
         try:
+            request = {"model": self.model, "max_tokens": 1024, "messages": [{"role": "user", "content": prompt}]}
+            started = time.monotonic()
             async with httpx.AsyncClient(timeout=30.0) as client:
                 response = await client.post(
                     f"{ANTHROPIC_API_URL}/v1/messages",
                     headers=headers,
-                    json={
-                        "model": self.model,
-                        "max_tokens": 1024,
-                        "messages": [{"role": "user", "content": prompt}]
-                    }
+                    json=request
                 )
 
                 if response.status_code != 200:
+                    log_ai_call("anthropic", self.model, "memory", request, started=started,
+                                error=f"status {response.status_code}")
                     return self._heuristic_relevance(candidates, current_message)
 
                 data = response.json()
                 content = data.get("content", [{}])[0].get("text", "[]")
+                log_ai_call("anthropic", self.model, "memory", request, content, data, started)
 
                 # Parse JSON response
                 import re
@@ -1944,6 +1989,12 @@ except ImportError:
     LIBSQL_AVAILABLE = False
     libsql = None
 
+# Format changes to unified.db, oldest first (see migrations.py). The memories table itself is
+# created by _init_db, sized for the configured embedding dimension
+MEMORY_MIGRATIONS = [
+    Migration(1, "memories table with vector index", lambda conn: None),
+]
+
 
 class SemanticMemoryStore:
     """
@@ -1983,7 +2034,7 @@ class SemanticMemoryStore:
             embedding_dim: Dimension of embedding vectors
         """
         if not LIBSQL_AVAILABLE:
-            raise ImportError(
+            raise MemoryStoreError(
                 "libsql-experimental not installed. "
                 "Install with: pip install libsql-experimental"
             )
@@ -2000,12 +2051,19 @@ class SemanticMemoryStore:
         # Connection
         self._conn = None
 
-        # Initialize database
-        self._init_db()
+        # Initialize database (migration errors keep their own type)
+        try:
+            self._init_db()
+        except XswarmError:
+            raise
+        except Exception as e:
+            raise MemoryStoreError(f"Can't open the memory database {self._db_path}: {e}") from e
 
     def _init_db(self) -> None:
         """Initialize database schema with vector support."""
         self._conn = libsql.connect(str(self._db_path))
+        # Refuses a database from a newer xswarm before anything below changes it
+        self.migrated = migrate_sqlite(self._conn, self._db_path, "memory", MEMORY_MIGRATIONS)
 
         # Check if table exists and verify dimension compatibility
         existing_dim = self._get_existing_dimension()
@@ -2503,22 +2561,23 @@ Rules:
 - Consider both semantic similarity AND contextual relevance"""
 
         try:
+            request = {"model": self.model, "max_tokens": 1024, "messages": [{"role": "user", "content": prompt}]}
+            started = time.monotonic()
             async with httpx.AsyncClient(timeout=30.0) as client:
                 response = await client.post(
                     f"{ANTHROPIC_API_URL}/v1/messages",
                     headers=headers,
-                    json={
-                        "model": self.model,
-                        "max_tokens": 1024,
-                        "messages": [{"role": "user", "content": prompt}]
-                    }
+                    json=request
                 )
 
                 if response.status_code != 200:
+                    log_ai_call("anthropic", self.model, "memory", request, started=started,
+                                error=f"status {response.status_code}")
                     return self._heuristic_filter(candidates)
 
                 data = response.json()
                 content = data.get("content", [{}])[0].get("text", "[]")
+                log_ai_call("anthropic", self.model, "memory", request, content, data, started)
 
                 # Parse JSON response
                 import re
@@ -0,0 +1,219 @@
+"""
+Memory Digest - A weekly look back at what was discussed, learned and done.
+
+    "what did we discuss this week?"   -> the spoken version
+    memory_digest job (config.memory_digest_day/_time) -> a Markdown report
+
+build_digest() gathers the last DIGEST_DAYS from what is already kept:
+
+- conversations: the saved chat sessions (memory.PersistentChatHistory),
+  their summaries where a session has one, and the topics the user brought
+  up most (the words said in the most messages)
+- bookmarks made that week (bookmarks.py)
+- facts learned about the user (memory.UserProfile)
+- reminders that went out (the "reminder" events) and tasks completed
+
+Digest.markdown() is the report to review. save_digest() writes it as
+YYYY-Www.md into config.memory_digest_dir - a folder in a Markdown vault
+such as Obsidian, ~/.xswarm/digests by default - and with
+config.memory_digest_email it is emailed too. Digest.spoken() is the short
+version read out. `xswarm dev memory digest` prints it; the weekly_digest tool
+gives it to the model.
+"""
+
+import json
+import logging
+import re
+from collections import Counter
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Iterable, List, Optional
+
+from .capabilities import register_capability
+from .verbalize import join_words
+
+logger = logging.getLogger(__name__)
+
+DIGEST_DAYS = 7
+DEFAULT_DIR = Path.home() / ".xswarm" / "digests"
+CHAT_DIR = Path.home() / ".xswarm" / "chat_history"
+MAX_TOPICS = 5
+MAX_SPOKEN = 3  # Items named per part when read out
+WEEKDAYS = ("monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday")
+COMMON_WORDS = {
+    "about", "after", "again", "also", "because", "been", "before", "being", "could", "does", "doing", "done",
+    "from", "going", "have", "here", "into", "just", "know", "like", "make", "many", "more", "much", "need",
+    "okay", "only", "over", "please", "really", "should", "some", "tell", "than", "thank", "thanks", "that",
+    "their", "them", "then", "there", "these", "they", "thing", "things", "think", "this", "time", "today",
+    "tomorrow", "want", "well", "were", "what", "when", "where", "which", "will", "with", "would", "yeah",
+    "your", "yours", "can't", "don't", "what's", "it's", "i'm", "that's", "let's", "remind", "remember",
+}
+
+_REQUEST = re.compile(
+    r"^\W*(?:(?:so|and|okay|hey)\s+)?(?:"
+    r"what\s+(?:did|have)\s+we\s+(?:discuss(?:ed)?|talk(?:ed)?\s+about|cover(?:ed)?|go(?:ne)?\s+over)"
+    r"(?:\s+(?:this|last|in\s+the\s+last|over\s+the\s+last)\s+week)?"
+    r"|(?:(?:give|read|show)\s+me\s+)?(?:the\s+|my\s+|this\s+week'?s\s+)?(?:weekly|memory)\s+digest"
+    r")(?:\s+please)?\W*$",
+    re.IGNORECASE,
+)
+_WORD = re.compile(r"[a-z][a-z']{3,}")
+
+
+def is_digest_request(text: str) -> bool:
+    """True for "what did we discuss this week?" and "read me the weekly digest"."""
+    return bool(_REQUEST.match(text or ""))
+
+
+def digest_cron(day: str, at: str) -> str:
+    """The job's cron line for config.memory_digest_day ("sunday") and _time ("18:00")."""
+    name = day.strip().lower()
+    weekday = next((i for i, weekday in enumerate(WEEKDAYS) if name and weekday.startswith(name[:3])), None)
+    if weekday is None:
+        raise ValueError(f"Unknown day '{day}' (use monday ... sunday)")
+    hour, minute = at.split(":")
+    return f"{int(minute)} {int(hour)} * * {(weekday + 1) % 7}"
+
+
+def _within(stamp: Optional[str], since: datetime, until: datetime) -> bool:
+    try:
+        return bool(stamp) and since <= datetime.fromisoformat(stamp) < until
+    except ValueError:
+        return False
+
+
+def topics(messages: Iterable[str], limit: int = MAX_TOPICS) -> List[str]:
+    """The words said in the most messages (twice at least), most first."""
+    counts = Counter()
+    for text in messages:
+        counts.update({w.strip("'") for w in _WORD.findall(text.lower())} - COMMON_WORDS)
+    return [word for word, n in counts.most_common(limit) if n >= 2]
+
+
+@dataclass
+class Digest:
+    """What a week held, ready to show or say."""
+    since: datetime
+    until: datetime
+    sessions: int = 0
+    messages: int = 0
+    summaries: List[str] = field(default_factory=list)
+    topics: List[str] = field(default_factory=list)
+    bookmarks: List[str] = field(default_factory=list)
+    facts: List[str] = field(default_factory=list)
+    reminders: List[str] = field(default_factory=list)
+    completed: List[str] = field(default_factory=list)
+
+    @property
+    def week(self) -> str:
+        """The ISO week it ends in, e.g. 2026-W42 (its file name)."""
+        return f"{self.until - timedelta(seconds=1):%G-W%V}"
+
+    def is_empty(self) -> bool:
+        return not (self.messages or self.bookmarks or self.facts or self.reminders or self.completed)
+
+    def markdown(self) -> str:
+        last = self.until - timedelta(seconds=1)
+        lines = [f"# Weekly digest {self.week}", "", f"{self.since:%a %b %d} – {last:%a %b %d, %Y}", ""]
+        lines.append("## Conversations")
+        if self.messages:
+            lines.append(f"{self.sessions} conversation{'s' * (self.sessions != 1)}, {self.messages} messages.")
+            if self.topics:
+                lines.append(f"Topics: {', '.join(self.topics)}")
+            lines.extend(f"- {summary}" for summary in self.summaries)
+        else:
+            lines.append("None this week.")
+        for title, items in (("Bookmarked", [f"“{label}”" for label in self.bookmarks]),
+                             ("Learned about you", self.facts), ("Reminders", self.reminders),
+                             ("Completed", self.completed)):
+            if items:
+                lines.extend(["", f"## {title}"])
+                lines.extend(f"- {item}" for item in items)
+        return "\n".join(lines) + "\n"
+
+    def spoken(self) -> str:
+        if self.is_empty():
+            return "There's nothing to look back on from this week yet."
+        parts = []
+        if self.messages:
+            talked = f"This week we talked {self.sessions} time{'s' * (self.sessions != 1)}"
+            parts.append(talked + (f", mostly about {join_words(self.topics[:MAX_SPOKEN])}." if self.topics else "."))
+        if self.bookmarks:
+            labels = [f"'{label}'" for label in self.bookmarks[:MAX_SPOKEN]]
+            parts.append(f"You bookmarked {join_words(labels)}.")
+        if self.facts:
+            parts.append(f"I learned {len(self.facts)} new thing{'s' * (len(self.facts) != 1)} about you.")
+        if self.reminders:
+            parts.append(f"{len(self.reminders)} reminder{'s' * (len(self.reminders) != 1)} went out.")
+        if self.completed:
+            done = join_words(self.completed[:MAX_SPOKEN]) + (" and more" if len(self.completed) > MAX_SPOKEN else "")
+            parts.append(f"You finished {len(self.completed)} task{'s' * (len(self.completed) != 1)}: {done}.")
+        return " ".join(parts)
+
+
+def _conversations(chat_dir: Path, since: datetime, until: datetime, digest: Digest) -> None:
+    """Sessions and messages from the saved chat history, every persona's."""
+    said = []
+    for path in sorted(chat_dir.glob("*/session_*.json")):
+        try:
+            session = json.loads(path.read_text(encoding="utf-8"))
+        except Exception as e:
+            logger.warning(f"Failed to read {path}: {e}")
+            continue
+        messages = [m for m in session.get("messages", []) if _within(m.get("timestamp"), since, until)
+                    and m.get("role") in ("user", "assistant")]
+        if not messages:
+            continue
+        digest.sessions += 1
+        digest.messages += len(messages)
+        said.extend(m.get("content", "") for m in messages if m["role"] == "user")
+        if session.get("summary"):
+            digest.summaries.append(session["summary"])
+    digest.topics = topics(said)
+
+
+def build_digest(now: Optional[datetime] = None, days: int = DIGEST_DAYS, chat_dir: Optional[Path] = None,
+                 profile: Any = None, bookmarks: Any = None, events: Any = None, planner: Any = None) -> Digest:
+    """The digest of the `days` before `now`; each source defaults to the assistant's own."""
+    until = now or datetime.now()
+    since = until - timedelta(days=days)
+    digest = Digest(since, until)
+    _conversations(chat_dir or CHAT_DIR, since, until, digest)
+
+    if profile is None:
+        from .tools import get_user_profile
+        profile = get_user_profile()
+    digest.facts = [f.fact for f in profile.facts if _within(f.added_at, since, until)]
+
+    if bookmarks is None:
+        from .bookmarks import get_bookmark_store
+        bookmarks = get_bookmark_store()
+    digest.bookmarks = [b.label for b in bookmarks.bookmarks() if _within(b.created_at, since, until)]
+
+    if events is None:
+        from .events import get_event_store
+        events = get_event_store()
+    if events is not None:
+        digest.reminders = [e.summary for e in events.query(["reminder"], since, until)]
+
+    if planner is None:
+        from .tools import get_planner_data
+        planner = get_planner_data()
+    digest.completed = [t.title for t in planner.get_tasks(status="complete")
+                        if _within(t.completed_at, since, until)]
+    return digest
+
+
+def save_digest(digest: Digest, folder: Optional[Path] = None) -> Path:
+    """Write the report into the vault folder (replacing the week's earlier one); returns its path."""
+    folder = Path(folder or DEFAULT_DIR).expanduser()
+    folder.mkdir(parents=True, exist_ok=True)
+    path = folder / f"{digest.week}.md"
+    path.write_text(digest.markdown(), encoding="utf-8")
+    return path
+
+
+register_capability("Weekly digest", "A look back at the week: what we talked about, what I learned and what got done",
+                    ["what did we discuss this week?", "read me the weekly digest"],
+                    category="Information", requires=("memory_enabled",), keywords=("digest", "week", "summary"))
@@ -0,0 +1,392 @@
+"""
+Memory Sync - Facts, lists and meeting preferences kept the same on the user's own machines.
+
+For someone running xswarm on a desktop and a laptop, what one learns
+("I'm vegetarian", "no meetings before 10", milk on the shopping list)
+should be known to the other. The server relays the changes but can't
+read them: they're encrypted end to end with a key only those machines
+have.
+
+Joining: start pairing on a machine already syncing (ctrl+y in the
+dashboard; the first one creates the key then), and on the other run
+
+    xswarm dev sync join "xswarm://pair?host=192.168.1.20&port=8765&code=482913"
+
+The code itself never goes over the network. Over the LAN connection
+(pairing.py) the joining machine sends an X25519 public key ("sync_join")
+and the other answers with its own ("sync_challenge"). Both derive keys
+from the shared secret with HKDF-SHA256, salted with the code; the joiner
+proves it has the code with an HMAC over both public keys ("sync_confirm"),
+and only once that checks out does the other send the sync key, sealed with
+AES-256-GCM ("sync_key") - which in turn only opens with the code. A wrong
+proof uses up one of the code's tries. Someone who only watches the LAN
+learns nothing; like device pairing, do it on a network you trust, since
+the 6-digit code is all that tells the two machines apart from an impostor.
+
+Syncing (the "memory_sync" job, or `xswarm dev sync now`):
+1. Push: every fact, list item and scheduling preference that changed
+   since the last sync (or went away) becomes a change, stamped with the
+   time; they're sealed into blobs and posted to the relay
+   (POST /api/sync/blobs, only the user id, the group and the sender's
+   device id are readable).
+2. Pull: the other machines' blobs since the last one seen
+   (GET /api/sync/blobs) are opened and applied where they're newer than
+   what this machine last knew of the record - the newest change wins.
+
+Lists stop syncing in the clear (lists.sync_lists) once this is set up.
+The first sync after joining merges both machines' memories. Leaving
+(`xswarm dev sync leave`) deletes the key here; to shut out a machine for
+good, leave on every machine and join again, which makes a new key.
+
+Storage: ~/.xswarm/memory_sync.json (the key included, readable only by the user)
+"""
+
+import base64
+import hashlib
+import hmac
+import json
+import logging
+import os
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timezone
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+from urllib.parse import parse_qs, urlparse
+
+from . import endpoints
+
+logger = logging.getLogger(__name__)
+
+SYNC_PATH = Path.home() / ".xswarm" / "memory_sync.json"
+HKDF_INFO = b"xswarm memory sync v1"
+PUSH_BATCH = 200  # Changes per blob (the relay takes up to 256 KB each)
+_NONCE = 12
+
+
+class SyncError(ValueError):
+    """A key exchange or a blob that can't be used (wrong code, another group's key, damaged)."""
+
+
+def _b64(data: bytes) -> str:
+    return base64.b64encode(data).decode("ascii")
+
+
+def _unb64(text: str) -> bytes:
+    try:
+        return base64.b64decode(text, validate=True)
+    except (ValueError, TypeError):
+        raise SyncError("Not base64") from None
+
+
+def _now() -> str:
+    # UTC, like list items, so machines in different time zones order changes correctly
+    return datetime.now(timezone.utc).isoformat()
+
+
+@dataclass
+class SyncState:
+    """This machine's membership: the group key, who it is, and what it last knew of each record."""
+    group: str
+    key: bytes
+    device: str = field(default_factory=lambda: uuid.uuid4().hex[:12])
+    cursor: str = ""  # The last relay blob applied
+    known: Dict[str, Dict[str, Any]] = field(default_factory=dict)  # record -> {"hash", "at", "deleted"}
+    last_synced_at: Optional[str] = None
+
+    @classmethod
+    def create(cls) -> "SyncState":
+        return cls(uuid.uuid4().hex[:16], os.urandom(32))
+
+    @classmethod
+    def load(cls, path: Path = SYNC_PATH) -> Optional["SyncState"]:
+        """The saved state, or None when this machine isn't syncing."""
+        try:
+            data = json.loads(path.read_text(encoding="utf-8"))
+            return cls(data["group"], _unb64(data["key"]), data["device"], data.get("cursor", ""),
+                       data.get("known", {}), data.get("last_synced_at"))
+        except FileNotFoundError:
+            return None
+        except (OSError, ValueError, KeyError, SyncError) as e:
+            logger.warning(f"Failed to load memory sync state: {e}")
+            return None
+
+    def save(self, path: Path = SYNC_PATH) -> None:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        data = {**asdict(self), "key": _b64(self.key)}
+        temp = path.with_suffix(".tmp")
+        temp.write_text(json.dumps(data, indent=2), encoding="utf-8")
+        temp.chmod(0o600)
+        temp.replace(path)
+
+
+def is_enabled(path: Optional[Path] = None) -> bool:
+    return (path or SYNC_PATH).exists()
+
+
+# ------------------------------------------------------------------------------
+# Encryption
+# ------------------------------------------------------------------------------
+
+def seal(state: SyncState, payload: Dict[str, Any]) -> str:
+    """`payload` encrypted with the group key (AES-256-GCM, the group as associated data), base64."""
+    from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+    nonce = os.urandom(_NONCE)
+    return _b64(nonce + AESGCM(state.key).encrypt(nonce, json.dumps(payload).encode(), state.group.encode()))
+
+
+def unseal(state: SyncState, blob: str) -> Dict[str, Any]:
+    from cryptography.exceptions import InvalidTag
+    from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+    raw = _unb64(blob)
+    try:
+        return json.loads(AESGCM(state.key).decrypt(raw[:_NONCE], raw[_NONCE:], state.group.encode()))
+    except (InvalidTag, ValueError):
+        raise SyncError("A blob this key can't open (another group's, or damaged)") from None
+
+
+def _peer(public: str):
+    from cryptography.hazmat.primitives.asymmetric.x25519 import X25519PublicKey
+    try:
+        return X25519PublicKey.from_public_bytes(_unb64(public))
+    except ValueError:
+        raise SyncError("Not an X25519 public key") from None
+
+
+def _session_keys(private, peer_public: str, code: str) -> Tuple[bytes, bytes]:
+    """(proof key, wrapping key) from the shared secret, salted with the code."""
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.kdf.hkdf import HKDF
+    keys = HKDF(hashes.SHA256(), 64, salt=code.encode(), info=HKDF_INFO).derive(private.exchange(_peer(peer_public)))
+    return keys[:32], keys[32:]
+
+
+def _proof(proof_key: bytes, joiner_public: str, syncing_public: str) -> str:
+    """HMAC over both public keys, the joiner's first."""
+    transcript = b"sync_join" + _unb64(joiner_public) + _unb64(syncing_public)
+    return _b64(hmac.new(proof_key, transcript, hashlib.sha256).digest())
+
+
+def _public(private) -> str:
+    from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
+    return _b64(private.public_key().public_bytes(Encoding.Raw, PublicFormat.Raw))
+
+
+def _generate():
+    from cryptography.hazmat.primitives.asymmetric.x25519 import X25519PrivateKey
+    return X25519PrivateKey.generate()
+
+
+def key_request(name: str = "") -> Tuple[Any, Dict[str, Any]]:
+    """The joining machine's side: its private key and the sync_join message to send (no code in it)."""
+    private = _generate()
+    return private, {"type": "sync_join", "name": name, "public_key": _public(private)}
+
+
+def key_challenge(peer_public: str) -> Tuple[Any, Dict[str, Any]]:
+    """The syncing machine's answer to a sync_join: its private key and the sync_challenge to send."""
+    _peer(peer_public)
+    private = _generate()
+    return private, {"type": "sync_challenge", "public_key": _public(private)}
+
+
+def key_proof(private, challenge: Dict[str, Any], code: str) -> Dict[str, Any]:
+    """The joining machine's sync_confirm: proof that it has the code, for exactly these two keys."""
+    if challenge.get("type") != "sync_challenge":
+        raise SyncError(challenge.get("error") or "The other machine didn't answer the join")
+    peer_public = str(challenge.get("public_key", ""))
+    proof_key = _session_keys(private, peer_public, code)[0]
+    return {"type": "sync_confirm", "proof": _proof(proof_key, _public(private), peer_public)}
+
+
+def check_proof(private, peer_public: str, proof: str, code: str) -> bool:
+    """Whether the joiner's proof was made with `code` over its key and ours."""
+    expected = _proof(_session_keys(private, peer_public, code)[0], peer_public, _public(private))
+    return hmac.compare_digest(expected, str(proof))
+
+
+def key_offer(state: SyncState, private, peer_public: str, code: str) -> Dict[str, Any]:
+    """The sync_key for a joiner whose proof checked out: the group key, sealed for it."""
+    from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+    nonce = os.urandom(_NONCE)
+    sealed = AESGCM(_session_keys(private, peer_public, code)[1]).encrypt(nonce, state.key, state.group.encode())
+    return {"type": "sync_key", "group": state.group, "key": _b64(nonce + sealed)}
+
+
+def accept_key(private, challenge: Dict[str, Any], reply: Dict[str, Any], code: str) -> SyncState:
+    """The joining machine's new state from the sync_key reply to its proof."""
+    from cryptography.exceptions import InvalidTag
+    from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+    if reply.get("type") != "sync_key":
+        raise SyncError(reply.get("error") or "The other machine didn't send a key")
+    group, raw = str(reply.get("group", "")), _unb64(str(reply.get("key", "")))
+    try:
+        key = AESGCM(_session_keys(private, str(challenge.get("public_key", "")), code)[1]).decrypt(
+            raw[:_NONCE], raw[_NONCE:], group.encode())
+    except InvalidTag:
+        raise SyncError("The key didn't open - was the code right?") from None
+    return SyncState(group, key)
+
+
+def parse_pairing_uri(uri: str) -> Tuple[str, int, str]:
+    """(host, port, code) from xswarm://pair?host=...&port=...&code=..."""
+    query = parse_qs(urlparse(uri).query)
+    try:
+        return query["host"][0], int(query["port"][0]), query["code"][0]
+    except (KeyError, IndexError, ValueError):
+        raise SyncError("Expected xswarm://pair?host=...&port=...&code=... (shown by ctrl+y)") from None
+
+
+async def join(uri: str, name: str = "", path: Path = SYNC_PATH) -> SyncState:
+    """Get the sync key from the machine showing the pairing code, and save it here."""
+    import websockets
+    host, port, code = parse_pairing_uri(uri)
+    private, message = key_request(name)
+    async with websockets.connect(f"ws://{host}:{port}") as websocket:
+        await websocket.send(json.dumps(message))
+        challenge = json.loads(await websocket.recv())
+        await websocket.send(json.dumps(key_proof(private, challenge, code)))
+        reply = json.loads(await websocket.recv())
+    state = accept_key(private, challenge, reply, code)
+    state.save(path)
+    return state
+
+
+# ------------------------------------------------------------------------------
+# Records
+# ------------------------------------------------------------------------------
+
+def _hash(value: Optional[Dict[str, Any]]) -> str:
+    return hashlib.sha256(json.dumps(value, sort_keys=True).encode()).hexdigest()[:16]
+
+
+def _deleted(value: Optional[Dict[str, Any]]) -> bool:
+    return value is None or bool(value.get("deleted"))
+
+
+def snapshot(profile, lists, preferences) -> Dict[str, Dict[str, Any]]:
+    """Every synced record on this machine by name: "fact:<text>", "list:<id>", "preference:<id>"."""
+    records = {f"fact:{fact.fact.lower().strip()}": asdict(fact) for fact in profile.facts}
+    records.update({f"list:{raw['id']}": dict(raw) for raw in lists._load()["items"]})
+    records.update({f"preference:{c.id}": asdict(c) for c in preferences.constraints()})
+    return records
+
+
+def local_changes(state: SyncState, records: Dict[str, Dict[str, Any]], now: str) -> List[Dict[str, Any]]:
+    """Changes for the records that differ from what was last synced, and deletions of ones gone since."""
+    changes = []
+    for record, value in records.items():
+        if state.known.get(record, {}).get("hash") != _hash(value):
+            changes.append({"record": record, "value": value, "at": now})
+    for record, seen in state.known.items():
+        if record not in records and not seen.get("deleted"):
+            changes.append({"record": record, "value": None, "at": now})
+    return changes
+
+
+def apply_change(change: Dict[str, Any], profile, lists, preferences) -> None:
+    """Make a record here what another machine said it is."""
+    kind, _, name = change["record"].partition(":")
+    value = change.get("value")
+    if kind == "fact":
+        profile.remove_fact(name)
+        if not _deleted(value):
+            profile.restore_facts([value])
+    elif kind == "list" and value is not None:
+        lists.merge_remote([value], None)
+    elif kind == "preference":
+        preferences.remove(name)
+        if not _deleted(value):
+            preferences.restore([value])
+
+
+class MemorySync:
+    """One machine's side of the sync: push what changed here, then apply what changed elsewhere."""
+
+    def __init__(self, state: SyncState, client, user_id: str, profile, lists, preferences,
+                 path: Path = SYNC_PATH):
+        self.state = state
+        self.client = client
+        self.user_id = user_id
+        self.profile = profile
+        self.lists = lists
+        self.preferences = preferences
+        self.path = path
+
+    def _remember(self, change: Dict[str, Any]) -> None:
+        self.state.known[change["record"]] = {"hash": _hash(change["value"]), "at": change["at"],
+                                              "deleted": _deleted(change["value"])}
+
+    async def push(self) -> int:
+        changes = local_changes(self.state, snapshot(self.profile, self.lists, self.preferences), _now())
+        for start in range(0, len(changes), PUSH_BATCH):
+            batch = changes[start:start + PUSH_BATCH]
+            await self.client.call(endpoints.SYNC_PUSH(), json={
+                "user_id": self.user_id, "group": self.state.group, "device": self.state.device,
+                "blob": seal(self.state, {"changes": batch})})
+            for change in batch:
+                self._remember(change)
+            self.state.save(self.path)
+        # The relay has the list changes now; they don't also go to the server in the clear (lists.sync_lists)
+        self.lists.clear_pending([c["record"][5:] for c in changes if c["record"].startswith("list:")])
+        return len(changes)
+
+    async def pull(self) -> int:
+        applied, more = 0, True
+        while more:
+            params = {"user_id": self.user_id, "group": self.state.group}
+            if self.state.cursor:
+                params["since"] = self.state.cursor
+            body = (await self.client.call(endpoints.SYNC_PULL(), params=params)).json()
+            blobs, more = body.get("blobs", []), bool(body.get("more"))
+            for blob in blobs:
+                if blob.get("device") != self.state.device:
+                    try:
+                        changes = unseal(self.state, blob.get("blob", "")).get("changes", [])
+                    except SyncError as e:
+                        logger.warning(f"Skipping memory sync blob {blob.get('id')}: {e}")
+                        changes = []
+                    for change in changes:
+                        if change.get("at", "") > self.state.known.get(change.get("record"), {}).get("at", ""):
+                            apply_change(change, self.profile, self.lists, self.preferences)
+                            self._remember(change)
+                            applied += 1
+                self.state.cursor = blob.get("id", self.state.cursor)
+            self.state.save(self.path)
+            more = more and bool(blobs)
+        return applied
+
+    async def sync(self) -> Dict[str, int]:
+        """Push first, so a change made here is stamped before anything older from elsewhere is applied."""
+        # Another process here (`xswarm dev sync now` beside the dashboard) may have written since they were read
+        self.profile.reload()
+        self.preferences.reload()
+        pushed = await self.push()
+        applied = await self.pull()
+        self.state.last_synced_at = _now()
+        self.state.save(self.path)
+        return {"pushed": pushed, "applied": applied}
+
+
+async def sync_memory(config, user_id: str = "local-user", profile=None, lists=None, preferences=None,
+                      client=None, path: Path = SYNC_PATH) -> Optional[Dict[str, int]]:
+    """The memory_sync job: None when this machine isn't syncing. Errors are left to the caller."""
+    from .api_client import ApiClient, ApiPolicy
+    from .lists import get_list_store
+    from .scheduling_preferences import get_scheduling_preferences
+    from .tools import get_user_profile
+
+    state = SyncState.load(path)
+    if state is None:
+        return None
+    own_client = client is None
+    if own_client:
+        client = ApiClient(getattr(config, "server_url", "http://localhost:3000"), getattr(config, "api_token", None),
+                           policy=ApiPolicy.from_config(config))
+    try:
+        sync = MemorySync(state, client, user_id, profile or get_user_profile(), lists or get_list_store(),
+                          preferences or get_scheduling_preferences(), path)
+        return await sync.sync()
+    finally:
+        if own_client:
+            await client.close()
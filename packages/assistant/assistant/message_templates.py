@@ -0,0 +1,134 @@
+"""
+Message Templates - User wording for reminders, per channel and category.
+
+Reminder text is built-in ("⏰ Standup in 10 minutes", "Reminder: Standup in
+ten minutes.") unless config.reminder_templates says otherwise. Templates are
+Jinja, keyed by category (an event tag, or "default" for every reminder) and
+channel:
+
+    {"default": {"speech": "Heads up: {{ title }} starts {{ when }}."},
+     "work": {"desktop_title": "Work", "desktop": "{{ title }} at {{ time }}"}}
+
+Channels:
+- "activity": the activity feed line
+- "desktop_title", "desktop": the desktop notification's title and body
+- "speech": what is said out loud
+
+Variables: title, minutes (a number), when ("in ten minutes"), time
+("09:00"), category (the tag the template was picked by), tags, and the
+built-in wording as text ("Standup in 10 minutes") and spoken.
+
+The first of a reminder's tags with a template for a channel wins, then
+"default", then the built-in wording. Templates are checked when loaded
+(syntax and unknown variables, see validate); broken ones are logged and left
+out, and one that fails or renders empty when used falls back too.
+`xswarm dev reminders preview` shows every channel for a sample reminder.
+"""
+
+import logging
+from datetime import datetime, timedelta
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from jinja2 import StrictUndefined
+from jinja2.sandbox import SandboxedEnvironment
+
+logger = logging.getLogger(__name__)
+
+CHANNELS = ("activity", "desktop_title", "desktop", "speech")
+DEFAULT_CATEGORY = "default"
+
+_env = SandboxedEnvironment(undefined=StrictUndefined, autoescape=False)
+
+
+def builtin_message(channel: str, context: Dict[str, Any]) -> str:
+    """The wording used when no template applies."""
+    if channel == "activity":
+        return f"⏰ {context['text']}"
+    if channel == "desktop_title":
+        return "Upcoming event"
+    if channel == "desktop":
+        return context["text"]
+    return context["spoken"]
+
+
+def sample_context(title: str = "Standup", minutes: int = 10, category: str = "work",
+                   now: Optional[datetime] = None) -> Dict[str, Any]:
+    """A reminder's template variables, for validation and previews."""
+    from .reminders import Reminder
+
+    start = (now or datetime.now()).replace(second=0, microsecond=0) + timedelta(minutes=minutes)
+    tags = [category] if category and category != DEFAULT_CATEGORY else []
+    return Reminder(key="preview", title=title, start=start, minutes=minutes, tags=tags).context()
+
+
+class MessageTemplates:
+    """Validated reminder templates by category and channel, rendered with fallbacks."""
+
+    def __init__(self, templates: Optional[Dict[str, Dict[str, str]]] = None):
+        self.sources: Dict[str, Dict[str, str]] = {}
+        self.errors: List[Tuple[str, str, str]] = []  # (category, channel, problem)
+        self._compiled: Dict[Tuple[str, str], Any] = {}
+        for category, channels in (templates or {}).items():
+            category = category.lower().lstrip("#")
+            for channel, source in (channels or {}).items():
+                problem = self.validate(channel, source)
+                if problem:
+                    self.errors.append((category, channel, problem))
+                    logger.warning(f"Reminder template {category}.{channel} ignored: {problem}")
+                    continue
+                self.sources.setdefault(category, {})[channel] = source
+                self._compiled[(category, channel)] = _env.from_string(source)
+
+    @classmethod
+    def from_config(cls, config=None) -> "MessageTemplates":
+        return cls(getattr(config, "reminder_templates", None) or {})
+
+    @staticmethod
+    def validate(channel: str, source: Any) -> Optional[str]:
+        """Why a template can't be used, or None: unknown channel, bad syntax, unknown variable, empty output."""
+        if channel not in CHANNELS:
+            return f"unknown channel '{channel}' (use {', '.join(CHANNELS)})"
+        if not isinstance(source, str):
+            return "not text"
+        try:
+            rendered = _env.from_string(source).render(sample_context())
+        except Exception as e:  # Also runtime errors, e.g. {{ minutes + title }}
+            return str(e)
+        if not rendered.strip():
+            return "renders as nothing"
+        return None
+
+    def pick(self, channel: str, tags: Iterable[str] = ()) -> Optional[str]:
+        """The category whose template applies to a channel for these tags, or None for the built-in."""
+        for category in [*(tag.lower() for tag in tags), DEFAULT_CATEGORY]:
+            if (category, channel) in self._compiled:
+                return category
+        return None
+
+    def render(self, channel: str, context: Dict[str, Any]) -> str:
+        """A channel's text for a reminder's context (see Reminder.context), falling back to the built-in."""
+        category = self.pick(channel, context.get("tags", ()))
+        if category is not None:
+            try:
+                text = self._compiled[(category, channel)].render({**context, "category": category}).strip()
+                if text:
+                    return text
+            except Exception as e:
+                logger.debug(f"Reminder template {category}.{channel} failed, using the built-in: {e}")
+        return builtin_message(channel, context)
+
+
+_templates: Optional[MessageTemplates] = None
+
+
+def get_message_templates() -> MessageTemplates:
+    """The configured templates; none (every channel built-in) until set_message_templates."""
+    global _templates
+    if _templates is None:
+        _templates = MessageTemplates()
+    return _templates
+
+
+def set_message_templates(templates: Optional[MessageTemplates]) -> None:
+    global _templates
+    _templates = templates
@@ -0,0 +1,144 @@
+"""
+Migrations - Upgrading stored data when its format changes.
+
+Each store keeps the version of the format it was written in:
+
+- SQLite stores (events.db, calendar_sync/mirror.db, memory's unified.db): PRAGMA user_version
+- JSON stores (planner.json): a top-level "schema_version"
+
+and lists its Migrations in order, each bringing the data up to its
+`version`. When a store opens, the migrations newer than its data run
+one at a time, recording the new version after each. Data written before
+this framework is version 0, so every store's migration 1 is its
+baseline format.
+
+Before anything runs the file is copied next to itself
+("events.db.v1-20261016-0930.bak"), so a failed migration can be undone
+by hand. Data with a newer version than this xswarm knows is never opened
+(SchemaTooNew) - an older release would otherwise rewrite it in the old
+format. migrate_stores() upgrades everything at startup, before the
+dashboard opens.
+"""
+
+import logging
+import shutil
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional, Sequence, Tuple, Union
+
+from .errors import XswarmError
+
+logger = logging.getLogger(__name__)
+
+
+class SchemaTooNew(XswarmError):
+    """Stored data written by a newer xswarm; opening it here could lose data."""
+
+
+class MigrationFailed(XswarmError):
+    """A migration raised; the data is as the last successful step left it (and backed up before)."""
+
+
+@dataclass(frozen=True)
+class Migration:
+    version: int  # Format version once this has run
+    description: str
+    apply: Callable[[Any], Any]  # SQLite: gets the connection. JSON: changes the data, or returns a new dict
+
+
+def latest_version(migrations: Sequence[Migration]) -> int:
+    return migrations[-1].version if migrations else 0
+
+
+def _pending(store: str, current: int, migrations: Sequence[Migration]) -> List[Migration]:
+    latest = latest_version(migrations)
+    if current > latest:
+        raise SchemaTooNew(f"{store} data is format {current}, but this xswarm only knows up to {latest} - update xswarm")
+    return [m for m in migrations if m.version > current]
+
+
+def backup_file(path: Union[Path, str], version: int, now: Optional[datetime] = None) -> Optional[Path]:
+    """Copy `path` to "<name>.v<version>-<time>.bak" beside it; None when there's nothing to copy."""
+    path = Path(path)
+    if not path.is_file():
+        return None
+    copy = path.with_name(f"{path.name}.v{version}-{(now or datetime.now()):%Y%m%d-%H%M%S}.bak")
+    shutil.copy2(path, copy)
+    logger.info(f"Backed up {path} to {copy} before migrating")
+    return copy
+
+
+def migrate_sqlite(conn, path: Union[Path, str, None], store: str, migrations: Sequence[Migration]) -> List[Migration]:
+    """Bring an open SQLite database up to date; returns the migrations that ran."""
+    current = conn.execute("PRAGMA user_version").fetchone()[0]
+    pending = _pending(store, current, migrations)
+    if not pending:
+        return []
+    has_data = conn.execute("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'").fetchone()[0] > 0
+    backup = backup_file(path, current) if has_data and path and str(path) != ":memory:" else None
+    for migration in pending:
+        try:
+            migration.apply(conn)
+            conn.execute(f"PRAGMA user_version = {int(migration.version)}")
+            conn.commit()
+        except Exception as e:
+            _rollback(conn)
+            raise MigrationFailed(_failure(store, migration, e, backup)) from e
+        logger.info(f"Migrated {store} to format {migration.version}: {migration.description}")
+    return pending
+
+
+def _rollback(conn) -> None:
+    try:
+        conn.rollback()
+    except Exception:
+        pass
+
+
+def migrate_json(data: Dict[str, Any], path: Union[Path, str, None], store: str,
+                 migrations: Sequence[Migration]) -> Tuple[Dict[str, Any], List[Migration]]:
+    """Bring loaded JSON data up to date; returns (data, migrations that ran). The caller saves it."""
+    current = int(data.get("schema_version", 0) or 0)
+    pending = _pending(store, current, migrations)
+    backup = backup_file(path, current) if pending and path else None
+    for migration in pending:
+        try:
+            result = migration.apply(data)
+        except Exception as e:
+            raise MigrationFailed(_failure(store, migration, e, backup)) from e
+        data = result if isinstance(result, dict) else data
+        data["schema_version"] = migration.version
+        logger.info(f"Migrated {store} to format {migration.version}: {migration.description}")
+    return data, pending
+
+
+def _failure(store: str, migration: Migration, error: Exception, backup: Optional[Path]) -> str:
+    message = f"Migrating {store} to format {migration.version} ({migration.description}) failed: {error}"
+    return message + (f" - the previous data is in {backup}" if backup else "")
+
+
+def migrate_stores(config) -> List[str]:
+    """
+    Open each store so it migrates before the dashboard uses it; returns
+    what was upgraded ("events: format 1"). Raises SchemaTooNew for data
+    from a newer xswarm and MigrationFailed when a migration breaks.
+    """
+    from .calendar_mirror import CalendarMirror
+    from .events import EventStore
+    from .planner import PlannerData
+
+    upgraded = []
+    events = EventStore.from_config(config)
+    if events.migrated:
+        upgraded.append(f"events: format {events.migrated[-1].version}")
+    events.close()
+    mirror = CalendarMirror()
+    if mirror.migrated:
+        upgraded.append(f"calendar mirror: format {mirror.migrated[-1].version}")
+    mirror.close()
+    planner = PlannerData()
+    planner.reload()
+    if planner.migrated:
+        upgraded.append(f"planner: format {planner.migrated[-1].version}")
+    return upgraded
@@ -0,0 +1,119 @@
+"""
+Model Loading - Background Moshi startup with progress reporting.
+
+The voice server process (voice_server.py) downloads and loads the Moshi
+weights on its own; it reports each stage on its status queue as
+("progress", stage) and signals "ready" on server_to_client when done.
+
+wait_for_server() polls both queues without blocking the event loop, so
+the TUI, chat, calendar and memory are usable while models load, and
+turns the stages into LoadProgress updates for the footer progress bar.
+The server also reports ("memory", component, ram_bytes, gpu_bytes) once
+the LM is loaded, which goes into the startup MemoryReport.
+"""
+
+import asyncio
+import queue
+import time
+from dataclasses import dataclass
+from typing import Callable, Optional
+
+from .model_memory import MemoryReport
+
+# Stage reported by the voice server -> (fraction done when the stage starts, label)
+STAGES = {
+    "starting": (0.0, "Starting voice server"),
+    "download": (0.05, "Downloading model"),
+    "tokenizer": (0.55, "Loading tokenizer"),
+    "model": (0.6, "Building model"),
+    "weights": (0.7, "Loading weights"),
+    "warmup": (0.9, "Warming up"),
+    "ready": (1.0, "Voice ready"),
+}
+
+# First launch downloads several GB, so allow far longer than a warm start needs
+LOAD_TIMEOUT = 30 * 60.0
+POLL_INTERVAL = 0.1
+
+
+@dataclass
+class LoadProgress:
+    """Where model loading is, for the footer progress bar."""
+    stage: str = "starting"
+    fraction: float = 0.0
+    label: str = STAGES["starting"][1]
+    error: Optional[str] = None
+
+    @classmethod
+    def for_stage(cls, stage: str) -> "LoadProgress":
+        fraction, label = STAGES.get(stage, (0.0, stage))
+        return cls(stage=stage, fraction=fraction, label=label)
+
+    @property
+    def done(self) -> bool:
+        return self.stage == "ready"
+
+
+def render_bar(fraction: float, width: int = 10) -> str:
+    """Text progress bar, e.g. ▰▰▰▱▱▱▱▱▱▱ for 0.3."""
+    filled = round(max(0.0, min(1.0, fraction)) * width)
+    return "▰" * filled + "▱" * (width - filled)
+
+
+async def wait_for_server(
+    server_to_client,
+    status_queue,
+    on_progress: Optional[Callable[[LoadProgress], None]] = None,
+    is_alive: Optional[Callable[[], bool]] = None,
+    timeout: float = LOAD_TIMEOUT,
+    log: Optional[Callable[[str], None]] = None,
+    memory_report: Optional[MemoryReport] = None,
+) -> bool:
+    """
+    Wait for the voice server's "ready" signal, reporting progress as it loads.
+
+    Returns False if the server reports an error, exits, or times out.
+    """
+    def report(progress: LoadProgress) -> None:
+        if on_progress:
+            on_progress(progress)
+
+    report(LoadProgress())
+    deadline = time.monotonic() + timeout
+
+    while time.monotonic() < deadline:
+        # Read the ready signal first, so status sent just before it (the memory report) is drained below
+        try:
+            msg = server_to_client.get_nowait()
+        except queue.Empty:
+            msg = None
+
+        # Stage updates and errors
+        while status_queue is not None:
+            try:
+                kind, *payload = status_queue.get_nowait()
+            except queue.Empty:
+                break
+            if kind == "progress" and payload:
+                report(LoadProgress.for_stage(payload[0]))
+            elif kind == "memory" and memory_report is not None and len(payload) == 3:
+                memory_report.record(*payload)
+            elif kind == "error":
+                error = str(payload[0]) if payload else "unknown error"
+                report(LoadProgress(stage="error", fraction=0.0, label="Voice failed", error=error))
+                return False
+
+        if msg == "ready":
+            report(LoadProgress.for_stage("ready"))
+            return True
+        if msg is not None and log:
+            log(f"⚠️ Unexpected message from voice server while loading: {msg}")
+
+        if is_alive is not None and not is_alive():
+            report(LoadProgress(stage="error", label="Voice failed", error="voice server exited"))
+            return False
+
+        await asyncio.sleep(POLL_INTERVAL)
+
+    report(LoadProgress(stage="error", label="Voice failed", error="timed out loading models"))
+    return False
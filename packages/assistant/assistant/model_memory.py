@@ -0,0 +1,100 @@
+"""
+Model Memory - Voice model variants, memory caps and the startup report.
+
+Voice loads three models: the Moshi LM (in the voice server process), the
+Mimi audio codec and the Vosk transcriber (both in the app process). The
+LM dominates, and comes in three variants:
+
+    bf16  ~15.5GB   q8  ~8.3GB   q4  ~4.6GB
+
+With `voice_model_memory_gb` set (e.g. 6.0 = "max 6GB for voice models"),
+select_voice_variant() steps down from the requested variant until the
+whole voice stack fits the cap, or returns None if even q4 doesn't.
+
+While loading, each component reports what it actually uses (RSS and, for
+MLX, GPU memory) into a MemoryReport, logged once voice is ready.
+"""
+
+import os
+from dataclasses import dataclass
+from typing import List, Optional
+
+# Estimated resident size of each Moshi LM variant, largest (best quality) first
+MOSHI_VARIANTS = {
+    "bf16": 15.5,
+    "q8": 8.3,
+    "q4": 4.6,
+}
+# Models loaded alongside the LM regardless of variant
+CODEC_GB = 0.4        # Mimi (rustymimi)
+TRANSCRIBER_GB = 0.3  # Vosk small English
+
+GB = 1024 ** 3
+
+
+def estimated_voice_gb(variant: str) -> float:
+    """Estimated memory for the whole voice stack with this LM variant."""
+    return MOSHI_VARIANTS[variant] + CODEC_GB + TRANSCRIBER_GB
+
+
+def select_voice_variant(requested: str, cap_gb: Optional[float] = None) -> Optional[str]:
+    """
+    The best variant no larger than `requested` that fits `cap_gb`.
+
+    Unknown requests (e.g. "auto") start from q4, the default. Returns None
+    when nothing fits - voice should then run in the cloud or not at all.
+    """
+    variants = list(MOSHI_VARIANTS)
+    start = variants.index(requested) if requested in MOSHI_VARIANTS else variants.index("q4")
+    for variant in variants[start:]:
+        if cap_gb is None or estimated_voice_gb(variant) <= cap_gb:
+            return variant
+    return None
+
+
+def process_rss() -> int:
+    """Resident memory of this process in bytes (0 if it can't be measured)."""
+    try:
+        import psutil
+        return psutil.Process(os.getpid()).memory_info().rss
+    except Exception:
+        return 0
+
+
+@dataclass
+class ComponentMemory:
+    name: str
+    ram_bytes: int = 0
+    gpu_bytes: int = 0
+
+
+class MemoryReport:
+    """Per-component RAM/VRAM used by the voice models, for the startup log."""
+
+    def __init__(self, cap_gb: Optional[float] = None):
+        self.cap_gb = cap_gb
+        self.components: List[ComponentMemory] = []
+
+    def record(self, name: str, ram_bytes: int = 0, gpu_bytes: int = 0) -> None:
+        self.components.append(ComponentMemory(name, max(0, int(ram_bytes)), max(0, int(gpu_bytes))))
+
+    @property
+    def total_bytes(self) -> int:
+        return sum(c.ram_bytes + c.gpu_bytes for c in self.components)
+
+    @property
+    def over_cap(self) -> bool:
+        return self.cap_gb is not None and self.total_bytes > self.cap_gb * GB
+
+    def lines(self) -> List[str]:
+        lines = []
+        for c in self.components:
+            parts = [f"{c.ram_bytes / GB:.1f}GB RAM"]
+            if c.gpu_bytes:
+                parts.append(f"{c.gpu_bytes / GB:.1f}GB GPU")
+            lines.append(f"   {c.name}: {', '.join(parts)}")
+        total = f"   Total: {self.total_bytes / GB:.1f}GB"
+        if self.cap_gb is not None:
+            total += f" (cap {self.cap_gb:g}GB{', OVER' if self.over_cap else ''})"
+        lines.append(total)
+        return lines
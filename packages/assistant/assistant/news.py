@@ -0,0 +1,452 @@
+"""
+News - RSS/Atom feeds read out in the briefing.
+
+    xswarm dev news add https://blog.rust-lang.org/feed.xml --name "the Rust blog"
+    xswarm dev news add https://example.com/atom.xml --schedule "0 6 * * *"
+    xswarm dev news list / fetch / unread / remove ID
+
+Each feed has its own schedule ("HH:MM" or cron, as for announcements;
+default hourly). The dashboard's news job (every minute) fetches the feeds
+that are due, using ETag/Last-Modified so unchanged feeds cost nothing, and
+gives each new item a one-sentence summary from the AI client (voice.AIClient
+or anything with async chat(messages, max_tokens) and is_available()).
+Without one, the summary is the start of the item's own description.
+
+The briefing ({news} and {briefing} in announcements, and the wake-up
+alarm's briefing) includes the newest unread items, grouped by feed:
+"Three new items from the Rust blog. ...". Items are marked read once
+they've been in a briefing (or read by the read_news tool), so nothing is
+repeated. A newly added feed only brings in its latest few items as
+unread, not its whole backlog.
+
+Storage: ~/.xswarm/news/news.json
+"""
+
+import hashlib
+import json
+import logging
+import re
+import uuid
+import xml.etree.ElementTree as ET
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timedelta
+from email.utils import parsedate_to_datetime
+from html import unescape
+from pathlib import Path
+from typing import Dict, List, Optional, Tuple
+
+from .announcements import AnnouncementError, parse_schedule
+from .capabilities import register_capability
+from .verbalize import number_words
+
+logger = logging.getLogger(__name__)
+
+DEFAULT_SCHEDULE = "@hourly"
+BRIEFING_ITEMS = 3  # Items in the briefing, across all feeds
+FIRST_FETCH_UNREAD = 3  # A new feed's latest items left unread; older ones count as read
+MAX_SUMMARIES = 10  # AI summaries per fetch run; the rest use their description
+KEEP_DAYS = 30  # Read items older than this are forgotten
+SUMMARY_WORDS = 30
+USER_AGENT = "xswarm-assistant (https://xswarm.ai)"
+
+
+class NewsError(ValueError):
+    """A feed URL, schedule or id that doesn't work."""
+
+
+@dataclass
+class Feed:
+    url: str
+    name: str = ""  # Said in the briefing ("the Rust blog"); the feed's own title if empty
+    schedule: str = DEFAULT_SCHEDULE
+    id: str = field(default_factory=lambda: uuid.uuid4().hex[:8])
+    last_fetched: Optional[str] = None
+    etag: Optional[str] = None
+    modified: Optional[str] = None
+    error: Optional[str] = None  # Why the last fetch failed
+
+    def next_fetch(self) -> datetime:
+        """When it's next due; a feed never fetched is due now."""
+        if not self.last_fetched:
+            return datetime.min
+        return parse_schedule(self.schedule).next_after(datetime.fromisoformat(self.last_fetched))
+
+
+@dataclass
+class NewsItem:
+    feed_id: str
+    title: str
+    link: str = ""
+    published: str = ""  # ISO, or "" when the feed doesn't say
+    description: str = ""
+    summary: str = ""
+    read: bool = False
+    id: str = ""
+    fetched_at: str = field(default_factory=lambda: datetime.now().replace(microsecond=0).isoformat())
+
+    @property
+    def spoken(self) -> str:
+        """What the briefing says for it: the summary, else the title."""
+        text = self.summary or self.title
+        return text if text.endswith((".", "!", "?")) else text + "."
+
+
+# ==============================================================================
+# PARSING
+# ==============================================================================
+
+def _local(tag: str) -> str:
+    return tag.rsplit("}", 1)[-1].lower()
+
+
+def _child(element, *names: str):
+    for child in element:
+        if _local(child.tag) in names:
+            return child
+    return None
+
+
+def _text(element, *names: str) -> str:
+    child = _child(element, *names)
+    return (child.text or "").strip() if child is not None else ""
+
+
+def plain_text(html: str) -> str:
+    """Description HTML as plain text."""
+    text = re.sub(r"<(script|style)\b.*?</\1>", " ", html or "", flags=re.S | re.I)
+    return " ".join(unescape(re.sub(r"<[^>]+>", " ", text)).split())
+
+
+def _iso(value: str) -> str:
+    """RSS (RFC 822) or Atom (ISO 8601) dates as naive local ISO; "" if unreadable."""
+    if not value:
+        return ""
+    try:
+        moment = parsedate_to_datetime(value)
+    except (TypeError, ValueError):
+        try:
+            moment = datetime.fromisoformat(value.strip().replace("Z", "+00:00"))
+        except ValueError:
+            return ""
+    if moment.tzinfo is not None:
+        moment = moment.astimezone().replace(tzinfo=None)
+    return moment.replace(microsecond=0).isoformat()
+
+
+def parse_feed(xml_text: str) -> Tuple[str, List[Dict[str, str]]]:
+    """(feed title, entries) from RSS 2.0, RSS 1.0 or Atom; entries are newest first as the feed lists them."""
+    try:
+        root = ET.fromstring(xml_text.encode("utf-8") if isinstance(xml_text, str) else xml_text)
+    except ET.ParseError as e:
+        raise NewsError(f"Not an RSS or Atom feed ({e})")
+    kind = _local(root.tag)
+    if kind == "feed":  # Atom
+        channel, entries = root, [e for e in root if _local(e.tag) == "entry"]
+    elif kind in ("rss", "rdf"):
+        channel = _child(root, "channel")
+        if channel is None:
+            raise NewsError("RSS feed without a channel")
+        entries = [e for e in (channel if kind == "rss" else root) if _local(e.tag) == "item"]
+    else:
+        raise NewsError(f"Not an RSS or Atom feed (root element <{kind}>)")
+
+    parsed = []
+    for entry in entries:
+        link = _text(entry, "link")
+        if not link:  # Atom: <link rel="alternate" href="..."/>
+            links = [e for e in entry if _local(e.tag) == "link"]
+            alternate = [e for e in links if e.get("rel", "alternate") == "alternate"] or links
+            link = alternate[0].get("href", "") if alternate else ""
+        parsed.append({
+            "guid": _text(entry, "guid", "id") or link,
+            "title": plain_text(_text(entry, "title")),
+            "link": link,
+            "published": _iso(_text(entry, "pubdate", "published", "updated", "date")),
+            "description": plain_text(_text(entry, "description", "summary", "content", "encoded")),
+        })
+    return plain_text(_text(channel, "title")), [e for e in parsed if e["title"] or e["description"]]
+
+
+def _item_id(feed_id: str, guid: str) -> str:
+    return hashlib.sha1(f"{feed_id}:{guid}".encode("utf-8")).hexdigest()[:16]
+
+
+def first_sentence(text: str, words: int = SUMMARY_WORDS) -> str:
+    sentence = re.split(r"(?<=[.!?])\s", text.strip(), maxsplit=1)[0]
+    cut = sentence.split()
+    return " ".join(cut[:words]) + ("…" if len(cut) > words else "")
+
+
+# ==============================================================================
+# STORE
+# ==============================================================================
+
+class NewsStore:
+    """Feeds and their items, in one JSON file (the CLI edits it while the dashboard runs)."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "news"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "news.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+        path = self._path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load news: {e}")
+        self._data = {"feeds": [], "items": []}
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        try:
+            with open(self._path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save news: {e}")
+
+    def reload(self) -> None:
+        self._data = None
+        self._load()
+
+    # Feeds
+
+    def feeds(self) -> List[Feed]:
+        return [Feed(**raw) for raw in self._load()["feeds"]]
+
+    def get_feed(self, key: str) -> Optional[Feed]:
+        """A feed by id, name ("Rust blog" finds "the Rust blog") or URL."""
+        bare = lambda text: re.sub(r"^the\s+", "", text.strip().lower())
+        for feed in self.feeds():
+            if key.strip() == feed.id or bare(key) in (bare(feed.name), feed.url.lower()):
+                return feed
+        return None
+
+    def add_feed(self, url: str, name: str = "", schedule: str = DEFAULT_SCHEDULE) -> Feed:
+        url = url.strip()
+        if not re.match(r"^https?://\S+$", url):
+            raise NewsError(f"Not a feed URL: {url!r}")
+        if any(feed.url == url for feed in self.feeds()):
+            raise NewsError(f"Already following {url}")
+        try:
+            parse_schedule(schedule)
+        except AnnouncementError as e:
+            raise NewsError(str(e))
+        feed = Feed(url, name.strip(), schedule.strip())
+        self._load()["feeds"].append(asdict(feed))
+        self._save()
+        return feed
+
+    def remove_feed(self, key: str) -> Feed:
+        feed = self.get_feed(key)
+        if feed is None:
+            raise NewsError(f"No feed {key!r} (see `xswarm dev news list`)")
+        data = self._load()
+        data["feeds"] = [raw for raw in data["feeds"] if raw["id"] != feed.id]
+        data["items"] = [raw for raw in data["items"] if raw["feed_id"] != feed.id]
+        self._save()
+        return feed
+
+    def update_feed(self, feed: Feed) -> None:
+        data = self._load()
+        data["feeds"] = [asdict(feed) if raw["id"] == feed.id else raw for raw in data["feeds"]]
+        self._save()
+
+    def due(self, now: datetime) -> List[Feed]:
+        """Feeds whose next scheduled fetch has come (new feeds straight away)."""
+        return [feed for feed in self.feeds() if feed.next_fetch() <= now]
+
+    # Items
+
+    def items(self, feed_id: Optional[str] = None) -> List[NewsItem]:
+        return [NewsItem(**raw) for raw in self._load()["items"] if feed_id in (None, raw["feed_id"])]
+
+    def unread(self, feed_id: Optional[str] = None) -> List[NewsItem]:
+        """Unread items, newest first."""
+        items = [item for item in self.items(feed_id) if not item.read]
+        return sorted(items, key=lambda item: item.published or item.fetched_at, reverse=True)
+
+    def add_entries(self, feed: Feed, entries: List[Dict[str, str]], now: datetime) -> List[NewsItem]:
+        """Store entries not seen before; returns the new ones. A feed's first fetch leaves only its latest unread."""
+        data = self._load()
+        known = {raw["id"] for raw in data["items"]}
+        first_fetch = not any(raw["feed_id"] == feed.id for raw in data["items"])
+        if all(entry["published"] for entry in entries):
+            entries = sorted(entries, key=lambda entry: entry["published"], reverse=True)
+        new = []
+        for index, entry in enumerate(entries):
+            item_id = _item_id(feed.id, entry["guid"] or entry["title"])
+            if item_id in known:
+                continue
+            known.add(item_id)
+            item = NewsItem(feed.id, entry["title"], entry["link"], entry["published"], entry["description"][:2000],
+                            read=first_fetch and index >= FIRST_FETCH_UNREAD, id=item_id,
+                            fetched_at=now.replace(microsecond=0).isoformat())
+            data["items"].append(asdict(item))
+            if not item.read:
+                new.append(item)
+        self._prune(now)
+        self._save()
+        return new
+
+    def set_summary(self, item_id: str, summary: str) -> None:
+        for raw in self._load()["items"]:
+            if raw["id"] == item_id:
+                raw["summary"] = summary
+        self._save()
+
+    def mark_read(self, item_ids: List[str]) -> None:
+        ids = set(item_ids)
+        for raw in self._load()["items"]:
+            if raw["id"] in ids:
+                raw["read"] = True
+        self._save()
+
+    def _prune(self, now: datetime) -> None:
+        # Read items are kept a while so a feed re-listing them doesn't bring them back
+        cutoff = (now - timedelta(days=KEEP_DAYS)).isoformat()
+        data = self._load()
+        data["items"] = [raw for raw in data["items"] if not raw["read"] or raw["fetched_at"] >= cutoff]
+
+
+# ==============================================================================
+# FETCHING
+# ==============================================================================
+
+class NewsReader:
+    """Fetches due feeds and summarizes what's new."""
+
+    def __init__(self, store: Optional[NewsStore] = None, ai=None, timeout: float = 15.0):
+        self.store = store or get_news_store()
+        self.ai = ai
+        self.timeout = timeout
+
+    async def fetch(self, feed: Feed, now: datetime) -> List[NewsItem]:
+        """Fetch one feed (conditionally) and store its new items. Failures are kept on the feed and raised."""
+        import httpx
+
+        headers = {"User-Agent": USER_AGENT}
+        if feed.etag:
+            headers["If-None-Match"] = feed.etag
+        if feed.modified:
+            headers["If-Modified-Since"] = feed.modified
+        feed.last_fetched = now.replace(second=0, microsecond=0).isoformat()
+        try:
+            async with httpx.AsyncClient(timeout=self.timeout, headers=headers, follow_redirects=True) as client:
+                response = await client.get(feed.url)
+            if response.status_code == 304:
+                feed.error = None
+                return []
+            response.raise_for_status()
+            title, entries = parse_feed(response.text)
+        except Exception as e:
+            feed.error = str(e)[:200]
+            raise
+        finally:
+            self.store.update_feed(feed)
+        feed.etag = response.headers.get("etag")
+        feed.modified = response.headers.get("last-modified")
+        feed.error = None
+        if not feed.name and title:
+            feed.name = title
+        self.store.update_feed(feed)
+        return self.store.add_entries(feed, entries, now)
+
+    async def fetch_due(self, now: datetime) -> List[NewsItem]:
+        """The news job: fetch every due feed, then summarize the new items."""
+        new, failed = [], []
+        self.store.reload()  # `xswarm dev news` edits the file
+        for feed in self.store.due(now):
+            try:
+                new += await self.fetch(feed, now)
+            except Exception as e:
+                logger.debug(f"Feed {feed.url} failed: {e}")
+                failed.append(feed.name or feed.url)
+        await self.summarize(new)
+        if failed and not new:
+            raise NewsError(f"Couldn't fetch {', '.join(failed)}")
+        return new
+
+    async def summarize(self, items: List[NewsItem]) -> None:
+        """A one-sentence spoken summary for each item (the AI's, or the description's first sentence)."""
+        for index, item in enumerate(items):
+            summary = ""
+            if index < MAX_SUMMARIES and item.description and self.ai is not None and self.ai.is_available():
+                messages = [
+                    {"role": "system", "content": (
+                        f"Summarize this news item in one plain sentence of at most {SUMMARY_WORDS} words, to be "
+                        "read aloud. No preamble, no links, no markdown.")},
+                    {"role": "user", "content": f"{item.title}\n\n{item.description[:1500]}"},
+                ]
+                try:
+                    summary = " ".join((await self.ai.chat(messages, max_tokens=120)).split())
+                except Exception as e:
+                    logger.debug(f"Summary failed for {item.link}: {e}")
+            if not summary and item.description:
+                summary = first_sentence(item.description)
+            if summary:
+                item.summary = summary
+                self.store.set_summary(item.id, summary)
+
+
+# ==============================================================================
+# BRIEFING
+# ==============================================================================
+
+def news_briefing(store: Optional[NewsStore] = None, limit: int = BRIEFING_ITEMS, mark_read: bool = True,
+                  feed_id: Optional[str] = None) -> str:
+    """
+    The newest unread items as sentences, grouped by feed: "Three new items
+    from the Rust blog. ... One new item from Hacker News. ...". "" when
+    there's nothing new. The items are marked read.
+    """
+    store = store or get_news_store()
+    items = store.unread(feed_id)[:limit]
+    if not items:
+        return ""
+    names = {feed.id: feed.name or feed.url for feed in store.feeds()}
+    by_feed: Dict[str, List[NewsItem]] = {}
+    for item in items:
+        by_feed.setdefault(item.feed_id, []).append(item)
+    sentences = []
+    for feed_id, feed_items in by_feed.items():
+        count = len(feed_items)
+        sentences.append(f"{number_words(count).capitalize()} new item{'s' if count != 1 else ''} "
+                         f"from {names.get(feed_id, 'a feed')}.")
+        sentences += [item.spoken for item in feed_items]
+    if mark_read:
+        store.mark_read([item.id for item in items])
+    return " ".join(sentences)
+
+
+_store: Optional[NewsStore] = None
+
+
+def get_news_store() -> NewsStore:
+    """Get the global news store."""
+    global _store
+    if _store is None:
+        _store = NewsStore()
+    return _store
+
+
+def set_news_store(store: Optional[NewsStore]) -> None:
+    global _store
+    _store = store
+
+
+register_capability("News", "New items from the RSS and Atom feeds you follow",
+                    ["read me the news", "anything new from the Rust blog?"], category="Information",
+                    keywords=("feeds", "rss", "headlines"))
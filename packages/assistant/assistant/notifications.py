@@ -0,0 +1,366 @@
+"""
+Notification Policy - One place to decide whether the assistant may interrupt.
+
+Every user-facing notification goes through NotificationPolicy.check() with a
+channel and a priority:
+
+- speech:      the assistant speaking unprompted
+- desktop:     OS-level desktop notifications
+- suggestions: proactive context/suggestions from background thinking
+- sms:         text messages to the user
+- call:        outbound phone calls to the user
+- companion:   pushes to paired phone/web clients (see pairing.py)
+- push:        ntfy/Pushover pushes for selected events (see push.py)
+
+During quiet hours a notification is delivered only if its priority meets the
+channel's threshold (config.quiet_hours_channels, default "emergency").
+Do not disturb (config.do_not_disturb) applies the same thresholds until
+it is turned off.
+
+Notifications about tagged items (see categories.py) also honor
+config.category_notifications: a category can be muted, kept off weekends,
+or limited to hours like "08:00-18:00".
+
+While a calendar event shown as busy is under way (config.meeting_quiet,
+on by default), the assistant doesn't talk over it: spoken announcements
+below high priority are held until the meeting ends (PolicyDecision.defer,
+replayed by the dashboard), and high-priority ones are whispered
+(PolicyDecision.whisper) at config.whisper_level of the speech volume.
+
+EMERGENCY always bypasses quiet hours, category preferences and meetings.
+
+Before the policy is asked, desktop notifications and spoken announcements
+go through the hooks added with add_notification_hook(), which can reword
+or drop them (user scripts, see scripting.py). Emergencies skip them.
+
+A notification identical to one sent less than a minute before (same
+channel, title and message) isn't delivered again; the next one after a
+quiet spell mentions the repeats held back (see coalesce.py).
+"""
+
+import logging
+import platform
+import shutil
+import subprocess
+import threading
+from dataclasses import dataclass, replace
+from datetime import datetime, time, timedelta
+from enum import Enum
+from typing import Callable, Iterable, List, Optional, Tuple
+
+from .coalesce import Coalescer
+
+logger = logging.getLogger(__name__)
+
+
+class NotificationPriority(str, Enum):
+    LOW = "low"
+    NORMAL = "normal"
+    HIGH = "high"
+    EMERGENCY = "emergency"
+
+
+PRIORITY_ORDER = [NotificationPriority.LOW, NotificationPriority.NORMAL, NotificationPriority.HIGH, NotificationPriority.EMERGENCY]
+
+CHANNELS = ("speech", "desktop", "suggestions", "sms", "call", "companion", "push")
+MEETING_CHANNELS = ("speech",)  # Channels held (or whispered) during busy calendar events
+
+
+@dataclass
+class PolicyDecision:
+    """Result of a policy check."""
+    allowed: bool
+    reason: str = ""
+    resume_at: Optional[datetime] = None  # When a suppressed notification could be delivered
+    defer: bool = False  # Hold it and deliver at resume_at rather than drop it
+    whisper: bool = False  # Deliver it, quietly
+
+
+@dataclass(frozen=True)
+class Notification:
+    """What a notification says, as notification hooks see it."""
+    channel: str
+    title: str
+    message: str
+    priority: str = "normal"
+    tags: Tuple[str, ...] = ()
+
+
+@dataclass
+class Meeting:
+    """The busy calendar event under way."""
+    title: str
+    end: datetime
+
+
+def current_meeting(events, now: datetime) -> Optional[Meeting]:
+    """The busy event (planner CalendarEvent) going on at `now`, ending last if several overlap."""
+    current = None
+    for event in events:
+        if not getattr(event, "busy", True):
+            continue
+        try:
+            start, end = datetime.fromisoformat(event.start_time), datetime.fromisoformat(event.end_time)
+        except (TypeError, ValueError):
+            continue
+        if start <= now < end and (current is None or end > current.end):
+            current = Meeting(event.title, end)
+    return current
+
+
+def _parse_hhmm(value: str) -> time:
+    hours, minutes = value.strip().split(":")
+    return time(int(hours), int(minutes))
+
+
+def _priority(value) -> NotificationPriority:
+    try:
+        return NotificationPriority(value)
+    except ValueError:
+        logger.warning(f"Unknown notification priority '{value}', treating as normal")
+        return NotificationPriority.NORMAL
+
+
+class NotificationPolicy:
+    """Quiet-hours policy shared by every notification channel."""
+
+    def __init__(self, config=None, meetings: Optional[Callable[[datetime], Optional[Meeting]]] = None):
+        self.config = config
+        self.meetings = meetings  # The busy calendar event at a given time, if any
+
+    @property
+    def enabled(self) -> bool:
+        return bool(getattr(self.config, "quiet_hours_enabled", False))
+
+    @property
+    def do_not_disturb(self) -> bool:
+        return bool(getattr(self.config, "do_not_disturb", False))
+
+    def meeting(self, now: Optional[datetime] = None) -> Optional[Meeting]:
+        """The busy calendar event under way, when config.meeting_quiet is on."""
+        if self.meetings is None or not getattr(self.config, "meeting_quiet", True):
+            return None
+        try:
+            return self.meetings(now or datetime.now())
+        except Exception as e:
+            logger.debug(f"Couldn't read the calendar for meetings: {e}")
+            return None
+
+    def _window(self):
+        start = _parse_hhmm(getattr(self.config, "quiet_hours_start", "22:00"))
+        end = _parse_hhmm(getattr(self.config, "quiet_hours_end", "07:00"))
+        return start, end
+
+    def is_in_quiet_hours(self, now: Optional[datetime] = None) -> bool:
+        """True when quiet hours are enabled and `now` falls inside the window."""
+        if not self.enabled:
+            return False
+        now = now or datetime.now()
+        start, end = self._window()
+        current = now.time()
+        if start == end:
+            return False
+        if start < end:
+            return start <= current < end
+        # Window wraps midnight (e.g. 22:00-07:00)
+        return current >= start or current < end
+
+    def quiet_hours_end(self, now: Optional[datetime] = None) -> datetime:
+        """Next time quiet hours end, relative to `now`."""
+        now = now or datetime.now()
+        _, end = self._window()
+        candidate = datetime.combine(now.date(), end)
+        return candidate if candidate > now else candidate + timedelta(days=1)
+
+    def threshold(self, channel: str) -> NotificationPriority:
+        """Minimum priority a channel lets through during quiet hours."""
+        overrides = getattr(self.config, "quiet_hours_channels", None) or {}
+        return _priority(overrides.get(channel, NotificationPriority.EMERGENCY.value))
+
+    def category_check(self, tags: Iterable[str], now: Optional[datetime] = None) -> Optional[PolicyDecision]:
+        """The first category preference that blocks a notification about an item with `tags`, if any."""
+        prefs = getattr(self.config, "category_notifications", None) or {}
+        now = now or datetime.now()
+        for tag in tags or ():
+            pref = prefs.get(tag) or {}
+            if pref.get("muted"):
+                return PolicyDecision(False, f"{tag} notifications muted")
+            if pref.get("weekends") is False and now.weekday() >= 5:
+                monday = datetime.combine(now.date() + timedelta(days=7 - now.weekday()), time())
+                return PolicyDecision(False, f"no {tag} notifications on weekends", monday)
+            if pref.get("hours"):
+                try:
+                    start, end = (_parse_hhmm(part) for part in pref["hours"].split("-"))
+                except ValueError:
+                    logger.warning(f"Invalid hours '{pref['hours']}' for category '{tag}'")
+                    continue
+                current = now.time()
+                inside = start <= current < end if start <= end else (current >= start or current < end)
+                if not inside:
+                    resume = datetime.combine(now.date(), start)
+                    if resume <= now:
+                        resume += timedelta(days=1)
+                    return PolicyDecision(False, f"outside {tag} hours ({pref['hours']})", resume)
+        return None
+
+    def check(self, channel: str, priority="normal", now: Optional[datetime] = None,
+              tags: Iterable[str] = ()) -> PolicyDecision:
+        """Decide whether a notification on `channel` (about an item with `tags`) may be delivered now."""
+        if channel not in CHANNELS:
+            logger.warning(f"Unknown notification channel '{channel}'")
+        priority = _priority(priority)
+
+        if priority == NotificationPriority.EMERGENCY:
+            return PolicyDecision(True, "emergency bypass")
+        blocked = self.category_check(tags, now)
+        if blocked:
+            return blocked
+        if self.do_not_disturb:
+            quiet, resume_at = "do not disturb", None  # Until turned off
+        elif self.is_in_quiet_hours(now):
+            quiet, resume_at = "quiet hours", self.quiet_hours_end(now)
+        else:
+            return self._meeting_check(channel, priority, now)
+        if PRIORITY_ORDER.index(priority) >= PRIORITY_ORDER.index(self.threshold(channel)):
+            decision = self._meeting_check(channel, priority, now)
+            if decision.reason:
+                return decision
+            return PolicyDecision(True, f"{channel} allows {priority.value} during {quiet}")
+        return PolicyDecision(False, quiet, resume_at)
+
+    def _meeting_check(self, channel: str, priority: NotificationPriority, now: Optional[datetime]) -> PolicyDecision:
+        """Hold or whisper speech during a busy calendar event (emergencies never get here)."""
+        meeting = self.meeting(now) if channel in MEETING_CHANNELS else None
+        if meeting is None:
+            return PolicyDecision(True)
+        if PRIORITY_ORDER.index(priority) >= PRIORITY_ORDER.index(NotificationPriority.HIGH):
+            return PolicyDecision(True, f"in {meeting.title}", whisper=True)
+        return PolicyDecision(False, f"in {meeting.title} until {meeting.end:%H:%M}", meeting.end, defer=True)
+
+    def allows(self, channel: str, priority="normal", now: Optional[datetime] = None,
+               tags: Iterable[str] = ()) -> bool:
+        return self.check(channel, priority, now, tags).allowed
+
+
+_policy: Optional[NotificationPolicy] = None
+
+
+def get_notification_policy() -> NotificationPolicy:
+    """Get the global notification policy (quiet hours disabled until configured)."""
+    global _policy
+    if _policy is None:
+        _policy = NotificationPolicy()
+    return _policy
+
+
+def set_notification_policy(policy: NotificationPolicy) -> None:
+    """Install the policy built from the loaded Config (called at startup)."""
+    global _policy
+    _policy = policy
+
+
+_hooks: List[Callable[[Notification], Optional[Notification]]] = []
+
+
+def add_notification_hook(hook: Callable[[Notification], Optional[Notification]]) -> None:
+    """Call `hook` before each notification goes out: it returns the notification (changed or not), or None to drop it."""
+    _hooks.append(hook)
+
+
+def remove_notification_hook(hook: Callable[[Notification], Optional[Notification]]) -> None:
+    if hook in _hooks:
+        _hooks.remove(hook)
+
+
+def apply_notification_hooks(notification: Notification) -> Optional[Notification]:
+    """The notification as the hooks left it, or None if one dropped it (emergencies pass untouched)."""
+    if _priority(notification.priority) == NotificationPriority.EMERGENCY:
+        return notification
+    for hook in list(_hooks):
+        try:
+            notification = hook(notification)
+        except Exception as e:
+            logger.debug(f"Notification hook failed: {e}")
+        if notification is None:
+            logger.info("🔕 Notification dropped by a hook")
+            return None
+    return notification
+
+
+_repeats = Coalescer()
+
+
+def coalesce_notification(notification: Notification) -> Optional[Notification]:
+    """None for a repeat of one just sent, else the notification, noting earlier repeats held back (see coalesce.py)."""
+    if _priority(notification.priority) == NotificationPriority.EMERGENCY:
+        return notification
+    repeat = _repeats.note((notification.channel, notification.title, notification.message))
+    if not repeat.new:
+        logger.debug(f"🔕 Repeated notification held back (×{repeat.count}): {notification.message[:60]}")
+        return None
+    if repeat.earlier:
+        times = "time" if repeat.earlier == 1 else "times"
+        return replace(notification, message=f"{notification.message} (repeated {repeat.earlier} more {times})")
+    return notification
+
+
+def send_desktop_notification(title: str, message: str, priority="normal", tags: Iterable[str] = (),
+                              on_click: Optional[Callable[[], None]] = None) -> bool:
+    """
+    Show an OS desktop notification, and push it to paired companion
+    devices, where the policy allows. Returns True if either delivered it.
+
+    `on_click` is called (from a background thread) if the user clicks the
+    notification; only notify-send on Linux reports clicks.
+    """
+    notification = apply_notification_hooks(Notification("desktop", title, message, _priority(priority).value, tuple(tags)))
+    if notification is not None:
+        notification = coalesce_notification(notification)
+    if notification is None:
+        return False
+    title, message, priority = notification.title, notification.message, notification.priority
+    pushed = False
+    if get_notification_policy().allows("companion", priority, tags=tags):
+        from .pairing import notify_companions
+        pushed = notify_companions(title, message, _priority(priority).value)
+    return _show_desktop(title, message, priority, tags, on_click) or pushed
+
+
+def _show_desktop(title: str, message: str, priority, tags: Iterable[str],
+                  on_click: Optional[Callable[[], None]] = None) -> bool:
+    decision = get_notification_policy().check("desktop", priority, tags=tags)
+    if not decision.allowed:
+        logger.debug(f"Desktop notification suppressed ({decision.reason}): {title}")
+        return False
+
+    try:
+        system = platform.system()
+        if system == "Darwin":
+            script = f'display notification {_applescript_str(message)} with title {_applescript_str(title)}'
+            subprocess.run(["osascript", "-e", script], check=False, timeout=5)
+            return True
+        if system == "Linux" and shutil.which("notify-send"):
+            urgency = "critical" if _priority(priority) == NotificationPriority.EMERGENCY else "normal"
+            if on_click is None:
+                subprocess.run(["notify-send", "-u", urgency, title, message], check=False, timeout=5)
+            else:
+                command = ["notify-send", "-u", urgency, "--action=default=Open", "--wait", title, message]
+                threading.Thread(target=_wait_for_click, args=(command, on_click), daemon=True).start()
+            return True
+    except Exception as e:
+        logger.debug(f"Desktop notification failed: {e}")
+    return False
+
+
+def _wait_for_click(command, on_click: Callable[[], None]) -> None:
+    """Run notify-send --wait until the notification closes; it prints the action if it was clicked."""
+    try:
+        result = subprocess.run(command, check=False, capture_output=True, text=True, timeout=60 * 60)
+        if result.stdout.strip() == "default":
+            on_click()
+    except Exception as e:
+        logger.debug(f"Desktop notification click not tracked: {e}")
+
+
+def _applescript_str(text: str) -> str:
+    return '"' + text.replace("\\", "\\\\").replace('"', '\\"') + '"'
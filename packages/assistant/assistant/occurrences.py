@@ -0,0 +1,116 @@
+"""
+Occurrences - Skipping one meeting of a recurring series by voice.
+
+    "skip next week's standup"          -> Standup on Mon Oct 19 skipped, the rest of the series stays
+    "cancel just tomorrow's 1:1"        -> 1:1 on Fri Oct 16 skipped
+    "cancel the standup on friday"
+
+The title is matched against the recurring events due that day (for "this
+week"/"next week", the first one due in that week) and the occurrence is
+cancelled as an exception on the series (calendar_core.py), through the
+delete_calendar_event tool so "undo that" brings it back. Expansion leaves
+it out from then on, so the schedule, reminders and the server copy
+(calendar sync deletes the occurrence) all drop it. Anything that isn't an
+occurrence of a recurring event - a one-off, an unknown title, no day
+given - goes to the AI as usual.
+"""
+
+import re
+from dataclasses import dataclass
+from datetime import date, timedelta
+from typing import Any, Optional
+
+from .capabilities import register_capability
+from .dates import parse_natural_date
+
+_SKIP_INTENT = re.compile(
+    r"^\s*(?:(?:please|can\s+you|could\s+you)\s+)?(?:skip|cancel|call\s+off|drop)\s+(?:just\s+|only\s+)?"
+    r"(?P<rest>.+?)(?:\s+(?:only|please))?\s*[.!?]*\s*$",
+    re.IGNORECASE,
+)
+_POSSESSIVE = re.compile(r"^(?P<when>.+?)['’]s\s+(?P<title>.+)$")
+ARTICLES = ("the ", "my ", "our ", "this ")
+CONNECTORS = {"on", "for", "this", "next"}
+
+
+@dataclass
+class SkipRequest:
+    """What a "skip tomorrow's standup" names."""
+    phrase: str  # "standup"
+    start: date
+    end: date  # The same as start unless a week was named
+
+
+def _days(when: str, today: date) -> Optional[tuple]:
+    day = parse_natural_date(when, today=today)
+    if day is None:
+        return None
+    if re.search(r"\bweek\b", when, re.IGNORECASE):
+        monday = day - timedelta(days=day.weekday())
+        return max(monday, today), monday + timedelta(days=6)
+    return day, day
+
+
+def _phrase(words: str) -> str:
+    lowered = words.strip(" ,").lower()
+    for article in ARTICLES:
+        if lowered.startswith(article):
+            lowered = lowered[len(article):]
+            break
+    return lowered
+
+
+def parse_skip_request(text: str, today: Optional[date] = None) -> Optional[SkipRequest]:
+    """Split "skip next week's standup" into the title said and the days meant; None if it isn't one."""
+    match = _SKIP_INTENT.match(text or "")
+    if not match:
+        return None
+    today = today or date.today()
+    rest = match.group("rest")
+    possessive = _POSSESSIVE.match(rest)
+    if possessive:
+        days = _days(possessive.group("when"), today)
+        phrase = possessive.group("title")
+    else:
+        # "the standup on friday": the longest tail that reads as a day is the "when"
+        words, days = rest.split(), None
+        for i in range(1, len(words)):
+            days = _days(" ".join(words[i:]), today)
+            if days:
+                words = words[:i]
+                break
+        while words and words[-1].lower() in CONNECTORS:
+            words = words[:-1]
+        phrase = " ".join(words)
+    phrase = _phrase(phrase)
+    if days is None or not phrase:
+        return None
+    return SkipRequest(phrase, *days)
+
+
+def find_occurrence(request: SkipRequest, planner) -> Optional[Any]:
+    """The first occurrence of a recurring event whose title matches, due on the request's days."""
+    for event in planner.get_calendar_events(request.start.isoformat(), request.end.isoformat()):
+        title = event.title.lower()
+        if event.recurrence != "none" and (request.phrase in title or title in request.phrase):
+            if event._original_id:
+                return event
+            # The series' own first occurrence: the same thing under its occurrence id
+            return planner.get_occurrence(event.id, planner.get_calendar_event(event.id).start_time[:10])
+    return None
+
+
+def match_skip(text: str, planner=None, today: Optional[date] = None) -> Optional[Any]:
+    """The occurrence "skip next week's standup" means, or None when `text` doesn't name one."""
+    request = parse_skip_request(text, today)
+    if request is None:
+        return None
+    if planner is None:
+        from .tools import get_planner_data
+        planner = get_planner_data()
+    return find_occurrence(request, planner)
+
+
+register_capability("Skipping a meeting", "Cancel one occurrence of a recurring meeting and keep the rest",
+                    ["skip next week's standup", "cancel just tomorrow's 1:1"], category="Planning",
+                    keywords=("recurring", "cancel", "exception", "series"))
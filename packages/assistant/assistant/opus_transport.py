@@ -0,0 +1,281 @@
+"""
+Opus Transport - Call audio as Opus packets instead of raw PCM, for remote clients.
+
+Raw audio is heavy off the LAN: Moshi's 24kHz float32 is 96 KB/s each way.
+A client of the Media Streams server (phone.py) that offers "opus" next to
+"audio-v1" in its frames (see ws_framing.py) sends and receives 20ms Opus
+packets at 24kHz instead - config.opus_bitrate bits per second (24 kbps by
+default, about 3 KB/s) - and skips the 8kHz mulaw round trip Twilio calls
+need. Twilio itself never offers it, so phone calls are unchanged.
+
+Packets arrive over the internet late, out of order or not at all. Each
+connection's JitterBuffer holds config.opus_jitter_ms of them and hands them
+on in sequence order; a gap still open once the buffer is full is decoded
+as lost (Opus conceals it) rather than waited for, and packets arriving
+after their turn are dropped.
+
+`xswarm dev opus-bench` checks that this costs no latency on a LAN: the
+same speech-like audio goes through the raw float path and the Opus path
+(encode, send, decode) over a simulated link, and the per-frame times are
+compared - Opus is at parity when its p95 is within PARITY_MS of raw.
+Encode and decode times of live calls are kept in CodecTimes.
+
+Needs the "opus" extra (opuslib, which loads the system's libopus).
+"""
+
+import logging
+import math
+import random
+import struct
+import time
+from collections import deque
+from dataclasses import dataclass, field
+from typing import Callable, Deque, Dict, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+SAMPLE_RATES = (8000, 12000, 16000, 24000, 48000)  # What Opus encodes natively
+SAMPLE_RATE = 24000  # Moshi's rate, so nothing is resampled
+FRAME_MS = 20
+DEFAULT_BITRATE = 24000
+PARITY_MS = 2.0  # Opus p95 within this of raw counts as parity
+RAW_BYTES_PER_SAMPLE = 4  # The raw path sends float32
+
+
+class OpusUnavailable(RuntimeError):
+    """opuslib or libopus is missing."""
+
+
+def opus_available() -> bool:
+    try:
+        import opuslib  # noqa: F401
+    except Exception:  # ImportError, or libopus missing (opuslib raises on import)
+        return False
+    return True
+
+
+@dataclass
+class CodecTimes:
+    """Recent per-frame encode and decode times, in milliseconds."""
+    encode: Deque[float] = field(default_factory=lambda: deque(maxlen=500))
+    decode: Deque[float] = field(default_factory=lambda: deque(maxlen=500))
+
+    def summary(self) -> Dict[str, float]:
+        return {f"{name}_{label}": round(value, 3)
+                for name, samples in (("encode", self.encode), ("decode", self.decode)) if samples
+                for label, value in (("p50", percentile(samples, 50)), ("p95", percentile(samples, 95)))}
+
+
+def percentile(samples, pct: float) -> float:
+    ordered = sorted(samples)
+    if not ordered:
+        return 0.0
+    return ordered[min(len(ordered) - 1, max(0, math.ceil(pct / 100 * len(ordered)) - 1))]
+
+
+class OpusCodec:
+    """
+    Mono 16-bit PCM to and from FRAME_MS Opus packets. `backend` is
+    (encoder, decoder) with opuslib's encode(pcm, samples)/decode(packet,
+    samples), made from opuslib when not given.
+    """
+
+    def __init__(self, sample_rate: int = SAMPLE_RATE, bitrate: int = DEFAULT_BITRATE,
+                 backend: Optional[Tuple[object, object]] = None):
+        if sample_rate not in SAMPLE_RATES:
+            raise ValueError(f"Opus takes {', '.join(map(str, SAMPLE_RATES))} Hz, not {sample_rate}")
+        self.sample_rate = sample_rate
+        self.bitrate = bitrate
+        self.frame_samples = sample_rate * FRAME_MS // 1000
+        self.times = CodecTimes()
+        self._pending = b""
+        if backend is None:
+            try:
+                import opuslib
+            except Exception as e:
+                raise OpusUnavailable(f"Opus needs the opus extra and libopus: {e}") from e
+            encoder = opuslib.Encoder(sample_rate, 1, opuslib.APPLICATION_VOIP)
+            encoder.bitrate = bitrate
+            backend = (encoder, opuslib.Decoder(sample_rate, 1))
+        self.encoder, self.decoder = backend
+
+    @property
+    def frame_bytes(self) -> int:
+        return self.frame_samples * 2
+
+    def encode(self, pcm: bytes) -> List[bytes]:
+        """Packets for the whole frames in `pcm`; a partial frame waits for the next call."""
+        data = self._pending + pcm
+        whole = len(data) - len(data) % self.frame_bytes
+        self._pending = data[whole:]
+        packets = []
+        for offset in range(0, whole, self.frame_bytes):
+            started = time.perf_counter()
+            packets.append(self.encoder.encode(data[offset:offset + self.frame_bytes], self.frame_samples))
+            self.times.encode.append((time.perf_counter() - started) * 1000)
+        return packets
+
+    def decode(self, packet: Optional[bytes]) -> bytes:
+        """One frame of PCM; None (a lost packet) is concealed by the decoder."""
+        started = time.perf_counter()
+        pcm = self.decoder.decode(packet or b"", self.frame_samples)
+        self.times.decode.append((time.perf_counter() - started) * 1000)
+        return pcm
+
+
+class JitterBuffer:
+    """Reorders packets by sequence number, playing each once `depth` newer ones could have arrived."""
+
+    def __init__(self, depth: int = 3, max_depth: int = 25):
+        self.depth = max(1, depth)
+        self.max_depth = max(self.depth, max_depth)
+        self.next: Optional[int] = None  # Sequence number due out next
+        self.packets: Dict[int, bytes] = {}
+        self.stats = {"received": 0, "late": 0, "duplicate": 0, "lost": 0, "skipped": 0}
+
+    @classmethod
+    def from_config(cls, config=None) -> "JitterBuffer":
+        return cls(max(1, round(int(getattr(config, "opus_jitter_ms", 60)) / FRAME_MS)))
+
+    def push(self, sequence: int, packet: bytes) -> bool:
+        """Hold a packet; False when it came after its turn or twice."""
+        if self.next is None:
+            self.next = sequence
+        if sequence < self.next:
+            self.stats["late"] += 1
+            return False
+        if sequence in self.packets:
+            self.stats["duplicate"] += 1
+            return False
+        self.packets[sequence] = packet
+        self.stats["received"] += 1
+        return True
+
+    def pop(self) -> List[Optional[bytes]]:
+        """The packets now due, in order (None for a lost one), keeping `depth` buffered."""
+        due: List[Optional[bytes]] = []
+        if not self.packets:
+            return due
+        newest = max(self.packets)
+        if newest - self.next + 1 > self.max_depth:
+            # Far behind (a long dropout, or the client jumped ahead): skip to it rather than conceal it all
+            skip_to = min(seq for seq in self.packets if seq > newest - self.max_depth)
+            self.stats["skipped"] += skip_to - self.next
+            self.packets = {seq: packet for seq, packet in self.packets.items() if seq >= skip_to}
+            self.next = skip_to
+        while newest - self.next + 1 > self.depth:
+            packet = self.packets.pop(self.next, None)
+            if packet is None:
+                self.stats["lost"] += 1
+            due.append(packet)
+            self.next += 1
+        return due
+
+    def drain(self) -> List[Optional[bytes]]:
+        """Everything still held, in order (at the end of a call)."""
+        due: List[Optional[bytes]] = []
+        while self.packets:
+            packet = self.packets.pop(self.next, None)
+            if packet is None:
+                self.stats["lost"] += 1
+            due.append(packet)
+            self.next += 1
+        return due
+
+
+class OpusStream:
+    """One connection's Opus: packets in through the jitter buffer, PCM out as packets."""
+
+    def __init__(self, codec: OpusCodec, jitter: Optional[JitterBuffer] = None):
+        self.codec = codec
+        self.jitter = jitter or JitterBuffer()
+
+    @classmethod
+    def from_config(cls, config=None, backend=None) -> "OpusStream":
+        codec = OpusCodec(SAMPLE_RATE, int(getattr(config, "opus_bitrate", DEFAULT_BITRATE)), backend=backend)
+        return cls(codec, JitterBuffer.from_config(config))
+
+    def receive(self, sequence: int, packet: bytes) -> List[bytes]:
+        """PCM frames now ready to play from a packet that arrived."""
+        self.jitter.push(sequence, packet)
+        return [self.codec.decode(due) for due in self.jitter.pop()]
+
+    def send(self, pcm: bytes) -> List[bytes]:
+        return self.codec.encode(pcm)
+
+
+# ------------------------------------------------------------------------------
+# LAN parity benchmark
+# ------------------------------------------------------------------------------
+
+def speech_like(seconds: float, sample_rate: int = SAMPLE_RATE, seed: int = 7) -> bytes:
+    """Voiced-sounding test audio: a wandering pitch with harmonics, syllable bursts and noise."""
+    rng = random.Random(seed)
+    samples = []
+    phase = 0.0
+    for n in range(int(seconds * sample_rate)):
+        t = n / sample_rate
+        pitch = 140 + 40 * math.sin(2 * math.pi * 0.7 * t)
+        phase += 2 * math.pi * pitch / sample_rate
+        envelope = 0.5 + 0.5 * math.sin(2 * math.pi * 4 * t)  # ~4 syllables a second
+        value = envelope * (0.5 * math.sin(phase) + 0.25 * math.sin(2 * phase) + 0.1 * math.sin(3 * phase))
+        samples.append(max(-32768, min(32767, int((value + rng.uniform(-0.02, 0.02)) * 20000))))
+    return struct.pack(f"<{len(samples)}h", *samples)
+
+
+@dataclass
+class PathResult:
+    name: str
+    bytes_per_second: float
+    p50_ms: float
+    p95_ms: float
+
+
+@dataclass
+class BenchResult:
+    raw: PathResult
+    opus: PathResult
+    link_mbps: float
+
+    @property
+    def parity(self) -> bool:
+        return self.opus.p95_ms <= self.raw.p95_ms + PARITY_MS
+
+    def lines(self) -> List[str]:
+        lines = [f"  {path.name:<5} {path.bytes_per_second / 1024:8.1f} KB/s   "
+                 f"p50 {path.p50_ms:6.2f} ms   p95 {path.p95_ms:6.2f} ms" for path in (self.raw, self.opus)]
+        verdict = "at parity with" if self.parity else "slower than"
+        lines.append(f"{'✓' if self.parity else '✗'} Opus is {verdict} raw on a {self.link_mbps:g} Mbit/s link "
+                     f"({self.opus.p95_ms - self.raw.p95_ms:+.2f} ms at p95, {PARITY_MS:g} ms allowed)")
+        return lines
+
+
+def run_bench(codec: OpusCodec, seconds: float = 5.0, link_mbps: float = 100.0,
+              clock: Callable[[], float] = time.perf_counter) -> BenchResult:
+    """Per-frame latency of the raw float path and the Opus path over a link of `link_mbps`."""
+    pcm = speech_like(seconds, codec.sample_rate)
+
+    def link(size: int) -> float:  # Milliseconds on the wire
+        return size * 8 / (link_mbps * 1_000_000) * 1000
+
+    frames = [pcm[i:i + codec.frame_bytes] for i in range(0, len(pcm) - codec.frame_bytes + 1, codec.frame_bytes)]
+    raw_times, opus_times, opus_bytes = [], [], 0
+    for frame in frames:
+        started = clock()
+        count = len(frame) // 2
+        floats = [s / 32768 for s in struct.unpack(f"<{count}h", frame)]
+        wire = struct.pack(f"<{count}f", *floats)
+        struct.unpack(f"<{count}f", wire)
+        raw_times.append((clock() - started) * 1000 + link(len(wire)))
+
+        started = clock()
+        (packet,) = codec.encode(frame)
+        codec.decode(packet)
+        opus_times.append((clock() - started) * 1000 + link(len(packet)))
+        opus_bytes += len(packet)
+    duration = len(frames) * FRAME_MS / 1000 or 1
+    raw_rate = codec.frame_samples * RAW_BYTES_PER_SAMPLE * 1000 / FRAME_MS
+    return BenchResult(PathResult("raw", raw_rate, percentile(raw_times, 50), percentile(raw_times, 95)),
+                       PathResult("opus", opus_bytes / duration, percentile(opus_times, 50),
+                                  percentile(opus_times, 95)),
+                       link_mbps)
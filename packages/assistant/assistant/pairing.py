@@ -0,0 +1,458 @@
+"""
+Device Pairing - Let a phone or web client talk to the running assistant.
+
+Pairing (ctrl+y in the dashboard, see keymap.py) shows a 6-digit code and a
+QR code of the companion URI:
+
+    xswarm://pair?host=192.168.1.20&port=8765&code=482913
+
+The code is valid for CODE_TTL and a few tries. A client that sends it
+gets a device token for the scopes it was offered:
+
+- notify:  receive notifications (reminders, inbox, calls) as they happen
+- message: send text messages to the assistant and get its replies
+- status:  mic state, do not disturb and the next appointment
+
+Protocol: JSON messages over a WebSocket (config.companion_port,
+reachable from outside the LAN through a tunnel, see tunnel_manager.py):
+
+    {"type": "pair", "code": "482913", "name": "Pixel 8"}  -> {"type": "paired", "device_id": ..., "token": ...}
+    {"type": "auth", "token": "..."}                       -> {"type": "ready", "scopes": [...]}
+    {"type": "message", "text": "what's next today?"}      -> {"type": "accepted"}, then {"type": "chat", ...}
+    {"type": "status"}                                     -> {"type": "status", ...}
+    pushed: {"type": "notification", "title": ..., "body": ..., "priority": ...}
+
+Another of the user's machines uses the same code to join memory sync
+(memory_sync.py) instead of pairing as a device, proving it has the code
+without sending it:
+
+    {"type": "sync_join", "public_key": ...}  -> {"type": "sync_challenge", "public_key": ...}
+    {"type": "sync_confirm", "proof": ...}    -> {"type": "sync_key", "group": ..., "key": ...}
+
+Messages are compressed with permessage-deflate when the client accepts it
+(config.ws_compression, see ws_framing.py).
+
+Tokens are stored hashed. `xswarm dev devices list` shows paired devices
+and `xswarm dev devices revoke NAME` revokes one; a connected device is
+cut off at its next message or notification.
+
+Storage: ~/.xswarm/devices.json
+"""
+
+import asyncio
+import hashlib
+import json
+import logging
+import secrets
+import socket
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Awaitable, Callable, Dict, List, Optional, Tuple
+
+from .memory_sync import check_proof, key_challenge
+from .rate_limit import ClientGuard, ListenerLimits
+from .ws_framing import FrameSession
+
+logger = logging.getLogger(__name__)
+
+SCOPES = {
+    "notify": "Receive notifications",
+    "message": "Send messages to the assistant",
+    "status": "See status and the next appointment",
+}
+CODE_TTL = timedelta(minutes=5)
+CODE_ATTEMPTS = 5  # Wrong codes before the offer is withdrawn
+DEFAULT_PORT = 8765
+COMPANION_LIMITS = ListenerLimits(max_connections=10, max_connections_per_client=3, max_message_bytes=16 * 1024,
+                                  messages_per_second=5.0, burst=20, max_violations=20)
+
+
+class PairingError(Exception):
+    """A pairing code that's wrong, expired or already used."""
+
+
+def _hash(token: str) -> str:
+    return hashlib.sha256(token.encode()).hexdigest()
+
+
+@dataclass
+class Device:
+    id: str
+    name: str
+    scopes: List[str]
+    token_hash: str
+    paired_at: str
+    last_seen: Optional[str] = None
+    revoked_at: Optional[str] = None
+
+    @property
+    def active(self) -> bool:
+        return self.revoked_at is None
+
+
+@dataclass
+class PairingOffer:
+    code: str
+    expires_at: datetime
+    scopes: List[str] = field(default_factory=lambda: list(SCOPES))
+    attempts: int = 0
+
+
+class DeviceRegistry:
+    """Paired devices and the pairing code currently on offer."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "devices.json"
+
+    def __init__(self, path: Optional[Path] = None, clock: Callable[[], datetime] = datetime.now):
+        self.path = path or self.DEFAULT_PATH
+        self.clock = clock
+        self.offer: Optional[PairingOffer] = None
+        self._devices: Dict[str, Device] = {}
+        self._mtime: Optional[float] = None
+        self._load()
+
+    def _load(self) -> None:
+        """(Re)read the file, so revocations from `xswarm dev devices revoke` apply."""
+        try:
+            mtime = self.path.stat().st_mtime
+        except OSError:
+            return
+        if mtime == self._mtime:
+            return
+        try:
+            data = json.loads(self.path.read_text(encoding="utf-8"))
+            self._devices = {d["id"]: Device(**d) for d in data.get("devices", [])}
+            self._mtime = mtime
+        except (OSError, ValueError, TypeError, KeyError) as e:
+            logger.warning(f"Failed to load paired devices: {e}")
+
+    def _save(self) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps({"devices": [asdict(d) for d in self._devices.values()]}, indent=2),
+                             encoding="utf-8")
+        self.path.chmod(0o600)
+        self._mtime = self.path.stat().st_mtime
+
+    def devices(self, include_revoked: bool = False) -> List[Device]:
+        self._load()
+        return [d for d in self._devices.values() if include_revoked or d.active]
+
+    def start_pairing(self, scopes: Optional[List[str]] = None) -> PairingOffer:
+        """Offer a new code (replacing any previous one)."""
+        unknown = [s for s in scopes or [] if s not in SCOPES]
+        if unknown:
+            raise PairingError(f"Unknown scope '{unknown[0]}' (use {', '.join(SCOPES)})")
+        code = f"{secrets.randbelow(10 ** 6):06d}"
+        self.offer = PairingOffer(code, self.clock() + CODE_TTL, list(scopes or SCOPES))
+        return self.offer
+
+    def cancel_pairing(self) -> None:
+        self.offer = None
+
+    def redeem_code(self, code: str) -> PairingOffer:
+        """Use up the offer if `code` is the one on it; PairingError when it isn't (or none is on offer)."""
+        return self.redeem(lambda offered: secrets.compare_digest(str(code).strip(), offered))
+
+    def redeem(self, matches: Callable[[str], bool]) -> PairingOffer:
+        """Use up the offer if `matches(its code)` (a proof made with it, say); a wrong try otherwise."""
+        offer = self.offer
+        if offer is None or self.clock() >= offer.expires_at:
+            self.offer = None
+            raise PairingError("No pairing in progress - start pairing on the assistant first")
+        if not matches(offer.code):
+            offer.attempts += 1
+            if offer.attempts >= CODE_ATTEMPTS:
+                self.offer = None
+                raise PairingError("Too many wrong codes - start pairing again")
+            raise PairingError("Wrong pairing code")
+        self.offer = None
+        return offer
+
+    def complete_pairing(self, code: str, name: str) -> Tuple[Device, str]:
+        """Pair a device that sent the offered code; returns it and its token (shown only now)."""
+        offer = self.redeem_code(code)
+        self._load()
+        token = secrets.token_urlsafe(32)
+        device = Device(uuid.uuid4().hex[:8], (name or "Device").strip()[:40], offer.scopes, _hash(token),
+                        self.clock().isoformat(timespec="seconds"))
+        self._devices[device.id] = device
+        self._save()
+        return device, token
+
+    def authenticate(self, token: str) -> Optional[Device]:
+        """The active device with this token, or None."""
+        self._load()
+        token_hash = _hash(token or "")
+        for device in self._devices.values():
+            if device.active and secrets.compare_digest(device.token_hash, token_hash):
+                device.last_seen = self.clock().isoformat(timespec="seconds")
+                self._save()
+                return device
+        return None
+
+    def is_active(self, device_id: str) -> bool:
+        self._load()
+        device = self._devices.get(device_id)
+        return device is not None and device.active
+
+    def revoke(self, name_or_id: str) -> Optional[Device]:
+        """Revoke a device by id or (case-insensitive) name."""
+        self._load()
+        wanted = name_or_id.strip().lower()
+        for device in self._devices.values():
+            if device.active and wanted in (device.id, device.name.lower()):
+                device.revoked_at = self.clock().isoformat(timespec="seconds")
+                self._save()
+                return device
+        return None
+
+
+def lan_address() -> str:
+    """This machine's address on the local network (what a phone on the same Wi-Fi connects to)."""
+    try:
+        with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as sock:
+            sock.connect(("192.0.2.1", 9))  # No packet is sent; this only picks the outgoing interface
+            return sock.getsockname()[0]
+    except OSError:
+        return "127.0.0.1"
+
+
+def pairing_uri(host: str, port: int, code: str) -> str:
+    return f"xswarm://pair?host={host}&port={port}&code={code}"
+
+
+def qr_text(data: str) -> Optional[str]:
+    """`data` as a QR code drawn with block characters, or None without the qrcode package."""
+    try:
+        import qrcode
+    except ImportError:
+        return None
+    qr = qrcode.QRCode(border=1)
+    qr.add_data(data)
+    qr.make(fit=True)
+    matrix = qr.get_matrix()
+    if len(matrix) % 2:
+        matrix.append([False] * len(matrix[0]))
+    chars = {(True, True): "█", (True, False): "▀", (False, True): "▄", (False, False): " "}
+    return "\n".join("".join(chars[(top, bottom)] for top, bottom in zip(matrix[y], matrix[y + 1]))
+                     for y in range(0, len(matrix), 2))
+
+
+class CompanionSession:
+    """One connected client: unauthenticated until it pairs or sends its token."""
+
+    def __init__(self, send: Callable[[Dict[str, Any]], Awaitable[None]], client: str = "",
+                 close: Optional[Callable[[], Awaitable[None]]] = None):
+        self.send = send
+        self.client = client
+        self.close = close
+        self.device: Optional[Device] = None
+        self.sync_join: Optional[Tuple[Any, str, str]] = None  # Our private key, the joiner's public key and name
+
+
+class CompanionServer:
+    """WebSocket server for paired companion clients."""
+
+    def __init__(self, registry: DeviceRegistry, on_message: Callable[[str, Device], Any],
+                 status: Callable[[], Dict[str, Any]], host: str = "0.0.0.0", port: int = DEFAULT_PORT,
+                 on_violation: Optional[Callable[[str], None]] = None,
+                 on_sync_join: Optional[Callable[[Any, str, str], Dict[str, Any]]] = None,
+                 compression: Optional[str] = "deflate"):
+        self.registry = registry
+        self.on_message = on_message  # Text from a device, handled like typed chat
+        self.status = status
+        self.host = host
+        self.port = port
+        self.guard = ClientGuard("Companion", COMPANION_LIMITS, on_violation)
+        # (our private key, the joiner's public key, code) -> the sync_key reply (memory_sync.key_offer)
+        self.on_sync_join = on_sync_join
+        self.compression = compression  # websockets' permessage-deflate offer ("deflate" or None)
+        self.sessions: List[CompanionSession] = []
+        self._server = None
+
+    async def start(self) -> bool:
+        """Listen for companions; False (logged) when the port can't be opened."""
+        try:
+            import websockets
+            self._server = await websockets.serve(self._serve, self.host, self.port,
+                                                  max_size=COMPANION_LIMITS.max_message_bytes,
+                                                  compression=self.compression)
+        except (ImportError, OSError) as e:
+            logger.warning(f"Companion server could not listen on {self.host}:{self.port}: {e}")
+            return False
+        logger.info(f"Companion server listening on ws://{self.host}:{self.port}")
+        return True
+
+    async def _serve(self, websocket) -> None:
+        remote = websocket.remote_address
+        client = str(remote[0] if isinstance(remote, tuple) else remote)
+        if not self.guard.admit(client):
+            await websocket.close(code=1013, reason="Too many connections")
+            return
+        framing = FrameSession("companion", websocket)
+
+        def send(reply: Dict[str, Any]) -> Awaitable[None]:
+            text = json.dumps(reply)
+            framing.sent(text)
+            return websocket.send(text)
+
+        session = CompanionSession(send, client, lambda: websocket.close(code=1008, reason="Device revoked"))
+        self.sessions.append(session)
+        try:
+            async for raw in websocket:
+                verdict = self.guard.check_message(client, id(websocket), len(raw))
+                if verdict == "disconnect":
+                    await websocket.close(code=1008, reason="Rate limit exceeded")
+                    break
+                if verdict == "drop":
+                    continue
+                framing.received(raw)
+                try:
+                    request = json.loads(raw)
+                    if not isinstance(request, dict):
+                        raise ValueError("expected an object")
+                except ValueError as e:
+                    await session.send({"type": "error", "error": f"Bad request: {e}"})
+                    continue
+                await session.send(await self.handle(session, request))
+                if session.device is not None and not self.registry.is_active(session.device.id):
+                    await websocket.close(code=1008, reason="Device revoked")
+                    break
+        except Exception as e:
+            logger.debug(f"Companion connection from {client} ended: {e}")
+        finally:
+            self.guard.release(client, id(websocket))
+            if session in self.sessions:
+                self.sessions.remove(session)
+
+    async def handle(self, session: CompanionSession, request: Dict[str, Any]) -> Dict[str, Any]:
+        kind = request.get("type")
+        if kind == "pair":
+            try:
+                device, token = self.registry.complete_pairing(str(request.get("code", "")), str(request.get("name", "")))
+            except PairingError as e:
+                return {"type": "error", "error": str(e)}
+            session.device = device
+            logger.info(f"Paired {device.name} ({device.id}) from {session.client}")
+            return {"type": "paired", "device_id": device.id, "token": token, "scopes": device.scopes}
+        if kind == "sync_join":
+            if self.on_sync_join is None:
+                return {"type": "error", "error": "Memory sync isn't available on this machine"}
+            public_key = str(request.get("public_key", ""))
+            try:
+                private, reply = key_challenge(public_key)
+            except ValueError as e:
+                return {"type": "error", "error": str(e)}
+            session.sync_join = (private, public_key, str(request.get("name") or session.client))
+            return reply
+        if kind == "sync_confirm":
+            pending, session.sync_join = session.sync_join, None  # One proof per challenge
+            if pending is None or self.on_sync_join is None:
+                return {"type": "error", "error": "Send sync_join first"}
+            private, public_key, name = pending
+            proof = str(request.get("proof", ""))
+            try:
+                # The key is only released for a proof made with the code on offer
+                code = self.registry.redeem(lambda offered: check_proof(private, public_key, proof, offered)).code
+                reply = self.on_sync_join(private, public_key, code)
+            except (PairingError, ValueError) as e:
+                return {"type": "error", "error": str(e)}
+            logger.info(f"Shared the memory sync key with {name}")
+            return reply
+        if kind == "auth":
+            device = self.registry.authenticate(str(request.get("token", "")))
+            if device is None:
+                return {"type": "error", "error": "Unknown or revoked device"}
+            session.device = device
+            return {"type": "ready", "device_id": device.id, "scopes": device.scopes}
+
+        device = session.device
+        if device is None or not self.registry.is_active(device.id):
+            session.device = None
+            return {"type": "error", "error": "Pair or authenticate first"}
+        if kind == "message":
+            if "message" not in device.scopes:
+                return {"type": "error", "error": "This device can't send messages"}
+            text = str(request.get("text", "")).strip()
+            if not text:
+                return {"type": "error", "error": "Empty message"}
+            result = self.on_message(text, device)
+            if asyncio.iscoroutine(result):
+                await result
+            return {"type": "accepted"}
+        if kind == "status":
+            if "status" not in device.scopes:
+                return {"type": "error", "error": "This device can't see status"}
+            return {"type": "status", **self.status()}
+        return {"type": "error", "error": f"Unknown request type '{kind}'"}
+
+    async def disconnect_revoked(self) -> None:
+        for session in list(self.sessions):
+            if session.device is not None and not self.registry.is_active(session.device.id):
+                session.device = None
+                if session.close is not None:
+                    await session.close()
+
+    async def broadcast(self, message: Dict[str, Any], scope: str) -> int:
+        """Send to every connected device with `scope`; returns how many got it."""
+        await self.disconnect_revoked()
+        sent = 0
+        for session in list(self.sessions):
+            device = session.device
+            if device is None or scope not in device.scopes:
+                continue
+            try:
+                await session.send(message)
+                sent += 1
+            except Exception as e:
+                logger.debug(f"Companion {device.name} unreachable: {e}")
+        return sent
+
+    def close(self) -> None:
+        if self._server is not None:
+            self._server.close()
+            self._server = None
+
+
+_server: Optional[CompanionServer] = None
+_last_chat: Optional[Tuple[str, str]] = None
+
+
+def get_companion_server() -> Optional[CompanionServer]:
+    """The running companion server, or None when it's off."""
+    return _server
+
+
+def set_companion_server(server: Optional[CompanionServer]) -> None:
+    global _server
+    _server = server
+
+
+def _push(message: Dict[str, Any], scope: str) -> bool:
+    server = _server
+    if server is None or not any(s.device and scope in s.device.scopes for s in server.sessions):
+        return False
+    try:
+        asyncio.get_running_loop().create_task(server.broadcast(message, scope))
+    except RuntimeError:
+        return False  # Not on the event loop (e.g. a worker thread); companions miss this one
+    return True
+
+
+def notify_companions(title: str, body: str, priority: str = "normal") -> bool:
+    """Push a notification to connected devices with the notify scope; True when any is connected."""
+    return _push({"type": "notification", "title": title, "body": body, "priority": priority}, "notify")
+
+
+def forward_chat(sender: str, text: str) -> None:
+    """Send a finished chat message to devices with the message scope (streaming updates are skipped)."""
+    global _last_chat
+    stripped = (text or "").strip()
+    if sender.lower() in ("thinking", "debug") or not stripped or stripped.endswith(("▌", "...")):
+        return
+    if (sender, stripped) == _last_chat:
+        return
+    _last_chat = (sender, stripped)
+    _push({"type": "chat", "sender": sender, "text": stripped}, "message")
@@ -0,0 +1,87 @@
+"""
+Palette - The dashboard's command palette (Ctrl+P): every action and skill, fuzzy-searched.
+
+Entries come from two places, so the palette grows with the features:
+- the keymap's actions (keymap.py), run straight away and shown with the
+  keys that would have done the same ("Go to Inbox   8")
+- the capabilities registry (capabilities.py), one entry per example
+  phrase of each feature that's set up; choosing one puts the phrase in the
+  chat input to send as is or edit first ("set a pasta timer for 9 minutes")
+
+Typing narrows the list with a fuzzy match: the typed letters have to
+appear in order, and matches at the start of words or in a row rank first
+("gtin" finds "Go to Inbox", "pasta" the timer example).
+
+CommandPaletteScreen in dashboard.py shows it.
+"""
+
+from dataclasses import dataclass
+from typing import List, Optional
+
+from .capabilities import capabilities
+from .keymap import ACTIONS, key_label
+
+MAX_SHOWN = 12  # Entries visible at once
+
+
+@dataclass(frozen=True)
+class PaletteEntry:
+    title: str
+    detail: str = ""  # The keys, or what the feature does
+    action: str = ""  # Textual action to run
+    say: str = ""  # Or: a phrase for the chat input
+
+
+def palette_entries(keymap, config=None) -> List[PaletteEntry]:
+    """The actions (with their keys in `keymap`), then the example phrases of every set-up capability."""
+    entries = []
+    for action in ACTIONS:
+        if action.name == "command_palette":
+            continue
+        title = f"Go to {action.description}" if action.group == "Tabs" else action.description
+        entries.append(PaletteEntry(title, " / ".join(key_label(k) for k in keymap.keys(action.name)),
+                                    action=action.command))
+    for capability in capabilities():
+        if capability.available(config):
+            entries += [PaletteEntry(f"{capability.name}: {example}", capability.summary, say=example)
+                        for example in capability.examples]
+    return entries
+
+
+def fuzzy_score(query: str, text: str) -> Optional[float]:
+    """
+    How well `query` matches `text` (higher is better), or None when its
+    letters don't all appear in order. Spaces in the query are ignored,
+    except that the query as typed, found whole, beats any scattered match.
+    """
+    whole = " ".join(query.lower().split())
+    query = whole.replace(" ", "")
+    text = text.lower()
+    if not query:
+        return 0.0
+    found = text.find(whole)
+    if found >= 0:
+        at_word = found == 0 or not text[found - 1].isalnum()
+        return 10 + 5 * len(query) + (3 if at_word else 0) - 0.01 * found
+    score, position, previous = 0.0, 0, -2
+    for char in query:
+        found = text.find(char, position)
+        if found < 0:
+            return None
+        if found == previous + 1:
+            score += 2  # In a row
+        if found == 0 or not text[found - 1].isalnum():
+            score += 3  # Start of a word
+        score -= 0.05 * (found - position)  # Skipped letters
+        previous, position = found, found + 1
+    return score
+
+
+def filter_entries(entries: List[PaletteEntry], query: str) -> List[PaletteEntry]:
+    """Entries matching `query`, best first (in their usual order for an empty query)."""
+    scored = []
+    for index, entry in enumerate(entries):
+        score = fuzzy_score(query, entry.title)
+        if score is not None:
+            scored.append((-score, index, entry))
+    return [entry for *_, entry in sorted(scored, key=lambda item: item[:2])]
@@ -58,6 +58,31 @@ class VoiceSettings(BaseModel):
     speed: float = Field(1.0, ge=0.5, le=2.0, description="Speaking speed")
     tone: str = Field("neutral", description="Tone descriptor (neutral, warm, professional, etc.)")
     quality: float = Field(0.8, ge=0.0, le=1.0, description="Generation quality (0-1)")
+    markup: bool = Field(True, description="Ask for speech hints in spoken replies (see prosody.py)")
+
+
+class EarconSettings(BaseModel):
+    """Listening cues (see earcons.py)"""
+
+    style: str = Field("blip", description="Cue style: beep, chime, blip, none")
+    volume: float = Field(1.0, ge=0.0, le=1.0, description="Cue level, scaled by config.earcon_volume")
+
+
+class MoodSettings(BaseModel):
+    """How far and how fast the persona's mood drifts in a session (see personas/mood.py)"""
+
+    enabled: bool = Field(True, description="Let the mood drift at all")
+    range: float = Field(0.3, ge=0.0, le=1.0, description="Furthest warmth/energy stray from rest")
+    sensitivity: float = Field(0.3, ge=0.0, le=1.0, description="How much one message moves it")
+    half_life_minutes: float = Field(30.0, gt=0.0, description="Minutes to ease half-way back to rest")
+
+
+class SpeechFilterSettings(BaseModel):
+    """What this persona may say aloud; unset follows config.speech_* (see speech_filter.py)"""
+
+    profanity: Optional[str] = Field(None, description="mask (said as \"bleep\"), remove or off")
+    pii: Optional[bool] = Field(None, description="Leave phone numbers, emails and secrets out of speech")
+    max_words: Optional[int] = Field(None, ge=0, description="Ask \"Shall I continue?\" past this (0 = no limit)")
 
 
 class ThemeColors(BaseModel):
@@ -116,6 +141,16 @@ class PersonaConfig(BaseModel):
     # Theme (NEW)
     theme: ThemeConfig = Field(default_factory=ThemeConfig, description="Visual theme configuration")
 
+    # Listening cues
+    earcons: EarconSettings = Field(default_factory=EarconSettings, description="Wake/done/error sounds")
+
+    # Mood over a session
+    mood: MoodSettings = Field(default_factory=MoodSettings, description="Mood drift bounds")
+
+    # Filters on what is said aloud
+    speech_filter: SpeechFilterSettings = Field(default_factory=SpeechFilterSettings,
+                                                description="Profanity, personal details, spoken length")
+
     # System prompt
     system_prompt: str = Field("", description="Base system prompt")
     personality_guide: str = Field("", description="Detailed personality guide")
@@ -126,6 +161,12 @@ class PersonaConfig(BaseModel):
         description="Custom vocabulary (preferred_phrases, avoid_phrases, etc.)"
     )
 
+    # Canned greetings, acknowledgments, apologies and fillers (phrases.yaml, see phrases.py)
+    phrases: Optional[Dict[str, Any]] = Field(
+        default=None,
+        description="Phrases by kind, said without asking a model"
+    )
+
     # Wake word (optional override)
     wake_word: Optional[str] = Field(
         default=None,
@@ -165,8 +206,8 @@ class PersonaConfig(BaseModel):
         
         return ", ".join(descriptions) + "."
 
-    def build_system_prompt(self, include_personality: bool = True) -> str:
-        """Build complete system prompt with template replacement."""
+    def build_system_prompt(self, include_personality: bool = True, spoken: bool = False) -> str:
+        """Build complete system prompt with template replacement (spoken: for replies read aloud)."""
         # Start with base system prompt
         prompt = self.system_prompt or ""
         
@@ -198,5 +239,10 @@ class PersonaConfig(BaseModel):
                 avoid = ", ".join(self.vocabulary["avoid_phrases"])
                 parts.append(f"I avoid phrases like: {avoid}.")
 
+        # Pauses, emphasis and spelled-out codes, put into words for engines without markup
+        if spoken and self.voice.markup:
+            from ..prosody import PROMPT_GUIDE
+            parts.append(PROMPT_GUIDE)
+
         return "\n\n".join(parts)
 
@@ -6,6 +6,7 @@ from pathlib import Path
 from typing import Dict, Optional, List
 import yaml
 from .config import PersonaConfig
+from .phrases import PhraseBank, set_phrase_bank
 
 
 class PersonaManager:
@@ -74,10 +75,12 @@ class PersonaManager:
                 theme.yaml          # Main config (REQUIRED)
                 personality.md      # Detailed guide (optional)
                 vocabulary.yaml     # Vocabulary (optional)
+                phrases.yaml        # Canned phrases (optional, see phrases.py)
         """
         theme_file = persona_dir / "theme.yaml"
         personality_file = persona_dir / "personality.md"
         vocab_file = persona_dir / "vocabulary.yaml"
+        phrases_file = persona_dir / "phrases.yaml"
 
         # Load main theme config
         with open(theme_file, 'r') as f:
@@ -94,6 +97,11 @@ class PersonaManager:
                 vocab_data = yaml.safe_load(f)
                 theme_data['vocabulary'] = vocab_data
 
+        # Load canned phrases if they exist
+        if phrases_file.exists():
+            with open(phrases_file, 'r') as f:
+                theme_data['phrases'] = yaml.safe_load(f)
+
         # Create PersonaConfig
         persona = PersonaConfig(**theme_data)
 
@@ -135,6 +143,7 @@ class PersonaManager:
         persona = self.get_persona(name)
         if persona:
             self.current_persona = persona
+            set_phrase_bank(PhraseBank.from_persona(persona))
             return True
         else:
             return False
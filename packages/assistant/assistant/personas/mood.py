@@ -0,0 +1,184 @@
+"""
+Persona mood - How the persona feels, drifting over a session.
+
+A mood is warmth and energy, each from -1 to 1. The persona rests at the
+mood its traits give it (agreeableness and calm for warmth, enthusiasm and
+extraversion for energy) and every user message nudges it:
+
+    "thanks, that's perfect!"      -> warmer and livelier
+    "ugh, that's wrong again"      -> cooler
+    "ok" at 11pm                   -> quieter (the time of day pulls energy)
+
+Between messages it eases back to rest, half-way every
+MoodSettings.half_life_minutes, and it never strays further than
+MoodSettings.range from rest - a persona's theme.yaml sets both
+(mood: {range: 0.2}), or turns it off (mood: {enabled: false}).
+
+The mood shapes replies (ChatEngine): the sampling temperature moves up to
+TEMPERATURE_SWING with energy, and the system prompt gets a "Current mood"
+line. The dashboard footer shows it; the reset_mood tool puts it back at
+rest, as does a new session or persona.
+"""
+
+import re
+from dataclasses import dataclass, replace
+from datetime import datetime
+from typing import Any, Optional, Tuple
+
+TEMPERATURE_SWING = 0.15  # Temperature change at full energy either way
+TONE_WORDS = {
+    # word: (warmth, energy)
+    "thanks": (1, 0.3), "thank": (1, 0.3), "great": (0.8, 0.6), "perfect": (1, 0.5), "awesome": (1, 1),
+    "love": (1, 0.6), "nice": (0.6, 0.3), "brilliant": (1, 0.8), "haha": (0.8, 0.8), "lol": (0.6, 0.6),
+    "please": (0.3, 0), "cool": (0.4, 0.3), "yay": (0.8, 1),
+    "ugh": (-0.8, -0.2), "wrong": (-0.6, 0.2), "annoying": (-0.8, 0.3), "useless": (-1, 0.3),
+    "stupid": (-1, 0.5), "hate": (-1, 0.5), "frustrated": (-0.8, 0.2), "tired": (-0.2, -0.8),
+    "sigh": (-0.4, -0.6), "boring": (-0.4, -0.8), "again": (-0.2, 0), "damn": (-0.6, 0.6),
+}
+# Energy pulled toward by the hour: (from hour, offset), the last one that started applies
+TIME_OF_DAY = ((0, -0.3), (6, 0.1), (9, 0.2), (12, 0.0), (17, -0.1), (22, -0.3))
+LEVEL = 0.25  # Warmth or energy past this either way shows in the label
+LABELS = {
+    # (warmth, energy): -1 low, 0 middling, 1 high
+    (1, 1): "cheerful", (1, 0): "warm", (1, -1): "relaxed",
+    (0, 1): "energetic", (0, 0): "neutral", (0, -1): "tired",
+    (-1, 1): "irritable", (-1, 0): "cool", (-1, -1): "gloomy",
+}
+GUIDANCE = {
+    "cheerful": "upbeat and playful",
+    "energetic": "brisk and lively",
+    "warm": "friendly and encouraging",
+    "irritable": "curt, with an edge",
+    "cool": "dry and matter-of-fact",
+    "tired": "brief and low-key",
+    "gloomy": "subdued and terse",
+    "relaxed": "easy-going and unhurried",
+    "neutral": "even",
+}
+
+_WORDS = re.compile(r"[a-z']+")
+
+
+@dataclass(frozen=True)
+class Mood:
+    warmth: float = 0.0
+    energy: float = 0.0
+
+    @property
+    def label(self) -> str:
+        """"cheerful", "tired", "irritable"... from where warmth and energy are."""
+        return LABELS[(_level(self.warmth), _level(self.energy))]
+
+
+def _level(value: float) -> int:
+    return 1 if value > LEVEL else -1 if value < -LEVEL else 0
+
+
+def _clamp(value: float, low: float = -1.0, high: float = 1.0) -> float:
+    return max(low, min(high, value))
+
+
+def resting_mood(traits: Any = None) -> Mood:
+    """The mood a persona's traits (PersonalityTraits, 0-1 each) give it at rest."""
+    if traits is None:
+        return Mood()
+    warmth = (traits.agreeableness - 0.5) + (0.5 - traits.neuroticism) * 0.5
+    energy = (traits.enthusiasm - 0.5) + (traits.extraversion - 0.5)
+    return Mood(round(_clamp(warmth), 3), round(_clamp(energy), 3))
+
+
+def tone(text: str) -> Tuple[float, float]:
+    """How a message reads, as a (warmth, energy) nudge from -1 to 1 each."""
+    words = _WORDS.findall((text or "").lower())
+    hits = [TONE_WORDS[w] for w in words if w in TONE_WORDS]
+    warmth = sum(w for w, _ in hits) / len(hits) if hits else 0.0
+    energy = sum(e for _, e in hits) / len(hits) if hits else 0.0
+    letters = [c for c in text or "" if c.isalpha()]
+    if text and "!" in text:
+        energy += 0.4
+    if len(letters) >= 8 and sum(c.isupper() for c in letters) / len(letters) > 0.7:
+        energy, warmth = energy + 0.5, warmth - 0.3  # SHOUTING
+    return _clamp(warmth), _clamp(energy)
+
+
+def time_of_day_energy(now: datetime) -> float:
+    return [offset for hour, offset in TIME_OF_DAY if now.hour >= hour][-1]
+
+
+class MoodModel:
+    """One persona's mood over a session, kept within its configured range of rest."""
+
+    def __init__(self, persona: Any = None, now: Optional[datetime] = None):
+        settings = getattr(persona, "mood", None)
+        self.persona = getattr(persona, "name", None)
+        self.enabled = settings.enabled if settings else True
+        self.range = settings.range if settings else 0.3
+        self.sensitivity = settings.sensitivity if settings else 0.3
+        self.half_life_minutes = settings.half_life_minutes if settings else 30.0
+        self.rest = resting_mood(getattr(persona, "traits", None))
+        self.reset(now)
+
+    def reset(self, now: Optional[datetime] = None) -> None:
+        """Back to rest."""
+        self._mood = self.rest
+        self._updated_at = now or datetime.now()
+
+    def _bounded(self, mood: Mood) -> Mood:
+        return Mood(round(_clamp(mood.warmth, self.rest.warmth - self.range, self.rest.warmth + self.range), 3),
+                    round(_clamp(mood.energy, self.rest.energy - self.range, self.rest.energy + self.range), 3))
+
+    def _eased(self, now: datetime) -> Mood:
+        """The last mood, eased back toward rest for the time since."""
+        minutes = max(0.0, (now - self._updated_at).total_seconds() / 60)
+        kept = 0.5 ** (minutes / self.half_life_minutes)
+        return Mood(self.rest.warmth + (self._mood.warmth - self.rest.warmth) * kept,
+                    self.rest.energy + (self._mood.energy - self.rest.energy) * kept)
+
+    def current(self, now: Optional[datetime] = None) -> Mood:
+        """The mood now: eased back toward rest since the last message, with the time of day's pull."""
+        if not self.enabled:
+            return self.rest
+        now = now or datetime.now()
+        eased = self._eased(now)
+        return self._bounded(replace(eased, energy=eased.energy + time_of_day_energy(now)))
+
+    def observe(self, text: str, now: Optional[datetime] = None) -> Mood:
+        """Nudge the mood by how a user message reads; returns the mood now."""
+        if not self.enabled:
+            return self.rest
+        now = now or datetime.now()
+        eased = self._eased(now)
+        warmth, energy = tone(text)
+        self._mood = self._bounded(Mood(eased.warmth + warmth * self.sensitivity,
+                                        eased.energy + energy * self.sensitivity))
+        self._updated_at = now
+        return self.current(now)
+
+    def shape(self, config: Any, now: Optional[datetime] = None) -> Any:
+        """A copy of a ChatEngineConfig with the temperature moved by the mood's energy."""
+        temperature = config.temperature + TEMPERATURE_SWING * self.current(now).energy
+        return replace(config, temperature=round(_clamp(temperature, 0.0, 1.0), 3))
+
+    def prompt_line(self, now: Optional[datetime] = None) -> str:
+        """"Current mood: cheerful. ..." for the system prompt; empty when off or neutral."""
+        label = self.current(now).label
+        if not self.enabled or label == "neutral":
+            return ""
+        return (f"Current mood: {label}. Let it colour your tone ({GUIDANCE[label]}) without getting in "
+                f"the way of helping.")
+
+
+_model: Optional[MoodModel] = None
+
+
+def get_mood_model(persona: Any = None) -> MoodModel:
+    """The session's mood model; a new one at rest when the persona changes."""
+    global _model
+    if _model is None or (persona is not None and getattr(persona, "name", None) != _model.persona):
+        _model = MoodModel(persona)
+    return _model
+
+
+def set_mood_model(model: Optional[MoodModel]) -> None:
+    global _model
+    _model = model
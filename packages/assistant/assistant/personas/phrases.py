@@ -0,0 +1,121 @@
+"""
+Phrase bank - Short, predictable things a persona says, without asking a model.
+
+Greetings, acknowledgments, apologies and "one moment" fillers don't need
+a language model: it costs a call and a second or two to produce what a
+persona could have written down once. Each persona can bring its own in
+phrases.yaml next to theme.yaml:
+
+    greeting:
+      - "Good {time_of_day}, {user}."
+      - text: "At your service, {user}."
+        weight: 2                     # Picked twice as often (default 1)
+    acknowledgment: ["Right away.", "Very good."]
+    apology: ["My apologies, I couldn't manage that just now."]
+    filler: ["One moment.", "Allow me a second."]
+
+Kinds it doesn't list fall back to DEFAULT_PHRASES. Placeholders are
+{user}, {persona} and {time_of_day}; a phrase needing one that isn't known
+(no user name yet) is skipped. Picks are weighted at random, and the last
+RECENT picks of a kind aren't repeated while there's another to choose.
+
+Where they're used: the welcome when there's no earlier conversation to
+mention and the "okay" when the user stops a long reply (dashboard.py),
+the reply when every AI fails (voice.py) and the filler past the latency
+budget (latency.py). Phrases without placeholders are also recorded ahead
+by the speech cache (speech_cache.common_phrases).
+"""
+
+import logging
+import random
+import string
+from collections import deque
+from dataclasses import dataclass
+from typing import Any, Deque, Dict, List, Mapping, Optional
+
+logger = logging.getLogger(__name__)
+
+KINDS = ("greeting", "acknowledgment", "apology", "filler")
+RECENT = 2  # Picks of a kind not repeated while there's another phrase
+
+DEFAULT_PHRASES: Dict[str, List[Any]] = {
+    "greeting": ["Good {time_of_day}, {user}.", "Good {time_of_day}. How can I help?",
+                 "Hello, I'm {persona}. How can I help you today?"],
+    "acknowledgment": ["Okay.", "Got it.", "On it.", "Done."],
+    "apology": ["Sorry, I couldn't answer that just now.", "Sorry, something went wrong. Could you try again?"],
+    "filler": ["Let me check.", "One moment.", "Just a second."],
+}
+
+
+@dataclass(frozen=True)
+class Phrase:
+    text: str
+    weight: float = 1.0
+
+    @property
+    def slots(self) -> List[str]:
+        return [name for _, name, _, _ in string.Formatter().parse(self.text) if name]
+
+
+def _phrases(entries: Any, kind: str) -> List[Phrase]:
+    phrases = []
+    for entry in entries if isinstance(entries, list) else [entries]:
+        if isinstance(entry, str):
+            phrases.append(Phrase(entry))
+        elif isinstance(entry, dict) and isinstance(entry.get("text"), str):
+            phrases.append(Phrase(entry["text"], max(0.0, float(entry.get("weight", 1.0)))))
+        else:
+            logger.warning(f"Skipping a {kind} phrase that is neither text nor text+weight: {entry!r}")
+    return phrases
+
+
+class PhraseBank:
+    """A persona's canned phrases by kind, over DEFAULT_PHRASES."""
+
+    def __init__(self, phrases: Optional[Mapping[str, Any]] = None, persona: str = "",
+                 rng: Optional[random.Random] = None):
+        phrases = dict(phrases or {})
+        unknown = sorted(set(phrases) - set(KINDS))
+        if unknown:
+            logger.warning(f"Ignoring unknown phrase kind(s) for {persona or 'the persona'}: {', '.join(unknown)}")
+        self.persona = persona
+        self.phrases = {kind: _phrases(phrases.get(kind, DEFAULT_PHRASES[kind]), kind) for kind in KINDS}
+        self.rng = rng or random.Random()
+        self._recent: Dict[str, Deque[str]] = {kind: deque(maxlen=RECENT) for kind in KINDS}
+
+    @classmethod
+    def from_persona(cls, persona) -> "PhraseBank":
+        return cls(getattr(persona, "phrases", None), getattr(persona, "name", ""))
+
+    def pick(self, kind: str, **slots: Optional[str]) -> Optional[str]:
+        """A phrase of `kind` filled in, or None when none can be (unknown placeholders, or none at all)."""
+        slots = {name: value for name, value in {"persona": self.persona, **slots}.items() if value}
+        usable = [phrase for phrase in self.phrases[kind]
+                  if phrase.weight > 0 and all(name in slots for name in phrase.slots)]
+        fresh = [phrase for phrase in usable if phrase.text not in self._recent[kind]]
+        choices = fresh or usable
+        if not choices:
+            return None
+        phrase = self.rng.choices(choices, weights=[phrase.weight for phrase in choices])[0]
+        self._recent[kind].append(phrase.text)
+        return phrase.text.format(**slots)
+
+    def cacheable(self) -> List[str]:
+        """Phrases that always read the same (no placeholders), for the speech cache to record."""
+        return [phrase.text for kind in KINDS for phrase in self.phrases[kind] if not phrase.slots]
+
+
+_bank: Optional[PhraseBank] = None
+
+
+def get_phrase_bank() -> PhraseBank:
+    """The current persona's bank (set by PersonaManager.set_current_persona), or only the defaults."""
+    global _bank
+    if _bank is None:
+        _bank = PhraseBank()
+    return _bank
+
+
+def set_phrase_bank(bank: Optional[PhraseBank]) -> None:
+    global _bank
+    _bank = bank
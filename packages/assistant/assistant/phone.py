@@ -24,6 +24,14 @@ from .personas.config import PersonaConfig
 from .personas.manager import PersonaManager
 from .memory import MemoryManager
 from .voice import MoshiBridge
+from .privacy import get_privacy_monitor
+from .prosody import POLLY_NEURAL, render
+from .quota import get_quota_manager
+from .speech_filter import get_speech_filter
+from .rate_limit import ClientGuard, ListenerLimits
+from .opus_transport import SAMPLE_RATE as OPUS_RATE, OpusStream, OpusUnavailable
+from .webhook_signing import WebhookVerifier
+from .ws_framing import FrameError, FrameSession, decode_frames
 
 # ==============================================================================
 # AUDIO CONVERTER
@@ -94,6 +102,16 @@ def pcm24k_to_mulaw(pcm_24k_float32: np.ndarray) -> str:
     return mulaw_base64
 
 
+def pcm16_to_float(pcm: bytes) -> np.ndarray:
+    """16-bit PCM bytes (decoded Opus) to float32 [-1.0, 1.0] for Moshi."""
+    return np.frombuffer(pcm, dtype=np.int16).astype(np.float32) / 32768.0
+
+
+def float_to_pcm16(audio: np.ndarray) -> bytes:
+    """Moshi's float32 audio to 16-bit PCM bytes (for the Opus encoder)."""
+    return (np.clip(audio, -1.0, 1.0) * 32767.0).astype(np.int16).tobytes()
+
+
 def get_audio_stats(audio: np.ndarray) -> dict:
     """Get audio statistics for debugging."""
     return {
@@ -183,6 +201,8 @@ class OutboundCaller:
             "Marvin": "Polly.Brian-Neural",  # British English, deadpan
         }
         voice = voice_map.get(persona.name, "Polly.Matthew-Neural")
+        # Polly takes SSML inside <Say>: pauses and spelled-out codes, the rest escaped (see prosody.py)
+        message = render(get_speech_filter().apply(message, persona, "call"), POLLY_NEURAL)
 
         # Start TwiML
         twiml = f"""<?xml version="1.0" encoding="UTF-8"?>
@@ -193,7 +213,7 @@ class OutboundCaller:
         if questions:
             for i, question in enumerate(questions):
                 twiml += f"""
-    <Say voice="{voice}">{question}</Say>
+    <Say voice="{voice}">{render(question, POLLY_NEURAL)}</Say>
     <Record maxLength="30" timeout="3" transcribe="true" recordingStatusCallback="/api/twilio/recording-callback" />"""
 
         # End call
@@ -419,7 +439,21 @@ class TwilioVoiceBridge:
             Base64-encoded mulaw 8kHz response audio (if any), else None
         """
         # Convert Twilio audio to Moshi format
-        pcm_24k = mulaw_to_pcm24k(mulaw_base64)
+        response_audio = await self.process_pcm(mulaw_to_pcm24k(mulaw_base64))
+        if response_audio is None:
+            return None
+        # Convert to mulaw and return immediately
+        mulaw_response = pcm24k_to_mulaw(response_audio)
+        logger.debug(f"[TwilioVoiceBridge] Sending {len(mulaw_response)} bytes back to Twilio")
+        return mulaw_response
+
+    async def process_pcm(self, pcm_24k: np.ndarray) -> Optional[np.ndarray]:
+        """
+        Process incoming 24kHz float32 audio (Twilio's after conversion, or an Opus client's).
+
+        Returns:
+            Moshi's 24kHz float32 response audio (if any), else None
+        """
         logger.debug(f"[TwilioVoiceBridge] Received {len(pcm_24k)} PCM samples")
 
         # Add to buffer
@@ -447,10 +481,7 @@ class TwilioVoiceBridge:
                 logger.debug(f"[TwilioVoiceBridge] Moshi text: '{text_piece}'")
                 # Accumulate text (could be used for real-time transcript)
                 pass
-            # Convert to mulaw and return immediately
-            mulaw_response = pcm24k_to_mulaw(response_audio)
-            logger.debug(f"[TwilioVoiceBridge] Sending {len(mulaw_response)} bytes back to Twilio")
-            return mulaw_response
+            return response_audio
         else:
             logger.debug(f"[TwilioVoiceBridge] Moshi returned no audio (still listening)")
         return None
@@ -477,9 +508,14 @@ class TwilioVoiceBridge:
         return np.concatenate(result) if result else np.array([], dtype=np.float32)
 
     async def generate_and_send_greeting(self) -> str:
-        """Generate Moshi's initial greeting."""
+        """Generate Moshi's initial greeting (base64 mulaw for Twilio)."""
+        greeting_audio = await self.generate_greeting_pcm()
+        return pcm24k_to_mulaw(greeting_audio) if greeting_audio is not None else ""
+
+    async def generate_greeting_pcm(self) -> Optional[np.ndarray]:
+        """Generate Moshi's initial greeting as 24kHz float32 audio (None once it's been said)."""
         if self._greeting_sent:
-            return ""
+            return None
         self._greeting_sent = True
         # Get current persona
         persona = self.persona_manager.get_current_persona()
@@ -504,9 +540,7 @@ class TwilioVoiceBridge:
             "timestamp": time.time(),
         })
         self._set_state("listening")
-        # Convert to mulaw
-        mulaw_greeting = pcm24k_to_mulaw(greeting_audio)
-        return mulaw_greeting
+        return greeting_audio
 
     def _set_state(self, new_state: str):
         """Update state and notify callback."""
@@ -550,6 +584,12 @@ class MediaStreamsServer:
     - Session management (multiple concurrent calls)
     - Message routing (start, media, stop, mark, dtmf)
     - Integration with TwilioVoiceBridge
+    - Connection, payload size and message rate limits (see rate_limit.py)
+    - permessage-deflate and, for clients that offer them, binary audio
+      frames instead of base64 "media" events (see ws_framing.py), carrying
+      24kHz Opus when offered too (see opus_transport.py)
+    - Signed handshakes only: Twilio's signature or an xSwarm-signed one, and
+      each CallSid once (see webhook_signing.py); anything else gets a 401
     """
 
     def __init__(
@@ -557,6 +597,11 @@ class MediaStreamsServer:
         host: str = "0.0.0.0",
         port: int = 5000,
         bridge_factory: Optional[Callable] = None,
+        limits: Optional[ListenerLimits] = None,
+        on_violation: Optional[Callable[[str], None]] = None,
+        compression: Optional[str] = "deflate",
+        config=None,
+        verifier: Optional[WebhookVerifier] = None,
     ):
         """
         Initialize Media Streams server.
@@ -566,10 +611,20 @@ class MediaStreamsServer:
             port: Server port (default: 5000)
             bridge_factory: Function to create TwilioVoiceBridge per call
                            Signature: async def factory(call_sid, from_number, to_number) -> TwilioVoiceBridge
+            limits: Connection/size/rate limits (default: ListenerLimits())
+            on_violation: Called with a message when a client hits a limit (e.g. the activity feed)
+            compression: "deflate" to offer permessage-deflate, None for plain frames
+            config: Opus bitrate and jitter buffer (config.opus_bitrate, config.opus_jitter_ms), and
+                    the public URL Twilio signs (config.voice_tunnel_url)
+            verifier: Handshake signature checks (default: WebhookVerifier.from_config(config))
         """
         self.host = host
         self.port = port
         self.bridge_factory = bridge_factory
+        self.guard = ClientGuard("Media Streams", limits, on_violation)
+        self.compression = compression
+        self.config = config
+        self.verifier = verifier or WebhookVerifier.from_config(config)
 
         # Active sessions (call_sid -> bridge)
         self._sessions: Dict[str, TwilioVoiceBridge] = {}
@@ -577,11 +632,18 @@ class MediaStreamsServer:
         # Stream metadata (stream_sid -> call_sid)
         self._streams: Dict[str, str] = {}
 
+        # When each call started (call_sid -> monotonic time), for the phone-minute quota
+        self._started: Dict[str, float] = {}
+
     async def start(self):
         """Start WebSocket server."""
         logger.info(f"Twilio Media Streams server starting on ws://{self.host}:{self.port}")
 
-        async with websockets.serve(self.handle_connection, self.host, self.port):
+        # max_size makes the websockets library reject oversized frames before we parse them,
+        # and process_request refuses unsigned handshakes before a connection exists
+        async with websockets.serve(self.handle_connection, self.host, self.port,
+                                    max_size=self.guard.limits.max_message_bytes, compression=self.compression,
+                                    process_request=self.verifier.process_request):
             logger.info(f"Server ready - waiting for connections...")
             await asyncio.Future()  # Run forever
 
@@ -593,28 +655,90 @@ class MediaStreamsServer:
 
         logger.debug(f"[MediaStreams] New connection from {websocket.remote_address}")
 
+        remote = websocket.remote_address
+        client = str(remote[0] if isinstance(remote, tuple) else remote)
+        if not self.guard.admit(client):
+            await websocket.close(code=1013, reason="Too many connections")
+            return
+        framing = FrameSession("phone", websocket)
+        opus: Optional[OpusStream] = None
+
         try:
             async for message in websocket:
+                verdict = self.guard.check_message(client, id(websocket), len(message))
+                if verdict == "disconnect":
+                    await websocket.close(code=1008, reason="Rate limit exceeded")
+                    break
+                if verdict == "drop":
+                    continue
+                framing.received(message)
+
+                if isinstance(message, bytes) and framing.audio:
+                    # Audio as binary frames, from a client that negotiated them (see ws_framing.py)
+                    try:
+                        frames = decode_frames(message)
+                    except FrameError as e:
+                        logger.warning(f"[MediaStreams] Bad audio frame: {e}")
+                        continue
+                    for frame in frames:
+                        if opus and (frame.codec, frame.sample_rate) == ("opus", OPUS_RATE):
+                            for pcm in opus.receive(frame.sequence, frame.payload):
+                                if bridge:
+                                    response = await bridge.process_pcm(pcm16_to_float(pcm))
+                                    if response is not None:
+                                        await self.send_opus(websocket, opus, framing, response)
+                        elif (frame.codec, frame.sample_rate) != ("mulaw", 8000):
+                            logger.warning(f"[MediaStreams] Calls take 8kHz mulaw or 24kHz Opus, not "
+                                           f"{frame.codec}/{frame.sample_rate}")
+                        elif bridge:
+                            payload = base64.b64encode(frame.payload).decode("ascii")
+                            await self._handle_media({"media": {"payload": payload}}, bridge, websocket, stream_sid,
+                                                     framing)
+                    continue
+
                 try:
                     data = json.loads(message)
                     event = data.get("event")
 
-                    if event == "start":
+                    if event == "connected" and "frames" in data:
+                        # Not Twilio: a client offering binary audio frames
+                        formats = framing.accept(data["frames"])
+                        if framing.opus:
+                            try:
+                                opus = OpusStream.from_config(self.config)
+                            except OpusUnavailable as e:
+                                logger.warning(f"[MediaStreams] {e}")
+                                formats = framing.accept(data["frames"], supported=("audio-v1",))
+                        reply = json.dumps({"event": "frames", "frames": formats})
+                        await websocket.send(reply)
+                        framing.sent(reply)
+
+                    elif event == "start":
+                        # A signed handshake replayed: its call has already streamed
+                        if not self.verifier.first_call(str(data.get("start", {}).get("callSid"))):
+                            logger.warning("[MediaStreams] Call already streamed - replayed handshake refused")
+                            await websocket.close(code=1008, reason="Call already streamed")
+                            break
                         # Initialize call session
                         stream_sid, call_sid, bridge = await self._handle_start(data, websocket)
                         # Send greeting to test audio playback
                         logger.debug("[MediaStreams] Generating greeting...")
+                        if opus:
+                            greeting_pcm = await bridge.generate_greeting_pcm()
+                            if greeting_pcm is not None:
+                                await self.send_opus(websocket, opus, framing, greeting_pcm)
+                            continue
                         greeting_audio = await bridge.generate_and_send_greeting()
                         if greeting_audio:
                             logger.debug(f"[MediaStreams] Sending greeting audio ({len(greeting_audio)} bytes)")
-                            await self.send_audio(websocket, stream_sid, greeting_audio)
+                            await self.send_audio(websocket, stream_sid, greeting_audio, framing)
                         else:
                             logger.debug("[MediaStreams] No greeting audio generated")
 
                     elif event == "media":
                         # Process audio chunk
                         if bridge:
-                            await self._handle_media(data, bridge, websocket, stream_sid)
+                            await self._handle_media(data, bridge, websocket, stream_sid, framing)
 
                     elif event == "stop":
                         # End call session
@@ -637,6 +761,11 @@ class MediaStreamsServer:
 
         finally:
             # Cleanup on disconnect
+            self.guard.release(client, id(websocket))
+            if opus:
+                logger.info(f"[MediaStreams] Opus stream {stream_sid}: {opus.jitter.stats}, "
+                            f"codec {opus.codec.times.summary()}")
+                get_privacy_monitor().end_egress(f"opus:{id(websocket)}")
             if bridge:
                 await bridge.cleanup()
 
@@ -645,6 +774,8 @@ class MediaStreamsServer:
 
             if stream_sid and stream_sid in self._streams:
                 del self._streams[stream_sid]
+            if stream_sid:
+                get_privacy_monitor().end_egress(f"twilio:{stream_sid}")
 
     async def _handle_start(self, data: dict, websocket) -> tuple:
         """Handle 'start' message from Twilio."""
@@ -669,9 +800,11 @@ class MediaStreamsServer:
         # Store session
         self._sessions[call_sid] = bridge
         self._streams[stream_sid] = call_sid
+        self._started[call_sid] = time.monotonic()
         return stream_sid, call_sid, bridge
 
-    async def _handle_media(self, data: dict, bridge: TwilioVoiceBridge, websocket, stream_sid: str):
+    async def _handle_media(self, data: dict, bridge: TwilioVoiceBridge, websocket, stream_sid: str,
+                            framing: Optional[FrameSession] = None):
         """Handle 'media' message from Twilio."""
         media_data = data.get("media", {})
         payload = media_data.get("payload")  # Base64 mulaw audio
@@ -684,7 +817,7 @@ class MediaStreamsServer:
 
         # If bridge has a response, send it back
         if response_payload:
-            await self.send_audio(websocket, stream_sid, response_payload)
+            await self.send_audio(websocket, stream_sid, response_payload, framing)
 
     async def _handle_stop(self, data: dict, bridge: Optional[TwilioVoiceBridge]):
         """Handle 'stop' message from Twilio."""
@@ -693,12 +826,22 @@ class MediaStreamsServer:
 
         logger.info(f"[MediaStreams] Call ended: {call_sid}")
 
+        started = self._started.pop(call_sid, None)
+        quota = get_quota_manager()
+        if started is not None and quota is not None:
+            warning = quota.record_call(time.monotonic() - started)
+            if warning:
+                logger.warning(f"[MediaStreams] {warning}")
+
         if bridge:
             transcript = bridge.get_transcript()
             logger.debug(f"[MediaStreams] Transcript: {len(transcript)} messages")
 
-    async def send_audio(self, websocket, stream_sid: str, audio_payload: str):
-        """Send audio back to Twilio."""
+    async def send_audio(self, websocket, stream_sid: str, audio_payload: str,
+                         framing: Optional[FrameSession] = None):
+        """Send audio back to Twilio (or as a binary frame, to a client that negotiated them)."""
+        # The first chunk of each call puts an entry in the activity log (privacy.py)
+        get_privacy_monitor().report_egress(f"twilio:{stream_sid}", "Twilio", "phone call audio")
         message = {
             "event": "media",
             "streamSid": stream_sid,
@@ -707,8 +850,28 @@ class MediaStreamsServer:
             }
         }
 
+        text = json.dumps(message)
         try:
-            await websocket.send(json.dumps(message))
+            if framing and framing.audio:
+                frame = framing.audio_message("mulaw", 8000, base64.b64decode(audio_payload))
+                await websocket.send(frame)
+                framing.sent(frame, instead_of=len(text))
+            else:
+                await websocket.send(text)
+                if framing:
+                    framing.sent(text)
+        except Exception as e:
+            logger.error(f"[MediaStreams] Error sending audio: {e}")
+
+    async def send_opus(self, websocket, opus: OpusStream, framing: FrameSession, audio: np.ndarray):
+        """Send Moshi's audio to an Opus client as binary frames, one 20ms packet each."""
+        get_privacy_monitor().report_egress(f"opus:{id(websocket)}", "Media Streams client", "call audio")
+        raw_size = opus.codec.frame_samples * 4  # What the frame would be as raw float32
+        try:
+            for packet in opus.send(float_to_pcm16(audio)):
+                frame = framing.audio_message("opus", OPUS_RATE, packet)
+                await websocket.send(frame)
+                framing.sent(frame, instead_of=raw_size)
         except Exception as e:
             logger.error(f"[MediaStreams] Error sending audio: {e}")
 
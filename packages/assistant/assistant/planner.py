@@ -16,6 +16,15 @@ from pathlib import Path
 from typing import Optional, List, Dict, Any
 from uuid import uuid4
 
+from .categories import has_tag
+from .migrations import Migration, MigrationFailed, SchemaTooNew, latest_version, migrate_json
+from .calendar_core import (
+    OCCURRENCE_FIELDS, apply_exception, expand_recurrence, occurrence_id, split_occurrence_id,
+)
+from .holidays import get_holiday_calendar
+from .timezones import to_display
+from .verbalize import verbalize_time
+
 logger = logging.getLogger(__name__)
 
 
@@ -175,6 +184,7 @@ class Task:
     completed_at: Optional[str] = None  # ISO datetime when completed
     notes: str = ""
     created_at: str = ""
+    tags: List[str] = field(default_factory=list)  # Categories, e.g. ["work"] (see categories.py)
     # GTD tracking fields
     defer_count: int = 0  # Times task was bumped/deferred
     last_scheduled: Optional[str] = None  # YYYY-MM-DD when last scheduled
@@ -276,6 +286,7 @@ class Commitment:
     status: str = "pending"
     project_id: Optional[str] = None
     created_at: str = ""
+    tags: List[str] = field(default_factory=list)
 
     def __post_init__(self):
         if not self.created_at:
@@ -305,6 +316,27 @@ class DailyFocus:
     notes: str = ""
 
 
+@dataclass
+class TimeBlock:
+    """A task pinned to a time on a given day (planned in the evening review)."""
+    task_id: str
+    title: str
+    start: str  # HH:MM
+    minutes: int = 30
+
+
+@dataclass
+class JournalEntry:
+    """A short end-of-day note, recorded by the evening review."""
+    date: str  # YYYY-MM-DD
+    text: str
+    created_at: str = ""
+
+    def __post_init__(self):
+        if not self.created_at:
+            self.created_at = datetime.now().isoformat()
+
+
 class RecurrenceType(str, Enum):
     """Type of event recurrence."""
     NONE = "none"
@@ -315,6 +347,9 @@ class RecurrenceType(str, Enum):
     YEARLY = "yearly"
 
 
+RSVP_ICONS = {"accepted": "✓", "declined": "✗", "tentative": "?", "pending": "…"}
+
+
 @dataclass
 class CalendarEvent:
     """A calendar event or meeting."""
@@ -330,6 +365,20 @@ class CalendarEvent:
     reminder_minutes: int = 15  # Minutes before event to remind
     project_id: Optional[str] = None
     created_at: str = ""
+    # Attendee -> RSVP status (pending, accepted, declined, tentative), synced from the server
+    rsvp: Dict[str, str] = field(default_factory=dict)
+    tags: List[str] = field(default_factory=list)
+    # Coordinates of location, filled in by the geocode_locations job (geocoding.py)
+    latitude: Optional[float] = None
+    longitude: Optional[float] = None
+    # Shown as busy: spoken announcements wait until it's over (notifications.py); False is "show as free"
+    busy: bool = True
+    # What outside integrations see: public, busy (the time only) or private; "" is config.shared_visibility
+    visibility: str = ""
+    # Recurring: leave out occurrences due on a day off (holidays.py), e.g. for a work standup
+    skip_holidays: bool = False
+    # Recurring: date an occurrence was due -> {"cancelled": True} or the fields changed for it (calendar_core.py)
+    exceptions: Dict[str, Dict[str, Any]] = field(default_factory=dict)
     # Fields for recurring instances (not persisted, set during expansion)
     _is_recurring_instance: bool = False
     _original_id: Optional[str] = None
@@ -338,11 +387,31 @@ class CalendarEvent:
         if not self.created_at:
             self.created_at = datetime.now().isoformat()
 
+    def participant_summary(self) -> str:
+        """e.g. "with Sarah ✓, Bob ?" - empty when there are no attendees."""
+        if not self.attendees:
+            return ""
+        names = [f"{a} {RSVP_ICONS[self.rsvp[a]]}" if self.rsvp.get(a) in RSVP_ICONS else a for a in self.attendees]
+        return "with " + ", ".join(names)
+
 
 # ==============================================================================
 # PLANNER DATA (Persistence Layer)
 # ==============================================================================
 
+def _fill_missing_lists(data: Dict) -> None:
+    """Files from before calendar events/commitments/ideas existed lack their lists."""
+    for key in ("projects", "habits", "goals", "tasks", "commitments", "ideas", "calendar_events"):
+        if not isinstance(data.get(key), list):
+            data[key] = []
+
+
+# Format changes to planner.json, oldest first (see migrations.py)
+PLANNER_MIGRATIONS = [
+    Migration(1, "every item list present", _fill_missing_lists),
+]
+
+
 class PlannerData:
     """
     Single-file persistent storage for planning data.
@@ -356,6 +425,9 @@ class PlannerData:
         self.storage_dir = storage_dir or self.DEFAULT_DIR
         self.storage_dir.mkdir(parents=True, exist_ok=True)
         self._data: Optional[Dict] = None
+        self.migrated: List[Migration] = []  # Format upgrades applied when the file was loaded
+        self.revision = 0  # Bumped by every save and every load of a changed file (appointment_cache.py)
+        self._loaded_at: Optional[str] = None  # updated_at of the data last loaded or saved
 
     def _planner_path(self) -> Path:
         """Get path to planner file."""
@@ -373,8 +445,16 @@ class PlannerData:
 
         try:
             with open(path, 'r', encoding='utf-8') as f:
-                self._data = json.load(f)
-                return self._data
+                data = json.load(f)
+            self._data, self.migrated = migrate_json(data, path, "planner", PLANNER_MIGRATIONS)
+            if self._data.get("updated_at") != self._loaded_at:
+                self._loaded_at = self._data.get("updated_at")
+                self.revision += 1
+            if self.migrated:
+                self._save()
+            return self._data
+        except (SchemaTooNew, MigrationFailed):
+            raise  # Never fall back to empty data that would then be saved over it
         except Exception as e:
             logger.warning(f"Failed to load planner data: {e}")
             self._data = self._default_data()
@@ -391,7 +471,8 @@ class PlannerData:
             return
 
         try:
-            self._data["updated_at"] = datetime.now().isoformat()
+            self._data["updated_at"] = self._loaded_at = datetime.now().isoformat()
+            self.revision += 1
             with open(self._planner_path(), 'w', encoding='utf-8') as f:
                 json.dump(self._data, f, indent=2, ensure_ascii=False)
         except Exception as e:
@@ -401,6 +482,7 @@ class PlannerData:
         """Default empty planner structure."""
         return {
             "version": "1.2",
+            "schema_version": latest_version(PLANNER_MIGRATIONS),
             "created_at": datetime.now().isoformat(),
             "updated_at": datetime.now().isoformat(),
             "last_daily_planning": None,
@@ -791,14 +873,7 @@ class PlannerData:
 
     def delete_goal(self, goal_id: str) -> bool:
         """Delete a goal by ID."""
-        data = self._load()
-        goals = data.get("goals", [])
-        original_len = len(goals)
-        data["goals"] = [g for g in goals if g["id"] != goal_id]
-        if len(data["goals"]) < original_len:
-            self._save()
-            return True
-        return False
+        return self._soft_delete("goals", goal_id)
 
     def get_goal_progress_visual(self, goal_id: str) -> Dict[str, Any]:
         """
@@ -854,7 +929,8 @@ class PlannerData:
         self,
         status: Optional[str] = None,
         context: Optional[str] = None,
-        project_id: Optional[str] = None
+        project_id: Optional[str] = None,
+        tag: Optional[str] = None
     ) -> List[Task]:
         """Get tasks with optional filters."""
         data = self._load()
@@ -862,6 +938,8 @@ class PlannerData:
 
         if status:
             tasks = [t for t in tasks if t.status == status]
+        if tag:
+            tasks = [t for t in tasks if has_tag(t.tags, tag)]
         if context:
             tasks = [t for t in tasks if context in t.contexts]
         if project_id:
@@ -1011,7 +1089,8 @@ class PlannerData:
         scheduled_time: Optional[str] = None,
         status: str = "inbox",
         notes: str = "",
-        auto_schedule: bool = True
+        auto_schedule: bool = True,
+        tags: Optional[List[str]] = None
     ) -> Task:
         """
         Add a new task. Auto-schedules by default.
@@ -1042,7 +1121,8 @@ class PlannerData:
             due_date=due_date,
             scheduled_time=scheduled_time,
             status=status,
-            notes=notes
+            notes=notes,
+            tags=tags or []
         )
         data["tasks"].append(asdict(task))
         self._save()
@@ -1146,7 +1226,14 @@ class PlannerData:
                 if task.get("scheduled_time"):
                     task["scheduled_time"] = None
                     tasks_cleared += 1
-        if tasks_cleared > 0:
+        # Tasks planned for today in last night's review keep their times
+        open_tasks = {t["id"]: t for t in data["tasks"] if t.get("status") not in ["complete", "someday"]}
+        pinned = 0
+        for block in self.get_day_plan(today_str):
+            if block.task_id in open_tasks:
+                open_tasks[block.task_id].update(scheduled_time=block.start, status="scheduled")
+                pinned += 1
+        if tasks_cleared > 0 or pinned > 0:
             self._save()
             self.reload()  # Reload after clearing
 
@@ -1397,13 +1484,15 @@ class PlannerData:
     # COMMITMENTS CRUD
     # ==========================================================================
 
-    def get_commitments(self, include_completed: bool = False) -> List[Commitment]:
+    def get_commitments(self, include_completed: bool = False, tag: Optional[str] = None) -> List[Commitment]:
         """Get commitments."""
         data = self._load()
         commitments = [Commitment(**c) for c in data["commitments"]]
 
         if not include_completed:
             commitments = [c for c in commitments if c.status == "pending"]
+        if tag:
+            commitments = [c for c in commitments if has_tag(c.tags, tag)]
 
         return commitments
 
@@ -1412,7 +1501,8 @@ class PlannerData:
         description: str,
         to_whom: str,
         deadline: str,
-        project_id: Optional[str] = None
+        project_id: Optional[str] = None,
+        tags: Optional[List[str]] = None
     ) -> Commitment:
         """Add a commitment."""
         data = self._load()
@@ -1421,7 +1511,8 @@ class PlannerData:
             description=description,
             to_whom=to_whom,
             deadline=deadline,
-            project_id=project_id
+            project_id=project_id,
+            tags=tags or []
         )
         data["commitments"].append(asdict(commitment))
         self._save()
@@ -1495,13 +1586,7 @@ class PlannerData:
 
     def delete_idea(self, idea_id: str) -> bool:
         """Delete an idea by ID."""
-        data = self._load()
-        original_len = len(data["ideas"])
-        data["ideas"] = [i for i in data["ideas"] if i["id"] != idea_id]
-        if len(data["ideas"]) < original_len:
-            self._save()
-            return True
-        return False
+        return self._soft_delete("ideas", idea_id)
 
     # ==========================================================================
     # CALENDAR EVENTS CRUD
@@ -1517,101 +1602,16 @@ class PlannerData:
         Expand a recurring event into instances within the date range.
         Returns list of event dicts (virtual instances with modified start/end times).
         """
-        recurrence = event.get("recurrence", "none")
-        if recurrence == "none":
-            return []
-
-        instances = []
-        event_start = datetime.fromisoformat(event["start_time"])
-        event_end = datetime.fromisoformat(event["end_time"])
-        duration = event_end - event_start
-
-        # Determine recurrence end date
-        recurrence_end_str = event.get("recurrence_end")
-        if recurrence_end_str:
-            recurrence_end = date.fromisoformat(recurrence_end_str)
-        else:
-            # Default: 1 year from original event if no end specified
-            recurrence_end = event_start.date() + timedelta(days=365)
-
-        # Parse query range
-        query_start = date.fromisoformat(start_date)
-        query_end = date.fromisoformat(end_date)
-
-        # Determine delta based on recurrence type
-        if recurrence == "daily":
-            delta = timedelta(days=1)
-        elif recurrence == "weekly":
-            delta = timedelta(weeks=1)
-        elif recurrence == "biweekly":
-            delta = timedelta(weeks=2)
-        elif recurrence == "monthly":
-            delta = None  # Handle monthly specially
-        elif recurrence == "yearly":
-            delta = None  # Handle yearly specially
-        else:
-            return []
-
-        # Generate instances
-        current = event_start
-        iteration = 0
-        max_iterations = 1000  # Safety limit
-
-        while iteration < max_iterations:
-            iteration += 1
-            current_date = current.date()
-
-            # Stop if past recurrence end
-            if current_date > recurrence_end:
-                break
-
-            # Stop if past query range
-            if current_date > query_end:
-                break
-
-            # Include if within query range AND not the original event date
-            original_date = event_start.date()
-            if query_start <= current_date <= query_end and current_date != original_date:
-                # Create virtual instance
-                instance = event.copy()
-                instance["start_time"] = current.isoformat()
-                instance["end_time"] = (current + duration).isoformat()
-                # Mark as recurring instance (useful for UI)
-                instance["_is_recurring_instance"] = True
-                instance["_original_id"] = event["id"]
-                instances.append(instance)
-
-            # Advance to next occurrence
-            if delta:
-                current = current + delta
-            elif recurrence == "monthly":
-                # Add one month, preserving day of month
-                month = current.month + 1
-                year = current.year
-                if month > 12:
-                    month = 1
-                    year += 1
-                # Handle month overflow (e.g., Jan 31 -> Feb 28)
-                day = min(current.day, 28)  # Safe default
-                try:
-                    current = current.replace(year=year, month=month, day=current.day)
-                except ValueError:
-                    current = current.replace(year=year, month=month, day=day)
-            elif recurrence == "yearly":
-                # Add one year
-                try:
-                    current = current.replace(year=current.year + 1)
-                except ValueError:
-                    # Feb 29 on non-leap year
-                    current = current.replace(year=current.year + 1, day=28)
-
-        return instances
+        holidays = get_holiday_calendar().days_off(date.fromisoformat(start_date), date.fromisoformat(end_date)) \
+            if event.get("skip_holidays") else ()
+        return expand_recurrence(event, start_date, end_date, holidays)
 
     def get_calendar_events(
         self,
         start_date: Optional[str] = None,
         end_date: Optional[str] = None,
-        expand_recurring: bool = True
+        expand_recurring: bool = True,
+        tag: Optional[str] = None
     ) -> List[CalendarEvent]:
         """
         Get calendar events, optionally filtered by date range.
@@ -1628,17 +1628,23 @@ class PlannerData:
             end_date = (date.today() + timedelta(days=30)).isoformat()
 
         for e in events:
-            event_date = e["start_time"][:10]
+            if not has_tag(e.get("tags"), tag):
+                continue
+            recurring = e.get("recurrence", "none") != "none"
+            # A recurring event is also its own first occurrence, which can have an exception
+            first = apply_exception(e, e.get("exceptions", {}).get(e["start_time"][:10])) if (
+                expand_recurring and recurring) else e
+            event_date = first["start_time"][:10] if first else ""
 
             # Check if original event is in range
-            in_range = True
+            in_range = first is not None
             if start_date and event_date < start_date:
                 in_range = False
             if end_date and event_date > end_date:
                 in_range = False
 
             if in_range:
-                result.append(e)
+                result.append(first)
 
             # Expand recurring events if requested
             if expand_recurring and e.get("recurrence", "none") != "none":
@@ -1651,13 +1657,71 @@ class PlannerData:
         return [CalendarEvent(**e) for e in result]
 
     def get_calendar_event(self, event_id: str) -> Optional[CalendarEvent]:
-        """Get a specific calendar event by ID."""
+        """Get a specific calendar event by ID (an occurrence id gets that occurrence, see get_occurrence)."""
+        series_id, day = split_occurrence_id(event_id)
+        if day:
+            return self.get_occurrence(series_id, day)
         data = self._load()
         for e in data.get("calendar_events", []):
             if e["id"] == event_id:
                 return CalendarEvent(**e)
         return None
 
+    def _series(self, series_id: str) -> Optional[Dict[str, Any]]:
+        for e in self._load().get("calendar_events", []):
+            if e["id"] == series_id and e.get("recurrence", "none") != "none":
+                return e
+        return None
+
+    @staticmethod
+    def _due_occurrence(series: Dict[str, Any], day: str) -> Optional[Dict[str, Any]]:
+        """The occurrence of `series` due on `day` as the series has it (no exception), None if none is due."""
+        plain = {**series, "exceptions": {}}
+        if series["start_time"][:10] == day:
+            return {**plain, "id": occurrence_id(series["id"], day), "_is_recurring_instance": True,
+                    "_original_id": series["id"]}
+        found = expand_recurrence(plain, day, day)
+        return found[0] if found else None
+
+    def get_occurrence(self, series_id: str, day: str) -> Optional[CalendarEvent]:
+        """
+        The occurrence of a recurring event due on `day` (YYYY-MM-DD), with
+        any change made to it; None if the event doesn't repeat, nothing is
+        due that day or that occurrence was cancelled.
+        """
+        series = self._series(series_id)
+        occurrence = self._due_occurrence(series, day) if series else None
+        if occurrence is None:
+            return None
+        occurrence = apply_exception(occurrence, series.get("exceptions", {}).get(day))
+        return CalendarEvent(**occurrence) if occurrence else None
+
+    def update_occurrence(self, series_id: str, day: str, **updates) -> Optional[CalendarEvent]:
+        """Change one occurrence of a recurring event (OCCURRENCE_FIELDS), leaving the rest of the series."""
+        series = self._series(series_id)
+        occurrence = self._due_occurrence(series, day) if series else None
+        exception = series.get("exceptions", {}).get(day, {}) if series else {}
+        if occurrence is None or exception.get("cancelled"):
+            return None
+        exception = {**exception, **{k: v for k, v in updates.items() if k in OCCURRENCE_FIELDS and v is not None}}
+        exception = {k: v for k, v in exception.items() if occurrence.get(k) != v}  # Only what differs
+        exceptions = series.setdefault("exceptions", {})
+        if exception:
+            exceptions[day] = exception
+        else:
+            exceptions.pop(day, None)
+        self._save()
+        return CalendarEvent(**apply_exception(occurrence, exception))
+
+    def cancel_occurrence(self, series_id: str, day: str) -> bool:
+        """Skip one occurrence of a recurring event; False if none is due on `day`."""
+        series = self._series(series_id)
+        if series is None or self._due_occurrence(series, day) is None:
+            return False
+        series.setdefault("exceptions", {})[day] = {"cancelled": True}
+        self._save()
+        return True
+
     def add_calendar_event(
         self,
         title: str,
@@ -1669,7 +1733,11 @@ class PlannerData:
         recurrence: str = "none",
         recurrence_end: Optional[str] = None,
         reminder_minutes: int = 15,
-        project_id: Optional[str] = None
+        project_id: Optional[str] = None,
+        tags: Optional[List[str]] = None,
+        busy: bool = True,
+        visibility: str = "",
+        skip_holidays: bool = False
     ) -> CalendarEvent:
         """Add a new calendar event."""
         data = self._load()
@@ -1687,7 +1755,11 @@ class PlannerData:
             recurrence=recurrence,
             recurrence_end=recurrence_end,
             reminder_minutes=reminder_minutes,
-            project_id=project_id
+            project_id=project_id,
+            tags=tags or [],
+            busy=busy,
+            visibility=visibility,
+            skip_holidays=skip_holidays
         )
         data["calendar_events"].append(asdict(event))
         self._save()
@@ -1696,41 +1768,70 @@ class PlannerData:
     def update_calendar_event(
         self, event_id: str, **updates
     ) -> Optional[CalendarEvent]:
-        """Update a calendar event."""
+        """Update a calendar event (an occurrence id changes just that occurrence)."""
+        series_id, day = split_occurrence_id(event_id)
+        if day:
+            return self.update_occurrence(series_id, day, **updates)
         data = self._load()
         for e in data.get("calendar_events", []):
             if e["id"] == event_id:
+                # Exceptions are keyed by the dates occurrences were due: a new pattern leaves them meaningless
+                if ((updates.get("recurrence") or e.get("recurrence")) != e.get("recurrence")
+                        or (updates.get("start_time") or e["start_time"])[:10] != e["start_time"][:10]):
+                    e["exceptions"] = {}
+                # A new location needs geocoding again
+                if updates.get("location") is not None and updates["location"] != e.get("location"):
+                    e["latitude"] = e["longitude"] = None
                 for key, value in updates.items():
-                    if key in e and value is not None:
+                    # Fields added later (e.g. tags) may be missing from older events
+                    if key in CalendarEvent.__dataclass_fields__ and value is not None:
                         e[key] = value
                 self._save()
                 return CalendarEvent(**e)
         return None
 
-    def delete_calendar_event(self, event_id: str) -> bool:
-        """Delete a calendar event by ID."""
+    def set_event_coordinates(self, event_id: str, latitude: Optional[float],
+                              longitude: Optional[float]) -> Optional[CalendarEvent]:
+        """Store (or clear, with None) the coordinates of an event's location."""
         data = self._load()
-        events = data.get("calendar_events", [])
-        original_len = len(events)
-        data["calendar_events"] = [e for e in events if e["id"] != event_id]
-        if len(data["calendar_events"]) < original_len:
-            self._save()
-            return True
-        return False
+        for e in data.get("calendar_events", []):
+            if e["id"] == event_id:
+                e["latitude"], e["longitude"] = latitude, longitude
+                self._save()
+                return CalendarEvent(**e)
+        return None
+
+    def set_event_rsvps(self, event_id: str, rsvps: Dict[str, str]) -> Optional[CalendarEvent]:
+        """Record attendee RSVP statuses for an event (merged with existing ones)."""
+        data = self._load()
+        for e in data.get("calendar_events", []):
+            if e["id"] == event_id:
+                e.setdefault("rsvp", {}).update(rsvps)
+                self._save()
+                return CalendarEvent(**e)
+        return None
+
+    def delete_calendar_event(self, event_id: str) -> bool:
+        """Delete a calendar event by ID (an occurrence id cancels just that occurrence)."""
+        series_id, day = split_occurrence_id(event_id)
+        if day:
+            return self.cancel_occurrence(series_id, day)
+        return self._soft_delete("calendar_events", event_id)
 
-    def get_upcoming_events(self, days: int = 7) -> List[CalendarEvent]:
+    def get_upcoming_events(self, days: int = 7, tag: Optional[str] = None) -> List[CalendarEvent]:
         """Get events in the next N days."""
         today = date.today()
         end = today + timedelta(days=days)
         return self.get_calendar_events(
             start_date=today.isoformat(),
-            end_date=end.isoformat()
+            end_date=end.isoformat(),
+            tag=tag
         )
 
-    def get_todays_events(self) -> List[CalendarEvent]:
+    def get_todays_events(self, tag: Optional[str] = None) -> List[CalendarEvent]:
         """Get all events for today."""
         today = date.today().isoformat()
-        return self.get_calendar_events(start_date=today, end_date=today)
+        return self.get_calendar_events(start_date=today, end_date=today, tag=tag)
 
     # ==========================================================================
     # DELETE METHODS FOR OTHER ENTITIES
@@ -1738,42 +1839,68 @@ class PlannerData:
 
     def delete_task(self, task_id: str) -> bool:
         """Delete a task by ID."""
-        data = self._load()
-        original_len = len(data["tasks"])
-        data["tasks"] = [t for t in data["tasks"] if t["id"] != task_id]
-        if len(data["tasks"]) < original_len:
-            self._save()
-            return True
-        return False
+        return self._soft_delete("tasks", task_id)
 
     def delete_project(self, project_id: str) -> bool:
         """Delete a project by ID."""
-        data = self._load()
-        original_len = len(data["projects"])
-        data["projects"] = [p for p in data["projects"] if p["id"] != project_id]
-        if len(data["projects"]) < original_len:
-            self._save()
-            return True
-        return False
+        return self._soft_delete("projects", project_id)
 
     def delete_habit(self, habit_id: str) -> bool:
         """Delete a habit by ID."""
-        data = self._load()
-        original_len = len(data["habits"])
-        data["habits"] = [h for h in data["habits"] if h["id"] != habit_id]
-        if len(data["habits"]) < original_len:
-            self._save()
-            return True
-        return False
+        return self._soft_delete("habits", habit_id)
 
     def delete_commitment(self, commitment_id: str) -> bool:
         """Delete a commitment by ID."""
+        return self._soft_delete("commitments", commitment_id)
+
+    # ==========================================================================
+    # SOFT DELETE & UNDO SUPPORT (see undo.py)
+    # ==========================================================================
+
+    def _soft_delete(self, collection: str, item_id: str) -> bool:
+        """
+        Move an item into the trash instead of dropping it.
+
+        Trashed items are hidden everywhere but can be put back with
+        restore_deleted() until the undo window passes.
+        """
+        from .undo import UNDO_WINDOW
+
         data = self._load()
-        original_len = len(data["commitments"])
-        data["commitments"] = [c for c in data["commitments"] if c["id"] != commitment_id]
-        if len(data["commitments"]) < original_len:
-            self._save()
-            return True
+        items = data.get(collection, [])
+        for index, item in enumerate(items):
+            if item["id"] == item_id:
+                items.pop(index)
+                now = datetime.now()
+                trash = [d for d in data.get("trash", [])
+                         if now - datetime.fromisoformat(d["deleted_at"]) < UNDO_WINDOW]
+                trash.append({"collection": collection, "index": index, "item": item, "deleted_at": now.isoformat()})
+                data["trash"] = trash
+                self._save()
+                return True
+        return False
+
+    def restore_deleted(self, collection: str, item_id: str) -> Optional[Dict[str, Any]]:
+        """Put a soft-deleted item back where it was. Returns the item, or None if it's gone."""
+        data = self._load()
+        trash = data.get("trash", [])
+        for entry in trash:
+            if entry["collection"] == collection and entry["item"]["id"] == item_id:
+                trash.remove(entry)
+                items = data.setdefault(collection, [])
+                items.insert(min(entry["index"], len(items)), entry["item"])
+                self._save()
+                return entry["item"]
+        return None
+
+    def restore_fields(self, collection: str, item_id: str, fields: Dict[str, Any]) -> bool:
+        """Reset fields on an item (e.g. status before it was completed)."""
+        data = self._load()
+        for item in data.get(collection, []):
+            if item["id"] == item_id:
+                item.update(fields)
+                self._save()
+                return True
         return False
 
     def update_habit(self, habit_id: str, **updates) -> Optional[Habit]:
@@ -1820,6 +1947,35 @@ class PlannerData:
         data["last_review"] = datetime.now().isoformat()
         self._save()
 
+    def set_day_plan(self, day: str, blocks: List[TimeBlock]) -> None:
+        """Pin tasks to times on `day` (YYYY-MM-DD); auto-scheduling keeps them there."""
+        data = self._load()
+        plans = data.setdefault("day_plans", {})
+        # Only upcoming days are worth keeping
+        for old_day in [d for d in plans if d < date.today().isoformat()]:
+            del plans[old_day]
+        plans[day] = [asdict(b) for b in blocks]
+        self._save()
+
+    def get_day_plan(self, day: str) -> List[TimeBlock]:
+        """Time blocks planned for `day`, earliest first."""
+        blocks = self._load().get("day_plans", {}).get(day, [])
+        return sorted((TimeBlock(**b) for b in blocks), key=lambda b: b.start)
+
+    def add_journal_entry(self, text: str, day: Optional[str] = None) -> JournalEntry:
+        """Record a short journal note for `day` (default today)."""
+        data = self._load()
+        entry = JournalEntry(date=day or date.today().isoformat(), text=text.strip())
+        data.setdefault("journal", []).append(asdict(entry))
+        self._save()
+        return entry
+
+    def get_journal_entries(self, days: int = 7) -> List[JournalEntry]:
+        """Journal notes from the last `days` days, newest first."""
+        since = (date.today() - timedelta(days=days)).isoformat()
+        entries = [JournalEntry(**e) for e in self._load().get("journal", []) if e["date"] >= since]
+        return sorted(entries, key=lambda e: e.created_at, reverse=True)
+
     def set_daily_focus(
         self,
         priorities: List[str],
@@ -2191,6 +2347,29 @@ class PlanningSession:
             ""
         ]
 
+        # Today's calendar, with times worded the way they should be said
+        events = self.planner.get_calendar_events(summary["date"], summary["date"])
+        if events:
+            lines.append("**Today's calendar (say the times as written):**")
+            for event in events:
+                start = to_display(datetime.fromisoformat(event.start_time))
+                where = f" ({event.location})" if event.location else ""
+                lines.append(f"- {verbalize_time(start)}: {event.title}{where}")
+            lines.append("")
+
+        # Last night's evening review: the journal note and the blocks planned for today
+        yesterday = (date.fromisoformat(summary["date"]) - timedelta(days=1)).isoformat()
+        journal = [e for e in self.planner.get_journal_entries(days=1) if e.date == yesterday]
+        if journal:
+            lines.append(f"**Last night's journal:** {journal[0].text}")
+        blocks = self.planner.get_day_plan(summary["date"])
+        if blocks:
+            lines.append("**Planned last night:**")
+            for block in blocks:
+                lines.append(f"- {verbalize_time(datetime.strptime(block.start, '%H:%M'))}: {block.title}")
+        if journal or blocks:
+            lines.append("")
+
         # Projects needing attention
         if summary["projects_needing_attention"]:
             lines.append("**Projects needing attention:**")
@@ -2326,6 +2505,7 @@ class PlanningSession:
             "- Acknowledge deferrals without judgment",
             "- Confirm streak status - last chance to save them!",
             "- Briefly seed tomorrow (don't plan it)",
+            "- Offer the guided review (the user says \"evening review\") to carry tasks over and block out tomorrow",
             "",
             "After review, call the 'mark_review_done' tool.",
             "</planning_mode>"
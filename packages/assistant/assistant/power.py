@@ -0,0 +1,186 @@
+"""
+Power - A lighter performance profile on battery or when the machine runs hot.
+
+PowerManager samples the power source (psutil's battery sensor, on macOS
+and Linux) and thermal pressure:
+
+- macOS: `pmset -g therm` - a CPU speed limit under 100% means throttling
+- Linux: the hottest /sys/class/thermal zone
+
+and picks a profile. config.power_mode "auto" runs LOW_POWER on battery or
+under serious thermal pressure, PERFORMANCE otherwise; "performance" and
+"low_power" pin one. Leaving low power waits for RECOVER_SAMPLES calm
+samples in a row, so a laptop on the edge doesn't flap.
+
+What the low-power profile changes:
+
+- smaller models: AI replies start at the provider's small model
+  (latency.BudgetedAI), and the voice model is capped at q4 (at startup)
+- the transcriber, which hears the wake word, decodes in batches every
+  transcribe_poll seconds instead of every frame
+- the visualizer animates at visualizer_fps
+
+The dashboard applies profile changes and shows the active one in the
+header; the control socket's status includes it.
+"""
+
+import glob
+import logging
+import platform
+import re
+import subprocess
+import time
+from dataclasses import dataclass
+from typing import Callable, List, Optional
+
+logger = logging.getLogger(__name__)
+
+SAMPLE_INTERVAL = 30.0  # Seconds between power/thermal readings
+RECOVER_SAMPLES = 3  # Calm samples in a row before leaving low power
+THERMAL_LEVELS = ("nominal", "fair", "serious", "critical")
+LINUX_THERMAL_C = ((95.0, "critical"), (85.0, "serious"), (75.0, "fair"))
+
+
+@dataclass(frozen=True)
+class Profile:
+    name: str
+    label: str
+    small_models: bool  # Prefer the small AI model; voice capped at q4
+    voice_variant: Optional[str]  # Largest voice model variant at startup (None: as configured)
+    transcribe_poll: float  # Seconds between transcriber decodes (0: every frame)
+    visualizer_fps: int
+
+
+PERFORMANCE = Profile("performance", "⚡ Performance", small_models=False, voice_variant=None,
+                      transcribe_poll=0.0, visualizer_fps=20)
+LOW_POWER = Profile("low_power", "🔋 Low power", small_models=True, voice_variant="q4",
+                    transcribe_poll=0.3, visualizer_fps=5)
+PROFILES = {profile.name: profile for profile in (PERFORMANCE, LOW_POWER)}
+
+
+@dataclass
+class PowerState:
+    on_battery: Optional[bool] = None  # None: no battery (desktop) or unknown
+    battery_percent: Optional[float] = None
+    thermal: str = "nominal"  # nominal, fair, serious, critical
+
+
+def _macos_thermal() -> str:
+    out = subprocess.run(["pmset", "-g", "therm"], capture_output=True, text=True, timeout=2).stdout
+    match = re.search(r"CPU_Speed_Limit\s*=\s*(\d+)", out)
+    limit = int(match.group(1)) if match else 100
+    return "nominal" if limit >= 100 else "fair" if limit >= 80 else "serious" if limit >= 50 else "critical"
+
+
+def _linux_thermal() -> str:
+    hottest = 0.0
+    for path in glob.glob("/sys/class/thermal/thermal_zone*/temp"):
+        try:
+            with open(path) as f:
+                hottest = max(hottest, int(f.read().strip()) / 1000.0)
+        except (OSError, ValueError):
+            continue
+    return next((level for limit, level in LINUX_THERMAL_C if hottest >= limit), "nominal")
+
+
+def read_power_state() -> PowerState:
+    """The machine's power source and thermal pressure now (unknowns left at their defaults)."""
+    state = PowerState()
+    try:
+        import psutil
+        battery = psutil.sensors_battery()
+        if battery is not None:
+            state.on_battery = not battery.power_plugged
+            state.battery_percent = float(battery.percent)
+    except Exception as e:
+        logger.debug(f"Battery state unavailable: {e}")
+    try:
+        system = platform.system()
+        if system == "Darwin":
+            state.thermal = _macos_thermal()
+        elif system == "Linux":
+            state.thermal = _linux_thermal()
+    except Exception as e:
+        logger.debug(f"Thermal state unavailable: {e}")
+    return state
+
+
+class PowerManager:
+    """Chooses the performance profile from the power source and thermal pressure."""
+
+    def __init__(self, mode: str = "auto", reader: Callable[[], PowerState] = read_power_state,
+                 clock: Callable[[], float] = time.monotonic, sample_interval: float = SAMPLE_INTERVAL):
+        if mode != "auto" and mode not in PROFILES:
+            logger.warning(f"Unknown power_mode '{mode}' - using auto")
+            mode = "auto"
+        self.mode = mode
+        self._reader = reader
+        self._clock = clock
+        self.sample_interval = sample_interval
+        self.state = PowerState()
+        self.profile = PROFILES.get(mode, PERFORMANCE)
+        self.reasons: List[str] = []
+        self._calm = 0
+        self._sampled_at: Optional[float] = None
+
+    @classmethod
+    def from_config(cls, config) -> "PowerManager":
+        return cls(getattr(config, "power_mode", "auto"))
+
+    def _pressure(self, state: PowerState) -> List[str]:
+        reasons = []
+        if state.on_battery:
+            percent = f" {state.battery_percent:.0f}%" if state.battery_percent is not None else ""
+            reasons.append(f"on battery{percent}")
+        if THERMAL_LEVELS.index(state.thermal) >= THERMAL_LEVELS.index("serious"):
+            reasons.append(f"thermal pressure {state.thermal}")
+        return reasons
+
+    def sample(self, force: bool = False) -> Optional[Profile]:
+        """Read the power state (every sample_interval, or now with force); the new profile if it changed."""
+        now = self._clock()
+        if not force and self._sampled_at is not None and now - self._sampled_at < self.sample_interval:
+            return None
+        self._sampled_at = now
+        if self.mode != "auto":
+            return None
+        try:
+            self.state = self._reader()
+        except Exception as e:
+            logger.debug(f"Power sampling failed: {e}")
+            return None
+        self.reasons = self._pressure(self.state)
+        self._calm = 0 if self.reasons else self._calm + 1
+        if self.reasons:
+            wanted = LOW_POWER
+        elif self.profile is LOW_POWER and self._calm < RECOVER_SAMPLES:
+            wanted = LOW_POWER
+        else:
+            wanted = PERFORMANCE
+        if wanted is self.profile:
+            return None
+        self.profile = wanted
+        logger.info(f"Power profile: {wanted.name}" + (f" ({', '.join(self.reasons)})" if self.reasons else ""))
+        return wanted
+
+    def status_line(self) -> str:
+        """The active profile for the dashboard header, e.g. "🔋 Low power (on battery 41%)"."""
+        if self.profile is LOW_POWER and self.reasons:
+            return f"{self.profile.label} ({', '.join(self.reasons)})"
+        return self.profile.label
+
+
+_manager: Optional[PowerManager] = None
+
+
+def get_power_manager() -> PowerManager:
+    """Get the global power manager (performance until startup installs one from the config)."""
+    global _manager
+    if _manager is None:
+        _manager = PowerManager("performance")
+    return _manager
+
+
+def set_power_manager(manager: PowerManager) -> None:
+    global _manager
+    _manager = manager
@@ -0,0 +1,185 @@
+"""
+Privacy - What the microphone is doing, always visible.
+
+Mic states (MicState):
+
+- off: no audio is captured (text-only mode, voice not started or stopped)
+- wake_word: the mic is open, but speech without the wake word is thrown
+  away (config.require_wake_word, outside the follow-up window)
+- streaming: speech is transcribed and acted on
+
+Everything runs on this machine unless reported otherwise: whatever sends
+audio off it (a phone call streamed through Twilio) calls report_egress()
+when the stream starts and end_egress() when it stops. Each stream gets
+one activity-log entry, and the indicators show it while it lasts.
+
+Indicators, fed by PrivacyMonitor listeners:
+
+- a persistent badge in the dashboard footer
+- with config.privacy_tray_icon, a menu-bar/tray icon (macOS/Linux; needs
+  pystray and Pillow: `pip install voice-assistant[tray]`)
+
+`xswarm tray` shows the same icon with quick actions, from its own process
+(see tray.py).
+"""
+
+import logging
+import threading
+import time
+from dataclasses import dataclass
+from enum import Enum
+from typing import Callable, Dict, List, Optional
+
+logger = logging.getLogger(__name__)
+
+
+class MicState(Enum):
+    OFF = "off"
+    WAKE_WORD = "wake_word"
+    STREAMING = "streaming"
+
+
+LABELS = {MicState.OFF: "mic off", MicState.WAKE_WORD: "wake word only", MicState.STREAMING: "listening"}
+
+
+@dataclass
+class Egress:
+    """Audio currently leaving the machine."""
+    destination: str  # Who receives it, e.g. "Twilio"
+    detail: str  # What it is, e.g. "phone call audio"
+    started_at: float
+
+
+class PrivacyMonitor:
+    """Mic state and outbound audio streams, for the indicators to show."""
+
+    def __init__(self, clock: Callable[[], float] = time.time):
+        self.clock = clock
+        self.mic_state = MicState.OFF
+        self.egress: Dict[str, Egress] = {}
+        self.listeners: List[Callable[["PrivacyMonitor"], None]] = []  # Called on any change
+        # Activity-log entry for each new outbound stream (default: the log)
+        self.on_egress: Optional[Callable[[str], None]] = None
+        self._lock = threading.Lock()
+
+    def _changed(self) -> None:
+        for listener in list(self.listeners):
+            try:
+                listener(self)
+            except Exception as e:
+                logger.debug(f"Privacy indicator failed: {e}")
+
+    def set_mic_state(self, state: MicState) -> None:
+        if state != self.mic_state:
+            logger.info(f"🎤 Mic: {LABELS[state]}")
+            self.mic_state = state
+            self._changed()
+
+    def report_egress(self, key: str, destination: str, detail: str) -> None:
+        """Audio started leaving the machine (`key` identifies the stream; repeats are ignored)."""
+        with self._lock:
+            if key in self.egress:
+                return
+            self.egress[key] = Egress(destination, detail, self.clock())
+        message = f"🔊 Audio leaving this machine: {detail} → {destination}"
+        if self.on_egress:
+            try:
+                self.on_egress(message)
+            except Exception as e:
+                logger.debug(f"Could not log audio egress: {e}")
+        else:
+            logger.info(message)
+        self._changed()
+
+    def end_egress(self, key: str) -> None:
+        with self._lock:
+            if self.egress.pop(key, None) is None:
+                return
+        self._changed()
+
+    def destinations(self) -> List[str]:
+        """Where audio is going right now, without repeats."""
+        with self._lock:
+            return sorted({e.destination for e in self.egress.values()})
+
+    def summary(self) -> str:
+        """ "listening", "wake word only · audio → Twilio"... for tooltips and the tray."""
+        text = LABELS[self.mic_state]
+        destinations = self.destinations()
+        return f"{text} · audio → {', '.join(destinations)}" if destinations else text
+
+
+ICON_COLORS = {MicState.OFF: (120, 120, 120), MicState.WAKE_WORD: (230, 170, 40), MicState.STREAMING: (220, 50, 50)}
+EGRESS_COLOR = (60, 140, 230)  # Ring while audio leaves the machine
+
+
+def draw_icon(state: MicState, egress: bool = False):
+    """Tray icon image: a dot in the mic state's color, ringed while audio leaves the machine (needs Pillow)."""
+    from PIL import Image, ImageDraw
+    image = Image.new("RGBA", (64, 64), (0, 0, 0, 0))
+    draw = ImageDraw.Draw(image)
+    if egress:
+        draw.ellipse((2, 2, 62, 62), outline=EGRESS_COLOR, width=6)
+    draw.ellipse((14, 14, 50, 50), fill=ICON_COLORS[state])
+    return image
+
+
+class TrayIndicator:
+    """Menu-bar/tray icon showing the mic state (optional pystray + Pillow)."""
+
+    def __init__(self, monitor: "PrivacyMonitor"):
+        self.monitor = monitor
+        self._icon = None
+
+    def start(self) -> bool:
+        """Show the icon; False (logged) when the tray libraries or a tray aren't available."""
+        try:
+            import pystray
+        except ImportError:
+            logger.warning("Tray icon needs pystray and Pillow: pip install voice-assistant[tray]")
+            return False
+        try:
+            self._icon = pystray.Icon("xswarm", self._image(), self._title())
+            self._icon.run_detached()
+        except Exception as e:
+            logger.warning(f"Could not show the tray icon: {e}")
+            self._icon = None
+            return False
+        self.monitor.listeners.append(self.update)
+        return True
+
+    def _image(self):
+        return draw_icon(self.monitor.mic_state, bool(self.monitor.destinations()))
+
+    def _title(self) -> str:
+        return f"xSwarm: {self.monitor.summary()}"
+
+    def update(self, _monitor: Optional["PrivacyMonitor"] = None) -> None:
+        if self._icon is not None:
+            self._icon.icon = self._image()
+            self._icon.title = self._title()
+
+    def stop(self) -> None:
+        if self._icon is not None:
+            try:
+                self._icon.stop()
+            except Exception:
+                pass
+            self._icon = None
+        if self.update in self.monitor.listeners:
+            self.monitor.listeners.remove(self.update)
+
+
+_monitor: Optional[PrivacyMonitor] = None
+
+
+def get_privacy_monitor() -> PrivacyMonitor:
+    global _monitor
+    if _monitor is None:
+        _monitor = PrivacyMonitor()
+    return _monitor
+
+
+def set_privacy_monitor(monitor: PrivacyMonitor) -> None:
+    global _monitor
+    _monitor = monitor
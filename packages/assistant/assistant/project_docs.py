@@ -0,0 +1,726 @@
+"""
+Project Docs - Index a project's repos and docs, and answer questions from them.
+
+Each project (planner.Project) lists its folders - code repos, docs
+directories. `xswarm dev project index <name>` (and the document_indexing
+job, every 6 hours) crawls them into Meilisearch: source files are cut at
+function/class boundaries and docs at headings (code_chunks.py), each chunk
+stored with its project, path, line range and enclosing symbol - path,
+symbol, kind and language are filterable. Files unchanged since the last run
+are skipped, and chunks of deleted files are removed.
+
+Questions about a project ("where do we configure retries in project X?")
+go to the ask_project tool: the question's keywords are searched within the
+project's chunks, the best hits are handed to the AI as numbered excerpts,
+and the answer comes back with the sources it cited (path:lines and the
+function or class they're in), which the TUI prints under it. Without an AI
+the hits themselves are the answer, each with its best-matching line.
+
+Long documents (SUMMARY_SUFFIXES files of SUMMARY_MIN_CHARS or more) get an
+AI summary and keyword list when they're indexed, kept with the file's index
+record and searchable as a "summary" chunk. "Summarize the architecture doc"
+(summarize_project_doc tool) answers from it at once instead of reading the
+document again; it's only redone when the document's text changes.
+
+Meilisearch: config.meilisearch_url and meilisearch_key (MEILI_MASTER_KEY).
+Anything with async add/delete/search like MeiliIndex works (see tests).
+Installed at startup with set_project_docs(ProjectDocs.from_config(config, ai)).
+
+Profiles: several people (or one person's work and personal setups) can
+share a Meilisearch. Each profile - config.index_profile, by default the
+login name - has its own index (xswarm_project_docs_<profile>), and every
+document carries its owner, which every search filters on. With the master
+key, each profile gets an API key limited to its own index (MeiliKeys), so
+what a profile runs with can't read another's documents; `xswarm dev
+project key` shows it and `--revoke` replaces it.
+
+Storage: ~/.xswarm/project_index/<profile>.json (what was indexed per project),
+~/.xswarm/meilisearch_keys.json (the profiles' API keys, readable only by you)
+"""
+
+import getpass
+import hashlib
+import json
+import logging
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, Iterable, Iterator, List, Optional
+
+from .code_chunks import CHUNKER_VERSION, chunk_file
+
+logger = logging.getLogger(__name__)
+
+STATE_DIR = Path.home() / ".xswarm" / "project_index"
+KEYS_PATH = Path.home() / ".xswarm" / "meilisearch_keys.json"
+MEILISEARCH_URL = "http://localhost:7700"
+INDEX_NAME = "xswarm_project_docs"  # Each profile's index is INDEX_NAME_<profile>
+
+MAX_FILE_BYTES = 256 * 1024  # Larger files are generated or data, not docs
+MAX_SOURCES = 6  # Excerpts handed to the AI per question
+SUMMARY_SUFFIXES = {".md", ".mdx", ".rst", ".txt", ".adoc", ".org"}
+SUMMARY_MIN_CHARS = 6000  # About two pages; shorter documents are read whole when asked about
+SUMMARY_INPUT_CHARS = 40000  # Longer documents are summarized from their start
+
+TEXT_SUFFIXES = {
+    ".md", ".mdx", ".rst", ".txt", ".adoc", ".org",
+    ".py", ".rs", ".js", ".mjs", ".ts", ".tsx", ".jsx", ".go", ".java", ".kt", ".rb", ".php", ".swift",
+    ".c", ".h", ".cc", ".cpp", ".hpp", ".cs", ".sh", ".sql", ".html", ".css", ".tcss",
+    ".toml", ".yaml", ".yml", ".json", ".ini", ".cfg", ".conf",
+}
+TEXT_NAMES = {"Dockerfile", "Makefile", "Justfile", "justfile", "README", "LICENSE", ".env.example"}
+SKIP_DIRS = {".git", "node_modules", "target", "dist", "build", "__pycache__", ".venv", "venv", ".tox",
+             ".mypy_cache", ".pytest_cache", ".next", "vendor"}
+LOCK_FILES = {"package-lock.json", "pnpm-lock.yaml", "yarn.lock", "Cargo.lock", "poetry.lock", "uv.lock"}
+
+# Words that say how a question is asked rather than what it's about
+QUESTION_WORDS = {
+    "a", "an", "the", "in", "on", "of", "for", "to", "at", "by", "from", "with", "about", "and", "or", "is", "are",
+    "was", "do", "does", "did", "we", "i", "our", "us", "you", "it", "its", "this", "that", "where", "what", "which",
+    "how", "who", "when", "why", "can", "could", "should", "would", "project", "repo", "docs", "code", "find",
+    "tell", "me", "show", "there", "any", "some",
+}
+# Words that say a document is meant rather than which one ("summarize the architecture doc")
+DOCUMENT_WORDS = QUESTION_WORDS | {"summarize", "summarise", "summary", "doc", "document", "documentation", "file",
+                                   "page", "give", "please", "say", "says"}
+
+
+class ProjectDocsError(Exception):
+    """Meilisearch unreachable or refusing the request."""
+
+    def __init__(self, message: str, status: int = 0):
+        super().__init__(message)
+        self.status = status  # Meilisearch's HTTP status; 0 when it wasn't reached
+
+
+@dataclass
+class Source:
+    """An indexed chunk: where it is and what it says."""
+    path: str
+    line: int
+    end_line: int
+    text: str
+    symbol: str = ""
+    kind: str = ""
+
+    @property
+    def citation(self) -> str:
+        return f"{self.path}:{self.line}-{self.end_line}"
+
+    @property
+    def heading(self) -> str:
+        """The citation with the enclosing symbol: "src/retry.rs:12-30 (method RetryPolicy.backoff)"."""
+        return f"{self.citation} ({self.kind} {self.symbol})" if self.symbol else self.citation
+
+    def snippet(self, terms: str, width: int = 100) -> str:
+        """The line matching the most search terms, shortened to `width`."""
+        words = terms.lower().split()
+        best = max((line.strip() for line in self.text.splitlines() if line.strip()),
+                   key=lambda line: sum(word in line.lower() for word in words), default="")
+        return best if len(best) <= width else best[:width - 1] + "…"
+
+
+@dataclass
+class ProjectAnswer:
+    text: str
+    sources: List[Source] = field(default_factory=list)
+    terms: str = ""  # Set when the sources are the answer: each is shown with its best-matching line
+
+    def render(self) -> str:
+        """The answer with its citations underneath, as printed in the TUI."""
+        if not self.sources:
+            return self.text
+        lines = [self.text, "", "Sources:"]
+        for n, source in enumerate(self.sources, 1):
+            lines.append(f"  [{n}] {source.heading}")
+            if self.terms:
+                lines.append(f"      {source.snippet(self.terms)}")
+        return "\n".join(lines)
+
+
+@dataclass
+class IndexReport:
+    """What one indexing run did for a project."""
+    files: int = 0  # Files (re)indexed
+    chunks: int = 0
+    unchanged: int = 0
+    removed: int = 0  # Files gone since the last run
+    summarized: int = 0  # Long documents given a (new) AI summary
+    missing_folders: List[str] = field(default_factory=list)
+
+    def summary(self) -> str:
+        line = f"{self.files} files indexed ({self.chunks} chunks), {self.unchanged} unchanged, {self.removed} removed"
+        if self.summarized:
+            line += f", {self.summarized} documents summarized"
+        for folder in self.missing_folders:
+            line += f"\n  ✗ Folder not found: {folder}"
+        return line
+
+
+def profile_name(config=None) -> str:
+    """The profile whose documents this is: config.index_profile, else the login name."""
+    name = getattr(config, "index_profile", None)
+    if not name:
+        try:
+            name = getpass.getuser()
+        except Exception:  # No login name (some containers)
+            name = "default"
+    return re.sub(r"[^a-z0-9_-]", "_", name.lower())  # Meilisearch index names allow a-z 0-9 _ -
+
+
+async def _meili_request(base_url: str, api_key: Optional[str], method: str, path: str, body: Any = None,
+                         timeout: float = 15.0) -> Any:
+    import httpx
+
+    headers = {"Authorization": f"Bearer {api_key}"} if api_key else {}
+    try:
+        async with httpx.AsyncClient(timeout=timeout, headers=headers) as client:
+            response = await client.request(method, f"{base_url}{path}", json=body)
+    except httpx.HTTPError as e:
+        raise ProjectDocsError(f"Meilisearch isn't reachable at {base_url} ({e})") from e
+    if response.status_code >= 400:
+        raise ProjectDocsError(f"Meilisearch refused {method} {path}: {response.status_code} {response.text[:200]}",
+                               status=response.status_code)
+    return response.json() if response.content else None
+
+
+class MeiliKeys:
+    """
+    Per-profile Meilisearch API keys, made with the master key.
+
+    A profile's key can only read and write its own index. Keys are kept in
+    KEYS_PATH and reused until revoked; a revoked key is replaced on next use.
+    """
+
+    ACTIONS = ["search", "documents.add", "documents.get", "documents.delete", "indexes.create", "indexes.get",
+               "settings.get", "settings.update", "tasks.get"]
+
+    def __init__(self, base_url: str, master_key: str, path: Path = KEYS_PATH, timeout: float = 15.0):
+        self.base_url = base_url.rstrip("/")
+        self.master_key = master_key
+        self.path = Path(path)
+        self.timeout = timeout
+
+    def _load(self) -> Dict[str, Dict[str, str]]:
+        try:
+            return json.loads(self.path.read_text())
+        except (OSError, ValueError):
+            return {}
+
+    def _save(self, keys: Dict[str, Dict[str, str]]) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps(keys, indent=2))
+        self.path.chmod(0o600)
+
+    def stored(self, profile: str) -> Optional[Dict[str, str]]:
+        """The profile's key entry (uid, key, index), if one was made."""
+        return self._load().get(profile)
+
+    async def key_for(self, profile: str, index: str) -> str:
+        """The profile's key for its index, made on first use."""
+        entry = self.stored(profile)
+        if entry and entry.get("index") == index:
+            return entry["key"]
+        created = await _meili_request(self.base_url, self.master_key, "POST", "/keys", {
+            "name": f"xswarm {profile}", "description": f"xswarm project docs for profile {profile}",
+            "actions": self.ACTIONS, "indexes": [index], "expiresAt": None,
+        }, self.timeout)
+        keys = self._load()
+        keys[profile] = {"uid": created["uid"], "key": created["key"], "index": index}
+        self._save(keys)
+        return created["key"]
+
+    def forget(self, profile: str) -> None:
+        """Drop the stored key (it no longer works); the next key_for makes a new one."""
+        keys = self._load()
+        if keys.pop(profile, None) is not None:
+            self._save(keys)
+
+    async def revoke(self, profile: str) -> bool:
+        """Delete the profile's key from Meilisearch and forget it; False when it had none."""
+        entry = self.stored(profile)
+        if entry is None:
+            return False
+        try:
+            await _meili_request(self.base_url, self.master_key, "DELETE", f"/keys/{entry['uid']}", None, self.timeout)
+        except ProjectDocsError as e:
+            if e.status != 404:  # Already deleted in Meilisearch is fine
+                raise
+        self.forget(profile)
+        return True
+
+
+class MeiliIndex:
+    """A profile's project-docs index in Meilisearch (filtered by owner and project_id)."""
+
+    def __init__(self, base_url: str = MEILISEARCH_URL, api_key: Optional[str] = None, name: str = INDEX_NAME,
+                 timeout: float = 15.0, owner: str = "", keys: Optional[MeiliKeys] = None):
+        self.base_url = base_url.rstrip("/")
+        self.api_key = api_key
+        self.name = name
+        self.timeout = timeout
+        self.owner = owner  # Stamped on every document and required by every search
+        self.keys = keys  # Swaps api_key for the owner's own key
+        self._ready = False
+
+    async def _request(self, method: str, path: str, body: Any = None) -> Any:
+        try:
+            return await _meili_request(self.base_url, self.api_key, method, path, body, self.timeout)
+        except ProjectDocsError as e:
+            if e.status not in (401, 403) or self.keys is None:
+                raise
+            # The profile key was revoked: make a new one and try once more
+            self.keys.forget(self.owner)
+            self.api_key = await self.keys.key_for(self.owner, self.name)
+            return await _meili_request(self.base_url, self.api_key, method, path, body, self.timeout)
+
+    async def _ensure(self) -> None:
+        if self._ready:
+            return
+        if self.keys is not None:
+            try:
+                self.api_key = await self.keys.key_for(self.owner, self.name)
+            except ProjectDocsError as e:
+                if e.status not in (401, 403):
+                    raise
+                # meilisearch_key isn't the master key - it's used as given (e.g. a key an admin made for you)
+                logger.info(f"Using meilisearch_key as is, it can't make profile keys: {e}")
+                self.keys = None
+        await self._request("POST", "/indexes", {"uid": self.name, "primaryKey": "id"})  # Already there is fine
+        await self._request("PATCH", f"/indexes/{self.name}/settings",
+                            {"filterableAttributes": ["owner", "project_id", "path", "symbol", "kind", "language"],
+                             "searchableAttributes": ["symbol", "text", "path"]})
+        self._ready = True
+
+    async def add(self, documents: List[Dict[str, Any]]) -> None:
+        if documents:
+            await self._ensure()
+            await self._request("POST", f"/indexes/{self.name}/documents",
+                                [{**document, "owner": self.owner} for document in documents])
+
+    async def delete(self, ids: List[str]) -> None:
+        if ids:
+            await self._ensure()
+            await self._request("POST", f"/indexes/{self.name}/documents/delete-batch", ids)
+
+    async def search(self, query: str, project_id: str, limit: int = MAX_SOURCES,
+                     filters: Optional[Dict[str, str]] = None) -> List[Dict[str, Any]]:
+        """Best chunks of a project for the query; filters narrow by path, symbol, kind or language."""
+        await self._ensure()
+        wanted = {"owner": self.owner, "project_id": project_id, **(filters or {})}
+        clauses = [f'{name} = {json.dumps(value)}' for name, value in wanted.items()]
+        result = await self._request("POST", f"/indexes/{self.name}/search", {
+            "q": query, "filter": " AND ".join(clauses), "limit": limit,
+            "matchingStrategy": "last",  # Drop trailing words until something matches
+        })
+        return [hit for hit in result.get("hits", []) if hit.get("owner", "") == self.owner]
+
+
+def _is_text(path: Path) -> bool:
+    return (path.suffix.lower() in TEXT_SUFFIXES or path.name in TEXT_NAMES) and path.name not in LOCK_FILES
+
+
+def _glob_regex(pattern: str) -> "re.Pattern":
+    """A .gitignore glob as a regex over "/"-separated paths."""
+    out, i = "", 0
+    while i < len(pattern):
+        if pattern.startswith("**/", i):
+            out, i = out + "(?:.*/)?", i + 3
+        elif pattern.startswith("/**", i) and i + 3 == len(pattern):
+            out, i = out + "/.*", i + 3
+        elif pattern[i] == "*":
+            out, i = out + "[^/]*", i + 1
+        elif pattern[i] == "?":
+            out, i = out + "[^/]", i + 1
+        elif pattern[i] == "[" and "]" in pattern[i + 1:]:
+            end = pattern.index("]", i + 1)
+            out, i = out + "[" + pattern[i + 1:end].replace("!", "^", 1) + "]", end + 1
+        else:
+            out, i = out + re.escape(pattern[i]), i + 1
+    return re.compile(out)
+
+
+class IgnoreRules:
+    """
+    What the index leaves out under one folder: VCS, dependency and build
+    directories, hidden directories, config.project_ignore patterns and the
+    .gitignore files in the folder and its subdirectories (same semantics:
+    last match wins, "!" re-includes, "dir/" matches directories only,
+    patterns with a "/" are relative to their .gitignore).
+    """
+
+    def __init__(self, root: Path, extra: Iterable[str] = ()):
+        self.root = Path(root)
+        self.extra = [line for line in extra if line.strip()]
+        self._rules: Dict[str, List[tuple]] = {}  # Directory (relative, "" = root) -> parsed rules
+
+    @staticmethod
+    def _parse(lines: Iterable[str]) -> List[tuple]:
+        rules = []
+        for line in lines:
+            line = line.rstrip("\n").rstrip()
+            if not line or line.startswith("#"):
+                continue
+            negate = line.startswith("!")
+            line = line[1:] if negate else line
+            dir_only = line.endswith("/")
+            line = line.rstrip("/")
+            if "/" in line:
+                regex = _glob_regex(line.lstrip("/"))
+            else:
+                regex = re.compile("(?:.*/)?" + _glob_regex(line).pattern)
+            rules.append((regex, negate, dir_only))
+        return rules
+
+    def _rules_in(self, directory: str) -> List[tuple]:
+        if directory not in self._rules:
+            try:
+                lines = (self.root / directory / ".gitignore").read_text(encoding="utf-8").splitlines()
+            except (OSError, UnicodeDecodeError):
+                lines = []
+            self._rules[directory] = self._parse(self.extra if not directory else []) + self._parse(lines)
+        return self._rules[directory]
+
+    def forget(self) -> None:
+        """Re-read the .gitignore files (one of them changed)."""
+        self._rules.clear()
+
+    def _matches(self, relative: str, is_dir: bool) -> bool:
+        parts = relative.split("/")
+        result = False
+        for depth in range(len(parts)):  # The root's .gitignore first, then each directory's on the way down
+            rest = "/".join(parts[depth:])
+            for regex, negate, dir_only in self._rules_in("/".join(parts[:depth])):
+                if (is_dir or not dir_only) and regex.fullmatch(rest):
+                    result = not negate
+        return result
+
+    def ignored(self, relative: str, is_dir: bool = False) -> bool:
+        """Whether a path ("src/app.py", relative to the folder) is left out, itself or by a parent directory."""
+        parts = relative.split("/")
+        for i in range(1, len(parts) + 1):
+            part_is_dir = is_dir or i < len(parts)
+            if part_is_dir and (parts[i - 1] in SKIP_DIRS or parts[i - 1].startswith(".")):
+                return True
+            if self._matches("/".join(parts[:i]), part_is_dir):
+                return True
+        return False
+
+
+def crawl(folder: Path, rules: Optional[IgnoreRules] = None) -> Iterator[Path]:
+    """Text and source files under a folder, skipping ignored ones (see IgnoreRules)."""
+    rules = rules or IgnoreRules(folder)
+    for root, dirs, files in os.walk(folder):
+        base = Path(root).relative_to(rules.root).as_posix()  # Rules are relative to the project folder
+        prefix = "" if base == "." else base + "/"
+        dirs[:] = sorted(d for d in dirs if not rules.ignored(prefix + d, is_dir=True))
+        for name in sorted(files):
+            path = Path(root) / name
+            if _is_text(path) and not rules.ignored(prefix + name):
+                yield path
+
+
+def _mentions(text: str, word: str) -> bool:
+    """Whether text has the word, or a word sharing its stem ("deploy" for "deployment")."""
+    return any(t == word or len(min(t, word, key=len)) >= 4 and (t.startswith(word) or word.startswith(t))
+               for t in re.findall(r"[a-z0-9]+", text.lower()))
+
+
+def _digest(text: str) -> str:
+    return hashlib.sha1(text.encode("utf-8")).hexdigest()
+
+
+def _chunk_id(project_id: str, path: str, line: int) -> str:
+    # Meilisearch ids allow only letters, digits, - and _
+    return hashlib.sha1(f"{project_id}\0{path}\0{line}".encode()).hexdigest()
+
+
+def search_terms(question: str, project_name: str = "") -> str:
+    """The words of a question that say what it's about (not "where do we ... in project X")."""
+    skip = QUESTION_WORDS | set(re.findall(r"\w+", project_name.lower()))
+    words = [word for word in re.findall(r"[\w.-]+", question.lower()) if word.strip(".-") not in skip]
+    return " ".join(word.strip(".-") for word in words) or question
+
+
+def find_project(planner, name: str):
+    """A project by id or name (case-insensitive; a unique partial name will do)."""
+    wanted = name.strip().lower()
+    projects = planner.get_projects()
+    for project in projects:
+        if wanted in (project.id.lower(), project.name.lower()):
+            return project
+    partial = [project for project in projects if wanted and wanted in project.name.lower()]
+    return partial[0] if len(partial) == 1 else None
+
+
+class ProjectDocs:
+    """Indexes projects' folders and answers questions from what was indexed."""
+
+    def __init__(self, index, ai=None, state_path: Path = STATE_DIR / "default.json", ignore: Iterable[str] = ()):
+        self.index = index
+        self.ai = ai
+        self.state_path = Path(state_path)
+        self.ignore = list(ignore)
+        self._state: Optional[Dict[str, Dict[str, Dict[str, Any]]]] = None
+        self._rules: Dict[Path, IgnoreRules] = {}
+
+    @classmethod
+    def from_config(cls, config=None, ai=None) -> "ProjectDocs":
+        url = getattr(config, "meilisearch_url", None) or MEILISEARCH_URL
+        key = getattr(config, "meilisearch_key", None)
+        profile = profile_name(config)
+        index = MeiliIndex(url, key, name=f"{INDEX_NAME}_{profile}", owner=profile,
+                           keys=MeiliKeys(url, key) if key else None)
+        return cls(index, ai, state_path=STATE_DIR / f"{profile}.json",
+                   ignore=getattr(config, "project_ignore", None) or ())
+
+    def rules(self, root: Path) -> IgnoreRules:
+        """The ignore rules for a project folder (kept, so .gitignore files are read once)."""
+        if root not in self._rules:
+            self._rules[root] = IgnoreRules(root, self.ignore)
+        return self._rules[root]
+
+    @staticmethod
+    def roots(project) -> Dict[Path, str]:
+        """A project's folders, each with the prefix its files are labelled with (when it has several)."""
+        several = len(project.folders) > 1
+        roots = {}
+        for folder in project.folders:
+            root = Path(folder).expanduser()
+            roots[root] = f"{root.name}/" if several else ""
+        return roots
+
+    def _load(self) -> Dict[str, Dict[str, Dict[str, Any]]]:
+        if self._state is None:
+            try:
+                self._state = json.loads(self.state_path.read_text())
+            except (OSError, ValueError):
+                self._state = {}
+        return self._state
+
+    def _save(self) -> None:
+        self.state_path.parent.mkdir(parents=True, exist_ok=True)
+        self.state_path.write_text(json.dumps(self._state, indent=2))
+
+    def is_indexed(self, project) -> bool:
+        return bool(self._load().get(project.id))
+
+    async def _index_file(self, project, path: Path, label: str, report: IndexReport) -> None:
+        """(Re)index one file if it changed since it was last indexed."""
+        known = self._load().setdefault(project.id, {})
+        stat = path.stat()
+        entry = known.get(label)
+        if (entry and entry["mtime"] == stat.st_mtime and entry["size"] == stat.st_size
+                and entry.get("chunker") == CHUNKER_VERSION
+                and ("summary" in entry or not self._wants_summary(label, stat.st_size))):
+            report.unchanged += 1
+            return
+        if stat.st_size > MAX_FILE_BYTES:
+            return
+        try:
+            text = path.read_text(encoding="utf-8")
+        except (OSError, UnicodeDecodeError):
+            return  # Binary despite its name
+        documents = [{"id": _chunk_id(project.id, label, chunk.line), "project_id": project.id,
+                      "project": project.name, "path": label, "line": chunk.line, "end_line": chunk.end_line,
+                      "text": chunk.text, "symbol": chunk.symbol, "kind": chunk.kind, "language": chunk.language}
+                     for chunk in chunk_file(label, text)]
+        summary = (entry or {}).get("summary")
+        if summary and summary["sha"] != _digest(text):
+            summary = None  # The text changed (not just touched)
+        if summary is None and self._wants_summary(label, len(text)):
+            summary = await self._summarize(label, text)
+            report.summarized += summary is not None
+        if summary:
+            # Searchable too: "which doc covers deployment?" can land on a summary
+            documents.append({"id": _chunk_id(project.id, label, 0), "project_id": project.id,
+                              "project": project.name, "path": label, "line": 1, "end_line": summary["lines"],
+                              "text": f"{summary['text']}\nKeywords: {', '.join(summary['keywords'])}",
+                              "symbol": summary["title"], "kind": "summary",
+                              "language": documents[0]["language"] if documents else ""})
+        stale = [i for i in (entry or {}).get("ids", []) if i not in {d["id"] for d in documents}]
+        await self.index.delete(stale)
+        await self.index.add(documents)
+        known[label] = {"mtime": stat.st_mtime, "size": stat.st_size, "chunker": CHUNKER_VERSION,
+                        "ids": [d["id"] for d in documents]}
+        if summary:
+            known[label]["summary"] = summary
+        report.files += 1
+        report.chunks += len(documents)
+
+    def _wants_summary(self, label: str, size: int) -> bool:
+        """Whether a document is long enough to summarize when indexed (and there's an AI to do it)."""
+        return (Path(label).suffix.lower() in SUMMARY_SUFFIXES and size >= SUMMARY_MIN_CHARS
+                and self.ai is not None and self.ai.is_available())
+
+    async def _summarize(self, label: str, text: str) -> Optional[Dict[str, Any]]:
+        """An AI summary and keyword list for a document, or None when the AI fails."""
+        messages = [
+            {"role": "system", "content": (
+                "You summarize a project document for someone who may hear the summary read aloud. "
+                "Reply in exactly this form:\n"
+                "SUMMARY: <3-6 plain sentences: what the document covers, its key decisions, names and numbers>\n"
+                "KEYWORDS: <5-12 comma-separated terms someone might search for>")},
+            {"role": "user", "content": f"Document {label}:\n\n{text[:SUMMARY_INPUT_CHARS]}"},
+        ]
+        try:
+            reply = (await self.ai.chat(messages, max_tokens=500)).strip()
+        except Exception as e:
+            logger.warning(f"Couldn't summarize {label}: {e}")
+            return None
+        match = re.search(r"SUMMARY:\s*(.*?)\s*(?:KEYWORDS:\s*(.*))?$", reply, re.S | re.I)
+        summary = (match.group(1) if match else reply).strip()
+        if not summary:
+            return None
+        keywords = [k.strip() for k in (match.group(2) or "").split(",") if k.strip()] if match else []
+        heading = re.search(r"^#+\s+(.+)$", text, re.M)
+        return {"title": heading.group(1).strip() if heading else Path(label).stem, "text": summary,
+                "keywords": keywords, "lines": len(text.splitlines()), "sha": _digest(text)}
+
+    async def _forget(self, project, labels: Iterable[str], report: IndexReport) -> None:
+        known = self._load().setdefault(project.id, {})
+        for label in list(labels):
+            await self.index.delete(known.pop(label)["ids"])
+            report.removed += 1
+
+    async def index_project(self, project) -> IndexReport:
+        """Bring the project's chunks in line with its folders (changed files only)."""
+        report = IndexReport()
+        known = self._load().setdefault(project.id, {})
+        seen = set()
+        for root, prefix in self.roots(project).items():
+            if not root.is_dir():
+                report.missing_folders.append(str(root))
+                continue
+            for path in crawl(root, self.rules(root)):
+                label = prefix + path.relative_to(root).as_posix()
+                seen.add(label)
+                await self._index_file(project, path, label, report)
+        await self._forget(project, [label for label in known if label not in seen], report)
+        self._save()
+        return report
+
+    async def update_files(self, project, paths: Iterable[Path]) -> IndexReport:
+        """Reindex just these paths (saved, created or deleted files and directories), as the watcher saw them."""
+        report = IndexReport()
+        known = self._load().setdefault(project.id, {})
+        roots = self.roots(project)
+        for path in paths:
+            path = Path(path)
+            root = next((r for r in roots if path == r or r in path.parents), None)
+            if root is None:
+                continue
+            relative = path.relative_to(root).as_posix()
+            rules = self.rules(root)
+            if path.name == ".gitignore":
+                rules.forget()  # Newly ignored files go at the next full run
+                continue
+            label = roots[root] + relative
+            if path.is_file() and _is_text(path) and not rules.ignored(relative):
+                await self._index_file(project, path, label, report)
+            elif not path.exists():
+                # Deleted, or renamed away: the file, or everything under the directory
+                await self._forget(project, [k for k in known if k == label or k.startswith(label + "/")], report)
+            elif path.is_dir() and not rules.ignored(relative, is_dir=True):
+                # Created or moved in: whatever's under it
+                for child in crawl(path, rules):
+                    await self._index_file(project, child, roots[root] + child.relative_to(root).as_posix(), report)
+        self._save()
+        return report
+
+    async def index_all(self, planner) -> Dict[str, IndexReport]:
+        """The document_indexing job: every active project with folders."""
+        reports = {}
+        for project in planner.get_projects(status="active"):
+            if project.folders:
+                reports[project.name] = await self.index_project(project)
+        return reports
+
+    def find_document(self, project, description: str) -> Optional[str]:
+        """The indexed document best matching a description ("the architecture doc"), by path, title and keywords."""
+        words = [w for w in re.findall(r"[a-z0-9]+", description.lower()) if w not in DOCUMENT_WORDS]
+        best, best_score = None, 0
+        documents = {label: entry for label, entry in self._load().get(project.id, {}).items()
+                     if Path(label).suffix.lower() in SUMMARY_SUFFIXES}
+        for label, entry in documents.items():
+            summary = entry.get("summary") or {}
+            score = sum(3 * _mentions(label, w) + 2 * _mentions(summary.get("title", ""), w)
+                        + _mentions(" ".join(summary.get("keywords", [])), w) for w in words)
+            if score > best_score:
+                best, best_score = label, score
+        if best is None and not words and len(documents) == 1:
+            best = next(iter(documents))  # "Summarize the doc" when there's only one
+        return best
+
+    def _path(self, project, label: str) -> Optional[Path]:
+        """Where an indexed file is on disk."""
+        for root, prefix in self.roots(project).items():
+            if label.startswith(prefix) and (root / label[len(prefix):]).is_file():
+                return root / label[len(prefix):]
+        return None
+
+    async def summarize(self, project, description: str) -> ProjectAnswer:
+        """A document's summary: the one made when it was indexed, else written now and kept."""
+        label = self.find_document(project, description)
+        if label is None:
+            return ProjectAnswer(f"I couldn't find a document like \"{description}\" in {project.name}.")
+        entry = self._load()[project.id][label]
+        summary = entry.get("summary")
+        if summary is None:
+            path = self._path(project, label)
+            if path is None:
+                return ProjectAnswer(f"{label} is no longer in {project.name}'s folders.")
+            if self.ai is None or not self.ai.is_available():
+                return ProjectAnswer(f"{label} hasn't been summarized, and there's no AI to summarize it now.")
+            summary = await self._summarize(label, path.read_text(encoding="utf-8", errors="replace"))
+            if summary is None:
+                return ProjectAnswer(f"I couldn't summarize {label} just now.")
+            entry["summary"] = summary  # Kept until the text changes
+            self._save()
+        return ProjectAnswer(summary["text"], [Source(label, 1, summary["lines"], "", summary["title"], "summary")])
+
+    async def ask(self, project, question: str, language: str = "") -> ProjectAnswer:
+        """Answer a question from the project's indexed files, citing the excerpts used."""
+        terms = search_terms(question, project.name)
+        hits = await self.index.search(terms, project.id, limit=MAX_SOURCES,
+                                       filters={"language": language.lower()} if language else None)
+        sources = [Source(hit["path"], hit["line"], hit["end_line"], hit["text"], hit.get("symbol", ""),
+                          hit.get("kind", "")) for hit in hits]
+        if not sources:
+            hint = "" if self.is_indexed(project) else f" - it isn't indexed yet (`xswarm dev project index {project.name}`)"
+            return ProjectAnswer(f"I couldn't find anything about that in {project.name}{hint}.")
+        if self.ai is None or not self.ai.is_available():
+            return ProjectAnswer(f"The closest matches in {project.name}:", sources, terms)
+
+        excerpts = "\n\n".join(f"[{n}] {source.heading}\n{source.text}" for n, source in enumerate(sources, 1))
+        messages = [
+            {"role": "system", "content": (
+                f"You answer questions about the project \"{project.name}\" using only the numbered excerpts from "
+                "its files. Be brief and specific (file names, settings, functions). Cite the excerpts you use "
+                "like [1]. If the excerpts don't answer the question, say so.")},
+            {"role": "user", "content": f"{excerpts}\n\nQuestion: {question}"},
+        ]
+        text = (await self.ai.chat(messages, max_tokens=600)).strip()
+        cited = [sources[int(n) - 1] for n in dict.fromkeys(re.findall(r"\[(\d+)\]", text))
+                 if 0 < int(n) <= len(sources)]
+        if cited:
+            # Renumber so the printed list matches: the first source cited becomes [1]
+            numbers = {source.citation: n for n, source in enumerate(cited, 1)}
+            text = re.sub(r"\[(\d+)\]", lambda m: f"[{numbers[sources[int(m.group(1)) - 1].citation]}]"
+                          if 0 < int(m.group(1)) <= len(sources) else m.group(0), text)
+        return ProjectAnswer(text, cited or sources)
+
+
+_project_docs: Optional[ProjectDocs] = None
+
+
+def get_project_docs() -> Optional[ProjectDocs]:
+    """The global project docs, or None before startup installs one."""
+    return _project_docs
+
+
+def set_project_docs(docs: Optional[ProjectDocs]) -> None:
+    """Install the project docs built from the loaded Config (called at startup)."""
+    global _project_docs
+    _project_docs = docs
@@ -0,0 +1,268 @@
+"""
+Project Watch - Keep the project index current as files are saved.
+
+The document_indexing job reindexes every 6 hours; with config.project_watch
+on (or `xswarm dev project watch`), changes are picked up within seconds
+instead. The folders of active projects are watched for filesystem
+notifications (watchdog, pip install voice-assistant[watch]; without it the
+folders are rescanned every POLL_SECONDS). Each saved, created, moved or
+deleted file is queued, and once a file has been quiet for the debounce
+(config.project_watch_debounce, default 2 seconds - editors write several
+times per save) the queued files are reindexed through
+ProjectDocs.update_files. Ignored paths (.gitignore, config.project_ignore,
+dependency and build directories) never reach the queue.
+
+Projects gaining or losing folders are picked up every ROOTS_SECONDS. The
+Projects view shows each project's status_line ("● Index up to date · 2
+files updated 14:03").
+"""
+
+import asyncio
+import logging
+import os
+import threading
+import time
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, Dict, List, Optional, Tuple
+
+from .project_docs import IndexReport, ProjectDocs, ProjectDocsError, crawl
+from .supervisor import get_task_supervisor
+
+logger = logging.getLogger(__name__)
+
+DEBOUNCE_SECONDS = 2.0
+POLL_SECONDS = 5.0  # Rescan interval without watchdog
+ROOTS_SECONDS = 30.0  # How often project folders are re-read from the planner
+WRITE_EVENTS = {"created", "modified", "deleted", "moved", "closed"}  # Not "opened"/"closed_no_write"
+
+
+@dataclass
+class WatchStatus:
+    """A watched project's index state, for the Projects view."""
+    state: str = "watching"  # watching, indexing or error
+    pending: int = 0  # Changed files waiting out the debounce
+    updated_at: Optional[datetime] = None
+    files: int = 0  # Files reindexed or removed in the last update
+    error: str = ""
+
+
+class ProjectWatcher:
+    """Reindexes changed files in active projects' folders shortly after they change."""
+
+    def __init__(self, docs: ProjectDocs, planner, debounce: float = DEBOUNCE_SECONDS,
+                 clock: Callable[[], float] = time.monotonic, on_update: Optional[Callable] = None,
+                 only: Optional[str] = None):
+        self.docs = docs
+        self.planner = planner
+        self.only = only  # Watch just this project id
+        self.debounce = debounce
+        self.clock = clock
+        self.on_update = on_update  # (project, IndexReport) after each update, e.g. to refresh the view
+        self.statuses: Dict[str, WatchStatus] = {}
+        self.roots: Dict[Path, object] = {}  # Folder -> project
+        self._pending: Dict[Path, Tuple[float, str]] = {}  # Path -> (time of its last event, project id)
+        self._lock = threading.Lock()  # Events arrive on the observer's thread
+        self._observer = None
+        self._snapshot: Dict[Path, Tuple[float, int]] = {}
+        self._task: Optional[asyncio.Task] = None
+
+    # --------------------------------------------------------------------------
+    # What's watched
+    # --------------------------------------------------------------------------
+
+    def refresh_roots(self) -> bool:
+        """Re-read active projects' folders; True when they changed."""
+        roots = {}
+        for project in self.planner.get_projects(status="active"):
+            if self.only and project.id != self.only:
+                continue
+            for root in self.docs.roots(project):
+                if root.is_dir():
+                    roots[root] = project
+        changed = set(roots) != set(self.roots)
+        self.roots = roots
+        for project in roots.values():
+            self.statuses.setdefault(project.id, WatchStatus())
+        return changed
+
+    def _locate(self, path: Path):
+        """The (folder, project) a path is in, or None."""
+        for root, project in self.roots.items():
+            if path == root or root in path.parents:
+                return root, project
+        return None
+
+    def notify(self, path) -> None:
+        """A file or directory changed (called from the observer thread)."""
+        path = Path(path)
+        found = self._locate(path)
+        if found is None:
+            return
+        root, project = found
+        if path != root and path.name != ".gitignore":
+            relative = path.relative_to(root).as_posix()
+            if self.docs.rules(root).ignored(relative, is_dir=path.is_dir()):
+                return
+        with self._lock:
+            self._pending[path] = (self.clock(), project.id)
+            self._count_pending()
+
+    def _count_pending(self) -> None:
+        for project_id, status in self.statuses.items():
+            status.pending = sum(1 for _, pending_id in self._pending.values() if pending_id == project_id)
+
+    # --------------------------------------------------------------------------
+    # Indexing
+    # --------------------------------------------------------------------------
+
+    async def flush(self, force: bool = False) -> Dict[str, IndexReport]:
+        """Reindex queued paths that have been quiet for the debounce (all of them with force)."""
+        now = self.clock()
+        with self._lock:
+            ready = [p for p, (at, _) in self._pending.items() if force or now - at >= self.debounce]
+            for path in ready:
+                del self._pending[path]
+            self._count_pending()
+        by_project: Dict[str, Tuple[object, List[Path]]] = {}
+        for path in ready:
+            found = self._locate(path)
+            if found:
+                by_project.setdefault(found[1].id, (found[1], []))[1].append(path)
+
+        reports = {}
+        for project, paths in by_project.values():
+            status = self.statuses.setdefault(project.id, WatchStatus())
+            status.state = "indexing"
+            try:
+                report = await self.docs.update_files(project, paths)
+            except ProjectDocsError as e:
+                status.state, status.error = "error", str(e)
+                with self._lock:  # Try again after the next debounce
+                    for path in paths:
+                        self._pending.setdefault(path, (self.clock(), project.id))
+                    self._count_pending()
+                continue
+            status.state, status.error = "watching", ""
+            if report.files or report.removed:
+                status.updated_at, status.files = datetime.now(), report.files + report.removed
+                reports[project.name] = report
+                if self.on_update:
+                    self.on_update(project, report)
+        return reports
+
+    # --------------------------------------------------------------------------
+    # Running
+    # --------------------------------------------------------------------------
+
+    def _observe(self) -> bool:
+        """(Re)start watchdog on the current folders; False when watchdog isn't installed."""
+        try:
+            from watchdog.events import FileSystemEventHandler
+            from watchdog.observers import Observer
+        except ImportError:
+            return False
+
+        watcher = self
+
+        class Handler(FileSystemEventHandler):
+            def on_any_event(self, event):
+                if event.event_type not in WRITE_EVENTS or event.is_directory and event.event_type == "modified":
+                    return  # Reads, or a directory whose file changed (that file has its own event)
+                watcher.notify(os.fsdecode(event.src_path))
+                if getattr(event, "dest_path", None):
+                    watcher.notify(os.fsdecode(event.dest_path))
+
+        self._stop_observer()
+        self._observer = Observer()
+        for root in self.roots:
+            self._observer.schedule(Handler(), str(root), recursive=True)
+        self._observer.start()
+        return True
+
+    def _stop_observer(self) -> None:
+        if self._observer is not None:
+            self._observer.stop()
+            self._observer.join(timeout=2)
+            self._observer = None
+
+    def scan(self) -> None:
+        """Polling without watchdog: queue files whose size/mtime changed since the last scan, or that went."""
+        snapshot = {}
+        for root in self.roots:
+            for path in crawl(root, self.docs.rules(root)):
+                try:
+                    stat = path.stat()
+                except OSError:
+                    continue
+                snapshot[path] = (stat.st_mtime, stat.st_size)
+        if self._snapshot:
+            for path in set(snapshot) | set(self._snapshot):
+                if snapshot.get(path) != self._snapshot.get(path):
+                    self.notify(path)
+        self._snapshot = snapshot
+
+    async def run(self) -> None:
+        """Watch until cancelled."""
+        self.refresh_roots()
+        watching = self._observe()
+        if not watching:
+            logger.info("watchdog not installed: polling project folders for changes")
+            await asyncio.to_thread(self.scan)
+        roots_checked = last_poll = self.clock()
+        try:
+            while True:
+                await asyncio.sleep(min(0.5, self.debounce / 2) if watching else 1.0)
+                now = self.clock()
+                if now - roots_checked >= ROOTS_SECONDS:
+                    roots_checked = now
+                    if self.refresh_roots() and watching:
+                        self._observe()
+                if not watching and now - last_poll >= POLL_SECONDS:
+                    last_poll = now
+                    await asyncio.to_thread(self.scan)
+                await self.flush()
+        finally:
+            self._stop_observer()
+
+    def start(self) -> None:
+        """Watch in the background (supervised, restarted if it fails)."""
+        if self._task is None:
+            self._task = get_task_supervisor().spawn("project watch", self.run)
+
+    def stop(self) -> None:
+        if self._task is not None:
+            self._task.cancel()
+            self._task = None
+        self._stop_observer()
+
+    def status_line(self, project_id: str) -> str:
+        """One line for the Projects view; "" when the project isn't watched."""
+        status = self.statuses.get(project_id)
+        if status is None:
+            return ""
+        if status.state == "indexing":
+            return "⟳ Updating the index…"
+        if status.state == "error":
+            return f"✗ Index not updated: {status.error}"
+        line = "● Watching for changes"
+        if status.updated_at:
+            files = "1 file" if status.files == 1 else f"{status.files} files"
+            line = f"● Index up to date · {files} updated {status.updated_at:%H:%M}"
+        if status.pending:
+            line += f" · {status.pending} pending"
+        return line
+
+
+_watcher: Optional[ProjectWatcher] = None
+
+
+def get_project_watcher() -> Optional[ProjectWatcher]:
+    """The running project watcher, or None when config.project_watch is off."""
+    return _watcher
+
+
+def set_project_watcher(watcher: Optional[ProjectWatcher]) -> None:
+    global _watcher
+    _watcher = watcher
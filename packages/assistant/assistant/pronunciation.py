@@ -0,0 +1,175 @@
+"""
+Pronunciation - How names the voice gets wrong should be said.
+
+Moshi reads text the way it's spelled, so "Siobhan" comes out as
+"see-ob-han" and a project codename as whatever it looks like. The lexicon
+maps a word to how it's said, and speak_text swaps it in (after the dates
+and times are put into words, before synthesis):
+
+    Siobhan: shiv-AWN                 a respelling, read as written
+    Nguyen: /wɪn/                     IPA between slashes, turned into a respelling ("win")
+    k8s: kubernetes                   any word or phrase
+
+Words match whole and ignoring case ("Siobhan's" becomes "shiv-AWN's").
+A persona can say a word its own way - its entries win over the shared
+ones while it's the current persona.
+
+The lexicon is a YAML file the user can edit by hand; edits are picked up
+at the next thing said. `xswarm dev tts pronounce add "Siobhan" "shiv-AWN"`
+(--persona NAME for one persona) adds to it, `... list` and `... remove`
+review it, and `... test "text"` shows how text will be read. In
+conversation, the set_pronunciation tool adds to it ("it's pronounced
+shiv-AWN").
+
+Storage: ~/.xswarm/pronunciations.yaml
+"""
+
+import logging
+import re
+from pathlib import Path
+from typing import Dict, Optional, Tuple
+
+import yaml
+
+from .capabilities import register_capability
+
+logger = logging.getLogger(__name__)
+
+HEADER = "# How words are said aloud (see assistant/pronunciation.py)\n"
+
+# IPA to English-reading letters, longest symbols first; stress (ˈ) capitalizes the syllable after it
+IPA_SOUNDS = [
+    ("tʃ", "ch"), ("dʒ", "j"), ("aɪ", "eye"), ("eɪ", "ay"), ("ɔɪ", "oy"), ("aʊ", "ow"), ("oʊ", "oh"),
+    ("əʊ", "oh"), ("ɪə", "eer"), ("eə", "air"), ("ʊə", "oor"), ("iː", "ee"), ("uː", "oo"), ("ɑː", "ah"),
+    ("ɔː", "aw"), ("ɜː", "ur"), ("ʃ", "sh"), ("ʒ", "zh"), ("θ", "th"), ("ð", "th"), ("ŋ", "ng"), ("j", "y"),
+    ("ɪ", "i"), ("ʊ", "u"), ("ɛ", "e"), ("æ", "a"), ("ʌ", "u"), ("ɒ", "o"), ("ɑ", "ah"), ("ɔ", "aw"),
+    ("ə", "uh"), ("ɚ", "er"), ("ɝ", "ur"), ("ɹ", "r"), ("ɾ", "t"), ("ʔ", ""), ("ɡ", "g"), ("i", "ee"),
+    ("u", "oo"), ("e", "eh"), ("o", "oh"), ("a", "ah"), ("ː", ""),
+]
+_IPA = re.compile(r"^/(?P<ipa>[^/]+)/$")
+
+
+def respell(spoken: str) -> str:
+    """What is read aloud for an entry: a respelling as is, IPA (/ʃɪˈvɔːn/) turned into one."""
+    match = _IPA.match(spoken.strip())
+    if not match:
+        return spoken.strip()
+    syllables = []
+    for syllable in re.split(r"[.ˌ]|(?=ˈ)", match.group("ipa").strip()):
+        stressed = syllable.startswith("ˈ")
+        letters, rest = "", syllable.lstrip("ˈ")
+        while rest:
+            for symbol, sound in IPA_SOUNDS:
+                if rest.startswith(symbol):
+                    letters, rest = letters + sound, rest[len(symbol):]
+                    break
+            else:
+                letters, rest = letters + rest[0], rest[1:]
+        if letters:
+            syllables.append(letters.upper() if stressed else letters)
+    return "-".join(syllables)
+
+
+class PronunciationLexicon:
+    """Words and how to say them, shared and per persona, from a user-editable YAML file."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "pronunciations.yaml"
+
+    def __init__(self, path: Optional[Path] = None):
+        self.path = path or self.DEFAULT_PATH
+        self._data: Optional[Dict] = None
+        self._mtime: Optional[float] = None
+        self._patterns: Dict[Optional[str], Tuple[Optional[re.Pattern], Dict[str, str]]] = {}
+
+    def _load(self) -> Dict:
+        mtime = self.path.stat().st_mtime if self.path.exists() else None
+        if self._data is not None and mtime == self._mtime:
+            return self._data
+        self._data, self._mtime, self._patterns = {"words": {}, "personas": {}}, mtime, {}
+        if mtime is not None:
+            try:
+                raw = yaml.safe_load(self.path.read_text(encoding="utf-8")) or {}
+                self._data["words"] = {str(k): str(v) for k, v in (raw.get("words") or {}).items()}
+                self._data["personas"] = {str(name): {str(k): str(v) for k, v in (words or {}).items()}
+                                          for name, words in (raw.get("personas") or {}).items()}
+            except Exception as e:
+                logger.warning(f"Ignoring pronunciations in {self.path}: {e}")
+        return self._data
+
+    def _save(self) -> None:
+        data = self._load()
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        body = yaml.safe_dump({"words": data["words"], "personas": data["personas"]},
+                              allow_unicode=True, default_flow_style=False, sort_keys=True)
+        self.path.write_text(HEADER + body, encoding="utf-8")
+        self._data = None  # Reloaded, with the new mtime, next time
+
+    def _persona_words(self, persona: Optional[str]) -> Dict[str, str]:
+        personas = self._load()["personas"]
+        matching = [name for name in personas if persona and name.lower() == persona.lower()]
+        return personas[matching[0]] if matching else {}
+
+    def entries(self, persona: Optional[str] = None) -> Dict[str, str]:
+        """Every word and how it's said, with `persona`'s own entries over the shared ones."""
+        merged = {word.lower(): (word, spoken) for word, spoken in self._load()["words"].items()}
+        merged.update({word.lower(): (word, spoken) for word, spoken in self._persona_words(persona).items()})
+        return dict(merged.values())
+
+    def add(self, word: str, spoken: str, persona: Optional[str] = None) -> None:
+        word, spoken = " ".join(word.split()), spoken.strip()
+        if not word or not spoken:
+            raise ValueError("Both the word and how to say it are needed")
+        data = self._load()
+        if persona:
+            persona = next((name for name in data["personas"] if name.lower() == persona.lower()), persona)
+        words = data["personas"].setdefault(persona, {}) if persona else data["words"]
+        for existing in [w for w in words if w.lower() == word.lower()]:
+            del words[existing]
+        words[word] = spoken
+        self._save()
+
+    def remove(self, word: str, persona: Optional[str] = None) -> bool:
+        data = self._load()
+        words = self._persona_words(persona) if persona else data["words"]
+        found = [w for w in words if w.lower() == " ".join(word.split()).lower()]
+        for existing in found:
+            del words[existing]
+        if found:
+            data["personas"] = {name: entries for name, entries in data["personas"].items() if entries}
+            self._save()
+        return bool(found)
+
+    def apply(self, text: str, persona: Optional[str] = None) -> str:
+        """`text` with every word in the lexicon swapped for how it's said."""
+        self._load()
+        if persona not in self._patterns:
+            said = {word.lower(): respell(spoken) for word, spoken in self.entries(persona).items()}
+            # Longest first, so "Siobhan Kelly" wins over "Siobhan"
+            words = "|".join(re.escape(word) for word in sorted(said, key=len, reverse=True))
+            pattern = re.compile(rf"(?<![\w-])(?:{words})(?![\w-])", re.IGNORECASE) if said else None
+            self._patterns[persona] = (pattern, said)
+        pattern, said = self._patterns[persona]
+        if pattern is None:
+            return text
+        return pattern.sub(lambda match: said[" ".join(match.group(0).lower().split())], text)
+
+
+_lexicon: Optional[PronunciationLexicon] = None
+
+
+def get_lexicon() -> PronunciationLexicon:
+    """Get the global pronunciation lexicon."""
+    global _lexicon
+    if _lexicon is None:
+        _lexicon = PronunciationLexicon()
+    return _lexicon
+
+
+def set_lexicon(lexicon: Optional[PronunciationLexicon]) -> None:
+    global _lexicon
+    _lexicon = lexicon
+
+
+register_capability("Pronunciation", "Say names and words the way you tell me to",
+                    ["Siobhan is pronounced shiv-AWN", "you're saying my name wrong"],
+                    category="Settings", keywords=("pronounce", "pronunciation", "say", "name"))
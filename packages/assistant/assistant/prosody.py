@@ -0,0 +1,198 @@
+"""
+Prosody - Speech hints in replies: pauses, emphasis, spelled-out codes.
+
+Replies meant to be spoken can carry a small SSML-like markup, which the
+persona's spoken prompt teaches the model (PersonaConfig.build_system_prompt
+with spoken=True, unless the persona turns voice.markup off):
+
+    Your code is <say-as interpret-as="characters">XK92</say-as>.
+    That's <emphasis>today</emphasis>, not tomorrow.
+    Ready? <break time="700ms"/> Go.
+
+Each speech engine says which of these it honors (MarkupSupport) and
+render() turns a reply into what that engine takes:
+
+- SSML engines (Twilio's Polly voices for calls, phone.py) get SSML, with
+  the plain text escaped; a hint the engine lacks is put into words instead
+- Moshi reads a text prompt (MoshiClient.markup is PLAIN), so hints become
+  words and punctuation: a code is spelled "X K 9 2", a pause is a comma or
+  an ellipsis, emphasis is just the word
+
+strip() gives the text as shown in chat, hints removed. Anything that isn't
+one of these tags - "a < b", <thinking> - is left alone, and tags left open
+close at the end of the text.
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Dict, FrozenSet, List, Optional, Union
+from xml.sax.saxutils import escape, quoteattr
+
+TAGS = ("break", "emphasis", "say-as")
+SPELLED = ("characters", "spell-out", "digits")  # say-as interpret-as values read character by character
+EMPHASIS_LEVELS = ("strong", "moderate", "reduced")
+BREAK_STRENGTHS = {"none": 0, "x-weak": 100, "weak": 250, "medium": 400, "strong": 700, "x-strong": 1200}
+LONG_PAUSE_MS = 600  # A plain-text pause this long or longer is an ellipsis, shorter ones a comma
+
+PROMPT_GUIDE = (
+    "Your replies are spoken aloud. Where it helps the listener you may add speech hints: "
+    "<break time=\"500ms\"/> for a pause, <emphasis>word</emphasis> to stress a word, and "
+    "<say-as interpret-as=\"characters\">XK92</say-as> to spell out codes, PINs and reference numbers. "
+    "Use them sparingly and no other tags."
+)
+
+_TAG = re.compile(r"<(?P<close>/?)(?P<tag>break|emphasis|say-as)\b(?P<attrs>[^<>]*?)(?P<empty>/?)>", re.IGNORECASE)
+_ATTR = re.compile(r"([\w-]+)\s*=\s*(?:\"([^\"]*)\"|'([^']*)')")
+_DURATION = re.compile(r"^\s*(\d+(?:\.\d+)?)\s*(ms|s)\s*$", re.IGNORECASE)
+
+
+@dataclass(frozen=True)
+class MarkupSupport:
+    """The hints a speech engine honors; the others are put into words."""
+
+    tags: FrozenSet[str] = frozenset()
+    speak_root: bool = True  # Wrap SSML in <speak> (Twilio's <Say> takes the tags bare)
+
+    @property
+    def ssml(self) -> bool:
+        return bool(self.tags)
+
+
+PLAIN = MarkupSupport()
+SSML = MarkupSupport(frozenset(TAGS))
+POLLY_NEURAL = MarkupSupport(frozenset({"break", "say-as"}), speak_root=False)  # Neural voices ignore emphasis
+
+
+@dataclass
+class Hint:
+    tag: str
+    attrs: Dict[str, str] = field(default_factory=dict)
+    children: List[Union[str, "Hint"]] = field(default_factory=list)
+
+
+def parse(text: str) -> List[Union[str, Hint]]:
+    """Text and hints, nested as written; stray closing tags are dropped."""
+    root = Hint("")
+    stack = [root]
+    position = 0
+    for match in _TAG.finditer(text):
+        if match.start() > position:
+            stack[-1].children.append(text[position:match.start()])
+        position = match.end()
+        tag = match.group("tag").lower()
+        if match.group("close"):
+            if any(hint.tag == tag for hint in stack[1:]):
+                while stack.pop().tag != tag:
+                    pass
+            continue
+        attrs = {name.lower(): double or single for name, double, single in _ATTR.findall(match.group("attrs"))}
+        hint = Hint(tag, attrs)
+        stack[-1].children.append(hint)
+        if tag != "break" and not match.group("empty"):
+            stack.append(hint)
+    if position < len(text):
+        stack[-1].children.append(text[position:])
+    return root.children
+
+
+def pause_ms(hint: Hint) -> int:
+    """How long a break is: its time ("500ms", "1.5s"), else its strength, else medium."""
+    duration = _DURATION.match(hint.attrs.get("time", ""))
+    if duration:
+        value = float(duration.group(1))
+        return int(value * 1000 if duration.group(2).lower() == "s" else value)
+    return BREAK_STRENGTHS.get(hint.attrs.get("strength", "").lower(), BREAK_STRENGTHS["medium"])
+
+
+def _spelled(text: str) -> str:
+    """"XK-92" -> "X K, 9 2": one character at a time, a comma between groups."""
+    groups = [" ".join(ch for ch in group if ch.isalnum()) for group in re.split(r"[\s\-_./]+", text)]
+    return ", ".join(group for group in groups if group)
+
+
+def _inner_text(nodes: List[Union[str, Hint]]) -> str:
+    return "".join(node if isinstance(node, str) else _inner_text(node.children) for node in nodes)
+
+
+class _Plain:
+    """Hints put into words and punctuation, for engines that read text as written."""
+
+    def __init__(self):
+        self.out = ""
+        self.after_pause = False
+
+    def text(self, text: str) -> None:
+        if self.after_pause:
+            text = text.lstrip()
+            self.after_pause = not text
+        self.out += text
+
+    def pause(self, ms: int) -> None:
+        if ms <= 0 or not self.out.strip():
+            return
+        self.out = self.out.rstrip()
+        if ms >= LONG_PAUSE_MS:
+            self.out = self.out.rstrip(",")
+            if not self.out.endswith("..."):
+                self.out += " ..." if self.out[-1] in ".;:!?" else "..."
+        elif self.out[-1] not in ".,;:!?":
+            self.out += ","
+        self.out += " "
+        self.after_pause = True
+
+
+def _plain(nodes: List[Union[str, Hint]], out: _Plain) -> None:
+    for node in nodes:
+        if isinstance(node, str):
+            out.text(node)
+        elif node.tag == "break":
+            out.pause(pause_ms(node))
+        elif node.tag == "say-as" and node.attrs.get("interpret-as", "").lower() in SPELLED:
+            out.text(_spelled(_inner_text(node.children)))
+        else:
+            _plain(node.children, out)
+
+
+def _ssml(nodes: List[Union[str, Hint]], support: MarkupSupport) -> str:
+    parts = []
+    for node in nodes:
+        if isinstance(node, str):
+            parts.append(escape(node))
+        elif node.tag not in support.tags:
+            out = _Plain()
+            _plain([node], out)
+            parts.append(escape(out.out))
+        elif node.tag == "break":
+            parts.append(f"<break time=\"{pause_ms(node)}ms\"/>")
+        elif node.tag == "emphasis":
+            level = node.attrs.get("level", "").lower()
+            level = f" level={quoteattr(level)}" if level in EMPHASIS_LEVELS else ""
+            parts.append(f"<emphasis{level}>{_ssml(node.children, support)}</emphasis>")
+        else:
+            interpret = node.attrs.get("interpret-as", "").lower()
+            if interpret not in SPELLED:
+                parts.append(_ssml(node.children, support))
+            else:
+                parts.append(f"<say-as interpret-as={quoteattr(interpret)}>"
+                             f"{escape(_inner_text(node.children))}</say-as>")
+    return "".join(parts)
+
+
+def render(text: str, support: Optional[MarkupSupport] = None) -> str:
+    """A reply as a speech engine takes it: SSML for engines with markup, plain words for the rest."""
+    support = support or PLAIN
+    if not support.ssml:
+        if not _TAG.search(text):
+            return text
+        out = _Plain()
+        _plain(parse(text), out)
+        return out.out.strip()
+    body = _ssml(parse(text), support)
+    return f"<speak>{body}</speak>" if support.speak_root else body
+
+
+def strip(text: str) -> str:
+    """A reply as shown: hints removed, what they wrapped kept as written."""
+    if not _TAG.search(text):
+        return text
+    return re.sub(r"[ \t]{2,}", " ", _inner_text(parse(text))).strip()
@@ -0,0 +1,410 @@
+"""
+Push Notifications - Selected events forwarded to ntfy.sh or Pushover.
+
+Some events matter when you're away from the desk. Each event class is
+routed to its own push targets (config.push_routes):
+
+- missed_reminder: a reminder quiet hours or preferences kept to the
+  activity feed
+- error: an error in the activity feed
+- task_done: a background job that ran for at least config.long_job_seconds
+  finishing
+
+Targets are "ntfy:<topic>" (settings in config.push_providers["ntfy"]:
+server, token) or "pushover" / "pushover:<device>" (token, user). Every
+provider has its own rate limit ("per_hour", default PER_HOUR), so a burst
+of errors sends a handful of pushes rather than hundreds; pushes over it
+are dropped and logged. Pushes also follow the notification policy's
+"push" channel (quiet hours, do not disturb).
+
+The router listens to the event history (events.add_event_listener) and
+sends from a background thread, so neither the UI nor jobs wait on the
+network. `xswarm dev push test CLASS` sends a test push.
+
+A push that fails goes into the DeliveryQueue (~/.xswarm/push_queue.json,
+so it survives restarts) and is retried by the push_retry job, waiting
+config.push_retry_seconds, then twice as long each time up to
+config.push_retry_max_seconds. After config.push_retry_attempts tries it's
+kept as a dead letter: the dashboard says so, and `xswarm dev push retry`
+(or asking the assistant to retry failed notifications) sends it again.
+"""
+
+import json
+import logging
+import threading
+import time
+import uuid
+from concurrent.futures import ThreadPoolExecutor
+from dataclasses import asdict, dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+from .notifications import get_notification_policy
+from .rate_limit import TokenBucket
+
+logger = logging.getLogger(__name__)
+
+# Event class -> (push title, priority)
+EVENT_CLASSES = {
+    "missed_reminder": ("Missed reminder", "normal"),
+    "error": ("xswarm error", "high"),
+    "task_done": ("Task finished", "low"),
+}
+PER_HOUR = 20  # Default pushes per provider per hour
+RETRY_ATTEMPTS = 6
+RETRY_SECONDS = 30.0  # Wait before the first retry; doubles after each failure
+RETRY_MAX_SECONDS = 60 * 60.0
+NTFY_SERVER = "https://ntfy.sh"
+PUSHOVER_URL = "https://api.pushover.net/1/messages.json"
+
+
+class PushError(Exception):
+    """Push settings that can't work (unknown provider, missing credentials)."""
+
+
+@dataclass
+class Push:
+    """One notification for one target."""
+    title: str
+    message: str
+    priority: str = "normal"
+    target: str = ""  # ntfy topic or Pushover device ("" = all devices)
+    event_class: str = ""
+
+
+@dataclass
+class FailedPush:
+    """A push waiting for a retry, or a dead letter once out of attempts."""
+    id: str
+    target: str
+    push: Push
+    attempts: int  # Tries so far
+    next_at: float  # time.time() of the next retry
+    error: str  # Why the last try failed
+    failed_at: str  # ISO time of the last failure
+    dead: bool = False
+
+    def line(self) -> str:
+        state = "gave up" if self.dead else "retrying"
+        return (f"{self.id}  {self.target}  {self.push.title}: {self.push.message[:60]}  "
+                f"({state} after {self.attempts}: {self.error})")
+
+
+class DeliveryQueue:
+    """Failed pushes to retry with exponential backoff, persisted, with dead letters kept for a manual retry."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "push_queue.json"
+
+    def __init__(self, path: Optional[Path] = None, max_attempts: int = RETRY_ATTEMPTS,
+                 backoff: float = RETRY_SECONDS, max_backoff: float = RETRY_MAX_SECONDS,
+                 clock: Callable[[], float] = time.time, on_dead: Optional[Callable[[FailedPush], None]] = None):
+        self.path = path or self.DEFAULT_PATH
+        self.max_attempts = max(1, max_attempts)
+        self.backoff = backoff
+        self.max_backoff = max_backoff
+        self.clock = clock
+        self.on_dead = on_dead  # Called (from the sending thread) when an entry runs out of attempts
+        self._lock = threading.Lock()  # Sends and retries run on the push thread, commands on others
+
+    @classmethod
+    def from_config(cls, config, **kwargs) -> "DeliveryQueue":
+        return cls(max_attempts=getattr(config, "push_retry_attempts", RETRY_ATTEMPTS),
+                   backoff=getattr(config, "push_retry_seconds", RETRY_SECONDS),
+                   max_backoff=getattr(config, "push_retry_max_seconds", RETRY_MAX_SECONDS), **kwargs)
+
+    def _load(self) -> Dict[str, FailedPush]:
+        # Read every time: `xswarm dev push retry` changes the file under a running dashboard
+        entries = {}
+        try:
+            if self.path.exists():
+                for raw in json.loads(self.path.read_text(encoding="utf-8")):
+                    entry = FailedPush(**{**raw, "push": Push(**raw["push"])})
+                    entries[entry.id] = entry
+        except Exception as e:
+            logger.warning(f"Failed to load the push retry queue: {e}")
+        return entries
+
+    def _save(self, entries: Dict[str, FailedPush]) -> None:
+        try:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            self.path.write_text(json.dumps([asdict(e) for e in entries.values()], indent=2), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save the push retry queue: {e}")
+
+    def delay(self, attempts: int) -> float:
+        """Seconds to wait after the `attempts`th failure."""
+        return min(self.backoff * 2 ** (attempts - 1), self.max_backoff)
+
+    def failed(self, target: str, push: Push, error: str) -> FailedPush:
+        """A push failed for the first time: queue it for a retry."""
+        return self.retry_failed(FailedPush(uuid.uuid4().hex[:8], target, push, 0, 0.0, "", ""), error)
+
+    def retry_failed(self, entry: FailedPush, error: str) -> FailedPush:
+        """A try failed: wait longer, or give up once out of attempts."""
+        with self._lock:
+            entries = self._load()
+            entry = entries.setdefault(entry.id, entry)
+            entry.attempts += 1
+            entry.error = error
+            entry.failed_at = datetime.now().isoformat(timespec="seconds")
+            entry.dead = entry.attempts >= self.max_attempts
+            entry.next_at = self.clock() + self.delay(entry.attempts)
+            self._save(entries)
+        if entry.dead and self.on_dead:
+            self.on_dead(entry)
+        return entry
+
+    def delivered(self, entry: FailedPush) -> None:
+        with self._lock:
+            entries = self._load()
+            if entries.pop(entry.id, None) is not None:
+                self._save(entries)
+
+    def due(self) -> List[FailedPush]:
+        """Entries whose retry time has come."""
+        now = self.clock()
+        with self._lock:
+            return [e for e in self._load().values() if not e.dead and e.next_at <= now]
+
+    def pending(self) -> List[FailedPush]:
+        with self._lock:
+            return [e for e in self._load().values() if not e.dead]
+
+    def dead(self) -> List[FailedPush]:
+        with self._lock:
+            return [e for e in self._load().values() if e.dead]
+
+    @staticmethod
+    def _select(entries: Dict[str, FailedPush], ids: Optional[Iterable[str]]) -> List[FailedPush]:
+        if ids:
+            return [entries[i] for i in ids if i in entries]
+        return [e for e in entries.values() if e.dead]
+
+    def retry(self, ids: Optional[Iterable[str]] = None) -> List[FailedPush]:
+        """Give dead letters (or the given entries) a fresh set of attempts, due now."""
+        with self._lock:
+            entries = self._load()
+            selected = self._select(entries, ids)
+            for entry in selected:
+                entry.dead, entry.attempts, entry.next_at = False, 0, 0.0
+            if selected:
+                self._save(entries)
+        return selected
+
+    def discard(self, ids: Optional[Iterable[str]] = None) -> int:
+        """Forget dead letters (or the given entries)."""
+        with self._lock:
+            entries = self._load()
+            selected = self._select(entries, ids)
+            for entry in selected:
+                del entries[entry.id]
+            if selected:
+                self._save(entries)
+        return len(selected)
+
+
+def classify(event) -> Optional[str]:
+    """The push event class of a recorded event, or None when it isn't one."""
+    if event.type == "reminder" and event.data.get("delivered") is False:
+        return "missed_reminder"
+    if event.type == "activity" and event.data.get("level") == "error":
+        return "error"
+    if event.type == "task":
+        return "task_done"
+    return None
+
+
+def _http_post(url: str, **kwargs) -> None:
+    import httpx
+
+    response = httpx.post(url, timeout=10, **kwargs)
+    response.raise_for_status()
+
+
+class NtfyProvider:
+    """Publishes to ntfy.sh (or a self-hosted server) topics."""
+
+    name = "ntfy"
+    PRIORITIES = {"low": 2, "normal": 3, "high": 4, "emergency": 5}
+
+    def __init__(self, server: str = NTFY_SERVER, token: Optional[str] = None,
+                 post: Callable[..., None] = _http_post):
+        self.server = server.rstrip("/")
+        self.token = token
+        self.post = post
+
+    def validate(self, target: str) -> None:
+        if not target:
+            raise PushError("ntfy targets need a topic (\"ntfy:my-topic\")")
+
+    def send(self, push: Push) -> None:
+        headers = {"Authorization": f"Bearer {self.token}"} if self.token else {}
+        body = {"topic": push.target, "title": push.title, "message": push.message,
+                "priority": self.PRIORITIES.get(push.priority, 3)}
+        if push.event_class:
+            body["tags"] = [push.event_class]
+        self.post(self.server, json=body, headers=headers)
+
+
+class PushoverProvider:
+    """Sends through Pushover to one user (optionally one of their devices)."""
+
+    name = "pushover"
+    # Pushover's emergency priority (2) repeats until acknowledged; high is as far as xswarm goes
+    PRIORITIES = {"low": -1, "normal": 0, "high": 1, "emergency": 1}
+
+    def __init__(self, token: str, user: str, post: Callable[..., None] = _http_post):
+        if not token or not user:
+            raise PushError("Pushover needs both an application token and a user key")
+        self.token = token
+        self.user = user
+        self.post = post
+
+    def validate(self, target: str) -> None:
+        pass  # Any device name, or none for all of them
+
+    def send(self, push: Push) -> None:
+        data = {"token": self.token, "user": self.user, "title": push.title, "message": push.message,
+                "priority": self.PRIORITIES.get(push.priority, 0)}
+        if push.target:
+            data["device"] = push.target
+        self.post(PUSHOVER_URL, data=data)
+
+
+def make_provider(name: str, settings: Dict[str, Any]):
+    if name == "ntfy":
+        return NtfyProvider(settings.get("server") or NTFY_SERVER, settings.get("token"))
+    if name == "pushover":
+        return PushoverProvider(settings.get("token"), settings.get("user"))
+    raise PushError(f"Unknown push provider '{name}' (providers: ntfy, pushover)")
+
+
+class PushRouter:
+    """Routes event classes to push targets, rate limited per provider."""
+
+    def __init__(self, providers: Dict[str, Any], routes: Dict[str, List[str]],
+                 per_hour: Optional[Dict[str, int]] = None, policy=None,
+                 run: Optional[Callable[..., Any]] = None, clock: Callable[[], float] = time.monotonic,
+                 queue: Optional[DeliveryQueue] = None):
+        for event_class, targets in routes.items():
+            if event_class not in EVENT_CLASSES:
+                raise PushError(f"Unknown push event class '{event_class}' (classes: {', '.join(EVENT_CLASSES)})")
+            for target in targets:
+                provider, _, topic = target.partition(":")
+                if provider not in providers:
+                    raise PushError(f"Push target '{target}' needs config.push_providers['{provider}']")
+                providers[provider].validate(topic)
+        self.providers = providers
+        self.routes = routes
+        self.policy = policy
+        self.clock = clock
+        self._buckets = {name: self._bucket((per_hour or {}).get(name, PER_HOUR)) for name in providers}
+        self._executor: Optional[ThreadPoolExecutor] = None
+        self._run = run
+        self.queue = queue  # Failed pushes are retried from here (None: dropped)
+
+    def _bucket(self, per_hour: int) -> TokenBucket:
+        return TokenBucket(per_hour / 3600, max(1, per_hour), clock=self.clock)
+
+    @classmethod
+    def from_config(cls, config, **kwargs) -> Optional["PushRouter"]:
+        """The configured router, or None when no routes are set. Raises PushError for bad settings."""
+        routes = {name: list(targets) for name, targets in (getattr(config, "push_routes", None) or {}).items()
+                  if targets}
+        if not routes:
+            return None
+        settings = getattr(config, "push_providers", None) or {}
+        providers = {name: make_provider(name, values or {}) for name, values in settings.items()}
+        per_hour = {name: int((values or {}).get("per_hour", PER_HOUR)) for name, values in settings.items()}
+        kwargs.setdefault("queue", DeliveryQueue.from_config(config))
+        return cls(providers, routes, per_hour, **kwargs)
+
+    def pushes(self, event_class: str, message: str) -> List[Tuple[str, Push]]:
+        """(target, push) for an event of `event_class`, one per configured target."""
+        title, priority = EVENT_CLASSES[event_class]
+        return [(target, Push(title, message, priority, target.partition(":")[2], event_class))
+                for target in self.routes.get(event_class, [])]
+
+    def on_event(self, event) -> int:
+        """Event listener: push events that belong to a routed class; returns how many were queued."""
+        event_class = classify(event)
+        if event_class is None or event_class not in self.routes:
+            return 0
+        return self.push(event_class, event.summary.lstrip("✗⚠ "))
+
+    def push(self, event_class: str, message: str) -> int:
+        """Queue pushes for `event_class` to its targets, within rate limits; returns how many were queued."""
+        _, priority = EVENT_CLASSES[event_class]
+        policy = self.policy or get_notification_policy()
+        decision = policy.check("push", priority)
+        if not decision.allowed:
+            logger.debug(f"Push for {event_class} suppressed ({decision.reason})")
+            return 0
+        queued = 0
+        for target, push in self.pushes(event_class, message):
+            name = target.partition(":")[0]
+            if not self._buckets[name].take():
+                logger.warning(f"Push to {target} dropped: over the {name} rate limit")
+                continue
+            self._submit(self.providers[name], push, target)
+            queued += 1
+        return queued
+
+    def send_now(self, event_class: str, message: str) -> Dict[str, Optional[str]]:
+        """Send right away, past the policy and rate limits (`dev push test`); target -> error, None if sent."""
+        results = {}
+        for target, push in self.pushes(event_class, message):
+            try:
+                self.providers[target.partition(":")[0]].send(push)
+                results[target] = None
+            except Exception as e:
+                results[target] = str(e)
+        return results
+
+    def _submit(self, provider, push: Push, target: str) -> None:
+        if self._run is not None:
+            self._run(self._send, provider, push, target)
+            return
+        if self._executor is None:
+            self._executor = ThreadPoolExecutor(max_workers=1, thread_name_prefix="push")
+        self._executor.submit(self._send, provider, push, target)
+
+    def _send(self, provider, push: Push, target: str) -> None:
+        try:
+            provider.send(push)
+        except Exception as e:
+            if self.queue is None:
+                logger.warning(f"Push to {target} failed: {e}")
+                return
+            entry = self.queue.failed(target, push, str(e))
+            logger.warning(f"Push to {target} failed, retrying in {self.queue.delay(entry.attempts):.0f}s: {e}")
+
+    def retry_due(self) -> Tuple[int, int]:
+        """Resend queued pushes whose time has come (push_retry job; blocking). Returns (sent, failed)."""
+        if self.queue is None:
+            return 0, 0
+        sent = failed = 0
+        for entry in self.queue.due():
+            name = entry.target.partition(":")[0]
+            provider = self.providers.get(name)
+            if provider is None:
+                self.queue.retry_failed(entry, f"no {name} provider configured")
+                failed += 1
+                continue
+            if not self._buckets[name].take():
+                continue  # Over the rate limit: still due next time
+            try:
+                provider.send(entry.push)
+            except Exception as e:
+                self.queue.retry_failed(entry, str(e))
+                failed += 1
+                continue
+            self.queue.delivered(entry)
+            sent += 1
+        return sent, failed
+
+    def close(self) -> None:
+        if self._executor is not None:
+            self._executor.shutdown(wait=False)
+            self._executor = None
@@ -0,0 +1,417 @@
+"""
+Quick Math - Arithmetic, unit and currency conversion answered on the spot.
+
+Like volume and undo requests, these never reach the model: the dashboard
+asks answer_quick_question() first and speaks the answer straight away.
+
+    "what's 15% of 240"                 -> 15% of 240 is 36
+    "twelve times seven"                -> 12 times 7 is 84
+    "square root of 81 plus 2"          -> square root of 81 plus 2 is 11
+    "convert 5 miles to km"             -> 5 miles is 8.05 km
+    "how many ounces in a pound"        -> 1 pound is 16 ounces
+    "72 fahrenheit in celsius"          -> 72 fahrenheit is 22.22 celsius
+    "100 dollars in euros" / "€50 to GBP"
+
+Only whole utterances that are a calculation or a conversion are taken, so
+"add 2 eggs to the list" or "remind me in 5 minutes" still go to the model.
+Expressions are parsed (ast), never eval'd.
+
+Currency uses exchange rates cached in ~/.xswarm/rates/rates.json,
+refreshed by the dashboard's exchange_rates job (open.er-api.com, no key)
+with config.exchange_rates. Offline, the last rates are used; answers say
+when they're older than STALE_DAYS.
+"""
+
+import ast
+import json
+import logging
+import math
+import operator
+import re
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Optional, Tuple
+
+from .capabilities import register_capability
+
+logger = logging.getLogger(__name__)
+
+RATES_URL = "https://open.er-api.com/v6/latest/USD"
+STALE_DAYS = 3  # Older rates are used, with their date
+MAX_EXPONENT = 100  # 9 ** 9 ** 9 would hang the event loop
+
+# ==============================================================================
+# NUMBERS
+# ==============================================================================
+
+_ONES = {
+    "zero": 0, "one": 1, "two": 2, "three": 3, "four": 4, "five": 5, "six": 6, "seven": 7, "eight": 8,
+    "nine": 9, "ten": 10, "eleven": 11, "twelve": 12, "thirteen": 13, "fourteen": 14, "fifteen": 15,
+    "sixteen": 16, "seventeen": 17, "eighteen": 18, "nineteen": 19,
+}
+_TENS = {"twenty": 20, "thirty": 30, "forty": 40, "fifty": 50, "sixty": 60, "seventy": 70, "eighty": 80,
+         "ninety": 90}
+_SCALES = {"hundred": 100, "thousand": 1000, "million": 10 ** 6, "billion": 10 ** 9}
+_NUMBER_WORD = re.compile(
+    r"\b(?:(?:%s)(?:[\s-]+(?:and\s+)?(?:%s|point\s+\w+))*)\b" % (
+        "|".join([*_ONES, *_TENS, *_SCALES]), "|".join([*_ONES, *_TENS, *_SCALES])),
+    re.IGNORECASE)
+
+
+def _words_value(words: str) -> Optional[float]:
+    """"two hundred and five" -> 205, "three point five" -> 3.5."""
+    tokens = [t for t in re.split(r"[\s-]+", words.lower()) if t and t != "and"]
+    total, current, decimals = 0, 0, None
+    for token in tokens:
+        if decimals is not None:
+            if token not in _ONES or _ONES[token] > 9:
+                return None
+            decimals += str(_ONES[token])
+        elif token == "point":
+            decimals = ""
+        elif token in _ONES:
+            current += _ONES[token]
+        elif token in _TENS:
+            current += _TENS[token]
+        elif token == "hundred":
+            current = (current or 1) * 100
+        elif token in _SCALES:
+            total += (current or 1) * _SCALES[token]
+            current = 0
+        else:
+            return None
+    value = total + current
+    return value + float(f"0.{decimals}") if decimals else value
+
+
+def numbers_to_digits(text: str) -> str:
+    """Spell-out numbers as digits: "twelve times seven" -> "12 times 7"."""
+    def replace(match: re.Match) -> str:
+        value = _words_value(match.group(0))
+        return match.group(0) if value is None else _format(value)
+    return _NUMBER_WORD.sub(replace, text)
+
+
+def _format(value: float, decimals: int = 2) -> str:
+    if isinstance(value, int) or (abs(value) < 1e15 and value == round(value)):
+        return f"{int(round(value)):,}" if abs(value) >= 10000 else str(int(round(value)))
+    if abs(value) >= 1e15:
+        return f"{value:.3e}"
+    if abs(value) < 0.01:
+        return f"{value:.3g}"
+    text = f"{value:,.{decimals}f}" if abs(value) >= 10000 else f"{value:.{decimals}f}"
+    return text.rstrip("0").rstrip(".")
+
+
+# ==============================================================================
+# ARITHMETIC
+# ==============================================================================
+
+_OPERATORS = {
+    ast.Add: operator.add, ast.Sub: operator.sub, ast.Mult: operator.mul, ast.Div: operator.truediv,
+    ast.Mod: operator.mod, ast.Pow: operator.pow, ast.FloorDiv: operator.floordiv,
+}
+_WORD_OPERATORS = [
+    (r"\bsquare\s+root\s+of\s+(\d+(?:\.\d+)?)", r"sqrt(\1)"),
+    (r"\bto\s+the\s+power\s+of\b", "**"),
+    (r"\bsquared\b", "**2"),
+    (r"\bcubed\b", "**3"),
+    (r"\bmultiplied\s+by\b|\btimes\b|(?<=\d)\s*x\s*(?=\d)|×", "*"),
+    (r"\bdivided\s+by\b|\bover\b|÷", "/"),
+    (r"\bplus\b|\band\b", "+"),
+    (r"\bminus\b|\btake\s+away\b|−", "-"),
+    (r"\bmod(?:ulo)?\b", "%"),
+    (r"\^", "**"),
+]
+_PERCENT_OF = re.compile(r"^(\d+(?:\.\d+)?)\s*(?:%|percent)\s+of\s+(.+)$", re.IGNORECASE)
+_EXPRESSION = re.compile(r"^[\d\s.+\-*/%()sqrt]+$")
+_LEAD = re.compile(
+    r"^\s*(?:(?:ok(?:ay)?|hey|please|quick(?:ly)?|so)[,\s]+)*"
+    r"(?P<ask>(?:what(?:'s|\s+is|\s+are)|how\s+much\s+is|calculate|compute|convert|work\s+out|tell\s+me)\s+)?",
+    re.IGNORECASE)
+_TAIL = re.compile(r"(?:[,\s]+(?:please|exactly|again))*\s*[.!?=]*\s*$", re.IGNORECASE)
+
+
+def _evaluate_node(node: ast.AST) -> float:
+    if isinstance(node, ast.Expression):
+        return _evaluate_node(node.body)
+    if isinstance(node, ast.Constant) and isinstance(node.value, (int, float)):
+        return node.value
+    if isinstance(node, ast.UnaryOp) and isinstance(node.op, (ast.USub, ast.UAdd)):
+        value = _evaluate_node(node.operand)
+        return -value if isinstance(node.op, ast.USub) else value
+    if isinstance(node, ast.BinOp) and type(node.op) in _OPERATORS:
+        left, right = _evaluate_node(node.left), _evaluate_node(node.right)
+        if isinstance(node.op, ast.Pow) and abs(right) > MAX_EXPONENT:
+            raise ValueError("exponent too large")
+        return _OPERATORS[type(node.op)](left, right)
+    if (isinstance(node, ast.Call) and isinstance(node.func, ast.Name) and node.func.id == "sqrt"
+            and len(node.args) == 1 and not node.keywords):
+        return math.sqrt(_evaluate_node(node.args[0]))
+    raise ValueError(f"unsupported expression: {ast.dump(node)[:40]}")
+
+
+def evaluate(expression: str) -> Optional[float]:
+    """The value of an arithmetic expression (+ - * / % ** sqrt), or None if it isn't one."""
+    if not _EXPRESSION.match(expression) or not re.search(r"\d", expression):
+        return None
+    try:
+        return _evaluate_node(ast.parse(expression.replace(",", ""), mode="eval"))
+    except (SyntaxError, ValueError, ZeroDivisionError, OverflowError, TypeError, RecursionError):
+        return None
+
+
+def _arithmetic(core: str, asked: bool) -> Optional[str]:
+    percent = _PERCENT_OF.match(core)
+    if percent:
+        base = evaluate(_to_symbols(percent.group(2)))
+        if base is None:
+            return None
+        return f"{core} is {_format(float(percent.group(1)) * base / 100)}"
+    expression = _to_symbols(core)
+    operators = set(re.findall(r"\*\*|sqrt|[+*/%\-]", expression))
+    if not operators or (not asked and re.fullmatch(r"[\d\s./-]+", core)):
+        return None  # "2026-10-16" or "10/16" on their own are dates
+    value = evaluate(expression)
+    return None if value is None else f"{core} is {_format(value)}"
+
+
+def _to_symbols(text: str) -> str:
+    for pattern, replacement in _WORD_OPERATORS:
+        text = re.sub(pattern, replacement, text, flags=re.IGNORECASE)
+    return text.replace(",", "")
+
+
+# ==============================================================================
+# UNITS
+# ==============================================================================
+
+# dimension -> name -> size in the dimension's base unit
+_UNIT_TABLE = {
+    "length": {
+        "m": 1.0, "meter": 1.0, "metre": 1.0, "mm": 0.001, "millimeter": 0.001, "millimetre": 0.001,
+        "cm": 0.01, "centimeter": 0.01, "centimetre": 0.01, "km": 1000.0, "kilometer": 1000.0,
+        "kilometre": 1000.0, "in": 0.0254, "inch": 0.0254, "ft": 0.3048, "foot": 0.3048, "feet": 0.3048,
+        "yd": 0.9144, "yard": 0.9144, "mi": 1609.344, "mile": 1609.344, "nautical mile": 1852.0,
+    },
+    "mass": {
+        "kg": 1.0, "kilo": 1.0, "kilogram": 1.0, "g": 0.001, "gram": 0.001, "mg": 1e-6, "milligram": 1e-6,
+        "lb": 0.45359237, "lbs": 0.45359237, "pound": 0.45359237, "oz": 0.028349523125, "ounce": 0.028349523125,
+        "stone": 6.35029318, "tonne": 1000.0, "metric ton": 1000.0, "ton": 907.18474,
+    },
+    "volume": {
+        "l": 1.0, "liter": 1.0, "litre": 1.0, "ml": 0.001, "milliliter": 0.001, "millilitre": 0.001,
+        "tsp": 0.00492892159375, "teaspoon": 0.00492892159375, "tbsp": 0.01478676478125,
+        "tablespoon": 0.01478676478125, "fl oz": 0.0295735295625, "fluid ounce": 0.0295735295625,
+        "cup": 0.2365882365, "pint": 0.473176473, "quart": 0.946352946, "gallon": 3.785411784,
+    },
+    "area": {
+        "square meter": 1.0, "square metre": 1.0, "sq m": 1.0, "m2": 1.0, "square foot": 0.09290304,
+        "square feet": 0.09290304, "sq ft": 0.09290304, "square kilometer": 1e6, "square kilometre": 1e6,
+        "km2": 1e6, "square mile": 2589988.110336, "sq mi": 2589988.110336, "acre": 4046.8564224,
+        "hectare": 10000.0, "ha": 10000.0,
+    },
+    "speed": {
+        "m/s": 1.0, "meters per second": 1.0, "km/h": 1 / 3.6, "kph": 1 / 3.6, "kmh": 1 / 3.6,
+        "kilometers per hour": 1 / 3.6, "kilometres per hour": 1 / 3.6, "mph": 0.44704,
+        "miles per hour": 0.44704, "knot": 0.514444,
+    },
+    "time": {
+        "s": 1.0, "sec": 1.0, "second": 1.0, "min": 60.0, "minute": 60.0, "h": 3600.0, "hr": 3600.0,
+        "hour": 3600.0, "day": 86400.0, "week": 604800.0, "year": 31557600.0,
+    },
+    "data": {
+        "b": 1.0, "byte": 1.0, "kb": 1e3, "kilobyte": 1e3, "mb": 1e6, "megabyte": 1e6, "gb": 1e9, "gigabyte": 1e9,
+        "tb": 1e12, "terabyte": 1e12, "kib": 1024.0, "mib": 1024.0 ** 2, "gib": 1024.0 ** 3, "tib": 1024.0 ** 4,
+    },
+}
+UNITS: Dict[str, Tuple[str, float]] = {
+    name: (dimension, size) for dimension, sizes in _UNIT_TABLE.items() for name, size in sizes.items()}
+TEMPERATURES = {"c": "C", "celsius": "C", "centigrade": "C", "f": "F", "fahrenheit": "F", "k": "K", "kelvin": "K"}
+
+CURRENCY_NAMES = {
+    "dollar": "USD", "us dollar": "USD", "buck": "USD", "$": "USD", "euro": "EUR", "€": "EUR",
+    "pound": "GBP", "pound sterling": "GBP", "quid": "GBP", "£": "GBP", "british pound": "GBP",
+    "yen": "JPY", "¥": "JPY", "yuan": "CNY", "renminbi": "CNY", "rupee": "INR", "swiss franc": "CHF",
+    "franc": "CHF", "canadian dollar": "CAD", "australian dollar": "AUD", "new zealand dollar": "NZD",
+    "peso": "MXN", "mexican peso": "MXN", "won": "KRW", "real": "BRL", "reais": "BRL", "rand": "ZAR",
+    "krona": "SEK", "kronor": "SEK", "krone": "NOK", "kroner": "NOK", "zloty": "PLN", "lira": "TRY",
+}
+_SYMBOL_AMOUNT = re.compile(r"([$€£¥])\s*(\d[\d,]*(?:\.\d+)?)")
+_CONVERSION = re.compile(
+    r"^(?P<amount>-?\d[\d,]*(?:\.\d+)?)\s*(?P<source>[^\d].*?)\s+(?:to|in|into|as)\s+(?P<target>[^\d].*?)$",
+    re.IGNORECASE)
+_HOW_MANY = re.compile(
+    r"^how\s+many\s+(?P<target>.+?)\s+(?:are\s+)?(?:there\s+)?in\s+(?:(?P<amount>\d[\d,]*(?:\.\d+)?)|an?|one)\s+"
+    r"(?P<source>.+)$", re.IGNORECASE)
+
+
+def _unit_key(phrase: str, table) -> Optional[str]:
+    phrase = re.sub(r"^(?:an?|the|degrees?|°)\s*|\s+(?:degrees?)$", "", phrase.lower().strip()).strip(" .")
+    phrase = phrase.replace("°", "").strip()
+    candidates = [phrase, phrase[:-1] if phrase.endswith("s") else None,
+                  phrase[:-2] if phrase.endswith("es") else None,
+                  phrase.replace("inches", "inch").replace("feet", "foot")]
+    for candidate in candidates:
+        if candidate and candidate in table:
+            return candidate
+    return None
+
+
+def convert_units(amount: float, source: str, target: str) -> Optional[float]:
+    """`amount` of one unit in another of the same kind (temperatures included), or None."""
+    hot, cold = _unit_key(source, TEMPERATURES), _unit_key(target, TEMPERATURES)
+    if hot and cold:
+        celsius = {"C": amount, "F": (amount - 32) * 5 / 9, "K": amount - 273.15}[TEMPERATURES[hot]]
+        return {"C": celsius, "F": celsius * 9 / 5 + 32, "K": celsius + 273.15}[TEMPERATURES[cold]]
+    from_key, to_key = _unit_key(source, UNITS), _unit_key(target, UNITS)
+    if not from_key or not to_key or UNITS[from_key][0] != UNITS[to_key][0]:
+        return None
+    return amount * UNITS[from_key][1] / UNITS[to_key][1]
+
+
+def currency_code(phrase: str, known: Optional[Dict[str, float]] = None) -> Optional[str]:
+    phrase = phrase.strip()
+    if re.fullmatch(r"[A-Za-z]{3}", phrase) and (known is None or phrase.upper() in known):
+        return phrase.upper()
+    key = _unit_key(phrase, CURRENCY_NAMES)
+    return CURRENCY_NAMES[key] if key else None
+
+
+# ==============================================================================
+# EXCHANGE RATES
+# ==============================================================================
+
+class RateCache:
+    """Exchange rates against USD, kept on disk so conversions work offline."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "rates"
+
+    def __init__(self, storage_dir: Optional[Path] = None, url: str = RATES_URL):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self.url = url
+        self._data: Optional[Dict] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "rates.json"
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+        path = self._path()
+        if path.exists():
+            try:
+                with open(path, "r", encoding="utf-8") as f:
+                    self._data = json.load(f)
+                    return self._data
+            except Exception as e:
+                logger.warning(f"Failed to load exchange rates: {e}")
+        self._data = {"rates": {}, "updated": None}
+        return self._data
+
+    def _save(self) -> None:
+        try:
+            with open(self._path(), "w", encoding="utf-8") as f:
+                json.dump(self._data, f, indent=2)
+        except Exception as e:
+            logger.warning(f"Failed to save exchange rates: {e}")
+
+    @property
+    def rates(self) -> Dict[str, float]:
+        return self._load()["rates"]
+
+    @property
+    def updated(self) -> Optional[float]:
+        return self._load()["updated"]
+
+    def store(self, rates: Dict[str, float], updated: Optional[float] = None) -> None:
+        self._data = {"rates": {code.upper(): float(rate) for code, rate in rates.items()},
+                      "updated": time.time() if updated is None else updated}
+        self._save()
+
+    async def refresh(self) -> int:
+        """Fetch today's rates (exchange_rates job); returns how many currencies."""
+        import httpx
+        async with httpx.AsyncClient(timeout=15) as client:
+            response = await client.get(self.url)
+            response.raise_for_status()
+            data = response.json()
+        if data.get("result") != "success" or not data.get("rates"):
+            raise ValueError(f"Unexpected exchange rate response: {str(data)[:100]}")
+        self.store(data["rates"], data.get("time_last_update_unix"))
+        return len(self.rates)
+
+    def convert(self, amount: float, source: str, target: str) -> Optional[float]:
+        rates = self.rates
+        if source not in rates or target not in rates:
+            return None
+        return amount / rates[source] * rates[target]
+
+    def staleness(self, now: Optional[float] = None) -> Optional[str]:
+        """" (rates from Oct 12)" when they're older than STALE_DAYS, else ""."""
+        if self.updated is None:
+            return None
+        if (now or time.time()) - self.updated <= STALE_DAYS * 86400:
+            return ""
+        updated = datetime.fromtimestamp(self.updated)
+        return f" (rates from {updated:%b} {updated.day})"
+
+
+_rate_cache: Optional[RateCache] = None
+
+
+def get_rate_cache() -> RateCache:
+    """Get the global exchange rate cache."""
+    global _rate_cache
+    if _rate_cache is None:
+        _rate_cache = RateCache()
+    return _rate_cache
+
+
+def set_rate_cache(cache: Optional[RateCache]) -> None:
+    global _rate_cache
+    _rate_cache = cache
+
+
+# ==============================================================================
+# ANSWERS
+# ==============================================================================
+
+def _conversion(core: str, rates: Optional[RateCache]) -> Optional[str]:
+    core = _SYMBOL_AMOUNT.sub(lambda m: f"{m.group(2)} {m.group(1)}", core)
+    match = _CONVERSION.match(core) or _HOW_MANY.match(core)
+    if not match:
+        return None
+    amount = float((match.group("amount") or "1").replace(",", ""))
+    source, target = match.group("source").strip(), match.group("target").strip()
+    value = convert_units(amount, source, target)
+    if value is not None:
+        return f"{_format(amount)} {source} is {_format(value)} {target}"
+    cache = rates or get_rate_cache()
+    known = cache.rates or None
+    from_code, to_code = currency_code(source, known), currency_code(target, known)
+    if not from_code or not to_code:
+        return None
+    value = cache.convert(amount, from_code, to_code)
+    if value is None:
+        return "I don't have exchange rates yet - they're fetched once the assistant is online"
+    return f"{_format(amount)} {from_code} is {value:,.2f} {to_code}{cache.staleness() or ''}"
+
+
+def answer_quick_question(text: str, rates: Optional[RateCache] = None) -> Optional[str]:
+    """The answer to a calculation or conversion, or None if `text` isn't one."""
+    if not text or len(text) > 120:
+        return None
+    lead = _LEAD.match(text)
+    core = _TAIL.sub("", text[lead.end():]).strip()
+    if not core:
+        return None
+    core = numbers_to_digits(core)
+    return _conversion(core, rates) or _arithmetic(core, bool(lead.group("ask")))
+
+
+register_capability("Quick maths", "Sums, percentages and unit or currency conversions, answered instantly",
+                    ["what's 15% of 240", "convert 5 miles to km", "100 dollars in euros"],
+                    category="Information", keywords=("calculator", "convert", "units", "currency", "math"))
@@ -0,0 +1,242 @@
+"""
+Quotas - Subscription limits on phone minutes and text messages, enforced locally.
+
+The server knows the subscription tier and what's left this billing
+period (GET /api/identity: subscription_tier, voice_minutes_remaining,
+sms_messages_remaining). The core keeps its own count as well, so the
+limits hold between syncs and while the server is unreachable:
+
+- phone-call minutes (phone.py, rounded up per call like Twilio bills them)
+  and text-message replies (reply_drafts.py) are recorded as they're used
+- at 80% of a limit there's one warning per month ("You've used 82 of 100
+  phone minutes this month")
+- an action that would go over is blocked with an explanation - what's
+  left, when it resets, how to get more - instead of failing at Twilio
+  (config.quota_allow_overage lets paid tiers go over and be billed)
+- the quota_sync job sends usage recorded since the last sync to the
+  server (POST /api/usage) and refreshes what's left from it
+
+Until the first sync the limits are the tier's (config.subscription_tier),
+mirroring the server's feature matrix (packages/server/src/lib/features.js).
+
+Storage: ~/.xswarm/quota.json
+"""
+
+import json
+import logging
+import math
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Callable, Dict, Optional
+
+from . import endpoints
+
+logger = logging.getLogger(__name__)
+
+QUOTA_PATH = Path.home() / ".xswarm" / "quota.json"
+WARN_AT = 0.8  # Share of a limit used before the warning
+
+# Resource: (one, many) for messages
+RESOURCES = {"voice_minutes": ("phone minute", "phone minutes"), "sms_messages": ("text message", "text messages")}
+# Monthly limits per tier (None = unlimited); "premium" is the older name for "personal"
+TIER_LIMITS: Dict[str, Dict[str, Optional[int]]] = {
+    "free": {"voice_minutes": 0, "sms_messages": 0},
+    "personal": {"voice_minutes": 100, "sms_messages": 100},
+    "premium": {"voice_minutes": 100, "sms_messages": 100},
+    "professional": {"voice_minutes": 500, "sms_messages": 500},
+    "enterprise": {"voice_minutes": None, "sms_messages": None},
+    "admin": {"voice_minutes": None, "sms_messages": None},
+}
+OVERAGE_TIERS = {"personal", "premium", "professional"}
+
+
+@dataclass
+class QuotaDecision:
+    """Whether an action fits the quota, and what to tell the user."""
+    allowed: bool
+    message: str = ""  # Why it was blocked, or a note about going over
+
+
+def _amount(resource: str, amount: float) -> str:
+    one, many = RESOURCES[resource]
+    return f"{amount:g} {one if amount == 1 else many}"
+
+
+class QuotaManager:
+    """Local count of metered usage this month, checked against the tier's limits."""
+
+    def __init__(self, tier: str = "free", path: Path = QUOTA_PATH, allow_overage: bool = False,
+                 clock: Callable[[], datetime] = datetime.now):
+        self.tier = (tier or "free").lower()
+        self.path = Path(path)
+        self.allow_overage = allow_overage
+        self.clock = clock
+        self._state: Optional[dict] = None
+
+    @classmethod
+    def from_config(cls, config=None) -> "QuotaManager":
+        return cls(getattr(config, "subscription_tier", "free"),
+                   allow_overage=getattr(config, "quota_allow_overage", False))
+
+    def _period(self) -> str:
+        return self.clock().strftime("%Y-%m")
+
+    def _load(self) -> dict:
+        if self._state is None:
+            try:
+                self._state = json.loads(self.path.read_text())
+            except (OSError, ValueError):
+                self._state = {}
+        period = self._period()
+        if self._state.get("period") != period:
+            # New month: usage starts over, but what wasn't synced yet still has to reach the server
+            self._state = {"period": period, "used": {}, "unsynced": self._state.get("unsynced", {}),
+                           "remaining": {}, "warned": [], "tier": self._state.get("tier"),
+                           "user_id": self._state.get("user_id")}
+        return self._state
+
+    def _save(self) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps(self._state, indent=2))
+
+    @property
+    def effective_tier(self) -> str:
+        """The tier the server last reported, else the configured one."""
+        return self._load().get("tier") or self.tier
+
+    def limit(self, resource: str) -> Optional[float]:
+        """This month's limit (None = unlimited)."""
+        limits = TIER_LIMITS.get(self.effective_tier, TIER_LIMITS["free"])
+        state = self._load()
+        if resource in state["remaining"]:
+            remaining = state["remaining"][resource]
+            if remaining is None:
+                return None
+            return limits.get(resource) or state["used"].get(resource, 0) + remaining
+        return limits.get(resource)
+
+    def used(self, resource: str) -> float:
+        return self._load()["used"].get(resource, 0)
+
+    def remaining(self, resource: str) -> Optional[float]:
+        """What's left this month (None = unlimited)."""
+        state = self._load()
+        if resource in state["remaining"]:
+            # The server's figure at the last sync, less what was used since
+            remaining = state["remaining"][resource]
+            return None if remaining is None else max(0, remaining - state["unsynced"].get(resource, 0))
+        limit = self.limit(resource)
+        return None if limit is None else max(0, limit - self.used(resource))
+
+    def _resets(self) -> str:
+        now = self.clock()
+        first = now.replace(year=now.year + (now.month == 12), month=now.month % 12 + 1, day=1)
+        return f"{first:%B} {first.day}"
+
+    def check(self, resource: str, amount: float = 1) -> QuotaDecision:
+        """Whether `amount` more fits; blocked ones come with an explanation."""
+        remaining = self.remaining(resource)
+        if remaining is None or amount <= remaining:
+            return QuotaDecision(True)
+        tier = self.effective_tier
+        many = RESOURCES[resource][1]
+        if self.limit(resource) == 0:
+            return QuotaDecision(False, f"{many.capitalize()} aren't included in the {tier} plan - "
+                                        "upgrade to a paid plan to use them.")
+        if self.allow_overage and tier in OVERAGE_TIERS:
+            return QuotaDecision(True, f"You're past this month's {many}; this will be billed as overage.")
+        left = f"only {_amount(resource, remaining)} left" if remaining else f"no {many} left"
+        return QuotaDecision(False, f"You have {left} this month on the {tier} plan; it resets on "
+                                    f"{self._resets()}. Upgrade your plan, or set quota_allow_overage "
+                                    "to go over and be billed.")
+
+    def record(self, resource: str, amount: float) -> Optional[str]:
+        """Count usage; returns a warning the first time this month it passes WARN_AT of the limit."""
+        if amount <= 0:
+            return None
+        state = self._load()
+        for bucket in ("used", "unsynced"):
+            state[bucket][resource] = state[bucket].get(resource, 0) + amount
+        warning = None
+        limit, remaining = self.limit(resource), self.remaining(resource)
+        if limit and remaining is not None and resource not in state["warned"] and limit - remaining >= WARN_AT * limit:
+            state["warned"].append(resource)
+            warning = (f"You've used {limit - remaining:g} of {limit:g} {RESOURCES[resource][1]} this month "
+                       f"(resets {self._resets()}).")
+        self._save()
+        return warning
+
+    def record_call(self, seconds: float) -> Optional[str]:
+        """A phone call's minutes, rounded up per started minute."""
+        return self.record("voice_minutes", math.ceil(seconds / 60)) if seconds > 0 else None
+
+    def apply_identity(self, identity: dict) -> None:
+        """Take the tier and remaining counts the server reported (GET /api/identity)."""
+        state = self._load()
+        state["tier"] = identity.get("subscription_tier") or state.get("tier")
+        state["user_id"] = identity.get("id") or state.get("user_id")
+        for resource in RESOURCES:
+            key = f"{resource}_remaining"
+            if key in identity:
+                state["remaining"][resource] = identity[key]
+        self._save()
+
+    async def sync(self, client) -> Dict[str, float]:
+        """Report usage since the last sync and refresh what's left. Returns what was reported."""
+        response = await client.call(endpoints.IDENTITY())
+        self.apply_identity(response.json())
+        state = self._load()
+        reported = {k: v for k, v in state["unsynced"].items() if v}
+        if reported:
+            await client.call(endpoints.USAGE(), json={"user_id": state.get("user_id"), **reported})
+            for resource, amount in reported.items():
+                remaining = state["remaining"].get(resource)
+                if remaining is not None:
+                    state["remaining"][resource] = max(0, remaining - amount)
+        state["unsynced"] = {}
+        state["synced_at"] = self.clock().isoformat(timespec="seconds")
+        self._save()
+        return reported
+
+    def summary(self) -> str:
+        """Usage this month per resource, for `xswarm dev quota`."""
+        lines = [f"Plan: {self.effective_tier}"]
+        for resource, (_, many) in RESOURCES.items():
+            limit, remaining = self.limit(resource), self.remaining(resource)
+            used = f"{self.used(resource):g}"
+            if limit is None:
+                lines.append(f"  {many}: {used} used (unlimited)")
+            else:
+                lines.append(f"  {many}: {used} of {limit:g} used, {remaining:g} left")
+        state = self._load()
+        pending = ", ".join(_amount(r, a) for r, a in state["unsynced"].items() if a)
+        lines.append(f"  Last synced: {state.get('synced_at') or 'never'}" + (f" ({pending} to report)" if pending else ""))
+        return "\n".join(lines)
+
+
+_manager: Optional[QuotaManager] = None
+
+
+def get_quota_manager() -> Optional[QuotaManager]:
+    """The global quota manager, or None before startup installs one (nothing is limited then)."""
+    return _manager
+
+
+def set_quota_manager(manager: Optional[QuotaManager]) -> None:
+    """Install the manager built from the loaded Config (called at startup)."""
+    global _manager
+    _manager = manager
+
+
+async def sync_quota(config=None, manager: Optional[QuotaManager] = None) -> Dict[str, float]:
+    """The quota_sync job: report usage to the server and refresh the remaining counts."""
+    from .api_client import ApiClient, ApiPolicy
+
+    manager = manager or get_quota_manager() or QuotaManager.from_config(config)
+    client = ApiClient(getattr(config, "server_url", "http://localhost:3000"), getattr(config, "api_token", None),
+                       policy=ApiPolicy.from_config(config))
+    try:
+        return await manager.sync(client)
+    finally:
+        await client.close()
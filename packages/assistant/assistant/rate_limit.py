@@ -0,0 +1,144 @@
+"""
+Rate Limiting - Abuse protection for local listeners.
+
+Anything that accepts connections from outside the process (the Twilio
+Media Streams WebSocket server, tunneled via ngrok) goes through a
+ClientGuard, which enforces:
+
+- connection limits: total and per client address
+- payload size caps: oversized messages are rejected
+- per-client message rates: a token bucket per connection; clients that
+  keep exceeding it are disconnected
+
+Violations are reported through an `on_violation(message)` callback (the
+TUI passes its activity feed) and are themselves throttled, so a flood
+can't turn into a flood of log lines.
+"""
+
+import logging
+import time
+from dataclasses import dataclass
+from typing import Callable, Dict, Optional
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass
+class ListenerLimits:
+    """Limits for one listener. Defaults fit Twilio media streams (~50 frames/s per call)."""
+    max_connections: int = 20
+    max_connections_per_client: int = 4
+    max_message_bytes: int = 64 * 1024
+    messages_per_second: float = 100.0
+    burst: int = 200
+    # Disconnect after this many rejected messages in a row
+    max_violations: int = 50
+    # Report at most one violation per client per this many seconds
+    report_interval: float = 10.0
+
+
+class TokenBucket:
+    """Allows `rate` events per second on average, up to `burst` at once."""
+
+    def __init__(self, rate: float, burst: int, clock: Callable[[], float] = time.monotonic):
+        self.rate = rate
+        self.capacity = float(burst)
+        self.tokens = float(burst)
+        self.clock = clock
+        self.updated = clock()
+
+    def take(self) -> bool:
+        now = self.clock()
+        self.tokens = min(self.capacity, self.tokens + (now - self.updated) * self.rate)
+        self.updated = now
+        if self.tokens >= 1:
+            self.tokens -= 1
+            return True
+        return False
+
+
+class ClientGuard:
+    """Connection, size and rate limits for one listener, keyed by client address."""
+
+    def __init__(
+        self,
+        name: str,
+        limits: Optional[ListenerLimits] = None,
+        on_violation: Optional[Callable[[str], None]] = None,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        self.name = name
+        self.limits = limits or ListenerLimits()
+        self.on_violation = on_violation
+        self.clock = clock
+        self._connections: Dict[str, int] = {}
+        self._buckets: Dict[int, TokenBucket] = {}
+        self._strikes: Dict[int, int] = {}
+        self._last_report: Dict[str, float] = {}
+
+    @property
+    def connection_count(self) -> int:
+        return sum(self._connections.values())
+
+    def admit(self, client: str) -> bool:
+        """Register a new connection; False (and a reported violation) if over a limit."""
+        if self.connection_count >= self.limits.max_connections:
+            self._report(client, f"refused connection from {client} (server full, {self.limits.max_connections} connections)")
+            return False
+        if self._connections.get(client, 0) >= self.limits.max_connections_per_client:
+            self._report(client, f"refused connection from {client} (over {self.limits.max_connections_per_client} connections)")
+            return False
+        self._connections[client] = self._connections.get(client, 0) + 1
+        return True
+
+    def release(self, client: str, conn_id: Optional[int] = None) -> None:
+        """Forget a closed connection."""
+        count = self._connections.get(client, 0) - 1
+        if count > 0:
+            self._connections[client] = count
+        else:
+            self._connections.pop(client, None)
+        if conn_id is not None:
+            self._buckets.pop(conn_id, None)
+            self._strikes.pop(conn_id, None)
+
+    def check_message(self, client: str, conn_id: int, size: int) -> Optional[str]:
+        """
+        Check one incoming message.
+
+        Returns None when it may be processed, "drop" to skip it, or
+        "disconnect" when the client should be cut off.
+        """
+        if size > self.limits.max_message_bytes:
+            self._report(client, f"rejected {size}-byte message from {client} (limit {self.limits.max_message_bytes})")
+            return self._strike(conn_id)
+
+        bucket = self._buckets.get(conn_id)
+        if bucket is None:
+            bucket = self._buckets[conn_id] = TokenBucket(self.limits.messages_per_second, self.limits.burst, self.clock)
+        if not bucket.take():
+            self._report(client, f"rate limited {client} (over {self.limits.messages_per_second:g} messages/s)")
+            return self._strike(conn_id)
+
+        self._strikes.pop(conn_id, None)
+        return None
+
+    def _strike(self, conn_id: int) -> str:
+        strikes = self._strikes.get(conn_id, 0) + 1
+        self._strikes[conn_id] = strikes
+        return "disconnect" if strikes >= self.limits.max_violations else "drop"
+
+    def _report(self, client: str, message: str) -> None:
+        now = self.clock()
+        last = self._last_report.get(client)
+        if last is not None and now - last < self.limits.report_interval:
+            return
+        self._last_report[client] = now
+
+        text = f"⚠ {self.name}: {message}"
+        logger.warning(text)
+        if self.on_violation:
+            try:
+                self.on_violation(text)
+            except Exception as e:
+                logger.debug(f"Violation callback failed: {e}")
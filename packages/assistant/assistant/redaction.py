@@ -0,0 +1,183 @@
+"""
+Redaction - Keep phone numbers, emails and credentials out of logs and exports.
+
+People read numbers and passwords aloud, so transcripts end up in log
+files, copied activity logs and memory. The Redactor masks them before
+they're written:
+
+    "call +1 555 123 4567"           -> "call [phone]"
+    "mail dana@example.com"          -> "mail [email]"
+    "my password is hunter2"         -> "my password is [secret]"
+    "OPENAI_API_KEY=sk-abc123..."    -> "OPENAI_API_KEY=[secret]"
+
+Strict mode (config.redaction_strict) replaces identifiers with a keyed
+hash instead - "[phone:1f9c0a2e]" - so the same number can still be
+followed through a log without being readable. Credentials and card
+numbers are always masked, never hashed. The hash key is created once per
+machine (~/.xswarm/redaction.key), so hashes can't be reversed by trying
+every phone number.
+
+Applied to: log files (RedactingFilter), copied activity logs, `xswarm dev
+events query` output, and messages stored in memory. Extra patterns come
+from config.redaction_patterns ({"employee_id": "EMP-\\d{6}"}); an empty
+pattern turns a built-in one off ({"phone": ""}).
+"""
+
+import hashlib
+import hmac
+import logging
+import re
+import secrets
+from pathlib import Path
+from typing import Callable, Dict, List, Optional, Pattern, Tuple
+
+logger = logging.getLogger(__name__)
+
+KEY_PATH = Path.home() / ".xswarm" / "redaction.key"
+
+# Identifiers: hashed in strict mode
+IDENTIFIERS: Dict[str, str] = {
+    "email": r"[\w.+-]+@[\w-]+(?:\.[\w-]+)+",
+    "phone": r"(?<![\w.])\+?\(?\d[\d\s().-]{8,17}\d(?![\w.])",
+}
+
+# Secrets: always masked. Group 1, when there is one, is kept ("password is " + [secret])
+SECRETS: Dict[str, str] = {
+    "credential": r"(?i)(?<![a-z])((?:api[_-]?key|access[_-]?key|secret|token|password|passwd|pwd|passcode|pin)(?:_\w+)?"
+                  r"(?:\s+(?:is|was)\s+|\s*[:=]\s*)[\"']?)[^\s\"',;]{3,}",
+    "bearer": r"(?i)\b(bearer\s+)[\w\-.~+/]{8,}=*",
+    "api_key": r"\b(?:sk|pk|rk)-[\w-]{16,}|\bgh[pousr]_\w{20,}|\bAKIA[0-9A-Z]{16}\b|\bxox[abpr]-[\w-]{10,}",
+    "card": r"(?<!\d)(?:\d[ -]?){12,18}\d(?!\d)",
+}
+
+
+def _digits(text: str) -> str:
+    return re.sub(r"\D", "", text)
+
+
+def _luhn(number: str) -> bool:
+    total = 0
+    for i, digit in enumerate(reversed(number)):
+        value = int(digit) * (2 if i % 2 else 1)
+        total += value - 9 if value > 9 else value
+    return total % 10 == 0
+
+
+def _looks_like_phone(text: str) -> bool:
+    """10-15 digits, and not a date or time ("2026-10-14 15:30")."""
+    return 10 <= len(_digits(text)) <= 15 and not re.search(r"\d{4}-\d{2}-\d{2}|\d:\d\d", text)
+
+
+def load_key(path: Path = KEY_PATH) -> bytes:
+    """This machine's hash key for strict mode, created on first use."""
+    try:
+        return bytes.fromhex(path.read_text().strip())
+    except (OSError, ValueError):
+        key = secrets.token_bytes(32)
+        try:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            path.write_text(key.hex())
+            path.chmod(0o600)
+        except OSError as e:
+            logger.debug(f"Could not save the redaction key: {e}")
+        return key
+
+
+class Redactor:
+    """Masks (or in strict mode, hashes) sensitive values in text."""
+
+    def __init__(self, enabled: bool = True, strict: bool = False,
+                 patterns: Optional[Dict[str, str]] = None, key: Optional[bytes] = None):
+        self.enabled = enabled
+        self.strict = strict
+        self._key = key
+        self.rules: List[Tuple[str, Pattern, bool]] = []  # (name, pattern, is_identifier)
+        extra = dict(patterns or {})
+        for name, pattern in SECRETS.items():
+            if extra.pop(name, pattern):
+                self.rules.append((name, re.compile(pattern), False))
+        for name, pattern in {**IDENTIFIERS, **extra}.items():
+            if not pattern:
+                continue
+            try:
+                self.rules.append((name, re.compile(pattern), True))
+            except re.error as e:
+                logger.warning(f"Ignoring redaction pattern '{name}': {e}")
+
+    @classmethod
+    def from_config(cls, config) -> "Redactor":
+        strict = bool(getattr(config, "redaction_strict", False))
+        return cls(
+            enabled=getattr(config, "redaction_enabled", True),
+            strict=strict,
+            patterns=getattr(config, "redaction_patterns", None),
+            key=load_key() if strict else None,
+        )
+
+    def _hash(self, value: str) -> str:
+        if self._key is None:
+            self._key = load_key()
+        return hmac.new(self._key, value.encode(), hashlib.sha256).hexdigest()[:8]
+
+    def _replacement(self, name: str, identifier: bool) -> Callable[[re.Match], str]:
+        def replace(match: re.Match) -> str:
+            value = match.group(0)
+            kept = match.group(1) if match.re.groups and match.group(1) else ""
+            if name == "phone" and not _looks_like_phone(value):
+                return value
+            if name == "card" and not _luhn(_digits(value)):
+                return value
+            if identifier and self.strict:
+                normalized = _digits(value) if name == "phone" else value.lower()
+                return f"{kept}[{name}:{self._hash(normalized)}]"
+            return f"{kept}[{'secret' if not identifier and name != 'card' else name}]"
+        return replace
+
+    def __call__(self, text: str) -> str:
+        if not self.enabled or not text:
+            return text
+        for name, pattern, identifier in self.rules:
+            text = pattern.sub(self._replacement(name, identifier), text)
+        return text
+
+
+class RedactingFilter(logging.Filter):
+    """Logging filter that redacts each record's message (uses the current get_redactor())."""
+
+    def filter(self, record: logging.LogRecord) -> bool:
+        try:
+            message = record.getMessage()
+        except Exception:
+            return True
+        redacted = get_redactor()(message)
+        if redacted != message:
+            record.msg, record.args = redacted, None
+        return True
+
+
+def install_log_redaction(root: Optional[logging.Logger] = None) -> None:
+    """Redact everything written by the root logger's handlers (log files)."""
+    for handler in (root or logging.getLogger()).handlers:
+        if not any(isinstance(f, RedactingFilter) for f in handler.filters):
+            handler.addFilter(RedactingFilter())
+
+
+_redactor: Optional[Redactor] = None
+
+
+def get_redactor() -> Redactor:
+    """The redactor built from config (built-in patterns until set_redactor is called)."""
+    global _redactor
+    if _redactor is None:
+        _redactor = Redactor()
+    return _redactor
+
+
+def set_redactor(redactor: Redactor) -> None:
+    global _redactor
+    _redactor = redactor
+
+
+def redact(text: str) -> str:
+    """`text` with sensitive values masked (see Redactor)."""
+    return get_redactor()(text)
@@ -0,0 +1,240 @@
+"""
+Event Reminders - Spot calendar events about to start and tell the user.
+
+The dashboard's event_reminders job (every minute, see scheduler.py) calls
+due_reminders() with today's planner events, then deliver_reminder() for
+each one it returns:
+
+- a "⏰ Standup in 10 minutes" line in the activity feed
+- a desktop notification
+- a spoken announcement through the voice orchestrator ("Reminder:
+  Standup in ten minutes.", worded by verbalize.py)
+
+Each channel's wording can be replaced per category with
+config.reminder_templates (see message_templates.py).
+
+Desktop and speech honor quiet hours and per-category preferences
+(notifications.py), and can be turned off with config.reminder_methods; the
+activity line is always written.
+
+Receipts: clicking a reminder's desktop notification, or answering a spoken
+one ("got it", "thanks") within ACK_WINDOW, is recorded as a reminder_ack
+event (ReminderReceipts). effectiveness() compares those with the reminders
+sent on each channel ("Spoken reminders get acknowledged 90% of the time,
+desktop notifications 40%."; `xswarm dev reminders effectiveness`), and with
+config.reminder_methods_auto a channel the user keeps ignoring is left out
+(adjust_methods). It gets tried again once its old reminders age out of
+the EFFECTIVENESS_DAYS window.
+"""
+
+import logging
+import re
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from typing import Any, Callable, Dict, Iterable, List, Optional, Set, Tuple
+
+from .capabilities import register_capability
+from .events import record_event
+from .message_templates import DEFAULT_CATEGORY, get_message_templates
+from .notifications import send_desktop_notification
+from .scheduler_client import instance_key
+from .verbalize import verbalize_relative
+
+logger = logging.getLogger(__name__)
+
+METHODS = ("desktop", "speech")
+METHOD_NAMES = {"desktop": "desktop notifications", "speech": "spoken reminders"}
+ACK_WINDOW = timedelta(minutes=3)  # How long after a spoken reminder "got it" counts as an answer to it
+EFFECTIVENESS_DAYS = 30
+MIN_SENT = 10  # Reminders on a channel before its acknowledgement rate means anything
+
+_ACK_WORDS = r"ok(?:ay)?|got\s+it|thanks?(?:\s+you)?|will\s+do|on\s+it|on\s+my\s+way|noted|yep|i\s+know|roger"
+_ACK = re.compile(rf"^\W*(?:{_ACK_WORDS})(?:\W+(?:{_ACK_WORDS}))*\W*$", re.IGNORECASE)
+
+
+@dataclass
+class Reminder:
+    """One event reminder, ready to deliver."""
+    key: str  # instance_key of the event, so each occurrence is reminded once
+    title: str
+    start: datetime
+    minutes: int
+    tags: List[str] = field(default_factory=list)
+
+    @property
+    def text(self) -> str:
+        return format_reminder(self.title, self.minutes)
+
+    @property
+    def spoken(self) -> str:
+        """e.g. "Reminder: Standup in ten minutes." """
+        return f"Reminder: {self.title} {verbalize_relative(timedelta(minutes=self.minutes))}."
+
+    def context(self) -> Dict[str, Any]:
+        """Variables for reminder templates (see message_templates.py)."""
+        return {
+            "title": self.title,
+            "minutes": self.minutes,
+            "when": verbalize_relative(timedelta(minutes=self.minutes)),
+            "time": self.start.strftime("%H:%M"),
+            "category": self.tags[0] if self.tags else DEFAULT_CATEGORY,
+            "tags": list(self.tags),
+            "text": self.text,
+            "spoken": self.spoken,
+        }
+
+    def message(self, channel: str) -> str:
+        """This reminder's text for a channel (activity, desktop_title, desktop, speech), templated or built-in."""
+        return get_message_templates().render(channel, self.context())
+
+
+def format_reminder(title: str, minutes: int) -> str:
+    """e.g. "Standup in 10 minutes"."""
+    return f"{title} in {minutes} minute{'s' if minutes != 1 else ''}"
+
+
+def due_reminders(events: Iterable[Any], now: datetime, reminded: Set[str]) -> List[Reminder]:
+    """
+    Events whose reminder window (reminder_minutes before start) has opened.
+
+    Keys of returned reminders are added to `reminded`, so each event
+    instance is returned once however often this is called.
+    """
+    due = []
+    for event in events:
+        key = instance_key(event)
+        start = datetime.fromisoformat(event.start_time)
+        if key in reminded or not start - timedelta(minutes=event.reminder_minutes) <= now < start:
+            continue
+        reminded.add(key)
+        minutes = max(1, round((start - now).total_seconds() / 60))
+        due.append(Reminder(key=key, title=event.title, start=start, minutes=minutes, tags=list(event.tags or [])))
+    return due
+
+
+async def deliver_reminder(
+    reminder: Reminder,
+    activity: Optional[Callable[[str], None]] = None,
+    notify: Callable[..., bool] = send_desktop_notification,
+    announcer: Optional[Any] = None,
+    methods: Iterable[str] = METHODS,
+    on_click: Optional[Callable[[Reminder], None]] = None,
+) -> Dict[str, bool]:
+    """
+    Deliver a reminder on the activity feed and each of `methods`.
+    `announcer` is anything with an async announce(text, tags=...) (the
+    voice orchestrator); `on_click` is called if the desktop notification
+    is clicked.
+
+    Returns which channels delivered it: {"activity", "desktop", "speech"}.
+    """
+    delivered = {"activity": False, "desktop": False, "speech": False}
+    methods = set(methods)
+    if activity:
+        activity(reminder.message("activity"))
+        delivered["activity"] = True
+    if "desktop" in methods:
+        clicked = {"on_click": lambda: on_click(reminder)} if on_click else {}
+        delivered["desktop"] = bool(notify(reminder.message("desktop_title"), reminder.message("desktop"),
+                                           tags=reminder.tags, **clicked))
+    if announcer is not None and "speech" in methods:
+        try:
+            delivered["speech"] = bool(await announcer.announce(reminder.message("speech"), tags=reminder.tags))
+        except Exception as e:
+            logger.debug(f"Spoken reminder failed: {e}")
+    return delivered
+
+
+def is_acknowledgement(text: str) -> bool:
+    """ "got it", "okay thanks", "on my way" - nothing more."""
+    return bool(_ACK.match(text or ""))
+
+
+class ReminderReceipts:
+    """
+    Reminders delivered this session and which channels got a reaction.
+    Each first reaction per channel is recorded as a reminder_ack event.
+    """
+
+    def __init__(self):
+        self._sent: Dict[str, Tuple[Reminder, datetime, Set[str]]] = {}
+        self._acknowledged: Set[Tuple[str, str]] = set()
+
+    def sent(self, reminder: Reminder, delivered: Dict[str, bool], now: Optional[datetime] = None) -> None:
+        channels = {channel for channel in METHODS if delivered.get(channel)}
+        if channels:
+            self._sent[reminder.key] = (reminder, now or datetime.now(), channels)
+
+    def acknowledge(self, key: str, channel: str, now: Optional[datetime] = None) -> Optional[Reminder]:
+        """Note a reaction to reminder `key` on `channel`; the reminder, or None if unknown or already noted."""
+        entry = self._sent.get(key)
+        if entry is None or channel not in entry[2] or (key, channel) in self._acknowledged:
+            return None
+        reminder, sent_at, _ = entry
+        self._acknowledged.add((key, channel))
+        seconds = max(0.0, ((now or datetime.now()) - sent_at).total_seconds())
+        record_event("reminder_ack", f"{reminder.title} ({channel})",
+                     {"key": key, "title": reminder.title, "channel": channel, "seconds": round(seconds)})
+        return reminder
+
+    def heard(self, text: str, now: Optional[datetime] = None) -> Optional[Reminder]:
+        """The spoken reminder `text` answers (the latest within ACK_WINDOW), or None."""
+        if not is_acknowledgement(text):
+            return None
+        now = now or datetime.now()
+        recent = [(sent_at, key) for key, (_, sent_at, channels) in self._sent.items()
+                  if "speech" in channels and (key, "speech") not in self._acknowledged
+                  and timedelta(0) <= now - sent_at <= ACK_WINDOW]
+        return self.acknowledge(max(recent)[1], "speech", now) if recent else None
+
+
+@dataclass
+class ChannelStats:
+    """Reminders sent on a channel and how many got a reaction."""
+    channel: str
+    sent: int = 0
+    acknowledged: int = 0
+
+    @property
+    def rate(self) -> Optional[float]:
+        return self.acknowledged / self.sent if self.sent else None
+
+
+def effectiveness(store, now: Optional[datetime] = None, days: int = EFFECTIVENESS_DAYS) -> Dict[str, ChannelStats]:
+    """Per channel, reminders sent and acknowledged over the last `days` (from the event history)."""
+    since = (now or datetime.now()) - timedelta(days=days)
+    stats = {channel: ChannelStats(channel) for channel in METHODS}
+    acks = {(e.data.get("key"), e.data.get("channel")) for e in store.query(["reminder_ack"], since=since)}
+    for event in store.query(["reminder"], since=since):
+        for channel in event.data.get("channels") or []:  # Older events don't say
+            if channel in stats:
+                stats[channel].sent += 1
+                stats[channel].acknowledged += (event.data.get("key"), channel) in acks
+    return stats
+
+
+def describe_effectiveness(stats: Dict[str, ChannelStats]) -> str:
+    """ "Spoken reminders get acknowledged 90% of the time, desktop notifications 40%." """
+    measured = sorted((s for s in stats.values() if s.sent), key=lambda s: -s.rate)
+    if not measured:
+        return "No reminders sent yet."
+    first, *rest = measured
+    text = f"{METHOD_NAMES[first.channel].capitalize()} get acknowledged {first.rate:.0%} of the time"
+    return text + "".join(f", {METHOD_NAMES[s.channel]} {s.rate:.0%}" for s in rest) + "."
+
+
+def adjust_methods(methods: Iterable[str], stats: Dict[str, ChannelStats], min_rate: float,
+                   min_sent: int = MIN_SENT) -> List[str]:
+    """
+    `methods` without the channels acknowledged less than `min_rate` of the
+    time (once they've sent `min_sent`); the best of them is always kept.
+    """
+    methods = [m for m in methods if m in METHODS]
+    kept = [m for m in methods if m not in stats or stats[m].sent < min_sent or stats[m].rate >= min_rate]
+    if kept or not methods:
+        return kept
+    return [max(methods, key=lambda m: stats[m].rate)]
+
+
+register_capability("Reminders", "Reminders before your events, spoken or by notification, text or call",
+                    ["got it", "thanks"], category="Planning", keywords=("remind", "notification", "acknowledge"))
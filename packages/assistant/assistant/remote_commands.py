@@ -0,0 +1,157 @@
+"""
+Remote Commands - Actions the server asks this assistant to take.
+
+The server (or the user's web dashboard) can queue commands for the
+assistant at POST /api/commands:
+
+- speak:          say args.text out loud (args.priority, default normal)
+- refresh_config: re-read the config file (Config.reload)
+- call:           phone the user with args.message (make_call)
+
+The dashboard fetches them when the calendar subscription socket hints that
+one is waiting ({"type": "command"}, see calendar_live.py) and every minute
+(the remote_commands job). Only the types in config.remote_commands run:
+speak and refresh_config by default; calls cost money and have to be added.
+Every command is acknowledged to the server - done, failed or rejected -
+with a short result. Expired ones are dropped by the server.
+
+Each command's idempotency key is remembered (~/.xswarm/remote_commands.json,
+for SEEN_DAYS) before it runs, so one delivered twice - an ack lost on the
+way, a restart mid-command - is acknowledged again but never run twice.
+"""
+
+import json
+import logging
+import time
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Awaitable, Callable, Dict, Iterable, List, Optional
+
+from . import endpoints
+from .api_client import ApiClient, ApiError, ApiPolicy
+
+logger = logging.getLogger(__name__)
+
+COMMAND_TYPES = ("speak", "refresh_config", "call")
+SEEN_DAYS = 7
+
+Handler = Callable[[Dict[str, Any]], Awaitable[str]]
+
+
+class CommandFailed(Exception):
+    """A handler couldn't do what was asked; the message is sent as the result."""
+
+
+@dataclass
+class RemoteCommand:
+    id: str
+    type: str
+    args: Dict[str, Any] = field(default_factory=dict)
+    idempotency_key: str = ""
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "RemoteCommand":
+        return cls(str(data["id"]), str(data.get("type") or ""), dict(data.get("args") or {}),
+                   str(data.get("idempotency_key") or data["id"]))
+
+
+@dataclass
+class Outcome:
+    """How a command was acknowledged."""
+    command: RemoteCommand
+    status: str  # done, failed or rejected
+    result: str
+    repeated: bool = False  # Already handled; only acknowledged again
+
+
+class CommandRunner:
+    """Fetches, checks, runs and acknowledges the signed-in user's remote commands."""
+
+    DEFAULT_PATH = Path.home() / ".xswarm" / "remote_commands.json"
+
+    def __init__(self, client: ApiClient, handlers: Dict[str, Handler],
+                 allowed: Iterable[str] = ("speak", "refresh_config"), path: Optional[Path] = None,
+                 clock: Callable[[], float] = time.time):
+        self.client = client
+        self.handlers = handlers
+        self.allowed = set(allowed)
+        self.path = path or self.DEFAULT_PATH
+        self.clock = clock
+        self._seen: Optional[Dict[str, Dict[str, Any]]] = None  # idempotency key -> status, result, at
+        self._running = False
+
+    @classmethod
+    def from_config(cls, config, handlers: Dict[str, Handler]) -> "CommandRunner":
+        client = ApiClient(getattr(config, "server_url", "http://localhost:3000"), getattr(config, "api_token", None),
+                           policy=ApiPolicy.from_config(config))
+        return cls(client, handlers, getattr(config, "remote_commands", ("speak", "refresh_config")))
+
+    async def close(self):
+        await self.client.close()
+
+    def _load(self) -> Dict[str, Dict[str, Any]]:
+        if self._seen is None:
+            try:
+                self._seen = json.loads(self.path.read_text(encoding="utf-8")) if self.path.exists() else {}
+            except Exception as e:
+                logger.warning(f"Failed to load remote command history: {e}")
+                self._seen = {}
+        return self._seen
+
+    def _remember(self, command: RemoteCommand, status: str, result: str) -> None:
+        seen = self._load()
+        cutoff = self.clock() - SEEN_DAYS * 86400
+        for key in [key for key, entry in seen.items() if entry.get("at", 0) < cutoff]:
+            del seen[key]
+        seen[command.idempotency_key] = {"id": command.id, "type": command.type, "status": status,
+                                         "result": result, "at": self.clock()}
+        try:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            self.path.write_text(json.dumps(seen, indent=2), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save remote command history: {e}")
+
+    async def _execute(self, command: RemoteCommand) -> Outcome:
+        previous = self._load().get(command.idempotency_key)
+        if previous is not None:
+            return Outcome(command, previous["status"], previous["result"], repeated=True)
+        if command.type not in COMMAND_TYPES or command.type not in self.handlers:
+            outcome = Outcome(command, "rejected", f"unknown command '{command.type}'")
+        elif command.type not in self.allowed:
+            outcome = Outcome(command, "rejected", f"{command.type} isn't allowed here (config.remote_commands)")
+        else:
+            # Remembered before it runs: a crash mid-command must not run it again
+            self._remember(command, "failed", "interrupted")
+            try:
+                outcome = Outcome(command, "done", await self.handlers[command.type](command.args))
+            except CommandFailed as e:
+                outcome = Outcome(command, "failed", str(e))
+            except Exception as e:
+                logger.warning(f"Remote command {command.type} failed: {e}")
+                outcome = Outcome(command, "failed", f"error: {e}")
+        self._remember(command, outcome.status, outcome.result)
+        return outcome
+
+    async def _ack(self, outcome: Outcome) -> None:
+        try:
+            # Acks are idempotent on the server, so they're safe to retry (endpoints.COMMAND_ACK)
+            await self.client.call(endpoints.COMMAND_ACK(command_id=outcome.command.id), ok_statuses=(404,),
+                                   json={"status": outcome.status, "result": outcome.result})
+        except ApiError as e:
+            logger.debug(f"Acknowledging command {outcome.command.id} failed, it will be seen again: {e}")
+
+    async def poll(self) -> List[Outcome]:
+        """Run (or reject) the pending commands and acknowledge each. Skipped while a poll is running."""
+        if self._running:
+            return []
+        self._running = True
+        try:
+            response = await self.client.call(endpoints.COMMANDS())
+            outcomes = []
+            for raw in response.json().get("commands", []):
+                outcome = await self._execute(RemoteCommand.from_dict(raw))
+                await self._ack(outcome)
+                outcomes.append(outcome)
+            return outcomes
+        finally:
+            self._running = False
@@ -0,0 +1,592 @@
+"""
+Scenario Replay - Regression-test the voice pipeline from a script.
+
+`xswarm dev replay scenario.yaml` feeds each turn of a scenario through the
+same path a spoken utterance takes, with the AI and TTS mocked:
+
+    wake word -> STT -> intent routing -> AI (scripted) -> tools -> TTS (recorded)
+
+- STT: a turn is either a transcript (`say:`) or a recorded WAV (`audio:`,
+  mono 16-bit PCM, transcribed with the Vosk model from wake_word_model)
+- wake word: with `wake_word:` set, turns without it are ignored, as the
+  live detector would, unless they follow up on a reply within
+  config.follow_up_window (conversation_window.py); `pause:` on a turn is the silence
+  before it, in seconds. The wake word itself is stripped before routing
+- routing: guided flows, pending confirmations, "undo that", "speak louder",
+  "what did I miss?" and follow-up detection run for real (flows.py,
+  confirmation.py, undo.py, volume.py, events.py, followups.py),
+  and tool calls are checked against what was said (intents.py), with
+  "it" resolved to what the conversation is about (dialogue.py)
+- AI: the turn's `ai:` block is the model's answer - `reply` text (which
+  may contain [TOOL: name key=value] commands) and `tools` calls. Tool
+  calls go through the real registry and confirmation policy
+- TTS: everything the assistant would say is recorded in `spoken`
+
+Scenarios run against a throwaway planner, undo log and event history,
+never your own.
+Outbound tools (email, calls) can be replaced with canned results via
+`mock_tools`.
+
+Example:
+
+    name: Book the dentist
+    wake_word: jarvis
+    turns:
+      - say: "jarvis book the dentist friday at four"
+        ai:
+          reply: "Booked the dentist for Friday at 4."
+          tools:
+            - add_calendar_event: {title: Dentist, day: friday, start_time: "16:00"}
+      - say: "what's the weather"          # No wake word, after the follow-up window: ignored
+        pause: 10
+    expect:
+      tools: [add_calendar_event]
+      appointments: [{title: Dentist, time: "16:00"}]
+      spoken: ["Booked the dentist"]
+
+Assertions (`expect:` on the scenario, or on a turn for that turn only):
+spoken (substrings), not_spoken, tools (names, in call order),
+appointments / tasks (partial matches: title, date YYYY-MM-DD, time
+HH:MM, tags, attendees), followups (title substrings), ignored (turn only).
+
+Failover drills: with `chaos:` on the scenario (or `--chaos`), the
+simulated failures of chaos.py strike the replay too. A transcript can be
+lost (route stt_error), and the scripted answer comes from a latency
+ladder - the provider, its small model, then "local" - so a rung that
+fails or is slow hands over to the next, fillers are spoken past
+config.voice_latency_budget, and with every rung down the turn's route is
+ai_error. Turn-only assertions for these: route, answered_by (the rung's
+name) and fillers (how many were spoken):
+
+    chaos: {outage: anthropic}
+    config: {ai_provider: anthropic}
+    turns:
+      - say: "what's next?"
+        ai: {reply: "Standup at nine."}
+        expect: {answered_by: local, spoken: ["Standup at nine"]}
+"""
+
+import shutil
+import tempfile
+import wave
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+import yaml
+
+from .chaos import Chaos, ChaosError, get_chaos, set_chaos
+from .conversation_window import FollowUpWindow
+
+
+class ScenarioError(ValueError):
+    """The scenario file is malformed."""
+
+
+# ==============================================================================
+# SCENARIO FORMAT
+# ==============================================================================
+
+@dataclass
+class ToolCallSpec:
+    name: str
+    args: Dict[str, Any] = field(default_factory=dict)
+
+
+@dataclass
+class Turn:
+    say: Optional[str] = None
+    audio: Optional[Path] = None
+    reply: str = ""
+    tools: List[ToolCallSpec] = field(default_factory=list)
+    expect: Dict[str, Any] = field(default_factory=dict)
+    pause: float = 0.0  # Seconds of silence before the turn
+
+
+@dataclass
+class Scenario:
+    name: str
+    turns: List[Turn]
+    wake_word: List[str] = field(default_factory=list)
+    config: Dict[str, Any] = field(default_factory=dict)
+    mock_tools: Dict[str, Any] = field(default_factory=dict)
+    expect: Dict[str, Any] = field(default_factory=dict)
+    chaos: Optional[Chaos] = None
+    path: Optional[Path] = None
+
+    @classmethod
+    def load(cls, path: Path) -> "Scenario":
+        path = Path(path)
+        try:
+            data = yaml.safe_load(path.read_text(encoding="utf-8"))
+        except (OSError, yaml.YAMLError) as e:
+            raise ScenarioError(f"Can't read {path}: {e}")
+        return cls.from_dict(data, base_dir=path.parent, path=path)
+
+    @classmethod
+    def from_dict(cls, data: Any, base_dir: Path = Path("."), path: Optional[Path] = None) -> "Scenario":
+        if not isinstance(data, dict) or not isinstance(data.get("turns"), list) or not data["turns"]:
+            raise ScenarioError("A scenario needs a non-empty 'turns' list")
+
+        turns = []
+        for number, raw in enumerate(data["turns"], 1):
+            if not isinstance(raw, dict) or ("say" in raw) == ("audio" in raw):
+                raise ScenarioError(f"Turn {number}: give exactly one of 'say' or 'audio'")
+            ai = raw.get("ai") or {}
+            turns.append(Turn(
+                say=str(raw["say"]) if "say" in raw else None,
+                audio=base_dir / raw["audio"] if "audio" in raw else None,
+                reply=str(ai.get("reply", "")),
+                tools=[_tool_spec(spec, number) for spec in ai.get("tools", [])],
+                expect=raw.get("expect") or {},
+                pause=float(raw.get("pause") or 0),
+            ))
+
+        wake_word = data.get("wake_word") or []
+        try:
+            chaos = Chaos.parse(data["chaos"]) if data.get("chaos") else None
+        except ValueError as e:
+            raise ScenarioError(str(e))
+        return cls(
+            name=str(data.get("name") or (path.stem if path else "scenario")),
+            turns=turns,
+            wake_word=[w.lower().strip() for w in ([wake_word] if isinstance(wake_word, str) else wake_word)],
+            config=data.get("config") or {},
+            mock_tools=data.get("mock_tools") or {},
+            expect=data.get("expect") or {},
+            chaos=chaos,
+            path=path,
+        )
+
+
+def _tool_spec(spec: Any, turn: int) -> ToolCallSpec:
+    # Either {tool_name: {args}} or {name: tool_name, args: {...}}
+    if isinstance(spec, dict) and "name" in spec:
+        return ToolCallSpec(str(spec["name"]), dict(spec.get("args") or {}))
+    if isinstance(spec, dict) and len(spec) == 1:
+        name, args = next(iter(spec.items()))
+        return ToolCallSpec(str(name), dict(args or {}))
+    raise ScenarioError(f"Turn {turn}: tool calls look like '- tool_name: {{arg: value}}'")
+
+
+# ==============================================================================
+# SPEECH TO TEXT
+# ==============================================================================
+
+class VoskFileTranscriber:
+    """Transcribe recorded WAV files with the same Vosk model the live pipeline uses."""
+
+    def __init__(self, model_path: Path):
+        if not Path(model_path).exists():
+            raise ScenarioError(f"Vosk model not found: {model_path} (needed for 'audio' turns)")
+        from vosk import Model
+        self.model = Model(str(model_path))
+
+    def __call__(self, path: Path) -> str:
+        import json
+        from vosk import KaldiRecognizer
+
+        with wave.open(str(path), "rb") as wav:
+            if wav.getnchannels() != 1 or wav.getsampwidth() != 2:
+                raise ScenarioError(f"{path}: audio turns must be mono 16-bit PCM WAV")
+            recognizer = KaldiRecognizer(self.model, wav.getframerate())
+            while True:
+                data = wav.readframes(4000)
+                if not data:
+                    break
+                recognizer.AcceptWaveform(data)
+        return json.loads(recognizer.FinalResult()).get("text", "")
+
+
+# ==============================================================================
+# REPLAY
+# ==============================================================================
+
+@dataclass
+class ToolCall:
+    name: str
+    args: Dict[str, Any]
+    success: bool
+    result: str
+    pending_confirmation: bool = False
+
+
+@dataclass
+class TurnResult:
+    number: int
+    heard: str
+    ignored: bool = False
+    route: str = "ai"  # ai, flow, confirmation, undo, volume, missed; stt_error, ai_error under chaos
+    tools: List[ToolCall] = field(default_factory=list)
+    spoken: List[str] = field(default_factory=list)
+    followups: List[str] = field(default_factory=list)
+    failures: List[str] = field(default_factory=list)
+    answered_by: str = ""  # The ladder rung that answered, under chaos
+    fillers: int = 0
+    problem: str = ""  # What chaos broke on the turn
+
+
+@dataclass
+class ReplayResult:
+    scenario: str
+    turns: List[TurnResult] = field(default_factory=list)
+    failures: List[str] = field(default_factory=list)
+    appointments: List[Dict[str, Any]] = field(default_factory=list)
+    tasks: List[Dict[str, Any]] = field(default_factory=list)
+    chaos: str = ""  # What chaos injected over the run
+
+    @property
+    def passed(self) -> bool:
+        return not self.failures and not any(t.failures for t in self.turns)
+
+    @property
+    def spoken(self) -> List[str]:
+        return [line for t in self.turns for line in t.spoken]
+
+    @property
+    def tools(self) -> List[ToolCall]:
+        return [call for t in self.turns for call in t.tools]
+
+    def lines(self) -> List[str]:
+        """Human-readable transcript of the run, with any failed assertions."""
+        out = [f"Scenario: {self.scenario}"]
+        for turn in self.turns:
+            if turn.ignored:
+                out.append(f"  {turn.number}. 🎤 \"{turn.heard}\" (ignored - no wake word)")
+            else:
+                out.append(f"  {turn.number}. 🎤 \"{turn.heard}\"" + (f" [{turn.route}]" if turn.route != "ai" else ""))
+            if turn.problem:
+                out.append(f"     🐒 {turn.problem}")
+            elif turn.answered_by:
+                out.append(f"     🐒 answered by {turn.answered_by}")
+            for call in turn.tools:
+                mark = "⏸" if call.pending_confirmation else ("🔧" if call.success else "✗")
+                out.append(f"     {mark} {call.name}({_format_args(call.args)}) -> {call.result}")
+            for title in turn.followups:
+                out.append(f"     📌 follow-up: {title}")
+            for line in turn.spoken:
+                out.append(f"     🔊 {line}")
+            for failure in turn.failures:
+                out.append(f"     ✗ {failure}")
+        for failure in self.failures:
+            out.append(f"  ✗ {failure}")
+        if self.chaos:
+            out.append(f"  🐒 chaos: {self.chaos}")
+        out.append("✓ passed" if self.passed else f"✗ failed ({len(self.failures) + sum(len(t.failures) for t in self.turns)} assertion(s))")
+        return out
+
+
+def _format_args(args: Dict[str, Any]) -> str:
+    return ", ".join(f"{k}={v!r}" for k, v in args.items())
+
+
+class ReplayRunner:
+    """Runs one scenario against an isolated planner, undo log and confirmation policy."""
+
+    def __init__(self, scenario: Scenario, transcriber: Optional[Callable[[Path], str]] = None,
+                 base_config: Any = None, registry: Any = None, chaos: Optional[Chaos] = None):
+        self.scenario = scenario
+        self.transcriber = transcriber
+        self.base_config = base_config
+        self.chaos = chaos or scenario.chaos or Chaos()  # --chaos wins over the scenario's
+        if registry is None:
+            from .tools import registry
+        self.registry = registry
+        self.planner = None
+        self.flow = None  # Active FlowRun
+
+    async def run(self) -> ReplayResult:
+        from . import events, intents, tools
+        from .confirmation import ConfirmationPolicy, get_confirmation_policy, set_confirmation_policy
+        from .dates import DateSettings, get_date_settings, set_date_settings
+        from .dialogue import DialogueState, get_dialogue_state, set_dialogue_state
+        from .planner import PlannerData
+        from .undo import UndoLog
+        from .volume import VolumeSettings, get_volume_settings, set_volume_settings
+
+        workdir = Path(tempfile.mkdtemp(prefix="xswarm-replay-"))
+        saved = (tools._planner_data, tools._undo_log, get_confirmation_policy(), get_date_settings(),
+                 intents._current, get_dialogue_state(), get_volume_settings(), events._store, get_chaos())
+        saved_tools = {}
+        try:
+            self.planner = PlannerData(workdir / "planner")
+            tools.set_planner_data(self.planner)
+            tools._undo_log = UndoLog(workdir / "undo")
+            config = self.config = self._config()
+            set_chaos(self.chaos)
+            policy = ConfirmationPolicy(config)
+            set_confirmation_policy(policy)
+            set_date_settings(DateSettings.from_config(config))
+            set_dialogue_state(DialogueState())
+            volume = VolumeSettings.from_config(config)
+            volume.config = None  # Volume changes stay in the scenario
+            set_volume_settings(volume)
+            events.set_event_store(events.EventStore(":memory:"))
+            self._now = 0.0
+            self.follow_up = FollowUpWindow.from_config(config, clock=lambda: self._now)
+            self._spoken_verbal: List[str] = []
+            policy.on_verbal = self._spoken_verbal.append
+            saved_tools = self._install_mock_tools()
+
+            result = ReplayResult(self.scenario.name)
+            for number, turn in enumerate(self.scenario.turns, 1):
+                self._now += turn.pause
+                turn_result = await self._run_turn(number, turn, policy)
+                if turn_result.spoken:
+                    self.follow_up.open()  # The user can answer without the wake word
+                turn_result.failures = check_expectations(turn.expect, turn_result=turn_result)
+                result.turns.append(turn_result)
+
+            self.planner.reload()
+            # Every stored event, not just the next 30 days, so scenarios with fixed dates don't expire
+            result.appointments = [_event_row(e) for e in self.planner.get_calendar_events(expand_recurring=False)]
+            result.tasks = [_task_row(t) for t in self.planner.get_tasks()]
+            result.failures = check_expectations(self.scenario.expect, result=result)
+            if self.chaos.active:
+                result.chaos = self.chaos.summary()
+            return result
+        finally:
+            for name, tool in saved_tools.items():
+                self.registry._tools[name] = tool
+            tools._planner_data, tools._undo_log = saved[0], saved[1]
+            set_confirmation_policy(saved[2])
+            set_date_settings(saved[3])
+            intents._current = saved[4]
+            set_dialogue_state(saved[5])
+            set_volume_settings(saved[6])
+            events.get_event_store().close()
+            events.set_event_store(saved[7])
+            set_chaos(saved[8])
+            shutil.rmtree(workdir, ignore_errors=True)
+
+    def _config(self):
+        from .config import Config
+        base = self.base_config.model_dump() if self.base_config is not None else {}
+        return Config(**{**base, **self.scenario.config})
+
+    def _install_mock_tools(self) -> Dict[str, Any]:
+        """Replace outbound tools with canned results. Returns the originals to restore."""
+        from .tools import ToolDefinition
+        saved = {}
+        for name, canned in self.scenario.mock_tools.items():
+            original = self.registry.get_tool(name)
+            if original is not None:
+                saved[name] = original
+
+            def mocked(_canned=canned, **kwargs):
+                return _canned
+
+            self.registry._tools[name] = ToolDefinition(
+                name=name, description=f"(mocked) {original.description if original else name}",
+                func=mocked, parameters=original.parameters if original else {},
+            )
+        return saved
+
+    def _hear(self, turn: Turn) -> str:
+        if turn.say is not None:
+            return turn.say
+        if self.transcriber is None:
+            raise ScenarioError("This scenario has 'audio' turns but no speech-to-text model is available")
+        return self.transcriber(turn.audio)
+
+    async def _run_turn(self, number: int, turn: Turn, policy) -> TurnResult:
+        from .flows import FlowRun, flow_for_request
+        from .followups import detect_followups
+        from .intents import hear
+        from .events import get_event_store, is_missed_request, missed_since, summarize_missed
+        from .undo import is_undo_request
+        from .volume import parse_volume_request
+
+        heard = self._hear(turn).strip()
+        result = TurnResult(number=number, heard=heard)
+        try:
+            await self.chaos.disrupt("stt", "vosk")
+        except ChaosError as e:
+            result.route, result.problem = "stt_error", f"transcript lost: {e}"
+            return result
+        text = heard
+        if self.scenario.wake_word:
+            text = self.follow_up.admit(heard, self.scenario.wake_word)
+            if text is None:
+                result.ignored = True
+                return result
+        self._spoken_verbal.clear()
+        hear(text)
+        last_active = get_event_store().touch()
+
+        # Same routing as the dashboard's _on_voice_text
+        if self.flow is not None and not self.flow.expired() and self.flow.is_active:
+            result.route = "flow"
+            result.spoken.append(self.flow.handle(text))
+            return result
+
+        if policy.has_pending():
+            resolution = policy.resolve(text)
+            if resolution is not None:
+                result.route = "confirmation"
+                if resolution.status == "confirmed":
+                    action = resolution.action
+                    call = await self._call_tool(action.tool_name, action.args, confirmed=True,
+                                                 registry=action.registry)
+                    result.tools.append(call)
+                    result.spoken.append(call.result.lstrip("✓✗ "))
+                else:
+                    result.spoken.append(resolution.message)
+                return result
+
+        if is_undo_request(text):
+            from .tools import undo_last
+            result.route = "undo"
+            result.spoken.append(undo_last().lstrip("✓✗ "))
+            return result
+
+        volume = parse_volume_request(text)
+        if volume is not None:
+            from .tools import set_volume
+            result.route = "volume"
+            result.spoken.append(set_volume(volume.stream, volume.level).lstrip("✓✗ "))
+            return result
+
+        if is_missed_request(text):
+            result.route = "missed"
+            result.spoken.append(summarize_missed(get_event_store().query(since=missed_since(last_active))))
+            return result
+
+        flow = flow_for_request(text)
+        if flow is not None:
+            self.flow = FlowRun(flow)
+            result.route = "flow"
+            result.spoken.append(self.flow.start())
+            return result
+
+        result.followups = [f.title for f in detect_followups(text)]
+        if self.chaos.active and not await self._ask_ladder(text, result):
+            return result
+
+        # The scripted model answer: function calls, then [TOOL: ...] commands in the reply text
+        from .tools import CommandParser
+        calls = [(spec.name, spec.args) for spec in turn.tools]
+        for name, args, kwargs in CommandParser.parse(turn.reply):
+            tool = self.registry.get_tool(name)
+            bound = _bind(tool.func, args, kwargs) if tool else dict(kwargs)
+            calls.append((name, bound))
+        for name, args in calls:
+            call = await self._call_tool(name, args)
+            result.tools.append(call)
+            if call.pending_confirmation:
+                result.spoken.append(policy.pending.prompt())
+
+        reply = CommandParser.strip_commands(turn.reply)
+        if reply:
+            result.spoken.append(reply)
+        result.spoken.extend(self._spoken_verbal)
+        return result
+
+    async def _ask_ladder(self, text: str, result: TurnResult) -> bool:
+        """The scripted answer through a latency ladder chaos can fail and slow down; False if no rung answered."""
+        from .latency import BudgetedAI, LatencyBudget, LatencyError, LatencyMetrics, Rung
+
+        def rung(name: str, **kind) -> Rung:
+            async def chat(messages, max_tokens):
+                result.answered_by = name
+                return ""
+            return Rung(name, chat, **kind)
+
+        async def filler(phrase: str) -> None:
+            result.fillers += 1
+            result.spoken.append(phrase)
+
+        provider = self.config.ai_provider
+        ladder = [rung(provider), rung(f"{provider}:small", small=True), rung("local", local=True)]
+        ai = BudgetedAI(ladder, LatencyBudget.from_config(self.config), filler, LatencyMetrics())
+        try:
+            await ai.chat([{"role": "user", "content": text}])
+        except LatencyError as e:
+            result.route, result.problem = "ai_error", f"no answer: {e}"
+            return False
+        return True
+
+    async def _call_tool(self, name: str, args: Dict[str, Any], confirmed: bool = False,
+                         registry: Any = None) -> ToolCall:
+        outcome = await (registry or self.registry).execute_tool(name, args, confirmed=confirmed)
+        if outcome["success"]:
+            return ToolCall(name, dict(args), True, str(outcome["result"]),
+                            pending_confirmation=bool(outcome.get("pending_confirmation")))
+        return ToolCall(name, dict(args), False, f"✗ {outcome['message']}")
+
+
+def _bind(func: Callable, args: List[Any], kwargs: Dict[str, Any]) -> Dict[str, Any]:
+    import inspect
+    try:
+        return dict(inspect.signature(func).bind_partial(*args, **kwargs).arguments)
+    except TypeError:
+        return dict(kwargs)
+
+
+def _event_row(event) -> Dict[str, Any]:
+    start = event.start_time or ""
+    return {"title": event.title, "date": start[:10], "time": start[11:16], "tags": list(event.tags or []),
+            "attendees": list(event.attendees or [])}
+
+
+def _task_row(task) -> Dict[str, Any]:
+    scheduled = getattr(task, "scheduled_time", None) or ""
+    return {"title": task.title, "status": task.status, "date": getattr(task, "due_date", None) or "",
+            "time": scheduled[11:16] if "T" in scheduled else scheduled}
+
+
+# ==============================================================================
+# ASSERTIONS
+# ==============================================================================
+
+def check_expectations(expect: Dict[str, Any], result: Optional[ReplayResult] = None,
+                       turn_result: Optional[TurnResult] = None) -> List[str]:
+    """Failed assertions (empty when everything matched)."""
+    if not expect:
+        return []
+    source = turn_result or result
+    spoken = source.spoken
+    tool_names = [call.name for call in source.tools]
+    followups = turn_result.followups if turn_result else [f for t in result.turns for f in t.followups]
+    failures = []
+
+    for phrase in expect.get("spoken", []):
+        if not any(phrase.lower() in line.lower() for line in spoken):
+            failures.append(f"expected to hear {phrase!r}, heard {spoken}")
+    for phrase in expect.get("not_spoken", []):
+        if any(phrase.lower() in line.lower() for line in spoken):
+            failures.append(f"did not expect to hear {phrase!r}")
+    if "tools" in expect and tool_names != list(expect["tools"]):
+        failures.append(f"expected tool calls {list(expect['tools'])}, got {tool_names}")
+    for phrase in expect.get("followups", []):
+        if not any(phrase.lower() in title.lower() for title in followups):
+            failures.append(f"expected follow-up {phrase!r}, got {followups}")
+    if "ignored" in expect and turn_result is not None and bool(expect["ignored"]) != turn_result.ignored:
+        failures.append("expected the turn to be ignored" if expect["ignored"] else "turn was ignored (no wake word)")
+    if turn_result is not None:
+        for key in ("route", "answered_by", "fillers"):
+            if key in expect and str(expect[key]) != str(getattr(turn_result, key)):
+                failures.append(f"expected {key} {expect[key]!r}, got {getattr(turn_result, key)!r}")
+
+    if result is not None:
+        for kind in ("appointments", "tasks"):
+            rows = getattr(result, kind)
+            for wanted in expect.get(kind, []):
+                if not any(_matches(row, wanted) for row in rows):
+                    failures.append(f"expected {kind[:-1]} {wanted}, have {[r['title'] for r in rows]}")
+    return failures
+
+
+def _matches(row: Dict[str, Any], wanted: Dict[str, Any]) -> bool:
+    for key, value in wanted.items():
+        actual = row.get(key)
+        if key == "title":
+            if str(value).lower() not in str(actual).lower():
+                return False
+        elif key in ("tags", "attendees"):
+            if not set(value) <= set(actual or []):
+                return False
+        elif str(actual) != str(value):
+            return False
+    return True
+
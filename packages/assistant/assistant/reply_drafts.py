@@ -0,0 +1,230 @@
+"""
+Reply Drafts - Voice-driven "draft a reply" workflow for inbox messages.
+
+Flow for a single inbox item:
+1. The AI drafts a reply in the current persona's voice, shaped for the
+   channel (short and plain for SMS, greeting + sign-off for email).
+2. The draft is read back and the user is asked to send, change or cancel.
+3. Edit requests ("make it shorter", "mention Friday") produce a revision
+   and loop back to step 2.
+4. Nothing is sent until the user explicitly confirms.
+
+The workflow is transport-agnostic: callers feed it user utterances (typed
+or transcribed) and speak/display whatever it returns.
+"""
+
+import logging
+import re
+from dataclasses import dataclass, field
+from typing import Optional, List
+
+from .api_client import explain
+from .inbox import InboxError
+from .quota import get_quota_manager
+
+logger = logging.getLogger(__name__)
+
+
+# Channel-specific drafting guidance
+CHANNEL_GUIDELINES = {
+    "sms": (
+        "This is a text message. Keep it under 300 characters, plain text, "
+        "no greeting line and no sign-off."
+    ),
+    "email": (
+        "This is an email. Open with a short greeting using the sender's name "
+        "if known, keep the body to a few short paragraphs, and end with a "
+        "brief sign-off."
+    ),
+}
+
+# Explicit confirmations only - a bare "yes" is ambiguous mid-conversation
+CONFIRM_PHRASES = (
+    "send it", "send", "yes send it", "yes send", "send the reply",
+    "go ahead", "go ahead and send it", "confirm", "yes confirm", "ship it",
+)
+CANCEL_PHRASES = (
+    "cancel", "never mind", "nevermind", "don't send", "do not send",
+    "stop", "forget it", "discard", "discard it",
+)
+REPEAT_PHRASES = (
+    "repeat", "read it again", "say it again", "read it back", "what was it",
+)
+
+MAX_REVISIONS = 10
+
+
+def _normalize(text: str) -> str:
+    text = re.sub(r"[^\w\s']", " ", text.lower())
+    return " ".join(text.split())
+
+
+def classify_reply_command(text: str) -> str:
+    """
+    Classify a user utterance made while a draft awaits confirmation.
+
+    Returns one of: "confirm", "cancel", "repeat", "edit".
+    Anything that is not an exact confirm/cancel/repeat phrase is treated as
+    an edit instruction, so "send it but shorter" never sends.
+    """
+    normalized = _normalize(text)
+    if normalized in CONFIRM_PHRASES:
+        return "confirm"
+    if normalized in CANCEL_PHRASES:
+        return "cancel"
+    if normalized in REPEAT_PHRASES:
+        return "repeat"
+    return "edit"
+
+
+@dataclass
+class ReplyDraftSession:
+    """State for drafting a reply to one inbox item."""
+    item_id: str
+    channel: str
+    sender: str
+    original: str
+    subject: Optional[str] = None
+    state: str = "drafting"  # drafting, awaiting_confirmation, sent, cancelled
+    draft: str = ""
+    revisions: List[str] = field(default_factory=list)
+
+    @property
+    def reply_channel(self) -> str:
+        """Voice messages are answered by text."""
+        return "email" if self.channel == "email" else "sms"
+
+    @property
+    def is_active(self) -> bool:
+        return self.state in ("drafting", "awaiting_confirmation")
+
+    def set_draft(self, text: str) -> None:
+        if self.draft:
+            self.revisions.append(self.draft)
+        self.draft = text.strip()
+        self.state = "awaiting_confirmation"
+
+
+class ReplyWorkflow:
+    """
+    Drives a ReplyDraftSession from user utterances.
+
+    ai_client is the voice.AIClient; when it is unavailable, drafts come from
+    the server's /api/inbox/:id/draft endpoint via inbox_manager.
+    """
+
+    def __init__(self, ai_client, persona_manager, inbox_manager):
+        self.ai = ai_client
+        self.persona_manager = persona_manager
+        self.inbox_manager = inbox_manager
+        self.session: Optional[ReplyDraftSession] = None
+
+    @property
+    def is_active(self) -> bool:
+        return self.session is not None and self.session.is_active
+
+    async def start(self, item_id: str) -> str:
+        """Begin drafting a reply. Returns the text to speak/display."""
+        try:
+            item = self.inbox_manager.store.get_item(item_id)
+        except InboxError as e:
+            return f"I'm not sure which message you mean: {e}."
+        if not item:
+            return "I couldn't find that message."
+
+        self.session = ReplyDraftSession(
+            item_id=item.id,
+            channel=item.channel,
+            sender=item.sender,
+            original=item.content,
+            subject=item.subject,
+        )
+        draft = await self._generate()
+        if not draft:
+            self.session.state = "cancelled"
+            return "Sorry, I couldn't draft a reply right now."
+
+        self.session.set_draft(draft)
+        return self._read_back(f"Here's a draft reply to {item.sender}")
+
+    async def handle(self, utterance: str) -> str:
+        """Handle a user utterance while a draft is pending."""
+        if not self.is_active:
+            return ""
+
+        command = classify_reply_command(utterance)
+        if command == "cancel":
+            self.session.state = "cancelled"
+            return "Okay, I won't send it."
+        if command == "repeat":
+            return self._read_back("The draft says")
+        if command == "confirm":
+            return await self._send()
+
+        if len(self.session.revisions) >= MAX_REVISIONS:
+            return "That's a lot of revisions. Say 'send it' to send the current draft or 'cancel' to discard it."
+        draft = await self._generate(instructions=utterance)
+        if not draft:
+            return "Sorry, I couldn't revise it. " + self._prompt()
+        self.session.set_draft(draft)
+        return self._read_back("Here's the revised draft")
+
+    def _prompt(self) -> str:
+        return "Shall I send it, change it, or cancel?"
+
+    def _read_back(self, lead: str) -> str:
+        return f"{lead}: {self.session.draft}\n\n{self._prompt()}"
+
+    async def _send(self) -> str:
+        session = self.session
+        quota = get_quota_manager() if session.reply_channel == "sms" else None
+        decision = quota.check("sms_messages") if quota else None
+        if decision and not decision.allowed:
+            return f"I can't send that text: {decision.message} Say 'cancel' to discard the draft."
+        sent = await self.inbox_manager.send_reply(session.item_id, session.draft)
+        if not sent:
+            error = getattr(self.inbox_manager, "last_error", None)
+            reason = explain(error) if error else "The server may be unreachable"
+            return f"I couldn't send that. {reason}. " + self._prompt()
+        session.state = "sent"
+        via = "email" if session.reply_channel == "email" else "text message"
+        notes = [decision.message if decision else "", quota.record("sms_messages", 1) if quota else ""]
+        return " ".join([f"Sent your reply to {session.sender} by {via}.", *filter(None, notes)])
+
+    def build_messages(self, instructions: Optional[str] = None) -> List[dict]:
+        """Chat messages for the AI client, in the current persona's voice."""
+        session = self.session
+        persona = self.persona_manager.get_current_persona() if self.persona_manager else None
+        system = persona.build_system_prompt() if persona else "You are a helpful assistant."
+        system += (
+            "\n\nYou are drafting a reply that the user will send as themselves. "
+            + CHANNEL_GUIDELINES[session.reply_channel]
+            + " Return only the reply text."
+        )
+
+        parts = [f"Incoming {session.channel} message from {session.sender}:"]
+        if session.subject:
+            parts.append(f"Subject: {session.subject}")
+        parts.append(session.original)
+        if session.draft and instructions:
+            parts.append(f"Current draft:\n{session.draft}")
+            parts.append(f"Revise the draft: {instructions}")
+        else:
+            parts.append("Draft a reply.")
+
+        return [
+            {"role": "system", "content": system},
+            {"role": "user", "content": "\n\n".join(parts)},
+        ]
+
+    async def _generate(self, instructions: Optional[str] = None) -> Optional[str]:
+        if self.ai is not None and self.ai.is_available():
+            try:
+                return (await self.ai.chat(self.build_messages(instructions), max_tokens=600)).strip()
+            except Exception as e:
+                logger.debug(f"Reply draft via AI client failed: {e}")
+
+        # Fall back to server-side drafting
+        if self.session.draft and instructions:
+            instructions = f"Revise this draft: {self.session.draft}\nChange requested: {instructions}"
+        return await self.inbox_manager.draft_reply(self.session.item_id, instructions=instructions)
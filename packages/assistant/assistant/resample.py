@@ -0,0 +1,134 @@
+"""
+Resampling - Per-consumer sample rates for the audio pipeline.
+
+The mic is captured once, but consumers want different formats:
+
+- Moshi encoder:              24kHz float32
+- User transcriber/wake word: 16kHz int16 (Vosk)
+- Playback:                   the output device's native rate
+
+Each consumer declares an `input_format` (AudioFormat); FormatNegotiator
+converts captured frames to it, running one StreamResampler per target
+rate and caching the result on the frame, so two 16kHz consumers share one
+conversion (see audio_frame.py).
+
+StreamResampler is a polyphase FIR (Kaiser-windowed sinc) that keeps its
+filter history between calls, so 20ms frames resample without clicks at
+frame boundaries - unlike resampling each frame independently.
+"""
+
+import logging
+from dataclasses import dataclass
+from math import gcd
+from typing import Dict, Optional
+
+import numpy as np
+from scipy import signal
+
+from .audio_frame import AudioFrame
+
+logger = logging.getLogger(__name__)
+
+# Filter taps per polyphase branch (scaled up by the decimation ratio when downsampling):
+# higher = sharper anti-aliasing, more CPU
+TAPS_PER_PHASE = 24
+# Pass band as a fraction of the lower Nyquist frequency (the rest is transition band)
+PASSBAND = 0.92
+
+
+@dataclass(frozen=True)
+class AudioFormat:
+    """What a consumer expects: sample rate and sample type (mono)."""
+    sample_rate: int
+    dtype: str = "float32"  # "float32" or "int16"
+
+
+class StreamResampler:
+    """Stateful rational-ratio resampler for a continuous stream of mono frames."""
+
+    def __init__(self, src_rate: int, dst_rate: int, taps_per_phase: int = TAPS_PER_PHASE):
+        self.src_rate = src_rate
+        self.dst_rate = dst_rate
+        g = gcd(src_rate, dst_rate)
+        self.up = dst_rate // g
+        self.down = src_rate // g
+        # A lower cutoff needs a proportionally longer filter for the same transition band
+        self.taps = taps_per_phase * -(-max(self.up, self.down) // self.up)
+
+        if self.up == self.down:
+            self._phases = None
+            return
+
+        # Low-pass at the lower Nyquist, designed at the upsampled rate; gain `up` makes up for zero-stuffing
+        cutoff = PASSBAND / max(self.up, self.down)
+        h = signal.firwin(self.taps * self.up, cutoff, window=("kaiser", 8.0)) * self.up
+        # _phases[p, k] = h[k * up + p]: branch p filters the input for outputs landing on phase p
+        self._phases = h.reshape(self.taps, self.up).T.astype(np.float32)
+        self._history = np.zeros(self.taps - 1, dtype=np.float32)
+        self._pos = 0  # Next output position, in upsampled samples from the start of the next frame
+
+    @property
+    def passthrough(self) -> bool:
+        return self._phases is None
+
+    def process(self, samples: np.ndarray) -> np.ndarray:
+        """Resample the next frame of the stream."""
+        samples = np.asarray(samples, dtype=np.float32)
+        if self.passthrough or len(samples) == 0:
+            return samples
+
+        n = len(samples)
+        buf = np.concatenate((self._history, samples))
+        t = np.arange(self._pos, n * self.up, self.down)
+        if len(t) == 0:
+            self._pos -= n * self.up
+            self._history = buf[-(self.taps - 1):]
+            return np.zeros(0, dtype=np.float32)
+
+        # Output at upsampled time t uses input i = t // up on branch p = t % up:
+        # y = sum_k h[p + k*up] * x[i - k]   (buf is offset by taps - 1 history samples)
+        i = t // self.up
+        idx = i[:, None] - np.arange(self.taps)[None, :] + (self.taps - 1)
+        out = np.einsum("nk,nk->n", self._phases[t % self.up], buf[idx])
+
+        self._pos = int(t[-1]) + self.down - n * self.up
+        self._history = buf[-(self.taps - 1):]
+        return out.astype(np.float32)
+
+    def reset(self) -> None:
+        if not self.passthrough:
+            self._history[:] = 0
+            self._pos = 0
+
+
+class FormatNegotiator:
+    """Converts frames from the capture rate to each consumer's rate (one resampler per rate)."""
+
+    def __init__(self, source_rate: int):
+        self.source_rate = source_rate
+        self._resamplers: Dict[int, StreamResampler] = {}
+
+    @staticmethod
+    def format_of(consumer, default: AudioFormat) -> AudioFormat:
+        """A consumer's declared input_format, or the default for consumers that don't declare one."""
+        return getattr(consumer, "input_format", None) or default
+
+    def convert(self, frame: AudioFrame, rate: int) -> AudioFrame:
+        """The frame at `rate` - computed once per frame and rate, then shared."""
+        if rate == (frame.sample_rate or self.source_rate):
+            return frame
+        cached = frame.converted(rate)
+        if cached is not None:
+            return cached
+
+        resampler = self._resamplers.get(rate)
+        if resampler is None:
+            logger.debug(f"Resampling {self.source_rate}Hz -> {rate}Hz")
+            resampler = self._resamplers[rate] = StreamResampler(self.source_rate, rate)
+        result = AudioFrame(resampler.process(frame.samples), sample_rate=rate)
+        frame.cache_converted(rate, result)
+        return result
+
+    def for_consumer(self, frame: AudioFrame, consumer, default: Optional[AudioFormat] = None) -> AudioFrame:
+        fmt = self.format_of(consumer, default or AudioFormat(self.source_rate))
+        return self.convert(frame, fmt.sample_rate)
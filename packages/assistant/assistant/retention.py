@@ -0,0 +1,259 @@
+"""
+Data Retention - How long each kind of stored data is kept, in one place.
+
+Every data class has a retention period in days (0 = keep forever):
+
+- transcripts: saved chat sessions (~/.xswarm/chat_history), 365 days
+- audio: audio recordings (~/.xswarm/recordings), 30 days
+- events: activity and inbox history (~/.xswarm/events.db), 90 days or
+  config.event_history_days
+- ai_log: requests to language models (~/.xswarm/ai_log.db), 30 days
+- logs: log files (/tmp/xswarm_*.log, ~/.xswarm/logs), 14 days
+- exports: exported reports (~/.xswarm/exports), 30 days
+
+config.retention_days overrides single classes:
+
+    retention_days:
+      audio: 7
+      transcripts: 0      # keep forever
+
+The "retention" job (daily, see scheduler.py) deletes what has expired;
+with config.retention_dry_run it only reports it. `xswarm dev retention`
+shows what would be deleted now, and `--apply` deletes it.
+"""
+
+import json
+import logging
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Callable, Dict, List, Optional
+
+from .ai_log import AILog
+from .events import KEEP_DAYS, EventStore
+
+logger = logging.getLogger(__name__)
+
+XSWARM_DIR = Path.home() / ".xswarm"
+TMP_LOGS = (Path("/tmp"), "xswarm_*.log*")
+AUDIO_SUFFIXES = {".wav", ".mp3", ".ogg", ".flac", ".m4a", ".opus"}
+
+# Data class -> (default days, what it is)
+DATA_CLASSES: Dict[str, tuple] = {
+    "transcripts": (365, "Chat transcripts"),
+    "audio": (30, "Audio recordings"),
+    "events": (KEEP_DAYS, "Activity and inbox events"),
+    "ai_log": (30, "AI request log"),
+    "logs": (14, "Log files"),
+    "exports": (30, "Exports"),
+}
+
+
+class RetentionPolicy:
+    """Retention days per data class: the defaults with config overrides."""
+
+    def __init__(self, overrides: Optional[Dict[str, int]] = None, dry_run: bool = False):
+        self.problems: List[str] = []
+        self.days: Dict[str, int] = {name: days for name, (days, _) in DATA_CLASSES.items()}
+        for name, days in (overrides or {}).items():
+            if name not in DATA_CLASSES:
+                self.problems.append(f"Unknown retention class '{name}' (use {', '.join(DATA_CLASSES)})")
+            elif not isinstance(days, (int, float)) or days < 0:
+                self.problems.append(f"Retention for {name} must be a number of days (0 = keep forever)")
+            else:
+                self.days[name] = int(days)
+        self.dry_run = dry_run
+        for problem in self.problems:
+            logger.warning(problem)
+
+    @classmethod
+    def from_config(cls, config) -> "RetentionPolicy":
+        overrides = dict(getattr(config, "retention_days", None) or {})
+        overrides.setdefault("events", getattr(config, "event_history_days", KEEP_DAYS))
+        return cls(overrides, dry_run=bool(getattr(config, "retention_dry_run", False)))
+
+    def cutoff(self, name: str, now: datetime) -> Optional[datetime]:
+        """Data of class `name` older than this is expired; None when it's kept forever."""
+        days = self.days.get(name, 0)
+        return now - timedelta(days=days) if days else None
+
+
+@dataclass
+class Expired:
+    """Something past its retention period: a file, a chat session or a batch of events."""
+    label: str
+    when: Optional[datetime] = None
+    size: int = 0  # Bytes
+    count: int = 1
+
+
+@dataclass
+class RetentionReport:
+    applied: bool  # False: a dry run, nothing was deleted
+    days: Dict[str, int]
+    expired: Dict[str, List[Expired]] = field(default_factory=dict)
+    errors: List[str] = field(default_factory=list)
+
+    def total(self, name: Optional[str] = None) -> int:
+        names = [name] if name else list(self.expired)
+        return sum(item.count for n in names for item in self.expired.get(n, []))
+
+    def summary(self) -> str:
+        """"Deleted 3 transcripts, 412 events" (or "Would delete ..."), for the activity feed."""
+        parts = [f"{self.total(name)} {name}" for name in DATA_CLASSES if self.total(name)]
+        if not parts:
+            return "Nothing past its retention period"
+        return f"{'Deleted' if self.applied else 'Would delete'} {', '.join(parts)}"
+
+    def lines(self, verbose: bool = False) -> List[str]:
+        lines = ["Retention" if self.applied else "Retention (dry run - nothing deleted)"]
+        for name, (_, description) in DATA_CLASSES.items():
+            days = self.days.get(name, 0)
+            kept = f"{days} days" if days else "forever"
+            items = self.expired.get(name, [])
+            if not days:
+                status = "kept forever"
+            elif not items:
+                status = "nothing expired"
+            else:
+                size = sum(item.size for item in items)
+                status = f"{self.total(name)} {'deleted' if self.applied else 'to delete'}"
+                status += f" ({_format_size(size)})" if size else ""
+            lines.append(f"  {name:<12} {kept:<9} {status}")
+            if verbose:
+                for item in items:
+                    when = f"{item.when:%Y-%m-%d}  " if item.when else ""
+                    lines.append(f"      {when}{item.label}")
+        lines.extend(f"  ✗ {error}" for error in self.errors)
+        return lines
+
+
+def _format_size(size: int) -> str:
+    for unit in ("B", "KB", "MB"):
+        if size < 1024:
+            return f"{size:.0f} {unit}"
+        size /= 1024
+    return f"{size:.1f} GB"
+
+
+def _modified(path: Path) -> datetime:
+    return datetime.fromtimestamp(path.stat().st_mtime)
+
+
+class RetentionEngine:
+    """Finds (and deletes) data past the policy's retention periods."""
+
+    def __init__(self, policy: RetentionPolicy, root: Path = XSWARM_DIR,
+                 events: Optional[EventStore] = None, tmp_logs: tuple = TMP_LOGS,
+                 clock: Callable[[], datetime] = datetime.now):
+        self.policy = policy
+        self.root = root
+        self.events = events  # The open store; otherwise root/events.db is opened when it exists
+        self.tmp_logs = tmp_logs
+        self.clock = clock
+
+    @classmethod
+    def from_config(cls, config, events: Optional[EventStore] = None) -> "RetentionEngine":
+        return cls(RetentionPolicy.from_config(config), events=events)
+
+    def run(self, apply: Optional[bool] = None) -> RetentionReport:
+        """Delete expired data (apply=False, or config.retention_dry_run: only report it)."""
+        apply = not self.policy.dry_run if apply is None else apply
+        report = RetentionReport(applied=apply, days=dict(self.policy.days))
+        now = self.clock()
+        sweeps = {
+            "transcripts": self._transcripts,
+            "audio": lambda cutoff, apply: self._files([self.root / "recordings"], cutoff, apply, AUDIO_SUFFIXES),
+            "events": self._events,
+            "ai_log": self._ai_log,
+            "logs": lambda cutoff, apply: self._files([self.root / "logs"], cutoff, apply, pattern=self.tmp_logs),
+            "exports": lambda cutoff, apply: self._files([self.root / "exports"], cutoff, apply),
+        }
+        for name, sweep in sweeps.items():
+            cutoff = self.policy.cutoff(name, now)
+            if cutoff is None:
+                continue
+            try:
+                report.expired[name] = sweep(cutoff, apply)
+            except Exception as e:
+                logger.warning(f"Retention for {name} failed: {e}")
+                report.errors.append(f"{name}: {e}")
+        if apply and report.total():
+            logger.info(report.summary())
+        return report
+
+    def _files(self, dirs: List[Path], cutoff: datetime, apply: bool, suffixes: Optional[set] = None,
+               pattern: Optional[tuple] = None) -> List[Expired]:
+        paths = [path for folder in dirs if folder.is_dir() for path in folder.rglob("*")]
+        if pattern:
+            folder, glob = pattern
+            paths += list(Path(folder).glob(glob))
+        expired = []
+        for path in sorted(paths):
+            if not path.is_file() or (suffixes and path.suffix.lower() not in suffixes):
+                continue
+            when = _modified(path)
+            if when >= cutoff:
+                continue
+            expired.append(Expired(str(path), when, path.stat().st_size))
+            if apply:
+                path.unlink(missing_ok=True)
+        return expired
+
+    def _transcripts(self, cutoff: datetime, apply: bool) -> List[Expired]:
+        """Saved chat sessions, by when they ended (file time for sessions missing from the index)."""
+        base = self.root / "chat_history"
+        if not base.is_dir():
+            return []
+        expired = []
+        for persona_dir in sorted(p for p in base.iterdir() if p.is_dir()):
+            index_path = persona_dir / "sessions.json"
+            try:
+                index = json.loads(index_path.read_text(encoding="utf-8")) if index_path.exists() else []
+            except (OSError, ValueError) as e:
+                logger.warning(f"Skipping {persona_dir.name} transcripts, unreadable index: {e}")
+                continue
+            ended = {}
+            for entry in index:
+                try:
+                    ended[entry["session_id"]] = datetime.fromisoformat(entry.get("ended_at") or entry["started_at"])
+                except (KeyError, TypeError, ValueError):
+                    continue
+            removed = set()
+            for path in sorted(persona_dir.glob("session_*.json")):
+                when = ended.get(path.stem) or _modified(path)
+                if when >= cutoff:
+                    continue
+                expired.append(Expired(f"{persona_dir.name}/{path.stem}", when, path.stat().st_size))
+                removed.add(path.stem)
+                if apply:
+                    path.unlink(missing_ok=True)
+            if apply and removed:
+                index = [entry for entry in index if entry.get("session_id") not in removed]
+                index_path.write_text(json.dumps(index, indent=2, ensure_ascii=False), encoding="utf-8")
+        return expired
+
+    def _events(self, cutoff: datetime, apply: bool) -> List[Expired]:
+        store, opened = self.events, False
+        if store is None:
+            path = self.root / "events.db"
+            if not path.exists():
+                return []
+            store, opened = EventStore(path, keep_days=None), True
+        try:
+            count = store.prune(cutoff, dry_run=not apply)
+        finally:
+            if opened:
+                store.close()
+        return [Expired(f"{count} events before {cutoff:%Y-%m-%d}", count=count)] if count else []
+
+    def _ai_log(self, cutoff: datetime, apply: bool) -> List[Expired]:
+        path = self.root / "ai_log.db"
+        if not path.exists():
+            return []
+        log = AILog(path)
+        try:
+            count = log.prune(cutoff, dry_run=not apply)
+        finally:
+            log.close()
+        return [Expired(f"{count} AI calls before {cutoff:%Y-%m-%d}", count=count)] if count else []
@@ -3,31 +3,232 @@ Scheduler - Periodic task execution for the assistant.
 
 Handles background monitoring tasks like checking email, project status,
 planning/habit checks, and memory consolidation without requiring user interaction.
+Non-critical tasks are deferred while the machine is busy (see governor.py).
+
+Every background job runs here rather than in its own loop or UI timer:
+the thinking-engine checks below plus the jobs the dashboard registers
+(inbox sync, calendar sync, event reminders, call screening). Each job has
+
+- a schedule: an interval in seconds or a cron expression
+  ("*/15 * * * *", "0 9 * * 1-5", "@hourly"), plus optional jitter
+- an enable flag, overridable per job in config (`jobs: {name: {enabled: false}}`)
+- last-run status (ok/error/skipped, duration, error), persisted so
+  `xswarm dev jobs list` can show it and `xswarm dev jobs run NAME` can
+  trigger a job by hand
+
+A job that runs for config.long_job_seconds or more records a "task"
+event when it finishes (see events.py), which push.py can forward.
+
+Storage: ~/.xswarm/jobs/state.json
 """
 
 import asyncio
+import inspect
+import json
 import logging
+import random
 import time
-from datetime import datetime
-from typing import Dict, Optional, Callable
-from dataclasses import dataclass
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Awaitable, Callable, Dict, List, Optional, Set, Union
+
+from .errors import SchedulerError, UnknownJobError
+from .events import record_event
+from .governor import ResourceGovernor, get_resource_governor
+from .supervisor import get_task_supervisor
 
 logger = logging.getLogger(__name__)
 
+
+# ==============================================================================
+# CRON EXPRESSIONS
+# ==============================================================================
+
+CRON_ALIASES = {
+    "@hourly": "0 * * * *",
+    "@daily": "0 0 * * *",
+    "@midnight": "0 0 * * *",
+    "@weekly": "0 0 * * 0",
+    "@monthly": "0 0 1 * *",
+}
+
+# (min, max) for minute, hour, day of month, month, day of week
+_CRON_FIELDS = [(0, 59), (0, 23), (1, 31), (1, 12), (0, 7)]
+
+
+def _parse_cron_field(text: str, low: int, high: int) -> Set[int]:
+    values: Set[int] = set()
+    for part in text.split(","):
+        step = 1
+        if "/" in part:
+            part, step_text = part.split("/", 1)
+            step = int(step_text)
+            if step < 1:
+                raise SchedulerError(f"Invalid cron step: {step_text}")
+        if part == "*":
+            start, end = low, high
+        elif "-" in part:
+            start, end = (int(x) for x in part.split("-", 1))
+        else:
+            start = int(part)
+            end = high if step > 1 else start
+        if start < low or end > high or start > end:
+            raise SchedulerError(f"Cron value out of range {low}-{high}: {part}")
+        values.update(range(start, end + 1, step))
+    return values
+
+
+class CronSchedule:
+    """Standard 5-field cron expression (minute hour day-of-month month day-of-week)."""
+
+    def __init__(self, expression: str):
+        self.expression = expression
+        fields = CRON_ALIASES.get(expression.strip(), expression).split()
+        if len(fields) != 5:
+            raise SchedulerError(f"Cron expression needs 5 fields: {expression!r}")
+        parsed = [_parse_cron_field(f, lo, hi) for f, (lo, hi) in zip(fields, _CRON_FIELDS)]
+        self.minutes, self.hours, self.days, self.months, weekdays = parsed
+        # Cron weekdays: 0 and 7 are Sunday; Python's weekday(): Monday is 0
+        self.weekdays = {(d - 1) % 7 for d in weekdays}
+        # Like cron: if both day fields are restricted, either one matching is enough
+        self._day_restricted = fields[2] != "*"
+        self._weekday_restricted = fields[4] != "*"
+
+    def _day_matches(self, dt: datetime) -> bool:
+        day_ok = dt.day in self.days
+        weekday_ok = dt.weekday() in self.weekdays
+        if self._day_restricted and self._weekday_restricted:
+            return day_ok or weekday_ok
+        return day_ok and weekday_ok
+
+    def matches(self, dt: datetime) -> bool:
+        return (dt.minute in self.minutes and dt.hour in self.hours
+                and dt.month in self.months and self._day_matches(dt))
+
+    def next_after(self, dt: datetime) -> datetime:
+        """First matching minute strictly after `dt`."""
+        t = dt.replace(second=0, microsecond=0) + timedelta(minutes=1)
+        limit = t + timedelta(days=5 * 366)
+        while t < limit:
+            if t.month not in self.months:
+                t = (t.replace(day=1) + timedelta(days=32)).replace(day=1, hour=0, minute=0)
+            elif not self._day_matches(t):
+                t = (t + timedelta(days=1)).replace(hour=0, minute=0)
+            elif t.hour not in self.hours:
+                t = (t + timedelta(hours=1)).replace(minute=0)
+            elif t.minute not in self.minutes:
+                t += timedelta(minutes=1)
+            else:
+                return t
+        raise SchedulerError(f"Cron expression never matches: {self.expression!r}")
+
+
+# ==============================================================================
+# JOBS
+# ==============================================================================
+
+JobHandler = Callable[[], Union[Awaitable[Any], Any]]
+
+
 @dataclass
 class ScheduledTask:
     name: str
-    interval: int  # seconds
+    interval: int = 0  # seconds (ignored when cron is set)
     last_run: float = 0.0
-    handler: Optional[Callable] = None
+    handler: Optional[JobHandler] = None
     # Time window constraints (optional)
     active_after_hour: Optional[int] = None  # Only run after this hour (0-23)
     active_before_hour: Optional[int] = None  # Only run before this hour (0-23)
+    cron: Optional[str] = None  # Cron expression instead of an interval
+    jitter: float = 0.0  # Up to this many seconds of random delay per run (spreads server load)
+    enabled: bool = True
+    description: str = ""
+    run_at_start: bool = False  # First run right away even if a cron time or the last session's run says otherwise
+    # Last-run status
+    last_status: Optional[str] = None  # "ok", "error", "skipped"
+    last_error: Optional[str] = None
+    last_duration: float = 0.0
+    run_count: int = 0
+    next_run: float = 0.0
+    _cron: Optional[CronSchedule] = field(default=None, repr=False)
+
+    def __post_init__(self):
+        if self.cron:
+            self._cron = CronSchedule(self.cron)
+
+    @property
+    def schedule(self) -> str:
+        """Human-readable schedule, e.g. "every 5m" or "cron 0 9 * * 1-5"."""
+        if self._cron:
+            return f"cron {self.cron}"
+        seconds = self.interval
+        for unit, size in (("h", 3600), ("m", 60)):
+            if seconds >= size and seconds % size == 0:
+                return f"every {seconds // size}{unit}"
+        return f"every {seconds}s"
+
+    def compute_next_run(self, after: float) -> float:
+        if self._cron:
+            base = self._cron.next_after(datetime.fromtimestamp(after)).timestamp()
+        else:
+            base = after + self.interval
+        return base + (random.uniform(0, self.jitter) if self.jitter else 0.0)
+
+
+class JobStateStore:
+    """Persists last-run status per job, for `xswarm dev jobs list`."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "jobs"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict[str, Dict[str, Any]]] = None
+
+    def _path(self) -> Path:
+        return self.storage_dir / "state.json"
+
+    def _load(self) -> Dict[str, Dict[str, Any]]:
+        if self._data is None:
+            try:
+                self._data = json.loads(self._path().read_text(encoding="utf-8")) if self._path().exists() else {}
+            except Exception as e:
+                logger.warning(f"Failed to load job state: {e}")
+                self._data = {}
+        return self._data
+
+    def reload(self) -> None:
+        self._data = None
+
+    def get(self, name: str) -> Dict[str, Any]:
+        return dict(self._load().get(name, {}))
+
+    def all(self) -> Dict[str, Dict[str, Any]]:
+        return {name: dict(state) for name, state in self._load().items()}
+
+    def record(self, task: ScheduledTask) -> None:
+        self._load()[task.name] = {
+            "schedule": task.schedule,
+            "description": task.description,
+            "enabled": task.enabled,
+            "last_run": task.last_run,
+            "last_status": task.last_status,
+            "last_error": task.last_error,
+            "last_duration": round(task.last_duration, 3),
+            "run_count": task.run_count,
+            "next_run": task.next_run,
+        }
+        try:
+            self._path().write_text(json.dumps(self._data, indent=2), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save job state: {e}")
+
 
 class Scheduler:
     """
     Manages periodic background tasks.
-    
+
     Default Schedule:
     - email_check: 5 min
     - project_status: 1 min
@@ -35,13 +236,24 @@ class Scheduler:
     - memory_consolidation: 1 hour
     - document_indexing: 6 hours
     """
-    
-    def __init__(self, thinking_engine):
+
+    # Jobs this frequent only persist their status when it changes (keeps disk writes down)
+    PERSIST_EVERY_RUN_INTERVAL = 60
+
+    def __init__(self, thinking_engine=None, governor: Optional[ResourceGovernor] = None,
+                 store: Optional[JobStateStore] = None, config=None,
+                 clock: Callable[[], float] = time.time):
         self.thinking_engine = thinking_engine
+        self.governor = governor or get_resource_governor()
+        self.store = store
+        self.job_settings: Dict[str, Dict[str, Any]] = dict(getattr(config, "jobs", None) or {})
+        self.long_job_seconds = float(getattr(config, "long_job_seconds", 0) or 0)
+        self._clock = clock
         self.running = False
         self.tasks: Dict[str, ScheduledTask] = {}
+        self._loop_task: Optional[asyncio.Task] = None
         self._setup_default_tasks()
-        
+
     def _setup_default_tasks(self):
         """Initialize default schedule from architecture docs."""
         defaults = {
@@ -53,51 +265,109 @@ class Scheduler:
         }
 
         for name, interval in defaults.items():
-            self.tasks[name] = ScheduledTask(name=name, interval=interval)
+            self.add_task(ScheduledTask(name=name, interval=interval, description="Thinking engine check"))
 
         # Planning-related tasks
         # Streak check: runs every 30 min after 6pm (18:00) to alert on at-risk habits
-        self.tasks['streak_check'] = ScheduledTask(
+        self.add_task(ScheduledTask(
             name='streak_check',
             interval=30 * 60,  # 30 minutes
-            active_after_hour=18  # Only after 6pm
-        )
+            active_after_hour=18,  # Only after 6pm
+            description="Alert on at-risk habit streaks",
+        ))
 
         # Planning state sync: lightweight sync every 15 min during active hours
-        self.tasks['planning_sync'] = ScheduledTask(
+        self.add_task(ScheduledTask(
             name='planning_sync',
             interval=15 * 60,  # 15 minutes
             active_after_hour=7,  # 7am
-            active_before_hour=22  # 10pm
-        )
+            active_before_hour=22,  # 10pm
+            description="Sync planning state",
+        ))
+
+    def add_task(self, task: ScheduledTask) -> ScheduledTask:
+        """Register (or replace) a task, applying config overrides and restoring persisted status."""
+        overrides = self.job_settings.get(task.name, {})
+        if "enabled" in overrides:
+            task.enabled = bool(overrides["enabled"])
+        if "interval" in overrides:
+            task.interval, task.cron, task._cron = int(overrides["interval"]), None, None
+        if overrides.get("cron"):
+            task.cron, task._cron = overrides["cron"], CronSchedule(overrides["cron"])
+        if "jitter" in overrides:
+            task.jitter = float(overrides["jitter"])
+
+        if self.store:
+            saved = self.store.get(task.name)
+            for key in ("last_run", "last_status", "last_error", "last_duration", "run_count"):
+                if saved.get(key) is not None:
+                    setattr(task, key, saved[key])
+
+        self.tasks[task.name] = task
+        if self.running:
+            self._schedule_first_run(task, self._clock())
+        return task
+
+    def add_job(self, name: str, handler: JobHandler, interval: int = 0, cron: Optional[str] = None,
+                jitter: float = 0.0, description: str = "", run_at_start: bool = False,
+                enabled: bool = True) -> ScheduledTask:
+        """Register a job with an interval (seconds) or cron schedule."""
+        if not interval and not cron:
+            raise SchedulerError(f"Job {name} needs an interval or a cron expression")
+        return self.add_task(ScheduledTask(
+            name=name, interval=interval, handler=handler, cron=cron, jitter=jitter,
+            description=description, run_at_start=run_at_start, enabled=enabled,
+        ))
+
+    def _schedule_first_run(self, task: ScheduledTask, now: float) -> None:
+        if task.run_at_start:
+            task.next_run = now
+        elif task.cron:
+            task.next_run = task.compute_next_run(now)
+        else:
+            # Pick up where the last session left off; a job that never ran is due now
+            task.next_run = max(now, task.last_run + task.interval) if task.last_run else now
 
     def start(self):
         """Start the scheduler loop."""
         self.running = True
-        asyncio.create_task(self._loop())
+        now = self._clock()
+        for task in self.tasks.values():
+            self._schedule_first_run(task, now)
+        self._loop_task = get_task_supervisor().spawn("job scheduler", self._loop)
         logger.debug("Scheduler started")
 
     def stop(self):
         """Stop the scheduler loop."""
         self.running = False
+        if self._loop_task:
+            self._loop_task.cancel()
+            self._loop_task = None
 
     async def _loop(self):
         """Main scheduler loop."""
         while self.running:
-            current_time = time.time()
-            current_hour = datetime.now().hour
+            await self.run_due()
+            # Check every second
+            await asyncio.sleep(1)
 
-            for task in self.tasks.values():
-                if current_time - task.last_run >= task.interval:
-                    # Check time window constraints
-                    if not self._is_task_active(task, current_hour):
-                        continue
+    async def run_due(self) -> None:
+        """Run every enabled task whose time has come."""
+        current_time = self._clock()
+        current_hour = datetime.fromtimestamp(current_time).hour
 
-                    await self._execute_task(task)
-                    task.last_run = current_time
+        for task in list(self.tasks.values()):
+            if not task.enabled or current_time < task.next_run:
+                continue
+            # Check time window constraints
+            if not self._is_task_active(task, current_hour):
+                continue
+            # Deferred tasks stay due and are retried on the next tick
+            if not self.governor.allow(task.name):
+                continue
 
-            # Check every second
-            await asyncio.sleep(1)
+            await self._execute_task(task)
+            task.next_run = task.compute_next_run(current_time)
 
     def _is_task_active(self, task: ScheduledTask, current_hour: int) -> bool:
         """Check if task is within its active time window."""
@@ -109,20 +379,100 @@ class Scheduler:
                 return False
         return True
 
+    async def run_now(self, name: str) -> ScheduledTask:
+        """Run a task immediately, ignoring its schedule, enable flag and the governor."""
+        task = self.tasks.get(name)
+        if task is None:
+            raise UnknownJobError(name)
+        await self._execute_task(task)
+        if self.running:
+            task.next_run = task.compute_next_run(self._clock())
+        return task
+
     async def _execute_task(self, task: ScheduledTask):
         """
-        Execute a scheduled task.
+        Execute a scheduled task and record how it went.
 
-        1. Gather context (if specific handler exists)
-        2. Tickle thinking engine with task context
+        Tasks without a handler tickle the thinking engine with the task context.
         """
         logger.debug(f"Running scheduled task: {task.name}")
-        
+        previous_status = task.last_status
+        started = time.monotonic()
+        task.last_run = self._clock()
+        try:
+            if task.handler:
+                result = task.handler()
+                if inspect.isawaitable(result):
+                    await result
+                task.last_status, task.last_error = "ok", None
+            elif self.thinking_engine:
+                await self._tickle_thinking_engine(task)
+                task.last_status, task.last_error = "ok", None
+            else:
+                task.last_status, task.last_error = "skipped", "thinking engine not running"
+        except Exception as e:
+            logger.warning(f"Scheduled task {task.name} failed: {e}")
+            task.last_status, task.last_error = "error", str(e)
+        task.last_duration = time.monotonic() - started
+        task.run_count += 1
+        if task.last_status == "ok" and self.long_job_seconds and task.last_duration >= self.long_job_seconds:
+            record_event("task", f"{task.name} finished after {task.last_duration / 60:.0f} min",
+                         {"name": task.name, "duration": round(task.last_duration, 1)})
+
+        if self.store and (task.cron or task.interval >= self.PERSIST_EVERY_RUN_INTERVAL
+                           or task.last_status != previous_status or task.last_status == "error"):
+            self.store.record(task)
+
+    async def _tickle_thinking_engine(self, task: ScheduledTask):
         context = f"Scheduled task '{task.name}' is due."
-        
+
         # If we had specific handlers to gather context, we'd call them here
         # e.g. if task.handler: context = await task.handler()
-        
+
         # Tickle the thinking engine
-        if self.thinking_engine:
-            await self.thinking_engine.process_scheduled_task(task.name, context)
+        await self.thinking_engine.process_scheduled_task(task.name, context)
+
+    def list_tasks(self) -> List[Dict[str, Any]]:
+        """Status of every registered task, for `xswarm dev jobs list`."""
+        rows = []
+        for task in self.tasks.values():
+            rows.append({
+                "name": task.name,
+                "schedule": task.schedule,
+                "enabled": task.enabled,
+                "description": task.description,
+                "last_run": task.last_run,
+                "last_status": task.last_status,
+                "last_error": task.last_error,
+                "last_duration": task.last_duration,
+                "run_count": task.run_count,
+                "next_run": task.next_run if self.running else None,
+            })
+        return rows
+
+
+def format_job_table(rows: List[Dict[str, Any]], now: Optional[float] = None) -> str:
+    """Plain-text job listing for the CLI."""
+    now = now or time.time()
+
+    def ago(ts: Optional[float]) -> str:
+        if not ts:
+            return "never"
+        seconds = int(now - ts)
+        if seconds < 60:
+            return f"{seconds}s ago"
+        if seconds < 3600:
+            return f"{seconds // 60}m ago"
+        if seconds < 86400:
+            return f"{seconds // 3600}h ago"
+        return datetime.fromtimestamp(ts).strftime("%Y-%m-%d %H:%M")
+
+    lines = [f"{'JOB':<22} {'SCHEDULE':<22} {'LAST RUN':<18} STATUS"]
+    for row in sorted(rows, key=lambda r: r["name"]):
+        status = row.get("last_status") or "-"
+        if row.get("last_error"):
+            status += f" ({row['last_error']})"
+        if not row.get("enabled", True):
+            status += " [disabled]"
+        lines.append(f"{row['name']:<22} {row['schedule']:<22} {ago(row.get('last_run')):<18} {status}")
+    return "\n".join(lines)
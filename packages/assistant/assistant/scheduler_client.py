@@ -0,0 +1,439 @@
+"""
+Scheduler Client - Bulk appointment and reminder calls to the server calendar API.
+
+Uses the batch endpoints so calendar sync and recurring-instance
+materialization cost one request per MAX_BATCH items instead of one each:
+
+- POST /api/calendar/appointments/batch         create_appointments()
+- POST /api/calendar/appointments/batch-delete  delete_appointments()
+- PUT  /api/calendar/reminders/batch            update_reminders()
+
+and single reminders with create_reminder() (POST /api/calendar/reminders).
+
+Participants are invited per appointment (send_invitations()) and their
+RSVPs read back with participants().
+
+Reads (today_schedule(), list_appointments()) are polled, so they're cached
+per query: for config.calendar_cache_seconds a repeat is answered locally,
+after that it's revalidated with If-None-Match/If-Modified-Since and a 304
+keeps the cached copy. The cache is dropped after this client changes
+anything, and on new inbox events (on_event; a text, email or call may have
+booked something on the server).
+
+CalendarSync mirrors the local planner calendar (recurring events expanded
+into instances) to server appointments, so phone/SMS reminders see it, and
+pulls server-side changes back into a CalendarMirror (calendar_mirror.py).
+
+Storage: ~/.xswarm/calendar_sync/state.json (local instance -> server id)
+"""
+
+import hashlib
+import json
+import logging
+import time
+from dataclasses import dataclass
+from datetime import date, datetime, time as clock_time, timedelta
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+from urllib.parse import urlencode
+
+from . import endpoints
+from .api_client import ApiClient, ApiPolicy
+from .dates import get_date_settings
+from .events import INBOX_TYPES
+from .planner import CalendarEvent
+
+logger = logging.getLogger(__name__)
+
+# Items per request; the server accepts up to 500
+MAX_BATCH = 100
+CACHE_SECONDS = 30.0
+
+
+def _chunks(items: List[Any], size: int = MAX_BATCH):
+    for i in range(0, len(items), size):
+        yield items[i:i + size]
+
+
+@dataclass
+class CachedRead:
+    """A calendar read and the validators to revalidate it with."""
+    data: Dict[str, Any]
+    etag: str
+    last_modified: str
+    checked_at: float  # Clock time the server last confirmed it
+
+
+class SchedulerClient:
+    """Async client for the server's calendar endpoints."""
+
+    def __init__(self, server_url: str = "http://localhost:3000", api_token: Optional[str] = None,
+                 timeout: float = 30.0, policy: Optional[ApiPolicy] = None,
+                 cache_seconds: float = CACHE_SECONDS, clock: Callable[[], float] = time.monotonic):
+        self.client = ApiClient(server_url, api_token, timeout, policy)
+        self.cache_seconds = cache_seconds
+        self.clock = clock
+        self._cache: Dict[str, CachedRead] = {}
+        self.cache_stats = {"fresh": 0, "not_modified": 0, "fetched": 0}
+
+    @classmethod
+    def from_config(cls, config=None) -> "SchedulerClient":
+        return cls(
+            getattr(config, "server_url", "http://localhost:3000"),
+            getattr(config, "api_token", None),
+            policy=ApiPolicy.from_config(config),
+            cache_seconds=getattr(config, "calendar_cache_seconds", CACHE_SECONDS),
+        )
+
+    async def close(self):
+        await self.client.close()
+
+    # --------------------------------------------------------------------------
+    # Cached reads
+    # --------------------------------------------------------------------------
+
+    async def today_schedule(self, user_id: str) -> List[Dict[str, Any]]:
+        """Today's server appointments."""
+        data = await self._read(endpoints.CALENDAR_TODAY(), {"user_id": user_id})
+        return data.get("schedule", [])
+
+    async def list_appointments(self, user_id: str, start: Optional[str] = None, end: Optional[str] = None,
+                                tag: Optional[str] = None, status: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Server appointments, optionally between start and end (ISO times) or with a tag."""
+        params = {"user_id": user_id, "start": start, "end": end, "tag": tag, "status": status}
+        data = await self._read(endpoints.APPOINTMENTS(), {k: v for k, v in params.items() if v is not None})
+        return data.get("appointments", [])
+
+    async def _read(self, route: endpoints.Route, params: Dict[str, str]) -> Dict[str, Any]:
+        key = f"{route.path}?{urlencode(sorted(params.items()))}"
+        cached = self._cache.get(key)
+        if cached and self.clock() - cached.checked_at < self.cache_seconds:
+            self.cache_stats["fresh"] += 1
+            return cached.data
+
+        headers = {}
+        if cached and cached.etag:
+            headers["If-None-Match"] = cached.etag
+        if cached and cached.last_modified:
+            headers["If-Modified-Since"] = cached.last_modified
+        response = await self.client.call(route, params=params, headers=headers)
+        if response.status_code == 304 and cached:
+            self.cache_stats["not_modified"] += 1
+            cached.checked_at = self.clock()
+            return cached.data
+
+        self.cache_stats["fetched"] += 1
+        data = response.json()
+        self._cache[key] = CachedRead(
+            data, response.headers.get("ETag", ""), response.headers.get("Last-Modified", ""), self.clock()
+        )
+        return data
+
+    async def changes(self, user_id: str, since: int = 0, limit: Optional[int] = None) -> Dict[str, Any]:
+        """
+        Appointment/reminder changes after journal position `since` (see calendar_mirror.py).
+
+        Returns {"changes", "cursor", "has_more"} and "reset" when `since` isn't from the server's journal.
+        """
+        params = {"user_id": user_id, "since": since}
+        if limit:
+            params["limit"] = limit
+        response = await self.client.call(endpoints.CALENDAR_CHANGES(), params=params)
+        return response.json()
+
+    def invalidate(self) -> None:
+        """Forget cached reads; the next ones go to the server."""
+        self._cache.clear()
+
+    def on_event(self, event) -> None:
+        """Event-history listener: new inbox messages may have changed the server calendar."""
+        if event.type in INBOX_TYPES:
+            self.invalidate()
+
+    # --------------------------------------------------------------------------
+    # Changes
+    # --------------------------------------------------------------------------
+
+    async def create_appointments(self, user_id: str, appointments: List[Dict[str, Any]],
+                                  allow_conflicts: bool = False) -> Dict[str, List]:
+        """
+        Create appointments in batches.
+
+        Returns {"created", "conflicts", "errors"}; conflict/error indexes refer
+        to positions in `appointments`.
+        """
+        result = {"created": [], "conflicts": [], "errors": []}
+        try:
+            for offset in range(0, len(appointments), MAX_BATCH):
+                chunk = appointments[offset:offset + MAX_BATCH]
+                response = await self.client.call(endpoints.APPOINTMENTS_BATCH(), json={
+                    "user_id": user_id,
+                    "appointments": chunk,
+                    "allow_conflicts": allow_conflicts,
+                })
+                data = response.json()
+                result["created"].extend(data.get("created", []))
+                for key in ("conflicts", "errors"):
+                    result[key].extend({**item, "index": item["index"] + offset} for item in data.get(key, []))
+        finally:
+            self.invalidate()  # Earlier batches may have gone through
+        return result
+
+    async def delete_appointments(self, user_id: str, ids: List[str]) -> List[str]:
+        """Delete appointments in batches. Returns the ids the server deleted."""
+        deleted = []
+        try:
+            for chunk in _chunks(ids):
+                response = await self.client.call(endpoints.APPOINTMENTS_BATCH_DELETE(), json={
+                    "user_id": user_id,
+                    "ids": chunk,
+                })
+                deleted.extend(response.json().get("deleted", []))
+        finally:
+            self.invalidate()
+        return deleted
+
+    async def create_reminder(self, user_id: str, title: str, due_time: str, description: str = "",
+                              **fields) -> Dict[str, Any]:
+        """Create a server reminder (sent by SMS/email when due). Returns it."""
+        response = await self.client.call(endpoints.REMINDERS(), json={
+            "user_id": user_id, "title": title, "due_time": due_time, "description": description, **fields,
+        })
+        self.invalidate()
+        return response.json()["reminder"]
+
+    async def update_reminders(self, user_id: str, updates: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+        """
+        Bulk reminder status changes, e.g. [{"id": ..., "completed": True}].
+        Returns the updated reminders.
+        """
+        reminders = []
+        try:
+            for chunk in _chunks(updates):
+                response = await self.client.call(endpoints.REMINDERS_BATCH(), json={
+                    "user_id": user_id,
+                    "updates": chunk,
+                })
+                reminders.extend(response.json().get("reminders", []))
+        finally:
+            self.invalidate()
+        return reminders
+
+    async def participants(self, appointment_id: str) -> List[Dict[str, Any]]:
+        """Participants of a server appointment with their rsvp_status."""
+        response = await self.client.call(endpoints.APPOINTMENT_PARTICIPANTS(appointment_id=appointment_id))
+        return response.json().get("participants", [])
+
+    async def send_invitations(self, appointment_id: str, resend: bool = False) -> Dict[str, Any]:
+        """Invite participants who haven't been invited yet. Returns {"invitations", "participants"}."""
+        response = await self.client.call(
+            endpoints.APPOINTMENT_INVITATIONS(appointment_id=appointment_id), json={"resend": resend}
+        )
+        return response.json()
+
+
+def appointment_payload(event) -> Dict[str, Any]:
+    """Server appointment body for a planner CalendarEvent (or recurring instance)."""
+    home = get_date_settings().home_zone()
+
+    def aware(value: str) -> str:
+        # Planner times are naive home time; send an explicit offset
+        moment = datetime.fromisoformat(value)
+        return (moment.replace(tzinfo=home) if home else moment.astimezone()).isoformat()
+
+    return {
+        "title": event.title,
+        "description": event.description or None,
+        "start_time": aware(event.start_time),
+        "end_time": aware(event.end_time),
+        "location": event.location or None,
+        "latitude": getattr(event, "latitude", None),
+        "longitude": getattr(event, "longitude", None),
+        "participants": list(event.attendees),
+        "tags": list(event.tags),
+    }
+
+
+def instance_key(event) -> str:
+    """Stable key for one occurrence: the source event id plus its start time."""
+    return f"{event._original_id or event.id}@{event.start_time}"
+
+
+def _fingerprint(payload: Dict[str, Any]) -> str:
+    return hashlib.sha1(json.dumps(payload, sort_keys=True).encode()).hexdigest()[:12]
+
+
+class CalendarSync:
+    """One-way sync of planner events (with recurring instances) to server appointments."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "calendar_sync"
+
+    def __init__(self, planner, client: SchedulerClient, user_id: str = "default-user",
+                 storage_dir: Optional[Path] = None, mirror=None):
+        self.planner = planner
+        self.client = client
+        self.user_id = user_id
+        self.mirror = mirror  # CalendarMirror of the server calendar, pulled after each push
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self._data: Optional[Dict] = None
+        self._saves = 0
+
+    def _state_path(self) -> Path:
+        return self.storage_dir / "state.json"
+
+    @property
+    def revision(self) -> tuple:
+        """Changes when remote_events() may answer differently: a push or a mirror pull (appointment_cache.py)."""
+        return self._saves, self.mirror.revision if self.mirror is not None else 0
+
+    def _load(self) -> Dict:
+        if self._data is not None:
+            return self._data
+        path = self._state_path()
+        try:
+            self._data = json.loads(path.read_text(encoding="utf-8")) if path.exists() else {}
+        except Exception as e:
+            logger.warning(f"Failed to load calendar sync state: {e}")
+            self._data = {}
+        self._data.setdefault("appointments", {})
+        return self._data
+
+    def _save(self) -> None:
+        if self._data is None:
+            return
+        self._saves += 1
+        try:
+            self._data["synced_at"] = datetime.now().isoformat()
+            self._state_path().write_text(json.dumps(self._data, indent=2), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save calendar sync state: {e}")
+
+    async def push(self, days: int = 30, today: Optional[date] = None) -> Dict[str, int]:
+        """
+        Mirror the next `days` of the planner calendar to the server.
+
+        Changed or removed occurrences are deleted in one batch, new or changed
+        ones created in another. Returns counts of created/deleted/failed.
+        """
+        today = today or date.today()
+        start, end = today.isoformat(), (today + timedelta(days=days)).isoformat()
+        synced = self._load()["appointments"]
+
+        desired = {}
+        for event in self.planner.get_calendar_events(start, end):
+            payload = appointment_payload(event)
+            desired[instance_key(event)] = (payload, _fingerprint(payload))
+
+        # Only occurrences inside the window are ours to remove; past ones stay on the server
+        in_window = {k for k, v in synced.items() if start <= v["start"][:10] <= end}
+        stale = [k for k in in_window if k not in desired or desired[k][1] != synced[k]["fingerprint"]]
+        new = [k for k in desired if k not in synced or k in stale]
+
+        if stale:
+            await self.client.delete_appointments(self.user_id, [synced[k]["id"] for k in stale])
+            for key in stale:
+                del synced[key]
+            self._save()
+
+        failed = 0
+        if new:
+            # The local calendar is the source of truth, so overlaps are expected
+            result = await self.client.create_appointments(
+                self.user_id, [desired[k][0] for k in new], allow_conflicts=True
+            )
+            rejected = {item["index"] for item in result["errors"] + result["conflicts"]}
+            accepted = [k for i, k in enumerate(new) if i not in rejected]
+            for key, appointment in zip(accepted, result["created"]):
+                synced[key] = {"id": appointment["id"], "start": key.rsplit("@", 1)[1], "fingerprint": desired[key][1]}
+            failed = len(rejected)
+            self._save()
+
+        return {"created": len(new) - failed, "deleted": len(stale), "failed": failed}
+
+    async def pull(self):
+        """Bring the server calendar mirror up to date; returns its PullReport (None without a mirror)."""
+        if self.mirror is None:
+            return None
+        return await self.mirror.pull()
+
+    def remote_events(self, day: Optional[date] = None, tag: Optional[str] = None,
+                      days: int = 1) -> List[CalendarEvent]:
+        """
+        Mirrored server appointments on `day` (and the `days` - 1 after it)
+        that this planner didn't push (made on another device, by text, ...),
+        as planner CalendarEvents in naive home time. Empty without a mirror.
+        """
+        if self.mirror is None:
+            return []
+        home = get_date_settings().home_zone()
+        day = day or date.today()
+
+        def aware(moment: datetime) -> datetime:
+            return moment.replace(tzinfo=home) if home else moment.astimezone()
+
+        def local(value: str) -> str:
+            moment = datetime.fromisoformat(value)
+            if moment.tzinfo is not None:
+                moment = (moment.astimezone(home) if home else moment.astimezone()).replace(tzinfo=None)
+            return moment.isoformat(timespec="minutes")
+
+        ours = {v["id"] for v in self._load()["appointments"].values()}
+        start = aware(datetime.combine(day, clock_time.min))
+        events = []
+        for appointment in self.mirror.appointments(start.isoformat(), (start + timedelta(days=days)).isoformat()):
+            if appointment["id"] in ours or (tag and tag not in (appointment.get("tags") or [])):
+                continue
+            events.append(CalendarEvent(
+                id=f"server:{appointment['id']}",
+                title=appointment.get("title") or "Appointment",
+                start_time=local(appointment["start_time"]),
+                end_time=local(appointment.get("end_time") or appointment["start_time"]),
+                description=appointment.get("description") or "",
+                location=appointment.get("location") or "",
+                attendees=list(appointment.get("participants") or []),
+                tags=list(appointment.get("tags") or []),
+                latitude=appointment.get("latitude"),
+                longitude=appointment.get("longitude"),
+            ))
+        return events
+
+    def server_id(self, event_id: str, now: Optional[datetime] = None) -> Optional[str]:
+        """Server appointment for an event: its next synced occurrence (or the last one if all are past)."""
+        now_iso = (now or datetime.now()).isoformat()
+        occurrences = sorted(
+            (v["start"], v["id"]) for k, v in self._load()["appointments"].items()
+            if k.rsplit("@", 1)[0] == event_id
+        )
+        if not occurrences:
+            return None
+        upcoming = [o for o in occurrences if o[0] >= now_iso]
+        return upcoming[0][1] if upcoming else occurrences[-1][1]
+
+    async def invite(self, event_id: str, resend: bool = False) -> Dict[str, Any]:
+        """
+        Send invitations for an event's attendees (syncing it first if needed).
+
+        Recurring events invite to their next occurrence.
+        """
+        appointment_id = self.server_id(event_id)
+        if appointment_id is None:
+            await self.push()
+            appointment_id = self.server_id(event_id)
+        if appointment_id is None:
+            raise ValueError(f"Event '{event_id}' is not in the synced calendar window")
+        result = await self.client.send_invitations(appointment_id, resend=resend)
+        self._store_rsvps(event_id, result.get("participants", []))
+        return result
+
+    async def refresh_rsvps(self, event_id: str):
+        """Pull RSVP statuses for an event's attendees into the planner. Returns the updated event."""
+        appointment_id = self.server_id(event_id)
+        if appointment_id is None:
+            return self.planner.get_calendar_event(event_id)
+        participants = await self.client.participants(appointment_id)
+        return self._store_rsvps(event_id, participants)
+
+    def _store_rsvps(self, event_id: str, participants: List[Dict[str, Any]]):
+        rsvps = {p["label"]: p.get("rsvp_status", "pending") for p in participants if p.get("label")}
+        return self.planner.set_event_rsvps(event_id, rsvps)
@@ -0,0 +1,269 @@
+"""
+Scheduling Preferences - When the user takes meetings, learned from what they say.
+
+    "I never take meetings before 10"         -> no meetings before 10:00
+    "no calls after 4pm"                       -> no meetings after 16:00
+    "I don't do meetings on Fridays"           -> no meetings on Fridays
+    "no meetings on Friday afternoons"         -> no meetings after 12:00 on Fridays
+    "keep my mornings free"                    -> no meetings before 12:00
+    "no more than 3 meetings a day"            -> at most 3 meetings a day
+
+Each is kept as a Constraint next to the user profile, with what was said
+and when. A newer rule of the same kind for the same days replaces the old
+one ("actually, nothing before 9:30"). Slot suggestions (the
+suggest_meeting_times tool) only offer times inside the working day
+(config.workday_start/workday_end) that no constraint rules out, and
+add_calendar_event warns "⚠ You don't take meetings before 10:00" - a time
+the user asked for is still booked. list_scheduling_preferences reviews
+them and forget_scheduling_preference deletes one ("undo that" brings it
+back).
+
+Storage: ~/.xswarm/user_profile/scheduling.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from dataclasses import asdict, dataclass, field
+from datetime import date, datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Sequence, Tuple
+
+from .capabilities import register_capability
+from .dates import WORKDAY_END, WORKDAY_START, parse_time_expression
+
+logger = logging.getLogger(__name__)
+
+KINDS = ("before", "after", "day", "per_day")
+WEEKDAYS = ("monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday")
+PARTS_OF_DAY = {"morning": ("before", "12:00"), "afternoon": ("after", "12:00"), "evening": ("after", "17:00")}
+NUMBERS = {"one": 1, "two": 2, "three": 3, "four": 4, "five": 5, "six": 6}
+
+_MEETINGS = r"(?:meetings?|calls?|appointments?|anything|events?)"
+_RULE = re.compile(
+    r"^\s*(?:(?:oh|also|and|actually|okay|just\s+so\s+you\s+know|fyi),?\s+)*"
+    r"(?:i\s+(?:never|don'?t|do\s+not|won'?t|no\s+longer)\s+(?:ever\s+)?(?:take|do|have|want|accept|book)\s+"
+    r"|(?:please\s+)?(?:never|don'?t|do\s+not)\s+(?:book|schedule|put|set\s+up)\s+(?:me\s+)?"
+    r"|no\s+)(?:any\s+)?" + _MEETINGS + r"\s+(?P<rule>.+?)[\s.!]*(?:,?\s*please)?[\s.!]*$",
+    re.IGNORECASE,
+)
+_KEEP_FREE = re.compile(
+    r"^\s*(?:please\s+)?keep\s+(?:my\s+)?(?P<rule>.+?)\s+(?:free|clear|open|meeting[- ]free)"
+    r"(?:\s+of\s+" + _MEETINGS + r")?[\s.!]*$",
+    re.IGNORECASE,
+)
+_PER_DAY = re.compile(
+    r"^\s*(?:i\s+(?:want|take|do)\s+)?(?:no\s+more\s+than|at\s+most|max(?:imum)?(?:\s+of)?)\s+"
+    r"(?P<count>\d+|" + "|".join(NUMBERS) + r")\s+" + _MEETINGS + r"\s+(?:a|per|each)\s+day[\s.!]*$",
+    re.IGNORECASE,
+)
+_BEFORE_AFTER = re.compile(r"^(?P<kind>before|after|past)\s+(?P<time>.+)$", re.IGNORECASE)
+
+
+@dataclass
+class Constraint:
+    """One rule about meetings: before/after a time (value "HH:MM"), a whole day, or a daily limit."""
+    kind: str  # before, after, day or per_day
+    value: str = ""  # "10:00" for before/after, the count for per_day
+    days: List[int] = field(default_factory=list)  # Weekdays (0 = Monday) it applies on; empty = every day
+    said: str = ""  # What the user said
+    added_at: str = ""
+    id: str = field(default_factory=lambda: f"pref_{uuid.uuid4().hex[:8]}")
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Constraint":
+        return cls(**{k: v for k, v in data.items() if k in cls.__dataclass_fields__})
+
+    @property
+    def description(self) -> str:
+        """"no meetings before 10:00 on Fridays"."""
+        on = f" on {_days_text(self.days)}" if self.days else ""
+        if self.kind == "day":
+            return f"no meetings{on}"
+        if self.kind == "per_day":
+            return f"at most {self.value} meeting{'' if self.value == '1' else 's'} a day{on}"
+        return f"no meetings {self.kind} {self.value}{on}"
+
+    def applies_on(self, day: date) -> bool:
+        return not self.days or day.weekday() in self.days
+
+    def rules_out(self, start: datetime, end: datetime, booked: int = 0) -> bool:
+        """Whether a meeting from `start` to `end` breaks this rule, with `booked` others that day."""
+        if not self.applies_on(start.date()):
+            return False
+        if self.kind == "day":
+            return True
+        if self.kind == "before":
+            return start.strftime("%H:%M") < self.value
+        if self.kind == "after":
+            return end.date() > start.date() or end.strftime("%H:%M") > self.value
+        return booked >= int(self.value)
+
+
+def _days_text(days: Sequence[int]) -> str:
+    if sorted(days) == [5, 6]:
+        return "weekends"
+    names = [WEEKDAYS[d].capitalize() + "s" for d in sorted(days)]
+    return names[0] if len(names) == 1 else ", ".join(names[:-1]) + " and " + names[-1]
+
+
+def _split_days(rule: str) -> Tuple[List[int], str]:
+    """The weekdays a rule names ("on Fridays", "weekends") and what is left of it."""
+    days: List[int] = []
+    rest = rule
+    if re.search(r"\bweekends?\b", rest, re.IGNORECASE):
+        days.extend([5, 6])
+        rest = re.sub(r"\b(?:on\s+)?weekends?\b", "", rest, flags=re.IGNORECASE)
+    for index, name in enumerate(WEEKDAYS):
+        pattern = rf"\b(?:on\s+)?(?:{name}|{name[:3]})s?\b"
+        if re.search(pattern, rest, re.IGNORECASE):
+            days.append(index)
+            rest = re.sub(pattern, "", rest, flags=re.IGNORECASE)
+    rest = re.sub(r"\b(?:on|and|or|any|the|in)\b", " ", rest, flags=re.IGNORECASE)
+    return sorted(set(days)), " ".join(rest.split())
+
+
+def _rule(rule: str, said: str) -> Optional[Constraint]:
+    days, rest = _split_days(rule)
+    part = rest.lower().rstrip("s")
+    if part in PARTS_OF_DAY:
+        kind, value = PARTS_OF_DAY[part]
+        return Constraint(kind, value, days, said)
+    bound = _BEFORE_AFTER.match(rest)
+    if bound:
+        value = parse_time_expression(bound.group("time"))
+        if value is None:
+            return None
+        return Constraint("after" if bound.group("kind").lower() == "past" else bound.group("kind").lower(),
+                          value, days, said)
+    if days and not rest:
+        return Constraint("day", "", days, said)
+    return None
+
+
+def parse_preference(text: str) -> Optional[Constraint]:
+    """The constraint "I never take meetings before 10" states, or None when `text` doesn't state one."""
+    said = " ".join((text or "").split())
+    per_day = _PER_DAY.match(said)
+    if per_day:
+        count = per_day.group("count").lower()
+        return Constraint("per_day", str(NUMBERS.get(count) or int(count)), [], said)
+    match = _RULE.match(said) or _KEEP_FREE.match(said)
+    if not match:
+        return None
+    return _rule(match.group("rule"), said)
+
+
+class SchedulingPreferences:
+    """The learned constraints, stored beside the user profile, and the working day they narrow."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "user_profile"
+
+    def __init__(self, storage_dir: Optional[Path] = None, workday_start: str = WORKDAY_START,
+                 workday_end: str = WORKDAY_END):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self.workday_start = workday_start
+        self.workday_end = workday_end
+        self._constraints: Optional[List[Constraint]] = None
+
+    @classmethod
+    def from_config(cls, config, storage_dir: Optional[Path] = None) -> "SchedulingPreferences":
+        return cls(storage_dir, getattr(config, "workday_start", WORKDAY_START) or WORKDAY_START,
+                   getattr(config, "workday_end", WORKDAY_END) or WORKDAY_END)
+
+    def _path(self) -> Path:
+        return self.storage_dir / "scheduling.json"
+
+    def constraints(self) -> List[Constraint]:
+        """Every learned constraint, oldest first."""
+        if self._constraints is None:
+            self._constraints = []
+            path = self._path()
+            if path.exists():
+                try:
+                    raw = json.loads(path.read_text(encoding="utf-8"))
+                    self._constraints = [Constraint.from_dict(c) for c in raw.get("constraints", [])
+                                         if c.get("kind") in KINDS]
+                except Exception as e:
+                    logger.warning(f"Failed to load scheduling preferences: {e}")
+        return self._constraints
+
+    def _save(self) -> None:
+        try:
+            self._path().write_text(json.dumps({"constraints": [asdict(c) for c in self.constraints()]},
+                                               indent=2, ensure_ascii=False), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save scheduling preferences: {e}")
+
+    def reload(self) -> None:
+        self._constraints = None
+
+    def add(self, constraint: Constraint) -> List[Constraint]:
+        """Keep `constraint`; returns the ones it replaced (same kind, same days)."""
+        constraint.added_at = constraint.added_at or datetime.now().isoformat(timespec="seconds")
+        replaced = [c for c in self.constraints()
+                    if c.kind == constraint.kind and sorted(c.days) == sorted(constraint.days)]
+        self._constraints = [c for c in self.constraints() if c not in replaced] + [constraint]
+        self._save()
+        return replaced
+
+    def remove(self, match: str) -> List[Constraint]:
+        """Delete the constraints whose id, description or wording contains `match` (all of them for "")."""
+        needle = match.strip().lower()
+        removed = [c for c in self.constraints()
+                   if needle in c.id or needle in c.description.lower() or needle in c.said.lower()]
+        if removed:
+            self._constraints = [c for c in self.constraints() if c not in removed]
+            self._save()
+        return removed
+
+    def restore(self, constraints: List[Dict[str, Any]]) -> int:
+        """Put removed constraints back (undo); returns how many were missing."""
+        known = {c.id for c in self.constraints()}
+        back = [Constraint.from_dict(c) for c in constraints if c.get("id") not in known]
+        if back:
+            self._constraints = sorted(self.constraints() + back, key=lambda c: c.added_at)
+            self._save()
+        return len(back)
+
+    def broken_by(self, start: datetime, end: datetime, booked: int = 0) -> List[Constraint]:
+        """The constraints a meeting from `start` to `end` breaks, with `booked` other meetings that day."""
+        return [c for c in self.constraints() if c.rules_out(start, end, booked)]
+
+    def window(self, day: date, booked: int = 0) -> Optional[Tuple[str, str]]:
+        """The ("HH:MM", "HH:MM") part of the working day meetings may go on `day`; None when there's none."""
+        start, end = self.workday_start, self.workday_end
+        for c in self.constraints():
+            if not c.applies_on(day):
+                continue
+            if c.kind == "day" or (c.kind == "per_day" and booked >= int(c.value)):
+                return None
+            if c.kind == "before":
+                start = max(start, c.value)
+            elif c.kind == "after":
+                end = min(end, c.value)
+        return (start, end) if start < end else None
+
+
+_preferences: Optional[SchedulingPreferences] = None
+
+
+def get_scheduling_preferences() -> SchedulingPreferences:
+    """Get the global scheduling preferences (the default working day until configured)."""
+    global _preferences
+    if _preferences is None:
+        _preferences = SchedulingPreferences()
+    return _preferences
+
+
+def set_scheduling_preferences(preferences: SchedulingPreferences) -> None:
+    """Install the preferences built from the loaded Config (called at startup)."""
+    global _preferences
+    _preferences = preferences
+
+
+register_capability("Scheduling preferences", "Remember when you take meetings and only suggest times that fit",
+                    ["I never take meetings before 10", "no meetings on Fridays", "what are my scheduling rules?"],
+                    category="Planning", keywords=("preference", "rule", "never", "suggest", "slot"))
@@ -0,0 +1,260 @@
+"""
+Scripting - Lua hooks for small customizations that don't need a plugin.
+
+Off until config.scripts_enabled. Every *.lua file in ~/.xswarm/scripts/
+(config.scripts_dir) runs in its own Lua state, via lupa (pip install
+voice-assistant[scripting]), and can define any of these hooks:
+
+    -- Before any handler or the AI sees what the user said (source: "voice" or "chat").
+    -- Return new text, false to ignore it, or nil to leave it.
+    function on_transcript(text, source)
+      return (text:gsub("^hey boss,?%s*", ""))
+    end
+
+    -- Before a notification goes out (channel: "desktop" or "speech").
+    -- Return a table with changes (title, message, priority), false to drop it, or nil.
+    function before_notification(n)
+      if n.title:find("Standup") then return false end
+    end
+
+    -- Every event before it's recorded and pushed (events.py). Same returns.
+    function on_event(e)
+      if e.type == "task_done" then return {summary = "Done: " .. e.summary} end
+    end
+
+Scripts run in order of file name, each seeing what the one before
+returned; false stops the chain. Emergency notifications skip the hooks
+(notifications.py).
+
+The sandbox has string, table, math and utf8, os.time/date/clock, and
+print (to the log) - no io, os.execute, require, load or debug. Each hook
+call gets config.script_timeout_ms and loading a script (its top-level
+code) LOAD_TIMEOUT seconds, both wall-clock time from Python's
+time.monotonic - os.clock() counts the whole process's CPU time, which the
+audio and model threads would eat into. Each script gets MEMORY_LIMIT
+bytes. A script that doesn't load is reported and skipped; one whose hook
+call errors or runs over has that call ignored, and after MAX_FAILURES in
+a row is switched off until restart (shown in the activity feed).
+
+Full plugins with their own tools and intents are skill_plugins.py.
+"""
+
+import logging
+import time
+from dataclasses import replace
+from pathlib import Path
+from typing import Any, Callable, List, Optional
+
+from .events import Event
+from .notifications import Notification, NotificationPriority
+
+logger = logging.getLogger(__name__)
+
+SCRIPTS_DIR = Path.home() / ".xswarm" / "scripts"
+HOOKS = ("on_event", "on_transcript", "before_notification")
+MEMORY_LIMIT = 16 * 1024 * 1024
+LOAD_TIMEOUT = 1.0  # Seconds a script's top-level code may run when it's loaded
+MAX_FAILURES = 3  # Failed calls in a row before a script is switched off
+NOTIFICATION_FIELDS = ("title", "message", "priority")
+
+# Runs before the script, in its Lua state: keeps the only references to the
+# unsafe functions it needs (load with an environment, debug.sethook and
+# getinfo for the time limit) as upvalues, then leaves the script a sandboxed
+# environment. `now` is Python's time.monotonic, for wall-clock deadlines; it
+# is asked every 10000 instructions, so a short hook never calls back into
+# Python. Past the deadline the hook runs on every instruction of the
+# script's own code, so a pcall in the script can't swallow the timeout.
+_BOOTSTRAP = """
+local load, sethook, getinfo, pcall, error = load, debug.sethook, debug.getinfo, pcall, error
+local log, now = ...
+local source = nil  -- The script's chunk name, once loaded
+local function copy(t) local c = {} for k, v in pairs(t) do c[k] = v end return c end
+local env = {
+  assert = assert, error = error, ipairs = ipairs, next = next, pairs = pairs, pcall = pcall, select = select,
+  tonumber = tonumber, tostring = tostring, type = type, unpack = table.unpack,
+  string = copy(string), table = copy(table), math = copy(math), utf8 = copy(utf8),
+  os = {time = os.time, date = os.date, clock = os.clock},
+  print = function(...) local parts = {} for i = 1, select("#", ...) do parts[i] = tostring(select(i, ...)) end
+                        log(table.concat(parts, " ")) end,
+}
+env._G = env
+local function stop()  -- As a hook, level 2 is the function running
+  local info = getinfo(2, "S")
+  if info and info.source == source then error("timed out", 2) end
+end
+local function guarded(limit, fn, ...)
+  local deadline = now() + limit
+  sethook(function() if now() > deadline then sethook(stop, "", 1) end end, "", 10000)
+  local ok, result = pcall(fn, ...)
+  sethook()
+  if not ok then error(result, 0) end
+  return result
+end
+local function run(code, name, limit)
+  local chunk, err = load(code, "=" .. name, "t", env)
+  if not chunk then error(err, 0) end
+  source = "=" .. name
+  guarded(limit, chunk)
+end
+local function call(name, limit, ...)
+  local fn = env[name]
+  if type(fn) ~= "function" then return nil end
+  return guarded(limit, fn, ...)
+end
+local function has(name) return type(env[name]) == "function" end
+return run, call, has
+"""
+
+
+class ScriptError(Exception):
+    """A script can't be loaded, or a hook call failed or ran out of time."""
+
+
+class LuaEngine:
+    """One script's Lua state, sandboxed (see _BOOTSTRAP)."""
+
+    def __init__(self, name: str, source: str, load_timeout: float = LOAD_TIMEOUT):
+        try:
+            import lupa
+        except ImportError:
+            raise ScriptError("Scripts need the lupa package: pip install voice-assistant[scripting]")
+        self._lupa = lupa
+        self._lua = lupa.LuaRuntime(register_eval=False, register_builtins=False, max_memory=MEMORY_LIMIT)
+        script_logger = logging.getLogger(f"script.{name}")
+        try:
+            self._run, self._call, self._has = self._lua.execute(_BOOTSTRAP, lambda text: script_logger.info(text),
+                                                                 time.monotonic)
+            self._run(source, name, load_timeout)
+        except (lupa.LuaError, MemoryError) as e:
+            raise ScriptError(f"{name}: {e}")
+
+    def has(self, hook: str) -> bool:
+        return bool(self._has(hook))
+
+    def call(self, hook: str, timeout: float, *args) -> Any:
+        try:
+            return self._from_lua(self._call(hook, timeout, *[self._to_lua(a) for a in args]))
+        except (self._lupa.LuaError, MemoryError) as e:
+            raise ScriptError(str(e))
+
+    def _to_lua(self, value):
+        if isinstance(value, dict):
+            return self._lua.table_from({k: self._to_lua(v) for k, v in value.items()})
+        if isinstance(value, (list, tuple)):
+            return self._lua.table_from([self._to_lua(v) for v in value])
+        return value
+
+    def _from_lua(self, value):
+        if self._lupa.lua_type(value) == "table":
+            return {k: self._from_lua(v) for k, v in value.items()}
+        return value
+
+
+class Script:
+    """A loaded script and how its recent calls went."""
+
+    def __init__(self, name: str, engine: Any):
+        self.name = name
+        self.engine = engine
+        self.hooks = {hook for hook in HOOKS if engine.has(hook)}
+        self.failures = 0
+        self.disabled = False
+
+
+class ScriptHooks:
+    """The loaded scripts, run at each hook point in turn."""
+
+    def __init__(self, scripts: Optional[List[Script]] = None, timeout_ms: int = 100,
+                 on_disabled: Optional[Callable[[str], None]] = None):
+        self.scripts = scripts or []
+        self.timeout = timeout_ms / 1000
+        self.on_disabled = on_disabled  # Told when a script is switched off
+        self.problems: List[str] = []  # Scripts that couldn't be loaded
+
+    @classmethod
+    def from_config(cls, config, engine_factory: Callable[[str, str], Any] = LuaEngine) -> "ScriptHooks":
+        hooks = cls(timeout_ms=getattr(config, "script_timeout_ms", 100))
+        if not getattr(config, "scripts_enabled", False):
+            return hooks
+        directory = Path(getattr(config, "scripts_dir", None) or SCRIPTS_DIR).expanduser()
+        for path in sorted(directory.glob("*.lua")) if directory.is_dir() else []:
+            try:
+                hooks.scripts.append(Script(path.stem, engine_factory(path.stem, path.read_text(encoding="utf-8"))))
+            except (OSError, ScriptError) as e:
+                hooks.problems.append(f"{path.name}: {e}")
+        for problem in hooks.problems:
+            logger.warning(f"Script not loaded: {problem}")
+        return hooks
+
+    def _run(self, hook: str, value: Any, *extra) -> Any:
+        """
+        Pass `value` through every script defining `hook`: each returns a
+        replacement (for a table, just the fields to change), False to drop
+        it (None is returned) or nil to leave it.
+        """
+        for script in self.scripts:
+            if script.disabled or hook not in script.hooks:
+                continue
+            try:
+                result = script.engine.call(hook, self.timeout, value, *extra)
+            except ScriptError as e:
+                self._failed(script, hook, e)
+                continue
+            script.failures = 0
+            if result is False:
+                return None
+            if isinstance(value, dict) and isinstance(result, dict):
+                value = {**value, **result}
+            elif isinstance(result, type(value)):
+                value = result
+            elif result is not None:
+                logger.warning(f"Script {script.name} {hook} returned {type(result).__name__}; ignored")
+        return value
+
+    def _failed(self, script: Script, hook: str, error: Exception) -> None:
+        script.failures += 1
+        logger.warning(f"Script {script.name} {hook} failed: {error}")
+        if script.failures >= MAX_FAILURES:
+            script.disabled = True
+            if self.on_disabled:
+                self.on_disabled(f"Script {script.name} switched off after {MAX_FAILURES} failures: {error}")
+
+    def on_transcript(self, text: str, source: str = "voice") -> Optional[str]:
+        """What the user said as the scripts left it, or None if one said to ignore it."""
+        return self._run("on_transcript", text, source)
+
+    def before_notification(self, notification: Notification) -> Optional[Notification]:
+        """A notification as the scripts left it, or None to drop it (add_notification_hook)."""
+        result = self._run("before_notification", {"channel": notification.channel, "title": notification.title,
+                                                   "message": notification.message,
+                                                   "priority": notification.priority, "tags": list(notification.tags)})
+        if result is None:
+            return None
+        changes = {k: str(result[k]) for k in NOTIFICATION_FIELDS}
+        if changes["priority"] not in [p.value for p in NotificationPriority]:
+            changes["priority"] = notification.priority
+        return replace(notification, **changes)
+
+    def on_event(self, event: Event) -> Optional[Event]:
+        """An event as the scripts left it, or None to drop it (add_event_hook); its type can't change."""
+        result = self._run("on_event", {"type": event.type, "summary": event.summary, "data": event.data})
+        if result is None:
+            return None
+        return replace(event, summary=str(result["summary"]),
+                       data=result["data"] if isinstance(result["data"], dict) else event.data)
+
+
+_hooks: Optional[ScriptHooks] = None
+
+
+def get_script_hooks() -> ScriptHooks:
+    """Get the script hooks (none until startup loads config.scripts_dir)."""
+    global _hooks
+    if _hooks is None:
+        _hooks = ScriptHooks()
+    return _hooks
+
+
+def set_script_hooks(hooks: Optional[ScriptHooks]) -> None:
+    global _hooks
+    _hooks = hooks
@@ -0,0 +1,80 @@
+#!/usr/bin/env python3
+"""
+Benchmark mic frame fan-out: per-consumer copies vs shared AudioFrames.
+
+Simulates 20ms mic frames at 48kHz fanned out to the four consumers the
+voice pipeline feeds (Moshi encoder, visualizer, user transcriber, wake
+word) and reports CPU time per frame and as a share of the real-time budget.
+
+Usage:
+    python packages/assistant/assistant/scripts/bench_audio_fanout.py [--seconds 60] [--rate 48000]
+"""
+
+import argparse
+import sys
+import time
+from pathlib import Path
+
+import numpy as np
+
+sys.path.insert(0, str(Path(__file__).resolve().parents[2]))
+
+from assistant.audio_frame import AudioFrame, pcm16_bytes  # noqa: E402
+
+
+def copy_per_consumer(indata: np.ndarray) -> None:
+    """What the pipeline did before: every consumer copies and converts on its own."""
+    audio = np.ascontiguousarray(indata[:, 0], dtype=np.float32)
+    np.sqrt(np.mean(audio ** 2))                          # silence check
+    encoder = audio.astype(np.float32)                    # MoshiClient.feed_audio
+    np.sqrt(np.mean(audio.copy() ** 2))                   # visualizer amplitude
+    (audio.copy() * 32767).astype(np.int16).tobytes()     # user transcriber
+    (audio.copy() * 32767).astype(np.int16).tobytes()     # wake word
+    del encoder
+
+
+def shared_frame(indata: np.ndarray) -> None:
+    """Now: one copy out of the device buffer, conversions cached on the frame."""
+    frame = AudioFrame.from_buffer(indata[:, 0])
+    frame.rms()                                           # silence check
+    np.asarray(frame.samples, dtype=np.float32)           # MoshiClient.feed_audio (no copy)
+    frame.rms()                                           # visualizer amplitude (cached)
+    pcm16_bytes(frame)                                    # user transcriber
+    pcm16_bytes(frame)                                    # wake word (cached)
+
+
+def run(fn, frames: np.ndarray) -> float:
+    start = time.process_time()
+    for indata in frames:
+        fn(indata)
+    return time.process_time() - start
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[1])
+    parser.add_argument("--seconds", type=float, default=60.0, help="Seconds of audio to simulate")
+    parser.add_argument("--rate", type=int, default=48000, help="Sample rate")
+    parser.add_argument("--frame-ms", type=float, default=20.0, help="Frame length in ms")
+    args = parser.parse_args()
+
+    frame_len = int(args.rate * args.frame_ms / 1000)
+    count = int(args.seconds * 1000 / args.frame_ms)
+    rng = np.random.default_rng(0)
+    # Reuse a small pool of device buffers, like PortAudio does
+    pool = [rng.uniform(-0.5, 0.5, (frame_len, 1)).astype(np.float32) for _ in range(4)]
+    frames = [pool[i % len(pool)] for i in range(count)]
+
+    run(shared_frame, frames[:100])  # Warm up
+    before = run(copy_per_consumer, frames)
+    after = run(shared_frame, frames)
+
+    print(f"{count} frames of {frame_len} samples ({args.rate} Hz, {args.frame_ms:g} ms), 4 consumers")
+    for label, cpu in (("copy per consumer", before), ("shared AudioFrame", after)):
+        per_frame_us = cpu / count * 1e6
+        print(f"  {label:<18} {per_frame_us:8.1f} µs/frame  {100 * cpu / args.seconds:6.3f}% of one core")
+    if after > 0:
+        print(f"  CPU reduction: {100 * (1 - after / before):.0f}% ({before / after:.1f}x)")
+
+
+if __name__ == "__main__":
+    main()
@@ -17,6 +17,7 @@ from assistant.phone import MediaStreamsServer, TwilioVoiceBridge
 from assistant.personas.manager import PersonaManager
 from assistant.memory import MemoryManager
 from assistant.config import Config
+from assistant.ws_framing import compression
 from assistant.voice.moshi_mlx import MoshiBridge
 
 # Global Moshi instance (loaded once at startup, reused for all calls)
@@ -97,14 +98,21 @@ async def main():
     print(f"   $ ngrok http {args.port}")
     print("   Then update Twilio webhook to: wss://YOUR-NGROK-URL")
     print()
+    print("   Handshakes must be signed (see webhook_signing.py): set TWILIO_AUTH_TOKEN and")
+    print("   VOICE_TUNNEL_URL=wss://YOUR-NGROK-URL, or every connection gets a 401")
+    print()
     print("=" * 60)
     print()
 
     # Create server
+    config = Config()
     server = MediaStreamsServer(
         host=args.host,
         port=args.port,
         bridge_factory=create_bridge,
+        on_violation=print,
+        compression=compression(config),
+        config=config,
     )
 
     # Start server
@@ -0,0 +1,238 @@
+"""
+Self-update - New releases of the xswarm binary, signed, swapped in place.
+
+    xswarm self-update              # check, download, verify, swap, restart the daemon
+    xswarm self-update --check      # only say whether there's a newer release
+    xswarm self-update --rollback   # back to the binary before the last update
+
+The server (GET /api/releases/latest, see routes/releases.js) hands out
+the release manifest of a channel:
+
+    {"version": "0.33.0", "channel": "stable", "notes": "...",
+     "assets": {"linux-x86_64": {"sha256": "...", "size": 48211200}, ...},
+     "signature": "<Ed25519 signature of the rest, base64>"}
+
+Nothing in it is trusted until the signature checks out against one of
+RELEASE_KEYS - the signature covers the manifest without "signature" as
+canonical JSON (sign_manifest() is what cuts a release) - and the binary
+(GET /api/releases/{version}/{platform}) must match its SHA-256. Then:
+
+1. it is written next to the current binary (xswarm.new) and made executable
+2. it must answer `--version` with the release's version
+3. one rename swaps it in; the binary it replaces is kept as xswarm.previous
+4. the daemon (systemd unit xswarm, user or system) is restarted, and if it
+   isn't running RESTART_WAIT seconds later, the previous binary goes back
+
+--rollback swaps xswarm.previous back in the same way (twice undoes it).
+Installs that aren't the binary (pip, a checkout) are told the version and
+the command to update with instead.
+
+The dashboard checks every UPDATE_CHECK_HOURS (update_check job, off with
+config.update_check) and shows "⬆ 0.33.0" by the version in the status bar.
+"""
+
+import base64
+import hashlib
+import json
+import os
+import platform
+import shutil
+import subprocess
+import sys
+import time
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable, Dict, Optional, Sequence
+
+from . import __version__
+from .endpoints import RELEASE_ASSET, RELEASE_LATEST
+from .updater import UpdateInfo
+
+# Ed25519 public keys releases are signed with (raw, base64); a new key is added before the old one is retired
+RELEASE_KEYS = ("eR88iaOFGOvf+K0Z3ChR1SpbFniSzlQ+Bw0CvIfj714=",)
+SERVICE = "xswarm"  # The systemd unit deployment/distribution/systemd installs
+RESTART_WAIT = 10.0  # Seconds the restarted daemon gets to come up before the update is rolled back
+DOWNLOAD_TIMEOUT = 300.0
+UPDATE_CHECK_HOURS = 12
+PIP_COMMAND = "pip install --upgrade voice-assistant"
+
+_SYSTEMS = {"darwin": "macos", "win32": "windows"}
+_MACHINES = {"amd64": "x86_64", "x64": "x86_64", "arm64": "aarch64"}
+
+
+class UpdateError(Exception):
+    """An update (or rollback) that couldn't go ahead; the installed binary is left as it was."""
+
+
+def platform_name() -> str:
+    """This machine's asset name in a manifest, e.g. "linux-x86_64", "macos-aarch64"."""
+    system = _SYSTEMS.get(sys.platform, platform.system().lower())
+    machine = platform.machine().lower()
+    return f"{system}-{_MACHINES.get(machine, machine)}"
+
+
+def installed_binary() -> Optional[Path]:
+    """The xswarm binary running this, or None when it runs from Python (pip, a checkout)."""
+    return Path(sys.executable).resolve() if getattr(sys, "frozen", False) else None
+
+
+def canonical(manifest: Dict[str, Any]) -> bytes:
+    """What the signature covers: the manifest without its signature, as sorted, compact JSON."""
+    body = {key: value for key, value in manifest.items() if key != "signature"}
+    return json.dumps(body, sort_keys=True, separators=(",", ":"), ensure_ascii=False).encode("utf-8")
+
+
+def sign_manifest(manifest: Dict[str, Any], private_key: bytes) -> Dict[str, Any]:
+    """The manifest with its signature, for whoever cuts a release (raw 32-byte Ed25519 private key)."""
+    from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+
+    signature = Ed25519PrivateKey.from_private_bytes(private_key).sign(canonical(manifest))
+    return {**manifest, "signature": base64.b64encode(signature).decode("ascii")}
+
+
+@dataclass
+class Release:
+    version: str
+    channel: str = "stable"
+    notes: str = ""
+    assets: Dict[str, Dict[str, Any]] = field(default_factory=dict)
+
+    def newer_than(self, version: str) -> bool:
+        return UpdateInfo._compare_versions(self.version, version) > 0
+
+    def asset(self, name: str) -> Dict[str, Any]:
+        found = self.assets.get(name)
+        if not found or not found.get("sha256"):
+            raise UpdateError(f"xswarm {self.version} has no build for {name}")
+        return found
+
+
+def verify_manifest(manifest: Dict[str, Any], keys: Sequence[str] = RELEASE_KEYS) -> Release:
+    """The release a manifest describes, if one of `keys` signed it; UpdateError otherwise."""
+    from cryptography.exceptions import InvalidSignature
+    from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PublicKey
+
+    try:
+        signature = base64.b64decode(manifest.get("signature") or "", validate=True)
+    except ValueError:
+        signature = b""
+    for key in keys:
+        try:
+            Ed25519PublicKey.from_public_bytes(base64.b64decode(key)).verify(signature, canonical(manifest))
+        except (InvalidSignature, ValueError):
+            continue
+        if not manifest.get("version") or not isinstance(manifest.get("assets", {}), dict):
+            raise UpdateError("The release manifest is signed but incomplete")
+        return Release(str(manifest["version"]), manifest.get("channel", "stable"), manifest.get("notes", ""),
+                       manifest.get("assets", {}))
+    raise UpdateError("The release manifest isn't signed by a release key - not installing it")
+
+
+def _sibling(binary: Path, suffix: str) -> Path:
+    return binary.with_name(binary.name + suffix)
+
+
+class SelfUpdater:
+    """
+    Checks for, installs and rolls back releases of `binary` (installed_binary()
+    by default; None for a pip install, which is only told about new versions).
+    """
+
+    def __init__(self, client, channel: str = "stable", binary: Optional[Path] = None,
+                 keys: Sequence[str] = RELEASE_KEYS, platform_id: Optional[str] = None,
+                 run: Callable[..., Any] = subprocess.run, sleep: Callable[[float], None] = time.sleep):
+        self.client = client  # api_client.ApiClient
+        self.channel = channel
+        self.binary = binary
+        self.keys = keys
+        self.platform = platform_id or platform_name()
+        self.run = run
+        self.sleep = sleep
+
+    @classmethod
+    def from_config(cls, config=None, client=None) -> "SelfUpdater":
+        from .api_client import ApiClient, ApiPolicy
+
+        client = client or ApiClient(getattr(config, "server_url", "http://localhost:3000"),
+                                     getattr(config, "api_token", None), policy=ApiPolicy.from_config(config))
+        return cls(client, getattr(config, "update_channel", "stable"), installed_binary())
+
+    async def check(self) -> Release:
+        """The channel's latest release, verified."""
+        response = await self.client.call(RELEASE_LATEST(), params={"channel": self.channel})
+        return verify_manifest(response.json(), self.keys)
+
+    async def download(self, release: Release) -> Path:
+        """The release's binary, checked against the manifest, as an executable xswarm.new by the current one."""
+        asset = release.asset(self.platform)
+        response = await self.client.call(RELEASE_ASSET(version=release.version, platform=self.platform),
+                                          timeout=DOWNLOAD_TIMEOUT)
+        data = response.content
+        if hashlib.sha256(data).hexdigest() != asset["sha256"].lower():
+            raise UpdateError(f"The xswarm {release.version} download doesn't match its signed checksum")
+        new = _sibling(self.binary, ".new")
+        new.write_bytes(data)
+        new.chmod(0o755)
+        return new
+
+    def smoke_test(self, new: Path, version: str) -> None:
+        """The downloaded binary must start and say it's `version`."""
+        try:
+            result = self.run([str(new), "--version"], capture_output=True, text=True, timeout=30)
+            said = (result.stdout or "").split() if result.returncode == 0 else []
+        except (OSError, subprocess.SubprocessError):
+            said = []
+        if version not in said:
+            new.unlink(missing_ok=True)
+            raise UpdateError(f"The xswarm {version} download doesn't run on this machine - not installing it")
+
+    def swap(self, new: Path) -> None:
+        """`new` in place of the binary in one rename, the binary it replaces kept as xswarm.previous."""
+        shutil.copy2(self.binary, _sibling(self.binary, ".previous"))
+        os.replace(new, self.binary)
+
+    def restart_daemon(self) -> Optional[bool]:
+        """Restart the systemd unit (user, then system): whether it's running after; None without one."""
+        for scope in (["--user"], []):
+            try:
+                if self.run(["systemctl", *scope, "cat", SERVICE], capture_output=True).returncode != 0:
+                    continue
+                self.run(["systemctl", *scope, "restart", SERVICE], capture_output=True)
+                self.sleep(RESTART_WAIT)
+                return self.run(["systemctl", *scope, "is-active", "--quiet", SERVICE]).returncode == 0
+            except OSError:
+                return None  # No systemctl
+        return None
+
+    async def update(self, say: Callable[[str], None] = print) -> str:
+        """Install the latest release if it's newer; what happened, or UpdateError."""
+        release = await self.check()
+        if not release.newer_than(__version__):
+            return f"xswarm {__version__} is up to date"
+        if self.binary is None:
+            return f"xswarm {release.version} is out (you have {__version__}) - update this install with: {PIP_COMMAND}"
+        say(f"Downloading xswarm {release.version} for {self.platform}...")
+        new = await self.download(release)
+        self.smoke_test(new, release.version)
+        self.swap(new)
+        say(f"Installed xswarm {release.version} (the previous version is kept for --rollback)")
+        running = self.restart_daemon()
+        if running is False:
+            self.rollback()
+            self.restart_daemon()
+            raise UpdateError(f"xswarm {release.version} didn't start - rolled back to {__version__}")
+        if running is None:
+            return f"Updated to xswarm {release.version} - restart xswarm to use it"
+        return f"Updated to xswarm {release.version} and restarted the {SERVICE} service"
+
+    def rollback(self) -> None:
+        """Swap xswarm.previous back in, keeping the binary it replaces as the new xswarm.previous."""
+        if self.binary is None:
+            raise UpdateError("Only the xswarm binary rolls back - this install downgrades with pip")
+        previous = _sibling(self.binary, ".previous")
+        if not previous.exists():
+            raise UpdateError("There's no previous version to roll back to")
+        keep = _sibling(self.binary, ".rollback")
+        shutil.copy2(self.binary, keep)
+        os.replace(previous, self.binary)
+        os.replace(keep, previous)
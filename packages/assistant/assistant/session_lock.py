@@ -0,0 +1,139 @@
+"""
+Session Lock - Hide the dashboard from people looking over your shoulder.
+
+Locking covers the whole dashboard (chat, inbox, schedule, memory) with a
+lock screen until the user proves it's them:
+
+- pin (default): the same PIN as PIN-level confirmations
+  (config.confirmation_pin_hash, set with set_confirmation_pin)
+- account: the login password of the user running xswarm, checked with
+  PAM (needs the python-pam package)
+
+It locks with the lock key (ctrl+o, see keymap.py) or after
+config.lock_idle_minutes without a key press, click or utterance. Wrong
+answers are limited: after MAX_ATTEMPTS the lock screen waits LOCKOUT
+seconds, doubling each time, before trying again.
+"""
+
+import getpass
+import logging
+import time
+from dataclasses import dataclass
+from typing import Callable, Optional
+
+from .confirmation import hash_pin, spoken_pin
+
+logger = logging.getLogger(__name__)
+
+MAX_ATTEMPTS = 5
+LOCKOUT = 30.0  # Seconds, doubled after each further failed round
+
+
+def account_password_check() -> Optional[Callable[[str], bool]]:
+    """A check of this user's login password via PAM, or None when python-pam isn't installed."""
+    try:
+        import pam
+    except ImportError:
+        return None
+
+    def check(password: str) -> bool:
+        try:
+            return bool(pam.pam().authenticate(getpass.getuser(), password))
+        except Exception as e:
+            logger.warning(f"Account password check failed: {e}")
+            return False
+    return check
+
+
+@dataclass
+class UnlockResult:
+    ok: bool
+    message: str = ""
+
+
+class SessionLock:
+    """Whether the dashboard is locked, idle tracking, and checking the PIN/password to unlock."""
+
+    def __init__(self, config=None, clock: Callable[[], float] = time.monotonic,
+                 check_password: Optional[Callable[[str], bool]] = None):
+        self.config = config
+        self.clock = clock
+        # Only looked up for account unlocking, so PAM is never loaded otherwise
+        self.check_password = check_password
+        if check_password is None and self.method == "account":
+            self.check_password = account_password_check()
+        self.locked = False
+        self.last_active = clock()
+        self.attempts = 0
+        self.lockouts = 0
+        self.retry_at: Optional[float] = None
+
+    @property
+    def method(self) -> str:
+        return getattr(self.config, "lock_unlock_with", "pin") or "pin"
+
+    @property
+    def idle_timeout(self) -> Optional[float]:
+        """Seconds without activity before locking, None when idle locking is off."""
+        minutes = getattr(self.config, "lock_idle_minutes", 0) or 0
+        return minutes * 60 if minutes > 0 else None
+
+    @property
+    def prompt(self) -> str:
+        return "Account password" if self.method == "account" else "PIN"
+
+    def unavailable(self) -> Optional[str]:
+        """Why the dashboard can't be locked (nothing to unlock it with), or None."""
+        if self.method == "account":
+            if self.check_password is None:
+                return "Locking with the account password needs python-pam (pip install python-pam)"
+            return None
+        if not getattr(self.config, "confirmation_pin_hash", None):
+            return "Set a PIN first (\"set my PIN to ...\") to lock the screen"
+        return None
+
+    def touch(self) -> None:
+        """Someone pressed a key, clicked or spoke."""
+        self.last_active = self.clock()
+
+    def idle_expired(self) -> bool:
+        timeout = self.idle_timeout
+        return (not self.locked and timeout is not None and self.clock() - self.last_active >= timeout
+                and self.unavailable() is None)
+
+    def lock(self) -> bool:
+        if self.unavailable() is not None:
+            return False
+        self.locked = True
+        return True
+
+    def unlock(self, secret: str) -> UnlockResult:
+        if not self.locked:
+            return UnlockResult(True)
+        now = self.clock()
+        if self.retry_at is not None and now < self.retry_at:
+            return UnlockResult(False, f"Too many tries - wait {int(self.retry_at - now) + 1}s")
+
+        if self._matches(secret):
+            self.locked = False
+            self.attempts = self.lockouts = 0
+            self.retry_at = None
+            self.touch()
+            return UnlockResult(True)
+
+        self.attempts += 1
+        if self.attempts >= MAX_ATTEMPTS:
+            self.attempts = 0
+            self.retry_at = now + LOCKOUT * 2 ** self.lockouts
+            self.lockouts += 1
+            logger.warning("Too many wrong unlock attempts")
+            return UnlockResult(False, f"Too many tries - wait {int(self.retry_at - now)}s")
+        return UnlockResult(False, f"That {self.prompt.lower()} didn't match")
+
+    def _matches(self, secret: str) -> bool:
+        if not secret:
+            return False
+        if self.method == "account":
+            return bool(self.check_password(secret))
+        pin = spoken_pin(secret)
+        return bool(pin) and hash_pin(pin) == getattr(self.config, "confirmation_pin_hash", None)
@@ -0,0 +1,398 @@
+"""
+Share Notes - "Send Alice the notes": a conversation summarized and emailed to a contact.
+
+Requests name who gets the notes and, optionally, which conversation:
+
+    send Alice the notes                         this conversation
+    email Bob a summary of our last conversation the one before this
+    share the notes from the budget call with Alice Chen
+                                                 the latest one mentioning "budget"
+
+Flow:
+1. The recipient is looked up in config.contacts ({"Alice Chen": {"email":
+   "alice@example.com"}}); emergency contacts count too, but have no email
+   address. Two matches ("Alice") are asked about: "Which Alice...?"
+2. The conversation comes from the chat history (memory.PersistentChatHistory):
+   the current session, the last one, or the latest of the recent sessions
+   mentioning every word of the topic.
+3. The AI turns the transcript into notes - a short summary, then decisions
+   and action items - leaving out small talk. Without an AI the notes are
+   the transcript's substantial lines. Phone numbers, addresses and
+   credentials are masked first (redaction.py).
+4. The notes are read back; the user says "send it", asks for a change
+   ("make it shorter", "leave out the numbers") or cancels, as with reply
+   drafts (reply_drafts.classify_reply_command).
+5. Nothing is sent until the user confirms. The email goes out through the
+   persona mailer (mailer.PersonaMailer), worded by config.share_templates.
+
+Templates are Jinja with "subject" and "body" keys; variables: summary,
+recipient (first name), contact (full name), user, persona, topic and date.
+Ones that don't render are logged and the built-in wording is used.
+"""
+
+import logging
+import re
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import Any, Dict, List, Optional, Tuple
+
+from jinja2 import StrictUndefined
+from jinja2.sandbox import SandboxedEnvironment
+
+from .capabilities import register_capability
+from .redaction import redact
+from .reply_drafts import classify_reply_command
+
+logger = logging.getLogger(__name__)
+
+_NOT_NAME = r"(?!(?:the|a|an|my|our|some|notes|summary|recap|minutes|please)\b)"
+_NAME = rf"{_NOT_NAME}[a-z][\w'.-]*(?:\s+{_NOT_NAME}[a-z][\w'.-]*)?"
+_NOTES = r"(?:(?:the|a|my|our|some)\s+)?(?:notes|summary|recap|minutes)"
+_TOPIC = r"(?:\s+(?:from|of|on|about|for)\s+(?P<topic>.+?))?"
+_SHARE_INTENTS = (
+    # send Alice the notes (from the standup)
+    re.compile(rf"^\s*(?:please\s+|can\s+you\s+|could\s+you\s+)?(?:send|email|e-mail|mail|forward)\s+"
+               rf"(?P<name>{_NAME})\s+{_NOTES}{_TOPIC}(?:\s+please)?\s*[.!?]*\s*$", re.IGNORECASE),
+    # share the notes (from the standup) with Alice
+    re.compile(rf"^\s*(?:please\s+|can\s+you\s+|could\s+you\s+)?(?:send|email|e-mail|mail|forward|share)\s+"
+               rf"{_NOTES}{_TOPIC}\s+(?:to|with)\s+(?P<name>{_NAME})(?:\s+please)?\s*[.!?]*\s*$", re.IGNORECASE),
+)
+_CURRENT = re.compile(r"^(?:this|that|the|our|my)?\s*(?:conversation|chat|talk|discussion|session)?\s*"
+                      r"(?:today|just now)?$|^(?:this|that|today|just now)$", re.IGNORECASE)
+_LAST = re.compile(r"^(?:the|our|my)\s+(?:last|previous)\s+(?:conversation|chat|talk|discussion|session)$",
+                   re.IGNORECASE)
+TOPIC_FILLER = {"the", "our", "my", "a", "an", "this", "that", "today", "conversation", "chat", "talk", "discussion",
+                "session", "meeting", "call", "about", "on", "with", "we", "had"}
+
+SEARCH_SESSIONS = 20  # Recent sessions looked through for a topic
+MAX_TRANSCRIPT_CHARS = 12000  # The end of a longer conversation is what's summarized
+MAX_PLAIN_LINES = 20  # Lines in notes made without an AI
+MAX_REVISIONS = 10
+
+DEFAULT_TEMPLATES = {
+    "subject": "Notes{% if topic %}: {{ topic }}{% endif %} ({{ date }})",
+    "body": "Hi {{ recipient }},\n\nHere are the notes{% if topic %} from {{ topic }}{% endif %}.\n\n"
+            "{{ summary }}{% if user %}\n\n{{ user }}{% endif %}",
+}
+
+_env = SandboxedEnvironment(undefined=StrictUndefined, autoescape=False)
+
+
+@dataclass(frozen=True)
+class ShareRequest:
+    """Who gets the notes, and which conversation ("" for this one)."""
+    recipient: str
+    topic: str = ""
+
+    @property
+    def which(self) -> str:
+        """"current", "last" or "topic"."""
+        if not self.topic or _CURRENT.match(self.topic):
+            return "current"
+        return "last" if _LAST.match(self.topic) else "topic"
+
+    @property
+    def keywords(self) -> List[str]:
+        return [word for word in re.findall(r"[\w'-]+", self.topic.lower()) if word not in TOPIC_FILLER]
+
+
+def parse_share_request(text: str) -> Optional[ShareRequest]:
+    """A ShareRequest for "send Alice the notes" and the like, or None."""
+    for pattern in _SHARE_INTENTS:
+        match = pattern.match(text or "")
+        if match:
+            topic = (match.group("topic") or "").strip()
+            return ShareRequest(match.group("name").strip(), topic)
+    return None
+
+
+@dataclass(frozen=True)
+class Contact:
+    name: str
+    email: str = ""
+    phone: str = ""
+
+
+def load_contacts(config) -> List[Contact]:
+    """config.contacts ({"name": {"email", "phone"}}), then emergency contacts not named there."""
+    contacts = []
+    for name, details in (getattr(config, "contacts", None) or {}).items():
+        details = details if isinstance(details, dict) else {"email": str(details)}
+        contacts.append(Contact(str(name), str(details.get("email") or ""), str(details.get("phone") or "")))
+    named = {contact.name.casefold() for contact in contacts}
+    for raw in getattr(config, "emergency_contacts", None) or []:
+        name = str(raw.get("name") or "")
+        if name and name.casefold() not in named:
+            contacts.append(Contact(name, phone=str(raw.get("phone") or "")))
+    return contacts
+
+
+def match_contacts(spoken: str, contacts: List[Contact]) -> List[Contact]:
+    """Contacts a spoken name means: the full name if one has it, else every one whose names include it."""
+    spoken_words = spoken.casefold().split()
+    exact = [contact for contact in contacts if contact.name.casefold().split() == spoken_words]
+    if exact:
+        return exact
+    return [contact for contact in contacts if spoken_words
+            and all(word in contact.name.casefold().split() for word in spoken_words)]
+
+
+def select_conversation(history, request: ShareRequest):
+    """The ChatSession a request means, or None: this one, the last one, or the latest mentioning the topic."""
+    current = history.current_session if history.current_session and history.current_session.messages else None
+    saved = []
+    for info in history.get_recent_sessions(limit=SEARCH_SESSIONS):
+        if current and info.get("session_id") == current.session_id:
+            continue
+        session = history.get_session(info.get("session_id", ""))
+        if session and session.messages:
+            saved.append(session)
+    if request.which == "current":
+        return current or (saved[0] if saved else None)
+    if request.which == "last":
+        return saved[0] if saved else None
+    keywords = request.keywords
+    for session in ([current] if current else []) + saved:
+        text = " ".join(entry.content.lower() for entry in session.messages)
+        if keywords and all(word in text for word in keywords):
+            return session
+    return None
+
+
+def transcript(session, user_name: Optional[str] = None, persona_name: Optional[str] = None) -> List[Tuple[str, str]]:
+    """(speaker, text) for what the user and the assistant said, redacted and tidied."""
+    speakers = {"user": user_name or "Me", "assistant": persona_name or session.persona or "Assistant"}
+    lines = []
+    for entry in session.messages:
+        text = " ".join((entry.content or "").split())
+        if entry.role in speakers and text:
+            lines.append((speakers[entry.role], redact(text)))
+    return lines
+
+
+def plain_notes(lines: List[Tuple[str, str]]) -> str:
+    """Notes without an AI: the substantial lines ("ok", "thanks" left out), as a list."""
+    kept = [f"- {speaker}: {text}" for speaker, text in lines if len(text.split()) > 2]
+    return "\n".join(kept[:MAX_PLAIN_LINES])
+
+
+class ShareTemplates:
+    """config.share_templates over DEFAULT_TEMPLATES, checked when loaded."""
+
+    def __init__(self, templates: Optional[Dict[str, str]] = None):
+        self.sources = dict(DEFAULT_TEMPLATES)
+        for key, source in (templates or {}).items():
+            problem = self.validate(key, source)
+            if problem:
+                logger.warning(f"Share template {key} ignored: {problem}")
+                continue
+            self.sources[key] = source
+
+    @classmethod
+    def from_config(cls, config=None) -> "ShareTemplates":
+        return cls(getattr(config, "share_templates", None) or {})
+
+    @staticmethod
+    def validate(key: str, source: Any) -> Optional[str]:
+        if key not in DEFAULT_TEMPLATES:
+            return f"unknown template '{key}' (use {', '.join(DEFAULT_TEMPLATES)})"
+        if not isinstance(source, str):
+            return "not text"
+        try:
+            rendered = _env.from_string(source).render(sample_context())
+        except Exception as e:
+            return str(e)
+        return None if rendered.strip() else "renders as nothing"
+
+    def render(self, key: str, context: Dict[str, Any]) -> str:
+        try:
+            text = _env.from_string(self.sources[key]).render(context).strip()
+            if text:
+                return text
+        except Exception as e:
+            logger.debug(f"Share template {key} failed, using the built-in: {e}")
+        return _env.from_string(DEFAULT_TEMPLATES[key]).render(context).strip()
+
+
+def sample_context() -> Dict[str, Any]:
+    return {"summary": "- Budget approved", "recipient": "Alice", "contact": "Alice Chen", "user": "Sam",
+            "persona": "JARVIS", "topic": "the budget call", "date": "October 16"}
+
+
+@dataclass
+class ShareSession:
+    """State for sharing one conversation's notes."""
+    request: ShareRequest
+    state: str = "choosing_contact"  # choosing_contact, awaiting_confirmation, sent, cancelled
+    candidates: List[Contact] = field(default_factory=list)
+    contact: Optional[Contact] = None
+    conversation: Any = None  # memory.ChatSession
+    notes: str = ""
+    revisions: List[str] = field(default_factory=list)
+
+    @property
+    def is_active(self) -> bool:
+        return self.state in ("choosing_contact", "awaiting_confirmation")
+
+    def set_notes(self, text: str) -> None:
+        if self.notes:
+            self.revisions.append(self.notes)
+        self.notes = text.strip()
+        self.state = "awaiting_confirmation"
+
+
+class ShareNotesWorkflow:
+    """
+    Drives a ShareSession from user utterances.
+
+    ai_client is the voice.AIClient (None or unavailable: plain notes);
+    history is the PersistentChatHistory and mailer the PersonaMailer.
+    """
+
+    def __init__(self, ai_client, persona_manager, history, mailer, config=None):
+        self.ai = ai_client
+        self.persona_manager = persona_manager
+        self.history = history
+        self.mailer = mailer
+        self.contacts = load_contacts(config)
+        self.templates = ShareTemplates.from_config(config)
+        self.user_name = getattr(config, "user_name", None)
+        self.session: Optional[ShareSession] = None
+
+    @property
+    def is_active(self) -> bool:
+        return self.session is not None and self.session.is_active
+
+    def _persona(self):
+        return self.persona_manager.get_current_persona() if self.persona_manager else None
+
+    async def start(self, request: ShareRequest) -> str:
+        """Begin sharing: find the contact and the conversation, and read the notes back."""
+        self.session = session = ShareSession(request)
+        matches = match_contacts(request.recipient, self.contacts)
+        if not matches:
+            session.state = "cancelled"
+            return f"I don't have a contact called {request.recipient}. Add them to contacts in your config."
+        session.candidates = [contact for contact in matches if contact.email]
+        if not session.candidates:
+            session.state = "cancelled"
+            return f"I don't have an email address for {matches[0].name}."
+        if len(session.candidates) > 1:
+            names = [contact.name for contact in session.candidates]
+            return f"Which {request.recipient}: {', '.join(names[:-1])} or {names[-1]}?"
+        return await self._choose(session.candidates[0])
+
+    async def handle(self, utterance: str) -> str:
+        """Handle a user utterance while a share is pending."""
+        if not self.is_active:
+            return ""
+        session = self.session
+        command = classify_reply_command(utterance)
+        if command == "cancel":
+            session.state = "cancelled"
+            return "Okay, I won't send them."
+        if session.state == "choosing_contact":
+            picked = match_contacts(utterance.strip(" .!?"), session.candidates)
+            if len(picked) != 1:
+                return f"Which one? {', '.join(c.name for c in session.candidates)} - or say cancel."
+            return await self._choose(picked[0])
+        if command == "repeat":
+            return self._read_back("The notes say")
+        if command == "confirm":
+            return await self._send()
+        if len(session.revisions) >= MAX_REVISIONS:
+            return "That's a lot of revisions. Say 'send it' to send these notes or 'cancel' to discard them."
+        if not self._ai_available():
+            return "I can't change the notes without the AI. " + self._prompt()
+        notes = await self._summarize(instructions=utterance)
+        if not notes:
+            return "Sorry, I couldn't revise the notes. " + self._prompt()
+        session.set_notes(notes)
+        return self._read_back("Here are the revised notes")
+
+    async def _choose(self, contact: Contact) -> str:
+        session = self.session
+        session.contact = contact
+        session.conversation = select_conversation(self.history, session.request) if self.history else None
+        if session.conversation is None:
+            session.state = "cancelled"
+            topic = session.request.topic if session.request.which == "topic" else ""
+            if topic:
+                return f"I couldn't find a conversation about {topic}."
+            return "There's no conversation to share yet."
+        notes = await self._summarize()
+        if not notes:
+            session.state = "cancelled"
+            return "There's nothing in that conversation worth sending."
+        session.set_notes(notes)
+        return self._read_back(f"Here are the notes for {contact.name} ({contact.email})")
+
+    def _prompt(self) -> str:
+        return "Shall I send it, change it, or cancel?"
+
+    def _read_back(self, lead: str) -> str:
+        return f"{lead}:\n{self.session.notes}\n\n{self._prompt()}"
+
+    def _ai_available(self) -> bool:
+        return self.ai is not None and self.ai.is_available()
+
+    def _lines(self) -> List[Tuple[str, str]]:
+        persona = self._persona()
+        return transcript(self.session.conversation, self.user_name, persona.name if persona else None)
+
+    def build_messages(self, instructions: Optional[str] = None) -> List[dict]:
+        """Chat messages asking the AI for the notes (or a revision of them)."""
+        session = self.session
+        text = "\n".join(f"{speaker}: {line}" for speaker, line in self._lines())[-MAX_TRANSCRIPT_CHARS:]
+        sender = self.user_name or "the user"
+        system = (
+            f"You turn conversation transcripts into notes {sender} will email to {session.contact.name}. "
+            "Start with a two or three sentence summary, then list decisions and action items as '- ' lines. "
+            "Leave out small talk, greetings and anything unrelated to the topic. Plain text, no headings. "
+            "Return only the notes."
+        )
+        parts = [f"Transcript:\n{text}"]
+        if session.request.which == "topic":
+            parts.append(f"The notes are about: {session.request.topic}")
+        if session.notes and instructions:
+            parts.append(f"Current notes:\n{session.notes}")
+            parts.append(f"Revise the notes: {instructions}")
+        return [{"role": "system", "content": system}, {"role": "user", "content": "\n\n".join(parts)}]
+
+    async def _summarize(self, instructions: Optional[str] = None) -> Optional[str]:
+        if self._ai_available():
+            try:
+                return (await self.ai.chat(self.build_messages(instructions), max_tokens=700)).strip()
+            except Exception as e:
+                logger.debug(f"Share notes via AI client failed: {e}")
+                if instructions:
+                    return None
+        return plain_notes(self._lines())
+
+    def context(self) -> Dict[str, Any]:
+        """The templates' variables."""
+        session, persona, now = self.session, self._persona(), datetime.now()
+        return {"summary": session.notes, "recipient": session.contact.name.split()[0],
+                "contact": session.contact.name, "user": self.user_name or "",
+                "persona": persona.name if persona else "",
+                "topic": session.request.topic if session.request.which == "topic" else "",
+                "date": f"{now:%B} {now.day}"}
+
+    async def _send(self) -> str:
+        session, persona = self.session, self._persona()
+        if self.mailer is None or persona is None:
+            return "I can't send email right now. Say 'cancel' to discard the notes."
+        context = self.context()
+        result = await self.mailer.send_email(to_email=session.contact.email,
+                                              subject=self.templates.render("subject", context),
+                                              content=self.templates.render("body", context), persona=persona)
+        if not result.get("success"):
+            return f"I couldn't send them: {result.get('error') or 'the mail service refused'}. " + self._prompt()
+        session.state = "sent"
+        return f"Sent the notes to {session.contact.name} by email."
+
+
+register_capability("Share notes", "Turn a conversation into notes and email them to one of your contacts, "
+                    "after you hear them and say send", ["send Alice the notes",
+                                                         "email Bob a summary of our last conversation"],
+                    requires=("contacts",), category="Messages", keywords=("summary", "recap", "minutes", "email"))
@@ -0,0 +1,605 @@
+"""
+Skill Plugins - Third-party skills as sandboxed WebAssembly modules.
+
+Each plugin is a folder in ~/.xswarm/plugins/ with a skill.yaml manifest and
+a .wasm (or .wat) module, run with wasmtime (pip install voice-assistant[plugins]):
+
+    name: coin_flip
+    version: 0.1.0
+    description: Flip a coin
+    module: coin_flip.wat
+    intents:                 # Utterances it answers (regexes, named groups become args; see _pattern)
+      - name: flip
+        patterns: ["flip a coin", "heads or tails"]
+        examples: ["flip a coin"]
+    tools:                   # Tools the AI can call (type: string, integer, number, boolean)
+      - name: flip_coin
+        description: Flip a coin and say how it landed
+        parameters: {times: {type: integer, default: 1}}
+    config:                  # Its settings, given in config.plugin_settings["coin_flip"]
+      language: {type: string, default: en, description: Language to answer in}
+    permissions:             # Asked for; nothing is granted until the user does
+      network: [api.example.com]
+      files: [~/Notes]
+
+A plugin does nothing until `xswarm dev plugins enable NAME`, and gets
+network or file access only with --grant network / --grant files.
+~/.xswarm/plugins/state.json keeps the exact hosts and folders the manifest
+named when the user granted them; if an updated manifest asks for any
+others, the plugin isn't loaded until it's enabled (and approved) again.
+The module has no WASI: no clock, files, sockets or environment of its own.
+All it can reach are the host functions below, which check the grants, so
+even with them it only reaches the approved hosts and folders. Each call
+gets FUEL instructions and MEMORY_LIMIT bytes.
+
+The ABI (JSON in, JSON out, UTF-8 in the module's memory):
+- exports: memory, alloc(len) -> ptr, handle(ptr, len) -> (ptr << 32 | len)
+- request: {"kind": "intent" | "tool", "name", "args", "config", "text"}
+- response: {"say": "..."} or {"error": "..."}
+- imports from "xswarm": log(ptr, len), random() -> i32, http_get(ptr, len)
+  and read_file(ptr, len), the last two returning a packed pointer to
+  {"status", "body"} / {"content"} or {"error"}
+
+Enabled plugins register their intents (answered before the AI, like
+timers), their tools (tools.py registry, so household scopes and
+confirmations apply) and a capability (capabilities.py) so help lists them.
+A tool name that's already taken is refused.
+
+examples/plugins/coin_flip is a sample: copy it into ~/.xswarm/plugins/.
+
+assistant/plugins/ is the older in-process Python plugin API; it's trusted
+code, these are not.
+"""
+
+import inspect
+import json
+import logging
+import re
+import secrets
+import threading
+from dataclasses import dataclass, field
+from pathlib import Path
+from re import _parser as sre_parse
+from typing import Any, Callable, Dict, List, Optional, Tuple
+from urllib.parse import urlparse
+
+import yaml
+
+from .capabilities import CATEGORIES, register_capability
+
+logger = logging.getLogger(__name__)
+
+PLUGINS_DIR = Path.home() / ".xswarm" / "plugins"
+MANIFEST = "skill.yaml"
+STATE_FILE = "state.json"
+PERMISSIONS = ("network", "files")
+FUEL = 50_000_000  # Instructions per call
+MEMORY_LIMIT = 64 * 1024 * 1024
+MAX_BODY = 256 * 1024  # Bytes of an HTTP response or file handed to a plugin
+HTTP_TIMEOUT = 10.0
+MAX_PATTERN = 200  # Characters in one intent pattern
+
+_TYPES: Dict[str, type] = {"string": str, "integer": int, "number": float, "boolean": bool}
+_NAME = re.compile(r"[a-z][a-z0-9_]*")
+_REPEATS = (sre_parse.MAX_REPEAT, sre_parse.MIN_REPEAT, sre_parse.POSSESSIVE_REPEAT)
+
+
+class PluginError(Exception):
+    """A plugin can't be loaded, or a call to it failed."""
+
+
+@dataclass
+class Intent:
+    name: str
+    patterns: List[re.Pattern]
+    examples: List[str]
+
+    def match(self, text: str) -> Optional[Dict[str, str]]:
+        for pattern in self.patterns:
+            found = pattern.match(text or "")
+            if found:
+                return {k: v for k, v in found.groupdict().items() if v is not None}
+        return None
+
+
+@dataclass
+class Setting:
+    type: str
+    default: Any = None
+    description: str = ""
+
+
+@dataclass
+class ToolSpec:
+    name: str
+    description: str
+    parameters: Dict[str, Setting]
+
+
+@dataclass
+class Manifest:
+    name: str
+    version: str
+    description: str
+    module: Path
+    intents: List[Intent] = field(default_factory=list)
+    tools: List[ToolSpec] = field(default_factory=list)
+    config: Dict[str, Setting] = field(default_factory=dict)
+    permissions: Dict[str, List[str]] = field(default_factory=dict)
+    category: str = "Everyday"
+
+    @classmethod
+    def load(cls, directory: Path) -> "Manifest":
+        """Read and check a plugin folder's skill.yaml."""
+        try:
+            raw = yaml.safe_load((directory / MANIFEST).read_text(encoding="utf-8")) or {}
+        except (OSError, yaml.YAMLError) as e:
+            raise PluginError(f"{directory.name}: can't read {MANIFEST}: {e}")
+        if not isinstance(raw, dict):
+            raise PluginError(f"{directory.name}: {MANIFEST} must be a mapping of fields")
+        name = str(raw.get("name") or "")
+        if not _NAME.fullmatch(name):
+            raise PluginError(f"{directory.name}: name must be lowercase letters, digits and _")
+        module = directory / str(raw.get("module") or "")
+        if not raw.get("module") or not module.is_file() or module.suffix not in (".wasm", ".wat"):
+            raise PluginError(f"{name}: module must name a .wasm or .wat file in the plugin folder")
+        if directory.resolve() not in module.resolve().parents:
+            raise PluginError(f"{name}: module must be inside the plugin folder")
+        permissions = _mapping(name, "permissions", raw.get("permissions"))
+        unknown = set(permissions) - set(PERMISSIONS)
+        if unknown:
+            raise PluginError(f"{name}: unknown permission '{sorted(unknown)[0]}' (use {', '.join(PERMISSIONS)})")
+        category = raw.get("category", "Everyday")
+        if category not in CATEGORIES:
+            raise PluginError(f"{name}: category must be one of {', '.join(CATEGORIES)}")
+        intents = [_intent(name, i) for i in _list(name, "intents", raw.get("intents"))]
+        tools = [ToolSpec(_checked_name(name, t.get("name")), str(t.get("description") or ""),
+                          _settings(name, t.get("parameters")))
+                 for t in (_mapping(name, "each tool", t) for t in _list(name, "tools", raw.get("tools")))]
+        return cls(name, str(raw.get("version") or "0.0.0"), str(raw.get("description") or ""), module,
+                   intents, tools, _settings(name, raw.get("config")),
+                   {k: [str(v) for v in _list(name, f"permissions.{k}", v)] for k, v in permissions.items()},
+                   category)
+
+    def settings(self, values: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+        """The plugin's settings: config.plugin_settings values over the defaults, type-checked."""
+        result = {key: setting.default for key, setting in self.config.items()}
+        for key, value in (values or {}).items():
+            if key not in self.config:
+                raise PluginError(f"{self.name}: unknown setting '{key}'")
+            result[key] = _coerce(self.name, key, self.config[key].type, value)
+        return result
+
+
+def _mapping(plugin: str, what: str, value) -> Dict[str, Any]:
+    if value is None:
+        return {}
+    if not isinstance(value, dict):
+        raise PluginError(f"{plugin}: {what} must be a mapping")
+    return value
+
+
+def _list(plugin: str, what: str, value) -> List[Any]:
+    if value is None:
+        return []
+    if not isinstance(value, list):
+        raise PluginError(f"{plugin}: {what} must be a list")
+    return value
+
+
+def _intent(plugin: str, raw) -> Intent:
+    raw = _mapping(plugin, "each intent", raw)
+    name = _checked_name(plugin, raw.get("name"))
+    patterns = _list(plugin, f"bad intent '{name}': patterns", raw.get("patterns"))
+    if not patterns:
+        raise PluginError(f"{plugin}: bad intent '{name}': it needs patterns")
+    return Intent(name, [_pattern(plugin, name, p) for p in patterns],
+                  [str(e) for e in _list(plugin, f"bad intent '{name}': examples", raw.get("examples"))])
+
+
+def _pattern(plugin: str, intent: str, pattern) -> re.Pattern:
+    """
+    An intent pattern compiled to match a whole utterance. Each user utterance
+    is tried against every pattern, so ones that can backtrack for ages are
+    refused: longer than MAX_PATTERN, or a repeat inside a repeat like (\w+\s?)+.
+    """
+    if not isinstance(pattern, str) or not pattern.strip():
+        raise PluginError(f"{plugin}: bad intent '{intent}': patterns must be non-empty strings")
+    if len(pattern) > MAX_PATTERN:
+        raise PluginError(f"{plugin}: bad intent '{intent}': a pattern is longer than {MAX_PATTERN} characters")
+    try:
+        parsed = sre_parse.parse(pattern)
+    except re.error as e:
+        raise PluginError(f"{plugin}: bad intent '{intent}': {e}")
+    if _nested_repeat(parsed):
+        raise PluginError(f"{plugin}: bad intent '{intent}': {pattern!r} repeats a repeat, which can take "
+                          f"forever to fail")
+    return re.compile(rf"^\W*(?:{pattern})\W*$", re.IGNORECASE)
+
+
+def _nested_repeat(items, repeated: bool = False) -> bool:
+    """Whether a parsed pattern has a repeat (more than once) inside another."""
+    for op, av in items:
+        if op in _REPEATS:
+            low, high, sub = av
+            if high > 1 and repeated:
+                return True
+            if _nested_repeat(sub, repeated or high > 1):
+                return True
+        elif op == sre_parse.SUBPATTERN and _nested_repeat(av[-1], repeated):
+            return True
+        elif op == sre_parse.BRANCH and any(_nested_repeat(branch, repeated) for branch in av[1]):
+            return True
+        elif op in (sre_parse.ASSERT, sre_parse.ASSERT_NOT) and _nested_repeat(av[1], repeated):
+            return True
+    return False
+
+
+def _checked_name(plugin: str, name) -> str:
+    if not isinstance(name, str) or not _NAME.fullmatch(name):
+        raise PluginError(f"{plugin}: intent and tool names must be lowercase letters, digits and _")
+    return name
+
+
+def _settings(plugin: str, raw) -> Dict[str, Setting]:
+    settings = {}
+    for key, spec in _mapping(plugin, "config and tool parameters", raw).items():
+        spec = spec if isinstance(spec, dict) else {"type": spec}
+        kind = spec.get("type", "string")
+        if not isinstance(kind, str) or kind not in _TYPES:
+            raise PluginError(f"{plugin}: '{key}' has unknown type '{kind}' (use {', '.join(_TYPES)})")
+        settings[key] = Setting(kind, spec.get("default"), str(spec.get("description") or ""))
+    return settings
+
+
+def _coerce(plugin: str, key: str, kind: str, value):
+    expected = _TYPES[kind]
+    if expected is float and isinstance(value, int) and not isinstance(value, bool):
+        return float(value)
+    if not isinstance(value, expected) or (expected is int and isinstance(value, bool)):
+        raise PluginError(f"{plugin}: '{key}' should be {kind}, got {value!r}")
+    return value
+
+
+@dataclass
+class Grants:
+    """What a plugin may reach: hosts and folders from its manifest that the user granted."""
+    hosts: List[str] = field(default_factory=list)
+    folders: List[Path] = field(default_factory=list)
+
+    @classmethod
+    def asked(cls, manifest: Manifest, permissions) -> "Grants":
+        """The hosts and folders the manifest asks for under these permissions."""
+        hosts = manifest.permissions.get("network", []) if "network" in permissions else []
+        folders = manifest.permissions.get("files", []) if "files" in permissions else []
+        return cls([h.lower() for h in hosts], [Path(f).expanduser().resolve() for f in folders])
+
+    @classmethod
+    def for_plugin(cls, manifest: Manifest, approved: Dict[str, List[str]]) -> "Grants":
+        """What the user approved (as PluginState keeps it); PluginError if the manifest now asks for more."""
+        asked = cls.asked(manifest, approved)
+        unapproved = asked.beyond(approved)
+        if unapproved:
+            raise PluginError(f"{manifest.name} now asks for {', '.join(unapproved)}, which wasn't approved: "
+                              f"enable it again to grant that")
+        return asked
+
+    def approval(self, permissions) -> Dict[str, List[str]]:
+        """These hosts and folders by permission, for PluginState."""
+        approved = {"network": list(self.hosts), "files": [str(f) for f in self.folders]}
+        return {permission: approved[permission] for permission in PERMISSIONS if permission in permissions}
+
+    def beyond(self, approved: Dict[str, List[str]]) -> List[str]:
+        """Hosts and folders here that `approved` doesn't list."""
+        return ([h for h in self.hosts if h not in approved.get("network", [])]
+                + [str(f) for f in self.folders if str(f) not in approved.get("files", [])])
+
+    def allows_url(self, url: str) -> bool:
+        parsed = urlparse(url)
+        return parsed.scheme in ("http", "https") and (parsed.hostname or "").lower() in self.hosts
+
+    def allows_path(self, path: Path) -> bool:
+        path = path.expanduser().resolve()
+        return any(path == folder or folder in path.parents for folder in self.folders)
+
+
+def _http_get(url: str) -> Tuple[int, str]:
+    import httpx
+    response = httpx.get(url, timeout=HTTP_TIMEOUT, follow_redirects=False)  # A redirect could leave the host
+    return response.status_code, response.text[:MAX_BODY]
+
+
+class SkillHost:
+    """The host functions a plugin can call; every one goes through its grants."""
+
+    def __init__(self, name: str, grants: Grants, fetch: Callable[[str], Tuple[int, str]] = _http_get):
+        self.name = name
+        self.grants = grants
+        self.fetch = fetch
+        self.logger = logging.getLogger(f"plugin.{name}")
+
+    def log(self, message: str) -> None:
+        self.logger.info(message[:1000])
+
+    def http_get(self, url: str) -> Dict[str, Any]:
+        if not self.grants.allows_url(url):
+            return {"error": f"network access to {urlparse(url).hostname or url} not granted"}
+        try:
+            status, body = self.fetch(url)
+            return {"status": status, "body": body}
+        except Exception as e:
+            return {"error": str(e)}
+
+    def read_file(self, path: str) -> Dict[str, Any]:
+        if not self.grants.allows_path(Path(path)):
+            return {"error": f"file access to {path} not granted"}
+        try:
+            with open(Path(path).expanduser(), encoding="utf-8", errors="replace") as f:
+                return {"content": f.read(MAX_BODY)}
+        except OSError as e:
+            return {"error": str(e)}
+
+
+class WasmRuntime:
+    """One plugin's module in a wasmtime store, instantiated on first use."""
+
+    def __init__(self, module_path: Path, host: SkillHost):
+        self.module_path = module_path
+        self.host = host
+        self._store = None
+        self._exports = None
+
+    def _instantiate(self) -> None:
+        try:
+            import wasmtime
+        except ImportError:
+            raise PluginError("Plugins need the wasmtime package: pip install voice-assistant[plugins]")
+        config = wasmtime.Config()
+        config.consume_fuel = True
+        engine = wasmtime.Engine(config)
+        store = wasmtime.Store(engine)
+        store.set_limits(memory_size=MEMORY_LIMIT)
+        linker = wasmtime.Linker(engine)
+        i32, i64 = wasmtime.ValType.i32(), wasmtime.ValType.i64()
+
+        def text_in(caller, ptr: int, length: int) -> str:
+            return bytes(caller["memory"].read(caller, ptr, ptr + length)).decode("utf-8", "replace")
+
+        def json_out(caller, value: Dict[str, Any]) -> int:
+            data = json.dumps(value).encode("utf-8")
+            ptr = caller["alloc"](caller, len(data))
+            caller["memory"].write(caller, data, ptr)
+            return (ptr << 32) | len(data)
+
+        linker.define_func("xswarm", "log", wasmtime.FuncType([i32, i32], []),
+                           lambda caller, p, n: self.host.log(text_in(caller, p, n)), access_caller=True)
+        linker.define_func("xswarm", "random", wasmtime.FuncType([], [i32]), lambda: secrets.randbits(31))
+        linker.define_func("xswarm", "http_get", wasmtime.FuncType([i32, i32], [i64]),
+                           lambda caller, p, n: json_out(caller, self.host.http_get(text_in(caller, p, n))),
+                           access_caller=True)
+        linker.define_func("xswarm", "read_file", wasmtime.FuncType([i32, i32], [i64]),
+                           lambda caller, p, n: json_out(caller, self.host.read_file(text_in(caller, p, n))),
+                           access_caller=True)
+        try:
+            if self.module_path.suffix == ".wat":
+                module = wasmtime.Module(engine, self.module_path.read_text(encoding="utf-8"))
+            else:
+                module = wasmtime.Module.from_file(engine, str(self.module_path))
+            self._exports = linker.instantiate(store, module).exports(store)
+        except Exception as e:
+            raise PluginError(f"{self.host.name}: can't load {self.module_path.name}: {e}")
+        self._store = store
+
+    def call(self, request: Dict[str, Any]) -> Dict[str, Any]:
+        if self._store is None:
+            self._instantiate()
+        store, exports = self._store, self._exports
+        data = json.dumps(request).encode("utf-8")
+        try:
+            store.set_fuel(FUEL)
+            ptr = exports["alloc"](store, len(data))
+            exports["memory"].write(store, data, ptr)
+            packed = exports["handle"](store, ptr, len(data))
+            out_ptr, out_len = (packed >> 32) & 0xFFFFFFFF, packed & 0xFFFFFFFF
+            return json.loads(bytes(exports["memory"].read(store, out_ptr, out_ptr + out_len)))
+        except Exception as e:
+            self._store = None  # A trapped instance isn't reused
+            raise PluginError(f"{self.host.name} failed: {e}")
+
+
+class SkillPlugin:
+    """An enabled plugin: its manifest, grants, settings and runtime."""
+
+    def __init__(self, manifest: Manifest, granted: Dict[str, List[str]], settings: Dict[str, Any],
+                 runtime: Optional[Any] = None, fetch: Callable[[str], Tuple[int, str]] = _http_get):
+        self.manifest = manifest
+        self.granted = granted
+        self.settings = settings
+        self.host = SkillHost(manifest.name, Grants.for_plugin(manifest, granted), fetch)
+        self.runtime = runtime or WasmRuntime(manifest.module, self.host)
+        self._lock = threading.Lock()  # A wasmtime store is used by one thread at a time
+
+    def match(self, text: str) -> Optional[Tuple[Intent, Dict[str, str]]]:
+        for intent in self.manifest.intents:
+            args = intent.match(text)
+            if args is not None:
+                return intent, args
+        return None
+
+    def run(self, kind: str, name: str, args: Dict[str, Any], text: str = "") -> str:
+        """Call the module; what it says, or PluginError."""
+        request = {"kind": kind, "name": name, "args": args, "config": self.settings, "text": text}
+        with self._lock:
+            response = self.runtime.call(request)
+        if not isinstance(response, dict):
+            raise PluginError(f"{self.manifest.name} returned {type(response).__name__}, not an object")
+        if response.get("error"):
+            raise PluginError(f"{self.manifest.name}: {response['error']}")
+        return str(response.get("say") or "")
+
+
+class PluginState:
+    """Which plugins are enabled and what they were granted, in ~/.xswarm/plugins/state.json."""
+
+    def __init__(self, path: Path):
+        self.path = path
+        try:
+            self.plugins: Dict[str, Dict[str, Any]] = json.loads(path.read_text(encoding="utf-8"))
+        except (OSError, ValueError):
+            self.plugins = {}
+
+    def enabled(self, name: str) -> bool:
+        return bool(self.plugins.get(name, {}).get("enabled"))
+
+    def granted(self, name: str) -> Dict[str, List[str]]:
+        """The hosts and folders approved, by permission."""
+        grants = self.plugins.get(name, {}).get("grants") or {}
+        if isinstance(grants, list):  # Older files kept only the permissions, so nothing counts as approved
+            return {permission: [] for permission in grants}
+        return {permission: list(targets) for permission, targets in grants.items()}
+
+    def enable(self, name: str, grants: Dict[str, List[str]]) -> None:
+        self.plugins[name] = {"enabled": True, "grants": grants}
+        self._save()
+
+    def disable(self, name: str) -> None:
+        self.plugins[name] = {"enabled": False, "grants": {}}
+        self._save()
+
+    def _save(self) -> None:
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps(self.plugins, indent=2), encoding="utf-8")
+
+
+class SkillPlugins:
+    """The plugins folder: discovery, enabling, and the loaded plugins' intents and tools."""
+
+    def __init__(self, directory: Optional[Path] = None, settings: Optional[Dict[str, Dict[str, Any]]] = None,
+                 runtime_factory: Optional[Callable[[Manifest, SkillHost], Any]] = None):
+        self.directory = Path(directory) if directory else PLUGINS_DIR
+        self.state = PluginState(self.directory / STATE_FILE)
+        self.settings = settings or {}
+        self.runtime_factory = runtime_factory
+        self.plugins: List[SkillPlugin] = []
+        self.problems: List[str] = []  # Plugins that couldn't be loaded, shown in the activity feed
+
+    @classmethod
+    def from_config(cls, config) -> "SkillPlugins":
+        return cls(settings=getattr(config, "plugin_settings", None) or {})
+
+    def discover(self) -> Tuple[List[Manifest], List[str]]:
+        """Every plugin folder's manifest, and the problems with the ones that can't be read."""
+        manifests, problems = [], []
+        folders = sorted(p for p in self.directory.iterdir() if p.is_dir()) if self.directory.is_dir() else []
+        for folder in folders:
+            if not (folder / MANIFEST).exists():
+                continue
+            try:
+                manifests.append(Manifest.load(folder))
+            except PluginError as e:
+                problems.append(str(e))
+        return manifests, problems
+
+    def find(self, name: str) -> Optional[Manifest]:
+        return next((m for m in self.discover()[0] if m.name == name), None)
+
+    def enable(self, name: str, grants: List[str]) -> Manifest:
+        """Enable a plugin with these permissions (each one its manifest asks for), approving what it names now."""
+        manifest = self.find(name)
+        if manifest is None:
+            raise PluginError(f"No plugin called '{name}' in {self.directory}")
+        unasked = [g for g in grants if g not in manifest.permissions]
+        if unasked:
+            raise PluginError(f"{name} doesn't ask for {unasked[0]} access")
+        manifest.settings(self.settings.get(name))  # Bad settings fail here rather than at startup
+        self.state.enable(name, Grants.asked(manifest, grants).approval(grants))
+        return manifest
+
+    def disable(self, name: str) -> None:
+        if self.find(name) is None:
+            raise PluginError(f"No plugin called '{name}' in {self.directory}")
+        self.state.disable(name)
+
+    def load(self, registry=None) -> List[SkillPlugin]:
+        """Load the enabled plugins and register their tools and capabilities."""
+        manifests, self.problems = self.discover()
+        taken = set(registry.list_tools()) if registry is not None else set()
+        for manifest in manifests:
+            if not self.state.enabled(manifest.name):
+                continue
+            try:
+                clash = next((t.name for t in manifest.tools if t.name in taken), None)
+                if clash:
+                    raise PluginError(f"{manifest.name}: tool '{clash}' is already taken")
+                plugin = SkillPlugin(manifest, self.state.granted(manifest.name),
+                                     manifest.settings(self.settings.get(manifest.name)))
+                if self.runtime_factory:
+                    plugin.runtime = self.runtime_factory(manifest, plugin.host)
+            except PluginError as e:
+                self.problems.append(str(e))
+                continue
+            self.plugins.append(plugin)
+            if registry is not None:
+                for spec in manifest.tools:
+                    registry.register(spec.name, spec.description)(_tool_function(plugin, spec))
+                    taken.add(spec.name)
+            examples = [e for intent in manifest.intents for e in intent.examples]
+            register_capability(manifest.name.replace("_", " ").capitalize(),
+                                manifest.description or f"The {manifest.name} plugin",
+                                examples or [f"ask about {manifest.name.replace('_', ' ')}"],
+                                category=manifest.category, keywords=("plugin",))
+        for problem in self.problems:
+            logger.warning(f"Plugin not loaded: {problem}")
+        return self.plugins
+
+    def match(self, text: str) -> Optional[Tuple[SkillPlugin, Intent, Dict[str, str]]]:
+        """The loaded plugin intent `text` asks for, if any."""
+        for plugin in self.plugins:
+            found = plugin.match(text)
+            if found:
+                return plugin, found[0], found[1]
+        return None
+
+    def answer(self, text: str) -> Optional[str]:
+        """Run the matching intent; what the plugin says, or None when no plugin wants `text`."""
+        found = self.match(text)
+        if found is None:
+            return None
+        plugin, intent, args = found
+        try:
+            return plugin.run("intent", intent.name, args, text) or None
+        except PluginError as e:
+            logger.warning(str(e))
+            return f"The {plugin.manifest.name.replace('_', ' ')} plugin couldn't do that."
+
+
+def _tool_function(plugin: SkillPlugin, spec: ToolSpec) -> Callable:
+    """A function with the tool's parameters as its signature (required ones first), for the AI's tool schema."""
+
+    def call(**kwargs) -> str:
+        args = {k: _coerce(plugin.manifest.name, k, spec.parameters[k].type, v) for k, v in kwargs.items()}
+        return plugin.run("tool", spec.name, args)
+
+    parameters = [
+        inspect.Parameter(key, inspect.Parameter.POSITIONAL_OR_KEYWORD, annotation=_TYPES[setting.type],
+                          default=inspect.Parameter.empty if setting.default is None else setting.default)
+        for key, setting in spec.parameters.items()]
+    call.__signature__ = inspect.Signature(sorted(parameters, key=lambda p: p.default is not inspect.Parameter.empty))
+    call.__doc__ = spec.description
+    return call
+
+
+_plugins: Optional[SkillPlugins] = None
+
+
+def get_skill_plugins() -> SkillPlugins:
+    """Get the plugins (none loaded until startup loads the enabled ones)."""
+    global _plugins
+    if _plugins is None:
+        _plugins = SkillPlugins()
+    return _plugins
+
+
+def set_skill_plugins(plugins: Optional[SkillPlugins]) -> None:
+    global _plugins
+    _plugins = plugins
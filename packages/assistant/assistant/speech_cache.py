@@ -0,0 +1,293 @@
+"""
+Speech Cache - Recorded audio for the things the assistant says often.
+
+Acknowledgments ("Got it."), time announcements ("It's three o'clock.") and
+reminder lead-ins are kept as audio once Moshi has said them, so the next
+time they play at once - and still play while the voice server is loading
+or down. VoiceBridgeOrchestrator.speak_text() checks the cache first; on a
+miss it has Moshi read the text and records the reply (SpeechCapture) if
+the text is worth keeping: one of common_phrases(), or anything short that
+has been said config.speech_cache_after times.
+
+VoiceBridgeOrchestrator.warm_speech_cache() pre-records the common phrases
+that are missing, with playback and the transcript muted; the dashboard's
+speech_cache job runs it a few phrases at a time while nothing is said.
+
+Entries are keyed by persona, voice fingerprint and text. The fingerprint
+covers the persona's voice settings (pitch, speed, tone, quality) and the
+Moshi variant, so changing any of them stops old recordings from playing;
+retain_voice() deletes them when the persona is loaded or switched.
+Recordings are 16-bit WAV files in ~/.xswarm/speech_cache/, least recently
+used first out once they pass config.speech_cache_mb.
+"""
+
+import hashlib
+import json
+import logging
+import re
+import time
+import wave
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+import numpy as np
+
+from .flags import enabled as flag_enabled
+
+logger = logging.getLogger(__name__)
+
+MAX_CHARS = 160  # Longer texts are never cached
+SILENCE_RMS = 0.01  # Decoded Moshi audio below this is silence (Moshi's noise gate zeroes it)
+START_TIMEOUT = 6.0  # Seconds of silence before a recording is given up
+END_SILENCE = 0.8  # Seconds of silence that end a recording
+MAX_SECONDS = 15.0  # Longer replies weren't just the text; they're dropped
+MAX_TRACKED = 500  # Texts whose repeat count is kept (the most repeated survive)
+
+ACKNOWLEDGMENTS = (
+    "Okay.", "Got it.", "Done.", "Sure.", "One moment.", "On it.", "All set.", "Let me check.", "Just a second.",
+    "Sorry, I didn't catch that.", "Could you say that again?", "I can't do that right now.",
+)
+REMINDER_LEAD_INS = (
+    "You have a reminder.", "Your next meeting starts in five minutes.",
+    "Your next meeting starts in fifteen minutes.", "You have nothing else scheduled today.",
+    "Your meeting is starting now.",
+)
+HOURS = ("twelve", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven")
+
+
+def common_phrases() -> List[str]:
+    """
+    What warm_speech_cache() pre-records: acknowledgments, the current
+    persona's canned phrases (personas/phrases.py), the hour announcements
+    and reminder lead-ins.
+    """
+    from .personas.phrases import get_phrase_bank
+
+    hours = [f"It's {hour} o'clock." for hour in HOURS] + [f"It's half past {hour}." for hour in HOURS]
+    return list(dict.fromkeys([*ACKNOWLEDGMENTS, *get_phrase_bank().cacheable(), *hours, *REMINDER_LEAD_INS]))
+
+
+def normalize(text: str) -> str:
+    return re.sub(r"\s+", " ", text).strip()
+
+
+def voice_fingerprint(persona, config=None) -> str:
+    """Changes whenever the persona's voice settings or the Moshi variant do."""
+    voice = getattr(persona, "voice", None)
+    settings = {name: getattr(voice, name, None) for name in ("pitch", "speed", "tone", "quality")}
+    settings["moshi_quality"] = getattr(config, "moshi_quality", None)
+    return hashlib.sha256(json.dumps(settings, sort_keys=True).encode()).hexdigest()[:16]
+
+
+def _key(persona: str, fingerprint: str, text: str) -> str:
+    return hashlib.sha256(f"{persona}\n{fingerprint}\n{normalize(text)}".encode()).hexdigest()[:24]
+
+
+def _rms(audio: np.ndarray) -> float:
+    return float(np.sqrt(np.mean(np.square(audio)))) if len(audio) else 0.0
+
+
+@dataclass
+class SpeechCapture:
+    """
+    Records Moshi's reply to one speak request: from the first sound until
+    END_SILENCE of quiet. failed is set when it never starts or runs long.
+    """
+    persona: str
+    fingerprint: str
+    text: str
+    sample_rate: int = 24000
+    silent: bool = False  # Swallow the audio instead of playing it (warm_speech_cache)
+    store: bool = True  # False: a warm-up read, recorded only to be thrown away
+
+    def __post_init__(self):
+        self.chunks: List[np.ndarray] = []
+        self.done = False
+        self.failed = False
+        self._waited = 0  # Samples of silence before speech started
+        self._quiet = 0  # Samples of silence since the last sound
+
+    def feed(self, audio: np.ndarray) -> None:
+        if self.done:
+            return
+        audio = np.asarray(audio, dtype=np.float32)
+        loud = _rms(audio) >= SILENCE_RMS
+        if not self.chunks:
+            if loud:
+                self.chunks.append(audio)
+            else:
+                self._waited += len(audio)
+                if self._waited >= START_TIMEOUT * self.sample_rate:
+                    self.done = self.failed = True
+            return
+        self.chunks.append(audio)
+        self._quiet = 0 if loud else self._quiet + len(audio)
+        if self._quiet >= END_SILENCE * self.sample_rate:
+            self.done = True
+        elif sum(len(chunk) for chunk in self.chunks) >= MAX_SECONDS * self.sample_rate:
+            self.done = self.failed = True
+
+    def audio(self) -> np.ndarray:
+        """The recording without its trailing silence."""
+        if not self.chunks:
+            return np.zeros(0, dtype=np.float32)
+        audio = np.concatenate(self.chunks)
+        return audio[:len(audio) - self._quiet] if self._quiet else audio
+
+
+class SpeechCache:
+    """Cached recordings of short utterances, per persona and voice."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "speech_cache"
+
+    def __init__(self, directory: Optional[Path] = None, max_bytes: int = 50 * 1024 * 1024,
+                 record_after: int = 2, clock: Callable[[], float] = time.time):
+        self.directory = Path(directory or self.DEFAULT_DIR)
+        self.max_bytes = max_bytes
+        self.record_after = record_after
+        self.clock = clock
+        self.common = {normalize(phrase) for phrase in common_phrases()}
+        self._index: Optional[Dict[str, Any]] = None  # {"entries": {key: ...}, "spoken": {key: count}}
+
+    @classmethod
+    def from_config(cls, config) -> Optional["SpeechCache"]:
+        if not getattr(config, "speech_cache", True) or not flag_enabled("speech_cache"):
+            return None
+        return cls(max_bytes=int(getattr(config, "speech_cache_mb", 50)) * 1024 * 1024,
+                   record_after=int(getattr(config, "speech_cache_after", 2)))
+
+    @property
+    def _index_path(self) -> Path:
+        return self.directory / "index.json"
+
+    def _load(self) -> Dict[str, Any]:
+        if self._index is None:
+            try:
+                self._index = json.loads(self._index_path.read_text(encoding="utf-8"))
+            except FileNotFoundError:
+                self._index = {}
+            except Exception as e:
+                logger.warning(f"Speech cache index unreadable, starting over: {e}")
+                self._index = {}
+            self._index.setdefault("entries", {})
+            self._index.setdefault("spoken", {})
+        return self._index
+
+    def _save(self) -> None:
+        try:
+            self.directory.mkdir(parents=True, exist_ok=True)
+            self._index_path.write_text(json.dumps(self._load(), indent=2), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save the speech cache index: {e}")
+
+    def get(self, persona: str, fingerprint: str, text: str) -> Optional[Tuple[np.ndarray, int]]:
+        """(float32 samples, sample rate) for text in this voice, or None."""
+        key = _key(persona, fingerprint, text)
+        entry = self._load()["entries"].get(key)
+        if entry is None:
+            return None
+        try:
+            with wave.open(str(self.directory / entry["file"]), "rb") as wav:
+                rate = wav.getframerate()
+                pcm = np.frombuffer(wav.readframes(wav.getnframes()), dtype=np.int16)
+        except (OSError, wave.Error, EOFError) as e:
+            logger.debug(f"Dropping unreadable speech cache entry '{entry['text']}': {e}")
+            self._remove(key)
+            self._save()
+            return None
+        entry["last_used"] = self.clock()
+        entry["hits"] = entry.get("hits", 0) + 1
+        self._save()
+        return (pcm.astype(np.float32) / 32767.0), rate
+
+    def wants(self, persona: str, fingerprint: str, text: str) -> bool:
+        """
+        Count text as said once more; True when a recording of it should be
+        kept (short, not cached yet, and common or said record_after times).
+        """
+        text = normalize(text)
+        if not text or len(text) > MAX_CHARS:
+            return False
+        key = _key(persona, fingerprint, text)
+        index = self._load()
+        if key in index["entries"]:
+            return False
+        spoken = index["spoken"]
+        spoken[key] = spoken.get(key, 0) + 1
+        if len(spoken) > MAX_TRACKED:
+            keep = sorted(spoken, key=spoken.get, reverse=True)[:MAX_TRACKED // 2]
+            index["spoken"] = spoken = {k: spoken[k] for k in keep + [key]}
+        self._save()
+        return text in self.common or spoken[key] >= self.record_after
+
+    def put(self, persona: str, fingerprint: str, text: str, audio: np.ndarray, sample_rate: int) -> bool:
+        """Store a recording; False if it's empty or bigger than the whole cache."""
+        pcm = (np.clip(np.asarray(audio, dtype=np.float32), -1.0, 1.0) * 32767.0).astype(np.int16)
+        size = pcm.nbytes + 44
+        if not len(pcm) or size > self.max_bytes:
+            return False
+        key = _key(persona, fingerprint, text)
+        try:
+            self.directory.mkdir(parents=True, exist_ok=True)
+            with wave.open(str(self.directory / f"{key}.wav"), "wb") as wav:
+                wav.setnchannels(1)
+                wav.setsampwidth(2)
+                wav.setframerate(sample_rate)
+                wav.writeframes(pcm.tobytes())
+        except OSError as e:
+            logger.warning(f"Failed to cache speech for '{text[:40]}': {e}")
+            return False
+        index = self._load()
+        index["spoken"].pop(key, None)
+        index["entries"][key] = {"persona": persona, "fingerprint": fingerprint, "text": normalize(text),
+                                 "file": f"{key}.wav", "bytes": size, "sample_rate": sample_rate,
+                                 "last_used": self.clock(), "hits": 0}
+        self._evict()
+        self._save()
+        return True
+
+    def _remove(self, key: str) -> None:
+        entry = self._load()["entries"].pop(key, None)
+        if entry:
+            (self.directory / entry["file"]).unlink(missing_ok=True)
+
+    def _evict(self) -> None:
+        entries = self._load()["entries"]
+        by_use = sorted(entries, key=lambda key: entries[key]["last_used"])
+        while by_use and self.size() > self.max_bytes:
+            self._remove(by_use.pop(0))
+
+    def size(self) -> int:
+        return sum(entry["bytes"] for entry in self._load()["entries"].values())
+
+    def retain_voice(self, persona: str, fingerprint: str) -> int:
+        """Delete the persona's recordings made in any other voice; returns how many."""
+        index = self._load()
+        stale = [key for key, entry in index["entries"].items()
+                 if entry["persona"] == persona and entry["fingerprint"] != fingerprint]
+        for key in stale:
+            self._remove(key)
+        if stale:
+            index["spoken"].clear()  # Counts were per voice too
+            self._save()
+        return len(stale)
+
+    def missing(self, persona: str, fingerprint: str, phrases: Optional[Iterable[str]] = None) -> List[str]:
+        """Phrases (default common_phrases()) with no recording in this voice."""
+        entries = self._load()["entries"]
+        return [phrase for phrase in (phrases if phrases is not None else common_phrases())
+                if _key(persona, fingerprint, phrase) not in entries]
+
+    def clear(self) -> int:
+        entries = list(self._load()["entries"])
+        for key in entries:
+            self._remove(key)
+        self._load()["spoken"].clear()
+        self._save()
+        return len(entries)
+
+    def stats(self) -> Dict[str, Any]:
+        entries = self._load()["entries"].values()
+        return {"entries": len(entries), "bytes": self.size(), "max_bytes": self.max_bytes,
+                "hits": sum(entry.get("hits", 0) for entry in entries)}
@@ -0,0 +1,220 @@
+"""
+Speech Filter - What may be said aloud, between a reply and the voice.
+
+Replies are shown in full in chat; what is spoken goes through three
+filters first:
+
+- profanity: "mask" says "bleep" for a swear word, "remove" leaves it
+  out, "off" says it
+- pii: phone numbers, email addresses, card numbers and secrets (the
+  patterns of redaction.py, with config.redaction_patterns) aren't read
+  out - "the phone number on screen" is said instead
+- max_words: a longer reply is said up to there (whole sentences), then
+  "Shall I continue?". "yes" / "go on" says the next part, "no" drops the
+  rest, and so does saying anything else or CONTINUE_WINDOW passing. On a
+  call (no limit unless one is set), where there's no answering, the rest
+  is left out
+
+Settings are layered, the last one set winning: config.speech_profanity,
+speech_pii and speech_max_words; the current persona's speech_filter:
+block (personas/config.py); the channel's defaults; then
+config.speech_filter_channels[channel]:
+
+    speech_max_words: 120
+    speech_filter_channels: {call: {max_words: 60}}
+
+Channels: "speech" (the local voice, VoiceOrchestrator.speak_text - replies,
+announcements, fillers) and "call" (what phone calls say, phone.py). Moshi's
+own conversation is generated as audio and doesn't pass through here.
+"""
+
+import logging
+import re
+import time
+from dataclasses import dataclass, replace
+from typing import Any, Callable, Dict, List, Optional
+
+from .prosody import strip as strip_hints
+from .redaction import Redactor
+
+logger = logging.getLogger(__name__)
+
+PROFANITY_MODES = ("mask", "remove", "off")
+CONTINUE_PROMPT = "Shall I continue?"
+CONTINUE_WINDOW = 60.0  # Seconds "Shall I continue?" can still be answered
+INTERACTIVE = ("speech",)  # Channels where the user can answer "Shall I continue?"
+CHANNEL_DEFAULTS: Dict[str, Dict[str, Any]] = {
+    "call": {"max_words": 0},  # Call messages are written to be said whole
+}
+PII_SAID = {"speech": "the {label} on screen", "call": "the {label}, which I won't read out"}
+PII_LABELS = {"phone": "phone number", "email": "email address", "card": "card number", "secret": "secret"}
+
+# Word starts ("damn" covers "damned"); the first two anywhere in a word too ("motherfucker", "bullshit")
+PROFANITY = ("fuck", "shit", "bitch", "bastard", "asshole", "arsehole", "dickhead", "cunt", "wanker",
+             "goddamn", "damn", "crap", "piss", "bollocks", "twat")
+_PROFANITY = re.compile(r"\b(?:\w*(?:fuck|shit)|" + "|".join(PROFANITY[2:]) + r")\w*\b", re.IGNORECASE)
+_MASKED = re.compile(r"\[(\w+?)(?::\w+)?\]")
+_SENTENCE = re.compile(r"(?<=[.!?…])\s+")
+
+_YES = re.compile(r"^\W*(?:yes|yeah|yep|yup|sure|ok(?:ay)?|please(?: do)?|go on|go ahead|continue|keep going|"
+                  r"carry on|more|tell me more|and\?)(?:\W+(?:please|go on|continue|thanks))*\W*$", re.IGNORECASE)
+_NO = re.compile(r"^\W*(?:no|nope|nah|stop|enough|that's enough|that's all|skip it|no thanks|no thank you|"
+                 r"never ?mind)(?:\W+(?:thanks|thank you|that's enough))*\W*$", re.IGNORECASE)
+
+
+def parse_continue(text: str) -> Optional[bool]:
+    """True for "yes" / "go on", False for "no" / "that's enough", None for anything else."""
+    if _YES.match(text or ""):
+        return True
+    if _NO.match(text or ""):
+        return False
+    return None
+
+
+def mask_profanity(text: str, mode: str = "mask") -> str:
+    """Swear words said as "bleep" (mask), left out (remove) or kept (off)."""
+    if mode == "off":
+        return text
+    if mode == "remove":
+        text = re.sub(r"\s+([,.!?])", r"\1", re.sub(r" {2,}", " ", _PROFANITY.sub("", text)))
+        return re.sub(r",([.!?])", r"\1", text).strip()
+    return _PROFANITY.sub("bleep", text)
+
+
+def suppress_pii(text: str, redactor: Optional[Redactor] = None, said: str = PII_SAID["speech"]) -> str:
+    """Personal details (and secrets) replaced by what is said instead, e.g. "the phone number on screen"."""
+    before = set(_MASKED.findall(text))
+    masked = (redactor or Redactor())(text)
+
+    def spoken(match: re.Match) -> str:
+        name = match.group(1)
+        if name in before:  # Already in brackets before redaction: not ours
+            return match.group(0)
+        return said.format(label=PII_LABELS.get(name, name.replace("_", " ")))
+    return _MASKED.sub(spoken, masked)
+
+
+def _words(text: str) -> int:
+    return len(strip_hints(text).split())
+
+
+def split_for_speech(text: str, max_words: int) -> List[str]:
+    """Parts of at most max_words, whole sentences where they fit (0: the whole text)."""
+    text = text.strip()
+    if max_words <= 0 or _words(text) <= max_words:
+        return [text]
+    parts, current = [], []
+    for sentence in _SENTENCE.split(text):
+        pieces = [sentence]
+        if _words(sentence) > max_words:  # A sentence too long on its own is cut between words
+            words = sentence.split()
+            pieces = [" ".join(words[i:i + max_words]) for i in range(0, len(words), max_words)]
+        for piece in pieces:
+            if current and _words(" ".join(current + [piece])) > max_words:
+                parts.append(" ".join(current))
+                current = []
+            current.append(piece)
+    if current:
+        parts.append(" ".join(current))
+    return parts
+
+
+@dataclass(frozen=True)
+class FilterSettings:
+    profanity: str = "mask"
+    pii: bool = True
+    max_words: int = 150
+
+
+class SpeechFilter:
+    """Filters text on its way to a voice, and keeps what a long reply has left to say."""
+
+    def __init__(self, defaults: Optional[FilterSettings] = None,
+                 channels: Optional[Dict[str, Dict[str, Any]]] = None,
+                 redactor: Optional[Redactor] = None, clock: Callable[[], float] = time.monotonic):
+        self.defaults = defaults or FilterSettings()
+        self.channels = channels or {}
+        self.redactor = redactor or Redactor()
+        self.clock = clock
+        self.pending: List[str] = []  # Parts not yet said, after "Shall I continue?"
+        self.asked_at: Optional[float] = None
+
+    @classmethod
+    def from_config(cls, config) -> "SpeechFilter":
+        defaults = FilterSettings(str(getattr(config, "speech_profanity", "mask")),
+                                  bool(getattr(config, "speech_pii", True)),
+                                  int(getattr(config, "speech_max_words", 150) or 0))
+        return cls(defaults, dict(getattr(config, "speech_filter_channels", None) or {}),
+                   Redactor(patterns=getattr(config, "redaction_patterns", None)))
+
+    def settings(self, persona: Any = None, channel: str = "speech") -> FilterSettings:
+        """The defaults, then the persona's speech_filter, then the channel's defaults and overrides."""
+        settings = self.defaults
+        own = getattr(persona, "speech_filter", None)
+        layers = [{key: getattr(own, key, None) for key in ("profanity", "pii", "max_words")}]
+        layers += [CHANNEL_DEFAULTS.get(channel, {}), self.channels.get(channel, {})]
+        for layer in layers:
+            settings = replace(settings, **{k: v for k, v in layer.items()
+                                            if k in ("profanity", "pii", "max_words") and v is not None})
+        if settings.profanity not in PROFANITY_MODES:
+            logger.warning(f"Unknown speech profanity filter '{settings.profanity}' (use mask, remove, off)")
+            settings = replace(settings, profanity="mask")
+        return settings
+
+    def apply(self, text: str, persona: Any = None, channel: str = "speech") -> str:
+        """What to say for text; the first part and "Shall I continue?" when it's too long."""
+        settings = self.settings(persona, channel)
+        text = mask_profanity(text, settings.profanity)
+        if settings.pii:
+            text = suppress_pii(text, self.redactor, PII_SAID.get(channel, PII_SAID["speech"]))
+        parts = split_for_speech(text, settings.max_words)
+        if len(parts) == 1:
+            return parts[0]
+        if channel not in INTERACTIVE:
+            return parts[0]
+        self.pending, self.asked_at = parts[1:], self.clock()
+        return f"{parts[0]} {CONTINUE_PROMPT}"
+
+    def has_more(self) -> bool:
+        if self.pending and self.clock() - (self.asked_at or 0.0) > CONTINUE_WINDOW:
+            self.drop()
+        return bool(self.pending)
+
+    def answer(self, text: str) -> Optional[bool]:
+        """
+        The user's reply while "Shall I continue?" is open: True to go on (say
+        next_part()), False to stop. None when nothing is open - or the reply
+        was about something else, which drops the rest.
+        """
+        if not self.has_more():
+            return None
+        more = parse_continue(text)
+        if not more:
+            self.drop()
+        return more
+
+    def next_part(self) -> str:
+        """The next part to say (already filtered), asking again if more remain."""
+        if not self.pending:
+            return ""
+        part = self.pending.pop(0)
+        if not self.pending:
+            self.asked_at = None
+            return part
+        self.asked_at = self.clock()
+        return f"{part} {CONTINUE_PROMPT}"
+
+    def drop(self) -> None:
+        self.pending, self.asked_at = [], None
+
+
+_filter = SpeechFilter()
+
+
+def get_speech_filter() -> SpeechFilter:
+    return _filter
+
+
+def set_speech_filter(speech_filter: SpeechFilter) -> None:
+    global _filter
+    _filter = speech_filter
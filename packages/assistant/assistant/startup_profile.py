@@ -0,0 +1,142 @@
+"""
+Startup profile - How long each stage of starting up took, against a budget.
+
+    xswarm --profile-startup     # start up, print the breakdown, quit
+
+The stages, in roughly the order they start (some overlap):
+
+    config        loading and migrating config.yaml                   (main.py)
+    voice_server  starting the voice server process                   (main.py)
+    dashboard     building the TUI until it's mounted                 (dashboard.py)
+    memory        opening the memory store                            (dashboard.py)
+    server        the xSwarm server's first answer (GET /health)      (dashboard.py)
+    models        the voice server loading Moshi and Mimi             (voice.py)
+    audio         opening the audio output                            (voice.py)
+    transcriber   loading the Vosk transcriber                        (voice.py)
+    microphone    microphone permission and the input stream          (dashboard.py)
+    chat_engine   the chat engine, ready for the first message        (dashboard.py)
+
+Each has a budget in seconds (BUDGETS; config.startup_budgets changes
+them, e.g. {"models": 90}). A stage over its budget is a warning in the
+activity feed, and the whole breakdown goes to the log once startup is
+done (the dashboard calls done() when voice and the chat engine are
+ready) - the numbers the lazy-loading work is measured against.
+
+A stage that doesn't run this time (voice off, text-only) isn't listed.
+Only a stage's first run counts: memory is opened again by voice, say.
+"""
+
+import logging
+import time
+from contextlib import contextmanager
+from dataclasses import dataclass
+from typing import Callable, Dict, Iterator, List, Mapping, Optional
+
+logger = logging.getLogger(__name__)
+
+# Seconds each stage should take at most
+BUDGETS: Dict[str, float] = {
+    "config": 0.5, "voice_server": 2.0, "dashboard": 2.0, "memory": 2.0, "server": 2.0,
+    "models": 60.0, "audio": 1.0, "transcriber": 5.0, "microphone": 1.0, "chat_engine": 5.0,
+}
+
+
+@dataclass
+class Stage:
+    name: str
+    started: float  # Seconds after the process started
+    seconds: Optional[float] = None  # None while it runs
+    budget: Optional[float] = None
+    error: str = ""
+
+    @property
+    def over(self) -> bool:
+        return self.seconds is not None and self.budget is not None and self.seconds > self.budget
+
+
+class StartupProfile:
+    """Stage timings since `origin` (the start of main()); see the module docstring."""
+
+    def __init__(self, budgets: Optional[Mapping[str, float]] = None, clock: Callable[[], float] = time.perf_counter,
+                 exit_when_done: bool = False):
+        self.budgets = dict(BUDGETS)
+        self.budgets.update(budgets or {})
+        self.clock = clock
+        self.origin = clock()
+        self.exit_when_done = exit_when_done  # --profile-startup: quit once startup is done
+        self.stages: Dict[str, Stage] = {}
+        self.finished_at: Optional[float] = None
+        self.on_over: Optional[Callable[[Stage], None]] = None  # The dashboard's warning, once it's up
+
+    def set_budgets(self, budgets: Optional[Mapping[str, float]]) -> None:
+        """Change budgets (config.startup_budgets, known once config is loaded), for stages already started too."""
+        self.budgets.update(budgets or {})
+        for stage in self.stages.values():
+            stage.budget = self.budgets.get(stage.name)
+
+    def start(self, name: str) -> None:
+        if name not in self.stages:
+            self.stages[name] = Stage(name, self.clock() - self.origin, budget=self.budgets.get(name))
+
+    def finish(self, name: str, error: str = "") -> Optional[Stage]:
+        """End a started stage (once); it's reported when over its budget."""
+        stage = self.stages.get(name)
+        if stage is None or stage.seconds is not None:
+            return None
+        stage.seconds = self.clock() - self.origin - stage.started
+        stage.error = error
+        if stage.over:
+            logger.warning(f"Startup stage {name} took {stage.seconds:.2f}s (budget {stage.budget:g}s)")
+            if self.on_over:
+                self.on_over(stage)
+        return stage
+
+    @contextmanager
+    def stage(self, name: str) -> Iterator[None]:
+        """Time a block as a stage; an error ends it too, and is noted."""
+        self.start(name)
+        try:
+            yield
+        except BaseException as e:
+            self.finish(name, f"{type(e).__name__}: {e}")
+            raise
+        self.finish(name)
+
+    def over_budget(self) -> List[Stage]:
+        return [stage for stage in self.stages.values() if stage.over]
+
+    def done(self) -> List[str]:
+        """Startup is over: log the breakdown (once) and return it."""
+        if self.finished_at is None:
+            self.finished_at = self.clock() - self.origin
+            for line in self.lines():
+                logger.info(line)
+        return self.lines()
+
+    def lines(self) -> List[str]:
+        total = self.finished_at if self.finished_at is not None else self.clock() - self.origin
+        lines = [f"Startup: {total:.2f}s{' to ready' if self.finished_at is not None else ' so far'}",
+                 f"  {'stage':<13} {'start':>7} {'took':>8} {'budget':>7}"]
+        for stage in sorted(self.stages.values(), key=lambda stage: stage.started):
+            took = f"{stage.seconds:.2f}s" if stage.seconds is not None else "running"
+            budget = f"{stage.budget:g}s" if stage.budget is not None else "-"
+            note = "  ⚠ over budget" if stage.over else ""
+            note += f"  ✗ {stage.error}" if stage.error else ""
+            lines.append(f"  {stage.name:<13} {stage.started:>6.2f}s {took:>8} {budget:>7}{note}")
+        return lines
+
+
+_profile: Optional[StartupProfile] = None
+
+
+def get_startup_profile() -> StartupProfile:
+    """The profile main() started (one starting now, when nothing did)."""
+    global _profile
+    if _profile is None:
+        _profile = StartupProfile()
+    return _profile
+
+
+def set_startup_profile(profile: Optional[StartupProfile]) -> None:
+    global _profile
+    _profile = profile
@@ -0,0 +1,164 @@
+"""
+Task Supervisor - Keep long-running background tasks alive.
+
+An uncaught exception in a fire-and-forget asyncio task (the Moshi audio
+forwarder, the thinking engine's listener, the job scheduler loop) only
+surfaces as "Task exception was never retrieved" at shutdown - the feature
+just stops. Tasks spawned through the supervisor instead:
+
+- have the crash logged (and reported to the dashboard's activity feed)
+- are restarted with exponential backoff (RESTART_DELAY doubling up to
+  MAX_RESTART_DELAY)
+- give up after MAX_RESTARTS crashes in a row and are listed as failed,
+  which the dashboard shows in the footer
+
+A task that then runs for STABLE_AFTER seconds has its crash count reset,
+so a subsystem that hiccups once a day is never marked failed. A task that
+returns normally is finished and is not restarted; cancelling the task
+returned by spawn() stops it for good.
+
+Subsystems with a state of their own (the voice conversation's state
+machine) report it with report_state(); the latest state per subsystem is
+in states, and on_state hears every report.
+"""
+
+import asyncio
+import logging
+import time
+from dataclasses import dataclass
+from typing import Awaitable, Callable, Dict, List, Optional
+
+logger = logging.getLogger(__name__)
+
+MAX_RESTARTS = 5           # Crashes in a row before a subsystem is marked failed
+RESTART_DELAY = 1.0        # Seconds before the first restart (doubles each time)
+MAX_RESTART_DELAY = 30.0
+STABLE_AFTER = 60.0        # Seconds of clean running that reset the crash count
+
+
+@dataclass
+class SubsystemFailure:
+    """A supervised task that crashed too often and was given up on."""
+    name: str
+    error: str
+    restarts: int
+
+
+@dataclass
+class SubsystemState:
+    """The latest state a subsystem reported, and why it changed."""
+    name: str
+    state: str
+    reason: str
+    since: float
+
+
+class TaskSupervisor:
+    """Runs named coroutines as tasks, restarting them when they crash."""
+
+    def __init__(
+        self,
+        on_crash: Optional[Callable[[str, BaseException, float], None]] = None,
+        on_failed: Optional[Callable[[SubsystemFailure], None]] = None,
+        on_state: Optional[Callable[[SubsystemState], None]] = None,
+        max_restarts: int = MAX_RESTARTS,
+        restart_delay: float = RESTART_DELAY,
+        max_restart_delay: float = MAX_RESTART_DELAY,
+        stable_after: float = STABLE_AFTER,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        self.on_crash = on_crash    # (name, error, restart delay) for each crash that will be retried
+        self.on_failed = on_failed  # Once per subsystem that exhausts its restarts
+        self.on_state = on_state    # Every state a subsystem reports
+        self.max_restarts = max_restarts
+        self.restart_delay = restart_delay
+        self.max_restart_delay = max_restart_delay
+        self.stable_after = stable_after
+        self._clock = clock
+        self.tasks: Dict[str, asyncio.Task] = {}
+        self.restarts: Dict[str, int] = {}  # Restarts since the task last ran stably
+        self.failed: Dict[str, SubsystemFailure] = {}
+        self.states: Dict[str, SubsystemState] = {}
+
+    def spawn(self, name: str, factory: Callable[[], Awaitable]) -> asyncio.Task:
+        """
+        Start `factory()` as a supervised task named `name`.
+
+        `factory` is called again for every restart, so pass the coroutine
+        function (e.g. self._monitor_loop), not a coroutine object.
+        """
+        self.failed.pop(name, None)
+        self.restarts[name] = 0
+        task = asyncio.create_task(self._supervise(name, factory), name=f"supervised:{name}")
+        self.tasks[name] = task
+        task.add_done_callback(lambda t: self.tasks.pop(name, None) if self.tasks.get(name) is t else None)
+        return task
+
+    async def _supervise(self, name: str, factory: Callable[[], Awaitable]) -> None:
+        while True:
+            started = self._clock()
+            try:
+                await factory()
+                return
+            except asyncio.CancelledError:
+                raise
+            except Exception as e:
+                error = e
+
+            if self._clock() - started >= self.stable_after:
+                self.restarts[name] = 0
+            if self.restarts[name] >= self.max_restarts:
+                failure = SubsystemFailure(name=name, error=f"{type(error).__name__}: {error}", restarts=self.restarts[name])
+                self.failed[name] = failure
+                logger.error(f"{name} failed permanently after {failure.restarts} restarts: {failure.error}",
+                             exc_info=(type(error), error, error.__traceback__))
+                if self.on_failed:
+                    self._notify(self.on_failed, failure)
+                return
+
+            delay = min(self.restart_delay * (2 ** self.restarts[name]), self.max_restart_delay)
+            self.restarts[name] += 1
+            logger.warning(f"{name} crashed ({type(error).__name__}: {error}), restarting in {delay:.0f}s",
+                           exc_info=(type(error), error, error.__traceback__))
+            if self.on_crash:
+                self._notify(self.on_crash, name, error, delay)
+            await asyncio.sleep(delay)
+
+    @staticmethod
+    def _notify(callback: Callable, *args) -> None:
+        # A broken UI callback must not take the supervisor down with it
+        try:
+            callback(*args)
+        except Exception as e:
+            logger.debug(f"Supervisor callback failed: {e}")
+
+    def report_state(self, name: str, state: str, reason: str = "") -> None:
+        """Record a subsystem's new state (e.g. the conversation going from speaking to interrupted)."""
+        report = SubsystemState(name=name, state=state, reason=reason, since=self._clock())
+        self.states[name] = report
+        if self.on_state:
+            self._notify(self.on_state, report)
+
+    def failures(self) -> List[SubsystemFailure]:
+        return list(self.failed.values())
+
+    def cancel_all(self) -> None:
+        for task in list(self.tasks.values()):
+            task.cancel()
+
+
+_supervisor: Optional[TaskSupervisor] = None
+
+
+def get_task_supervisor() -> TaskSupervisor:
+    """Get the global task supervisor (logs only until the dashboard installs its own)."""
+    global _supervisor
+    if _supervisor is None:
+        _supervisor = TaskSupervisor()
+    return _supervisor
+
+
+def set_task_supervisor(supervisor: TaskSupervisor) -> None:
+    """Install the supervisor whose callbacks report to the dashboard (called at startup)."""
+    global _supervisor
+    _supervisor = supervisor
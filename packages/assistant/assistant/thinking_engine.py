@@ -13,11 +13,15 @@ This is the "brain" that gives Moshi access to external capabilities.
 import asyncio
 import logging
 import os
+import time
 from typing import Optional, Dict, Any, List
 from anthropic import AsyncAnthropic
 
+from .ai_log import log_ai_call
 from .tools import ToolRegistry, Tool, ToolParameter, send_email_tool, make_call_tool
 from .memory import MemoryManager
+from .notifications import get_notification_policy
+from .supervisor import get_task_supervisor
 
 logger = logging.getLogger(__name__)
 
@@ -125,7 +129,7 @@ class ThinkingEngine:
             return
 
         self.running = True
-        asyncio.create_task(self._monitor_loop())
+        get_task_supervisor().spawn("thinking engine listener", self._monitor_loop)
         logger.debug("Thinking engine started")
 
     async def stop(self):
@@ -190,7 +194,7 @@ class ThinkingEngine:
         """
         
         try:
-            response = await self.claude.messages.create(
+            response_text = await self._ask_claude(
                 model="claude-3-haiku-20240307",
                 max_tokens=300,
                 system=system_prompt,
@@ -199,13 +203,12 @@ class ThinkingEngine:
             
             # Parse and execute
             import json
-            response_text = response.content[0].text
             json_start = response_text.find('{')
             json_end = response_text.rfind('}') + 1
             
             if json_start >= 0:
                 decision = json.loads(response_text[json_start:json_end])
-                await self._execute_decision(decision)
+                await self._execute_decision(decision, proactive=True)
                 
         except Exception as e:
             logger.debug(f"Error in scheduled task {task_name}: {e}")
@@ -312,16 +315,13 @@ New message from {source}:
 What action, if any, should be taken?"""
 
         try:
-            response = await self.claude.messages.create(
+            response_text = await self._ask_claude(
                 model="claude-3-haiku-20240307",  # Fast, cheap for quick decisions
                 max_tokens=500,
                 system=system_prompt,
                 messages=[{"role": "user", "content": user_message}]
             )
 
-            # Parse response
-            response_text = response.content[0].text
-
             # Extract JSON from response
             import json
             try:
@@ -338,6 +338,18 @@ What action, if any, should be taken?"""
             # Log but don't crash
             pass
 
+    async def _ask_claude(self, **request) -> str:
+        """One Claude call, logged in the AI log (see ai_log.py); returns the reply's text."""
+        started = time.monotonic()
+        try:
+            response = await self.claude.messages.create(**request)
+        except Exception as e:
+            log_ai_call("anthropic", request["model"], "thinking", request, started=started, error=str(e))
+            raise
+        reply = response.content[0].text
+        log_ai_call("anthropic", request["model"], "thinking", request, reply, response, started)
+        return reply
+
     async def _summarize_for_moshi(self, data: str, context: str) -> str:
         """
         Use Sonnet 4.5 to create a terse summary for Moshi's small context window.
@@ -383,20 +395,27 @@ Example bad summaries (too verbose):
 Create a terse 2-3 sentence summary:"""
 
         try:
-            response = await self.claude.messages.create(
+            response_text = await self._ask_claude(
                 model="claude-sonnet-4-5-20250514",  # Sonnet 4.5 for smart summarization
                 max_tokens=150,  # Force brevity
                 system=system_prompt,
                 messages=[{"role": "user", "content": user_message}]
             )
-            return response.content[0].text.strip()
+            return response_text.strip()
         except Exception as e:
             # Fallback: truncate
             return data[:200] + "..." if len(data) > 200 else data
 
-    async def _execute_decision(self, decision: Dict[str, Any]):
-        """Execute the thinking engine's decision."""
+    async def _execute_decision(self, decision: Dict[str, Any], proactive: bool = False):
+        """
+        Execute the thinking engine's decision.
+
+        Proactive decisions (from scheduled tasks, not the live conversation)
+        still run tools, but only inject results when the notification policy
+        allows suggestions right now.
+        """
         action = decision.get("action", "none")
+        suggestions_allowed = not proactive or get_notification_policy().allows("suggestions")
 
         if action == "none":
             return
@@ -418,18 +437,19 @@ Create a terse 2-3 sentence summary:"""
                     else:
                         result_text = str(result_data)
 
-                    summary = await self._summarize_for_moshi(
-                        result_text,
-                        f"tool '{tool_name}' result"
-                    )
-                    self.voice_client.inject_tool_result(tool_name, summary)
+                    if suggestions_allowed:
+                        summary = await self._summarize_for_moshi(
+                            result_text,
+                            f"tool '{tool_name}' result"
+                        )
+                        self.voice_client.inject_tool_result(tool_name, summary)
 
                     if self.on_tool_result:
                         self.on_tool_result(tool_name, result)
 
         elif action == "inject_context":
             context = decision.get("context_to_inject", "")
-            if context:
+            if context and suggestions_allowed:
                 # Summarize if context is long
                 if len(context) > 200:
                     context = await self._summarize_for_moshi(context, "context injection")
@@ -0,0 +1,160 @@
+"""
+Timeline - What happened around a moment, rebuilt from the event history.
+
+    xswarm dev events replay 3:04pm               # interactions going on that minute
+    xswarm dev events replay "9am..9:30am"
+    xswarm dev events replay 2h --tui              # the last two hours, in the activity feed
+    xswarm dev events replay --thread 3fa9c1
+
+Events are grouped by the interaction that set them off (their thread,
+see correlation.py), and a thread seen in the range is shown whole, even
+the parts before or after it. Each step shows when it happened after the
+start and how long after the step before - the time taken by speech-to-
+text, the model, a tool or a notification - and the model calls of the
+thread (ai_log.py) are steps too, with their own latency. Events outside
+any interaction get a line of their own.
+
+--tui shows the same in an activity feed with collapsible threads, as the
+dashboard showed it, for screenshots in bug reports.
+"""
+
+import re
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from typing import Any, Dict, List, Optional, Tuple
+
+from .events import Event, EventStore, parse_since
+
+# What each event type was, as a step of an interaction
+STAGES = {
+    "turn": "heard", "reply": "answered", "tool": "action", "activity": "activity", "reminder": "notified",
+    "reminder_ack": "acknowledged", "task": "job", "sms": "text in", "email": "email in", "voice": "voicemail in",
+}
+AI_MARGIN = timedelta(minutes=5)  # How far past a thread's events its model calls are looked for
+
+_CLOCK = re.compile(r"^(?:(today|yesterday)\s+)?(\d{1,2})(?::(\d{2}))?(?::(\d{2}))?\s*(am|pm)?$", re.IGNORECASE)
+
+
+def parse_moment(text: str, now: Optional[datetime] = None, day: Optional[datetime] = None) -> Tuple[datetime, bool]:
+    """
+    A moment: a clock time ("3:04pm", "15:04", "yesterday 9am") - the last
+    one that has passed, or on `day` - or what parse_since() takes. True
+    with a clock time. ValueError otherwise.
+    """
+    now = now or datetime.now()
+    phrase = " ".join((text or "").lower().split())
+    match = _CLOCK.match(phrase)
+    if not match or not (match.group(3) or match.group(5)):
+        return parse_since(phrase, now), False
+    when, hour, minute, second, half = match.groups()
+    hour = int(hour)
+    if half:
+        if not 1 <= hour <= 12:
+            raise ValueError(f"Unknown time '{text}' (try 3:04pm or 15:04)")
+        hour = hour % 12 + (12 if half == "pm" else 0)
+    base = day or now - timedelta(days=1 if when == "yesterday" else 0)
+    try:
+        moment = base.replace(hour=hour, minute=int(minute or 0), second=int(second or 0), microsecond=0)
+    except ValueError:
+        raise ValueError(f"Unknown time '{text}' (try 3:04pm or 15:04)")
+    if moment > now and not when and day is None:
+        moment -= timedelta(days=1)
+    return moment, True
+
+
+def parse_range(text: str, now: Optional[datetime] = None) -> Tuple[datetime, datetime]:
+    """
+    "3:04pm" (that minute, or second for 15:04:10), "9am..9:30am", "yesterday
+    9am..10am", or a start that parse_since() takes ("2h", "today") until now.
+    """
+    now = now or datetime.now()
+    first, sep, last = (text or "").partition("..")
+    start, clock = parse_moment(first, now)
+    if sep:
+        end = parse_moment(last, now, day=start)[0]  # A clock time on the start's day
+        if end <= start:
+            raise ValueError(f"'{text}' ends before it starts")
+        return start, end
+    if clock:
+        return start, start + timedelta(seconds=1 if first.count(":") == 2 else 60)
+    return start, now
+
+
+@dataclass
+class Step:
+    time: datetime
+    stage: str
+    summary: str
+    took: Optional[float] = None  # Its own latency in seconds, when known (a model call, the reply)
+    level: str = "info"
+
+
+@dataclass
+class Timeline:
+    """One interaction's steps in order (thread None: an event outside any)."""
+    thread: Optional[str]
+    steps: List[Step] = field(default_factory=list)
+
+    @property
+    def start(self) -> datetime:
+        return self.steps[0].time
+
+    @property
+    def duration(self) -> float:
+        return (self.steps[-1].time - self.start).total_seconds()
+
+    @property
+    def source(self) -> str:
+        """How it started: voice, chat, or the first step (a message coming in)."""
+        first = self.steps[0]
+        return first.summary if first.stage == "heard" else first.stage
+
+    def lines(self) -> List[str]:
+        if self.thread is None:
+            step = self.steps[0]
+            return [f"{step.time:%Y-%m-%d %H:%M:%S}  {'-':<6}  {step.stage:<12} {step.summary}"]
+        lines = [f"{self.start:%Y-%m-%d %H:%M:%S}  {self.thread:<6}  {self.source}: "
+                 f"{len(self.steps)} steps in {self.duration:.2f}s"]
+        previous = self.start
+        for step in self.steps:
+            after = (step.time - self.start).total_seconds()
+            gap = (step.time - previous).total_seconds()
+            took = f" ({step.took:.2f}s)" if step.took is not None else ""
+            lines.append(f"  {f'+{after:.2f}s':>9}  {gap:>6.2f}s  {step.stage:<12} {step.summary}{took}")
+            previous = step.time
+        return lines
+
+
+def _step(event: Event) -> Step:
+    took = event.data.get("latency") if event.type == "reply" else None
+    return Step(event.time, STAGES.get(event.type, event.type), event.summary, took, event.data.get("level", "info"))
+
+
+def _ai_step(call: Any) -> Step:
+    summary = f"{call.provider} {call.model} {call.purpose}" + (f" ✗ {call.error}" if call.error else "")
+    return Step(call.time, "ai", summary, call.latency, "error" if call.error else "info")
+
+
+def build_timelines(store: EventStore, start: Optional[datetime] = None, end: Optional[datetime] = None,
+                    ai_log: Any = None, thread: Optional[str] = None) -> List[Timeline]:
+    """The interactions seen between start and end (or one thread) in full, oldest first, with their model calls."""
+    if thread is not None:
+        events = store.query(thread=thread)
+    else:
+        in_range = store.query(since=start, until=end)
+        threads = list(dict.fromkeys(e.thread for e in in_range if e.thread))
+        events = [e for e in in_range if not e.thread] + [e for t in threads for e in store.query(thread=t)]
+    timelines: Dict[Any, Timeline] = {}
+    for n, event in enumerate(events):
+        timelines.setdefault(event.thread or n, Timeline(event.thread)).steps.append(_step(event))
+    threaded = [t for t in timelines.values() if t.thread]
+    if ai_log is not None and threaded:
+        first = min(t.steps[0].time for t in threaded)
+        last = max(t.steps[-1].time for t in threaded) + AI_MARGIN
+        for call in ai_log.query(since=first):
+            found = timelines.get(call.data.get("thread"))
+            if found is not None and call.time <= last:
+                found.steps.append(_ai_step(call))
+    for timeline in timelines.values():
+        timeline.steps.sort(key=lambda step: step.time)
+    return sorted(timelines.values(), key=lambda timeline: timeline.start)
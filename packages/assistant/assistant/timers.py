@@ -0,0 +1,337 @@
+"""
+Timers - Kitchen timers and a stopwatch, by voice.
+
+Handled in the dashboard before the model, like volume and undo:
+
+    "set a pasta timer for 9 minutes"      -> starts "pasta", 9:00
+    "timer for an hour and a half"         -> an unnamed timer
+    "how long is left on the pasta timer"  -> 8 minutes and 42 seconds
+    "cancel the pasta timer" / "cancel all timers"
+    "start the stopwatch" / "stop the stopwatch" / "lap" / "reset the stopwatch"
+
+Starting a timer with a name that's already running restarts it. Running
+timers count down in the dashboard footer; when one finishes the alarm
+beep plays and the assistant says so.
+
+Timers of PERSIST_SECONDS or longer are saved, so they survive the daemon
+restarting; one that ran out while it was down is announced as late. The
+stopwatch and short timers live in memory only.
+
+Storage: ~/.xswarm/timers/timers.json
+"""
+
+import json
+import logging
+import re
+from dataclasses import asdict, dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Dict, List, Optional
+
+from .capabilities import register_capability
+from .quick_math import numbers_to_digits
+from .verbalize import join_words
+
+logger = logging.getLogger(__name__)
+
+PERSIST_SECONDS = 5 * 60  # Timers at least this long survive a restart
+MAX_SECONDS = 24 * 60 * 60
+MAX_NAME_WORDS = 3
+
+_UNITS = {"h": 3600, "hr": 3600, "hrs": 3600, "hour": 3600, "hours": 3600, "m": 60, "min": 60, "mins": 60,
+          "minute": 60, "minutes": 60, "s": 1, "sec": 1, "secs": 1, "second": 1, "seconds": 1}
+_DURATION = re.compile(
+    r"\b(?P<amount>\d+(?:\.\d+)?|an?|half\s+an?)(?:\s+and\s+a\s+half)?[\s-]*"
+    r"(?P<unit>hours?|hrs?|h|minutes?|mins?|m|seconds?|secs?|s)\b(?P<half>\s+and\s+a\s+half)?",
+    re.IGNORECASE)
+_LEAD = re.compile(
+    r"^\s*(?:(?:ok(?:ay)?|hey|please|can\s+you|could\s+you|would\s+you|will\s+you)[,\s]+)*", re.IGNORECASE)
+_TIMER = re.compile(r"\btimers?\b", re.IGNORECASE)
+_START = re.compile(r"^(?:set|start|put\s+on|make|create|give\s+me|begin)\b|^(?:an?\s+)?[\w\s.-]*\btimer\s+for\b",
+                    re.IGNORECASE)
+_CANCEL = re.compile(r"\b(?:cancel|stop|delete|clear|kill|remove|turn\s+off|end)\b", re.IGNORECASE)
+_REMAINING = re.compile(r"\bhow\s+(?:much|long)\b.*\b(?:left|remaining|to\s+go)\b|\b(?:time\s+)?(?:left|remaining)\b\??$|"
+                        r"\b(?:what|which|any|list\s+(?:my\s+)?|show\s+(?:my\s+)?)\s*timers\b", re.IGNORECASE)
+_STOPWATCH = re.compile(r"\bstop\s*-?\s*watch\b", re.IGNORECASE)
+_FILLER = re.compile(r"\b(?:set|start|put|on|make|create|give|me|begin|cancel|stop|delete|clear|kill|remove|turn|"
+                     r"off|end|a|an|the|my|for|of|on|called|named|how|much|long|is|left|remaining|to|go|"
+                     r"time|timer|timers|what's|whats|what|which|any|list|show|do|i|have|got|are|there|running|in|and|half|"
+                     r"please)\b", re.IGNORECASE)
+
+
+def parse_duration(text: str) -> Optional[float]:
+    """Seconds in "9 minutes", "an hour and a half", "1 hour 30 minutes", "90s"; None if none is given."""
+    total, found = 0.0, False
+    for match in _DURATION.finditer(numbers_to_digits(text)):
+        amount = match.group("amount").lower()
+        value = 0.5 if amount.startswith("half") else 1.0 if amount in ("a", "an") else float(amount)
+        if " and a half" in match.group(0).lower():
+            value += 0.5
+        total += value * _UNITS[match.group("unit").lower()]
+        found = True
+    return total if found and total > 0 else None
+
+
+def spoken_duration(seconds: float) -> str:
+    """"1 hour and 30 minutes", "8 minutes and 42 seconds", "45 seconds"."""
+    seconds = int(round(seconds))
+    hours, rest = divmod(seconds, 3600)
+    minutes, secs = divmod(rest, 60)
+    parts = [f"{value} {unit}{'' if value == 1 else 's'}"
+             for value, unit in ((hours, "hour"), (minutes, "minute"), (secs if not hours else 0, "second")) if value]
+    return join_words(parts) if parts else "0 seconds"
+
+
+def duration_name(seconds: float) -> str:
+    """An unnamed timer's name: "10 minute", "1 hour 30 minute", "45 second"."""
+    return re.sub(r"s\b", "", spoken_duration(seconds)).replace(" and ", " ").replace(",", "")
+
+
+def clock_face(seconds: float) -> str:
+    """Countdown display: "8:42", "1:05:00"."""
+    seconds = max(0, int(seconds + 0.999))  # 8:41.2 left still shows 8:42
+    hours, rest = divmod(seconds, 3600)
+    return f"{hours}:{rest // 60:02d}:{rest % 60:02d}" if hours else f"{rest // 60}:{rest % 60:02d}"
+
+
+@dataclass
+class Timer:
+    name: str  # Unnamed timers are named for their length: "10 minute"
+    seconds: float
+    ends: str  # ISO datetime
+
+    @property
+    def end_time(self) -> datetime:
+        return datetime.fromisoformat(self.ends)
+
+    @property
+    def label(self) -> str:
+        return f"{self.name} timer"
+
+    def remaining(self, now: datetime) -> float:
+        return (self.end_time - now).total_seconds()
+
+
+@dataclass
+class Stopwatch:
+    started: Optional[datetime] = None  # While running
+    elapsed: float = 0.0  # Before the current run
+    laps: List[float] = field(default_factory=list)
+
+    @property
+    def running(self) -> bool:
+        return self.started is not None
+
+    def total(self, now: datetime) -> float:
+        return self.elapsed + ((now - self.started).total_seconds() if self.started else 0.0)
+
+
+@dataclass
+class TimerCommand:
+    action: str  # start, cancel, remaining, stopwatch
+    name: str = ""  # Timer name; "*" for all; for the stopwatch: start, stop, lap, reset or read
+    seconds: Optional[float] = None
+
+
+def _timer_name(text: str) -> str:
+    before = _TIMER.split(text, maxsplit=1)[0]
+    called = re.search(r"\btimer\s+(?:called|named)\s+([a-z][a-z\s'-]*?)(?:\s+for\b|$)", text, re.IGNORECASE)
+    if called:
+        before = called.group(1)
+    words = _FILLER.sub(" ", _DURATION.sub(" ", numbers_to_digits(before))).split()
+    words = [w for w in words if not re.fullmatch(r"[\d.]+|[-,.!?]+", w)]
+    if not words and parse_duration(before):
+        return duration_name(parse_duration(before))  # "cancel the 10 minute timer"
+    return " ".join(words[-MAX_NAME_WORDS:]).lower()
+
+
+def parse_timer_command(text: str, registry: Optional["TimerRegistry"] = None) -> Optional[TimerCommand]:
+    """
+    The timer or stopwatch request in `text`, or None. Questions that don't
+    say "timer" ("how long is left?") only count while a timer is running.
+    """
+    if not text or len(text) > 120:
+        return None
+    core = _LEAD.sub("", text).strip().rstrip(".!?")
+    if _STOPWATCH.search(core):
+        rest = _STOPWATCH.sub(" ", core).lower()
+        if re.search(r"\b(?:reset|clear|zero)\b", rest):
+            return TimerCommand("stopwatch", "reset")
+        if re.search(r"\b(?:lap|split)\b", rest):
+            return TimerCommand("stopwatch", "lap")
+        if re.search(r"\b(?:stop|pause|halt|end)\b", rest):
+            return TimerCommand("stopwatch", "stop")
+        if re.search(r"\b(?:start|begin|resume|go|run)\b", rest) and not re.search(r"\bhow\b", rest):
+            return TimerCommand("stopwatch", "start")
+        return TimerCommand("stopwatch", "read")
+    if re.fullmatch(r"lap|split", core, re.IGNORECASE) and registry and registry.stopwatch.running:
+        return TimerCommand("stopwatch", "lap")
+    if not _TIMER.search(core):
+        if registry and registry.timers and _REMAINING.search(core) and len(core.split()) <= 8:
+            return TimerCommand("remaining")
+        return None
+    if _CANCEL.search(core) and not _REMAINING.search(core):
+        name = "*" if re.search(r"\ball\b.*\btimers\b|\btimers\b", core, re.IGNORECASE) else _timer_name(core)
+        return TimerCommand("cancel", name)
+    if _REMAINING.search(core):
+        return TimerCommand("remaining", _timer_name(core))
+    seconds = parse_duration(core)
+    if seconds is not None and _START.search(core):
+        return TimerCommand("start", _timer_name(core), seconds)
+    return None
+
+
+class TimerRegistry:
+    """Running timers and the stopwatch; long timers are kept on disk."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "timers"
+
+    def __init__(self, storage_dir: Optional[Path] = None):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.timers: Dict[str, Timer] = {}
+        self.stopwatch = Stopwatch()
+        self._load()
+
+    def _path(self) -> Path:
+        return self.storage_dir / "timers.json"
+
+    def _load(self) -> None:
+        path = self._path()
+        if not path.exists():
+            return
+        try:
+            with open(path, "r", encoding="utf-8") as f:
+                for raw in json.load(f).get("timers", []):
+                    timer = Timer(**raw)
+                    self.timers[timer.name] = timer
+        except Exception as e:
+            logger.warning(f"Failed to load timers: {e}")
+
+    def _save(self) -> None:
+        long_timers = [asdict(t) for t in self.timers.values() if t.seconds >= PERSIST_SECONDS]
+        try:
+            self.storage_dir.mkdir(parents=True, exist_ok=True)
+            with open(self._path(), "w", encoding="utf-8") as f:
+                json.dump({"timers": long_timers}, f, indent=2, ensure_ascii=False)
+        except Exception as e:
+            logger.warning(f"Failed to save timers: {e}")
+
+    def start(self, name: str, seconds: float, now: datetime) -> Timer:
+        name = name or duration_name(seconds)
+        timer = Timer(name, seconds, (now + timedelta(seconds=seconds)).isoformat())
+        self.timers[name] = timer
+        self._save()
+        return timer
+
+    def cancel(self, name: str) -> List[Timer]:
+        if name == "*":
+            cancelled = list(self.timers.values())
+        elif name in self.timers:
+            cancelled = [self.timers[name]]
+        elif not name and len(self.timers) == 1:
+            cancelled = list(self.timers.values())
+        else:
+            return []
+        for timer in cancelled:
+            del self.timers[timer.name]
+        self._save()
+        return cancelled
+
+    def running(self, now: datetime) -> List[Timer]:
+        """Timers still counting down, soonest first."""
+        return sorted((t for t in self.timers.values() if t.remaining(now) > 0), key=lambda t: t.ends)
+
+    def finished(self, now: datetime) -> List[Timer]:
+        """Timers that ran out (removed); ones that ended while the assistant was down included."""
+        done = [t for t in self.timers.values() if t.remaining(now) <= 0]
+        for timer in done:
+            del self.timers[timer.name]
+        if done:
+            self._save()
+        return sorted(done, key=lambda t: t.ends)
+
+    def countdown(self, now: datetime) -> List[str]:
+        """Footer entries: "pasta 8:42", "3:10", "stopwatch 1:23"."""
+        entries = [f"{t.name} {clock_face(t.remaining(now))}" for t in self.running(now)]
+        if self.stopwatch.running or self.stopwatch.elapsed:
+            entries.append(f"stopwatch {clock_face(self.stopwatch.total(now))}")
+        return entries
+
+    def apply(self, command: TimerCommand, now: datetime) -> str:
+        """Carry out a parsed request; returns what to say."""
+        if command.action == "start":
+            if command.seconds > MAX_SECONDS:
+                return "Timers can run for up to 24 hours."
+            restarted = command.name in self.timers
+            timer = self.start(command.name, command.seconds, now)
+            if restarted:
+                return f"Restarted the {timer.label}: {spoken_duration(timer.seconds)}."
+            if not command.name:
+                return f"{spoken_duration(timer.seconds).capitalize()}, starting now."
+            return f"{timer.name.capitalize()} timer set for {spoken_duration(timer.seconds)}."
+        if command.action == "cancel":
+            cancelled = self.cancel(command.name)
+            if cancelled:
+                return f"Cancelled {join_words([f'the {t.label}' for t in cancelled])}."
+            if not self.timers:
+                return "There aren't any timers running."
+            return f"Which one? You have {self._names()}."
+        if command.action == "remaining":
+            running = self.running(now)
+            if command.name:
+                running = [t for t in running if t.name == command.name]
+            if not running:
+                return f"There's no {command.name} timer running." if command.name else \
+                    "There aren't any timers running."
+            return " ".join(f"{spoken_duration(t.remaining(now)).capitalize()} left on the {t.label}."
+                            for t in running)
+        return self._stopwatch(command.name, now)
+
+    def _stopwatch(self, action: str, now: datetime) -> str:
+        watch = self.stopwatch
+        if action == "start":
+            if watch.running:
+                return f"The stopwatch is already running: {spoken_duration(watch.total(now))}."
+            watch.started = now
+            return "Stopwatch started." if not watch.elapsed else "Stopwatch resumed."
+        if action == "stop":
+            if not watch.running:
+                return "The stopwatch isn't running."
+            watch.elapsed, watch.started = watch.total(now), None
+            return f"Stopped at {spoken_duration(watch.elapsed)}."
+        if action == "reset":
+            self.stopwatch = Stopwatch()
+            return "Stopwatch reset."
+        if action == "lap":
+            if not watch.running:
+                return "The stopwatch isn't running."
+            watch.laps.append(watch.total(now))
+            return f"Lap {len(watch.laps)}: {spoken_duration(watch.laps[-1])}."
+        if not watch.running and not watch.elapsed:
+            return "The stopwatch isn't running."
+        return f"{spoken_duration(watch.total(now)).capitalize()}{'' if watch.running else ', stopped'}."
+
+    def _names(self) -> str:
+        return join_words([f"the {t.label}" for t in self.timers.values()])
+
+
+_registry: Optional[TimerRegistry] = None
+
+
+def get_timer_registry() -> TimerRegistry:
+    """Get the global timer registry (timers saved by an earlier run are loaded)."""
+    global _registry
+    if _registry is None:
+        _registry = TimerRegistry()
+    return _registry
+
+
+def set_timer_registry(registry: Optional[TimerRegistry]) -> None:
+    global _registry
+    _registry = registry
+
+
+register_capability("Timers", "Kitchen timers and a stopwatch",
+                    ["set a pasta timer for 9 minutes", "how long is left on the pasta timer",
+                     "cancel all timers", "start the stopwatch"],
+                    keywords=("countdown", "cooking", "lap"))
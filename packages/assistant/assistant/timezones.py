@@ -0,0 +1,160 @@
+"""
+Timezones - The home timezone, OS detection and travel mode.
+
+Planner times are stored as naive wall-clock times in the home zone
+(config.timezone, or the OS zone when unset - system_timezone() reads TZ,
+/etc/timezone and the /etc/localtime link). Everything else converts at
+the edges:
+
+- travel mode (config.travel_timezone, set_travel_mode tool): times are
+  shown, spoken and entered in the travel zone - "3pm" said in London is
+  stored as 10:00 for a New York home - and calendar views show both
+  ("15:00 (10:00 home)")
+- speech (verbalize.py) converts to the display zone before wording a time
+- server sync (scheduler_client.appointment_payload) sends home times with
+  the home zone's offset
+
+When the OS zone no longer matches the configured home (the laptop moved),
+travel_suggestion() says so once at startup rather than silently shifting
+every stored time.
+"""
+
+import logging
+import os
+from dataclasses import replace
+from datetime import datetime
+from pathlib import Path
+from typing import Mapping, Optional
+from zoneinfo import ZoneInfo, available_timezones
+
+from .dates import DateSettings, get_date_settings, set_date_settings
+
+logger = logging.getLogger(__name__)
+
+LOCALTIME_PATH = Path("/etc/localtime")
+TIMEZONE_FILE = Path("/etc/timezone")
+
+
+def system_timezone(environ: Optional[Mapping[str, str]] = None,
+                    localtime: Path = LOCALTIME_PATH, timezone_file: Path = TIMEZONE_FILE) -> Optional[str]:
+    """
+    The OS timezone as an IANA name ("Europe/London"), or None when it can't
+    be told: TZ, then /etc/timezone (Debian), then where /etc/localtime links
+    to (.../zoneinfo/Europe/London).
+    """
+    environ = os.environ if environ is None else environ
+    candidates = [environ.get("TZ", "").lstrip(":")]
+    try:
+        candidates.append(timezone_file.read_text().strip())
+    except OSError:
+        pass
+    try:
+        target = str(localtime.resolve()) if localtime.is_symlink() else ""
+        if "zoneinfo/" in target:
+            candidates.append(target.split("zoneinfo/", 1)[1])
+    except OSError:
+        pass
+    for name in candidates:
+        if name and find_timezone(name) == name:
+            return name
+    return None
+
+
+def find_timezone(text: str) -> Optional[str]:
+    """
+    An IANA name from what the user said: "Europe/London", "london",
+    "new york", "UTC". None when nothing matches.
+    """
+    wanted = text.strip().replace(" ", "_")
+    if not wanted:
+        return None
+    names = available_timezones()
+    if wanted in names:
+        return wanted
+    lowered = wanted.lower()
+    for name in sorted(names):
+        if name.lower() == lowered or name.rsplit("/", 1)[-1].lower() == lowered:
+            return name
+    return None
+
+
+def home_timezone_name(settings: Optional[DateSettings] = None) -> str:
+    """Configured home zone, else the detected OS zone, else "local"."""
+    settings = settings or get_date_settings()
+    return settings.timezone or system_timezone() or "local"
+
+
+def to_display(value: datetime, settings: Optional[DateSettings] = None) -> datetime:
+    """A naive home time (as the planner stores it) as naive wall time in the display zone."""
+    settings = settings or get_date_settings()
+    travel = settings.travel_zone()
+    if value.tzinfo is None and travel is None:
+        return value
+    if value.tzinfo is None:
+        home = settings.home_zone()
+        value = value.replace(tzinfo=home) if home else value.astimezone()
+    target = settings.display_zone()
+    return (value.astimezone(target) if target else value.astimezone()).replace(tzinfo=None)
+
+
+def from_display(value: datetime, settings: Optional[DateSettings] = None) -> datetime:
+    """A naive time the user gave in the display zone as naive home time, for storing."""
+    settings = settings or get_date_settings()
+    travel = settings.travel_zone()
+    if travel is None:
+        return value
+    home = settings.home_zone()
+    moved = value.replace(tzinfo=travel)
+    return (moved.astimezone(home) if home else moved.astimezone()).replace(tzinfo=None)
+
+
+def display_now(settings: Optional[DateSettings] = None) -> datetime:
+    """Now as naive wall time in the display zone."""
+    zone = (settings or get_date_settings()).display_zone()
+    return datetime.now(zone).replace(tzinfo=None) if zone else datetime.now()
+
+
+def format_dual(value: datetime, settings: Optional[DateSettings] = None, fmt: str = "%H:%M") -> str:
+    """
+    A naive home time for calendar views: "15:00" normally, "15:00 (10:00
+    home)" in travel mode when the zones differ.
+    """
+    settings = settings or get_date_settings()
+    shown = to_display(value, settings)
+    if settings.travel_zone() is None or shown == value:
+        return shown.strftime(fmt)
+    return f"{shown.strftime(fmt)} ({value.strftime(fmt)} home)"
+
+
+def set_travel_timezone(name: Optional[str], settings: Optional[DateSettings] = None) -> DateSettings:
+    """Turn travel mode on (an IANA name) or off (None), install the settings and save the config."""
+    settings = settings or get_date_settings()
+    updated = replace(settings, travel_timezone=name)
+    set_date_settings(updated)
+    if settings.config is not None:
+        settings.config.travel_timezone = name
+        try:
+            settings.config.save_to_file()
+        except Exception as e:
+            logger.warning(f"Could not save travel mode: {e}")
+    return updated
+
+
+def travel_suggestion(settings: Optional[DateSettings] = None,
+                      detected: Optional[str] = None) -> Optional[str]:
+    """
+    A note for the activity feed when the OS zone differs from the configured
+    home and travel mode is off (the machine has moved), else None.
+    """
+    settings = settings or get_date_settings()
+    detected = detected or system_timezone()
+    if not settings.timezone or not detected or settings.travel_timezone:
+        return None
+    now = datetime.now()
+    try:
+        if now.astimezone(ZoneInfo(detected)).utcoffset() == now.astimezone(ZoneInfo(settings.timezone)).utcoffset():
+            return None
+    except Exception:
+        return None
+    return (f"🧳 This computer is on {detected} time but home is {settings.timezone} - "
+            f"say \"travel mode {detected.rsplit('/', 1)[-1].replace('_', ' ')}\" to show times in local time")
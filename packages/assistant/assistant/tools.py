@@ -16,6 +16,14 @@ from dataclasses import dataclass
 from pathlib import Path
 import subprocess
 
+from .calendar_core import VISIBILITIES, split_occurrence_id
+from .capabilities import register_capability
+from .confirmation import ConfirmationLevel, get_confirmation_policy
+from .dialogue import get_dialogue_state
+from .events import record_event
+from .household import get_household
+from .intents import score_call
+
 # ==============================================================================
 # REGISTRY & DATA STRUCTURES
 # ==============================================================================
@@ -127,17 +135,54 @@ class ToolRegistry:
     def list_tools(self) -> Dict[str, str]:
         return {name: tool.description for name, tool in self._tools.items()}
 
-    async def execute_tool(self, name: str, args: Dict[str, Any]) -> Dict[str, Any]:
-        """Execute a tool by name with arguments."""
+    async def execute_tool(self, name: str, args: Dict[str, Any], confirmed: bool = False) -> Dict[str, Any]:
+        """
+        Execute a tool by name with arguments.
+
+        Calls outside the active household profile's scope (see household.py)
+        are refused. Calls that need an explicit yes or PIN (see
+        confirmation.py) are held instead; the result tells the model to ask
+        the user. confirmed=True runs a call the user has already confirmed.
+        """
         tool = self._tools.get(name)
         if not tool:
             return {"success": False, "message": f"Tool '{name}' not found"}
-            
+        refusal = get_household().refusal(name)
+        if refusal:
+            return {"success": False, "message": refusal}
+
+        # "Move it to 4": "it" is the item the conversation is about (see dialogue.py)
+        args = get_dialogue_state().resolve_args(name, args, tool.parameters)
+
+        policy = get_confirmation_policy()
+        level = policy.required_level(name)
+        if not confirmed and level in (ConfirmationLevel.EXPLICIT_YES, ConfirmationLevel.PIN):
+            pending = policy.hold(name, args, level, registry=self)
+            return {
+                "success": True,
+                "result": f"⏸ Not done yet - needs the user's confirmation. Ask them: {pending.prompt()}",
+                "pending_confirmation": True,
+            }
+
+        # Not sure the call acts on what the user meant (see intents.py): check first
+        threshold = policy.confidence_threshold(name)
+        score = score_call(name, args) if not confirmed and threshold > 0 else None
+        if score is not None and score.confidence < threshold:
+            pending = policy.hold(name, args, ConfirmationLevel.EXPLICIT_YES, registry=self, question=score.question)
+            return {
+                "success": True,
+                "result": f"⏸ Not done yet - not sure that's what they meant ({score.reason}). Ask them: {pending.prompt()}",
+                "pending_confirmation": True,
+            }
+
         try:
             if inspect.iscoroutinefunction(tool.func):
                 result = await tool.func(**args)
             else:
                 result = tool.func(**args)
+            policy.record_executed(name, level, result)
+            get_dialogue_state().note_call(name, args, result)
+            record_event("tool", name, {"name": name})  # Most used commands (analytics.py)
             return {"success": True, "result": result}
         except Exception as e:
             return {"success": False, "message": str(e)}
@@ -284,10 +329,12 @@ class ToolExecutor:
 
         try:
             logger.info(f"Executing tool: {tool_name} args={args} kwargs={kwargs}")
-            if asyncio.iscoroutinefunction(tool_def.func):
-                result = await tool_def.func(*args, **kwargs)
-            else:
-                result = tool_def.func(*args, **kwargs)
+            # Same confirmation policy as function calling (bind positional args to names)
+            call_args = inspect.signature(tool_def.func).bind_partial(*args, **kwargs).arguments
+            result = await self.registry.execute_tool(tool_name, dict(call_args))
+            if not result["success"]:
+                return f"Error executing '{tool_name}': {result['message']}"
+            result = result["result"]
             logger.info(f"Tool result: {result}")
             return result
         except Exception as e:
@@ -522,8 +569,14 @@ send_email_tool = Tool(
     handler=send_email_handler
 )
 
-async def make_call_handler(message: str, questions: Optional[list] = None, persona_name: str | None = None) -> Dict[str, Any]:
+async def make_call_handler(message: str, questions: Optional[list] = None, persona_name: str | None = None, priority: str = "normal") -> Dict[str, Any]:
     try:
+        from .notifications import get_notification_policy
+        decision = get_notification_policy().check("call", priority)
+        if not decision.allowed:
+            resume = decision.resume_at.strftime("%H:%M") if decision.resume_at else "later"
+            return {"success": False, "message": f"Call not placed: quiet hours until {resume}. Use priority='emergency' if urgent."}
+
         to_number = os.getenv("USER_PHONE") or os.getenv("XSWARM_DEV_ADMIN_PHONE")
         if not to_number:
             config_path = Path("config.toml")
@@ -538,8 +591,16 @@ async def make_call_handler(message: str, questions: Optional[list] = None, pers
         if not persona:
             return {"success": False, "message": "Persona not found"}
 
+        from .quota import get_quota_manager
+        quota = get_quota_manager()
+        allowance = quota.check("voice_minutes") if quota else None
+        if allowance and not allowance.allowed:
+            return {"success": False, "message": f"Call not placed: {allowance.message}"}
+
         caller = get_caller()
         result = await caller.make_call(to_number=to_number, message=message, persona=persona, questions=questions)
+        if result.get("success") and allowance and allowance.message:
+            result["message"] = allowance.message
         return result if result.get("success") else {"success": False, "message": f"Failed: {result.get('error')}"}
     except Exception as e:
         return {"success": False, "message": f"Error: {str(e)}"}
@@ -550,7 +611,8 @@ make_call_tool = Tool(
     parameters=[
         ToolParameter("message", "string", "Message to speak"),
         ToolParameter("questions", "array", "Optional questions", required=False),
-        ToolParameter("persona_name", "string", "Optional persona", required=False)
+        ToolParameter("persona_name", "string", "Optional persona", required=False),
+        ToolParameter("priority", "string", "low, normal, high or emergency (emergency bypasses quiet hours)", required=False)
     ],
     handler=make_call_handler
 )
@@ -605,6 +667,11 @@ def set_planner_data(planner: "PlannerData"):  # noqa: F821
     _planner_data = planner
 
 
+register_capability("Tasks and habits", "Tasks, habits, goals and promises to people, planned around your day",
+                    ["what's on today?", "I did my workout", "plan my day", "how's my savings goal going?"],
+                    category="Planning", keywords=("todo", "streak", "goal", "commitment", "idea"))
+
+
 @registry.register("find_similar_tasks", "Check for existing tasks similar to a description (ALWAYS call before add_task)")
 def find_similar_tasks(description: str, threshold: float = 0.6) -> str:
     """
@@ -644,7 +711,8 @@ def add_task(
     duration_min: int = 30,
     due_date: str = "",
     project_id: str = "",
-    force: bool = False
+    force: bool = False,
+    tags: str = ""
 ) -> str:
     """
     Add a new task with GTD attributes.
@@ -654,8 +722,12 @@ def add_task(
 
     Args:
         force: If True, skip duplicate check and add anyway
+        tags: Comma-separated categories (work, personal, health...); inferred from the title if empty
     """
+    from .categories import format_tags, resolve_tags
+
     planner = get_planner_data()
+    tag_list, title = resolve_tags(tags, title)
 
     # Check for duplicates unless force=True
     if not force:
@@ -668,6 +740,12 @@ def add_task(
             result += "To update existing, use: update_task(task_id, ...)"
             return result
 
+    if due_date:
+        try:
+            due_date = _parse_natural_date(due_date).split("T")[0]
+        except ValueError as e:
+            return _date_error(e)
+
     # Parse contexts from comma-separated string
     context_list = [c.strip() for c in contexts.split(",") if c.strip()] if contexts else []
 
@@ -678,10 +756,11 @@ def add_task(
         energy=energy,
         duration_min=duration_min,
         due_date=due_date if due_date else None,
-        project_id=project_id if project_id else None
+        project_id=project_id if project_id else None,
+        tags=tag_list
     )
 
-    return f"✓ Added task: '{task.title}' (priority: {task.priority}, id: {task.id})"
+    return f"✓ Added task: '{task.title}'{format_tags(task.tags)} (priority: {task.priority}, id: {task.id})"
 
 
 @registry.register("complete_task", "Mark a task as complete")
@@ -689,11 +768,16 @@ def complete_task(task_id: str) -> str:
     """Mark a task as complete."""
     planner = get_planner_data()
 
+    before = planner.get_task(task_id)
     task = planner.complete_task(task_id)
     if not task:
         return f"✗ Task '{task_id}' not found"
 
-    return f"✓ Completed task: '{task.title}'"
+    _record_undo("planner_fields", f"Completed task '{task.title}'", {
+        "collection": "tasks", "id": task_id,
+        "fields": {"status": before.status, "completed_at": before.completed_at},
+    })
+    return f"✓ Completed task: '{task.title}'{UNDO_HINT}"
 
 
 @registry.register("log_habit", "Record completion of a habit (updates streak)")
@@ -803,9 +887,13 @@ def get_todays_schedule() -> str:
     - Habits due today (with preferred times)
     - Unscheduled tasks marked as 'next'
 
-    Items are shown as a checklist with completion status.
+    Items are shown as a checklist with completion status. In travel mode
+    meeting times show local and home time. Back-to-back meetings too far
+    apart to travel between (from geocoded locations) are flagged.
     """
     from datetime import date, datetime
+    from .geocoding import travel_conflicts
+    from .timezones import format_dual
 
     planner = get_planner_data()
     today = date.today()
@@ -821,7 +909,7 @@ def get_todays_schedule() -> str:
     # 1. Calendar events
     events = planner.get_todays_events()
     for e in events:
-        time = e.start_time[11:16] if "T" in e.start_time else "00:00"
+        time = format_dual(datetime.fromisoformat(e.start_time)) if "T" in e.start_time else "00:00"
         timed_items.append((time, "event", e.title, 60, False, e.id))
 
     # 2. Scheduled tasks (including completed ones from today)
@@ -866,6 +954,11 @@ def get_todays_schedule() -> str:
     else:
         lines.append("  No items scheduled yet.")
 
+    conflicts = travel_conflicts(events)
+    if conflicts:
+        lines.append("")
+        lines.extend(f"⚠ Travel: {c.text}" for c in conflicts)
+
     # 4. Unscheduled tasks (next actions)
     next_tasks = planner.get_tasks(status="next")
     if next_tasks:
@@ -1202,13 +1295,21 @@ def reschedule_task(task_id: str, new_time: str = "", new_date: str = "") -> str
 
     if new_date:
         # Move to different day
-        parsed_date = _parse_natural_date(new_date, "09:00")
+        try:
+            parsed_date = _parse_natural_date(new_date, "09:00")
+        except ValueError as e:
+            return _date_error(e)
         date_only = parsed_date.split("T")[0]
         planner.update_task(task_id, due_date=date_only, scheduled_time=None, status="next")
         return f"✓ Moved '{task.title}' to {date_only}"
 
     elif new_time:
         # Change time on same day
+        from .dates import parse_time_expression
+        time_of_day = parse_time_expression(new_time)
+        if time_of_day is None:
+            return f"✗ Couldn't understand the time '{new_time}' (try '14:30' or 'half past two')"
+        new_time = time_of_day
         planner.update_task(task_id, scheduled_time=new_time, status="scheduled")
         return f"✓ Rescheduled '{task.title}' to {new_time}"
 
@@ -1315,19 +1416,24 @@ def add_commitment(
     description: str,
     to_whom: str,
     deadline: str,
-    project_id: str = ""
+    project_id: str = "",
+    tags: str = ""
 ) -> str:
-    """Add a commitment to track."""
+    """Add a commitment to track. tags: comma-separated categories, inferred if empty."""
+    from .categories import format_tags, resolve_tags
+
     planner = get_planner_data()
+    tag_list, description = resolve_tags(tags, description)
 
     planner.add_commitment(
         description=description,
         to_whom=to_whom,
         deadline=deadline,
-        project_id=project_id if project_id else None
+        project_id=project_id if project_id else None,
+        tags=tag_list
     )
 
-    return f"✓ Added commitment to {to_whom}: '{description}' (due: {deadline})"
+    return f"✓ Added commitment to {to_whom}: '{description}'{format_tags(tag_list)} (due: {deadline})"
 
 
 @registry.register("mark_planning_done", "Mark daily planning session as complete")
@@ -1557,7 +1663,8 @@ def delete_goal(goal_id: str) -> str:
 
     name = goal.name
     if planner.delete_goal(goal_id):
-        return f"✓ Deleted goal: '{name}'"
+        _record_undo("planner_restore", f"Deleted goal '{name}'", {"collection": "goals", "id": goal_id})
+        return f"✓ Deleted goal: '{name}'{UNDO_HINT}"
     return "✗ Failed to delete goal"
 
 
@@ -1649,9 +1756,12 @@ def update_task(
     priority: str = "",
     status: str = "",
     due_date: str = "",
-    notes: str = ""
+    notes: str = "",
+    tags: str = ""
 ) -> str:
-    """Update a task's properties."""
+    """Update a task's properties. tags replaces the categories (comma-separated, "none" clears them)."""
+    from .categories import normalize_tags
+
     planner = get_planner_data()
 
     task = planner.get_task(task_id)
@@ -1669,6 +1779,8 @@ def update_task(
         updates["due_date"] = due_date
     if notes:
         updates["notes"] = notes
+    if tags:
+        updates["tags"] = [] if tags.strip().lower() == "none" else normalize_tags(tags)
 
     task = planner.update_task(task_id, **updates)
     return f"✓ Updated task: '{task.title}'"
@@ -1685,7 +1797,8 @@ def delete_task(task_id: str) -> str:
 
     title = task.title
     if planner.delete_task(task_id):
-        return f"✓ Deleted task: '{title}'"
+        _record_undo("planner_restore", f"Deleted task '{title}'", {"collection": "tasks", "id": task_id})
+        return f"✓ Deleted task: '{title}'{UNDO_HINT}"
     return "✗ Failed to delete task"
 
 
@@ -1700,7 +1813,8 @@ def delete_project(project_id: str) -> str:
 
     name = project.name
     if planner.delete_project(project_id):
-        return f"✓ Deleted project: '{name}'"
+        _record_undo("planner_restore", f"Deleted project '{name}'", {"collection": "projects", "id": project_id})
+        return f"✓ Deleted project: '{name}'{UNDO_HINT}"
     return "✗ Failed to delete project"
 
 
@@ -1738,6 +1852,60 @@ def remove_project_folder(project_id: str, folder_path: str) -> str:
     return f"✗ Folder '{folder_path}' not in project"
 
 
+register_capability("Projects", "Your projects, and answers from their code and docs",
+                    ["where do we configure retries in project X?", "summarize the architecture doc"],
+                    category="Planning", keywords=("code", "repo", "docs"))
+
+
+@registry.register("ask_project", "Answer a question about a project from its indexed repo and docs, citing the files")
+async def ask_project(project: str, question: str, language: str = "") -> str:
+    """
+    Answer from the project's folders as indexed by `xswarm dev project index` (see project_docs.py).
+
+    Args:
+        project: Project id or name
+        question: The question as asked, e.g. "where do we configure retries?"
+        language: Only search files in this language ("rust", "python", "markdown", ...)
+    """
+    from .project_docs import ProjectDocsError, find_project, get_project_docs
+
+    docs = get_project_docs()
+    if docs is None:
+        return "✗ Project document search is not set up"
+    found = find_project(get_planner_data(), project)
+    if not found:
+        return f"✗ Project '{project}' not found"
+    try:
+        answer = await docs.ask(found, question, language)
+    except ProjectDocsError as e:
+        return f"✗ {e}"
+    return answer.render()
+
+
+@registry.register("summarize_project_doc", "Summarize one of a project's documents (kept from indexing, so instant)")
+async def summarize_project_doc(project: str, document: str) -> str:
+    """
+    A project document's summary, e.g. "summarize the architecture doc" (see project_docs.py).
+
+    Args:
+        project: Project id or name
+        document: Which document, as said: "architecture doc", "the deployment guide", "README"
+    """
+    from .project_docs import ProjectDocsError, find_project, get_project_docs
+
+    docs = get_project_docs()
+    if docs is None:
+        return "✗ Project document search is not set up"
+    found = find_project(get_planner_data(), project)
+    if not found:
+        return f"✗ Project '{project}' not found"
+    try:
+        answer = await docs.summarize(found, document)
+    except ProjectDocsError as e:
+        return f"✗ {e}"
+    return answer.render()
+
+
 @registry.register("update_habit", "Update a habit's properties")
 def update_habit(
     habit_id: str,
@@ -1778,7 +1946,8 @@ def delete_habit(habit_id: str) -> str:
 
     name = habit.name
     if planner.delete_habit(habit_id):
-        return f"✓ Deleted habit: '{name}'"
+        _record_undo("planner_restore", f"Deleted habit '{name}'", {"collection": "habits", "id": habit_id})
+        return f"✓ Deleted habit: '{name}'{UNDO_HINT}"
     return "✗ Failed to delete habit"
 
 
@@ -1787,11 +1956,15 @@ def complete_commitment(commitment_id: str) -> str:
     """Complete a commitment."""
     planner = get_planner_data()
 
+    before = next((c for c in planner.get_commitments(include_completed=True) if c.id == commitment_id), None)
     commitment = planner.complete_commitment(commitment_id)
     if not commitment:
         return f"✗ Commitment '{commitment_id}' not found"
 
-    return f"✓ Completed commitment: '{commitment.description}'"
+    _record_undo("planner_fields", f"Completed commitment '{commitment.description}'", {
+        "collection": "commitments", "id": commitment_id, "fields": {"status": before.status},
+    })
+    return f"✓ Completed commitment: '{commitment.description}'{UNDO_HINT}"
 
 
 @registry.register("delete_commitment", "Delete a commitment by ID")
@@ -1811,7 +1984,8 @@ def delete_commitment(commitment_id: str) -> str:
         return f"✗ Commitment '{commitment_id}' not found"
 
     if planner.delete_commitment(commitment_id):
-        return f"✓ Deleted commitment: '{desc}'"
+        _record_undo("planner_restore", f"Deleted commitment '{desc}'", {"collection": "commitments", "id": commitment_id})
+        return f"✓ Deleted commitment: '{desc}'{UNDO_HINT}"
     return "✗ Failed to delete commitment"
 
 
@@ -1821,7 +1995,8 @@ def delete_idea(idea_id: str) -> str:
     planner = get_planner_data()
 
     if planner.delete_idea(idea_id):
-        return f"✓ Deleted idea: {idea_id}"
+        _record_undo("planner_restore", f"Deleted idea {idea_id}", {"collection": "ideas", "id": idea_id})
+        return f"✓ Deleted idea: {idea_id}{UNDO_HINT}"
     return f"✗ Idea '{idea_id}' not found"
 
 
@@ -1830,13 +2005,16 @@ def delete_idea(idea_id: str) -> str:
 # ==============================================================================
 
 @registry.register("list_tasks", "List tasks with optional filtering")
-def list_tasks(status: str = "", project_id: str = "") -> str:
-    """List tasks, optionally filtered."""
+def list_tasks(status: str = "", project_id: str = "", tag: str = "") -> str:
+    """List tasks, optionally filtered by status, project or tag (e.g. "work")."""
+    from .categories import format_tags
+
     planner = get_planner_data()
 
     tasks = planner.get_tasks(
         status=status if status else None,
-        project_id=project_id if project_id else None
+        project_id=project_id if project_id else None,
+        tag=tag if tag else None
     )
 
     if not tasks:
@@ -1845,7 +2023,7 @@ def list_tasks(status: str = "", project_id: str = "") -> str:
     lines = [f"Tasks ({len(tasks)}):"]
     for t in tasks:
         due = f" (due: {t.due_date})" if t.due_date else ""
-        lines.append(f"  [{t.id}] {t.title} - {t.status}, {t.priority}{due}")
+        lines.append(f"  [{t.id}] {t.title}{format_tags(t.tags)} - {t.status}, {t.priority}{due}")
 
     return "\n".join(lines)
 
@@ -1887,11 +2065,13 @@ def list_habits() -> str:
 
 
 @registry.register("list_commitments", "List commitments")
-def list_commitments(include_completed: bool = False) -> str:
-    """List commitments."""
+def list_commitments(include_completed: bool = False, tag: str = "") -> str:
+    """List commitments, optionally only those with a tag (e.g. "work")."""
+    from .categories import format_tags
+
     planner = get_planner_data()
 
-    commitments = planner.get_commitments(include_completed=include_completed)
+    commitments = planner.get_commitments(include_completed=include_completed, tag=tag if tag else None)
 
     if not commitments:
         return "No commitments found."
@@ -1899,7 +2079,7 @@ def list_commitments(include_completed: bool = False) -> str:
     lines = [f"Commitments ({len(commitments)}):"]
     for c in commitments:
         status_icon = "✓" if c.status == "complete" else "○"
-        lines.append(f"  [{c.id}] {status_icon} To {c.to_whom}: {c.description} (due: {c.deadline})")
+        lines.append(f"  [{c.id}] {status_icon} To {c.to_whom}: {c.description}{format_tags(c.tags)} (due: {c.deadline})")
 
     return "\n".join(lines)
 
@@ -1928,85 +2108,83 @@ def list_ideas(show_processed: bool = False) -> str:
 
 def _parse_natural_date(date_str: str, time_str: str = "09:00") -> str:
     """
-    Parse natural language dates into ISO format.
+    Parse natural language dates into ISO format (grammar in dates.py).
 
     Accepts:
-    - Day names: "Monday", "Tuesday", "friday" (finds next occurrence)
-    - Relative: "today", "tomorrow", "next Monday"
-    - ISO format: "2025-12-05" (passed through)
+    - Day names: "Monday", "next Friday", "friday next week"
+    - Relative: "today", "tomorrow", "in 3 days", "next month on the 3rd"
+    - Calendar days: "March 3", "the 3rd", "3/14", "2025-12-05"
+    - A time in the date itself: "friday at half past three"
     - ISO datetime: "2025-12-05T10:00" (passed through)
 
-    Returns ISO datetime string like "2025-12-05T09:00:00"
+    time_str may be spoken too ("4pm", "a quarter to 5").
+
+    Numeric dates follow the configured day/month order; one that reads
+    both ways ("04/05/2025") raises AmbiguousDateError unless the user
+    turned confirm_ambiguous_dates off.
+
+    Returns ISO datetime string like "2025-12-05T09:00:00".
+    Raises ValueError when the date or time isn't understood.
     """
-    from datetime import datetime, date, timedelta
+    from datetime import datetime
+    from .dates import (AmbiguousDateError, ambiguous_readings, get_date_settings, parse_natural_datetime,
+                        parse_time_expression)
 
-    date_str = date_str.strip()
-    time_str = time_str.strip()
+    date_str = (date_str or "").strip()
+    time_str = (time_str or "").strip()
 
     # Already ISO datetime format
     if "T" in date_str and len(date_str) >= 16:
+        try:
+            datetime.fromisoformat(date_str)
+        except ValueError:
+            raise ValueError(f"'{date_str}' is not a valid date and time")
         return date_str if len(date_str) >= 19 else date_str + ":00"
 
-    # Already ISO date format (YYYY-MM-DD)
-    if len(date_str) == 10 and date_str[4] == "-" and date_str[7] == "-":
-        return f"{date_str}T{time_str}:00" if len(time_str) == 5 else f"{date_str}T{time_str}"
-
-    today = date.today()
-    target_date = None
-
-    # Normalize for comparison
-    date_lower = date_str.lower()
-
-    # Handle "today" and "tomorrow"
-    if date_lower == "today":
-        target_date = today
-    elif date_lower == "tomorrow":
-        target_date = today + timedelta(days=1)
-    else:
-        # Day name mapping
-        day_names = {
-            "monday": 0, "mon": 0,
-            "tuesday": 1, "tue": 1, "tues": 1,
-            "wednesday": 2, "wed": 2,
-            "thursday": 3, "thu": 3, "thur": 3, "thurs": 3,
-            "friday": 4, "fri": 4,
-            "saturday": 5, "sat": 5,
-            "sunday": 6, "sun": 6,
-        }
+    time_of_day = parse_time_expression(time_str) if time_str else "09:00"
+    if time_of_day is None:
+        raise ValueError(f"Couldn't understand the time '{time_str}' (try '14:30' or 'half past two')")
 
-        # Remove "next " prefix if present
-        check_str = date_lower.replace("next ", "")
+    if get_date_settings().ask_when_ambiguous:
+        readings = ambiguous_readings(date_str)
+        if readings:
+            raise AmbiguousDateError(date_str, readings)
 
-        if check_str in day_names:
-            target_weekday = day_names[check_str]
-            current_weekday = today.weekday()
+    when = parse_natural_datetime(date_str, default_time=time_of_day)
+    if when is None:
+        raise ValueError(f"Couldn't understand the date '{date_str}' (try 'friday', 'March 3' or '2025-12-05')")
+    return when.isoformat()
 
-            # Calculate days until target weekday
-            days_ahead = target_weekday - current_weekday
-            if days_ahead <= 0:  # Target day already passed this week
-                days_ahead += 7
 
-            target_date = today + timedelta(days=days_ahead)
+def _date_error(error: ValueError) -> str:
+    """Tool result for a date that can't be used; ambiguous ones are handed back for the model to ask about."""
+    from .dates import AmbiguousDateError
+    if isinstance(error, AmbiguousDateError):
+        return (f"⏸ Not done yet - '{error.text}' could be either day. Ask them: {error.question} "
+                f"Then call again with the date as YYYY-MM-DD.")
+    return f"✗ {error}"
 
-    if target_date:
-        time_part = time_str if len(time_str) == 5 else time_str[:5]
-        return f"{target_date.isoformat()}T{time_part}:00"
 
-    # Fallback: assume it's already a valid format or close to it
-    return date_str
+register_capability("Calendar", "Meetings and events, one-off or recurring, with invitations and replies",
+                    ["what's on my calendar?", "add lunch with Bob tomorrow at 1", "who's coming to the offsite?"],
+                    category="Planning", keywords=("meeting", "event", "appointment", "rsvp", "invite"))
 
 
 @registry.register("add_calendar_event", "Add a one-time meeting or event")
 def add_calendar_event(
     title: str,
     day: str,
-    start_time: str = "09:00",
-    duration_minutes: int = 60,
+    start_time: str = "",
+    duration_minutes: int = 0,
     description: str = "",
     location: str = "",
     attendees: str = "",
     reminder_minutes: int = 15,
-    project_id: str = ""
+    project_id: str = "",
+    tags: str = "",
+    show_as: str = "busy",
+    visibility: str = "",
+    end_time: str = ""
 ) -> str:
     """
     Add a ONE-TIME calendar event. For recurring meetings, use add_recurring_meeting instead.
@@ -2014,20 +2192,49 @@ def add_calendar_event(
     Args:
         title: Event title
         day: Day of event - "Monday", "Friday", "tomorrow", "today", or "2025-12-05"
-        start_time: Time like "09:00" or "14:30" (default: 09:00)
-        duration_minutes: How long (default: 60)
+        start_time: Time like "09:00" or "14:30" - ask the user if they didn't say, don't guess
+        duration_minutes: How long, only if they said; 0 infers it from past events like it and the kind
+            of event (durations.py)
+        end_time: When it ends if they said so instead - "15:00", "until 3", "through the afternoon"
         attendees: Comma-separated names/emails
+        tags: Comma-separated categories (work, personal, health...); inferred from the title if empty
+        show_as: "busy" (announcements wait until it's over) or "free" (e.g. a lunch or focus block)
+        visibility: What outside integrations see - "public", "busy" (the time only) or "private"
     """
     from datetime import datetime, timedelta
+    from .appointment_cache import get_appointment_cache
+    from .categories import resolve_tags
+    from .durations import get_duration_defaults
+    from .timezones import format_dual, from_display, to_display
 
     planner = get_planner_data()
+    tag_list, title = resolve_tags(tags, title)
+    shared = _visibility(visibility)
+    if shared is None:
+        return _VISIBILITY_ERROR
 
-    # Parse natural language date
-    start_datetime = _parse_natural_date(day, start_time)
-
-    # Calculate end time from duration
-    start_dt = datetime.fromisoformat(start_datetime)
-    end_dt = start_dt + timedelta(minutes=duration_minutes)
+    # Parse natural language date (said in the travel zone while travelling, stored as home time)
+    try:
+        start_dt = from_display(datetime.fromisoformat(_parse_natural_date(day, start_time)))
+    except ValueError as e:
+        return _date_error(e)
+    start_datetime = start_dt.isoformat()
+    if not start_time.strip() and "T" not in day:
+        return (f"⏸ Not done yet - no time was given for '{title}'. Ask them what time (or whether "
+                f"9am is fine), then call again with start_time.")
+
+    # The end said ("until 3"), the length said, or the usual length of this kind of event
+    inferred = ""
+    if end_time.strip():
+        end_dt = _parse_end(end_time, start_dt)
+        if end_dt is None:
+            return f"✗ Couldn't understand the end time '{end_time}' (e.g. \"15:00\" or \"until 3\")"
+    elif duration_minutes > 0:
+        end_dt = start_dt + timedelta(minutes=duration_minutes)
+    else:
+        duration = get_duration_defaults().infer(title, planner.get_calendar_events(expand_recurring=False))
+        end_dt = start_dt + timedelta(minutes=duration.minutes)
+        inferred = duration.note
     end_datetime = end_dt.isoformat()
 
     attendee_list = [a.strip() for a in attendees.split(",") if a.strip()] if attendees else []
@@ -2041,16 +2248,25 @@ def add_calendar_event(
         attendees=attendee_list,
         recurrence="none",
         reminder_minutes=reminder_minutes,
-        project_id=project_id if project_id else None
+        project_id=project_id if project_id else None,
+        tags=tag_list,
+        busy=show_as.strip().lower() != "free",
+        visibility=shared
     )
 
     # Format nice output with day name
     event_date = datetime.fromisoformat(event.start_time)
-    day_name = event_date.strftime("%A")
-    date_str = event_date.strftime("%b %d")
-    time_str = event_date.strftime("%H:%M")
+    shown = to_display(event_date)
+    day_name = shown.strftime("%A")
+    date_str = shown.strftime("%b %d")
+    time_str = format_dual(event_date)
 
-    return f"✓ Added: '{event.title}' on {day_name} {date_str} at {time_str}"
+    result = f"✓ Added: '{event.title}' on {day_name} {date_str} at {time_str}{inferred}"
+    clashes = get_appointment_cache().conflicts(event)
+    if clashes:
+        result += "\n⚠ Overlaps " + ", ".join(
+            f"'{c.title}' at {format_dual(datetime.fromisoformat(c.start_time))}" for c in clashes)
+    return result + _holiday_warning(start_dt.date()) + _preference_warning(event)
 
 
 @registry.register("add_recurring_meeting", "Add a recurring meeting (weekly, daily, etc)")
@@ -2064,7 +2280,10 @@ def add_recurring_meeting(
     location: str = "",
     attendees: str = "",
     reminder_minutes: int = 15,
-    project_id: str = ""
+    project_id: str = "",
+    tags: str = "",
+    visibility: str = "",
+    skip_holidays: bool = False
 ) -> str:
     """
     Add a RECURRING meeting. Creates ONE event that repeats automatically.
@@ -2076,19 +2295,30 @@ def add_recurring_meeting(
         duration_minutes: How long (default: 60)
         frequency: "daily", "weekly", "biweekly", "monthly" (default: weekly)
         attendees: Comma-separated names/emails
+        visibility: "public", "busy" or "private", as for add_calendar_event
+        skip_holidays: Leave out occurrences on public holidays (work meetings like a standup)
 
     Example: add_recurring_meeting("Team Standup", "Monday", "09:00", frequency="weekly")
     This creates ONE meeting entry that shows up every Monday automatically.
     """
     from datetime import datetime, timedelta
+    from .categories import resolve_tags
+    from .timezones import format_dual, from_display, to_display
 
     planner = get_planner_data()
+    tag_list, title = resolve_tags(tags, title)
+    shared = _visibility(visibility)
+    if shared is None:
+        return _VISIBILITY_ERROR
 
     # For recurring, find the NEXT occurrence of that day
-    start_datetime = _parse_natural_date(day_of_week, start_time)
+    try:
+        start_dt = from_display(datetime.fromisoformat(_parse_natural_date(day_of_week, start_time)))
+    except ValueError as e:
+        return _date_error(e)
+    start_datetime = start_dt.isoformat()
 
     # Calculate end time from duration
-    start_dt = datetime.fromisoformat(start_datetime)
     end_dt = start_dt + timedelta(minutes=duration_minutes)
     end_datetime = end_dt.isoformat()
 
@@ -2109,13 +2339,16 @@ def add_recurring_meeting(
         recurrence=frequency,
         recurrence_end=None,  # Recurring indefinitely
         reminder_minutes=reminder_minutes,
-        project_id=project_id if project_id else None
+        project_id=project_id if project_id else None,
+        tags=tag_list,
+        visibility=shared,
+        skip_holidays=skip_holidays
     )
 
     # Format nice output
     event_date = datetime.fromisoformat(event.start_time)
-    day_name = event_date.strftime("%A")
-    time_str = event_date.strftime("%H:%M")
+    day_name = to_display(event_date).strftime("%A")
+    time_str = format_dual(event_date)
 
     freq_text = {
         "daily": "every day",
@@ -2125,7 +2358,10 @@ def add_recurring_meeting(
         "yearly": "yearly"
     }.get(frequency, frequency)
 
-    return f"✓ Added recurring: '{event.title}' {freq_text} at {time_str}"
+    result = f"✓ Added recurring: '{event.title}' {freq_text} at {time_str}"
+    if skip_holidays:
+        return result + ", except on holidays"
+    return result + _holiday_warning(start_dt.date())
 
 
 @registry.register("update_calendar_event", "Update a calendar event")
@@ -2137,9 +2373,26 @@ def update_calendar_event(
     description: str = "",
     location: str = "",
     recurrence: str = "",
-    reminder_minutes: int = 0
+    reminder_minutes: int = 0,
+    attendees: str = "",
+    tags: str = "",
+    show_as: str = "",
+    visibility: str = "",
+    skip_holidays: str = ""
 ) -> str:
-    """Update a calendar event."""
+    """
+    Update a calendar event. attendees replaces the list (comma-separated names/emails/phones);
+    tags replaces the categories (comma-separated, "none" clears them). start_time can be just a
+    time ("16:00") to move it on the same day; it keeps its length unless end_time is given.
+    show_as is "busy" or "free" (free events don't hold announcements); visibility is "public",
+    "busy" or "private" (what outside integrations see); skip_holidays "yes" leaves a recurring
+    event's occurrences on public holidays out ("no" brings them back). An occurrence id from
+    list_calendar_events ("evt_1@2026-10-20") changes only that occurrence of a recurring event.
+    """
+    from datetime import datetime
+    from .categories import normalize_tags
+    from .dates import parse_time_expression
+
     planner = get_planner_data()
 
     event = planner.get_calendar_event(event_id)
@@ -2149,8 +2402,19 @@ def update_calendar_event(
     updates = {}
     if title:
         updates["title"] = title
+    if start_time and "T" not in start_time:
+        # "16:00" / "4pm": the same day at a new time ("move it to 4 instead")
+        from .timezones import from_display, to_display
+        clock = parse_time_expression(start_time)
+        if not clock:
+            return f"✗ Couldn't understand the time '{start_time}'"
+        day = to_display(datetime.fromisoformat(event.start_time)).date()
+        start_time = from_display(datetime.fromisoformat(f"{day}T{clock}")).isoformat()
     if start_time:
         updates["start_time"] = start_time
+        if not end_time and event.end_time:  # Moving keeps the length
+            length = datetime.fromisoformat(event.end_time) - datetime.fromisoformat(event.start_time)
+            updates["end_time"] = (datetime.fromisoformat(start_time) + length).isoformat()
     if end_time:
         updates["end_time"] = end_time
     if description:
@@ -2161,14 +2425,77 @@ def update_calendar_event(
         updates["recurrence"] = recurrence
     if reminder_minutes > 0:
         updates["reminder_minutes"] = reminder_minutes
+    if attendees:
+        updates["attendees"] = [a.strip() for a in attendees.split(",") if a.strip()]
+    if tags:
+        updates["tags"] = [] if tags.strip().lower() == "none" else normalize_tags(tags)
+    if show_as:
+        updates["busy"] = show_as.strip().lower() != "free"
+    if visibility:
+        updates["visibility"] = _visibility(visibility)
+        if updates["visibility"] is None:
+            return _VISIBILITY_ERROR
+    if skip_holidays:
+        updates["skip_holidays"] = skip_holidays.strip().lower() in ("yes", "true", "1")
 
     event = planner.update_calendar_event(event_id, **updates)
+    if split_occurrence_id(event_id)[1]:
+        return f"✓ Updated event: '{event.title}' on {_occurrence_day(event_id)} only"
     return f"✓ Updated event: '{event.title}'"
 
 
+def _parse_end(text: str, start):
+    """The end of an event starting at `start` (home time) from "15:00", "until 3" or an ISO datetime; None if not."""
+    from datetime import datetime
+    from .dates import parse_end_time
+    from .timezones import from_display, to_display
+    try:
+        end = datetime.fromisoformat(text.strip())
+        return end if end > start else None
+    except ValueError:
+        end = parse_end_time(text, to_display(start))  # Said on the clock shown, like the start
+        return from_display(end) if end is not None else None
+
+
+def _holiday_warning(day) -> str:
+    """"\n⚠ That's Thanksgiving" when `day` is a holiday (holidays.py), else ""."""
+    from .holidays import get_holiday_calendar
+    names = [h.name for h in get_holiday_calendar().on(day)]
+    return f"\n⚠ That's {' and '.join(names)}" if names else ""
+
+
+def _preference_warning(event) -> str:
+    """"\n⚠ You asked for no meetings before 10:00" when `event` breaks a scheduling preference, else ""."""
+    from datetime import datetime, timedelta
+    from .appointment_cache import get_appointment_cache
+    from .scheduling_preferences import get_scheduling_preferences
+    start, end = datetime.fromisoformat(event.start_time), datetime.fromisoformat(event.end_time)
+    midnight = datetime.combine(start.date(), datetime.min.time())
+    booked = [e for e in get_appointment_cache().between(midnight, midnight + timedelta(days=1)) if e.id != event.id]
+    broken = get_scheduling_preferences().broken_by(start, end, len(booked))
+    return f"\n⚠ You asked for {' and '.join(c.description for c in broken)}" if broken else ""
+
+
+_VISIBILITY_ERROR = "✗ visibility is \"public\", \"busy\" (the time only) or \"private\""
+
+
+def _visibility(value: str) -> Optional[str]:
+    """A visibility the AI passed ("busy-only" and "busy only" are "busy"); "" for none, None when unknown."""
+    value = value.strip().lower().replace("-", " ")
+    value = value[:-len(" only")] if value.endswith(" only") else value
+    return value if not value or value in VISIBILITIES else None
+
+
+def _occurrence_day(event_id: str) -> str:
+    """"Mon Oct 19" for an occurrence id."""
+    from datetime import date
+    return f"{date.fromisoformat(split_occurrence_id(event_id)[1]):%a %b %d}"
+
+
 @registry.register("delete_calendar_event", "Delete a calendar event")
 def delete_calendar_event(event_id: str) -> str:
-    """Delete a calendar event."""
+    """Delete a calendar event. An occurrence id ("evt_1@2026-10-20") skips only that occurrence of a recurring event."""
+    import copy
     planner = get_planner_data()
 
     event = planner.get_calendar_event(event_id)
@@ -2176,30 +2503,935 @@ def delete_calendar_event(event_id: str) -> str:
         return f"✗ Event '{event_id}' not found"
 
     title = event.title
+    series_id, day = split_occurrence_id(event_id)
+    if day:
+        exceptions = copy.deepcopy(planner.get_calendar_event(series_id).exceptions)
+        if planner.cancel_occurrence(series_id, day):
+            _record_undo("planner_fields", f"Skipped '{title}' on {_occurrence_day(event_id)}",
+                         {"collection": "calendar_events", "id": series_id, "fields": {"exceptions": exceptions}})
+            return f"✓ Skipped '{title}' on {_occurrence_day(event_id)} (the rest of the series stays){UNDO_HINT}"
+        return "✗ Failed to skip that occurrence"
     if planner.delete_calendar_event(event_id):
-        return f"✓ Deleted event: '{title}'"
+        _record_undo("planner_restore", f"Deleted event '{title}'", {"collection": "calendar_events", "id": event_id})
+        return f"✓ Deleted event: '{title}'{UNDO_HINT}"
     return "✗ Failed to delete event"
 
 
 @registry.register("list_calendar_events", "List calendar events")
-def list_calendar_events(days: int = 7) -> str:
-    """List upcoming calendar events."""
+def list_calendar_events(days: int = 7, tag: str = "") -> str:
+    """List upcoming calendar events, optionally only those with a tag (e.g. "work")."""
+    from datetime import datetime
+    from .categories import format_tags
+    from .timezones import format_dual, to_display
+
     planner = get_planner_data()
 
-    events = planner.get_upcoming_events(days=days)
+    events = planner.get_upcoming_events(days=days, tag=tag if tag else None)
 
     if not events:
-        return f"No events in the next {days} days."
+        scope = f" tagged #{tag.lstrip('#')}" if tag else ""
+        return f"No events{scope} in the next {days} days."
 
     lines = [f"Calendar ({len(events)} events in next {days} days):"]
     for e in events:
-        # Format: 2024-01-15T10:00 -> Jan 15 10:00
-        dt = e.start_time[:16]
+        # Format: 2024-01-15T10:00 -> 2024-01-15 10:00 (travel mode: local time, then home time)
+        start = datetime.fromisoformat(e.start_time)
+        dt = f"{to_display(start):%Y-%m-%d} {format_dual(start)}"
         recur = f" ↻{e.recurrence}" if e.recurrence != "none" else ""
         loc = f" @ {e.location}" if e.location else ""
-        lines.append(f"  [{e.id}] {dt} - {e.title}{loc}{recur}")
+        people = f" ({e.participant_summary()})" if e.attendees else ""
+        lines.append(f"  [{e.id}] {dt} - {e.title}{format_tags(e.tags)}{loc}{recur}{people}")
+
+    return "\n".join(lines)
+
+
+@registry.register("list_holidays", "List upcoming public holidays and observances")
+def list_holidays(days: int = 60) -> str:
+    """Holidays in the next N days for the configured region (config.holiday_region)."""
+    from datetime import date, timedelta
+    from .holidays import get_holiday_calendar
+
+    calendar = get_holiday_calendar()
+    holidays = calendar.between(date.today(), date.today() + timedelta(days=days))
+    if not holidays:
+        return f"No holidays in the next {days} days" + ("." if calendar.region else " (no holiday region set).")
+    lines = [f"Holidays in the next {days} days:"]
+    for h in holidays:
+        lines.append(f"  {h.day:%a %Y-%m-%d} - {h.name}{'' if h.day_off else ' (observance)'}")
+    return "\n".join(lines)
 
+
+# ==============================================================================
+# SCHEDULING PREFERENCES (see scheduling_preferences.py)
+# ==============================================================================
+
+@registry.register("suggest_meeting_times", "Suggest free times for a meeting that fit the user's scheduling preferences")
+def suggest_meeting_times(day: str = "", duration_minutes: int = 60, count: int = 3) -> str:
+    """
+    Free times in the working day that no scheduling preference rules out.
+
+    Args:
+        day: A day to look on ("friday", "2025-12-05"); empty looks over the next 7 days
+        duration_minutes: How long the meeting is (default: 60)
+        count: How many times to suggest (default: 3)
+    """
+    from datetime import date, datetime, timedelta
+    from .appointment_cache import get_appointment_cache
+    from .scheduling_preferences import get_scheduling_preferences
+    from .timezones import format_dual, to_display
+
+    if day.strip():
+        try:
+            days = [datetime.fromisoformat(_parse_natural_date(day, "00:00")).date()]
+        except ValueError as e:
+            return _date_error(e)
+    else:
+        days = [date.today() + timedelta(days=offset) for offset in range(7)]
+
+    preferences = get_scheduling_preferences()
+    cache = get_appointment_cache()
+    now = datetime.now()
+    length = timedelta(minutes=max(duration_minutes, 5))
+    found = []
+    for current in days:
+        midnight = datetime.combine(current, datetime.min.time())
+        booked = cache.between(midnight, midnight + timedelta(days=1))
+        window = preferences.window(current, len(booked))
+        if window is None:
+            continue
+        start, end = (datetime.combine(current, datetime.strptime(t, "%H:%M").time()) for t in window)
+        if start < now:
+            start = now.replace(second=0, microsecond=0)
+            start += timedelta(minutes=-start.minute % 15)  # On the quarter hour
+        for event in [*booked, None]:
+            gap_end = min(end, datetime.fromisoformat(event.start_time)) if event else end
+            while gap_end - start >= length and len(found) < count:
+                found.append(start)
+                start += length
+            if event:
+                start = max(start, datetime.fromisoformat(event.end_time))
+
+    rules = preferences.constraints()
+    keeping = f" (keeping to {'; '.join(c.description for c in rules)})" if rules else ""
+    if not found:
+        when = f"on {days[0]:%a %b %d}" if day.strip() else "in the next 7 days"
+        return f"✗ No free {duration_minutes} min slot {when} within working hours{keeping}"
+    lines = [f"Free for {duration_minutes} min{keeping}:"]
+    lines.extend(f"  {to_display(start):%a %b %d} at {format_dual(start)}" for start in found)
+    return "\n".join(lines)
+
+
+@registry.register("add_scheduling_preference", "Remember when the user does or doesn't take meetings")
+def add_scheduling_preference(rule: str) -> str:
+    """
+    Keep a scheduling preference the user stated; slot suggestions respect it from then on.
+
+    Args:
+        rule: What they said - "no meetings before 10", "no meetings on Fridays",
+            "keep my afternoons free", "no more than 3 meetings a day"
+    """
+    from .scheduling_preferences import get_scheduling_preferences, parse_preference
+
+    constraint = parse_preference(rule)
+    if constraint is None:
+        return (f"✗ Couldn't read '{rule}' as a scheduling preference (e.g. \"no meetings before 10\", "
+                f"\"no meetings on Fridays\", \"no more than 3 meetings a day\")")
+    replaced = get_scheduling_preferences().add(constraint)
+    instead = f" (instead of {', '.join(c.description for c in replaced)})" if replaced else ""
+    return f"✓ Noted: {constraint.description}{instead}"
+
+
+@registry.register("list_scheduling_preferences", "List the scheduling preferences learned from the user")
+def list_scheduling_preferences() -> str:
+    """What the user has said about when they take meetings, with ids to forget them by."""
+    from .scheduling_preferences import get_scheduling_preferences
+
+    preferences = get_scheduling_preferences()
+    constraints = preferences.constraints()
+    if not constraints:
+        return "No scheduling preferences yet (e.g. say \"I never take meetings before 10\")."
+    lines = [f"Scheduling preferences ({len(constraints)}), working day "
+             f"{preferences.workday_start}-{preferences.workday_end}:"]
+    lines.extend(f"  [{c.id}] {c.description} - \"{c.said}\" ({c.added_at[:10]})" for c in constraints)
+    return "\n".join(lines)
+
+
+@registry.register("forget_scheduling_preference", "Delete learned scheduling preferences")
+def forget_scheduling_preference(match: str) -> str:
+    """
+    Delete scheduling preferences, e.g. "before 10" or "fridays".
+
+    Args:
+        match: An id from list_scheduling_preferences or text in the preference; "everything" deletes all
+    """
+    from dataclasses import asdict
+    from .scheduling_preferences import get_scheduling_preferences
+
+    removed = get_scheduling_preferences().remove("" if match.strip().lower() == "everything" else match)
+    if not removed:
+        return f"✗ No scheduling preferences match '{match}'"
+    noun = "preference" if len(removed) == 1 else "preferences"
+    _record_undo("scheduling_constraints", f"Forgot {len(removed)} scheduling {noun}",
+                 {"constraints": [asdict(c) for c in removed]})
+    lines = [f"✓ Forgot {len(removed)} scheduling {noun}{UNDO_HINT}:"]
+    lines.extend(f"  - {c.description}" for c in removed)
     return "\n".join(lines)
 
 
 # get_todays_schedule is defined earlier in file with full checklist support
+
+
+# ==============================================================================
+# TIMEZONE / TRAVEL TOOLS
+# ==============================================================================
+
+def _offset_from_home(zone_name: str) -> str:
+    """ "5 hours ahead of home", "30 minutes behind home", "same time as home" """
+    from datetime import datetime
+    from zoneinfo import ZoneInfo
+    from .dates import get_date_settings
+
+    now = datetime.now().astimezone()
+    home = get_date_settings().home_zone()
+    difference = now.astimezone(ZoneInfo(zone_name)).utcoffset() - (now.astimezone(home) if home else now).utcoffset()
+    minutes = int(difference.total_seconds() // 60)
+    if minutes == 0:
+        return "same time as home"
+    hours, rest = divmod(abs(minutes), 60)
+    amount = " ".join(part for part in (f"{hours} hour{'s' if hours != 1 else ''}" if hours else "",
+                                        f"{rest} minutes" if rest else "") if part)
+    return f"{amount} {'ahead of' if minutes > 0 else 'behind'} home"
+
+
+register_capability("Travel mode", "Times shown and spoken in another timezone while you travel",
+                    ["I'm in Tokyo this week", "I'm back home"], category="Settings",
+                    keywords=("timezone", "trip", "abroad"))
+
+
+@registry.register("set_travel_mode", "Show, speak and enter times in another timezone while travelling")
+def set_travel_mode(timezone: str) -> str:
+    """
+    Turn on travel mode: calendar times are shown and spoken in this zone, and
+    times the user gives ("dinner at 7") are taken as local there. Stored
+    events keep their home time. Use end_travel_mode when back home.
+
+    Args:
+        timezone: City or IANA name - "London", "Tokyo", "America/New_York"
+    """
+    from .timezones import find_timezone, home_timezone_name, set_travel_timezone
+
+    zone_name = find_timezone(timezone)
+    if zone_name is None:
+        return f"✗ Unknown timezone '{timezone}' (try a city like 'London' or a name like 'America/New_York')"
+    home = home_timezone_name()
+    if zone_name == home:
+        set_travel_timezone(None)
+        return f"✓ {zone_name} is home - travel mode off"
+    set_travel_timezone(zone_name)
+    return f"✓ Travel mode on: times now in {zone_name} ({_offset_from_home(zone_name)}, {home})"
+
+
+@registry.register("end_travel_mode", "Go back to showing times in the home timezone")
+def end_travel_mode() -> str:
+    """Turn travel mode off."""
+    from .dates import get_date_settings
+    from .timezones import home_timezone_name, set_travel_timezone
+
+    if not get_date_settings().travel_timezone:
+        return f"✓ Already on home time ({home_timezone_name()})"
+    set_travel_timezone(None)
+    return f"✓ Travel mode off: times back in home time ({home_timezone_name()})"
+
+
+# ==============================================================================
+# MEETING PREP TOOLS
+# ==============================================================================
+
+_chat_history = None
+
+
+def set_chat_history(history: "PersistentChatHistory"):  # noqa: F821
+    """Let meeting prep search past conversations (set by the dashboard's chat engine)."""
+    global _chat_history
+    _chat_history = history
+
+
+register_capability("Meeting prep", "A briefing before a meeting: your last conversations with the people in it",
+                    ["prep me for the pricing review"], category="Planning", keywords=("briefing", "meeting"))
+
+
+@registry.register("prepare_for_meeting", "Brief on an upcoming meeting: last conversations and messages with participants, project notes")
+def prepare_for_meeting(event: str = "") -> str:
+    """
+    Gather what's worth knowing before a meeting: the last conversation that
+    mentioned each participant, recent messages from them, facts about them,
+    and the event's project notes.
+
+    Args:
+        event: Event ID or part of its title; empty for the next meeting with attendees or a project
+    """
+    from datetime import datetime
+    from .meeting_prep import build_brief
+
+    planner = get_planner_data()
+    now = datetime.now()
+    upcoming = [e for e in planner.get_upcoming_events(days=7) if e.end_time >= now.isoformat()]
+    if event:
+        wanted = event.strip().lower()
+        matches = [e for e in upcoming if e.id.startswith(wanted) or wanted in e.title.lower()]
+    else:
+        matches = [e for e in upcoming if e.attendees or e.project_id]
+    if not matches:
+        return f"✗ No upcoming meeting matching '{event}'" if event else "✗ No upcoming meetings with attendees or a project"
+    target = min(matches, key=lambda e: e.start_time)
+    brief = build_brief(target, planner, inbox=get_inbox_store(), profile=get_user_profile(),
+                        history=_chat_history, now=now)
+    return brief.text
+
+
+# ==============================================================================
+# INBOX TOOLS (SMS, Email, Voice)
+# ==============================================================================
+
+_inbox_store = None
+
+def get_inbox_store():
+    """Get the global inbox store instance (lazy load)."""
+    global _inbox_store
+    if _inbox_store is None:
+        from .inbox import InboxStore
+        _inbox_store = InboxStore()
+    return _inbox_store
+
+
+register_capability("Inbox", "Texts, emails and voicemails in one place, with replies drafted for you",
+                    ["read my new messages", "draft a reply"], category="Messages",
+                    keywords=("sms", "email", "text", "reply"))
+
+
+@registry.register("list_inbox", "List inbound SMS, email and voice messages")
+def list_inbox(status: str = "", channel: str = "") -> str:
+    """
+    List inbox items, newest first. Archived items are hidden unless status="archived".
+
+    Args:
+        status: Filter by "unread", "read", "replied" or "archived"
+        channel: Filter by "sms", "email" or "voice"
+    """
+    from .inbox import format_inbox
+
+    store = get_inbox_store()
+    store.reload()
+    items = store.get_items(status=status or None, channel=channel or None)
+    header = f"Inbox ({store.unread_count()} unread):"
+    return f"{header}\n{format_inbox(items)}"
+
+
+@registry.register("update_inbox_item", "Mark an inbox message read, replied or archived")
+def update_inbox_item(item_id: str, status: str) -> str:
+    """Change an inbox item's status. The change syncs to the server on next inbox sync."""
+    store = get_inbox_store()
+    try:
+        item = store.set_status(item_id, status)
+    except ValueError as e:
+        return f"✗ {e}"
+    if not item:
+        return f"✗ Inbox item '{item_id}' not found"
+    return f"✓ Marked message from {item.sender} as {item.status}"
+
+
+# ==============================================================================
+# CALENDAR SERVER SYNC TOOLS
+# ==============================================================================
+
+_calendar_sync = None
+
+def set_calendar_sync(sync: "CalendarSync"):  # noqa: F821
+    """Set the calendar sync (called by the dashboard with the loaded config)."""
+    global _calendar_sync
+    _calendar_sync = sync
+
+
+def get_calendar_sync() -> Optional["CalendarSync"]:  # noqa: F821
+    return _calendar_sync
+
+
+@registry.register("sync_calendar_to_server", "Push the local calendar (including recurring meetings) to the server for phone/SMS reminders")
+async def sync_calendar_to_server(days: int = 30) -> str:
+    """Mirror the next `days` of calendar events to the server in bulk."""
+    if _calendar_sync is None:
+        return "✗ Calendar sync is not configured"
+    try:
+        result = await _calendar_sync.push(days=int(days))
+    except Exception as e:
+        return f"✗ Calendar sync failed: {e}"
+    line = f"✓ Calendar synced: {result['created']} added, {result['deleted']} removed"
+    if result["failed"]:
+        line += f", {result['failed']} rejected by the server"
+    return line
+
+
+_push_router = None
+
+
+def set_push_router(router: Optional["PushRouter"]):  # noqa: F821
+    """Set the ntfy/Pushover router whose failed pushes retry_failed_notifications resends."""
+    global _push_router
+    _push_router = router
+
+
+@registry.register("retry_failed_notifications", "Resend ntfy/Pushover push notifications that couldn't be delivered")
+async def retry_failed_notifications(notification_id: str = "") -> str:
+    """
+    Give undeliverable pushes (or one of them) a fresh set of attempts and send them now.
+
+    Args:
+        notification_id: One failed push's id (from `xswarm dev push failed`); empty for all
+    """
+    if _push_router is None or _push_router.queue is None:
+        return "✗ Push notifications are not configured"
+    entries = _push_router.queue.retry([notification_id] if notification_id else None)
+    if not entries:
+        return f"✗ No failed push {notification_id}" if notification_id else "✓ No failed pushes to resend"
+    sent, failed = await asyncio.to_thread(_push_router.retry_due)
+    line = f"✓ Resent {sent} of {len(entries)} failed push(es)"
+    if failed:
+        line += f"; {failed} failed again and will keep retrying"
+    return line
+
+
+@registry.register("invite_event_attendees", "Email/text invitations with RSVP links to a calendar event's attendees")
+async def invite_event_attendees(event_id: str, resend: bool = False) -> str:
+    """
+    Invite an event's attendees (emails get an email, phone numbers an SMS).
+
+    Args:
+        event_id: The calendar event id
+        resend: Also re-invite attendees who were invited but haven't replied
+    """
+    if _calendar_sync is None:
+        return "✗ Calendar sync is not configured"
+    event = get_planner_data().get_calendar_event(event_id)
+    if not event:
+        return f"✗ Event '{event_id}' not found"
+    if not event.attendees:
+        return f"✗ '{event.title}' has no attendees to invite"
+    try:
+        result = await _calendar_sync.invite(event_id, resend=bool(resend))
+    except Exception as e:
+        return f"✗ Could not send invitations: {e}"
+
+    invitations = result.get("invitations", {})
+    lines = [f"✓ Invited {len(invitations.get('sent', []))} to '{event.title}'"]
+    for failure in invitations.get("failed", []):
+        lines.append(f"  ✗ {failure['label']}: {failure['error']}")
+    return "\n".join(lines)
+
+
+@registry.register("check_event_rsvps", "Check who has accepted or declined a calendar event")
+async def check_event_rsvps(event_id: str) -> str:
+    """Refresh and show attendee RSVPs for an event."""
+    if _calendar_sync is None:
+        return "✗ Calendar sync is not configured"
+    try:
+        event = await _calendar_sync.refresh_rsvps(event_id)
+    except Exception as e:
+        return f"✗ Could not check RSVPs: {e}"
+    if not event:
+        return f"✗ Event '{event_id}' not found"
+    if not event.attendees:
+        return f"'{event.title}' has no attendees"
+    return f"{event.title}: {event.participant_summary()}"
+
+
+# ==============================================================================
+# CALL SCREENING & VOICEMAIL TOOLS
+# ==============================================================================
+
+_voicemail_store = None
+_call_screening = None
+
+def get_voicemail_store():
+    """Get the global voicemail store instance (lazy load)."""
+    global _voicemail_store
+    if _voicemail_store is None:
+        from .call_screening import VoicemailStore
+        _voicemail_store = VoicemailStore()
+    return _voicemail_store
+
+
+def set_call_screening(manager: "CallScreeningManager"):  # noqa: F821
+    """Set the call screening manager (called by the dashboard once it polls)."""
+    global _call_screening
+    _call_screening = manager
+
+
+register_capability("Call screening", "I answer unknown callers first; take the call, decline it or send it to voicemail",
+                    ["send it to voicemail", "search my voicemail for the plumber"],
+                    requires=("has_phone_subscription",), category="Messages", keywords=("phone", "call"))
+
+
+@registry.register("screen_call", "Accept, decline (with a spoken message) or send to voicemail a call being screened")
+async def screen_call(action: str, message: str = "", call_id: str = "") -> str:
+    """
+    Decide what happens to a caller the assistant is screening.
+
+    Args:
+        action: "accept", "decline" or "voicemail"
+        message: For decline, what the assistant should say to the caller
+        call_id: Optional call id prefix; defaults to the most recent screened call
+    """
+    if _call_screening is None:
+        return "✗ Call screening is not running"
+    return await _call_screening.decide(action, call_id or None, message or None)
+
+
+@registry.register("search_voicemail", "Search voicemail transcripts")
+def search_voicemail(query: str = "", limit: int = 10) -> str:
+    """Find voicemails whose transcript, caller or stated purpose contains every word of the query."""
+    from .call_screening import format_voicemails
+
+    store = get_voicemail_store()
+    store.reload()
+    return format_voicemails(store.search(query, limit=int(limit)))
+
+
+# ==============================================================================
+# FOLLOW-UP TOOLS (commitments detected in conversation, events and deadlines in email)
+# ==============================================================================
+
+_followup_store = None
+
+def get_followup_store():
+    """Get the global follow-up store instance (lazy load)."""
+    global _followup_store
+    if _followup_store is None:
+        from .followups import FollowUpStore
+        _followup_store = FollowUpStore()
+    return _followup_store
+
+
+@registry.register("list_followups", "List follow-ups detected in conversation or email that await confirmation")
+def list_followups() -> str:
+    """Show proposed tasks/commitments the user mentioned but hasn't confirmed yet."""
+    store = get_followup_store()
+    store.reload()
+    pending = store.pending()
+    if not pending:
+        return "No follow-ups waiting for confirmation."
+    lines = [f"📝 {len(pending)} follow-up(s) to confirm:"]
+    for proposal in pending:
+        lines.append(f"  [{proposal.id}] {proposal.describe()}  — \"{proposal.source}\"")
+    return "\n".join(lines)
+
+
+@registry.register("confirm_followup", "Add a detected follow-up to the planner (use 'all' for every pending one)")
+def confirm_followup(followup_id: str) -> str:
+    """Create the task, commitment or calendar event for a proposed follow-up after the user confirms it."""
+    store = get_followup_store()
+    ids = [p.id for p in store.pending()] if followup_id == "all" else [followup_id]
+    results = [store.accept(i, get_planner_data()) for i in ids]
+    results = [r for r in results if r]
+    return "\n".join(results) if results else f"✗ Follow-up '{followup_id}' not found"
+
+
+@registry.register("dismiss_followup", "Discard a detected follow-up")
+def dismiss_followup(followup_id: str) -> str:
+    """Drop a proposed follow-up the user doesn't want."""
+    proposal = get_followup_store().dismiss(followup_id)
+    if not proposal:
+        return f"✗ Follow-up '{followup_id}' not found"
+    return f"✓ Dismissed: {proposal.title}"
+
+
+# ==============================================================================
+# UNDO & MEMORY TOOLS (reversible destructive actions, see undo.py)
+# ==============================================================================
+
+UNDO_HINT = " (can be undone for 5 minutes)"
+
+_undo_log = None
+_user_profile = None
+
+def get_undo_log():
+    """Get the global undo log instance (lazy load)."""
+    global _undo_log
+    if _undo_log is None:
+        from .undo import UndoLog
+        _undo_log = UndoLog()
+    return _undo_log
+
+
+def get_user_profile():
+    """Get the user profile (the chat engine's instance once it has started)."""
+    global _user_profile
+    if _user_profile is None:
+        from .memory import UserProfile
+        _user_profile = UserProfile()
+    return _user_profile
+
+
+def set_user_profile(profile: "UserProfile"):  # noqa: F821
+    """Use the chat engine's profile so forgotten facts leave its context too."""
+    global _user_profile
+    _user_profile = profile
+
+
+def _record_undo(kind: str, description: str, payload: dict) -> None:
+    try:
+        get_undo_log().record(kind, description, payload)
+    except Exception as e:
+        logger.warning(f"Could not record undo for '{description}': {e}")
+
+
+def undo_last() -> str:
+    """Reverse the most recent destructive action (shared by the tool, Ctrl+Z and the CLI)."""
+    from .undo import undo_last as _undo_last
+    log = get_undo_log()
+    log.reload()
+    planner = get_planner_data()
+    planner.reload()  # The action may have come from another process (e.g. `xswarm dev undo`)
+    return _undo_last(log, planner=planner, profile=get_user_profile())
+
+
+@registry.register("undo_last_action", "Undo the last delete/complete/forget (within 5 minutes)")
+def undo_last_action() -> str:
+    """Undo the most recent destructive action when the user says "undo that"."""
+    return undo_last()
+
+
+@registry.register("list_undoable_actions", "List recent actions that can still be undone")
+def list_undoable_actions() -> str:
+    """Show what undo would reverse, newest first."""
+    from datetime import datetime
+    log = get_undo_log()
+    log.reload()
+    pending = log.undoable()
+    if not pending:
+        return "Nothing to undo."
+    now = datetime.now()
+    lines = [f"Undoable ({len(pending)}), newest first:"]
+    for entry in pending:
+        left = int((log.window - entry.age(now)).total_seconds() // 60) + 1
+        lines.append(f"  {entry.description} ({left} min left)")
+    return "\n".join(lines)
+
+
+register_capability("Memory", "Facts about you I remember from our conversations, and forget when asked",
+                    ["forget my old address"], requires=("memory_enabled",), keywords=("remember", "forget"))
+
+
+@registry.register("forget_facts", "Forget remembered facts about the user that match some text")
+def forget_facts(match: str) -> str:
+    """
+    Remove facts from the user profile, e.g. "my old address".
+
+    Args:
+        match: Text to look for in facts (case-insensitive); "everything" forgets all facts
+    """
+    profile = get_user_profile()
+    removed = profile.remove_facts_matching("" if match.strip().lower() == "everything" else match)
+    if not removed:
+        return f"✗ No remembered facts match '{match}'"
+
+    from dataclasses import asdict
+    noun = "fact" if len(removed) == 1 else "facts"
+    _record_undo("profile_facts", f"Forgot {len(removed)} {noun} about you", {"facts": [asdict(f) for f in removed]})
+    lines = [f"✓ Forgot {len(removed)} {noun}{UNDO_HINT}:"]
+    lines.extend(f"  - {f.fact}" for f in removed)
+    return "\n".join(lines)
+
+
+@registry.register("bookmark_conversation", "Keep the last few turns of the conversation under a label")
+def bookmark_conversation(label: str = "", turns: int = 4) -> str:
+    """
+    Bookmark what was just said ("remember this as the contract terms"), see bookmarks.py.
+
+    Args:
+        label: What it's about, e.g. "contract terms"; made up from what was said when empty
+        turns: How many messages back, both sides counted (4 = the last two exchanges)
+    """
+    from .bookmarks import get_bookmark_store, recent_turns
+    try:
+        bookmark = get_bookmark_store().add(label, recent_turns(int(turns)))
+    except ValueError as e:
+        return f"✗ {e}"
+    return f"✓ Bookmarked {len(bookmark.turns)} turns as '{bookmark.label}'"
+
+
+@registry.register("find_bookmarks", "Find bookmarked parts of past conversations")
+def find_bookmarks(query: str = "", limit: int = 3) -> str:
+    """
+    Read back bookmarks ("what did I bookmark about the contract?").
+
+    Args:
+        query: Words to look for in labels and what was said; empty lists every bookmark
+        limit: How many to show in full
+    """
+    from datetime import datetime
+    from .bookmarks import get_bookmark_store
+    store = get_bookmark_store()
+    store.reload()
+    found = store.find(query)
+    if not found:
+        return f"No bookmarks about '{query}'." if query.strip() else "No bookmarks yet."
+    about = f" about '{query.strip()}'" if query.strip() else ""
+    lines = [f"Bookmarks{about} ({len(found)}):"]
+    for bookmark in found[:max(1, int(limit))]:
+        lines.append(f"  '{bookmark.label}' ({datetime.fromisoformat(bookmark.created_at):%b %d %H:%M}):")
+        lines.extend(f"    {line}" for line in bookmark.transcript())
+    if len(found) > limit:
+        lines.append(f"  ...and {len(found) - limit} more: " + ", ".join(f"'{b.label}'" for b in found[limit:]))
+    return "\n".join(lines)
+
+
+@registry.register("forget_bookmark", "Delete a bookmark")
+def forget_bookmark(match: str) -> str:
+    """
+    Delete bookmarks by label or id.
+
+    Args:
+        match: Text in the bookmark's label, or its id
+    """
+    from dataclasses import asdict
+    from .bookmarks import get_bookmark_store
+    removed = get_bookmark_store().remove(match)
+    if not removed:
+        return f"✗ No bookmark matches '{match}'"
+    noun = "bookmark" if len(removed) == 1 else "bookmarks"
+    _record_undo("bookmarks", f"Forgot {len(removed)} {noun}", {"bookmarks": [asdict(b) for b in removed]})
+    return f"✓ Forgot {len(removed)} {noun}{UNDO_HINT}: " + ", ".join(f"'{b.label}'" for b in removed)
+
+
+@registry.register("weekly_digest", "What was discussed, learned and done over the last week")
+def weekly_digest(days: int = 7) -> str:
+    """
+    The memory digest ("what did we discuss this week?"), see memory_digest.py.
+
+    Args:
+        days: How many days back
+    """
+    from .memory_digest import build_digest
+    return build_digest(days=max(1, int(days))).markdown()
+
+
+# ==============================================================================
+# PERSONA MOOD (see personas/mood.py)
+# ==============================================================================
+
+register_capability("Mood", "My mood drifts with how our conversation goes and the time of day, and resets when asked",
+                    ["reset your mood", "cheer up"], category="Settings", keywords=("mood", "grumpy", "feeling"))
+
+
+@registry.register("reset_mood", "Put the persona's mood back to its resting state")
+def reset_mood() -> str:
+    """Reset the mood that has drifted this session (the footer shows it)."""
+    from .personas.mood import get_mood_model
+    mood = get_mood_model()
+    was = mood.current().label
+    mood.reset()
+    return f"✓ Mood reset: {was} → {mood.current().label}"
+
+
+# ==============================================================================
+# CONFIRMATION SETTINGS (see confirmation.py)
+# ==============================================================================
+
+def _save_confirmation_config(policy) -> None:
+    if policy.config is not None:
+        try:
+            policy.config.save_to_file()
+        except Exception as e:
+            logger.warning(f"Could not save confirmation settings: {e}")
+
+
+register_capability("Confirmations", "Which actions I check with you first, and a spoken PIN for the important ones",
+                    ["always ask before sending email", "set my PIN"], category="Settings",
+                    keywords=("confirm", "pin", "security"))
+
+
+@registry.register("set_confirmation_level", "Change how an action class or tool must be confirmed")
+def set_confirmation_level(action: str, level: str) -> str:
+    """
+    Set the confirmation level for an action class or a single tool.
+
+    Args:
+        action: Action class (read, create, modify, complete, delete, communicate,
+                bulk_delete, memory_purge, system, settings) or a tool name
+        level: silent, verbal, explicit_yes or pin; "default" removes the override
+    """
+    from .confirmation import DEFAULT_LEVELS
+    policy = get_confirmation_policy()
+    if policy.config is None:
+        return "✗ Confirmation settings aren't available"
+    action = action.strip().lower().replace(" ", "_")
+    level = level.strip().lower().replace(" ", "_")
+    if action not in DEFAULT_LEVELS and action not in registry._tools:
+        return f"✗ Unknown action '{action}'. Classes: {', '.join(DEFAULT_LEVELS)}"
+
+    overrides = dict(policy.config.confirmation_levels)
+    if level == "default":
+        overrides.pop(action, None)
+    else:
+        try:
+            ConfirmationLevel(level)
+        except ValueError:
+            return f"✗ Unknown level '{level}'. Use silent, verbal, explicit_yes, pin or default"
+        overrides[action] = level
+    policy.config.confirmation_levels = overrides
+    _save_confirmation_config(policy)
+    return f"✓ '{action}' now needs: {policy.level_for(action).value.replace('_', ' ')}"
+
+
+@registry.register("set_confirmation_pin", "Set or clear the spoken PIN for PIN-level actions")
+def set_confirmation_pin(pin: str = "") -> str:
+    """
+    Set the PIN required for PIN-level actions. Only a hash is stored.
+
+    Args:
+        pin: 4-8 digits (spoken words like "four two" work); empty clears the PIN
+    """
+    from .confirmation import spoken_pin
+    policy = get_confirmation_policy()
+    if policy.config is None:
+        return "✗ Confirmation settings aren't available"
+    if not pin.strip():
+        policy.set_pin(None)
+        _save_confirmation_config(policy)
+        return "✓ PIN cleared - PIN-level actions now need an explicit yes"
+
+    digits = spoken_pin(pin)
+    if not 4 <= len(digits) <= 8:
+        return "✗ The PIN must be 4-8 digits"
+    policy.set_pin(digits)
+    _save_confirmation_config(policy)
+    return "✓ PIN set"
+
+
+@registry.register("set_volume", "Change how loud the assistant's voice, sound effects or media play")
+def set_volume(stream: str = "master", level: str = "up") -> str:
+    """
+    Set a playback volume. Saved, and applied to playback immediately.
+
+    Args:
+        stream: master (everything), speech (the assistant's voice), earcons
+                (listening chimes/beeps) or media
+        level: up, down, mute, unmute, or a percentage like "60"
+    """
+    from .volume import NAMES, get_volume_settings
+    stream = stream.strip().lower()
+    stream = {"voice": "speech", "sounds": "earcons", "sound effects": "earcons", "music": "media",
+              "all": "master", "everything": "master"}.get(stream, stream)
+    if stream not in NAMES:
+        return f"✗ Unknown stream '{stream}'. Use master, speech, earcons or media"
+    try:
+        value = get_volume_settings().change(stream, level)
+    except ValueError:
+        return f"✗ Unknown level '{level}'. Use up, down, mute, unmute or a percentage"
+    if value == 0:
+        return f"✓ {NAMES[stream]} muted"
+    return f"✓ {NAMES[stream]} {round(value * 100)}%"
+
+
+@registry.register("change_setting", "Change a setting the user asked to change (voice, quiet hours, wake word...)")
+def change_setting(setting: str, value: str) -> str:
+    """
+    Change one setting, checked first; saved and in effect at once (see voice_config.py).
+    Read the result back to the user: it says the new value, or what values are allowed.
+
+    Args:
+        setting: voice, quiet_hours, wake_word, follow_up_window, live_captions, barge_in,
+                 meeting_quiet, max_spoken_words, profanity, meeting_prep_minutes, user_name
+                 or working_memory (turns of the conversation you see)
+        value: As the user said it, e.g. "10pm to 7am", "off", "Glados", "15"
+    """
+    from .voice_config import get_config_changer
+    return get_config_changer().change(setting, value)
+
+
+@registry.register("list_settings", "List the settings that can be changed by asking, with their values and the voices")
+def list_settings() -> str:
+    from .voice_config import get_config_changer
+    return get_config_changer().summary()
+
+
+@registry.register("set_pronunciation", "Teach the voice how to say a name or word it gets wrong")
+def set_pronunciation(word: str, spoken: str) -> str:
+    """
+    Add to the pronunciation lexicon (see pronunciation.py); used for everything read aloud from then on.
+
+    Args:
+        word: The name or word as written, e.g. "Siobhan"
+        spoken: How it sounds, respelled - "shiv-AWN" (capitals for the stressed part); empty goes back
+            to saying it as spelled
+    """
+    from .pronunciation import get_lexicon, respell
+    if not spoken.strip():
+        if get_lexicon().remove(word):
+            return f"✓ '{word}' is said as spelled again"
+        return f"✗ No pronunciation for '{word}'"
+    try:
+        get_lexicon().add(word, spoken)
+    except ValueError as e:
+        return f"✗ {e}"
+    return f"✓ '{word}' will be said \"{respell(spoken)}\""
+
+
+@registry.register("set_alarm", "Set a wake-up alarm that rings until snoozed or dismissed")
+def set_alarm(time: str, days: str = "", label: str = "", briefing: bool = True) -> str:
+    """
+    Set an alarm. It rings with rising volume; "five more minutes" snoozes it,
+    "I'm up" stops it and reads out the day's briefing.
+
+    Args:
+        time: Time of day, e.g. "06:30", "7am", "half past six"
+        days: weekdays, weekends, daily or e.g. "mon,wed,fri"; empty rings once
+        label: What it's for, shown when it rings
+        briefing: Read today's calendar and tasks once it's dismissed
+    """
+    from .alarms import AlarmError, AlarmStore, describe_days
+    try:
+        alarm = AlarmStore().add(time, days, label, briefing)
+    except AlarmError as e:
+        return f"✗ {e}"
+    repeat = f" ({describe_days(alarm.days)})" if alarm.recurring else ""
+    return f"✓ Alarm set for {alarm.ring_time():%A at %H:%M}{repeat}"
+
+
+@registry.register("web_search", "Search the web for current facts and news; answer naming the source (\"according to ...\")")
+async def web_search(query: str) -> str:
+    """
+    Look something up on the web (see web_search.py). Only when the user asks
+    about something recent or that you don't know; cite the source by name.
+
+    Args:
+        query: What to search for, e.g. "Rust 1.90 release notes"
+    """
+    from .web_search import WebSearchError, get_web_search
+
+    search = get_web_search()
+    if search is None:
+        return "✗ Web search is off (set web_search in the config to searxng or brave to turn it on)"
+    try:
+        return await search.answer_context(query)
+    except WebSearchError as e:
+        return f"✗ {e}"
+
+
+@registry.register("read_news", "Read out new items from the followed RSS/Atom feeds (marks them read)")
+def read_news(feed: str = "", limit: int = 5) -> str:
+    """
+    The newest unread items from followed feeds (`xswarm dev news`), grouped by feed.
+
+    Args:
+        feed: Only this feed (name, id or URL); empty for all
+        limit: How many items at most
+    """
+    from .news import get_news_store, news_briefing
+
+    store = get_news_store()
+    store.reload()
+    found = store.get_feed(feed) if feed else None
+    if feed and found is None:
+        return f"✗ Not following a feed called '{feed}'"
+    return news_briefing(store, limit, feed_id=found.id if found else None) or "Nothing new in the feeds."
@@ -6,11 +6,16 @@ Handles continuous speech recognition for the user's voice input.
 import json
 import queue
 import threading
+import time
 import logging
 from typing import Optional, Callable
 from pathlib import Path
 import numpy as np
 
+from .audio_frame import AudioLike, pcm16_bytes
+from .chaos import ChaosError, get_chaos
+from .resample import AudioFormat
+
 try:
     from vosk import Model, KaldiRecognizer
 except ImportError:
@@ -35,9 +40,12 @@ class UserTranscriber:
         Args:
             model_path: Path to Vosk model
             sample_rate: Audio sample rate
-            on_text: Callback for recognized text (text, is_final)
+            on_text: Callback for recognized text (text, is_final), partial hypotheses
+                as they change and then the final transcript
         """
         self.sample_rate = sample_rate
+        # Vosk takes 16-bit PCM at the model's rate; the pipeline resamples frames to this
+        self.input_format = AudioFormat(sample_rate, "int16")
         self.on_text = on_text
         
         if not model_path.exists():
@@ -49,8 +57,11 @@ class UserTranscriber:
         self.recognizer.SetWords(True)
         
         self.is_active = False
+        self._partial = ""  # The last partial hypothesis passed on
         self._audio_queue = queue.Queue()
         self._thread: Optional[threading.Thread] = None
+        # Seconds between decodes, frames queued meanwhile decoded together (low-power profile, see power.py)
+        self.poll_interval = 0.0
 
     def start(self):
         """Start transcription thread"""
@@ -69,39 +80,59 @@ class UserTranscriber:
             self._thread.join(timeout=1.0)
         logger.info("User transcription stopped")
 
-    def process_audio(self, audio: np.ndarray):
+    def warm(self, seconds: float = 1.0) -> None:
+        """Decode silence with a throwaway recognizer, paging the model back in (see warmup.py)."""
+        recognizer = KaldiRecognizer(self.model, self.sample_rate)
+        recognizer.AcceptWaveform(bytes(int(self.sample_rate * seconds) * 2))
+        recognizer.FinalResult()
+
+    def process_audio(self, audio: AudioLike):
         """
         Process audio frame.
         
         Args:
-            audio: Audio samples at 16kHz (int16 or float32)
+            audio: Audio samples at 16kHz (int16 or float32), or a shared AudioFrame
         """
         if not self.is_active:
             return
 
-        # Convert to int16 if needed
-        if audio.dtype == np.float32 or audio.dtype == np.float64:
-            audio = (audio * 32767).astype(np.int16)
+        # Convert to int16 bytes (done once per frame for shared AudioFrames)
+        self._audio_queue.put(pcm16_bytes(audio))
 
-        self._audio_queue.put(audio.tobytes())
+    def _deliver(self, text: str):
+        """Pass on a final transcript, unless chaos mode loses it (see chaos.py)."""
+        try:
+            get_chaos().disrupt_sync("stt", "vosk")
+        except ChaosError:
+            self.on_text("", False)  # Clear the caption: the utterance is gone
+            return
+        self.on_text(text, True)
 
     def _transcription_loop(self):
         """Background thread for transcription"""
         while self.is_active:
             try:
                 audio_data = self._audio_queue.get(timeout=0.1)
+                while not self._audio_queue.empty():
+                    audio_data += self._audio_queue.get_nowait()
 
                 if self.recognizer.AcceptWaveform(audio_data):
                     # Final result
                     result = json.loads(self.recognizer.Result())
                     text = result.get("text", "").strip()
                     if text and self.on_text:
-                        self.on_text(text, True)
+                        self._deliver(text)
+                    elif self._partial and self.on_text:
+                        self.on_text("", False)  # Only noise after all: clear the caption
+                    self._partial = ""
                 else:
-                    # Partial result
-                    # We usually don't want to show partials in chat to avoid flickering
-                    # But we could if we wanted to support streaming text
-                    pass
+                    # Partial result, passed on only when it changes (live captions - see captions.py)
+                    partial = json.loads(self.recognizer.PartialResult()).get("partial", "").strip()
+                    if partial != self._partial and self.on_text:
+                        self.on_text(partial, False)
+                    self._partial = partial
+                if self.poll_interval:
+                    time.sleep(self.poll_interval)
 
             except queue.Empty:
                 continue
@@ -0,0 +1,173 @@
+"""
+Tray Companion - Quick actions from the menu bar/tray, without the TUI.
+
+`xswarm tray` runs in its own process and talks to the running assistant
+over the control socket (see control.py):
+
+- the icon shows the mic state like the privacy indicator (privacy.py)
+- Mute mic / Do not disturb toggle them (checked while on)
+- Next: the next appointment, refreshed every few seconds
+- Open dashboard starts the assistant in a terminal if it isn't running,
+  otherwise rings the dashboard's bell
+- Quit xSwarm exits the assistant; Close tray only closes this menu
+
+Needs pystray and Pillow: `pip install voice-assistant[tray]`.
+"""
+
+import logging
+import platform
+import shutil
+import subprocess
+import threading
+from typing import Any, Callable, Dict, List, Optional
+
+from .control import ControlError, send_command
+from .privacy import MicState, draw_icon
+
+logger = logging.getLogger(__name__)
+
+POLL_SECONDS = 5.0
+TERMINALS = ("x-terminal-emulator", "gnome-terminal", "konsole", "xterm")
+
+
+def terminal_command(system: Optional[str] = None) -> Optional[List[str]]:
+    """Command that opens a terminal running the assistant, or None if no terminal is known."""
+    program = shutil.which("xswarm") or "xswarm"
+    if (system or platform.system()) == "Darwin":
+        return ["open", "-a", "Terminal", program]
+    for terminal in TERMINALS:
+        path = shutil.which(terminal)
+        if path:
+            return [path, "--", program] if terminal == "gnome-terminal" else [path, "-e", program]
+    return None
+
+
+def launch_dashboard() -> str:
+    command = terminal_command()
+    if command is None:
+        return "✗ No terminal found - run xswarm yourself"
+    subprocess.Popen(command, start_new_session=True)
+    return "✓ Starting xSwarm"
+
+
+class TrayCompanion:
+    """Tray menu state and actions; `send` talks to the assistant (control.send_command)."""
+
+    def __init__(self, send: Callable[..., Dict[str, Any]] = send_command,
+                 launch: Callable[[], str] = launch_dashboard):
+        self.send = send
+        self.launch = launch
+        self.status: Dict[str, Any] = {}  # Last "status" reply, empty while the assistant isn't running
+        self._icon = None
+        self._stop = threading.Event()
+
+    @property
+    def running(self) -> bool:
+        return bool(self.status)
+
+    @property
+    def mic_state(self) -> MicState:
+        try:
+            return MicState(self.status.get("mic", "off"))
+        except ValueError:
+            return MicState.OFF
+
+    def title(self) -> str:
+        return self.status.get("message", "xSwarm") if self.running else "xSwarm: not running"
+
+    def next_label(self) -> str:
+        return f"Next: {self.status.get('next', '?')}" if self.running else "Next: -"
+
+    def refresh(self) -> None:
+        try:
+            self.status = self.send("status")
+        except ControlError:
+            self.status = {}
+        if not self.status.get("ok"):
+            self.status = {}
+        self._update_icon()
+
+    def _command(self, command: str, **args) -> str:
+        try:
+            reply = self.send(command, **args)
+        except ControlError as e:
+            return f"✗ {e}"
+        return reply.get("message", "✓ Done") if reply.get("ok") else f"✗ {reply.get('error', 'Failed')}"
+
+    def toggle_mute(self) -> str:
+        result = self._command("mute")
+        self.refresh()
+        return self._notify(result)
+
+    def toggle_dnd(self) -> str:
+        result = self._command("dnd")
+        self.refresh()
+        return self._notify(result)
+
+    def open_dashboard(self) -> str:
+        self.refresh()
+        return self._notify(self._command("show") if self.running else self.launch())
+
+    def quit_assistant(self) -> str:
+        result = self._command("quit")
+        self.status = {}
+        self._update_icon()
+        return self._notify(result)
+
+    def _notify(self, message: str) -> str:
+        logger.info(f"Tray: {message}")
+        if self._icon is not None and message.startswith("✗"):
+            try:
+                self._icon.notify(message.lstrip("✗ "), "xSwarm")
+            except Exception:
+                pass
+        return message
+
+    def _update_icon(self) -> None:
+        if self._icon is not None:
+            self._icon.icon = draw_icon(self.mic_state, bool(self.status.get("egress")))
+            self._icon.title = self.title()
+            self._icon.update_menu()
+
+    def _menu(self, pystray):
+        item = pystray.MenuItem
+        return pystray.Menu(
+            item(lambda _item: self.title(), None, enabled=False),
+            item(lambda _item: self.next_label(), None, enabled=False),
+            pystray.Menu.SEPARATOR,
+            item("Mute mic", lambda: self.toggle_mute(), checked=lambda _item: bool(self.status.get("muted")),
+                 enabled=lambda _item: bool(self.status.get("voice"))),
+            item("Do not disturb", lambda: self.toggle_dnd(), checked=lambda _item: bool(self.status.get("dnd")),
+                 enabled=lambda _item: self.running),
+            item("Open dashboard", lambda: self.open_dashboard(), default=True),
+            pystray.Menu.SEPARATOR,
+            item("Quit xSwarm", lambda: self.quit_assistant(), enabled=lambda _item: self.running),
+            item("Close tray", lambda: self.stop()),
+        )
+
+    def _poll(self) -> None:
+        while not self._stop.wait(POLL_SECONDS):
+            self.refresh()
+
+    def run(self) -> bool:
+        """Show the tray menu until closed; False (logged) when the tray libraries or a tray aren't available."""
+        try:
+            import pystray
+        except ImportError:
+            logger.warning("The tray needs pystray and Pillow: pip install voice-assistant[tray]")
+            return False
+        self.refresh()
+        try:
+            self._icon = pystray.Icon("xswarm-tray", draw_icon(self.mic_state), self.title(), self._menu(pystray))
+        except Exception as e:
+            logger.warning(f"Could not show the tray icon: {e}")
+            return False
+        threading.Thread(target=self._poll, name="tray-poll", daemon=True).start()
+        self._icon.run()
+        return True
+
+    def stop(self) -> None:
+        self._stop.set()
+        if self._icon is not None:
+            self._icon.stop()
+            self._icon = None
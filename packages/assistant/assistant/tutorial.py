@@ -0,0 +1,246 @@
+"""
+Tutorial - A first-use walkthrough, run as a flow (see flows.py).
+
+The first time the dashboard starts (nothing in ~/.xswarm/tutorial.json),
+the tutorial starts on its own once the welcome is shown. It teaches, one
+question at a time:
+
+1. the wake word: "say 'jarvis, hello'" - passes once the wake word is heard
+2. the microphone: "say 'testing, one, two, three' and watch the wave" -
+   passes when the visualizer's mic level got above QUIET_LEVEL, otherwise
+   it suggests moving closer or picking another microphone (voice only)
+3. a reminder: "remind me to stretch in ten minutes" - read back with its
+   time, as practice (nothing is saved)
+4. the calendar: "what's on my calendar?" - answered with the next event
+
+Each question can be skipped ("skip") and the tutorial stopped ("cancel").
+Starting it unprompted is recorded straight away, and finishing or stopping
+it again, so it never starts by itself a second time; "start the tutorial"
+runs it again whenever the user likes.
+
+The dashboard installs the hooks the questions check against (wake words,
+the mic meter, the calendar) with set_tutorial_hooks.
+"""
+
+import json
+import logging
+import re
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from .capabilities import register_capability
+from .dates import parse_natural_datetime, parse_time_expression
+from .flows import Flow, Step, register_flow
+from .conversation_window import strip_wake_word
+from .timers import parse_duration
+from .verbalize import verbalize_datetime
+
+logger = logging.getLogger(__name__)
+
+TUTORIAL_PATH = Path.home() / ".xswarm" / "tutorial.json"
+QUIET_LEVEL = 0.02  # Peak mic amplitude (0-1, as the visualizer gets it) below which speech barely registers
+TIMEOUT = 300.0  # Seconds a tutorial question waits: people try things out in between
+RETRIES = 3
+
+_REMIND = re.compile(r"^\W*(?:please\s+)?remind\s+me\s+(?P<rest>.+?)\W*$", re.IGNORECASE)
+_WHEN = re.compile(r"\b(?:in|at|on|by|tomorrow|tonight|today|this|next)\b", re.IGNORECASE)
+_WHAT = re.compile(r"^(?:to|about)\s+", re.IGNORECASE)
+_CALENDAR = re.compile(
+    r"\b(?:calendar|schedule|agenda|appointments?|meetings?|diary|what(?:'s| is)\s+(?:on|next|coming\s+up))\b",
+    re.IGNORECASE)
+
+
+class TutorialProgress:
+    """Whether the tutorial has run, kept in ~/.xswarm/tutorial.json."""
+
+    def __init__(self, path: Optional[Path] = None):
+        self.path = Path(path) if path else TUTORIAL_PATH
+        try:
+            self.record: Dict[str, Any] = json.loads(self.path.read_text(encoding="utf-8"))
+        except (OSError, ValueError):
+            self.record = {}
+
+    @property
+    def should_offer(self) -> bool:
+        """True until the tutorial has been started once."""
+        return not self.record
+
+    def mark(self, outcome: str, now: Optional[datetime] = None) -> None:
+        """Note that the tutorial was "started", "finished" or "cancelled"."""
+        self.record.setdefault("started_at", (now or datetime.now()).isoformat(timespec="seconds"))
+        self.record["outcome"] = outcome
+        if outcome != "started":
+            self.record["ended_at"] = (now or datetime.now()).isoformat(timespec="seconds")
+        try:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            self.path.write_text(json.dumps(self.record, indent=2), encoding="utf-8")
+        except OSError as e:
+            logger.warning(f"Could not save tutorial progress: {e}")
+
+
+class MicMeter:
+    """The loudest mic level the visualizer has shown since reset()."""
+
+    def __init__(self):
+        self.peak = 0.0
+
+    def sample(self, level: float) -> None:
+        self.peak = max(self.peak, level)
+
+    def reset(self) -> None:
+        self.peak = 0.0
+
+
+@dataclass
+class TutorialHooks:
+    """What the tutorial's questions check against; the dashboard installs the real ones."""
+    wake_words: List[str] = field(default_factory=list)
+    wake_word_heard: Callable[[], bool] = lambda: False  # Said recently (it's stripped from what the flow gets)
+    mic: Optional[MicMeter] = None  # None: voice is off, the microphone question is left out
+    calendar: Optional[Callable[[], str]] = None  # The next event; None: the planner's
+    progress: Optional[TutorialProgress] = None
+    now: Callable[[], datetime] = datetime.now
+
+
+_hooks: Optional[TutorialHooks] = None
+
+
+def get_tutorial_hooks() -> TutorialHooks:
+    global _hooks
+    if _hooks is None:
+        _hooks = TutorialHooks()
+    return _hooks
+
+
+def set_tutorial_hooks(hooks: Optional[TutorialHooks]) -> None:
+    global _hooks
+    _hooks = hooks
+
+
+def _due(when: str, now: datetime) -> Optional[datetime]:
+    if when.lower().startswith("in "):
+        seconds = parse_duration(when)
+        return now + timedelta(seconds=seconds) if seconds else None
+    due = parse_natural_datetime(when, today=now.date())
+    clock = parse_time_expression(re.sub(r"^(?:at|by)\s+", "", when, flags=re.IGNORECASE))
+    if due is None and clock:
+        hour, minute = map(int, clock.split(":"))
+        due = now.replace(hour=hour, minute=minute, second=0, microsecond=0)
+        if due <= now:
+            due += timedelta(days=1)
+    if due and "tonight" in when.lower() and due.hour < 12:
+        due += timedelta(hours=12)  # "tonight at 8"
+    return due
+
+
+def parse_reminder(text: str, now: datetime) -> Optional[Tuple[str, datetime]]:
+    """ "remind me to stretch in ten minutes" -> ("stretch", now + 10 minutes); None if it isn't one."""
+    match = _REMIND.match(text or "")
+    if not match:
+        return None
+    rest = match.group("rest")
+    parts = re.split(r"\s+(?:to|about)\s+", rest, maxsplit=1)
+    if len(parts) == 2 and _WHEN.match(parts[0]):  # "remind me at 5 to call Mum"
+        due = _due(parts[0], now)
+        return (parts[1].strip(), due) if due else None
+    for when in _WHEN.finditer(rest):  # "remind me to check in on Dad at 5": the first split that reads as a time
+        what = _WHAT.sub("", rest[:when.start()].strip())
+        due = _due(rest[when.start():], now) if what else None
+        if due:
+            return what, due
+    return None
+
+
+def _next_event() -> str:
+    from .control import next_appointment
+    from .tools import get_planner_data
+    return next_appointment(get_planner_data())
+
+
+def tutorial_flow(hooks: Optional[TutorialHooks] = None) -> Flow:
+    """The walkthrough; see the module docstring."""
+    hooks = hooks or get_tutorial_hooks()
+    progress = hooks.progress or TutorialProgress()
+    wake = hooks.wake_words[0] if hooks.wake_words else ""
+    steps = []
+
+    if wake:
+        def check_wake_word(value, _values) -> Optional[str]:
+            if hooks.wake_word_heard() or strip_wake_word(value, hooks.wake_words) is not None:
+                return None
+            return f"I didn't hear '{wake}' in that."
+
+        steps.append(Step("wake_word", f"First, the wake word: start with '{wake}' whenever you want me. "
+                                       f"Try it now - say '{wake}, hello'.", optional=True, validate=check_wake_word))
+
+    if hooks.mic is not None:
+        def ask_mic(values) -> str:
+            hooks.mic.reset()  # Only this answer counts (again after a retry or "repeat")
+            heard = "Perfect, I heard that. " if values.get("wake_word") else ""
+            return (f"{heard}Next, your microphone. Watch the wave at the bottom of the screen "
+                    "and say 'testing, one, two, three'.")
+
+        def check_mic(_value, _values) -> Optional[str]:
+            if hooks.mic.peak >= QUIET_LEVEL:
+                return None
+            return ("That was very quiet - the wave hardly moved. Move closer to the microphone, "
+                    "or pick another one in Settings, then try again.")
+
+        steps.append(Step("mic", ask_mic, optional=True, validate=check_mic))
+
+    def ask_reminder(values) -> str:
+        lead = "Your microphone sounds good. " if values.get("mic") else ""
+        return (f"{lead}Now let's practise a reminder. Say something like "
+                "'remind me to stretch in ten minutes'.")
+
+    def check_reminder(value, _values) -> Optional[str]:
+        if parse_reminder(value, hooks.now()):
+            return None
+        return "Say what to be reminded about and when, like 'remind me to call Mum at five'."
+
+    def ask_calendar(values) -> str:
+        reminder = parse_reminder(values["reminder"], hooks.now()) if values.get("reminder") else None
+        lead = ""
+        if reminder:
+            what, due = reminder
+            lead = (f"That's all it takes - I'd remind you to {what} {verbalize_datetime(due, now=hooks.now())}. "
+                    "This one was practice, so I haven't set it. ")
+        return f"{lead}Last one: ask me about your calendar - try 'what's on my calendar?'"
+
+    def check_calendar(value, _values) -> Optional[str]:
+        return None if _CALENDAR.search(value) else "Ask about your calendar or schedule."
+
+    def complete(values: Dict[str, Any]) -> str:
+        progress.mark("finished", hooks.now())
+        answer = ""
+        if values.get("calendar"):
+            try:
+                answer = f"Next up: {(hooks.calendar or _next_event)()}. "
+            except Exception as e:
+                logger.debug(f"Tutorial calendar answer failed: {e}")
+        return (f"{answer}That's the tour! Ask me anything the same way. "
+                "Say 'start the tutorial' whenever you want to go through it again.")
+
+    def cancel(_values: Dict[str, Any]) -> str:
+        progress.mark("cancelled", hooks.now())
+        return "Okay, tutorial stopped. Say 'start the tutorial' whenever you'd like to pick it up."
+
+    steps += [
+        Step("reminder", ask_reminder, optional=True, validate=check_reminder),
+        Step("calendar", ask_calendar, optional=True, validate=check_calendar),
+    ]
+    return Flow("tutorial", steps, on_complete=complete, on_cancel=cancel, timeout=TIMEOUT, max_retries=RETRIES,
+                intro="Welcome! Here's a quick tour of how to talk to me. Say 'skip' to move past a step, "
+                      "or 'cancel' to stop.")
+
+
+register_flow("tutorial", tutorial_flow,
+              triggers=[r"(?:please\s+)?(?:start|run|begin|replay|restart|do)\s+(?:the\s+)?(?:tutorial|tour)"
+                        r"(?:\s+again)?(?:\s+please)?",
+                        r"tutorial", r"(?:show|teach)\s+me\s+how\s+(?:to\s+use\s+(?:this|you|xswarm)|this\s+works)"])
+
+register_capability("Tutorial", "A short guided tour of talking to me",
+                    ["start the tutorial", "show me how to use this"], category="Settings",
+                    keywords=("tour", "getting started", "learn"))
@@ -0,0 +1,180 @@
+"""
+Undo - Reverse destructive actions within a short window.
+
+Destructive tools (delete an event or task, complete a reminder, forget
+memories) record an UndoEntry describing how to put things back:
+
+- planner_restore: a soft-deleted planner item (PlannerData keeps it in its
+  trash until the window passes) is moved back
+- planner_fields:  fields changed by completing something are reset
+- profile_facts:   forgotten user facts are added back
+- scheduling_constraints: forgotten scheduling preferences are added back
+- bookmarks:       forgotten conversation bookmarks are added back
+- config_fields:   a setting changed by asking (voice_config.py) goes back to what it was
+
+Entries can be undone for UNDO_WINDOW after they were recorded, newest
+first, via the "undo that" intent, Ctrl+Z in the TUI, the undo_last_action
+tool or `xswarm dev undo`.
+
+Storage: ~/.xswarm/undo/log.json
+"""
+
+import json
+import logging
+import re
+import uuid
+from dataclasses import asdict, dataclass
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from .capabilities import register_capability
+
+logger = logging.getLogger(__name__)
+
+UNDO_WINDOW = timedelta(minutes=5)
+
+# Entries are kept a while past the window so `xswarm dev undo --list` can show recent history
+HISTORY_KEEP = timedelta(days=1)
+
+_UNDO_INTENT = re.compile(
+    r"^\s*(?:(?:oops|wait|no)[,!.]?\s+)?(?:please\s+)?"
+    r"(?:undo(?:\s+(?:that|it|this|the\s+last\s+(?:one|thing|action|change)))?|put\s+(?:that|it)\s+back|restore\s+(?:that|it))"
+    r"(?:\s+please)?\s*[.!]*\s*$",
+    re.IGNORECASE,
+)
+
+
+def is_undo_request(text: str) -> bool:
+    """True for short utterances like "undo that", "oops, undo" or "put it back"."""
+    return bool(_UNDO_INTENT.match(text or ""))
+
+
+@dataclass
+class UndoEntry:
+    """One reversible action."""
+    id: str
+    kind: str  # "planner_restore", "planner_fields", "profile_facts"
+    description: str  # What was done, e.g. "Deleted event 'Dentist'"
+    payload: Dict[str, Any]
+    created_at: str
+    undone_at: Optional[str] = None
+
+    def age(self, now: Optional[datetime] = None) -> timedelta:
+        return (now or datetime.now()) - datetime.fromisoformat(self.created_at)
+
+
+class UndoLog:
+    """Persistent log of reversible actions."""
+
+    DEFAULT_DIR = Path.home() / ".xswarm" / "undo"
+
+    def __init__(self, storage_dir: Optional[Path] = None, window: timedelta = UNDO_WINDOW):
+        self.storage_dir = storage_dir or self.DEFAULT_DIR
+        self.storage_dir.mkdir(parents=True, exist_ok=True)
+        self.window = window
+        self._entries: Optional[List[UndoEntry]] = None
+
+    def _log_path(self) -> Path:
+        return self.storage_dir / "log.json"
+
+    def _load(self) -> List[UndoEntry]:
+        if self._entries is not None:
+            return self._entries
+        path = self._log_path()
+        try:
+            data = json.loads(path.read_text(encoding="utf-8")) if path.exists() else {}
+            self._entries = [UndoEntry(**e) for e in data.get("entries", [])]
+        except Exception as e:
+            logger.warning(f"Failed to load undo log: {e}")
+            self._entries = []
+        return self._entries
+
+    def _save(self) -> None:
+        if self._entries is None:
+            return
+        try:
+            data = {"entries": [asdict(e) for e in self._entries]}
+            self._log_path().write_text(json.dumps(data, indent=2, ensure_ascii=False), encoding="utf-8")
+        except Exception as e:
+            logger.warning(f"Failed to save undo log: {e}")
+
+    def reload(self) -> None:
+        self._entries = None
+        self._load()
+
+    def record(self, kind: str, description: str, payload: Dict[str, Any],
+               now: Optional[datetime] = None) -> UndoEntry:
+        """Remember how to reverse an action that just happened."""
+        now = now or datetime.now()
+        entries = [e for e in self._load() if e.age(now) < HISTORY_KEEP]
+        entry = UndoEntry(
+            id=f"undo-{uuid.uuid4().hex[:8]}",
+            kind=kind,
+            description=description,
+            payload=payload,
+            created_at=now.isoformat(),
+        )
+        entries.append(entry)
+        self._entries = entries
+        self._save()
+        return entry
+
+    def entries(self) -> List[UndoEntry]:
+        """All remembered actions, newest first."""
+        return list(reversed(self._load()))
+
+    def undoable(self, now: Optional[datetime] = None) -> List[UndoEntry]:
+        """Actions still inside the undo window, newest first."""
+        return [e for e in self.entries() if e.undone_at is None and e.age(now) < self.window]
+
+    def mark_undone(self, entry_id: str, now: Optional[datetime] = None) -> None:
+        for entry in self._load():
+            if entry.id == entry_id:
+                entry.undone_at = (now or datetime.now()).isoformat()
+        self._save()
+
+
+def undo_last(log: UndoLog, planner=None, profile=None, now: Optional[datetime] = None) -> str:
+    """Reverse the most recent undoable action. Returns a ✓/✗ status line."""
+    pending = log.undoable(now)
+    if not pending:
+        minutes = int(log.window.total_seconds() // 60)
+        return f"✗ Nothing to undo (actions can be undone for {minutes} minutes)"
+
+    entry = pending[0]
+    try:
+        restored = _revert(entry, planner, profile)
+    except Exception as e:
+        logger.warning(f"Undo of {entry.id} failed: {e}")
+        restored = False
+    if not restored:
+        return f"✗ Couldn't undo: {entry.description}"
+
+    log.mark_undone(entry.id, now)
+    return f"✓ Undone: {entry.description}"
+
+
+def _revert(entry: UndoEntry, planner, profile) -> bool:
+    payload = entry.payload
+    if entry.kind == "planner_restore":
+        return planner is not None and planner.restore_deleted(payload["collection"], payload["id"]) is not None
+    if entry.kind == "planner_fields":
+        return planner is not None and planner.restore_fields(payload["collection"], payload["id"], payload["fields"])
+    if entry.kind == "profile_facts":
+        return profile is not None and profile.restore_facts(payload["facts"]) > 0
+    if entry.kind == "scheduling_constraints":
+        from .scheduling_preferences import get_scheduling_preferences
+        return get_scheduling_preferences().restore(payload["constraints"]) > 0
+    if entry.kind == "bookmarks":
+        from .bookmarks import get_bookmark_store
+        return get_bookmark_store().restore(payload["bookmarks"]) > 0
+    if entry.kind == "config_fields":
+        from .voice_config import get_config_changer
+        return get_config_changer().restore(payload["setting"], payload["fields"])
+    logger.warning(f"Unknown undo kind '{entry.kind}'")
+    return False
+
+
+register_capability("Undo", "Take back a delete, a completed task or a forgotten fact for five minutes after",
+                    ["undo that", "put it back"], category="Planning", keywords=("mistake", "restore"))
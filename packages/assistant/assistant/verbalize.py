@@ -0,0 +1,280 @@
+"""
+Spoken Dates - Turn datetimes into the words a person would say.
+
+The inverse of dates.py: where parse_natural_datetime("tomorrow at half past
+two") gives a datetime, verbalize_datetime() gives "tomorrow at half past
+two" back. Used wherever the assistant talks rather than prints:
+
+- reminders (reminders.Reminder.spoken): "Reminder: Standup in ten minutes."
+- anything read aloud (voice.VoiceBridgeOrchestrator.speak_text runs the
+  text through speakable(), so "✓ Moved 'Report' to 2026-10-17" is read
+  as "... to tomorrow")
+- the morning briefing's calendar (planner.PlanningSession morning context)
+
+join_words() is the spoken "A, B and C" list the briefings, timers, lists
+and reviews share.
+
+Follows DateSettings (dates.py):
+- clock: 12h gives "half past two", "quarter to seven in the evening"; 24h
+  gives "fourteen thirty", "eighteen forty-five"
+- order: mdy gives "April fifth", dmy gives "the fifth of April"
+- timezone: times are spoken in the display zone (timezones.py): naive
+  datetimes are home times as the planner stores them, converted when
+  travel mode is on; aware ones are converted from wherever they are
+
+12h times leave out am/pm when the bare hour would be read right anyway
+(dates.py reads a bare 7-11 as morning and 1-6 as afternoon), so a 12h
+time said here parses back to the same time.
+"""
+
+import re
+from datetime import date, datetime, time, timedelta
+from typing import List, Optional, Union
+
+from .dates import MONTHS, WEEKDAYS, DateSettings, get_date_settings
+from .timezones import display_now, to_display
+
+ONES = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen",
+        "eighteen", "nineteen"]
+TENS = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"]
+ORDINAL_ONES = {
+    "one": "first", "two": "second", "three": "third", "five": "fifth", "eight": "eighth",
+    "nine": "ninth", "twelve": "twelfth",
+}
+
+# Datetimes closer than this to now are said relatively ("in twenty minutes")
+RELATIVE_WITHIN = timedelta(hours=1)
+
+
+def number_words(n: int) -> str:
+    """0-9999 in words: 7 -> "seven", 45 -> "forty-five", 2026 -> "two thousand twenty-six"."""
+    if n < 0:
+        return f"minus {number_words(-n)}"
+    if n < 20:
+        return ONES[n]
+    if n < 100:
+        tens, ones = divmod(n, 10)
+        return TENS[tens] + (f"-{ONES[ones]}" if ones else "")
+    if n < 1000:
+        hundreds, rest = divmod(n, 100)
+        return f"{ONES[hundreds]} hundred" + (f" {number_words(rest)}" if rest else "")
+    thousands, rest = divmod(n, 1000)
+    return f"{number_words(thousands)} thousand" + (f" {number_words(rest)}" if rest else "")
+
+
+def ordinal_words(n: int) -> str:
+    """1 -> "first", 22 -> "twenty-second", 30 -> "thirtieth"."""
+    words = number_words(n)
+    head, sep, last = words.rpartition("-") if "-" in words else ("", "", words)
+    if last in ORDINAL_ONES:
+        last = ORDINAL_ONES[last]
+    elif last.endswith("y"):
+        last = last[:-1] + "ieth"
+    else:
+        last += "th"
+    return f"{head}{sep}{last}"
+
+
+def year_words(year: int) -> str:
+    """How years are said: 2026 -> "twenty twenty-six", 2000 -> "two thousand", 2005 -> "two thousand five"."""
+    if year % 1000 < 10 or not 1100 <= year < 10000:
+        return number_words(year)
+    century, rest = divmod(year, 100)
+    return f"{number_words(century)} {_minutes_words(rest) if rest else 'hundred'}"
+
+
+def join_words(items: List[str]) -> str:
+    """A spoken list: "A", "A and B", "A, B and C"."""
+    return items[0] if len(items) == 1 else f"{', '.join(items[:-1])} and {items[-1]}"
+
+
+def _minutes_words(minute: int) -> str:
+    return f"oh {ONES[minute]}" if minute < 10 else number_words(minute)
+
+
+def _hour12(hour: int) -> str:
+    return ONES[hour % 12 or 12]
+
+
+def _day_part(hour: int) -> str:
+    """What to add to a 12h hour when the bare hour would be misread ("" when it wouldn't)."""
+    if 7 <= hour <= 18:
+        return ""
+    return " in the morning" if hour < 12 else " in the evening"
+
+
+def verbalize_time(value: Union[time, datetime], clock: Optional[str] = None) -> str:
+    """
+    A clock time in words.
+
+    12h: "noon", "midnight", "nine o'clock", "half past two", "quarter to
+    seven in the evening", "two oh five", "four twenty in the morning"
+    24h: "midnight", "nine hundred", "fourteen thirty", "nine oh five"
+    """
+    clock = clock or get_date_settings().resolved_clock()
+    hour, minute = value.hour, value.minute
+    if clock == "24h":
+        if minute == 0:
+            return "midnight" if hour == 0 else f"{number_words(hour)} hundred"
+        return f"{number_words(hour)} {_minutes_words(minute)}"
+
+    if minute == 0:
+        if hour == 0:
+            return "midnight"
+        if hour == 12:
+            return "noon"
+        return f"{_hour12(hour)} o'clock{_day_part(hour)}"
+    if minute in (15, 30):
+        if hour == 0:
+            return f"{'quarter' if minute == 15 else 'half'} past midnight"
+        return f"{'quarter' if minute == 15 else 'half'} past {_hour12(hour)}{_day_part(hour)}"
+    if minute == 45:
+        following = hour + 1
+        if following in (12, 24):
+            return f"quarter to {'noon' if following == 12 else 'midnight'}"
+        return f"quarter to {_hour12(following)}{_day_part(following)}"
+    return f"{_hour12(hour)} {_minutes_words(minute)}{_day_part(hour)}"
+
+
+def verbalize_date(day: date, today: Optional[date] = None, order: Optional[str] = None) -> str:
+    """
+    A day said relative to today where that's natural: "today", "tomorrow",
+    "yesterday", "Friday" (within the coming week), "last Friday" (within the
+    past week), else "April fifth"/"the fifth of April" (with the year when
+    it isn't this year).
+    """
+    today = today or date.today()
+    offset = (day - today).days
+    if offset == 0:
+        return "today"
+    if offset == 1:
+        return "tomorrow"
+    if offset == -1:
+        return "yesterday"
+    weekday = WEEKDAYS[day.weekday()].title()
+    if 1 < offset < 7:
+        return weekday
+    if -7 < offset < -1:
+        return f"last {weekday}"
+
+    order = order or get_date_settings().resolved_order()
+    month = MONTHS[day.month - 1].title()
+    spoken = f"{month} {ordinal_words(day.day)}" if order == "mdy" else f"the {ordinal_words(day.day)} of {month}"
+    if day.year != today.year:
+        spoken += f"{',' if order == 'mdy' else ''} {year_words(day.year)}"
+    return spoken
+
+
+def _amount(n: int, unit: str) -> str:
+    return f"{'an' if unit == 'hour' else 'a'} {unit}" if n == 1 and unit in ("hour", "day", "week") \
+        else f"{number_words(n)} {unit}{'s' if n != 1 else ''}"
+
+
+def verbalize_duration(delta: timedelta) -> str:
+    """
+    A length of time: "one minute", "twenty minutes", "an hour", "an hour
+    and a half", "two hours and ten minutes", "five hours", "three days".
+    Rounded the way people round: to the minute under three hours, to the
+    hour under two days, to the day after.
+    """
+    minutes = round(abs(delta).total_seconds() / 60)
+    if minutes < 60:
+        return f"{number_words(minutes)} minute{'s' if minutes != 1 else ''}"
+    hours, rest = divmod(minutes, 60)
+    if hours < 3:
+        if rest == 0:
+            return _amount(hours, "hour")
+        if rest == 30:
+            return f"{_amount(hours, 'hour')} and a half"
+        return f"{_amount(hours, 'hour')} and {number_words(rest)} minute{'s' if rest != 1 else ''}"
+    if minutes < 48 * 60:
+        return _amount(round(minutes / 60), "hour")
+    days = round(minutes / (24 * 60))
+    return _amount(days // 7, "week") if days % 7 == 0 and days < 35 else _amount(days, "day")
+
+
+def verbalize_relative(delta: timedelta) -> str:
+    """From now: "in twenty minutes", "in an hour and a half", "ten minutes ago", "in less than a minute", "just now"."""
+    if abs(delta) < timedelta(seconds=30):
+        return "in less than a minute" if delta > timedelta(0) else "just now"
+    spoken = verbalize_duration(delta)
+    return f"in {spoken}" if delta > timedelta(0) else f"{spoken} ago"
+
+
+def verbalize_datetime(value: datetime, now: Optional[datetime] = None,
+                       settings: Optional[DateSettings] = None, relative: bool = True) -> str:
+    """
+    A moment as it would be said: "in twenty minutes" (within the hour, when
+    `relative`), "today at noon", "tomorrow at half past two", "Friday at
+    nine o'clock", "the fifth of April at fourteen thirty".
+    """
+    settings = settings or get_date_settings()
+    local = to_display(value, settings)
+    now = display_now(settings) if now is None else to_display(now, settings)
+    if relative and abs(local - now) < RELATIVE_WITHIN:
+        return verbalize_relative(local - now)
+    day = verbalize_date(local.date(), today=now.date(), order=settings.resolved_order())
+    return f"{day} at {verbalize_time(local, settings.resolved_clock())}"
+
+
+# Machine-formatted values in text meant for speech (tool results, replies)
+ISO_DATETIME_PATTERN = re.compile(r"\b(on )?(\d{4}-\d{2}-\d{2})(?:T| at | )(\d{2}:\d{2})(?::\d{2}(?:\.\d+)?)?\b")
+ISO_DATE_PATTERN = re.compile(r"\b(on )?(\d{4}-\d{2}-\d{2})\b")
+TIME_RANGE_PATTERN = re.compile(r"\b(\d{1,2}:\d{2})\s*(?:-|–|to)\s*(\d{1,2}:\d{2})\b")
+TIME_PATTERN = re.compile(r"(?<![\d:])(\d{1,2}):(\d{2})(?![\d:])")
+RELATIVE_DAYS = ("today", "tomorrow", "yesterday", "last ", "in ", "just ")
+
+
+def _parse_clock(text: str) -> Optional[time]:
+    hour, minute = (int(part) for part in text.split(":"))
+    return time(hour, minute) if hour < 24 and minute < 60 else None
+
+
+def speakable(text: str, now: Optional[datetime] = None, settings: Optional[DateSettings] = None) -> str:
+    """
+    Rewrite ISO dates and HH:MM times in `text` as words, for text-to-speech.
+
+    "Moved 'Report' to 2026-10-17" -> "Moved 'Report' to tomorrow"
+    "Added: 'Dentist' on 2026-10-20 at 14:30" -> "Added: 'Dentist' on Tuesday at half past two"
+    "Quiet until 07:00" -> "Quiet until seven o'clock"
+
+    "on" is dropped before relative days ("on tomorrow" -> "tomorrow").
+    Anything that isn't a valid date or time is left alone.
+    """
+    settings = settings or get_date_settings()
+    today = (now or display_now(settings)).date()
+    clock, order = settings.resolved_clock(), settings.resolved_order()
+
+    def with_on(on: Optional[str], spoken: str) -> str:
+        return spoken if not on or spoken.startswith(RELATIVE_DAYS) else f"on {spoken}"
+
+    def datetime_words(match: re.Match) -> str:
+        try:
+            value = datetime.fromisoformat(f"{match.group(2)}T{match.group(3)}")
+        except ValueError:
+            return match.group(0)
+        day = verbalize_date(value.date(), today=today, order=order)
+        return with_on(match.group(1), f"{day} at {verbalize_time(value, clock)}")
+
+    def date_words(match: re.Match) -> str:
+        try:
+            day = date.fromisoformat(match.group(2))
+        except ValueError:
+            return match.group(0)
+        return with_on(match.group(1), verbalize_date(day, today=today, order=order))
+
+    def range_words(match: re.Match) -> str:
+        start, end = _parse_clock(match.group(1)), _parse_clock(match.group(2))
+        if start is None or end is None:
+            return match.group(0)
+        return f"{verbalize_time(start, clock)} to {verbalize_time(end, clock)}"
+
+    def time_words(match: re.Match) -> str:
+        value = _parse_clock(match.group(0))
+        return verbalize_time(value, clock) if value else match.group(0)
+
+    text = ISO_DATETIME_PATTERN.sub(datetime_words, text)
+    text = ISO_DATE_PATTERN.sub(date_words, text)
+    text = TIME_RANGE_PATTERN.sub(range_words, text)
+    return TIME_PATTERN.sub(time_words, text)
@@ -15,7 +15,6 @@ import time
 import os
 import threading
 import logging
-from enum import Enum
 from typing import Optional, Callable, Dict, Any, AsyncGenerator, List
 from dataclasses import dataclass
 from datetime import datetime
@@ -28,13 +27,36 @@ import backoff
 
 # Local imports
 from .audio import AudioIO, VoiceActivityDetector
+from .audio_bus import AudioBroadcast, FrameQueue
+from .ai_log import log_ai_call
+from .chaos import ChaosError, get_chaos
+from .audio_frame import AudioFrame
+from .compute import CPU, get_compute_manager
+from .conversation_state import ConversationMachine, ConversationState, Event, Transition
+from .earcons import EarconPlayer
+from .errors import ConfigError, VoiceError
+from .flags import enabled as flag_enabled
+from .latency import BudgetedAI, LatencyBudget, Rung
+from .power import get_power_manager
+from .resample import AudioFormat, FormatNegotiator
+from .speech_cache import SILENCE_RMS, SpeechCache, SpeechCapture, voice_fingerprint
+from .supervisor import get_task_supervisor
 from .memory import MemoryManager, MemoryOrchestrator
+from .model_loading import LoadProgress, wait_for_server
+from .model_memory import MemoryReport, process_rss
 from .tools import registry, CommandParser, ToolExecutor
 from .transcription import UserTranscriber # Added UserTranscriber
+from .verbalize import speakable
+from .pronunciation import get_lexicon
+from .prosody import PLAIN, render
+from .speech_filter import get_speech_filter
+from .startup_profile import get_startup_profile
+from .warmup import Warmup
 # Note: Persona imports will be updated when personas are consolidated.
 # For now, assuming they are still in ..personas
 from .personas.manager import PersonaManager
 from .personas.config import PersonaConfig
+from .personas.phrases import get_phrase_bank
 
 # MLX imports (try/except for safety)
 try:
@@ -62,6 +84,11 @@ except ImportError:
 
 class MoshiClient:
     """Client that handles audio codec and communicates with server process."""
+    # Mimi encodes 24kHz mono; mic frames are resampled to this (see resample.py)
+    input_format = AudioFormat(24000)
+    # Moshi reads a text prompt, so speech hints are put into words (see prosody.py)
+    markup = PLAIN
+
     def __init__(self, client_to_server, server_to_client, hf_repo: str = "kyutai/moshiko-mlx-bf16", mimi_file: Optional[str] = None, log_callback: Optional[Callable[[str], None]] = None):
         self.client_to_server = client_to_server
         self.server_to_client = server_to_client
@@ -69,13 +96,17 @@ class MoshiClient:
         if mimi_file is None:
             mimi_file = huggingface_hub.hf_hub_download(hf_repo, "tokenizer-e351c8d8-checkpoint125.safetensors")
         self.audio_tokenizer = rustymimi.StreamTokenizer(mimi_file)
-        self.input_queue = queue.Queue()
-        self.output_queue = queue.Queue()
+        # Mic frames waiting for the encoder (drop-oldest if encoding falls behind)
+        self.input_queue = FrameQueue("mic/encoder", capacity=100)
+        # Visualizer taps are non-critical: downsampled while the UI lags, drained on each amplitude read
+        self._mic_visualizer = FrameQueue("mic/visualizer", capacity=8, critical=False)
+        self.output_bus = AudioBroadcast("moshi")
+        self._moshi_visualizer = self.output_bus.subscribe("visualizer", capacity=8, critical=False)
         self.on_output_audio: Optional[Callable[[np.ndarray], None]] = None
         self.on_text_token: Optional[Callable[[str], None]] = None
         self._running = False
-        self.mic_amplitude = 0.0
-        self.moshi_amplitude = 0.0
+        self._mic_amplitude = 0.0
+        self._moshi_amplitude = 0.0
 
     def log(self, msg: str):
         if self.log_callback:
@@ -86,13 +117,37 @@ class MoshiClient:
         return float(np.clip(rms * 4, 0, 1))
 
     def update_mic_amplitude(self, audio: np.ndarray):
-        self.mic_amplitude = self.get_amplitude(audio)
+        self._mic_visualizer.put_nowait(audio)
 
     def update_moshi_amplitude(self, audio: np.ndarray):
-        self.moshi_amplitude = self.get_amplitude(audio)
+        self._moshi_amplitude = self.get_amplitude(audio)
+
+    @property
+    def mic_amplitude(self) -> float:
+        latest = self._mic_visualizer.drain_latest()
+        if latest is not None:
+            self._mic_amplitude = self.get_amplitude(latest)
+        return self._mic_amplitude
+
+    @property
+    def moshi_amplitude(self) -> float:
+        latest = self._moshi_visualizer.drain_latest()
+        if latest is not None:
+            self._moshi_amplitude = self.get_amplitude(latest)
+        return self._moshi_amplitude
+
+    @property
+    def output_queue(self) -> FrameQueue:
+        """Decoded audio for pull-based consumers (subscribed on first use so nothing piles up unread)."""
+        return self.output_bus.subscribe("output", capacity=200)
+
+    def audio_stats(self) -> List[Dict[str, Any]]:
+        """Backpressure counters for the encoder queue and every decoded-audio subscriber."""
+        return [self.input_queue.stats(), self._mic_visualizer.stats(), *self.output_bus.stats()]
 
     def feed_audio(self, audio: np.ndarray):
-        self.input_queue.put_nowait(audio.astype(np.float32))
+        # asarray: no copy for float32 frames (mic frames are already float32 and read-only)
+        self.input_queue.put_nowait(np.asarray(audio, dtype=np.float32))
 
     def get_output_audio(self) -> Optional[np.ndarray]:
         try:
@@ -166,7 +221,7 @@ class MoshiClient:
                 # Robust unpacking
                 if isinstance(result, str):
                     if result == "ready":
-                        # Should have been consumed by wait_for_ready, but ignore if late
+                        # Should have been consumed by wait_for_server, but ignore if late
                         continue
                     self.log(f"⚠️ Unknown string message: {result}")
                     continue
@@ -218,8 +273,11 @@ class MoshiClient:
                     self.log("⚠️ Warning: Decoded audio contains NaN/Inf! Replacing with silence.")
                     data = np.zeros_like(data)
                 
-                # Clip to [-1, 1] to prevent distortion
-                data = np.clip(data, -1.0, 1.0)
+                # Clip to [-1, 1] to prevent distortion (in place - the decoder hands us a fresh array)
+                if data.flags.writeable:
+                    np.clip(data, -1.0, 1.0, out=data)
+                else:
+                    data = np.clip(data, -1.0, 1.0)
 
                 # Noise Gate: Silence very quiet audio to prevent white noise
                 rms = np.sqrt(np.mean(data**2))
@@ -240,7 +298,9 @@ class MoshiClient:
                 if np.random.random() < 0.005:
                     self.log(f"🔊 Decoded Audio: Shape={data.shape}, RMS={rms:.4f}")
 
-                self.output_queue.put_nowait(data)
+                # Shared by reference from here on (output bus subscribers, playback)
+                data.flags.writeable = False
+                self.output_bus.publish(data)
                 if self.on_output_audio:
                     self.on_output_audio(data)
         await asyncio.gather(send_loop(), send_loop2(), recv_loop(), recv_loop2())
@@ -270,10 +330,16 @@ class ConversationTurn:
     assistant_audio: Optional[np.ndarray] = None
     metadata: Optional[Dict[str, Any]] = None
 
+# Each provider's default model, and the smaller one a slow turn falls back to (see latency.py)
+MODELS = {"anthropic": "claude-3-5-sonnet-20241022", "openai": "gpt-4o"}
+FAST_MODELS = {"anthropic": "claude-3-5-haiku-20241022", "openai": "gpt-4o-mini"}
+
+
 class AIClient:
-    """Unified AI client wrapper."""
-    def __init__(self, config):
+    """Unified AI client wrapper; `purpose` names its calls in the AI log (see ai_log.py)."""
+    def __init__(self, config, purpose: str = "assistant"):
         self.config = config
+        self.purpose = purpose
         self.provider = None
         self.client = None
         self._initialize_client()
@@ -296,24 +362,104 @@ class AIClient:
             except ImportError:
                 pass
 
-    async def chat(self, messages: list, max_tokens: int = 1024) -> str:
-        if self.provider == "anthropic":
-            system_msg = next((m["content"] for m in messages if m["role"] == "system"), None)
-            conversation = [m for m in messages if m["role"] != "system"]
-            response = await self.client.messages.create(
-                model="claude-3-5-sonnet-20241022", max_tokens=max_tokens, messages=conversation, system=system_msg
-            )
-            return response.content[0].text
-        elif self.provider == "openai":
-            response = await self.client.chat.completions.create(
-                model="gpt-4o", messages=messages, max_tokens=max_tokens
-            )
-            return response.choices[0].message.content
-        raise RuntimeError("AI client not initialized")
+    async def chat(self, messages: list, max_tokens: int = 1024, model: Optional[str] = None) -> str:
+        if self.provider not in MODELS:
+            raise VoiceError("AI client not initialized")
+        model = model or MODELS[self.provider]
+        started = time.monotonic()
+        try:
+            if self.provider == "anthropic":
+                system_msg = next((m["content"] for m in messages if m["role"] == "system"), None)
+                conversation = [m for m in messages if m["role"] != "system"]
+                response = await self.client.messages.create(
+                    model=model, max_tokens=max_tokens, messages=conversation, system=system_msg
+                )
+                reply = response.content[0].text
+            else:
+                response = await self.client.chat.completions.create(
+                    model=model, messages=messages, max_tokens=max_tokens
+                )
+                reply = response.choices[0].message.content
+        except Exception as e:
+            log_ai_call(self.provider, model, self.purpose, {"messages": messages}, started=started, error=str(e))
+            raise
+        log_ai_call(self.provider, model, self.purpose, {"messages": messages}, reply, response, started)
+        return reply
 
     def is_available(self) -> bool:
         return self.client is not None
 
+
+class LocalAIClient:
+    """A model on this machine (config.local_ai_provider: ollama or lmstudio), the last rung of the ladder."""
+    URLS = {"ollama": "http://localhost:11434", "lmstudio": "http://localhost:1234"}
+
+    def __init__(self, config, purpose: str = "assistant"):
+        self.purpose = purpose
+        self.provider = getattr(config, "local_ai_provider", "disabled")
+        self.model = getattr(config, "local_ai_model", "")
+        self.url = (getattr(config, "local_ai_url", "") or self.URLS.get(self.provider, "")).rstrip("/")
+
+    def is_available(self) -> bool:
+        return self.provider in self.URLS and bool(self.model) and flag_enabled("local_llm")
+
+    async def chat(self, messages: list, max_tokens: int = 1024) -> str:
+        import httpx
+        started = time.monotonic()
+        async with httpx.AsyncClient(base_url=self.url, timeout=60.0) as client:
+            if self.provider == "ollama":
+                response = await client.post("/api/chat", json={
+                    "model": self.model, "messages": messages, "stream": False, "options": {"num_predict": max_tokens},
+                })
+                response.raise_for_status()
+                data = response.json()
+                reply = data["message"]["content"]
+            else:
+                # LM Studio speaks the OpenAI API
+                response = await client.post("/v1/chat/completions", json={
+                    "model": self.model, "messages": messages, "max_tokens": max_tokens,
+                })
+                response.raise_for_status()
+                data = response.json()
+                reply = data["choices"][0]["message"]["content"]
+        log_ai_call(self.provider, self.model, self.purpose, {"messages": messages}, reply, data, started)
+        return reply
+
+    async def warm(self, keep_alive: str = "1h") -> None:
+        """Load the model into memory ahead of the first question (see warmup.py)."""
+        import httpx
+        async with httpx.AsyncClient(base_url=self.url, timeout=60.0) as client:
+            if self.provider == "ollama":
+                # A prompt-less generate only loads the model
+                response = await client.post("/api/generate", json={"model": self.model, "keep_alive": keep_alive})
+            else:
+                # LM Studio loads models just in time for a request
+                response = await client.post("/v1/chat/completions", json={
+                    "model": self.model, "messages": [{"role": "user", "content": "hi"}], "max_tokens": 1,
+                })
+            response.raise_for_status()
+
+
+def budgeted_ai(config, on_filler=None, primary: Optional[AIClient] = None,
+                purpose: str = "assistant") -> BudgetedAI:
+    """
+    The configured AI behind a latency budget: primary model, then the
+    provider's small model, then the local model (whichever are set up).
+    on_filler(text) speaks a filler when the soft budget passes.
+    """
+    primary = primary or AIClient(config, purpose)
+    ladder = []
+    if primary.is_available():
+        ladder.append(Rung(f"{primary.provider}:{MODELS.get(primary.provider, 'default')}", primary.chat))
+        fast = FAST_MODELS.get(primary.provider)
+        if fast:
+            ladder.append(Rung(f"{primary.provider}:{fast}",
+                               lambda messages, max_tokens: primary.chat(messages, max_tokens, model=fast), small=True))
+    local = LocalAIClient(config, purpose)
+    if local.is_available():
+        ladder.append(Rung(f"{local.provider}:{local.model}", local.chat, local=True))
+    return BudgetedAI(ladder, LatencyBudget.from_config(config), on_filler=on_filler)
+
 # ==============================================================================
 # SUBCONSCIOUS BRIDGE (Bicameral Thinking)
 # ==============================================================================
@@ -341,7 +487,7 @@ class SubconsciousBridge:
 
     async def start(self):
         self.running = True
-        self._monitor_task = asyncio.create_task(self._monitor_loop())
+        self._monitor_task = get_task_supervisor().spawn("subconscious monitor", self._monitor_loop)
         logging.info("🧠 Subconscious Bridge started")
 
     def stop(self):
@@ -448,7 +594,7 @@ class SubconsciousBridge:
 
 class ConversationLoop:
     """Manages the conversation loop with VAD -> STT -> AI -> TTS -> Output."""
-    def __init__(self, moshi_bridge: Any, persona_manager: PersonaManager, memory_manager: MemoryManager, ai_client: AIClient, memory_orchestrator: Optional[MemoryOrchestrator] = None, subconscious_bridge: Optional['SubconsciousBridge'] = None, user_id: str = "default", on_turn_complete: Optional[Callable[[ConversationTurn], None]] = None, on_state_change: Optional[Callable[[str], None]] = None, log_callback: Optional[Callable[[str], None]] = None, audio_io: Optional[AudioIO] = None, on_text_output: Optional[Callable[[str, str], None]] = None):
+    def __init__(self, moshi_bridge: Any, persona_manager: PersonaManager, memory_manager: MemoryManager, ai_client: AIClient, memory_orchestrator: Optional[MemoryOrchestrator] = None, subconscious_bridge: Optional['SubconsciousBridge'] = None, user_id: str = "default", on_turn_complete: Optional[Callable[[ConversationTurn], None]] = None, on_state_change: Optional[Callable[[str], None]] = None, log_callback: Optional[Callable[[str], None]] = None, audio_io: Optional[AudioIO] = None, on_text_output: Optional[Callable[[str, str], None]] = None, machine: Optional[ConversationMachine] = None):
         self.moshi = moshi_bridge
         self.persona = persona_manager
         self.memory = memory_manager
@@ -460,15 +606,26 @@ class ConversationLoop:
         self.on_state_change = on_state_change
         self.log_callback = log_callback
         self.on_text_output = on_text_output
+        # The conversation's state: fed mic VAD and Moshi's audio, see conversation_state.py
+        self.machine = machine if machine is not None else ConversationMachine()
+        if on_state_change:
+            self.machine.add_listener(lambda transition: on_state_change(transition.new.value))
         # Use provided AudioIO or create new one
         self.audio_io = audio_io if audio_io is not None else AudioIO(log_callback=self.log_callback)
+        # Converts captured frames to each consumer's declared input_format
+        self.formats = FormatNegotiator(self.audio_io.sample_rate)
         self.vad = VoiceActivityDetector()
+        self.user_speaking = False  # The VAD's verdict on the latest mic frame (audio thread)
         self.tool_executor = ToolExecutor(registry)
         self.command_parser = CommandParser()
         self.running = False
         self._loop_task: Optional[asyncio.Task] = None
         self._audio_buffer = []
         self._is_listening = False
+        self.muted = False  # Mic muted (tray menu): silence is fed instead, Moshi needs a steady stream
+        # Sees Moshi's audio before it plays (speech cache recordings); returning True swallows it
+        self.output_tap: Optional[Callable[[np.ndarray], bool]] = None
+        self.quiet = False  # Moshi's text isn't shown while the speech cache records in the background
 
     def log(self, msg: str):
         logging.info(msg)
@@ -480,7 +637,7 @@ class ConversationLoop:
         try:
             # Only start input if not already started
             if not self.audio_io.input_stream or not self.audio_io.input_stream.active:
-                self.audio_io.start_input(callback=self._on_audio_frame)
+                self.audio_io.start_input(callback=self._on_audio_frame, shared_frames=True)
                 self.log("✅ Audio input started")
             else:
                 self.log("ℹ️  Audio input already started")
@@ -499,7 +656,7 @@ class ConversationLoop:
                 logging.warning("   Voice features disabled. Please grant microphone permission in System Settings.")
                 # Continue without voice - app can still function
                 self.running = False
-                raise RuntimeError(f"Microphone access denied: {error_msg}")
+                raise VoiceError(f"Microphone access denied: {error_msg}")
             else:
                 raise
         
@@ -515,13 +672,13 @@ class ConversationLoop:
             
         # Start Moshi async loops if available (MoshiClient)
         if hasattr(self.moshi, 'run_async_loops'):
-            self._loop_task = asyncio.create_task(self.moshi.run_async_loops())
+            self._loop_task = get_task_supervisor().spawn("voice audio forwarder", self.moshi.run_async_loops)
             self.log("🔄 Moshi Client Async Loops Started")
         else:
             # Fallback for local bridge (if any)
-            self._loop_task = asyncio.create_task(self._conversation_loop_legacy())
+            self._loop_task = get_task_supervisor().spawn("voice audio forwarder", self._conversation_loop_legacy)
             
-        self._set_state("listening")
+        self.machine.fire(Event.START)
 
     async def stop(self):
         self._running = False # Assuming _running is the new internal state variable
@@ -560,13 +717,18 @@ class ConversationLoop:
                 pass
         
         self.log("✅ Voice bridge stopped.")
-        self._set_state("idle")
+        self.machine.fire(Event.STOP)
 
-    def _on_audio_frame(self, audio: np.ndarray):
-        """Callback for each audio frame from microphone"""
+    def _on_audio_frame(self, frame: AudioFrame):
+        """Callback for each audio frame from microphone (shared, read-only - see audio_frame.py)"""
         # Always process audio for duplex communication
         # if not self._is_listening:
         #     return
+        audio = self.formats.for_consumer(frame, self.moshi).samples
+        if self.muted:
+            audio = np.zeros_like(audio)
+        # Read by the conversation state task on the event loop (barge-in)
+        self.user_speaking = self.vad.process_frame(audio)
         
         # Update amplitude for visualization (USER INPUT)
         if hasattr(self.moshi, 'update_mic_amplitude'):
@@ -590,9 +752,9 @@ class ConversationLoop:
             if not hasattr(self, '_logged_transcriber_inactive'):
                 logging.info(f"⚠️ user_transcriber not active: {self.user_transcriber.is_active}")
                 self._logged_transcriber_inactive = True
-        else:
-            # Feed audio
-            self.user_transcriber.process_audio(audio)
+        elif not self.muted:
+            # Feed audio at the transcriber's rate (the frame itself, so conversions are shared)
+            self.user_transcriber.process_audio(self.formats.for_consumer(frame, self.user_transcriber))
             # Log occasionally
             if not hasattr(self, '_transcriber_feed_count'):
                 self._transcriber_feed_count = 0
@@ -604,17 +766,23 @@ class ConversationLoop:
     def _on_moshi_audio(self, audio: np.ndarray):
         """Callback for audio received from Moshi"""
         # self.log(f"DEBUG: Playing audio chunk {audio.shape}")
-        # Update amplitude (MOSHI OUTPUT)
-        if hasattr(self.moshi, 'update_moshi_amplitude'):
+        if self.output_tap and self.output_tap(audio):
+            return
+        if self.machine.state == ConversationState.INTERRUPTED:
+            return  # The user talked over the reply; the rest of it is dropped
+        self.machine.reply_audio(float(np.sqrt(np.mean(np.square(audio)))) >= SILENCE_RMS if len(audio) else False)
+        # Update amplitude (MOSHI OUTPUT) - MoshiClient taps its output bus for this instead
+        if hasattr(self.moshi, 'update_moshi_amplitude') and not isinstance(self.moshi, MoshiClient):
             self.moshi.update_moshi_amplitude(audio)
             
         # Play audio
         self.audio_io.play_audio(audio)
-        self._set_state("speaking")
 
     def _on_moshi_text(self, text: str):
         """Callback for text received from Moshi"""
         # self.log(f"🤖 Moshi: {text}")
+        if self.quiet:
+            return
         if self.subconscious:
             self.subconscious.add_to_transcript(text)
             
@@ -632,10 +800,6 @@ class ConversationLoop:
     async def _capture_speech_segment(self): pass
     async def _process_turn(self, user_audio): pass
 
-    def _set_state(self, state: str):
-        if self.on_state_change:
-            self.on_state_change(state)
-
     def get_amplitudes(self) -> Dict[str, float]:
         return {"mic_amplitude": self.moshi.mic_amplitude, "moshi_amplitude": self.moshi.moshi_amplitude}
 
@@ -854,16 +1018,9 @@ class MoshiBridgeProxy:
 # VOICE BRIDGE ORCHESTRATOR
 # ==============================================================================
 
-class ConversationState(Enum):
-    IDLE = "idle"
-    LISTENING = "listening"
-    THINKING = "thinking"
-    SPEAKING = "speaking"
-    ERROR = "error"
-
 class VoiceBridgeOrchestrator:
     """Orchestrates voice conversation using MoshiBridge, PersonaManager, and MemoryManager."""
-    def __init__(self, persona_manager: PersonaManager, memory_manager: MemoryManager, config, user_id: str = "default", moshi_quality: str = "auto", voice_queues=None, log_callback: Optional[Callable[[str], None]] = None, text_callback: Optional[Callable[[str, str], None]] = None):
+    def __init__(self, persona_manager: PersonaManager, memory_manager: MemoryManager, config, user_id: str = "default", moshi_quality: str = "auto", voice_queues=None, log_callback: Optional[Callable[[str], None]] = None, text_callback: Optional[Callable[[str, str], None]] = None, caption_callback: Optional[Callable[[str, bool], None]] = None):
         self.persona_manager = persona_manager
         self.memory_manager = memory_manager
         self.config = config
@@ -872,16 +1029,38 @@ class VoiceBridgeOrchestrator:
         self.voice_queues = voice_queues
         self.log_callback = log_callback
         self.text_callback = text_callback
+        # Live captions: the user's words as they're recognized, (text, is_final) - see captions.py
+        self.caption_callback = caption_callback
         self.moshi: Optional[Any] = None # MoshiBridge or MoshiBridgeProxy
         self.current_persona: Optional[PersonaConfig] = None
-        self.ai_client: Optional[AIClient] = None
+        self.ai_client: Optional[BudgetedAI] = None
         self.subconscious: Optional[SubconsciousBridge] = None
         self.conversation_loop: Optional[ConversationLoop] = None
-        self.state = ConversationState.IDLE
+        # Idle/listening/thinking/speaking/interrupted: every change goes through here - see conversation_state.py
+        self.machine = ConversationMachine.from_config(config, on_transition=self._on_transition)
+        self._state_task: Optional[asyncio.Task] = None
         self.state_callbacks: list = []
         self._audio_buffer: list[np.ndarray] = []
         self._running = False
         self.user_transcriber: Optional[UserTranscriber] = None
+        self.earcons: Optional[EarconPlayer] = None  # Wake/done/error cues, set up with audio output
+        self.mic_muted = False
+        # (resume_at, text, priority, tags) of announcements held during a meeting, see announce_held()
+        self.held_announcements: list = []
+        # RAM/VRAM per voice model, filled in by initialize()
+        self.memory_report = MemoryReport(getattr(config, "voice_model_memory_gb", None))
+        # Recorded common utterances, played without waiting for Moshi - see speech_cache.py
+        self.speech_cache: Optional[SpeechCache] = SpeechCache.from_config(config)
+        self._capture: Optional[SpeechCapture] = None
+        # Throwaway STT/LLM/TTS passes after load and long idle periods - see warmup.py
+        self.warmup = Warmup.from_config(config)
+        self.warmup.add_step("stt", self._warm_stt)
+        self.warmup.add_step("llm", self._warm_llm)
+        self.warmup.add_step("tts", self._warm_tts)
+
+    @property
+    def state(self) -> ConversationState:
+        return self.machine.state
 
     @property
     def _current_mic_amplitude(self) -> float:
@@ -905,23 +1084,40 @@ class VoiceBridgeOrchestrator:
         if self.log_callback:
             self.log_callback(msg)
 
-    async def initialize(self):
+    async def initialize(self, on_progress: Optional[Callable[[LoadProgress], None]] = None, server_alive: Optional[Callable[[], bool]] = None):
+        """
+        Connect to the voice server and set up audio. Never blocks the event loop:
+        downloads and model loading run off-thread / in the server process, with
+        stage updates sent to on_progress.
+        """
         self.current_persona = self.persona_manager.get_current_persona()
         if not self.current_persona:
-            raise ValueError("No persona set")
+            raise ConfigError("No persona set")
             
         if self.voice_queues:
             self.log("🔌 Connecting to Voice Server Process...")
             c2s, s2c, status = self.voice_queues
-            # Use MoshiClient for full duplex streaming
-            self.moshi = MoshiClient(c2s, s2c, log_callback=self.log_callback)
-            self.moshi.wait_for_ready()
-            self.log("✅ Moshi Client created (Full Duplex)")
+            # Use MoshiClient for full duplex streaming (may download the Mimi codec on first run).
+            # RSS deltas attribute app-process memory to each model (approximate: other threads allocate too)
+            profile = get_startup_profile()
+            rss_before = process_rss()
+            with profile.stage("models"):
+                self.moshi = await asyncio.to_thread(MoshiClient, c2s, s2c, log_callback=self.log_callback)
+                self.memory_report.record("Mimi codec", process_rss() - rss_before)
+                self.log("✅ Moshi Client created (Full Duplex)")
+                self.log("⏳ Waiting for voice server to load models...")
+                if not await wait_for_server(s2c, status, on_progress=on_progress, is_alive=server_alive, log=self.log,
+                                             memory_report=self.memory_report):
+                    raise VoiceError("Voice server failed to load models")
+            self.log("✅ Voice server is ready!")
             
             # Initialize AudioIO for playback
             from .audio import AudioIO
-            self.audio_io = AudioIO(log_callback=self.log_callback)
-            self.audio_io.start_output()
+            with profile.stage("audio"):
+                self.audio_io = AudioIO(log_callback=self.log_callback,
+                                        bluetooth_fallback=bool(getattr(self.config, "bluetooth_mic_fallback", False)))
+                self.audio_io.start_output()
+            self.earcons = EarconPlayer(self.audio_io, self.config, self.current_persona)
             self.log("✅ Audio output started")
 
             # Initialize User Transcriber
@@ -932,10 +1128,16 @@ class VoiceBridgeOrchestrator:
                 if isinstance(vosk_model_path, str):
                     vosk_model_path = Path(vosk_model_path)
                     
-                self.user_transcriber = UserTranscriber(
-                    model_path=vosk_model_path,
-                    on_text=self._on_user_text
-                )
+                rss_before = process_rss()
+                with profile.stage("transcriber"):
+                    self.user_transcriber = await asyncio.to_thread(
+                        UserTranscriber,
+                        model_path=vosk_model_path,
+                        on_text=self._on_user_text
+                    )
+                self.memory_report.record("Vosk transcriber", process_rss() - rss_before)
+                get_compute_manager().register("stt", (CPU,))  # Vosk only runs on the CPU
+                self.user_transcriber.poll_interval = get_power_manager().profile.transcribe_poll
                 self.log("✅ User Transcriber initialized")
             except Exception as e:
                 self.log(f"❌ Failed to init user transcriber: {e}")
@@ -943,11 +1145,18 @@ class VoiceBridgeOrchestrator:
             # Hook up Moshi audio output to AudioIO
             self.moshi.on_output_audio = self.audio_io.play_audio
             self.log("✅ Moshi audio output connected")
+
+            self.log("📊 Voice model memory:")
+            for line in self.memory_report.lines():
+                self.log(line)
+            if self.memory_report.over_cap:
+                self.log(f"⚠️  Voice models exceed the {self.memory_report.cap_gb:g}GB cap (voice_model_memory_gb)")
             
             # Initial persona injection is handled after SubconsciousBridge start
             # to ensure the system message handler is ready.
             
-        self.ai_client = AIClient(self.config)
+        # Replies the user waits for get a spoken filler when slow; background thoughts don't
+        self.ai_client = budgeted_ai(self.config, on_filler=self.speak_text, purpose="voice")
         
         # Initialize Subconscious Bridge if we have a client and tokenizer
         if isinstance(self.moshi, MoshiClient) and hasattr(self.moshi, 'client_to_server'):
@@ -955,7 +1164,7 @@ class VoiceBridgeOrchestrator:
              # The bridge needs a text tokenizer.
              # For now, we can pass None and let the bridge rely on string injection, 
              # as the tokenization happens on the server side.
-             self.subconscious = SubconsciousBridge(self.moshi, self.ai_client, None)
+             self.subconscious = SubconsciousBridge(self.moshi, self.ai_client.without_filler(), None)
              await self.subconscious.start()
              
              # INJECT FULL PERSONA
@@ -984,17 +1193,20 @@ class VoiceBridgeOrchestrator:
             subconscious_bridge=self.subconscious,
             user_id=self.user_id,
             on_turn_complete=self._on_conversation_turn,
-            on_state_change=self._on_state_change,
             log_callback=self.log_callback,
            audio_io=self.audio_io,  # CRITICAL FIX: Share the AudioIO instance
-           on_text_output=self.text_callback
+           on_text_output=self.text_callback,
+           machine=self.machine
         )
+        self.conversation_loop.muted = self.mic_muted
+        self.conversation_loop.output_tap = self._tap_output
+        self.conversation_loop.vad.threshold = float(getattr(self.config, "barge_in_threshold", 0.02))
+        self._retain_voice()
         logging.info("✅ ConversationLoop created")
-        self._set_state(ConversationState.IDLE)
 
     async def start_conversation(self):
         if not self.conversation_loop:
-            raise RuntimeError("Not initialized")
+            raise VoiceError("Not initialized")
         logging.info("🎙️  Starting conversation loop...")
         self._running = True
         
@@ -1004,7 +1216,10 @@ class VoiceBridgeOrchestrator:
             
         try:
             await self.conversation_loop.start()
-            self._set_state(ConversationState.LISTENING)
+            self.machine.fire(Event.START)
+            loop = self.conversation_loop
+            self._state_task = get_task_supervisor().spawn(
+                "conversation state", lambda: self.machine.run(lambda: loop.user_speaking))
             
             if self.user_transcriber:
                 self.user_transcriber.start()
@@ -1017,9 +1232,19 @@ class VoiceBridgeOrchestrator:
 
     async def stop_conversation(self):
         self._running = False
+        if self._state_task:
+            self._state_task.cancel()
+            self._state_task = None
         if self.conversation_loop:
             await self.conversation_loop.stop()
-        self._set_state(ConversationState.IDLE)
+        self.machine.fire(Event.STOP)
+
+    def set_mic_muted(self, muted: bool) -> None:
+        """Mute or unmute the microphone without stopping the conversation."""
+        self.mic_muted = muted
+        if self.conversation_loop:
+            self.conversation_loop.muted = muted
+        self.log("🔇 Mic muted" if muted else "🎤 Mic unmuted")
 
     async def process_audio_input(self, audio_chunk: np.ndarray) -> Optional[Dict[str, Any]]:
         # This method seems redundant if ConversationLoop handles everything, 
@@ -1028,9 +1253,9 @@ class VoiceBridgeOrchestrator:
         # If UI calls this, it bypasses ConversationLoop's VAD loop.
         # Assuming UI uses this for manual audio feeding?
         # For now, mirroring original logic but using MoshiBridge directly.
-        if not self.moshi: raise RuntimeError("Moshi not initialized")
+        if not self.moshi: raise VoiceError("Moshi not initialized")
         self._current_mic_amplitude = self.moshi.get_amplitude(audio_chunk)
-        self._set_state(ConversationState.THINKING)
+        self.machine.fire(Event.USER_DONE)
         try:
             history = await self.memory_manager.get_conversation_history(self.user_id, limit=10)
             system_prompt = self._build_prompt_with_history(history)
@@ -1038,14 +1263,14 @@ class VoiceBridgeOrchestrator:
             self._current_moshi_amplitude = self.moshi.get_amplitude(response_audio)
             await self.memory_manager.store_message(self.user_id, "[Audio input]", "user", {"persona": self.current_persona.name})
             await self.memory_manager.store_message(self.user_id, response_text, "assistant", {"persona": self.current_persona.name})
-            self._set_state(ConversationState.SPEAKING)
+            self.machine.fire(Event.REPLY_AUDIO)
             self._audio_buffer.append(response_audio)
             return {"response_audio": response_audio, "response_text": response_text, "mic_amplitude": self._current_mic_amplitude, "moshi_amplitude": self._current_moshi_amplitude}
-        except Exception:
-            self._set_state(ConversationState.ERROR)
+        except Exception as e:
+            self.machine.fire(Event.ERROR, str(e))
             return None
         finally:
-            if self._running: self._set_state(ConversationState.LISTENING)
+            if self._running: self.machine.fire(Event.RECOVER if self.state == ConversationState.ERROR else Event.REPLY_DONE)
 
     async def generate_response(self, text: str) -> Optional[Dict[str, Any]]:
         await self.memory_manager.store_message(self.user_id, text, "user")
@@ -1055,7 +1280,8 @@ class VoiceBridgeOrchestrator:
             try:
                 response_text = await self.ai_client.chat([{"role": "system", "content": system_prompt}, {"role": "user", "content": text}], max_tokens=150)
             except Exception:
-                response_text = f"Hello! I'm {self.current_persona.name}."
+                # Every model failed: the persona's apology, not another call
+                response_text = get_phrase_bank().pick("apology") or f"Hello! I'm {self.current_persona.name}."
         else:
             response_text = f"Hello! I'm {self.current_persona.name}."
         await self.memory_manager.store_message(self.user_id, response_text, "assistant", {"persona": self.current_persona.name})
@@ -1078,12 +1304,21 @@ class VoiceBridgeOrchestrator:
             return self.conversation_loop.get_amplitudes()
         return {"mic_amplitude": self._current_mic_amplitude, "moshi_amplitude": self._current_moshi_amplitude}
 
+    def get_audio_stats(self) -> List[Dict[str, Any]]:
+        """Per-queue backpressure counters (lag, drops, downsampling) across the audio pipeline."""
+        stats = []
+        for source in (self.moshi, getattr(self, 'audio_io', None)):
+            if source is not None and hasattr(source, 'audio_stats'):
+                stats.extend(source.audio_stats())
+        return stats
+
     def on_state_change(self, callback):
         self.state_callbacks.append(callback)
 
     async def switch_persona(self, persona_name: str) -> bool:
         if self.persona_manager.set_current_persona(persona_name):
             self.current_persona = self.persona_manager.get_current_persona()
+            self._retain_voice()
             
             # Inject new persona context
             if self.moshi and self.current_persona:
@@ -1106,6 +1341,181 @@ class VoiceBridgeOrchestrator:
             return True
         return False
 
+    async def announce(self, text: str, priority: str = "normal", tags=()) -> bool:
+        """
+        Speak unprompted (alerts, reminders), subject to quiet hours, category prefs and
+        meetings. Returns True if spoken; announcements held for a meeting are spoken by
+        announce_held() once it ends.
+        """
+        from .notifications import (Notification, apply_notification_hooks, coalesce_notification,
+                                    get_notification_policy)
+        notification = apply_notification_hooks(Notification("speech", "", text, str(getattr(priority, "value", priority)),
+                                                              tuple(tags)))
+        if notification is not None:
+            notification = coalesce_notification(notification)
+        if notification is None:
+            return False
+        decision = get_notification_policy().check("speech", notification.priority, tags=tags)
+        if decision.defer and decision.resume_at:
+            logging.info(f"🔕 Announcement held ({decision.reason}): {text[:60]}")
+            # As given: the hooks see it again when it's spoken
+            self.held_announcements.append((decision.resume_at, text, priority, tuple(tags)))
+            return False
+        if not decision.allowed:
+            logging.info(f"🔕 Announcement suppressed ({decision.reason}): {text[:60]}")
+            return False
+        await self.speak_text(notification.message, whisper=decision.whisper)
+        return True
+
+    async def announce_held(self, now: Optional[datetime] = None) -> int:
+        """Speak held announcements whose meeting is over (they're held again if another has started)."""
+        now = now or datetime.now()
+        due = [held for held in self.held_announcements if held[0] <= now]
+        if not due:
+            return 0
+        self.held_announcements = [held for held in self.held_announcements if held[0] > now]
+        spoken = 0
+        for _, text, priority, tags in due:
+            spoken += await self.announce(text, priority, tags)
+        return spoken
+
+    async def speak_text(self, text: str, whisper: bool = False, filtered: bool = False):
+        """
+        Have the persona read text aloud verbatim (not stored as a user message), whispered if asked.
+        Cached recordings play straight away, even while Moshi isn't running (see speech_cache.py).
+        The speech filter runs first unless the text has been through it (filtered).
+        """
+        if not filtered:  # Swearing, personal details, "Shall I continue?" (see speech_filter.py)
+            text = get_speech_filter().apply(text, self.current_persona, "speech")
+        text = render(text, getattr(self.moshi, "markup", PLAIN))  # Speech hints, as the engine takes them
+        text = speakable(text)  # "2026-10-17 at 14:30" -> "tomorrow at half past two"
+        text = get_lexicon().apply(text, getattr(self.current_persona, "name", None))  # "Siobhan" -> "shiv-AWN"
+        cached = self._cached_speech(text)
+        moshi_ready = bool(self.moshi and hasattr(self.moshi, 'client_to_server'))
+        if cached is None and moshi_ready:
+            try:
+                await get_chaos().disrupt("tts", "moshi")
+            except ChaosError as e:
+                logging.warning(f"⚠️ Cannot speak text - {e}")
+                return
+        if cached is None and not moshi_ready:
+            logging.warning("⚠️ Cannot speak text - Moshi not initialized")
+            return
+        if whisper:
+            from .volume import get_volume_settings
+            get_volume_settings().whisper(text)
+        if cached is not None:
+            self.audio_io.play_audio(cached[0], sample_rate=cached[1])
+        else:
+            how = "in a soft whisper" if whisper else "aloud"
+            self.moshi.client_to_server.put(("user_text", f"Read the following {how} exactly as written:\n{text}"))
+            if not whisper:  # A whispered reading isn't how the text normally sounds
+                self._start_capture(text)
+
+    # --------------------------------------------------------------------------
+    # Speech cache
+    # --------------------------------------------------------------------------
+
+    def _cache_voice(self) -> Optional[tuple]:
+        """(persona name, voice fingerprint) for speech cache entries, or None when caching is off."""
+        if self.speech_cache is None or self.current_persona is None:
+            return None
+        return self.current_persona.name, voice_fingerprint(self.current_persona, self.config)
+
+    def _cached_speech(self, text: str) -> Optional[tuple]:
+        voice = self._cache_voice()
+        if voice is None or getattr(self, 'audio_io', None) is None:
+            return None
+        return self.speech_cache.get(*voice, text)
+
+    def _start_capture(self, text: str, silent: bool = False, store: bool = True) -> bool:
+        """Record Moshi's reading of text if it's worth caching, or throw it away (one recording at a time)."""
+        voice = self._cache_voice()
+        if store and voice is None:
+            return False
+        if self.conversation_loop is None or (self._capture and not self._capture.done):
+            return False
+        if store and not silent and not self.speech_cache.wants(*voice, text):
+            return False
+        voice = voice or ("", "")
+        rate = getattr(self.moshi, 'sample_rate', 24000)
+        self._capture = SpeechCapture(voice[0], voice[1], text, sample_rate=rate, silent=silent, store=store)
+        return True
+
+    def _tap_output(self, audio: np.ndarray) -> bool:
+        """ConversationLoop.output_tap: feed the recording in progress; True while it's silent."""
+        capture = self._capture
+        if capture is None or capture.done:
+            return False
+        capture.feed(audio)
+        if capture.done and not capture.failed and capture.store:
+            self.speech_cache.put(capture.persona, capture.fingerprint, capture.text, capture.audio(),
+                                  capture.sample_rate)
+        return capture.silent
+
+    def _retain_voice(self) -> None:
+        """Drop the current persona's recordings from before a voice change."""
+        voice = self._cache_voice()
+        if voice is not None:
+            removed = self.speech_cache.retain_voice(*voice)
+            if removed:
+                logging.info(f"🗑️ Voice settings changed - dropped {removed} cached utterance(s)")
+
+    async def warm_speech_cache(self, limit: int = 5, timeout: float = 20.0) -> int:
+        """
+        Record up to `limit` missing common phrases without playing them or
+        showing their text. Returns how many were cached.
+        """
+        voice = self._cache_voice()
+        if voice is None or self.conversation_loop is None or not hasattr(self.moshi, 'client_to_server'):
+            return 0
+        cached = 0
+        for phrase in self.speech_cache.missing(*voice)[:limit]:
+            recorded = await self._read_silently(phrase, timeout)
+            if recorded is None:
+                break
+            cached += recorded
+        return cached
+
+    async def _read_silently(self, text: str, timeout: float, store: bool = True) -> Optional[bool]:
+        """Have Moshi read text without playing it; whether it was recorded (None: couldn't start)."""
+        if not self._start_capture(text, silent=True, store=store):
+            return None
+        capture = self._capture
+        self.conversation_loop.quiet = True
+        try:
+            self.moshi.client_to_server.put(("user_text", f"Read the following aloud exactly as written:\n{text}"))
+            waited = 0.0
+            while not capture.done and waited < timeout:
+                await asyncio.sleep(0.1)
+                waited += 0.1
+        finally:
+            self.conversation_loop.quiet = False
+            if not capture.done:
+                capture.done = capture.failed = True
+        return not capture.failed
+
+    async def _warm_stt(self) -> bool:
+        if self.user_transcriber is None:
+            return False
+        await asyncio.to_thread(self.user_transcriber.warm)
+        return True
+
+    async def _warm_llm(self) -> bool:
+        local = LocalAIClient(self.config)
+        if not local.is_available():
+            return False  # Cloud models stay warm on the provider's side
+        await local.warm()
+        return True
+
+    async def _warm_tts(self) -> bool:
+        if self.conversation_loop is None or not hasattr(self.moshi, 'client_to_server'):
+            return False
+        recorded = await self._read_silently("Ready.", timeout=self.warmup.step_timeout - 1, store=False)
+        if not recorded:
+            raise VoiceError("no speech" if recorded is False else "voice busy")
+        return True
+
     async def send_text(self, text: str):
         """Send text input to the model (as if spoken by user)."""
         logging.info(f"📝 send_text called with: '{text}'")
@@ -1144,6 +1554,7 @@ class VoiceBridgeOrchestrator:
     async def reload_persona(self) -> bool:
         if self.current_persona and self.persona_manager.reload_persona(self.current_persona.name):
             self.current_persona = self.persona_manager.get_current_persona()
+            self._retain_voice()
             return True
         return False
 
@@ -1151,19 +1562,28 @@ class VoiceBridgeOrchestrator:
         await self.memory_manager.clear_history(self.user_id)
 
     def _build_prompt_with_history(self, history: str) -> str:
-        persona_prompt = self.current_persona.build_system_prompt(include_personality=True)
+        persona_prompt = self.current_persona.build_system_prompt(include_personality=True, spoken=True)
         tool_prompt = registry.get_tool_prompt()
         full_prompt = f"{persona_prompt}\n\n{tool_prompt}"
         if history:
             return f"{full_prompt}\n\n## Recent Conversation\n{history}"
         return full_prompt
 
-    def _set_state(self, new_state: ConversationState):
-        if self.state != new_state:
-            self.state = new_state
+    def _on_transition(self, transition: Transition):
+        self.warmup.touch()
+        if transition.new == ConversationState.INTERRUPTED:
+            self.log("✋ Interrupted - stopping the reply")
+            if getattr(self, 'audio_io', None) is not None:
+                self.audio_io.stop_speech()
+            if self._capture and not self._capture.done:
+                self._capture.done = self._capture.failed = True  # Only a whole reading is cached
+        if transition.old != transition.new:
+            if self.earcons:
+                self.earcons.persona = self.current_persona  # Follows persona switches
+                self.earcons.on_state_change(transition.old.value, transition.new.value)
             for callback in self.state_callbacks:
                 try:
-                    callback(new_state)
+                    callback(transition.new)
                 except Exception:
                     pass
 
@@ -1217,9 +1637,13 @@ class VoiceBridgeOrchestrator:
                 pass # self.log(f"  Latency: {turn.metadata['latency_ms']}ms")
     
     def _on_user_text(self, text: str, is_final: bool):
-        """Callback for text recognized from user voice"""
+        """Callback for text recognized from user voice (partial hypotheses only go to the captions)"""
+        if self.caption_callback:
+            self.caption_callback(text, is_final)
+        if not is_final:
+            return
         logging.info(f"🎤 User voice recognized: '{text}' (final={is_final})")
-        
+
         if self.text_callback:
             self.text_callback("User", text)
         else:
@@ -1229,6 +1653,3 @@ class VoiceBridgeOrchestrator:
         if self.subconscious:
             self.subconscious.add_to_transcript(f"User: {text}")
 
-    def _on_state_change(self, state: str):
-        state_map = {"idle": ConversationState.IDLE, "listening": ConversationState.LISTENING, "thinking": ConversationState.THINKING, "speaking": ConversationState.SPEAKING, "error": ConversationState.ERROR}
-        self._set_state(state_map.get(state, ConversationState.IDLE))
@@ -0,0 +1,274 @@
+"""
+Voice Config - Change a few settings by asking, checked before they're saved.
+
+"Switch your voice to Glados" or "set quiet hours from 10pm to 7am" reach
+the model, which calls the change_setting tool with one of SETTINGS and
+the value as said. Only these settings can be changed this way, and each
+value is checked against its kind before anything is written:
+
+    voice             a persona ("the British one" is matched by the model against their descriptions)
+    quiet_hours       "10pm to 7am", "off" or "on"
+    wake_word         whether speech needs the wake word ("on"/"off")
+    follow_up_window  seconds after an answer a reply needs no wake word (0-30)
+    ...and a few more: live captions, barge-in, quiet in meetings, how much is
+    read out, swearing, meeting brief timing, the user's name and how many
+    turns of a conversation the AI sees (working_memory.py)
+
+A change is set on the shared Config, so every module sees it at once,
+saved to the config file, and passed to any hook registered for the
+setting (the dashboard switches persona for "voice"). The tool's result
+says the new value the way it should be read back ("Quiet hours are now
+10 PM to 7 AM"), and the old values go to the undo log, so "undo that"
+puts them back. list_settings says what can be changed and the current
+values.
+"""
+
+import logging
+import re
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+from .capabilities import register_capability
+from .dates import parse_time_expression
+from .errors import ConfigError
+
+logger = logging.getLogger(__name__)
+
+_TRUE = {"on", "yes", "true", "enable", "enabled", "1"}
+_FALSE = {"off", "no", "false", "disable", "disabled", "0", "none"}
+_RANGE_SPLIT = re.compile(r"\s*(?:-|–|\bto\b|\buntil\b|\btill\b|\bthrough\b)\s*", re.IGNORECASE)
+_NUMBER = re.compile(r"-?\d+(?:\.\d+)?")
+
+
+class SettingError(ConfigError):
+    """A value a setting can't take; the message says what it can."""
+
+
+@dataclass
+class Setting:
+    """One setting that can be changed by asking, and the config fields it writes."""
+    name: str
+    kind: str  # bool, number, choice, text, time_range (quiet hours) or persona
+    fields: Tuple[str, ...]
+    description: str
+    choices: Tuple[str, ...] = ()
+    minimum: float = 0
+    maximum: float = 0
+    unit: str = ""
+    integer: bool = False
+
+
+SETTINGS: Dict[str, Setting] = {s.name: s for s in (
+    Setting("voice", "persona", ("default_persona",), "The assistant's voice and personality"),
+    Setting("quiet_hours", "time_range", ("quiet_hours_enabled", "quiet_hours_start", "quiet_hours_end"),
+            "When only urgent things are announced"),
+    Setting("wake_word", "bool", ("require_wake_word",), "Whether speech needs the wake word"),
+    Setting("follow_up_window", "number", ("follow_up_window",),
+            "Seconds after an answer a reply needs no wake word", minimum=0, maximum=30, unit="seconds"),
+    Setting("live_captions", "bool", ("live_captions",), "Show the user's words while they speak"),
+    Setting("barge_in", "bool", ("barge_in",), "Talking over the assistant stops its reply"),
+    Setting("meeting_quiet", "bool", ("meeting_quiet",), "Hold announcements during meetings"),
+    Setting("max_spoken_words", "number", ("speech_max_words",),
+            "Longest reply read out before asking to continue (0 = no limit)", maximum=1000, unit="words",
+            integer=True),
+    Setting("profanity", "choice", ("speech_profanity",), "How swearing is read out",
+            choices=("mask", "remove", "off")),
+    Setting("meeting_prep_minutes", "number", ("meeting_prep_minutes",),
+            "How long before a meeting its brief comes (0 = off)", maximum=120, unit="minutes", integer=True),
+    Setting("user_name", "text", ("user_name",), "What the assistant calls the user"),
+    Setting("working_memory", "number", ("working_memory_turns",),
+            "Turns of a conversation the AI sees; older ones are summarized (0 = all)", maximum=200, unit="turns",
+            integer=True),
+)}
+
+_ALIASES = {"persona": "voice", "personality": "voice", "quiet_time": "quiet_hours", "do_not_disturb_hours":
+            "quiet_hours", "require_wake_word": "wake_word", "captions": "live_captions",
+            "interruptions": "barge_in", "name": "user_name", "my_name": "user_name", "swearing": "profanity",
+            "context": "working_memory", "memory_window": "working_memory"}
+
+
+def find_setting(name: str) -> Optional[Setting]:
+    key = re.sub(r"[\s-]+", "_", str(name).strip().lower())
+    return SETTINGS.get(_ALIASES.get(key, key))
+
+
+def _spoken_time(hhmm: str) -> str:
+    hour, minute = (int(part) for part in hhmm.split(":"))
+    if (hour, minute) == (0, 0):
+        return "midnight"
+    if (hour, minute) == (12, 0):
+        return "noon"
+    clock = f"{hour % 12 or 12}" + (f":{minute:02d}" if minute else "")
+    return f"{clock} {'AM' if hour < 12 else 'PM'}"
+
+
+def parse_bool(value: Any) -> bool:
+    text = str(value).strip().lower()
+    if text in _TRUE:
+        return True
+    if text in _FALSE:
+        return False
+    raise SettingError(f"Say on or off, not '{value}'")
+
+
+def parse_value(setting: Setting, value: Any, personas: Optional[Dict[str, str]] = None) -> Dict[str, Any]:
+    """The config fields `value` sets for `setting`; SettingError when it isn't a value the setting takes."""
+    text = str(value).strip()
+    if setting.kind == "bool":
+        return {setting.fields[0]: parse_bool(text)}
+    if setting.kind == "number":
+        match = _NUMBER.search(text)
+        if not match:
+            raise SettingError(f"{setting.name} needs a number of {setting.unit}")
+        number = float(match.group())
+        if not setting.minimum <= number <= setting.maximum:
+            raise SettingError(f"{setting.name} goes from {setting.minimum:g} to {setting.maximum:g} {setting.unit}")
+        return {setting.fields[0]: int(number) if setting.integer else number}
+    if setting.kind == "choice":
+        if text.lower() not in setting.choices:
+            raise SettingError(f"{setting.name} can be {', '.join(setting.choices)}")
+        return {setting.fields[0]: text.lower()}
+    if setting.kind == "text":
+        if not text or len(text) > 40:
+            raise SettingError(f"{setting.name} must be 1 to 40 characters")
+        return {setting.fields[0]: text}
+    if setting.kind == "persona":
+        return {setting.fields[0]: match_persona(text, personas or {})}
+    # time_range: "10pm to 7am", or just on/off
+    enabled, start, end = setting.fields
+    if text.lower() in _TRUE | _FALSE:
+        return {enabled: parse_bool(text)}
+    parts = _RANGE_SPLIT.split(re.sub(r"^(?:from|between)\s+", "", text, flags=re.IGNORECASE), maxsplit=1)
+    times = [parse_time_expression(part) for part in parts]
+    if len(times) != 2 or None in times or times[0] == times[1]:
+        raise SettingError("Quiet hours need a start and an end, like '10pm to 7am', or on/off")
+    return {enabled: True, start: times[0], end: times[1]}
+
+
+def match_persona(text: str, personas: Dict[str, str]) -> str:
+    """The persona `text` names, or the one whose description it uniquely fits ("the Iron Man one")."""
+    wanted = re.sub(r"^(?:the\s+)?(.*?)(?:\s+(?:one|voice|persona))?$", r"\1", text.strip(), flags=re.IGNORECASE)
+    wanted = wanted.lower()
+    for name in personas:
+        if name.lower() == wanted:
+            return name
+    fits = [name for name, description in personas.items() if wanted and wanted in f"{name} {description}".lower()]
+    if len(fits) == 1:
+        return fits[0]
+    raise SettingError(f"No single voice matches '{text}'. The voices are: "
+                       + "; ".join(f"{name} ({description})" if description else name
+                                   for name, description in personas.items()))
+
+
+def describe(setting: Setting, config, changed: bool = False) -> str:
+    """The setting's current value, worded to be read out ("... is now ..." right after a change)."""
+    values = [getattr(config, field, None) for field in setting.fields]
+    label = setting.name.replace("_", " ").capitalize()
+    now = " now" if changed else ""
+    if setting.kind == "time_range":
+        enabled, start, end = values
+        if not enabled:
+            return f"Quiet hours are{now} off (they'd be {_spoken_time(start)} to {_spoken_time(end)})"
+        return f"Quiet hours are{now} {_spoken_time(start)} to {_spoken_time(end)}"
+    value = values[0]
+    if setting.kind == "bool":
+        return f"{label} is{now} {'on' if value else 'off'}"
+    if setting.kind == "number":
+        return f"{label} is{now} {value:g} {setting.unit}"
+    if setting.kind == "persona":
+        return f"The voice is{now} {value}"
+    return f"{label} is{now} {value or 'not set'}"
+
+
+class ConfigChanger:
+    """Applies change_setting to the shared Config: checked, saved, hooks told, undo recorded."""
+
+    def __init__(self, config, personas: Optional[Callable[[], Dict[str, str]]] = None, save: bool = True,
+                 undo_log=None):
+        self.config = config
+        self.personas = personas or persona_choices
+        self.save = save
+        self.undo_log = undo_log
+        self.hooks: Dict[str, List[Callable[[Dict[str, Any]], Any]]] = {}
+
+    def on_change(self, name: str, hook: Callable[[Dict[str, Any]], Any]) -> None:
+        """Call `hook` with the new field values whenever `name` changes (e.g. switch persona for "voice")."""
+        self.hooks.setdefault(name, []).append(hook)
+
+    def _write(self, setting: Setting, values: Dict[str, Any]) -> None:
+        for field, value in values.items():
+            setattr(self.config, field, value)
+        if self.save:
+            try:
+                self.config.save_to_file()
+            except Exception as e:
+                logger.warning(f"Could not save the {setting.name} setting: {e}")
+        for hook in self.hooks.get(setting.name, []):
+            try:
+                hook(values)
+            except Exception as e:
+                logger.warning(f"Applying the {setting.name} setting failed: {e}")
+
+    def change(self, name: str, value: Any) -> str:
+        """Change a setting; a ✓ line with the new value to read back, or ✗ with what it can be."""
+        setting = find_setting(name)
+        if setting is None:
+            return f"✗ '{name}' can't be changed by asking. These can: {', '.join(SETTINGS)}"
+        try:
+            values = parse_value(setting, value, self.personas() if setting.kind == "persona" else None)
+        except SettingError as e:
+            return f"✗ {e}"
+        old = {field: getattr(self.config, field, None) for field in values}
+        if old == values:
+            return f"✓ Already so: {describe(setting, self.config)}"
+        self._write(setting, values)
+        if self.undo_log is not None:
+            self.undo_log.record("config_fields", f"changed {setting.name.replace('_', ' ')}",
+                                 {"setting": setting.name, "fields": old})
+        logger.info(f"Setting {setting.name} changed by request: {values}")
+        return f"✓ {describe(setting, self.config, changed=True)}"
+
+    def restore(self, name: str, fields: Dict[str, Any]) -> bool:
+        """Put back the values a change replaced (undo)."""
+        setting = SETTINGS.get(name)
+        if setting is None or not set(fields) <= set(setting.fields):
+            return False
+        self._write(setting, fields)
+        return True
+
+    def summary(self) -> str:
+        """Every setting that can be changed by asking, with its value now."""
+        lines = [f"- {s.name}: {describe(s, self.config)} ({s.description})" for s in SETTINGS.values()]
+        voices = ", ".join(self.personas())
+        return "\n".join(lines + [f"Voices: {voices}" if voices else ""]).strip()
+
+
+def persona_choices(manager=None) -> Dict[str, str]:
+    """Installed persona names and descriptions, for matching "the British one"."""
+    if manager is None:
+        from .tools import get_persona_manager
+        manager = get_persona_manager()
+    return {name: getattr(manager.get_persona(name), "description", "") or "" for name in manager.list_personas()}
+
+
+_changer: Optional[ConfigChanger] = None
+
+
+def get_config_changer() -> ConfigChanger:
+    """Get the global changer (on the saved config until the dashboard sets its own)."""
+    global _changer
+    if _changer is None:
+        from .config import Config
+        from .tools import get_undo_log
+        _changer = ConfigChanger(Config.load_from_file(), undo_log=get_undo_log())
+    return _changer
+
+
+def set_config_changer(changer: Optional[ConfigChanger]) -> None:
+    global _changer
+    _changer = changer
+
+
+register_capability("Change settings", "Change the voice, quiet hours, the wake word and more by asking",
+                    ["set quiet hours from 10pm to 7am", "switch your voice to Glados", "what can I change?"],
+                    category="Settings", keywords=("configure", "preferences", "quiet hours", "persona"))
@@ -0,0 +1,349 @@
+"""
+Voice Self-Test - An automated quality regression suite for the spoken voice.
+
+`xswarm dev voice-selftest` starts the voice server on the models already in
+the cache (nothing is downloaded) and, for each prompt in a corpus, has
+Moshi read it aloud, records the reading and transcribes it back with
+Whisper:
+
+- WER: word error rate of the transcript against the prompt, after both
+  are lower-cased and stripped of punctuation
+- latency: seconds from sending the prompt to the first sound
+
+A prompt passes when both are under its thresholds. The report lists every
+prompt, the mean WER and median latency; the command exits 1 if any prompt
+failed (or the voice server didn't start), so it can gate CI on a machine
+with the models cached. `--json` prints the report as JSON instead.
+
+Whisper runs locally with faster-whisper when it's installed (its model
+cached too), otherwise through OpenAI's transcription API with
+config.openai_api_key.
+
+The corpus is DEFAULT_CORPUS or a YAML file (`--corpus`):
+
+    max_wer: 0.25          # Defaults for every prompt
+    max_latency: 3.0
+    prompts:
+      - Good morning, you have two meetings today.
+      - text: Your flight to Lisbon leaves at seven in the evening.
+        max_wer: 0.35      # Place names are harder
+"""
+
+import asyncio
+import io
+import os
+import re
+import statistics
+import time
+import wave
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Any, Awaitable, Callable, Dict, List, Optional
+
+import yaml
+
+MAX_WER = 0.25
+MAX_LATENCY = 3.0  # Seconds to the first sound
+WHISPER_RATE = 16000
+WHISPER_MODEL = "small.en"  # faster-whisper model
+READ_PROMPT = "Read the following aloud exactly as written:\n{text}"
+
+DEFAULT_CORPUS = [
+    "Good morning, you have two meetings today.",
+    "Your next appointment is with the dentist this afternoon.",
+    "I added milk and eggs to the shopping list.",
+    "The build finished and all the tests passed.",
+    "Do you want me to read the message from Sarah?",
+    "Reminder: call the bank before it closes.",
+    "It looks like rain later, so take an umbrella.",
+    "Okay, I will stay quiet until the meeting ends.",
+]
+
+
+class SelftestError(RuntimeError):
+    """The self-test couldn't run: bad corpus, no cached models or no Whisper."""
+
+
+@dataclass
+class Prompt:
+    text: str
+    max_wer: float = MAX_WER
+    max_latency: float = MAX_LATENCY
+
+
+@dataclass
+class Sample:
+    """One reading: the audio, its rate and the seconds to its first sound (None: silence)."""
+    audio: Any
+    sample_rate: int
+    latency: Optional[float]
+
+
+def load_corpus(path: Optional[Path] = None, max_wer: Optional[float] = None,
+                max_latency: Optional[float] = None) -> List[Prompt]:
+    """The prompts from a corpus file (or DEFAULT_CORPUS); max_wer / max_latency override the file's defaults."""
+    data: Dict[str, Any] = {"prompts": DEFAULT_CORPUS}
+    if path is not None:
+        try:
+            data = yaml.safe_load(Path(path).read_text()) or {}
+        except (OSError, yaml.YAMLError) as e:
+            raise SelftestError(f"Can't read corpus {path}: {e}")
+        if isinstance(data, list):
+            data = {"prompts": data}
+    wer = max_wer if max_wer is not None else float(data.get("max_wer", MAX_WER))
+    latency = max_latency if max_latency is not None else float(data.get("max_latency", MAX_LATENCY))
+    prompts = []
+    for entry in data.get("prompts") or []:
+        if isinstance(entry, str):
+            entry = {"text": entry}
+        if not isinstance(entry, dict) or not str(entry.get("text", "")).strip():
+            raise SelftestError(f"Corpus prompt {len(prompts) + 1} has no text")
+        prompts.append(Prompt(str(entry["text"]).strip(), float(entry.get("max_wer", wer)),
+                              float(entry.get("max_latency", latency))))
+    if not prompts:
+        raise SelftestError("The corpus has no prompts")
+    return prompts
+
+
+def normalize(text: str) -> List[str]:
+    """Words to compare: lower case, hyphens split, other punctuation dropped ("Don't-stop!" -> don't stop)."""
+    text = re.sub(r"[-‐–—/]", " ", text.lower().replace("’", "'"))
+    return [word.strip("'") for word in re.sub(r"[^\w\s']", "", text).split() if word.strip("'")]
+
+
+def word_error_rate(reference: str, hypothesis: str) -> float:
+    """(substitutions + deletions + insertions) / reference words; 1.0 for anything against an empty reference."""
+    ref, hyp = normalize(reference), normalize(hypothesis)
+    if not ref:
+        return 0.0 if not hyp else 1.0
+    previous = list(range(len(hyp) + 1))
+    for i, word in enumerate(ref, 1):
+        current = [i]
+        for j, heard in enumerate(hyp, 1):
+            current.append(min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + (word != heard)))
+        previous = current
+    return previous[-1] / len(ref)
+
+
+# ==============================================================================
+# REPORT
+# ==============================================================================
+
+@dataclass
+class PromptResult:
+    text: str
+    transcript: str = ""
+    wer: Optional[float] = None
+    latency: Optional[float] = None
+    max_wer: float = MAX_WER
+    max_latency: float = MAX_LATENCY
+    error: Optional[str] = None
+
+    @property
+    def passed(self) -> bool:
+        return (self.error is None and self.wer is not None and self.wer <= self.max_wer
+                and self.latency is not None and self.latency <= self.max_latency)
+
+    def line(self) -> str:
+        mark = "✓" if self.passed else "✗"
+        if self.error:
+            return f"{mark} \"{self.text}\": {self.error}"
+        wer = f"WER {self.wer:.2f}" + (f" > {self.max_wer:.2f}" if self.wer > self.max_wer else "")
+        if self.latency is None:
+            latency = "no speech"
+        else:
+            latency = f"latency {self.latency:.2f}s" + (f" > {self.max_latency:.1f}s"
+                                                         if self.latency > self.max_latency else "")
+        heard = "" if self.passed else f"\n    heard: \"{self.transcript}\""
+        return f"{mark} \"{self.text}\": {wer}, {latency}{heard}"
+
+
+@dataclass
+class SelftestReport:
+    results: List[PromptResult] = field(default_factory=list)
+
+    @property
+    def passed(self) -> bool:
+        return bool(self.results) and all(result.passed for result in self.results)
+
+    @property
+    def mean_wer(self) -> Optional[float]:
+        scored = [result.wer for result in self.results if result.wer is not None]
+        return statistics.mean(scored) if scored else None
+
+    @property
+    def median_latency(self) -> Optional[float]:
+        timed = [result.latency for result in self.results if result.latency is not None]
+        return statistics.median(timed) if timed else None
+
+    def lines(self) -> List[str]:
+        lines = [result.line() for result in self.results]
+        passed = sum(result.passed for result in self.results)
+        summary = f"{passed}/{len(self.results)} prompts passed"
+        if self.mean_wer is not None:
+            summary += f" (mean WER {self.mean_wer:.2f}"
+            summary += f", median latency {self.median_latency:.2f}s)" if self.median_latency is not None else ")"
+        return lines + ["", summary]
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"passed": self.passed, "mean_wer": self.mean_wer, "median_latency": self.median_latency,
+                "results": [{**asdict(result), "passed": result.passed} for result in self.results]}
+
+
+class VoiceSelftest:
+    """Reads each prompt with `synthesize`, transcribes it with `transcribe` and scores it."""
+
+    def __init__(self, prompts: List[Prompt], synthesize: Callable[[str], Awaitable[Sample]],
+                 transcribe: Callable[[Any, int], str], on_result: Optional[Callable[[PromptResult], None]] = None):
+        self.prompts = prompts
+        self.synthesize = synthesize
+        self.transcribe = transcribe
+        self.on_result = on_result
+
+    async def run(self) -> SelftestReport:
+        report = SelftestReport()
+        for prompt in self.prompts:
+            result = PromptResult(prompt.text, max_wer=prompt.max_wer, max_latency=prompt.max_latency)
+            try:
+                sample = await self.synthesize(prompt.text)
+                result.latency = sample.latency
+                if sample.latency is None:
+                    result.wer = 1.0
+                else:
+                    result.transcript = (await asyncio.to_thread(self.transcribe, sample.audio,
+                                                                 sample.sample_rate)).strip()
+                    result.wer = word_error_rate(prompt.text, result.transcript)
+            except SelftestError:
+                raise
+            except Exception as e:
+                result.error = str(e) or type(e).__name__
+            report.results.append(result)
+            if self.on_result:
+                self.on_result(result)
+        return report
+
+
+# ==============================================================================
+# WHISPER AND THE VOICE MODEL
+# ==============================================================================
+
+def _wav_bytes(audio, sample_rate: int) -> bytes:
+    import numpy as np
+    pcm = (np.clip(np.asarray(audio, dtype=np.float32), -1.0, 1.0) * 32767).astype("<i2")
+    buffer = io.BytesIO()
+    with wave.open(buffer, "wb") as wav:
+        wav.setnchannels(1)
+        wav.setsampwidth(2)
+        wav.setframerate(sample_rate)
+        wav.writeframes(pcm.tobytes())
+    return buffer.getvalue()
+
+
+class WhisperTranscriber:
+    """Whisper speech-to-text: local faster-whisper if installed, else OpenAI's API ("auto")."""
+
+    def __init__(self, backend: str = "auto", api_key: Optional[str] = None, model: str = WHISPER_MODEL):
+        self.api_key = api_key or os.getenv("OPENAI_API_KEY")
+        self._local = None
+        if backend in ("auto", "local"):
+            try:
+                from faster_whisper import WhisperModel
+                self._local = WhisperModel(model, device="cpu", compute_type="int8")
+            except ImportError:
+                if backend == "local":
+                    raise SelftestError("faster-whisper isn't installed (pip install faster-whisper)")
+            except Exception as e:
+                raise SelftestError(f"Can't load Whisper model {model}: {e}")
+        if self._local is None and not self.api_key:
+            raise SelftestError("No Whisper: install faster-whisper or set openai_api_key / OPENAI_API_KEY")
+        self.backend = "local" if self._local is not None else "api"
+
+    def __call__(self, audio, sample_rate: int) -> str:
+        if self._local is not None:
+            import numpy as np
+            from .resample import StreamResampler
+            audio = np.asarray(audio, dtype=np.float32)
+            if sample_rate != WHISPER_RATE:
+                audio = StreamResampler(sample_rate, WHISPER_RATE).process(audio)
+            segments, _ = self._local.transcribe(audio, language="en", beam_size=1)
+            return " ".join(segment.text.strip() for segment in segments)
+        import httpx
+        response = httpx.post("https://api.openai.com/v1/audio/transcriptions",
+                              headers={"Authorization": f"Bearer {self.api_key}"},
+                              data={"model": "whisper-1", "language": "en"},
+                              files={"file": ("reading.wav", _wav_bytes(audio, sample_rate), "audio/wav")},
+                              timeout=60.0)
+        response.raise_for_status()
+        return response.json().get("text", "")
+
+
+class MoshiSynthesizer:
+    """Has the local voice model read prompts aloud, recording each reading (cached models only)."""
+
+    def __init__(self, quality: str = "q4", device: str = "gpu", mmap_weights: bool = True,
+                 log: Optional[Callable[[str], None]] = None):
+        self.quality = quality
+        self.device = device
+        self.mmap_weights = mmap_weights
+        self.log = log or (lambda msg: None)
+        self.client = None
+        self._process = None
+        self._loops: Optional[asyncio.Task] = None
+        self._capture = None
+        self._first_sound: Optional[float] = None
+
+    async def start(self) -> None:
+        from .model_loading import wait_for_server
+        from .voice import MoshiClient
+        from .voice_server import start_server_process
+
+        os.environ["HF_HUB_OFFLINE"] = "1"  # Only models already downloaded
+        self._process, c2s, s2c, status = start_server_process(quality=self.quality, mmap_weights=self.mmap_weights,
+                                                               device=self.device)
+        try:
+            self.client = await asyncio.to_thread(MoshiClient, c2s, s2c)
+        except Exception as e:
+            raise SelftestError(f"Can't load the Mimi codec (download the models first): {e}")
+        if not await wait_for_server(s2c, status, is_alive=self._process.is_alive, log=self.log):
+            raise SelftestError(f"The {self.quality} voice model didn't load (download the models first)")
+        self.client.on_output_audio = self._on_audio
+        self._loops = asyncio.ensure_future(self.client.run_async_loops())
+
+    def _on_audio(self, audio) -> None:
+        capture = self._capture
+        if capture is None or capture.done:
+            return
+        capture.feed(audio)
+        if capture.chunks and self._first_sound is None:
+            self._first_sound = time.monotonic()
+
+    async def __call__(self, text: str) -> Sample:
+        from .speech_cache import END_SILENCE, MAX_SECONDS, START_TIMEOUT, SpeechCapture
+
+        rate = getattr(self.client, "sample_rate", 24000)
+        capture = SpeechCapture("selftest", "", text, sample_rate=rate, silent=True, store=False)
+        self._first_sound = None
+        self._capture = capture
+        sent = time.monotonic()
+        self.client.client_to_server.put(("user_text", READ_PROMPT.format(text=text)))
+        deadline = sent + START_TIMEOUT + MAX_SECONDS + END_SILENCE
+        while not capture.done and time.monotonic() < deadline:
+            await asyncio.sleep(0.05)
+        capture.done = True
+        if not capture.chunks:
+            return Sample(capture.audio(), rate, None)
+        return Sample(capture.audio(), rate, self._first_sound - sent)
+
+    async def stop(self) -> None:
+        if self.client is not None:
+            self.client.stop()
+            try:
+                self.client.client_to_server.put("stop")
+            except Exception:
+                pass
+        if self._loops is not None:
+            self._loops.cancel()
+        if self._process is not None:
+            await asyncio.to_thread(self._process.join, 5)
+            if self._process.is_alive():
+                self._process.terminate()
@@ -32,6 +32,45 @@ def hf_hub_download(repo, path: str) -> str:
     return huggingface_hub.hf_hub_download(repo, path)
 
 
+BF16_REPO = "kyutai/moshiko-mlx-bf16"
+QUANTIZED_FILES = {8: "model.q8.safetensors", 4: "model.q4.safetensors", None: "model.safetensors"}
+
+
+def _cached_file(repo: str, filename: str):
+    """Local path if the file is already in the HuggingFace cache, else None (never downloads)."""
+    try:
+        path = huggingface_hub.try_to_load_from_cache(repo, filename)
+    except Exception:
+        return None
+    return path if isinstance(path, str) else None
+
+
+def resolve_model_file(hf_repo: str, quantized):
+    """
+    Pick where the LM weights come from: (repo, filename, quantize_on_load).
+
+    Prefers the pre-quantized checkpoint. If only the bf16 checkpoint is
+    cached, quantizes it on the fly instead of downloading another variant.
+    """
+    if quantized not in QUANTIZED_FILES:
+        raise ValueError(f"Invalid quantized value: {quantized}")
+    filename = QUANTIZED_FILES[quantized]
+    if quantized is not None and not _cached_file(hf_repo, filename) and _cached_file(BF16_REPO, QUANTIZED_FILES[None]):
+        return BF16_REPO, QUANTIZED_FILES[None], True
+    return hf_repo, filename, False
+
+
+def gpu_active_memory() -> int:
+    """Bytes MLX currently has allocated on the GPU (0 if unavailable)."""
+    for getter in (getattr(mx, "get_active_memory", None), getattr(getattr(mx, "metal", None), "get_active_memory", None)):
+        if getter:
+            try:
+                return int(getter())
+            except Exception:
+                pass
+    return 0
+
+
 def server_process(
     client_to_server: multiprocessing.Queue,
     server_to_client: multiprocessing.Queue,
@@ -39,7 +78,9 @@ def server_process(
     hf_repo: str,
     quantized: int,
     log_file: str = "/tmp/xswarm_voice_server.log",
-    max_steps: int = 2000
+    max_steps: int = 2000,
+    mmap_weights: bool = True,
+    device: str = "gpu"
 ):
     """
     Server process that runs MLX inference.
@@ -55,6 +96,8 @@ def server_process(
         quantized: Quantization level (4 or 8) or None for bf16
         log_file: Path to log file
         max_steps: Maximum generation steps
+        mmap_weights: Load weights lazily from the file instead of reading it all into memory first
+        device: "gpu" (Metal) or "cpu" - compute.py's placement for the voice model
     """
     import sys
     import os
@@ -82,6 +125,14 @@ def server_process(
             # logging.StreamHandler() # Disabled to prevent TUI corruption
         ]
     )
+    # Transcripts read aloud can contain phone numbers and passwords - mask them in the log
+    try:
+        from .config import Config
+        from .redaction import Redactor, install_log_redaction, set_redactor
+        set_redactor(Redactor.from_config(Config.load_from_file()))
+        install_log_redaction()
+    except Exception as e:
+        logging.warning(f"Log redaction unavailable: {e}")
     logger = logging.getLogger("voice_server")
     
     # Track last log message to avoid flooding with duplicates
@@ -112,43 +163,64 @@ def server_process(
         logger.info(msg)
         status_queue.put(("info", f"LOG: {msg}"))
 
-    try:
-        # Download model files
-        if quantized == 8:
-            model_file = hf_hub_download(hf_repo, "model.q8.safetensors")
-        elif quantized == 4:
-            model_file = hf_hub_download(hf_repo, "model.q4.safetensors")
-        elif quantized is None:
-            model_file = hf_hub_download(hf_repo, "model.safetensors")
-        else:
-            raise ValueError(f"Invalid quantized value: {quantized}")
+    def progress(stage):
+        # Stage names are mapped to the TUI progress bar in model_loading.STAGES
+        status_queue.put(("progress", stage))
 
-        tokenizer_file = hf_hub_download(hf_repo, "tokenizer_spm_32k_3.model")
+    try:
+        # Download model files (instant when already cached)
+        progress("download")
+        weights_repo, weights_name, quantize_on_load = resolve_model_file(hf_repo, quantized)
+        if quantize_on_load:
+            log(f"Quantizing cached bf16 weights to q{quantized} on load (skips a download)")
+        model_file = hf_hub_download(weights_repo, weights_name)
+        tokenizer_file = hf_hub_download(weights_repo, "tokenizer_spm_32k_3.model")
 
         # Load text tokenizer
+        progress("tokenizer")
         log("Loading text tokenizer...")
         text_tokenizer = sentencepiece.SentencePieceProcessor(tokenizer_file)
 
         # Initialize model
+        progress("model")
+        if device == "cpu":
+            mx.set_default_device(mx.cpu)
+            log("Running the voice model on the CPU (model_placement)")
         mx.random.seed(299792458)
         lm_config = models.config_v0_1()
         model = models.Lm(lm_config)
         model.set_dtype(mx.bfloat16)
 
-        # Apply quantization if needed
-        if quantized is not None:
-            group_size = 32 if quantized == 4 else 64
+        group_size = 32 if quantized == 4 else 64
+        # Pre-quantized checkpoints need the quantized layers before their weights load
+        if quantized is not None and not quantize_on_load:
             nn.quantize(model, bits=quantized, group_size=group_size)
 
         # Load weights
+        progress("weights")
         log("Loading model weights...")
-        model.load_weights(model_file, strict=True)
+        if mmap_weights:
+            # mx.load is lazy: tensors are read from the file as they're evaluated, so there's
+            # never a second full copy of the checkpoint in memory (and bf16 weights being
+            # quantized on load are never all resident at once)
+            model.load_weights(list(mx.load(model_file).items()), strict=True)
+        else:
+            model.load_weights(model_file, strict=True)
+        if quantize_on_load:
+            nn.quantize(model, bits=quantized, group_size=group_size)
+        mx.eval(model.parameters())
         log("Weights loaded")
 
         # Warmup
+        progress("warmup")
         model.warmup()
         log("Model warmed up")
 
+        # Startup memory report (model_memory.MemoryReport)
+        import psutil
+        variant = "bf16" if quantized is None else f"q{quantized}"
+        status_queue.put(("memory", f"Moshi LM ({variant})", psutil.Process().memory_info().rss, gpu_active_memory()))
+
         # Create generator with large max_steps for long conversations
         # NOTE: This is a temporary fix. True duplex operation requires rearchitecting
         # the server to handle simultaneous input/output streams.
@@ -330,15 +402,19 @@ def server_process(
         traceback.print_exc()
 
 
-def start_server_process(quality: str = "q4", max_steps: int = 2000):
+def start_server_process(quality: str = "q4", max_steps: int = 2000, mmap_weights: bool = True, device: str = "gpu"):
     """
     Start the Voice server process.
 
     This should be called BEFORE Textual starts to avoid multiprocessing issues.
+    It returns as soon as the process is spawned; models load in the background
+    (see model_loading.wait_for_server for progress and the ready signal).
 
     Args:
         quality: "bf16", "q8", or "q4"
         max_steps: Maximum generation steps
+        mmap_weights: Load weights lazily from the checkpoint file (lower peak RAM)
+        device: "gpu" or "cpu" for the voice model
 
     Returns:
         Tuple of (process, client_to_server, server_to_client, status_queue)
@@ -377,7 +453,7 @@ def start_server_process(quality: str = "q4", max_steps: int = 2000):
     # Start server process using the spawn context
     process = ctx.Process(
         target=server_process,
-        args=(client_to_server, server_to_client, status_queue, hf_repo, quantized, log_file, max_steps),
+        args=(client_to_server, server_to_client, status_queue, hf_repo, quantized, log_file, max_steps, mmap_weights, device),
         daemon=True
     )
 
@@ -0,0 +1,229 @@
+"""
+Volume - How loud each kind of xswarm audio plays, and "speak louder".
+
+Playback is mixed from three streams (audio_mixer.OutputMixer):
+
+- speech: the assistant's voice (config.speech_volume)
+- earcons: listening cues, see earcons.py (config.earcon_volume)
+- media: other sounds passed through AudioIO.play_media (config.media_volume)
+
+Each is scaled by its own level and by config.volume (master). While the
+assistant is speaking, media is ducked to config.duck_level of its volume.
+Announcements whispered during a meeting (see notifications.py) play speech
+at config.whisper_level for as long as they take to say.
+
+Levels change by voice, typed chat or the set_volume tool:
+
+    "speak louder" / "talk quieter" / "I can't hear you"   -> speech up/down
+    "volume up" / "turn the volume down"                    -> master up/down
+    "set the volume to 60%" / "volume max"                  -> master to a level
+    "turn the chimes down" / "mute the music"               -> earcons / media
+
+parse_volume_request() only accepts short utterances that are about volume,
+so "turn down the meeting with Bob" still goes to the model. Changes are
+saved to the config file and apply to playback immediately.
+"""
+
+import logging
+import re
+import time
+from dataclasses import dataclass, field
+from typing import Any, Optional
+
+from .capabilities import register_capability
+
+logger = logging.getLogger(__name__)
+
+STREAMS = ("speech", "earcons", "media")
+
+STEP = 0.15  # One "louder" / "quieter"
+WORDS_PER_SECOND = 2.5  # Speaking pace, for how long a whisper lasts
+MAX_MASTER = 1.0
+MAX_STREAM = 1.5  # Streams can be boosted past their recorded level (clipped at full scale)
+
+# Config field for each level
+CONFIG_FIELDS = {
+    "master": "volume",
+    "speech": "speech_volume",
+    "earcons": "earcon_volume",
+    "media": "media_volume",
+}
+DEFAULTS = {"master": 1.0, "speech": 1.0, "earcons": 0.3, "media": 0.8}
+
+NAMES = {"master": "Volume", "speech": "Speech volume", "earcons": "Sound effect volume", "media": "Media volume"}
+
+_STREAM_WORDS = (
+    r"(?P<speech>your\s+voice|voice|speech|talking)|"
+    r"(?P<earcons>sound\s+effects?|sounds|chimes?|beeps?|blips?|cues?|earcons?)|"
+    r"(?P<media>media|music|playback)"
+)
+_LEAD = re.compile(
+    r"^\s*(?:(?:ok(?:ay)?|hey|please|can\s+you|could\s+you|would\s+you|will\s+you)[,\s]+)*", re.IGNORECASE)
+_TAIL = re.compile(r"(?:[,\s]+(?:please|a\s+(?:little\s+)?bit|a\s+little|a\s+touch|some))*\s*[.!?]*\s*$",
+                   re.IGNORECASE)
+_SPEAK_UP = re.compile(
+    r"^(?:(?:speak|talk)\s+(?:up|louder|more\s+loudly)|louder|(?:i\s+)?can'?t\s+hear\s+you)$", re.IGNORECASE)
+_SPEAK_DOWN = re.compile(
+    r"^(?:(?:speak|talk)\s+(?:quieter|softer|more\s+(?:quietly|softly)|down)|quieter|softer|"
+    r"(?:you'?re\s+|that'?s\s+|it'?s\s+)?(?:too|so)\s+loud|not\s+so\s+loud|lower\s+your\s+voice)$", re.IGNORECASE)
+# [verb [up/down]] [the] [stream] [volume] rest - the stream or "volume" has to come first,
+# so "set a reminder to turn the music down" isn't a volume change
+_VOLUME = re.compile(
+    r"^(?:(?P<verb>turn|set|put|make|mute|unmute|silence|raise|lower|increase|decrease|reduce)\s+"
+    r"(?:(?:up|down|off|on)\s+)?)?(?:the\s+|your\s+|my\s+)?"
+    rf"(?:(?:{_STREAM_WORDS})\b\s*)?(?P<volume>volume\b)?(?P<rest>.*)$",
+    re.IGNORECASE)
+_UP = re.compile(r"\b(?:up|louder|higher|raise|increase)\b", re.IGNORECASE)
+_DOWN = re.compile(r"\b(?:down|quieter|softer|lower|decrease|reduce)\b", re.IGNORECASE)
+_MUTE = re.compile(r"^(?:mute|silence)\b|\b(?:off|zero)$", re.IGNORECASE)
+_UNMUTE = re.compile(r"^unmute\b|\bon$", re.IGNORECASE)
+_PERCENT = re.compile(r"\b(\d{1,3})\s*(%|percent\b)?", re.IGNORECASE)
+_NAMED_LEVELS = {"max": 100, "maximum": 100, "full": 100, "half": 50}
+_NAMED = re.compile(r"\b(max(?:imum)?|full|half)\b", re.IGNORECASE)
+
+MAX_REST_WORDS = 5  # After the stream/"volume": "to 60 percent", "up a notch"
+
+
+@dataclass
+class VolumeRequest:
+    """A spoken/typed volume change, in set_volume's terms."""
+    stream: str  # master, speech, earcons or media
+    level: str  # up, down, mute, unmute or a percentage ("60")
+
+
+def parse_volume_request(text: str) -> Optional[VolumeRequest]:
+    """
+    The volume change `text` asks for, or None. A bare number up to 10 is
+    out of ten ("volume 7" is 70%); anything larger is a percentage.
+    """
+    core = _TAIL.sub("", _LEAD.sub("", text or "")).strip()
+    if not core:
+        return None
+    if _SPEAK_UP.match(core):
+        return VolumeRequest("speech", "up")
+    if _SPEAK_DOWN.match(core):
+        return VolumeRequest("speech", "down")
+
+    match = _VOLUME.match(core)
+    stream = next((name for name in STREAMS if match and match.group(name)), None)
+    if not match or not (match.group("volume") or (stream and match.group("verb"))):
+        return None
+    if len(match.group("rest").split()) > MAX_REST_WORDS:
+        return None
+    stream = stream or "master"
+
+    if _UNMUTE.search(core):
+        return VolumeRequest(stream, "unmute")
+    if _MUTE.search(core):
+        return VolumeRequest(stream, "mute")
+    named = _NAMED.search(core)
+    if named:
+        return VolumeRequest(stream, str(_NAMED_LEVELS[named.group(1).lower()]))
+    percent = _PERCENT.search(core)
+    if percent:
+        value = int(percent.group(1))
+        return VolumeRequest(stream, str(value * 10 if value <= 10 and not percent.group(2) else value))
+    if _UP.search(core):
+        return VolumeRequest(stream, "up")
+    if _DOWN.search(core):
+        return VolumeRequest(stream, "down")
+    return None
+
+
+def _clamp(value: float, limit: float) -> float:
+    return round(min(max(value, 0.0), limit), 2)
+
+
+@dataclass
+class VolumeSettings:
+    """Playback levels (config.volume, speech_volume, earcon_volume, media_volume, duck_level)."""
+    master: float = DEFAULTS["master"]
+    speech: float = DEFAULTS["speech"]
+    earcons: float = DEFAULTS["earcons"]
+    media: float = DEFAULTS["media"]
+    duck_level: float = 0.25  # Media plays at this fraction of its volume while the assistant speaks
+    whisper_level: float = 0.35  # Speech plays at this fraction of its volume while whispering
+    whisper_until: float = field(default=0.0, repr=False, compare=False)  # time.monotonic() a whisper ends
+    config: Optional[Any] = field(default=None, repr=False, compare=False)  # Saved when a level changes
+
+    @classmethod
+    def from_config(cls, config) -> "VolumeSettings":
+        def level(stream: str, limit: float) -> float:
+            value = getattr(config, CONFIG_FIELDS[stream], DEFAULTS[stream])
+            return _clamp(float(DEFAULTS[stream] if value is None else value), limit)
+
+        return cls(master=level("master", MAX_MASTER),
+                   speech=level("speech", MAX_STREAM),
+                   earcons=level("earcons", MAX_STREAM),
+                   media=level("media", MAX_STREAM),
+                   duck_level=_clamp(float(getattr(config, "duck_level", 0.25)), 1.0),
+                   whisper_level=_clamp(float(getattr(config, "whisper_level", 0.35)), 1.0),
+                   config=config)
+
+    def level(self, stream: str) -> float:
+        """A stream's own level (or "master")."""
+        return getattr(self, stream)
+
+    def gain(self, stream: str) -> float:
+        """What a stream's samples are multiplied by: its level times the master volume."""
+        gain = self.master * self.level(stream)
+        if stream == "speech" and time.monotonic() < self.whisper_until:
+            gain *= self.whisper_level
+        return gain
+
+    def whisper(self, text: str) -> None:
+        """Play speech at whisper_level for about as long as `text` takes to say."""
+        self.whisper_until = time.monotonic() + len(text.split()) / WORDS_PER_SECOND + 2.0
+
+    def set_level(self, stream: str, value: float) -> float:
+        """Set a level (clamped), save it to the config and return it."""
+        value = _clamp(value, MAX_MASTER if stream == "master" else MAX_STREAM)
+        setattr(self, stream, value)
+        if self.config is not None:
+            setattr(self.config, CONFIG_FIELDS[stream], value)
+            try:
+                self.config.save_to_file()
+            except Exception as e:
+                logger.warning(f"Could not save volume settings: {e}")
+        return value
+
+    def change(self, stream: str, level: str) -> float:
+        """
+        Apply a set_volume level to a stream: "up"/"down" step by STEP,
+        "mute" is 0, "unmute" goes back to the default if muted, "60" is 60%.
+        Raises ValueError for anything else.
+        """
+        level = level.strip().lower().rstrip("%").strip()
+        if level in ("up", "louder"):
+            return self.set_level(stream, self.level(stream) + STEP)
+        if level in ("down", "quieter", "softer"):
+            return self.set_level(stream, self.level(stream) - STEP)
+        if level in ("mute", "off"):
+            return self.set_level(stream, 0.0)
+        if level in ("unmute", "on"):
+            return self.set_level(stream, self.level(stream) or DEFAULTS[stream])
+        if level in _NAMED_LEVELS:
+            level = str(_NAMED_LEVELS[level])
+        return self.set_level(stream, float(level) / 100.0)
+
+
+_settings: Optional[VolumeSettings] = None
+
+
+def get_volume_settings() -> VolumeSettings:
+    """Get the global volume settings (defaults until configured)."""
+    global _settings
+    if _settings is None:
+        _settings = VolumeSettings()
+    return _settings
+
+
+def set_volume_settings(settings: VolumeSettings) -> None:
+    """Install the settings built from the loaded Config (called at startup)."""
+    global _settings
+    _settings = settings
+
+
+register_capability("Volume", "How loud my voice, the chimes and media play",
+                    ["speak louder", "set the volume to 60%", "mute the music"],
+                    category="Settings", keywords=("loud", "quiet", "mute", "sound"))
@@ -7,10 +7,14 @@ import json
 import logging
 import queue
 import threading
+import time
 from typing import Optional, Callable
 from pathlib import Path
 import numpy as np
 
+from .audio_frame import AudioLike, pcm16_bytes
+from .resample import AudioFormat
+
 logger = logging.getLogger(__name__)
 
 try:
@@ -57,6 +61,8 @@ class WakeWordDetector:
         self.wake_word = self.wake_words[0] if self.wake_words else "jarvis"
 
         self.sample_rate = sample_rate
+        # Vosk takes 16-bit PCM at the model's rate; the pipeline resamples frames to this
+        self.input_format = AudioFormat(sample_rate, "int16")
         self.sensitivity = sensitivity
 
         # Load Vosk model
@@ -70,6 +76,8 @@ class WakeWordDetector:
         self.model = Model(str(model_path))
         self.recognizer = KaldiRecognizer(self.model, sample_rate)
         self.recognizer.SetWords(True)  # Get word-level confidence
+        # Seconds between decodes, frames queued meanwhile decoded together (low-power profile, see power.py)
+        self.poll_interval = 0.0
 
         # Log all wake words
         if len(self.wake_words) == 1:
@@ -114,22 +122,18 @@ class WakeWordDetector:
             self._thread.join(timeout=1.0)
         logger.debug("Wake word detection stopped")
 
-    def process_audio(self, audio: np.ndarray):
+    def process_audio(self, audio: AudioLike):
         """
         Process audio frame for wake word detection.
 
         Args:
-            audio: Audio samples at 16kHz (int16 or float32)
+            audio: Audio samples at 16kHz (int16 or float32), or a shared AudioFrame
         """
         if not self.is_active:
             return
 
-        # Convert to int16 if needed
-        if audio.dtype == np.float32 or audio.dtype == np.float64:
-            audio = (audio * 32767).astype(np.int16)
-
-        # Queue for processing
-        self._audio_queue.put(audio.tobytes())
+        # Queue for processing (int16 bytes, converted once per frame for shared AudioFrames)
+        self._audio_queue.put(pcm16_bytes(audio))
 
     def _detection_loop(self):
         """Background thread for wake word detection"""
@@ -137,6 +141,8 @@ class WakeWordDetector:
             try:
                 # Get audio from queue (blocking with timeout)
                 audio_data = self._audio_queue.get(timeout=0.1)
+                while not self._audio_queue.empty():
+                    audio_data += self._audio_queue.get_nowait()
 
                 # Process with Vosk
                 if self.recognizer.AcceptWaveform(audio_data):
@@ -147,6 +153,8 @@ class WakeWordDetector:
                     # Partial result (still speaking)
                     result = json.loads(self.recognizer.PartialResult())
                     self._check_wake_word(result, partial=True)
+                if self.poll_interval:
+                    time.sleep(self.poll_interval)
 
             except queue.Empty:
                 continue
@@ -0,0 +1,127 @@
+"""
+Warmup - Throwaway inference through the voice pipeline so the first real turn isn't cold.
+
+Models that sat unused get paged out, and kernels and caches go cold, so the
+first thing said after a long quiet spell (the first of the day, say) waits
+noticeably longer than the rest. Warmup runs a dummy pass through each stage:
+
+    stt   a second of silence through a fresh Vosk recognizer
+    llm   loads the local model (Ollama / LM Studio) and keeps it resident;
+          cloud models have nothing to warm
+    tts   Moshi silently reads a short phrase (the audio is discarded)
+
+The dashboard's warmup job runs it once the voice models have loaded and
+again after config.voice_warmup_idle_minutes without an interaction. It is
+skipped on battery and in the low-power profile (see power.py), while a turn
+is in progress, and entirely with config.voice_warmup off.
+"""
+
+import asyncio
+import logging
+import time
+from dataclasses import dataclass
+from typing import Awaitable, Callable, Dict, List, Optional
+
+from .power import LOW_POWER, get_power_manager
+
+logger = logging.getLogger(__name__)
+
+IDLE_AFTER = 30 * 60.0  # Seconds without an interaction before warming again
+STEP_TIMEOUT = 30.0  # Seconds one step may take
+
+
+@dataclass
+class StepResult:
+    name: str
+    seconds: float
+    warmed: bool  # False: nothing to warm for this step (e.g. no local model)
+    error: Optional[str] = None
+
+    def __str__(self) -> str:
+        if self.error:
+            return f"{self.name} failed ({self.error})"
+        return f"{self.name} {self.seconds:.1f}s" if self.warmed else f"{self.name} skipped"
+
+
+def _power_saving() -> Optional[str]:
+    manager = get_power_manager()
+    if manager.state.on_battery:
+        return "on battery"
+    if manager.profile is LOW_POWER:
+        return "low power"
+    return None
+
+
+class Warmup:
+    """Runs the warm-up steps after load and after long idle periods."""
+
+    def __init__(self, enabled: bool = True, idle_after: float = IDLE_AFTER, step_timeout: float = STEP_TIMEOUT,
+                 power_saving: Callable[[], Optional[str]] = _power_saving,
+                 clock: Callable[[], float] = time.monotonic):
+        self.enabled = enabled
+        self.idle_after = idle_after
+        self.step_timeout = step_timeout
+        self.power_saving = power_saving
+        self._clock = clock
+        # name -> async step; a step returns False when it had nothing to warm
+        self.steps: Dict[str, Callable[[], Awaitable[bool]]] = {}
+        self.last_activity = clock()
+        self.last_run: Optional[float] = None
+        self.results: List[StepResult] = []
+        self.running = False
+
+    @classmethod
+    def from_config(cls, config) -> "Warmup":
+        return cls(enabled=bool(getattr(config, "voice_warmup", True)),
+                   idle_after=float(getattr(config, "voice_warmup_idle_minutes", IDLE_AFTER / 60)) * 60)
+
+    def add_step(self, name: str, step: Callable[[], Awaitable[bool]]) -> None:
+        self.steps[name] = step
+
+    def touch(self) -> None:
+        """An interaction happened: the pipeline is warm, the idle clock restarts."""
+        self.last_activity = self._clock()
+
+    def skip_reason(self) -> Optional[str]:
+        """Why a warm-up shouldn't run now, or None."""
+        if not self.enabled:
+            return "disabled"
+        if self.running:
+            return "already running"
+        return self.power_saving()
+
+    def due(self) -> bool:
+        """Never warmed since load, or idle for idle_after since the last interaction or warm-up."""
+        if self.skip_reason() is not None or not self.steps:
+            return False
+        if self.last_run is None:
+            return True
+        return self._clock() - max(self.last_activity, self.last_run) >= self.idle_after
+
+    async def run(self) -> List[StepResult]:
+        """Run every step in order (one failing doesn't stop the rest)."""
+        self.running = True
+        results = []
+        try:
+            for name, step in self.steps.items():
+                started = self._clock()
+                try:
+                    warmed = await asyncio.wait_for(step(), timeout=self.step_timeout)
+                    results.append(StepResult(name, self._clock() - started, warmed is not False))
+                except asyncio.TimeoutError:
+                    results.append(StepResult(name, self._clock() - started, False, "timed out"))
+                except Exception as e:
+                    logger.debug(f"Warm-up step {name} failed: {e}")
+                    results.append(StepResult(name, self._clock() - started, False, str(e) or type(e).__name__))
+        finally:
+            self.running = False
+            self.last_run = self._clock()
+        self.results = results
+        logger.info("🔥 Voice warm-up: " + ", ".join(str(result) for result in results))
+        return results
+
+    async def maybe_run(self) -> Optional[List[StepResult]]:
+        """Run if due (the warmup job); the results, or None when it didn't run."""
+        if not self.due():
+            return None
+        return await self.run()
@@ -0,0 +1,214 @@
+"""
+Web Search - Up-to-date answers to factual questions, with their sources.
+
+The web_search tool lets the model look things up ("what's new in Rust
+1.90?") and answer citing where it read it: each result carries a spoken
+source name ("the Rust blog", "Wikipedia", "reuters.com") so the reply can
+say "according to the Rust blog..." instead of reading out a URL.
+
+Off by default, since every query leaves the machine. Providers are
+pluggable (config.web_search):
+- "none": no searches; the tool says search is off
+- "searxng": a SearxNG instance (config.web_search_url), e.g. self-hosted
+  so queries go nowhere else; its JSON format must be enabled
+- "brave": the Brave Search API (config.web_search_key)
+
+Anything with an async search(query, limit) -> List[SearchResult] works
+(see StaticSearch for the shape). Installed at startup with
+set_web_search(WebSearch.from_config(config)).
+"""
+
+import logging
+import re
+from dataclasses import dataclass
+from typing import Dict, List, Optional
+from urllib.parse import urlparse
+
+from .capabilities import register_capability
+
+logger = logging.getLogger(__name__)
+
+PROVIDERS = ("none", "searxng", "brave")
+BRAVE_URL = "https://api.search.brave.com/res/v1/web/search"
+DEFAULT_RESULTS = 5
+MAX_SNIPPET = 300
+
+# Sites better known by a name than their domain
+SOURCE_NAMES: Dict[str, str] = {
+    "blog.rust-lang.org": "the Rust blog",
+    "rust-lang.org": "the Rust website",
+    "wikipedia.org": "Wikipedia",
+    "bbc.co.uk": "the BBC",
+    "bbc.com": "the BBC",
+    "nytimes.com": "The New York Times",
+    "theguardian.com": "The Guardian",
+    "reuters.com": "Reuters",
+    "apnews.com": "AP News",
+    "github.com": "GitHub",
+    "stackoverflow.com": "Stack Overflow",
+    "docs.python.org": "the Python docs",
+    "developer.mozilla.org": "MDN",
+    "weather.gov": "the National Weather Service",
+    "metoffice.gov.uk": "the Met Office",
+}
+
+
+class WebSearchError(Exception):
+    """Search is off, misconfigured or the provider failed."""
+
+
+def source_name(url: str) -> str:
+    """What to call a site out loud: "https://en.wikipedia.org/wiki/X" -> "Wikipedia"."""
+    bare = re.sub(r"^(?:www|m|en|mobile)\.", "", (urlparse(url).hostname or "").lower())
+    host = bare
+    while host:
+        if host in SOURCE_NAMES:
+            return SOURCE_NAMES[host]
+        if host.count(".") < 2:
+            break
+        host = host.split(".", 1)[1]  # news.bbc.co.uk -> bbc.co.uk
+    return bare or "the web"
+
+
+def _clean(text: str) -> str:
+    text = re.sub(r"<[^>]+>", "", text or "")  # Providers highlight matches with <strong>
+    text = " ".join(text.split())
+    return text if len(text) <= MAX_SNIPPET else text[:MAX_SNIPPET].rsplit(" ", 1)[0] + "…"
+
+
+@dataclass(frozen=True)
+class SearchResult:
+    title: str
+    url: str
+    snippet: str = ""
+    published: str = ""  # As the provider gives it, e.g. "2026-10-02" or "3 days ago"
+
+    @property
+    def source(self) -> str:
+        return source_name(self.url)
+
+
+class StaticSearch:
+    """Fixed results (tests, offline demos): {"query words": [SearchResult, ...]}, matched by substring."""
+
+    def __init__(self, results: Dict[str, List[SearchResult]]):
+        self.results = {key.lower(): value for key, value in results.items()}
+
+    async def search(self, query: str, limit: int = DEFAULT_RESULTS) -> List[SearchResult]:
+        for key, results in self.results.items():
+            if key in query.lower():
+                return results[:limit]
+        return []
+
+
+class SearxngSearch:
+    """A SearxNG instance's JSON API (`search.formats: [html, json]` in its settings.yml)."""
+
+    def __init__(self, base_url: str, timeout: float = 10.0):
+        self.base_url = base_url.rstrip("/")
+        self.timeout = timeout
+
+    async def search(self, query: str, limit: int = DEFAULT_RESULTS) -> List[SearchResult]:
+        import httpx
+
+        async with httpx.AsyncClient(timeout=self.timeout) as client:
+            response = await client.get(f"{self.base_url}/search", params={"q": query, "format": "json"})
+            if response.status_code == 403:
+                raise WebSearchError("SearxNG refused JSON output - add json to search.formats in its settings.yml")
+            response.raise_for_status()
+            raw = response.json().get("results", [])
+        return [SearchResult(_clean(r.get("title", "")), r.get("url", ""), _clean(r.get("content", "")),
+                             r.get("publishedDate") or "")
+                for r in raw[:limit] if r.get("url")]
+
+
+class BraveSearch:
+    """Brave Search API (web results)."""
+
+    def __init__(self, api_key: str, timeout: float = 10.0):
+        self.api_key = api_key
+        self.timeout = timeout
+
+    async def search(self, query: str, limit: int = DEFAULT_RESULTS) -> List[SearchResult]:
+        import httpx
+
+        headers = {"Accept": "application/json", "X-Subscription-Token": self.api_key}
+        async with httpx.AsyncClient(timeout=self.timeout, headers=headers) as client:
+            response = await client.get(BRAVE_URL, params={"q": query, "count": limit})
+            response.raise_for_status()
+            raw = response.json().get("web", {}).get("results", [])
+        return [SearchResult(_clean(r.get("title", "")), r.get("url", ""), _clean(r.get("description", "")),
+                             r.get("page_age") or r.get("age") or "")
+                for r in raw[:limit] if r.get("url")]
+
+
+class WebSearch:
+    """The configured provider, with results formatted for the model to answer from and cite."""
+
+    def __init__(self, provider, limit: int = DEFAULT_RESULTS):
+        self.provider = provider
+        self.limit = limit
+
+    @classmethod
+    def from_config(cls, config=None) -> Optional["WebSearch"]:
+        """None when search is off (the default)."""
+        name = (getattr(config, "web_search", None) or "none").lower()
+        limit = getattr(config, "web_search_results", DEFAULT_RESULTS)
+        if name == "none":
+            return None
+        if name == "searxng":
+            url = getattr(config, "web_search_url", None)
+            if not url:
+                raise WebSearchError("web_search is searxng but web_search_url isn't set")
+            return cls(SearxngSearch(url), limit)
+        if name == "brave":
+            key = getattr(config, "web_search_key", None)
+            if not key:
+                raise WebSearchError("web_search is brave but web_search_key isn't set")
+            return cls(BraveSearch(key), limit)
+        raise WebSearchError(f"Unknown web_search provider '{name}' (use {', '.join(PROVIDERS)})")
+
+    async def search(self, query: str) -> List[SearchResult]:
+        query = " ".join(query.split())
+        if not query:
+            return []
+        try:
+            return await self.provider.search(query, self.limit)
+        except WebSearchError:
+            raise
+        except Exception as e:
+            logger.debug(f"Web search failed: {e}")
+            raise WebSearchError(f"Web search failed: {e}") from e
+
+    async def answer_context(self, query: str) -> str:
+        """Numbered results for the model, each with the name to cite it by."""
+        results = await self.search(query)
+        if not results:
+            return f"No web results for \"{query}\"."
+        lines = [f"Web results for \"{query}\":"]
+        for n, result in enumerate(results, 1):
+            dated = f", {result.published[:10]}" if result.published else ""
+            lines.append(f"[{n}] {result.title} ({result.source}{dated}) {result.url}")
+            if result.snippet:
+                lines.append(f"    {result.snippet}")
+        lines.append("Answer from these, saying where it's from by name (\"according to "
+                     f"{results[0].source}...\"), not the URL. If they don't answer it, say so.")
+        return "\n".join(lines)
+
+
+_web_search: Optional[WebSearch] = None
+
+
+def get_web_search() -> Optional[WebSearch]:
+    """The configured web search, or None when it's off."""
+    return _web_search
+
+
+def set_web_search(search: Optional[WebSearch]) -> None:
+    global _web_search
+    _web_search = search
+
+
+register_capability("Web search", "Look things up online and say where the answer came from",
+                    ["what's new in Rust 1.90?", "search the web for the train strike"], requires=("web_search",),
+                    category="Information", keywords=("internet", "google", "look up"))
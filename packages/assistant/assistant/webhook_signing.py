@@ -0,0 +1,155 @@
+"""
+Webhook Signing - Verifying who opened the daemon's webhook socket.
+
+The HTTP webhooks (SMS, email) reach this machine through the Cloudflare
+tunnel to the server's worker, which verifies them
+(packages/server/src/middleware/webhook-signature.js). The one webhook the
+daemon takes itself is the Twilio Media Streams WebSocket (phone.py), so
+its handshake is checked here with the same two schemes:
+
+- Twilio: X-Twilio-Signature, base64 HMAC-SHA1 of the public URL Twilio
+  connected to (config.voice_tunnel_url plus the path) with
+  TWILIO_AUTH_TOKEN, or TWILIO_AUTH_TOKEN_SECONDARY while the token is being
+  rotated. Twilio's handshake carries no timestamp and signs only the URL,
+  so replays are caught one step later: each call's CallSid is accepted
+  once within WINDOW_SECONDS (first_call()).
+- xSwarm-signed clients (the binary-frames clients of ws_framing.py):
+  X-Xswarm-Timestamp (unix seconds) and X-Xswarm-Signature
+  "v1=<hex HMAC-SHA256 of `{timestamp}.{path}.`>" - the server's format,
+  with an empty body. WEBHOOK_SIGNING_SECRETS is comma-separated: sign with
+  the first, any of them verifies. Timestamps more than WINDOW_SECONDS off,
+  and signatures already seen within the window, are rejected.
+
+Anything else is refused with 401 at the handshake - including every
+connection when no token or secret is set, since then nothing can be
+verified. Results are counted in `stats` and shown in the log.
+"""
+
+import base64
+import hashlib
+import hmac
+import logging
+import os
+import time
+from collections import OrderedDict
+from http import HTTPStatus
+from typing import Callable, Dict, List, Mapping, Optional, Sequence
+
+logger = logging.getLogger(__name__)
+
+WINDOW_SECONDS = 5 * 60
+MAX_REMEMBERED = 10000  # Signatures and call ids remembered for replays (the oldest dropped first)
+RESULTS = ("verified", "unconfigured", "missing", "bad_signature", "stale", "replayed")
+
+
+def twilio_signature(token: str, url: str, params: Optional[Mapping[str, str]] = None) -> str:
+    data = url + "".join(f"{key}{params[key]}" for key in sorted(params or {}))
+    return base64.b64encode(hmac.new(token.encode(), data.encode(), hashlib.sha1).digest()).decode()
+
+
+def xswarm_signature(secret: str, timestamp: str, path: str, body: str = "") -> str:
+    return hmac.new(secret.encode(), f"{timestamp}.{path}.{body}".encode(), hashlib.sha256).hexdigest()
+
+
+def sign(secret: str, path: str, body: str = "", now: Optional[float] = None) -> Dict[str, str]:
+    """Headers for an xSwarm-signed handshake (or callback) to `path`."""
+    timestamp = str(int(time.time() if now is None else now))
+    return {"X-Xswarm-Timestamp": timestamp,
+            "X-Xswarm-Signature": f"v1={xswarm_signature(secret, timestamp, path, body)}"}
+
+
+def _split(value: Optional[str]) -> List[str]:
+    return [part.strip() for part in (value or "").split(",") if part.strip()]
+
+
+class WebhookVerifier:
+    """Checks handshake signatures and remembers what it has seen within the window."""
+
+    def __init__(self, twilio_tokens: Sequence[str] = (), secrets: Sequence[str] = (), public_url: str = "",
+                 clock: Callable[[], float] = time.time):
+        self.twilio_tokens = [t for t in twilio_tokens if t]
+        self.secrets = [s for s in secrets if s]
+        self.public_url = public_url.rstrip("/")
+        self.clock = clock
+        self.stats = {result: 0 for result in RESULTS}
+        self._seen: "OrderedDict[str, float]" = OrderedDict()  # key -> when it can be forgotten
+
+    @classmethod
+    def from_config(cls, config=None) -> "WebhookVerifier":
+        """Tokens and secrets from the environment (as the server names them), the URL from the voice tunnel."""
+        public_url = getattr(config, "voice_tunnel_url", None) or os.getenv("VOICE_TUNNEL_URL", "")
+        return cls([os.getenv("TWILIO_AUTH_TOKEN", ""), os.getenv("TWILIO_AUTH_TOKEN_SECONDARY", "")],
+                   _split(os.getenv("WEBHOOK_SIGNING_SECRETS")), public_url)
+
+    def _first_sighting(self, key: str) -> bool:
+        now = self.clock()
+        while self._seen and (next(iter(self._seen.values())) <= now or len(self._seen) >= MAX_REMEMBERED):
+            self._seen.popitem(last=False)
+        if key in self._seen:
+            return False
+        self._seen[key] = now + WINDOW_SECONDS
+        return True
+
+    def check(self, path: str, headers: Mapping[str, str]) -> str:
+        """One of RESULTS for a handshake to `path` with these headers."""
+        headers = {key.lower(): value for key, value in headers.items()}
+        if "x-xswarm-signature" in headers or "x-xswarm-timestamp" in headers:
+            result = self._check_xswarm(path, headers)
+        else:
+            result = self._check_twilio(path, headers)
+        self.stats[result] += 1
+        return result
+
+    def _check_twilio(self, path: str, headers: Dict[str, str]) -> str:
+        if not self.twilio_tokens or not self.public_url:
+            return "unconfigured"
+        signature = headers.get("x-twilio-signature")
+        if not signature:
+            return "missing"
+        url = self.public_url + path
+        if any(hmac.compare_digest(signature, twilio_signature(token, url)) for token in self.twilio_tokens):
+            return "verified"
+        return "bad_signature"
+
+    def _check_xswarm(self, path: str, headers: Dict[str, str]) -> str:
+        if not self.secrets:
+            return "unconfigured"
+        timestamp, header = headers.get("x-xswarm-timestamp"), headers.get("x-xswarm-signature")
+        if not timestamp or not header:
+            return "missing"
+        if not timestamp.isdigit() or abs(self.clock() - int(timestamp)) > WINDOW_SECONDS:
+            return "stale"
+        given = [part.strip()[3:] for part in header.split(",") if part.strip().startswith("v1=")]
+        for secret in self.secrets:
+            expected = xswarm_signature(secret, timestamp, path)
+            match = next((signature for signature in given if hmac.compare_digest(signature, expected)), None)
+            if match:
+                return "verified" if self._first_sighting(f"xswarm:{match}") else "replayed"
+        return "bad_signature"
+
+    def first_call(self, call_sid: str) -> bool:
+        """False when this call already streamed within the window (a replayed Twilio handshake)."""
+        if self._first_sighting(f"call:{call_sid}"):
+            return True
+        self.stats["replayed"] += 1
+        return False
+
+    def process_request(self, *args):
+        """
+        websockets' process_request hook: None to go on with the handshake,
+        or a 401. Takes both APIs - (path, request_headers) from the legacy
+        server and (connection, request) from the new one.
+        """
+        if len(args) == 2 and hasattr(args[1], "headers") and hasattr(args[1], "path"):
+            connection, request = args
+            path, headers = request.path, request.headers
+        else:
+            connection = None
+            path, headers = args
+        result = self.check(path, dict(headers.items()))
+        if result == "verified":
+            return None
+        logger.warning(f"Webhook handshake to {path} refused: {result}")
+        if connection is not None:
+            return connection.respond(HTTPStatus.UNAUTHORIZED, f"Webhook signature {result}\n")
+        return HTTPStatus.UNAUTHORIZED, [("Content-Type", "text/plain")], f"Webhook signature {result}\n".encode()
@@ -0,0 +1,158 @@
+"""
+Working Memory - How much of a conversation the AI sees, and a synopsis of the rest.
+
+The chat engine used to send every message of the session, until a long
+conversation no longer fit. Now each request carries only the latest turns
+(a turn is the user's message and the replies to it):
+
+- config.working_memory_turns: at most this many turns (0 = no limit)
+- config.working_memory_tokens: and at most about this many tokens of them
+  (estimated at CHARS_PER_TOKEN; the latest turn is always kept)
+
+Both are runtime settings - "remember more of our conversation" (the
+working_memory setting, voice_config.py) changes the turns for every
+conversation at once, and ChatEngine.set_working_memory() changes one
+conversation only.
+
+Turns that fall out of the window aren't dropped: they are folded into a
+rolling synopsis by the AI ("what's been established so far"), each time
+starting from the previous synopsis, and the synopsis goes at the start of
+the window. Without an AI, or when the call fails, the synopsis lists the
+user's earlier requests instead, so the context shrinks visibly rather
+than silently. The synopsis is also kept as the saved session's summary
+(memory.PersistentChatHistory), which the memory digest and later
+sessions read.
+"""
+
+import logging
+from dataclasses import dataclass
+from typing import Awaitable, Callable, List, Optional, Sequence, Tuple
+
+logger = logging.getLogger(__name__)
+
+CHARS_PER_TOKEN = 4
+SYNOPSIS_WORDS = 200  # Asked-for length of the synopsis
+FALLBACK_REQUESTS = 12  # Earlier requests the AI-less synopsis lists
+FALLBACK_HEADING = "Earlier the user asked:"
+
+Summarize = Callable[[list, int], Awaitable[str]]  # AIClient.chat(messages, max_tokens)
+
+SYNOPSIS_PROMPT = """Keep a running synopsis of a conversation between a user and their assistant, for the \
+assistant to remember what came before the messages it can still see.
+
+Update the synopsis with the older messages below. Keep what was decided, asked for, promised, and any names, \
+dates and numbers that may come up again; drop small talk. Write at most {words} words of plain prose, no \
+headings. Reply with the synopsis only."""
+
+
+def estimate_tokens(text: str) -> int:
+    return len(text) // CHARS_PER_TOKEN + 1
+
+
+@dataclass
+class Window:
+    turns: int = 20  # 0 = no limit
+    tokens: int = 8000  # 0 = no limit
+
+    @classmethod
+    def from_config(cls, config=None) -> "Window":
+        return cls(max(0, int(getattr(config, "working_memory_turns", cls.turns))),
+                   max(0, int(getattr(config, "working_memory_tokens", cls.tokens))))
+
+
+def window_start(messages: Sequence, window: Window) -> int:
+    """
+    Index of the first message inside the window. `messages` have .role
+    ("user" or other) and .content; the window starts at a user message.
+    """
+    starts = [i for i, message in enumerate(messages) if _role(message) == "user"]
+    if not starts:
+        return 0
+    kept = starts[-window.turns:] if window.turns else starts
+    start = kept[0]
+    if window.tokens:
+        for candidate in kept:  # Oldest first: move the start up until what's left fits
+            start = candidate
+            if sum(estimate_tokens(_content(m)) for m in messages[candidate:]) <= window.tokens:
+                break
+    return start
+
+
+def _role(message) -> str:
+    role = message.get("role") if isinstance(message, dict) else getattr(message, "role", "")
+    return str(getattr(role, "value", role))
+
+
+def _content(message) -> str:
+    return str(message.get("content", "") if isinstance(message, dict) else getattr(message, "content", ""))
+
+
+def transcript(messages: Sequence) -> str:
+    return "\n".join(f"{_role(m).capitalize()}: {_content(m)}" for m in messages if _role(m) in ("user", "assistant"))
+
+
+def fallback_synopsis(previous: str, messages: Sequence) -> str:
+    """Without an AI: the previous synopsis's prose, then the user's earlier requests (the newest kept)."""
+    prose, requests = [], []
+    for line in previous.splitlines():
+        if line.startswith("- "):
+            requests.append(line)
+        elif line != FALLBACK_HEADING:
+            prose.append(line)
+    requests += [f"- {_content(m).strip()[:160]}" for m in messages if _role(m) == "user"]
+    return "\n".join(prose + [FALLBACK_HEADING] + requests[-FALLBACK_REQUESTS:]).strip()
+
+
+class WorkingMemory:
+    """One conversation's window and the synopsis of what fell out of it."""
+
+    def __init__(self, config=None, summarize: Optional[Summarize] = None):
+        self.config = config
+        self.summarize = summarize
+        self.override: Optional[Window] = None  # This conversation's own window (set_window)
+        self.synopsis = ""
+        self.folded = 0  # Messages (from the start of the conversation) in the synopsis
+
+    @property
+    def window(self) -> Window:
+        return self.override or Window.from_config(self.config)
+
+    def set_window(self, turns: Optional[int] = None, tokens: Optional[int] = None) -> Window:
+        current = self.window
+        self.override = Window(current.turns if turns is None else max(0, turns),
+                               current.tokens if tokens is None else max(0, tokens))
+        return self.override
+
+    def reset(self) -> None:
+        self.synopsis = ""
+        self.folded = 0
+
+    async def update(self, messages: Sequence) -> bool:
+        """Fold the messages that have left the window into the synopsis; True when it changed."""
+        start = window_start(messages, self.window)
+        if start <= self.folded:
+            return False
+        older = messages[self.folded:start]
+        self.synopsis = await self._consolidate(older)
+        self.folded = start
+        logger.info(f"Working memory: {len(older)} older messages folded into the synopsis "
+                    f"({estimate_tokens(self.synopsis)} tokens)")
+        return True
+
+    async def _consolidate(self, older: Sequence) -> str:
+        if self.summarize is not None:
+            prompt = SYNOPSIS_PROMPT.format(words=SYNOPSIS_WORDS)
+            body = (f"Synopsis so far:\n{self.synopsis}\n\n" if self.synopsis else "") + \
+                f"Older messages:\n{transcript(older)}"
+            try:
+                synopsis = (await self.summarize([{"role": "system", "content": prompt},
+                                                  {"role": "user", "content": body}], SYNOPSIS_WORDS * 2)).strip()
+                if synopsis:
+                    return synopsis
+            except Exception as e:
+                logger.warning(f"Working memory synopsis failed, listing earlier requests instead: {e}")
+        return fallback_synopsis(self.synopsis, older)
+
+    def visible(self, messages: Sequence) -> Tuple[List, str]:
+        """The messages the AI sees (from the window's start) and the synopsis to put before them."""
+        return list(messages[self.folded:]), self.synopsis
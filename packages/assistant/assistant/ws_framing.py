@@ -0,0 +1,217 @@
+"""
+WebSocket Framing - Compressed JSON events and compact binary audio frames.
+
+The assistant's two WebSocket listeners speak JSON: the companion server
+(pairing.py) and the Twilio Media Streams server (phone.py), where every
+20ms of call audio is a base64 string inside a JSON "media" event - a third
+bigger than the audio, plus the envelope. Two things make that cheaper,
+both negotiated so clients that know neither keep working unchanged:
+
+- permessage-deflate (RFC 7692) for the JSON events: offered in the
+  handshake when config.ws_compression is on (the default). A client that
+  doesn't ask for it gets plain frames.
+- Binary audio frames: a client that lists "audio-v1" in the `frames` of
+  its first event (phone.py's "connected") is told {"event": "frames",
+  "frames": ["audio-v1"]} and from then on sends and receives audio as
+  binary messages of one or more length-prefixed frames (with "opus" too,
+  when libopus is installed, the frames carry Opus - opus_transport.py):
+
+      offset  size  field
+      0       2     magic b"XA"
+      2       1     version (1)
+      3       1     codec: 0 pcm16, 1 opus, 2 mulaw
+      4       4     sample rate (Hz)
+      8       4     sequence number
+      12      4     payload length (n)
+      16      n     payload
+
+  Control events (start, stop, mark) stay JSON. Twilio never offers
+  frames, so calls from Twilio are unchanged.
+
+Bytes each way per listener, the bytes binary frames saved against the JSON
+they replace, and how many connections negotiated deflate are counted in a
+BandwidthMeter, shown as the "Net" chart on the dashboard's Status tab and
+in the control socket's status.
+"""
+
+import logging
+import struct
+import time
+from collections import deque
+from dataclasses import dataclass
+from typing import Any, Deque, Dict, Iterable, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+MAGIC = b"XA"
+VERSION = 1
+HEADER = struct.Struct("!2sBBIII")  # magic, version, codec, sample rate, sequence, payload length
+CODECS = {"pcm16": 0, "opus": 1, "mulaw": 2}
+FRAME_FORMATS = ("audio-v1", "opus")  # "opus": audio-v1 frames carrying Opus (see opus_transport.py)
+MAX_PAYLOAD = 64 * 1024  # One frame's audio; 20ms of anything fits many times over
+RATE_WINDOW = 60.0  # Seconds the meter's rate is averaged over
+
+
+class FrameError(ValueError):
+    """A binary message that isn't a whole, known audio frame."""
+
+
+@dataclass
+class AudioFrame:
+    codec: str
+    sample_rate: int
+    sequence: int
+    payload: bytes
+
+    def encode(self) -> bytes:
+        if self.codec not in CODECS:
+            raise FrameError(f"Unknown codec '{self.codec}'")
+        return HEADER.pack(MAGIC, VERSION, CODECS[self.codec], self.sample_rate, self.sequence,
+                           len(self.payload)) + self.payload
+
+
+def encode_frames(frames: Iterable[AudioFrame]) -> bytes:
+    return b"".join(frame.encode() for frame in frames)
+
+
+def decode_frames(data: bytes) -> List[AudioFrame]:
+    """The frames in one binary message; FrameError when any of it is malformed."""
+    names = {number: name for name, number in CODECS.items()}
+    frames, offset = [], 0
+    while offset < len(data):
+        if len(data) - offset < HEADER.size:
+            raise FrameError("Truncated frame header")
+        magic, version, codec, rate, sequence, length = HEADER.unpack_from(data, offset)
+        if magic != MAGIC or version != VERSION:
+            raise FrameError("Not an audio-v1 frame")
+        if codec not in names:
+            raise FrameError(f"Unknown codec {codec}")
+        if length > MAX_PAYLOAD:
+            raise FrameError(f"Frame of {length} bytes is over {MAX_PAYLOAD}")
+        start = offset + HEADER.size
+        if len(data) < start + length:
+            raise FrameError("Truncated frame payload")
+        frames.append(AudioFrame(names[codec], rate, sequence, bytes(data[start:start + length])))
+        offset = start + length
+    return frames
+
+
+def supported_formats() -> Tuple[str, ...]:
+    """The formats this machine can do: Opus only with libopus installed."""
+    from .opus_transport import opus_available
+    return FRAME_FORMATS if opus_available() else ("audio-v1",)
+
+
+def negotiate(offered: Any, supported: Optional[Iterable[str]] = None) -> List[str]:
+    """The frame formats both sides know, from a client's `frames` list."""
+    if not isinstance(offered, list):
+        return []
+    agreed = [name for name in (supported_formats() if supported is None else supported) if name in offered]
+    return agreed if "audio-v1" in agreed else []
+
+
+def compression(config) -> Optional[str]:
+    """websockets' `compression` argument for the listeners (config.ws_compression)."""
+    return "deflate" if getattr(config, "ws_compression", True) else None
+
+
+def deflate_active(websocket) -> bool:
+    """Whether the handshake settled on permessage-deflate (old and new websockets APIs)."""
+    extensions = getattr(websocket, "extensions", None)
+    if extensions is None:
+        extensions = getattr(getattr(websocket, "protocol", None), "extensions", None) or []
+    return any(getattr(ext, "name", "") == "permessage-deflate" for ext in extensions)
+
+
+class BandwidthMeter:
+    """Bytes sent and received per listener, and what binary audio frames saved."""
+
+    def __init__(self, window: float = RATE_WINDOW, clock=time.monotonic):
+        self.window = window
+        self.clock = clock
+        self.totals: Dict[str, Dict[str, int]] = {}
+        self._recent: Deque[Tuple[float, int]] = deque()
+
+    def _channel(self, channel: str) -> Dict[str, int]:
+        return self.totals.setdefault(channel, {"sent": 0, "received": 0, "saved": 0, "messages": 0,
+                                                "connections": 0, "deflate": 0})
+
+    def connected(self, channel: str, deflate: bool) -> None:
+        totals = self._channel(channel)
+        totals["connections"] += 1
+        totals["deflate"] += int(deflate)
+
+    def record(self, channel: str, sent: int = 0, received: int = 0, saved: int = 0) -> None:
+        totals = self._channel(channel)
+        totals["sent"] += sent
+        totals["received"] += received
+        totals["saved"] += max(0, saved)
+        totals["messages"] += 1
+        now = self.clock()
+        self._recent.append((now, sent + received))
+        while self._recent and self._recent[0][0] < now - self.window:
+            self._recent.popleft()
+
+    def rate(self) -> float:
+        """Bytes per second over the last `window` seconds, all listeners."""
+        now = self.clock()
+        return sum(size for at, size in self._recent if at >= now - self.window) / self.window
+
+    def summary(self) -> Dict[str, Any]:
+        return {"rate": round(self.rate()), "channels": {name: dict(totals) for name, totals in self.totals.items()}}
+
+
+def _size(message) -> int:
+    return len(message.encode("utf-8")) if isinstance(message, str) else len(message)
+
+
+class FrameSession:
+    """One connection's framing: what it negotiated, the next sequence number, and its metering."""
+
+    def __init__(self, channel: str, websocket=None, meter: Optional[BandwidthMeter] = None):
+        self.channel = channel
+        self.meter = meter or get_bandwidth_meter()
+        self.formats: List[str] = []
+        self.sequence = 0
+        if websocket is not None:
+            self.meter.connected(channel, deflate_active(websocket))
+
+    @property
+    def audio(self) -> bool:
+        return "audio-v1" in self.formats
+
+    @property
+    def opus(self) -> bool:
+        return "opus" in self.formats
+
+    def accept(self, offered: Any, supported: Optional[Iterable[str]] = None) -> List[str]:
+        self.formats = negotiate(offered, supported)
+        return self.formats
+
+    def audio_message(self, codec: str, sample_rate: int, payload: bytes) -> bytes:
+        frame = AudioFrame(codec, sample_rate, self.sequence, payload)
+        self.sequence = (self.sequence + 1) & 0xFFFFFFFF
+        return frame.encode()
+
+    def sent(self, message, instead_of: int = 0) -> None:
+        """Count a message sent; `instead_of` is the size of the JSON a binary frame replaced."""
+        size = _size(message)
+        self.meter.record(self.channel, sent=size, saved=instead_of - size if instead_of else 0)
+
+    def received(self, message) -> None:
+        self.meter.record(self.channel, received=_size(message))
+
+
+_meter: Optional[BandwidthMeter] = None
+
+
+def get_bandwidth_meter() -> BandwidthMeter:
+    global _meter
+    if _meter is None:
+        _meter = BandwidthMeter()
+    return _meter
+
+
+def set_bandwidth_meter(meter: Optional[BandwidthMeter]) -> None:
+    global _meter
+    _meter = meter
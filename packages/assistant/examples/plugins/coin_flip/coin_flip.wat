@@ -0,0 +1,30 @@
+;; Coin flip - the sample xswarm skill plugin (ABI in assistant/skill_plugins.py).
+;; It doesn't need anything from the request: every intent or tool call flips once.
+;; It asks for no permissions, so it can log and draw a random number and nothing else.
+(module
+  (import "xswarm" "log" (func $log (param i32 i32)))
+  (import "xswarm" "random" (func $random (result i32)))
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 4096))
+
+  (data (i32.const 1024) "{\"say\": \"Heads.\"}")
+  (data (i32.const 1088) "{\"say\": \"Tails.\"}")
+  (data (i32.const 1152) "flipping a coin")
+
+  ;; Bump allocator for the request the host writes in; handle() starts it over
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (if (i32.gt_u (global.get $next) (i32.mul (memory.size) (i32.const 65536)))
+      (then (drop (memory.grow
+        (i32.sub (i32.add (i32.div_u (global.get $next) (i32.const 65536)) (i32.const 1)) (memory.size))))))
+    (local.get $ptr))
+
+  ;; Returns (ptr << 32 | len) of the response JSON
+  (func (export "handle") (param $ptr i32) (param $len i32) (result i64)
+    (global.set $next (i32.const 4096))
+    (call $log (i32.const 1152) (i32.const 15))
+    (if (result i64) (i32.and (call $random) (i32.const 1))
+      (then (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const 17)))
+      (else (i64.or (i64.shl (i64.const 1088) (i64.const 32)) (i64.const 17))))))
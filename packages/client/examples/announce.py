@@ -0,0 +1,26 @@
+#!/usr/bin/env python3
+"""
+Say something through the user's assistant, e.g. at the end of a CI job:
+
+    XSWARM_TOKEN=... XSWARM_USER=u_123 announce.py "Deploy finished" --key deploy-$BUILD_ID
+"""
+
+import argparse
+import os
+import sys
+
+from xswarm_client import ServerClient, ServerError
+
+parser = argparse.ArgumentParser(description=__doc__.splitlines()[1])
+parser.add_argument("text")
+parser.add_argument("--key", help="Idempotency key: a retried job won't announce twice")
+parser.add_argument("--priority", default="normal", choices=["low", "normal", "high", "emergency"])
+parser.add_argument("--server", default=os.environ.get("XSWARM_SERVER", "https://api.xswarm.ai"))
+args = parser.parse_args()
+
+client = ServerClient(args.server, os.environ.get("XSWARM_TOKEN"), os.environ.get("XSWARM_USER"))
+try:
+    command = client.speak(args.text, priority=args.priority, idempotency_key=args.key)
+except ServerError as e:
+    sys.exit(f"xSwarm: {e}")
+print(f"Queued {command.id} ({command.status})")
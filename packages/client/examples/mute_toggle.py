@@ -0,0 +1,12 @@
+#!/usr/bin/env python3
+"""Toggle the assistant's mic - bind it to a hotkey."""
+
+import sys
+
+from xswarm_client import ControlClient, ControlError
+
+try:
+    muted = ControlClient().mute()
+except ControlError as e:
+    sys.exit(f"xSwarm: {e}")
+print("Mic muted" if muted else "Mic on")
@@ -0,0 +1,40 @@
+"""
+xswarm-client - Script the xSwarm assistant from your own tools.
+
+Two ways in, neither needing the assistant's own package:
+
+- ControlClient talks to the assistant running on this machine over its
+  control socket (~/.config/xswarm/control.sock): status, mute, do not
+  disturb, the next appointment.
+- ServerClient talks to the xSwarm server: it queues commands for the
+  user's assistant wherever it runs (speak, refresh_config, call), reads
+  the calendar and inbox, and adds or changes appointments.
+
+xswarm_client.core (the "core" extra, which installs the assistant) adds
+its natural-language date parsing and memory search, for notebooks.
+
+Everything in __all__ (here and in core) is the stable API: it changes only
+with a new major version. Replies carry more fields than the models read,
+and new commands are added over time, so scripts should ignore what they
+don't know.
+"""
+
+from .control import AsyncControlClient, ControlClient, ControlError, socket_path
+from .models import Appointment, CalendarChange, Command, InboxItem, Status
+from .server import ServerClient, ServerError
+
+__version__ = "0.1.0"
+
+__all__ = [
+    "Appointment",
+    "AsyncControlClient",
+    "CalendarChange",
+    "Command",
+    "ControlClient",
+    "ControlError",
+    "InboxItem",
+    "ServerClient",
+    "ServerError",
+    "Status",
+    "socket_path",
+]
@@ -0,0 +1,149 @@
+"""
+Control - Drive the assistant running on this machine.
+
+The assistant's dashboard listens on a Unix socket (~/.config/xswarm/control.sock,
+owner-only) while it runs. The protocol is one JSON object per line each way:
+
+    {"command": "mute", "on": true}  -> {"ok": true, "message": "✓ Mic muted", "muted": true}
+    {"command": "nope"}              -> {"ok": false, "error": "Unknown command 'nope'"}
+
+ControlClient opens a connection per command and raises ControlError when
+the assistant isn't running, doesn't answer or refuses the command.
+AsyncControlClient is the same for asyncio code.
+"""
+
+import asyncio
+import json
+import socket
+from pathlib import Path
+from typing import Any, Dict, Optional
+
+from .models import Status
+
+TIMEOUT = 3.0  # Seconds to wait for the assistant
+
+Reply = Dict[str, Any]
+
+
+class ControlError(Exception):
+    """The assistant isn't running, didn't answer, or refused the command."""
+
+
+def socket_path() -> Path:
+    return Path.home() / ".config" / "xswarm" / "control.sock"
+
+
+def _request(command: str, args: Dict[str, Any]) -> bytes:
+    return (json.dumps({"command": command, **args}) + "\n").encode()
+
+
+def _reply(data: bytes) -> Reply:
+    try:
+        reply = json.loads(data)
+    except ValueError:
+        raise ControlError("No answer from xSwarm")
+    if not isinstance(reply, dict):
+        raise ControlError("No answer from xSwarm")
+    if not reply.get("ok"):
+        raise ControlError(reply.get("error") or "xSwarm refused the command")
+    return reply
+
+
+class ControlClient:
+    """Blocking client for the assistant's control socket."""
+
+    def __init__(self, path: Optional[Path] = None, timeout: float = TIMEOUT):
+        self.path = Path(path) if path else socket_path()
+        self.timeout = timeout
+
+    def send(self, command: str, **args) -> Reply:
+        """Send any command and return the reply (without "ok")."""
+        try:
+            with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as sock:
+                sock.settimeout(self.timeout)
+                sock.connect(str(self.path))
+                sock.sendall(_request(command, args))
+                data = b""
+                while not data.endswith(b"\n"):
+                    chunk = sock.recv(4096)
+                    if not chunk:
+                        break
+                    data += chunk
+        except (FileNotFoundError, ConnectionRefusedError):
+            raise ControlError("xSwarm isn't running")
+        except OSError as e:
+            raise ControlError(f"No answer from xSwarm: {e}")
+        reply = _reply(data)
+        reply.pop("ok")
+        return reply
+
+    def running(self) -> bool:
+        try:
+            self.send("status")
+        except ControlError:
+            return False
+        return True
+
+    def status(self) -> Status:
+        return Status.from_dict(self.send("status"))
+
+    def mute(self, on: Optional[bool] = None) -> bool:
+        """Mute (True) or unmute (False) the mic, or toggle it; returns whether it's now muted."""
+        return bool(self.send("mute", **({} if on is None else {"on": on}))["muted"])
+
+    def dnd(self, on: Optional[bool] = None) -> bool:
+        """Turn do not disturb on or off, or toggle it; returns whether it's now on."""
+        return bool(self.send("dnd", **({} if on is None else {"on": on}))["dnd"])
+
+    def next_appointment(self) -> str:
+        return str(self.send("next")["message"])
+
+    def show(self) -> str:
+        return str(self.send("show")["message"])
+
+    def quit(self) -> str:
+        return str(self.send("quit")["message"])
+
+
+class AsyncControlClient:
+    """asyncio client for the assistant's control socket."""
+
+    def __init__(self, path: Optional[Path] = None, timeout: float = TIMEOUT):
+        self.path = Path(path) if path else socket_path()
+        self.timeout = timeout
+
+    async def send(self, command: str, **args) -> Reply:
+        """Send any command and return the reply (without "ok")."""
+        try:
+            reader, writer = await asyncio.wait_for(asyncio.open_unix_connection(str(self.path)), self.timeout)
+        except (FileNotFoundError, ConnectionRefusedError):
+            raise ControlError("xSwarm isn't running")
+        except (OSError, asyncio.TimeoutError) as e:
+            raise ControlError(f"No answer from xSwarm: {e}")
+        try:
+            writer.write(_request(command, args))
+            await writer.drain()
+            data = await asyncio.wait_for(reader.readline(), self.timeout)
+        except (OSError, asyncio.TimeoutError) as e:
+            raise ControlError(f"No answer from xSwarm: {e}")
+        finally:
+            writer.close()
+            try:
+                await writer.wait_closed()
+            except OSError:
+                pass
+        reply = _reply(data)
+        reply.pop("ok")
+        return reply
+
+    async def status(self) -> Status:
+        return Status.from_dict(await self.send("status"))
+
+    async def mute(self, on: Optional[bool] = None) -> bool:
+        return bool((await self.send("mute", **({} if on is None else {"on": on})))["muted"])
+
+    async def dnd(self, on: Optional[bool] = None) -> bool:
+        return bool((await self.send("dnd", **({} if on is None else {"on": on})))["dnd"])
+
+    async def next_appointment(self) -> str:
+        return str((await self.send("next"))["message"])
@@ -0,0 +1,122 @@
+"""
+Core - The assistant's own date parsing and memory search, for notebooks.
+
+Needs the assistant package as well (pip install "xswarm-client[core]");
+importing this module without it raises ImportError saying so. The rest of
+xswarm_client works without it.
+
+    from xswarm_client import ServerClient
+    from xswarm_client.core import MemorySearch, parse_datetime, schedule
+
+    parse_datetime("friday at half past three")
+    schedule(ServerClient(url, token, user_id="u_123"), "Dentist", "tomorrow at 4pm", minutes=45)
+    MemorySearch().search("what did I say about the budget?")
+
+Dates are read exactly as the assistant reads them (dates.py): "next
+friday", "end of the quarter", "the 3rd of next month", "half past three".
+Memory search reads the assistant's memory database on this machine
+(~/.xswarm/memory/unified.db) with the same embeddings it uses.
+
+Everything here is synchronous and safe to call from Jupyter, where an
+event loop is already running. MemorySearch() raises MemoryStoreError when
+the memory database can't be opened; the assistant's errors all derive from
+XswarmError (see assistant/errors.py).
+"""
+
+import asyncio
+import concurrent.futures
+from dataclasses import dataclass, field
+from datetime import date, datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+try:
+    from assistant import dates
+    from assistant.errors import ConfigError, MemoryStoreError, XswarmError
+    from assistant.memory import Embedder, SemanticMemoryStore
+except ImportError as e:
+    raise ImportError('xswarm_client.core needs the assistant package: pip install "xswarm-client[core]"') from e
+
+from .models import Appointment
+from .server import ServerClient
+
+__all__ = ["ConfigError", "MemoryHit", "MemorySearch", "MemoryStoreError", "XswarmError", "parse_date",
+           "parse_datetime", "parse_time", "schedule"]
+
+
+def _run(coroutine):
+    """Run a coroutine to completion, on a worker thread if this thread already has a loop (Jupyter)."""
+    try:
+        asyncio.get_running_loop()
+    except RuntimeError:
+        return asyncio.run(coroutine)
+    with concurrent.futures.ThreadPoolExecutor(max_workers=1) as pool:
+        return pool.submit(asyncio.run, coroutine).result()
+
+
+def parse_date(text: str, today: Optional[date] = None, date_order: Optional[str] = None) -> Optional[date]:
+    """ "next friday", "end of the quarter", "3/14"... or None when it isn't a date.
+    date_order ("mdy"/"dmy") decides numeric dates; default the system locale's."""
+    return dates.parse_natural_date(text, today, date_order)
+
+
+def parse_time(text: str) -> Optional[str]:
+    """ "half past three", "4pm", "noon"... as 24h "HH:MM", or None."""
+    return dates.parse_time_expression(text)
+
+
+def parse_datetime(text: str, default_time: str = "09:00", today: Optional[date] = None,
+                   date_order: Optional[str] = None) -> Optional[datetime]:
+    """A date with an optional time, either way round ("at 4pm tomorrow"); None when the date isn't understood."""
+    return dates.parse_natural_datetime(text, default_time, today, date_order)
+
+
+def schedule(client: ServerClient, title: str, when: str, minutes: int = 30, today: Optional[date] = None,
+             **fields) -> Appointment:
+    """Add an appointment at a natural-language time ("tomorrow at 4pm"), `minutes` long, local time."""
+    start = parse_datetime(when, today=today)
+    if start is None:
+        raise ValueError(f"Couldn't read '{when}' as a date")
+    start = start.astimezone()  # The server stores UTC; keep the local offset
+    end = start + timedelta(minutes=minutes)
+    return client.create_appointment(title, start.isoformat(), end.isoformat(), **fields)
+
+
+@dataclass
+class MemoryHit:
+    id: int
+    content: str
+    role: str  # user or assistant
+    timestamp: str
+    similarity: float  # 1.0 is identical
+    session_id: Optional[str] = None
+    metadata: Dict[str, Any] = field(default_factory=dict)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "MemoryHit":
+        return cls(int(data["id"]), str(data.get("content", "")), str(data.get("role", "")),
+                   str(data.get("timestamp", "")), float(data.get("similarity", 0.0)), data.get("session_id"),
+                   dict(data.get("metadata") or {}))
+
+
+class MemorySearch:
+    """Semantic search over the assistant's memory on this machine."""
+
+    def __init__(self, storage_dir: Optional[Path] = None, embedder: Optional[Embedder] = None,
+                 store: Optional[SemanticMemoryStore] = None):
+        self.embedder = embedder or Embedder()
+        self.store = store or SemanticMemoryStore(storage_dir, embedding_dim=self.embedder.get_dimension())
+
+    def search(self, query: str, limit: int = 10, role: Optional[str] = None) -> List[MemoryHit]:
+        """The memories closest in meaning to `query`, best first (role: only "user" or "assistant" ones)."""
+        embedding = _run(self.embedder.embed(query))
+        return [MemoryHit.from_dict(row) for row in self.store.search(embedding, limit, role)]
+
+    def recent(self, limit: int = 50) -> List[MemoryHit]:
+        return [MemoryHit.from_dict({"similarity": 1.0, **row}) for row in self.store.get_recent(limit)]
+
+    def __len__(self) -> int:
+        return self.store.count()
+
+    def close(self) -> None:
+        self.store.close()
@@ -0,0 +1,112 @@
+"""
+Models - What the assistant and the server send back.
+
+Each model reads the fields it knows from a reply (from_dict) and ignores
+the rest, so newer assistants and servers stay compatible.
+"""
+
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional
+
+COMMAND_TYPES = ("speak", "refresh_config", "call")  # Commands the server can queue for the assistant
+COMMAND_STATUSES = ("pending", "done", "failed", "rejected", "expired")
+
+
+@dataclass
+class Status:
+    """The running assistant (control socket "status")."""
+    mic: str  # off, wake_word or listening
+    muted: bool
+    voice: bool  # Voice is running, not just the text UI
+    dnd: bool  # Do not disturb
+    next: str  # "Dentist · today 16:00"
+    egress: List[str] = field(default_factory=list)  # Where mic audio is going
+    message: str = ""
+    latency: Dict[str, Any] = field(default_factory=dict)  # AI reply times per model, fillers, fallbacks
+    states: Dict[str, str] = field(default_factory=dict)  # Subsystem states, e.g. {"conversation": "speaking"}
+    power: str = "performance"  # Performance profile: performance or low_power (on battery / hot)
+    bandwidth: Dict[str, Any] = field(default_factory=dict)  # WebSocket bytes per listener and the rate (B/s)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Status":
+        return cls(str(data.get("mic", "off")), bool(data.get("muted")), bool(data.get("voice")),
+                   bool(data.get("dnd")), str(data.get("next", "")), list(data.get("egress") or []),
+                   str(data.get("message", "")), dict(data.get("latency") or {}), dict(data.get("states") or {}),
+                   str(data.get("power", "performance")), dict(data.get("bandwidth") or {}))
+
+
+@dataclass
+class Command:
+    """A command queued for the user's assistant (POST /api/commands)."""
+    id: str
+    type: str
+    args: Dict[str, Any]
+    idempotency_key: str
+    status: str  # See COMMAND_STATUSES
+    result: Optional[str] = None  # What the assistant said when it acknowledged it
+    created_at: str = ""
+    expires_at: str = ""
+    acked_at: Optional[str] = None
+
+    @property
+    def pending(self) -> bool:
+        return self.status == "pending"
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Command":
+        return cls(str(data["id"]), str(data["type"]), dict(data.get("args") or {}),
+                   str(data.get("idempotency_key", "")), str(data.get("status", "pending")), data.get("result"),
+                   str(data.get("created_at", "")), str(data.get("expires_at", "")), data.get("acked_at"))
+
+
+@dataclass
+class Appointment:
+    """A server calendar appointment (times are ISO 8601, UTC)."""
+    id: str
+    title: str
+    start_time: str
+    end_time: str
+    description: str = ""
+    location: str = ""
+    status: str = "scheduled"
+    participants: List[str] = field(default_factory=list)
+    tags: List[str] = field(default_factory=list)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Appointment":
+        return cls(str(data["id"]), str(data.get("title") or ""), str(data.get("start_time") or ""),
+                   str(data.get("end_time") or data.get("start_time") or ""), str(data.get("description") or ""),
+                   str(data.get("location") or ""), str(data.get("status") or "scheduled"),
+                   list(data.get("participants") or []), list(data.get("tags") or []))
+
+
+@dataclass
+class CalendarChange:
+    """One entry of the calendar change feed (GET /api/calendar/changes)."""
+    seq: int  # Position in the change journal
+    entity: str  # appointment or reminder
+    id: str
+    op: str  # upsert or delete
+    data: Optional[Dict[str, Any]] = None  # The row, for upserts
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "CalendarChange":
+        return cls(int(data.get("seq", 0)), str(data["entity"]), str(data["id"]), str(data["op"]), data.get("data"))
+
+
+@dataclass
+class InboxItem:
+    """A message in the unified inbox (GET /api/inbox)."""
+    id: str
+    channel: str  # sms, email or voice
+    sender: str
+    content: str
+    status: str  # unread, read, replied or archived
+    subject: Optional[str] = None
+    received_at: str = ""
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "InboxItem":
+        return cls(str(data["id"]), str(data.get("channel", "")), str(data.get("sender", "")),
+                   str(data.get("content", "")), str(data.get("status", "unread")), data.get("subject"),
+                   str(data.get("received_at", "")))
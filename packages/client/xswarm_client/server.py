@@ -0,0 +1,157 @@
+"""
+Server - Queue commands for the assistant and read what the server knows.
+
+ServerClient wraps the xSwarm server's HTTP API with the standard library
+(urllib), so it works from cron jobs, CI and git hooks:
+
+    client = ServerClient("https://api.xswarm.ai", token, user_id="u_123")
+    client.speak("Deploy finished", idempotency_key=f"deploy-{build_id}")
+
+Queued commands reach the user's assistant wherever it runs; it only runs
+the types its config.remote_commands allows and acknowledges each one.
+Reusing an idempotency key returns the command already queued instead of
+queueing another, so retrying a script is safe.
+
+Errors raise ServerError with the HTTP status (0 when the server couldn't
+be reached) and the server's error message.
+"""
+
+import json
+from typing import Any, Dict, List, Optional, Tuple
+from urllib.error import HTTPError, URLError
+from urllib.parse import urlencode
+from urllib.request import Request, urlopen
+
+from .models import COMMAND_TYPES, Appointment, CalendarChange, Command, InboxItem
+
+TIMEOUT = 10.0
+
+
+class ServerError(Exception):
+    def __init__(self, status: int, message: str):
+        super().__init__(f"{message} (HTTP {status})" if status else message)
+        self.status = status
+        self.message = message
+
+
+class ServerClient:
+    """Blocking client for the xSwarm server API."""
+
+    def __init__(self, server_url: str, token: Optional[str] = None, user_id: Optional[str] = None,
+                 timeout: float = TIMEOUT, opener=urlopen):
+        self.server_url = server_url.rstrip("/")
+        self.token = token
+        self.user_id = user_id
+        self.timeout = timeout
+        self._open = opener
+
+    def _user(self) -> str:
+        if not self.user_id:
+            raise ValueError("ServerClient needs a user_id for this call")
+        return self.user_id
+
+    def request(self, method: str, path: str, params: Optional[Dict[str, Any]] = None,
+                body: Optional[Dict[str, Any]] = None, headers: Optional[Dict[str, str]] = None) -> Dict[str, Any]:
+        """Call any endpoint and return its JSON body."""
+        url = self.server_url + path
+        params = {key: value for key, value in (params or {}).items() if value is not None}
+        if params:
+            url += "?" + urlencode(params)
+        request = Request(url, method=method, headers={"Accept": "application/json", **(headers or {})})
+        if self.token:
+            request.add_header("Authorization", f"Bearer {self.token}")
+        data = None
+        if body is not None:
+            data = json.dumps(body).encode()
+            request.add_header("Content-Type", "application/json")
+        try:
+            with self._open(request, data, timeout=self.timeout) as response:
+                raw = response.read()
+        except HTTPError as e:
+            try:
+                message = json.loads(e.read()).get("error") or e.reason
+            except (ValueError, AttributeError):
+                message = str(e.reason)
+            raise ServerError(e.code, str(message))
+        except (URLError, OSError) as e:
+            raise ServerError(0, f"Can't reach {self.server_url}: {getattr(e, 'reason', e)}")
+        try:
+            return json.loads(raw) if raw else {}
+        except ValueError:
+            raise ServerError(0, f"{path} didn't return JSON")
+
+    # Commands for the assistant
+
+    def queue_command(self, type: str, args: Optional[Dict[str, Any]] = None, idempotency_key: Optional[str] = None,
+                      ttl_seconds: Optional[int] = None) -> Tuple[Command, bool]:
+        """Queue a command; returns it and whether it was already queued under this key."""
+        if type not in COMMAND_TYPES:
+            raise ValueError(f"Unknown command type '{type}'. Expected one of: {', '.join(COMMAND_TYPES)}")
+        body: Dict[str, Any] = {"type": type, "args": args or {}}
+        if idempotency_key:
+            body["idempotency_key"] = idempotency_key
+        if ttl_seconds:
+            body["ttl_seconds"] = ttl_seconds
+        reply = self.request("POST", "/api/commands", body=body)
+        return Command.from_dict(reply["command"]), bool(reply.get("duplicate"))
+
+    def speak(self, text: str, priority: str = "normal", idempotency_key: Optional[str] = None,
+              ttl_seconds: Optional[int] = None) -> Command:
+        """Have the assistant say `text` out loud."""
+        return self.queue_command("speak", {"text": text, "priority": priority}, idempotency_key, ttl_seconds)[0]
+
+    def refresh_config(self, idempotency_key: Optional[str] = None) -> Command:
+        """Have the assistant re-read its config file."""
+        return self.queue_command("refresh_config", {}, idempotency_key)[0]
+
+    def call(self, message: str, priority: str = "high", idempotency_key: Optional[str] = None) -> Command:
+        """Have the assistant phone the user (only if its config.remote_commands allows calls)."""
+        return self.queue_command("call", {"message": message, "priority": priority}, idempotency_key)[0]
+
+    def pending_commands(self, limit: int = 20) -> List[Command]:
+        reply = self.request("GET", "/api/commands", params={"limit": limit})
+        return [Command.from_dict(raw) for raw in reply.get("commands", [])]
+
+    # Calendar and inbox
+
+    def appointments(self, start: Optional[str] = None, end: Optional[str] = None,
+                     tag: Optional[str] = None) -> List[Appointment]:
+        """Scheduled appointments, optionally between two ISO 8601 times."""
+        reply = self.request("GET", "/api/calendar/appointments",
+                             params={"user_id": self._user(), "start": start, "end": end, "tag": tag})
+        return [Appointment.from_dict(raw) for raw in reply.get("appointments", [])]
+
+    def create_appointment(self, title: str, start_time: str, end_time: str, **fields) -> Appointment:
+        """Add an appointment (ISO 8601 times; fields: description, location, participants, tags, timezone...).
+        Raises ServerError 409 when it overlaps a scheduled one."""
+        reply = self.request("POST", "/api/calendar/appointments",
+                             body={"user_id": self._user(), "title": title, "start_time": start_time,
+                                   "end_time": end_time, **fields})
+        return Appointment.from_dict(reply["appointment"])
+
+    def update_appointment(self, appointment_id: str, **fields) -> Appointment:
+        """Change an appointment's fields (status="cancelled" cancels it)."""
+        reply = self.request("PUT", f"/api/calendar/appointments/{appointment_id}", body=fields)
+        return Appointment.from_dict(reply["appointment"])
+
+    def delete_appointment(self, appointment_id: str) -> None:
+        self.request("DELETE", f"/api/calendar/appointments/{appointment_id}")
+
+    def calendar_changes(self, since: int = 0) -> Tuple[List[CalendarChange], int]:
+        """Every calendar change after cursor `since`, following pages; returns them and the new cursor."""
+        changes: List[CalendarChange] = []
+        while True:
+            reply = self.request("GET", "/api/calendar/changes", params={"user_id": self._user(), "since": since})
+            if reply.get("reset"):
+                changes, since = [], 0
+                continue
+            changes.extend(CalendarChange.from_dict(raw) for raw in reply.get("changes", []))
+            since = int(reply.get("cursor", since))
+            if not reply.get("has_more"):
+                return changes, since
+
+    def inbox(self, status: Optional[str] = None, channel: Optional[str] = None,
+              limit: int = 100) -> List[InboxItem]:
+        reply = self.request("GET", "/api/inbox",
+                             params={"status": status, "channel": channel, "limit": limit})
+        return [InboxItem.from_dict(raw) for raw in reply.get("items", [])]
@@ -0,0 +1,51 @@
+/**
+ * Appointment Locations Database Migration
+ *
+ * Adds `latitude` and `longitude` columns to appointments. The desktop
+ * assistant geocodes event locations and sends the coordinates with calendar
+ * sync; they give invitation emails and API responses a map link.
+ * Run with: node scripts/migrate-appointment-locations.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting appointment locations migration...');
+
+  try {
+    for (const column of ['latitude', 'longitude']) {
+      try {
+        await db.execute(`ALTER TABLE appointments ADD COLUMN ${column} REAL`);
+        console.log(`Added ${column} column to appointments`);
+      } catch (error) {
+        if (error.message.includes('duplicate column')) {
+          console.log(`appointments.${column} column already exists`);
+        } else {
+          throw error;
+        }
+      }
+    }
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
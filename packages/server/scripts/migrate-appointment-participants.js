@@ -0,0 +1,62 @@
+/**
+ * Appointment Participants Database Migration
+ *
+ * Creates the appointment_participants table (invitations and RSVPs).
+ * Run with: node scripts/migrate-appointment-participants.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting appointment participants migration...');
+
+  try {
+    // label is the participant entry as the client sent it (name, email or phone)
+    console.log('Creating appointment_participants table...');
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS appointment_participants (
+        id TEXT PRIMARY KEY,
+        appointment_id TEXT NOT NULL REFERENCES appointments(id) ON DELETE CASCADE,
+        label TEXT NOT NULL,
+        name TEXT,
+        email TEXT,
+        phone TEXT,
+        rsvp_status TEXT NOT NULL DEFAULT 'pending'
+          CHECK (rsvp_status IN ('pending', 'accepted', 'declined', 'tentative')),
+        rsvp_token TEXT NOT NULL UNIQUE,
+        invited_at TEXT,
+        invited_via TEXT CHECK (invited_via IN ('email', 'sms')),
+        responded_at TEXT,
+        created_at TEXT NOT NULL,
+        UNIQUE (appointment_id, label)
+      )
+    `);
+
+    await db.execute(`
+      CREATE INDEX IF NOT EXISTS idx_appointment_participants_appointment
+      ON appointment_participants(appointment_id)
+    `);
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -0,0 +1,86 @@
+/**
+ * Calendar Change Journal Migration
+ *
+ * Adds `calendar_changes`, an append-only journal of appointment and
+ * reminder writes that GET /api/calendar/changes serves deltas from. Each
+ * row gets an increasing `seq`; clients keep the last seq they applied as
+ * their cursor. Triggers fill it on every insert, update and delete, so all
+ * write paths (single, batch, natural-language, integrations) are covered.
+ * Existing rows are journaled once so a first sync from cursor 0 sees them.
+ * Run with: node scripts/migrate-calendar-changes.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+const ENTITIES = { appointments: 'appointment', reminders: 'reminder' };
+
+function triggers(table, entity) {
+  const journal = (op, row) => `INSERT INTO calendar_changes (user_id, entity, entity_id, op)
+      VALUES (${row}.user_id, '${entity}', ${row}.id, '${op}');`;
+  return [
+    `CREATE TRIGGER IF NOT EXISTS ${table}_journal_insert AFTER INSERT ON ${table}
+     BEGIN ${journal('upsert', 'NEW')} END`,
+    `CREATE TRIGGER IF NOT EXISTS ${table}_journal_update AFTER UPDATE ON ${table}
+     BEGIN ${journal('upsert', 'NEW')} END`,
+    `CREATE TRIGGER IF NOT EXISTS ${table}_journal_delete AFTER DELETE ON ${table}
+     BEGIN ${journal('delete', 'OLD')} END`,
+  ];
+}
+
+async function migrate() {
+  console.log('Starting calendar change journal migration...');
+
+  try {
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS calendar_changes (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        user_id TEXT NOT NULL,
+        entity TEXT NOT NULL CHECK (entity IN ('appointment', 'reminder')),
+        entity_id TEXT NOT NULL,
+        op TEXT NOT NULL CHECK (op IN ('upsert', 'delete')),
+        changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+      )
+    `);
+    await db.execute('CREATE INDEX IF NOT EXISTS idx_calendar_changes_user_seq ON calendar_changes(user_id, seq)');
+    console.log('Created calendar_changes table');
+
+    const { rows } = await db.execute('SELECT COUNT(*) AS count FROM calendar_changes');
+    const seed = Number(rows[0].count) === 0;
+
+    for (const [table, entity] of Object.entries(ENTITIES)) {
+      for (const sql of triggers(table, entity)) {
+        await db.execute(sql);
+      }
+      console.log(`Added journal triggers to ${table}`);
+      if (seed) {
+        const result = await db.execute(
+          `INSERT INTO calendar_changes (user_id, entity, entity_id, op)
+           SELECT user_id, '${entity}', id, 'upsert' FROM ${table}`
+        );
+        console.log(`Journaled ${result.rowsAffected} existing ${table}`);
+      }
+    }
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
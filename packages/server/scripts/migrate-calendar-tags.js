@@ -0,0 +1,50 @@
+/**
+ * Calendar Tags Database Migration
+ *
+ * Adds a `tags` column (JSON array, e.g. ["work"]) to appointments and
+ * reminders so they can be categorized and filtered with ?tag=.
+ * Run with: node scripts/migrate-calendar-tags.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting calendar tags migration...');
+
+  try {
+    for (const table of ['appointments', 'reminders']) {
+      try {
+        await db.execute(`ALTER TABLE ${table} ADD COLUMN tags TEXT NOT NULL DEFAULT '[]'`);
+        console.log(`Added tags column to ${table}`);
+      } catch (error) {
+        if (error.message.includes('duplicate column')) {
+          console.log(`${table}.tags column already exists`);
+        } else {
+          throw error;
+        }
+      }
+    }
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -0,0 +1,83 @@
+/**
+ * Call Screening Database Migration
+ *
+ * Creates the screened_calls and voicemails tables.
+ * Run with: node scripts/migrate-call-screening.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting call screening migration...');
+
+  try {
+    // Create screened_calls table (id is the Twilio CallSid)
+    console.log('Creating screened_calls table...');
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS screened_calls (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        caller TEXT NOT NULL,
+        caller_name TEXT,
+        purpose TEXT NOT NULL DEFAULT '',
+        status TEXT NOT NULL DEFAULT 'screening'
+          CHECK (status IN ('screening', 'waiting', 'accepted', 'declined', 'voicemail', 'ended')),
+        decline_message TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+      )
+    `);
+
+    await db.execute(`
+      CREATE INDEX IF NOT EXISTS idx_screened_calls_user_status
+      ON screened_calls(user_id, status)
+    `);
+
+    // Create voicemails table
+    console.log('Creating voicemails table...');
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS voicemails (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        call_sid TEXT NOT NULL,
+        caller TEXT NOT NULL,
+        recording_url TEXT,
+        duration INTEGER NOT NULL DEFAULT 0,
+        transcript TEXT,
+        created_at TEXT NOT NULL
+      )
+    `);
+
+    await db.execute(`
+      CREATE INDEX IF NOT EXISTS idx_voicemails_user_created
+      ON voicemails(user_id, created_at DESC)
+    `);
+
+    await db.execute(`
+      CREATE INDEX IF NOT EXISTS idx_voicemails_call_sid
+      ON voicemails(call_sid)
+    `);
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -0,0 +1,62 @@
+/**
+ * Client Commands Migration
+ *
+ * Adds `client_commands`, the queue of actions the server asks a user's
+ * local assistant to take (speak an announcement, reload its config, place
+ * a call). The assistant fetches pending commands and acknowledges each
+ * one with done, failed or rejected (its local policy didn't allow it).
+ * (user_id, idempotency_key) is unique so a retried request doesn't queue
+ * the same command twice.
+ * Run with: node scripts/migrate-client-commands.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting client commands migration...');
+
+  try {
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS client_commands (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        type TEXT NOT NULL,
+        args TEXT NOT NULL DEFAULT '{}',
+        idempotency_key TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'pending'
+          CHECK (status IN ('pending', 'done', 'failed', 'rejected', 'expired')),
+        result TEXT,
+        created_at TEXT NOT NULL DEFAULT (datetime('now')),
+        expires_at TEXT NOT NULL,
+        acked_at TEXT,
+        UNIQUE (user_id, idempotency_key)
+      )
+    `);
+    await db.execute(
+      'CREATE INDEX IF NOT EXISTS idx_client_commands_user_status ON client_commands(user_id, status, created_at)'
+    );
+    console.log('Created client_commands table');
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -0,0 +1,72 @@
+/**
+ * Emergency Alerts Migration
+ *
+ * Adds `emergency_alerts`: alerts the local assistant raised when the user
+ * said their emergency phrase. Each alert keeps the contacts it texts and
+ * calls, how many rounds went out, and who acknowledged it (a contact
+ * replying OK or pressing 1 on the call) or whether the user stood it down.
+ * And `emergency_contacts`: the only numbers an alert may text or call,
+ * saved by the user's assistant.
+ * Run with: node scripts/migrate-emergency.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting emergency alerts migration...');
+
+  try {
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS emergency_alerts (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        message TEXT NOT NULL,
+        contacts TEXT NOT NULL,
+        methods TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'active',
+        rounds INTEGER NOT NULL DEFAULT 0,
+        acknowledged_by TEXT,
+        created_at TEXT NOT NULL DEFAULT (datetime('now')),
+        last_sent_at TEXT,
+        resolved_at TEXT
+      )
+    `);
+    await db.execute(
+      'CREATE INDEX IF NOT EXISTS idx_emergency_alerts_user_status ON emergency_alerts(user_id, status)'
+    );
+    console.log('Created emergency_alerts table');
+
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS emergency_contacts (
+        user_id TEXT NOT NULL,
+        phone TEXT NOT NULL,
+        name TEXT NOT NULL DEFAULT '',
+        position INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (user_id, phone)
+      )
+    `);
+    console.log('Created emergency_contacts table');
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -0,0 +1,49 @@
+/**
+ * Feature Flags Migration
+ *
+ * Adds `feature_flags`, the server's overrides of the local assistant's
+ * feature flags. user_id '*' applies to every user; a user's own row
+ * wins over it. `enabled` is 0 or 1.
+ * Run with: node scripts/migrate-flags.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting feature flags migration...');
+
+  try {
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS feature_flags (
+        user_id TEXT NOT NULL,
+        flag TEXT NOT NULL,
+        enabled INTEGER NOT NULL,
+        updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+        PRIMARY KEY (user_id, flag)
+      )
+    `);
+    console.log('Created feature_flags table');
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
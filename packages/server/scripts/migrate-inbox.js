@@ -0,0 +1,69 @@
+/**
+ * Unified Inbox Database Migration
+ *
+ * Creates the inbox_items table that aggregates inbound SMS, email and voice.
+ * Run with: node scripts/migrate-inbox.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting inbox migration...');
+
+  try {
+    // Create inbox_items table
+    console.log('Creating inbox_items table...');
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS inbox_items (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        channel TEXT NOT NULL CHECK (channel IN ('sms', 'email', 'voice')),
+        sender TEXT NOT NULL,
+        subject TEXT,
+        content TEXT NOT NULL,
+        external_id TEXT,
+        status TEXT NOT NULL DEFAULT 'unread'
+          CHECK (status IN ('unread', 'read', 'replied', 'archived')),
+        reply_text TEXT,
+        received_at TEXT NOT NULL,
+        replied_at TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT
+      )
+    `);
+
+    // Create indexes for inbox_items
+    console.log('Creating indexes for inbox_items...');
+    await db.execute(`
+      CREATE INDEX IF NOT EXISTS idx_inbox_user_received
+      ON inbox_items(user_id, received_at DESC)
+    `);
+
+    await db.execute(`
+      CREATE INDEX IF NOT EXISTS idx_inbox_user_status
+      ON inbox_items(user_id, status)
+    `);
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -0,0 +1,58 @@
+/**
+ * Lists Migration
+ *
+ * Adds `list_items`, the items on a user's named lists (shopping, todo,
+ * packing...) that the local assistant keeps and syncs. Items are never
+ * hard-deleted: removing one sets `deleted` so other devices see it go.
+ * `updated_at` is set by the device that made the change; the newest
+ * change to an item wins.
+ * Run with: node scripts/migrate-lists.js
+ */
+
+import { createClient } from '@libsql/client';
+import * as dotenv from 'dotenv';
+import { fileURLToPath } from 'url';
+import { dirname, join } from 'path';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// Load .env from project root
+dotenv.config({ path: join(__dirname, '../../../.env') });
+
+const db = createClient({
+  url: process.env.TURSO_DATABASE_URL,
+  authToken: process.env.TURSO_AUTH_TOKEN,
+});
+
+async function migrate() {
+  console.log('Starting lists migration...');
+
+  try {
+    await db.execute(`
+      CREATE TABLE IF NOT EXISTS list_items (
+        id TEXT NOT NULL,
+        user_id TEXT NOT NULL,
+        list_name TEXT NOT NULL,
+        text TEXT NOT NULL,
+        done INTEGER NOT NULL DEFAULT 0,
+        deleted INTEGER NOT NULL DEFAULT 0,
+        updated_at TEXT NOT NULL,
+        synced_at TEXT NOT NULL DEFAULT (datetime('now')),
+        PRIMARY KEY (user_id, id)
+      )
+    `);
+    await db.execute(
+      'CREATE INDEX IF NOT EXISTS idx_list_items_user_synced ON list_items(user_id, synced_at)'
+    );
+    console.log('Created list_items table');
+
+    console.log('Migration completed successfully!');
+
+  } catch (error) {
+    console.error('Migration failed:', error);
+    process.exit(1);
+  }
+}
+
+migrate();
@@ -89,6 +89,12 @@ const DEFAULT_CONFIG = {
       moshi_model: 'kyutai/moshika-mlx-q4',
     },
   },
+  claude_code: {
+    session_budget_usd: 5,
+    daily_budget_usd: 20,
+    warn_at: 0.8,
+    projects: {}, // project path -> { session_budget_usd, daily_budget_usd }
+  },
   voice: {
     default_persona: 'boss',
     default_wake_word: 'hey boss',
@@ -125,6 +131,15 @@ export async function loadConfig(env) {
   if (env.TURSO_DATABASE_URL) {
     config.turso.database_url = env.TURSO_DATABASE_URL;
   }
+  if (env.CLAUDE_CODE_SESSION_BUDGET_USD) {
+    config.claude_code.session_budget_usd = Number(env.CLAUDE_CODE_SESSION_BUDGET_USD);
+  }
+  if (env.CLAUDE_CODE_DAILY_BUDGET_USD) {
+    config.claude_code.daily_budget_usd = Number(env.CLAUDE_CODE_DAILY_BUDGET_USD);
+  }
+  if (env.CLAUDE_CODE_PROJECT_BUDGETS) {
+    config.claude_code.projects = JSON.parse(env.CLAUDE_CODE_PROJECT_BUDGETS);
+  }
 
   // TODO: Load from R2 bucket in production
   // if (env.CONFIG_BUCKET) {
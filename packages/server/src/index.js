@@ -24,7 +24,10 @@ import {
   handleEmailDisconnect,
 } from './routes/email-management.js';
 import { handleMoshiWebSocket } from './routes/moshi-proxy.js';
-import { handleGetIdentity, handleAuthValidate } from './routes/identity.js';
+import { capRequestBody, checkRateLimit } from './middleware/rate-limit.js';
+import { checkWebhookSignature, webhookMetrics } from './middleware/webhook-signature.js';
+import { handleGetIdentity, handleAuthValidate, handleReportUsage } from './routes/identity.js';
+import { createSession, sendMessage, disconnectSession, approveSession, getSessionCost } from './routes/claude-code.js';
 import { handleSignup } from './routes/auth/signup.js';
 import { handleVerifyEmail } from './routes/auth/verify-email.js';
 import { handleLogin } from './routes/auth/login.js';
@@ -49,13 +52,50 @@ import {
   getAppointments,
   getTodaySchedule,
   getWeekSchedule,
+  getCalendarChanges,
   updateAppointment,
   deleteAppointment,
   createReminder,
   getReminders,
   updateReminder,
   scheduleNaturalLanguage,
+  createAppointmentsBatch,
+  deleteAppointmentsBatch,
+  updateRemindersBatch,
+  getAppointmentParticipants,
+  inviteAppointmentParticipants,
 } from './routes/calendar.js';
+import { subscribeToCalendar } from './lib/calendar-hub.js';
+import { createCommand, getCommands, acknowledgeCommand } from './routes/commands.js';
+import { getLists, putListItems } from './routes/lists.js';
+import { getFlags } from './routes/flags.js';
+import { createCrashReport } from './routes/crash-reports.js';
+import { pushSyncBlob, pullSyncBlobs } from './routes/sync.js';
+import {
+  getEmergencyContactList,
+  putEmergencyContacts,
+  createEmergencyAlert,
+  getEmergencyAlert,
+  repeatEmergencyAlert,
+  cancelEmergencyAlert,
+  handleEmergencyTwiml,
+  handleEmergencyAnswer,
+} from './routes/emergency.js';
+import { getLatestRelease, getReleaseAsset } from './routes/releases.js';
+import { handleRsvp } from './routes/rsvp.js';
+import { deleteIcsFeed, publishIcsFeed, serveIcsFeed } from './routes/ics-feed.js';
+import { getInbox, updateInbox, draftInboxReply, sendInboxReply } from './routes/inbox.js';
+import {
+  handleScreeningPartial,
+  handleScreeningPurpose,
+  handleScreeningWait,
+  handleScreeningDialed,
+  handleVoicemailRecorded,
+  handleVoicemailTranscribed,
+  getActiveScreenings,
+  decideScreenedCall,
+  getVoicemails,
+} from './routes/call-screening.js';
 import {
   createProject,
   listProjects,
@@ -143,6 +183,9 @@ import {
   handleGetTrainingStatus,
 } from './routes/personas.js';
 
+// Durable Object classes must be exported from the main module
+export { CalendarHub } from './lib/calendar-hub.js';
+
 export default {
   async fetch(request, env, ctx) {
     const url = new URL(request.url);
@@ -165,11 +208,29 @@ export default {
         status: 'ok',
         service: 'xswarm-webhooks',
         timestamp: new Date().toISOString(),
+        webhooks: webhookMetrics(),
       }), {
         headers: { 'Content-Type': 'application/json' },
       });
     }
 
+    // Per-client rate limits and payload caps on webhook/auth endpoints
+    const limited = checkRateLimit(request, path);
+    if (limited) {
+      return limited;
+    }
+    const capped = await capRequestBody(request, path);
+    if (capped instanceof Response) {
+      return capped;
+    }
+    request = capped;
+
+    // Signed by Twilio or with WEBHOOK_SIGNING_SECRETS, within the timestamp window, not replayed
+    const unsigned = await checkWebhookSignature(request, path, env);
+    if (unsigned) {
+      return unsigned;
+    }
+
     try {
       // Authentication Routes
       if (path === '/auth/signup' && request.method === 'POST') {
@@ -239,6 +300,26 @@ export default {
       if (path === '/api/auth/validate' && request.method === 'POST') {
         return await handleAuthValidate(request, env);
       }
+      if (path === '/api/usage' && request.method === 'POST') {
+        return await handleReportUsage(request, env);
+      }
+
+      // Claude Code sessions via the supervisor, with cost budgets
+      if (path === '/api/claude-code/sessions' && request.method === 'POST') {
+        return await createSession(request, env);
+      }
+      if (path.match(/^\/api\/claude-code\/sessions\/[^/]+\/messages$/) && request.method === 'POST') {
+        return await sendMessage(request, env);
+      }
+      if (path.match(/^\/api\/claude-code\/sessions\/[^/]+\/approve$/) && request.method === 'POST') {
+        return await approveSession(request, env);
+      }
+      if (path.match(/^\/api\/claude-code\/sessions\/[^/]+\/cost$/) && request.method === 'GET') {
+        return await getSessionCost(request, env);
+      }
+      if (path.match(/^\/api\/claude-code\/sessions\/[^/]+$/) && request.method === 'DELETE') {
+        return await disconnectSession(request, env);
+      }
 
       // MOSHI WebSocket proxy route (must be before other /voice/* routes)
       if (path === '/voice/moshi') {
@@ -300,6 +381,46 @@ export default {
         return await handleInboundCall(request, env);
       }
 
+      // Call screening webhooks (unknown callers to the Boss number)
+      if (path === '/voice/screen/partial' && request.method === 'POST') {
+        return await handleScreeningPartial(request, env);
+      }
+      if (path === '/voice/screen/purpose' && request.method === 'POST') {
+        return await handleScreeningPurpose(request, env);
+      }
+      if (path === '/voice/screen/wait' && request.method === 'POST') {
+        return await handleScreeningWait(request, env);
+      }
+      if (path === '/voice/screen/dialed' && request.method === 'POST') {
+        return await handleScreeningDialed(request, env);
+      }
+      if (path === '/voice/screen/recorded' && request.method === 'POST') {
+        return await handleVoicemailRecorded(request, env);
+      }
+      if (path === '/voice/screen/transcribed' && request.method === 'POST') {
+        return await handleVoicemailTranscribed(request, env);
+      }
+
+      // Emergency alert calls to the user's contacts
+      if (path.match(/^\/emergency\/[^/]+\/twiml$/) && request.method === 'POST') {
+        return await handleEmergencyTwiml(request, env, path.split('/')[2]);
+      }
+      if (path.match(/^\/emergency\/[^/]+\/answer$/) && request.method === 'POST') {
+        return await handleEmergencyAnswer(request, env, path.split('/')[2]);
+      }
+
+      // Call screening and voicemail API
+      if (path === '/api/calls/screening' && request.method === 'GET') {
+        return await getActiveScreenings(request, env);
+      }
+      if (path.match(/^\/api\/calls\/[^/]+\/screen$/) && request.method === 'POST') {
+        const callSid = path.split('/')[3];
+        return await decideScreenedCall(request, env, callSid);
+      }
+      if (path === '/api/voicemail' && request.method === 'GET') {
+        return await getVoicemails(request, env);
+      }
+
       // Email routes
       // Inbound email handler (SendGrid Parse webhook)
       // Using unified message layer
@@ -460,6 +581,18 @@ export default {
         }
       }
 
+      // Appointment invitation RSVP links (public)
+      if (path.match(/^\/rsvp\/[^/]+$/) && request.method === 'GET') {
+        const token = path.split('/')[2];
+        return await handleRsvp(request, env, token);
+      }
+
+      // Calendar feed of xswarm's events for calendar apps (public: the token is the secret)
+      if (path.match(/^\/ics\/[^/]+\.ics$/) && request.method === 'GET') {
+        const token = path.split('/')[2].slice(0, -'.ics'.length);
+        return await serveIcsFeed(request, env, token);
+      }
+
       // Marketing Email Routes
       if (path === '/marketing/enroll' && request.method === 'POST') {
         return await handleEnroll(request, env);
@@ -559,12 +692,26 @@ export default {
       }
 
       // Appointments
+      if (path === '/api/calendar/appointments/batch' && request.method === 'POST') {
+        return await createAppointmentsBatch(request, env);
+      }
+      if (path === '/api/calendar/appointments/batch-delete' && request.method === 'POST') {
+        return await deleteAppointmentsBatch(request, env);
+      }
       if (path === '/api/calendar/appointments' && request.method === 'POST') {
         return await createAppointment(request, env);
       }
       if (path === '/api/calendar/appointments' && request.method === 'GET') {
         return await getAppointments(request, env);
       }
+      if (path.match(/^\/api\/calendar\/appointments\/[^/]+\/participants$/) && request.method === 'GET') {
+        const appointmentId = path.split('/')[4];
+        return await getAppointmentParticipants(request, env, appointmentId);
+      }
+      if (path.match(/^\/api\/calendar\/appointments\/[^/]+\/invitations$/) && request.method === 'POST') {
+        const appointmentId = path.split('/')[4];
+        return await inviteAppointmentParticipants(request, env, appointmentId);
+      }
       if (path.startsWith('/api/calendar/appointments/')) {
         const appointmentId = path.split('/')[4];
         if (request.method === 'PUT') {
@@ -575,6 +722,14 @@ export default {
         }
       }
 
+      // The ICS feed the assistant publishes (ics_feed.py)
+      if (path === '/api/calendar/feed' && request.method === 'PUT') {
+        return await publishIcsFeed(request, env);
+      }
+      if (path === '/api/calendar/feed' && request.method === 'DELETE') {
+        return await deleteIcsFeed(request, env);
+      }
+
       // Today's schedule
       if (path === '/api/calendar/today' && request.method === 'GET') {
         return await getTodaySchedule(request, env);
@@ -585,7 +740,20 @@ export default {
         return await getWeekSchedule(request, env);
       }
 
+      // Changes since a cursor (delta sync)
+      if (path === '/api/calendar/changes' && request.method === 'GET') {
+        return await getCalendarChanges(request, env);
+      }
+
+      // Live change notifications (WebSocket)
+      if (path === '/api/calendar/subscribe' && request.method === 'GET') {
+        return await subscribeToCalendar(request, env);
+      }
+
       // Reminders
+      if (path === '/api/calendar/reminders/batch' && request.method === 'PUT') {
+        return await updateRemindersBatch(request, env);
+      }
       if (path === '/api/calendar/reminders' && request.method === 'POST') {
         return await createReminder(request, env);
       }
@@ -599,6 +767,90 @@ export default {
         }
       }
 
+      // Commands for the local assistant (speak, refresh config, call)
+      if (path === '/api/commands' && request.method === 'POST') {
+        return await createCommand(request, env);
+      }
+      if (path === '/api/commands' && request.method === 'GET') {
+        return await getCommands(request, env);
+      }
+      if (path.match(/^\/api\/commands\/[^/]+\/ack$/) && request.method === 'POST') {
+        const commandId = path.split('/')[3];
+        return await acknowledgeCommand(request, env, commandId);
+      }
+
+      // Named lists kept by the local assistant (shopping, todo...)
+      if (path === '/api/lists' && request.method === 'GET') {
+        return await getLists(request, env);
+      }
+      if (path === '/api/lists/items' && request.method === 'PUT') {
+        return await putListItems(request, env);
+      }
+
+      // Emergency alerts raised by the local assistant (emergency phrase)
+      if (path === '/api/emergency/contacts' && request.method === 'GET') {
+        return await getEmergencyContactList(request, env);
+      }
+      if (path === '/api/emergency/contacts' && request.method === 'PUT') {
+        return await putEmergencyContacts(request, env);
+      }
+      if (path === '/api/emergency/alerts' && request.method === 'POST') {
+        return await createEmergencyAlert(request, env);
+      }
+      if (path.match(/^\/api\/emergency\/alerts\/[^/]+$/) && request.method === 'GET') {
+        return await getEmergencyAlert(request, env, path.split('/')[4]);
+      }
+      if (path.match(/^\/api\/emergency\/alerts\/[^/]+\/repeat$/) && request.method === 'POST') {
+        return await repeatEmergencyAlert(request, env, path.split('/')[4]);
+      }
+      if (path.match(/^\/api\/emergency\/alerts\/[^/]+\/cancel$/) && request.method === 'POST') {
+        return await cancelEmergencyAlert(request, env, path.split('/')[4]);
+      }
+
+      // Crash reports from the local assistant (only with the user's consent)
+      if (path === '/api/crash-reports' && request.method === 'POST') {
+        return await createCrashReport(request, env);
+      }
+
+      // Encrypted memory sync between the user's machines (the server only relays)
+      if (path === '/api/sync/blobs' && request.method === 'POST') {
+        return await pushSyncBlob(request, env);
+      }
+      if (path === '/api/sync/blobs' && request.method === 'GET') {
+        return await pullSyncBlobs(request, env);
+      }
+
+      // Feature flag overrides for the local assistant (flag_sync job)
+      if (path === '/api/flags' && request.method === 'GET') {
+        return await getFlags(request, env);
+      }
+
+      // Signed releases of the assistant binary (xswarm self-update)
+      if (path === '/api/releases/latest' && request.method === 'GET') {
+        return await getLatestRelease(request, env);
+      }
+      if (path.match(/^\/api\/releases\/[^/]+\/[^/]+$/) && request.method === 'GET') {
+        const [, , , version, platform] = path.split('/');
+        return await getReleaseAsset(request, env, decodeURIComponent(version), decodeURIComponent(platform));
+      }
+
+      // Unified inbox routes
+      if (path === '/api/inbox' && request.method === 'GET') {
+        return await getInbox(request, env);
+      }
+      if (path.match(/^\/api\/inbox\/[^/]+\/draft$/) && request.method === 'POST') {
+        const itemId = path.split('/')[3];
+        return await draftInboxReply(request, env, itemId);
+      }
+      if (path.match(/^\/api\/inbox\/[^/]+\/reply$/) && request.method === 'POST') {
+        const itemId = path.split('/')[3];
+        return await sendInboxReply(request, env, itemId);
+      }
+      if (path.match(/^\/api\/inbox\/[^/]+$/) && request.method === 'PUT') {
+        const itemId = path.split('/')[3];
+        return await updateInbox(request, env, itemId);
+      }
+
       // Billing API routes
       if (path === '/api/billing/usage' && request.method === 'GET') {
         return await handleGetUsage(request, env);
@@ -0,0 +1,280 @@
+/**
+ * Appointment Participants
+ *
+ * First-class participants for appointments: who was invited, how to reach
+ * them, and their RSVP. Participants come from the `participants` array on
+ * an appointment and may be plain strings ("sarah@example.com",
+ * "+15551234567", "Bob") or objects ({ name, email, phone }).
+ *
+ * Invitations go out by email (SendGrid) or SMS (Twilio) with a personal
+ * RSVP link: GET /rsvp/:token?response=accepted|declined|tentative
+ */
+
+import { createClient } from '@libsql/client';
+import { sendEmail } from './send-email.js';
+import { sendSms } from './outbound.js';
+import { mapLink } from './maps.js';
+
+export const RSVP_STATUSES = ['pending', 'accepted', 'declined', 'tentative'];
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getParticipantsDb(env) {
+  if (!dbClient) {
+    dbClient = createClient({
+      url: env.TURSO_DATABASE_URL,
+      authToken: env.TURSO_AUTH_TOKEN,
+    });
+  }
+  return dbClient;
+}
+
+/**
+ * Normalize one participant entry to { label, name, email, phone }
+ *
+ * `label` is the entry as the client supplied it, so clients can match
+ * RSVPs back to their own attendee list.
+ */
+export function normalizeParticipant(entry) {
+  if (entry && typeof entry === 'object') {
+    const label = entry.label || entry.name || entry.email || entry.phone || '';
+    return {
+      label,
+      name: entry.name || null,
+      email: entry.email || null,
+      phone: entry.phone || null,
+    };
+  }
+
+  const label = String(entry || '').trim();
+  const email = label.match(/[^\s<>]+@[^\s<>]+\.[^\s<>]+/);
+  const phone = label.replace(/[\s().-]/g, '').match(/^\+?\d{10,15}$/);
+  const name = label.replace(/<?[^\s<>]+@[^\s<>]+>?/, '').trim();
+
+  return {
+    label,
+    name: !phone && name ? name : null,
+    email: email ? email[0] : null,
+    phone: phone ? phone[0] : null,
+  };
+}
+
+/**
+ * Ensure participant rows exist for an appointment's participants
+ *
+ * Existing rows (matched by label) keep their RSVP and token.
+ *
+ * @returns {Promise<Array>} All participant rows for the appointment
+ */
+export async function syncParticipants(env, appointmentId, participants) {
+  const db = getParticipantsDb(env);
+  const existing = await listParticipants(env, appointmentId);
+  const known = new Set(existing.map((p) => p.label));
+  const now = new Date().toISOString();
+
+  const statements = participants
+    .map(normalizeParticipant)
+    .filter((p) => p.label && !known.has(p.label))
+    .map((p) => ({
+      sql: `
+        INSERT INTO appointment_participants (
+          id, appointment_id, label, name, email, phone,
+          rsvp_status, rsvp_token, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, 'pending', ?, ?)
+      `,
+      args: [crypto.randomUUID(), appointmentId, p.label, p.name, p.email, p.phone, crypto.randomUUID(), now],
+    }));
+
+  if (statements.length > 0) {
+    await db.batch(statements, 'write');
+    return await listParticipants(env, appointmentId);
+  }
+  return existing;
+}
+
+/**
+ * Participants of an appointment, in invitation order
+ */
+export async function listParticipants(env, appointmentId) {
+  const db = getParticipantsDb(env);
+  const result = await db.execute({
+    sql: 'SELECT * FROM appointment_participants WHERE appointment_id = ? ORDER BY created_at ASC, label ASC',
+    args: [appointmentId],
+  });
+  return result.rows.map(formatParticipant);
+}
+
+/**
+ * Send invitations to participants who haven't been invited yet
+ *
+ * Each participant is reached by email if known, otherwise SMS. Failures
+ * are recorded per participant and never abort the rest.
+ *
+ * @returns {Promise<Object>} { sent: [labels], skipped: [labels], failed: [{ label, error }] }
+ */
+export async function sendInvitations(env, appointment, { resend = false } = {}) {
+  const db = getParticipantsDb(env);
+  const participants = await syncParticipants(env, appointment.id, appointment.participants || []);
+  const sent = [];
+  const skipped = [];
+  const failed = [];
+
+  for (const participant of participants) {
+    if ((participant.invited_at && !resend) || participant.rsvp_status !== 'pending') {
+      skipped.push(participant.label);
+      continue;
+    }
+    if (!participant.email && !participant.phone) {
+      failed.push({ label: participant.label, error: 'No email or phone number' });
+      continue;
+    }
+
+    try {
+      const channel = await deliverInvitation(env, appointment, participant);
+      await db.execute({
+        sql: 'UPDATE appointment_participants SET invited_at = ?, invited_via = ? WHERE id = ?',
+        args: [new Date().toISOString(), channel, participant.id],
+      });
+      sent.push(participant.label);
+    } catch (error) {
+      console.error(`Invitation to ${participant.label} failed:`, error);
+      failed.push({ label: participant.label, error: error.message });
+    }
+  }
+
+  return { sent, skipped, failed };
+}
+
+/**
+ * Record an RSVP from a participant's personal link
+ *
+ * @returns {Promise<Object|null>} { participant, appointment } or null for an unknown token
+ */
+export async function recordRsvp(env, token, response) {
+  if (!RSVP_STATUSES.includes(response) || response === 'pending') {
+    throw new Error(`Invalid RSVP response: ${response}`);
+  }
+
+  const db = getParticipantsDb(env);
+  const result = await db.execute({
+    sql: `
+      UPDATE appointment_participants
+      SET rsvp_status = ?, responded_at = ?
+      WHERE rsvp_token = ?
+      RETURNING *
+    `,
+    args: [response, new Date().toISOString(), token],
+  });
+  if (result.rows.length === 0) {
+    return null;
+  }
+
+  const participant = formatParticipant(result.rows[0]);
+  const appointment = await db.execute({
+    sql: 'SELECT * FROM appointments WHERE id = ?',
+    args: [participant.appointment_id],
+  });
+  return { participant, appointment: appointment.rows[0] || null };
+}
+
+/**
+ * Look up a participant by RSVP token (for rendering the RSVP page)
+ */
+export async function getParticipantByToken(env, token) {
+  const db = getParticipantsDb(env);
+  const result = await db.execute({
+    sql: `
+      SELECT p.*, a.title AS appointment_title, a.start_time AS appointment_start
+      FROM appointment_participants p
+      JOIN appointments a ON a.id = p.appointment_id
+      WHERE p.rsvp_token = ?
+    `,
+    args: [token],
+  });
+  return result.rows[0] || null;
+}
+
+/**
+ * Send one invitation. Returns the channel used.
+ */
+async function deliverInvitation(env, appointment, participant) {
+  const baseUrl = env.BASE_URL || 'https://xswarm.ai';
+  const rsvpUrl = `${baseUrl}/rsvp/${participant.rsvp_token}`;
+  const when = formatWhen(appointment.start_time, appointment.timezone);
+  const where = appointment.location ? ` at ${appointment.location}` : '';
+  const map = appointment.map_url || mapLink(appointment.location, appointment.latitude, appointment.longitude);
+
+  if (participant.email) {
+    const greeting = participant.name ? `Hi ${participant.name},` : 'Hi,';
+    await sendEmail({
+      to: participant.email,
+      from: env.FROM_EMAIL || 'boss@xswarm.ai',
+      subject: `Invitation: ${appointment.title} (${when})`,
+      text: [
+        greeting,
+        '',
+        `You're invited to "${appointment.title}" on ${when}${where}.`,
+        ...(map ? [`Map: ${map}`] : []),
+        appointment.description ? `\n${appointment.description}\n` : '',
+        `Will you attend?`,
+        `  Yes:   ${rsvpUrl}?response=accepted`,
+        `  No:    ${rsvpUrl}?response=declined`,
+        `  Maybe: ${rsvpUrl}?response=tentative`,
+      ].join('\n'),
+    }, env);
+    return 'email';
+  }
+
+  await sendSms(
+    participant.phone,
+    `You're invited to "${appointment.title}" on ${when}${where}. RSVP: ${rsvpUrl}`,
+    env
+  );
+  return 'sms';
+}
+
+function formatWhen(startTime, timezone) {
+  try {
+    return new Date(startTime).toLocaleString('en-US', {
+      weekday: 'short',
+      month: 'short',
+      day: 'numeric',
+      hour: 'numeric',
+      minute: '2-digit',
+      timeZone: timezone || 'UTC',
+      timeZoneName: 'short',
+    });
+  } catch {
+    return startTime;
+  }
+}
+
+/**
+ * Participant as returned by the API (without the private RSVP token)
+ */
+export function publicParticipant(participant) {
+  const { rsvp_token, ...rest } = participant;
+  return rest;
+}
+
+/**
+ * Format participant row
+ */
+function formatParticipant(row) {
+  return {
+    id: row.id,
+    appointment_id: row.appointment_id,
+    label: row.label,
+    name: row.name,
+    email: row.email,
+    phone: row.phone,
+    rsvp_status: row.rsvp_status,
+    rsvp_token: row.rsvp_token,
+    invited_at: row.invited_at,
+    invited_via: row.invited_via,
+    responded_at: row.responded_at,
+  };
+}
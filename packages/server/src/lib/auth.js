@@ -11,9 +11,9 @@
  * @param {Object} params - POST parameters from webhook
  * @param {string} signature - X-Twilio-Signature header
  * @param {string} authToken - Twilio auth token
- * @returns {boolean} True if signature is valid
+ * @returns {Promise<boolean>} True if signature is valid
  */
-export function verifyTwilioSignature(url, params, signature, authToken) {
+export async function verifyTwilioSignature(url, params, signature, authToken) {
   if (!signature || !authToken) {
     return false;
   }
@@ -29,7 +29,7 @@ export function verifyTwilioSignature(url, params, signature, authToken) {
     }
 
     // Compute HMAC-SHA1
-    const expectedSignature = computeHmacSha1(data, authToken);
+    const expectedSignature = await computeHmacSha1(data, authToken);
 
     // Compare signatures (constant-time comparison)
     return constantTimeEqual(signature, expectedSignature);
@@ -78,7 +78,7 @@ async function computeHmacSha1(data, key) {
  * @param {string} b - Second string
  * @returns {boolean} True if strings are equal
  */
-function constantTimeEqual(a, b) {
+export function constantTimeEqual(a, b) {
   if (a.length !== b.length) {
     return false;
   }
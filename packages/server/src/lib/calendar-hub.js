@@ -0,0 +1,125 @@
+/**
+ * Calendar Change Subscriptions
+ *
+ * Assistants open a WebSocket to /api/calendar/subscribe?user_id=xxx and are
+ * told within a second when that user's appointments or reminders change
+ * from anywhere (another device, SMS scheduling, a calendar integration),
+ * instead of waiting for their next poll. Messages are small hints:
+ *
+ *   { "type": "calendar_change", "entity": "appointment", "op": "upsert", "count": 1 }
+ *
+ * and the client fetches what changed through GET /api/calendar/changes.
+ * "ping" is answered with "pong" for keepalives. Other hints for the same
+ * assistant share the socket through notifySubscribers(), e.g. queued
+ * commands ({ "type": "command", "id": ... }, see client-commands.js).
+ *
+ * Isolates don't share memory, so each user's sockets live in a CalendarHub
+ * Durable Object (binding CALENDAR_HUB) that the write routes notify. It
+ * uses the hibernation API, so idle subscriptions cost nothing. Without the
+ * binding subscribing returns 503 and clients keep polling.
+ */
+
+/**
+ * Durable Object holding one user's calendar subscriptions
+ */
+export class CalendarHub {
+  constructor(state, env) {
+    this.state = state;
+    this.env = env;
+  }
+
+  async fetch(request) {
+    const url = new URL(request.url);
+
+    if (url.pathname === '/notify' && request.method === 'POST') {
+      const message = JSON.stringify(await request.json());
+      let delivered = 0;
+      for (const socket of this.state.getWebSockets()) {
+        try {
+          socket.send(message);
+          delivered++;
+        } catch {
+          // Closing; the client resyncs when it reconnects
+        }
+      }
+      return new Response(JSON.stringify({ delivered }), { headers: { 'Content-Type': 'application/json' } });
+    }
+
+    if (request.headers.get('Upgrade') !== 'websocket') {
+      return new Response('Expected a WebSocket upgrade', { status: 426 });
+    }
+    const [client, server] = Object.values(new WebSocketPair());
+    this.state.acceptWebSocket(server);
+    server.send(JSON.stringify({ type: 'subscribed' }));
+    return new Response(null, { status: 101, webSocket: client });
+  }
+
+  async webSocketMessage(socket, message) {
+    if (message === 'ping') {
+      socket.send('pong');
+    }
+  }
+
+  async webSocketClose(socket, code) {
+    try {
+      socket.close(code, 'closed');
+    } catch {
+      // Already closed
+    }
+  }
+}
+
+function hub(env, userId) {
+  return env.CALENDAR_HUB.get(env.CALENDAR_HUB.idFromName(userId));
+}
+
+/**
+ * Send a hint to a user's subscribers (never throws; a missed hint is caught by polling)
+ * @param {Object} env - Environment with the CALENDAR_HUB binding
+ * @param {string} userId
+ * @param {Object} message - Sent as JSON; `type` says what it's about
+ */
+export async function notifySubscribers(env, userId, message) {
+  if (!env.CALENDAR_HUB || !userId) {
+    return;
+  }
+  try {
+    await hub(env, userId).fetch('https://calendar-hub/notify', {
+      method: 'POST',
+      body: JSON.stringify(message),
+    });
+  } catch (error) {
+    console.error('Error notifying subscribers:', error);
+  }
+}
+
+/**
+ * Tell a user's subscribers their calendar changed
+ * @param {Object} env - Environment with the CALENDAR_HUB binding
+ * @param {string} userId
+ * @param {Object} change - { entity: 'appointment'|'reminder', op: 'upsert'|'delete', count }
+ */
+export async function notifyCalendarChange(env, userId, change) {
+  await notifySubscribers(env, userId, { type: 'calendar_change', count: 1, ...change });
+}
+
+/**
+ * Subscribe to calendar changes
+ * GET /api/calendar/subscribe?user_id=xxx (WebSocket upgrade)
+ */
+export async function subscribeToCalendar(request, env) {
+  const userId = new URL(request.url).searchParams.get('user_id');
+  if (!userId) {
+    return new Response(
+      JSON.stringify({ error: 'Missing user_id parameter' }),
+      { status: 400, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+  if (!env.CALENDAR_HUB) {
+    return new Response(
+      JSON.stringify({ error: 'Calendar subscriptions are not enabled; poll /api/calendar/changes' }),
+      { status: 503, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+  return hub(env, userId).fetch(request);
+}
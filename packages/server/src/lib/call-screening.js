@@ -0,0 +1,269 @@
+/**
+ * Call Screening and Voicemail
+ *
+ * When someone other than the owner calls the Boss number, the assistant
+ * answers, asks what the call is about and streams the caller's answer into
+ * `screened_calls` as Twilio transcribes it. The dashboard polls that table
+ * and records the owner's decision, which the held call picks up:
+ *
+ * - screening: asking the caller for their purpose
+ * - waiting:   purpose captured, caller on hold for a decision
+ * - accepted:  forward the call to the owner's phone
+ * - declined:  play the owner's message and hang up
+ * - voicemail: record a message (transcript stored in `voicemails`)
+ * - ended:     caller hung up or the call finished
+ */
+
+import { createClient } from '@libsql/client';
+
+export const SCREENING_DECISIONS = ['accepted', 'declined', 'voicemail'];
+
+// How long a caller waits on hold before falling through to voicemail
+export const HOLD_POLL_SECONDS = 5;
+export const MAX_HOLD_POLLS = 9;
+
+const VOICE = 'Polly.Brian-Neural';
+
+const DEFAULT_DECLINE_MESSAGE = "They aren't able to take your call right now. Please try again later. Goodbye.";
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getScreeningDb(env) {
+	if (!dbClient) {
+		dbClient = createClient({
+			url: env.TURSO_DATABASE_URL,
+			authToken: env.TURSO_AUTH_TOKEN,
+		});
+	}
+	return dbClient;
+}
+
+/**
+ * Whether inbound calls from unknown numbers should be screened
+ * (set CALL_SCREENING=false to keep rejecting them instead)
+ */
+export function isScreeningEnabled(env) {
+	return env.CALL_SCREENING !== 'false' && Boolean(env.TURSO_DATABASE_URL);
+}
+
+// ============================================================================
+// Screened calls
+// ============================================================================
+
+export async function createScreenedCall(db, { callSid, userId, caller, callerName }) {
+	const now = new Date().toISOString();
+	const result = await db.execute({
+		sql: `
+			INSERT INTO screened_calls (
+				id, user_id, caller, caller_name, purpose, status, created_at, updated_at
+			) VALUES (?, ?, ?, ?, '', 'screening', ?, ?)
+			ON CONFLICT(id) DO UPDATE SET updated_at = excluded.updated_at
+			RETURNING *
+		`,
+		args: [callSid, userId, caller, callerName || null, now, now],
+	});
+	return formatScreenedCall(result.rows[0]);
+}
+
+export async function getScreenedCall(db, callSid) {
+	const result = await db.execute({
+		sql: 'SELECT * FROM screened_calls WHERE id = ?',
+		args: [callSid],
+	});
+	return result.rows.length > 0 ? formatScreenedCall(result.rows[0]) : null;
+}
+
+/**
+ * Update live purpose transcript and/or status
+ */
+export async function updateScreenedCall(db, callSid, { purpose, status, decline_message }) {
+	const updates = [];
+	const args = [];
+
+	if (purpose !== undefined) {
+		updates.push('purpose = ?');
+		args.push(purpose);
+	}
+	if (status !== undefined) {
+		updates.push('status = ?');
+		args.push(status);
+	}
+	if (decline_message !== undefined) {
+		updates.push('decline_message = ?');
+		args.push(decline_message);
+	}
+
+	updates.push('updated_at = ?');
+	args.push(new Date().toISOString());
+	args.push(callSid);
+
+	const result = await db.execute({
+		sql: `UPDATE screened_calls SET ${updates.join(', ')} WHERE id = ? RETURNING *`,
+		args,
+	});
+	return result.rows.length > 0 ? formatScreenedCall(result.rows[0]) : null;
+}
+
+/**
+ * Calls still waiting on the owner (screening or on hold)
+ */
+export async function listActiveScreenedCalls(db, userId) {
+	const result = await db.execute({
+		sql: `
+			SELECT * FROM screened_calls
+			WHERE user_id = ? AND status IN ('screening', 'waiting')
+			ORDER BY created_at DESC
+		`,
+		args: [userId],
+	});
+	return result.rows.map(formatScreenedCall);
+}
+
+export function formatScreenedCall(row) {
+	return {
+		id: row.id,
+		user_id: row.user_id,
+		caller: row.caller,
+		caller_name: row.caller_name,
+		purpose: row.purpose,
+		status: row.status,
+		decline_message: row.decline_message,
+		created_at: row.created_at,
+		updated_at: row.updated_at,
+	};
+}
+
+// ============================================================================
+// Voicemail
+// ============================================================================
+
+export async function createVoicemail(db, { callSid, userId, caller, recordingUrl, duration }) {
+	const result = await db.execute({
+		sql: `
+			INSERT INTO voicemails (
+				id, user_id, call_sid, caller, recording_url, duration, transcript, created_at
+			) VALUES (?, ?, ?, ?, ?, ?, NULL, ?)
+			RETURNING *
+		`,
+		args: [
+			crypto.randomUUID(),
+			userId,
+			callSid,
+			caller,
+			recordingUrl || null,
+			Number(duration) || 0,
+			new Date().toISOString(),
+		],
+	});
+	return formatVoicemail(result.rows[0]);
+}
+
+export async function setVoicemailTranscript(db, callSid, transcript) {
+	const result = await db.execute({
+		sql: 'UPDATE voicemails SET transcript = ? WHERE call_sid = ? RETURNING *',
+		args: [transcript, callSid],
+	});
+	return result.rows.length > 0 ? formatVoicemail(result.rows[0]) : null;
+}
+
+/**
+ * List voicemails, newest first, optionally matching all words of a query
+ * against the transcript, caller and screened purpose
+ */
+export async function searchVoicemails(db, userId, { query, limit = 50 } = {}) {
+	let sql = `
+		SELECT v.*, s.purpose AS purpose
+		FROM voicemails v
+		LEFT JOIN screened_calls s ON s.id = v.call_sid
+		WHERE v.user_id = ?
+	`;
+	const args = [userId];
+
+	const terms = (query || '').toLowerCase().split(/\s+/).filter(Boolean).slice(0, 8);
+	for (const term of terms) {
+		sql += " AND lower(coalesce(v.transcript, '') || ' ' || v.caller || ' ' || coalesce(s.purpose, '')) LIKE ?";
+		args.push(`%${term}%`);
+	}
+
+	sql += ' ORDER BY v.created_at DESC LIMIT ?';
+	args.push(Math.min(Number(limit) || 50, 200));
+
+	const result = await db.execute({ sql, args });
+	return result.rows.map(formatVoicemail);
+}
+
+export function formatVoicemail(row) {
+	return {
+		id: row.id,
+		user_id: row.user_id,
+		call_sid: row.call_sid,
+		caller: row.caller,
+		purpose: row.purpose || null,
+		recording_url: row.recording_url,
+		duration: row.duration,
+		transcript: row.transcript,
+		created_at: row.created_at,
+	};
+}
+
+// ============================================================================
+// TwiML
+// ============================================================================
+
+export function generateScreeningTwiML(ownerName, callSid) {
+	const sid = encodeURIComponent(callSid);
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Gather input="speech" timeout="6" speechTimeout="auto" action="/voice/screen/purpose?call=${sid}" method="POST" partialResultCallback="/voice/screen/partial?call=${sid}" partialResultCallbackMethod="POST">
+    <Say voice="${VOICE}">Hello, you've reached ${escapeXml(ownerName)}'s assistant. May I ask what your call is regarding?</Say>
+  </Gather>
+  <Redirect method="POST">/voice/screen/wait?call=${sid}&amp;attempt=0</Redirect>
+</Response>`;
+}
+
+export function generateHoldTwiML(callSid, attempt, announce = false) {
+	const intro = announce ? `  <Say voice="${VOICE}">Thank you. One moment while I see if they're available.</Say>\n` : '';
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+${intro}  <Pause length="${HOLD_POLL_SECONDS}"/>
+  <Redirect method="POST">/voice/screen/wait?call=${encodeURIComponent(callSid)}&amp;attempt=${attempt}</Redirect>
+</Response>`;
+}
+
+export function generateConnectTwiML(ownerPhone) {
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Say voice="${VOICE}">Connecting you now.</Say>
+  <Dial timeout="25" action="/voice/screen/dialed" method="POST">${escapeXml(ownerPhone)}</Dial>
+</Response>`;
+}
+
+export function generateDeclineTwiML(message) {
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Say voice="${VOICE}">${escapeXml(message || DEFAULT_DECLINE_MESSAGE)}</Say>
+  <Hangup/>
+</Response>`;
+}
+
+export function generateVoicemailTwiML() {
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Say voice="${VOICE}">Please leave a message after the tone.</Say>
+  <Record maxLength="120" playBeep="true" action="/voice/screen/recorded" method="POST" transcribe="true" transcribeCallback="/voice/screen/transcribed"/>
+  <Say voice="${VOICE}">I didn't catch a message. Goodbye.</Say>
+  <Hangup/>
+</Response>`;
+}
+
+function escapeXml(text) {
+	return String(text)
+		.replace(/&/g, '&amp;')
+		.replace(/</g, '&lt;')
+		.replace(/>/g, '&gt;')
+		.replace(/"/g, '&quot;')
+		.replace(/'/g, '&apos;');
+}
@@ -0,0 +1,222 @@
+/**
+ * Claude Code Cost Guard
+ *
+ * Hard per-session and per-day spend limits for Claude Code sessions. The
+ * supervisor reports what each reply cost (cost_usd); the guard adds it up
+ * per session and per user per day, and:
+ *
+ * - warns once when spend crosses warn_at (default 80%) of a budget
+ * - pauses the session when a budget is used up: its messages are refused
+ *   until the user approves continuing (POST /api/claude-code/sessions/:id/approve),
+ *   which allows one more budget's worth before it pauses again
+ *
+ * Warnings and pauses go to the supervisor as claude_code_budget events so
+ * the assistant says them and shows them on the dashboard, and come back in
+ * the API response of the message that crossed the line.
+ *
+ * Budgets come from config.claude_code (session_budget_usd, daily_budget_usd,
+ * warn_at), with per-project overrides in claude_code.projects keyed by
+ * project path; a project with its own daily budget gets its own daily total.
+ * Totals live in isolate memory, like the rate limiter's counters.
+ */
+
+export const DEFAULT_BUDGETS = {
+  session_budget_usd: 5,
+  daily_budget_usd: 20,
+  warn_at: 0.8,
+};
+
+// Stop tracking sessions past this many (the oldest are dropped first)
+const MAX_TRACKED_SESSIONS = 1000;
+
+/**
+ * Budgets for a project: the project's overrides on top of the defaults
+ * @param {Object} settings - config.claude_code
+ * @param {string} projectPath
+ * @returns {Object} { session_budget_usd, daily_budget_usd, warn_at, own_day }
+ */
+export function budgetsFor(settings = {}, projectPath = '') {
+  const project = (settings.projects || {})[projectPath] || {};
+  return {
+    session_budget_usd: project.session_budget_usd ?? settings.session_budget_usd ?? DEFAULT_BUDGETS.session_budget_usd,
+    daily_budget_usd: project.daily_budget_usd ?? settings.daily_budget_usd ?? DEFAULT_BUDGETS.daily_budget_usd,
+    warn_at: project.warn_at ?? settings.warn_at ?? DEFAULT_BUDGETS.warn_at,
+    own_day: project.daily_budget_usd != null,
+  };
+}
+
+function usd(amount) {
+  return `$${amount.toFixed(2)}`;
+}
+
+function dayKey(session, now) {
+  const date = new Date(now).toISOString().slice(0, 10);
+  return session.budgets.own_day ? `${session.user_id}:${session.project_path}:${date}` : `${session.user_id}:${date}`;
+}
+
+export class CostGuard {
+  /**
+   * @param {Object} settings - config.claude_code
+   * @param {Function} [clock] - Current time in ms (for tests)
+   */
+  constructor(settings = {}, clock = Date.now) {
+    this.settings = settings;
+    this.clock = clock;
+    this.sessions = new Map();
+    this.days = new Map();
+  }
+
+  /**
+   * Start tracking a session
+   */
+  start(sessionId, userId, projectPath) {
+    if (this.sessions.size >= MAX_TRACKED_SESSIONS) {
+      this.sessions.delete(this.sessions.keys().next().value);
+    }
+    const budgets = budgetsFor(this.settings, projectPath);
+    this.sessions.set(sessionId, {
+      user_id: userId,
+      project_path: projectPath,
+      budgets,
+      spent_usd: 0,
+      allowance_usd: budgets.session_budget_usd,
+      warned: false,
+      paused: null, // 'session' or 'day' while waiting for approval
+    });
+  }
+
+  _day(session) {
+    const key = dayKey(session, this.clock());
+    if (!this.days.has(key)) {
+      const today = key.slice(-10);
+      for (const old of this.days.keys()) {
+        if (!old.endsWith(today)) {
+          this.days.delete(old);
+        }
+      }
+      this.days.set(key, { spent_usd: 0, allowance_usd: session.budgets.daily_budget_usd, warned: false });
+    }
+    return this.days.get(key);
+  }
+
+  /**
+   * Whether the session may send another message
+   * @returns {Object|null} { level: 'paused', message } when it's paused, else null
+   */
+  check(sessionId) {
+    const session = this.sessions.get(sessionId);
+    if (!session) {
+      return null;
+    }
+    const day = this._day(session);
+    if (!session.paused && day.spent_usd >= day.allowance_usd) {
+      session.paused = 'day';
+    }
+    if (!session.paused) {
+      return null;
+    }
+    return { level: 'paused', message: this._pausedMessage(session, day) };
+  }
+
+  _pausedMessage(session, day) {
+    const which = session.paused === 'day'
+      ? `today's Claude Code budget (${usd(day.spent_usd)} of ${usd(day.allowance_usd)})`
+      : `this session's budget (${usd(session.spent_usd)} of ${usd(session.allowance_usd)})`;
+    return `Claude Code paused: it has used ${which}. Approve continuing to allow another ` +
+      `${usd(session.paused === 'day' ? session.budgets.daily_budget_usd : session.budgets.session_budget_usd)}.`;
+  }
+
+  /**
+   * Add a reply's cost
+   * @returns {Object|null} { level: 'warning'|'paused', message } when a threshold was crossed
+   */
+  record(sessionId, costUsd) {
+    const session = this.sessions.get(sessionId);
+    const cost = Number(costUsd) || 0;
+    if (!session || cost <= 0) {
+      return null;
+    }
+    const day = this._day(session);
+    session.spent_usd += cost;
+    day.spent_usd += cost;
+
+    if (session.spent_usd >= session.allowance_usd || day.spent_usd >= day.allowance_usd) {
+      session.paused = session.spent_usd >= session.allowance_usd ? 'session' : 'day';
+      return { level: 'paused', message: this._pausedMessage(session, day) };
+    }
+    const warnAt = session.budgets.warn_at;
+    if (!session.warned && session.spent_usd >= warnAt * session.allowance_usd) {
+      session.warned = true;
+      return {
+        level: 'warning',
+        message: `Claude Code has used ${usd(session.spent_usd)} of this session's ${usd(session.allowance_usd)} budget.`,
+      };
+    }
+    if (!day.warned && day.spent_usd >= warnAt * day.allowance_usd) {
+      day.warned = true;
+      return {
+        level: 'warning',
+        message: `Claude Code has used ${usd(day.spent_usd)} of today's ${usd(day.allowance_usd)} budget.`,
+      };
+    }
+    return null;
+  }
+
+  /**
+   * The user approved continuing: allow one more budget's worth of what ran out
+   * @returns {boolean} False when the session isn't paused
+   */
+  approve(sessionId) {
+    const session = this.sessions.get(sessionId);
+    if (!session || !session.paused) {
+      return false;
+    }
+    const day = this._day(session);
+    if (session.spent_usd >= session.allowance_usd) {
+      session.allowance_usd = session.spent_usd + session.budgets.session_budget_usd;
+      session.warned = false;
+    }
+    if (day.spent_usd >= day.allowance_usd) {
+      day.allowance_usd = day.spent_usd + session.budgets.daily_budget_usd;
+      day.warned = false;
+    }
+    session.paused = null;
+    return true;
+  }
+
+  /**
+   * Spend and budgets for a session, for the cost endpoint and responses
+   */
+  status(sessionId) {
+    const session = this.sessions.get(sessionId);
+    if (!session) {
+      return null;
+    }
+    const day = this._day(session);
+    return {
+      session_usd: Number(session.spent_usd.toFixed(4)),
+      session_budget_usd: Number(session.allowance_usd.toFixed(4)),
+      day_usd: Number(day.spent_usd.toFixed(4)),
+      daily_budget_usd: Number(day.allowance_usd.toFixed(4)),
+      paused: session.paused,
+    };
+  }
+
+  end(sessionId) {
+    this.sessions.delete(sessionId);
+  }
+}
+
+let guard = null;
+
+/**
+ * The isolate's cost guard, created from config.claude_code on first use
+ * @param {Object} [settings] - config.claude_code
+ * @returns {CostGuard}
+ */
+export function getCostGuard(settings) {
+  if (!guard) {
+    guard = new CostGuard(settings);
+  }
+  return guard;
+}
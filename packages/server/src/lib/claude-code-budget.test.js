@@ -0,0 +1,75 @@
+/**
+ * Tests for the Claude Code cost guard (per-session and per-day budgets)
+ */
+
+import { test } from 'node:test';
+import assert from 'node:assert';
+import { CostGuard, budgetsFor } from './claude-code-budget.js';
+
+const NOON = Date.parse('2026-10-16T12:00:00Z');
+
+function makeGuard(settings = {}) {
+  const clock = { now: NOON };
+  const guard = new CostGuard({ session_budget_usd: 5, daily_budget_usd: 8, warn_at: 0.8, ...settings },
+    () => clock.now);
+  return { guard, clock };
+}
+
+test('budgetsFor - project overrides on top of the defaults', () => {
+  const settings = { session_budget_usd: 5, projects: { '/big': { session_budget_usd: 15, daily_budget_usd: 40 } } };
+  assert.deepStrictEqual(budgetsFor(settings, '/big'),
+    { session_budget_usd: 15, daily_budget_usd: 40, warn_at: 0.8, own_day: true });
+  assert.deepStrictEqual(budgetsFor(settings, '/small'),
+    { session_budget_usd: 5, daily_budget_usd: 20, warn_at: 0.8, own_day: false });
+});
+
+test('CostGuard - warns once, pauses at the session budget, approval allows one more', () => {
+  const { guard } = makeGuard();
+  guard.start('s1', 'u1', '/app');
+
+  assert.strictEqual(guard.record('s1', 3), null);
+  assert.deepStrictEqual(guard.record('s1', 1.2),
+    { level: 'warning', message: "Claude Code has used $4.20 of this session's $5.00 budget." });
+  assert.strictEqual(guard.check('s1'), null);
+
+  const paused = guard.record('s1', 0.9);
+  assert.strictEqual(paused.level, 'paused');
+  assert.strictEqual(paused.message, "Claude Code paused: it has used this session's budget ($5.10 of $5.00). " +
+    'Approve continuing to allow another $5.00.');
+  assert.strictEqual(guard.check('s1').level, 'paused');
+
+  assert.strictEqual(guard.approve('s1'), true);
+  assert.strictEqual(guard.check('s1'), null);
+  assert.strictEqual(guard.status('s1').session_budget_usd, 10.1);
+  assert.strictEqual(guard.approve('s1'), false); // Not paused any more
+});
+
+test('CostGuard - the daily budget pauses every session of the user until the next day', () => {
+  const { guard, clock } = makeGuard();
+  guard.start('s1', 'u1', '/app');
+  guard.start('s2', 'u1', '/site');
+  guard.start('s3', 'u2', '/app');
+
+  guard.record('s1', 4);
+  guard.record('s2', 3);
+  assert.strictEqual(guard.record('s2', 1).level, 'paused');
+  assert.match(guard.check('s1').message, /today's Claude Code budget \(\$8\.00 of \$8\.00\)/);
+  assert.strictEqual(guard.check('s3'), null); // Another user
+
+  clock.now += 24 * 60 * 60 * 1000;
+  assert.strictEqual(guard.status('s1').day_usd, 0);
+  assert.strictEqual(guard.approve('s1'), true);
+  assert.strictEqual(guard.check('s1'), null);
+});
+
+test('CostGuard - projects with their own daily budget count separately', () => {
+  const { guard } = makeGuard({ projects: { '/big': { daily_budget_usd: 30, session_budget_usd: 30 } } });
+  guard.start('s1', 'u1', '/app');
+  guard.start('s2', 'u1', '/big');
+  guard.record('s1', 4.5);
+  guard.record('s2', 20);
+  assert.deepStrictEqual(guard.status('s2'),
+    { session_usd: 20, session_budget_usd: 30, day_usd: 20, daily_budget_usd: 30, paused: null });
+  assert.strictEqual(guard.status('s1').day_usd, 4.5);
+  assert.strictEqual(guard.record('unknown', 1), null);
+});
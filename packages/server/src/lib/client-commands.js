@@ -0,0 +1,138 @@
+/**
+ * Client Commands
+ *
+ * Actions the server asks a user's local assistant to take, queued in the
+ * `client_commands` table (scripts/migrate-client-commands.js):
+ *
+ * - speak:          say { text, priority } out loud
+ * - refresh_config: reload the assistant's config file
+ * - call:           place an outbound call { message, priority }
+ *
+ * Protocol: queueing a command hints the assistant over its calendar
+ * subscription socket ({ type: 'command', id }), and it also polls
+ * GET /api/commands. It acknowledges every command it sees with done,
+ * failed or rejected (its local policy - config.remote_commands - didn't
+ * allow it), plus a short result. Acks are idempotent: acking a command
+ * twice returns the first ack. Queueing is idempotent per idempotency key:
+ * the same key returns the command already queued. Commands nobody
+ * acknowledged before expires_at are marked expired and never delivered.
+ */
+
+import { createClient } from '@libsql/client';
+import { notifySubscribers } from './calendar-hub.js';
+
+export const COMMAND_TYPES = ['speak', 'refresh_config', 'call'];
+export const ACK_STATUSES = ['done', 'failed', 'rejected'];
+export const DEFAULT_TTL_SECONDS = 10 * 60;
+export const MAX_TTL_SECONDS = 24 * 60 * 60;
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getCommandsDb(env) {
+  if (!dbClient) {
+    dbClient = createClient({
+      url: env.TURSO_DATABASE_URL,
+      authToken: env.TURSO_AUTH_TOKEN,
+    });
+  }
+  return dbClient;
+}
+
+function formatCommand(row) {
+  return {
+    id: row.id,
+    type: row.type,
+    args: JSON.parse(row.args || '{}'),
+    idempotency_key: row.idempotency_key,
+    status: row.status,
+    result: row.result,
+    created_at: row.created_at,
+    expires_at: row.expires_at,
+    acked_at: row.acked_at,
+  };
+}
+
+/**
+ * Queue a command for a user's assistant and hint it to fetch it
+ *
+ * @param {Object} env - Environment variables
+ * @param {string} userId - User identifier
+ * @param {Object} command - { type, args, idempotency_key, ttl_seconds }
+ * @returns {Promise<{command: Object, duplicate: boolean}>}
+ */
+export async function queueClientCommand(env, userId, { type, args = {}, idempotency_key, ttl_seconds }) {
+  if (!COMMAND_TYPES.includes(type)) {
+    throw new Error(`Unknown command type '${type}'. Expected one of: ${COMMAND_TYPES.join(', ')}`);
+  }
+  const db = getCommandsDb(env);
+  const key = idempotency_key || crypto.randomUUID();
+  const ttl = Math.min(Math.max(Number(ttl_seconds) || DEFAULT_TTL_SECONDS, 1), MAX_TTL_SECONDS);
+  const now = new Date();
+
+  const inserted = await db.execute({
+    sql: `
+      INSERT INTO client_commands (id, user_id, type, args, idempotency_key, created_at, expires_at)
+      VALUES (?, ?, ?, ?, ?, ?, ?)
+      ON CONFLICT (user_id, idempotency_key) DO NOTHING
+      RETURNING *
+    `,
+    args: [
+      crypto.randomUUID(), userId, type, JSON.stringify(args), key,
+      now.toISOString(), new Date(now.getTime() + ttl * 1000).toISOString(),
+    ],
+  });
+  if (inserted.rows.length === 0) {
+    const existing = await db.execute({
+      sql: 'SELECT * FROM client_commands WHERE user_id = ? AND idempotency_key = ?',
+      args: [userId, key],
+    });
+    return { command: formatCommand(existing.rows[0]), duplicate: true };
+  }
+
+  const command = formatCommand(inserted.rows[0]);
+  await notifySubscribers(env, userId, { type: 'command', id: command.id });
+  return { command, duplicate: false };
+}
+
+/**
+ * Pending commands for a user, oldest first (expired ones are marked so first)
+ */
+export async function listPendingCommands(db, userId, limit = 20) {
+  const now = new Date().toISOString();
+  await db.execute({
+    sql: `UPDATE client_commands SET status = 'expired'
+          WHERE user_id = ? AND status = 'pending' AND datetime(expires_at) <= datetime(?)`,
+    args: [userId, now],
+  });
+  const result = await db.execute({
+    sql: `SELECT * FROM client_commands WHERE user_id = ? AND status = 'pending'
+          ORDER BY created_at ASC LIMIT ?`,
+    args: [userId, Math.min(Number(limit) || 20, 100)],
+  });
+  return result.rows.map(formatCommand);
+}
+
+/**
+ * Record the assistant's acknowledgment of a command
+ *
+ * @returns {Promise<Object|null>} The command (with the first ack if it was already acked), or null if unknown
+ */
+export async function ackCommand(db, userId, commandId, { status, result }) {
+  const updated = await db.execute({
+    sql: `UPDATE client_commands SET status = ?, result = ?, acked_at = ?
+          WHERE id = ? AND user_id = ? AND status = 'pending'
+          RETURNING *`,
+    args: [status, result == null ? null : String(result).slice(0, 1000), new Date().toISOString(), commandId, userId],
+  });
+  if (updated.rows.length > 0) {
+    return formatCommand(updated.rows[0]);
+  }
+  const existing = await db.execute({
+    sql: 'SELECT * FROM client_commands WHERE id = ? AND user_id = ?',
+    args: [commandId, userId],
+  });
+  return existing.rows.length > 0 ? formatCommand(existing.rows[0]) : null;
+}
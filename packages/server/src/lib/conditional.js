@@ -0,0 +1,62 @@
+/**
+ * Conditional GET Responses
+ *
+ * Polled read endpoints (calendar schedule/appointments) send an ETag - a
+ * hash of the response body - and a Last-Modified from the newest row's
+ * updated_at. A client that sends back If-None-Match with the same ETag (or,
+ * without one, an If-Modified-Since at or after Last-Modified) gets an empty
+ * 304 instead of the payload. The ETag is checked first since Last-Modified
+ * can't see deleted rows.
+ */
+
+async function sha1Hex(text) {
+  const digest = await crypto.subtle.digest('SHA-1', new TextEncoder().encode(text));
+  return [...new Uint8Array(digest)].map((b) => b.toString(16).padStart(2, '0')).join('');
+}
+
+/**
+ * Newest updated_at (or created_at) among rows, as a Date
+ * @param {Array<Object>} rows
+ * @returns {Date|null}
+ */
+export function lastModified(rows) {
+  let newest = null;
+  for (const row of rows) {
+    const stamp = Date.parse(row.updated_at || row.created_at || '');
+    if (!Number.isNaN(stamp) && (newest === null || stamp > newest)) {
+      newest = stamp;
+    }
+  }
+  // HTTP dates have whole seconds
+  return newest === null ? null : new Date(Math.floor(newest / 1000) * 1000);
+}
+
+function etagMatches(header, etag) {
+  return header.split(',').map((tag) => tag.trim().replace(/^W\//, '')).some((tag) => tag === '*' || tag === etag);
+}
+
+/**
+ * JSON response with ETag/Last-Modified, or 304 when the client's copy is current
+ * @param {Request} request
+ * @param {Object} data - Response body
+ * @param {Date|null} [modified] - When the data last changed
+ * @returns {Promise<Response>}
+ */
+export async function conditionalJson(request, data, modified = null) {
+  const body = JSON.stringify(data);
+  const etag = `"${await sha1Hex(body)}"`;
+  const headers = { ETag: etag, 'Cache-Control': 'private, no-cache' };
+  if (modified) {
+    headers['Last-Modified'] = modified.toUTCString();
+  }
+
+  const ifNoneMatch = request.headers.get('If-None-Match');
+  const ifModifiedSince = Date.parse(request.headers.get('If-Modified-Since') || '');
+  const current = ifNoneMatch
+    ? etagMatches(ifNoneMatch, etag)
+    : modified !== null && !Number.isNaN(ifModifiedSince) && modified.getTime() <= ifModifiedSince;
+  if (current) {
+    return new Response(null, { status: 304, headers });
+  }
+  return new Response(body, { status: 200, headers: { ...headers, 'Content-Type': 'application/json' } });
+}
@@ -0,0 +1,279 @@
+/**
+ * Emergency Alerts
+ *
+ * When the user says their emergency phrase (and doesn't cancel the
+ * countdown), the local assistant raises an alert here. Each round texts
+ * and/or calls every contact with the message, which carries the time and
+ * location. The assistant asks for another round every few minutes until
+ * the alert is resolved:
+ *
+ * - active:       rounds keep going out
+ * - acknowledged: a contact replied OK by text or pressed 1 on the call
+ * - cancelled:    the user stood it down ("I'm okay"); contacts are told
+ *
+ * Alerts only go to the user's saved emergency contacts: the assistant saves
+ * them (PUT /api/emergency/contacts) and an alert names some of them or
+ * goes to all, so a request can't text or call numbers of its choosing.
+ *
+ * Rows live in `emergency_alerts` and `emergency_contacts`
+ * (scripts/migrate-emergency.js).
+ */
+
+import { createClient } from '@libsql/client';
+import { makeOutboundCall, sendSms } from './outbound.js';
+
+export const ALERT_METHODS = ['sms', 'call'];
+export const MAX_CONTACTS = 10;
+export const MAX_MESSAGE_LENGTH = 600;
+
+// A contact's text that acknowledges the alert
+const ACK_REPLY = /^\s*(ok|okay|yes|got it|on (my|the) way|coming|received|ack)\b/i;
+
+const VOICE = 'Polly.Matthew-Neural';
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getEmergencyDb(env) {
+	if (!dbClient) {
+		dbClient = createClient({
+			url: env.TURSO_DATABASE_URL,
+			authToken: env.TURSO_AUTH_TOKEN,
+		});
+	}
+	return dbClient;
+}
+
+function normalizePhone(phone) {
+	return String(phone || '').replace(/[^\d+]/g, '');
+}
+
+function formatAlert(row) {
+	return {
+		id: row.id,
+		user_id: row.user_id,
+		message: row.message,
+		contacts: JSON.parse(row.contacts),
+		methods: JSON.parse(row.methods),
+		status: row.status,
+		rounds: row.rounds,
+		acknowledged_by: row.acknowledged_by || null,
+		created_at: row.created_at,
+		last_sent_at: row.last_sent_at || null,
+		resolved_at: row.resolved_at || null,
+	};
+}
+
+/**
+ * Why a list of contacts can't be used, or null when it's fine
+ */
+export function validateContacts(contacts) {
+	if (!Array.isArray(contacts) || contacts.length === 0) return 'contacts must be a non-empty array';
+	if (contacts.length > MAX_CONTACTS) return `at most ${MAX_CONTACTS} contacts`;
+	for (const [index, contact] of contacts.entries()) {
+		if (!/^\+\d{7,15}$/.test(normalizePhone(contact?.phone))) {
+			return `contacts[${index}].phone must be an E.164 number like +15551234567`;
+		}
+	}
+	return null;
+}
+
+/**
+ * Why an alert can't be raised, or null when it's fine (contacts are optional: all saved ones)
+ */
+export function validateAlert({ message, contacts, methods }) {
+	if (typeof message !== 'string' || !message.trim()) return 'message is required';
+	if (message.length > MAX_MESSAGE_LENGTH) return `message is longer than ${MAX_MESSAGE_LENGTH} characters`;
+	if (contacts !== undefined) {
+		const problem = validateContacts(contacts);
+		if (problem) return problem;
+	}
+	if (!Array.isArray(methods) || methods.length === 0 || methods.some((m) => !ALERT_METHODS.includes(m))) {
+		return `methods must be some of ${ALERT_METHODS.join(', ')}`;
+	}
+	return null;
+}
+
+export async function getEmergencyContacts(db, userId) {
+	const result = await db.execute({
+		sql: 'SELECT name, phone FROM emergency_contacts WHERE user_id = ? ORDER BY position',
+		args: [userId],
+	});
+	return result.rows.map((row) => ({ name: row.name, phone: row.phone }));
+}
+
+/**
+ * Replace the user's saved contacts (already checked with validateContacts)
+ */
+export async function saveEmergencyContacts(db, userId, contacts) {
+	await db.execute({ sql: 'DELETE FROM emergency_contacts WHERE user_id = ?', args: [userId] });
+	for (const [position, contact] of contacts.entries()) {
+		await db.execute({
+			sql: `INSERT OR REPLACE INTO emergency_contacts (user_id, phone, name, position) VALUES (?, ?, ?, ?)`,
+			args: [userId, normalizePhone(contact.phone), String(contact.name || ''), position],
+		});
+	}
+	return getEmergencyContacts(db, userId);
+}
+
+/**
+ * The saved contacts an alert goes to: the ones it names, or all of them
+ *
+ * @returns {{contacts: Array}|{error: string}}
+ */
+export function pickContacts(saved, requested) {
+	if (saved.length === 0) {
+		return { error: 'No emergency contacts saved (PUT /api/emergency/contacts)' };
+	}
+	if (requested === undefined) {
+		return { contacts: saved };
+	}
+	const contacts = [];
+	for (const [index, contact] of requested.entries()) {
+		const match = saved.find((c) => c.phone === normalizePhone(contact.phone));
+		if (!match) {
+			return { error: `contacts[${index}] is not one of your saved emergency contacts` };
+		}
+		if (!contacts.includes(match)) contacts.push(match);
+	}
+	return { contacts };
+}
+
+export async function createAlert(db, userId, { message, contacts, methods }) {
+	const result = await db.execute({
+		sql: `INSERT INTO emergency_alerts (id, user_id, message, contacts, methods)
+		      VALUES (?, ?, ?, ?, ?) RETURNING *`,
+		args: [
+			crypto.randomUUID(),
+			userId,
+			message.trim(),
+			JSON.stringify(contacts.map((c) => ({ name: c.name || '', phone: normalizePhone(c.phone) }))),
+			JSON.stringify(methods),
+		],
+	});
+	return formatAlert(result.rows[0]);
+}
+
+export async function getAlert(db, alertId, userId = null) {
+	const result = userId
+		? await db.execute({ sql: 'SELECT * FROM emergency_alerts WHERE id = ? AND user_id = ?', args: [alertId, userId] })
+		: await db.execute({ sql: 'SELECT * FROM emergency_alerts WHERE id = ?', args: [alertId] });
+	return result.rows.length ? formatAlert(result.rows[0]) : null;
+}
+
+/**
+ * Text and/or call every contact once
+ *
+ * @returns {Promise<{alert: Object, results: Array}>} Per contact, "sent" or the error for each method
+ */
+export async function sendAlertRound(db, env, alert, baseUrl) {
+	const results = [];
+	for (const contact of alert.contacts) {
+		const result = { name: contact.name, phone: contact.phone };
+		if (alert.methods.includes('sms')) {
+			try {
+				await sendSms(contact.phone, `${alert.message} Reply OK to let them know you're on it.`, env);
+				result.sms = 'sent';
+			} catch (error) {
+				result.sms = error.message;
+			}
+		}
+		if (alert.methods.includes('call')) {
+			try {
+				await makeOutboundCall(contact.phone, `${baseUrl}/emergency/${alert.id}/twiml`, env);
+				result.call = 'sent';
+			} catch (error) {
+				result.call = error.message;
+			}
+		}
+		results.push(result);
+	}
+	const updated = await db.execute({
+		sql: `UPDATE emergency_alerts SET rounds = rounds + 1, last_sent_at = datetime('now')
+		      WHERE id = ? RETURNING *`,
+		args: [alert.id],
+	});
+	return { alert: formatAlert(updated.rows[0]), results };
+}
+
+/**
+ * Move an active alert to acknowledged or cancelled
+ *
+ * @returns {Promise<Object|null>} The alert, or null if it wasn't active
+ */
+export async function resolveAlert(db, alertId, status, acknowledgedBy = null) {
+	const result = await db.execute({
+		sql: `UPDATE emergency_alerts SET status = ?, acknowledged_by = ?, resolved_at = datetime('now')
+		      WHERE id = ? AND status = 'active' RETURNING *`,
+		args: [status, acknowledgedBy, alertId],
+	});
+	return result.rows.length ? formatAlert(result.rows[0]) : null;
+}
+
+/**
+ * Acknowledge the user's active alert if `sender` is one of its contacts replying OK
+ *
+ * @returns {Promise<Object|null>} The acknowledged alert, or null when the text isn't an acknowledgement
+ */
+export async function acknowledgeBySms(db, userId, sender, body) {
+	if (!ACK_REPLY.test(body || '')) return null;
+	const result = await db.execute({
+		sql: `SELECT * FROM emergency_alerts WHERE user_id = ? AND status = 'active' ORDER BY created_at DESC`,
+		args: [userId],
+	});
+	const phone = normalizePhone(sender);
+	for (const row of result.rows) {
+		const alert = formatAlert(row);
+		const contact = alert.contacts.find((c) => c.phone === phone);
+		if (contact) {
+			return resolveAlert(db, alert.id, 'acknowledged', contact.name || contact.phone);
+		}
+	}
+	return null;
+}
+
+/**
+ * The contact with this number on an alert, for call answers
+ */
+export function findContact(alert, phone) {
+	return alert.contacts.find((c) => c.phone === normalizePhone(phone)) || null;
+}
+
+// ============================================================================
+// TwiML
+// ============================================================================
+
+export function generateAlertTwiML(alert) {
+	const message = escapeXml(alert.message);
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Gather input="dtmf" numDigits="1" timeout="8" action="/emergency/${encodeURIComponent(alert.id)}/answer" method="POST">
+    <Say voice="${VOICE}">This is an emergency alert. ${message}</Say>
+    <Say voice="${VOICE}">Again: ${message} Press 1 to say you're on it.</Say>
+  </Gather>
+  <Say voice="${VOICE}">You'll get another call until someone presses 1. Goodbye.</Say>
+  <Hangup/>
+</Response>`;
+}
+
+export function generateAnswerTwiML(acknowledged) {
+	const text = acknowledged
+		? "Thank you. They'll be told you're on it. Goodbye."
+		: 'This alert has already been answered or cancelled. Goodbye.';
+	return `<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Say voice="${VOICE}">${text}</Say>
+  <Hangup/>
+</Response>`;
+}
+
+function escapeXml(text) {
+	return String(text)
+		.replace(/&/g, '&amp;')
+		.replace(/</g, '&lt;')
+		.replace(/>/g, '&gt;')
+		.replace(/"/g, '&quot;')
+		.replace(/'/g, '&apos;');
+}
@@ -0,0 +1,83 @@
+/**
+ * Tests for emergency alerts (validation, saved contacts and the alert lifecycle)
+ */
+
+import { test } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, makeTestEnv } from '../test-env.js';
+import {
+  MAX_CONTACTS,
+  acknowledgeBySms,
+  createAlert,
+  getAlert,
+  pickContacts,
+  resolveAlert,
+  saveEmergencyContacts,
+  validateAlert,
+} from './emergency.js';
+
+const SAM = { name: 'Sam', phone: '+15551234567' };
+const ALEX = { name: 'Alex', phone: '+15557654321' };
+const ALERT = { message: 'EMERGENCY: Jo needs help.', methods: ['sms'], contacts: [SAM, ALEX] };
+
+test('validateAlert - message, contacts and methods', () => {
+  assert.strictEqual(validateAlert(ALERT), null);
+  assert.strictEqual(validateAlert({ ...ALERT, contacts: undefined }), null); // All saved contacts
+  assert.strictEqual(validateAlert({ ...ALERT, message: '  ' }), 'message is required');
+  assert.match(validateAlert({ ...ALERT, message: 'x'.repeat(601) }), /longer than 600/);
+  assert.strictEqual(validateAlert({ ...ALERT, contacts: [] }), 'contacts must be a non-empty array');
+  assert.strictEqual(validateAlert({ ...ALERT, contacts: Array(MAX_CONTACTS + 1).fill(SAM) }), 'at most 10 contacts');
+  assert.match(validateAlert({ ...ALERT, contacts: [SAM, { phone: '555-1234' }] }), /^contacts\[1\]\.phone/);
+  assert.match(validateAlert({ ...ALERT, methods: ['sms', 'fax'] }), /^methods must be/);
+  assert.match(validateAlert({ ...ALERT, methods: [] }), /^methods must be/);
+});
+
+test('pickContacts - only saved contacts, matched by number', () => {
+  assert.deepStrictEqual(pickContacts([SAM, ALEX], undefined), { contacts: [SAM, ALEX] });
+  assert.deepStrictEqual(pickContacts([SAM, ALEX], [{ phone: '+1 (555) 765-4321' }]), { contacts: [ALEX] });
+  assert.deepStrictEqual(pickContacts([SAM], [{ name: 'Sam', phone: '+19005550000' }]),
+    { error: 'contacts[0] is not one of your saved emergency contacts' });
+  assert.match(pickContacts([], undefined).error, /No emergency contacts saved/);
+});
+
+test('saveEmergencyContacts - replaces the list, keeping its order', async () => {
+  const { db } = await makeTestEnv(SCHEMA.emergency);
+  await saveEmergencyContacts(db, 'u1', [SAM]);
+  assert.deepStrictEqual(await saveEmergencyContacts(db, 'u1', [ALEX, { name: 'Sam', phone: '+1 555 123 4567' }]),
+    [ALEX, SAM]);
+  assert.deepStrictEqual(await saveEmergencyContacts(db, 'u2', [SAM]), [SAM]);
+});
+
+test('resolveAlert - an active alert resolves once', async () => {
+  const { db } = await makeTestEnv(SCHEMA.emergency);
+  const alert = await createAlert(db, 'u1', ALERT);
+  assert.strictEqual(alert.status, 'active');
+
+  const acknowledged = await resolveAlert(db, alert.id, 'acknowledged', 'Sam');
+  assert.strictEqual(acknowledged.status, 'acknowledged');
+  assert.strictEqual(acknowledged.acknowledged_by, 'Sam');
+  assert.ok(acknowledged.resolved_at);
+
+  assert.strictEqual(await resolveAlert(db, alert.id, 'cancelled'), null); // Not active any more
+  assert.strictEqual((await getAlert(db, alert.id)).status, 'acknowledged');
+
+  const other = await createAlert(db, 'u1', ALERT);
+  assert.strictEqual((await resolveAlert(db, other.id, 'cancelled')).status, 'cancelled');
+  assert.strictEqual(await getAlert(db, other.id, 'u2'), null); // Another user's
+});
+
+test('acknowledgeBySms - an OK from one of the contacts acknowledges the active alert', async () => {
+  const { db } = await makeTestEnv(SCHEMA.emergency);
+  const alert = await createAlert(db, 'u1', ALERT);
+  const elsewhere = await createAlert(db, 'u2', ALERT);
+
+  assert.strictEqual(await acknowledgeBySms(db, 'u1', ALEX.phone, 'who is this?'), null);
+  assert.strictEqual(await acknowledgeBySms(db, 'u1', '+19005550000', 'OK'), null); // Not a contact
+  assert.strictEqual((await getAlert(db, alert.id)).status, 'active');
+
+  const acknowledged = await acknowledgeBySms(db, 'u1', '+1 555 765 4321', 'On my way!');
+  assert.strictEqual(acknowledged.id, alert.id);
+  assert.strictEqual(acknowledged.acknowledged_by, 'Alex');
+  assert.strictEqual(await acknowledgeBySms(db, 'u1', ALEX.phone, 'ok'), null); // Already answered
+  assert.strictEqual((await getAlert(db, elsewhere.id)).status, 'active');
+});
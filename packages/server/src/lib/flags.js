@@ -0,0 +1,54 @@
+/**
+ * Feature Flags
+ *
+ * Server-side overrides of the local assistant's feature flags (see
+ * assistant/flags.py), kept in the `feature_flags` table
+ * (scripts/migrate-flags.js). A row for user_id '*' applies to everyone;
+ * a user's own row wins over it. Flags without a row are left to the
+ * assistant's defaults and release channel.
+ *
+ * Rows are set by hand or by admin tooling, e.g.
+ *   INSERT INTO feature_flags (user_id, flag, enabled) VALUES ('*', 'local_llm', 0)
+ * turns the local model fallback off for everyone (a kill switch), and a
+ * user row with enabled = 1 lets one user try a subsystem that ships dark.
+ */
+
+import { createClient } from '@libsql/client';
+
+export const EVERYONE = '*';
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getFlagsDb(env) {
+  if (!dbClient) {
+    dbClient = createClient({
+      url: env.TURSO_DATABASE_URL,
+      authToken: env.TURSO_AUTH_TOKEN,
+    });
+  }
+  return dbClient;
+}
+
+/**
+ * The overrides that apply to a user: everyone's, then their own on top
+ *
+ * @returns {Promise<{flags: Object<string, boolean>}>}
+ */
+export async function flagsForUser(db, userId) {
+  const result = await db.execute({
+    sql: `
+      SELECT flag, enabled FROM feature_flags
+      WHERE user_id IN (?, ?)
+      ORDER BY CASE WHEN user_id = ? THEN 0 ELSE 1 END
+    `,
+    args: [EVERYONE, userId, EVERYONE],
+  });
+  const flags = {};
+  for (const row of result.rows) {
+    flags[row.flag] = Boolean(row.enabled);
+  }
+  return { flags };
+}
@@ -0,0 +1,177 @@
+/**
+ * Unified Inbox
+ *
+ * Persists inbound communications from every channel (SMS, email, voice)
+ * into a single `inbox_items` table so clients can render one inbox view.
+ *
+ * Each item carries a status that clients sync back:
+ * - unread:   just arrived
+ * - read:     seen in a client
+ * - replied:  a reply was sent (reply text stored alongside)
+ * - archived: hidden from the default inbox view
+ */
+
+import { createClient } from '@libsql/client';
+
+export const INBOX_STATUSES = ['unread', 'read', 'replied', 'archived'];
+export const INBOX_CHANNELS = ['sms', 'email', 'voice'];
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getInboxDb(env) {
+  if (!dbClient) {
+    dbClient = createClient({
+      url: env.TURSO_DATABASE_URL,
+      authToken: env.TURSO_AUTH_TOKEN,
+    });
+  }
+  return dbClient;
+}
+
+/**
+ * Record an inbound unified message in the inbox
+ *
+ * Best-effort: failures are logged and swallowed so that webhook handling
+ * is never blocked by inbox persistence.
+ *
+ * @param {Object} env - Environment variables
+ * @param {Object} message - UnifiedMessage (see unified-message.js)
+ * @param {string} userId - Owning user identifier
+ * @returns {Promise<Object|null>} Created inbox item, or null on failure
+ */
+export async function recordInboundMessage(env, message, userId) {
+  if (!env.TURSO_DATABASE_URL || !INBOX_CHANNELS.includes(message.channel)) {
+    return null;
+  }
+
+  try {
+    const db = getInboxDb(env);
+    const id = crypto.randomUUID();
+    const metadata = message.metadata || {};
+
+    const result = await db.execute({
+      sql: `
+        INSERT INTO inbox_items (
+          id, user_id, channel, sender, subject, content,
+          external_id, status, received_at, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, 'unread', ?, ?)
+        RETURNING *
+      `,
+      args: [
+        id,
+        userId,
+        message.channel,
+        message.from || '',
+        metadata.subject || null,
+        message.content || '',
+        metadata.messageSid || metadata.callSid || null,
+        message.timestamp || new Date().toISOString(),
+        new Date().toISOString(),
+      ],
+    });
+
+    return formatInboxItem(result.rows[0]);
+  } catch (error) {
+    console.error('[Inbox] Failed to record inbound message:', error);
+    return null;
+  }
+}
+
+/**
+ * List inbox items for a user, newest first
+ *
+ * @param {Object} db - LibSQL client
+ * @param {string} userId - User identifier
+ * @param {Object} filters - { status, channel, since, limit }
+ */
+export async function listInboxItems(db, userId, filters = {}) {
+  const { status, channel, since, limit = 100 } = filters;
+
+  let sql = 'SELECT * FROM inbox_items WHERE user_id = ?';
+  const args = [userId];
+
+  if (status) {
+    sql += ' AND status = ?';
+    args.push(status);
+  } else {
+    // Archived items are hidden unless explicitly requested
+    sql += " AND status != 'archived'";
+  }
+  if (channel) {
+    sql += ' AND channel = ?';
+    args.push(channel);
+  }
+  if (since) {
+    sql += ' AND datetime(updated_at) > datetime(?)';
+    args.push(since);
+  }
+
+  sql += ' ORDER BY received_at DESC LIMIT ?';
+  args.push(Math.min(Number(limit) || 100, 500));
+
+  const result = await db.execute({ sql, args });
+  return result.rows.map(formatInboxItem);
+}
+
+/**
+ * Update status (and optionally the sent reply) of one of a user's inbox items
+ */
+export async function updateInboxItem(db, itemId, userId, { status, reply_text }) {
+  const updates = [];
+  const args = [];
+
+  if (status !== undefined) {
+    updates.push('status = ?');
+    args.push(status);
+  }
+  if (reply_text !== undefined) {
+    updates.push('reply_text = ?', 'replied_at = ?');
+    args.push(reply_text, new Date().toISOString());
+  }
+
+  updates.push('updated_at = ?');
+  args.push(new Date().toISOString());
+  args.push(itemId, userId);
+
+  const result = await db.execute({
+    sql: `UPDATE inbox_items SET ${updates.join(', ')} WHERE id = ? AND user_id = ? RETURNING *`,
+    args,
+  });
+
+  return result.rows.length > 0 ? formatInboxItem(result.rows[0]) : null;
+}
+
+/**
+ * Get one of a user's inbox items (null for another user's)
+ */
+export async function getInboxItem(db, itemId, userId) {
+  const result = await db.execute({
+    sql: 'SELECT * FROM inbox_items WHERE id = ? AND user_id = ?',
+    args: [itemId, userId],
+  });
+  return result.rows.length > 0 ? formatInboxItem(result.rows[0]) : null;
+}
+
+/**
+ * Format inbox row
+ */
+export function formatInboxItem(row) {
+  return {
+    id: row.id,
+    user_id: row.user_id,
+    channel: row.channel,
+    sender: row.sender,
+    subject: row.subject,
+    content: row.content,
+    external_id: row.external_id,
+    status: row.status,
+    reply_text: row.reply_text,
+    received_at: row.received_at,
+    replied_at: row.replied_at,
+    created_at: row.created_at,
+    updated_at: row.updated_at,
+  };
+}
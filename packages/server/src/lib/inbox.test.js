@@ -0,0 +1,43 @@
+/**
+ * Tests for the unified inbox store (recording, listing filters and per-user updates)
+ */
+
+import { test } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, makeTestEnv } from '../test-env.js';
+import { getInboxDb, getInboxItem, listInboxItems, recordInboundMessage, updateInboxItem } from './inbox.js';
+
+const { env } = await makeTestEnv(SCHEMA.inbox);
+const db = getInboxDb(env);
+
+const sms = await recordInboundMessage(env, { channel: 'sms', from: '+15551234567', content: 'Running late' }, 'jo');
+const email = await recordInboundMessage(env, {
+  channel: 'email', from: 'sam@example.com', content: 'Agenda attached', metadata: { subject: 'Agenda' },
+}, 'jo');
+const kims = await recordInboundMessage(env, { channel: 'sms', from: '+15557654321', content: 'Hi' }, 'kim');
+
+test('recordInboundMessage - stores unread items and skips unknown channels', async () => {
+  assert.strictEqual(sms.status, 'unread');
+  assert.strictEqual(email.subject, 'Agenda');
+  assert.strictEqual(await recordInboundMessage(env, { channel: 'fax', content: 'x' }, 'jo'), null);
+});
+
+test('listInboxItems - one user, filtered by status and channel, archived hidden by default', async () => {
+  const ids = async (filters) => (await listInboxItems(db, 'jo', filters)).map((item) => item.id).sort();
+  assert.deepStrictEqual(await ids(), [sms.id, email.id].sort());
+  assert.deepStrictEqual(await ids({ channel: 'email' }), [email.id]);
+
+  await updateInboxItem(db, email.id, 'jo', { status: 'archived' });
+  assert.deepStrictEqual(await ids(), [sms.id]);
+  assert.deepStrictEqual(await ids({ status: 'archived' }), [email.id]);
+});
+
+test("updateInboxItem and getInboxItem - never reach another user's item", async () => {
+  assert.strictEqual(await updateInboxItem(db, kims.id, 'jo', { status: 'read' }), null);
+  assert.strictEqual(await getInboxItem(db, kims.id, 'jo'), null);
+  assert.strictEqual((await getInboxItem(db, kims.id, 'kim')).status, 'unread');
+
+  const replied = await updateInboxItem(db, sms.id, 'jo', { status: 'replied', reply_text: 'No problem' });
+  assert.strictEqual(replied.reply_text, 'No problem');
+  assert.ok(replied.replied_at);
+});
@@ -0,0 +1,103 @@
+/**
+ * Lists
+ *
+ * A user's named lists (shopping, todo...), kept by their local assistant
+ * and mirrored in the `list_items` table (scripts/migrate-lists.js) so
+ * every device sees the same items.
+ *
+ * Protocol: the assistant PUTs the items it changed since its last sync,
+ * each with the `updated_at` of the change; an item only overwrites the
+ * stored one when it's newer (last writer wins), so replaying a batch is
+ * harmless. It then GETs everything stored since its last `synced_at`.
+ * Removed items come back with deleted: true.
+ */
+
+import { createClient } from '@libsql/client';
+
+export const MAX_BATCH = 500;
+export const MAX_TEXT_LENGTH = 500;
+
+/**
+ * Create Turso client (singleton pattern)
+ */
+let dbClient = null;
+
+export function getListsDb(env) {
+  if (!dbClient) {
+    dbClient = createClient({
+      url: env.TURSO_DATABASE_URL,
+      authToken: env.TURSO_AUTH_TOKEN,
+    });
+  }
+  return dbClient;
+}
+
+function formatItem(row) {
+  return {
+    id: row.id,
+    list: row.list_name,
+    text: row.text,
+    done: Boolean(row.done),
+    deleted: Boolean(row.deleted),
+    updated_at: row.updated_at,
+  };
+}
+
+/**
+ * Why an item can't be stored, or null when it's fine
+ */
+export function validateItem(item) {
+  if (!item || typeof item !== 'object') return 'item must be an object';
+  if (!item.id || typeof item.id !== 'string') return 'id is required';
+  if (!item.list || typeof item.list !== 'string') return 'list is required';
+  if (typeof item.text !== 'string' || !item.text.trim()) return 'text is required';
+  if (item.text.length > MAX_TEXT_LENGTH) return `text is longer than ${MAX_TEXT_LENGTH} characters`;
+  if (!item.updated_at || Number.isNaN(Date.parse(item.updated_at))) return 'updated_at must be a timestamp';
+  return null;
+}
+
+/**
+ * Store items a device changed, keeping whichever version of each is newer
+ *
+ * @returns {Promise<{stored: number, synced_at: string}>} How many were newer than what the server had
+ */
+export async function upsertListItems(db, userId, items) {
+  const now = new Date().toISOString();
+  let stored = 0;
+  for (const item of items) {
+    const result = await db.execute({
+      sql: `
+        INSERT INTO list_items (id, user_id, list_name, text, done, deleted, updated_at, synced_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT (user_id, id) DO UPDATE SET
+          list_name = excluded.list_name, text = excluded.text, done = excluded.done,
+          deleted = excluded.deleted, updated_at = excluded.updated_at, synced_at = excluded.synced_at
+        WHERE datetime(excluded.updated_at) > datetime(list_items.updated_at)
+      `,
+      args: [
+        item.id, userId, item.list.trim().toLowerCase(), item.text.trim(),
+        item.done ? 1 : 0, item.deleted ? 1 : 0, item.updated_at, now,
+      ],
+    });
+    stored += result.rowsAffected;
+  }
+  return { stored, synced_at: now };
+}
+
+/**
+ * Items stored since `since` (all of them, deleted included, when it's missing)
+ */
+export async function listItemsSince(db, userId, since) {
+  const now = new Date().toISOString();
+  const result = since
+    ? await db.execute({
+        sql: `SELECT * FROM list_items WHERE user_id = ? AND synced_at > ? AND synced_at <= ?
+              ORDER BY synced_at ASC`,
+        args: [userId, since, now],
+      })
+    : await db.execute({
+        sql: 'SELECT * FROM list_items WHERE user_id = ? AND synced_at <= ? ORDER BY synced_at ASC',
+        args: [userId, now],
+      });
+  return { items: result.rows.map(formatItem), synced_at: now };
+}
@@ -0,0 +1,34 @@
+/**
+ * Map Links
+ *
+ * Links to an appointment's location for emails and API responses. The
+ * desktop assistant geocodes locations and syncs latitude/longitude with the
+ * appointment; with coordinates the link drops a pin, without them it
+ * searches for the location text.
+ */
+
+/**
+ * A coordinate pair when both values are valid numbers in range, else nulls.
+ */
+export function parseCoordinates(latitude, longitude) {
+  const lat = Number(latitude);
+  const lon = Number(longitude);
+  if (latitude == null || longitude == null || !Number.isFinite(lat) || !Number.isFinite(lon)
+    || Math.abs(lat) > 90 || Math.abs(lon) > 180) {
+    return { latitude: null, longitude: null };
+  }
+  return { latitude: lat, longitude: lon };
+}
+
+/**
+ * OpenStreetMap link for a location (null without one).
+ */
+export function mapLink(location, latitude = null, longitude = null) {
+  if (latitude != null && longitude != null) {
+    return `https://www.openstreetmap.org/?mlat=${latitude}&mlon=${longitude}#map=17/${latitude}/${longitude}`;
+  }
+  if (!location) {
+    return null;
+  }
+  return `https://www.openstreetmap.org/search?query=${encodeURIComponent(location)}`;
+}
@@ -69,6 +69,57 @@ export async function makeOutboundCall(toNumber, callbackUrl, env) {
 	}
 }
 
+/**
+ * Send an outbound SMS using Twilio API
+ *
+ * @param {string} toNumber - Phone number to text (E.164 format)
+ * @param {string} body - Message text
+ * @param {Object} env - Environment variables (TWILIO credentials)
+ * @returns {Promise<Object>} Message SID and status
+ */
+export async function sendSms(toNumber, body, env) {
+	const accountSid = env.TWILIO_ACCOUNT_SID;
+	const authToken = env.TWILIO_AUTH_TOKEN;
+	const fromNumber = env.TWILIO_PHONE_NUMBER;
+
+	if (!accountSid || !authToken || !fromNumber) {
+		throw new Error('Missing Twilio credentials in environment');
+	}
+
+	const url = `https://api.twilio.com/2010-04-01/Accounts/${accountSid}/Messages.json`;
+
+	const formData = new URLSearchParams();
+	formData.append('To', toNumber);
+	formData.append('From', fromNumber);
+	formData.append('Body', body);
+
+	const credentials = btoa(`${accountSid}:${authToken}`);
+
+	const response = await fetch(url, {
+		method: 'POST',
+		headers: {
+			'Authorization': `Basic ${credentials}`,
+			'Content-Type': 'application/x-www-form-urlencoded',
+		},
+		body: formData.toString(),
+	});
+
+	if (!response.ok) {
+		const errorText = await response.text();
+		throw new Error(`Twilio API error: ${response.status} - ${errorText}`);
+	}
+
+	const messageData = await response.json();
+	console.log(`✓ Outbound SMS sent: ${messageData.sid}`);
+
+	return {
+		messageSid: messageData.sid,
+		status: messageData.status,
+		to: toNumber,
+		from: fromNumber,
+	};
+}
+
 /**
  * Generate TwiML for MOSHI voice AI integration
  *
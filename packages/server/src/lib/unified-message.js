@@ -25,6 +25,7 @@ import {
   getHelpResponse
 } from './claude.js';
 import { getSupervisorClient } from './supervisor-client.js';
+import { recordInboundMessage } from './inbox.js';
 
 // =============================================================================
 // UNIFIED MESSAGE SCHEMA
@@ -309,6 +310,9 @@ export async function processUnifiedMessage(message, env) {
     };
   }
 
+  // Keep a copy in the unified inbox (SMS/email/voice only)
+  await recordInboundMessage(env, message, user.username || user.email);
+
   try {
     // Process message with AI/routing logic
     const responseMessage = await processMessageWithAI(user, content, channel, metadata, env);
@@ -0,0 +1,166 @@
+/**
+ * Rate Limiting Middleware
+ *
+ * Per-client request limits and payload size caps for the public webhook
+ * and auth endpoints, the emergency API and inbox replies. A body without a
+ * Content-Length (chunked) is counted as it's read (capRequestBody), so the
+ * cap holds either way. Counters live in isolate
+ * memory (fixed one-minute windows keyed by CF-Connecting-IP), so they
+ * bound bursts hitting one isolate rather than enforcing exact global
+ * quotas.
+ *
+ * Violations are logged with a [RateLimit] prefix (visible in `wrangler tail`).
+ */
+
+const WINDOW_MS = 60 * 1000;
+
+// First matching rule wins
+const RULES = [
+  { name: 'auth', match: (path) => path.startsWith('/auth/'), perMinute: 10, maxBytes: 16 * 1024 },
+  // Alerts text and call real people, so a few per minute is plenty
+  { name: 'emergency', match: (path) => path.startsWith('/api/emergency/'), perMinute: 10, maxBytes: 16 * 1024 },
+  // Replies go out as SMS or email from the user's own number and address
+  {
+    name: 'inbox-reply',
+    match: (path) => /^\/api\/inbox\/[^/]+\/reply$/.test(path),
+    perMinute: 30,
+    maxBytes: 16 * 1024,
+  },
+  { name: 'email-webhook', match: (path) => path === '/email/inbound', perMinute: 60, maxBytes: 10 * 1024 * 1024 },
+  {
+    name: 'webhook',
+    match: (path) =>
+      path.startsWith('/voice/') ||
+      path.startsWith('/sms/') ||
+      path === '/stripe/webhook' ||
+      path === '/marketing/webhook/sendgrid' ||
+      path === '/api/message',
+    perMinute: 120,
+    maxBytes: 256 * 1024,
+  },
+];
+
+// Stop tracking new clients past this many (the oldest windows are dropped first)
+const MAX_TRACKED_CLIENTS = 10000;
+
+const windows = new Map();
+
+function clientKey(request) {
+  return request.headers.get('CF-Connecting-IP') || request.headers.get('X-Forwarded-For')?.split(',')[0].trim() || 'unknown';
+}
+
+function limitedRule(request, path) {
+  const rule = RULES.find((r) => r.match(path));
+  return rule && request.method !== 'GET' && request.method !== 'OPTIONS' ? rule : null;
+}
+
+function tooLarge(rule, client, path, bytes) {
+  console.warn(`[RateLimit] ${rule.name}: ${client} sent ${bytes} bytes to ${path} (limit ${rule.maxBytes})`);
+  return new Response(JSON.stringify({
+    error: 'Payload Too Large',
+    max_bytes: rule.maxBytes,
+  }), {
+    status: 413,
+    headers: { 'Content-Type': 'application/json' },
+  });
+}
+
+function tooMany(retryAfter) {
+  return new Response(JSON.stringify({
+    error: 'Too Many Requests',
+    retry_after: retryAfter,
+  }), {
+    status: 429,
+    headers: { 'Content-Type': 'application/json', 'Retry-After': String(retryAfter) },
+  });
+}
+
+/**
+ * Check a request against the rate limit rules
+ * @param {Request} request
+ * @param {string} path - URL pathname
+ * @param {number} [now] - Current time in ms (for tests)
+ * @returns {Response|null} 413/429 response to return, or null to continue
+ */
+export function checkRateLimit(request, path, now = Date.now()) {
+  const rule = limitedRule(request, path);
+  if (!rule) {
+    return null;
+  }
+
+  const client = clientKey(request);
+
+  const length = parseInt(request.headers.get('Content-Length') || '0', 10);
+  if (length > rule.maxBytes) {
+    return tooLarge(rule, client, path, length);
+  }
+
+  const key = `${rule.name}:${client}`;
+  let window = windows.get(key);
+  if (!window || now - window.start >= WINDOW_MS) {
+    if (!window && windows.size >= MAX_TRACKED_CLIENTS) {
+      windows.delete(windows.keys().next().value);
+    }
+    window = { start: now, count: 0, reported: false };
+    windows.delete(key); // Re-insert so Map order tracks the newest windows
+    windows.set(key, window);
+  }
+
+  window.count += 1;
+  if (window.count > rule.perMinute) {
+    if (!window.reported) {
+      // One log line per client per window, so a flood doesn't become a log flood
+      console.warn(`[RateLimit] ${rule.name}: ${client} exceeded ${rule.perMinute} requests/min on ${path}`);
+      window.reported = true;
+    }
+    return tooMany(Math.ceil((window.start + WINDOW_MS - now) / 1000));
+  }
+
+  return null;
+}
+
+/**
+ * Read a body that has no Content-Length (chunked), stopping at the cap
+ *
+ * Call after checkRateLimit, which caps bodies that declare their length.
+ * @param {Request} request
+ * @param {string} path - URL pathname
+ * @returns {Promise<Request|Response>} The request to handle (its body buffered if it was read), or a 413
+ */
+export async function capRequestBody(request, path) {
+  const rule = limitedRule(request, path);
+  if (!rule || !request.body || request.headers.has('Content-Length')) {
+    return request;
+  }
+
+  const reader = request.body.getReader();
+  const chunks = [];
+  let size = 0;
+  for (;;) {
+    const { done, value } = await reader.read();
+    if (done) {
+      break;
+    }
+    size += value.byteLength;
+    if (size > rule.maxBytes) {
+      await reader.cancel();
+      return tooLarge(rule, clientKey(request), path, `more than ${rule.maxBytes}`);
+    }
+    chunks.push(value);
+  }
+
+  const body = new Uint8Array(size);
+  let offset = 0;
+  for (const chunk of chunks) {
+    body.set(chunk, offset);
+    offset += chunk.byteLength;
+  }
+  return new Request(request, { body });
+}
+
+/**
+ * Reset all counters (tests)
+ */
+export function resetRateLimits() {
+  windows.clear();
+}
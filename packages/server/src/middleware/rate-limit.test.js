@@ -0,0 +1,60 @@
+/**
+ * Tests for rate limits and payload caps (declared and chunked bodies)
+ */
+
+import { test, beforeEach } from 'node:test';
+import assert from 'node:assert';
+import { capRequestBody, checkRateLimit, resetRateLimits } from './rate-limit.js';
+
+const NOW = Date.parse('2026-10-16T12:00:00Z');
+const REPLY = '/api/inbox/abc123/reply';
+
+function post(path, body, headers = {}) {
+  return new Request(`https://xswarm.test${path}`, {
+    method: 'POST', body, headers: { 'CF-Connecting-IP': '203.0.113.7', ...headers },
+  });
+}
+
+// A body sent in chunks, with no Content-Length
+function chunked(path, chunks) {
+  const stream = new ReadableStream({
+    start(controller) {
+      for (const chunk of chunks) controller.enqueue(new TextEncoder().encode(chunk));
+      controller.close();
+    },
+  });
+  return new Request(`https://xswarm.test${path}`, { method: 'POST', body: stream, duplex: 'half' });
+}
+
+beforeEach(() => resetRateLimits());
+
+test('a declared Content-Length over the cap is refused before reading', () => {
+  const response = checkRateLimit(post(REPLY, 'x', { 'Content-Length': String(16 * 1024 + 1) }), REPLY, NOW);
+  assert.strictEqual(response.status, 413);
+  assert.strictEqual(checkRateLimit(post(REPLY, 'x', { 'Content-Length': '1' }), REPLY, NOW), null);
+});
+
+test('a chunked body is capped as it is read', async () => {
+  const tooBig = await capRequestBody(chunked(REPLY, Array(20).fill('x'.repeat(1024))), REPLY);
+  assert.ok(tooBig instanceof Response);
+  assert.deepStrictEqual([tooBig.status, (await tooBig.json()).max_bytes], [413, 16 * 1024]);
+
+  const small = await capRequestBody(chunked(REPLY, ['{"text": ', '"On my way"}']), REPLY);
+  assert.ok(small instanceof Request);
+  assert.deepStrictEqual(await small.json(), { text: 'On my way' });
+});
+
+test('other routes and GETs pass through untouched', async () => {
+  const request = chunked('/api/inbox', ['x'.repeat(32 * 1024)]);
+  assert.strictEqual(await capRequestBody(request, '/api/inbox'), request);
+  assert.strictEqual(checkRateLimit(new Request(`https://xswarm.test${REPLY}`), REPLY, NOW), null);
+});
+
+test('inbox replies are limited per client per minute', () => {
+  for (let i = 0; i < 30; i++) {
+    assert.strictEqual(checkRateLimit(post(REPLY, '{}'), REPLY, NOW), null);
+  }
+  const limited = checkRateLimit(post(REPLY, '{}'), REPLY, NOW);
+  assert.strictEqual(limited.status, 429);
+  assert.strictEqual(checkRateLimit(post(REPLY, '{}'), REPLY, NOW + 60 * 1000), null);
+});
@@ -0,0 +1,210 @@
+/**
+ * Webhook Signature Middleware
+ *
+ * Verifies that inbound webhooks come from who they claim to before any
+ * route sees them:
+ *
+ * - Twilio (/voice/*, /sms/*): X-Twilio-Signature, HMAC-SHA1 of the URL and
+ *   sorted form params with TWILIO_AUTH_TOKEN - or TWILIO_AUTH_TOKEN_SECONDARY
+ *   while the token is being rotated. Twilio sends no timestamp, so replays
+ *   are caught by its I-Twilio-Idempotency-Token instead.
+ * - xSwarm-signed callbacks (/email/inbound, /marketing/webhook/sendgrid,
+ *   relays and forwarders): X-Xswarm-Timestamp (unix seconds) and
+ *   X-Xswarm-Signature "v1=<hex HMAC-SHA256 of `${timestamp}.${path}.${body}`>".
+ *   WEBHOOK_SIGNING_SECRETS is a comma-separated list: senders sign with the
+ *   first, any of them verifies - add the new secret first, move senders
+ *   over, then drop the old one. Timestamps more than WINDOW_SECONDS off are
+ *   rejected, and so is a signature already seen within the window.
+ * - Stripe verifies its own signature (with a timestamp tolerance) in
+ *   routes/stripe.js.
+ *
+ * Failures are rejected (401, or 409 for a replay), and so is a path whose
+ * secrets aren't set (401, counted as unconfigured) - an unverifiable
+ * webhook is never let through. Twilio paths are always enforced, as
+ * routes/voice.js did before this middleware. For xSwarm-signed paths only,
+ * WEBHOOK_VERIFY=report logs and counts failures instead of rejecting them
+ * while senders are moved over (SendGrid's Inbound Parse can't sign - it has
+ * to go through a relay first). Counts are in /health under `webhooks`.
+ *
+ * Replay memory lives in the isolate, like the rate limiter's counters.
+ */
+
+import { constantTimeEqual, verifyTwilioSignature } from '../lib/auth.js';
+
+export const WINDOW_SECONDS = 5 * 60;
+
+// Stop remembering new signatures past this many (the oldest are dropped first)
+const MAX_REMEMBERED = 10000;
+
+const SCHEMES = [
+  {
+    name: 'twilio',
+    match: (path) => (path.startsWith('/voice/') || path.startsWith('/sms/')) && path !== '/voice/moshi',
+    enforced: () => true,
+  },
+  {
+    name: 'xswarm',
+    match: (path) => path === '/email/inbound' || path === '/marketing/webhook/sendgrid',
+    enforced: (env) => env.WEBHOOK_VERIFY !== 'report',
+  },
+];
+
+const seen = new Map(); // Signature or idempotency token -> ms it can be forgotten
+const metrics = {
+  verified: 0,
+  reported: 0, // Failures let through by WEBHOOK_VERIFY=report
+  rejected: { unconfigured: 0, missing: 0, bad_signature: 0, stale: 0, replayed: 0 },
+};
+
+/**
+ * Verification counts since the isolate started (for /health)
+ * @returns {Object}
+ */
+export function webhookMetrics() {
+  return { ...metrics, rejected: { ...metrics.rejected } };
+}
+
+/**
+ * Forget remembered signatures and zero the counts (for tests)
+ */
+export function resetWebhookState() {
+  seen.clear();
+  metrics.verified = 0;
+  metrics.reported = 0;
+  for (const reason of Object.keys(metrics.rejected)) {
+    metrics.rejected[reason] = 0;
+  }
+}
+
+async function hmacSha256Hex(secret, data) {
+  const encoder = new TextEncoder();
+  const key = await crypto.subtle.importKey('raw', encoder.encode(secret), { name: 'HMAC', hash: 'SHA-256' }, false, ['sign']);
+  const signature = await crypto.subtle.sign('HMAC', key, encoder.encode(data));
+  return [...new Uint8Array(signature)].map((b) => b.toString(16).padStart(2, '0')).join('');
+}
+
+function secretsFrom(env) {
+  return (env.WEBHOOK_SIGNING_SECRETS || '').split(',').map((s) => s.trim()).filter(Boolean);
+}
+
+/**
+ * Headers that sign a callback to an xSwarm webhook path, with the current (first) secret
+ * @param {Object} env - Environment with WEBHOOK_SIGNING_SECRETS
+ * @param {string} path - URL pathname it's sent to
+ * @param {string} body - Raw request body
+ * @param {number} [now] - Current time in ms (for tests)
+ * @returns {Promise<Object>} X-Xswarm-Timestamp and X-Xswarm-Signature
+ */
+export async function signWebhook(env, path, body, now = Date.now()) {
+  const [secret] = secretsFrom(env);
+  if (!secret) {
+    throw new Error('WEBHOOK_SIGNING_SECRETS is not set');
+  }
+  const timestamp = String(Math.floor(now / 1000));
+  return {
+    'X-Xswarm-Timestamp': timestamp,
+    'X-Xswarm-Signature': `v1=${await hmacSha256Hex(secret, `${timestamp}.${path}.${body}`)}`,
+  };
+}
+
+/**
+ * Remember a signature for the replay window; false if it was already seen
+ */
+function firstSighting(key, now) {
+  for (const [k, expires] of seen) {
+    if (expires > now && seen.size < MAX_REMEMBERED) {
+      break; // Insertion order: the rest expire later
+    }
+    seen.delete(k);
+  }
+  if (seen.has(key)) {
+    return false;
+  }
+  seen.set(key, now + WINDOW_SECONDS * 1000);
+  return true;
+}
+
+async function checkXswarm(request, path, body, env, now) {
+  const secrets = secretsFrom(env);
+  if (!secrets.length) {
+    return 'unconfigured';
+  }
+  const timestamp = request.headers.get('X-Xswarm-Timestamp');
+  const header = request.headers.get('X-Xswarm-Signature');
+  if (!timestamp || !header) {
+    return 'missing';
+  }
+  if (!/^\d+$/.test(timestamp) || Math.abs(now / 1000 - Number(timestamp)) > WINDOW_SECONDS) {
+    return 'stale';
+  }
+  const given = header.split(',').map((part) => part.trim()).filter((part) => part.startsWith('v1=')).map((part) => part.slice(3));
+  for (const secret of secrets) {
+    const expected = await hmacSha256Hex(secret, `${timestamp}.${path}.${body}`);
+    const match = given.find((signature) => constantTimeEqual(signature, expected));
+    if (match) {
+      return firstSighting(`xswarm:${match}`, now) ? 'verified' : 'replayed';
+    }
+  }
+  return 'bad_signature';
+}
+
+async function checkTwilio(request, body, env, now) {
+  const tokens = [env.TWILIO_AUTH_TOKEN, env.TWILIO_AUTH_TOKEN_SECONDARY].filter(Boolean);
+  if (!tokens.length) {
+    return 'unconfigured';
+  }
+  const signature = request.headers.get('X-Twilio-Signature');
+  if (!signature) {
+    return 'missing';
+  }
+  const params = Object.fromEntries(new URLSearchParams(body));
+  for (const token of tokens) {
+    if (await verifyTwilioSignature(request.url, params, signature, token)) {
+      const idempotency = request.headers.get('I-Twilio-Idempotency-Token');
+      return !idempotency || firstSighting(`twilio:${idempotency}`, now) ? 'verified' : 'replayed';
+    }
+  }
+  return 'bad_signature';
+}
+
+/**
+ * Verify an inbound webhook's signature
+ * @param {Request} request
+ * @param {string} path - URL pathname
+ * @param {Object} env - Environment (secrets, and WEBHOOK_VERIFY=report for xSwarm paths)
+ * @param {number} [now] - Current time in ms (for tests)
+ * @returns {Promise<Response|null>} 401/409 response to return, or null to continue
+ */
+export async function checkWebhookSignature(request, path, env, now = Date.now()) {
+  const scheme = SCHEMES.find((s) => s.match(path));
+  if (!scheme || request.method !== 'POST') {
+    return null;
+  }
+
+  const body = await request.clone().text();
+  const result = scheme.name === 'twilio'
+    ? await checkTwilio(request, body, env, now)
+    : await checkXswarm(request, path, body, env, now);
+
+  if (result === 'verified') {
+    metrics.verified++;
+    return null;
+  }
+  const enforce = scheme.enforced(env);
+  console.warn(`[WebhookSignature] ${scheme.name} ${path}: ${result}${enforce ? '' : ' (report only)'}`);
+  if (!enforce) {
+    metrics.reported++;
+    return null;
+  }
+  metrics.rejected[result]++;
+  return new Response(JSON.stringify({
+    error: {
+      replayed: 'Webhook already received',
+      unconfigured: 'Webhook verification is not configured',
+    }[result] || 'Invalid webhook signature',
+    reason: result,
+  }), {
+    status: result === 'replayed' ? 409 : 401,
+    headers: { 'Content-Type': 'application/json' },
+  });
+}
@@ -0,0 +1,90 @@
+/**
+ * Tests for webhook signature verification (timestamp window, replays, key rotation)
+ */
+
+import { test, beforeEach } from 'node:test';
+import assert from 'node:assert';
+import crypto from 'node:crypto';
+import { checkWebhookSignature, resetWebhookState, signWebhook, webhookMetrics } from './webhook-signature.js';
+
+const NOW = Date.parse('2026-10-16T12:00:00Z');
+const ENFORCE = { WEBHOOK_SIGNING_SECRETS: 'new-secret, old-secret', WEBHOOK_VERIFY: 'enforce' };
+const BODY = 'from=alice%40example.com&subject=Invoice';
+
+function post(path, body, headers = {}) {
+  return new Request(`https://hooks.example.com${path}`, { method: 'POST', body, headers });
+}
+
+async function status(request, env = ENFORCE, now = NOW) {
+  const response = await checkWebhookSignature(request, new URL(request.url).pathname, env, now);
+  return response ? response.status : 200;
+}
+
+beforeEach(() => resetWebhookState());
+
+test('xSwarm signature - verified once, replays and tampering rejected', async () => {
+  const headers = await signWebhook(ENFORCE, '/email/inbound', BODY, NOW);
+  assert.strictEqual(await status(post('/email/inbound', BODY, headers)), 200);
+  assert.strictEqual(await status(post('/email/inbound', BODY, headers)), 409);
+  assert.strictEqual(await status(post('/email/inbound', `${BODY}&to=mallory`, headers)), 401);
+  // Signed for one path, sent to another
+  const other = await signWebhook(ENFORCE, '/marketing/webhook/sendgrid', BODY, NOW);
+  assert.strictEqual(await status(post('/email/inbound', BODY, other)), 401);
+  assert.strictEqual(await status(post('/email/inbound', BODY)), 401);
+  assert.deepStrictEqual(webhookMetrics().rejected,
+    { unconfigured: 0, missing: 1, bad_signature: 2, stale: 0, replayed: 1 });
+});
+
+test('xSwarm signature - timestamps outside the window are rejected', async () => {
+  const old = await signWebhook(ENFORCE, '/email/inbound', BODY, NOW - 6 * 60 * 1000);
+  assert.strictEqual(await status(post('/email/inbound', BODY, old)), 401);
+  const early = await signWebhook(ENFORCE, '/email/inbound', BODY, NOW + 4 * 60 * 1000);
+  assert.strictEqual(await status(post('/email/inbound', BODY, early)), 200);
+});
+
+test('Key rotation - either secret verifies while both are listed', async () => {
+  const oldSender = await signWebhook({ WEBHOOK_SIGNING_SECRETS: 'old-secret' }, '/email/inbound', BODY, NOW);
+  assert.strictEqual(await status(post('/email/inbound', BODY, oldSender)), 200);
+  const retired = { ...ENFORCE, WEBHOOK_SIGNING_SECRETS: 'new-secret' };
+  const later = await signWebhook({ WEBHOOK_SIGNING_SECRETS: 'old-secret' }, '/email/inbound', BODY, NOW + 1000);
+  assert.strictEqual(await status(post('/email/inbound', BODY, later), retired, NOW + 1000), 401);
+});
+
+test('Twilio - primary or secondary auth token, idempotency token replays rejected', async () => {
+  const env = { TWILIO_AUTH_TOKEN: 'primary', TWILIO_AUTH_TOKEN_SECONDARY: 'secondary', WEBHOOK_VERIFY: 'enforce' };
+  const body = 'To=%2B15550001&From=%2B15550002&Body=hi';
+  const url = 'https://hooks.example.com/sms/inbound';
+  const data = url + 'Bodyhi' + 'From+15550002' + 'To+15550001';
+  const sign = (token) => crypto.createHmac('sha1', token).update(data).digest('base64');
+
+  const headers = { 'X-Twilio-Signature': sign('secondary'), 'I-Twilio-Idempotency-Token': 'abc' };
+  assert.strictEqual(await status(post('/sms/inbound', body, headers), env), 200);
+  assert.strictEqual(await status(post('/sms/inbound', body, headers), env), 409);
+  assert.strictEqual(await status(post('/sms/inbound', body, { 'X-Twilio-Signature': sign('primary') }), env), 200);
+  assert.strictEqual(await status(post('/sms/inbound', body, { 'X-Twilio-Signature': sign('stolen') }), env), 401);
+});
+
+test('Report mode lets xSwarm failures through, counted; missing secrets are rejected', async () => {
+  const report = { WEBHOOK_SIGNING_SECRETS: 'new-secret', WEBHOOK_VERIFY: 'report' };
+  assert.strictEqual(await status(post('/email/inbound', BODY), report), 200);
+  assert.strictEqual(await status(post('/email/inbound', BODY), { WEBHOOK_SIGNING_SECRETS: 'new-secret' }), 401);
+  assert.strictEqual(await status(post('/email/inbound', BODY), {}), 401);
+  assert.strictEqual(await status(post('/api/tasks', BODY), ENFORCE), 200); // Not a webhook path
+  const metrics = webhookMetrics();
+  assert.strictEqual(metrics.reported, 1);
+  assert.strictEqual(metrics.rejected.missing, 1);
+  assert.strictEqual(metrics.rejected.unconfigured, 1);
+});
+
+test('Twilio - enforced by default, even in report mode, and without an auth token', async () => {
+  const body = 'To=%2B15550001&From=%2B15550002';
+  const forged = { 'X-Twilio-Signature': 'forged' };
+  assert.strictEqual(await status(post('/voice/u1', body, forged), { TWILIO_AUTH_TOKEN: 'primary' }), 401);
+  const report = { TWILIO_AUTH_TOKEN: 'primary', WEBHOOK_VERIFY: 'report' };
+  assert.strictEqual(await status(post('/voice/u1', body), report), 401);
+  const response = await checkWebhookSignature(post('/sms/inbound', body, forged), '/sms/inbound', {}, NOW);
+  assert.strictEqual(response.status, 401);
+  assert.strictEqual((await response.json()).reason, 'unconfigured');
+  assert.deepStrictEqual(webhookMetrics().rejected,
+    { unconfigured: 1, missing: 1, bad_signature: 1, stale: 0, replayed: 0 });
+});
@@ -5,6 +5,8 @@
  */
 
 import { generateBossIntroTwiML, generateBossResponseTwiML, generateMoshiTwiML, makeOutboundCall } from '../lib/outbound.js';
+import { isScreeningEnabled } from '../lib/call-screening.js';
+import { startCallScreening } from './call-screening.js';
 import usersConfig from '../config/users.json' with { type: 'json' };
 
 /**
@@ -37,7 +39,8 @@ async function getAuthorizedUser(callerNumber) {
  * Handle inbound calls to Boss
  *
  * This is the webhook URL set in Twilio for incoming calls.
- * SECURITY: Only authorized phone numbers can access Boss.
+ * SECURITY: Only authorized phone numbers can access Boss. Other callers
+ * are screened (see call-screening.js) or rejected.
  */
 export async function handleInboundCall(request, env) {
 	try {
@@ -51,6 +54,14 @@ export async function handleInboundCall(request, env) {
 		// Security check: Is this caller authorized?
 		const user = await getAuthorizedUser(from);
 
+		if (!user && isScreeningEnabled(env)) {
+			// Unknown caller - answer, ask their purpose and let the owner decide
+			const screening = await startCallScreening(formData, env);
+			if (screening) {
+				return screening;
+			}
+		}
+
 		if (!user) {
 			console.warn(`Unauthorized call attempt from: ${from}`);
 
@@ -10,6 +10,31 @@
  */
 
 import { createClient } from '@libsql/client';
+import {
+  listParticipants,
+  publicParticipant,
+  sendInvitations,
+  syncParticipants,
+} from '../lib/appointment-participants.js';
+import { notifyCalendarChange } from '../lib/calendar-hub.js';
+import { conditionalJson, lastModified } from '../lib/conditional.js';
+import { mapLink, parseCoordinates } from '../lib/maps.js';
+
+// Largest batch accepted by the bulk endpoints (calendar sync, recurring instances)
+const MAX_BATCH_SIZE = 500;
+
+// Matches rows whose JSON `tags` array contains the bound tag (?tag= filters)
+const TAG_FILTER_SQL = 'EXISTS (SELECT 1 FROM json_each(tags) WHERE json_each.value = ?)';
+
+// Journal rows per /api/calendar/changes page (default and most a client can ask for)
+const CHANGES_PAGE = 500;
+const MAX_CHANGES_PAGE = 1000;
+
+// Entities in the calendar_changes journal
+const JOURNALED = {
+  appointment: { table: 'appointments', format: formatAppointment },
+  reminder: { table: 'reminders', format: formatReminder },
+};
 
 /**
  * Create Turso client (singleton pattern)
@@ -41,8 +66,12 @@ export async function createAppointment(request, env) {
       end_time,
       timezone = 'UTC',
       location,
+      latitude,
+      longitude,
       recurrence_rule,
       participants = [],
+      tags = [],
+      send_invitations = false,
     } = body;
 
     // Validate required fields
@@ -86,9 +115,9 @@ export async function createAppointment(request, env) {
       sql: `
         INSERT INTO appointments (
           id, user_id, title, description, start_time, end_time,
-          timezone, location, recurrence_rule, participants,
+          timezone, location, latitude, longitude, recurrence_rule, participants, tags,
           status, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING *
       `,
       args: [
@@ -100,17 +129,38 @@ export async function createAppointment(request, env) {
         end_time,
         timezone,
         location || null,
+        ...Object.values(parseCoordinates(latitude, longitude)),
         recurrence_rule || null,
         JSON.stringify(participants),
+        JSON.stringify(normalizeTags(tags)),
         'scheduled',
         now,
       ],
     });
 
+    const appointment = formatAppointment(result.rows[0]);
+    await notifyCalendarChange(env, user_id, { entity: 'appointment', op: 'upsert' });
+
+    // Invitations are best-effort: the appointment exists either way
+    let invitations = null;
+    if (participants.length > 0) {
+      try {
+        if (send_invitations) {
+          invitations = await sendInvitations(env, appointment);
+        } else {
+          await syncParticipants(env, id, participants);
+        }
+      } catch (error) {
+        console.error('Error inviting participants:', error);
+        invitations = { sent: [], skipped: [], failed: [{ label: '*', error: error.message }] };
+      }
+    }
+
     return new Response(
       JSON.stringify({
         success: true,
-        appointment: formatAppointment(result.rows[0]),
+        appointment,
+        invitations,
       }),
       { status: 201, headers: { 'Content-Type': 'application/json' } }
     );
@@ -125,7 +175,10 @@ export async function createAppointment(request, env) {
 
 /**
  * Get appointments for a user
- * GET /api/calendar/appointments?user_id=xxx&start=xxx&end=xxx
+ * GET /api/calendar/appointments?user_id=xxx&start=xxx&end=xxx&tag=xxx
+ *
+ * Like the today/week schedules, answers If-None-Match/If-Modified-Since
+ * with 304 when nothing changed (lib/conditional.js).
  */
 export async function getAppointments(request, env) {
   try {
@@ -134,6 +187,7 @@ export async function getAppointments(request, env) {
     const start = url.searchParams.get('start');
     const end = url.searchParams.get('end');
     const status = url.searchParams.get('status') || 'scheduled';
+    const tag = url.searchParams.get('tag');
 
     if (!user_id) {
       return new Response(
@@ -157,17 +211,18 @@ export async function getAppointments(request, env) {
       sql += ' AND datetime(end_time) <= datetime(?)';
       args.push(end);
     }
+    if (tag) {
+      sql += ` AND ${TAG_FILTER_SQL}`;
+      args.push(normalizeTags([tag])[0]);
+    }
 
     sql += ' ORDER BY start_time ASC';
 
     const result = await db.execute({ sql, args });
 
-    return new Response(
-      JSON.stringify({
-        appointments: result.rows.map(formatAppointment),
-      }),
-      { status: 200, headers: { 'Content-Type': 'application/json' } }
-    );
+    return await conditionalJson(request, {
+      appointments: result.rows.map(formatAppointment),
+    }, lastModified(result.rows));
   } catch (error) {
     console.error('Error getting appointments:', error);
     return new Response(
@@ -200,12 +255,9 @@ export async function getTodaySchedule(request, env) {
       args: [user_id],
     });
 
-    return new Response(
-      JSON.stringify({
-        schedule: result.rows.map(formatAppointment),
-      }),
-      { status: 200, headers: { 'Content-Type': 'application/json' } }
-    );
+    return await conditionalJson(request, {
+      schedule: result.rows.map(formatAppointment),
+    }, lastModified(result.rows));
   } catch (error) {
     console.error('Error getting today schedule:', error);
     return new Response(
@@ -238,16 +290,105 @@ export async function getWeekSchedule(request, env) {
       args: [user_id],
     });
 
+    return await conditionalJson(request, {
+      schedule: result.rows.map(formatAppointment),
+    }, lastModified(result.rows));
+  } catch (error) {
+    console.error('Error getting week schedule:', error);
     return new Response(
-      JSON.stringify({
-        schedule: result.rows.map(formatAppointment),
-      }),
+      JSON.stringify({ error: 'Failed to get week schedule' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Appointment and reminder changes since a cursor (delta sync)
+ * GET /api/calendar/changes?user_id=xxx&since=<seq>&limit=500
+ *
+ * Reads the calendar_changes journal (scripts/migrate-calendar-changes.js).
+ * Returns { changes: [{ seq, entity, id, op: 'upsert'|'delete', data }],
+ * cursor, has_more }: each entity once with its current state (or a delete
+ * when it's gone), in journal order. Pass `cursor` back as `since` until
+ * has_more is false. `reset: true` means the cursor isn't from this journal
+ * and the client should start over from 0.
+ */
+export async function getCalendarChanges(request, env) {
+  try {
+    const url = new URL(request.url);
+    const user_id = url.searchParams.get('user_id');
+    const since = parseInt(url.searchParams.get('since') || '0', 10);
+    const limit = Math.min(Math.max(parseInt(url.searchParams.get('limit') || '', 10) || CHANGES_PAGE, 1), MAX_CHANGES_PAGE);
+
+    if (!user_id) {
+      return new Response(
+        JSON.stringify({ error: 'Missing user_id parameter' }),
+        { status: 400, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+    if (Number.isNaN(since) || since < 0) {
+      return new Response(
+        JSON.stringify({ error: 'since must be a non-negative sequence number' }),
+        { status: 400, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const db = getDbClient(env);
+
+    const latest = await db.execute('SELECT COALESCE(MAX(seq), 0) AS seq FROM calendar_changes');
+    if (since > Number(latest.rows[0].seq)) {
+      return new Response(
+        JSON.stringify({ changes: [], cursor: 0, has_more: true, reset: true }),
+        { status: 200, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const journal = await db.execute({
+      sql: 'SELECT seq, entity, entity_id, op FROM calendar_changes WHERE user_id = ? AND seq > ? ORDER BY seq ASC LIMIT ?',
+      args: [user_id, since, limit + 1],
+    });
+    const hasMore = journal.rows.length > limit;
+    const page = journal.rows.slice(0, limit);
+    const cursor = page.length ? Number(page[page.length - 1].seq) : since;
+
+    // An entity changed several times in this page is sent once, at its last position
+    const byEntity = new Map();
+    for (const row of page) {
+      const key = `${row.entity}:${row.entity_id}`;
+      byEntity.delete(key);
+      byEntity.set(key, row);
+    }
+
+    const current = {};
+    for (const [entity, { table, format }] of Object.entries(JOURNALED)) {
+      const ids = [...byEntity.values()].filter((row) => row.entity === entity && row.op === 'upsert').map((row) => row.entity_id);
+      current[entity] = new Map();
+      if (ids.length) {
+        const result = await db.execute({
+          sql: `SELECT * FROM ${table} WHERE user_id = ? AND id IN (${ids.map(() => '?').join(', ')})`,
+          args: [user_id, ...ids],
+        });
+        for (const record of result.rows) {
+          current[entity].set(record.id, format(record));
+        }
+      }
+    }
+
+    const changes = [...byEntity.values()].map((row) => {
+      const data = current[row.entity]?.get(row.entity_id);
+      return data
+        ? { seq: Number(row.seq), entity: row.entity, id: row.entity_id, op: 'upsert', data }
+        : { seq: Number(row.seq), entity: row.entity, id: row.entity_id, op: 'delete' };
+    });
+
+    return new Response(
+      JSON.stringify({ changes, cursor, has_more: hasMore }),
       { status: 200, headers: { 'Content-Type': 'application/json' } }
     );
   } catch (error) {
-    console.error('Error getting week schedule:', error);
+    console.error('Error getting calendar changes:', error);
     return new Response(
-      JSON.stringify({ error: 'Failed to get week schedule' }),
+      JSON.stringify({ error: 'Failed to get calendar changes' }),
       { status: 500, headers: { 'Content-Type': 'application/json' } }
     );
   }
@@ -267,8 +408,11 @@ export async function updateAppointment(request, env, appointmentId) {
       end_time,
       timezone,
       location,
+      latitude,
+      longitude,
       recurrence_rule,
       participants,
+      tags,
       status,
     } = body;
 
@@ -303,6 +447,14 @@ export async function updateAppointment(request, env, appointmentId) {
       updates.push('location = ?');
       args.push(location);
     }
+    if (latitude !== undefined || longitude !== undefined) {
+      // Set together; a new location without coordinates clears the old pin
+      const coordinates = parseCoordinates(latitude, longitude);
+      updates.push('latitude = ?', 'longitude = ?');
+      args.push(coordinates.latitude, coordinates.longitude);
+    } else if (location !== undefined) {
+      updates.push('latitude = NULL', 'longitude = NULL');
+    }
     if (recurrence_rule !== undefined) {
       updates.push('recurrence_rule = ?');
       args.push(recurrence_rule);
@@ -311,6 +463,10 @@ export async function updateAppointment(request, env, appointmentId) {
       updates.push('participants = ?');
       args.push(JSON.stringify(participants));
     }
+    if (tags !== undefined) {
+      updates.push('tags = ?');
+      args.push(JSON.stringify(normalizeTags(tags)));
+    }
     if (status !== undefined) {
       updates.push('status = ?');
       args.push(status);
@@ -338,6 +494,12 @@ export async function updateAppointment(request, env, appointmentId) {
       );
     }
 
+    // Newly added participants start out pending (invite them via /invitations)
+    if (participants !== undefined) {
+      await syncParticipants(env, appointmentId, participants);
+    }
+    await notifyCalendarChange(env, result.rows[0].user_id, { entity: 'appointment', op: 'upsert' });
+
     return new Response(
       JSON.stringify({
         success: true,
@@ -373,6 +535,7 @@ export async function deleteAppointment(request, env, appointmentId) {
         { status: 404, headers: { 'Content-Type': 'application/json' } }
       );
     }
+    await notifyCalendarChange(env, result.rows[0].user_id, { entity: 'appointment', op: 'delete' });
 
     return new Response(
       JSON.stringify({ success: true }),
@@ -387,6 +550,228 @@ export async function deleteAppointment(request, env, appointmentId) {
   }
 }
 
+/**
+ * List an appointment's participants with their RSVP status
+ * GET /api/calendar/appointments/:id/participants
+ */
+export async function getAppointmentParticipants(request, env, appointmentId) {
+  try {
+    const participants = await listParticipants(env, appointmentId);
+
+    return new Response(
+      JSON.stringify({ participants: participants.map(publicParticipant) }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    console.error('Error getting participants:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to get participants' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Send invitations to an appointment's participants
+ * POST /api/calendar/appointments/:id/invitations
+ * Body: { resend = false } - resend also re-invites participants who haven't replied
+ */
+export async function inviteAppointmentParticipants(request, env, appointmentId) {
+  try {
+    const body = await request.json().catch(() => ({}));
+    const db = getDbClient(env);
+
+    const result = await db.execute({
+      sql: 'SELECT * FROM appointments WHERE id = ?',
+      args: [appointmentId],
+    });
+
+    if (result.rows.length === 0) {
+      return new Response(
+        JSON.stringify({ error: 'Appointment not found' }),
+        { status: 404, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const appointment = formatAppointment(result.rows[0]);
+    const invitations = await sendInvitations(env, appointment, { resend: Boolean(body.resend) });
+    const participants = await listParticipants(env, appointmentId);
+
+    return new Response(
+      JSON.stringify({
+        success: true,
+        invitations,
+        participants: participants.map(publicParticipant),
+      }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    console.error('Error sending invitations:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to send invitations' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Create many appointments in one request
+ * POST /api/calendar/appointments/batch
+ * Body: { user_id, appointments: [...], allow_conflicts = false }
+ *
+ * Items are validated and conflict-checked individually; the valid ones are
+ * inserted in a single transaction. Rejected items are reported by index.
+ */
+export async function createAppointmentsBatch(request, env) {
+  try {
+    const body = await request.json();
+    const { user_id, appointments, allow_conflicts = false } = body;
+
+    const invalid = validateBatch(user_id, appointments, 'appointments');
+    if (invalid) return invalid;
+
+    const db = getDbClient(env);
+    const now = new Date().toISOString();
+    const errors = [];
+    const conflicts = [];
+
+    const valid = [];
+    appointments.forEach((appointment, index) => {
+      if (!appointment.title || !appointment.start_time || !appointment.end_time) {
+        errors.push({ index, error: 'Missing required fields: title, start_time, end_time' });
+      } else if (isNaN(Date.parse(appointment.start_time)) || isNaN(Date.parse(appointment.end_time))) {
+        errors.push({ index, error: 'Invalid start_time or end_time' });
+      } else {
+        valid.push({ index, appointment });
+      }
+    });
+
+    // One conflict query spanning the whole batch instead of one per item
+    let booked = [];
+    if (!allow_conflicts && valid.length > 0) {
+      const earliest = new Date(Math.min(...valid.map((v) => Date.parse(v.appointment.start_time)))).toISOString();
+      const latest = new Date(Math.max(...valid.map((v) => Date.parse(v.appointment.end_time)))).toISOString();
+      const existing = await db.execute({
+        sql: `
+          SELECT * FROM appointments
+          WHERE user_id = ?
+            AND status = 'scheduled'
+            AND datetime(start_time) < datetime(?)
+            AND datetime(end_time) > datetime(?)
+        `,
+        args: [user_id, latest, earliest],
+      });
+      booked = existing.rows.map(formatAppointment);
+    }
+
+    const accepted = [];
+    for (const { index, appointment } of valid) {
+      if (!allow_conflicts) {
+        const start = Date.parse(appointment.start_time);
+        const end = Date.parse(appointment.end_time);
+        const overlapping = booked.filter((b) => Date.parse(b.start_time) < end && Date.parse(b.end_time) > start);
+        if (overlapping.length > 0) {
+          conflicts.push({ index, conflicts: overlapping });
+          continue;
+        }
+        // Later items in the batch must not overlap earlier ones either
+        booked.push(appointment);
+      }
+      accepted.push(appointment);
+    }
+
+    const statements = accepted.map((appointment) => ({
+      sql: `
+        INSERT INTO appointments (
+          id, user_id, title, description, start_time, end_time,
+          timezone, location, latitude, longitude, recurrence_rule, participants, tags,
+          status, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        RETURNING *
+      `,
+      args: [
+        crypto.randomUUID(),
+        user_id,
+        appointment.title,
+        appointment.description || null,
+        appointment.start_time,
+        appointment.end_time,
+        appointment.timezone || 'UTC',
+        appointment.location || null,
+        ...Object.values(parseCoordinates(appointment.latitude, appointment.longitude)),
+        appointment.recurrence_rule || null,
+        JSON.stringify(appointment.participants || []),
+        JSON.stringify(normalizeTags(appointment.tags)),
+        'scheduled',
+        now,
+      ],
+    }));
+
+    const results = statements.length > 0 ? await db.batch(statements, 'write') : [];
+    if (results.length > 0) {
+      await notifyCalendarChange(env, user_id, { entity: 'appointment', op: 'upsert', count: results.length });
+    }
+
+    return new Response(
+      JSON.stringify({
+        success: true,
+        created: results.map((r) => formatAppointment(r.rows[0])),
+        conflicts,
+        errors,
+      }),
+      { status: results.length > 0 ? 201 : 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    console.error('Error creating appointments batch:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to create appointments' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Delete many appointments in one request
+ * POST /api/calendar/appointments/batch-delete
+ * Body: { user_id, ids: [...] }
+ */
+export async function deleteAppointmentsBatch(request, env) {
+  try {
+    const body = await request.json();
+    const { user_id, ids } = body;
+
+    const invalid = validateBatch(user_id, ids, 'ids');
+    if (invalid) return invalid;
+
+    const db = getDbClient(env);
+    const placeholders = ids.map(() => '?').join(', ');
+
+    const result = await db.execute({
+      sql: `DELETE FROM appointments WHERE user_id = ? AND id IN (${placeholders}) RETURNING id`,
+      args: [user_id, ...ids],
+    });
+
+    const deleted = result.rows.map((row) => row.id);
+    if (deleted.length > 0) {
+      await notifyCalendarChange(env, user_id, { entity: 'appointment', op: 'delete', count: deleted.length });
+    }
+    return new Response(
+      JSON.stringify({
+        success: true,
+        deleted,
+        not_found: ids.filter((id) => !deleted.includes(id)),
+      }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    console.error('Error deleting appointments batch:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to delete appointments' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
 /**
  * Create a reminder
  * POST /api/calendar/reminders
@@ -402,6 +787,7 @@ export async function createReminder(request, env) {
       priority = 3,
       recurrence_rule,
       notification_channels = ['sms', 'email'],
+      tags = [],
     } = body;
 
     if (!user_id || !title || !due_time) {
@@ -421,8 +807,8 @@ export async function createReminder(request, env) {
       sql: `
         INSERT INTO reminders (
           id, user_id, title, description, due_time, priority,
-          recurrence_rule, notification_channels, completed, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+          recurrence_rule, notification_channels, tags, completed, created_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING *
       `,
       args: [
@@ -434,11 +820,14 @@ export async function createReminder(request, env) {
         priority,
         recurrence_rule || null,
         JSON.stringify(notification_channels),
+        JSON.stringify(normalizeTags(tags)),
         false,
         now,
       ],
     });
 
+    await notifyCalendarChange(env, user_id, { entity: 'reminder', op: 'upsert' });
+
     return new Response(
       JSON.stringify({
         success: true,
@@ -457,13 +846,14 @@ export async function createReminder(request, env) {
 
 /**
  * Get reminders for a user
- * GET /api/calendar/reminders?user_id=xxx&completed=false
+ * GET /api/calendar/reminders?user_id=xxx&completed=false&tag=xxx
  */
 export async function getReminders(request, env) {
   try {
     const url = new URL(request.url);
     const user_id = url.searchParams.get('user_id');
     const completed = url.searchParams.get('completed') === 'true';
+    const tag = url.searchParams.get('tag');
 
     if (!user_id) {
       return new Response(
@@ -474,14 +864,15 @@ export async function getReminders(request, env) {
 
     const db = getDbClient(env);
 
-    const result = await db.execute({
-      sql: `
-        SELECT * FROM reminders
-        WHERE user_id = ? AND completed = ?
-        ORDER BY due_time ASC
-      `,
-      args: [user_id, completed],
-    });
+    let sql = 'SELECT * FROM reminders WHERE user_id = ? AND completed = ?';
+    const args = [user_id, completed];
+    if (tag) {
+      sql += ` AND ${TAG_FILTER_SQL}`;
+      args.push(normalizeTags([tag])[0]);
+    }
+    sql += ' ORDER BY due_time ASC';
+
+    const result = await db.execute({ sql, args });
 
     return new Response(
       JSON.stringify({
@@ -505,22 +896,11 @@ export async function getReminders(request, env) {
 export async function updateReminder(request, env, reminderId) {
   try {
     const body = await request.json();
-    const { completed, snoozed_until } = body;
 
     const db = getDbClient(env);
     const now = new Date().toISOString();
 
-    const updates = [];
-    const args = [];
-
-    if (completed !== undefined) {
-      updates.push('completed = ?');
-      args.push(completed);
-    }
-    if (snoozed_until !== undefined) {
-      updates.push('snoozed_until = ?');
-      args.push(snoozed_until);
-    }
+    const { updates, args } = reminderUpdateColumns(body);
 
     if (updates.length === 0) {
       return new Response(
@@ -543,6 +923,7 @@ export async function updateReminder(request, env, reminderId) {
         { status: 404, headers: { 'Content-Type': 'application/json' } }
       );
     }
+    await notifyCalendarChange(env, result.rows[0].user_id, { entity: 'reminder', op: 'upsert' });
 
     return new Response(
       JSON.stringify({
@@ -560,6 +941,64 @@ export async function updateReminder(request, env, reminderId) {
   }
 }
 
+/**
+ * Update the status of many reminders in one request
+ * PUT /api/calendar/reminders/batch
+ * Body: { user_id, updates: [{ id, completed?, snoozed_until?, tags? }, ...] }
+ */
+export async function updateRemindersBatch(request, env) {
+  try {
+    const body = await request.json();
+    const { user_id, updates: items } = body;
+
+    const invalid = validateBatch(user_id, items, 'updates');
+    if (invalid) return invalid;
+
+    const db = getDbClient(env);
+    const now = new Date().toISOString();
+    const errors = [];
+    const ids = [];
+    const statements = [];
+
+    items.forEach((item, index) => {
+      const { updates, args } = reminderUpdateColumns(item);
+      if (!item.id || updates.length === 0) {
+        errors.push({ index, error: 'Each update needs an id and completed, snoozed_until or tags' });
+        return;
+      }
+      updates.push('updated_at = ?');
+      args.push(now, item.id, user_id);
+      ids.push(item.id);
+      statements.push({
+        sql: `UPDATE reminders SET ${updates.join(', ')} WHERE id = ? AND user_id = ? RETURNING *`,
+        args,
+      });
+    });
+
+    const results = statements.length > 0 ? await db.batch(statements, 'write') : [];
+    const reminders = results.filter((r) => r.rows.length > 0).map((r) => formatReminder(r.rows[0]));
+    if (reminders.length > 0) {
+      await notifyCalendarChange(env, user_id, { entity: 'reminder', op: 'upsert', count: reminders.length });
+    }
+
+    return new Response(
+      JSON.stringify({
+        success: true,
+        reminders,
+        not_found: ids.filter((_, i) => results[i].rows.length === 0),
+        errors,
+      }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    console.error('Error updating reminders batch:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to update reminders' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
 /**
  * Natural language scheduling endpoint
  * POST /api/calendar/schedule
@@ -598,6 +1037,61 @@ export async function scheduleNaturalLanguage(request, env) {
   }
 }
 
+/**
+ * Validate the envelope of a bulk request. Returns an error Response or null.
+ */
+function validateBatch(user_id, items, field) {
+  if (!user_id || !Array.isArray(items) || items.length === 0) {
+    return new Response(
+      JSON.stringify({ error: `Missing required fields: user_id, ${field}` }),
+      { status: 400, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+  if (items.length > MAX_BATCH_SIZE) {
+    return new Response(
+      JSON.stringify({ error: `Too many ${field}: maximum is ${MAX_BATCH_SIZE} per request` }),
+      { status: 413, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+  return null;
+}
+
+/**
+ * SET clauses for a reminder update (status and tags)
+ */
+function reminderUpdateColumns({ completed, snoozed_until, tags }) {
+  const updates = [];
+  const args = [];
+
+  if (completed !== undefined) {
+    updates.push('completed = ?');
+    args.push(completed);
+  }
+  if (snoozed_until !== undefined) {
+    updates.push('snoozed_until = ?');
+    args.push(snoozed_until);
+  }
+  if (tags !== undefined) {
+    updates.push('tags = ?');
+    args.push(JSON.stringify(normalizeTags(tags)));
+  }
+
+  return { updates, args };
+}
+
+/**
+ * Lowercase, '#'-less, de-duplicated tags (same rules as the assistant's categories.py)
+ */
+function normalizeTags(tags) {
+  if (!Array.isArray(tags)) {
+    return [];
+  }
+  const normalized = tags
+    .map((tag) => String(tag).trim().replace(/^#+/, '').toLowerCase())
+    .filter(Boolean);
+  return [...new Set(normalized)];
+}
+
 /**
  * Format appointment row
  */
@@ -611,8 +1105,12 @@ function formatAppointment(row) {
     end_time: row.end_time,
     timezone: row.timezone,
     location: row.location,
+    latitude: row.latitude ?? null,
+    longitude: row.longitude ?? null,
+    map_url: mapLink(row.location, row.latitude ?? null, row.longitude ?? null),
     recurrence_rule: row.recurrence_rule,
     participants: row.participants ? JSON.parse(row.participants) : [],
+    tags: row.tags ? JSON.parse(row.tags) : [],
     status: row.status,
     external_calendar_id: row.external_calendar_id,
     external_event_id: row.external_event_id,
@@ -638,6 +1136,7 @@ function formatReminder(row) {
     notification_channels: row.notification_channels
       ? JSON.parse(row.notification_channels)
       : [],
+    tags: row.tags ? JSON.parse(row.tags) : [],
     created_at: row.created_at,
     updated_at: row.updated_at,
   };
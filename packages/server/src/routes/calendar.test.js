@@ -0,0 +1,105 @@
+/**
+ * Tests for the calendar's change feed and appointment participants: the checks on the cursor, paging and
+ * coalescing of changes, and invitations that can't be sent
+ */
+
+import { test, before, after, beforeEach } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, apiRequest, json, makeTestEnv, stubFetch } from '../test-env.js';
+import { getAppointmentParticipants, getCalendarChanges, inviteAppointmentParticipants } from './calendar.js';
+
+let env, db, twilio;
+
+before(async () => {
+  ({ env, db } = await makeTestEnv(SCHEMA.calendar));
+  twilio = stubFetch();
+});
+
+after(() => twilio.restore());
+
+beforeEach(() => {
+  twilio.sent.length = 0;
+});
+
+function addAppointment(id, userId, participants = []) {
+  return db.execute({
+    sql: `INSERT INTO appointments (id, user_id, title, start_time, end_time, participants)
+          VALUES (?, ?, ?, '2026-10-20T15:00:00Z', '2026-10-20T16:00:00Z', ?)`,
+    args: [id, userId, `Meeting ${id}`, JSON.stringify(participants)],
+  });
+}
+
+function changes(query) {
+  return getCalendarChanges(apiRequest('GET', `/api/calendar/changes${query}`), env);
+}
+
+function invite(id, body = {}) {
+  return inviteAppointmentParticipants(apiRequest('POST', `/api/calendar/appointments/${id}/invitations`, { body }),
+    env, id);
+}
+
+function participants(id) {
+  return getAppointmentParticipants(apiRequest('GET', `/api/calendar/appointments/${id}/participants`), env, id);
+}
+
+test('changes need a user_id and a non-negative since', async () => {
+  assert.deepStrictEqual(await json(await changes('?since=0')),
+    { status: 400, body: { error: 'Missing user_id parameter' } });
+  for (const since of ['-1', 'latest']) {
+    assert.deepStrictEqual(await json(await changes(`?user_id=jo&since=${since}`)),
+      { status: 400, body: { error: 'since must be a non-negative sequence number' } });
+  }
+});
+
+test('a cursor past the end of the journal tells the client to start over', async () => {
+  assert.deepStrictEqual((await json(await changes('?user_id=jo&since=999999'))).body,
+    { changes: [], cursor: 0, has_more: true, reset: true });
+});
+
+test("changes are the user's own, each entity once at its latest, a page at a time", async () => {
+  await addAppointment('standup', 'jo');
+  await addAppointment('dentist', 'jo');
+  await addAppointment('lunch', 'kim');
+  await db.execute("UPDATE appointments SET title = 'Standup (moved)' WHERE id = 'standup'");
+  await db.execute("DELETE FROM appointments WHERE id = 'dentist'");
+
+  const all = (await json(await changes('?user_id=jo'))).body;
+  assert.deepStrictEqual(all.changes.map(({ id, op }) => [id, op]), [['standup', 'upsert'], ['dentist', 'delete']]);
+  assert.strictEqual(all.changes[0].data.title, 'Standup (moved)');
+  assert.strictEqual(all.has_more, false);
+
+  const first = (await json(await changes('?user_id=jo&limit=1'))).body;
+  assert.strictEqual(first.changes.length, 1);
+  assert.strictEqual(first.has_more, true);
+  const rest = (await json(await changes(`?user_id=jo&since=${first.cursor}&limit=-5`))).body;
+  assert.strictEqual(rest.changes.length, 1, 'a limit below 1 is raised to 1');
+});
+
+test("inviting to an appointment that doesn't exist is a 404 and sends nothing", async () => {
+  assert.deepStrictEqual(await json(await invite('nope')), { status: 404, body: { error: 'Appointment not found' } });
+  assert.deepStrictEqual(twilio.sent, []);
+});
+
+test("participants who can't be reached are reported, and the rest are invited once", async () => {
+  await addAppointment('review', 'jo', ['Bob', 'sam@example.com', '+1 (555) 123-4567']);
+
+  const { status, body } = await json(await invite('review'));
+  assert.strictEqual(status, 200);
+  assert.deepStrictEqual(body.invitations.sent, ['+1 (555) 123-4567']);
+  assert.deepStrictEqual(body.invitations.failed, [
+    { label: 'Bob', error: 'No email or phone number' },
+    { label: 'sam@example.com', error: 'SENDGRID_API_KEY not configured' },
+  ]);
+  assert.deepStrictEqual(twilio.sent.map((message) => message.to), ['+15551234567']);
+
+  const again = (await json(await invite('review'))).body.invitations;
+  assert.deepStrictEqual(again.skipped, ['+1 (555) 123-4567']);
+  assert.strictEqual(twilio.sent.length, 1);
+});
+
+test('participants are listed without their private RSVP tokens', async () => {
+  const { body } = await json(await participants('review'));
+  assert.strictEqual(body.participants.length, 3);
+  assert.ok(body.participants.every((participant) => !('rsvp_token' in participant)));
+  assert.deepStrictEqual((await json(await participants('nope'))).body, { participants: [] });
+});
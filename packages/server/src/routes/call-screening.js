@@ -0,0 +1,308 @@
+/**
+ * Call Screening Routes
+ *
+ * Twilio webhooks for screening calls from unknown numbers, plus the API
+ * the dashboard uses to watch live screening and decide what happens to
+ * the caller. See lib/call-screening.js for the call lifecycle.
+ */
+
+import {
+	SCREENING_DECISIONS,
+	MAX_HOLD_POLLS,
+	getScreeningDb,
+	createScreenedCall,
+	getScreenedCall,
+	updateScreenedCall,
+	listActiveScreenedCalls,
+	createVoicemail,
+	setVoicemailTranscript,
+	searchVoicemails,
+	generateScreeningTwiML,
+	generateHoldTwiML,
+	generateConnectTwiML,
+	generateDeclineTwiML,
+	generateVoicemailTwiML,
+} from '../lib/call-screening.js';
+import { recordInboundMessage } from '../lib/inbox.js';
+import usersConfig from '../config/users.json' with { type: 'json' };
+
+function twimlResponse(twiml) {
+	return new Response(twiml, {
+		status: 200,
+		headers: {
+			'Content-Type': 'application/xml',
+		},
+	});
+}
+
+function jsonResponse(data, status = 200) {
+	return new Response(JSON.stringify(data), {
+		status,
+		headers: { 'Content-Type': 'application/json' },
+	});
+}
+
+/**
+ * Find the account that owns the dialed Boss number
+ */
+function getOwnerByBossPhone(bossPhone) {
+	return usersConfig.users.find((user) => user.boss_phone === bossPhone) || null;
+}
+
+/**
+ * Answer a call from an unknown number and ask for its purpose
+ *
+ * Called from handleInboundCall with the already-parsed Twilio form data.
+ * Returns null when no owner matches the dialed number.
+ */
+export async function startCallScreening(formData, env) {
+	const owner = getOwnerByBossPhone(formData.get('To') || '');
+	if (!owner) {
+		return null;
+	}
+
+	const callSid = formData.get('CallSid');
+	const db = getScreeningDb(env);
+	await createScreenedCall(db, {
+		callSid,
+		userId: owner.username,
+		caller: formData.get('From') || 'unknown',
+		callerName: formData.get('CallerName'),
+	});
+
+	console.log(`Screening call ${callSid} for ${owner.username}`);
+	return twimlResponse(generateScreeningTwiML(owner.name, callSid));
+}
+
+/**
+ * Live partial transcription of the caller's purpose
+ * POST /voice/screen/partial?call=CallSid
+ */
+export async function handleScreeningPartial(request, env) {
+	try {
+		const url = new URL(request.url);
+		const formData = await request.formData();
+		const callSid = url.searchParams.get('call') || formData.get('CallSid');
+		const partial = formData.get('UnstableSpeechResult') || formData.get('StableSpeechResult') || '';
+
+		if (partial) {
+			await updateScreenedCall(getScreeningDb(env), callSid, { purpose: partial });
+		}
+		return new Response(null, { status: 204 });
+	} catch (error) {
+		console.error('Error recording screening partial:', error);
+		return new Response(null, { status: 204 });
+	}
+}
+
+/**
+ * Final purpose from Gather; put the caller on hold for a decision
+ * POST /voice/screen/purpose?call=CallSid
+ */
+export async function handleScreeningPurpose(request, env) {
+	const url = new URL(request.url);
+	const formData = await request.formData();
+	const callSid = url.searchParams.get('call') || formData.get('CallSid');
+
+	try {
+		const purpose = formData.get('SpeechResult') || '';
+		await updateScreenedCall(getScreeningDb(env), callSid, {
+			purpose: purpose || undefined,
+			status: 'waiting',
+		});
+		return twimlResponse(generateHoldTwiML(callSid, 0, true));
+	} catch (error) {
+		console.error('Error handling screening purpose:', error);
+		return twimlResponse(generateVoicemailTwiML());
+	}
+}
+
+/**
+ * Held caller polls for the owner's decision
+ * POST /voice/screen/wait?call=CallSid&attempt=N
+ */
+export async function handleScreeningWait(request, env) {
+	const url = new URL(request.url);
+	const callSid = url.searchParams.get('call');
+	const attempt = Number(url.searchParams.get('attempt')) || 0;
+
+	try {
+		const db = getScreeningDb(env);
+		const call = await getScreenedCall(db, callSid);
+
+		if (!call) {
+			return twimlResponse(generateVoicemailTwiML());
+		}
+		if (call.status === 'screening') {
+			// Gather timed out without speech
+			await updateScreenedCall(db, callSid, { status: 'waiting' });
+		}
+		if (call.status === 'accepted') {
+			const owner = usersConfig.users.find((user) => user.username === call.user_id);
+			if (owner) {
+				return twimlResponse(generateConnectTwiML(owner.phone));
+			}
+		}
+		if (call.status === 'declined') {
+			return twimlResponse(generateDeclineTwiML(call.decline_message));
+		}
+		if (call.status === 'voicemail' || attempt >= MAX_HOLD_POLLS) {
+			await updateScreenedCall(db, callSid, { status: 'voicemail' });
+			return twimlResponse(generateVoicemailTwiML());
+		}
+
+		return twimlResponse(generateHoldTwiML(callSid, attempt + 1));
+	} catch (error) {
+		console.error('Error checking screening decision:', error);
+		return twimlResponse(generateVoicemailTwiML());
+	}
+}
+
+/**
+ * Forwarded call finished; fall back to voicemail if the owner didn't pick up
+ * POST /voice/screen/dialed
+ */
+export async function handleScreeningDialed(request, env) {
+	const formData = await request.formData();
+	const dialStatus = formData.get('DialCallStatus');
+
+	if (dialStatus === 'completed') {
+		await updateScreenedCall(getScreeningDb(env), formData.get('CallSid'), { status: 'ended' }).catch(() => null);
+		return twimlResponse('<?xml version="1.0" encoding="UTF-8"?><Response><Hangup/></Response>');
+	}
+
+	await updateScreenedCall(getScreeningDb(env), formData.get('CallSid'), { status: 'voicemail' }).catch(() => null);
+	return twimlResponse(generateVoicemailTwiML());
+}
+
+/**
+ * Voicemail recording finished
+ * POST /voice/screen/recorded
+ */
+export async function handleVoicemailRecorded(request, env) {
+	try {
+		const formData = await request.formData();
+		const callSid = formData.get('CallSid');
+		const db = getScreeningDb(env);
+		const call = await getScreenedCall(db, callSid);
+
+		await createVoicemail(db, {
+			callSid,
+			userId: call ? call.user_id : 'unknown',
+			caller: formData.get('From') || (call && call.caller) || 'unknown',
+			recordingUrl: formData.get('RecordingUrl'),
+			duration: formData.get('RecordingDuration'),
+		});
+		await updateScreenedCall(db, callSid, { status: 'ended' });
+	} catch (error) {
+		console.error('Error saving voicemail:', error);
+	}
+
+	return twimlResponse(`<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Say voice="Polly.Brian-Neural">Thank you. Your message has been saved. Goodbye.</Say>
+  <Hangup/>
+</Response>`);
+}
+
+/**
+ * Twilio transcription of a voicemail is ready
+ * POST /voice/screen/transcribed
+ */
+export async function handleVoicemailTranscribed(request, env) {
+	try {
+		const formData = await request.formData();
+		const callSid = formData.get('CallSid');
+		const transcript = formData.get('TranscriptionText') || '';
+		const voicemail = await setVoicemailTranscript(getScreeningDb(env), callSid, transcript);
+
+		// Surface the voicemail in the unified inbox
+		if (voicemail && transcript) {
+			await recordInboundMessage(env, {
+				channel: 'voice',
+				from: voicemail.caller,
+				content: transcript,
+				timestamp: voicemail.created_at,
+				metadata: { callSid, subject: 'Voicemail' },
+			}, voicemail.user_id);
+		}
+	} catch (error) {
+		console.error('Error saving voicemail transcript:', error);
+	}
+	return new Response(null, { status: 204 });
+}
+
+/**
+ * Calls currently being screened
+ * GET /api/calls/screening?user_id=xxx
+ */
+export async function getActiveScreenings(request, env) {
+	try {
+		const userId = new URL(request.url).searchParams.get('user_id');
+		if (!userId) {
+			return jsonResponse({ error: 'Missing user_id parameter' }, 400);
+		}
+
+		const calls = await listActiveScreenedCalls(getScreeningDb(env), userId);
+		return jsonResponse({ calls });
+	} catch (error) {
+		console.error('Error listing screened calls:', error);
+		return jsonResponse({ error: 'Failed to list screened calls' }, 500);
+	}
+}
+
+/**
+ * Record the owner's decision for a screened call
+ * POST /api/calls/:callSid/screen
+ * Body: { "decision": "accepted" | "declined" | "voicemail", "message": "..." }
+ */
+export async function decideScreenedCall(request, env, callSid) {
+	try {
+		const { decision, message } = await request.json();
+
+		if (!SCREENING_DECISIONS.includes(decision)) {
+			return jsonResponse({ error: `Invalid decision. Expected one of: ${SCREENING_DECISIONS.join(', ')}` }, 400);
+		}
+
+		const db = getScreeningDb(env);
+		const call = await getScreenedCall(db, callSid);
+		if (!call) {
+			return jsonResponse({ error: 'Screened call not found' }, 404);
+		}
+		if (!['screening', 'waiting'].includes(call.status)) {
+			return jsonResponse({ error: `Call already ${call.status}` }, 409);
+		}
+
+		const updated = await updateScreenedCall(db, callSid, {
+			status: decision,
+			decline_message: decision === 'declined' && message ? message : undefined,
+		});
+		return jsonResponse({ success: true, call: updated });
+	} catch (error) {
+		console.error('Error deciding screened call:', error);
+		return jsonResponse({ error: 'Failed to update screened call' }, 500);
+	}
+}
+
+/**
+ * Search voicemail transcripts
+ * GET /api/voicemail?user_id=xxx&q=invoice&limit=20
+ */
+export async function getVoicemails(request, env) {
+	try {
+		const url = new URL(request.url);
+		const userId = url.searchParams.get('user_id');
+		if (!userId) {
+			return jsonResponse({ error: 'Missing user_id parameter' }, 400);
+		}
+
+		const voicemails = await searchVoicemails(getScreeningDb(env), userId, {
+			query: url.searchParams.get('q'),
+			limit: url.searchParams.get('limit'),
+		});
+		return jsonResponse({ voicemails });
+	} catch (error) {
+		console.error('Error searching voicemail:', error);
+		return jsonResponse({ error: 'Failed to search voicemail' }, 500);
+	}
+}
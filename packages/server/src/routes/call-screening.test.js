@@ -0,0 +1,115 @@
+/**
+ * Tests for call screening: who gets screened, the held caller's fallbacks to voicemail, and the checks on the
+ * dashboard's decisions and listings
+ */
+
+import { test, before } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, apiRequest, json, makeTestEnv } from '../test-env.js';
+import { MAX_HOLD_POLLS, getScreenedCall, getScreeningDb } from '../lib/call-screening.js';
+import usersConfig from '../config/users.json' with { type: 'json' };
+import {
+  decideScreenedCall,
+  getActiveScreenings,
+  getVoicemails,
+  handleScreeningPartial,
+  handleScreeningPurpose,
+  handleScreeningWait,
+  handleVoicemailTranscribed,
+  startCallScreening,
+} from './call-screening.js';
+
+const owner = usersConfig.users[0];
+let env;
+
+before(async () => {
+  ({ env } = await makeTestEnv(SCHEMA.screening));
+});
+
+function ring(callSid, to = owner.boss_phone) {
+  return startCallScreening(new URLSearchParams({ CallSid: callSid, To: to, From: '+15557654321' }), env);
+}
+
+function webhook(handler, path, form = {}) {
+  return handler(apiRequest('POST', path, { form }), env);
+}
+
+function decide(callSid, body) {
+  return decideScreenedCall(apiRequest('POST', `/api/calls/${callSid}/screen`, { body }), env, callSid);
+}
+
+function call(callSid) {
+  return getScreenedCall(getScreeningDb(env), callSid);
+}
+
+test("a call to a number no one owns isn't screened", async () => {
+  assert.strictEqual(await ring('CA-nobody', '+15550001111'), null);
+  assert.strictEqual(await call('CA-nobody'), null);
+});
+
+test('an unknown caller is asked their purpose and held once they give it', async () => {
+  const answered = await ring('CA-hold');
+  assert.strictEqual(answered.status, 200);
+  assert.match(await answered.text(), /<Gather/);
+
+  const held = await webhook(handleScreeningPurpose, '/voice/screen/purpose?call=CA-hold',
+    { SpeechResult: 'About the invoice' });
+  assert.match(await held.text(), /<Redirect[^>]*>\/voice\/screen\/wait\?call=CA-hold&amp;attempt=0</);
+  const { status, purpose } = await call('CA-hold');
+  assert.deepStrictEqual([status, purpose], ['waiting', 'About the invoice']);
+});
+
+test('a partial transcript without speech leaves the purpose alone', async () => {
+  await ring('CA-quiet');
+  await webhook(handleScreeningPartial, '/voice/screen/partial?call=CA-quiet',
+    { UnstableSpeechResult: 'Calling about' });
+  const response = await webhook(handleScreeningPartial, '/voice/screen/partial?call=CA-quiet');
+  assert.strictEqual(response.status, 204);
+  assert.strictEqual((await call('CA-quiet')).purpose, 'Calling about');
+});
+
+test('listing screened calls and voicemail needs a user_id', async () => {
+  assert.deepStrictEqual(await json(await getActiveScreenings(apiRequest('GET', '/api/calls/screening'), env)),
+    { status: 400, body: { error: 'Missing user_id parameter' } });
+  assert.deepStrictEqual(await json(await getVoicemails(apiRequest('GET', '/api/voicemail?q=invoice'), env)),
+    { status: 400, body: { error: 'Missing user_id parameter' } });
+});
+
+test('a decision must be accepted, declined or voicemail', async () => {
+  await ring('CA-bad');
+  for (const decision of [undefined, 'hangup', 'ACCEPTED']) {
+    const { status, body } = await json(await decide('CA-bad', { decision }));
+    assert.strictEqual(status, 400);
+    assert.match(body.error, /Expected one of: accepted, declined, voicemail/);
+  }
+  assert.strictEqual((await call('CA-bad')).status, 'screening');
+});
+
+test("an unknown call can't be decided, nor one that's already been decided", async () => {
+  assert.deepStrictEqual(await json(await decide('CA-missing', { decision: 'accepted' })),
+    { status: 404, body: { error: 'Screened call not found' } });
+
+  await ring('CA-twice');
+  const declined = await json(await decide('CA-twice', { decision: 'declined', message: 'Not today' }));
+  assert.strictEqual(declined.body.call.decline_message, 'Not today');
+  assert.deepStrictEqual(await json(await decide('CA-twice', { decision: 'accepted' })),
+    { status: 409, body: { error: 'Call already declined' } });
+});
+
+test('a held caller goes to voicemail when their call is unknown or the owner never decides', async () => {
+  const unknown = await webhook(handleScreeningWait, '/voice/screen/wait?call=CA-gone&attempt=0');
+  assert.match(await unknown.text(), /<Record /);
+
+  await ring('CA-waited');
+  const waiting = await webhook(handleScreeningWait, '/voice/screen/wait?call=CA-waited&attempt=2');
+  assert.match(await waiting.text(), /attempt=3</);
+  const timedOut = await webhook(handleScreeningWait, `/voice/screen/wait?call=CA-waited&attempt=${MAX_HOLD_POLLS}`);
+  assert.match(await timedOut.text(), /<Record /);
+  assert.strictEqual((await call('CA-waited')).status, 'voicemail');
+});
+
+test('a transcript for a voicemail that was never recorded is ignored', async () => {
+  const response = await webhook(handleVoicemailTranscribed, '/voice/screen/transcribed',
+    { CallSid: 'CA-none', TranscriptionText: 'Hi' });
+  assert.strictEqual(response.status, 204);
+});
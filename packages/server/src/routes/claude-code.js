@@ -5,12 +5,48 @@
  * - Session management (create, connect, disconnect)
  * - Message routing between Admin and Claude Code
  * - Cost tracking and usage monitoring
+ * - Per-session and per-day budgets: sessions pause until approved (lib/claude-code-budget.js)
  * - Session status and history
  *
  * Integrates with Rust supervisor WebSocket for bidirectional communication
  */
 
 import { getUserById, getUserByPhone, getUserByXswarmPhone } from '../lib/users.js';
+import { getCostGuard } from '../lib/claude-code-budget.js';
+import { loadConfig } from '../config/loader.js';
+
+/**
+ * Helper: The cost guard, with budgets from config.claude_code
+ *
+ * @param {Object} env - Cloudflare Worker environment
+ * @returns {Promise<CostGuard>}
+ */
+async function getGuard(env) {
+  const config = await loadConfig(env);
+  return getCostGuard(config.claude_code);
+}
+
+/**
+ * Helper: Tell the supervisor about a budget warning or pause, so the
+ * assistant says it and shows it on the dashboard
+ *
+ * @param {WebSocket} ws - Open supervisor connection
+ * @param {string} session_id - Session ID
+ * @param {Object} notice - { level, message } from the cost guard
+ */
+function notifyBudget(ws, session_id, notice) {
+  try {
+    ws.send(JSON.stringify({
+      type: 'claude_code_budget',
+      session_id,
+      level: notice.level,
+      message: notice.message,
+      speak: true
+    }));
+  } catch (error) {
+    console.error('Error sending budget notice to supervisor:', error);
+  }
+}
 
 /**
  * Helper: Get WebSocket connection to Rust supervisor
@@ -120,6 +156,7 @@ export async function createSession(request, env) {
       });
 
       if (response.type === 'claude_code_connected') {
+        (await getGuard(env)).start(response.session_id, user_id, project_path);
         return new Response(JSON.stringify({
           session_id: response.session_id,
           status: response.status,
@@ -192,6 +229,20 @@ export async function sendMessage(request, env) {
       });
     }
 
+    // Paused sessions wait for the user's approval
+    const guard = await getGuard(env);
+    const paused = guard.check(session_id);
+    if (paused) {
+      return new Response(JSON.stringify({
+        error: 'budget_paused',
+        message: paused.message,
+        budget: guard.status(session_id)
+      }), {
+        status: 402,
+        headers: { 'Content-Type': 'application/json' }
+      });
+    }
+
     // Connect to supervisor
     const ws = await getSupervisorWebSocket(env);
 
@@ -205,10 +256,16 @@ export async function sendMessage(request, env) {
       });
 
       if (response.type === 'claude_code_response') {
+        const notice = guard.record(session_id, response.cost_usd);
+        if (notice) {
+          notifyBudget(ws, session_id, notice);
+        }
         return new Response(JSON.stringify({
           message_id: response.message_id,
           content: response.content,
           cost_usd: response.cost_usd,
+          budget: guard.status(session_id),
+          budget_notice: notice,
           timestamp: response.timestamp
         }), {
           status: 200,
@@ -267,6 +324,7 @@ export async function disconnectSession(request, env) {
       });
 
       if (response.type === 'claude_code_disconnected') {
+        (await getGuard(env)).end(session_id);
         return new Response(JSON.stringify({
           session_id: response.session_id,
           reason: response.reason,
@@ -306,6 +364,47 @@ export async function disconnectSession(request, env) {
   }
 }
 
+/**
+ * POST /api/claude-code/sessions/:session_id/approve
+ *
+ * Let a session paused by its budget continue, for one more budget's worth
+ */
+export async function approveSession(request, env) {
+  try {
+    const url = new URL(request.url);
+    const pathParts = url.pathname.split('/');
+    const session_id = pathParts[pathParts.length - 2]; // .../sessions/{id}/approve
+
+    const guard = await getGuard(env);
+    if (!guard.approve(session_id)) {
+      return new Response(JSON.stringify({
+        error: 'Session is not paused'
+      }), {
+        status: 409,
+        headers: { 'Content-Type': 'application/json' }
+      });
+    }
+
+    return new Response(JSON.stringify({
+      session_id,
+      approved: true,
+      budget: guard.status(session_id)
+    }), {
+      status: 200,
+      headers: { 'Content-Type': 'application/json' }
+    });
+
+  } catch (error) {
+    console.error('Error approving Claude Code session:', error);
+    return new Response(JSON.stringify({
+      error: error.message
+    }), {
+      status: 500,
+      headers: { 'Content-Type': 'application/json' }
+    });
+  }
+}
+
 /**
  * POST /api/claude-code/route-conversation
  *
@@ -419,6 +518,7 @@ export async function getSessionCost(request, env) {
         return new Response(JSON.stringify({
           session_id,
           cost_usd: response.cost_usd,
+          budget: (await getGuard(env)).status(session_id),
           message_count: response.message_count,
           duration_seconds: response.duration_seconds,
           status: response.status
@@ -0,0 +1,98 @@
+/**
+ * Client Command API Routes
+ *
+ * Handles:
+ * - Queueing a command for the signed-in user's assistant (idempotent per key)
+ * - The assistant fetching its pending commands
+ * - The assistant acknowledging each one (done / failed / rejected)
+ *
+ * Every route needs the user's token and only reaches that user's commands.
+ *
+ * See lib/client-commands.js for the protocol.
+ */
+
+import {
+  ACK_STATUSES,
+  COMMAND_TYPES,
+  ackCommand,
+  getCommandsDb,
+  listPendingCommands,
+  queueClientCommand,
+} from '../lib/client-commands.js';
+import { AuthError, createAuthErrorResponse, requireAuth } from '../lib/auth-middleware.js';
+
+function json(body, status = 200) {
+  return new Response(JSON.stringify(body), { status, headers: { 'Content-Type': 'application/json' } });
+}
+
+/**
+ * Queue a command for the signed-in user's assistant
+ * POST /api/commands { type, args, idempotency_key, ttl_seconds }
+ * Idempotency-Key header is accepted in place of idempotency_key.
+ */
+export async function createCommand(request, env) {
+  try {
+    const user = await requireAuth(request, env);
+    const body = await request.json();
+    if (!COMMAND_TYPES.includes(body.type)) {
+      return json({ error: `Invalid type. Expected one of: ${COMMAND_TYPES.join(', ')}` }, 400);
+    }
+    const { command, duplicate } = await queueClientCommand(env, user.id, {
+      type: body.type,
+      args: body.args || {},
+      idempotency_key: body.idempotency_key || request.headers.get('Idempotency-Key'),
+      ttl_seconds: body.ttl_seconds,
+    });
+    return json({ command, duplicate }, duplicate ? 200 : 201);
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error queueing command:', error);
+    return json({ error: 'Failed to queue command' }, 500);
+  }
+}
+
+/**
+ * Pending commands for the signed-in user's assistant
+ * GET /api/commands?limit=20
+ */
+export async function getCommands(request, env) {
+  try {
+    const user = await requireAuth(request, env);
+    const url = new URL(request.url);
+    const commands = await listPendingCommands(getCommandsDb(env), user.id, url.searchParams.get('limit'));
+    return json({ commands });
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error getting commands:', error);
+    return json({ error: 'Failed to get commands' }, 500);
+  }
+}
+
+/**
+ * Acknowledge one of the signed-in user's commands
+ * POST /api/commands/:id/ack { status: done|failed|rejected, result }
+ */
+export async function acknowledgeCommand(request, env, commandId) {
+  try {
+    const user = await requireAuth(request, env);
+    const { status, result } = await request.json();
+    if (!ACK_STATUSES.includes(status)) {
+      return json({ error: `Invalid status. Expected one of: ${ACK_STATUSES.join(', ')}` }, 400);
+    }
+    const command = await ackCommand(getCommandsDb(env), user.id, commandId, { status, result });
+    if (!command) {
+      return json({ error: 'Command not found' }, 404);
+    }
+    return json({ command });
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error acknowledging command:', error);
+    return json({ error: 'Failed to acknowledge command' }, 500);
+  }
+}
@@ -0,0 +1,56 @@
+/**
+ * Tests for the client command API: sign-in, and fetching and acknowledging only the user's own commands
+ */
+
+import { test, before } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, addUser, apiRequest, json, makeTestEnv } from '../test-env.js';
+import { acknowledgeCommand, createCommand, getCommands } from './commands.js';
+
+let env, jo, kim, joSpeak;
+
+before(async () => {
+  let db;
+  ({ env, db } = await makeTestEnv(SCHEMA.commands));
+  jo = await addUser(db, env, 'jo');
+  kim = await addUser(db, env, 'kim');
+  const queued = await json(await queue(jo, { type: 'speak', args: { text: 'Deploy finished' } }));
+  joSpeak = queued.body.command;
+});
+
+function queue(token, body) {
+  return createCommand(apiRequest('POST', '/api/commands', { token, body }), env);
+}
+
+function pending(token, query = '') {
+  return getCommands(apiRequest('GET', `/api/commands${query}`, { token }), env);
+}
+
+function ack(token, id, body = { status: 'done', result: 'spoken' }) {
+  return acknowledgeCommand(apiRequest('POST', `/api/commands/${id}/ack`, { token, body }), env, id);
+}
+
+test('fetching and acknowledging need a valid token', async () => {
+  assert.strictEqual((await pending(undefined)).status, 401);
+  assert.strictEqual((await pending('forged')).status, 401);
+  assert.strictEqual((await ack(undefined, joSpeak.id)).status, 401);
+});
+
+test("the pending list holds only the token's user's commands, whatever user_id is asked for", async () => {
+  const theirs = await json(await pending(kim, '?user_id=jo'));
+  assert.deepStrictEqual(theirs, { status: 200, body: { commands: [] } });
+  const mine = await json(await pending(jo));
+  assert.deepStrictEqual(mine.body.commands.map((c) => c.id), [joSpeak.id]);
+});
+
+test("another user's command can't be acknowledged, and acks are checked and idempotent", async () => {
+  assert.deepStrictEqual(await json(await ack(kim, joSpeak.id)),
+    { status: 404, body: { error: 'Command not found' } });
+  assert.strictEqual((await ack(jo, joSpeak.id, { status: 'maybe' })).status, 400);
+
+  const done = await json(await ack(jo, joSpeak.id));
+  assert.deepStrictEqual([done.status, done.body.command.status], [200, 'done']);
+  const again = await json(await ack(jo, joSpeak.id, { status: 'failed', result: 'late' }));
+  assert.deepStrictEqual([again.body.command.status, again.body.command.result], ['done', 'spoken']);
+  assert.deepStrictEqual((await json(await pending(jo))).body.commands, []);
+});
@@ -0,0 +1,50 @@
+/**
+ * Crash Report Routes
+ *
+ * Crash reports the local assistant sends when its user opted in
+ * (config.crash_upload) or sent one by hand (`xswarm dev crash-report
+ * send`). Reports are redacted on the machine before they're sent (see
+ * assistant/crash_report.py) and kept in R2:
+ *
+ *   crash-reports/<user_id>/<report id>.json
+ *
+ * Sending the same report again overwrites it, so retries are harmless.
+ */
+
+const REPORT_ID = /^[a-f0-9]{6,32}$/;
+const MAX_REPORT_BYTES = 256 * 1024;
+
+function json(body, status = 200) {
+  return new Response(JSON.stringify(body), { status, headers: { 'Content-Type': 'application/json' } });
+}
+
+/**
+ * Store a crash report
+ * POST /api/crash-reports { user_id, report: { id, time, where, error, stack, log_tail, subsystems, ... } }
+ */
+export async function createCrashReport(request, env) {
+  try {
+    const { user_id, report } = await request.json();
+    if (!user_id) {
+      return json({ error: 'Missing user_id' }, 400);
+    }
+    if (!report || !REPORT_ID.test(report.id || '') || typeof report.error !== 'string') {
+      return json({ error: 'report needs an id and an error' }, 400);
+    }
+    const body = JSON.stringify({ ...report, user_id, received_at: new Date().toISOString() });
+    if (body.length > MAX_REPORT_BYTES) {
+      return json({ error: `Crash reports are limited to ${MAX_REPORT_BYTES / 1024} KB` }, 400);
+    }
+    if (!env.R2_BUCKET) {
+      return json({ error: 'Crash reports are not configured' }, 503);
+    }
+    await env.R2_BUCKET.put(`crash-reports/${encodeURIComponent(user_id)}/${report.id}.json`, body, {
+      httpMetadata: { contentType: 'application/json' },
+    });
+    console.log(`💥 Crash report ${report.id} from ${user_id}: ${report.error.slice(0, 120)}`);
+    return json({ stored: report.id }, 201);
+  } catch (error) {
+    console.error('Error storing crash report:', error);
+    return json({ error: 'Failed to store the crash report' }, 500);
+  }
+}
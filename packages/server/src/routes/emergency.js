@@ -0,0 +1,223 @@
+/**
+ * Emergency Alert Routes
+ *
+ * The API the local assistant uses to raise, repeat, check and stand down
+ * an emergency alert, plus the Twilio webhooks for the alert calls. A
+ * contact's "OK" text is handled by the SMS webhook (acknowledgeBySms).
+ * The API routes need the user's token and act only on that user's alerts,
+ * which only go to the contacts the user saved. See lib/emergency.js for
+ * the alert lifecycle.
+ */
+
+import {
+	getEmergencyDb,
+	validateAlert,
+	validateContacts,
+	getEmergencyContacts,
+	saveEmergencyContacts,
+	pickContacts,
+	createAlert,
+	getAlert,
+	sendAlertRound,
+	resolveAlert,
+	findContact,
+	generateAlertTwiML,
+	generateAnswerTwiML,
+} from '../lib/emergency.js';
+import { sendSms } from '../lib/outbound.js';
+import { AuthError, createAuthErrorResponse, requireAuth } from '../lib/auth-middleware.js';
+
+function twimlResponse(twiml) {
+	return new Response(twiml, {
+		status: 200,
+		headers: {
+			'Content-Type': 'application/xml',
+		},
+	});
+}
+
+function jsonResponse(data, status = 200) {
+	return new Response(JSON.stringify(data), {
+		status,
+		headers: { 'Content-Type': 'application/json' },
+	});
+}
+
+function baseUrlFor(request, env) {
+	return env.PUBLIC_BASE_URL || new URL(request.url).origin;
+}
+
+/**
+ * The signed-in user's saved emergency contacts
+ * GET /api/emergency/contacts
+ */
+export async function getEmergencyContactList(request, env) {
+	try {
+		const user = await requireAuth(request, env);
+		return jsonResponse({ contacts: await getEmergencyContacts(getEmergencyDb(env), user.id) });
+	} catch (error) {
+		if (error instanceof AuthError) {
+			return createAuthErrorResponse(error);
+		}
+		console.error('Error getting emergency contacts:', error);
+		return jsonResponse({ error: 'Failed to get emergency contacts' }, 500);
+	}
+}
+
+/**
+ * Replace the signed-in user's emergency contacts
+ * PUT /api/emergency/contacts { contacts: [{ name, phone }] }
+ */
+export async function putEmergencyContacts(request, env) {
+	try {
+		const user = await requireAuth(request, env);
+		const { contacts } = await request.json();
+		const problem = validateContacts(contacts);
+		if (problem) {
+			return jsonResponse({ error: problem }, 400);
+		}
+		return jsonResponse({ contacts: await saveEmergencyContacts(getEmergencyDb(env), user.id, contacts) });
+	} catch (error) {
+		if (error instanceof AuthError) {
+			return createAuthErrorResponse(error);
+		}
+		console.error('Error saving emergency contacts:', error);
+		return jsonResponse({ error: 'Failed to save emergency contacts' }, 500);
+	}
+}
+
+/**
+ * Raise an alert and send its first round, to the saved contacts it names (all of them if none)
+ * POST /api/emergency/alerts { message, methods: ['sms', 'call'], contacts: [{ phone }] }
+ */
+export async function createEmergencyAlert(request, env) {
+	try {
+		const user = await requireAuth(request, env);
+		const body = await request.json();
+		const problem = validateAlert(body);
+		if (problem) {
+			return jsonResponse({ error: problem }, 400);
+		}
+		const db = getEmergencyDb(env);
+		const picked = pickContacts(await getEmergencyContacts(db, user.id), body.contacts);
+		if (picked.error) {
+			return jsonResponse({ error: picked.error }, 400);
+		}
+		const alert = await createAlert(db, user.id, { ...body, contacts: picked.contacts });
+		console.log(`🚨 Emergency alert ${alert.id} for ${user.id} to ${alert.contacts.length} contact(s)`);
+		return jsonResponse(await sendAlertRound(db, env, alert, baseUrlFor(request, env)), 201);
+	} catch (error) {
+		if (error instanceof AuthError) {
+			return createAuthErrorResponse(error);
+		}
+		console.error('Error raising emergency alert:', error);
+		return jsonResponse({ error: 'Failed to raise emergency alert' }, 500);
+	}
+}
+
+/**
+ * An alert's status
+ * GET /api/emergency/alerts/:id
+ */
+export async function getEmergencyAlert(request, env, alertId) {
+	try {
+		const user = await requireAuth(request, env);
+		const alert = await getAlert(getEmergencyDb(env), alertId, user.id);
+		return alert ? jsonResponse({ alert }) : jsonResponse({ error: 'Alert not found' }, 404);
+	} catch (error) {
+		if (error instanceof AuthError) {
+			return createAuthErrorResponse(error);
+		}
+		console.error('Error getting emergency alert:', error);
+		return jsonResponse({ error: 'Failed to get emergency alert' }, 500);
+	}
+}
+
+/**
+ * Send another round unless someone has answered
+ * POST /api/emergency/alerts/:id/repeat
+ */
+export async function repeatEmergencyAlert(request, env, alertId) {
+	try {
+		const user = await requireAuth(request, env);
+		const db = getEmergencyDb(env);
+		const alert = await getAlert(db, alertId, user.id);
+		if (!alert) {
+			return jsonResponse({ error: 'Alert not found' }, 404);
+		}
+		if (alert.status !== 'active') {
+			return jsonResponse({ alert, results: [] });
+		}
+		return jsonResponse(await sendAlertRound(db, env, alert, baseUrlFor(request, env)));
+	} catch (error) {
+		if (error instanceof AuthError) {
+			return createAuthErrorResponse(error);
+		}
+		console.error('Error repeating emergency alert:', error);
+		return jsonResponse({ error: 'Failed to repeat emergency alert' }, 500);
+	}
+}
+
+/**
+ * The user stood the alert down; contacts who were texted are told
+ * POST /api/emergency/alerts/:id/cancel { message }
+ */
+export async function cancelEmergencyAlert(request, env, alertId) {
+	try {
+		const user = await requireAuth(request, env);
+		const { message } = await request.json();
+		const db = getEmergencyDb(env);
+		const alert = await getAlert(db, alertId, user.id);
+		if (!alert) {
+			return jsonResponse({ error: 'Alert not found' }, 404);
+		}
+		const cancelled = await resolveAlert(db, alertId, 'cancelled');
+		if (cancelled && message && alert.rounds > 0 && alert.methods.includes('sms')) {
+			for (const contact of alert.contacts) {
+				try {
+					await sendSms(contact.phone, message, env);
+				} catch (error) {
+					console.error(`Could not send the all-clear to ${contact.phone}:`, error);
+				}
+			}
+		}
+		return jsonResponse({ alert: cancelled || alert });
+	} catch (error) {
+		if (error instanceof AuthError) {
+			return createAuthErrorResponse(error);
+		}
+		console.error('Error cancelling emergency alert:', error);
+		return jsonResponse({ error: 'Failed to cancel emergency alert' }, 500);
+	}
+}
+
+/**
+ * What an alert call says
+ * POST /emergency/:id/twiml (Twilio)
+ */
+export async function handleEmergencyTwiml(request, env, alertId) {
+	const alert = await getAlert(getEmergencyDb(env), alertId);
+	if (!alert || alert.status !== 'active') {
+		return twimlResponse(generateAnswerTwiML(false));
+	}
+	return twimlResponse(generateAlertTwiML(alert));
+}
+
+/**
+ * A contact pressed a key on an alert call; 1 acknowledges it
+ * POST /emergency/:id/answer (Twilio)
+ */
+export async function handleEmergencyAnswer(request, env, alertId) {
+	const formData = await request.formData();
+	const db = getEmergencyDb(env);
+	const alert = await getAlert(db, alertId);
+	const contact = alert ? findContact(alert, formData.get('To')) : null;
+	if (!contact || alert.status !== 'active') {
+		return twimlResponse(generateAnswerTwiML(false));
+	}
+	if (formData.get('Digits') !== '1') {
+		return twimlResponse(generateAlertTwiML(alert));
+	}
+	const acknowledged = await resolveAlert(db, alertId, 'acknowledged', contact.name || contact.phone);
+	return twimlResponse(generateAnswerTwiML(Boolean(acknowledged)));
+}
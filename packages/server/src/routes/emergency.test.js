@@ -0,0 +1,105 @@
+/**
+ * Tests for the emergency alert API: sign-in, the user's own alerts, saved contacts only
+ */
+
+import { test, before, after, beforeEach } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, addUser, apiRequest, json, makeTestEnv, stubFetch } from '../test-env.js';
+import {
+  cancelEmergencyAlert,
+  createEmergencyAlert,
+  getEmergencyAlert,
+  getEmergencyContactList,
+  putEmergencyContacts,
+  repeatEmergencyAlert,
+} from './emergency.js';
+
+const SAM = { name: 'Sam', phone: '+15551234567' };
+const ALERT = { message: 'EMERGENCY: Jo needs help.', methods: ['sms'] };
+
+let env, jo, kim, twilio;
+
+before(async () => {
+  let db;
+  ({ env, db } = await makeTestEnv(SCHEMA.emergency));
+  jo = await addUser(db, env, 'jo');
+  kim = await addUser(db, env, 'kim');
+  twilio = stubFetch();
+});
+
+after(() => twilio.restore());
+
+beforeEach(() => {
+  twilio.sent.length = 0;
+});
+
+async function raise(token, body) {
+  return json(await createEmergencyAlert(apiRequest('POST', '/api/emergency/alerts', { token, body }), env));
+}
+
+test('every route needs a valid token', async () => {
+  const anonymous = [
+    await createEmergencyAlert(apiRequest('POST', '/api/emergency/alerts', { body: { ...ALERT, contacts: [SAM] } }),
+      env),
+    await getEmergencyAlert(apiRequest('GET', '/api/emergency/alerts/a1'), env, 'a1'),
+    await repeatEmergencyAlert(apiRequest('POST', '/api/emergency/alerts/a1/repeat', { body: {} }), env, 'a1'),
+    await cancelEmergencyAlert(apiRequest('POST', '/api/emergency/alerts/a1/cancel', { body: {} }), env, 'a1'),
+    await getEmergencyContactList(apiRequest('GET', '/api/emergency/contacts'), env),
+    await putEmergencyContacts(apiRequest('PUT', '/api/emergency/contacts', { body: { contacts: [SAM] } }), env),
+  ];
+  assert.deepStrictEqual(anonymous.map((r) => r.status), [401, 401, 401, 401, 401, 401]);
+  const forged = await raise('not-a-jwt', ALERT);
+  assert.deepStrictEqual(forged, { status: 401, body: { error: 'Invalid token' } });
+  assert.deepStrictEqual(twilio.sent, []);
+});
+
+test('alerts only go to saved contacts', async () => {
+  assert.match((await raise(jo, ALERT)).body.error, /No emergency contacts saved/);
+
+  const bad = await putEmergencyContacts(apiRequest('PUT', '/api/emergency/contacts',
+    { token: jo, body: { contacts: [{ phone: '12345' }] } }), env);
+  assert.strictEqual(bad.status, 400);
+  const saved = await json(await putEmergencyContacts(apiRequest('PUT', '/api/emergency/contacts',
+    { token: jo, body: { contacts: [SAM] } }), env));
+  assert.deepStrictEqual(saved, { status: 200, body: { contacts: [SAM] } });
+  const listed = await json(await getEmergencyContactList(apiRequest('GET', '/api/emergency/contacts', { token: kim }),
+    env));
+  assert.deepStrictEqual(listed.body, { contacts: [] }); // Another user's list
+
+  const stranger = await raise(jo, { ...ALERT, contacts: [{ name: 'Premium line', phone: '+19005550000' }] });
+  assert.deepStrictEqual(stranger.body, { error: 'contacts[0] is not one of your saved emergency contacts' });
+  assert.deepStrictEqual(twilio.sent, []);
+
+  const raised = await raise(jo, { ...ALERT, user_id: 'kim' }); // The token decides whose alert it is
+  assert.strictEqual(raised.status, 201);
+  assert.strictEqual(raised.body.alert.user_id, 'jo');
+  assert.deepStrictEqual(raised.body.alert.contacts, [SAM]);
+  assert.deepStrictEqual(twilio.sent.map((s) => s.to), [SAM.phone]);
+});
+
+test("another user can't read, repeat or cancel an alert", async () => {
+  const { body } = await raise(jo, ALERT);
+  const id = body.alert.id;
+  twilio.sent.length = 0;
+
+  const peek = await getEmergencyAlert(apiRequest('GET', `/api/emergency/alerts/${id}?user_id=jo`, { token: kim }),
+    env, id);
+  const repeat = await repeatEmergencyAlert(apiRequest('POST', `/api/emergency/alerts/${id}/repeat`,
+    { token: kim, body: { user_id: 'jo' } }), env, id);
+  const cancel = await cancelEmergencyAlert(apiRequest('POST', `/api/emergency/alerts/${id}/cancel`,
+    { token: kim, body: { user_id: 'jo', message: 'All clear' } }), env, id);
+  assert.deepStrictEqual([peek.status, repeat.status, cancel.status], [404, 404, 404]);
+  assert.deepStrictEqual(twilio.sent, []);
+
+  const repeated = await json(await repeatEmergencyAlert(apiRequest('POST', `/api/emergency/alerts/${id}/repeat`,
+    { token: jo, body: {} }), env, id));
+  assert.strictEqual(repeated.body.alert.rounds, 2);
+  const cancelled = await json(await cancelEmergencyAlert(apiRequest('POST', `/api/emergency/alerts/${id}/cancel`,
+    { token: jo, body: { message: 'All clear' } }), env, id));
+  assert.strictEqual(cancelled.body.alert.status, 'cancelled');
+  assert.deepStrictEqual(twilio.sent.map((s) => s.body), [`${ALERT.message} Reply OK to let them know you're on it.`,
+    'All clear']);
+  const status = await json(await getEmergencyAlert(apiRequest('GET', `/api/emergency/alerts/${id}`, { token: jo }),
+    env, id));
+  assert.strictEqual(status.body.alert.status, 'cancelled');
+});
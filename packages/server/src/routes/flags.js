@@ -0,0 +1,31 @@
+/**
+ * Feature Flag Routes
+ *
+ * Handles:
+ * - The assistant fetching its user's feature flag overrides (flag_sync job)
+ *
+ * See lib/flags.js for how overrides are stored.
+ */
+
+import { flagsForUser, getFlagsDb } from '../lib/flags.js';
+
+function json(body, status = 200) {
+  return new Response(JSON.stringify(body), { status, headers: { 'Content-Type': 'application/json' } });
+}
+
+/**
+ * The flag overrides for a user
+ * GET /api/flags?user_id=xxx
+ */
+export async function getFlags(request, env) {
+  try {
+    const userId = new URL(request.url).searchParams.get('user_id');
+    if (!userId) {
+      return json({ error: 'Missing user_id parameter' }, 400);
+    }
+    return json(await flagsForUser(getFlagsDb(env), userId));
+  } catch (error) {
+    console.error('Error getting feature flags:', error);
+    return json({ error: 'Failed to get feature flags' }, 500);
+  }
+}
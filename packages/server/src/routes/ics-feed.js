@@ -0,0 +1,108 @@
+/**
+ * ICS Feed Routes
+ *
+ * A read-only calendar feed of the events the user made with the local
+ * assistant (see assistant/ics_feed.py), for Apple or Google Calendar to
+ * subscribe to. The assistant renders the feed and uploads it; calendar
+ * apps fetch it without an account, so the secret token in the URL is
+ * what keeps it private. Kept in R2:
+ *
+ *   ics-feeds/<token>.ics          the feed itself
+ *   ics-feeds/users/<user_id>.json which token is the user's now
+ *
+ * Uploading under a new token (`xswarm dev ics regenerate`) deletes the
+ * old feed, so the old URL stops working.
+ */
+
+const TOKEN = /^[A-Za-z0-9_-]{32,64}$/;
+const MAX_FEED_BYTES = 2 * 1024 * 1024;
+
+function json(body, status = 200) {
+  return new Response(JSON.stringify(body), { status, headers: { 'Content-Type': 'application/json' } });
+}
+
+function userKey(userId) {
+  return `ics-feeds/users/${encodeURIComponent(userId)}.json`;
+}
+
+async function currentToken(bucket, userId) {
+  const stored = await bucket.get(userKey(userId));
+  return stored ? (await stored.json()).token : null;
+}
+
+/**
+ * Upload the user's feed
+ * PUT /api/calendar/feed { user_id, token, ics }
+ */
+export async function publishIcsFeed(request, env) {
+  try {
+    const { user_id, token, ics } = await request.json();
+    if (!user_id) {
+      return json({ error: 'Missing user_id' }, 400);
+    }
+    if (!TOKEN.test(token || '') || typeof ics !== 'string' || !ics.startsWith('BEGIN:VCALENDAR')) {
+      return json({ error: 'A feed needs a token and the calendar' }, 400);
+    }
+    if (ics.length > MAX_FEED_BYTES) {
+      return json({ error: `Feeds are limited to ${MAX_FEED_BYTES / 1024 / 1024} MB` }, 413);
+    }
+    if (!env.R2_BUCKET) {
+      return json({ error: 'Calendar feeds are not configured' }, 503);
+    }
+    const previous = await currentToken(env.R2_BUCKET, user_id);
+    await env.R2_BUCKET.put(`ics-feeds/${token}.ics`, ics, {
+      httpMetadata: { contentType: 'text/calendar; charset=utf-8' },
+    });
+    if (previous !== token) {
+      await env.R2_BUCKET.put(userKey(user_id), JSON.stringify({ token }));
+      if (previous) {
+        await env.R2_BUCKET.delete(`ics-feeds/${previous}.ics`);
+      }
+    }
+    return json({ published: true, replaced: Boolean(previous && previous !== token) });
+  } catch (error) {
+    console.error('Error publishing ICS feed:', error);
+    return json({ error: 'Failed to publish the feed' }, 500);
+  }
+}
+
+/**
+ * Stop publishing the user's feed
+ * DELETE /api/calendar/feed?user_id=xxx
+ */
+export async function deleteIcsFeed(request, env) {
+  try {
+    const userId = new URL(request.url).searchParams.get('user_id');
+    if (!userId) {
+      return json({ error: 'Missing user_id parameter' }, 400);
+    }
+    if (!env.R2_BUCKET) {
+      return json({ error: 'Calendar feeds are not configured' }, 503);
+    }
+    const token = await currentToken(env.R2_BUCKET, userId);
+    if (token) {
+      await env.R2_BUCKET.delete([`ics-feeds/${token}.ics`, userKey(userId)]);
+    }
+    return json({ deleted: Boolean(token) });
+  } catch (error) {
+    console.error('Error deleting ICS feed:', error);
+    return json({ error: 'Failed to delete the feed' }, 500);
+  }
+}
+
+/**
+ * The feed, for calendar apps (public: the token is the secret)
+ * GET /ics/<token>.ics
+ */
+export async function serveIcsFeed(request, env, token) {
+  if (!TOKEN.test(token) || !env.R2_BUCKET) {
+    return new Response('Not found', { status: 404 });
+  }
+  const stored = await env.R2_BUCKET.get(`ics-feeds/${token}.ics`);
+  if (!stored) {
+    return new Response('Not found', { status: 404 });
+  }
+  return new Response(stored.body, {
+    headers: { 'Content-Type': 'text/calendar; charset=utf-8', 'Cache-Control': 'private, max-age=300' },
+  });
+}
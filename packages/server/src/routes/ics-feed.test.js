@@ -0,0 +1,67 @@
+/**
+ * Tests for the ICS feed: the checks on uploads, serving only well-formed known tokens, and retiring the old URL
+ */
+
+import { test } from 'node:test';
+import assert from 'node:assert';
+import { apiRequest, json, makeBucket } from '../test-env.js';
+import { deleteIcsFeed, publishIcsFeed, serveIcsFeed } from './ics-feed.js';
+
+const env = { R2_BUCKET: makeBucket() };
+const TOKEN = 'a'.repeat(32);
+const NEW_TOKEN = 'b'.repeat(40);
+const ICS = 'BEGIN:VCALENDAR\r\nVERSION:2.0\r\nEND:VCALENDAR\r\n';
+
+function publish(body, to = env) {
+  return publishIcsFeed(apiRequest('PUT', '/api/calendar/feed', { body }), to);
+}
+
+function serve(token) {
+  return serveIcsFeed(apiRequest('GET', `/ics/${token}.ics`), env, token);
+}
+
+test('publishing and deleting need a user_id', async () => {
+  assert.deepStrictEqual(await json(await publish({ token: TOKEN, ics: ICS })),
+    { status: 400, body: { error: 'Missing user_id' } });
+  assert.deepStrictEqual(await json(await deleteIcsFeed(apiRequest('DELETE', '/api/calendar/feed'), env)),
+    { status: 400, body: { error: 'Missing user_id parameter' } });
+});
+
+test('a feed needs a long url-safe token and a calendar', async () => {
+  const bad = [
+    { token: 'short', ics: ICS }, { token: `${'a'.repeat(31)}/`, ics: ICS }, { ics: ICS },
+    { token: TOKEN, ics: '<html>' }, { token: TOKEN, ics: 42 }, { token: TOKEN },
+  ];
+  for (const fields of bad) {
+    assert.deepStrictEqual(await json(await publish({ user_id: 'jo', ...fields })),
+      { status: 400, body: { error: 'A feed needs a token and the calendar' } });
+  }
+  assert.strictEqual(env.R2_BUCKET.objects.size, 0);
+});
+
+test('a feed over 2 MB is refused, and without a bucket feeds are not configured', async () => {
+  const huge = `BEGIN:VCALENDAR\r\n${'X'.repeat(2 * 1024 * 1024)}`;
+  assert.strictEqual((await publish({ user_id: 'jo', token: TOKEN, ics: huge })).status, 413);
+  assert.strictEqual((await publish({ user_id: 'jo', token: TOKEN, ics: ICS }, {})).status, 503);
+  assert.strictEqual(env.R2_BUCKET.objects.size, 0);
+});
+
+test('only a well-formed token with a feed behind it is served', async () => {
+  assert.strictEqual((await serve('../users/jo')).status, 404);
+  assert.strictEqual((await serve('c'.repeat(32))).status, 404);
+});
+
+test('publishing under a new token retires the old url, and deleting retires the feed', async () => {
+  assert.deepStrictEqual((await json(await publish({ user_id: 'jo', token: TOKEN, ics: ICS }))).body,
+    { published: true, replaced: false });
+  assert.strictEqual(await (await serve(TOKEN)).text(), ICS);
+
+  assert.deepStrictEqual((await json(await publish({ user_id: 'jo', token: NEW_TOKEN, ics: ICS }))).body,
+    { published: true, replaced: true });
+  assert.strictEqual((await serve(TOKEN)).status, 404);
+  assert.strictEqual((await serve(NEW_TOKEN)).status, 200);
+
+  const deleted = await json(await deleteIcsFeed(apiRequest('DELETE', '/api/calendar/feed?user_id=jo'), env));
+  assert.deepStrictEqual(deleted.body, { deleted: true });
+  assert.strictEqual((await serve(NEW_TOKEN)).status, 404);
+});
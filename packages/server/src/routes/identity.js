@@ -5,6 +5,7 @@
  * 1. Get current user identity
  * 2. Validate authentication tokens
  * 3. Fetch user permissions and limits
+ * 4. Report metered usage (phone minutes, SMS) counted on the client
  *
  * Architecture:
  * - Server owns all user data (libsql → Turso)
@@ -13,6 +14,7 @@
  */
 
 import { loadConfig } from '../config/loader.js';
+import { getOrCreateUsageRecord, trackSMSUsage, trackVoiceUsage } from '../lib/usage-tracker.js';
 
 /**
  * GET /api/identity
@@ -162,3 +164,53 @@ export async function handleAuthValidate(request, env) {
     });
   }
 }
+
+/**
+ * POST /api/usage
+ * Records phone minutes and SMS the client used since its last report
+ *
+ * Body: { user_id, voice_minutes?, sms_messages? }
+ */
+export async function handleReportUsage(request, env) {
+  try {
+    const body = await request.json();
+    const voiceMinutes = Number(body.voice_minutes) || 0;
+    const smsMessages = Number(body.sms_messages) || 0;
+
+    if (!body.user_id || voiceMinutes < 0 || smsMessages < 0) {
+      return new Response(JSON.stringify({
+        error: 'Bad Request',
+        message: 'Expected user_id and non-negative voice_minutes/sms_messages',
+      }), {
+        status: 400,
+        headers: { 'Content-Type': 'application/json' },
+      });
+    }
+
+    // The tracking updates need this period's usage row to exist
+    await getOrCreateUsageRecord(body.user_id, env);
+    if (voiceMinutes > 0) {
+      await trackVoiceUsage(body.user_id, voiceMinutes, env);
+    }
+    if (smsMessages > 0) {
+      await trackSMSUsage(body.user_id, smsMessages, env);
+    }
+
+    return new Response(JSON.stringify({
+      recorded: { voice_minutes: voiceMinutes, sms_messages: smsMessages },
+    }), {
+      headers: { 'Content-Type': 'application/json' },
+    });
+
+  } catch (error) {
+    console.error('Error recording usage:', error);
+
+    return new Response(JSON.stringify({
+      error: 'Internal Server Error',
+      message: error.message,
+    }), {
+      status: 500,
+      headers: { 'Content-Type': 'application/json' },
+    });
+  }
+}
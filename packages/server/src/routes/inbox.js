@@ -0,0 +1,223 @@
+/**
+ * Unified Inbox API Routes
+ *
+ * Handles:
+ * - Listing inbound SMS, email and voice messages in one view
+ * - Syncing per-item status (unread/read/replied/archived) from clients
+ * - AI-drafted quick replies
+ * - Sending confirmed replies back over the original channel
+ *
+ * Every route needs the user's token and only reaches that user's items
+ * (a reply goes out from the user's number or address).
+ */
+
+import {
+  INBOX_STATUSES,
+  getInboxDb,
+  listInboxItems,
+  getInboxItem,
+  updateInboxItem,
+} from '../lib/inbox.js';
+import { getClaudeResponse } from '../lib/claude.js';
+import { sendSms } from '../lib/outbound.js';
+import { sendEmail } from '../lib/send-email.js';
+import { AuthError, createAuthErrorResponse, requireAuth } from '../lib/auth-middleware.js';
+
+/**
+ * Get the signed-in user's inbox items
+ * GET /api/inbox?status=unread&channel=sms&since=ISO&limit=50
+ */
+export async function getInbox(request, env) {
+  try {
+    const user = await requireAuth(request, env);
+    const url = new URL(request.url);
+    const status = url.searchParams.get('status');
+    if (status && !INBOX_STATUSES.includes(status)) {
+      return new Response(
+        JSON.stringify({ error: `Invalid status. Expected one of: ${INBOX_STATUSES.join(', ')}` }),
+        { status: 400, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const db = getInboxDb(env);
+    const items = await listInboxItems(db, user.id, {
+      status,
+      channel: url.searchParams.get('channel'),
+      since: url.searchParams.get('since'),
+      limit: url.searchParams.get('limit'),
+    });
+
+    return new Response(
+      JSON.stringify({
+        items,
+        unread: items.filter((item) => item.status === 'unread').length,
+        synced_at: new Date().toISOString(),
+      }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error getting inbox:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to get inbox' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Update an inbox item's status
+ * PUT /api/inbox/:id
+ */
+export async function updateInbox(request, env, itemId) {
+  try {
+    const user = await requireAuth(request, env);
+    const body = await request.json();
+    const { status, reply_text } = body;
+
+    if (status === undefined && reply_text === undefined) {
+      return new Response(
+        JSON.stringify({ error: 'No fields to update' }),
+        { status: 400, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+    if (status !== undefined && !INBOX_STATUSES.includes(status)) {
+      return new Response(
+        JSON.stringify({ error: `Invalid status. Expected one of: ${INBOX_STATUSES.join(', ')}` }),
+        { status: 400, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const db = getInboxDb(env);
+    const item = await updateInboxItem(db, itemId, user.id, { status, reply_text });
+
+    if (!item) {
+      return new Response(
+        JSON.stringify({ error: 'Inbox item not found' }),
+        { status: 404, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    return new Response(
+      JSON.stringify({ success: true, item }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error updating inbox item:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to update inbox item' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Draft an AI reply for an inbox item (does not send)
+ * POST /api/inbox/:id/draft
+ */
+export async function draftInboxReply(request, env, itemId) {
+  try {
+    const user = await requireAuth(request, env);
+    const body = await request.json().catch(() => ({}));
+    const { user_name = 'the user', instructions } = body;
+
+    const db = getInboxDb(env);
+    const item = await getInboxItem(db, itemId, user.id);
+
+    if (!item) {
+      return new Response(
+        JSON.stringify({ error: 'Inbox item not found' }),
+        { status: 404, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const prompt = [
+      `Draft a reply on my behalf to this ${item.channel} message from ${item.sender}.`,
+      item.subject ? `Subject: ${item.subject}` : null,
+      `Message:\n${item.content}`,
+      instructions ? `Guidance: ${instructions}` : null,
+      'Return only the reply text.',
+    ].filter(Boolean).join('\n\n');
+
+    const channel = item.channel === 'voice' ? 'sms' : item.channel;
+    const draft = await getClaudeResponse({ name: user_name }, prompt, channel, env);
+
+    return new Response(
+      JSON.stringify({ success: true, item_id: item.id, channel: item.channel, draft }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error drafting inbox reply:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to draft reply' }),
+      { status: 500, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
+
+/**
+ * Send a confirmed reply for an inbox item and mark it replied
+ * POST /api/inbox/:id/reply
+ *
+ * SMS and voice items are answered by SMS; email items by email.
+ */
+export async function sendInboxReply(request, env, itemId) {
+  try {
+    const user = await requireAuth(request, env);
+    const body = await request.json();
+    const { text } = body;
+
+    if (!text || !text.trim()) {
+      return new Response(
+        JSON.stringify({ error: 'Missing required field: text' }),
+        { status: 400, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    const db = getInboxDb(env);
+    const item = await getInboxItem(db, itemId, user.id);
+
+    if (!item) {
+      return new Response(
+        JSON.stringify({ error: 'Inbox item not found' }),
+        { status: 404, headers: { 'Content-Type': 'application/json' } }
+      );
+    }
+
+    let delivery;
+    if (item.channel === 'email') {
+      delivery = await sendEmail({
+        to: item.sender,
+        from: env.FROM_EMAIL || 'boss@xswarm.ai',
+        subject: item.subject ? `Re: ${item.subject}` : 'Re: your message',
+        text,
+      }, env);
+    } else {
+      delivery = await sendSms(item.sender, text, env);
+    }
+
+    const updated = await updateInboxItem(db, itemId, user.id, { status: 'replied', reply_text: text });
+
+    return new Response(
+      JSON.stringify({ success: true, item: updated, delivery }),
+      { status: 200, headers: { 'Content-Type': 'application/json' } }
+    );
+  } catch (error) {
+    if (error instanceof AuthError) {
+      return createAuthErrorResponse(error);
+    }
+    console.error('Error sending inbox reply:', error);
+    return new Response(
+      JSON.stringify({ error: 'Failed to send reply', message: error.message }),
+      { status: 502, headers: { 'Content-Type': 'application/json' } }
+    );
+  }
+}
@@ -0,0 +1,96 @@
+/**
+ * Tests for the inbox API: sign-in, and listing, updating, drafting and replying only to the user's own messages
+ */
+
+import { test, before, after, beforeEach } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, addUser, apiRequest, json, makeTestEnv, stubFetch } from '../test-env.js';
+import { recordInboundMessage } from '../lib/inbox.js';
+import { draftInboxReply, getInbox, sendInboxReply, updateInbox } from './inbox.js';
+
+let env, jo, kim, twilio, joSms;
+
+before(async () => {
+  let db;
+  ({ env, db } = await makeTestEnv(SCHEMA.inbox));
+  jo = await addUser(db, env, 'jo');
+  kim = await addUser(db, env, 'kim');
+  joSms = await recordInboundMessage(env, { channel: 'sms', from: '+15551234567', content: 'Can we move to 3pm?' },
+    'jo');
+  await recordInboundMessage(env, { channel: 'email', from: 'sam@example.com', content: 'Lunch?' }, 'kim');
+  twilio = stubFetch();
+});
+
+after(() => twilio.restore());
+
+beforeEach(() => {
+  twilio.sent.length = 0;
+});
+
+function reply(token, id, body = { text: 'Sure, 3pm works.' }) {
+  return sendInboxReply(apiRequest('POST', `/api/inbox/${id}/reply`, { token, body }), env, id);
+}
+
+function list(token, query = '') {
+  return getInbox(apiRequest('GET', `/api/inbox${query}`, { token }), env);
+}
+
+function update(token, id, body) {
+  return updateInbox(apiRequest('PUT', `/api/inbox/${id}`, { token, body }), env, id);
+}
+
+function draft(token, id) {
+  return draftInboxReply(apiRequest('POST', `/api/inbox/${id}/draft`, { token, body: {} }), env, id);
+}
+
+test('drafting and replying need a valid token', async () => {
+  assert.strictEqual((await reply(undefined, joSms.id)).status, 401);
+  assert.strictEqual((await reply('forged', joSms.id)).status, 401);
+  assert.strictEqual((await draft(undefined, joSms.id)).status, 401);
+  assert.deepStrictEqual(twilio.sent, []);
+});
+
+test("another user's message can't be drafted or replied to", async () => {
+  assert.deepStrictEqual(await json(await reply(kim, joSms.id)),
+    { status: 404, body: { error: 'Inbox item not found' } });
+  assert.strictEqual((await draft(kim, joSms.id)).status, 404);
+  assert.deepStrictEqual(twilio.sent, []);
+});
+
+test('replying checks the text, sends over the channel and marks the item replied', async () => {
+  assert.strictEqual((await reply(jo, joSms.id, { text: '  ' })).status, 400);
+  assert.strictEqual((await reply(jo, 'no-such-item')).status, 404);
+
+  const drafted = await json(await draft(jo, joSms.id));
+  assert.strictEqual(drafted.status, 200);
+  assert.strictEqual(drafted.body.item_id, joSms.id);
+
+  const sent = await json(await reply(jo, joSms.id));
+  assert.strictEqual(sent.status, 200);
+  assert.strictEqual(sent.body.item.status, 'replied');
+  assert.strictEqual(sent.body.item.reply_text, 'Sure, 3pm works.');
+  assert.deepStrictEqual(twilio.sent.map((s) => [s.to, s.body]), [['+15551234567', 'Sure, 3pm works.']]);
+});
+
+test('listing and updating need a valid token', async () => {
+  assert.strictEqual((await list(undefined)).status, 401);
+  assert.strictEqual((await list('forged')).status, 401);
+  assert.strictEqual((await update(undefined, joSms.id, { status: 'read' })).status, 401);
+});
+
+test("the list holds only the token's user's items, whatever user_id is asked for", async () => {
+  const mine = await json(await list(jo, '?user_id=kim'));
+  assert.strictEqual(mine.status, 200);
+  assert.deepStrictEqual(mine.body.items.map((item) => item.user_id), ['jo']);
+  assert.strictEqual((await list(jo, '?status=deleted')).status, 400);
+});
+
+test("another user's item can't be updated", async () => {
+  assert.deepStrictEqual(await json(await update(kim, joSms.id, { status: 'archived' })),
+    { status: 404, body: { error: 'Inbox item not found' } });
+  assert.strictEqual((await update(jo, joSms.id, { status: 'deleted' })).status, 400);
+
+  const read = await json(await update(jo, joSms.id, { status: 'read' }));
+  assert.strictEqual(read.status, 200);
+  assert.strictEqual(read.body.item.status, 'read');
+});
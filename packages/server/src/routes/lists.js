@@ -0,0 +1,63 @@
+/**
+ * Lists API Routes
+ *
+ * Handles:
+ * - The assistant pushing the list items it changed (newest change wins)
+ * - The assistant fetching items changed since its last sync
+ *
+ * See lib/lists.js for the protocol.
+ */
+
+import { MAX_BATCH, getListsDb, listItemsSince, upsertListItems, validateItem } from '../lib/lists.js';
+
+function json(body, status = 200) {
+  return new Response(JSON.stringify(body), { status, headers: { 'Content-Type': 'application/json' } });
+}
+
+/**
+ * List items changed since the last sync
+ * GET /api/lists?user_id=xxx&since=ISO
+ */
+export async function getLists(request, env) {
+  try {
+    const url = new URL(request.url);
+    const userId = url.searchParams.get('user_id');
+    if (!userId) {
+      return json({ error: 'Missing user_id parameter' }, 400);
+    }
+    const since = url.searchParams.get('since');
+    if (since && Number.isNaN(Date.parse(since))) {
+      return json({ error: 'Invalid since parameter' }, 400);
+    }
+    return json(await listItemsSince(getListsDb(env), userId, since));
+  } catch (error) {
+    console.error('Error getting lists:', error);
+    return json({ error: 'Failed to get lists' }, 500);
+  }
+}
+
+/**
+ * Store changed list items
+ * PUT /api/lists/items { user_id, items: [{ id, list, text, done, deleted, updated_at }] }
+ */
+export async function putListItems(request, env) {
+  try {
+    const { user_id, items } = await request.json();
+    if (!user_id) {
+      return json({ error: 'Missing user_id' }, 400);
+    }
+    if (!Array.isArray(items) || items.length > MAX_BATCH) {
+      return json({ error: `items must be an array of at most ${MAX_BATCH}` }, 400);
+    }
+    for (const [index, item] of items.entries()) {
+      const problem = validateItem(item);
+      if (problem) {
+        return json({ error: `items[${index}]: ${problem}` }, 400);
+      }
+    }
+    return json(await upsertListItems(getListsDb(env), user_id, items));
+  } catch (error) {
+    console.error('Error storing list items:', error);
+    return json({ error: 'Failed to store list items' }, 500);
+  }
+}
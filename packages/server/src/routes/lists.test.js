@@ -0,0 +1,72 @@
+/**
+ * Tests for the lists API: the checks on pushed items and on `since`, and that an older change never overwrites a
+ * newer one
+ */
+
+import { test, before } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, apiRequest, json, makeTestEnv } from '../test-env.js';
+import { MAX_BATCH, MAX_TEXT_LENGTH } from '../lib/lists.js';
+import { getLists, putListItems } from './lists.js';
+
+let env;
+
+before(async () => {
+  ({ env } = await makeTestEnv(SCHEMA.lists));
+});
+
+function item(fields = {}) {
+  return { id: 'milk', list: 'Shopping', text: 'Milk', updated_at: '2026-10-16T09:00:00Z', ...fields };
+}
+
+function push(body) {
+  return putListItems(apiRequest('PUT', '/api/lists/items', { body }), env);
+}
+
+function pull(query) {
+  return getLists(apiRequest('GET', `/api/lists${query}`), env);
+}
+
+test('pushing and pulling need a user_id', async () => {
+  assert.deepStrictEqual(await json(await push({ items: [item()] })),
+    { status: 400, body: { error: 'Missing user_id' } });
+  assert.deepStrictEqual(await json(await pull('')), { status: 400, body: { error: 'Missing user_id parameter' } });
+});
+
+test('since must be a timestamp', async () => {
+  assert.deepStrictEqual(await json(await pull('?user_id=jo&since=yesterday')),
+    { status: 400, body: { error: 'Invalid since parameter' } });
+});
+
+test(`items must be an array of at most ${MAX_BATCH}`, async () => {
+  for (const items of [undefined, item(), Array.from({ length: MAX_BATCH + 1 }, (_, i) => item({ id: `i${i}` }))]) {
+    const { status, body } = await json(await push({ user_id: 'jo', items }));
+    assert.strictEqual(status, 400);
+    assert.match(body.error, /items must be an array/);
+  }
+});
+
+test('the first bad item is named and nothing in the batch is stored', async () => {
+  const cases = [
+    [null, 'item must be an object'],
+    [item({ id: 7 }), 'id is required'],
+    [item({ list: '' }), 'list is required'],
+    [item({ text: '   ' }), 'text is required'],
+    [item({ text: 'x'.repeat(MAX_TEXT_LENGTH + 1) }), `text is longer than ${MAX_TEXT_LENGTH} characters`],
+    [item({ updated_at: 'soon' }), 'updated_at must be a timestamp'],
+  ];
+  for (const [bad, problem] of cases) {
+    assert.deepStrictEqual(await json(await push({ user_id: 'kim', items: [item(), bad] })),
+      { status: 400, body: { error: `items[1]: ${problem}` } });
+  }
+  assert.deepStrictEqual((await json(await pull('?user_id=kim'))).body.items, []);
+});
+
+test('an older change to an item is ignored', async () => {
+  await push({ user_id: 'jo', items: [item({ text: 'Oat milk', updated_at: '2026-10-16T10:00:00Z' })] });
+  const stale = await json(await push({ user_id: 'jo', items: [item({ text: 'Milk' })] }));
+  assert.strictEqual(stale.body.stored, 0);
+
+  const { items } = (await json(await pull('?user_id=jo'))).body;
+  assert.deepStrictEqual(items.map(({ list, text }) => [list, text]), [['shopping', 'Oat milk']]);
+});
@@ -0,0 +1,84 @@
+/**
+ * Release Routes
+ *
+ * Where `xswarm self-update` finds new versions of the binary. Releases
+ * are uploaded to R2 by whoever cuts them:
+ *
+ *   releases/<channel>/latest.json   the signed manifest (version, notes, sha256 per platform)
+ *   releases/<version>/<platform>    the binary, e.g. releases/0.33.0/linux-x86_64
+ *
+ * The manifest is signed offline (see assistant/self_update.py), so the
+ * server only hands out what it was given - the assistant checks the
+ * signature and each binary's checksum itself.
+ */
+
+const CHANNEL = /^[a-z][a-z0-9-]{0,31}$/;
+const VERSION = /^\d+\.\d+\.\d+[0-9A-Za-z.+-]{0,32}$/;
+const PLATFORM = /^[a-z]+-[a-z0-9_]+$/;
+
+function jsonResponse(data, status = 200) {
+	return new Response(JSON.stringify(data), {
+		status,
+		headers: { 'Content-Type': 'application/json' },
+	});
+}
+
+/**
+ * The latest release manifest of a channel
+ * GET /api/releases/latest?channel=stable
+ */
+export async function getLatestRelease(request, env) {
+	const channel = new URL(request.url).searchParams.get('channel') || 'stable';
+	if (!CHANNEL.test(channel)) {
+		return jsonResponse({ error: 'Invalid channel' }, 400);
+	}
+	if (!env.R2_BUCKET) {
+		return jsonResponse({ error: 'Releases are not configured' }, 503);
+	}
+	try {
+		const object = await env.R2_BUCKET.get(`releases/${channel}/latest.json`);
+		if (!object) {
+			return jsonResponse({ error: `No release on the ${channel} channel` }, 404);
+		}
+		return new Response(object.body, {
+			status: 200,
+			headers: {
+				'Content-Type': 'application/json',
+				'Cache-Control': 'public, max-age=300',
+			},
+		});
+	} catch (error) {
+		console.error('Release manifest error:', error);
+		return jsonResponse({ error: 'Failed to read the release manifest' }, 500);
+	}
+}
+
+/**
+ * A release's binary for one platform
+ * GET /api/releases/:version/:platform
+ */
+export async function getReleaseAsset(request, env, version, platform) {
+	if (!VERSION.test(version) || !PLATFORM.test(platform)) {
+		return jsonResponse({ error: 'Invalid version or platform' }, 400);
+	}
+	if (!env.R2_BUCKET) {
+		return jsonResponse({ error: 'Releases are not configured' }, 503);
+	}
+	try {
+		const object = await env.R2_BUCKET.get(`releases/${version}/${platform}`);
+		if (!object) {
+			return jsonResponse({ error: `No ${version} build for ${platform}` }, 404);
+		}
+		return new Response(object.body, {
+			status: 200,
+			headers: {
+				'Content-Type': 'application/octet-stream',
+				'Content-Length': object.size.toString(),
+				'Cache-Control': 'public, max-age=31536000, immutable', // A version's binary never changes
+			},
+		});
+	} catch (error) {
+		console.error('Release download error:', error);
+		return jsonResponse({ error: 'Failed to read the release' }, 500);
+	}
+}
@@ -0,0 +1,124 @@
+/**
+ * RSVP Route
+ *
+ * Public landing page for appointment invitation links.
+ *
+ * GET /rsvp/:token                      - Show the invitation with Yes/No/Maybe links
+ * GET /rsvp/:token?response=accepted    - Record the response (accepted|declined|tentative)
+ */
+
+import { getParticipantByToken, recordRsvp } from '../lib/appointment-participants.js';
+
+const RESPONSE_TEXT = {
+  accepted: "✓ You're attending. See you there!",
+  declined: '✗ You declined. Thanks for letting us know.',
+  tentative: '? Marked as maybe.',
+};
+
+/**
+ * Handle an RSVP link
+ *
+ * @param {Request} request
+ * @param {Object} env
+ * @param {string} token - The participant's RSVP token
+ * @returns {Response}
+ */
+export async function handleRsvp(request, env, token) {
+  try {
+    const url = new URL(request.url);
+    const response = url.searchParams.get('response');
+
+    const participant = await getParticipantByToken(env, token);
+    if (!participant) {
+      return rsvpPage('Invitation not found', '<p class="error">✗ This invitation link is invalid or has expired.</p>', 404);
+    }
+
+    const title = escapeHtml(participant.appointment_title);
+    const when = escapeHtml(new Date(participant.appointment_start).toUTCString());
+
+    if (response) {
+      if (!RESPONSE_TEXT[response]) {
+        return rsvpPage('Invalid response', '<p class="error">✗ Unknown response.</p>', 400);
+      }
+      await recordRsvp(env, token, response);
+      return rsvpPage('RSVP recorded', `
+        <p>${title}<br>${when}</p>
+        <p class="success">${RESPONSE_TEXT[response]}</p>
+        <p><a href="?response=accepted">Yes</a> · <a href="?response=declined">No</a> · <a href="?response=tentative">Maybe</a></p>
+      `);
+    }
+
+    return rsvpPage('Invitation', `
+      <p>You're invited to<br><strong>${title}</strong><br>${when}</p>
+      <p>Will you attend?</p>
+      <p><a href="?response=accepted">Yes</a> · <a href="?response=declined">No</a> · <a href="?response=tentative">Maybe</a></p>
+    `);
+  } catch (error) {
+    console.error('RSVP error:', error);
+    return new Response('Error processing RSVP', { status: 500 });
+  }
+}
+
+function rsvpPage(heading, body, status = 200) {
+  const html = `
+<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1.0">
+  <title>${heading} - xSwarm</title>
+  <style>
+    body {
+      font-family: 'Monaco', 'Courier New', monospace;
+      background-color: #000000;
+      color: #00ff00;
+      display: flex;
+      justify-content: center;
+      align-items: center;
+      min-height: 100vh;
+      margin: 0;
+      padding: 20px;
+    }
+    .container {
+      max-width: 600px;
+      padding: 40px;
+      border: 2px solid #00ff00;
+      background-color: #0a0a0a;
+      text-align: center;
+    }
+    p {
+      color: #00cc00;
+      line-height: 1.6;
+    }
+    .success {
+      color: #00ff00;
+    }
+    .error {
+      color: #ff0000;
+    }
+    a {
+      color: #00ff00;
+      text-decoration: none;
+      border-bottom: 1px solid #00ff00;
+    }
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>[ xSWARM AI ]</h1>
+    ${body}
+  </div>
+</body>
+</html>
+  `.trim();
+
+  return new Response(html, { status, headers: { 'Content-Type': 'text/html' } });
+}
+
+function escapeHtml(text) {
+  return String(text ?? '')
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;');
+}
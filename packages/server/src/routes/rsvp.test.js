@@ -0,0 +1,52 @@
+/**
+ * Tests for the RSVP page: unknown links, unknown answers, recording an answer, and escaping the invitation
+ */
+
+import { test, before } from 'node:test';
+import assert from 'node:assert';
+import { SCHEMA, apiRequest, makeTestEnv } from '../test-env.js';
+import { handleRsvp } from './rsvp.js';
+
+let env, db;
+
+before(async () => {
+  ({ env, db } = await makeTestEnv(SCHEMA.calendar));
+  await db.execute(`INSERT INTO appointments (id, user_id, title, start_time, end_time)
+    VALUES ('party', 'jo', '<script>alert(1)</script> party', '2026-10-24T19:00:00Z', '2026-10-24T23:00:00Z')`);
+  await db.execute(`INSERT INTO appointment_participants (id, appointment_id, label, rsvp_token, created_at)
+    VALUES ('p1', 'party', 'sam@example.com', 'token-sam', '2026-10-16T09:00:00Z')`);
+});
+
+function rsvp(token, query = '') {
+  return handleRsvp(apiRequest('GET', `/rsvp/${token}${query}`), env, token);
+}
+
+async function status() {
+  return (await db.execute("SELECT rsvp_status FROM appointment_participants WHERE id = 'p1'")).rows[0].rsvp_status;
+}
+
+test('an unknown link is a 404 page', async () => {
+  const response = await rsvp('token-nobody', '?response=accepted');
+  assert.strictEqual(response.status, 404);
+  assert.match(await response.text(), /invalid or has expired/);
+});
+
+test("an answer that isn't yes, no or maybe is refused and records nothing", async () => {
+  for (const answer of ['pending', 'maybe', 'ACCEPTED']) {
+    const response = await rsvp('token-sam', `?response=${answer}`);
+    assert.strictEqual(response.status, 400);
+    assert.match(await response.text(), /Unknown response/);
+  }
+  assert.strictEqual(await status(), 'pending');
+});
+
+test('the invitation is escaped, and an answer is recorded', async () => {
+  const page = await (await rsvp('token-sam')).text();
+  assert.ok(!page.includes('<script>'));
+  assert.match(page, /&lt;script&gt;alert\(1\)&lt;\/script&gt; party/);
+
+  const response = await rsvp('token-sam', '?response=tentative');
+  assert.strictEqual(response.status, 200);
+  assert.match(await response.text(), /Marked as maybe/);
+  assert.strictEqual(await status(), 'tentative');
+});
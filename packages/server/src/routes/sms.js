@@ -15,6 +15,7 @@ import {
   getHelpResponse
 } from '../lib/claude.js';
 import { getSupervisorClient } from '../lib/supervisor-client.js';
+import { acknowledgeBySms, getEmergencyDb } from '../lib/emergency.js';
 
 /**
  * Get authorized user by Boss phone and validate sender
@@ -99,6 +100,20 @@ export async function handleSmsWebhook(request, env, userId) {
     console.log(`SMS from: ${sender} to Boss: ${bossPhone}`);
     console.log(`Message: "${message}" (${messageSid})`);
 
+    // An emergency contact replying OK to an alert (they aren't authorized users)
+    if (env.TURSO_DATABASE_URL) {
+      const acknowledged = await acknowledgeBySms(getEmergencyDb(env), userId, sender, message).catch((error) => {
+        console.error('Emergency acknowledgement check failed:', error);
+        return null;
+      });
+      if (acknowledged) {
+        return new Response(`<?xml version="1.0" encoding="UTF-8"?>
+<Response>
+  <Message>Thank you. They'll be told you're on it.</Message>
+</Response>`, { status: 200, headers: { 'Content-Type': 'application/xml' } });
+      }
+    }
+
     // Security check: Validate sender is authorized for this Boss phone
     const user = getAuthorizedUserForSms(bossPhone, sender);
 
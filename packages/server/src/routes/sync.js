@@ -0,0 +1,116 @@
+/**
+ * Memory Sync Relay Routes
+ *
+ * The local assistants on one user's machines keep facts, lists and
+ * scheduling preferences in step through here (see
+ * assistant/memory_sync.py). Every blob is encrypted on the machine that
+ * sent it with a key only the user's machines have, so the server stores
+ * and hands back bytes it can't read. Blobs are kept in R2 by group
+ * (the key they're encrypted with) in the order they arrived:
+ *
+ *   sync/<user_id>/<group>/<arrival time>-<random>
+ *
+ * A machine pulls everything after the last id it saw. Blobs older than
+ * RETENTION_DAYS are deleted when a machine pushes, so a machine that was
+ * offline for longer starts again from its own state.
+ */
+
+const GROUP = /^[a-f0-9]{8,32}$/;
+const DEVICE = /^[a-f0-9]{6,32}$/;
+const BLOB_ID = /^\d{15}-[a-f0-9]{8}$/;
+const MAX_BLOB_BYTES = 256 * 1024;
+const PULL_LIMIT = 100;
+const RETENTION_DAYS = 90;
+
+function json(body, status = 200) {
+  return new Response(JSON.stringify(body), { status, headers: { 'Content-Type': 'application/json' } });
+}
+
+function prefix(userId, group) {
+  return `sync/${encodeURIComponent(userId)}/${group}/`;
+}
+
+function blobId(time = Date.now()) {
+  const random = crypto.getRandomValues(new Uint8Array(4));
+  return `${String(time).padStart(15, '0')}-${[...random].map((b) => b.toString(16).padStart(2, '0')).join('')}`;
+}
+
+async function pruneOld(bucket, base) {
+  const cutoff = String(Date.now() - RETENTION_DAYS * 24 * 60 * 60 * 1000).padStart(15, '0');
+  const listed = await bucket.list({ prefix: base, limit: PULL_LIMIT });
+  const old = listed.objects.map((object) => object.key).filter((key) => key.slice(base.length) < cutoff);
+  if (old.length) {
+    await bucket.delete(old);
+  }
+}
+
+/**
+ * Store an encrypted blob
+ * POST /api/sync/blobs { user_id, group, device, blob: "<base64 ciphertext>" }
+ */
+export async function pushSyncBlob(request, env) {
+  try {
+    const { user_id, group, device, blob } = await request.json();
+    if (!user_id) {
+      return json({ error: 'Missing user_id' }, 400);
+    }
+    if (!GROUP.test(group || '') || !DEVICE.test(device || '') || typeof blob !== 'string' || !blob) {
+      return json({ error: 'A blob needs a group, a device and the ciphertext' }, 400);
+    }
+    if (blob.length > MAX_BLOB_BYTES) {
+      return json({ error: `Sync blobs are limited to ${MAX_BLOB_BYTES / 1024} KB` }, 413);
+    }
+    if (!env.R2_BUCKET) {
+      return json({ error: 'Memory sync is not configured' }, 503);
+    }
+    const base = prefix(user_id, group);
+    const id = blobId();
+    await env.R2_BUCKET.put(base + id, JSON.stringify({ device, blob }), {
+      httpMetadata: { contentType: 'application/json' },
+    });
+    await pruneOld(env.R2_BUCKET, base);
+    return json({ id }, 201);
+  } catch (error) {
+    console.error('Error storing sync blob:', error);
+    return json({ error: 'Failed to store the sync blob' }, 500);
+  }
+}
+
+/**
+ * Encrypted blobs after a given one, oldest first
+ * GET /api/sync/blobs?user_id=xxx&group=xxx&since=<blob id>
+ */
+export async function pullSyncBlobs(request, env) {
+  try {
+    const params = new URL(request.url).searchParams;
+    const userId = params.get('user_id');
+    const group = params.get('group') || '';
+    const since = params.get('since') || '';
+    if (!userId) {
+      return json({ error: 'Missing user_id parameter' }, 400);
+    }
+    if (!GROUP.test(group) || (since && !BLOB_ID.test(since))) {
+      return json({ error: 'Bad group or since' }, 400);
+    }
+    if (!env.R2_BUCKET) {
+      return json({ error: 'Memory sync is not configured' }, 503);
+    }
+    const base = prefix(userId, group);
+    const listed = await env.R2_BUCKET.list({
+      prefix: base,
+      limit: PULL_LIMIT,
+      ...(since ? { startAfter: base + since } : {}),
+    });
+    const blobs = [];
+    for (const object of listed.objects) {
+      const stored = await env.R2_BUCKET.get(object.key);
+      if (stored) {
+        blobs.push({ id: object.key.slice(base.length), ...(await stored.json()) });
+      }
+    }
+    return json({ blobs, more: listed.truncated });
+  } catch (error) {
+    console.error('Error listing sync blobs:', error);
+    return json({ error: 'Failed to list sync blobs' }, 500);
+  }
+}
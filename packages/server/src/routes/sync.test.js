@@ -0,0 +1,71 @@
+/**
+ * Tests for the memory sync relay: the checks on pushed blobs and pull cursors, and a relay without a bucket
+ */
+
+import { test } from 'node:test';
+import assert from 'node:assert';
+import { apiRequest, json, makeBucket } from '../test-env.js';
+import { pullSyncBlobs, pushSyncBlob } from './sync.js';
+
+const env = { R2_BUCKET: makeBucket() };
+const GROUP = 'a1b2c3d4e5f60718';
+
+function blob(fields = {}) {
+  return { user_id: 'jo', group: GROUP, device: 'd00d1e', blob: 'c2VjcmV0', ...fields };
+}
+
+function push(body, to = env) {
+  return pushSyncBlob(apiRequest('POST', '/api/sync/blobs', { body }), to);
+}
+
+function pull(query, from = env) {
+  return pullSyncBlobs(apiRequest('GET', `/api/sync/blobs${query}`), from);
+}
+
+test('pushing and pulling need a user_id', async () => {
+  assert.deepStrictEqual(await json(await push(blob({ user_id: '' }))),
+    { status: 400, body: { error: 'Missing user_id' } });
+  assert.deepStrictEqual(await json(await pull(`?group=${GROUP}`)),
+    { status: 400, body: { error: 'Missing user_id parameter' } });
+});
+
+test('a blob needs a hex group and device and some ciphertext', async () => {
+  const bad = [
+    { group: 'not-hex!' }, { group: 'abc' }, { group: undefined },
+    { device: 'ZZZZZZ' }, { device: undefined },
+    { blob: '' }, { blob: { ciphertext: 'x' } },
+  ];
+  for (const fields of bad) {
+    assert.deepStrictEqual(await json(await push(blob(fields))),
+      { status: 400, body: { error: 'A blob needs a group, a device and the ciphertext' } });
+  }
+  assert.strictEqual(env.R2_BUCKET.objects.size, 0);
+});
+
+test('a blob over 256 KB is refused', async () => {
+  const { status } = await json(await push(blob({ blob: 'A'.repeat(256 * 1024 + 1) })));
+  assert.strictEqual(status, 413);
+  assert.strictEqual(env.R2_BUCKET.objects.size, 0);
+});
+
+test('pulling needs a hex group and a blob id for since', async () => {
+  for (const query of ['?user_id=jo', '?user_id=jo&group=xyz', `?user_id=jo&group=${GROUP}&since=yesterday`]) {
+    assert.deepStrictEqual(await json(await pull(query)), { status: 400, body: { error: 'Bad group or since' } });
+  }
+});
+
+test('without a bucket the relay says it is not configured', async () => {
+  assert.strictEqual((await push(blob(), {})).status, 503);
+  assert.strictEqual((await pull(`?user_id=jo&group=${GROUP}`, {})).status, 503);
+});
+
+test("a machine pulls only what came after the last blob it saw, and only its own user's", async () => {
+  const first = (await json(await push(blob({ blob: 'Zmlyc3Q=' })))).body.id;
+  await new Promise((resolve) => setTimeout(resolve, 2)); // Ids order by arrival to the millisecond
+  await push(blob({ blob: 'c2Vjb25k' }));
+  await push(blob({ user_id: 'kim', blob: 'a2lt' }));
+
+  const { body } = await json(await pull(`?user_id=jo&group=${GROUP}&since=${first}`));
+  assert.deepStrictEqual(body.blobs.map((b) => b.blob), ['c2Vjb25k']);
+  assert.strictEqual(body.more, false);
+});
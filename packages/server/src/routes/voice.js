@@ -5,7 +5,6 @@
  * Validates caller against whitelist before accepting call.
  */
 
-import { verifyTwilioSignature } from '../lib/auth.js';
 import { getUserByXswarmPhone } from '../lib/database.js';
 import { rejectCall, answerCall } from '../lib/twilio.js';
 
@@ -31,14 +30,7 @@ export async function handleVoiceWebhook(request, env, userId) {
 
     console.log(`Voice webhook: ${caller} → ${xswarmPhone} (${callSid})`);
 
-    // Verify Twilio signature
-    const url = request.url;
-    const signature = request.headers.get('X-Twilio-Signature');
-
-    if (!verifyTwilioSignature(url, params, signature, env.TWILIO_AUTH_TOKEN)) {
-      console.error('Invalid Twilio signature');
-      return new Response('Forbidden', { status: 403 });
-    }
+    // Twilio signature already checked by middleware/webhook-signature.js
 
     // Get user from database
     const user = await getUserByXswarmPhone(xswarmPhone, env);
@@ -128,6 +128,21 @@ export function extractTitle(text) {
   return title;
 }
 
+/**
+ * List the required details a schedule command leaves out
+ * Pure function - returns [] when the appointment can be created as asked
+ */
+export function missingScheduleDetails(text) {
+  const missing = [];
+  if (extractTitle(text) === 'Meeting') {
+    missing.push('title');
+  }
+  if (extractHour(text.toLowerCase()) === null) {
+    missing.push('time');
+  }
+  return missing;
+}
+
 /**
  * Extract reminder text from remind command
  * Pure function - no side effects
@@ -200,6 +215,21 @@ export function buildScheduleResponse(title, startTime) {
   return `Scheduled: ${title} on ${date.toLocaleDateString()} at ${date.toLocaleTimeString()}`;
 }
 
+/**
+ * Build clarification question for a schedule command missing details
+ * Pure function - no side effects
+ */
+export function buildScheduleClarification(title, missing) {
+  const example = `schedule ${missing.includes('title') ? 'dentist' : title} tomorrow at 2pm`;
+  if (missing.includes('title') && missing.includes('time')) {
+    return `What should I schedule, and when? Reply like '${example}'.`;
+  }
+  if (missing.includes('title')) {
+    return `What should I call it? Reply like '${example}'.`;
+  }
+  return `What time should I schedule ${title}? Reply like '${example}'.`;
+}
+
 /**
  * Build reminder confirmation response
  * Pure function - no side effects
@@ -439,6 +469,13 @@ async function handleScheduleCommand(userId, content, env) {
   try {
     // Pure functions - easy to test
     const title = extractTitle(content);
+
+    // Ask rather than guess a time or title; nothing is created until both are given
+    const missing = missingScheduleDetails(content);
+    if (missing.length > 0) {
+      return buildScheduleClarification(title, missing);
+    }
+
     const startTime = parseDateTime(content);
     const endTime = new Date(new Date(startTime).getTime() + 60 * 60 * 1000).toISOString();
 
@@ -17,6 +17,8 @@ import {
   detectCommand,
   formatUser,
   buildScheduleResponse,
+  buildScheduleClarification,
+  missingScheduleDetails,
   buildReminderResponse,
   buildCalendarResponse,
   buildHelpMessage,
@@ -123,6 +125,31 @@ test('extractTitle - defaults to "Meeting" for short/empty titles', () => {
   assert.strictEqual(result, 'Meeting');
 });
 
+test('missingScheduleDetails - nothing missing when title and time are given', () => {
+  const result = missingScheduleDetails('schedule dentist appointment tomorrow at 2pm');
+  assert.deepStrictEqual(result, []);
+});
+
+test('missingScheduleDetails - flags a missing time', () => {
+  const result = missingScheduleDetails('schedule dentist appointment tomorrow');
+  assert.deepStrictEqual(result, ['time']);
+});
+
+test('missingScheduleDetails - flags a missing title and time', () => {
+  const result = missingScheduleDetails('schedule a meeting');
+  assert.deepStrictEqual(result, ['title', 'time']);
+});
+
+test('buildScheduleClarification - asks for the missing time', () => {
+  const result = buildScheduleClarification('dentist', ['time']);
+  assert.strictEqual(result, "What time should I schedule dentist? Reply like 'schedule dentist tomorrow at 2pm'.");
+});
+
+test('buildScheduleClarification - asks for the missing title', () => {
+  const result = buildScheduleClarification('Meeting', ['title']);
+  assert.ok(result.startsWith('What should I call it?'));
+});
+
 test('extractReminderText - extracts reminder text', () => {
   const result = extractReminderText('remind me to call John tomorrow at 3pm');
   assert.strictEqual(result, 'call John');
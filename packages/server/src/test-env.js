@@ -0,0 +1,197 @@
+/**
+ * Shared setup for the route tests (*.test.js)
+ *
+ * A throwaway SQLite file stands in for Turso: every module's client points
+ * at it through env.TURSO_DATABASE_URL, so the routes run their real SQL.
+ * Tokens are real JWTs for users in its `users` table, Twilio is a
+ * fetch stub that records what would have been sent, and R2 is an
+ * in-memory bucket.
+ */
+
+import { mkdtempSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import { join } from 'node:path';
+import { createClient } from '@libsql/client';
+import { generateToken } from './lib/jwt.js';
+
+export const BASE_URL = 'https://xswarm.test';
+
+// The tables each area's routes use, as their scripts/migrate-*.js create them
+export const SCHEMA = {
+  calendar: [
+    `CREATE TABLE appointments (
+      id TEXT PRIMARY KEY, user_id TEXT NOT NULL, title TEXT NOT NULL, description TEXT, start_time TEXT NOT NULL,
+      end_time TEXT NOT NULL, timezone TEXT, location TEXT, latitude REAL, longitude REAL, recurrence_rule TEXT,
+      participants TEXT, tags TEXT, status TEXT NOT NULL DEFAULT 'scheduled', external_calendar_id TEXT,
+      external_event_id TEXT, created_at TEXT NOT NULL DEFAULT (datetime('now')), updated_at TEXT
+    )`,
+    `CREATE TABLE appointment_participants (
+      id TEXT PRIMARY KEY, appointment_id TEXT NOT NULL, label TEXT NOT NULL, name TEXT, email TEXT, phone TEXT,
+      rsvp_status TEXT NOT NULL DEFAULT 'pending', rsvp_token TEXT NOT NULL UNIQUE, invited_at TEXT,
+      invited_via TEXT, responded_at TEXT, created_at TEXT NOT NULL, UNIQUE (appointment_id, label)
+    )`,
+    `CREATE TABLE calendar_changes (
+      seq INTEGER PRIMARY KEY AUTOINCREMENT, user_id TEXT NOT NULL, entity TEXT NOT NULL, entity_id TEXT NOT NULL,
+      op TEXT NOT NULL, changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+    )`,
+    `CREATE TRIGGER appointments_journal_insert AFTER INSERT ON appointments BEGIN
+      INSERT INTO calendar_changes (user_id, entity, entity_id, op)
+      VALUES (NEW.user_id, 'appointment', NEW.id, 'upsert');
+    END`,
+    `CREATE TRIGGER appointments_journal_update AFTER UPDATE ON appointments BEGIN
+      INSERT INTO calendar_changes (user_id, entity, entity_id, op)
+      VALUES (NEW.user_id, 'appointment', NEW.id, 'upsert');
+    END`,
+    `CREATE TRIGGER appointments_journal_delete AFTER DELETE ON appointments BEGIN
+      INSERT INTO calendar_changes (user_id, entity, entity_id, op)
+      VALUES (OLD.user_id, 'appointment', OLD.id, 'delete');
+    END`,
+  ],
+  commands: [
+    `CREATE TABLE client_commands (
+      id TEXT PRIMARY KEY, user_id TEXT NOT NULL, type TEXT NOT NULL, args TEXT NOT NULL DEFAULT '{}',
+      idempotency_key TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'pending', result TEXT,
+      created_at TEXT NOT NULL DEFAULT (datetime('now')), expires_at TEXT NOT NULL, acked_at TEXT,
+      UNIQUE (user_id, idempotency_key)
+    )`,
+  ],
+  emergency: [
+    `CREATE TABLE emergency_alerts (
+      id TEXT PRIMARY KEY, user_id TEXT NOT NULL, message TEXT NOT NULL, contacts TEXT NOT NULL,
+      methods TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'active', rounds INTEGER NOT NULL DEFAULT 0,
+      acknowledged_by TEXT, created_at TEXT NOT NULL DEFAULT (datetime('now')), last_sent_at TEXT, resolved_at TEXT
+    )`,
+    `CREATE TABLE emergency_contacts (
+      user_id TEXT NOT NULL, phone TEXT NOT NULL, name TEXT NOT NULL DEFAULT '',
+      position INTEGER NOT NULL DEFAULT 0, PRIMARY KEY (user_id, phone)
+    )`,
+  ],
+  inbox: [
+    `CREATE TABLE inbox_items (
+      id TEXT PRIMARY KEY, user_id TEXT NOT NULL, channel TEXT NOT NULL, sender TEXT NOT NULL, subject TEXT,
+      content TEXT NOT NULL, external_id TEXT, status TEXT NOT NULL DEFAULT 'unread', reply_text TEXT,
+      received_at TEXT NOT NULL, replied_at TEXT, created_at TEXT NOT NULL, updated_at TEXT
+    )`,
+  ],
+  lists: [
+    `CREATE TABLE list_items (
+      id TEXT NOT NULL, user_id TEXT NOT NULL, list_name TEXT NOT NULL, text TEXT NOT NULL,
+      done INTEGER NOT NULL DEFAULT 0, deleted INTEGER NOT NULL DEFAULT 0, updated_at TEXT NOT NULL,
+      synced_at TEXT NOT NULL DEFAULT (datetime('now')), PRIMARY KEY (user_id, id)
+    )`,
+  ],
+  screening: [
+    `CREATE TABLE screened_calls (
+      id TEXT PRIMARY KEY, user_id TEXT NOT NULL, caller TEXT NOT NULL, caller_name TEXT,
+      purpose TEXT NOT NULL DEFAULT '', status TEXT NOT NULL DEFAULT 'screening', decline_message TEXT,
+      created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+    )`,
+    `CREATE TABLE voicemails (
+      id TEXT PRIMARY KEY, user_id TEXT NOT NULL, call_sid TEXT NOT NULL, caller TEXT NOT NULL, recording_url TEXT,
+      duration INTEGER NOT NULL DEFAULT 0, transcript TEXT, created_at TEXT NOT NULL
+    )`,
+  ],
+};
+
+/**
+ * An env and a client on a fresh database with `users` and the given tables
+ *
+ * @param {string[]} schema - CREATE TABLE statements the routes under test need (see SCHEMA)
+ */
+export async function makeTestEnv(schema = []) {
+  const path = join(mkdtempSync(join(tmpdir(), 'xswarm-test-')), 'test.db');
+  const env = {
+    TURSO_DATABASE_URL: `file:${path}`,
+    JWT_SECRET: 'test-jwt-secret',
+    PUBLIC_BASE_URL: BASE_URL,
+    TWILIO_ACCOUNT_SID: 'AC123',
+    TWILIO_AUTH_TOKEN: 'twilio-token',
+    TWILIO_PHONE_NUMBER: '+15550000000',
+  };
+  const db = createClient({ url: env.TURSO_DATABASE_URL });
+  await db.execute(`
+    CREATE TABLE users (
+      id TEXT PRIMARY KEY,
+      email TEXT,
+      email_verified INTEGER NOT NULL DEFAULT 1,
+      jwt_version INTEGER NOT NULL DEFAULT 0,
+      subscription_tier TEXT NOT NULL DEFAULT 'free'
+    )
+  `);
+  for (const statement of schema) {
+    await db.execute(statement);
+  }
+  return { env, db };
+}
+
+/**
+ * Add a verified user; returns their bearer token
+ */
+export async function addUser(db, env, id) {
+  const user = { id, email: `${id}@example.com`, jwt_version: 0 };
+  await db.execute({ sql: 'INSERT INTO users (id, email) VALUES (?, ?)', args: [id, user.email] });
+  return generateToken(user, env.JWT_SECRET);
+}
+
+/**
+ * A request to the API, signed in with `token` when given
+ */
+export function apiRequest(method, path, { token, body, form } = {}) {
+  const headers = {};
+  if (token) headers.Authorization = `Bearer ${token}`;
+  if (body !== undefined) headers['Content-Type'] = 'application/json';
+  return new Request(`${BASE_URL}${path}`, {
+    method,
+    headers,
+    body: form ? new URLSearchParams(form) : body === undefined ? undefined : JSON.stringify(body),
+  });
+}
+
+/**
+ * Replace fetch so Twilio (and any other outbound call) is recorded instead of sent
+ *
+ * @returns {{sent: Array<{url: string, to: string, body: string}>, restore: Function}}
+ */
+export function stubFetch() {
+  const realFetch = globalThis.fetch;
+  const sent = [];
+  globalThis.fetch = async (url, init = {}) => {
+    const form = new URLSearchParams(typeof init.body === 'string' ? init.body : '');
+    sent.push({ url: String(url), to: form.get('To'), body: form.get('Body') });
+    return new Response(JSON.stringify({ sid: `SM${sent.length}`, status: 'queued' }), {
+      status: 201,
+      headers: { 'Content-Type': 'application/json' },
+    });
+  };
+  return { sent, restore: () => { globalThis.fetch = realFetch; } };
+}
+
+/**
+ * An in-memory stand-in for an R2 bucket: put, get, list (sorted, with
+ * prefix, startAfter and limit) and delete of one key or several
+ */
+export function makeBucket() {
+  const objects = new Map();
+  return {
+    objects,
+    async put(key, value) {
+      objects.set(key, String(value));
+    },
+    async get(key) {
+      if (!objects.has(key)) return null;
+      const text = objects.get(key);
+      return { body: text, text: async () => text, json: async () => JSON.parse(text) };
+    },
+    async list({ prefix = '', limit = 1000, startAfter = '' } = {}) {
+      const keys = [...objects.keys()].filter((key) => key.startsWith(prefix) && key > startAfter).sort();
+      return { objects: keys.slice(0, limit).map((key) => ({ key })), truncated: keys.length > limit };
+    },
+    async delete(keys) {
+      for (const key of [].concat(keys)) objects.delete(key);
+    },
+  };
+}
+
+export async function json(response) {
+  return { status: response.status, body: await response.json() };
+}
@@ -0,0 +1,84 @@
+"""
+Tests for the screen-reader output stream (assistant/accessibility.py).
+
+Covers:
+- Plain lines without ANSI codes, emoji or box drawing
+- Activity levels as words
+- Streamed chat replies written once, when complete
+- Mic state lines only on change
+- Writing to a file alongside the TUI, and nothing when the stream is off
+"""
+
+import io
+from datetime import datetime
+
+import pytest
+
+from assistant import accessibility
+from assistant.accessibility import AccessibleStream, mirror_activity, mirror_chat, plain
+
+NOW = datetime(2026, 10, 14, 14, 2)
+
+
+@pytest.fixture
+def stream():
+    return AccessibleStream(io.StringIO(), clock=lambda: NOW)
+
+
+def lines(stream):
+    return stream.out.getvalue().splitlines()
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("✓ Synced 3 events", "Synced 3 events"),
+    ("\x1b[1;31mfailed\x1b[0m", "failed"),
+    ("🎙️ Mic on", "Mic on"),
+    ("📊 Usage ████░░  4", "Usage 4"),
+    ("Call Bob\n  back", "Call Bob back"),
+    ("Lunch → 13:00, 21°C", "Lunch → 13:00, 21°C"),
+])
+def test_plain(text, expected):
+    assert plain(text) == expected
+
+
+def test_activity(stream):
+    stream.activity("⚠ Inbox sync failed", "warning")
+    stream.activity("✓ Event added", "success")
+    stream.activity("📥 2 new messages")
+    assert lines(stream) == ["14:02 Warning: Inbox sync failed", "14:02 Done: Event added", "14:02 Info: 2 new messages"]
+
+
+def test_chat_streaming(stream):
+    stream.chat("User", "what's next?")
+    stream.chat("Jarvis", "...")
+    stream.chat("Jarvis", "▌")
+    stream.chat("Jarvis", "Your dentist ▌")
+    stream.chat("thinking", "Checking the calendar")
+    stream.chat("Jarvis", "Your dentist is at 4.")
+    stream.chat("Jarvis", "Your dentist is at 4.")  # Same final text set again
+    assert lines(stream) == ["14:02 You: what's next?", "14:02 Jarvis: Your dentist is at 4."]
+
+
+def test_mic(stream):
+    stream.mic("listening")
+    stream.mic("listening")
+    stream.mic("mic off")
+    assert lines(stream) == ["14:02 Mic: listening", "14:02 Mic: mic off"]
+
+
+def test_file_alongside_tui(tmp_path):
+    stream = AccessibleStream.open(tmp_path / "a11y" / "events.txt")
+    assert not stream.replaces_tui
+    stream.activity("✗ Voice server crashed", "error")
+    stream.close()
+    assert (tmp_path / "a11y" / "events.txt").read_text().endswith("Error: Voice server crashed\n")
+    assert AccessibleStream.open("-").replaces_tui
+
+
+def test_mirror(stream, monkeypatch):
+    monkeypatch.setattr(accessibility, "_stream", None)
+    mirror_activity("nothing to write to")
+    monkeypatch.setattr(accessibility, "_stream", stream)
+    mirror_activity("⚠ Low battery", "warning")
+    mirror_chat("Jarvis", "Done.")
+    assert lines(stream) == ["14:02 Warning: Low battery", "14:02 Jarvis: Done."]
@@ -0,0 +1,119 @@
+"""
+Tests for the AI request log (assistant/ai_log.py).
+
+Covers:
+- What is kept at each level: lengths only, the redacted start, or everything redacted
+- Personal details sent are counted whatever the level; already-masked ones aren't
+- Token usage from Anthropic, OpenAI (SDK objects) and Ollama responses
+- log_ai_call does nothing until a log is set up; errors and latency are kept
+- Filtering, one call in full, the per-provider summary, and retention
+"""
+
+import types
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant import ai_log
+from assistant.ai_log import AILog, format_call, log_ai_call, summarize, usage_of
+from assistant.redaction import Redactor, set_redactor
+from assistant.retention import RetentionEngine, RetentionPolicy
+
+REQUEST = {
+    "system": "You are Jarvis. The user's email is dana@example.com.",
+    "messages": [
+        {"role": "user", "content": "Text +1 555 123 4567 that I'm late, and mail [email] too"},
+        {"role": "assistant", "content": [{"type": "tool_use", "name": "send_sms", "input": {"to": "mom"}}]},
+    ],
+}
+
+
+@pytest.fixture(autouse=True)
+def redactor():
+    set_redactor(Redactor())
+    yield
+    set_redactor(Redactor())
+
+
+@pytest.mark.parametrize("level, system, user", [
+    ("metadata", 53, 56),
+    ("truncated", "You are Jarvis. The … (+24 chars)", "Text [phone] that I'… (+28 chars)"),
+    ("full", "You are Jarvis. The user's email is [email].", "Text [phone] that I'm late, and mail [email] too"),
+])
+def test_levels(level, system, user):
+    log = AILog(":memory:", level, chars=20)
+    call = log.record("anthropic", "claude-3-5-sonnet", "chat", REQUEST, "Done, texted them.")
+    assert call.data["sensitive"] == {"email": 1, "phone": 1}
+    assert call.data["system"] == system
+    assert call.data["messages"][0] == {"role": "user", "content": user}
+    assert log.get(call.id).data == call.data
+
+
+def test_openai_style_system_and_tools():
+    log = AILog(":memory:", "full")
+    call = log.record("openai", "gpt-4o", "news", {"messages": [
+        {"role": "system", "content": "Summarize"}, {"role": "user", "content": "Headlines"}]})
+    assert call.data["system"] == "Summarize"
+    assert [m["role"] for m in call.data["messages"]] == ["user"]
+    tool = log.record("anthropic", "claude", "chat", REQUEST).data["messages"][1]["content"]
+    assert tool == '[tool call send_sms: {"to": "mom"}]'
+
+
+def test_usage():
+    assert usage_of({"usage": {"input_tokens": 120, "output_tokens": 8}}) == {"input_tokens": 120, "output_tokens": 8}
+    sdk = types.SimpleNamespace(usage=types.SimpleNamespace(prompt_tokens=50, completion_tokens=5))
+    assert usage_of(sdk) == {"input_tokens": 50, "output_tokens": 5}
+    assert usage_of({"prompt_eval_count": 30, "eval_count": 3}) == {"input_tokens": 30, "output_tokens": 3}
+    assert usage_of({}) == {"input_tokens": None, "output_tokens": None}
+
+
+def test_log_ai_call(monkeypatch):
+    log_ai_call("anthropic", "claude", "chat", REQUEST, "hi")  # No log set up: nothing happens
+    log = AILog(":memory:", "metadata")
+    monkeypatch.setattr(ai_log, "_log", log)
+    started = ai_log.time.monotonic() - 1.5
+    log_ai_call("anthropic", "claude", "chat", REQUEST, "hi", {"usage": {"input_tokens": 10, "output_tokens": 2}},
+                started)
+    log_ai_call("openai", "gpt-4o", "voice", REQUEST, started=started, error="timeout")
+    first, second = log.query()
+    assert (first.input_tokens, first.output_tokens, first.error) == (10, 2, "")
+    assert first.latency >= 1.5
+    assert (second.input_tokens, second.error) == (None, "timeout")
+    assert "✗ timeout" in second.line() and "⚠ email×1, phone×1" in second.line()
+
+    monkeypatch.setattr(log, "level", "off")
+    log_ai_call("anthropic", "claude", "chat", REQUEST)
+    assert len(log.query()) == 2
+
+
+def test_query_show_and_summary():
+    log = AILog(":memory:", "truncated", chars=100)
+    now = datetime.now()
+    log.record("anthropic", "claude", "chat", REQUEST, "ok", {"input_tokens": 100, "output_tokens": 10},
+               at=now - timedelta(days=3))
+    log.record("anthropic", "claude", "memory", {"messages": [{"role": "user", "content": "facts?"}]}, "none",
+               {"input_tokens": 20, "output_tokens": 2})
+    call = log.record("ollama", "llama3", "chat", {"messages": [{"role": "user", "content": "hi"}]}, "hello")
+    assert [c.purpose for c in log.query(provider="anthropic")] == ["chat", "memory"]
+    assert [c.provider for c in log.query(since=now - timedelta(days=1), purpose="chat")] == ["ollama"]
+    assert [c.id for c in log.query(limit=1)] == [call.id]
+    assert log.get(999) is None
+    assert summarize(log.query()) == [
+        "anthropic: 2 calls, 120 tokens in, 12 out; personal details sent: email ×1, phone ×1",
+        "ollama: 1 calls, 0 tokens in, 0 out; personal details sent: none",
+    ]
+    assert format_call(call).splitlines()[1:] == ["--- user", "hi", "--- reply", "hello"]
+    quiet = AILog(":memory:", "metadata").record("ollama", "llama3", "chat", {"messages": []}, "hello")
+    assert format_call(quiet).splitlines()[-1] == "(5 chars, not kept)"
+
+
+def test_retention(tmp_path):
+    log = AILog(tmp_path / "ai_log.db")
+    log.record("anthropic", "claude", "chat", REQUEST, at=datetime.now() - timedelta(days=40))
+    log.record("anthropic", "claude", "chat", REQUEST)
+    log.close()
+    report = RetentionEngine(RetentionPolicy(), root=tmp_path, tmp_logs=(tmp_path, "*.log")).run(apply=True)
+    assert report.total("ai_log") == 1
+    assert len(AILog(tmp_path / "ai_log.db").query()) == 1
+    with pytest.raises(ValueError):
+        AILog(":memory:", "everything")
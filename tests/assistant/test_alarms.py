@@ -0,0 +1,88 @@
+"""
+Tests for wake-up alarms (assistant/alarms.py).
+
+Covers:
+- Times and days: "7am", weekdays/weekends/daily/mon,wed; recurring alarms skip other days
+- Ringing: briefing gathered just before, rings once, volume ramps, times out; one-offs turn off
+- Snooze and dismiss persist, so a restart mid-ring or mid-snooze carries on; missed alarms move on
+- Voice commands: "five more minutes", "snooze", "I'm up", "stop"
+"""
+
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.alarms import (
+    BRIEFING_LEAD, AlarmClock, AlarmError, AlarmStore, describe_days, parse_alarm_command, parse_days,
+)
+
+FRIDAY = datetime(2026, 10, 16, 22, 0)
+
+
+def test_times_and_days(tmp_path):
+    assert parse_days("weekdays") == [0, 1, 2, 3, 4] and parse_days("Mon, wed and fridays") == [0, 2, 4]
+    assert parse_days("") == [] and describe_days([5, 6]) == "weekends" and describe_days([0, 2]) == "mon,wed"
+    with pytest.raises(AlarmError):
+        parse_days("someday")
+    store = AlarmStore(tmp_path)
+    with pytest.raises(AlarmError):
+        store.add("breakfast")
+    once = store.add("7am", now=FRIDAY)
+    assert (once.time, once.ring_time()) == ("07:00", datetime(2026, 10, 17, 7, 0))
+    weekdays = store.add("06:30", "weekdays", now=FRIDAY)
+    assert weekdays.ring_time() == datetime(2026, 10, 19, 6, 30)  # Monday
+    assert [a.id for a in AlarmStore(tmp_path).all()] == [once.id, weekdays.id]
+
+
+def test_ring_ramp_and_timeout(tmp_path):
+    store = AlarmStore(tmp_path)
+    alarm = store.add("07:00", now=FRIDAY)
+    clock = AlarmClock(store, start_volume=0.2, ramp_seconds=60, ring_minutes=10)
+    ring_at = alarm.ring_time()
+    assert clock.tick(ring_at - BRIEFING_LEAD - timedelta(seconds=1)) == []
+    assert [e for e, _ in clock.tick(ring_at - BRIEFING_LEAD)] == ["prepare"]
+    assert clock.tick(ring_at - timedelta(seconds=30)) == []
+    assert [e for e, _ in clock.tick(ring_at)] == ["ring"]
+    assert clock.tick(ring_at + timedelta(seconds=2)) == []  # Still ringing, not started again
+    assert clock.volume(alarm, ring_at) == pytest.approx(0.2)
+    assert clock.volume(alarm, ring_at + timedelta(seconds=30)) == pytest.approx(0.6)
+    assert clock.volume(alarm, ring_at + timedelta(minutes=5)) == 1.0
+    assert [e for e, _ in clock.tick(ring_at + timedelta(minutes=10))] == ["timeout"]
+    assert not store.all()[0].enabled and clock.ringing(ring_at + timedelta(minutes=10)) == []
+
+
+def test_snooze_dismiss_and_restarts(tmp_path):
+    store = AlarmStore(tmp_path)
+    alarm = store.add("06:30", "weekdays", now=FRIDAY)
+    monday = alarm.ring_time()
+    clock = AlarmClock(store, snooze_minutes=9)
+    clock.tick(monday + timedelta(seconds=1))
+    assert [a.id for a in clock.snooze(monday + timedelta(minutes=1), minutes=5)] == [alarm.id]
+    assert clock.ringing(monday + timedelta(minutes=2)) == []
+
+    restarted = AlarmClock(AlarmStore(tmp_path))  # Daemon restarted during the snooze
+    assert [e for e, _ in restarted.tick(monday + timedelta(minutes=6))] == ["ring"]
+    assert restarted.store.all()[0].snoozes == 1
+    again = AlarmClock(AlarmStore(tmp_path))  # ...and again mid-ring: it rings on
+    assert [e for e, _ in again.tick(monday + timedelta(minutes=7))] == ["ring"]
+    assert [a.id for a in again.dismiss(monday + timedelta(minutes=8))] == [alarm.id]
+    after = AlarmStore(tmp_path).all()[0]
+    assert after.enabled and after.snoozes == 0 and after.ring_time() == monday + timedelta(days=1)
+
+    asleep = AlarmClock(AlarmStore(tmp_path), ring_minutes=10)  # Not running through Tuesday's ring window
+    assert [e for e, _ in asleep.tick(monday + timedelta(days=1, hours=3))] == ["missed"]
+    assert asleep.store.all()[0].ring_time() == monday + timedelta(days=2)
+
+
+@pytest.mark.parametrize("text, command", [
+    ("Five more minutes", ("snooze", 5)),
+    ("snooze for 10 minutes please", ("snooze", 10)),
+    ("ugh, snooze", ("snooze", None)),
+    ("a few more minutes", ("snooze", 3)),
+    ("I'm up", ("dismiss", None)),
+    ("okay stop the alarm", ("dismiss", None)),
+    ("Turn it off", ("dismiss", None)),
+    ("what's the weather like", None),
+])
+def test_alarm_commands(text, command):
+    assert parse_alarm_command(text) == command
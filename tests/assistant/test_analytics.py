@@ -0,0 +1,92 @@
+"""
+Tests for usage analytics (assistant/analytics.py).
+
+Covers:
+- Counting conversations per day from turns close together
+- Average reply latency, reminders delivered vs missed, top commands
+- New messages per day
+- JSON and CSV export
+- Tool calls recorded in the event history only when one is set up
+"""
+
+import json
+from datetime import date, datetime, timedelta
+
+import pytest
+
+from assistant import events
+from assistant.analytics import build_report
+from assistant.events import EventStore
+from assistant.tools import ToolRegistry
+
+TODAY = date(2026, 10, 14)
+
+
+def _at(days_ago, hour, minute=0):
+    return datetime.combine(TODAY - timedelta(days=days_ago), datetime.min.time()).replace(hour=hour, minute=minute)
+
+
+@pytest.fixture
+def store(tmp_path):
+    store = EventStore(tmp_path / "events.db", keep_days=None)
+    # Today: two conversations (9:00-9:03, then 14:00), replies taking 1s and 2s
+    for minute in (0, 2, 3):
+        store.record("turn", "voice", {"source": "voice"}, at=_at(0, 9, minute))
+    store.record("reply", "1.0s", {"latency": 1.0}, at=_at(0, 9, 0))
+    store.record("turn", "chat", {"source": "chat"}, at=_at(0, 14))
+    store.record("reply", "2.0s", {"latency": 2.0}, at=_at(0, 14))
+    store.record("tool", "add_calendar_event", {"name": "add_calendar_event"}, at=_at(0, 9, 1))
+    store.record("tool", "add_calendar_event", {"name": "add_calendar_event"}, at=_at(0, 14))
+    store.record("tool", "set_volume", {"name": "set_volume"}, at=_at(0, 14))
+    store.record("reminder", "Standup in 10 minutes", {"delivered": True}, at=_at(0, 8, 50))
+    store.record("reminder", "Gym in 15 minutes", {"delivered": False}, at=_at(0, 22))
+    store.record("sms", "💬 Bob: late", {"sender": "Bob"}, at=_at(0, 8))
+    store.record("email", "📧 Dana: Invoice", {"sender": "Dana"}, at=_at(1, 12))
+    # Three days ago: one conversation; last month: outside the week
+    store.record("turn", "voice", {"source": "voice"}, at=_at(3, 18))
+    store.record("turn", "voice", {"source": "voice"}, at=_at(30, 18))
+    return store
+
+
+def test_week(store):
+    report = build_report(store, "week", today=TODAY)
+    assert [d.day for d in report.days] == [TODAY - timedelta(days=6 - i) for i in range(7)]
+    assert [d.conversations for d in report.days] == [0, 0, 0, 1, 0, 0, 2]
+    assert report.days[-1].turns == 4
+    assert [d.messages for d in report.days][-2:] == [1, 1]
+    assert report.avg_latency == pytest.approx(1.5)
+    assert report.reminders == (1, 1)
+    assert report.commands == [("add_calendar_event", 2), ("set_volume", 1)]
+    assert report.chart(width=4)[-1] == "Wed 14  ████  2"
+    assert report.summary() == "avg reply 1.5s · reminders 1 delivered, 1 missed"
+
+
+def test_day(store):
+    report = build_report(store, "day", today=TODAY)
+    assert len(report.days) == 1 and report.conversations == 2
+    with pytest.raises(ValueError):
+        build_report(store, "month", today=TODAY)
+
+
+def test_export(store):
+    report = build_report(store, "week", today=TODAY)
+    data = json.loads(report.to_json())
+    assert (data["start"], data["end"], data["conversations"]) == ("2026-10-08", "2026-10-14", 3)
+    assert data["commands"][0] == {"name": "add_calendar_event", "count": 2}
+    rows = report.to_csv().splitlines()
+    assert rows[0] == "date,conversations,turns,avg_latency_s,reminders_delivered,reminders_missed,messages"
+    assert rows[1] == "2026-10-08,0,0,,0,0,0"
+    assert rows[-1] == "2026-10-14,2,4,1.5,1,1,1"
+
+
+@pytest.mark.asyncio
+async def test_tool_calls_recorded(tmp_path, monkeypatch):
+    registry = ToolRegistry()
+    registry.register("ping", "Ping")(lambda: "✓ pong")
+    monkeypatch.setattr(events, "_store", None)
+    await registry.execute_tool("ping", {})  # No history set up: nothing written anywhere
+
+    store = EventStore(tmp_path / "events.db")
+    monkeypatch.setattr(events, "_store", store)
+    await registry.execute_tool("ping", {})
+    assert [(e.type, e.data) for e in store.query()] == [("tool", {"name": "ping"})]
@@ -0,0 +1,83 @@
+"""
+Tests for scheduled announcements (assistant/announcements.py).
+
+Covers:
+- Schedules: HH:MM daily or cron; bad schedules, priorities and empty text are refused
+- Due once per scheduled time, persisted across restarts; times missed by long are skipped
+- Removing by id prefix
+- Placeholders: greeting, date, events, tasks and briefing; unknown ones left as written
+"""
+
+from dataclasses import dataclass
+from datetime import datetime
+
+import pytest
+
+from assistant.announcements import (
+    GRACE_MINUTES, AnnouncementError, AnnouncementStore, briefing_context, parse_schedule, render,
+)
+
+
+@dataclass
+class Item:
+    title: str
+    start_time: str = ""
+
+
+def store_with(tmp_path, schedule, created="2026-10-16T08:00:00", **kwargs):
+    store = AnnouncementStore(tmp_path)
+    announcement = store.add(schedule, "Good morning", **kwargs)
+    store._data["announcements"][0]["created_at"] = created
+    store._save()
+    return store, announcement
+
+
+def test_schedules_and_validation(tmp_path):
+    assert parse_schedule("9:05").next_after(datetime(2026, 10, 16, 8)) == datetime(2026, 10, 16, 9, 5)
+    assert parse_schedule("0 9 * * 1-5").next_after(datetime(2026, 10, 16, 10)) == datetime(2026, 10, 19, 9)
+    store = AnnouncementStore(tmp_path)
+    for schedule, text, priority in [("24:00", "hi", "normal"), ("tomorrow", "hi", "normal"),
+                                     ("09:00", "  ", "normal"), ("09:00", "hi", "urgent")]:
+        with pytest.raises(AnnouncementError):
+            store.add(schedule, text, priority)
+    assert store.all() == []
+
+
+def test_due_once_and_persisted(tmp_path):
+    store, announcement = store_with(tmp_path, "09:00", priority="high", tags=["work"])
+    assert store.due(datetime(2026, 10, 16, 8, 59)) == []
+    due = store.due(datetime(2026, 10, 16, 9, 0, 30))
+    assert [(a.id, a.priority, a.tags) for a in due] == [(announcement.id, "high", ["work"])]
+    assert store.due(datetime(2026, 10, 16, 9, 1)) == []
+    restarted = AnnouncementStore(tmp_path)
+    assert restarted.due(datetime(2026, 10, 16, 9, 2)) == []
+    assert len(restarted.due(datetime(2026, 10, 17, 9, GRACE_MINUTES))) == 1
+
+
+def test_missed_times_are_skipped(tmp_path):
+    store, _ = store_with(tmp_path, "@hourly")
+    assert store.due(datetime(2026, 10, 16, 13, GRACE_MINUTES + 1)) == []  # Asleep since 9: nothing said late
+    assert store.all()[0].last_fired == "2026-10-16T13:00:00"
+    assert len(store.due(datetime(2026, 10, 16, 14, 0))) == 1
+
+
+def test_remove(tmp_path):
+    store = AnnouncementStore(tmp_path)
+    first = store.add("09:00", "one")
+    store.add("10:00", "two")
+    with pytest.raises(AnnouncementError):
+        store.remove("nope")
+    assert store.remove(first.id[:6]).text == "one"
+    assert [a.text for a in AnnouncementStore(tmp_path).all()] == ["two"]
+
+
+def test_placeholders():
+    now = datetime(2026, 10, 16, 9, 0)
+    events = [Item("Dentist", "2026-10-16T16:00:00"), Item("Standup", "2026-10-16T09:30:00")]
+    context = briefing_context(now, events, [Item("Email Sam"), Item("Pay rent"), Item("Book flights"), Item("Extra")])
+    assert render("{greeting}! It's {weekday}, {date}. {briefing} {unknown}", context) == (
+        "Good morning! It's Friday, October 16. Today you have Standup at 09:30 and Dentist at 16:00. "
+        "Next up: Email Sam, Pay rent and Book flights. {unknown}")
+    quiet = briefing_context(datetime(2026, 10, 16, 19, 0))
+    assert render("{greeting}. {briefing}", quiet) == "Good evening. There's nothing on the calendar today."
+    assert render("Stray { brace", quiet) == "Stray { brace"
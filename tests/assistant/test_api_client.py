@@ -0,0 +1,216 @@
+"""
+Tests for the shared server API client.
+
+Covers:
+- Error classification (server down vs bad request vs auth)
+- Expired sign-in, plan limits, field validation and maintenance read from the error body,
+  each explained with what to do
+- Retries with backoff for idempotent requests only; a POST only when its connection failed
+- Per-endpoint circuit breaker opening and half-open recovery with a single trial request
+"""
+
+import asyncio
+
+import httpx
+import pytest
+
+from assistant import api_client
+from assistant.api_client import (
+    ApiClient,
+    ApiError,
+    ApiErrorKind,
+    ApiPolicy,
+    CircuitBreaker,
+    classify_status,
+    endpoint_key,
+    explain,
+)
+
+
+class FakeResponse:
+    def __init__(self, status_code, body=None, headers=None):
+        self.status_code = status_code
+        self._body = body if body is not None else {}
+        self.headers = headers or {}
+        self.text = ""
+
+    def json(self):
+        return self._body
+
+
+class FakeTransport:
+    """Stands in for httpx.AsyncClient; each entry is a response or an exception to raise."""
+
+    def __init__(self, *results):
+        self.results = list(results)
+        self.calls = []
+
+    async def request(self, method, path, **kwargs):
+        self.calls.append((method, path))
+        result = self.results.pop(0) if len(self.results) > 1 else self.results[0]
+        if isinstance(result, Exception):
+            raise result
+        return result
+
+
+def _client(*results, **policy):
+    sleeps = []
+
+    async def sleep(delay):
+        sleeps.append(delay)
+
+    api_client._breakers.clear()
+    client = ApiClient("http://test", "token", policy=ApiPolicy(**policy), client=FakeTransport(*results), sleep=sleep)
+    return client, sleeps
+
+
+class TestClassification:
+    @pytest.mark.parametrize("status,kind", [
+        (200, None),
+        (400, ApiErrorKind.BAD_REQUEST),
+        (401, ApiErrorKind.AUTH),
+        (402, ApiErrorKind.QUOTA_EXCEEDED),
+        (404, ApiErrorKind.NOT_FOUND),
+        (422, ApiErrorKind.VALIDATION),
+        (429, ApiErrorKind.RATE_LIMITED),
+        (503, ApiErrorKind.SERVER_ERROR),
+    ])
+    def test_status_codes(self, status, kind):
+        assert classify_status(status) == kind
+
+    def test_endpoint_key_collapses_ids(self):
+        assert endpoint_key("post", "/api/inbox/a1b2/reply") == "POST /api/inbox/:id/reply"
+        assert endpoint_key("GET", "/api/inbox?since=1") == "GET /api/inbox"
+
+
+class TestErrorBodies:
+    def _error(self, response, method="put"):
+        client, _ = _client(response, max_attempts=1)
+        with pytest.raises(ApiError) as excinfo:
+            asyncio.run(getattr(client, method)("/api/calendar/appointments/1", json={}))
+        return excinfo.value
+
+    def test_expired_sign_in(self):
+        error = self._error(FakeResponse(401, {"error": "Token has expired"}))
+        assert error.kind == ApiErrorKind.AUTH_EXPIRED and not error.permanent
+        assert explain(error) == "Your server sign-in expired - set a new api_token in config.yaml (or XSWARM_API_TOKEN)"
+        assert self._error(FakeResponse(401, {"error": "Authentication required"})).kind == ApiErrorKind.AUTH
+
+    def test_plan_limit(self):
+        error = self._error(FakeResponse(402, {"error": "Upgrade to Personal tier for unlimited personas",
+                                               "code": "TIER_LIMIT_EXCEEDED"}))
+        assert (error.kind, error.code) == (ApiErrorKind.QUOTA_EXCEEDED, "TIER_LIMIT_EXCEEDED")
+        assert explain(error) == ("Upgrade to Personal tier for unlimited personas - upgrade your plan, "
+                                  "or wait for the limit to reset")
+        # A code for the error and the words in message
+        error = self._error(FakeResponse(402, {"error": "budget_paused", "message": "Daily budget reached"}))
+        assert error.detail == "Daily budget reached"
+        assert self._error(FakeResponse(429, {"error": "Monthly SMS quota exceeded"})).kind == ApiErrorKind.QUOTA_EXCEEDED
+        assert self._error(FakeResponse(429, {"error": "Too many requests"})).kind == ApiErrorKind.RATE_LIMITED
+
+    def test_field_validation(self):
+        error = self._error(FakeResponse(400, {"error": "Missing required fields: user_id, title"}), "post")
+        assert error.kind == ApiErrorKind.VALIDATION and error.permanent
+        assert error.fields == {"user_id": "is required", "title": "is required"}
+        assert explain(error) == "The server didn't accept that: user_id is required; title is required"
+        error = self._error(FakeResponse(422, {"error": "Invalid appointment",
+                                               "details": [{"field": "end_time", "message": "is before start_time"}]}))
+        assert explain(error) == "The server didn't accept that: end_time is before start_time"
+        assert self._error(FakeResponse(400, {"error": "Bad JSON"})).kind == ApiErrorKind.BAD_REQUEST
+
+    def test_maintenance(self):
+        error = self._error(FakeResponse(503, {"error": "Down for maintenance", "code": "MAINTENANCE"},
+                                         headers={"Retry-After": "600"}))
+        assert error.kind == ApiErrorKind.MAINTENANCE
+        assert not error.transient and not error.permanent  # Not retried now, but queued changes are kept
+        assert explain(error) == "The server is down for maintenance - back in about 10 min; changes are kept until then"
+
+
+class TestRetries:
+    def test_get_retries_server_errors_with_backoff(self):
+        client, sleeps = _client(FakeResponse(503), FakeResponse(502), FakeResponse(200, {"ok": True}))
+        response = asyncio.run(client.get("/api/inbox"))
+        assert response.json() == {"ok": True}
+        assert len(client.client.calls) == 3
+        assert sleeps[0] < sleeps[1]
+
+    def test_bad_request_is_not_retried(self):
+        client, _ = _client(FakeResponse(400, {"error": "text is required"}))
+        with pytest.raises(ApiError) as excinfo:
+            asyncio.run(client.get("/api/inbox"))
+        assert len(client.client.calls) == 1
+        assert excinfo.value.kind == ApiErrorKind.BAD_REQUEST
+        assert not excinfo.value.transient
+        assert excinfo.value.detail == "text is required"
+
+    def test_post_not_retried_once_it_reached_the_server(self):
+        client, _ = _client(FakeResponse(500))
+        with pytest.raises(ApiError):
+            asyncio.run(client.post("/api/inbox/1/reply", json={}))
+        assert len(client.client.calls) == 1
+
+    def test_post_retried_when_connection_failed(self):
+        client, _ = _client(httpx.ConnectError("refused"), FakeResponse(200))
+        asyncio.run(client.post("/api/inbox/1/reply", json={}))
+        assert len(client.client.calls) == 2
+
+    def test_post_retried_when_connect_timed_out(self):
+        client, _ = _client(httpx.ConnectTimeout("connect timed out"), FakeResponse(200))
+        asyncio.run(client.post("/api/inbox/1/reply", json={}))
+        assert len(client.client.calls) == 2
+
+    @pytest.mark.parametrize("error", [httpx.ReadError("reset"), httpx.RemoteProtocolError("closed mid-response"),
+                                       httpx.WriteError("broken pipe")])
+    def test_post_not_retried_when_it_may_have_been_sent(self, error):
+        client, _ = _client(error, FakeResponse(200))
+        with pytest.raises(ApiError):
+            asyncio.run(client.post("/api/inbox/1/reply", json={}))
+        assert len(client.client.calls) == 1
+
+    def test_ok_statuses_pass_through(self):
+        client, _ = _client(FakeResponse(404))
+        assert asyncio.run(client.put("/api/inbox/1", json={}, ok_statuses=(404,))).status_code == 404
+
+
+class TestCircuitBreaker:
+    def test_opens_after_threshold_then_half_opens(self):
+        now = [0.0]
+        breaker = CircuitBreaker(threshold=2, reset_after=30, clock=lambda: now[0])
+        breaker.record_failure()
+        assert breaker.allow()
+        breaker.record_failure()
+        assert not breaker.allow()
+
+        now[0] = 31
+        assert breaker.state == CircuitBreaker.HALF_OPEN
+        breaker.record_failure()  # Trial failed: open again
+        assert not breaker.allow()
+
+        now[0] = 62
+        breaker.record_success()
+        assert breaker.state == CircuitBreaker.CLOSED
+
+    def test_half_open_lets_one_trial_through(self):
+        now = [0.0]
+        breaker = CircuitBreaker(threshold=1, reset_after=30, clock=lambda: now[0])
+        breaker.record_failure()
+        now[0] = 31
+        assert breaker.allow()
+        assert not breaker.allow()  # The trial is still out
+        now[0] = 62
+        assert breaker.allow()  # It never reported back: another may go
+        breaker.record_success()
+        assert breaker.allow() and breaker.allow()
+
+    def test_open_circuit_fails_fast_as_server_down(self):
+        client, _ = _client(httpx.ConnectError("refused"), max_attempts=1, circuit_threshold=2)
+        for _ in range(2):
+            with pytest.raises(ApiError):
+                asyncio.run(client.get("/api/calls/screening"))
+        assert len(client.client.calls) == 2
+
+        with pytest.raises(ApiError) as excinfo:
+            asyncio.run(client.get("/api/calls/screening"))
+        assert excinfo.value.kind == ApiErrorKind.SERVER_DOWN
+        assert len(client.client.calls) == 2  # Never hit the transport
+        assert api_client.get_api_health().last_error.kind == ApiErrorKind.SERVER_DOWN
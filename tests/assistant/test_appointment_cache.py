@@ -0,0 +1,156 @@
+"""
+Tests for the appointment cache (assistant/appointment_cache.py).
+
+Covers:
+- A day's planner events (recurring occurrences included) and server appointments, read from memory
+- Fetched again only when the planner, the calendar sync or the mirror changes, or the day moves on
+- Days outside the ±WINDOW_DAYS window are read from the sources
+- between/is_free/conflicts over the window's interval index
+- "am I free Thursday afternoon?" and the other ways of asking
+"""
+
+from datetime import date, datetime
+
+import pytest
+
+from assistant import dates
+from assistant.appointment_cache import AppointmentCache, answer_availability_question, parse_availability_question
+from assistant.calendar_mirror import CalendarMirror
+from assistant.dates import DateSettings
+from assistant.planner import PlannerData
+from assistant.scheduler_client import CalendarSync, SchedulerClient
+
+TODAY = date(2026, 10, 15)  # A Thursday
+NOW = datetime(2026, 10, 15, 10, 30)
+
+
+@pytest.fixture(autouse=True)
+def home_utc(monkeypatch):
+    monkeypatch.setattr(dates, "_settings", DateSettings(timezone="UTC", clock="12h"))
+
+
+def _appointment(id, title, start, end):
+    return {"entity": "appointment", "id": id, "op": "upsert", "data": {
+        "id": id, "title": title, "start_time": start, "end_time": end, "status": "scheduled", "tags": ["work"]}}
+
+
+class Clock:
+    def __init__(self, today=TODAY):
+        self.today = today
+
+    def __call__(self):
+        return self.today
+
+
+def _cache(tmp_path, mirror=None, clock=None):
+    planner = PlannerData(tmp_path / "planner")
+    sync = CalendarSync(planner, SchedulerClient(), storage_dir=tmp_path / "sync", mirror=mirror) if mirror else None
+    return AppointmentCache(planner, sync, today=clock or Clock()), planner
+
+
+def test_a_day_from_memory(tmp_path):
+    cache, planner = _cache(tmp_path)
+    planner.add_calendar_event("Standup", "2026-10-12T09:00", "2026-10-12T09:15", recurrence="daily")
+    planner.add_calendar_event("Dentist", "2026-10-15T14:00", "2026-10-15T15:00", tags=["health"])
+    assert [(e.title, e.start_time) for e in cache.events()] == [
+        ("Standup", "2026-10-15T09:00:00"), ("Dentist", "2026-10-15T14:00")]
+    assert [e.title for e in cache.events(tag="health")] == ["Dentist"]
+    assert [e.title for e in cache.events(date(2026, 11, 1))] == ["Standup"]
+    assert (cache.fetches, cache.hits, cache.misses) == (1, 3, 0)
+
+
+def test_planner_changes_are_seen(tmp_path):
+    cache, planner = _cache(tmp_path)
+    assert cache.events() == []
+    planner.add_calendar_event("Dentist", "2026-10-15T14:00", "2026-10-15T15:00")
+    assert [e.title for e in cache.events()] == ["Dentist"]
+    planner.reload()  # Nothing changed on disk: no fetch
+    cache.events()
+    assert cache.fetches == 2
+
+    elsewhere = PlannerData(tmp_path / "planner")  # Another process writing the file
+    elsewhere.add_calendar_event("Call Sam", "2026-10-15T16:00", "2026-10-15T16:30")
+    assert [e.title for e in cache.events()] == ["Dentist"]  # Until the planner re-reads it
+    planner.reload()
+    assert [e.title for e in cache.events()] == ["Dentist", "Call Sam"]
+
+
+def test_server_appointments(tmp_path):
+    mirror = CalendarMirror(path=":memory:")
+    cache, planner = _cache(tmp_path, mirror)
+    planner.add_calendar_event("Dentist", "2026-10-15T14:00", "2026-10-15T15:00")
+    mirror.apply([_appointment("apt-1", "Review", "2026-10-15T11:00:00Z", "2026-10-15T12:00:00Z"),
+                  _appointment("apt-2", "Offsite", "2026-11-10T09:00:00Z", "2026-11-10T17:00:00Z")], 2)
+    assert [e.title for e in cache.events()] == ["Review", "Dentist"]
+    assert [e.title for e in cache.events(date(2026, 11, 10))] == ["Offsite"]
+    mirror.apply([{"entity": "appointment", "id": "apt-1", "op": "delete"}], 3)
+    assert [e.title for e in cache.events()] == ["Dentist"] and cache.fetches == 2
+    mirror.apply([], 4)  # A pull with nothing new
+    cache.events()
+    assert cache.fetches == 2
+
+
+def test_an_unreadable_mirror_is_tried_again(tmp_path):
+    mirror = CalendarMirror(path=":memory:")
+    cache, planner = _cache(tmp_path, mirror)
+    mirror.close()
+    planner.add_calendar_event("Dentist", "2026-10-15T14:00", "2026-10-15T15:00")
+    assert [e.title for e in cache.events()] == ["Dentist"]
+    cache.events()
+    assert cache.fetches == 2
+
+
+def test_the_window_moves_with_the_day(tmp_path):
+    clock = Clock()
+    cache, planner = _cache(tmp_path, clock=clock)
+    planner.add_calendar_event("Far off", "2026-11-20T10:00", "2026-11-20T11:00")
+    assert [e.title for e in cache.events(date(2026, 11, 20))] == ["Far off"]
+    assert cache.misses == 1 and cache.last == date(2026, 11, 14)
+    clock.today = date(2026, 10, 25)
+    assert [e.title for e in cache.events(date(2026, 11, 20))] == ["Far off"]
+    assert cache.hits == 1 and cache.fetches == 2
+
+
+def test_between_and_conflicts(tmp_path):
+    cache, planner = _cache(tmp_path)
+    planner.add_calendar_event("Standup", "2026-10-12T09:00", "2026-10-12T09:15", recurrence="daily")
+    planner.add_calendar_event("Lunch", "2026-10-15T12:00", "2026-10-15T13:00", busy=False)
+    late = planner.add_calendar_event("Late call", "2026-10-15T23:30", "2026-10-16T00:30")
+    assert [e.title for e in cache.between(datetime(2026, 10, 16), datetime(2026, 10, 16, 12))] == [
+        "Late call", "Standup"]
+    assert cache.is_free(datetime(2026, 10, 15, 12), datetime(2026, 10, 15, 13))  # Lunch is "show as free"
+    assert [e.title for e in cache.conflicts(late)] == []
+    clash = planner.add_calendar_event("Review", "2026-10-20T09:00", "2026-10-20T10:00")
+    assert [e.title for e in cache.conflicts(clash)] == ["Standup"]
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("am I free Saturday afternoon?", ("2026-10-17T12:00", "2026-10-17T17:00", "Saturday afternoon", False)),
+    ("are we busy tomorrow at 3", ("2026-10-16T15:00", "2026-10-16T16:00", "tomorrow at three o'clock", True)),
+    ("am I free this afternoon", ("2026-10-15T12:00", "2026-10-15T17:00", "this afternoon", False)),
+    ("Am I free tonight?", ("2026-10-15T17:00", "2026-10-16T00:00", "tonight", False)),
+    ("am I free?", ("2026-10-15T10:30", "2026-10-15T18:00", "today", False)),  # From now
+    ("is my calendar clear on monday morning", ("2026-10-19T05:00", "2026-10-19T12:00", "Monday morning", False)),
+    ("am I free to talk", None),
+    ("what am I doing thursday", None),
+])
+def test_parse_availability_question(text, expected):
+    question = parse_availability_question(text, NOW)
+    if expected is None:
+        assert question is None
+    else:
+        start, end, said, busy = question
+        assert (start.isoformat(timespec="minutes"), end.isoformat(timespec="minutes"), said, busy) == expected
+
+
+def test_answer_availability_question(tmp_path):
+    cache, planner = _cache(tmp_path)
+    planner.add_calendar_event("Dentist", "2026-10-16T14:00", "2026-10-16T15:00")
+    planner.add_calendar_event("Call Sam", "2026-10-16T16:30", "2026-10-16T17:00")
+    assert answer_availability_question("am I free tomorrow afternoon?", cache, NOW) == (
+        "No - tomorrow afternoon you have Dentist at two o'clock and Call Sam at half past four.")
+    assert answer_availability_question("am I busy tomorrow morning", cache, NOW) == (
+        "No, you're free tomorrow morning.")
+    assert answer_availability_question("am I free this morning", cache, datetime(2026, 10, 15, 13)) == (
+        "This morning is already over.")
+    assert answer_availability_question("set a timer", cache, NOW) is None
@@ -0,0 +1,147 @@
+"""
+Tests for asking about missing scheduling details (assistant/appointments.py).
+
+Covers:
+- Splitting a scheduling request into title, day and time
+- Only asking when something is missing, and not mistaking other requests
+- The clarification flow: asking for a time, offering the default, asking
+  for a title, cancelling
+- The add_calendar_event tool asking instead of assuming 9am
+"""
+
+from datetime import date, datetime
+
+import pytest
+
+from assistant import dates, tools
+from assistant.appointments import appointment_flow, needs_details, parse_schedule_request, resolve_day
+from assistant.dates import DateSettings
+from assistant.flows import FlowRun, flow_for_request
+from assistant.planner import PlannerData
+
+NOW = datetime(2026, 10, 16, 10, 0)  # A Friday morning
+TODAY = NOW.date()
+
+
+@pytest.mark.parametrize("text, title, day, time", [
+    ("schedule the dentist", "Dentist", None, None),
+    ("Schedule the dentist on Friday.", "Dentist", date(2026, 10, 23), None),
+    ("book the dentist friday at four", "Dentist", date(2026, 10, 23), "16:00"),
+    ("schedule lunch with Sam at noon tomorrow", "Lunch with Sam", date(2026, 10, 17), "12:00"),
+    ("schedule lunch tomorrow", "Lunch", date(2026, 10, 17), None),
+    ("book something tomorrow at 3", "", date(2026, 10, 17), "15:00"),
+    ("can you schedule a meeting at 3pm please", "", None, "15:00"),
+    ("schedule", "", None, None),
+])
+def test_parse_schedule_request(text, title, day, time):
+    request = parse_schedule_request(text, today=TODAY)
+    assert (request.title, request.day, request.time) == (title, day, time)
+
+
+@pytest.mark.parametrize("text", [
+    "book a flight", "set up my email", "schedule my tasks", "book lunch", "what's on my schedule",
+    "schedule dentist 4/5",  # Could be April 5 or May 4: the AI asks which
+])
+def test_not_a_clarification(text):
+    assert parse_schedule_request(text, today=TODAY) is None
+
+
+def test_only_when_something_is_missing():
+    assert needs_details("book the dentist friday at four", today=TODAY) is None
+    assert needs_details("schedule the dentist", today=TODAY).missing == ["time"]
+    assert needs_details("book something tomorrow at 3", today=TODAY).missing == ["title"]
+
+
+def test_resolve_day():
+    assert resolve_day("15:00", NOW) == TODAY
+    assert resolve_day("09:00", NOW) == date(2026, 10, 17)
+
+
+class TestFlow:
+    def _run(self, text):
+        added = []
+
+        def add_event(title, day, start_time):
+            added.append((title, day, start_time))
+            return f"✓ Added: '{title}' on {day} at {start_time}"
+
+        run = FlowRun(appointment_flow(parse_schedule_request(text, today=TODAY), add_event=add_event, now=NOW))
+        return run, added
+
+    def test_asks_for_the_time(self):
+        run, added = self._run("schedule the dentist on wednesday")
+        assert run.start() == "What time should I schedule the dentist on Wednesday?"
+        assert run.handle("half past three") == "Added: 'Dentist' on 2026-10-21 at 15:30"
+        assert added == [("Dentist", "2026-10-21", "15:30")]
+
+    def test_default_only_when_accepted(self):
+        run, added = self._run("schedule the dentist")
+        run.start()
+        assert run.handle("you pick") == "Shall I put the dentist at nine o'clock tomorrow?"
+        assert run.handle("no") == "What time should I schedule the dentist?"
+        assert added == []
+        run.handle("whenever")
+        assert run.handle("yes") == "Added: 'Dentist' on 2026-10-17 at 09:00"
+
+    def test_time_without_day_is_the_next_one(self):
+        run, added = self._run("schedule the dentist")
+        run.start()
+        run.handle("2pm")
+        assert added == [("Dentist", "2026-10-16", "14:00")]
+
+    def test_asks_for_the_title(self):
+        run, added = self._run("book something tomorrow at 3")
+        assert run.start() == "What should I call it?"
+        run.handle("Haircut")
+        assert added == [("Haircut", "2026-10-17", "15:00")]
+
+    def test_asks_for_both(self):
+        run, added = self._run("schedule a meeting")
+        assert run.start() == "What should I call it?"
+        assert run.handle("Budget review") == "What time should I schedule Budget review?"
+        run.handle("11am")
+        assert added == [("Budget review", "2026-10-16", "11:00")]
+
+    def test_cancel(self):
+        run, added = self._run("schedule the dentist")
+        run.start()
+        assert run.handle("never mind") == "Okay, I won't schedule it."
+        assert added == []
+
+    def test_tool_failure_is_reported(self):
+        run = FlowRun(appointment_flow(parse_schedule_request("schedule the dentist", today=TODAY),
+                                       add_event=lambda *_: "✗ Calendar is read-only", now=NOW))
+        run.start()
+        assert run.handle("3pm") == "Sorry, that didn't work: Calendar is read-only"
+
+    def test_picked_up_by_flow_for_request(self):
+        assert flow_for_request("schedule the dentist").name == "schedule"
+        assert flow_for_request("book the dentist friday at four") is None
+
+
+class TestTool:
+    def _planner(self, tmp_path, monkeypatch):
+        planner = PlannerData(tmp_path / "planner")
+        monkeypatch.setattr(tools, "_planner_data", planner)
+        monkeypatch.setattr(dates, "_settings", DateSettings())
+        return planner
+
+    def test_no_time_asks(self, tmp_path, monkeypatch):
+        planner = self._planner(tmp_path, monkeypatch)
+        result = tools.add_calendar_event("Dentist", "2026-10-23")
+        assert result.startswith("⏸ Not done yet - no time was given for 'Dentist'.")
+        assert planner.get_calendar_events(expand_recurring=False) == []
+
+    def test_time_given(self, tmp_path, monkeypatch):
+        planner = self._planner(tmp_path, monkeypatch)
+        assert tools.add_calendar_event("Dentist", "2026-10-23", start_time="nine am").startswith("✓")
+        assert tools.add_calendar_event("Call", "2026-10-23T15:00").startswith("✓")  # Time in the date
+        assert [e.start_time[11:16] for e in planner.get_calendar_events(expand_recurring=False)] == ["09:00", "15:00"]
+
+    def test_flow_books_through_the_tool(self, tmp_path, monkeypatch):
+        planner = self._planner(tmp_path, monkeypatch)
+        run = FlowRun(appointment_flow(parse_schedule_request("schedule the dentist on 2026-10-23"), now=NOW))
+        run.start()
+        assert run.handle("4pm").startswith("Added: 'Dentist' on Friday Oct 23 at")
+        event = planner.get_calendar_events(expand_recurring=False)[0]
+        assert (event.start_time, event.end_time) == ("2026-10-23T16:00:00", "2026-10-23T17:00:00")
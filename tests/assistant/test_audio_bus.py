@@ -0,0 +1,79 @@
+"""
+Tests for the backpressure-aware audio queues.
+
+Covers:
+- Drop-oldest on overflow with counters; producers never block
+- Broadcast keeps one slow subscriber from affecting the others
+- Non-critical subscribers are downsampled while lagging and recover
+- Summary used by the dashboard footer
+"""
+
+from queue import Empty
+
+import pytest
+
+from assistant.audio_bus import MAX_STRIDE, AudioBroadcast, FrameQueue, summarize
+
+
+class TestFrameQueue:
+    def test_drop_oldest(self):
+        q = FrameQueue("playback", capacity=3)
+        for frame in range(5):
+            q.put(frame)  # Never blocks, even when full
+        assert [q.get_nowait() for _ in range(3)] == [2, 3, 4]
+        stats = q.stats()
+        assert stats["dropped"] == 2
+        assert stats["received"] == 5
+        assert stats["max_lag"] == 3
+
+    def test_get_times_out_when_empty(self):
+        q = FrameQueue("mic", capacity=2)
+        with pytest.raises(Empty):
+            q.get(timeout=0.01)
+
+    def test_drain_latest(self):
+        q = FrameQueue("viz", capacity=4)
+        for frame in range(3):
+            q.put_nowait(frame)
+        assert q.drain_latest() == 2
+        assert q.empty()
+        assert q.drain_latest() is None
+
+
+class TestBroadcast:
+    def test_slow_subscriber_does_not_affect_others(self):
+        bus = AudioBroadcast("moshi")
+        fast = bus.subscribe("output", capacity=10)
+        slow = bus.subscribe("recorder", capacity=2)
+        for frame in range(5):
+            bus.publish(frame)
+            fast.get_nowait()
+        assert fast.stats()["dropped"] == 0
+        assert slow.stats()["dropped"] == 3
+        assert bus.subscribe("output", capacity=10) is fast
+
+    def test_non_critical_is_downsampled_while_lagging(self):
+        bus = AudioBroadcast("moshi")
+        viz = bus.subscribe("visualizer", capacity=4, critical=False)
+        for frame in range(40):
+            bus.publish(frame)
+        stats = viz.stats()
+        assert stats["stride"] == MAX_STRIDE
+        assert stats["skipped"] > 0
+
+        # Consumer catches up: stride halves back toward 1
+        for frame in range(40):
+            viz.drain_latest()
+            bus.publish(frame)
+        assert viz.stats()["stride"] == 1
+
+    def test_summary(self):
+        playback = FrameQueue("playback", capacity=10)
+        viz = FrameQueue("viz", capacity=4, critical=False)
+        for frame in range(12):
+            playback.put_nowait(frame)
+            viz.put_nowait(frame)
+        health = summarize([playback.stats(), viz.stats()])
+        assert health["lag_percent"] == 100
+        assert health["dropped"] == 2
+        assert health["downsampled"] > 1
@@ -0,0 +1,90 @@
+"""
+Tests for Bluetooth headset profile detection (assistant/audio_devices.py).
+
+Covers:
+- pactl cards: bluez cards, their active and A2DP profiles; macOS connected devices
+- A Bluetooth capture device (by connected name or hint) means HFP, with a built-in mic to use instead
+- Linux cards in a headset profile are HFP whatever PortAudio calls the device
+- Warnings for the HFP and rerouted cases; switching a card back to A2DP
+"""
+
+import json
+import subprocess
+
+from assistant import audio_devices
+from assistant.audio_devices import (
+    BluetoothCheck, LinuxCard, check_bluetooth, linux_bluetooth_cards, macos_bluetooth_devices, restore_a2dp,
+)
+
+PACTL = """Card #42
+\tName: alsa_card.pci-0000_00_1f.3
+\tProperties:
+\t\tdevice.description = "Built-in Audio"
+\tActive Profile: output:analog-stereo+input:analog-stereo
+
+Card #57
+\tName: bluez_card.AC_80_0A_11_22_33
+\tDriver: module-bluez5-device.c
+\tProperties:
+\t\tdevice.description = "WH-1000XM4"
+\tProfiles:
+\t\ta2dp-sink: High Fidelity Playback (A2DP Sink) (sinks: 1, sources: 0, priority: 40, available: yes)
+\t\theadset-head-unit: Headset Head Unit (HSP/HFP) (sinks: 1, sources: 1, priority: 30, available: yes)
+\t\toff: Off (sinks: 0, sources: 0, priority: 0, available: yes)
+\tActive Profile: headset-head-unit
+"""
+
+DEVICES = [
+    {"name": "MacBook Pro Microphone", "max_input_channels": 1},
+    {"name": "MacBook Pro Speakers", "max_input_channels": 0},
+    {"name": "BlackHole 2ch", "max_input_channels": 2},
+    {"name": "Sam's AirPods Pro", "max_input_channels": 1},
+    {"name": "default", "max_input_channels": 32},
+]
+
+
+def test_parse_system_output():
+    assert linux_bluetooth_cards(PACTL) == {
+        "WH-1000XM4": LinuxCard("bluez_card.AC_80_0A_11_22_33", "headset-head-unit", "a2dp-sink")}
+    profiler = {"SPBluetoothDataType": [{"device_connected": [{"Desk Speaker": {}}, {"Pixel Buds": {}}],
+                                         "device_not_connected": [{"Old Headset": {}}]}]}
+    assert macos_bluetooth_devices(json.dumps(profiler)) == {"Desk Speaker", "Pixel Buds"}
+    assert macos_bluetooth_devices("not json") == set()
+
+
+def test_bluetooth_capture_device_is_hfp():
+    check = check_bluetooth(DEVICES, 3, known=(), linux_cards={})
+    assert check.hfp and check.headset == "Sam's AirPods Pro"
+    assert (check.builtin_index, check.builtin_name) == (0, "MacBook Pro Microphone")  # Not BlackHole or default
+    assert not check_bluetooth(DEVICES, 0, known=(), linux_cards={}).hfp
+    renamed = [{"name": "Desk Speaker", "max_input_channels": 1}, {"name": "USB Mic", "max_input_channels": 1}]
+    check = check_bluetooth(renamed, 0, known={"Desk Speaker"}, linux_cards={})
+    assert check.hfp and check.builtin_name == "USB Mic"
+
+
+def test_linux_card_profile():
+    devices = [{"name": "default", "max_input_channels": 32},
+               {"name": "HDA Intel PCH: ALC257 Analog (hw:0,0)", "max_input_channels": 2}]
+    check = check_bluetooth(devices, 0, known=(), linux_cards=linux_bluetooth_cards(PACTL))
+    assert check.hfp and check.headset == "WH-1000XM4" and check.builtin_index == 1
+    a2dp = {"WH-1000XM4": LinuxCard("bluez_card.AC", "a2dp-sink", "a2dp-sink")}
+    assert not check_bluetooth(devices, 0, known=(), linux_cards=a2dp).hfp
+
+
+def test_warnings_and_restore(monkeypatch):
+    assert BluetoothCheck().warning() is None
+    check = BluetoothCheck("AirPods", builtin_index=0, builtin_name="MacBook Pro Microphone")
+    assert "HFP" in check.warning() and "bluetooth_mic_fallback" in check.warning()
+    assert "use a wired or built-in mic" in BluetoothCheck("AirPods").warning()
+    assert check.warning(rerouted=True).startswith("🎧 Recording from MacBook Pro Microphone instead of AirPods")
+
+    calls = []
+    monkeypatch.setattr(audio_devices.subprocess, "run", lambda command, **kwargs: calls.append(command))
+    assert not restore_a2dp(check)  # macOS: nothing to switch
+    check.card = LinuxCard("bluez_card.AC", "headset-head-unit", "a2dp-sink")
+    assert restore_a2dp(check) and calls == [["pactl", "set-card-profile", "bluez_card.AC", "a2dp-sink"]]
+
+    def fail(command, **kwargs):
+        raise subprocess.CalledProcessError(1, command)
+    monkeypatch.setattr(audio_devices.subprocess, "run", fail)
+    assert not restore_a2dp(check)
@@ -0,0 +1,43 @@
+"""
+Tests for shared, read-only audio frames.
+
+Covers:
+- One copy out of a reused device buffer; no copies after that
+- Frames are read-only so consumers can't corrupt each other's view
+- PCM conversion is cached and matches the per-consumer conversion
+"""
+
+import numpy as np
+import pytest
+
+from assistant.audio_frame import AudioFrame, frame_samples, pcm16_bytes
+
+
+def test_from_buffer_copies_once():
+    buffer = np.full((960, 1), 0.25, dtype=np.float32)
+    frame = AudioFrame.from_buffer(buffer[:, 0])
+    buffer[:] = 0.0  # PortAudio reuses its buffer for the next frame
+    assert frame.samples[0] == pytest.approx(0.25)
+    assert frame_samples(frame) is frame.samples
+    assert np.asarray(frame.samples, dtype=np.float32) is frame.samples
+
+
+def test_frames_are_read_only():
+    frame = AudioFrame(np.zeros(960, dtype=np.float32))
+    with pytest.raises(ValueError):
+        frame.samples[0] = 1.0
+
+
+def test_pcm16_is_cached_and_matches():
+    samples = np.linspace(-1.0, 1.0, 960, dtype=np.float32)
+    frame = AudioFrame(samples)
+    assert frame.pcm16() is frame.pcm16()
+    assert pcm16_bytes(frame) == (samples * 32767).astype(np.int16).tobytes()
+    assert pcm16_bytes(samples) == frame.pcm16()
+    int16 = np.arange(4, dtype=np.int16)
+    assert pcm16_bytes(int16) == int16.tobytes()
+
+
+def test_rms():
+    assert AudioFrame(np.full(4, 0.5, dtype=np.float32)).rms() == pytest.approx(0.5)
+    assert AudioFrame(np.zeros(0, dtype=np.float32)).rms() == 0.0
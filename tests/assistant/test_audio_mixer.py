@@ -0,0 +1,80 @@
+"""
+Tests for the playback mixer (assistant/audio_mixer.py).
+
+Covers:
+- Speech scaled to its volume, earcons mixed over it without queueing
+- Media playing in order, and ducked while speech plays
+- Clipping the mixed block
+"""
+
+import numpy as np
+import pytest
+
+from assistant.audio_mixer import DUCK_RELEASE, OutputMixer
+from assistant.volume import VolumeSettings
+
+RATE = 24000
+BLOCK = 1920  # 80ms
+
+
+def _mixer(**levels):
+    settings = VolumeSettings(**levels)
+    return OutputMixer(RATE, settings=lambda: settings), settings
+
+
+def test_speech_volume():
+    mixer, _ = _mixer(master=0.5, speech=0.8)
+    block = np.full(100, 0.5, dtype=np.float32)
+    assert mixer.mix(block, speech=60) == 60
+    assert np.allclose(block[:60], 0.2) and np.allclose(block[60:], 0.5)
+
+
+def test_earcons_over_speech():
+    mixer, _ = _mixer(earcons=1.0)
+    mixer.add("earcons", np.full(150, 0.5, dtype=np.float32))
+    mixer.add("earcons", np.full(50, 0.1, dtype=np.float32))
+    speech = np.full(100, 0.25, dtype=np.float32)
+    assert mixer.mix(speech, speech=100) == 100
+    assert np.allclose(speech[:50], 0.85) and np.allclose(speech[50:], 0.75)
+    rest = np.zeros(100, dtype=np.float32)
+    assert mixer.mix(rest) == 50
+    assert np.allclose(rest[:50], 0.5) and np.allclose(rest[50:], 0.0)
+    assert not mixer.active()
+
+
+def test_media_plays_in_order():
+    mixer, _ = _mixer(media=1.0)
+    mixer.add("media", np.full(60, 0.1, dtype=np.float32))
+    mixer.add("media", np.full(60, 0.2, dtype=np.float32))
+    block = np.zeros(100, dtype=np.float32)
+    assert mixer.mix(block) == 100
+    assert np.allclose(block[:60], 0.1) and np.allclose(block[60:], 0.2)
+    assert mixer.active("media") and not mixer.active("earcons")
+
+
+def test_media_ducks_under_speech():
+    mixer, _ = _mixer(media=1.0, duck_level=0.25)
+    mixer.add("media", np.full(RATE * 2, 0.4, dtype=np.float32))
+    block = np.zeros(BLOCK, dtype=np.float32)
+    mixer.mix(block, speech=BLOCK)
+    assert mixer.duck == pytest.approx(0.25)  # An 80ms block is past the attack
+    assert block[-1] == pytest.approx(0.1)
+
+    quiet = [mixer.mix(np.zeros(BLOCK, dtype=np.float32)) for _ in range(int(DUCK_RELEASE * RATE / BLOCK))]
+    assert mixer.duck < 1.0  # Still coming back up...
+    mixer.mix(np.zeros(BLOCK, dtype=np.float32))
+    assert mixer.duck == 1.0 and all(quiet)
+
+
+def test_clips():
+    mixer, _ = _mixer(earcons=1.0)
+    mixer.add("earcons", np.full(10, 0.8, dtype=np.float32))
+    loud = np.full(10, 0.9, dtype=np.float32)
+    mixer.mix(loud, speech=10)
+    assert np.max(loud) == 1.0
+
+
+def test_unknown_stream():
+    mixer, _ = _mixer()
+    with pytest.raises(ValueError):
+        mixer.add("speech", np.zeros(10, dtype=np.float32))
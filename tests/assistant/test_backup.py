@@ -0,0 +1,130 @@
+"""
+Tests for backup and restore (assistant/backup.py).
+
+Covers:
+- A backup restoring every component to where it lives
+- Selective backup and restore (--only)
+- Restoring replaces a component (no stale files left) and puts everything back if a write fails
+- Wrong passphrases, damaged files and newer formats being refused
+- SQLite databases copied consistently
+"""
+
+import sqlite3
+from pathlib import Path
+
+import pytest
+
+from assistant.backup import (
+    FORMAT_VERSION, MAGIC, BackupError, components, create_backup, read_manifest, restore_backup, select,
+)
+from assistant.config import Config
+
+
+@pytest.fixture
+def home(tmp_path):
+    home = tmp_path / "home"
+    (home / ".config" / "xswarm").mkdir(parents=True)
+    (home / ".config" / "xswarm" / "config.yaml").write_text("wake_word: jarvis\n")
+    (home / ".xswarm" / "memory").mkdir(parents=True)
+    (home / ".xswarm" / "memory" / "facts.json").write_text('{"likes": "tea"}')
+    (home / ".xswarm" / "chat_history" / "jarvis").mkdir(parents=True)
+    (home / ".xswarm" / "chat_history" / "jarvis" / "session_1.json").write_text("{}")
+    db = sqlite3.connect(str(home / ".xswarm" / "events.db"))
+    db.execute("CREATE TABLE events (summary TEXT)")
+    db.execute("INSERT INTO events VALUES ('Synced')")
+    db.commit()
+    db.close()
+    return home
+
+
+def available(home):
+    return components(Config(wake_word_model=home / "vosk"), personas_dir=home / "personas", home=home)
+
+
+def test_round_trip(home, tmp_path):
+    target = tmp_path / "backup.xsb"
+    manifest = create_backup(target, "secret", select(available(home), None), "1.2.3")
+    assert manifest["format_version"] == FORMAT_VERSION
+    assert manifest["components"]["memory"]["files"] == ["memory/memory/facts.json"]
+    assert b"tea" not in target.read_bytes()  # Encrypted
+
+    (home / ".xswarm" / "memory" / "facts.json").write_text("{}")
+    (home / ".config" / "xswarm" / "config.yaml").unlink()
+    restored = restore_backup(target, "secret", select(available(home), None))
+    assert restored["memory"] == 1
+    assert (home / ".xswarm" / "memory" / "facts.json").read_text() == '{"likes": "tea"}'
+    assert (home / ".config" / "xswarm" / "config.yaml").read_text() == "wake_word: jarvis\n"
+    db = sqlite3.connect(str(home / ".xswarm" / "events.db"))
+    assert db.execute("SELECT summary FROM events").fetchall() == [("Synced",)]
+    db.close()
+    assert read_manifest(target, "secret")["app_version"] == "1.2.3"
+
+
+def test_selective(home, tmp_path):
+    target = tmp_path / "backup.xsb"
+    create_backup(target, "secret", select(available(home), None))
+    (home / ".xswarm" / "memory" / "facts.json").write_text("{}")
+    (home / ".config" / "xswarm" / "config.yaml").write_text("wake_word: friday\n")
+
+    assert restore_backup(target, "secret", select(available(home), ["memory"])) == {"memory": 1}
+    assert (home / ".xswarm" / "memory" / "facts.json").read_text() == '{"likes": "tea"}'
+    assert (home / ".config" / "xswarm" / "config.yaml").read_text() == "wake_word: friday\n"
+
+    partial = tmp_path / "events.xsb"
+    manifest = create_backup(partial, "secret", select(available(home), ["events,transcripts"]))
+    assert list(manifest["components"]) == ["events", "transcripts"]
+    with pytest.raises(BackupError, match="Unknown component 'photos'"):
+        select(available(home), ["photos"])
+
+
+def test_restore_replaces_or_rolls_back(home, tmp_path, monkeypatch):
+    target = tmp_path / "backup.xsb"
+    create_backup(target, "secret", select(available(home), None))
+    memory = home / ".xswarm" / "memory"
+    (memory / "facts.json").write_text("{}")
+    (memory / "stale.json").write_text("{}")
+    (home / ".config" / "xswarm" / "config.yaml").write_text("wake_word: friday\n")
+
+    write_bytes, writes = Path.write_bytes, []
+
+    def failing_write(path, data):
+        writes.append(path)
+        if len(writes) == 3:
+            raise OSError("disk full")
+        return write_bytes(path, data)
+
+    monkeypatch.setattr(Path, "write_bytes", failing_write)
+    with pytest.raises(OSError, match="disk full"):
+        restore_backup(target, "secret", select(available(home), None))
+    monkeypatch.setattr(Path, "write_bytes", write_bytes)
+    assert sorted(p.name for p in memory.iterdir()) == ["facts.json", "stale.json"]
+    assert (memory / "facts.json").read_text() == "{}"
+    assert (home / ".config" / "xswarm" / "config.yaml").read_text() == "wake_word: friday\n"
+    assert not [p for p in home.rglob("*before-restore*")]
+
+    restore_backup(target, "secret", select(available(home), ["memory", "config"]))
+    assert sorted(p.name for p in memory.iterdir()) == ["facts.json"]  # The stale file went with the old folder
+    assert (memory / "facts.json").read_text() == '{"likes": "tea"}'
+    assert not [p for p in home.rglob("*before-restore*")]
+
+
+def test_refuses_bad_backups(home, tmp_path):
+    target = tmp_path / "backup.xsb"
+    create_backup(target, "secret", select(available(home), ["config"]))
+    with pytest.raises(BackupError, match="Wrong passphrase"):
+        read_manifest(target, "guess")
+
+    blob = bytearray(target.read_bytes())
+    blob[-1] ^= 1
+    (tmp_path / "damaged.xsb").write_bytes(bytes(blob))
+    with pytest.raises(BackupError, match="damaged"):
+        read_manifest(tmp_path / "damaged.xsb", "secret")
+
+    (tmp_path / "newer.xsb").write_bytes(MAGIC + bytes([FORMAT_VERSION + 1]) + bytes(64))
+    with pytest.raises(BackupError, match="newer"):
+        read_manifest(tmp_path / "newer.xsb", "secret")
+    (tmp_path / "notes.txt").write_text("hello")
+    with pytest.raises(BackupError, match="Not an xswarm backup"):
+        read_manifest(tmp_path / "notes.txt", "secret")
+    with pytest.raises(BackupError, match="passphrase"):
+        create_backup(target, "", [])
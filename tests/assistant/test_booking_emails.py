@@ -0,0 +1,143 @@
+"""
+Tests for flight, shipping and reservation confirmations (assistant/booking_emails.py).
+
+Covers:
+- schema.org JSON-LD: flights (with connections), hotels, restaurants, deliveries
+- Times with a UTC offset converted to home time
+- Plain-text flight and tracking emails, and ones without a booking reference
+- Proposals carrying the details and tailored reminder, skipping the LLM review
+"""
+
+import asyncio
+import json
+from datetime import date, datetime
+from types import SimpleNamespace
+
+from assistant.booking_emails import from_markup, parse_confirmations
+from assistant.dates import DateSettings, get_date_settings, set_date_settings
+from assistant.email_tasks import EmailTaskDetector, detect_email_proposals, notice
+from assistant.followups import FollowUpStore
+from assistant.planner import PlannerData
+
+FRIDAY = date(2026, 10, 16)
+
+
+def email(content, subject="Your booking", sender="United Airlines"):
+    return SimpleNamespace(id="e1", channel="email", sender=sender, content=content, subject=subject,
+                           received_at="2026-10-16T08:00:00Z")
+
+
+def markup(*entries):
+    return "".join(f'<script type="application/ld+json">{json.dumps(e)}</script>' for e in entries) + "<p>Hi</p>"
+
+
+def flight(number, origin, destination, departs, arrives, **extra):
+    return {"@type": "Flight", "flightNumber": number, "airline": {"@type": "Airline", "iataCode": "UA"},
+            "departureAirport": {"@type": "Airport", "name": f"{origin} Intl", "iataCode": origin},
+            "arrivalAirport": {"@type": "Airport", "iataCode": destination},
+            "departureTime": departs, "arrivalTime": arrives, **extra}
+
+
+TRIP = markup({"@context": "http://schema.org", "@type": "FlightReservation", "reservationNumber": "K7X2QP",
+               "underName": {"@type": "Person", "name": "Sam Lee"}, "airplaneSeat": "14C",
+               "reservationFor": [flight("523", "SFO", "DEN", "2026-10-23T09:40:00", "2026-10-23T13:05:00",
+                                         departureTerminal="3", departureGate="F12"),
+                                  flight("UA1187", "DEN", "BOS", "2026-10-23T14:30:00", "2026-10-23T20:10:00")]})
+
+
+def test_markup_flights():
+    first, second = from_markup(TRIP)
+    assert (first.kind, first.title, first.start, first.end, first.location) == (
+        "flight", "Flight UA 523 SFO → DEN", datetime(2026, 10, 23, 9, 40), datetime(2026, 10, 23, 13, 5), "SFO Intl")
+    assert first.description() == ("Flight: UA 523\nConfirmation: K7X2QP\nRoute: SFO → DEN\nTerminal: 3\n"
+                                   "Gate: F12\nSeat: 14C\nPassenger: Sam Lee")
+    assert (second.title, first.reminder_minutes) == ("Flight UA1187 DEN → BOS", 180)
+
+
+def test_markup_other_kinds():
+    found = from_markup(markup(
+        {"@context": "http://schema.org", "@graph": [
+            {"@type": "LodgingReservation", "reservationNumber": "H-99812", "checkinTime": "2026-10-23T15:00:00",
+             "checkoutTime": "2026-10-26T11:00:00",
+             "reservationFor": {"@type": "LodgingBusiness", "name": "Hotel Monaco",
+                                "address": {"streetAddress": "1717 Champa St", "addressLocality": "Denver"}}},
+            {"@type": "FoodEstablishmentReservation", "startTime": "2026-10-24T19:30:00", "partySize": 2,
+             "reservationFor": {"@type": "FoodEstablishment", "name": "Bistro Vendôme"}},
+            {"@type": "ParcelDelivery", "expectedArrivalUntil": "2026-10-20T20:00:00-07:00",
+             "trackingNumber": "1Z999AA10123456784", "carrier": {"@type": "Organization", "name": "UPS"},
+             "itemShipped": {"@type": "Product", "name": "Noise-cancelling headphones"}},
+            {"@type": "Person", "name": "Not a booking"}]},
+        "{not json"))
+    hotel, dinner, parcel = found
+    assert (hotel.title, hotel.location, hotel.reminder_minutes) == (
+        "Check in: Hotel Monaco", "Hotel Monaco, 1717 Champa St, Denver", 120)
+    assert hotel.description() == "Hotel: Hotel Monaco\nConfirmation: H-99812\nCheck-out: Mon Oct 26 11:00"
+    assert (dinner.title, dinner.description()) == ("Reservation: Bistro Vendôme",
+                                                    "Restaurant: Bistro Vendôme\nParty size: 2")
+    assert (parcel.title, parcel.has_time) == ("Delivery: Noise-cancelling headphones", False)
+
+
+def test_offset_times_become_home_time():
+    previous = get_date_settings()
+    set_date_settings(DateSettings(timezone="America/New_York"))
+    try:
+        [leg] = from_markup(markup({"@type": "FlightReservation", "reservationFor": flight(
+            "523", "SFO", "DEN", "2026-10-23T09:40:00-07:00", "2026-10-23T13:05:00-06:00")}))
+    finally:
+        set_date_settings(previous)
+    assert (leg.start, leg.end) == (datetime(2026, 10, 23, 12, 40), datetime(2026, 10, 23, 15, 5))
+
+
+def test_text_formats():
+    [leg] = parse_confirmations(email(
+        "Your trip is confirmed. Confirmation code: K7X2QP\nFlight UA 523 SFO to DEN departs "
+        "Friday, October 23 at 9:40 AM from Terminal 3. Seat 14C."), FRIDAY)
+    assert (leg.title, leg.start, leg.location) == ("Flight UA 523 SFO → DEN", datetime(2026, 10, 23, 9, 40), "SFO")
+    assert leg.details == {"Flight": "UA 523", "Confirmation": "K7X2QP", "Route": "SFO → DEN",
+                           "Terminal": "3", "Gate": None, "Seat": "14C"}
+
+    [parcel] = parse_confirmations(email("Your order has shipped with FedEx. Tracking number: 771234567890.\n"
+                                         "Arriving Tuesday, October 20.", "Shipped!", "Acme Store"), FRIDAY)
+    assert (parcel.title, parcel.start, parcel.description()) == (
+        "Delivery: Acme Store", datetime(2026, 10, 20), "Carrier: FedEx\nTracking: 771234567890\nFrom: Acme Store")
+
+    # No booking reference: left to the generic rules
+    assert parse_confirmations(email("Your flight UA 523 to Denver departs Friday, October 23 at 9:40 AM."),
+                               FRIDAY) == []
+    assert from_markup(markup({"@type": "FlightReservation", "reservationFor": flight(
+        "523", "SFO", "DEN", "2026-10-02T09:40:00", None)}))  # Parsed, but past ones are dropped below
+    assert parse_confirmations(email(markup({"@type": "FlightReservation", "reservationFor": flight(
+        "523", "SFO", "DEN", "2026-10-02T09:40:00", None)})), FRIDAY) == []
+
+
+class FakeAI:
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        raise AssertionError("Parsed confirmations don't need the LLM")
+
+
+def test_proposals_and_accept(tmp_path):
+    item = email(TRIP, "Your trip confirmation")
+    first, second = asyncio.run(EmailTaskDetector(FakeAI()).detect(item))
+    assert (first.kind, first.start_time, first.end_time, first.reminder_minutes, first.detected_by) == (
+        "event", "2026-10-23T09:40", "2026-10-23T13:05", 180, "parser")
+    assert notice(item, first) == ("📧 Flight confirmation detected from United Airlines - add to calendar? "
+                                   "Flight UA 523 SFO → DEN 📅 Fri Oct 23 09:40 (confirm in Schedule)")
+
+    planner = PlannerData(tmp_path / "planner")
+    store = FollowUpStore(tmp_path / "followups")
+    store.add([first])
+    store.accept(first.id, planner)
+    [event] = planner.get_calendar_events(start_date="2026-10-23", end_date="2026-10-23")
+    assert (event.reminder_minutes, event.location) == (180, "SFO Intl")
+    assert event.description.startswith("Flight: UA 523\nConfirmation: K7X2QP\n")
+    assert event.description.endswith("Email from United Airlines: Your trip confirmation")
+
+    [parcel] = detect_email_proposals(email("Tracking number: 771234567890. Expected delivery October 20.",
+                                            "Shipped", "Acme Store"))
+    store.add([parcel])
+    assert store.accept(parcel.id, planner) == "✓ Task added: Delivery: Acme Store (due 2026-10-20)"
+    [task] = [t for t in planner.get_tasks() if t.title == "Delivery: Acme Store"]
+    assert task.notes.startswith("Tracking: 771234567890")
@@ -0,0 +1,99 @@
+"""
+Tests for conversation bookmarks (assistant/bookmarks.py).
+
+Covers:
+- "remember this as X", "bookmark the last N messages" and friends; questions about bookmarks
+- The turns noted from the chat: streamed replies replace themselves, the request itself is left out
+- Stored with a label (made up when none was given); found by label or what was said, plurals and all
+- The tools, forgetting one and "undo that"
+"""
+
+import pytest
+
+from assistant import bookmarks, tools
+from assistant.bookmarks import (BookmarkStore, clear_recent, note_turn, parse_bookmark_query,
+                                 parse_bookmark_request, recent_turns)
+from assistant.undo import UndoLog, undo_last
+
+
+@pytest.fixture
+def store(tmp_path, monkeypatch):
+    store = BookmarkStore(tmp_path / "memory")
+    monkeypatch.setattr(bookmarks, "_store", store)
+    monkeypatch.setattr(tools, "_undo_log", UndoLog(tmp_path / "undo"))
+    clear_recent()
+    yield store
+    clear_recent()
+
+
+def converse(*turns):
+    for sender, text in turns:
+        note_turn(sender, text)
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("remember this as the contract terms", (4, "contract terms")),
+    ("Okay, bookmark that", (4, "")),
+    ("pin this conversation, call it lease", None),
+    ("pin this conversation and call it lease", (4, "lease")),
+    ("bookmark the last 6 messages about the lease", (6, "lease")),
+    ("save the last three exchanges as 'trip plans' please", (6, "trip plans")),
+    ("remember to buy milk", None),
+    ("remember that my sister's birthday is in May", None),
+])
+def test_parse_request(text, expected):
+    assert parse_bookmark_request(text) == expected
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("what did I bookmark about the contract?", "the contract"),
+    ("show me my bookmarks", ""),
+    ("what bookmarks do I have", ""),
+    ("what did I say about the contract?", None),
+])
+def test_parse_query(text, expected):
+    assert parse_bookmark_query(text) == expected
+
+
+def test_recent_turns(store):
+    converse(("System", "Connected"), ("User", "what's the notice period?"), ("Jarvis", "..."))
+    note_turn("Jarvis", "Thirty", replace_last=True)
+    note_turn("Jarvis", "Thirty days, in writing ▌", replace_last=True)
+    note_turn("Jarvis", "Thirty days, in writing.", replace_last=True)
+    converse(("User", "remember this as the notice period"))
+    assert recent_turns() == [{"sender": "User", "text": "what's the notice period?"},
+                              {"sender": "Jarvis", "text": "Thirty days, in writing."}]
+    assert recent_turns(1) == [{"sender": "Jarvis", "text": "Thirty days, in writing."}]
+
+
+def test_store_and_find(store, tmp_path):
+    first = store.add("", [{"sender": "User", "text": "the landlord wants two months' deposit, is that normal?"},
+                           {"sender": "Jarvis", "text": "One month is usual."}])
+    assert first.label == "the landlord wants two months' deposit…" and first.persona == "Jarvis"
+    store.add("contract terms", [{"sender": "User", "text": "payment is net 30"}])
+    with pytest.raises(ValueError):
+        store.add("empty", [])
+
+    again = BookmarkStore(tmp_path / "memory")
+    assert [b.label for b in again.find("contracts")] == ["contract terms"]
+    assert [b.label for b in again.find("deposits")] == [first.label]
+    assert [b.label for b in again.find("")] == ["contract terms", first.label]
+    assert again.find("weather") == []
+    assert first.transcript() == ["You: the landlord wants two months' deposit, is that normal?",
+                                  "Jarvis: One month is usual."]
+
+
+def test_tools_and_undo(store):
+    assert tools.bookmark_conversation("lease").startswith("✗")
+    converse(("User", "the lease ends in March"), ("Jarvis", "Noted, March it is."),
+             ("User", "bookmark that as the lease"))
+    assert tools.bookmark_conversation("lease") == "✓ Bookmarked 2 turns as 'lease'"
+    assert tools.find_bookmarks("lease").splitlines()[2:] == ["    You: the lease ends in March",
+                                                              "    Jarvis: Noted, March it is."]
+    assert tools.find_bookmarks("taxes") == "No bookmarks about 'taxes'."
+
+    assert tools.forget_bookmark("nothing").startswith("✗")
+    assert tools.forget_bookmark("lease").startswith("✓ Forgot 1 bookmark")
+    assert store.bookmarks() == []
+    assert undo_last(tools.get_undo_log()) == "✓ Undone: Forgot 1 bookmark"
+    assert [b.label for b in store.bookmarks()] == ["lease"]
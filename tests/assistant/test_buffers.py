@@ -0,0 +1,32 @@
+"""
+Tests for the dashboard's buffer caps (assistant/buffers.py).
+
+Covers:
+- Caps from config.buffer_caps over the defaults; unknown names ignored
+- Ring buffers dropping their oldest entries
+- The memory self-report: entries against caps and a rough size
+"""
+
+from assistant.buffers import CAPS, BufferCaps, approx_size, buffer_usage, ring, usage_line
+
+
+def test_caps():
+    caps = BufferCaps({"activity": 50, "chat": 0, "typo": 3})
+    assert caps["activity"] == 50 and caps["chat"] == 1 and caps["chart"] == CAPS["chart"]
+    assert "typo" not in caps.caps
+
+
+def test_ring():
+    feed = ring(3)
+    for n in range(5):
+        feed.append({"id": n, "message": f"event {n}"})
+    assert [msg["id"] for msg in feed] == [2, 3, 4]
+
+
+def test_usage():
+    feed = ring(3, [{"message": "x" * 1000}])
+    assert approx_size(feed) > 1000 > approx_size(ring(3))
+    rows = buffer_usage({"feed": (feed, 3), "chat": ([], 10)})
+    assert [row[:3] for row in rows] == [("feed", 1, 3), ("chat", 0, 10)]
+    line = usage_line(rows)
+    assert line.startswith("feed 1/3 · chat 0/10 · ~") and line.endswith("KB")
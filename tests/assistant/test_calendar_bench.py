@@ -0,0 +1,74 @@
+"""
+Tests for the calendar load benchmarks (assistant/calendar_bench.py).
+
+Covers:
+- Synthetic calendars: the same events for the same seed, valid times, a mix of recurrences
+- Every benchmark runs and counts the work it timed
+- Growth exponents between sizes (linear vs quadratic)
+- Baselines: saved and loaded as JSON, regressions past the threshold reported
+"""
+
+import json
+from datetime import datetime
+
+import pytest
+
+from assistant.calendar_bench import (
+    BENCHMARKS, BenchError, BenchReport, BenchResult, run_benchmarks, synthetic_events,
+)
+
+
+def test_synthetic_events_are_repeatable():
+    events = synthetic_events(300)
+    assert events == synthetic_events(300) and events != synthetic_events(300, seed=1)
+    assert len({e["id"] for e in events}) == 300
+    for event in events:
+        assert datetime.fromisoformat(event["start_time"]) <= datetime.fromisoformat(event["end_time"])
+    recurrences = {e["recurrence"] for e in events}
+    assert {"none", "daily", "weekly", "monthly", "yearly"} <= recurrences
+    assert any("T" not in e["start_time"] for e in events) and any(not e["busy"] for e in events)
+
+
+def test_runs_every_benchmark():
+    seen = []
+    report = run_benchmarks([20, 40], repeat=1, on_result=seen.append)
+    assert [(r.name, r.size) for r in report.results] == [(n, s) for s in (20, 40) for n in BENCHMARKS]
+    assert seen == report.results
+    assert all(r.seconds > 0 and r.items > 0 for r in report.results)
+    expand = [r.items for r in report.results if r.name == "expand_recurrence"]
+    assert expand[1] > expand[0]  # More events, more occurrences
+
+
+def test_only_some():
+    report = run_benchmarks([10], repeat=1, names=["free_slots"])
+    assert [r.name for r in report.results] == ["free_slots"]
+    with pytest.raises(BenchError, match="Unknown benchmark 'sort'"):
+        run_benchmarks([10], names=["sort"])
+
+
+def test_exponents():
+    report = BenchReport([BenchResult("linear", 100, 0.01, 1), BenchResult("linear", 1000, 0.1, 1),
+                          BenchResult("quadratic", 100, 0.01, 1), BenchResult("quadratic", 1000, 1.0, 1)])
+    assert report.exponents("linear") == [pytest.approx(1.0)]
+    assert report.exponents("quadratic") == [pytest.approx(2.0)]
+    assert "  growth: n^2.00" in report.lines()
+
+
+def test_baseline(tmp_path):
+    baseline = BenchReport([BenchResult("find_conflicts", 100, 0.010, 50), BenchResult("free_slots", 100, 0.010, 9)])
+    path = tmp_path / "bench.json"
+    baseline.save(path)
+    assert BenchReport.load(path).results == baseline.results
+
+    run = BenchReport([BenchResult("find_conflicts", 100, 0.020, 50), BenchResult("free_slots", 100, 0.011, 9),
+                       BenchResult("free_slots", 500, 0.5, 9)])  # No baseline at 500
+    assert run.compare(BenchReport.load(path)) == ["find_conflicts at 100: 10.0 ms -> 20.0 ms (+100%)"]
+    assert run.compare(baseline, max_regression=1.5) == []
+    assert "(+100% vs baseline)" in "\n".join(run.lines(baseline))
+    assert json.loads(json.dumps(run.to_dict()))["results"][0]["name"] == "find_conflicts"
+
+
+def test_unreadable_baseline(tmp_path):
+    (tmp_path / "bench.json").write_text("{not json")
+    with pytest.raises(BenchError, match="Can't read the baseline"):
+        BenchReport.load(tmp_path / "bench.json")
@@ -0,0 +1,113 @@
+"""
+Tests for the pure calendar core (assistant/calendar_core.py, assistant/dates.py).
+
+The cases live in tests/fixtures/core/ so other implementations (the
+Pyodide bundle in a web dashboard) can run exactly the same ones.
+
+Covers:
+- Date, time, date+time and end time parsing cases
+- Recurrence expansion cases, and the planner using it
+- Conflict detection cases, and add_calendar_event warning about overlaps
+- The interval index finding the same conflicts as a scan
+- What each visibility shares, and free/busy times
+- The core importing nothing beyond the standard library, and the bundle
+"""
+
+import json
+import zipfile
+from datetime import date, datetime
+from pathlib import Path
+
+from assistant import tools
+from assistant.calendar_bench import synthetic_events
+from assistant.calendar_core import (
+    CORE_MODULES, IntervalIndex, busy_times, check_core, expand_recurrence, find_conflicts, shared_view,
+    write_bundle,
+)
+from assistant.dates import parse_end_time, parse_natural_date, parse_natural_datetime, parse_time_expression
+from assistant.planner import PlannerData
+
+CASES = Path(__file__).parent.parent / "fixtures" / "core"
+
+
+def load(name):
+    return json.loads((CASES / name).read_text(encoding="utf-8"))
+
+
+def test_date_cases():
+    cases = load("dates.json")
+    today = date.fromisoformat(cases["today"])
+    for case in cases["dates"]:
+        parsed = parse_natural_date(case["text"], today, case.get("date_order", "mdy"))
+        assert (parsed.isoformat() if parsed else None) == case["expected"], case["text"]
+    for case in cases["times"]:
+        assert parse_time_expression(case["text"]) == case["expected"], case["text"]
+    for case in cases["datetimes"]:
+        parsed = parse_natural_datetime(case["text"], today=today, date_order=case.get("date_order", "mdy"))
+        assert (parsed.isoformat() if parsed else None) == case["expected"], case["text"]
+    for case in cases["ends"]:
+        end = parse_end_time(case["text"], datetime.fromisoformat(case["start"]))
+        assert (end.isoformat() if end else None) == case["expected"], case["text"]
+
+
+def test_recurrence_cases(tmp_path):
+    for case in load("recurrence.json"):
+        instances = expand_recurrence(case["event"], case["start_date"], case["end_date"])
+        assert [i["start_time"] for i in instances] == case["expected"], case["name"]
+        assert all(i["_original_id"] == case["event"]["id"] for i in instances)
+
+    planner = PlannerData(storage_dir=tmp_path)
+    planner.add_calendar_event(title="Standup", start_time="2026-10-05T09:00:00", end_time="2026-10-05T09:15:00",
+                               recurrence="weekly")
+    events = planner.get_calendar_events(start_date="2026-10-01", end_date="2026-10-31")
+    assert [e.start_time[:10] for e in events] == ["2026-10-05", "2026-10-12", "2026-10-19", "2026-10-26"]
+
+
+def test_conflict_cases(tmp_path, monkeypatch):
+    for case in load("conflicts.json"):
+        found = find_conflicts(case["event"], case["others"])
+        assert [other["id"] for other in found] == case["expected"], case["name"]
+
+    planner = PlannerData(storage_dir=tmp_path)
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    planner.add_calendar_event(title="Standup", start_time="2026-10-19T09:00:00", end_time="2026-10-19T09:30:00")
+    result = tools.add_calendar_event("Dentist", "2026-10-19", "09:15", duration_minutes=45)
+    assert result.startswith("✓ Added: 'Dentist'")
+    assert "⚠ Overlaps 'Standup' at 09:00" in result
+    assert "Overlaps" not in tools.add_calendar_event("Lunch", "2026-10-19", "12:00", show_as="free")
+
+
+def test_sharing_cases():
+    cases = load("sharing.json")
+    for case in cases["views"]:
+        assert shared_view(case["event"], case["default"]) == case["expected"], case["name"]
+    for case in cases["busy_times"]:
+        assert [list(b) for b in busy_times(case["events"], case["default"])] == case["expected"], case["name"]
+
+
+def test_core_is_pure_and_bundles(tmp_path):
+    assert check_core() == []
+
+    (tmp_path / "dates.py").write_text("import httpx\nfrom .config import Config\n")
+    (tmp_path / "calendar_core.py").write_text("from .dates import parse_natural_date\n")
+    assert check_core(tmp_path) == ["dates.py: import httpx", "dates.py: from .config import ..."]
+
+    bundle = tmp_path / "core.zip"
+    names = write_bundle(bundle)
+    assert names == ["xswarm_core/__init__.py"] + [f"xswarm_core/{name}.py" for name in CORE_MODULES]
+    with zipfile.ZipFile(bundle) as archive:
+        assert archive.namelist() == names
+
+
+def test_interval_index_matches_the_scan():
+    for case in load("conflicts.json"):
+        found = find_conflicts(case["event"], IntervalIndex(case["others"]))
+        assert [other["id"] for other in found] == case["expected"], case["name"]
+
+    events = synthetic_events(400, seed=3)
+    day_events = [e for e in events if e["start_time"].startswith(events[0]["start_time"][:10])]
+    index = IntervalIndex(events)
+    assert len(index) == sum(1 for e in events if e["busy"] and "T" in e["start_time"])
+    for event in events + [{"id": "long", "start_time": "2026-01-01T00:00:00", "end_time": "2026-05-01T00:00:00"}]:
+        assert find_conflicts(event, index) == find_conflicts(event, events), event["id"]
+    assert find_conflicts(day_events[0], IntervalIndex([])) == []
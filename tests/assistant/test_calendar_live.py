@@ -0,0 +1,132 @@
+"""
+Tests for live server calendar changes (assistant/calendar_live.py, CalendarSync.remote_events).
+
+Covers:
+- A burst of change hints causes one pull; reconnecting catches up
+- Dropped connections retried with growing backoff; a server without subscriptions stops it
+- Appointments made on other devices shown as planner events in home time
+"""
+
+import asyncio
+from datetime import date
+
+import pytest
+
+from assistant import dates
+from assistant.calendar_live import CalendarSubscription
+from assistant.calendar_mirror import CalendarMirror
+from assistant.dates import DateSettings
+from assistant.planner import PlannerData
+from assistant.scheduler_client import CalendarSync, SchedulerClient
+
+HINT = '{"type": "calendar_change", "entity": "appointment", "op": "upsert", "count": 1}'
+
+
+class Refused(Exception):
+    def __init__(self, status_code):
+        super().__init__(f"server rejected WebSocket connection: HTTP {status_code}")
+        self.status_code = status_code
+
+
+class FakeSocket:
+    def __init__(self, messages):
+        self.messages = list(messages)
+
+    async def __aenter__(self):
+        return self
+
+    async def __aexit__(self, *exc):
+        return False
+
+    def __aiter__(self):
+        return self
+
+    async def __anext__(self):
+        if not self.messages:
+            raise ConnectionResetError("connection closed")
+        return self.messages.pop(0)
+
+
+class FakeServer:
+    """Each connect() takes the next scripted session: a list of messages, or an exception."""
+
+    def __init__(self, sessions):
+        self.sessions = list(sessions)
+        self.connects = []
+
+    def __call__(self, url, headers):
+        self.connects.append((url, headers))
+        session = self.sessions.pop(0) if self.sessions else Refused(503)
+        if isinstance(session, Exception):
+            raise session
+        return FakeSocket(session)
+
+
+def run_subscription(server, pulls=None):
+    pulls = [] if pulls is None else pulls
+    delays = []
+
+    async def on_change():
+        pulls.append(len(server.connects))
+
+    async def sleep(delay):
+        delays.append(delay)
+        await asyncio.sleep(0)  # Let the pull run
+
+    subscription = CalendarSubscription("https://api.xswarm.ai", "u 1", on_change, api_token="tok",
+                                        connect=server, sleep=sleep)
+    asyncio.run(subscription.run())
+    return subscription, pulls, delays
+
+
+def test_hints_coalesce_and_reconnects_catch_up():
+    server = FakeServer([[HINT, HINT, "pong", HINT], [HINT]])
+    subscription, pulls, delays = run_subscription(server)
+
+    assert server.connects[0] == ("wss://api.xswarm.ai/api/calendar/subscribe?user_id=u+1", {"Authorization": "Bearer tok"})
+    assert pulls == [1, 2]  # One per connection: the catch-up and the burst together
+    assert subscription.changes == 4
+    assert subscription.disabled and not subscription.connected
+
+
+def test_backoff_until_the_server_refuses():
+    server = FakeServer([OSError("unreachable")] * 7)
+    subscription, pulls, delays = run_subscription(server)
+    assert delays == [1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 60.0]
+    assert pulls == [] and subscription.disabled
+
+
+def test_refused_with_other_errors_keeps_retrying():
+    server = FakeServer([Refused(500), [HINT]])
+    subscription, pulls, delays = run_subscription(server)
+    assert delays[0] == 1.0 and pulls == [2]
+
+
+@pytest.fixture
+def home_new_york(monkeypatch):
+    monkeypatch.setattr(dates, "_settings", DateSettings(timezone="America/New_York"))
+
+
+def test_remote_events(tmp_path, home_new_york):
+    mirror = CalendarMirror(path=":memory:")
+    mirror.apply([
+        {"entity": "appointment", "id": "apt-1", "op": "upsert", "data": {
+            "id": "apt-1", "title": "Dentist", "start_time": "2026-10-14T14:00:00Z",
+            "end_time": "2026-10-14T15:00:00Z", "status": "scheduled", "tags": ["health"]}},
+        {"entity": "appointment", "id": "apt-2", "op": "upsert", "data": {
+            "id": "apt-2", "title": "Standup", "start_time": "2026-10-14T13:00:00Z",
+            "end_time": "2026-10-14T13:15:00Z", "status": "scheduled", "tags": []}},
+        {"entity": "appointment", "id": "apt-3", "op": "upsert", "data": {
+            "id": "apt-3", "title": "Late call", "start_time": "2026-10-15T02:00:00Z",  # 22:00 on the 14th in New York
+            "end_time": "2026-10-15T02:30:00Z", "status": "scheduled", "tags": []}},
+    ], 3)
+    sync = CalendarSync(PlannerData(tmp_path / "planner"), SchedulerClient(), storage_dir=tmp_path / "sync", mirror=mirror)
+    sync._load()["appointments"]["evt-1@2026-10-14T09:00"] = {"id": "apt-2", "start": "2026-10-14T09:00", "fingerprint": "x"}
+
+    events = sync.remote_events(date(2026, 10, 14))
+    assert [(e.id, e.title, e.start_time) for e in events] == [
+        ("server:apt-1", "Dentist", "2026-10-14T10:00"),  # apt-2 came from this planner
+        ("server:apt-3", "Late call", "2026-10-14T22:00"),
+    ]
+    assert [e.title for e in sync.remote_events(date(2026, 10, 14), tag="health")] == ["Dentist"]
+    assert sync.remote_events(date(2026, 10, 15)) == []
@@ -0,0 +1,117 @@
+"""
+Tests for delta sync of the server calendar (assistant/calendar_mirror.py).
+
+Covers:
+- A first pull pages through the whole journal; later pulls only ask for what's new
+- Updates and deletes applied, the cursor kept across restarts
+- A page that fails to apply leaves the mirror and cursor as they were
+- A cursor the server doesn't recognize resyncs from scratch
+- Reads by time range compare UTC offsets correctly
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.calendar_mirror import CalendarMirror
+
+
+def appointment(id, start, title="Standup", status="scheduled"):
+    return {"id": id, "title": title, "start_time": start, "end_time": start, "status": status}
+
+
+class FakeJournal:
+    """The server's change journal and GET /api/calendar/changes, two entries a page."""
+
+    PAGE = 2
+
+    def __init__(self):
+        self.entries = []  # (seq, entity, id, op, data)
+        self.requests = []
+
+    def write(self, entity, id, data=None):
+        self.entries.append((len(self.entries) + 1, entity, id, "delete" if data is None else "upsert", data))
+
+    async def changes(self, user_id, since=0, limit=None):
+        self.requests.append(since)
+        if since > len(self.entries):  # Not a position in this journal
+            return {"changes": [], "cursor": 0, "has_more": True, "reset": True}
+        page = [e for e in self.entries if e[0] > since][:self.PAGE]
+        changes = [{"seq": seq, "entity": entity, "id": id, "op": op, **({"data": data} if data else {})}
+                   for seq, entity, id, op, data in page]
+        return {
+            "changes": changes,
+            "cursor": page[-1][0] if page else since,
+            "has_more": len([e for e in self.entries if e[0] > since]) > self.PAGE,
+        }
+
+
+@pytest.fixture
+def journal():
+    journal = FakeJournal()
+    journal.write("appointment", "a1", appointment("a1", "2026-10-14T09:00:00-04:00"))
+    journal.write("appointment", "a2", appointment("a2", "2026-10-14T15:00:00+00:00", "Dentist"))
+    journal.write("reminder", "r1", {"id": "r1", "title": "Call mom", "due_time": "2026-10-14T18:00:00", "completed": False})
+    return journal
+
+
+def test_pulls_deltas_and_keeps_the_cursor(tmp_path, journal):
+    mirror = CalendarMirror(journal, "u1", tmp_path / "mirror.db")
+    report = asyncio.run(mirror.pull())
+    assert (report.updated, report.pages, report.complete) == (3, 2, True)
+    assert journal.requests == [0, 2]
+    assert [a["title"] for a in mirror.appointments()] == ["Standup", "Dentist"]
+    assert [r["title"] for r in mirror.reminders()] == ["Call mom"]
+
+    journal.write("appointment", "a1", appointment("a1", "2026-10-14T10:00:00-04:00", "Standup (moved)"))
+    journal.write("appointment", "a2")
+    journal.write("reminder", "r1", {"id": "r1", "title": "Call mom", "due_time": "2026-10-14T18:00:00", "completed": True})
+    mirror.close()
+
+    # A restart picks up where it left off
+    mirror = CalendarMirror(journal, "u1", tmp_path / "mirror.db")
+    assert mirror.cursor == 3
+    report = asyncio.run(mirror.pull())
+    assert (report.updated, report.deleted) == (2, 1)
+    assert journal.requests[2:] == [3, 5]
+    assert [a["title"] for a in mirror.appointments()] == ["Standup (moved)"]
+    assert mirror.reminders() == [] and len(mirror.reminders(completed=True)) == 1
+
+    assert asyncio.run(mirror.pull()).summary() == "Calendar mirror up to date"
+
+
+def test_failed_page_is_rolled_back(journal):
+    mirror = CalendarMirror(journal, "u1", ":memory:")
+    journal.PAGE = 3
+    asyncio.run(mirror.pull())
+
+    broken = [
+        {"seq": 4, "entity": "appointment", "id": "a1", "op": "delete"},
+        {"seq": 5, "entity": "appointment", "id": "a3", "op": "upsert"},  # No data
+    ]
+    with pytest.raises(KeyError):
+        mirror.apply(broken, 5)
+    assert mirror.cursor == 3
+    assert len(mirror.appointments()) == 2  # The delete before it didn't stick
+
+
+def test_unknown_cursor_resyncs(journal):
+    mirror = CalendarMirror(journal, "u1", ":memory:")
+    # Synced against a server whose database was since restored from an older backup
+    mirror.apply([{"seq": 9, "entity": "appointment", "id": "ghost", "op": "upsert",
+                   "data": appointment("ghost", "2026-10-15T09:00:00")}], 9)
+
+    report = asyncio.run(mirror.pull())
+    assert report.reset and report.complete
+    assert journal.requests == [9, 0, 2]
+    assert sorted(a["id"] for a in mirror.appointments()) == ["a1", "a2"]
+    assert "resynced from scratch" in report.summary()
+
+
+def test_time_range_reads(journal):
+    mirror = CalendarMirror(journal, "u1", ":memory:")
+    asyncio.run(mirror.pull())
+    # 09:00-04:00 is 13:00 UTC, before 15:00+00:00
+    assert [a["id"] for a in mirror.appointments(start="2026-10-14T12:00:00+00:00", end="2026-10-14T14:00:00+00:00")] == ["a1"]
+    assert [a["id"] for a in mirror.appointments(start="2026-10-14T14:00:00+00:00")] == ["a2"]
+    assert mirror.appointments(status="cancelled") == []
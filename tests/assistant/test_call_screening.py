@@ -0,0 +1,87 @@
+"""
+Tests for call screening and voicemail search.
+
+Covers:
+- Voicemail transcript search (all words must match)
+- Decisions are relayed for the right call and clear it locally
+"""
+
+import asyncio
+
+from assistant.call_screening import CallScreeningManager, ScreenedCall, VoicemailStore, format_voicemails
+
+
+def _voicemail(vm_id, transcript, created_at="2026-01-01T10:00:00", purpose=None):
+    return {
+        "id": vm_id,
+        "call_sid": f"CA{vm_id}",
+        "caller": "+15551234567",
+        "transcript": transcript,
+        "purpose": purpose,
+        "duration": "42",
+        "created_at": created_at,
+    }
+
+
+class FakeClient:
+    def __init__(self):
+        self.decisions = []
+
+    async def decide(self, call_sid, decision, message=None):
+        self.decisions.append((call_sid, decision, message))
+        return {"success": True}
+
+
+class TestVoicemailStore:
+    def test_search_requires_every_term(self, tmp_path):
+        store = VoicemailStore(tmp_path)
+        store.merge_remote([
+            _voicemail("1", "Calling about the overdue invoice"),
+            _voicemail("2", "Invoice looks fine, thanks"),
+        ])
+        assert [v.id for v in store.search("overdue invoice")] == ["1"]
+        assert len(store.search("invoice")) == 2
+
+    def test_search_includes_screened_purpose(self, tmp_path):
+        store = VoicemailStore(tmp_path)
+        store.merge_remote([_voicemail("1", None, purpose="dentist appointment")])
+        assert [v.id for v in store.search("dentist")] == ["1"]
+        assert "(transcription pending)" in format_voicemails(store.search("dentist"))
+
+    def test_newest_first_and_merge_counts(self, tmp_path):
+        store = VoicemailStore(tmp_path)
+        assert store.merge_remote([
+            _voicemail("old", "a", created_at="2026-01-01T09:00:00"),
+            _voicemail("new", "b", created_at="2026-01-02T09:00:00"),
+        ]) == 2
+        assert store.merge_remote([_voicemail("old", "a updated")]) == 0
+        assert [v.id for v in store.search()] == ["new", "old"]
+
+
+class TestCallScreeningManager:
+    def _manager(self, tmp_path):
+        manager = CallScreeningManager(store=VoicemailStore(tmp_path))
+        manager.client = FakeClient()
+        manager.active_calls = [
+            ScreenedCall(id="CA111", caller="+15550000001", status="waiting", purpose="Quick question"),
+            ScreenedCall(id="CA222", caller="+15550000002", status="screening"),
+        ]
+        return manager
+
+    def test_decline_with_message_targets_prefix(self, tmp_path):
+        manager = self._manager(tmp_path)
+        result = asyncio.run(manager.decide("decline", "CA22", "Please email instead."))
+
+        assert result.startswith("✓")
+        assert manager.client.decisions == [("CA222", "declined", "Please email instead.")]
+        assert [c.id for c in manager.active_calls] == ["CA111"]
+
+    def test_defaults_to_most_recent_call(self, tmp_path):
+        manager = self._manager(tmp_path)
+        asyncio.run(manager.decide("voicemail"))
+        assert manager.client.decisions[0][:2] == ("CA111", "voicemail")
+
+    def test_unknown_action_rejected(self, tmp_path):
+        manager = self._manager(tmp_path)
+        assert asyncio.run(manager.decide("hangup")).startswith("✗")
+        assert manager.client.decisions == []
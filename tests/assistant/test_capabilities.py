@@ -0,0 +1,93 @@
+"""
+Tests for the capabilities registry (assistant/capabilities.py).
+
+Covers:
+- Feature modules register themselves; every capability has examples and a known category
+- Required config: a capability is only offered once its fields are set
+- "what can you do?" lists what's set up; "how do I use timers?" gives that feature's examples
+- Topics that match nothing are left to the AI
+- Search for the Help tab: every word must match, best match first, set-up ones first
+- The Help tab has its own key
+"""
+
+import pytest
+
+from assistant.capabilities import (
+    CATEGORIES, answer_capability_question, capabilities, describe_capabilities, register_capability, search,
+)
+from assistant.config import Config
+from assistant.keymap import Keymap
+from assistant.layout import TABS
+
+
+def _names(found):
+    return [c.name for c in found]
+
+
+def test_feature_modules_register():
+    names = _names(capabilities())
+    for name in ("Timers", "Lists", "Alarms", "Quick maths", "Calendar", "Tutorial", "Emergency alert"):
+        assert name in names
+    assert all(c.examples and c.category in CATEGORIES for c in capabilities())
+
+
+def test_register_validates():
+    with pytest.raises(ValueError):
+        register_capability("Broken", "No examples", [])
+    with pytest.raises(ValueError):
+        register_capability("Broken", "Unknown category", ["do it"], category="Misc")
+
+
+def test_required_config():
+    emergency = next(c for c in capabilities() if c.name == "Emergency alert")
+    assert emergency.missing(Config()) == ["emergency_phrase", "emergency_contacts"]
+    assert emergency.available(Config(emergency_phrase="red alert", emergency_contacts=[{"name": "Sam"}]))
+    web = next(c for c in capabilities() if c.name == "Web search")
+    assert not web.available(Config(web_search="none")) and web.available(Config(web_search="searxng"))
+
+
+@pytest.mark.parametrize("text", ["what can you do?", "What else can you do", "help", "what can I say?",
+                                  "what are your features"])
+def test_what_can_you_do(text):
+    answer = answer_capability_question(text, Config())
+    assert answer == describe_capabilities(Config())
+    assert answer.startswith("I can help with ") and "more need setting up first" in answer
+    assert "emergency alert" not in answer.split(".")[0]
+
+
+def test_everything_set_up():
+    config = Config(emergency_phrase="red alert", emergency_contacts=[{"name": "Sam"}], web_search="brave",
+                    household_profiles={"Emma": {}}, has_phone_subscription=True,
+                    contacts={"Alice": {"email": "alice@example.com"}})
+    assert "need setting up" not in describe_capabilities(config)
+
+
+@pytest.mark.parametrize("text, start", [
+    ("how do I use timers?", "Timers: kitchen timers and a stopwatch. Try 'set a pasta timer for 9 minutes'"),
+    ("what can you do with lists", "Lists: shopping, todo"),
+    ("help me with the shopping list", "Lists:"),
+    ("How does the calendar work?", "Calendar:"),
+    ("help with the emergency alert", "Emergency alert needs setting up first: emergency phrase, emergency contacts"),
+])
+def test_about_one_feature(text, start):
+    assert answer_capability_question(text, Config()).startswith(start)
+
+
+@pytest.mark.parametrize("text", ["help me write an essay", "what time is it", "set a timer for 5 minutes",
+                                  "what can you tell me about volcanoes"])
+def test_not_a_capability_question(text):
+    assert answer_capability_question(text, Config()) is None
+
+
+def test_search():
+    assert _names(search("")) == _names(capabilities())
+    assert _names(search("shopping"))[0] == "Lists"
+    assert _names(search("timer"))[0] == "Timers"
+    assert search("timer zebra") == []
+    found = _names(search("phone", Config()))
+    assert found[-1] == "Call screening"  # Needs the phone add-on
+
+
+def test_help_tab_key():
+    assert TABS[-1][0] == "help"
+    assert Keymap().keys("goto_help") == ["9"]
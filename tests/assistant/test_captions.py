@@ -0,0 +1,78 @@
+"""
+Tests for live captions (assistant/captions.py, the partials from transcription.py).
+
+Covers:
+- Partials replace each other; the final transcript stays for the hold, then the line hides
+- The recognizer's reset after a final result doesn't wipe it; long utterances show their tail
+- The transcriber passes partials on only when they change, and clears a caption that was only noise
+"""
+
+import importlib
+import json
+import sys
+import types
+
+from assistant.captions import FINAL_HOLD, MAX_CHARS, Captions
+
+
+def test_partial_then_final():
+    captions = Captions()
+    assert captions.line() is None
+    captions.partial("remind me")
+    captions.partial("remind me to  call the")
+    assert captions.line() == ("remind me to call the", False)
+    captions.final("remind me to call the dentist", now=100.0)
+    captions.partial("")  # Vosk starts over
+    assert captions.line(now=100.0 + FINAL_HOLD - 0.1) == ("remind me to call the dentist", True)
+    assert captions.line(now=100.0 + FINAL_HOLD) is None
+    captions.partial("and")
+    assert captions.line(now=100.0 + FINAL_HOLD) == ("and", False)
+    captions.partial("")
+    assert captions.line() is None
+
+
+def test_long_utterance():
+    captions = Captions()
+    captions.partial(" ".join(f"word{n}" for n in range(40)))
+    text, _ = captions.line()
+    assert text.startswith("…word") and text.endswith("word39") and len(text) <= MAX_CHARS + 1
+
+
+class FakeRecognizer:
+    """Accepts a script of (is_final, text) per decode, then stops the loop."""
+
+    def __init__(self, transcriber, script):
+        self.transcriber, self.script, self.current = transcriber, list(script), None
+
+    def AcceptWaveform(self, data):
+        self.current = self.script.pop(0)
+        if self.script:
+            self.transcriber._audio_queue.put(b"\0\0")  # The next frame
+        else:
+            self.transcriber.is_active = False
+        return self.current[0]
+
+    def Result(self):
+        return json.dumps({"text": self.current[1]})
+
+    def PartialResult(self):
+        return json.dumps({"partial": self.current[1]})
+
+
+def test_transcriber_partials(monkeypatch, tmp_path):
+    vosk = types.SimpleNamespace(Model=lambda path: None, KaldiRecognizer=lambda *a: types.SimpleNamespace(
+        SetWords=lambda on: None))
+    monkeypatch.setitem(sys.modules, "vosk", vosk)
+    monkeypatch.delitem(sys.modules, "assistant.transcription", raising=False)
+    transcription = importlib.import_module("assistant.transcription")
+    heard = []
+    transcriber = transcription.UserTranscriber(tmp_path, on_text=lambda text, final: heard.append((text, final)))
+    transcriber.recognizer = FakeRecognizer(transcriber, [
+        (False, "what's"), (False, "what's"), (False, "what's the"), (True, "what's the time"),
+        (False, ""), (False, "uh"), (True, ""),
+    ])
+    transcriber.is_active = True
+    transcriber._audio_queue.put(b"\0\0")
+    transcriber._transcription_loop()
+    assert heard == [("what's", False), ("what's the", False), ("what's the time", True),
+                     ("uh", False), ("", False)]
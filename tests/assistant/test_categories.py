@@ -0,0 +1,114 @@
+"""
+Tests for item tags/categories and per-category notification preferences.
+
+Covers:
+- Explicit markers (#tag, "work:", "(health)", "personal reminder") and keyword inference
+- Planner filters by tag, including expanded recurring events
+- Category prefs: muted, no weekends, limited hours; emergency still bypasses
+"""
+
+from datetime import datetime
+
+from assistant.categories import extract_categories, normalize_tags, resolve_tags
+from assistant.config import Config
+from assistant.notifications import NotificationPolicy
+from assistant.planner import PlannerData
+
+WEDNESDAY_NOON = datetime(2026, 10, 14, 12, 0)
+SATURDAY_NOON = datetime(2026, 10, 17, 12, 0)
+
+
+class TestExtraction:
+    def test_hashtag_markers_are_removed(self):
+        assert extract_categories("#work prepare slides") == (["work"], "prepare slides")
+        assert extract_categories("call the bank #finance") == (["finance"], "call the bank")
+
+    def test_mid_sentence_hashtag_keeps_the_word(self):
+        tags, title = extract_categories("ask Sam about #taxes today")
+        assert tags == ["taxes"]
+        assert title == "ask Sam about taxes today"
+
+    def test_prefix_and_parenthesis(self):
+        assert extract_categories("Work: send the report") == (["work"], "send the report")
+        assert extract_categories("Refill prescription (health)") == (["health"], "Refill prescription")
+
+    def test_phrasing_is_kept_in_title(self):
+        tags, title = extract_categories("personal reminder to call Alex")
+        assert tags == ["personal"]
+        assert title == "personal reminder to call Alex"
+        assert extract_categories("finish slides for work")[0] == ["work"]
+
+    def test_keyword_inference_only_without_explicit_tags(self):
+        assert extract_categories("Dentist appointment")[0] == ["health"]
+        assert extract_categories("#family dentist for the kids")[0] == ["family"]
+        assert extract_categories("Think about things")[0] == []
+
+    def test_explicit_tags_win_over_title(self):
+        assert resolve_tags("Work, #Urgent", "Dentist") == (["work", "urgent"], "Dentist")
+        assert normalize_tags(["#Work", "work", " "]) == ["work"]
+
+
+class TestPlannerFilters:
+    def test_tasks_and_commitments(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        planner.add_task("Prepare slides", tags=["work"], auto_schedule=False)
+        planner.add_task("Buy milk", tags=["errands"], auto_schedule=False)
+        planner.add_commitment("Send invoice", "Dana", "2026-10-20", tags=["finance"])
+
+        assert [t.title for t in planner.get_tasks(tag="work")] == ["Prepare slides"]
+        assert len(planner.get_tasks()) == 2
+        assert [c.description for c in planner.get_commitments(tag="#finance")] == ["Send invoice"]
+        assert planner.get_commitments(tag="work") == []
+
+    def test_recurring_events_keep_tags(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        planner.add_calendar_event("Standup", "2026-10-14T09:00:00", "2026-10-14T09:15:00",
+                                   recurrence="daily", tags=["work"])
+        planner.add_calendar_event("Gym", "2026-10-14T18:00:00", "2026-10-14T19:00:00", tags=["health"])
+
+        work = planner.get_calendar_events("2026-10-14", "2026-10-16", tag="work")
+        assert len(work) == 3
+        assert all(e.tags == ["work"] for e in work)
+        assert [e.title for e in planner.get_calendar_events("2026-10-14", "2026-10-16", tag="health")] == ["Gym"]
+
+    def test_tags_can_be_added_to_older_events(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        event = planner.add_calendar_event("Lunch", "2026-10-14T12:00:00", "2026-10-14T13:00:00")
+        del planner._load()["calendar_events"][0]["tags"]  # Saved before tags existed
+        assert planner.update_calendar_event(event.id, tags=["personal"]).tags == ["personal"]
+
+
+def _policy(prefs, **overrides):
+    return NotificationPolicy(Config(category_notifications=prefs, **overrides))
+
+
+class TestCategoryPreferences:
+    def test_muted_category(self):
+        policy = _policy({"finance": {"muted": True}})
+        decision = policy.check("speech", "high", WEDNESDAY_NOON, tags=["finance"])
+        assert not decision.allowed
+        assert decision.resume_at is None
+        assert policy.allows("speech", "normal", WEDNESDAY_NOON, tags=["work"])
+        assert policy.allows("speech", "normal", WEDNESDAY_NOON)
+
+    def test_work_not_on_weekends(self):
+        policy = _policy({"work": {"weekends": False}})
+        assert policy.allows("desktop", "normal", WEDNESDAY_NOON, tags=["work"])
+        decision = policy.check("desktop", "normal", SATURDAY_NOON, tags=["work"])
+        assert not decision.allowed
+        assert decision.resume_at == datetime(2026, 10, 19, 0, 0)
+
+    def test_limited_hours(self):
+        policy = _policy({"work": {"hours": "08:00-18:00"}})
+        assert policy.allows("speech", "normal", WEDNESDAY_NOON, tags=["work"])
+        decision = policy.check("speech", "normal", datetime(2026, 10, 14, 19, 0), tags=["work"])
+        assert not decision.allowed
+        assert decision.resume_at == datetime(2026, 10, 15, 8, 0)
+
+    def test_emergency_bypasses_category_prefs(self):
+        policy = _policy({"health": {"muted": True}})
+        assert policy.allows("call", "emergency", WEDNESDAY_NOON, tags=["health"])
+
+    def test_quiet_hours_still_apply(self):
+        policy = _policy({}, quiet_hours_enabled=True)
+        assert not policy.allows("speech", "normal", datetime(2026, 10, 14, 23, 0), tags=["personal"])
@@ -0,0 +1,148 @@
+"""
+Tests for chaos mode (assistant/chaos.py) and the failover it exercises.
+
+Covers:
+- Specs from the command line or a scenario, and the mistakes they're refused for
+- Outages by path or provider; failure rates repeat with a seed; off changes nothing
+- AI: a failing rung hands over to the next, a slow one gets a filler, LatencyError when all are down
+- STT: a lost transcript clears the caption
+- Replay drills: a lost transcript, a failed-over answer, every rung down
+"""
+
+import asyncio
+import importlib
+import json
+import sys
+import types
+
+import pytest
+
+from assistant.chaos import Chaos, ChaosError, set_chaos
+from assistant.latency import BudgetedAI, LatencyBudget, LatencyError, LatencyMetrics, Rung
+from assistant.replay import ReplayRunner, Scenario, ScenarioError
+
+
+@pytest.fixture(autouse=True)
+def no_chaos():
+    set_chaos(Chaos())
+    yield
+    set_chaos(Chaos())
+
+
+def test_parse():
+    chaos = Chaos.parse("ai=0.3, tts.delay=1-2.5, delay=0.5, outage=anthropic+stt, seed=7")
+    assert chaos.rates == {"ai": 0.3}
+    assert chaos.delays == {"tts": (1.0, 2.5), "": (0.5, 0.5)}
+    assert chaos.outages == ("anthropic", "stt") and chaos.seed == 7
+    assert chaos.describe() == "ai fails 30%, tts delay 1-2.5s, delay 0.5s, outage anthropic, stt"
+    assert Chaos.parse({"outage": ["ollama"], "ai.delay": 2}).describe() == "ai delay 2s, outage ollama"
+    assert not Chaos.parse("").active and Chaos().describe() == "off"
+
+
+@pytest.mark.parametrize("spec, message", [
+    ("ai", "needs a value"),
+    ("ai=1.5", "0 to 1"),
+    ("delay=3-1", "LO-HI"),
+    ("llm=0.5", "Unknown chaos setting 'llm'"),
+    ("seed=soon", "whole number"),
+])
+def test_parse_errors(spec, message):
+    with pytest.raises(ValueError, match=message):
+        Chaos.parse(spec)
+
+
+def test_strikes():
+    chaos = Chaos.parse("outage=anthropic+tts")
+    with pytest.raises(ChaosError, match="simulated anthropic:claude outage"):
+        asyncio.run(chaos.disrupt("ai", "anthropic:claude"))
+    with pytest.raises(ChaosError):
+        chaos.disrupt_sync("tts", "moshi")
+    asyncio.run(chaos.disrupt("ai", "anthropic-proxy:claude"))  # Only the provider itself is down
+    asyncio.run(chaos.disrupt("ai", "ollama:llama3"))
+    assert chaos.summary() == "ai errors ×1, tts errors ×1"
+
+    def failures(seed):
+        chaos = Chaos.parse(f"stt=0.5,seed={seed}")
+        outcomes = []
+        for _ in range(20):
+            try:
+                chaos.disrupt_sync("stt")
+                outcomes.append(False)
+            except ChaosError:
+                outcomes.append(True)
+        return outcomes
+    assert failures(3) == failures(3) and 0 < sum(failures(3)) < 20
+
+
+def answer(text):
+    async def chat(messages, max_tokens):
+        return text
+    return chat
+
+
+def ask(ladder):
+    said = []
+
+    async def on_filler(text):
+        said.append(text)
+
+    async def go():
+        try:
+            return await BudgetedAI(ladder, LatencyBudget(soft=0.03, hard=0.5), on_filler, LatencyMetrics()).chat([])
+        except LatencyError as e:
+            return e
+    return asyncio.run(go()), said
+
+
+LADDER = [Rung("anthropic:claude-3-5-sonnet", answer("big")), Rung("anthropic:claude-3-5-haiku", answer("small")),
+          Rung("ollama:llama3", answer("local"), local=True)]
+
+
+def test_ai_failover():
+    set_chaos(Chaos.parse("outage=anthropic"))
+    assert ask(LADDER) == ("local", [])
+    set_chaos(Chaos.parse("ai.delay=0.06"))
+    reply, said = ask(LADDER)
+    assert reply == "big" and len(said) == 1
+    set_chaos(Chaos.parse("ai=1"))
+    error, _ = ask(LADDER)
+    assert isinstance(error, LatencyError) and "simulated ollama:llama3 outage" in str(error)
+
+
+def test_lost_transcript(monkeypatch, tmp_path):
+    vosk = types.SimpleNamespace(Model=lambda path: None, KaldiRecognizer=lambda *a: types.SimpleNamespace(
+        SetWords=lambda on: None))
+    monkeypatch.setitem(sys.modules, "vosk", vosk)
+    monkeypatch.delitem(sys.modules, "assistant.transcription", raising=False)
+    transcription = importlib.import_module("assistant.transcription")
+    heard = []
+    transcriber = transcription.UserTranscriber(tmp_path, on_text=lambda text, final: heard.append((text, final)))
+    transcriber.recognizer = types.SimpleNamespace(Result=lambda: json.dumps({"text": "what's the time"}))
+    transcriber._deliver("what's the time")
+    set_chaos(Chaos.parse("outage=stt"))
+    transcriber._deliver("what's the time")
+    assert heard == [("what's the time", True), ("", False)]
+
+
+def replay(chaos, **turn):
+    scenario = Scenario.from_dict({"chaos": chaos, "config": {"voice_latency_budget": 0.03, "ai_hard_limit": 0.5},
+                                   "turns": [{"say": "add milk", "ai": {"reply": "Added. [TOOL: add_task title=Milk]"},
+                                              **turn}]})
+    return asyncio.run(ReplayRunner(scenario).run())
+
+
+def test_replay_drills():
+    lost = replay("outage=stt", expect={"route": "stt_error"})
+    assert lost.passed and lost.tasks == [] and lost.turns[0].spoken == []
+    assert "     🐒 transcript lost: simulated vosk outage" in lost.lines()
+
+    local = replay("outage=anthropic", expect={"answered_by": "local", "fillers": 0, "spoken": ["Added."]})
+    assert local.passed, "\n".join(local.lines())
+    assert local.chaos == "ai errors ×2" and [t["title"] for t in local.tasks] == ["Milk"]
+
+    down = replay({"ai": 1}, expect={"route": "ai"})
+    assert not down.passed and down.turns[0].route == "ai_error" and down.tasks == []
+    assert down.turns[0].failures == ["expected route 'ai', got 'ai_error'"]
+
+    with pytest.raises(ScenarioError, match="0 to 1"):
+        Scenario.from_dict({"chaos": "ai=2", "turns": [{"say": "hi"}]})
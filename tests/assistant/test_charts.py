@@ -0,0 +1,59 @@
+"""
+Tests for text charts (assistant/charts.py).
+
+Covers:
+- Sparklines scaled to their own range or a fixed one
+- Fitting a sparkline to a width
+- Bar lengths relative to the largest value
+- Keeping only the latest samples, or older ones averaged to one a minute
+"""
+
+from assistant.charts import RECENT_SECONDS, History, bar_length, bar_rows, sparkline
+
+
+def test_sparkline():
+    assert sparkline([0, 1, 2, 3, 4, 5, 6, 7]) == "▁▂▃▄▅▆▇█"
+    assert sparkline([10, 20]) == "▁█"
+    assert sparkline([5, 5, 5]) == "▁▁▁"  # Flat: no range to scale to
+    assert sparkline([]) == ""
+
+
+def test_sparkline_fixed_scale():
+    assert sparkline([0, 50, 100], low=0, high=100) == "▁▅█"
+    assert sparkline([120, -5], low=0, high=100) == "█▁"  # Clamped
+
+
+def test_sparkline_width():
+    assert sparkline([1, 2, 3, 4], width=2) == "▁█"  # The newest two
+    assert sparkline([1, 2], width=5) == "   ▁█"
+    assert sparkline([], width=3) == "   "
+
+
+def test_bars():
+    assert bar_rows([("Mon", 2), ("Tue", 8), ("Wed", 0)], width=4) == [("Mon", 1, 2), ("Tue", 4, 8), ("Wed", 0, 0)]
+    assert bar_rows([("Mon", 0)], width=4) == [("Mon", 0, 0)]
+    assert bar_length(1, 100, 20) == 0 and bar_length(3, 2, 10) == 10
+
+
+def test_history():
+    history = History(size=3)
+    assert history.last is None and history.mean is None
+    for value in (1, 2, 3, 4):
+        history.add(value)
+    assert history.values == [2.0, 3.0, 4.0]
+    assert history.last == 4 and history.mean == 3 and len(history) == 3
+
+
+def test_history_minutes():
+    history = History(size=100, minutes=2)
+    start = 1_000_000 * 60
+    for second in (0, 20, 40, 60, 90):
+        history.add(second, at=start + second)
+    assert history.values == [0, 20, 40, 60, 90]  # All within the hour
+
+    history.add(7, at=start + RECENT_SECONDS + 120)
+    assert history.values == [20.0, 75.0, 7.0]  # The first minute's mean, the second's, then the new sample
+    assert len(history) == 3 and history.last == 7
+
+    history.add(8, at=start + RECENT_SECONDS * 3)
+    assert history.values == [75.0, 7.0, 8.0]  # Two minutes kept
@@ -0,0 +1,119 @@
+"""
+Tests for clock skew detection (assistant/clock_skew.py).
+
+Covers:
+- Offsets from server Date headers: median of readings, small ones treated as noise
+- Every API response's Date header is read
+- SNTP replies turned into an offset, preferred while fresh
+- The dashboard warning text
+"""
+
+import asyncio
+import struct
+from email.utils import formatdate
+
+from assistant import api_client
+from assistant.api_client import ApiClient, ApiPolicy
+from assistant.clock_skew import NTP_EPOCH_OFFSET, ClockSkew, get_clock_skew, sntp_offset
+
+NOW = 1_792_000_000.0  # Local time.time() in these tests
+
+
+def date_header(at):
+    return formatdate(at, usegmt=True)
+
+
+class Clock:
+    def __init__(self, now=NOW):
+        self.now = now
+
+    def __call__(self):
+        return self.now
+
+
+def test_offset_from_date_headers():
+    skew = ClockSkew(clock=Clock())
+    assert (skew.source, skew.offset) == (None, 0.0)
+
+    # Local clock 5 minutes behind; one reply delayed in transit
+    for lag in (0.1, 0.2, 0.1, 8.0, 0.2):
+        skew.observe_date(date_header(NOW + 300), NOW, NOW + lag)
+    assert skew.source == "server"
+    assert 299 <= skew.offset <= 301
+    assert skew.warning(60) == ("This computer's clock is 5 minutes behind (the server); "
+                                "reminders are adjusted, but turn on automatic time sync to fix it")
+
+    skew.observe_date("not a date", NOW, NOW)
+    skew.observe_date(None, NOW, NOW)
+    assert 299 <= skew.offset <= 301
+
+
+def test_small_offsets_are_noise():
+    skew = ClockSkew(clock=Clock())
+    skew.observe_date(date_header(NOW + 1), NOW, NOW + 0.2)
+    assert skew.offset == 0.0 and skew.warning(60) is None
+
+    skew = ClockSkew(clock=Clock())
+    skew.observe_date(date_header(NOW - 90), NOW, NOW + 0.2)
+    assert skew.offset < -88
+    assert "90 seconds ahead" in skew.warning(60)
+    assert skew.warning(120) is None
+
+
+def test_api_responses_feed_the_tracker():
+    class Response:
+        status_code = 200
+        headers = {"Date": "Wed, 14 Oct 2026 09:00:00 GMT"}
+
+    class Transport:
+        async def request(self, method, path, **kwargs):
+            return Response()
+
+    api_client._breakers.clear()
+    skew = get_clock_skew()
+    before = len(skew._samples)
+    asyncio.run(ApiClient("http://test", "token", policy=ApiPolicy(), client=Transport()).get("/api/inbox"))
+    assert len(skew._samples) == min(before + 1, skew._samples.maxlen)
+    skew._samples.clear()
+
+
+class FakeUdp:
+    """Answers an SNTP request like a server whose clock is `ahead` seconds ahead."""
+
+    def __init__(self, clock, ahead):
+        self.clock, self.ahead = clock, ahead
+        self.sent_to = None
+
+    def settimeout(self, timeout):
+        pass
+
+    def sendto(self, data, address):
+        assert data[0] == 0x23
+        self.sent_to = address
+        self.clock.now += 0.05  # Half the round trip
+
+    def recvfrom(self, size):
+        server = self.clock.now + self.ahead + NTP_EPOCH_OFFSET
+        stamp = struct.pack("!II", int(server), int((server % 1) * 2 ** 32))
+        self.clock.now += 0.05
+        return b"\x24" + 31 * b"\0" + stamp + stamp, ("ntp", 123)
+
+    def close(self):
+        pass
+
+
+def test_sntp_offset_preferred_while_fresh():
+    clock = Clock()
+    udp = FakeUdp(clock, ahead=-42.0)
+    offset = sntp_offset("pool.ntp.org", clock=clock, sock_factory=lambda: udp)
+    assert udp.sent_to == ("pool.ntp.org", 123)
+    assert abs(offset + 42.0) < 0.01
+
+    skew = ClockSkew(clock=clock)
+    skew.observe_date(date_header(NOW + 300), NOW, NOW)
+    skew.observe_ntp(offset)
+    assert skew.source == "ntp" and round(skew.offset) == -42
+    assert "(an NTP server)" in skew.warning(30)
+
+    clock.now += 7 * 60 * 60  # Stale: back to the server's Date headers
+    assert skew.source == "server" and skew.offset > 290
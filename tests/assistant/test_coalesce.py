@@ -0,0 +1,51 @@
+"""
+Tests for coalescing repeated events (assistant/coalesce.py).
+
+Covers:
+- Runs of the same key less than the window apart counted as one; the window slides
+- A key seen again after a quiet spell starts a new run, remembering the last one's repeats
+- Notifications: repeats held back, the next one after a quiet spell says how many; emergencies always go out
+"""
+
+import types
+
+import pytest
+
+from assistant import notifications
+from assistant.coalesce import Coalescer
+from assistant.notifications import Notification, coalesce_notification
+
+
+@pytest.fixture
+def clock():
+    return types.SimpleNamespace(now=0.0)
+
+
+def test_runs(clock):
+    repeats = Coalescer(window=60, clock=lambda: clock.now)
+    error = ("Voice bridge error: connection refused", "error")
+    assert repeats.note(error).new
+    for _ in range(100):  # Once a second for 100 seconds: still one run
+        clock.now += 1
+        repeat = repeats.note(error)
+    assert (repeat.count, repeat.first_seen, repeat.last_seen) == (101, 0, 100)
+    assert repeats.note(("Memory system initialized", "info")).new
+
+    clock.now += 60
+    repeat = repeats.note(error)
+    assert repeat.new and repeat.earlier == 100
+
+
+def test_notifications(clock, monkeypatch):
+    monkeypatch.setattr(notifications, "_repeats", Coalescer(clock=lambda: clock.now))
+    alert = Notification("desktop", "xSwarm", "Calendar sync failed")
+    assert coalesce_notification(alert) == alert
+    clock.now += 5
+    assert coalesce_notification(alert) is None and coalesce_notification(alert) is None
+    assert coalesce_notification(Notification("speech", "xSwarm", "Calendar sync failed")) is not None
+
+    clock.now += 120
+    assert coalesce_notification(alert).message == "Calendar sync failed (repeated 2 more times)"
+
+    fire = Notification("desktop", "Alarm", "Smoke detected", priority="emergency")
+    assert coalesce_notification(fire) == fire and coalesce_notification(fire) == fire
@@ -0,0 +1,142 @@
+"""
+Tests for code-aware chunking of the project index (assistant/code_chunks.py).
+
+Covers:
+- Python split by function/class (ast), with decorators and comments kept on their definition
+- Long classes/impls split into their methods, which keep the qualified symbol
+- The keyword heuristic for brace languages (Rust, JavaScript) without tree-sitter
+- Markdown split by heading, fenced code left alone
+- Symbols filterable and shown in the project search results
+"""
+
+import asyncio
+
+from assistant import code_chunks
+from assistant.code_chunks import chunk_file
+from assistant.planner import PlannerData
+from assistant.project_docs import ProjectDocs
+
+
+class FakeIndex:
+    """Meilisearch stand-in: every document matching the filters, most query words first."""
+
+    def __init__(self):
+        self.documents = {}
+
+    async def add(self, documents):
+        self.documents.update({d["id"]: d for d in documents})
+
+    async def delete(self, ids):
+        for i in ids:
+            self.documents.pop(i, None)
+
+    async def search(self, query, project_id, limit=6, filters=None):
+        wanted = {"project_id": project_id, **(filters or {})}
+        matches = [d for d in self.documents.values() if all(d[k] == v for k, v in wanted.items())]
+        return sorted(matches, key=lambda d: -sum(w in d["text"].lower() for w in query.split()))[:limit]
+
+
+def outline(chunks):
+    return [(c.line, c.end_line, c.kind, c.symbol) for c in chunks]
+
+
+def no_tree_sitter(monkeypatch):
+    monkeypatch.setattr(code_chunks, "_tree_sitter_parser", lambda language: None)
+
+
+def test_python(monkeypatch):
+    no_tree_sitter(monkeypatch)
+    body = "\n".join(f"        step_{i}()" for i in range(85))
+    source = (
+        "import httpx\n"
+        "\n"
+        "# Retries with backoff\n"
+        "@dataclass\n"
+        "class RetryPolicy:\n"
+        "    attempts: int = 3\n"
+        "\n"
+        "    def backoff(self, n):\n"
+        f"{body}\n"
+        "\n"
+        "    async def wait(self):\n"
+        "        pass\n"
+        "\n"
+        "def retry(fn):\n"
+        "    return fn()\n"
+    )
+    assert outline(chunk_file("src/retry.py", source)) == [
+        (1, 1, "", ""),
+        (3, 6, "class", "RetryPolicy"),  # The class's own lines, comment and decorator included
+        (8, 47, "method", "RetryPolicy.backoff"),  # Too long for one chunk: windows that keep the symbol
+        (48, 87, "method", "RetryPolicy.backoff"),
+        (88, 93, "method", "RetryPolicy.backoff"),
+        (95, 96, "method", "RetryPolicy.wait"),
+        (98, 99, "function", "retry"),
+    ]
+    assert chunk_file("src/retry.py", source)[-1].text == "def retry(fn):\n    return fn()"
+
+
+def test_brace_languages_without_tree_sitter(monkeypatch):
+    no_tree_sitter(monkeypatch)
+    method = "\n".join(["    pub fn backoff(&self, n: u32) -> Duration {"] + ["        tick();"] * 45 + ["    }"])
+    source = (
+        "use std::time::Duration;\n"
+        "\n"
+        "/// Retry settings\n"
+        "#[derive(Debug)]\n"
+        "pub struct RetryPolicy {\n"
+        "    pub attempts: u32,\n"
+        "}\n"
+        "\n"
+        "pub struct Unit;\n"
+        "\n"
+        "impl RetryPolicy {\n"
+        f"{method}\n"
+        "\n"
+        f"{method.replace('backoff', 'jitter')}\n"
+        "}\n"
+    )
+    chunks = chunk_file("src/retry.rs", source)
+    assert outline(chunks) == [
+        (1, 1, "", ""),
+        (3, 7, "struct", "RetryPolicy"),
+        (9, 9, "struct", "Unit"),
+        (11, 11, "impl", "RetryPolicy"),
+        (12, 58, "method", "RetryPolicy.backoff"),
+        (60, 106, "method", "RetryPolicy.jitter"),
+        (107, 107, "impl", "RetryPolicy"),
+    ]
+    assert {c.language for c in chunks} == {"rust"}
+
+    source = ("import x from 'y';\n\nexport class Client {\n  async send(req) {\n    if (req) {\n      return 1;\n"
+              "    }\n  }\n}\n\nexport const retry = async (fn) => {\n  return fn();\n};\n")
+    assert outline(chunk_file("src/client.js", source)) == [
+        (1, 1, "", ""), (3, 9, "class", "Client"), (11, 13, "function", "retry")]
+
+
+def test_markdown():
+    text = "Intro\n\n# Setup\n\n## Install\n\n```sh\n# not a heading\n```\n\n# Usage\nrun it\n"
+    assert outline(chunk_file("README.md", text)) == [
+        (1, 1, "", ""), (3, 3, "section", "Setup"), (5, 9, "section", "Setup > Install"), (11, 12, "section", "Usage")]
+
+
+def test_symbols_in_search(tmp_path, monkeypatch):
+    no_tree_sitter(monkeypatch)
+    repo = tmp_path / "api"
+    repo.mkdir()
+    (repo / "retry.rs").write_text("pub fn backoff(n: u32) -> u64 {\n    // retries double each time\n    1 << n\n}\n")
+    (repo / "retry.md").write_text("# Retries\n\nThe client retries three times.\n")
+    project = PlannerData(tmp_path / "planner").add_project("API", folders=[str(repo)])
+    docs = ProjectDocs(FakeIndex(), state_path=tmp_path / "index.json")
+    asyncio.run(docs.index_project(project))
+    assert {(d["symbol"], d["kind"], d["language"]) for d in docs.index.documents.values()} == {
+        ("backoff", "function", "rust"), ("Retries", "section", "markdown")}
+
+    answer = asyncio.run(docs.ask(project, "how do retries work?", language="Rust"))
+    assert answer.render() == ("The closest matches in API:\n\nSources:\n"
+                               "  [1] retry.rs:1-4 (function backoff)\n"
+                               "      // retries double each time")
+
+    # Files chunked by an older version are redone even though they haven't changed
+    docs._load()[project.id]["retry.rs"]["chunker"] = 1
+    assert asyncio.run(docs.index_project(project)).files == 1
@@ -0,0 +1,91 @@
+"""
+Tests for model placement across compute backends (assistant/compute.py).
+
+Covers:
+- auto picks the first available backend a model supports; config aliases and fallbacks
+- Moving a live model, refusing models that only move on restart
+- Yielding the GPU to the user's work and taking it back once calm
+- The report lists backends, VRAM and placements
+"""
+
+import pytest
+
+from assistant.compute import CALM_CHECKS, CPU, CUDA, METAL, ComputeError, ComputeManager
+from assistant.hardware import GPUCapability
+
+
+def gpu(free_gb=20.0, busy=10.0):
+    return GPUCapability(device_name="RTX", vram_total_gb=24.0, vram_used_gb=24.0 - free_gb, vram_free_gb=free_gb,
+                         compute_score=18.75, temp_c=None, grade="C-", util_percent=busy, device_type="nvidia")
+
+
+class Model:
+    def __init__(self):
+        self.moves = []
+
+    def __call__(self, backend):
+        self.moves.append(backend)
+
+
+def manager(state=None, **kwargs):
+    state = state if state is not None else {"gpu": gpu()}
+    return ComputeManager(available=[CUDA, CPU], probe=lambda: state["gpu"], **kwargs)
+
+
+def test_placement():
+    compute = manager(default="auto", placement={"voice": "cpu", "vad": "mps", "stt": "tpu"})
+    assert compute.register("vad", (METAL, CUDA, CPU)) == CPU  # Metal asked for, not available here
+    assert compute.register("voice", (METAL, CUDA, CPU)) == CPU
+    assert compute.register("stt", (CUDA, CPU)) == CUDA  # Unknown backend in the config: auto
+    assert compute.register("other", (METAL, CUDA, CPU)) == CUDA
+    with pytest.raises(ComputeError):
+        compute.register("mlx", (METAL,))
+
+
+def test_switch():
+    compute = manager()
+    vad = Model()
+    compute.register("vad", (METAL, CUDA, CPU), move=vad)
+    compute.register("voice", (CUDA, CPU))
+    assert compute.switch("vad", "cpu").backend == CPU
+    assert vad.moves == [CUDA, CPU]
+    with pytest.raises(ComputeError, match="restart"):
+        compute.switch("voice", "cpu")
+    with pytest.raises(ComputeError):
+        compute.switch("vad", "metal")
+    with pytest.raises(ComputeError):
+        compute.switch("nope", "cpu")
+
+
+def test_yields_gpu_to_the_user_and_takes_it_back():
+    state = {"gpu": gpu()}
+    compute = manager(state, yield_models=("vad", "stt"), gpu_reserve_gb=2.0, gpu_busy_percent=90)
+    vad, stt = Model(), Model()
+    compute.register("vad", (CUDA, CPU), move=vad)
+    compute.register("stt", (CPU,), move=stt)
+    compute.register("voice", (CUDA, CPU))
+    assert compute.check() == []
+
+    state["gpu"] = gpu(free_gb=1.0)  # The user's training run took the VRAM
+    moved = compute.check()
+    assert len(moved) == 1 and moved[0].startswith("vad → cpu")
+    assert compute.models["vad"].yielded and compute.models["voice"].backend == CUDA
+    state["gpu"] = gpu(busy=95)
+    assert compute.check() == []
+
+    state["gpu"] = gpu()
+    for _ in range(CALM_CHECKS - 1):
+        assert compute.check() == []
+    assert compute.check() == ["vad → cuda (GPU free again)"]
+    assert vad.moves == [CUDA, CPU, CUDA] and not compute.models["vad"].yielded
+    assert stt.moves == [CPU]
+
+
+def test_report():
+    compute = manager()
+    compute.register("stt", (CPU,))
+    report = compute.report()
+    assert report["backends"] == [CUDA, CPU]
+    assert report["gpu"]["free_gb"] == 20.0 and report["gpu"]["busy_percent"] == 10.0
+    assert report["models"] == [{"name": "stt", "backend": CPU, "supported": (CPU,), "live": False, "yielded": False}]
+    assert manager({"gpu": None}).report()["gpu"] is None
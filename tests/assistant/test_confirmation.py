@@ -0,0 +1,176 @@
+"""
+Tests for the voice confirmation policy.
+
+Covers:
+- Tools map to action classes; per-class and per-tool overrides from config
+- A burst of deletes escalates to bulk_delete until the user confirms once
+- Held actions resolve on yes/no/PIN; unrelated replies drop them
+- PIN-level actions fall back to an explicit yes without a PIN
+- ToolRegistry holds calls that need confirmation and runs them once confirmed
+"""
+
+import asyncio
+
+from assistant.config import Config
+from assistant.confirmation import (
+    ConfirmationLevel,
+    ConfirmationPolicy,
+    PENDING_TIMEOUT,
+    classify_action,
+    hash_pin,
+    set_confirmation_policy,
+    spoken_pin,
+)
+from assistant.tools import ToolRegistry
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+def _policy(**config):
+    clock = FakeClock()
+    return ConfirmationPolicy(Config(**config), clock=clock), clock
+
+
+class TestClassification:
+    def test_action_classes(self):
+        assert classify_action("delete_task") == "delete"
+        assert classify_action("complete_commitment") == "complete"
+        assert classify_action("add_calendar_event") == "create"
+        assert classify_action("update_task") == "modify"
+        assert classify_action("send_email") == "communicate"
+        assert classify_action("run_command") == "system"
+        assert classify_action("forget_facts") == "memory_purge"
+        assert classify_action("set_confirmation_pin") == "settings"
+        assert classify_action("list_tasks") == "read"
+
+    def test_default_levels(self):
+        policy, _ = _policy()
+        assert policy.required_level("list_tasks") == ConfirmationLevel.SILENT
+        assert policy.required_level("delete_task") == ConfirmationLevel.VERBAL
+        assert policy.required_level("forget_facts") == ConfirmationLevel.EXPLICIT_YES
+
+    def test_overrides_by_class_and_tool(self):
+        policy, _ = _policy(confirmation_levels={"delete": "explicit_yes", "run_command": "silent", "create": "bogus"})
+        assert policy.required_level("delete_habit") == ConfirmationLevel.EXPLICIT_YES
+        assert policy.required_level("run_command") == ConfirmationLevel.SILENT
+        assert policy.required_level("add_task") == ConfirmationLevel.SILENT
+
+
+class TestBulkDelete:
+    def test_burst_escalates_until_confirmed(self):
+        policy, clock = _policy()
+        for _ in range(2):
+            assert policy.required_level("delete_task") == ConfirmationLevel.VERBAL
+            policy.record_executed("delete_task", ConfirmationLevel.VERBAL, "✓ Deleted")
+        level = policy.required_level("delete_task")
+        assert level == ConfirmationLevel.EXPLICIT_YES
+
+        policy.hold("delete_task", {"task_id": "tsk-3"}, level)
+        assert policy.resolve("yes, delete them").status == "confirmed"
+        assert policy.required_level("delete_task") == ConfirmationLevel.VERBAL
+
+    def test_burst_window_passes(self):
+        policy, clock = _policy()
+        for _ in range(2):
+            policy.record_executed("delete_task", ConfirmationLevel.VERBAL, "✓ Deleted")
+        clock.now += 61
+        assert policy.required_level("delete_task") == ConfirmationLevel.VERBAL
+
+
+class TestResolution:
+    def test_yes_and_no(self):
+        policy, _ = _policy()
+        policy.hold("forget_facts", {"match": "my address"}, ConfirmationLevel.EXPLICIT_YES)
+        assert policy.resolve("no").status == "declined"
+        assert not policy.has_pending()
+
+        policy.hold("forget_facts", {"match": "my address"}, ConfirmationLevel.EXPLICIT_YES)
+        resolution = policy.resolve("Yes please")
+        assert resolution.status == "confirmed"
+        assert resolution.action.args == {"match": "my address"}
+
+    def test_unrelated_reply_drops_pending(self):
+        policy, _ = _policy()
+        policy.hold("forget_facts", {"match": "x"}, ConfirmationLevel.EXPLICIT_YES)
+        assert policy.resolve("what's on my calendar today?") is None
+        assert not policy.has_pending()
+
+    def test_pending_expires(self):
+        policy, clock = _policy()
+        policy.hold("forget_facts", {"match": "x"}, ConfirmationLevel.EXPLICIT_YES)
+        clock.now += PENDING_TIMEOUT + 1
+        assert policy.resolve("yes").status == "expired"
+
+    def test_spoken_pin(self):
+        policy, _ = _policy(confirmation_levels={"system": "pin"}, confirmation_pin_hash=hash_pin("4211"))
+        assert spoken_pin("four two one one") == "4211"
+        assert policy.required_level("run_command") == ConfirmationLevel.PIN
+
+        policy.hold("run_command", {"command": "ls"}, ConfirmationLevel.PIN)
+        assert policy.resolve("yes") is None  # A plain yes is not enough
+        assert not policy.has_pending()
+        policy.hold("run_command", {"command": "ls"}, ConfirmationLevel.PIN)
+        assert policy.resolve("1234").status == "retry"
+        assert policy.resolve("four two one one").status == "confirmed"
+
+    def test_wrong_pin_too_often(self):
+        policy, _ = _policy(confirmation_pin_hash=hash_pin("4211"))
+        policy.hold("run_command", {"command": "ls"}, ConfirmationLevel.PIN)
+        for _ in range(2):
+            assert policy.resolve("0000").status == "retry"
+        assert policy.resolve("0000").status == "declined"
+        assert not policy.has_pending()
+
+    def test_pin_falls_back_to_yes_without_pin(self):
+        policy, _ = _policy(confirmation_levels={"system": "pin"})
+        assert policy.required_level("run_command") == ConfirmationLevel.EXPLICIT_YES
+
+    def test_settings_need_pin_once_set(self):
+        policy, _ = _policy(confirmation_pin_hash=hash_pin("4211"))
+        assert policy.required_level("set_confirmation_level") == ConfirmationLevel.PIN
+
+
+class TestRegistryGate:
+    def test_held_then_confirmed(self):
+        calls = []
+        local = ToolRegistry()
+
+        @local.register("forget_facts", "Forget facts")
+        def forget_facts(match: str) -> str:
+            calls.append(match)
+            return f"✓ Forgot '{match}'"
+
+        policy, _ = _policy()
+        set_confirmation_policy(policy)
+        try:
+            held = asyncio.run(local.execute_tool("forget_facts", {"match": "my address"}))
+            assert held["pending_confirmation"]
+            assert calls == []
+
+            action = policy.resolve("yes").action
+            assert action.registry is local
+            done = asyncio.run(action.registry.execute_tool(action.tool_name, action.args, confirmed=True))
+            assert done["result"] == "✓ Forgot 'my address'"
+            assert calls == ["my address"]
+        finally:
+            set_confirmation_policy(ConfirmationPolicy())
+
+    def test_verbal_actions_are_read_back(self):
+        local = ToolRegistry()
+        local.register("delete_note", "Delete a note")(lambda note_id: f"✓ Deleted {note_id}")
+        policy, _ = _policy()
+        spoken = []
+        policy.on_verbal = spoken.append
+        set_confirmation_policy(policy)
+        try:
+            result = asyncio.run(local.execute_tool("delete_note", {"note_id": "n-1"}))
+            assert result["result"] == "✓ Deleted n-1"
+            assert spoken == ["✓ Deleted n-1"]
+        finally:
+            set_confirmation_policy(ConfirmationPolicy())
@@ -0,0 +1,142 @@
+"""
+Tests for controlling the assistant from outside the TUI (assistant/control.py, assistant/tray.py).
+
+Covers:
+- Commands and replies over the local socket, and bad requests
+- Clients finding the assistant isn't running
+- The next appointment shown in the tray
+- Do not disturb holding back notifications until turned off
+- Tray actions and labels
+"""
+
+import asyncio
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.config import Config
+from assistant.control import ControlError, ControlServer, next_appointment, send_command
+from assistant.notifications import NotificationPolicy
+from assistant.planner import PlannerData
+from assistant.tray import TrayCompanion
+
+
+@pytest.mark.asyncio
+async def test_round_trip(tmp_path):
+    muted = []
+
+    async def show(_request):
+        return {"message": "✓ Dashboard is open"}
+
+    server = ControlServer({
+        "mute": lambda request: muted.append(request.get("on")) or {"message": "✓ Mic muted", "muted": True},
+        "show": show,
+        "fail": lambda _request: 1 / 0,
+    }, path=tmp_path / "control.sock")
+    assert await server.start()
+    try:
+        path = server.path
+        reply = await asyncio.to_thread(send_command, "mute", path, on=True)
+        assert reply == {"ok": True, "message": "✓ Mic muted", "muted": True}
+        assert muted == [True]
+        assert (await asyncio.to_thread(send_command, "show", path))["message"] == "✓ Dashboard is open"
+        assert await asyncio.to_thread(send_command, "nope", path) == {"ok": False, "error": "Unknown command 'nope'"}
+        assert (await asyncio.to_thread(send_command, "fail", path))["ok"] is False
+    finally:
+        server.close()
+    assert not (tmp_path / "control.sock").exists()
+
+
+@pytest.mark.asyncio
+async def test_bad_request(tmp_path):
+    server = ControlServer({}, path=tmp_path / "control.sock")
+    assert await server.start()
+    try:
+        reader, writer = await asyncio.open_unix_connection(str(server.path))
+        writer.write(b"not json\n")
+        await writer.drain()
+        assert b'"ok": false' in await reader.readline()
+        writer.close()
+        await writer.wait_closed()
+    finally:
+        server.close()
+
+
+def test_not_running(tmp_path):
+    with pytest.raises(ControlError):
+        send_command("status", tmp_path / "control.sock")
+
+
+def test_next_appointment(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    now = datetime.now()
+    assert next_appointment(planner, now) == "Nothing in the next 7 days"
+
+    def add(title, start):
+        planner.add_calendar_event(title, start.isoformat(timespec="minutes"),
+                                   (start + timedelta(hours=1)).isoformat(timespec="minutes"))
+
+    add("Standup", now - timedelta(hours=1))
+    add("Dentist", now + timedelta(days=2))
+    assert next_appointment(planner, now).startswith("Dentist · ")
+    add("Lunch", now + timedelta(days=1))
+    assert next_appointment(planner, now).startswith("Lunch · tomorrow ")
+
+
+def test_do_not_disturb():
+    policy = NotificationPolicy(Config(do_not_disturb=True, quiet_hours_channels={"desktop": "high"}))
+    decision = policy.check("speech")
+    assert not decision.allowed and decision.reason == "do not disturb" and decision.resume_at is None
+    assert policy.allows("desktop", "high")
+    assert policy.allows("speech", "emergency")
+    assert NotificationPolicy(Config()).allows("speech")
+
+
+class FakeAssistant:
+    def __init__(self, running=True):
+        self.running = running
+        self.sent = []
+        self.state = {"mic": "streaming", "muted": False, "dnd": False, "voice": True,
+                      "next": "Dentist · today 16:00", "message": "xSwarm: listening"}
+
+    def __call__(self, command, **args):
+        if not self.running:
+            raise ControlError("xSwarm isn't running")
+        self.sent.append(command)
+        if command == "mute":
+            self.state["muted"] = not self.state["muted"]
+            self.state["mic"] = "off" if self.state["muted"] else "streaming"
+            return {"ok": True, "message": "✓ Mic muted" if self.state["muted"] else "✓ Mic on"}
+        if command == "quit":
+            self.running = False
+            return {"ok": True, "message": "✓ Quitting xSwarm"}
+        return {"ok": True, **self.state}
+
+
+class TestTray:
+    def test_labels(self):
+        tray = TrayCompanion(send=FakeAssistant())
+        tray.refresh()
+        assert tray.title() == "xSwarm: listening"
+        assert tray.next_label() == "Next: Dentist · today 16:00"
+
+    def test_mute(self):
+        tray = TrayCompanion(send=FakeAssistant())
+        assert tray.toggle_mute() == "✓ Mic muted"
+        assert tray.status["muted"] and tray.mic_state.value == "off"
+
+    def test_open_dashboard(self):
+        launched = []
+        assistant = FakeAssistant(running=False)
+        tray = TrayCompanion(send=assistant, launch=lambda: launched.append(1) or "✓ Starting xSwarm")
+        assert tray.open_dashboard() == "✓ Starting xSwarm" and launched == [1]
+        assistant.running = True
+        tray.open_dashboard()
+        assert assistant.sent[-1] == "show" and launched == [1]  # Already open: not started twice
+
+    def test_quit(self):
+        tray = TrayCompanion(send=FakeAssistant())
+        tray.refresh()
+        assert tray.quit_assistant() == "✓ Quitting xSwarm"
+        assert not tray.running and tray.title() == "xSwarm: not running"
+        assert tray.toggle_dnd() == "✗ xSwarm isn't running"
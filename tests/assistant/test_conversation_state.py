@@ -0,0 +1,122 @@
+"""
+Tests for the conversation state machine (assistant/conversation_state.py).
+
+Covers:
+- A turn: listening -> thinking -> speaking -> listening, with silence not counting as speech
+- Barge-in: talking over a reply interrupts it, finishing the sentence moves on to thinking
+- Timeouts: no reply, an interruption that trails off, errors recovering
+- Events with no transition are ignored; stop works from anywhere
+- Transitions are reported to the task supervisor
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.conversation_state import ConversationMachine, ConversationState, Event
+from assistant.supervisor import TaskSupervisor
+
+S = ConversationState
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self):
+        return self.now
+
+
+def machine(**kwargs):
+    clock = FakeClock()
+    supervisor = TaskSupervisor(clock=clock)
+    seen = []
+    m = ConversationMachine(lambda t: seen.append((t.old, t.new)), supervisor=supervisor, clock=clock,
+                            thinking_timeout=8.0, interrupt_timeout=3.0, speaking_tail=0.6, **kwargs)
+    m.fire(Event.START)
+    return m, clock, supervisor, seen
+
+
+def test_turn():
+    m, clock, _, seen = machine()
+    m.observe_user(True)
+    m.observe_user(False)
+    assert m.state == S.THINKING
+    m.reply_audio(loud=False)  # Moshi's stream between words is silence
+    assert m.state == S.THINKING
+    m.reply_audio(loud=True)
+    clock.now = 0.5
+    m.reply_audio(loud=True)
+    clock.now = 1.0
+    assert not m.tick() and m.state == S.SPEAKING
+    clock.now = 1.2
+    assert m.tick() and m.state == S.LISTENING
+    assert seen == [(S.IDLE, S.LISTENING), (S.LISTENING, S.THINKING), (S.THINKING, S.SPEAKING),
+                    (S.SPEAKING, S.LISTENING)]
+
+
+def test_barge_in():
+    m, clock, _, _ = machine()
+    m.reply_audio(loud=True)
+    assert m.state == S.SPEAKING
+    m.observe_user(True)
+    assert m.state == S.INTERRUPTED
+    m.reply_audio(loud=True)  # The rest of the reply doesn't take the turn back
+    assert m.state == S.INTERRUPTED
+    m.observe_user(False)
+    assert m.state == S.THINKING
+
+
+def test_barge_in_off():
+    m, _, _, _ = machine(barge_in=False)
+    m.reply_audio(loud=True)
+    m.observe_user(True)
+    assert m.state == S.SPEAKING
+    m.observe_user(False)
+    assert m.state == S.SPEAKING and m.ignored == 1
+
+
+@pytest.mark.parametrize("setup, wait, reason", [
+    (lambda m: m.fire(Event.USER_DONE), 8.0, "no reply"),
+    (lambda m: (m.reply_audio(True), m.observe_user(True)), 3.0, "user went quiet"),
+    (lambda m: m.fire(Event.ERROR, "socket closed"), 2.0, "recover"),
+])
+def test_timeouts_return_to_listening(setup, wait, reason):
+    m, clock, supervisor, _ = machine()
+    setup(m)
+    clock.now += wait - 0.1
+    assert not m.tick()
+    clock.now += 0.1
+    assert m.tick() and m.state == S.LISTENING
+    assert supervisor.states["conversation"].reason == reason
+
+
+def test_invalid_events_ignored_and_stop_from_anywhere():
+    m, _, supervisor, _ = machine()
+    assert not m.fire(Event.REPLY_DONE)
+    assert not m.fire(Event.START)
+    assert m.state == S.LISTENING and m.ignored == 2
+    m.fire(Event.USER_DONE)
+    m.fire(Event.ERROR, "boom")
+    assert not m.fire(Event.ERROR)
+    assert m.fire(Event.STOP) and m.state == S.IDLE
+    assert not m.fire(Event.STOP)
+    assert supervisor.states["conversation"].state == "idle"
+    assert [t.event for t in m.history] == [Event.START, Event.USER_DONE, Event.ERROR, Event.STOP]
+
+
+def test_run_feeds_vad_and_timeouts():
+    m, _, supervisor, _ = machine()
+    m.reply_audio(loud=True)
+    m.speaking_tail = 0.0
+    speaking = iter([True, True, False])
+
+    async def go():
+        task = asyncio.ensure_future(m.run(lambda: next(speaking, False), interval=0))
+        for _ in range(10):
+            await asyncio.sleep(0)
+        task.cancel()
+
+    asyncio.run(go())
+    assert [(t.old, t.new) for t in m.history][-2:] == [(S.SPEAKING, S.INTERRUPTED), (S.INTERRUPTED, S.THINKING)]
+    assert supervisor.states["conversation"].state == "thinking"
@@ -0,0 +1,75 @@
+"""
+Tests for replying without the wake word (assistant/conversation_window.py).
+
+Covers:
+- Telling "thanks, that's all" from a real follow-up
+- The window opening after an answer, and running out in silence
+- Acting on wake-word and in-window utterances, ignoring the rest
+"""
+
+import pytest
+
+from assistant.config import Config
+from assistant.conversation_window import FollowUpWindow, is_dismissal
+
+WAKE = ["jarvis"]
+
+
+@pytest.mark.parametrize("text, dismissal", [
+    ("thanks, that's all", True),
+    ("Thank you Jarvis", True),
+    ("no, that's all for now", True),
+    ("I'm done", True),
+    ("bye", True),
+    ("thanks, and add milk to the list", False),
+    ("that's it for the dentist", False),
+    ("no", False),
+    ("move it to 5", False),
+])
+def test_is_dismissal(text, dismissal):
+    assert is_dismissal(text) == dismissal
+
+
+class Clock:
+    def __init__(self):
+        self.now = 100.0
+
+    def __call__(self):
+        return self.now
+
+
+@pytest.fixture
+def window():
+    return FollowUpWindow.from_config(Config(follow_up_window=8), clock=Clock())
+
+
+class TestWindow:
+    def test_runs_out_in_silence(self, window):
+        assert not window.is_open
+        window.open()
+        window.clock.now += 5
+        assert window.remaining() == pytest.approx(3)
+        window.clock.now += 3
+        assert not window.is_open and window.remaining() == 0
+
+    def test_off(self):
+        window = FollowUpWindow.from_config(Config(follow_up_window=0))
+        window.open()
+        assert not window.is_open
+
+    def test_admit(self, window):
+        assert window.admit("what's the weather", WAKE) is None
+        assert window.admit("Jarvis, what's the weather?", WAKE) == "what's the weather?"
+        window.open()
+        assert window.admit("and tomorrow", WAKE) == "and tomorrow"
+        assert window.admit("and the day after", WAKE) is None  # Closed until the next answer
+
+    def test_dismissal_closes_it(self, window):
+        window.open()
+        assert window.admit("thanks, that's all", WAKE) is None
+        assert window.admit("what's the weather", WAKE) is None
+
+    def test_wake_word_alone_opens_it(self, window):
+        assert window.admit("Jarvis", WAKE) is None
+        window.clock.now += 2
+        assert window.admit("what's on tomorrow", WAKE) == "what's on tomorrow"
@@ -0,0 +1,90 @@
+"""
+Tests for interaction threads (assistant/correlation.py) and the events that carry them.
+
+Covers:
+- An interaction's ID reaching the tasks it starts, and not outliving a block or a callback
+- Late callbacks resuming the latest interaction within the window
+- Events, AI log entries and the inbox sync recorded with their thread; querying one thread
+- Grouping feed entries into open and collapsed threads
+"""
+
+import asyncio
+from contextvars import copy_context
+
+import pytest
+
+from assistant import correlation
+from assistant.ai_log import AILog, format_call
+from assistant.correlation import THREAD_WINDOW, begin, current_id, handle, interaction, resume, thread_rows
+from assistant.events import EventStore, record_event, set_event_store
+from assistant.inbox import InboxManager, InboxStore
+
+
+@pytest.fixture
+def store():
+    store = EventStore(":memory:", keep_days=None)
+    set_event_store(store)
+    yield store
+    set_event_store(None)
+
+
+def test_tasks_carry_the_id():
+    async def turn():
+        thread = begin("chat")
+        seen = await asyncio.gather(asyncio.create_task(asyncio.sleep(0, current_id())),
+                                    asyncio.to_thread(current_id))
+        return thread, seen
+    thread, seen = asyncio.run(turn())
+    assert seen == [thread, thread] and current_id() is None
+
+    with interaction("inbox") as inbox:
+        assert current_id() == inbox != thread
+    assert current_id() is None
+    assert handle("voice", current_id) not in (None, inbox) and current_id() is None
+
+
+def test_resume():
+    started = handle("voice", lambda: (current_id(), correlation._latest.started))
+    assert copy_context().run(resume, started[1] + THREAD_WINDOW) == started[0]
+    assert copy_context().run(resume, started[1] + THREAD_WINDOW + 1) is None
+    assert handle(None, current_id) == started[0]  # The voice bridge answering
+    assert handle("chat", lambda: resume() == current_id() != started[0])  # A current interaction wins
+
+
+def test_events_join_the_thread(store):
+    record_event("activity", "Synced")
+    thread = handle("voice", lambda: (record_event("turn", "voice"), record_event("tool", "add_task"), current_id()))[2]
+    assert [(e.type, e.thread) for e in store.query()] == [("activity", None), ("turn", thread), ("tool", thread)]
+    assert [e.summary for e in store.query(thread=thread)] == ["voice", "add_task"]
+    assert f"  {thread}  tool" in store.query(["tool"])[0].line()
+
+    log = AILog(":memory:", "metadata")
+    call = handle(None, log.record, "anthropic", "claude", "chat", {"messages": []})
+    assert call.data["thread"] == thread and format_call(call).splitlines()[0].endswith(f"(thread {thread})")
+
+
+def test_inbox_sync_is_one_thread(store, tmp_path):
+    class Client:
+        async def fetch(self, since=None):
+            return {"items": [{"id": "m1", "channel": "sms", "sender": "Bob", "content": "Late",
+                               "received_at": "2026-10-14T15:00:00"}], "synced_at": "2026-10-14T15:30:00"}
+
+    async def sync():
+        with interaction("inbox"):
+            manager = InboxManager(store=InboxStore(tmp_path / "inbox"), events=store)
+            manager.client = Client()
+            await manager.sync()
+            record_event("activity", "📥 1 new inbox message(s)")
+    asyncio.run(sync())
+    assert [e.type for e in store.query()] == ["sms", "activity"]
+    assert len({e.thread for e in store.query()} - {None}) == 1
+
+
+def test_thread_rows():
+    feed = [{"id": 1, "thread": "a"}, {"id": 2, "thread": None}, {"id": 3, "thread": "b"}, {"id": 4, "thread": "a"},
+            {"id": 5, "thread": "a"}, {"id": 6, "thread": "b"}, {"id": 7, "thread": "c"}]
+
+    def rows(collapsed=()):
+        return [(entry["id"], marker) for entry, marker in thread_rows(feed, collapsed)]
+    assert rows() == [(1, "▾"), (4, "└"), (5, "└"), (2, ""), (3, "▾"), (6, "└"), (7, "")]
+    assert rows({"a", "c"}) == [(1, "▸ +2"), (2, ""), (3, "▾"), (6, "└"), (7, "")]
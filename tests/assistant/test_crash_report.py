@@ -0,0 +1,119 @@
+"""
+Tests for crash reports (assistant/crash_report.py).
+
+Covers:
+- A report's redacted error, stack and log tail, with subsystem states and flags; one per distinct error
+- Errors from threads and from asyncio tasks no one awaited; fatal errors picked up at the next start
+- Finding, listing and pruning reports; the dashboard's "crashed last time" only once
+- Uploads only with crash_upload (or sent by hand), each report once
+"""
+
+import asyncio
+import faulthandler
+import sys
+import threading
+import types
+from datetime import datetime, timedelta
+from pathlib import Path
+
+import pytest
+
+from assistant import crash_report
+from assistant.crash_report import CrashReporter
+
+
+def fail(message):
+    raise ValueError(message)
+
+
+def error(message="Call +1 555 123 4567 back"):
+    try:
+        fail(message)
+    except ValueError as e:
+        return e
+
+
+@pytest.fixture
+def reporter(tmp_path):
+    log = tmp_path / "xswarm.log"
+    log.write_text("\n".join(f"line {n}" for n in range(150)) + "\nmail dana@example.com\n")
+    clock = types.SimpleNamespace(now=datetime(2026, 10, 16, 9, 0))
+
+    def tick():
+        clock.now += timedelta(minutes=1)
+        return clock.now
+    return CrashReporter(tmp_path / "reports", log_path=log, states=lambda: {"voice": "listening (wake word)"},
+                         flags=lambda: {"local_llm": True}, clock=tick)
+
+
+def test_capture(reporter):
+    report = reporter.capture(error(), "main")
+    assert report.error == "ValueError: Call [phone] back" and report.where == "main"
+    assert any("in fail" in line for line in report.stack) and report.stack[0].startswith("Traceback")
+    assert str(Path.home()) not in "\n".join(report.stack)
+    assert len(report.log_tail) == crash_report.LOG_TAIL and report.log_tail[-1] == "mail [email]"
+    assert (report.subsystems, report.flags) == ({"voice": "listening (wake word)"}, {"local_llm": True})
+    assert "Subsystems:\n  voice: listening (wake word)" in report.text()
+
+    assert reporter.capture(error(), "main") is None  # The same error again
+    assert reporter.capture(error("Another"), "main") is not None
+
+
+def test_threads_tasks_and_fatal(reporter, monkeypatch):
+    monkeypatch.setattr(sys, "excepthook", lambda *args: None)
+    monkeypatch.setattr(threading, "excepthook", lambda args: None)
+    reporter.fatal_path.parent.mkdir(parents=True)
+    reporter.fatal_path.write_text("Fatal Python error: Segmentation fault\n\nThread 0x01 (most recent call first):\n")
+    reporter.install()
+    try:
+        worker = threading.Thread(target=fail, args=("in a thread",), name="worker")
+        worker.start()
+        worker.join()
+    finally:
+        faulthandler.disable()
+        reporter._fatal_file.close()
+    assert [(r.where, r.error) for r in reporter.reports()] == [
+        ("thread worker", "ValueError: in a thread"), ("fatal", "Fatal Python error: Segmentation fault")]
+    assert reporter.fatal_path.read_text() == ""
+
+    async def orphan():
+        loop = asyncio.get_running_loop()
+        loop.default_exception_handler = lambda context: None
+        task = asyncio.create_task(asyncio.sleep(0), name="calendar_sync")
+        reporter.loop_exception_handler(loop, {"message": "never retrieved", "exception": error("Sync"), "task": task})
+        await task
+    asyncio.run(orphan())
+    assert reporter.reports()[0].where == "task calendar_sync"
+
+
+def test_finding_and_pruning(reporter, monkeypatch):
+    first = reporter.capture(error("First"))
+    second = reporter.capture(error("Second"))
+    assert [r.id for r in reporter.reports()] == [second.id, first.id]
+    assert reporter.get(first.id[:4]).error == "ValueError: First"
+    with pytest.raises(KeyError, match="No crash report"):
+        reporter.get("zzz")
+    assert first.id in reporter.reports()[1].summary() and "2026-10-16 09:01" in first.summary()
+
+    assert [r.id for r in reporter.unseen()] == [second.id, first.id] and reporter.unseen() == []
+    monkeypatch.setattr(crash_report, "MAX_REPORTS", 2)
+    third = reporter.capture(error("Third"))
+    assert [r.id for r in reporter.reports()] == [third.id, second.id]
+
+
+def test_upload_needs_consent(reporter):
+    class Client:
+        calls = []
+
+        async def call(self, route, **kwargs):
+            self.calls.append((route.key, kwargs["json"]["user_id"], kwargs["json"]["report"]["id"]))
+
+    report = reporter.capture(error())
+    client = Client()
+    assert asyncio.run(reporter.upload_pending(client, "u1")) == 0 and client.calls == []
+
+    reporter.upload = True
+    assert asyncio.run(reporter.upload_pending(client, "u1")) == 1
+    assert client.calls == [("POST /api/crash-reports", "u1", report.id)]
+    assert reporter.get(report.id).uploaded_at and "↑ sent" in reporter.get(report.id).summary()
+    assert asyncio.run(reporter.upload_pending(client, "u1")) == 0
@@ -13,8 +13,9 @@ sys.modules['assistant.voice_server'] = MagicMock()
 # but we do want to mock MLX
 sys.modules['mlx'] = MagicMock()
 
-from assistant.dashboard import VoiceAssistantApp
+from assistant.dashboard import LockScreen, VoiceAssistantApp
 from assistant.config import Config
+from assistant.confirmation import hash_pin
 
 @pytest.mark.asyncio
 async def test_dashboard_rendering():
@@ -34,3 +35,30 @@ async def test_dashboard_rendering():
         # Check initial state
         status = pilot.app.query_one("#status-text")
         assert "Ready" in str(status.render())
+
+
+@pytest.mark.asyncio
+async def test_locked_dashboard_ignores_shortcuts():
+    """While locked, app shortcuts (priority ones included) do nothing; only the PIN gets past."""
+    app = VoiceAssistantApp(Config(confirmation_pin_hash=hash_pin("4211")), personas_dir=MagicMock(),
+                            voice_server_process=MagicMock())
+    ran = []
+
+    async with app.run_test() as pilot:
+        with patch.object(app, "action_undo", lambda: ran.append("undo")), \
+                patch.object(app, "action_copy_logs", lambda: ran.append("copy_logs")):
+            app.action_lock()
+            await pilot.pause()
+            assert isinstance(app.screen, LockScreen)
+
+            await pilot.press("ctrl+z", "ctrl+l", "alt+w", "y")
+            await pilot.pause()
+            assert ran == []
+            assert app.screen.query_one("#unlock-input").value == "y"  # Plain keys only reach the PIN field
+
+            await pilot.press("backspace", *"4211", "enter")
+            await pilot.pause()
+            assert not isinstance(app.screen, LockScreen)
+            await pilot.press("ctrl+z")
+            await pilot.pause()
+            assert ran == ["undo"]
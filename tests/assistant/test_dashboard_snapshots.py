@@ -0,0 +1,181 @@
+"""
+Deterministic snapshot tests for the dashboard.
+
+Renders the real VoiceAssistantApp headless and compares it against SVG
+baselines (pytest-textual-snapshot), so layout regressions show up as a
+failing diff instead of needing someone to eyeball the TUI.
+
+Everything that changes between runs is pinned:
+- the clock (activity timestamps, "today" in the schedule) -> FROZEN_NOW
+- GPU detection -> a fixed 24GB card
+- the version in the footer, footer stat jitter (random seed)
+- background startup (voice, memory, chat engine, jobs) is skipped
+- planner data comes from a seeded tmp_path planner
+
+Covers the main layout at several terminal sizes, one per size class
+(layout.py) and after resizing, the Status tab with errors and a failed
+subsystem, and the Schedule tab.
+
+Run: pytest tests/assistant/test_dashboard_snapshots.py
+Update baselines: pytest tests/assistant/test_dashboard_snapshots.py --snapshot-update
+"""
+
+import datetime as datetime_module
+import random
+import types
+
+import pytest
+
+import assistant
+from assistant import dashboard, dashboard_widgets, planner as planner_module, tools
+from assistant.config import Config
+from assistant.dashboard import VoiceAssistantApp
+from assistant.dashboard_widgets import CyberpunkFooter
+from assistant.hardware import GPUCapability
+from assistant.layout import SizeClass
+from assistant.planner import PlannerData
+
+FROZEN_NOW = datetime_module.datetime(2026, 10, 14, 8, 50, 0)
+
+SIZES = [(80, 24), (120, 40), (200, 60)]
+SIZE_CLASSES = {SizeClass.COMPACT: (80, 24), SizeClass.MEDIUM: (100, 30), SizeClass.WIDE: (160, 45)}
+
+
+class FrozenDatetime(datetime_module.datetime):
+    @classmethod
+    def now(cls, tz=None):
+        return FROZEN_NOW.replace(tzinfo=tz) if tz else FROZEN_NOW
+
+
+class FrozenDate(datetime_module.date):
+    @classmethod
+    def today(cls):
+        return FROZEN_NOW.date()
+
+
+FIXED_GPU = GPUCapability(
+    device_name="Snapshot GPU", vram_total_gb=24.0, vram_used_gb=6.0, vram_free_gb=18.0,
+    compute_score=45.0, temp_c=None, grade="C-", util_percent=10.0, device_type="nvidia",
+)
+
+
+class SnapshotDashboard(VoiceAssistantApp):
+    """The dashboard without background startup, so each render is the same."""
+
+    async def initialize_memory(self):
+        pass
+
+    async def _init_chat_engine_background(self) -> None:
+        pass
+
+    async def _complete_voice_initialization(self):
+        pass
+
+    def _setup_jobs(self) -> None:
+        pass
+
+    def _setup_calendar_sync(self) -> None:
+        pass
+
+    def _update_resource_usage(self) -> None:
+        pass
+
+
+@pytest.fixture
+def frozen(monkeypatch, tmp_path):
+    """Pin the clock, hardware, version and planner data the dashboard renders."""
+    frozen_datetime = types.ModuleType("datetime")
+    frozen_datetime.__dict__.update(datetime_module.__dict__)
+    frozen_datetime.datetime, frozen_datetime.date = FrozenDatetime, FrozenDate
+    monkeypatch.setattr(dashboard, "datetime", frozen_datetime)
+    monkeypatch.setattr(dashboard_widgets, "datetime", FrozenDatetime)
+    monkeypatch.setattr(planner_module, "datetime", FrozenDatetime)
+    monkeypatch.setattr(planner_module, "date", FrozenDate)
+    monkeypatch.setattr(dashboard_widgets, "detect_gpu_capability", lambda: FIXED_GPU)
+    monkeypatch.setattr(assistant, "__version__", "0.0.0", raising=False)
+    random.seed(0)
+
+    planner = PlannerData(tmp_path / "planner")
+    planner.add_calendar_event("Standup", "2026-10-14T09:00:00", "2026-10-14T09:15:00", tags=["work"])
+    planner.add_calendar_event("Dentist", "2026-10-14T16:00:00", "2026-10-14T17:00:00", location="Main St")
+    planner.add_calendar_event("Planning review", "2026-10-16T11:00:00", "2026-10-16T12:00:00")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    return planner
+
+
+@pytest.fixture
+def personas_dir(tmp_path):
+    persona_dir = tmp_path / "personas" / "jarvis"
+    persona_dir.mkdir(parents=True)
+    (persona_dir / "config.yaml").write_text("""
+name: JARVIS
+description: Just A Rather Very Intelligent System
+system_prompt: You are Jarvis.
+voice:
+  speed: 1.0
+theme:
+  theme_color: "#00aaff"
+""")
+    return tmp_path / "personas"
+
+
+@pytest.fixture
+def app(frozen, personas_dir):
+    config = Config(text_only=True, memory_enabled=False, theme_base_color="#8899aa")
+    return SnapshotDashboard(config, personas_dir)
+
+
+def _show_tab(tab):
+    async def run_before(pilot):
+        pilot.app.active_tab = tab
+        await pilot.pause()
+    return run_before
+
+
+class TestDashboardSnapshots:
+    @pytest.mark.parametrize("size", SIZES)
+    def test_main_layout(self, snap_compare, app, size):
+        assert snap_compare(app, terminal_size=size, run_before=_show_tab("chat"))
+
+    @pytest.mark.parametrize("size", SIZES)
+    def test_status_with_errors(self, snap_compare, app, size):
+        async def run_before(pilot):
+            pilot.app.active_tab = "status"
+            pilot.app.update_activity("✓ Server connection restored", "success")
+            pilot.app.update_activity("⚠ Audio lagging: dropped 12 frames (worst: mic/moshi)", "warning")
+            pilot.app.update_activity("✗ voice audio forwarder stopped after 5 restarts: RuntimeError: queue closed", "error")
+            pilot.app.query_one(CyberpunkFooter).failed_subsystems = ("voice audio forwarder",)
+            await pilot.pause()
+
+        assert snap_compare(app, terminal_size=size, run_before=run_before)
+
+    @pytest.mark.parametrize("size", SIZES)
+    def test_schedule_tab(self, snap_compare, app, size):
+        assert snap_compare(app, terminal_size=size, run_before=_show_tab("schedule"))
+
+
+class TestSizeClasses:
+    @pytest.mark.parametrize("size_class", list(SIZE_CLASSES), ids=lambda c: c.value)
+    def test_layout(self, snap_compare, app, size_class):
+        async def run_before(pilot):
+            pilot.app.active_tab = "status"
+            await pilot.pause()
+            assert pilot.app.size_class == size_class
+
+        assert snap_compare(app, terminal_size=SIZE_CLASSES[size_class], run_before=run_before)
+
+    def test_resized_to_compact(self, snap_compare, app):
+        async def run_before(pilot):
+            await pilot.resize_terminal(*SIZE_CLASSES[SizeClass.COMPACT])
+            await pilot.pause()
+            assert pilot.app.has_class("-compact") and not pilot.app.has_class("-wide")
+
+        assert snap_compare(app, terminal_size=SIZE_CLASSES[SizeClass.WIDE], run_before=run_before)
+
+    def test_resized_back(self, snap_compare, app):
+        async def run_before(pilot):
+            await pilot.resize_terminal(*SIZE_CLASSES[SizeClass.WIDE])
+            await pilot.pause()
+            assert pilot.app.size_class == SizeClass.WIDE
+
+        assert snap_compare(app, terminal_size=SIZE_CLASSES[SizeClass.COMPACT], run_before=run_before)
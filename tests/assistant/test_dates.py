@@ -0,0 +1,390 @@
+"""
+Tests for natural language date and time parsing (assistant/dates.py).
+
+Covers:
+- Example phrasings for dates, times and both together; negative offsets ("in -3 days") refused
+- Properties over whole ranges: every clock time round-trips, weekdays are
+  always 1-7 days ahead, "in N days" is exact, every calendar day resolves
+  to its next occurrence
+- Fuzzing: seeded random mixes of date words and junk never raise and only
+  ever produce valid values
+- Calendar arithmetic: month/year steps clamp instead of drifting,
+  quarters and fiscal years for January and July fiscal starts, workday
+  times ("first thing Monday"), monthly recurring events keep their day
+- Day/month order: both locales, locale detection, ambiguity questions
+- The calendar tools report unparseable dates instead of failing, and ask
+  about ambiguous ones
+"""
+
+import random
+import re
+from datetime import date, datetime, timedelta
+
+import pytest
+
+from assistant import dates
+from assistant.dates import (
+    MONTHS,
+    WEEKDAYS,
+    AmbiguousDateError,
+    DateSettings,
+    add_months,
+    add_years,
+    ambiguous_readings,
+    month_length,
+    parse_natural_date,
+    parse_natural_datetime,
+    parse_time_expression,
+    system_date_order,
+)
+from assistant.config import Config
+
+WEDNESDAY = date(2026, 10, 14)
+
+
+@pytest.fixture(autouse=True)
+def month_first(monkeypatch):
+    """Numeric dates default to the US order here, whatever the machine's locale."""
+    monkeypatch.setattr(dates, "_settings", DateSettings(order="mdy"))
+
+
+class TestDates:
+    @pytest.mark.parametrize("text,expected", [
+        ("today", "2026-10-14"),
+        ("tomorrow", "2026-10-15"),
+        ("the day after tomorrow", "2026-10-16"),
+        ("friday", "2026-10-16"),
+        ("next Friday", "2026-10-16"),
+        ("wednesday", "2026-10-21"),
+        ("friday next week", "2026-10-23"),
+        ("in 3 days", "2026-10-17"),
+        ("in a couple of weeks", "2026-10-28"),
+        ("two months from now", "2026-12-14"),
+        ("end of the week", "2026-10-16"),
+        ("next week", "2026-10-19"),
+        ("end of month", "2026-10-31"),
+        ("next month", "2026-11-01"),
+        ("next month on the 3rd", "2026-11-03"),
+        ("the 3rd of next month", "2026-11-03"),
+        ("the 31st", "2026-10-31"),
+        ("the 3rd", "2026-11-03"),
+        ("March 3", "2027-03-03"),
+        ("3rd of March 2027", "2027-03-03"),
+        ("on Dec 25th", "2026-12-25"),
+        ("the twenty first of november", "2026-11-21"),
+        ("3/14", "2027-03-14"),
+        ("2026-10-16", "2026-10-16"),
+        ("feb 29", "2028-02-29"),
+    ])
+    def test_phrases(self, text, expected):
+        assert parse_natural_date(text, WEDNESDAY) == date.fromisoformat(expected)
+
+    @pytest.mark.parametrize("text", [
+        "", "someday", "next", "the", "2026-02-30", "february 30", "13/40", "in many days",
+        "next month on the 31st", "the 32nd", "friday friday",
+    ])
+    def test_not_dates(self, text):
+        assert parse_natural_date(text, WEDNESDAY) is None
+
+    @pytest.mark.parametrize("text", ["in -3 days", "in -1 week", "-2 days from now", "in -2 months"])
+    def test_negative_offsets_are_refused(self, text):
+        assert parse_natural_date(text, WEDNESDAY) is None
+        assert parse_natural_datetime(text, today=WEDNESDAY) is None
+
+
+class TestTimes:
+    @pytest.mark.parametrize("text,expected", [
+        ("16:00", "16:00"),
+        ("09:30:00", "09:30"),
+        ("4pm", "16:00"),
+        ("4:30 p.m.", "16:30"),
+        ("12 am", "00:00"),
+        ("noon", "12:00"),
+        ("midnight", "00:00"),
+        ("half past three", "15:30"),
+        ("a quarter to 5", "16:45"),
+        ("quarter past 2 pm", "14:15"),
+        ("ten past eleven", "11:10"),
+        ("twenty-five to six", "17:35"),
+        ("quarter to midnight", "23:45"),
+        ("three o'clock", "15:00"),
+        ("three thirty", "15:30"),
+        ("nine oh five", "09:05"),
+        ("seven in the evening", "19:00"),
+        ("six in the morning", "06:00"),
+        ("at four", "16:00"),
+        ("eleven", "11:00"),
+    ])
+    def test_phrases(self, text, expected):
+        assert parse_time_expression(text) == expected
+
+    @pytest.mark.parametrize("text", ["", "banana", "13 pm", "25:00", "4:75", "half past", "sixty past four"])
+    def test_not_times(self, text):
+        assert parse_time_expression(text) is None
+
+
+class TestDateTimes:
+    @pytest.mark.parametrize("text,expected", [
+        ("friday at half past three", "2026-10-16T15:30"),
+        ("at 4pm tomorrow", "2026-10-15T16:00"),
+        ("tomorrow at noon", "2026-10-15T12:00"),
+        ("march 3 2027 at 4pm", "2027-03-03T16:00"),
+        ("next month on the 3rd", "2026-11-03T09:00"),
+        ("tonight", "2026-10-14T19:00"),
+    ])
+    def test_phrases(self, text, expected):
+        assert parse_natural_datetime(text, today=WEDNESDAY) == datetime.fromisoformat(expected)
+
+    def test_unknown_date(self):
+        assert parse_natural_datetime("at 4pm", today=WEDNESDAY) is None
+
+
+class TestProperties:
+    def test_every_clock_time_round_trips(self):
+        for minutes in range(24 * 60):
+            hour, minute = divmod(minutes, 60)
+            expected = f"{hour:02d}:{minute:02d}"
+            assert parse_time_expression(expected) == expected
+            twelve = hour % 12 or 12
+            meridiem = "am" if hour < 12 else "pm"
+            assert parse_time_expression(f"{twelve}:{minute:02d} {meridiem}") == expected
+            assert parse_time_expression(f"{twelve}:{minute:02d}{meridiem.upper()}") == expected
+
+    def test_weekdays_are_the_coming_one(self):
+        for offset in range(14):
+            today = WEDNESDAY + timedelta(days=offset)
+            for index, name in enumerate(WEEKDAYS):
+                for phrase in (name, name[:3], f"next {name}", f"this {name}"):
+                    day = parse_natural_date(phrase, today)
+                    assert day.weekday() == index
+                    assert 1 <= (day - today).days <= 7
+
+    def test_relative_days_are_exact(self):
+        for n in range(0, 400, 7):
+            assert parse_natural_date(f"in {n} days", WEDNESDAY) == WEDNESDAY + timedelta(days=n)
+            assert parse_natural_date(f"{n} days from now", WEDNESDAY) == WEDNESDAY + timedelta(days=n)
+
+    def test_every_calendar_day_is_its_next_occurrence(self):
+        leap_year = date(2028, 1, 1)
+        for offset in range(366):
+            day = leap_year + timedelta(days=offset)
+            month = MONTHS[day.month - 1]
+            for phrase in (f"{month} {day.day}", f"{day.day} {month[:3]}", f"the {day.day}th of {month}"):
+                resolved = parse_natural_date(phrase, WEDNESDAY)
+                assert (resolved.month, resolved.day) == (day.month, day.day)
+                assert WEDNESDAY <= resolved < WEDNESDAY + timedelta(days=366 * 4)
+
+    def test_iso_dates_round_trip(self):
+        for offset in range(0, 3000, 13):
+            day = WEDNESDAY + timedelta(days=offset)
+            assert parse_natural_date(day.isoformat(), WEDNESDAY) == day
+
+
+VOCABULARY = (
+    WEEKDAYS + MONTHS + ["next", "this", "the", "of", "on", "at", "in", "a", "quarter", "half", "past",
+                         "to", "days", "weeks", "month", "from", "now", "tomorrow", "today", "noon",
+                         "midnight", "pm", "am", "o'clock", "3rd", "31st", "0", "12", "99", "9999",
+                         "2026-02-30", "13/13", "-", ":", "4:", ":75", "twenty", "oh", "é", "\u0000"]
+)
+
+
+class TestFuzz:
+    def test_random_phrases_never_raise(self):
+        rng = random.Random(1234)
+        for _ in range(5000):
+            words = [rng.choice(VOCABULARY) for _ in range(rng.randint(0, 8))]
+            if rng.random() < 0.2:
+                words.append("".join(chr(rng.randint(0, 0x2FFF)) for _ in range(rng.randint(1, 6))))
+            text = rng.choice([" ", "", "-", ","]).join(words)
+            today = WEDNESDAY + timedelta(days=rng.randint(-3000, 3000))
+
+            day = parse_natural_date(text, today)
+            assert day is None or isinstance(day, date)
+            time_of_day = parse_time_expression(text)
+            assert time_of_day is None or re.fullmatch(r"([01]\d|2[0-3]):[0-5]\d", time_of_day)
+            when = parse_natural_datetime(text, today=today)
+            assert when is None or isinstance(when, datetime)
+
+    @pytest.mark.parametrize("value", [None, 42, b"friday", "friday " * 100, "9" * 50, "in 99999999 days",
+                                       "in 9999 years"])
+    def test_odd_inputs(self, value):
+        parse_natural_date(value, WEDNESDAY)
+        parse_time_expression(value)
+        parse_natural_datetime(value, today=WEDNESDAY)
+
+    def test_far_future_is_none_not_an_error(self):
+        assert parse_natural_date("in 1000 years", date(9990, 1, 1)) is None
+
+
+class TestCalendarArithmetic:
+    def test_months_clamp_to_the_last_day(self):
+        assert add_months(date(2027, 1, 31), 1) == date(2027, 2, 28)
+        assert add_months(date(2028, 1, 31), 1) == date(2028, 2, 29)
+        assert add_months(date(2026, 3, 31), -1) == date(2026, 2, 28)
+        assert add_months(datetime(2026, 10, 31, 9, 30), 13) == datetime(2027, 11, 30, 9, 30)
+        assert add_years(date(2028, 2, 29), 1) == date(2029, 2, 28)
+        assert add_years(date(2028, 2, 29), 4) == date(2032, 2, 29)
+
+    def test_month_steps_never_drift(self):
+        start = date(2028, 1, 1)
+        for offset in range(366):
+            day = start + timedelta(days=offset)
+            for months in range(-25, 26):
+                moved = add_months(day, months)
+                assert (moved.year * 12 + moved.month) - (day.year * 12 + day.month) == months
+                assert moved.day == min(day.day, month_length(moved.year, moved.month))
+
+    @pytest.mark.parametrize("text,expected", [
+        ("in 1 month", "2026-11-14"),
+        ("in 18 months", "2028-04-14"),
+        ("a year from now", "2027-10-14"),
+        ("end of the month", "2026-10-31"),
+        ("end of next month", "2026-11-30"),
+        ("end of the year", "2026-12-31"),
+        ("end of the quarter", "2026-12-31"),
+        ("this quarter", "2026-12-31"),
+        ("next quarter", "2027-01-01"),
+        ("end of next quarter", "2027-03-31"),
+        ("q3", "2027-07-01"),  # This year's Q3 is over
+        ("q4", "2026-10-01"),  # The current one
+        ("end of q2 2027", "2027-06-30"),
+        ("end of the fiscal year", "2026-12-31"),
+        ("next fiscal year", "2027-01-01"),
+        ("end of fy2027", "2027-12-31"),
+    ])
+    def test_calendar_year(self, text, expected):
+        assert parse_natural_date(text, WEDNESDAY) == date.fromisoformat(expected)
+
+    @pytest.mark.parametrize("text,expected", [
+        ("end of the quarter", "2026-12-31"),
+        ("end of next quarter", "2027-03-31"),
+        ("q1", "2027-07-01"),
+        ("q2", "2026-10-01"),
+        ("end of q2 2027", "2026-12-31"),  # FY2027 runs July 2026 - June 2027
+        ("fy2027", "2026-07-01"),
+        ("end of the fiscal year", "2027-06-30"),
+        ("next fiscal year", "2027-07-01"),
+    ])
+    def test_fiscal_year_from_july(self, monkeypatch, text, expected):
+        monkeypatch.setattr(dates, "_settings", DateSettings.from_config(Config(fiscal_year_start_month=7)))
+        assert parse_natural_date(text, WEDNESDAY) == date.fromisoformat(expected)
+
+    @pytest.mark.parametrize("text,expected", [
+        ("first thing Monday", "2026-10-19T09:00"),
+        ("first thing in the morning tomorrow", "2026-10-15T09:00"),
+        ("end of day friday", "2026-10-16T18:00"),
+        ("close of business thursday", "2026-10-15T18:00"),
+        ("friday at lunchtime", "2026-10-16T12:00"),
+        ("tomorrow evening", "2026-10-15T19:00"),
+        ("tomorrow afternoon", "2026-10-15T14:00"),
+    ])
+    def test_workday_times(self, text, expected):
+        assert parse_natural_datetime(text, today=WEDNESDAY) == datetime.fromisoformat(expected)
+
+    def test_part_of_day_keeps_a_matching_time(self):
+        assert parse_natural_datetime("tomorrow morning", "10:30", today=WEDNESDAY) == datetime(2026, 10, 15, 10, 30)
+
+    def test_monthly_event_keeps_its_day(self, tmp_path):
+        from assistant.planner import PlannerData
+
+        planner = PlannerData(tmp_path / "planner")
+        planner.add_calendar_event("Rent", "2027-01-31T09:00:00", "2027-01-31T09:30:00", recurrence="monthly")
+        events = planner.get_calendar_events("2027-01-01", "2027-06-30")
+        assert [e.start_time[:10] for e in events] == [
+            "2027-01-31", "2027-02-28", "2027-03-31", "2027-04-30", "2027-05-31", "2027-06-30",
+        ]
+
+    def test_yearly_leap_day(self, tmp_path):
+        from assistant.planner import PlannerData
+
+        planner = PlannerData(tmp_path / "planner")
+        planner.add_calendar_event("Leap party", "2028-02-29T19:00:00", "2028-02-29T22:00:00",
+                                   recurrence="yearly", recurrence_end="2032-12-31")
+        events = planner.get_calendar_events("2028-01-01", "2032-12-31")
+        assert [e.start_time[:10] for e in events] == [
+            "2028-02-29", "2029-02-28", "2030-02-28", "2031-02-28", "2032-02-29",
+        ]
+
+
+class TestDateOrder:
+    @pytest.mark.parametrize("text,mdy,dmy", [
+        ("04/05/2025", "2025-04-05", "2025-05-04"),
+        ("4.5.2025", "2025-04-05", "2025-05-04"),
+        ("12-01-27", "2027-12-01", "2027-01-12"),
+        ("25/12", "2026-12-25", "2026-12-25"),  # Only one reading is a real day
+        ("12/25/2025", "2025-12-25", "2025-12-25"),
+        ("2025/04/05", "2025-04-05", "2025-04-05"),  # Year first is always y/m/d
+        ("7/7/2027", "2027-07-07", "2027-07-07"),
+    ])
+    def test_both_orders(self, text, mdy, dmy):
+        assert parse_natural_date(text, WEDNESDAY, date_order="mdy") == date.fromisoformat(mdy)
+        assert parse_natural_date(text, WEDNESDAY, date_order="dmy") == date.fromisoformat(dmy)
+
+    def test_configured_order_is_the_default(self, monkeypatch):
+        monkeypatch.setattr(dates, "_settings", DateSettings.from_config(Config(date_order="dmy")))
+        assert parse_natural_date("04/05/2025", WEDNESDAY) == date(2025, 5, 4)
+        assert parse_natural_datetime("04/05/2025 at 3pm", today=WEDNESDAY) == datetime(2025, 5, 4, 15, 0)
+
+    @pytest.mark.parametrize("environ,expected", [
+        ({"LANG": "en_US.UTF-8"}, "mdy"),
+        ({"LANG": "en_GB.UTF-8"}, "dmy"),
+        ({"LANG": "en_US.UTF-8", "LC_TIME": "de_DE.UTF-8"}, "dmy"),
+        ({"LC_ALL": "en_PH.UTF-8", "LANG": "fr_FR.UTF-8"}, "mdy"),
+        ({"LANG": "C.UTF-8"}, "mdy"),
+        ({}, "mdy"),
+    ])
+    def test_auto_follows_locale(self, environ, expected):
+        assert system_date_order(environ) == expected
+
+    def test_ambiguous_readings(self):
+        assert ambiguous_readings("04/05/2025", WEDNESDAY, "mdy") == [date(2025, 4, 5), date(2025, 5, 4)]
+        assert ambiguous_readings("04/05/2025", WEDNESDAY, "dmy") == [date(2025, 5, 4), date(2025, 4, 5)]
+        assert ambiguous_readings("on 4/5 at half past three", WEDNESDAY, "mdy") == [date(2027, 4, 5), date(2027, 5, 4)]
+        for text in ("25/12/2025", "7/7", "2025-04-05", "friday 4.10", "tomorrow", "banana"):
+            assert ambiguous_readings(text, WEDNESDAY) == []
+
+    def test_clarifying_question(self):
+        error = AmbiguousDateError("04/05/2025", ambiguous_readings("04/05/2025", WEDNESDAY, "dmy"))
+        assert error.question == "Did you mean Sunday May 4, 2025 or Saturday April 5, 2025?"
+
+
+def _planner(tmp_path, monkeypatch):
+    from assistant import tools
+    from assistant.planner import PlannerData
+
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    return planner
+
+
+class TestCalendarTools:
+    def test_ambiguous_date_asks_instead_of_booking(self, tmp_path, monkeypatch):
+        from assistant import tools
+
+        planner = _planner(tmp_path, monkeypatch)
+        monkeypatch.setattr(dates, "_settings", DateSettings(order="mdy"))
+        result = tools.add_calendar_event("Lunch", "04/05/2027", start_time="12:00")
+        assert result.startswith("⏸ Not done yet")
+        assert "Did you mean Monday April 5, 2027 or Tuesday May 4, 2027?" in result
+        assert planner.get_calendar_events(expand_recurring=False) == []
+        assert "⏸" in tools.add_task("File taxes", due_date="04/05/2027", force=True)
+
+    @pytest.mark.parametrize("order,expected", [("mdy", "2027-04-05T12:00:00"), ("dmy", "2027-05-04T12:00:00")])
+    def test_without_asking_the_order_decides(self, tmp_path, monkeypatch, order, expected):
+        from assistant import tools
+
+        planner = _planner(tmp_path, monkeypatch)
+        monkeypatch.setattr(dates, "_settings", DateSettings(order=order, ask_when_ambiguous=False))
+        assert tools.add_calendar_event("Lunch", "04/05/2027", start_time="12:00").startswith("✓")
+        [event] = planner.get_calendar_events(expand_recurring=False)
+        assert event.start_time == expected
+
+    def test_unparseable_date_is_reported(self, tmp_path, monkeypatch):
+        from assistant import tools
+
+        _planner(tmp_path, monkeypatch)
+        assert tools.add_calendar_event("Lunch", "someday").startswith("✗ Couldn't understand the date")
+        assert tools.add_calendar_event("Lunch", "friday", start_time="teatime").startswith("✗ Couldn't understand the time")
+        assert tools.add_calendar_event("Lunch", "2026-10-20", start_time="half past twelve").startswith("✓")
+        [event] = tools._planner_data.get_calendar_events(expand_recurring=False)
+        assert event.start_time == "2026-10-20T12:30:00"
@@ -0,0 +1,142 @@
+"""
+Tests for follow-up references (assistant/dialogue.py).
+
+Covers:
+- Telling follow-ups ("move it", "invite Bob too") from new topics
+- Focusing on what a tool call created or acted on, forgetting deleted items
+- Resolving a missing or pronoun id to the focus, and adding attendees on "too"
+- The context hint for the model, and the focus expiring
+- update_calendar_event moving an event to a time on the same day
+"""
+
+from datetime import date
+
+import pytest
+
+from assistant import dates, dialogue, intents, tools
+from assistant.dates import DateSettings
+from assistant.dialogue import FOCUS_TTL, DialogueState, refers_back
+from assistant.intents import REFERRED, hear, score_call
+from assistant.planner import PlannerData
+
+EVENT_PARAMS = ["event_id", "title", "start_time", "end_time", "attendees"]
+
+
+@pytest.mark.parametrize("text, follow_up", [
+    ("move it to 4 instead", True),
+    ("invite Bob too", True),
+    ("and Carol as well", True),
+    ("make that an hour", True),
+    ("what's the weather like", False),
+    ("book the dentist friday at 3", False),
+    ("never mind that", False),
+    ("new topic - how's my week looking", False),
+])
+def test_refers_back(text, follow_up):
+    assert refers_back(text) == follow_up
+
+
+class Clock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+@pytest.fixture
+def state(tmp_path, monkeypatch):
+    monkeypatch.setattr(dates, "_settings", DateSettings(clock="12h"))
+    monkeypatch.setattr(tools, "_planner_data", PlannerData(tmp_path / "planner"))
+    monkeypatch.setattr(intents, "_current", None)
+    state = DialogueState(clock=Clock())
+    monkeypatch.setattr(dialogue, "_state", state)
+    return state
+
+
+def _dentist(attendees=None):
+    today = date.today()
+    return tools.get_planner_data().add_calendar_event(
+        "Dentist", f"{today}T15:00:00", f"{today}T15:30:00", attendees=attendees).id
+
+
+class TestFocus:
+    def test_created_item(self, state):
+        event_id = _dentist()
+        state.note_call("add_calendar_event", {"title": "dentist", "day": "today"}, "✓ Added: 'Dentist'")
+        assert (state.focus.key, state.focus.item_id) == ("event_id", event_id)
+
+    def test_item_acted_on(self, state):
+        event_id = _dentist()
+        state.note_call("update_calendar_event", {"event_id": event_id, "location": "Main St"}, "✓ Updated event")
+        assert state.focus.title == "Dentist"
+
+    def test_failures_change_nothing(self, state):
+        state.note_call("update_calendar_event", {"event_id": _dentist()}, "✗ Event not found")
+        assert state.focus is None
+
+    def test_deleting_it_forgets_it(self, state):
+        event_id = _dentist()
+        state.focus_on("event_id", event_id, "Dentist")
+        state.note_call("delete_calendar_event", {"event_id": event_id}, "✓ Deleted event: 'Dentist'")
+        assert state.focus is None
+
+    def test_new_topic_forgets_it(self, state):
+        state.focus_on("event_id", _dentist(), "Dentist")
+        hear("move it to 4 instead")
+        assert state.focus is not None
+        hear("what's the weather like")
+        assert state.focus is None
+
+    def test_expires(self, state):
+        state.focus_on("event_id", _dentist(), "Dentist")
+        state.clock.now += FOCUS_TTL + 1
+        assert state.context_hint() == ""
+
+
+class TestResolve:
+    def test_pronoun_or_missing_id(self, state):
+        event_id = _dentist()
+        state.focus_on("event_id", event_id, "Dentist")
+        for args in ({"event_id": "it"}, {"event_id": "That one"}, {"start_time": "16:00"}):
+            resolved = state.resolve_args("update_calendar_event", args, EVENT_PARAMS)
+            assert resolved["event_id"] == event_id
+        assert state.resolve_args("update_calendar_event", {"event_id": "evt-other"}, EVENT_PARAMS) == \
+            {"event_id": "evt-other"}
+
+    def test_other_kinds_untouched(self, state):
+        state.focus_on("event_id", _dentist(), "Dentist")
+        assert state.resolve_args("complete_task", {"task_id": "it"}, ["task_id"]) == {"task_id": "it"}
+
+    def test_too_adds_attendees(self, state):
+        event_id = _dentist(attendees=["Alice"])
+        state.focus_on("event_id", event_id, "Dentist")
+        hear("invite Bob too")
+        assert state.resolve_args("update_calendar_event", {"event_id": "it", "attendees": "Bob, alice"},
+                                  EVENT_PARAMS)["attendees"] == "Alice, Bob"
+        hear("make it just Bob")
+        assert state.resolve_args("update_calendar_event", {"event_id": "it", "attendees": "Bob"},
+                                  EVENT_PARAMS)["attendees"] == "Bob"
+
+    def test_follow_up_is_grounded(self, state):
+        event_id = _dentist()
+        state.focus_on("event_id", event_id, "Dentist")
+        hear("invite Bob too")
+        score = score_call("update_calendar_event", {"event_id": event_id, "attendees": "Bob"})
+        assert (score.confidence, score.reason) == (REFERRED, "referred")
+
+    def test_context_hint(self, state):
+        event_id = _dentist()
+        assert state.with_context("move it") == "move it"
+        state.focus_on("event_id", event_id, "Dentist")
+        assert state.with_context("move it") == f"[Context: 'it' is the 3pm Dentist (event_id {event_id})] move it"
+        tools.get_planner_data().delete_calendar_event(event_id)
+        assert state.context_hint() == "" and state.focus is None
+
+
+def test_move_to_a_time_keeps_day_and_length(state):
+    event_id = _dentist()
+    assert tools.update_calendar_event(event_id, start_time="4pm").startswith("✓")
+    event = tools.get_planner_data().get_calendar_event(event_id)
+    assert (event.start_time, event.end_time) == (f"{date.today()}T16:00:00", f"{date.today()}T16:30:00")
+    assert tools.update_calendar_event(event_id, start_time="sometime").startswith("✗")
@@ -0,0 +1,83 @@
+"""
+Tests for inferring how long an event takes (assistant/durations.py).
+
+Covers:
+- Keyword defaults, config.event_durations over them, and the plain default
+- The user's own past events of the same kind, averaged once there are enough
+- add_calendar_event: inferred length, a length said, an end said ("until 3")
+- "schedule ... until 3" keeping the end for the tool
+"""
+
+from datetime import date, datetime
+
+import pytest
+
+from assistant import durations, tools
+from assistant.appointments import parse_schedule_request
+from assistant.durations import DurationDefaults
+from assistant.planner import PlannerData
+
+NOW = datetime(2026, 10, 16, 10, 0)
+
+
+@pytest.fixture
+def planner(tmp_path, monkeypatch):
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    monkeypatch.setattr(durations, "_defaults", DurationDefaults())
+    return planner
+
+
+@pytest.mark.parametrize("title, overrides, expected", [
+    ("Lunch with Sam", {}, (60, "lunch", "keyword")),
+    ("Coffee with Ana", {}, (30, "coffee", "keyword")),
+    ("Team standup", {}, (15, "standup", "keyword")),
+    ("Lunch with Sam", {"lunch": 45}, (45, "lunch", "config")),
+    ("Sam 1:1", {}, (30, "1:1", "keyword")),
+    ("Therapy session", {}, (50, "therapy", "keyword")),
+    ("Pick up the car", {}, (60, "pick up the car", "default")),
+])
+def test_without_history(title, overrides, expected):
+    duration = DurationDefaults(overrides).infer(title, now=NOW)
+    assert (duration.minutes, duration.kind, duration.source) == expected
+    assert duration.note == ""
+
+
+def test_from_history(planner):
+    planner.add_calendar_event("Lunch with Sam", "2026-10-12T12:00:00", "2026-10-12T12:40:00")
+    planner.add_calendar_event("Team lunch", "2026-10-13T12:00:00", "2026-10-13T12:50:00")
+    planner.add_calendar_event("Lunch with Ana", "2026-10-20T12:00:00", "2026-10-20T14:00:00")  # Not yet
+    planner.add_calendar_event("Coffee", "2026-10-14T09:00:00", "2026-10-14T10:00:00")
+    history = planner.get_calendar_events(expand_recurring=False)
+    duration = DurationDefaults().infer("lunch with Bob", history, NOW)
+    assert (duration.minutes, duration.samples, duration.note) == (45, 2, " (~45 min, like your past lunches)")
+    assert DurationDefaults().infer("Coffee", history, NOW).source == "keyword"  # Only one so far
+    config = type("Config", (), {"event_durations": {"Lunch": 40, "nap": 0}})()
+    assert DurationDefaults.from_config(config).overrides == {"lunch": 40}
+
+
+def test_add_calendar_event(planner):
+    planner.add_calendar_event("Lunch with Sam", "2026-10-12T12:00:00", "2026-10-12T12:40:00")
+    planner.add_calendar_event("Lunch with Ana", "2026-10-13T12:00:00", "2026-10-13T12:50:00")
+
+    def added(title):
+        event = next(e for e in planner.get_calendar_events(expand_recurring=False) if e.title == title)
+        return event.end_time[11:16]
+
+    assert tools.add_calendar_event("Lunch with Bob", "2026-10-19", "12:00").endswith(
+        "at 12:00 (~45 min, like your past lunches)")
+    assert added("Lunch with Bob") == "12:45"
+    assert tools.add_calendar_event("Coffee", "2026-10-19", "09:00", duration_minutes=20).endswith("at 09:00")
+    assert added("Coffee") == "09:20"
+    tools.add_calendar_event("Offsite", "2026-10-21", "13:00", end_time="through the afternoon")
+    assert added("Offsite") == "17:00"
+    tools.add_calendar_event("Review", "2026-10-22", "12:00", end_time="until 3")
+    assert added("Review") == "15:00"
+    assert tools.add_calendar_event("Demo", "2026-10-22", "12:00", end_time="soonish").startswith("✗")
+
+
+def test_schedule_request_keeps_the_end():
+    request = parse_schedule_request("schedule the offsite friday through the afternoon", date(2026, 10, 16))
+    assert (request.title, request.day, request.time, request.end) == (
+        "Offsite", date(2026, 10, 23), None, "through the afternoon")
+    assert parse_schedule_request("schedule the dentist tomorrow", date(2026, 10, 16)).end == ""
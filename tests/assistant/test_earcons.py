@@ -0,0 +1,89 @@
+"""
+Tests for listening cues (assistant/earcons.py).
+
+Covers:
+- Each style synthesizes every cue, normalized and click-free; "none" is silent
+- Which conversation state changes play which cue
+- Per-persona style and volume, and turning cues off
+"""
+
+import numpy as np
+import pytest
+
+from assistant.config import Config
+from assistant.earcons import CUES, EarconPlayer, cue_for_transition, synthesize
+from assistant.personas.config import PersonaConfig
+
+RATE = 24000
+
+
+@pytest.mark.parametrize("style", ["beep", "chime", "blip"])
+@pytest.mark.parametrize("cue", CUES)
+def test_synthesize(style, cue):
+    audio = synthesize(style, cue, RATE)
+    assert audio.dtype == np.float32
+    assert 0.05 * RATE < len(audio) < 0.6 * RATE
+    assert np.max(np.abs(audio)) == pytest.approx(1.0, abs=1e-5)
+    assert abs(audio[0]) < 0.01 and abs(audio[-1]) < 0.01  # Faded in and out
+
+
+def test_styles_differ():
+    assert not np.array_equal(synthesize("beep", "wake", RATE)[:1000], synthesize("chime", "wake", RATE)[:1000])
+    assert len(synthesize("none", "wake", RATE)) == 0
+    assert np.array_equal(synthesize("kazoo", "wake", RATE), synthesize("blip", "wake", RATE))
+
+
+@pytest.mark.parametrize("old, new, cue", [
+    ("idle", "listening", "wake"),
+    ("listening", "thinking", "listening_end"),
+    ("interrupted", "thinking", "listening_end"),  # The user finished talking over a reply
+    ("speaking", "error", "error"),
+    ("speaking", "listening", None),  # Back to listening after a reply: no cue
+    ("thinking", "speaking", None),
+    ("error", "error", None),
+])
+def test_cue_for_transition(old, new, cue):
+    assert cue_for_transition(old, new) == cue
+
+
+class FakeAudio:
+    playback_rate = RATE
+
+    def __init__(self):
+        self.played = []
+
+    def play_cue(self, audio, gain=1.0):
+        self.played.append((len(audio), gain))
+
+
+def _persona(**earcons):
+    return PersonaConfig(name="HAL", earcons=earcons)
+
+
+class TestPlayer:
+    def test_persona_style_and_volume(self):
+        audio = FakeAudio()
+        player = EarconPlayer(audio, Config(), _persona(style="beep", volume=0.5))
+        assert player.on_state_change("idle", "listening") == "wake"
+        assert audio.played == [(len(synthesize("beep", "wake", RATE)), pytest.approx(0.5))]
+
+    def test_follows_persona(self):
+        audio = FakeAudio()
+        player = EarconPlayer(audio, Config(), _persona(style="beep"))
+        player.play("wake")
+        player.persona = _persona(style="chime")
+        player.play("wake")
+        assert [n for n, _ in audio.played] == [len(synthesize("beep", "wake", RATE)),
+                                                len(synthesize("chime", "wake", RATE))]
+
+    def test_off(self):
+        audio = FakeAudio()
+        assert not EarconPlayer(audio, Config(earcons_enabled=False)).play("wake")
+        assert not EarconPlayer(audio, Config(), _persona(style="none")).play("wake")
+        assert not EarconPlayer(audio, Config(), _persona(volume=0)).play("error")
+        assert not EarconPlayer(audio, Config()).play("sneeze")
+        assert audio.played == []
+
+    def test_default_style(self):
+        player = EarconPlayer(FakeAudio(), Config(), PersonaConfig(name="Boss"))
+        assert player.style() == "blip"
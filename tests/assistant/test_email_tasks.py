@@ -0,0 +1,100 @@
+"""
+Tests for events and deadlines proposed from incoming email (assistant/email_tasks.py).
+
+Covers:
+- Rule pass: confirmations with a time become events, without one reminders
+- Deadlines becoming tasks, and emails with nothing to propose
+- LLM review replacing rule proposals when available
+- Skipping what the planner already has, and emails proposed before
+- Accepting an event proposal into the calendar
+"""
+
+import asyncio
+import json
+from datetime import date
+from types import SimpleNamespace
+
+from assistant.email_tasks import EmailTaskDetector, detect_email_proposals, notice
+from assistant.followups import FollowUpStore
+from assistant.planner import PlannerData
+
+FRIDAY = date(2026, 10, 16)
+
+
+def email(content, subject="", sender="United Airlines", id="e1"):
+    return SimpleNamespace(id=id, channel="email", sender=sender, content=content, subject=subject,
+                           received_at="2026-10-16T08:00:00Z")
+
+
+FLIGHT = email("Your flight UA 523 to Denver departs Friday, October 23 at 9:40 AM from SFO. "
+               "Check-in opens 24 hours before departure.", "Your trip confirmation")
+
+
+class FakeAI:
+    def __init__(self, response):
+        self.response = response
+
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        return self.response
+
+
+def test_confirmations():
+    [flight] = detect_email_proposals(FLIGHT)
+    assert (flight.kind, flight.title) == ("event", "Flight: Your trip confirmation")
+    assert (flight.start_time, flight.end_time) == ("2026-10-23T09:40", "2026-10-23T11:40")
+    assert flight.source == "Email from United Airlines: Your trip confirmation" and flight.source_id == "e1"
+    assert notice(FLIGHT, flight) == ("📧 Flight confirmation detected from United Airlines - add to calendar? "
+                                      "Flight: Your trip confirmation 📅 Fri Oct 23 09:40 (confirm in Schedule)")
+
+    [dinner] = detect_email_proposals(email("Your reservation for 2 on 10/24 at 7:30pm is confirmed.",
+                                            "Re: Table booking", "Bistro"))
+    assert (dinner.title, dinner.start_time) == ("Reservation: Table booking", "2026-10-24T19:30")
+
+    [dentist] = detect_email_proposals(email("A reminder of your appointment on Nov 2.", "Appointment reminder"))
+    assert (dentist.kind, dentist.due_date, dentist.start_time) == ("task", "2026-11-02", None)
+
+
+def test_deadlines_and_nothing():
+    [timesheet] = detect_email_proposals(email("Hi! Please submit your timesheet by Friday. Thanks", "Timesheets"))
+    assert (timesheet.kind, timesheet.title, timesheet.due_date) == ("task", "Submit your timesheet", "2026-10-23")
+    [invoice] = detect_email_proposals(email("Your invoice #123 is due October 30.", "Invoice", "Billing"))
+    assert invoice.title == "Invoice (Billing)"
+
+    assert detect_email_proposals(email("Big sale ends Oct 20! 50% off", "Sale")) == []
+    assert detect_email_proposals(email("Your flight on October 2 was great?", "Survey")) == []  # Already past
+    assert detect_email_proposals(SimpleNamespace(**{**vars(FLIGHT), "channel": "sms"})) == []
+
+
+def test_llm_review():
+    reply = json.dumps([
+        {"kind": "event", "title": "Flight UA 523 to Denver", "date": "2026-10-23", "time": "09:40",
+         "minutes": 150, "location": "SFO"},
+        {"kind": "task", "title": "Old thing", "date": "2026-10-01"},
+    ])
+    [flight] = asyncio.run(EmailTaskDetector(FakeAI(reply)).detect(FLIGHT))
+    assert (flight.title, flight.end_time, flight.location, flight.detected_by) == (
+        "Flight UA 523 to Denver", "2026-10-23T12:10", "SFO", "llm")
+    # Unusable answers fall back to the rules
+    assert [p.detected_by for p in asyncio.run(EmailTaskDetector(FakeAI("no idea")).detect(FLIGHT))] == ["rules"]
+
+
+def test_dedup_and_accept(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    detector = EmailTaskDetector()
+    store = FollowUpStore(tmp_path / "followups")
+
+    [flight] = asyncio.run(detector.detect(FLIGHT, planner))
+    assert store.add([flight]) == [flight]
+    assert store.add(detect_email_proposals(FLIGHT)) == []  # Same email again
+    assert store.accept(flight.id, planner) == "✓ Added to calendar: Flight: Your trip confirmation (Fri Oct 23 09:40)"
+    [event] = planner.get_calendar_events(start_date="2026-10-23", end_date="2026-10-23")
+    assert (event.end_time, event.description) == ("2026-10-23T11:40", flight.source)
+
+    forwarded = email(FLIGHT.content, "Fwd: Your trip confirmation", sender="Sam", id="e2")
+    assert asyncio.run(detector.detect(forwarded, planner)) == []  # Already on the calendar
+
+    planner.add_task("Submit your timesheet", due_date="2026-10-23")
+    assert asyncio.run(detector.detect(email("Please submit your timesheet by Friday.", "Timesheets"), planner)) == []
@@ -0,0 +1,158 @@
+"""
+Tests for the emergency phrase (assistant/emergency.py).
+
+Covers:
+- Phrase validation, and settings from config (off by default, bad contacts rejected)
+- Only the phrase on its own triggers, never a sentence mentioning it
+- The countdown: cancel contacts nobody, "send it now" skips the wait, silence sends
+- The contacts saved on the server before the first alert; rounds repeat until a contact answers; standing down
+- Retrying when the server can't be reached, and resuming an active alert after a restart
+"""
+
+import asyncio
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.config import Config
+from assistant.emergency import Emergency, EmergencyError, EmergencySettings, validate_phrase
+
+NOW = datetime(2026, 10, 16, 21, 30)
+
+
+class FakeResponse:
+    def __init__(self, body):
+        self.body = body
+
+    def json(self):
+        return self.body
+
+
+class FakeClient:
+    """Answers like routes/emergency.js; `status` is what the next GET reports."""
+
+    def __init__(self, fail=False):
+        self.calls = []
+        self.fail = fail
+        self.status = "active"
+        self.rounds = 0
+
+    async def call(self, route, json=None, params=None):
+        self.calls.append((route.method, route.path, json or params))
+        if self.fail:
+            raise ConnectionError("server unreachable")
+        alert = {"id": "a1", "status": self.status, "rounds": self.rounds, "acknowledged_by": "Sam"}
+        if route.path.endswith("/repeat") or route.path == "/api/emergency/alerts":
+            self.rounds += 1
+            alert["rounds"] = self.rounds
+            return FakeResponse({"alert": alert, "results": [{"name": "Sam", "sms": "sent", "call": "sent"}]})
+        if route.path.endswith("/cancel"):
+            alert["status"] = "cancelled"
+        return FakeResponse({"alert": alert})
+
+
+def _config(**overrides):
+    values = dict(emergency_phrase="Red alert, red alert!", emergency_contacts=[{"name": "Sam", "phone": "+1 555 123 4567"}],
+                  emergency_confirm_seconds=10, user_name="Alex")
+    values.update(overrides)
+    return Config(**values)
+
+
+def _emergency(tmp_path, client=None, **overrides):
+    return Emergency(EmergencySettings.from_config(_config(**overrides)), client=client or FakeClient(),
+                     path=tmp_path / "emergency.json")
+
+
+@pytest.mark.parametrize("phrase, ok", [
+    ("red alert red alert", True), ("pineapple upside down", True),
+    ("help me", False), ("help help help", False), ("", False),
+])
+def test_validate_phrase(phrase, ok):
+    assert (validate_phrase(phrase) is None) is ok
+
+
+def test_settings_from_config():
+    assert EmergencySettings.from_config(Config()) is None
+    settings = EmergencySettings.from_config(_config())
+    assert settings.phrase == "red alert red alert" and settings.contacts[0].phone == "+15551234567"
+    for bad in (dict(emergency_contacts=[]), dict(emergency_contacts=[{"name": "Sam", "phone": "555"}]),
+                dict(emergency_methods=["pager"]), dict(emergency_phrase="help")):
+        with pytest.raises(EmergencyError):
+            EmergencySettings.from_config(_config(**bad))
+
+
+@pytest.mark.parametrize("text, matches", [
+    ("Red alert red alert", True), ("red alert, red alert. Red alert red alert!", True),
+    ("hey boss red alert red alert", True),
+    ("what's my emergency phrase? red alert red alert", False), ("if I say red alert red alert", False),
+    ("red alert", False), ("red alert red alert please", False),
+])
+def test_only_the_phrase_on_its_own(text, matches):
+    assert EmergencySettings.from_config(_config()).matches(text, ["hey boss"]) is matches
+
+
+def test_cancelled_countdown_contacts_nobody(tmp_path):
+    emergency = _emergency(tmp_path)
+    reply = asyncio.run(emergency.heard("red alert red alert", NOW))
+    assert reply == "Emergency. Texting and calling Sam in 10 seconds. Say cancel to stop."
+    assert emergency.wants("cancel") and not emergency.wants("what's the weather")
+    assert asyncio.run(emergency.heard("cancel, false alarm", NOW)) == "Cancelled. Nobody was contacted."
+    assert asyncio.run(emergency.tick(NOW + timedelta(minutes=1))) == [] and emergency.client.calls == []
+
+
+def test_send_now_and_rounds_until_answered(tmp_path):
+    emergency = _emergency(tmp_path)
+    client = emergency.client
+    asyncio.run(emergency.heard("red alert red alert", NOW))
+    assert asyncio.run(emergency.tick(NOW + timedelta(seconds=2))) == []
+    assert asyncio.run(emergency.heard("send it now", NOW + timedelta(seconds=3))) == "Sending now."
+    [sent] = asyncio.run(emergency.tick(NOW + timedelta(seconds=4)))
+    assert sent.startswith("Emergency alert sent to Sam. I'll keep trying every 5 minutes")
+    sam = {"name": "Sam", "phone": "+15551234567"}
+    assert client.calls[0] == ("PUT", "/api/emergency/contacts", {"contacts": [sam]})
+    method, path, payload = client.calls[1]
+    assert (method, path) == ("POST", "/api/emergency/alerts") and "user_id" not in payload
+    assert payload["message"].startswith("EMERGENCY: Alex said their emergency phrase at 21:30 on Friday 16 October.")
+    assert payload["contacts"] == [sam]
+    assert (tmp_path / "emergency.json").exists()
+
+    assert asyncio.run(emergency.tick(NOW + timedelta(seconds=40))) == []  # Status check only
+    assert client.calls[-1][:2] == ("GET", "/api/emergency/alerts/a1")
+    assert asyncio.run(emergency.tick(NOW + timedelta(minutes=6))) == ["Sent the emergency alert to Sam again (round 2)"]
+    client.status = "acknowledged"
+    assert asyncio.run(emergency.tick(NOW + timedelta(minutes=7))) == ["Sam got your emergency alert and is on it."]
+    assert emergency.state == "idle" and not (tmp_path / "emergency.json").exists()
+
+
+def test_silence_sends_and_stand_down(tmp_path):
+    emergency = _emergency(tmp_path)
+    asyncio.run(emergency.heard("red alert red alert", NOW))
+    assert asyncio.run(emergency.tick(NOW + timedelta(seconds=10)))[0].startswith("Emergency alert sent")
+    assert asyncio.run(emergency.heard("red alert red alert", NOW)) == "The alert is still going out to Sam."
+    assert emergency.wants("I'm okay")
+    reply = asyncio.run(emergency.heard("I'm okay, really", NOW + timedelta(minutes=1)))
+    assert reply == "Okay. I've called off the alert and told Sam you're okay."
+    method, path, payload = emergency.client.calls[-1]
+    assert path == "/api/emergency/alerts/a1/cancel" and payload["message"].startswith("All clear: Alex")
+    assert emergency.state == "idle" and not emergency.wants("I'm okay")
+
+
+def test_server_unreachable_retries(tmp_path):
+    emergency = _emergency(tmp_path, FakeClient(fail=True))
+    asyncio.run(emergency.heard("red alert red alert", NOW))
+    [reply] = asyncio.run(emergency.tick(NOW + timedelta(seconds=10)))
+    assert "Trying again in 15 seconds" in reply and emergency.state == "confirming"
+    assert asyncio.run(emergency.tick(NOW + timedelta(seconds=20))) == []
+    emergency.client.fail = False
+    assert asyncio.run(emergency.tick(NOW + timedelta(seconds=25)))[0].startswith("Emergency alert sent")
+
+
+def test_resumes_after_restart(tmp_path):
+    emergency = _emergency(tmp_path)
+    asyncio.run(emergency.heard("red alert red alert", NOW))
+    asyncio.run(emergency.tick(NOW + timedelta(seconds=10)))
+    restarted = _emergency(tmp_path)
+    assert restarted.state == "active" and restarted.alert_id == "a1"
+    restarted.client.status = "cancelled"  # Stood down from elsewhere
+    assert asyncio.run(restarted.tick(NOW + timedelta(minutes=1))) == []
+    assert restarted.state == "idle"
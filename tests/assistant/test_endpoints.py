@@ -0,0 +1,100 @@
+"""
+Tests for the typed server endpoint layer.
+
+Covers:
+- Path parameters percent-encoded into one segment, missing/unknown ones rejected
+- Every endpoint matching a route in the server's index.js
+- ApiClient.call naming breakers by route and retrying idempotent POSTs
+"""
+
+import asyncio
+import re
+from pathlib import Path
+
+import pytest
+
+from assistant import api_client, endpoints
+from assistant.api_client import ApiClient, ApiError, ApiPolicy
+
+INDEX_JS = Path(__file__).resolve().parents[2] / "packages" / "server" / "src" / "index.js"
+
+
+class FakeResponse:
+    def __init__(self, status_code, body=None):
+        self.status_code = status_code
+        self._body = body or {}
+        self.headers = {}
+        self.text = ""
+
+    def json(self):
+        return self._body
+
+
+class FakeTransport:
+    def __init__(self, *statuses):
+        self.statuses = list(statuses)
+        self.calls = []
+
+    async def request(self, method, path, **kwargs):
+        self.calls.append((method, path))
+        return FakeResponse(self.statuses.pop(0) if len(self.statuses) > 1 else self.statuses[0])
+
+
+def _server_routes():
+    """(method, matcher) for each route index.js dispatches on."""
+    source = INDEX_JS.read_text(encoding="utf-8")
+    routes = []
+    for exact, method in re.findall(r"path === '([^']+)' && request\.method === '(\w+)'", source):
+        routes.append((method, re.compile(re.escape(exact) + "$")))
+    for pattern, method in re.findall(r"path\.match\(/\^(.+?)\$/\) && request\.method === '(\w+)'", source):
+        routes.append((method, re.compile(pattern.replace("\\/", "/") + "$")))
+    return routes
+
+
+class TestEndpoint:
+    def test_parameters_are_one_encoded_segment(self):
+        route = endpoints.INBOX_REPLY(item_id="msg/1 ?x")
+        assert route.path == "/api/inbox/msg%2F1%20%3Fx/reply"
+        assert route.key == "POST /api/inbox/:item_id/reply"
+        assert endpoints.INBOX().path == "/api/inbox"
+
+    def test_missing_or_unknown_parameters_raise(self):
+        with pytest.raises(TypeError, match="missing item_id"):
+            endpoints.INBOX_ITEM()
+        with pytest.raises(TypeError, match="missing item_id"):
+            endpoints.INBOX_ITEM(item_id="")
+        with pytest.raises(TypeError, match="unexpected user_id"):
+            endpoints.INBOX(user_id="u1")
+
+    def test_every_endpoint_is_a_server_route(self):
+        routes = _server_routes()
+        assert routes
+        for endpoint in endpoints.ALL:
+            sample = endpoint.path(**{name: "id-1" for name in endpoint.params})
+            assert any(method == endpoint.method and matcher.match(sample) for method, matcher in routes), endpoint.key
+
+
+class TestCall:
+    def test_breaker_is_named_by_route(self):
+        api_client._breakers.clear()
+        transport = FakeTransport(200)
+        client = ApiClient("http://test", "token", client=transport)
+        asyncio.run(client.call(endpoints.INBOX_DRAFT(item_id="abc"), json={}))
+        assert transport.calls == [("POST", "/api/inbox/abc/draft")]
+        assert list(api_client._breakers) == ["http://test POST /api/inbox/:item_id/draft"]
+
+    def test_idempotent_post_is_retried(self):
+        async def sleep(delay):
+            pass
+
+        api_client._breakers.clear()
+        transport = FakeTransport(503, 200)
+        client = ApiClient("http://test", "token", policy=ApiPolicy(max_attempts=3), client=transport, sleep=sleep)
+        asyncio.run(client.call(endpoints.COMMAND_ACK(command_id="c1"), json={}))
+        assert len(transport.calls) == 2
+
+        transport = FakeTransport(503, 200)
+        client = ApiClient("http://test", "token", policy=ApiPolicy(max_attempts=3), client=transport, sleep=sleep)
+        with pytest.raises(ApiError):
+            asyncio.run(client.call(endpoints.INBOX_REPLY(item_id="c1"), json={}))
+        assert len(transport.calls) == 1
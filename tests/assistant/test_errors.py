@@ -0,0 +1,64 @@
+"""
+Tests for the core's error types (assistant/errors.py).
+
+Covers:
+- Every error derives from XswarmError and the built-in error it replaced
+- The scheduler raises SchedulerError for bad cron and UnknownJobError for a missing job
+- Config loading and saving raise ConfigError when strict, and fall back to defaults otherwise
+- Settings and thinking-model specs fail as ConfigError; the package re-exports the errors
+"""
+
+import asyncio
+from types import SimpleNamespace
+
+import pytest
+
+import assistant
+from assistant.config import Config
+from assistant.errors import ConfigError, MemoryStoreError, SchedulerError, UnknownJobError, VoiceError, XswarmError
+from assistant.governor import ResourceGovernor, ResourceUsage
+from assistant.memory import ModelConfig
+from assistant.migrations import SchemaTooNew
+from assistant.scheduler import CronSchedule, JobStateStore, Scheduler
+from assistant.voice_config import SETTINGS, SettingError, parse_value
+
+
+def test_hierarchy():
+    for error, builtin in ((ConfigError, ValueError), (SchedulerError, ValueError), (UnknownJobError, KeyError),
+                           (MemoryStoreError, RuntimeError), (VoiceError, RuntimeError), (SchemaTooNew, Exception)):
+        assert issubclass(error, XswarmError) and issubclass(error, builtin)
+    assert issubclass(SettingError, ConfigError)
+    assert assistant.ConfigError is ConfigError and "XswarmError" in assistant.__all__
+
+
+def test_scheduler_errors(tmp_path):
+    with pytest.raises(SchedulerError, match="5 fields"):
+        CronSchedule("* * *")
+    scheduler = Scheduler(governor=ResourceGovernor(sampler=lambda: ResourceUsage(), sample_interval=0),
+                          store=JobStateStore(tmp_path), config=SimpleNamespace(jobs={}))
+    with pytest.raises(SchedulerError, match="interval or a cron"):
+        scheduler.add_job("nightly", lambda: None)
+    with pytest.raises(UnknownJobError) as raised:
+        asyncio.run(scheduler.run_now("nope"))
+    assert str(raised.value) == "Unknown job 'nope'"
+
+
+def test_config_strict(tmp_path):
+    broken = tmp_path / "config.yaml"
+    broken.write_text("voice_enabled: [unclosed\n")
+    with pytest.raises(ConfigError, match="Can't load config"):
+        Config.load_from_file(broken, strict=True)
+    with pytest.raises(ConfigError, match="No config file"):
+        Config.load_from_file(tmp_path / "missing.yaml", strict=True)
+    assert Config.load_from_file(broken) == Config()  # The app's lenient default
+
+    with pytest.raises(ConfigError, match="Can't save config"):
+        Config().save_to_file(tmp_path, strict=True)  # A directory
+    Config().save_to_file(tmp_path)
+
+
+def test_setting_and_model_errors():
+    with pytest.raises(ConfigError, match="needs a number of seconds"):
+        parse_value(SETTINGS["follow_up_window"], "forever")
+    with pytest.raises(ConfigError, match="Unknown provider"):
+        ModelConfig.parse("ACME:model")
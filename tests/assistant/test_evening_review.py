@@ -0,0 +1,181 @@
+"""
+Tests for the end-of-day review conversation (assistant/evening_review.py).
+
+Covers:
+- Recognizing requests to start it
+- Picking tasks by name or position
+- Free slots around tomorrow's meetings and habits, and packing blocks
+- The whole flow: review, carry over, plan (keep/move/skip), journal
+- Cancelling part way, and days with nothing open or nothing to plan
+- Planned blocks surviving tomorrow's auto-scheduling, and the journal in
+  the next morning briefing
+"""
+
+from datetime import date, datetime, timedelta
+
+import pytest
+
+from assistant.evening_review import (
+    EveningReview,
+    free_slots,
+    is_review_request,
+    match_items,
+    propose_blocks,
+)
+from assistant.planner import PlannerData, PlanningSession, Task, TimeBlock
+
+TODAY = date.today()
+TOMORROW = TODAY + timedelta(days=1)
+
+
+def _planner(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    planner.add_project("Launch")  # Not a new user
+    return planner
+
+
+def _task(planner, title, minutes=30, **kwargs):
+    kwargs.setdefault("due_date", TODAY.isoformat())
+    kwargs.setdefault("status", "next")
+    return planner.add_task(title, duration_min=minutes, auto_schedule=False, **kwargs)
+
+
+def _meeting(planner, start, end, day=TOMORROW):
+    planner.add_calendar_event("Standup", f"{day}T{start}:00", f"{day}T{end}:00")
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("evening review", True), ("Let's do the evening review", True), ("end of day review", True),
+    ("review my day", True), ("plan tomorrow please", True), ("end-of-day", True),
+    ("what's my evening like", False), ("review the report tomorrow", False), ("", False),
+])
+def test_review_request(text, expected):
+    assert is_review_request(text) is expected
+
+
+def test_match_items():
+    titles = ["Write report", "Polish slides", "Email Sarah"]
+    assert match_items("the report and the slides", titles) == [0, 1]
+    assert match_items("just the first and the last one", titles) == [0, 2]
+    assert match_items("sarah", titles) == [2]
+    assert match_items("the dishes", titles) == []
+
+
+class TestPlanning:
+    def test_free_slots_around_meetings_and_habits(self, tmp_path):
+        planner = _planner(tmp_path)
+        _meeting(planner, "10:00", "11:00")
+        _meeting(planner, "11:00", "11:10")  # Back to back: no slot between
+        _meeting(planner, "17:50", "18:30")  # Leaves a 10-minute gap, too short
+        planner.add_habit("Walk", preferred_time="afternoon")
+        slots = free_slots(planner, TOMORROW, "09:00", "18:00")
+        assert slots == [(9 * 60, 10 * 60), (11 * 60 + 10, 14 * 60), (15 * 60, 17 * 60 + 50)]
+
+    def test_propose_blocks_first_fit(self):
+        tasks = [Task(id="a", title="Report", duration_min=90), Task(id="b", title="Slides", duration_min=45),
+                 Task(id="c", title="Email", duration_min=15)]
+        blocks = propose_blocks(tasks, [(9 * 60, 10 * 60), (11 * 60, 14 * 60)])
+        assert [(b.title, b.start, b.minutes) for b in blocks] == [
+            ("Slides", "09:00", 45), ("Email", "09:45", 15), ("Report", "11:00", 90),
+        ]
+
+
+class TestFlow:
+    def test_full_review(self, tmp_path):
+        planner = _planner(tmp_path)
+        done = _task(planner, "Pay invoice")
+        planner.complete_task(done.id, reschedule=False)
+        report = _task(planner, "Write report", minutes=60)
+        slides = _task(planner, "Polish slides", minutes=45)
+        dishes = _task(planner, "Clean garage")
+        _meeting(planner, "09:00", "10:00")
+        review = EveningReview(planner)
+
+        opening = review.start()
+        assert opening.startswith("Today you got through 1: Pay invoice. Still open: ")
+        assert "Write report, Polish slides and Clean garage" in opening
+        assert opening.endswith("Which should I carry over to tomorrow - all, none, or name them?")
+
+        assert review.handle("hmm").startswith("I didn't catch which ones.")
+        reply = review.handle("the report and the slides")
+        assert reply.startswith("Carrying over Write report and Polish slides. Here's a plan for tomorrow: ")
+        assert "10:00 Write report, 11:00 Polish slides, 11:45 Clean garage" in reply  # Undated next actions too
+        assert planner.get_task(report.id).due_date == TOMORROW.isoformat()
+        assert planner.get_task(dishes.id).due_date == ""  # Back in Next Actions, undated
+        assert review.state == "plan"
+
+        assert review.handle("slides at 2pm").startswith("Moved Polish slides to 14:00.")
+        assert review.handle("the dishes at noon").startswith("Sorry, I didn't follow.")
+        assert review.handle("sounds good").startswith("Saved tomorrow's plan.")
+        assert [(b.title, b.start) for b in planner.get_day_plan(TOMORROW.isoformat())] == [
+            ("Write report", "10:00"), ("Clean garage", "11:45"), ("Polish slides", "14:00"),
+        ]
+
+        assert review.handle("Good day, the invoice is finally paid.") == \
+            "Noted. Tomorrow starts with Write report at 10:00. Good night."
+        assert review.state == "done" and not review.is_active
+        entry = planner.get_journal_entries()[0]
+        assert entry.text == ("Good day, the invoice is finally paid. Finished 1 (Pay invoice); "
+                              "carried over Write report, Polish slides.")
+        assert planner.was_review_done_today()
+        assert slides.id in {b.task_id for b in planner.get_day_plan(TOMORROW.isoformat())}
+
+    def test_nothing_open_and_plan_skipped(self, tmp_path):
+        planner = _planner(tmp_path)
+        _task(planner, "Someday thing", due_date=None)
+        review = EveningReview(planner)
+        opening = review.start()
+        assert opening.startswith("Nothing got checked off today - that happens. Nothing is left open. Here's a plan")
+        assert review.handle("no").startswith("Okay, no plan")
+        assert planner.get_day_plan(TOMORROW.isoformat()) == []
+        assert review.handle("skip") == "All set. Good night."
+        assert planner.get_journal_entries()[0].text == "Nothing finished."
+
+    def test_nothing_to_plan_goes_straight_to_journal(self, tmp_path):
+        planner = _planner(tmp_path)
+        review = EveningReview(planner)
+        assert review.start().endswith("There's nothing to plan for tomorrow. "
+                                       "Last thing: anything you'd like me to note about today?")
+        assert review.state == "journal"
+
+    def test_cancel(self, tmp_path):
+        planner = _planner(tmp_path)
+        _task(planner, "Write report")
+        review = EveningReview(planner)
+        review.start()
+        assert review.handle("never mind") == "Okay, stopping the review here."
+        assert review.state == "cancelled" and not review.is_active
+        assert review.handle("all") == ""
+        assert not planner.was_review_done_today()
+
+
+class TestNextDay:
+    def test_planned_blocks_survive_auto_scheduling(self, tmp_path):
+        planner = _planner(tmp_path)
+        task = _task(planner, "Write report", minutes=60)
+        planner.set_day_plan(TODAY.isoformat(), [TimeBlock(task.id, "Write report", "17:30", 60)])
+        planner.auto_schedule_tasks(work_start="00:00", work_end="23:59")
+        assert planner.get_task(task.id).scheduled_time == "17:30"
+
+    def test_old_plans_are_dropped(self, tmp_path):
+        planner = _planner(tmp_path)
+        yesterday = (TODAY - timedelta(days=1)).isoformat()
+        planner.set_day_plan(yesterday, [TimeBlock("t", "Old", "09:00")])
+        planner.set_day_plan(TOMORROW.isoformat(), [TimeBlock("t", "New", "09:00")])
+        assert planner.get_day_plan(yesterday) == []
+        assert [b.title for b in planner.get_day_plan(TOMORROW.isoformat())] == ["New"]
+
+    def test_morning_briefing_reads_back_the_review(self, tmp_path):
+        planner = _planner(tmp_path)
+        task = _task(planner, "Write report")
+        planner.add_journal_entry("Shipped the beta. Finished 2.", (TODAY - timedelta(days=1)).isoformat())
+        planner.set_day_plan(TODAY.isoformat(), [TimeBlock(task.id, "Write report", "10:00")])
+        session = PlanningSession(planner)
+        context = session._build_returning_user_morning_context(planner.get_planning_summary())
+        assert "**Last night's journal:** Shipped the beta. Finished 2." in context
+        assert "- ten o'clock: Write report" in context
+
+
+def test_review_starts_tomorrow_from_now():
+    review = EveningReview(PlannerData.__new__(PlannerData), now=datetime(2026, 12, 31, 20, 0))
+    assert review.tomorrow == date(2027, 1, 1)
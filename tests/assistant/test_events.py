@@ -0,0 +1,129 @@
+"""
+Tests for the event history (assistant/events.py).
+
+Covers:
+- Recording and querying events by type and time
+- Parsing --since values
+- Recognizing "what did I miss?" and summarizing what came in
+- New inbox messages being recorded on sync
+"""
+
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.events import (
+    MISSED_DEFAULT, EventStore, is_missed_request, missed_since, parse_since, summarize_missed,
+)
+from assistant.inbox import InboxManager, InboxStore
+
+NOW = datetime(2026, 10, 14, 15, 30)  # A Wednesday
+
+
+@pytest.fixture
+def store(tmp_path):
+    return EventStore(tmp_path / "events.db", keep_days=None)
+
+
+class TestStore:
+    def test_query(self, store):
+        store.record("sms", "💬 Bob: running late", {"sender": "Bob"}, at=NOW - timedelta(days=2))
+        store.record("email", "📧 Dana: Invoice", {"sender": "Dana"}, at=NOW - timedelta(hours=3))
+        store.record("sms", "💬 Alice: lunch?", {"sender": "Alice"}, at=NOW - timedelta(hours=1))
+        assert [e.summary for e in store.query(["sms"])] == ["💬 Bob: running late", "💬 Alice: lunch?"]
+        assert [e.type for e in store.query(since=NOW - timedelta(days=1))] == ["email", "sms"]
+        assert [e.data["sender"] for e in store.query(limit=2)] == ["Dana", "Alice"]  # The newest two
+        assert store.query(["voice"]) == []
+
+    def test_survives_reopening(self, tmp_path):
+        EventStore(tmp_path / "events.db").record("activity", "✓ Synced", {"level": "success"})
+        events = EventStore(tmp_path / "events.db").query()
+        assert [(e.summary, e.data) for e in events] == [("✓ Synced", {"level": "success"})]
+
+    def test_prunes_old_events(self, tmp_path):
+        EventStore(tmp_path / "events.db").record("activity", "old", at=datetime.now() - timedelta(days=40))
+        assert EventStore(tmp_path / "events.db", keep_days=30).query() == []
+
+    def test_touch(self, store):
+        assert store.touch(NOW) is None
+        assert store.touch(NOW + timedelta(hours=2)) == NOW
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("yesterday", datetime(2026, 10, 13)),
+    ("today", datetime(2026, 10, 14)),
+    ("monday", datetime(2026, 10, 12)),
+    ("wednesday", datetime(2026, 10, 7)),  # Today's weekday means last week's
+    ("2h", NOW - timedelta(hours=2)),
+    ("3 days ago", NOW - timedelta(days=3)),
+    ("last week", NOW - timedelta(weeks=1)),
+    ("2026-10-01", datetime(2026, 10, 1)),
+])
+def test_parse_since(text, expected):
+    assert parse_since(text, NOW) == expected
+
+
+def test_parse_since_unknown():
+    with pytest.raises(ValueError):
+        parse_since("a while back", NOW)
+
+
+@pytest.mark.parametrize("text, missed", [
+    ("what did I miss?", True),
+    ("Jarvis, what have I missed", True),
+    ("anything new while I was away?", True),
+    ("catch me up", True),
+    ("catch me up on the Henderson project notes", False),
+    ("I missed the bus", False),
+])
+def test_is_missed_request(text, missed):
+    assert is_missed_request(text) == missed
+
+
+class TestSummary:
+    def test_nothing(self):
+        assert summarize_missed([]) == "Nothing new while you were away."
+
+    def test_messages_and_alerts(self, store):
+        store.record("sms", "💬 Bob: late", {"sender": "Bob"})
+        store.record("sms", "💬 Alice: lunch?", {"sender": "Alice"})
+        store.record("sms", "💬 Bob: here", {"sender": "Bob"})
+        store.record("voice", "📞 Mom: call me", {"sender": "Mom"})
+        store.record("activity", "📥 4 new inbox message(s)", {"level": "info"})
+        store.record("activity", "⚠ Calendar sync failed", {"level": "warning"})
+        assert summarize_missed(store.query()) == (
+            "While you were away: 3 texts from Bob and Alice, 1 voicemail from Mom. 1 alert: Calendar sync failed.")
+
+    def test_only_alerts(self, store):
+        store.record("activity", "✗ Voice server crashed", {"level": "error"})
+        store.record("activity", "⚠ Inbox sync failed", {"level": "warning"})
+        assert summarize_missed(store.query()) == (
+            "While you were away: no new messages. 2 alerts, the latest: Inbox sync failed.")
+
+    def test_since(self):
+        assert missed_since(None, NOW) == NOW - MISSED_DEFAULT
+        assert missed_since(NOW - timedelta(hours=2), NOW) == NOW - timedelta(hours=2)
+        assert missed_since(NOW - timedelta(days=30), NOW) == NOW - timedelta(days=7)
+
+
+class FakeInboxClient:
+    def __init__(self, items):
+        self.items = items
+
+    async def fetch(self, since=None):
+        return {"items": self.items, "synced_at": "2026-10-14T15:30:00"}
+
+    async def push_update(self, update):
+        return True
+
+
+@pytest.mark.asyncio
+async def test_inbox_sync_records_new_messages(tmp_path, store):
+    message = {"id": "msg-1", "channel": "sms", "sender": "Bob", "content": "Running late",
+               "received_at": "2026-10-14T15:00:00"}
+    manager = InboxManager(store=InboxStore(tmp_path / "inbox"), events=store)
+    manager.client = FakeInboxClient([message])
+    await manager.sync()
+    await manager.sync()  # Nothing new the second time
+    events = store.query()
+    assert [(e.type, e.summary, e.data["sender"]) for e in events] == [("sms", "💬 Bob: Running late", "Bob")]
@@ -0,0 +1,71 @@
+"""
+Tests for runtime feature flags (assistant/flags.py).
+
+Covers:
+- Layers: the default, the release channel, the server's overrides, then config.feature_flags
+- Server overrides fetched for the user, saved, and read back at the next start
+- Unknown flags: ignored in config and from the server, a KeyError in code
+- A subsystem behind a flag (the speech cache) off when the flag is
+"""
+
+import asyncio
+import types
+
+import pytest
+
+from assistant.flags import FeatureFlags, Flag, set_feature_flags
+from assistant.speech_cache import SpeechCache
+
+FLAGS = {flag.name: flag for flag in (
+    Flag("local_llm", "Local model fallback", True),
+    Flag("new_tts", "The new TTS engine", channels=("beta",)),
+)}
+
+
+@pytest.fixture(autouse=True)
+def reset():
+    yield
+    set_feature_flags(None)
+
+
+class FakeClient:
+    def __init__(self, flags):
+        self.flags = flags
+        self.calls = []
+
+    async def call(self, route, **kwargs):
+        self.calls.append((route.path, kwargs.get("params")))
+        return types.SimpleNamespace(json=lambda: {"flags": self.flags})
+
+
+def test_layers():
+    stable = FeatureFlags(flags=FLAGS)
+    assert [(flag.name, on, source) for flag, on, source in stable.rows()] == [
+        ("local_llm", True, "default"), ("new_tts", False, "default")]
+    assert FeatureFlags("beta", flags=FLAGS).state("new_tts") == (True, "channel")
+
+    flags = FeatureFlags("beta", local={"local_llm": True, "typo": True}, flags=FLAGS)
+    assert flags.set_server({"local_llm": False, "new_tts": False}) == ["new_tts"]
+    assert flags.state("new_tts") == (False, "server") and flags.state("local_llm") == (True, "config")
+    with pytest.raises(KeyError, match="Unknown feature flag 'tts'"):
+        flags.enabled("tts")
+
+
+def test_server_overrides_kept(tmp_path):
+    path = tmp_path / "flags.json"
+    flags = FeatureFlags(path=path, flags=FLAGS)
+    client = FakeClient({"new_tts": True, "retired_flag": False})
+    assert asyncio.run(flags.sync(client, "u1")) == ["new_tts"]
+    assert client.calls == [("/api/flags", {"user_id": "u1"})]
+    assert FeatureFlags(path=path, flags=FLAGS).state("new_tts") == (True, "server")  # The next start
+
+    path.write_text("not json")
+    assert FeatureFlags(path=path, flags=FLAGS).state("new_tts") == (False, "default")
+
+
+def test_gated_subsystem():
+    config = types.SimpleNamespace(speech_cache=True, speech_cache_mb=1, speech_cache_after=2,
+                                   feature_flags={"speech_cache": False})
+    assert SpeechCache.from_config(config) is not None
+    set_feature_flags(FeatureFlags.from_config(config, path=None))
+    assert SpeechCache.from_config(config) is None
@@ -0,0 +1,186 @@
+"""
+Tests for the guided dialog engine (assistant/flows.py).
+
+Covers:
+- Parsing each slot type, with hints for unusable answers
+- Running a flow: prompts, branching, validation, retries and skipping
+- Cancelling, repeating and timing out
+- The registry and the built-in new_task flow
+"""
+
+from datetime import date
+
+import pytest
+
+from assistant.flows import (
+    Flow,
+    FlowRun,
+    Step,
+    get_flow,
+    match_flow,
+    new_task_flow,
+    parse_slot,
+)
+from assistant.planner import PlannerData
+
+TODAY = date(2026, 10, 16)  # A Friday
+
+
+@pytest.mark.parametrize("slot, answer, value", [
+    ("text", "  Buy   milk ", "Buy milk"),
+    ("yes_no", "Yeah, go on", True),
+    ("yes_no", "no, that's fine", False),
+    ("number", "about 45 minutes", 45),
+    ("number", "twenty five", 25),
+    ("number", "1.5", 1.5),
+    ("time", "half past two in the afternoon", "14:30"),
+    ("date", "next tuesday", date(2026, 10, 20)),
+    ("choice", "the second one", "Work"),
+    ("choice", "home please", "Home"),
+])
+def test_parse_slot(slot, answer, value):
+    step = Step("x", "?", slot=slot, choices=["Home", "Work", "Errands"])
+    assert parse_slot(step, answer, today=TODAY) == (value, None)
+
+
+@pytest.mark.parametrize("slot, answer, hint", [
+    ("text", "   ", "I didn't catch that."),
+    ("yes_no", "maybe", "Please answer yes or no."),
+    ("number", "lots", "I need a number."),
+    ("time", "whenever", "I didn't get a time - try something like 'half past two'."),
+    ("choice", "the garden", "Please pick one of: Home, Work, Errands."),
+])
+def test_parse_slot_hints(slot, answer, hint):
+    step = Step("x", "?", slot=slot, choices=["Home", "Work", "Errands"])
+    assert parse_slot(step, answer, today=TODAY) == (None, hint)
+
+
+def test_ambiguous_date_asks_which():
+    value, hint = parse_slot(Step("x", "?", slot="date"), "4/5/2027", today=TODAY)
+    assert value is None and hint.startswith("Did you mean ")
+
+
+def test_unknown_slot_type():
+    with pytest.raises(ValueError):
+        Step("x", "?", slot="colour")
+
+
+class Clock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self):
+        return self.now
+
+
+def _trip_flow(done):
+    def complete(values):
+        done.append(dict(values))
+        return f"Booked {values['where']}."
+
+    return Flow("trip", [
+        Step("where", "Where to?", slot="choice", choices=["Home", "Work", "Airport"]),
+        Step("bags", "Any bags?", slot="yes_no", next=lambda v: "count" if v["bags"] else None),
+        Step("count", lambda v: f"How many bags for the {v['where'].lower()}?", slot="number",
+             validate=lambda n, _v: None if 1 <= n <= 4 else "Up to four bags."),
+    ], on_complete=complete, intro="Let's book a ride.")
+
+
+class TestRun:
+    def test_branches_validates_and_completes(self):
+        done = []
+        run = FlowRun(_trip_flow(done))
+        assert run.start() == "Let's book a ride. Where to?"
+        assert run.handle("the airport") == "Any bags?"
+        assert run.handle("yes please") == "How many bags for the airport?"
+        assert run.handle("nine") == "Up to four bags. How many bags for the airport?"
+        assert run.handle("two") == "Booked Airport."
+        assert run.state == "done" and not run.is_active
+        assert done == [{"where": "Airport", "bags": True, "count": 2}]
+        assert run.handle("three") == ""
+
+    def test_branch_can_finish_early(self):
+        done = []
+        run = FlowRun(_trip_flow(done))
+        run.start()
+        run.handle("home")
+        assert run.handle("no") == "Booked Home."
+        assert done == [{"where": "Home", "bags": False}]
+
+    def test_gives_up_after_retries(self):
+        run = FlowRun(_trip_flow([]))
+        run.start()
+        assert run.handle("the moon").endswith("Where to?")
+        assert run.handle("mars").endswith("Where to?")
+        assert run.handle("venus") == "Let's leave that for now - start again whenever you're ready."
+        assert run.state == "failed"
+
+    def test_cancel_and_repeat(self):
+        run = FlowRun(_trip_flow([]))
+        run.start()
+        assert run.handle("Repeat that?") == "Where to?"
+        assert run.handle("Never mind.") == "Okay, cancelled."
+        assert run.state == "cancelled"
+
+    def test_times_out(self):
+        clock = Clock()
+        run = FlowRun(_trip_flow([]), clock=clock)
+        run.start()
+        clock.now = 100
+        assert not run.expired()
+        run.handle("work")  # An answer resets the timer
+        clock.now = 219
+        assert not run.expired()
+        clock.now = 221
+        assert run.expired() and run.state == "expired" and not run.is_active
+
+    def test_failing_completion(self):
+        def broken(_values):
+            raise OSError("disk full")
+
+        run = FlowRun(Flow("x", [Step("name", "Name?")], on_complete=broken))
+        run.start()
+        assert run.handle("Sam") == "Sorry, that didn't work: disk full"
+        assert run.state == "failed"
+
+
+class TestNewTask:
+    @pytest.mark.parametrize("text, name", [
+        ("add a task", "new_task"), ("Create a new task please.", "new_task"), ("new task", "new_task"),
+        ("add a task to buy milk", None), ("what's my next task", None),
+    ])
+    def test_triggers(self, text, name):
+        assert match_flow(text) == name
+
+    def test_registry(self):
+        assert get_flow("new_task").name == "new_task"
+        assert get_flow("nope") is None
+
+    def test_guided_capture(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        run = FlowRun(new_task_flow(planner, today=TODAY), today=TODAY)
+        assert run.start() == "What's the task?"
+        assert run.handle("Renew passport") == "When is it due? Say 'skip' if it isn't."
+        assert run.handle("next tuesday") == "Roughly how many minutes will it take?"
+        assert run.handle("a day").startswith("I need a number.")
+        assert run.handle("2").startswith("Somewhere between 5 minutes and 8 hours")
+        assert run.handle("forty") == "Added Renew passport, due Tuesday."
+        task = planner.get_tasks()[0]
+        assert (task.title, task.due_date, task.duration_min) == ("Renew passport", "2026-10-20", 40)
+
+    def test_optional_steps_skip(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        run = FlowRun(new_task_flow(planner, today=TODAY), today=TODAY)
+        run.start()
+        run.handle("Call the bank")
+        run.handle("skip")
+        assert run.handle("doesn't matter") == "Added Call the bank."
+        task = planner.get_tasks()[0]
+        assert (task.due_date, task.duration_min) == (None, 30)
+
+    def test_cancel_adds_nothing(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        run = FlowRun(new_task_flow(planner))
+        run.start()
+        assert run.handle("cancel") == "Okay, no task added."
+        assert planner.get_tasks() == []
@@ -0,0 +1,118 @@
+"""
+Tests for follow-up detection from conversation.
+
+Covers:
+- Rule pass: commitments, deadlines, recipients and hedges
+- LLM review replacing rule candidates when available
+- Store: de-duplication and accept/dismiss into the planner
+"""
+
+import asyncio
+from datetime import date
+
+import pytest
+
+from assistant.followups import (
+    FollowUpDetector,
+    FollowUpStore,
+    detect_followups,
+    resolve_deadline,
+)
+from assistant.planner import PlannerData
+
+WEDNESDAY = date(2026, 10, 14)
+
+
+class FakeAI:
+    def __init__(self, response):
+        self.response = response
+        self.calls = 0
+
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        self.calls += 1
+        return self.response
+
+
+class TestResolveDeadline:
+    @pytest.mark.parametrize("phrase,expected", [
+        ("tomorrow", "2026-10-15"),
+        ("friday", "2026-10-16"),
+        ("next monday", "2026-10-19"),
+        ("wednesday", "2026-10-21"),
+        ("end of week", "2026-10-16"),
+        ("next week", "2026-10-19"),
+        ("end of month", "2026-10-31"),
+    ])
+    def test_phrases(self, phrase, expected):
+        assert resolve_deadline(phrase, WEDNESDAY) == expected
+
+
+class TestDetectFollowups:
+    def test_commitment_with_recipient_and_deadline(self):
+        [proposal] = detect_followups("Sure. I'll send that to Sarah by Friday.", WEDNESDAY)
+        assert proposal.title == "Send that to Sarah"
+        assert proposal.kind == "commitment"
+        assert proposal.to_whom == "Sarah"
+        assert proposal.due_date == "2026-10-16"
+
+    def test_task_without_deadline(self):
+        [proposal] = detect_followups("remind me to buy milk", WEDNESDAY)
+        assert proposal.title == "Buy milk"
+        assert proposal.kind == "task"
+        assert proposal.due_date is None
+
+    @pytest.mark.parametrize("text", ["I'll think about it", "I'll be there", "I should probably sleep", "sounds good"])
+    def test_hedges_and_chatter_ignored(self, text):
+        assert detect_followups(text, WEDNESDAY) == []
+
+
+class TestFollowUpDetector:
+    def test_llm_review_replaces_rule_candidates(self):
+        ai = FakeAI('[{"title": "Email Bob the deck", "to_whom": "Bob", "due_date": "thursday"}]')
+        detector = FollowUpDetector(ai)
+
+        proposals = asyncio.run(detector.detect("I'll email Bob the deck before Thursday", today=WEDNESDAY))
+
+        assert ai.calls == 1
+        assert [(p.title, p.kind, p.due_date, p.detected_by) for p in proposals] == [
+            ("Email Bob the deck", "commitment", "2026-10-15", "llm"),
+        ]
+
+    def test_llm_not_called_for_plain_chatter(self):
+        ai = FakeAI("[]")
+        assert asyncio.run(FollowUpDetector(ai).detect("What's the weather like?")) == []
+        assert ai.calls == 0
+
+    def test_falls_back_to_rules_on_bad_llm_output(self):
+        ai = FakeAI("not json [")
+        proposals = asyncio.run(FollowUpDetector(ai).detect("I need to pay rent tomorrow", today=WEDNESDAY))
+        assert [p.title for p in proposals] == ["Pay rent"]
+
+
+class TestFollowUpStore:
+    def test_duplicates_skipped_and_accept_creates_commitment(self, tmp_path):
+        store = FollowUpStore(tmp_path / "followups")
+        planner = PlannerData(tmp_path / "planner")
+        proposals = detect_followups("I'll send the report to Ana by Friday", WEDNESDAY)
+
+        assert len(store.add(proposals)) == 1
+        assert store.add(detect_followups("I'll send the report to Ana by Friday", WEDNESDAY)) == []
+
+        result = store.accept(proposals[0].id, planner)
+        assert result.startswith("✓ Commitment to Ana")
+        assert [c.to_whom for c in planner.get_commitments()] == ["Ana"]
+        assert store.pending() == []
+
+    def test_dismiss_creates_nothing(self, tmp_path):
+        store = FollowUpStore(tmp_path / "followups")
+        planner = PlannerData(tmp_path / "planner")
+        [proposal] = store.add(detect_followups("let me fix the login bug", WEDNESDAY))
+
+        store.dismiss(proposal.id)
+
+        assert store.pending() == []
+        assert store.accept(proposal.id, planner) is None
+        assert planner.get_tasks() == []
@@ -0,0 +1,178 @@
+"""
+Tests for event location geocoding (assistant/geocoding.py).
+
+Covers:
+- Map links with and without coordinates, per provider
+- Distances and travel-time estimates
+- Travel-time conflicts between back-to-back events in different places
+- The geocode_locations job storing coordinates, with a cache that also
+  remembers misses
+- Changing an event's location clearing its coordinates
+- Server sync sending coordinates, and the schedule tool flagging conflicts
+"""
+
+import asyncio
+import json
+from datetime import date
+
+from assistant import dates, geocoding, tools
+from assistant.config import Config
+from assistant.dates import DateSettings
+from assistant.geocoding import (
+    CachedGeocoder,
+    GeoPoint,
+    LocationSettings,
+    StaticGeocoder,
+    distance_km,
+    event_map_link,
+    geocode_events,
+    map_link,
+    travel_conflicts,
+    travel_minutes,
+)
+from assistant.planner import PlannerData
+from assistant.scheduler_client import appointment_payload
+
+OFFICE = (51.5074, -0.1278)  # Central London
+AIRPORT = (51.4700, -0.4543)  # Heathrow, ~23 km west
+CAFE = (51.5080, -0.1270)  # Around the corner from the office
+PLACES = {"Office": OFFICE, "Heathrow": AIRPORT, "Cafe": CAFE}
+
+
+def _planner(tmp_path, monkeypatch):
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    monkeypatch.setattr(dates, "_settings", DateSettings())
+    monkeypatch.setattr(geocoding, "_settings", LocationSettings())
+    return planner
+
+
+def _event(planner, title, start, end, location):
+    day = date.today().isoformat()
+    return planner.add_calendar_event(title, f"{day}T{start}:00", f"{day}T{end}:00", location=location)
+
+
+def _geocode(planner, tmp_path):
+    geocoder = CachedGeocoder(StaticGeocoder(PLACES), tmp_path / "cache.json")
+    return asyncio.run(geocode_events(planner, geocoder))
+
+
+class TestMapLinks:
+    def test_pin_when_located(self):
+        assert map_link("Office", *OFFICE) == \
+            "https://www.openstreetmap.org/?mlat=51.5074&mlon=-0.1278#map=17/51.5074/-0.1278"
+        assert map_link("Office", *OFFICE, provider="google") == \
+            "https://www.google.com/maps/search/?api=1&query=51.5074,-0.1278"
+        assert map_link("Office", *OFFICE, provider="apple") == "https://maps.apple.com/?ll=51.5074,-0.1278&q=Office"
+
+    def test_search_when_not_located(self):
+        assert map_link("10 Main St") == "https://www.openstreetmap.org/search?query=10+Main+St"
+        assert map_link("10 Main St", provider="google") == "https://www.google.com/maps/search/?api=1&query=10+Main+St"
+
+    def test_provider_from_config(self, monkeypatch):
+        monkeypatch.setattr(geocoding, "_settings", LocationSettings.from_config(Config(map_provider="apple")))
+        assert map_link("Cafe").startswith("https://maps.apple.com/")
+        assert LocationSettings.from_config(Config(map_provider="bing")).map_provider == "openstreetmap"
+
+    def test_event_without_location_has_no_link(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        assert event_map_link(_event(planner, "Call", "09:00", "09:30", "")) is None
+
+
+class TestTravel:
+    def test_distance(self):
+        assert 22 < distance_km(GeoPoint(*OFFICE), GeoPoint(*AIRPORT)) < 24
+        assert distance_km(GeoPoint(*OFFICE), GeoPoint(*OFFICE)) == 0
+
+    def test_travel_minutes(self):
+        # ~23 km * 1.3 road factor at 30 km/h is ~60 minutes, plus 5 to get going
+        assert 60 < travel_minutes(GeoPoint(*OFFICE), GeoPoint(*AIRPORT)) < 70
+        assert travel_minutes(GeoPoint(*OFFICE), GeoPoint(*AIRPORT), speed_kmh=60) < 40
+        assert travel_minutes(GeoPoint(*OFFICE), GeoPoint(*CAFE)) == 0  # Same place
+
+    def test_conflict_when_gap_too_short(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        _event(planner, "Standup", "09:00", "09:30", "Office")
+        _event(planner, "Flight check-in", "10:00", "11:00", "Heathrow")
+        _event(planner, "Coffee", "09:30", "09:45", "Cafe")  # Around the corner: no travel needed
+        _geocode(planner, tmp_path)
+
+        conflicts = travel_conflicts(planner.get_todays_events())
+        assert len(conflicts) == 1
+        assert (conflicts[0].before.title, conflicts[0].after.title) == ("Coffee", "Flight check-in")
+        assert conflicts[0].gap_minutes == 15
+        assert conflicts[0].text.startswith("Coffee → Flight check-in: ~")
+        assert conflicts[0].text.endswith("min between them")
+
+    def test_no_conflict_with_time_to_travel_or_no_coordinates(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        _event(planner, "Standup", "09:00", "09:30", "Office")
+        _event(planner, "Flight check-in", "11:00", "12:00", "Heathrow")
+        _event(planner, "Lunch", "12:00", "13:00", "Somewhere unknown")
+        _geocode(planner, tmp_path)
+        assert travel_conflicts(planner.get_todays_events()) == []
+
+    def test_schedule_tool_flags_conflicts(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        _event(planner, "Standup", "09:00", "09:30", "Office")
+        _event(planner, "Flight check-in", "09:45", "11:00", "Heathrow")
+        _geocode(planner, tmp_path)
+        assert "⚠ Travel: Standup → Flight check-in: ~" in tools.get_todays_schedule()
+
+
+class TestGeocodingJob:
+    def test_stores_coordinates_and_caches_misses(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        _event(planner, "Standup", "09:00", "09:30", "office")
+        _event(planner, "Lunch", "12:00", "13:00", "Nowhere")
+        _event(planner, "Call", "15:00", "15:30", "")
+
+        assert _geocode(planner, tmp_path) == 1
+        standup = next(e for e in planner.get_todays_events() if e.title == "Standup")
+        assert (standup.latitude, standup.longitude) == OFFICE
+        assert event_map_link(standup).startswith("https://www.openstreetmap.org/?mlat=51.5074")
+
+        cache = json.loads((tmp_path / "cache.json").read_text())
+        assert cache["office"][:2] == list(OFFICE)
+        assert cache["nowhere"] is None
+
+    def test_cache_answers_without_asking_again(self, tmp_path):
+        class Counting(StaticGeocoder):
+            calls = 0
+
+            async def geocode(self, query):
+                Counting.calls += 1
+                return await super().geocode(query)
+
+        cached = CachedGeocoder(Counting(PLACES), tmp_path / "cache.json")
+        for _ in range(3):
+            asyncio.run(cached.geocode("Office"))
+            asyncio.run(cached.geocode("Nowhere"))
+        assert Counting.calls == 2
+        reloaded = CachedGeocoder(Counting(PLACES), tmp_path / "cache.json")
+        assert asyncio.run(reloaded.geocode("  OFFICE ")) == GeoPoint(*OFFICE, "Office")
+        assert Counting.calls == 2
+
+    def test_new_location_clears_coordinates(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        event = _event(planner, "Standup", "09:00", "09:30", "Office")
+        _geocode(planner, tmp_path)
+
+        assert planner.update_calendar_event(event.id, title="Daily standup").latitude == OFFICE[0]
+        moved = planner.update_calendar_event(event.id, location="Heathrow")
+        assert (moved.latitude, moved.longitude) == (None, None)
+        _geocode(planner, tmp_path)
+        assert planner.get_todays_events()[0].latitude == AIRPORT[0]
+
+    def test_geocoder_from_config(self):
+        assert LocationSettings.from_config(Config(geocoder="none")).make_geocoder() is None
+        geocoder = LocationSettings.from_config(Config(geocoder_url="http://localhost:8080/")).make_geocoder()
+        assert isinstance(geocoder, CachedGeocoder)
+        assert geocoder.inner.base_url == "http://localhost:8080"
+
+    def test_server_gets_coordinates(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch)
+        _event(planner, "Standup", "09:00", "09:30", "Office")
+        _geocode(planner, tmp_path)
+        payload = appointment_payload(planner.get_todays_events()[0])
+        assert (payload["location"], payload["latitude"], payload["longitude"]) == ("Office", *OFFICE)
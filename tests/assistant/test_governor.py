@@ -0,0 +1,130 @@
+"""
+Tests for the resource governor.
+
+Covers:
+- Non-critical jobs are deferred while the machine is busy; critical jobs never are
+- xswarm's own CPU/RAM ceilings
+- Hysteresis on release, and the max-deferral starvation guard
+- Scheduler tasks stay due while deferred
+"""
+
+import asyncio
+
+from assistant import governor as governor_module
+from assistant.governor import ResourceGovernor, ResourceUsage
+from assistant.scheduler import Scheduler
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+def _governor(**kwargs):
+    clock = FakeClock()
+    usage = ResourceUsage(cpu_percent=5, rss_mb=300, system_cpu_percent=20, system_memory_percent=50)
+    gov = ResourceGovernor(sampler=lambda: usage, clock=clock, sample_interval=0, **kwargs)
+    return gov, usage, clock
+
+
+class TestAllow:
+    def test_idle_machine_allows_everything(self):
+        gov, _, _ = _governor()
+        assert gov.allow("memory_consolidation")
+        assert gov.allow("email_check")
+        assert not gov.throttled
+
+    def test_busy_machine_defers_non_critical_only(self):
+        gov, usage, _ = _governor(busy_cpu_percent=80)
+        usage.system_cpu_percent = 95
+
+        assert not gov.allow("document_indexing")
+        assert not gov.allow("calendar_sync")
+        assert gov.allow("email_check")
+        assert gov.deferrals == {"document_indexing": 1, "calendar_sync": 1}
+        assert gov.reasons == ["machine busy (CPU 95%)"]
+
+    def test_own_ceilings(self):
+        gov, usage, _ = _governor(cpu_ceiling=50, rss_ceiling_mb=1000)
+        usage.cpu_percent = 70
+        usage.rss_mb = 1500
+
+        assert not gov.allow("memory_consolidation")
+        assert gov.reasons == ["xswarm CPU 70%", "xswarm memory 1500MB"]
+
+    def test_rss_ceiling_can_be_disabled(self):
+        gov, usage, _ = _governor(rss_ceiling_mb=None)
+        usage.rss_mb = 100000
+        assert gov.allow("memory_consolidation")
+
+    def test_hysteresis(self):
+        gov, usage, _ = _governor(busy_cpu_percent=80)
+        usage.system_cpu_percent = 85
+        assert not gov.allow("inbox_sync")
+
+        # Under the ceiling but not under 80% of it: still throttled
+        usage.system_cpu_percent = 70
+        assert not gov.allow("inbox_sync")
+
+        usage.system_cpu_percent = 60
+        assert gov.allow("inbox_sync")
+
+    def test_max_defer_runs_job_anyway(self):
+        gov, usage, clock = _governor()
+        usage.system_cpu_percent = 99
+        assert not gov.allow("calendar_sync")
+
+        clock.now += governor_module.MAX_DEFER - 1
+        assert not gov.allow("calendar_sync")
+        clock.now += 1
+        assert gov.allow("calendar_sync")
+        # The wait starts over after a forced run
+        assert not gov.allow("calendar_sync")
+
+    def test_sampler_failure_keeps_last_usage(self):
+        clock = FakeClock()
+
+        def broken():
+            raise RuntimeError("no psutil")
+
+        gov = ResourceGovernor(sampler=broken, clock=clock)
+        gov.sample()
+        assert gov.allow("memory_consolidation")
+
+    def test_status_line(self):
+        gov, usage, _ = _governor()
+        gov.sample()
+        assert gov.status_line() == "⚙ 5% CPU · 300MB"
+        usage.system_cpu_percent = 99
+        gov.sample()
+        assert gov.status_line().endswith("⏸ throttled")
+
+
+class TestScheduler:
+    def test_deferred_task_stays_due(self):
+        ran = []
+
+        class Engine:
+            async def process_scheduled_task(self, name, context):
+                ran.append(name)
+
+        gov, usage, _ = _governor()
+        usage.system_cpu_percent = 99
+        scheduler = Scheduler(Engine(), governor=gov)
+        for name, task in scheduler.tasks.items():
+            task.active_after_hour = task.active_before_hour = None
+
+        async def tick():
+            scheduler.running = True
+            loop = asyncio.create_task(scheduler._loop())
+            await asyncio.sleep(0.05)
+            scheduler.running = False
+            loop.cancel()
+
+        asyncio.run(tick())
+        assert "email_check" in ran
+        assert "memory_consolidation" not in ran
+        assert scheduler.tasks["memory_consolidation"].last_run == 0.0
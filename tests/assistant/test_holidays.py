@@ -0,0 +1,98 @@
+"""
+Tests for holiday and observance calendars (assistant/holidays.py).
+
+Covers:
+- Each kind of rule: fixed dates, nth and last weekdays, "on or before", from Easter
+- Weekend days off observed on a weekday (nearest in the US, the next free one elsewhere)
+- Custom holidays, unknown regions and no region
+- Booking on a holiday warns; the briefing mentions the coming ones
+- Recurring events with skip_holidays leave out days off, unless an occurrence was changed
+"""
+
+from datetime import date, datetime
+
+import pytest
+
+from assistant import holidays, tools
+from assistant.announcements import briefing_context
+from assistant.holidays import HolidayCalendar, easter, rule_date, upcoming_holidays
+from assistant.planner import PlannerData
+
+TODAY = date(2026, 11, 20)  # A Friday
+
+
+@pytest.fixture
+def planner(tmp_path, monkeypatch):
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    monkeypatch.setattr(holidays, "_calendar", HolidayCalendar("US"))
+    return planner
+
+
+@pytest.mark.parametrize("rule, year, expected", [
+    ("12-25", 2026, "2026-12-25"),
+    ("11/Thu/4", 2026, "2026-11-26"),  # Thanksgiving
+    ("05/Mon/-1", 2026, "2026-05-25"),  # Memorial Day
+    ("12/Mon/-1", 2026, "2026-12-28"),
+    ("05/Mon/<=24", 2026, "2026-05-18"),  # Victoria Day
+    ("05/Mon/<=24", 2027, "2027-05-24"),
+    ("easter", 2026, "2026-04-05"),
+    ("easter-2", 2027, "2027-03-26"),  # Good Friday
+    ("easter+1", 2024, "2024-04-01"),
+])
+def test_rules(rule, year, expected):
+    assert rule_date(rule, year).isoformat() == expected
+
+
+def test_easter():
+    assert [easter(year).isoformat() for year in (2000, 2019, 2025, 2038)] == [
+        "2000-04-23", "2019-04-21", "2025-04-20", "2038-04-25"]
+
+
+def test_observed_days():
+    us = {h.day.isoformat(): h.name for h in HolidayCalendar("US").year(2026)}
+    assert us["2026-07-03"] == "Independence Day (observed)"  # July 4th is a Saturday
+    assert HolidayCalendar("US").on(date(2026, 7, 4))[0].name == "Independence Day"
+    # Christmas on a Saturday and Boxing Day on a Sunday: Monday and Tuesday off
+    gb = {h.day.isoformat(): h.name for h in HolidayCalendar("UK").year(2027)}
+    assert (gb["2027-12-27"], gb["2027-12-28"]) == ("Christmas Day (observed)", "Boxing Day (observed)")
+    assert "2027-12-27" in HolidayCalendar("GB").days_off(date(2027, 12, 20), date(2027, 12, 31))
+    assert "2026-05-10" not in HolidayCalendar("US").days_off(date(2026, 5, 1), date(2026, 5, 31))  # Mother's Day
+
+
+def test_custom_and_no_region():
+    calendar = HolidayCalendar("", {"07-14": "Company picnic", "2026-12-28": "Office closed", "soon": "?"})
+    assert [(h.day.isoformat(), h.name) for h in calendar.between(date(2026, 1, 1), date(2027, 12, 31))] == [
+        ("2026-07-14", "Company picnic"), ("2026-12-28", "Office closed"), ("2027-07-14", "Company picnic")]
+    assert HolidayCalendar("Narnia").region is None
+
+
+def test_upcoming():
+    assert upcoming_holidays(TODAY, HolidayCalendar("US")) == "Thursday is Thanksgiving."
+    assert upcoming_holidays(date(2026, 12, 20), HolidayCalendar("US")) == (
+        "Thursday is Christmas Eve and Friday is Christmas Day.")
+    assert upcoming_holidays(date(2026, 3, 2), HolidayCalendar("US")) == ""
+    context = briefing_context(datetime(2026, 11, 20, 7), holidays="Thursday is Thanksgiving.")
+    assert context["briefing"] == "There's nothing on the calendar today. Thursday is Thanksgiving."
+    assert briefing_context(datetime(2026, 11, 20, 7))["holidays"] == "no holidays coming up"
+
+
+def test_booking_warns(planner):
+    assert tools.add_calendar_event("Review", "2026-11-26", "10:00").endswith("\n⚠ That's Thanksgiving")
+    assert "⚠" not in tools.add_calendar_event("Review", "2026-11-25", "10:00")
+
+
+def test_skip_holidays(planner):
+    tools.add_recurring_meeting("Standup", "2026-11-23", "09:00", frequency="daily", skip_holidays=True)
+    standup = planner.get_calendar_events(expand_recurring=False)[0]
+    assert standup.skip_holidays
+
+    def days():
+        return [e.start_time[:10] for e in planner.get_calendar_events("2026-11-24", "2026-11-28")]
+
+    assert days() == ["2026-11-24", "2026-11-25", "2026-11-27", "2026-11-28"]
+    # Moved on purpose: it happens after all
+    planner.update_calendar_event(f"{standup.id}@2026-11-26", start_time="2026-11-26T11:00:00")
+    assert "2026-11-26" in days()
+    tools.update_calendar_event(standup.id, skip_holidays="no")
+    assert len(days()) == 5
@@ -0,0 +1,141 @@
+"""
+Tests for household profiles (assistant/household.py).
+
+Covers:
+- Profiles from config: the owner plus the household, bad scopes and duplicate names rejected
+- Scopes by action class and tool name; deny wins, a named tool gets through a denied class
+- ToolRegistry refuses a call outside the active profile before any confirmation
+- Switching by voice: narrower profiles at once, wider ones only with the PIN
+- Switching by speaker identification
+- A profile without cloud AI only gets the local rungs of the AI ladder
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.config import Config
+from assistant.confirmation import ConfirmationPolicy, hash_pin, set_confirmation_policy
+from assistant.household import Household, HouseholdError, Profile, set_household
+from assistant.latency import BudgetedAI, LatencyBudget, LatencyError, LatencyMetrics, Rung
+from assistant.tools import ToolRegistry
+
+PIN = hash_pin("4211")
+PROFILES = {
+    "Emma": {"allow": ["read", "create"], "deny": ["add_calendar_event"], "cloud_ai": False, "speakers": ["kid-1"]},
+    "Sam": {"deny": ["communicate", "system"], "speakers": ["teen"]},
+}
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+def _household(start="", **config):
+    household = Household.from_config(Config(user_name="Alex", household_profiles=PROFILES,
+                                             household_start_profile=start, household_owner_speakers=["alex"],
+                                             **config))
+    household.clock = FakeClock()
+    return household
+
+
+@pytest.fixture
+def household():
+    household = _household()
+    set_household(household)
+    yield household
+    set_household(None)
+
+
+def test_profiles_from_config():
+    household = _household("emma")
+    assert [p.name for p in household.profiles] == ["Alex", "Emma", "Sam"]
+    assert household.active.name == "Emma" and household.find("owner") is household.owner
+    assert Household.from_config(Config()).active.owner
+    for bad in ({"Kid": {"allow": ["Read Stuff"]}}, {"Alex": {}}, {"owner": {}}):
+        with pytest.raises(HouseholdError):
+            Household.from_config(Config(user_name="Alex", household_profiles=bad))
+    with pytest.raises(HouseholdError):
+        _household("Nobody")
+
+
+@pytest.mark.parametrize("profile, tool, allowed", [
+    ("Emma", "list_tasks", True), ("Emma", "add_task", True), ("Emma", "add_calendar_event", False),
+    ("Emma", "send_email", False), ("Emma", "delete_task", False),
+    ("Sam", "delete_task", True), ("Sam", "send_email", False), ("Sam", "run_command", False),
+    ("Alex", "run_command", True),
+])
+def test_scopes(profile, tool, allowed):
+    assert _household().find(profile).allows(tool) is allowed
+
+
+def test_named_tool_gets_through_a_denied_class():
+    profile = Profile("Kid", allow={"read", "make_call"}, deny={"communicate"})
+    assert profile.allows("make_call") and not profile.allows("send_email")
+
+
+def test_registry_refuses_before_confirming(household):
+    calls = []
+    registry = ToolRegistry()
+    registry.register("send_email", "Send an email")(lambda to: calls.append(to) or "✓ Sent")
+    registry.register("list_tasks", "List tasks")(lambda: "Nothing to do")
+    policy = ConfirmationPolicy(Config())
+    set_confirmation_policy(policy)
+    try:
+        household.active = household.find("Emma")
+        result = asyncio.run(registry.execute_tool("send_email", {"to": "bob@example.com"}))
+        assert result == {"success": False, "message": "Emma's profile isn't allowed to send email - ask Alex."}
+        assert calls == [] and policy.pending is None
+        assert asyncio.run(registry.execute_tool("list_tasks", {}))["result"] == "Nothing to do"
+        household.active = household.owner
+        assert asyncio.run(registry.execute_tool("send_email", {"to": "bob@example.com"}))["success"]
+    finally:
+        set_confirmation_policy(ConfirmationPolicy())
+
+
+def test_switching_down_is_free_and_up_needs_the_pin(household):
+    assert household.heard("what's the weather", PIN) is None
+    assert household.heard("This is Emma.", PIN) == "Hi Emma. Switched to your profile."
+    assert household.heard("it's Emma", PIN) == "You're already on Emma's profile."
+    assert household.heard("switch to Sam's profile", None) == (
+        "Switching to Sam's profile needs the confirmation PIN, and none is set. Alex can set one.")
+    assert household.heard("I'm Alex", PIN) == "Say the PIN to switch to Alex's profile."
+    assert household.heard("one two three four", PIN) == "That PIN didn't match. Try again."
+    assert household.heard("four two one one", PIN) == "Hi Alex. Switched to your profile."
+    assert household.active.owner
+
+
+def test_pin_attempts_and_dropping(household):
+    household.active = household.find("Sam")
+    assert household.heard("switch to the owner profile", PIN).startswith("Say the PIN")
+    assert household.heard("what time is it", PIN) is None and household.pending is None
+    household.heard("this is Alex", PIN)
+    for _ in range(2):
+        assert household.heard("0000", PIN) == "That PIN didn't match. Try again."
+    assert household.heard("0000", PIN) == "Wrong PIN too many times. Staying on Sam's profile."
+    assert household.heard("switch to Emma", PIN) == "Hi Emma. Switched to your profile."  # Narrower than Sam
+
+
+def test_speaker_identification(household):
+    assert household.identify("kid-1").name == "Emma" and household.active.name == "Emma"
+    assert household.identify("stranger") is None and household.active.name == "Emma"
+    assert household.identify("ALEX") is household.owner and household.active.owner
+
+
+def test_no_cloud_ai_uses_local_rungs(household):
+    async def cloud(messages, max_tokens):
+        return "from the cloud"
+
+    async def local(messages, max_tokens):
+        return "from this machine"
+
+    ai = BudgetedAI([Rung("cloud", cloud), Rung("local", local, local=True)], LatencyBudget(), None, LatencyMetrics())
+    assert asyncio.run(ai.chat([], 10)) == "from the cloud"
+    household.active = household.find("Emma")
+    assert asyncio.run(ai.chat([], 10)) == "from this machine"
+    with pytest.raises(LatencyError):
+        asyncio.run(BudgetedAI([Rung("cloud", cloud)], LatencyBudget(), None, LatencyMetrics()).chat([], 10))
@@ -0,0 +1,107 @@
+"""
+Tests for the ICS feed of xswarm's events (assistant/ics_feed.py).
+
+Covers:
+- The VCALENDAR text: floating times, escaping, long lines folded, free and busy-only events, private ones left out
+- Recurring events as their occurrences, a cancelled one left out
+- The local API serving the feed only for its token, and a new token shutting out the old URL
+- Uploads only when the feed changed, and under the new token after regenerating
+"""
+
+import asyncio
+import types
+from datetime import date, datetime, timezone
+
+from assistant import endpoints
+from assistant.ics_feed import build_feed, feed_token, feed_url, publish, render, serve_feed
+from assistant.local_api import LocalApi
+from assistant.planner import PlannerData
+
+NOW = datetime(2026, 10, 16, 12, 0, tzinfo=timezone.utc)
+
+
+def config(mode="local"):
+    return types.SimpleNamespace(ics_feed=mode, shared_visibility="public", server_url="https://x.example",
+                                 local_api_port=8766, api_token=None)
+
+
+def test_build_feed():
+    events = [
+        {"id": "evt1", "title": "Lunch, with Sam; maybe", "start_time": "2026-10-20T12:00:00",
+         "end_time": "2026-10-20T13:00:00", "description": "Line one\nLine two " + "x" * 100, "busy": False},
+        {"id": "evt2", "title": "Therapy", "start_time": "2026-10-21T09:00:00", "end_time": "2026-10-21T10:00:00",
+         "location": "Main St", "visibility": "busy"},
+        {"id": "evt3", "title": "Secret", "start_time": "2026-10-22T09:00:00", "end_time": "2026-10-22T10:00:00",
+         "visibility": "private"},
+    ]
+    ics = build_feed(events, now=NOW)
+    assert ics.startswith("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n") and ics.endswith("END:VCALENDAR\r\n")
+    assert all(len(line.encode()) <= 75 for line in ics.split("\r\n"))
+    unfolded = ics.replace("\r\n ", "")
+    assert "SUMMARY:Lunch\\, with Sam\\; maybe" in unfolded
+    assert "DESCRIPTION:Line one\\nLine two xxx" in unfolded
+    assert "DTSTART:20261020T120000\r\n" in unfolded and "DTSTAMP:20261016T120000Z" in unfolded
+    assert "UID:evt1@xswarm" in unfolded and "TRANSP:TRANSPARENT" in unfolded
+    therapy = unfolded.split("UID:evt2@xswarm")[1].split("END:VEVENT")[0]
+    assert "SUMMARY:Busy" in therapy and "Main St" not in therapy and "CLASS:CONFIDENTIAL" in therapy
+    assert "Secret" not in unfolded and unfolded.count("BEGIN:VEVENT") == 2
+    assert "Therapy" not in build_feed(events[1:2], "busy", now=NOW)
+    assert "SUMMARY:Lunch" not in build_feed(events[:1], "private", now=NOW)
+
+
+def test_recurring_occurrences(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    standup = planner.add_calendar_event("Standup", "2026-10-19T09:00:00", "2026-10-19T09:15:00",
+                                         recurrence="daily", recurrence_end="2026-10-23")
+    planner.delete_calendar_event(f"{standup.id}@2026-10-21")
+    ics = render(config(), planner, today=date(2026, 10, 16), now=NOW)
+    starts = [line for line in ics.split("\r\n") if line.startswith("DTSTART:")]
+    assert starts == [f"DTSTART:202610{day}T090000" for day in (19, 20, 22, 23)]
+    assert f"UID:{standup.id}@2026-10-20@xswarm" in ics
+
+
+def test_local_feed(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    planner.add_calendar_event("Dentist", "2026-10-20T15:00:00", "2026-10-20T16:00:00")
+    token_path = tmp_path / "ics_feed_token"
+    token = feed_token(path=token_path)
+    api = LocalApi({}, port=0, token_path=tmp_path / "api_token",
+                   feed=lambda t: serve_feed(config(), t, planner, token_path))
+
+    async def get(path):
+        return await api.handle("GET", path, {}, b"")
+
+    status, ics = asyncio.run(get(f"/calendar/{token}.ics"))
+    assert status == 200 and "SUMMARY:Dentist" in ics
+    assert asyncio.run(get("/calendar/wrong.ics"))[0] == 404
+    feed_token(rotate=True, path=token_path)
+    assert asyncio.run(get(f"/calendar/{token}.ics"))[0] == 404
+    assert serve_feed(config("server"), feed_token(path=token_path), planner, token_path) is None
+    assert feed_url(config(), "abc") == "http://127.0.0.1:8766/calendar/abc.ics"
+    assert feed_url(config("server"), "abc") == "https://x.example/ics/abc.ics"
+
+
+def test_publish_when_changed(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    planner.add_calendar_event("Dentist", "2026-10-20T15:00:00", "2026-10-20T16:00:00")
+    token_path = tmp_path / "ics_feed_token"
+    uploads = []
+
+    class FakeClient:
+        async def call(self, route, json=None, **kwargs):
+            assert route.path == endpoints.ICS_FEED_PUBLISH.template
+            uploads.append(json)
+
+    def run():
+        return asyncio.run(publish(config("server"), "user-1", FakeClient(), planner, token_path=token_path))
+
+    assert asyncio.run(publish(config("local"), "user-1", FakeClient(), planner, token_path=token_path)) is None
+    assert run() is True
+    assert run() is False
+    planner.add_calendar_event("Haircut", "2026-10-22T15:00:00", "2026-10-22T16:00:00")
+    assert run() is True
+    first_token = uploads[0]["token"]
+    feed_token(rotate=True, path=token_path)
+    assert run() is True
+    assert [u["token"] == first_token for u in uploads] == [True, True, False]
+    assert "SUMMARY:Haircut" in uploads[-1]["ics"] and uploads[-1]["user_id"] == "user-1"
@@ -0,0 +1,119 @@
+"""
+Tests for the unified inbox local mirror.
+
+Covers:
+- Merging server items into the local store
+- Status changes being queued for sync
+- Pending local changes surviving a remote refresh
+- Items found by full id or a unique prefix; an ambiguous prefix raises, an empty id finds nothing
+- Sync keeping queued changes while signed out, dropping ones the server will never take
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.api_client import ApiError, ApiErrorKind
+from assistant.inbox import InboxError, InboxManager, InboxStore, format_inbox
+
+
+def _remote(item_id="msg-0001", status="unread", received_at="2026-01-01T10:00:00"):
+    return {
+        "id": item_id,
+        "user_id": "alice",
+        "channel": "sms",
+        "sender": "+15551234567",
+        "content": "Can we move the call to 3pm?",
+        "status": status,
+        "received_at": received_at,
+    }
+
+
+class TestInboxStore:
+    def test_merge_counts_new_items(self, tmp_path):
+        store = InboxStore(tmp_path)
+        assert store.merge_remote([_remote("a"), _remote("b")]) == 2
+        assert store.merge_remote([_remote("a")]) == 0
+        assert store.unread_count() == 2
+
+    def test_items_sorted_newest_first_and_archived_hidden(self, tmp_path):
+        store = InboxStore(tmp_path)
+        store.merge_remote([
+            _remote("old", received_at="2026-01-01T09:00:00"),
+            _remote("new", received_at="2026-01-01T11:00:00"),
+            _remote("gone", status="archived"),
+        ])
+        assert [i.id for i in store.get_items()] == ["new", "old"]
+        assert [i.id for i in store.get_items(status="archived")] == ["gone"]
+
+    def test_set_status_queues_single_pending_update(self, tmp_path):
+        store = InboxStore(tmp_path)
+        store.merge_remote([_remote("a")])
+
+        store.set_status("a", "read")
+        store.set_status("a", "archived")
+
+        pending = store.pending
+        assert len(pending) == 1
+        assert pending[0].status == "archived"
+
+    def test_pending_status_survives_remote_refresh(self, tmp_path):
+        store = InboxStore(tmp_path)
+        store.merge_remote([_remote("a")])
+        store.set_status("a", "replied", reply_text="Sure, 3pm works.")
+
+        store.merge_remote([_remote("a", status="unread")])
+
+        item = store.get_item("a")
+        assert item.status == "replied"
+        assert item.reply_text == "Sure, 3pm works."
+
+    def test_invalid_status_rejected(self, tmp_path):
+        store = InboxStore(tmp_path)
+        store.merge_remote([_remote("a")])
+        with pytest.raises(ValueError):
+            store.set_status("a", "deleted")
+
+    def test_item_ids_and_prefixes(self, tmp_path):
+        store = InboxStore(tmp_path)
+        store.merge_remote([_remote("msg-0001"), _remote("msg-0002"), _remote("msg-00")])
+        assert store.get_item("msg-0002").id == "msg-0002"
+        assert store.get_item("msg-00").id == "msg-00"  # Exact beats the prefix it also is
+        with pytest.raises(InboxError, match="'msg-000' matches 2 inbox items"):
+            store.get_item("msg-000")
+        with pytest.raises(InboxError):
+            store.set_status("msg-", "read")
+        assert store.get_item("") is None and store.set_status("  ", "read") is None
+        assert store.get_item("nope") is None and store.pending == []
+
+    def test_format_inbox_marks_unread(self, tmp_path):
+        store = InboxStore(tmp_path)
+        store.merge_remote([_remote("a")])
+        assert format_inbox(store.get_items()).startswith("●")
+        assert format_inbox([]) == "Inbox is empty."
+
+
+class TestSync:
+    class FakeClient:
+        def __init__(self, error):
+            self.error = error
+
+        async def push_update(self, update):
+            raise self.error
+
+        async def fetch(self, since=None):
+            return {"items": [], "synced_at": None}
+
+    def _sync(self, tmp_path, error):
+        store = InboxStore(tmp_path)
+        store.merge_remote([_remote("a")])
+        store.set_status("a", "read")
+        manager = InboxManager(store=store)
+        manager.client = self.FakeClient(error)
+        return asyncio.run(manager.sync())["pending"]
+
+    def test_signed_out_keeps_changes_queued(self, tmp_path):
+        assert self._sync(tmp_path, ApiError(ApiErrorKind.AUTH_EXPIRED, "PUT /api/inbox/:id", status_code=401)) == 1
+
+    def test_rejected_change_is_dropped(self, tmp_path):
+        assert self._sync(tmp_path, ApiError(ApiErrorKind.VALIDATION, "PUT /api/inbox/:id", status_code=422)) == 0
@@ -0,0 +1,196 @@
+"""
+Tests for scoring tool calls against what was said (assistant/intents.py).
+
+Covers:
+- How well an utterance names a call's target (title, time, "it", kind only)
+- Describing the target and the "did you mean" question
+- When a call isn't scored: no recent utterance, creates, unknown targets
+- Speech-to-text confidence lowering the score
+- Per-class thresholds with config overrides
+- ToolRegistry asking before an unsure call and running it on "yes"
+"""
+
+import asyncio
+from datetime import date, datetime
+
+import pytest
+
+from assistant import dates, intents, tools
+from assistant.config import Config
+from assistant.confirmation import ConfirmationPolicy, set_confirmation_policy
+from assistant.dates import DateSettings
+from assistant.intents import (
+    AT_TIME,
+    KIND_ONLY,
+    NAMED,
+    PARTLY_NAMED,
+    REFERRED,
+    UNMENTIONED,
+    Target,
+    Utterance,
+    describe_target,
+    grounding,
+    hear,
+    question_for,
+    score_call,
+)
+from assistant.planner import PlannerData
+from assistant.tools import ToolRegistry
+
+TODAY = date(2026, 10, 16)
+STANDUP = Target("event", "Team standup", datetime(2026, 10, 16, 15, 0))
+
+
+@pytest.mark.parametrize("text, score", [
+    ("delete the team standup", NAMED),
+    ("cancel standup", NAMED),
+    ("cancel my 3pm", AT_TIME),
+    ("drop the three o'clock", AT_TIME),
+    ("yeah cancel it", REFERRED),
+    ("cancel my meeting", KIND_ONLY),
+    ("what's the weather like", UNMENTIONED),
+])
+def test_grounding(text, score):
+    assert grounding(text, STANDUP)[0] == score
+
+
+def test_partly_named():
+    target = Target("task", "Write quarterly budget report")
+    assert grounding("finish the budget", target) == (PARTLY_NAMED, "partly named")
+
+
+@pytest.fixture
+def twelve_hour(monkeypatch):
+    monkeypatch.setattr(dates, "_settings", DateSettings(clock="12h"))
+
+
+@pytest.fixture
+def planner(tmp_path, monkeypatch, twelve_hour):
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    return planner
+
+
+def _standup(planner):
+    return planner.add_calendar_event("Standup", f"{date.today()}T15:00:00", f"{date.today()}T15:15:00").id
+
+
+class TestQuestion:
+    def test_describe_target(self, twelve_hour):
+        assert describe_target(STANDUP, today=TODAY) == "the 3pm Team standup"
+        tomorrow = Target("event", "Dentist", datetime(2026, 10, 17, 9, 30))
+        assert describe_target(tomorrow, today=TODAY) == "the Dentist tomorrow at 9:30am"
+        assert describe_target(Target("task", "Write report")) == "the task 'Write report'"
+
+    def test_24_hour_clock(self, monkeypatch):
+        monkeypatch.setattr(dates, "_settings", DateSettings(clock="24h"))
+        assert describe_target(STANDUP, today=TODAY) == "the 15:00 Team standup"
+
+    @pytest.mark.parametrize("tool_name, args, target, question", [
+        ("delete_calendar_event", {}, STANDUP, "Did you want me to cancel the 3pm Team standup?"),
+        ("delete_task", {}, Target("task", "Call Sam"), "Did you want me to delete the task 'Call Sam'?"),
+        ("complete_task", {}, Target("task", "Call Sam"), "Did you want me to mark the task 'Call Sam' done?"),
+        ("update_calendar_event", {"start_time": "16:00"}, STANDUP, "Did you want me to move the 3pm Team standup?"),
+        ("update_task", {"title": "Call Sam back"}, Target("task", "Call Sam"),
+         "Did you want me to change the task 'Call Sam'?"),
+    ])
+    def test_question_for(self, twelve_hour, tool_name, args, target, question):
+        assert question_for(tool_name, args, target, today=TODAY) == question
+
+
+class TestScoreCall:
+    def test_scores_against_the_target(self, planner):
+        event_id = _standup(planner)
+        score = score_call("delete_calendar_event", {"event_id": event_id}, utterance=Utterance("cancel my meeting"))
+        assert (score.confidence, score.reason) == (KIND_ONLY, "kind only")
+        assert score.question == "Did you want me to cancel the 3pm Standup?"
+
+    def test_speech_confidence_lowers_the_score(self, planner):
+        event_id = _standup(planner)
+        score = score_call("delete_calendar_event", {"event_id": event_id},
+                           utterance=Utterance("cancel the standup", confidence=0.6))
+        assert score.confidence == 0.6
+        assert score.reason == "named, heard with 60% confidence"
+
+    def test_nothing_to_score(self, planner):
+        event_id = _standup(planner)
+        said = Utterance("cancel my meeting")
+        assert score_call("add_calendar_event", {"title": "Standup", "project_id": "p-1"}, utterance=said) is None
+        assert score_call("delete_calendar_event", {"event_id": "evt-missing"}, utterance=said) is None
+        assert score_call("delete_calendar_event", {"event_id": event_id}, utterance=Utterance("  ")) is None
+
+    def test_old_utterances_expire(self, planner, monkeypatch):
+        event_id = _standup(planner)
+        now = [1000.0]
+        monkeypatch.setattr(intents, "_clock", lambda: now[0])
+        monkeypatch.setattr(intents, "_current", None)
+        assert score_call("delete_calendar_event", {"event_id": event_id}) is None
+        hear("cancel my meeting")
+        assert score_call("delete_calendar_event", {"event_id": event_id}).confidence == KIND_ONLY
+        now[0] += intents.UTTERANCE_TTL + 1
+        assert score_call("delete_calendar_event", {"event_id": event_id}) is None
+
+
+class TestThresholds:
+    def test_defaults_by_class(self):
+        policy = ConfirmationPolicy(Config())
+        assert policy.confidence_threshold("get_tasks") == 0.0
+        assert policy.confidence_threshold("add_task") == 0.0
+        assert policy.confidence_threshold("complete_task") == 0.6
+        assert policy.confidence_threshold("delete_calendar_event") == 0.7
+
+    def test_overrides(self):
+        policy = ConfirmationPolicy(Config(intent_confidence_thresholds={"delete": 0.4, "delete_task": 0.9}))
+        assert policy.confidence_threshold("delete_calendar_event") == 0.4
+        assert policy.confidence_threshold("delete_task") == 0.9
+
+
+class TestRegistry:
+    def _setup(self, planner, monkeypatch):
+        monkeypatch.setattr(intents, "_current", None)
+        event_id = _standup(planner)
+        calls = []
+        local = ToolRegistry()
+
+        @local.register("delete_calendar_event", "Delete an event")
+        def delete_calendar_event(event_id: str) -> str:
+            calls.append(event_id)
+            return f"✓ Deleted {event_id}"
+
+        policy = ConfirmationPolicy(Config())
+        set_confirmation_policy(policy)
+        return local, policy, event_id, calls
+
+    def test_unsure_call_asks_first(self, planner, monkeypatch):
+        local, policy, event_id, calls = self._setup(planner, monkeypatch)
+        try:
+            hear("cancel my meeting")
+            held = asyncio.run(local.execute_tool("delete_calendar_event", {"event_id": event_id}))
+            assert held["pending_confirmation"] and calls == []
+            assert held["result"].endswith("Ask them: Did you want me to cancel the 3pm Standup?")
+
+            action = policy.resolve("yes").action
+            done = asyncio.run(action.registry.execute_tool(action.tool_name, action.args, confirmed=True))
+            assert done["result"] == f"✓ Deleted {event_id}"
+            assert calls == [event_id]
+        finally:
+            set_confirmation_policy(ConfirmationPolicy())
+
+    def test_anything_else_drops_it(self, planner, monkeypatch):
+        local, policy, event_id, calls = self._setup(planner, monkeypatch)
+        try:
+            hear("cancel my meeting")
+            asyncio.run(local.execute_tool("delete_calendar_event", {"event_id": event_id}))
+            assert policy.resolve("no, the dentist").status == "declined"
+            assert calls == []
+        finally:
+            set_confirmation_policy(ConfirmationPolicy())
+
+    def test_named_call_runs(self, planner, monkeypatch):
+        local, _policy, event_id, calls = self._setup(planner, monkeypatch)
+        try:
+            hear("cancel my 3pm")
+            result = asyncio.run(local.execute_tool("delete_calendar_event", {"event_id": event_id}))
+            assert result["result"] == f"✓ Deleted {event_id}" and calls == [event_id]
+        finally:
+            set_confirmation_policy(ConfirmationPolicy())
@@ -0,0 +1,67 @@
+"""
+Tests for configurable dashboard keys (assistant/keymap.py).
+
+Covers:
+- Presets and per-action overrides from config
+- A key moved to another action, unbinding, config mistakes
+- Priority bindings and the help text
+"""
+
+from assistant.config import Config
+from assistant.keymap import ACTIONS, PRESETS, Keymap, key_label, parse_keys
+
+
+def test_presets_bind_every_action():
+    for preset in PRESETS:
+        keymap = Keymap(preset)
+        assert all(keymap.keys(action.name) for action in ACTIONS), preset
+        assert keymap.keys("help") and keymap.keys("help")[0] == "question_mark"
+
+
+def test_default_matches_the_old_keys():
+    keymap = Keymap()
+    assert keymap.keys("next_tab") == ["j", "down"]
+    assert keymap.keys("goto_inbox") == ["8"]
+    assert ("ctrl+l", "copy_logs", "Copy activity log", False) in keymap.bindings()
+
+
+def test_vi_and_emacs():
+    assert Keymap("vi").keys("undo") == ["u"]
+    assert "down" not in Keymap("vi").keys("next_tab")
+    emacs = Keymap("emacs")
+    assert emacs.keys("next_tab")[0] == "ctrl+n" and emacs.keys("cancel")[0] == "ctrl+g"
+    assert emacs.keys("goto_chat") == ["alt+1"]
+
+
+def test_overrides_from_config():
+    keymap = Keymap.from_config(Config(keymap_preset="vi", keymap={"next_tab": "ctrl+j, u", "copy_logs": "", "help": "?"}))
+    assert keymap.keys("next_tab") == ["ctrl+j", "u"]
+    assert keymap.keys("undo") == []  # u moved to next_tab
+    assert keymap.keys("copy_logs") == []
+    assert keymap.keys("help") == ["question_mark"]
+    assert not keymap.problems
+
+
+def test_config_mistakes():
+    keymap = Keymap("nano", {"launch_rockets": "r"})
+    assert keymap.preset == "default"
+    assert len(keymap.problems) == 2
+
+
+def test_priority():
+    priority = {(key, command) for key, command, _, urgent in Keymap().bindings() if urgent}
+    assert ("ctrl+q", "quit") in priority and ("tab", "cycle_focus_zone") in priority
+    assert ("q", "quit") not in priority  # Typing q in the chat input doesn't quit
+
+
+def test_help_lines():
+    lines = Keymap("emacs").help_lines()
+    assert lines[0] == "Keys (emacs)"
+    assert "Navigation" in lines
+    assert any(line.startswith("  Ctrl+N / ↓") and line.endswith("Next tab") for line in lines)
+
+
+def test_labels_and_parsing():
+    assert [key_label(k) for k in ("ctrl+q", "j", "f1", "shift+tab", "question_mark", "ctrl+up")] == \
+        ["Ctrl+Q", "j", "F1", "Shift+Tab", "?", "Ctrl+↑"]
+    assert parse_keys(["Ctrl+J", " ", "+"]) == ["ctrl+j", "plus"]
@@ -0,0 +1,90 @@
+"""
+Tests for the AI latency budget (assistant/latency.py).
+
+Covers:
+- A fast answer: no filler, no fallback, latency recorded
+- A slow answer: one filler at the soft budget, then the answer
+- A rung past the hard limit or failing: the next rung answers
+- Every rung failing raises LatencyError; background calls get no filler
+- The low-power profile asks small rungs first
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.latency import BudgetedAI, LatencyBudget, LatencyError, LatencyMetrics, Rung, percentile
+
+BUDGET = LatencyBudget(soft=0.05, hard=0.2)
+
+
+def answer(text, after=0.0):
+    async def chat(messages, max_tokens):
+        await asyncio.sleep(after)
+        return text
+    return chat
+
+
+def fail(message):
+    async def chat(messages, max_tokens):
+        raise RuntimeError(message)
+    return chat
+
+
+def run(ladder, filler=True):
+    said = []
+
+    async def on_filler(text):
+        said.append(text)
+
+    metrics = LatencyMetrics()
+    ai = BudgetedAI(ladder, BUDGET, on_filler if filler else None, metrics)
+
+    async def go():
+        try:
+            return await ai.chat([{"role": "user", "content": "hi"}], 50)
+        except LatencyError as e:
+            return e
+    return asyncio.run(go()), said, metrics.summary()
+
+
+def test_fast_answer():
+    reply, said, summary = run([Rung("big", answer("Hello")), Rung("small", answer("Hi"))])
+    assert reply == "Hello" and said == []
+    assert summary["rungs"]["big"]["ok"] == 1 and "small" not in summary["rungs"]
+    assert summary["degraded"] == 0 and summary["rungs"]["big"]["p50"] < 0.05
+
+
+def test_slow_answer_gets_one_filler():
+    reply, said, summary = run([Rung("big", answer("Hello", after=0.12))])
+    assert reply == "Hello"
+    assert len(said) == 1 and summary["fillers"] == 1
+
+
+@pytest.mark.parametrize("first", [answer("late", after=1.0), fail("overloaded")])
+def test_falls_back_to_next_rung(first):
+    reply, said, summary = run([Rung("big", first), Rung("small", answer("Hi"))])
+    assert reply == "Hi"
+    assert summary["degraded"] == 1
+    big = summary["rungs"]["big"]
+    assert big["timeouts"] + big["errors"] == 1 and big["ok"] == 0
+
+
+def test_all_rungs_fail():
+    error, said, summary = run([Rung("big", fail("overloaded")), Rung("local", answer("late", after=1.0))],
+                               filler=False)
+    assert isinstance(error, LatencyError)
+    assert "overloaded" in str(error) and "local took over" in str(error)
+    assert said == [] and summary["fillers"] == 0
+
+
+def test_percentile():
+    assert percentile([3, 1, 2], 50) == 2
+    assert percentile(range(1, 101), 95) == 95
+
+
+def test_low_power_asks_small_rungs_first(monkeypatch):
+    from assistant import power
+    monkeypatch.setattr(power, "_manager", power.PowerManager("low_power"))
+    reply, said, summary = run([Rung("big", answer("Hello")), Rung("small", answer("Hi"), small=True)])
+    assert reply == "Hi" and summary["degraded"] == 0 and "big" not in summary["rungs"]
@@ -0,0 +1,33 @@
+"""
+Tests for the responsive dashboard layout (assistant/layout.py).
+
+Covers:
+- The size class for common terminal sizes
+- Tab labels in the full and compact layouts
+"""
+
+import pytest
+
+from assistant.layout import TABS, SizeClass, size_class, tab_label
+
+
+@pytest.mark.parametrize("width, height, expected", [
+    (80, 24, SizeClass.COMPACT),
+    (200, 24, SizeClass.COMPACT),   # Short terminals go compact however wide they are
+    (89, 60, SizeClass.COMPACT),
+    (90, 26, SizeClass.MEDIUM),
+    (100, 30, SizeClass.MEDIUM),
+    (160, 33, SizeClass.MEDIUM),
+    (120, 34, SizeClass.WIDE),
+    (200, 60, SizeClass.WIDE),
+])
+def test_size_class(width, height, expected):
+    assert size_class(width, height) == expected
+
+
+def test_tab_labels():
+    assert tab_label("📅", "Schedule", SizeClass.WIDE) == " 📅  Schedule"
+    assert tab_label("📅", "Schedule", SizeClass.MEDIUM) == " 📅  Schedule"
+    assert tab_label("📅", "Schedule", SizeClass.COMPACT) == "📅"
+    assert [tab for tab, _, _ in TABS][:2] == ["chat", "schedule"]
+    assert SizeClass.COMPACT.css_class == "-compact"
@@ -0,0 +1,122 @@
+"""
+Tests for shopping/todo lists (assistant/lists.py).
+
+Covers:
+- List names ("my grocery list" is the shopping list) and items split from speech
+- Requests: add, read, check off (naming the list or not), remove, clear; other talk is left for the model
+- Markdown export
+- Sync: queued changes are pushed, newer changes from other devices win, removals sync and are forgotten
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.api_client import ApiError, ApiErrorKind
+from assistant.lists import ListCommand, ListStore, list_name, parse_list_command, sync_lists, to_markdown
+
+
+@pytest.mark.parametrize("raw, name", [
+    ("my Grocery list", "shopping"), ("the to-do list", "todo"), ("Packing", "packing"), ("the", ""),
+])
+def test_list_names(raw, name):
+    assert list_name(raw) == name
+
+
+@pytest.mark.parametrize("text, command", [
+    ("Add milk, eggs and bread to the shopping list", ListCommand("add", "shopping", ["milk", "eggs", "bread"])),
+    ("hey, put some oat milk on my grocery list please", ListCommand("add", "shopping", ["oat milk"])),
+    ("what's on my todo list?", ListCommand("read", "todo")),
+    ("read me the packing list", ListCommand("read", "packing")),
+    ("cross milk off the shopping list", ListCommand("done", "shopping", ["milk"])),
+    ("take bread off the shopping list", ListCommand("remove", "shopping", ["bread"])),
+    ("clear the checked items from the shopping list", ListCommand("clear_done", "shopping")),
+    ("clear the shopping list", ListCommand("clear", "shopping")),
+    ("I got the milk", None),  # No store to say which list has milk
+    ("add a meeting to my calendar", None),
+    ("check the weather", None),
+])
+def test_parse(text, command):
+    assert parse_list_command(text) == command
+
+
+def test_voice_round_trip(tmp_path):
+    store = ListStore(tmp_path)
+    say = lambda text: store.apply(parse_list_command(text, store))
+    assert say("add milk, eggs and bread to the shopping list") == "Added milk, eggs and bread to the shopping list."
+    assert say("add egg and butter to my grocery list") == "Added butter to the shopping list. Already on it: eggs."
+    assert parse_list_command("I got the eggs", store) == ListCommand("done", "shopping", ["eggs"])
+    assert say("I got the eggs") == "Checked off eggs."
+    assert say("what's on the list?") == "The shopping list has milk, bread and butter. Checked off: eggs."
+    assert say("take cheese off the shopping list") == "Cheese isn't on the shopping list."
+    assert say("add call the plumber to my to-do list") == "Added call the plumber to the todo list."
+    assert parse_list_command("what's on the list", store) is None  # Two lists now: which one?
+    assert say("clear the ticked items from the shopping list") == "Cleared 1 checked-off item from the shopping list."
+    assert say("what's on my shopping list") == "The shopping list has milk, bread and butter."
+    assert ListStore(tmp_path).names() == ["shopping", "todo"]  # Saved
+
+
+def test_markdown(tmp_path):
+    store = ListStore(tmp_path)
+    store.add("shopping", ["milk", "eggs"])
+    store.set_done("shopping", "egg")
+    assert to_markdown(store, "grocery") == "# Shopping list\n\n- [ ] milk\n- [x] eggs\n"
+
+
+class FakeResponse:
+    def __init__(self, body):
+        self.body = body
+
+    def json(self):
+        return self.body
+
+
+class FakeClient:
+    """Stands in for ApiClient: remembers what was pushed, returns `remote` when pulled."""
+
+    def __init__(self, remote=(), error=None):
+        self.remote, self.error, self.pushed, self.since = list(remote), error, [], None
+
+    async def call(self, route, json=None, params=None):
+        if route.method == "PUT":
+            if self.error:
+                raise self.error
+            self.pushed += json["items"]
+            return FakeResponse({"stored": len(json["items"])})
+        self.since = params.get("since")
+        return FakeResponse({"items": self.remote, "synced_at": "2026-10-16T12:00:00Z"})
+
+
+def test_sync(tmp_path):
+    store = ListStore(tmp_path)
+    store.add("shopping", ["milk", "eggs"])
+    milk, eggs = store.items("shopping")
+    store.remove("shopping", "eggs")
+    remote = [
+        dict(id=milk.id, list="shopping", text="milk", done=True, deleted=False, updated_at="2099-01-01T00:00:00+00:00"),
+        dict(id="other", list="todo", text="book dentist", done=False, deleted=False, updated_at="2026-10-16T11:00:00+00:00"),
+        dict(id="gone", list="todo", text="old", done=False, deleted=True, updated_at="2026-10-16T11:00:00+00:00"),
+    ]
+    client = FakeClient(remote)
+    result = asyncio.run(sync_lists(None, "user-1", store=store, client=client))
+    assert {item["text"] for item in client.pushed} == {"milk", "eggs"} and client.since is None
+    assert result == {"pushed": 2, "changed": 3, "pending": 0}
+    assert [(i.text, i.done) for i in store.items("shopping")] == [("milk", True)]  # Checked off elsewhere
+    assert [i.text for i in store.items("todo")] == ["book dentist"]
+    assert eggs.id not in [raw["id"] for raw in store._load()["items"]]  # Tombstone dropped once sent
+
+    client = FakeClient([dict(remote[0], done=False, updated_at="2000-01-01T00:00:00+00:00")])
+    asyncio.run(sync_lists(None, "user-1", store=store, client=client))
+    assert client.since == "2026-10-16T12:00:00Z" and store.items("shopping")[0].done  # Older change loses
+
+
+@pytest.mark.parametrize("kind, pending", [(ApiErrorKind.SERVER_DOWN, 1), (ApiErrorKind.VALIDATION, 0)])
+def test_sync_failures_keep_or_drop_the_queue(tmp_path, kind, pending):
+    store = ListStore(tmp_path)
+    store.add("todo", ["water plants"])
+    client = FakeClient(error=ApiError(kind, "PUT /api/lists/items"))
+    try:
+        asyncio.run(sync_lists(None, "user-1", store=store, client=client))
+    except ApiError:
+        pass
+    assert len(store.pending) == pending  # Unavailable: retried later. Rejected: dropped
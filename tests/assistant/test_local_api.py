@@ -0,0 +1,182 @@
+"""
+Tests for the local REST API (assistant/local_api.py) and the MCP server (assistant/mcp_server.py).
+
+Covers:
+- OpenAPI schema generated from the request/response dataclasses (CalendarEvent included)
+- Token auth, routing errors and request validation; the token file is owner-only from creation
+- A real HTTP round trip on localhost
+- MCP initialize, tools/list from the same operations, and tools/call through the API
+- Events shared at their visibility (busy-only as "Busy", private left out), and free/busy times
+"""
+
+import asyncio
+import io
+import json
+import os
+from dataclasses import replace
+from urllib.request import Request, urlopen
+
+from assistant import tools
+from assistant.local_api import (OPERATIONS, BusyTime, LocalApi, LocalApiError, SpeakRequest, SpeakResult,
+                                 load_token, object_schema, openapi, shared_appointments, shared_busy_times)
+from assistant.mcp_server import McpServer, api_caller
+from assistant.planner import CalendarEvent, PlannerData
+
+EVENT = CalendarEvent(id="evt-1", title="Dentist", start_time="2026-10-17T16:00:00", end_time="2026-10-17T17:00:00",
+                      created_at="2026-10-01T09:00:00")
+
+
+def make_api(tmp_path, **handlers):
+    calls = []
+
+    async def appointments(query):
+        calls.append(query)
+        return [EVENT]
+
+    async def speak(request):
+        calls.append(request)
+        if request.text == "fail":
+            raise LocalApiError(400, "nothing to say")
+        return SpeakResult(True, "spoken")
+
+    api = LocalApi({"list_appointments": appointments, "speak": speak, **handlers}, port=0,
+                   token_path=tmp_path / "token")
+    return api, load_token(tmp_path / "token"), calls
+
+
+def test_schema_from_dataclasses():
+    schema = object_schema(SpeakRequest)
+    assert schema["required"] == ["text"]
+    assert schema["properties"]["priority"] == {"type": "string"}
+
+    doc = openapi(8766)
+    assert set(doc["paths"]) == {op.path for op in OPERATIONS}
+    events = doc["components"]["schemas"]["CalendarEvent"]
+    assert {"id", "title", "start_time", "end_time"} <= set(events["required"])
+    assert events["properties"]["attendees"] == {"type": "array", "items": {"type": "string"}}
+    assert events["properties"]["latitude"] == {"type": "number", "nullable": True}
+    assert not any(name.startswith("_") for name in events["properties"])
+    listing = doc["paths"]["/appointments"]["get"]
+    assert listing["parameters"] == [{"name": "days", "in": "query", "schema": {"type": "integer"}}]
+    assert listing["responses"]["200"]["content"]["application/json"]["schema"]["type"] == "array"
+
+
+def test_auth_routing_and_validation(tmp_path):
+    api, token, calls = make_api(tmp_path)
+    auth = {"authorization": f"Bearer {token}"}
+
+    async def run():
+        return [
+            await api.handle("GET", "/appointments?days=3", {}, b""),
+            await api.handle("GET", "/appointments?days=3", {"authorization": "Bearer nope"}, b""),
+            await api.handle("GET", "/appointments?days=3", auth, b""),
+            await api.handle("GET", "/openapi.json", {}, b""),
+            await api.handle("GET", "/nowhere", auth, b""),
+            await api.handle("DELETE", "/speak", auth, b""),
+            await api.handle("POST", "/speak", auth, b'{"priority": "high"}'),
+            await api.handle("POST", "/speak", auth, b"not json"),
+            await api.handle("POST", "/speak", auth, b'{"text": "fail"}'),
+            await api.handle("POST", "/speak", auth, b'{"text": "Deploy done", "priority": "high"}'),
+            await api.handle("POST", "/reminders", auth, b'{"title": "x", "when": "tomorrow"}'),
+        ]
+
+    responses = asyncio.run(run())
+    assert [status for status, _ in responses] == [401, 401, 200, 200, 404, 405, 400, 400, 400, 200, 503]
+    assert responses[2][1][0]["title"] == "Dentist" and "_original_id" not in responses[2][1][0]
+    assert responses[6][1] == {"error": "Missing field: text"}
+    assert responses[9][1] == {"spoken": True, "message": "spoken"}
+    assert calls[0].days == 3
+    assert calls[-1] == SpeakRequest("Deploy done", "high")
+
+    rotated = load_token(tmp_path / "token", rotate=True)
+    assert rotated != token
+    assert asyncio.run(api.handle("GET", "/appointments", auth, b""))[0] == 401
+
+
+def test_token_file_is_owner_only(tmp_path):
+    path = tmp_path / "token"
+    path.write_text("old\n")
+    path.chmod(0o644)
+    umask = os.umask(0)  # Even with nothing masked
+    try:
+        token = load_token(path, rotate=True)
+    finally:
+        os.umask(umask)
+    assert path.read_text() == token + "\n"
+    assert path.stat().st_mode & 0o777 == 0o600
+    assert [p.name for p in tmp_path.iterdir()] == ["token"]  # No temp file left behind
+    assert load_token(path) == token
+
+
+def test_http_and_mcp_round_trip(tmp_path):
+    api, token, calls = make_api(tmp_path)
+
+    def fetch(url):
+        with urlopen(Request(url, headers={"Authorization": f"Bearer {token}"}), timeout=5) as response:
+            return response.status, json.loads(response.read())
+
+    async def run():
+        assert await api.start()
+        try:
+            base = f"http://127.0.0.1:{api.port}"
+            status, events = await asyncio.to_thread(fetch, f"{base}/appointments?days=2")
+            mcp = McpServer(api_caller(base, token))
+            spoken = await asyncio.to_thread(mcp.handle, {"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {
+                "name": "speak", "arguments": {"text": "Build finished"}}})
+            failed = await asyncio.to_thread(mcp.handle, {"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {
+                "name": "create_reminder", "arguments": {"title": "x", "when": "tomorrow"}}})
+            return status, events, spoken, failed
+        finally:
+            api.close()
+
+    status, events, spoken, failed = asyncio.run(run())
+    assert status == 200 and events[0]["id"] == "evt-1"
+    assert json.loads(spoken["result"]["content"][0]["text"]) == {"spoken": True, "message": "spoken"}
+    assert failed["result"]["isError"] and "HTTP 503" in failed["result"]["content"][0]["text"]
+    assert calls[0].days == 2 and calls[1].text == "Build finished"
+
+
+def test_shared_at_their_visibility(tmp_path, monkeypatch):
+    therapy = replace(EVENT, id="evt-2", title="Therapy appointment", description="Dr. Lee", attendees=["Dr. Lee"],
+                      start_time="2026-10-17T14:00:00", end_time="2026-10-17T15:00:00", visibility="busy")
+    diary = replace(EVENT, id="evt-3", title="Journal", start_time="2026-10-17T20:00:00",
+                    end_time="2026-10-17T21:00:00", visibility="private")
+    shared = shared_appointments([EVENT, therapy, diary])
+    assert [(e.id, e.title) for e in shared] == [("evt-1", "Dentist"), ("evt-2", "Busy")]
+    assert (shared[1].description, shared[1].attendees, shared[1].start_time) == ("", [], "2026-10-17T14:00:00")
+    assert [e.title for e in shared_appointments([EVENT, therapy, diary], default="busy")] == ["Busy", "Busy"]
+    assert shared_busy_times([EVENT, therapy, diary]) == [BusyTime("2026-10-17T14:00:00", "2026-10-17T15:00:00"),
+                                                          BusyTime("2026-10-17T16:00:00", "2026-10-17T17:00:00")]
+
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    assert tools.add_calendar_event("Therapy", "2026-10-19", "14:00", visibility="busy-only").startswith("✓ Added")
+    event = planner.get_calendar_events(expand_recurring=False)[0]
+    assert event.visibility == "busy"
+    assert tools.update_calendar_event(event.id, visibility="Private").startswith("✓ Updated")
+    assert planner.get_calendar_event(event.id).visibility == "private"
+    assert tools.update_calendar_event(event.id, visibility="hidden").startswith("✗ visibility is")
+
+
+def test_mcp_protocol():
+    server = McpServer(lambda op, arguments: {"op": op.name, **arguments})
+    stdin = io.StringIO("\n".join(json.dumps(message) for message in [
+        {"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"protocolVersion": "2024-11-05"}},
+        {"jsonrpc": "2.0", "method": "notifications/initialized"},
+        {"jsonrpc": "2.0", "id": 2, "method": "tools/list"},
+        {"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "list_appointments",
+                                                                      "arguments": {"days": 1}}},
+        {"jsonrpc": "2.0", "id": 4, "method": "resources/list"},
+    ]) + "\nnot json\n")
+    stdout = io.StringIO()
+    server.run(stdin, stdout)
+    replies = [json.loads(line) for line in stdout.getvalue().splitlines()]
+
+    assert [reply["id"] for reply in replies] == [1, 2, 3, 4, None]
+    assert replies[0]["result"]["serverInfo"]["name"] == "xswarm"
+    tools = {tool["name"]: tool for tool in replies[1]["result"]["tools"]}
+    assert set(tools) == {op.name for op in OPERATIONS}
+    assert tools["speak"]["inputSchema"]["required"] == ["text"]
+    assert json.loads(replies[2]["result"]["content"][0]["text"]) == {"op": "list_appointments", "days": 1}
+    assert replies[3]["error"]["code"] == -32601
+    assert replies[4]["error"]["code"] == -32700
@@ -0,0 +1,72 @@
+"""
+Tests for log rotation (assistant/log_rotation.py).
+
+Covers:
+- Rotating by size and by age into gzipped archives, then appending to a fresh file
+- The age of a log carried over from the last run, read from its first line
+- Keeping the folder under the total cap (the oldest archives first), and `dev logs prune --dry-run`
+"""
+
+import gzip
+import logging
+import time
+
+import pytest
+
+from assistant import log_rotation
+from assistant.log_rotation import RotatingLog, archives, prune
+
+
+@pytest.fixture
+def log(tmp_path):
+    handler = RotatingLog(tmp_path / "xswarm.log", max_mb=0.001, rotate_hours=24, total_mb=100)
+    handler.setFormatter(logging.Formatter("%(asctime)s - %(message)s"))
+    yield handler
+    handler.close()
+
+
+def emit(handler, message):
+    handler.emit(logging.makeLogRecord({"msg": message, "levelno": logging.INFO}))
+
+
+def test_rotates_by_size(log, tmp_path):
+    for n in range(40):
+        emit(log, f"line {n} " + "x" * 40)
+    rotated = archives(tmp_path)
+    assert rotated and all(path.suffix == ".gz" for path in rotated)
+    with gzip.open(rotated[0], "rt") as archive:
+        assert "line 0 " in archive.read()
+    assert (tmp_path / "xswarm.log").stat().st_size <= 1100
+
+
+def test_rotates_by_age(log, tmp_path):
+    log.maxBytes = 0
+    emit(log, "yesterday")
+    log.opened_at -= 25 * 3600
+    emit(log, "today")
+    assert len(archives(tmp_path)) == 1
+    assert "yesterday" not in (tmp_path / "xswarm.log").read_text() and "today" in (tmp_path / "xswarm.log").read_text()
+
+
+def test_age_carried_over(tmp_path):
+    path = tmp_path / "xswarm.log"
+    path.write_text("2026-10-14 08:00:00,123 - started\n")
+    handler = RotatingLog(path)
+    try:
+        assert time.time() - handler.opened_at > 24 * 3600  # Rotated at its next line
+    finally:
+        handler.close()
+
+
+def test_total_cap(tmp_path, monkeypatch):
+    monkeypatch.setattr(log_rotation, "MB", 100)  # Sizes in units of 100 bytes
+    for day in (14, 15, 16):
+        (tmp_path / f"xswarm-202610{day}-090000.log.gz").write_bytes(b"x" * 100)
+    (tmp_path / "xswarm.log").write_bytes(b"x" * 50)
+
+    assert [path.name for path, _ in prune(tmp_path, total_mb=2, dry_run=True)] == [
+        "xswarm-20261014-090000.log.gz", "xswarm-20261015-090000.log.gz"]
+    assert len(archives(tmp_path)) == 3
+    assert [size for _, size in prune(tmp_path, total_mb=3)] == [100]
+    assert [path.name for path in archives(tmp_path)] == [
+        "xswarm-20261015-090000.log.gz", "xswarm-20261016-090000.log.gz"]
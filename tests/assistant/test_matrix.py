@@ -0,0 +1,77 @@
+"""
+Tests for the Matrix bridge (assistant/matrix.py).
+
+Covers:
+- Only allowed users' invites and messages getting through
+- Messages from before the bridge started being skipped
+- Replies going back to the room the message came from, and not after local chat
+- Settings the bridge can't start without
+"""
+
+import asyncio
+from types import SimpleNamespace
+
+import pytest
+
+from assistant.matrix import MatrixBridge, MatrixError, relay_chat, set_matrix_bridge
+
+
+class FakeClient:
+    user_id = "@xswarm:example.org"
+
+    def __init__(self):
+        self.joined = []
+        self.sent = []
+
+    async def join(self, room_id):
+        self.joined.append(room_id)
+
+    async def room_send(self, room_id, message_type, content, ignore_unverified_devices=False):
+        self.sent.append((room_id, content["body"]))
+
+
+def make_bridge():
+    received = []
+    bridge = MatrixBridge(FakeClient(), ["@me:example.org"], lambda *args: received.append(args), started_ms=1000)
+    return bridge, received
+
+
+def test_only_allowed_users():
+    bridge, received = make_bridge()
+    assert asyncio.run(bridge.handle_invite("!home:example.org", "@me:example.org"))
+    assert not asyncio.run(bridge.handle_invite("!spam:example.org", "@stranger:example.org"))
+    assert bridge.client.joined == ["!home:example.org"]
+
+    assert bridge.handle_message("!home:example.org", "@me:example.org", " what's next? ", 2000)
+    assert not bridge.handle_message("!home:example.org", "@stranger:example.org", "hi", 2000)
+    assert not bridge.handle_message("!home:example.org", "@xswarm:example.org", "Dentist at 4.", 2000)  # Its own
+    assert not bridge.handle_message("!home:example.org", "@me:example.org", "old news", 500)  # Before start
+    assert received == [("what's next?", "@me:example.org", "!home:example.org")]
+
+
+@pytest.mark.asyncio
+async def test_replies_go_to_the_room():
+    bridge, _ = make_bridge()
+    set_matrix_bridge(bridge)
+    try:
+        bridge.handle_message("!home:example.org", "@me:example.org", "what's next?", 2000)
+        relay_chat("User", "what's next?")  # Shown in the chat: replies now go to that room
+        relay_chat("Jarvis", "▌")
+        relay_chat("Jarvis", "Dentist at 4.")
+        relay_chat("Jarvis", "Dentist at 4.")
+        relay_chat("thinking", "Checking the calendar")
+        relay_chat("User", "and tomorrow?")  # Typed locally
+        relay_chat("Jarvis", "Nothing tomorrow.")
+        await asyncio.sleep(0)
+    finally:
+        set_matrix_bridge(None)
+    assert bridge.client.sent == [("!home:example.org", "Dentist at 4.")]
+
+
+def test_from_config(tmp_path):
+    assert MatrixBridge.from_config(SimpleNamespace(matrix_enabled=False), print) is None
+    with pytest.raises(MatrixError, match="matrix_allowed_users"):
+        MatrixBridge.from_config(SimpleNamespace(matrix_enabled=True, matrix_allowed_users=[]), print)
+    with pytest.raises(MatrixError, match="dev matrix login"):
+        MatrixBridge.from_config(SimpleNamespace(matrix_enabled=True, matrix_allowed_users=["@me:example.org"]),
+                                 print, credentials_path=tmp_path / "credentials.json")
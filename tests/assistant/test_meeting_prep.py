@@ -0,0 +1,196 @@
+"""
+Tests for pre-meeting prep briefs (assistant/meeting_prep.py).
+
+Covers:
+- Naming and matching participants given as emails, names or numbers
+- Gathering recent messages, past conversations, profile facts and project
+  notes into one brief
+- On-screen and spoken wording
+- Per-category timing, opt-outs and speech
+- Delivery channels and the prepare_for_meeting tool
+"""
+
+import asyncio
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant import tools
+from assistant.config import Config
+from assistant.inbox import InboxStore
+from assistant.memory import PersistentChatHistory, UserProfile
+from assistant.meeting_prep import (
+    PrepRule,
+    PrepSettings,
+    build_brief,
+    deliver_prep,
+    due_preps,
+    participant_name,
+    participant_terms,
+)
+from assistant.planner import PlannerData
+
+NOW = datetime(2026, 10, 16, 13, 50)
+
+
+def _stores(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    inbox = InboxStore(tmp_path / "inbox")
+    profile = UserProfile(tmp_path / "profile")
+    history = PersistentChatHistory(storage_dir=tmp_path / "history")
+    return planner, inbox, profile, history
+
+
+def _meeting(planner, start=NOW + timedelta(minutes=10), **kwargs):
+    kwargs.setdefault("attendees", ["alice@example.com"])
+    return planner.add_calendar_event("Pricing review", start.isoformat(),
+                                      (start + timedelta(hours=1)).isoformat(), **kwargs)
+
+
+def _message(sender, subject, days_ago, channel="email"):
+    received = (NOW - timedelta(days=days_ago)).isoformat()
+    return {"id": f"{sender}-{days_ago}", "channel": channel, "sender": sender, "content": f"About {subject}",
+            "subject": subject, "received_at": received}
+
+
+@pytest.mark.parametrize("attendee, name, terms", [
+    ("alice@example.com", "Alice", ["alice@example.com", "alice"]),
+    ("alice.smith@example.com", "Alice", ["alice.smith@example.com", "alice"]),
+    ("Bob Jones", "Bob", ["bob jones", "bob"]),
+    ("Bob", "Bob", ["bob"]),
+    ("+15551234567", "+15551234567", ["+15551234567"]),
+    ("al@example.com", "Al", ["al@example.com"]),  # Too short to search text for
+])
+def test_participants(attendee, name, terms):
+    assert participant_name(attendee) == name
+    assert participant_terms(attendee) == terms
+
+
+class TestBrief:
+    def test_gathers_every_source(self, tmp_path):
+        planner, inbox, profile, history = _stores(tmp_path)
+        project = planner.add_project("Pricing", next_action="Agree the new tiers")
+        planner.add_task("Draft tier table", project_id=project.id, notes="Enterprise tier still undecided")
+        planner.add_task("Unrelated", notes="Not this project")
+        inbox.merge_remote([
+            _message("Alice <alice@example.com>", "Pricing change", 2),
+            _message("alice@example.com", "Older thread", 5),
+            _message("malice@example.com", "Not her", 1),
+        ])
+        profile.add_fact("relationship", "Alice is the CFO")
+        history.add_message("user", "Alice wants to delay the pricing change until January")
+        history.add_message("user", "Talked to Alicia about lunch")
+
+        brief = build_brief(_meeting(planner, project_id=project.id), planner, inbox, profile, history, now=NOW)
+        lines = brief.text.splitlines()
+        assert lines[0] == "Prep for Pricing review:"
+        assert "  • 📧 Alice emailed Oct 14: Pricing change" in lines
+        assert any(line.startswith("  • 💬 Last talked about Alice") and "delay the pricing change" in line
+                   for line in lines)
+        assert "  • 👤 Alice is the CFO" in lines
+        assert "  • 📁 Pricing (green); next: Agree the new tiers" in lines
+        assert "  • 📌 Draft tier table: Enterprise tier still undecided" in lines
+        assert not any("Not her" in line or "Alicia" in line or "Unrelated" in line for line in lines)
+
+    def test_old_messages_are_not_recent(self, tmp_path):
+        planner, inbox, _, _ = _stores(tmp_path)
+        inbox.merge_remote([_message("alice@example.com", "Ancient history", 45)])
+        assert build_brief(_meeting(planner), planner, inbox=inbox, now=NOW).items == []
+
+    def test_spoken(self, tmp_path):
+        planner, inbox, profile, history = _stores(tmp_path)
+        inbox.merge_remote([_message("alice@example.com", "the pricing change", 1)])
+        profile.add_fact("relationship", "Alice is the CFO")
+        brief = build_brief(_meeting(planner), planner, inbox, profile, history, now=NOW, minutes=10)
+        assert brief.title == "Prep for Pricing review in 10 minutes"
+        assert brief.spoken == ("Prep for Pricing review in ten minutes: Alice last emailed about "
+                                "the pricing change. The rest is on screen.")
+
+    def test_failing_source_is_skipped(self, tmp_path):
+        planner, inbox, _, _ = _stores(tmp_path)
+        inbox.merge_remote([_message("alice@example.com", "Pricing change", 1)])
+
+        class BrokenHistory:
+            def search_all_sessions(self, query, max_results=20):
+                raise OSError("disk gone")
+
+        brief = build_brief(_meeting(planner), planner, inbox=inbox, history=BrokenHistory(), now=NOW)
+        assert [item.kind for item in brief.items] == ["message"]
+
+
+class TestScheduling:
+    def test_window_and_once_per_event(self, tmp_path):
+        planner, *_ = _stores(tmp_path)
+        event = _meeting(planner)
+        _meeting(planner, attendees=[])  # Nothing to prepare for
+        settings = PrepSettings(minutes=10)
+        prepared = set()
+
+        assert due_preps(planner.get_calendar_events(), NOW - timedelta(minutes=1), prepared, settings) == []
+        due = due_preps(planner.get_calendar_events(), NOW, prepared, settings)
+        assert [(e.id, rule.spoken, minutes) for e, rule, minutes in due] == [(event.id, False, 10)]
+        assert due_preps(planner.get_calendar_events(), NOW + timedelta(minutes=1), prepared, settings) == []
+
+    def test_per_category(self, tmp_path):
+        planner, *_ = _stores(tmp_path)
+        work = _meeting(planner, start=NOW + timedelta(minutes=20), tags=["work"])
+        _meeting(planner, tags=["personal"])
+        settings = PrepSettings.from_config(Config(meeting_prep_categories={
+            "work": {"minutes": 30, "spoken": True}, "personal": {"enabled": False},
+        }))
+        due = due_preps(planner.get_calendar_events(), NOW, set(), settings)
+        assert [(e.id, rule.spoken, minutes) for e, rule, minutes in due] == [(work.id, True, 20)]
+
+    def test_rules(self):
+        settings = PrepSettings(minutes=0, categories={"work": {"minutes": 15}})
+        assert settings.rule_for([]) == PrepRule(enabled=False, minutes=0, spoken=False)
+        assert settings.rule_for(["home", "work"]) == PrepRule(enabled=True, minutes=15, spoken=False)
+        assert PrepSettings.from_config(Config()).rule_for([]) == PrepRule(enabled=True, minutes=10, spoken=False)
+
+
+class TestDelivery:
+    def _brief(self, tmp_path):
+        planner, inbox, *_ = _stores(tmp_path)
+        inbox.merge_remote([_message("alice@example.com", "Pricing change", 1)])
+        return build_brief(_meeting(planner, tags=["work"]), planner, inbox=inbox, now=NOW, minutes=10)
+
+    def test_screen_only_by_default(self, tmp_path):
+        lines, notices, spoken = [], [], []
+
+        class Announcer:
+            async def announce(self, text, tags=()):
+                spoken.append(text)
+                return True
+
+        def notify(title, body, tags=()):
+            notices.append((title, body, list(tags)))
+            return True
+
+        brief = self._brief(tmp_path)
+        delivered = asyncio.run(deliver_prep(brief, PrepRule(), activity=lines.append, notify=notify,
+                                             announcer=Announcer()))
+        assert delivered == {"activity": True, "desktop": True, "speech": False}
+        assert lines == ["📝 Prep for Pricing review in 10 minutes:", "📝 • 📧 Alice emailed Oct 15: Pricing change"]
+        assert notices == [("Prep for Pricing review in 10 minutes", "📧 Alice emailed Oct 15: Pricing change", ["work"])]
+        assert spoken == []
+
+        delivered = asyncio.run(deliver_prep(brief, PrepRule(spoken=True), notify=notify, announcer=Announcer()))
+        assert delivered["speech"] is True
+        assert spoken == ["Prep for Pricing review in ten minutes: Alice last emailed about Pricing change."]
+
+
+class TestTool:
+    def test_prepare_for_meeting(self, tmp_path, monkeypatch):
+        planner, inbox, profile, history = _stores(tmp_path)
+        monkeypatch.setattr(tools, "_planner_data", planner)
+        monkeypatch.setattr(tools, "_inbox_store", inbox)
+        monkeypatch.setattr(tools, "_user_profile", profile)
+        monkeypatch.setattr(tools, "_chat_history", history)
+        assert tools.prepare_for_meeting() == "✗ No upcoming meetings with attendees or a project"
+
+        start = (datetime.now() + timedelta(days=1)).replace(hour=9, minute=0, second=0, microsecond=0)
+        _meeting(planner, start=start, attendees=["Bob Jones"])
+        profile.add_fact("relationship", "Bob is my manager")
+        assert tools.prepare_for_meeting() == "Prep for Pricing review:\n  • 👤 Bob is my manager"
+        assert tools.prepare_for_meeting("pricing").startswith("Prep for Pricing review:")
+        assert tools.prepare_for_meeting("standup") == "✗ No upcoming meeting matching 'standup'"
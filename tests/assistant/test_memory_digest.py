@@ -0,0 +1,106 @@
+"""
+Tests for the weekly memory digest (assistant/memory_digest.py).
+
+Covers:
+- "what did we discuss this week?" and the other ways of asking; the job's cron line
+- The week's conversations (every persona's), topics, bookmarks, facts, reminders and finished tasks,
+  and nothing from before the week
+- The Markdown report saved into the vault folder, and the spoken version
+"""
+
+import json
+import types
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.bookmarks import BookmarkStore
+from assistant.events import EventStore
+from assistant.memory_digest import build_digest, digest_cron, is_digest_request, save_digest
+from assistant.planner import PlannerData
+
+NOW = datetime(2026, 10, 18, 18, 0)  # A Sunday
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("what did we discuss this week?", True),
+    ("So what have we talked about", True),
+    ("read me the weekly digest please", True),
+    ("what did we discuss with the landlord?", False),
+    ("what did I miss?", False),
+])
+def test_request(text, expected):
+    assert is_digest_request(text) == expected
+
+
+def test_cron():
+    assert digest_cron("sunday", "18:00") == "0 18 * * 0"
+    assert digest_cron("Mon", "07:30") == "30 7 * * 1"
+    with pytest.raises(ValueError):
+        digest_cron("someday", "18:00")
+
+
+def session(path, started, *messages):
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps({"session_id": path.stem, "started_at": started.isoformat(), "summary": None,
+                                "messages": [{"role": role, "content": text, "timestamp": at.isoformat()}
+                                             for role, text, at in messages]}))
+
+
+@pytest.fixture
+def sources(tmp_path):
+    chat = tmp_path / "chat_history"
+    week, old = NOW - timedelta(days=2), NOW - timedelta(days=10)
+    session(chat / "jarvis" / "session_1.json", week,
+            ("user", "When does the lease end?", week), ("assistant", "In March.", week),
+            ("user", "Can the landlord extend the lease to June?", week), ("assistant", "Ask the landlord.", week))
+    session(chat / "marvin" / "session_2.json", week, ("user", "Email the landlord about the lease", week))
+    session(chat / "jarvis" / "session_0.json", old, ("user", "Plan the garden party", old))
+
+    bookmarks = BookmarkStore(tmp_path / "memory")
+    bookmarks.add("lease terms", [{"sender": "User", "text": "When does the lease end?"}])
+    bookmarks.bookmarks()[0].created_at = week.isoformat()
+    bookmarks.add("garden", [{"sender": "User", "text": "Plan the garden party"}])
+    bookmarks.bookmarks()[1].created_at = old.isoformat()
+
+    fact = types.SimpleNamespace
+    profile = types.SimpleNamespace(facts=[fact(fact="Rents a flat in Leeds", added_at=week.isoformat()),
+                                           fact(fact="Likes tea", added_at=old.isoformat())])
+    events = EventStore(":memory:", keep_days=None)
+    events.record("reminder", "⏰ Dentist in 10 minutes", at=week)
+    events.record("reminder", "⏰ Garden party in 10 minutes", at=old)
+    events.record("activity", "Synced", at=week)
+
+    planner = PlannerData(tmp_path / "planner")
+    for title, done in (("Call landlord", week), ("Buy seeds", old), ("Pay rent", None)):
+        task = planner.add_task(title)
+        if done:
+            planner.update_task(task.id, status="complete", completed_at=done.isoformat())
+    return dict(now=NOW, chat_dir=chat, profile=profile, bookmarks=bookmarks, events=events, planner=planner)
+
+
+def test_digest(sources, tmp_path):
+    digest = build_digest(**sources)
+    assert (digest.sessions, digest.messages) == (2, 5)
+    assert digest.topics == ["lease", "landlord"]
+    assert digest.bookmarks == ["lease terms"]
+    assert digest.facts == ["Rents a flat in Leeds"]
+    assert digest.reminders == ["⏰ Dentist in 10 minutes"]
+    assert digest.completed == ["Call landlord"]
+    assert digest.spoken() == ("This week we talked 2 times, mostly about lease and landlord. "
+                               "You bookmarked 'lease terms'. I learned 1 new thing about you. "
+                               "1 reminder went out. You finished 1 task: Call landlord.")
+
+    path = save_digest(digest, tmp_path / "vault")
+    assert path.name == "2026-W42.md"
+    report = path.read_text()
+    assert report.startswith("# Weekly digest 2026-W42\n\nSun Oct 11 – Sun Oct 18, 2026\n\n## Conversations\n"
+                             "2 conversations, 5 messages.\nTopics: lease, landlord\n")
+    assert "## Learned about you\n- Rents a flat in Leeds\n" in report and "garden" not in report.lower()
+
+
+def test_quiet_week(sources):
+    digest = build_digest(**{**sources, "now": NOW + timedelta(days=30)})
+    assert digest.is_empty()
+    assert digest.spoken() == "There's nothing to look back on from this week yet."
+    assert "None this week." in digest.markdown()
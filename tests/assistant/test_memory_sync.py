@@ -0,0 +1,203 @@
+"""
+Tests for the encrypted memory sync between the user's machines (assistant/memory_sync.py).
+
+Covers:
+- Blobs sealed with the group key; another group's key (or a damaged blob) can't open them
+- The key exchange: the code is never sent; the joiner gets the group key with the right code, not
+  with a wrong one, and a proof made for someone else's key doesn't count
+- sync_join/sync_confirm on the companion server: the key only goes out for a valid proof, wrong
+  proofs use up the code's tries and a used code can't be redeemed again
+- Two machines through a relay that only stores blobs: facts, list items and preferences,
+  deletions, and the newest change winning
+- List sync through the server stops once memory sync is set up
+"""
+
+import asyncio
+import itertools
+import types
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+from assistant import lists, memory_sync
+from assistant.lists import ListStore, sync_lists
+from assistant.memory import UserProfile
+from assistant.memory_sync import (
+    MemorySync, SyncError, SyncState, accept_key, check_proof, key_challenge, key_offer, key_proof, key_request,
+    parse_pairing_uri, seal, unseal,
+)
+from assistant.pairing import CODE_ATTEMPTS, CompanionServer, CompanionSession, DeviceRegistry
+from assistant.scheduling_preferences import SchedulingPreferences, parse_preference
+
+
+class FakeRelay:
+    """The server's side: blobs in arrival order, readable only as ciphertext."""
+
+    def __init__(self):
+        self.blobs = []
+
+    async def call(self, route, json=None, params=None):
+        if route.method == "POST":
+            blob_id = f"{len(self.blobs) + 1:015d}-0000abcd"
+            self.blobs.append({"id": blob_id, "device": json["device"], "blob": json["blob"]})
+            return types.SimpleNamespace(json=lambda: {"id": blob_id})
+        since = params.get("since", "")
+        newer = [b for b in self.blobs if b["id"] > since]
+        return types.SimpleNamespace(json=lambda: {"blobs": newer[:2], "more": len(newer) > 2})
+
+
+def machine(tmp_path, name, state, relay):
+    root = tmp_path / name
+    return MemorySync(state, relay, "user-1", UserProfile(root / "profile"), ListStore(root / "lists"),
+                      SchedulingPreferences(root / "prefs"), root / "memory_sync.json")
+
+
+@pytest.fixture
+def machines(tmp_path):
+    relay = FakeRelay()
+    state = SyncState.create()
+    laptop_state = SyncState(state.group, state.key)
+    return machine(tmp_path, "desktop", state, relay), machine(tmp_path, "laptop", laptop_state, relay), relay
+
+
+def sync(*machines):
+    for m in machines:
+        asyncio.run(m.sync())
+
+
+def test_seal_and_unseal():
+    state = SyncState.create()
+    blob = seal(state, {"changes": [{"record": "fact:x"}]})
+    assert "fact" not in blob
+    assert unseal(state, blob) == {"changes": [{"record": "fact:x"}]}
+    with pytest.raises(SyncError):
+        unseal(SyncState.create(), blob)
+    with pytest.raises(SyncError):
+        unseal(state, blob[:-8] + "AAAAAAAA")
+
+
+def test_key_exchange():
+    state = SyncState.create()
+    private, message = key_request("laptop")
+    assert message["type"] == "sync_join" and "482913" not in str(message)
+    ours, challenge = key_challenge(message["public_key"])
+    confirm = key_proof(private, challenge, "482913")
+    assert "482913" not in str(confirm)
+    assert check_proof(ours, message["public_key"], confirm["proof"], "482913")
+    assert not check_proof(ours, message["public_key"], key_proof(private, challenge, "111111")["proof"], "482913")
+
+    reply = key_offer(state, ours, message["public_key"], "482913")
+    joined = accept_key(private, challenge, reply, "482913")
+    assert (joined.group, joined.key) == (state.group, state.key)
+    assert joined.device != state.device
+    with pytest.raises(SyncError, match="code"):
+        accept_key(private, challenge, reply, "111111")
+    with pytest.raises(SyncError, match="Wrong pairing code"):
+        accept_key(private, challenge, {"type": "error", "error": "Wrong pairing code"}, "482913")
+    with pytest.raises(SyncError, match="X25519"):
+        key_challenge("x")
+    assert parse_pairing_uri("xswarm://pair?host=10.0.0.2&port=8765&code=482913") == ("10.0.0.2", 8765, "482913")
+
+
+def test_proof_is_bound_to_both_keys():
+    """Someone in the middle swapping in their own key can't reuse the joiner's proof."""
+    private, message = key_request()
+    _, intruder = key_request()
+    ours, challenge = key_challenge(message["public_key"])
+    proof = key_proof(private, challenge, "482913")["proof"]
+    assert not check_proof(ours, intruder["public_key"], proof, "482913")
+    other, _ = key_challenge(message["public_key"])
+    assert not check_proof(other, message["public_key"], proof, "482913")
+
+
+def test_sync_join_uses_up_the_code(tmp_path):
+    registry = DeviceRegistry(tmp_path / "devices.json")
+    offer = registry.start_pairing()
+    state = SyncState.create()
+    joins = []
+
+    def on_sync_join(private, public_key, code):
+        joins.append(code)
+        return key_offer(state, private, public_key, code)
+
+    server = CompanionServer(registry, lambda text, device: None, lambda: {}, on_sync_join=on_sync_join)
+
+    def attempt(code):
+        session = CompanionSession(None, "10.0.0.2")
+        private, message = key_request("laptop")
+        challenge = asyncio.run(server.handle(session, message))
+        reply = asyncio.run(server.handle(session, key_proof(private, challenge, code)))
+        return private, challenge, reply
+
+    unasked = asyncio.run(server.handle(CompanionSession(None, "10.0.0.2"), {"type": "sync_confirm", "proof": "x"}))
+    assert unasked == {"type": "error", "error": "Send sync_join first"}
+    assert attempt("000000")[2] == {"type": "error", "error": "Wrong pairing code"}
+    private, challenge, reply = attempt(offer.code)
+    assert accept_key(private, challenge, reply, offer.code).key == state.key
+    assert attempt(offer.code)[2]["type"] == "error"  # Used up
+    assert joins == [offer.code]
+    assert registry.devices() == []
+
+    offer = registry.start_pairing()
+    replies = [attempt("000000")[2] for _ in range(CODE_ATTEMPTS)] + [attempt(offer.code)[2]]
+    assert "Too many wrong codes" in replies[-2]["error"] and "No pairing" in replies[-1]["error"]
+    assert len(joins) == 1  # Still only the first join got the key
+
+
+def test_two_machines(machines, monkeypatch):
+    start = datetime.now(timezone.utc)
+    clock = ((start + timedelta(seconds=s)).isoformat(timespec="seconds") for s in itertools.count(1))
+    monkeypatch.setattr(lists, "_now", lambda: next(clock))  # Each list change a second after the last
+    desktop, laptop, relay = machines
+    desktop.profile.add_fact("preference", "User is vegetarian")
+    desktop.lists.add("shopping", ["milk"])
+    desktop.preferences.add(parse_preference("I never take meetings before 10"))
+    laptop.lists.add("shopping", ["eggs"])
+    sync(desktop, laptop, desktop)
+
+    for m in (desktop, laptop):
+        assert [f.fact for f in m.profile.facts] == ["User is vegetarian"]
+        assert sorted(i.text for i in m.lists.items("shopping")) == ["eggs", "milk"]
+        assert [c.value for c in m.preferences.constraints()] == ["10:00"]
+    assert all("vegetarian" not in b["blob"] for b in relay.blobs)
+    assert laptop.lists.pending == []  # Went to the relay, not to /api/lists
+
+    # A removal on one machine removes it on the other
+    laptop.profile.remove_fact("User is vegetarian")
+    laptop.lists.remove("shopping", "milk")
+    sync(laptop, desktop)
+    assert desktop.profile.facts == []
+    assert [i.text for i in desktop.lists.items("shopping")] == ["eggs"]
+    assert asyncio.run(desktop.sync()) == {"pushed": 0, "applied": 0}
+
+
+def test_newest_change_wins(machines):
+    desktop, laptop, _ = machines
+    desktop.preferences.add(parse_preference("I never take meetings before 10"))
+    sync(desktop, laptop)
+    (constraint,) = laptop.preferences.constraints()
+    laptop.preferences.remove(constraint.id)
+    sync(laptop)
+    # The desktop still had the old one; it's older than the laptop's removal
+    desktop.state.known[f"preference:{constraint.id}"]["at"] = "2000-01-01T00:00:00+00:00"
+    sync(desktop)
+    assert desktop.preferences.constraints() == []
+
+    desktop.profile.add_fact("identity", "User lives in Lisbon")
+    sync(desktop)
+    laptop.state.known["fact:user lives in lisbon"] = {"hash": "", "at": "9999-01-01", "deleted": True}
+    sync(laptop)
+    assert laptop.profile.facts == []  # Its own (newer) removal stands
+
+
+def test_list_sync_off_with_memory_sync(tmp_path, monkeypatch):
+    class NoServer:
+        async def call(self, route, **kwargs):
+            raise AssertionError("lists went to the server")
+
+    store = ListStore(tmp_path / "lists")
+    store.add("shopping", ["milk"])
+    SyncState.create().save(tmp_path / "memory_sync.json")
+    monkeypatch.setattr(memory_sync, "SYNC_PATH", tmp_path / "memory_sync.json")
+    assert asyncio.run(sync_lists(None, "user-1", store=store, client=NoServer())) == \
+        {"pushed": 0, "changed": 0, "pending": 1}
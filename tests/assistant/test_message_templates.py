@@ -0,0 +1,88 @@
+"""
+Tests for reminder templates (assistant/message_templates.py).
+
+Covers:
+- Built-in wording on every channel when nothing is configured
+- Templates per channel, picked by the reminder's category before "default"
+- Validation: unknown channels, bad syntax, unknown variables and empty output are rejected
+- Fallback to the built-in wording when a template fails at delivery
+- deliver_reminder sends the templated text on each channel
+"""
+
+import asyncio
+from datetime import datetime
+
+import pytest
+
+from assistant.config import Config
+from assistant.message_templates import MessageTemplates, get_message_templates, set_message_templates
+from assistant.reminders import Reminder, deliver_reminder
+
+START = datetime(2026, 10, 16, 9, 0)
+
+
+def _reminder(tags=()):
+    return Reminder(key="k", title="Standup", start=START, minutes=10, tags=list(tags))
+
+
+def test_builtin_wording():
+    templates = MessageTemplates.from_config(Config())
+    context = _reminder().context()
+    assert [templates.render(channel, context) for channel in ("activity", "desktop_title", "desktop", "speech")] == [
+        "⏰ Standup in 10 minutes", "Upcoming event", "Standup in 10 minutes", "Reminder: Standup in ten minutes."]
+
+
+def test_category_before_default():
+    templates = MessageTemplates({
+        "default": {"speech": "Heads up: {{ title }} starts {{ when }}."},
+        "#Work": {"speech": "{{ category|capitalize }}: {{ title }} at {{ time }}.", "desktop_title": "Work"},
+    })
+    assert templates.render("speech", _reminder(["work"]).context()) == "Work: Standup at 09:00."
+    assert templates.render("speech", _reminder(["health"]).context()) == "Heads up: Standup starts in ten minutes."
+    assert templates.render("desktop_title", _reminder(["health"]).context()) == "Upcoming event"
+    assert templates.pick("desktop", ["work"]) is None
+
+
+@pytest.mark.parametrize("channel, source, problem", [
+    ("sms", "{{ title }}", "unknown channel"),
+    ("speech", "{{ title ", "unexpected end of template"),
+    ("speech", "{{ titel }}", "'titel' is undefined"),
+    ("speech", "{% if false %}x{% endif %}", "renders as nothing"),
+    ("speech", "{{ title.__class__.__mro__ }}", "unsafe"),
+])
+def test_invalid_templates_are_left_out(channel, source, problem):
+    templates = MessageTemplates({"work": {channel: source}})
+    assert problem in templates.errors[0][2] and templates.errors[0][:2] == ("work", channel)
+    assert templates.pick(channel, ["work"]) is None
+
+
+def test_failing_template_falls_back():
+    templates = MessageTemplates({"default": {"desktop": "{{ title }} ({{ tags[0] }})"}})
+    assert templates.errors == []  # The sample reminder has a tag
+    assert templates.render("desktop", _reminder().context()) == "Standup in 10 minutes"
+
+
+class Announcer:
+    def __init__(self):
+        self.said = []
+
+    async def announce(self, text, tags=None):
+        self.said.append(text)
+        return True
+
+
+def test_delivery_uses_templates():
+    set_message_templates(MessageTemplates({"work": {
+        "activity": "💼 {{ text }}", "desktop_title": "Work", "desktop": "{{ title }} at {{ time }}",
+        "speech": "{{ title }} is {{ when }}.",
+    }}))
+    try:
+        activity, notified, announcer = [], [], Announcer()
+        notify = lambda title, body, tags=None: notified.append((title, body)) or True
+        delivered = asyncio.run(deliver_reminder(_reminder(["work"]), activity.append, notify, announcer))
+        assert delivered == {"activity": True, "desktop": True, "speech": True}
+        assert activity == ["💼 Standup in 10 minutes"] and notified == [("Work", "Standup at 09:00")]
+        assert announcer.said == ["Standup is in ten minutes."]
+    finally:
+        set_message_templates(None)
+    assert get_message_templates().sources == {}
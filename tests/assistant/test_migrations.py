@@ -0,0 +1,99 @@
+"""
+Tests for on-disk format migrations (assistant/migrations.py).
+
+Covers:
+- Running pending migrations in order and recording the version
+- Backing up existing data before migrating
+- Refusing data from a newer version
+- A failing migration keeping the last good version
+- The event store and planner file migrating when opened
+"""
+
+import json
+import sqlite3
+
+import pytest
+
+from assistant.events import MIGRATIONS, EventStore
+from assistant.migrations import Migration, MigrationFailed, SchemaTooNew, migrate_json, migrate_sqlite
+from assistant.planner import PLANNER_MIGRATIONS, PlannerData
+
+STEPS = [
+    Migration(1, "notes table", lambda db: db.execute("CREATE TABLE notes (text TEXT)")),
+    Migration(2, "pinned column", lambda db: db.execute("ALTER TABLE notes ADD COLUMN pinned INTEGER DEFAULT 0")),
+]
+
+
+def version(db):
+    return db.execute("PRAGMA user_version").fetchone()[0]
+
+
+def test_sqlite_runs_pending_in_order(tmp_path):
+    path = tmp_path / "notes.db"
+    db = sqlite3.connect(str(path))
+    assert [m.version for m in migrate_sqlite(db, path, "notes", STEPS[:1])] == [1]
+    db.execute("INSERT INTO notes VALUES ('milk')")
+    db.commit()
+    assert list(tmp_path.glob("*.bak")) == []  # Nothing to back up when it was empty
+
+    assert [m.version for m in migrate_sqlite(db, path, "notes", STEPS)] == [2]
+    assert version(db) == 2
+    assert db.execute("SELECT text, pinned FROM notes").fetchall() == [("milk", 0)]
+    backups = list(tmp_path.glob("notes.db.v1-*.bak"))
+    assert len(backups) == 1
+    assert migrate_sqlite(db, path, "notes", STEPS) == []
+
+
+def test_refuses_newer_data(tmp_path):
+    db = sqlite3.connect(":memory:")
+    db.execute("PRAGMA user_version = 3")
+    with pytest.raises(SchemaTooNew, match="format 3, but this xswarm only knows up to 2"):
+        migrate_sqlite(db, None, "notes", STEPS)
+    with pytest.raises(SchemaTooNew):
+        migrate_json({"schema_version": 5}, None, "planner", PLANNER_MIGRATIONS)
+
+
+def test_failed_migration_keeps_last_version(tmp_path):
+    db = sqlite3.connect(":memory:")
+    broken = STEPS[:1] + [Migration(2, "broken", lambda db: db.execute("ALTER TABLE missing ADD x"))]
+    with pytest.raises(MigrationFailed, match="Migrating notes to format 2 \\(broken\\) failed"):
+        migrate_sqlite(db, None, "notes", broken)
+    assert version(db) == 1
+
+
+def test_json():
+    steps = [Migration(1, "lists", lambda data: data.setdefault("tasks", [])),
+             Migration(2, "rename", lambda data: {"items": data.pop("tasks")})]
+    data, ran = migrate_json({"tasks": ["a"]}, None, "todo", steps)
+    assert data == {"items": ["a"], "schema_version": 2}
+    assert len(ran) == 2
+
+
+def test_event_store_versioned(tmp_path):
+    path = tmp_path / "events.db"
+    old = sqlite3.connect(str(path))  # A database from before versioning
+    old.execute("CREATE TABLE events (id INTEGER PRIMARY KEY AUTOINCREMENT, type TEXT NOT NULL, "
+                "ts REAL NOT NULL, summary TEXT NOT NULL, data TEXT NOT NULL DEFAULT '{}')")
+    old.execute("INSERT INTO events (type, ts, summary) VALUES ('activity', 1, 'Synced')")
+    old.commit()
+    old.close()
+
+    store = EventStore(path, keep_days=None)
+    assert [m.version for m in store.migrated] == [m.version for m in MIGRATIONS]
+    assert [e.summary for e in store.query()] == ["Synced"]
+    store.close()
+    assert list(tmp_path.glob("events.db.v0-*.bak"))
+    assert EventStore(path, keep_days=None).migrated == []
+
+
+def test_planner_file_upgraded(tmp_path):
+    (tmp_path / "planner.json").write_text(json.dumps({"version": "1.0", "tasks": []}))
+    planner = PlannerData(tmp_path)
+    assert planner.get_projects() == []
+    saved = json.loads((tmp_path / "planner.json").read_text())
+    assert saved["schema_version"] == 1 and saved["calendar_events"] == []
+
+    (tmp_path / "planner.json").write_text(json.dumps({"schema_version": 99, "tasks": []}))
+    with pytest.raises(SchemaTooNew):
+        PlannerData(tmp_path).reload()
+    assert json.loads((tmp_path / "planner.json").read_text())["schema_version"] == 99
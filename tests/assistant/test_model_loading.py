@@ -0,0 +1,88 @@
+"""
+Tests for background model loading.
+
+Covers:
+- Stage updates from the voice server become progress for the footer
+- Ready, error, server exit and timeout end the wait
+- Progress bar rendering
+- Text-only config skips voice
+"""
+
+import asyncio
+import queue
+
+from assistant import model_loading
+from assistant.config import Config
+from assistant.model_loading import LoadProgress, render_bar, wait_for_server
+
+
+def _queues(*status, ready=True):
+    status_queue, server_to_client = queue.Queue(), queue.Queue()
+    for item in status:
+        status_queue.put(item)
+    if ready:
+        server_to_client.put("ready")
+    return server_to_client, status_queue
+
+
+def _wait(s2c, status, **kwargs):
+    updates = []
+    result = asyncio.run(wait_for_server(s2c, status, on_progress=updates.append, **kwargs))
+    return result, updates
+
+
+class TestWaitForServer:
+    def test_reports_stages_then_ready(self):
+        s2c, status = _queues(("info", "LOG: Loading"), ("progress", "download"), ("progress", "weights"))
+        ready, updates = _wait(s2c, status)
+
+        assert ready
+        assert [u.stage for u in updates] == ["starting", "download", "weights", "ready"]
+        fractions = [u.fraction for u in updates]
+        assert fractions == sorted(fractions)
+        assert updates[-1].done
+
+    def test_error_from_server(self):
+        s2c, status = _queues(("progress", "weights"), ("error", "out of memory"), ready=False)
+        ready, updates = _wait(s2c, status)
+
+        assert not ready
+        assert updates[-1].error == "out of memory"
+
+    def test_server_exit(self):
+        s2c, status = _queues(ready=False)
+        ready, updates = _wait(s2c, status, is_alive=lambda: False)
+
+        assert not ready
+        assert updates[-1].error == "voice server exited"
+
+    def test_timeout(self, monkeypatch):
+        monkeypatch.setattr(model_loading, "POLL_INTERVAL", 0.01)
+        s2c, status = _queues(ready=False)
+        ready, updates = _wait(s2c, status, timeout=0.05)
+
+        assert not ready
+        assert "timed out" in updates[-1].error
+
+    def test_unknown_stage_uses_its_name(self):
+        progress = LoadProgress.for_stage("quantizing")
+        assert progress.label == "quantizing"
+        assert not progress.done
+
+
+class TestRenderBar:
+    def test_fill(self):
+        assert render_bar(0.0) == "▱" * 10
+        assert render_bar(0.3) == "▰" * 3 + "▱" * 7
+        assert render_bar(1.0) == "▰" * 10
+
+    def test_clamps(self):
+        assert render_bar(1.5, width=4) == "▰" * 4
+        assert render_bar(-1, width=4) == "▱" * 4
+
+
+class TestTextOnly:
+    def test_voice_active(self):
+        assert not Config().voice_active
+        assert Config(voice_enabled=True).voice_active
+        assert not Config(voice_enabled=True, text_only=True).voice_active
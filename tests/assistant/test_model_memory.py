@@ -0,0 +1,72 @@
+"""
+Tests for voice model memory caps and the startup memory report.
+
+Covers:
+- Variant selection steps down from the requested variant to fit the cap
+- Per-component report lines and cap overrun detection
+- Memory reported by the voice server is recorded while waiting for ready
+"""
+
+import asyncio
+import queue
+
+from assistant.model_loading import wait_for_server
+from assistant.model_memory import GB, MemoryReport, estimated_voice_gb, select_voice_variant
+
+
+class TestSelectVoiceVariant:
+    def test_no_cap_keeps_request(self):
+        assert select_voice_variant("bf16") == "bf16"
+        assert select_voice_variant("q8") == "q8"
+
+    def test_auto_starts_from_q4(self):
+        assert select_voice_variant("auto") == "q4"
+
+    def test_cap_steps_down(self):
+        assert select_voice_variant("bf16", cap_gb=10) == "q8"
+        assert select_voice_variant("bf16", cap_gb=6) == "q4"
+
+    def test_never_steps_up(self):
+        assert select_voice_variant("q4", cap_gb=64) == "q4"
+
+    def test_nothing_fits(self):
+        assert select_voice_variant("q8", cap_gb=2) is None
+
+    def test_estimate_includes_codec_and_transcriber(self):
+        assert estimated_voice_gb("q4") > 4.6
+
+
+class TestMemoryReport:
+    def test_lines_and_total(self):
+        report = MemoryReport(cap_gb=6)
+        report.record("Moshi LM (q4)", ram_bytes=1 * GB, gpu_bytes=4 * GB)
+        report.record("Vosk transcriber", ram_bytes=GB // 2)
+
+        lines = report.lines()
+        assert lines[0] == "   Moshi LM (q4): 1.0GB RAM, 4.0GB GPU"
+        assert lines[1] == "   Vosk transcriber: 0.5GB RAM"
+        assert lines[-1] == "   Total: 5.5GB (cap 6GB)"
+        assert not report.over_cap
+
+    def test_over_cap(self):
+        report = MemoryReport(cap_gb=6)
+        report.record("Moshi LM (q8)", gpu_bytes=8 * GB)
+        assert report.over_cap
+        assert report.lines()[-1].endswith("OVER)")
+
+    def test_negative_deltas_clamped(self):
+        report = MemoryReport()
+        report.record("Mimi codec", ram_bytes=-5000)
+        assert report.total_bytes == 0
+        assert not report.over_cap
+
+
+def test_wait_for_server_records_memory():
+    status, s2c = queue.Queue(), queue.Queue()
+    status.put(("memory", "Moshi LM (q4)", GB, 4 * GB))
+    s2c.put("ready")
+    report = MemoryReport()
+
+    assert asyncio.run(wait_for_server(s2c, status, memory_report=report))
+    assert [c.name for c in report.components] == ["Moshi LM (q4)"]
+    assert report.total_bytes == 5 * GB
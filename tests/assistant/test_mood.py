@@ -0,0 +1,84 @@
+"""
+Tests for the persona's mood over a session (assistant/personas/mood.py).
+
+Covers:
+- Resting mood from traits, and how messages read (warmth, energy)
+- Drift stays within the persona's range and eases back to rest
+- The time of day pulls energy
+- The mood tunes the reply: temperature and the prompt line
+- Turned off per persona; reset by the tool and by a new persona
+"""
+
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant import tools
+from assistant.chat_engine import ChatEngineConfig
+from assistant.personas import mood as mood_module
+from assistant.personas.config import MoodSettings, PersonaConfig, PersonalityTraits
+from assistant.personas.mood import Mood, MoodModel, get_mood_model, resting_mood, tone
+
+NOON = datetime(2026, 10, 16, 12, 0)  # The time of day pulls energy nowhere at noon
+
+
+def _persona(name="Jarvis", **mood):
+    return PersonaConfig(name=name, mood=MoodSettings(**mood))
+
+
+@pytest.mark.parametrize("warmth, energy, label", [
+    (0.0, 0.0, "neutral"), (0.4, 0.4, "cheerful"), (-0.4, 0.4, "irritable"), (0.0, -0.4, "tired"),
+    (-0.4, -0.4, "gloomy"), (0.4, -0.4, "relaxed"), (-0.4, 0.0, "cool"),
+])
+def test_labels(warmth, energy, label):
+    assert Mood(warmth, energy).label == label
+
+
+def test_rest_and_tone():
+    assert resting_mood(PersonalityTraits()) == Mood(0.0, 0.0)
+    assert resting_mood(PersonalityTraits(enthusiasm=0.9, extraversion=0.8, agreeableness=0.9)).label == "cheerful"
+    assert tone("thanks, that's perfect!") == (1.0, 0.8)
+    assert tone("ugh, wrong again")[0] < -0.5
+    assert tone("STOP DOING THAT") == (-0.3, 0.5)
+    assert tone("add milk to the list") == (0.0, 0.0)
+
+
+def test_drift_is_bounded_and_eases_back():
+    model = MoodModel(_persona(range=0.4, sensitivity=0.3, half_life_minutes=30), now=NOON)
+    for minute in range(5):
+        model.observe("thanks, that's perfect!", NOON + timedelta(minutes=minute))
+    assert model.current(NOON + timedelta(minutes=4)) == Mood(0.4, 0.4)  # At the edge of the range
+    assert model.current(NOON + timedelta(minutes=4)).label == "cheerful"
+    assert model.current(NOON + timedelta(minutes=34)) == Mood(0.2, 0.2)  # Half-way back after a half-life
+    model.observe("ugh, this is useless", NOON + timedelta(minutes=35))
+    assert model.current(NOON + timedelta(minutes=35)).warmth < 0.2
+
+
+def test_time_of_day():
+    model = MoodModel(_persona(), now=NOON)
+    assert model.current(datetime(2026, 10, 16, 23, 30)) == Mood(0.0, -0.3)
+    assert model.current(datetime(2026, 10, 16, 23, 30)).label == "tired"
+    assert model.current(datetime(2026, 10, 16, 10, 0)).energy == 0.2
+
+
+def test_shapes_the_reply():
+    model = MoodModel(_persona(), now=NOON)
+    assert model.prompt_line(NOON) == ""  # Neutral says nothing
+    assert model.shape(ChatEngineConfig(temperature=0.7), NOON).temperature == 0.7
+    model.observe("awesome!! love it, thanks!", NOON)
+    assert model.prompt_line(NOON).startswith("Current mood: cheerful. Let it colour your tone (upbeat and playful)")
+    assert model.shape(ChatEngineConfig(temperature=0.7), NOON).temperature == 0.745
+
+
+def test_off_reset_and_persona_change(monkeypatch):
+    off = MoodModel(_persona(enabled=False), now=NOON)
+    off.observe("I HATE THIS, IT'S USELESS", NOON)
+    assert off.current(datetime(2026, 10, 16, 23, 0)) == Mood()
+
+    monkeypatch.setattr(mood_module, "_model", None)
+    model = get_mood_model(_persona("Jarvis"))
+    model.observe("ugh, useless, wrong again")
+    assert get_mood_model() is model
+    assert tools.reset_mood().startswith("✓ Mood reset: ")
+    assert model._mood == model.rest
+    assert get_mood_model(_persona("GLaDOS")) is not model
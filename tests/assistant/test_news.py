@@ -0,0 +1,151 @@
+"""
+Tests for RSS/Atom news in the briefing (assistant/news.py).
+
+Covers:
+- RSS 2.0 and Atom parsing: links, guids, dates, HTML descriptions as text
+- Per-feed schedules: new feeds fetch straight away, then on their schedule
+- Fetching: conditional requests, new items only, a new feed's backlog counted as read
+- Summaries from the AI client, or the description's first sentence without one
+- The briefing groups the newest unread items by feed and never repeats them
+"""
+
+import asyncio
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.announcements import briefing_context
+from assistant.news import NewsError, NewsReader, NewsStore, news_briefing, parse_feed
+
+NOW = datetime(2026, 10, 16, 6, 58)
+
+RSS = """<?xml version="1.0"?>
+<rss version="2.0"><channel><title>Rust Blog</title>
+{items}
+</channel></rss>"""
+
+ATOM = """<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>Example Atom</title>
+<entry><id>urn:1</id><title>Atom &amp; you</title><link rel="alternate" href="https://example.com/1"/>
+<updated>2026-10-15T08:00:00Z</updated><summary type="html">&lt;p&gt;First &lt;b&gt;entry&lt;/b&gt;.&lt;/p&gt;</summary></entry>
+</feed>"""
+
+
+def _rss(*titles):
+    return RSS.format(items="".join(
+        f"<item><title>{title}</title><link>https://blog.rust-lang.org/{n}</link>"
+        f"<pubDate>Thu, {10 + n:02d} Oct 2026 12:00:00 +0000</pubDate>"
+        f"<description>&lt;p&gt;{title} is out. More details inside.&lt;/p&gt;</description></item>"
+        for n, title in enumerate(titles)))
+
+
+def test_parse_rss_and_atom():
+    title, entries = parse_feed(_rss("Rust 1.90"))
+    assert title == "Rust Blog"
+    assert entries[0]["guid"] == entries[0]["link"] == "https://blog.rust-lang.org/0"
+    assert entries[0]["description"] == "Rust 1.90 is out. More details inside."
+    assert entries[0]["published"][:10] == "2026-10-10"
+    title, entries = parse_feed(ATOM)
+    assert (title, entries[0]["title"], entries[0]["link"], entries[0]["guid"]) == (
+        "Example Atom", "Atom & you", "https://example.com/1", "urn:1")
+    assert entries[0]["description"] == "First entry ."
+    with pytest.raises(NewsError):
+        parse_feed("<html><body>not a feed</body></html>")
+
+
+def test_feeds_and_schedules(tmp_path):
+    store = NewsStore(tmp_path)
+    feed = store.add_feed("https://blog.rust-lang.org/feed.xml", "the Rust blog", "06:00")
+    with pytest.raises(NewsError, match="Already following"):
+        store.add_feed("https://blog.rust-lang.org/feed.xml")
+    with pytest.raises(NewsError):
+        store.add_feed("https://example.com/x.xml", schedule="whenever")
+    assert store.get_feed("Rust blog").id == feed.id
+    assert store.due(NOW) == [feed]  # Never fetched
+    feed.last_fetched = NOW.isoformat()
+    store.update_feed(feed)
+    assert store.due(NOW + timedelta(hours=12)) == []
+    assert [f.id for f in store.due(NOW + timedelta(days=1))] == [feed.id]
+
+
+class Response:
+    def __init__(self, text, status_code=200, headers=None):
+        self.text, self.status_code, self.headers = text, status_code, headers or {}
+
+    def raise_for_status(self):
+        if self.status_code >= 400:
+            raise RuntimeError(f"HTTP {self.status_code}")
+
+
+def _serve(monkeypatch, responses):
+    sent = []
+
+    class Client:
+        def __init__(self, **kwargs):
+            self.headers = kwargs.get("headers", {})
+
+        async def __aenter__(self):
+            return self
+
+        async def __aexit__(self, *exc):
+            return False
+
+        async def get(self, url):
+            sent.append(dict(self.headers))
+            return responses.pop(0)
+
+    import httpx
+    monkeypatch.setattr(httpx, "AsyncClient", Client)
+    return sent
+
+
+class FakeAI:
+    def __init__(self):
+        self.calls = 0
+
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        self.calls += 1
+        return f"  Summary of {messages[1]['content'].splitlines()[0]}. "
+
+
+def test_fetch_summarize_and_brief(tmp_path, monkeypatch):
+    store = NewsStore(tmp_path)
+    feed = store.add_feed("https://blog.rust-lang.org/feed.xml")
+    sent = _serve(monkeypatch, [
+        Response(_rss("A", "B", "C", "D", "E"), headers={"etag": '"v1"'}),
+        Response("", status_code=304),
+        Response(_rss("A", "B", "C", "D", "E", "F")),
+    ])
+    ai = FakeAI()
+    reader = NewsReader(store, ai)
+    new = asyncio.run(reader.fetch_due(NOW))
+    assert [item.title for item in new] == ["E", "D", "C"]  # The rest of the backlog counts as read
+    assert store.get_feed(feed.id).name == "Rust Blog" and ai.calls == 3
+    assert store.unread()[0].summary == "Summary of E."
+
+    assert asyncio.run(reader.fetch_due(NOW + timedelta(minutes=30))) == []  # Not due yet
+    assert asyncio.run(reader.fetch_due(NOW + timedelta(hours=1))) == []  # 304 Not Modified
+    assert sent[1]["If-None-Match"] == '"v1"'
+
+    briefing = news_briefing(store, limit=2)
+    assert briefing == "Two new items from Rust Blog. Summary of E. Summary of D."
+    context = briefing_context(NOW, news=news_briefing(store))
+    assert context["briefing"] == "There's nothing on the calendar today. One new item from Rust Blog. Summary of C."
+    assert news_briefing(store) == ""  # Nothing is repeated
+
+    reader.ai = None
+    new = asyncio.run(reader.fetch_due(NOW + timedelta(hours=2)))
+    assert [(item.title, item.summary) for item in new] == [("F", "F is out.")]
+    assert NewsStore(tmp_path).unread()[0].title == "F"  # Saved
+
+
+def test_failed_fetch_is_recorded(tmp_path, monkeypatch):
+    store = NewsStore(tmp_path)
+    store.add_feed("https://example.com/feed.xml", "Example")
+    _serve(monkeypatch, [Response("", status_code=500)])
+    with pytest.raises(NewsError, match="Example"):
+        asyncio.run(NewsReader(store).fetch_due(NOW))
+    assert store.feeds()[0].error == "HTTP 500" and store.due(NOW) == []  # Retried on its schedule
@@ -0,0 +1,105 @@
+"""
+Tests for the shared notification policy (quiet hours).
+
+Covers:
+- Windows that wrap midnight and same-day windows
+- Per-channel priority thresholds and emergency bypass
+- Disabled quiet hours never suppress
+- Speech held or whispered during busy calendar events
+"""
+
+from datetime import datetime
+
+from assistant.config import Config
+from assistant.notifications import Meeting, NotificationPolicy, current_meeting
+from assistant.planner import CalendarEvent
+
+
+def _policy(**overrides):
+    settings = {"quiet_hours_enabled": True, "quiet_hours_start": "22:00", "quiet_hours_end": "07:00"}
+    settings.update(overrides)
+    return NotificationPolicy(Config(**settings))
+
+
+NIGHT = datetime(2026, 10, 14, 23, 30)
+EARLY = datetime(2026, 10, 15, 6, 59)
+DAY = datetime(2026, 10, 14, 12, 0)
+
+
+class TestQuietHoursWindow:
+    def test_overnight_window(self):
+        policy = _policy()
+        assert policy.is_in_quiet_hours(NIGHT)
+        assert policy.is_in_quiet_hours(EARLY)
+        assert not policy.is_in_quiet_hours(DAY)
+        assert not policy.is_in_quiet_hours(datetime(2026, 10, 15, 7, 0))
+
+    def test_same_day_window(self):
+        policy = _policy(quiet_hours_start="13:00", quiet_hours_end="14:00")
+        assert policy.is_in_quiet_hours(datetime(2026, 10, 14, 13, 30))
+        assert not policy.is_in_quiet_hours(NIGHT)
+
+    def test_disabled_never_quiet(self):
+        policy = _policy(quiet_hours_enabled=False)
+        assert not policy.is_in_quiet_hours(NIGHT)
+        assert policy.allows("speech", "low", NIGHT)
+
+
+class TestChannelDecisions:
+    def test_suppressed_until_window_ends(self):
+        decision = _policy().check("speech", "high", NIGHT)
+        assert not decision.allowed
+        assert decision.resume_at == datetime(2026, 10, 15, 7, 0)
+
+    def test_emergency_bypasses_every_channel(self):
+        policy = _policy()
+        for channel in ("speech", "desktop", "suggestions", "sms", "call"):
+            assert policy.allows(channel, "emergency", NIGHT)
+
+    def test_per_channel_override(self):
+        policy = _policy(quiet_hours_channels={"desktop": "high", "sms": "low"})
+        assert policy.allows("desktop", "high", NIGHT)
+        assert not policy.allows("desktop", "normal", NIGHT)
+        assert policy.allows("sms", "low", NIGHT)
+        assert not policy.allows("speech", "high", NIGHT)
+
+    def test_everything_allowed_outside_quiet_hours(self):
+        assert _policy().allows("suggestions", "low", DAY)
+
+
+class TestMeetings:
+    EVENTS = [
+        CalendarEvent("evt-1", "Standup", "2026-10-14T11:45:00", "2026-10-14T12:15:00"),
+        CalendarEvent("evt-2", "Planning", "2026-10-14T11:30:00", "2026-10-14T12:30:00"),
+        CalendarEvent("evt-3", "Lunch", "2026-10-14T12:00:00", "2026-10-14T13:00:00", busy=False),
+    ]
+
+    def _policy(self, **overrides):
+        return NotificationPolicy(Config(**overrides), meetings=lambda now: current_meeting(self.EVENTS, now))
+
+    def test_current_meeting(self):
+        assert current_meeting(self.EVENTS, DAY) == Meeting("Planning", datetime(2026, 10, 14, 12, 30))
+        assert current_meeting(self.EVENTS, datetime(2026, 10, 14, 12, 45)) is None  # Lunch shows as free
+        assert current_meeting(self.EVENTS, datetime(2026, 10, 14, 12, 30)) is None
+
+    def test_speech_held_or_whispered(self):
+        policy = self._policy()
+        held = policy.check("speech", "normal", DAY)
+        assert (held.allowed, held.defer, held.resume_at) == (False, True, datetime(2026, 10, 14, 12, 30))
+        assert held.reason == "in Planning until 12:30"
+        whispered = policy.check("speech", "high", DAY)
+        assert whispered.allowed and whispered.whisper
+        emergency = policy.check("speech", "emergency", DAY)
+        assert emergency.allowed and not emergency.whisper
+        assert policy.check("desktop", "normal", DAY) == policy.check("desktop", "normal", NIGHT)
+        assert policy.check("speech", "low", datetime(2026, 10, 14, 14, 0)).allowed
+
+    def test_quiet_hours_come_first(self):
+        policy = self._policy(quiet_hours_enabled=True, quiet_hours_start="12:00", quiet_hours_end="13:00",
+                              quiet_hours_channels={"speech": "high"})
+        assert not policy.check("speech", "normal", DAY).defer  # Dropped for quiet hours, as before
+        assert policy.check("speech", "high", DAY).whisper
+
+    def test_turned_off(self):
+        assert self._policy(meeting_quiet=False).check("speech", "low", DAY).allowed
+        assert NotificationPolicy(Config()).check("speech", "low", DAY).allowed
@@ -0,0 +1,140 @@
+"""
+Tests for changing one occurrence of a recurring event (calendar_core.py, planner.py, occurrences.py).
+
+Covers:
+- Occurrence ids: every expanded occurrence has "<series id>@<date due>"
+- get/update/cancel one occurrence, the first one included; the rest of the series is untouched
+- The tools: update or delete by occurrence id, and "undo that" brings a skipped one back
+- Changing the series' pattern drops its exceptions
+- Reminders leave out skipped occurrences and follow moved ones
+- "skip next week's standup" and the other ways of saying it
+"""
+
+from datetime import date, datetime
+
+import pytest
+
+from assistant import tools
+from assistant.calendar_core import split_occurrence_id
+from assistant.occurrences import match_skip, parse_skip_request
+from assistant.planner import PlannerData
+from assistant.reminders import due_reminders
+from assistant.undo import UndoLog, undo_last
+
+TODAY = date(2026, 10, 15)  # A Thursday
+
+
+@pytest.fixture
+def planner(tmp_path, monkeypatch):
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    monkeypatch.setattr(tools, "_undo_log", UndoLog(tmp_path / "undo"))
+    return planner
+
+
+def _standup(planner):
+    return planner.add_calendar_event("Standup", "2026-10-12T09:00:00", "2026-10-12T09:15:00", recurrence="daily")
+
+
+def _days(planner, start="2026-10-12", end="2026-10-18"):
+    return [(e.start_time[:16], e.title) for e in planner.get_calendar_events(start, end)]
+
+
+def test_occurrence_ids(planner):
+    standup = _standup(planner)
+    events = planner.get_calendar_events("2026-10-12", "2026-10-14")
+    assert [e.id for e in events] == [standup.id, f"{standup.id}@2026-10-13", f"{standup.id}@2026-10-14"]
+    assert split_occurrence_id(f"{standup.id}@2026-10-13") == (standup.id, "2026-10-13")
+    assert split_occurrence_id("evt_a@home") == ("evt_a@home", None)
+
+
+def test_cancel_one(planner):
+    standup = _standup(planner)
+    assert planner.delete_calendar_event(f"{standup.id}@2026-10-14")
+    assert "2026-10-14T09:00" not in [start for start, _ in _days(planner)]
+    assert len(_days(planner)) == 6
+    assert planner.get_calendar_event(f"{standup.id}@2026-10-14") is None
+    assert planner.get_calendar_event(standup.id) is not None  # The series stays
+    assert not planner.delete_calendar_event(f"{standup.id}@2026-10-11")  # Before it started
+
+
+def test_change_one(planner):
+    standup = _standup(planner)
+    moved = planner.update_calendar_event(f"{standup.id}@2026-10-14", start_time="2026-10-15T11:00:00",
+                                          end_time="2026-10-15T11:15:00", title="Standup (moved)")
+    assert moved.start_time.startswith("2026-10-15T11:00") and moved.id == f"{standup.id}@2026-10-14"
+    assert ("2026-10-15T11:00", "Standup (moved)") in _days(planner)
+    assert ("2026-10-15T09:00", "Standup") in _days(planner)
+    assert [t for _, t in _days(planner, "2026-10-14", "2026-10-14")] == []
+    # Setting a field back to the series' value drops it from the exception
+    planner.update_calendar_event(f"{standup.id}@2026-10-14", title="Standup")
+    assert planner.get_calendar_event(standup.id).exceptions == {
+        "2026-10-14": {"start_time": "2026-10-15T11:00:00", "end_time": "2026-10-15T11:15:00"}}
+
+
+def test_the_first_occurrence(planner):
+    standup = _standup(planner)
+    planner.delete_calendar_event(f"{standup.id}@2026-10-12")
+    assert _days(planner)[0] == ("2026-10-13T09:00", "Standup")
+    assert planner.get_calendar_events(expand_recurring=False)[0].start_time == "2026-10-12T09:00:00"
+
+
+def test_a_new_pattern_drops_exceptions(planner):
+    standup = _standup(planner)
+    planner.delete_calendar_event(f"{standup.id}@2026-10-14")
+    planner.update_calendar_event(standup.id, start_time="2026-10-12T09:30:00")  # Same day: kept
+    assert planner.get_calendar_event(standup.id).exceptions
+    planner.update_calendar_event(standup.id, recurrence="weekly")
+    assert planner.get_calendar_event(standup.id).exceptions == {}
+
+
+def test_tools_and_undo(planner):
+    standup = _standup(planner)
+    occurrence = f"{standup.id}@2026-10-14"
+    assert tools.update_calendar_event(occurrence, start_time="10:00") == (
+        "✓ Updated event: 'Standup' on Wed Oct 14 only")
+    assert planner.get_calendar_event(occurrence).start_time == "2026-10-14T10:00:00"
+    assert tools.delete_calendar_event(occurrence).startswith("✓ Skipped 'Standup' on Wed Oct 14")
+    assert planner.get_calendar_event(occurrence) is None
+    assert undo_last(tools.get_undo_log(), planner=planner) == "✓ Undone: Skipped 'Standup' on Wed Oct 14"
+    assert planner.get_calendar_event(occurrence).start_time == "2026-10-14T10:00:00"  # Back as it was
+
+
+def test_reminders(planner):
+    standup = _standup(planner)
+    planner.delete_calendar_event(f"{standup.id}@2026-10-14")
+    planner.update_calendar_event(f"{standup.id}@2026-10-15", start_time="2026-10-15T10:00:00")
+
+    def reminded(day, now):
+        return [r.start for r in due_reminders(planner.get_calendar_events(day, day), now, set())]
+
+    assert reminded("2026-10-14", datetime(2026, 10, 14, 8, 50)) == []
+    assert reminded("2026-10-15", datetime(2026, 10, 15, 8, 50)) == []
+    assert reminded("2026-10-15", datetime(2026, 10, 15, 9, 50)) == [datetime(2026, 10, 15, 10)]
+
+
+@pytest.mark.parametrize("text, phrase, start, end", [
+    ("skip next week's standup", "standup", "2026-10-19", "2026-10-25"),
+    ("cancel just tomorrow's 1:1", "1:1", "2026-10-16", "2026-10-16"),
+    ("cancel the standup on monday", "standup", "2026-10-19", "2026-10-19"),
+    ("Skip standup tomorrow please.", "standup", "2026-10-16", "2026-10-16"),
+    ("skip this week's team sync only", "team sync", "2026-10-15", "2026-10-18"),
+    ("cancel the standup", None, None, None),  # No day: the whole series is the AI's call
+    ("cancel my timer", None, None, None),
+])
+def test_parse_skip_request(text, phrase, start, end):
+    request = parse_skip_request(text, TODAY)
+    if phrase is None:
+        assert request is None
+    else:
+        assert (request.phrase, request.start.isoformat(), request.end.isoformat()) == (phrase, start, end)
+
+
+def test_match_skip(planner):
+    standup = planner.add_calendar_event("Team standup", "2026-10-16T09:00:00", "2026-10-16T09:15:00",
+                                         recurrence="weekly")
+    planner.add_calendar_event("Dentist", "2026-10-16T14:00:00", "2026-10-16T15:00:00")
+    assert match_skip("skip tomorrow's standup", planner, TODAY).id == f"{standup.id}@2026-10-16"
+    assert match_skip("skip next week's standup", planner, TODAY).id == f"{standup.id}@2026-10-23"
+    assert match_skip("cancel tomorrow's dentist", planner, TODAY) is None  # Not recurring
+    assert match_skip("skip monday's standup", planner, TODAY) is None  # Not due then
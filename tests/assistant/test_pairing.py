@@ -0,0 +1,125 @@
+"""
+Tests for companion device pairing (assistant/pairing.py).
+
+Covers:
+- Pairing with the offered code, and codes that are wrong, reused or expired
+- Authenticating with the device token, and revocation
+- Scopes limiting what a device may do
+- Notifications and chat replies pushed to connected devices
+"""
+
+import asyncio
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.pairing import (
+    CODE_ATTEMPTS, CODE_TTL, CompanionServer, CompanionSession, DeviceRegistry, PairingError,
+    forward_chat, notify_companions, set_companion_server,
+)
+
+
+class Clock:
+    def __init__(self):
+        self.now = datetime(2026, 10, 16, 9, 0)
+
+    def __call__(self):
+        return self.now
+
+
+@pytest.fixture
+def registry(tmp_path):
+    return DeviceRegistry(tmp_path / "devices.json", clock=Clock())
+
+
+def make_session():
+    sent = []
+
+    async def send(message):
+        sent.append(message)
+    session = CompanionSession(send, "192.168.1.30")
+    return session, sent
+
+
+def test_pair_and_authenticate(registry, tmp_path):
+    offer = registry.start_pairing()
+    device, token = registry.complete_pairing(offer.code, "Pixel 8")
+    assert device.scopes == ["notify", "message", "status"]
+    assert token not in (tmp_path / "devices.json").read_text()  # Stored hashed
+    with pytest.raises(PairingError, match="No pairing in progress"):
+        registry.complete_pairing(offer.code, "Again")  # One device per code
+
+    reloaded = DeviceRegistry(tmp_path / "devices.json")
+    assert reloaded.authenticate(token).name == "Pixel 8"
+    assert reloaded.authenticate("guess") is None
+
+    assert reloaded.revoke("pixel 8").id == device.id
+    assert registry.authenticate(token) is None  # Picks up the revocation from the file
+    assert registry.revoke("Pixel 8") is None
+
+
+def test_wrong_and_expired_codes(registry):
+    offer = registry.start_pairing()
+    wrong = "000000" if offer.code != "000000" else "111111"
+    for _ in range(CODE_ATTEMPTS - 1):
+        with pytest.raises(PairingError, match="Wrong pairing code"):
+            registry.complete_pairing(wrong, "Phone")
+    with pytest.raises(PairingError, match="Too many wrong codes"):
+        registry.complete_pairing(wrong, "Phone")
+    with pytest.raises(PairingError):
+        registry.complete_pairing(offer.code, "Phone")
+
+    offer = registry.start_pairing()
+    registry.clock.now += CODE_TTL + timedelta(seconds=1)
+    with pytest.raises(PairingError, match="No pairing in progress"):
+        registry.complete_pairing(offer.code, "Phone")
+    with pytest.raises(PairingError, match="Unknown scope"):
+        registry.start_pairing(["camera"])
+
+
+@pytest.mark.asyncio
+async def test_scopes(registry):
+    received = []
+    server = CompanionServer(registry, lambda text, device: received.append((text, device.name)),
+                             status=lambda: {"dnd": False})
+    session, _ = make_session()
+    assert (await server.handle(session, {"type": "message", "text": "hi"}))["type"] == "error"
+
+    offer = registry.start_pairing(["notify", "message"])
+    reply = await server.handle(session, {"type": "pair", "code": offer.code, "name": "Watch"})
+    assert reply["type"] == "paired" and reply["scopes"] == ["notify", "message"]
+    assert await server.handle(session, {"type": "message", "text": "what's next?"}) == {"type": "accepted"}
+    assert received == [("what's next?", "Watch")]
+    assert (await server.handle(session, {"type": "status"}))["error"] == "This device can't see status"
+
+    other, _ = make_session()
+    assert (await server.handle(other, {"type": "auth", "token": reply["token"]}))["type"] == "ready"
+    registry.revoke("Watch")
+    assert (await server.handle(other, {"type": "message", "text": "hi"}))["error"] == "Pair or authenticate first"
+
+
+@pytest.mark.asyncio
+async def test_pushes(registry):
+    server = CompanionServer(registry, lambda text, device: None, status=dict)
+    notified, notified_sent = make_session()
+    muted, muted_sent = make_session()
+    for session, scopes in ((notified, None), (muted, ["status"])):
+        offer = registry.start_pairing(scopes)
+        await server.handle(session, {"type": "pair", "code": offer.code, "name": f"Device {len(server.sessions)}"})
+        server.sessions.append(session)
+
+    set_companion_server(server)
+    try:
+        assert notify_companions("Upcoming event", "Dentist at 4", "high")
+        forward_chat("Jarvis", "Let me check...")  # Still streaming
+        forward_chat("Jarvis", "Dentist at 4.")
+        forward_chat("Jarvis", "Dentist at 4.")  # Repeated update
+        await asyncio.sleep(0.01)
+    finally:
+        set_companion_server(None)
+    assert notified_sent == [
+        {"type": "notification", "title": "Upcoming event", "body": "Dentist at 4", "priority": "high"},
+        {"type": "chat", "sender": "Jarvis", "text": "Dentist at 4."},
+    ]
+    assert muted_sent == []
+    assert not notify_companions("Nobody", "listening")
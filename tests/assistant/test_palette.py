@@ -0,0 +1,62 @@
+"""
+Tests for the command palette entries (assistant/palette.py).
+
+Covers:
+- Fuzzy matching: letters in order, word starts and runs ranked first
+- Entries: every keymap action with its keys, plus the examples of set-up capabilities
+- Filtering keeps the usual order for an empty query and puts the best match first
+- Ctrl+P opens it, Alt+X in the emacs preset (where Ctrl+P is the previous tab)
+"""
+
+import pytest
+
+from assistant.config import Config
+from assistant.keymap import Keymap
+from assistant.palette import filter_entries, fuzzy_score, palette_entries
+
+
+@pytest.mark.parametrize("query, text, matches", [
+    ("gtin", "Go to Inbox", True),
+    ("go inbox", "Go to Inbox", True),
+    ("inbox", "Go to Inbox", True),
+    ("xbni", "Go to Inbox", False),
+    ("", "anything", True),
+])
+def test_fuzzy_score(query, text, matches):
+    assert (fuzzy_score(query, text) is not None) is matches
+
+
+def test_word_starts_and_runs_rank_higher():
+    assert fuzzy_score("copy", "Copy activity log") > fuzzy_score("copy", "Cancel / back, or paste your copy")
+    assert fuzzy_score("gi", "Go to Inbox") > fuzzy_score("gi", "Go to Settings")
+
+
+def test_entries():
+    entries = palette_entries(Keymap(), Config())
+    by_title = {entry.title: entry for entry in entries}
+    assert by_title["Go to Inbox"].action == "goto_inbox" and by_title["Go to Inbox"].detail == "8"
+    assert by_title["Copy activity log"].detail == "Ctrl+L"
+    assert "Command palette" not in by_title
+    timer = by_title["Timers: set a pasta timer for 9 minutes"]
+    assert timer.say == "set a pasta timer for 9 minutes" and not timer.action
+    assert not any(title.startswith("Emergency alert") for title in by_title)  # Not set up
+
+
+def test_unbound_actions_are_still_listed():
+    entries = palette_entries(Keymap(overrides={"copy_logs": ""}), Config())
+    assert next(e for e in entries if e.title == "Copy activity log").detail == ""
+
+
+def test_filter():
+    entries = palette_entries(Keymap(), Config())
+    assert filter_entries(entries, "") == entries
+    assert filter_entries(entries, "go inbox")[0].title == "Go to Inbox"
+    assert filter_entries(entries, "pasta")[0].say == "set a pasta timer for 9 minutes"
+    assert filter_entries(entries, "qqqzzz") == []
+
+
+def test_keys():
+    assert Keymap().keys("command_palette") == ["ctrl+p"]
+    assert Keymap("vi").keys("command_palette") == ["ctrl+p"]
+    assert Keymap("emacs").keys("command_palette") == ["alt+x"]
+    assert ("ctrl+p", "command_palette", "Command palette", True) in Keymap().bindings()
@@ -0,0 +1,79 @@
+"""
+Tests for persona phrase banks (assistant/personas/phrases.py).
+
+Covers:
+- Weighted picks, with the last picks of a kind not repeated while there's another phrase
+- Phrases needing an unknown placeholder skipped; kinds a persona leaves out use the defaults
+- A persona's phrases.yaml loaded by PersonaManager and made the current bank on switching
+- Only phrases without placeholders offered to the speech cache
+"""
+
+import random
+from collections import Counter
+from pathlib import Path
+
+import pytest
+
+import assistant.personas as personas_package
+from assistant.personas import PersonaManager
+from assistant.personas.phrases import DEFAULT_PHRASES, PhraseBank, get_phrase_bank, set_phrase_bank
+
+
+@pytest.fixture(autouse=True)
+def reset_bank():
+    set_phrase_bank(None)
+    yield
+    set_phrase_bank(None)
+
+
+def test_weighted_picks_without_repeats():
+    bank = PhraseBank({"acknowledgment": [{"text": "Very good.", "weight": 8}, "Right away.", "As you wish.",
+                                          {"text": "Never.", "weight": 0}]},
+                      rng=random.Random(7))
+    picks = [bank.pick("acknowledgment") for _ in range(300)]
+    assert "Never." not in picks
+    assert all(a != b for a, b in zip(picks, picks[1:]))
+    assert all(len(set(picks[i:i + 3])) == 3 for i in range(len(picks) - 2))  # The last two aren't repeated
+
+    fresh = PhraseBank({"acknowledgment": [{"text": "Very good.", "weight": 8}, "Right away."]},
+                       rng=random.Random(7))
+    firsts = Counter()
+    for _ in range(200):
+        fresh._recent["acknowledgment"].clear()
+        firsts[fresh.pick("acknowledgment")] += 1
+    assert firsts["Very good."] > 3 * firsts["Right away."]
+
+    single = PhraseBank({"filler": ["One moment."]})
+    assert [single.pick("filler") for _ in range(3)] == ["One moment."] * 3
+
+
+def test_placeholders_and_defaults():
+    bank = PhraseBank({"greeting": ["Good {time_of_day}, {user}.", "Welcome back, {user}."]}, persona="JARVIS")
+    assert bank.pick("greeting", user=None, time_of_day="morning") is None
+    assert bank.pick("greeting", user="Tony", time_of_day="evening") in ("Good evening, Tony.", "Welcome back, Tony.")
+
+    defaults = PhraseBank(persona="JARVIS")
+    assert defaults.pick("greeting", time_of_day="morning") in ("Good morning. How can I help?",
+                                                                "Hello, I'm JARVIS. How can I help you today?")
+    assert defaults.pick("apology") in DEFAULT_PHRASES["apology"]
+    assert PhraseBank({"filler": []}).pick("filler") is None
+    assert PhraseBank({"farewell": ["Bye."], "filler": [42, "Hm."]}).phrases["filler"] == \
+        PhraseBank({"filler": ["Hm."]}).phrases["filler"]
+
+
+def test_persona_phrases_loaded():
+    manager = PersonaManager(Path(personas_package.__file__).parent)
+    assert get_phrase_bank().persona == ""
+    assert manager.set_current_persona("JARVIS")
+
+    bank = get_phrase_bank()
+    assert bank.persona == "JARVIS"
+    assert "Very good." in [phrase.text for phrase in bank.phrases["acknowledgment"]]
+    assert bank.pick("greeting", user="Tony", time_of_day="morning")
+
+
+def test_cacheable():
+    bank = PhraseBank({"greeting": ["Good {time_of_day}, {user}.", "At your service."], "filler": ["One moment."]})
+    cacheable = bank.cacheable()
+    assert "At your service." in cacheable and "One moment." in cacheable
+    assert not any("{" in text for text in cacheable)
@@ -0,0 +1,73 @@
+"""
+Tests for the battery/thermal-aware performance profile (assistant/power.py).
+
+Covers:
+- Battery or serious thermal pressure switches auto mode to low power
+- Leaving low power waits for several calm samples; sampling is rate limited
+- A pinned power_mode ignores the machine
+- macOS speed limits and Linux zone temperatures map to thermal levels
+"""
+
+from assistant import power
+from assistant.power import LOW_POWER, PERFORMANCE, RECOVER_SAMPLES, PowerManager, PowerState
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self):
+        return self.now
+
+
+def manager(states, mode="auto"):
+    clock = FakeClock()
+    readings = iter(states)
+    return PowerManager(mode, reader=lambda: next(readings), clock=clock, sample_interval=30), clock
+
+
+def test_battery_and_heat_switch_to_low_power():
+    pm, clock = manager([PowerState(on_battery=True, battery_percent=41), PowerState(on_battery=False, thermal="serious")])
+    assert pm.profile is PERFORMANCE
+    assert pm.sample() is LOW_POWER
+    assert pm.status_line() == "🔋 Low power (on battery 41%)"
+    clock.now = 30
+    assert pm.sample() is None and pm.reasons == ["thermal pressure serious"]
+
+
+def test_recovery_needs_calm_samples():
+    calm = PowerState(on_battery=False, thermal="fair")
+    pm, clock = manager([PowerState(on_battery=True)] + [calm] * RECOVER_SAMPLES)
+    assert pm.sample() is LOW_POWER
+    assert pm.sample() is None  # Within the sample interval: not read again
+    for _ in range(RECOVER_SAMPLES - 1):
+        clock.now += 30
+        assert pm.sample() is None and pm.profile is LOW_POWER
+    clock.now += 30
+    assert pm.sample() is PERFORMANCE
+    assert pm.status_line() == "⚡ Performance"
+
+
+def test_pinned_mode():
+    pm, _ = manager([PowerState(on_battery=True)], mode="performance")
+    assert pm.sample(force=True) is None and pm.profile is PERFORMANCE
+    assert PowerManager("low_power").profile is LOW_POWER
+    assert PowerManager("turbo").mode == "auto"
+
+
+def test_thermal_levels(monkeypatch, tmp_path):
+    class Run:
+        def __init__(self, stdout):
+            self.stdout = stdout
+
+    monkeypatch.setattr(power.subprocess, "run", lambda *a, **k: Run("CPU_Scheduler_Limit = 100\nCPU_Speed_Limit = 62\n"))
+    assert power._macos_thermal() == "serious"
+    monkeypatch.setattr(power.subprocess, "run", lambda *a, **k: Run("No thermal warning level has been recorded\n"))
+    assert power._macos_thermal() == "nominal"
+
+    for index, millidegrees in enumerate(["41000", "88000\n"]):
+        zone = tmp_path / f"thermal_zone{index}"
+        zone.mkdir()
+        (zone / "temp").write_text(millidegrees)
+    monkeypatch.setattr(power.glob, "glob", lambda pattern: sorted(str(p / "temp") for p in tmp_path.iterdir()))
+    assert power._linux_thermal() == "serious"
@@ -0,0 +1,47 @@
+"""
+Tests for the mic privacy indicators (assistant/privacy.py).
+
+Covers:
+- Mic state changes reaching the indicators
+- One activity-log entry per outbound audio stream, and when it ends
+- The tray icon staying off without pystray
+"""
+
+import sys
+
+from assistant.privacy import MicState, PrivacyMonitor, TrayIndicator
+
+
+def test_mic_state_notifies():
+    monitor = PrivacyMonitor()
+    seen = []
+    monitor.listeners.append(lambda m: seen.append(m.mic_state))
+    monitor.set_mic_state(MicState.WAKE_WORD)
+    monitor.set_mic_state(MicState.WAKE_WORD)
+    monitor.set_mic_state(MicState.STREAMING)
+    assert seen == [MicState.WAKE_WORD, MicState.STREAMING]
+    assert monitor.summary() == "listening"
+
+
+def test_egress_logged_once_per_stream():
+    monitor = PrivacyMonitor()
+    logged, changes = [], []
+    monitor.on_egress = logged.append
+    monitor.listeners.append(lambda m: changes.append(m.destinations()))
+    for _ in range(3):
+        monitor.report_egress("twilio:MZ1", "Twilio", "phone call audio")
+    assert logged == ["🔊 Audio leaving this machine: phone call audio → Twilio"]
+    assert monitor.summary() == "mic off · audio → Twilio"
+
+    monitor.end_egress("twilio:MZ1")
+    monitor.end_egress("twilio:MZ1")
+    assert changes == [["Twilio"], []]
+    monitor.report_egress("twilio:MZ2", "Twilio", "phone call audio")
+    assert len(logged) == 2
+
+
+def test_tray_needs_pystray(monkeypatch):
+    monkeypatch.setitem(sys.modules, "pystray", None)  # Import fails
+    monitor = PrivacyMonitor()
+    assert not TrayIndicator(monitor).start()
+    assert monitor.listeners == []
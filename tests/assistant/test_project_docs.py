@@ -0,0 +1,248 @@
+"""
+Tests for project-scoped document indexing and Q&A (assistant/project_docs.py).
+
+Covers:
+- Crawling a project's folders into chunks, skipping dependency/VCS directories
+- Reindexing only changed files, and dropping deleted ones
+- Answers from the AI with the cited sources renumbered and listed
+- The ask_project tool finding the project by name
+- Long documents summarized once when indexed, then summarized from that
+- Profiles sharing a Meilisearch: separate indexes, owner filters, keys limited to their own index
+"""
+
+import asyncio
+import os
+import re
+from types import SimpleNamespace
+
+from assistant.planner import PlannerData
+from assistant.code_chunks import windows
+from assistant import project_docs
+from assistant.project_docs import (MeiliIndex, MeiliKeys, ProjectDocs, ProjectDocsError, profile_name, search_terms,
+                                    set_project_docs)
+from assistant.tools import ask_project, set_planner_data
+
+
+class FakeIndex:
+    """Meilisearch stand-in: documents in a dict, hits ranked by how many query words they contain."""
+
+    def __init__(self):
+        self.documents = {}
+        self.queries = []
+
+    async def add(self, documents):
+        self.documents.update({d["id"]: d for d in documents})
+
+    async def delete(self, ids):
+        for i in ids:
+            self.documents.pop(i, None)
+
+    async def search(self, query, project_id, limit=6, filters=None):
+        self.queries.append(query)
+        words = query.split()
+        wanted = {"project_id": project_id, **(filters or {})}
+        scored = [(sum(w in d["text"].lower() for w in words), d) for d in self.documents.values()
+                  if all(d[k] == v for k, v in wanted.items())]
+        return [d for score, d in sorted(scored, key=lambda s: -s[0]) if score][:limit]
+
+
+class FakeAI:
+    def __init__(self, reply):
+        self.reply = reply
+        self.messages = None
+
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        self.messages = messages
+        return self.reply
+
+
+def make_repo(tmp_path):
+    repo = tmp_path / "api"
+    (repo / "src").mkdir(parents=True)
+    (repo / "node_modules" / "dep").mkdir(parents=True)
+    (repo / "src" / "client.py").write_text("import httpx\n\nRETRIES = 3  # retry policy\n")
+    (repo / "docs.md").write_text("# Setup\n\n" + "filler\n" * 45 + "Retries are set in config.toml [http] retries\n")
+    (repo / "node_modules" / "dep" / "index.js").write_text("retries everywhere")
+    (repo / "logo.png").write_bytes(b"\x89PNG")
+    return repo
+
+
+def make_docs(tmp_path, ai=None):
+    planner = PlannerData(tmp_path / "planner")
+    project = planner.add_project("Billing API", folders=[str(make_repo(tmp_path))])
+    return planner, project, ProjectDocs(FakeIndex(), ai, state_path=tmp_path / "project_index.json")
+
+
+def test_chunks_and_terms():
+    assert list(windows("a\nb\n\n\nc".splitlines(), 1, size=2)) == [(1, 2, "a\nb"), (5, 5, "c")]
+    assert search_terms("Where do we configure retries in project Billing API?", "Billing API") == "configure retries"
+
+
+def test_index_incrementally(tmp_path):
+    planner, project, docs = make_docs(tmp_path)
+    report = asyncio.run(docs.index_project(project))
+    assert (report.files, report.chunks, report.unchanged, report.removed) == (2, 3, 0, 0)
+    assert sorted({d["path"] for d in docs.index.documents.values()}) == ["docs.md", "src/client.py"]
+
+    repo = tmp_path / "api"
+    (repo / "docs.md").write_text("# Setup\n\nShort now\n")
+    os.utime(repo / "docs.md", (1, 1))
+    (repo / "src" / "client.py").unlink()
+    report = asyncio.run(docs.index_project(project))
+    assert (report.files, report.chunks, report.unchanged, report.removed) == (1, 1, 0, 1)
+    assert [d["text"] for d in docs.index.documents.values()] == ["# Setup\n\nShort now"]
+
+    # Kept on disk: a new run only looks at what changed
+    again = ProjectDocs(docs.index, state_path=tmp_path / "project_index.json")
+    assert asyncio.run(again.index_all(planner))["Billing API"].unchanged == 1
+
+
+def test_answer_with_citations(tmp_path):
+    ai = FakeAI("The default is 3 in the client [2]; config.toml [http] overrides it [1].")
+    planner, project, docs = make_docs(tmp_path, ai)
+    asyncio.run(docs.index_project(project))
+    answer = asyncio.run(docs.ask(project, "Where do we configure retries in Billing API?"))
+    assert docs.index.queries == ["configure retries"]
+    assert "[1] docs.md:41-48 (section Setup)\n" in ai.messages[1]["content"]
+    assert "[2] src/client.py:1-3\n" in ai.messages[1]["content"]
+    # Renumbered in the order cited, so the list under the answer matches
+    assert answer.render() == ("The default is 3 in the client [1]; config.toml [http] overrides it [2].\n\n"
+                               "Sources:\n  [1] src/client.py:1-3\n  [2] docs.md:41-48 (section Setup)")
+
+
+def test_ask_project_tool(tmp_path):
+    planner, project, docs = make_docs(tmp_path)  # No AI: the matches are the answer
+    set_planner_data(planner)
+    set_project_docs(docs)
+    try:
+        assert asyncio.run(ask_project("billing", "where are retries set?")) == (
+            "I couldn't find anything about that in Billing API - it isn't indexed yet "
+            "(`xswarm dev project index Billing API`).")
+        asyncio.run(docs.index_project(project))
+        assert asyncio.run(ask_project("billing", "where is RETRIES set?")).startswith(
+            "The closest matches in Billing API:\n\nSources:\n  [1] ")
+        assert asyncio.run(ask_project("Payroll", "anything")) == "✗ Project 'Payroll' not found"
+    finally:
+        set_project_docs(None)
+        set_planner_data(None)
+
+
+def test_document_summaries(tmp_path):
+    ai = FakeAI("SUMMARY: Three services talk over NATS; billing owns invoices.\nKEYWORDS: nats, services, billing")
+    ai.calls = 0
+    chat = ai.chat
+
+    async def counting_chat(messages, max_tokens=1024):
+        ai.calls += 1
+        return await chat(messages, max_tokens)
+    ai.chat = counting_chat
+
+    planner, project, docs = make_docs(tmp_path, ai)
+    repo = tmp_path / "api"
+    (repo / "architecture.md").write_text("# System Architecture\n\n" + "The services talk over NATS.\n" * 300)
+    report = asyncio.run(docs.index_project(project))
+    assert (report.summarized, ai.calls) == (1, 1)  # docs.md is too short to summarize
+    summary_chunk = [d for d in docs.index.documents.values() if d["kind"] == "summary"]
+    assert [(d["path"], d["symbol"], d["end_line"]) for d in summary_chunk] == [
+        ("architecture.md", "System Architecture", 302)]
+    assert summary_chunk[0]["text"].endswith("Keywords: nats, services, billing")
+
+    # Asked for, it's answered from the summary: the document isn't read again
+    answer = asyncio.run(docs.summarize(project, "summarize the architecture doc"))
+    assert answer.render() == ("Three services talk over NATS; billing owns invoices.\n\nSources:\n"
+                               "  [1] architecture.md:1-302 (summary System Architecture)")
+    assert asyncio.run(docs.summarize(project, "the nats one")).sources[0].path == "architecture.md"  # By keyword
+    assert ai.calls == 1
+
+    # Saved without changes: kept. Edited: summarized again
+    os.utime(repo / "architecture.md", (1, 1))
+    assert asyncio.run(docs.index_project(project)).summarized == 0
+    (repo / "architecture.md").write_text("# System Architecture\n\n" + "The services talk over gRPC.\n" * 300)
+    assert asyncio.run(docs.index_project(project)).summarized == 1
+    assert ai.calls == 2
+
+    # A short document is summarized when first asked about, then kept
+    (repo / "deploy.md").write_text("# Deploying\n\nRun make release.\n")
+    asyncio.run(docs.index_project(project))
+    assert asyncio.run(docs.summarize(project, "the deployment guide")).text.startswith("Three services")
+    assert asyncio.run(docs.summarize(project, "deployment")).sources[0].path == "deploy.md"
+    assert ai.calls == 3
+    assert "couldn't find" in asyncio.run(docs.summarize(project, "the roadmap")).text
+
+
+class FakeMeilisearch:
+    """A shared Meilisearch: the master key can do anything, other keys only what they were made for."""
+
+    def __init__(self):
+        self.indexes = {}
+        self.keys = {"master": None}  # Key -> indexes it may use (None = all)
+
+    async def request(self, base_url, api_key, method, path, body=None, timeout=15.0):
+        if api_key not in self.keys:
+            raise ProjectDocsError("invalid key", status=403)
+        if path.startswith("/keys"):
+            if self.keys[api_key] is not None:
+                raise ProjectDocsError("not the master key", status=403)
+            if method == "DELETE":
+                self.keys.pop(f"key-{path.rsplit('/', 1)[1]}", None)
+                return None
+            key = f"key-{len(self.keys)}"
+            self.keys[key] = body["indexes"]
+            return {"uid": str(len(self.keys) - 1), "key": key}
+        name = path.split("/")[2] if path.count("/") > 1 else body["uid"]
+        if self.keys[api_key] is not None and name not in self.keys[api_key]:
+            raise ProjectDocsError(f"key can't use {name}", status=403)
+        documents = self.indexes.setdefault(name, {})
+        if path.endswith("/documents"):
+            documents.update({d["id"]: d for d in body})
+        elif path.endswith("/search"):
+            wanted = dict(re.findall(r'(\w+) = "([^"]*)"', body["filter"]))
+            return {"hits": [d for d in documents.values() if all(d[k] == v for k, v in wanted.items())]}
+        return None
+
+
+def test_profiles_share_meilisearch_in_isolation(tmp_path, monkeypatch):
+    server = FakeMeilisearch()
+    monkeypatch.setattr(project_docs, "_meili_request", server.request)
+    assert profile_name(SimpleNamespace(index_profile="Ana Work")) == "ana_work"
+
+    def docs_for(profile):
+        keys = MeiliKeys("http://meili", "master", path=tmp_path / "keys.json")
+        index = MeiliIndex("http://meili", "master", name=f"xswarm_project_docs_{profile}", owner=profile, keys=keys)
+        return ProjectDocs(index, state_path=tmp_path / f"{profile}.json")
+
+    planner = PlannerData(tmp_path / "planner")
+    project = planner.add_project("Billing API", folders=[str(make_repo(tmp_path))])
+    ana, ben = docs_for("ana"), docs_for("ben")
+    asyncio.run(ana.index_project(project))
+    assert not ben.is_indexed(project)  # Each profile has its own record of what it indexed
+    asyncio.run(ben.index_project(project))
+
+    # Same project id, yet each profile only ever sees its own documents, in its own index
+    assert {d["owner"] for d in server.indexes["xswarm_project_docs_ana"].values()} == {"ana"}
+    assert all(hit["owner"] == "ben" for hit in asyncio.run(ben.index.search("retries", project.id)))
+    assert ana.index.api_key != ben.index.api_key != "master"
+    assert oct((tmp_path / "keys.json").stat().st_mode & 0o777) == "0o600"
+
+    # A profile's key can't reach another profile's index
+    stolen = MeiliIndex("http://meili", ben.index.api_key, name="xswarm_project_docs_ana", owner="ana")
+    try:
+        asyncio.run(stolen.search("retries", project.id))
+        assert False, "ben's key searched ana's index"
+    except ProjectDocsError as e:
+        assert e.status == 403
+
+    # A revoked key is replaced on next use
+    old_key = ana.index.api_key
+    assert asyncio.run(ana.index.keys.revoke("ana"))
+    assert asyncio.run(ana.index.search("retries", project.id))
+    assert ana.index.api_key not in (old_key, "master")
+
+    # Given a key that can't make keys, it's used as is
+    server.keys["admin-made"] = ["xswarm_project_docs_cy"]
+    cy = MeiliIndex("http://meili", "admin-made", name="xswarm_project_docs_cy", owner="cy",
+                    keys=MeiliKeys("http://meili", "admin-made", path=tmp_path / "cy-keys.json"))
+    assert asyncio.run(cy.search("retries", project.id)) == [] and cy.api_key == "admin-made"
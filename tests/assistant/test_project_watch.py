@@ -0,0 +1,164 @@
+"""
+Tests for incremental project indexing (assistant/project_watch.py, ProjectDocs.update_files).
+
+Covers:
+- .gitignore rules: nested files, "!" re-includes, directory-only patterns, config.project_ignore
+- Changes reindexed once they've been quiet for the debounce, ignored paths never queued
+- Deleted files and folders removed from the index
+- Polling rescans without watchdog
+- The status line shown in the Projects view
+"""
+
+import asyncio
+import shutil
+
+from assistant.planner import PlannerData
+from assistant.project_docs import IgnoreRules, ProjectDocs, ProjectDocsError
+from assistant.project_watch import ProjectWatcher
+
+
+class FakeIndex:
+    """Meilisearch stand-in keeping documents in a dict."""
+
+    def __init__(self):
+        self.documents = {}
+        self.fail = False
+
+    async def add(self, documents):
+        if self.fail:
+            raise ProjectDocsError("Meilisearch isn't reachable")
+        self.documents.update({d["id"]: d for d in documents})
+
+    async def delete(self, ids):
+        for i in ids:
+            self.documents.pop(i, None)
+
+    async def search(self, query, project_id, limit=6, filters=None):
+        return []
+
+
+class Clock:
+    def __init__(self):
+        self.now = 100.0
+
+    def __call__(self):
+        return self.now
+
+
+def setup(tmp_path, ignore=()):
+    repo = tmp_path / "api"
+    (repo / "src").mkdir(parents=True)
+    (repo / "src" / "retry.py").write_text("def retry():\n    pass\n")
+    (repo / "README.md").write_text("# API\n")
+    planner = PlannerData(tmp_path / "planner")
+    project = planner.add_project("API", folders=[str(repo)])
+    docs = ProjectDocs(FakeIndex(), state_path=tmp_path / "index.json", ignore=ignore)
+    asyncio.run(docs.index_project(project))
+    clock = Clock()
+    watcher = ProjectWatcher(docs, planner, debounce=2.0, clock=clock)
+    watcher.refresh_roots()
+    return repo, project, docs, watcher, clock
+
+
+def paths(docs):
+    return sorted({d["path"] for d in docs.index.documents.values()})
+
+
+def test_ignore_rules(tmp_path):
+    (tmp_path / ".gitignore").write_text("# build output\n*.log\nbuild/\n!keep.log\n")
+    (tmp_path / "web").mkdir()
+    (tmp_path / "web" / ".gitignore").write_text("/out\n*.map\n")
+    rules = IgnoreRules(tmp_path, extra=["fixtures/"])
+
+    assert rules.ignored("server.log", is_dir=False)
+    assert not rules.ignored("keep.log", is_dir=False)
+    assert rules.ignored("build", is_dir=True)
+    assert not rules.ignored("build", is_dir=False)  # "build/" only matches directories
+    assert rules.ignored("web/out", is_dir=True)
+    assert not rules.ignored("out", is_dir=True)  # Anchored to web/
+    assert rules.ignored("web/app/bundle.js.map", is_dir=False)
+    assert rules.ignored("tests/fixtures", is_dir=True)
+    assert rules.ignored("node_modules", is_dir=True) and rules.ignored(".venv", is_dir=True)
+    assert not rules.ignored("web/app.js", is_dir=False)
+
+
+def test_debounced_update(tmp_path):
+    repo, project, docs, watcher, clock = setup(tmp_path, ignore=["*.tmp"])
+    reports = []
+    watcher.on_update = lambda p, report: reports.append((p.name, report.files))
+
+    (repo / "src" / "backoff.py").write_text("def backoff():\n    pass\n")
+    watcher.notify(repo / "src" / "backoff.py")
+    (repo / "scratch.tmp").write_text("notes")
+    watcher.notify(repo / "scratch.tmp")  # config.project_ignore
+    watcher.notify(tmp_path / "elsewhere.py")  # Not in a project folder
+    assert watcher.statuses[project.id].pending == 1
+    assert watcher.status_line(project.id) == "● Watching for changes · 1 pending"
+
+    clock.now += 1.0
+    (repo / "src" / "backoff.py").write_text("def backoff(n):\n    pass\n")  # Saved again: waits again
+    watcher.notify(repo / "src" / "backoff.py")
+    clock.now += 1.5
+    assert asyncio.run(watcher.flush()) == {}
+    clock.now += 1.0
+    assert asyncio.run(watcher.flush())["API"].files == 1
+    assert reports == [("API", 1)]
+    assert paths(docs) == ["README.md", "src/backoff.py", "src/retry.py"]
+    assert watcher.status_line(project.id).startswith("● Index up to date · 1 file updated ")
+
+    # A new .gitignore drops what it now ignores from future updates
+    (repo / ".gitignore").write_text("generated/\n")
+    watcher.notify(repo / ".gitignore")
+    (repo / "generated").mkdir()
+    (repo / "generated" / "schema.py").write_text("SCHEMA = {}\n")
+    asyncio.run(watcher.flush(force=True))
+    watcher.notify(repo / "generated" / "schema.py")
+    assert watcher.statuses[project.id].pending == 0
+
+
+def test_deletions_and_errors(tmp_path):
+    repo, project, docs, watcher, clock = setup(tmp_path)
+    (repo / "src" / "retry.py").unlink()
+    watcher.notify(repo / "src" / "retry.py")
+    report = asyncio.run(watcher.flush(force=True))["API"]
+    assert (report.files, report.removed) == (0, 1)
+    assert paths(docs) == ["README.md"]
+
+    (repo / "docs").mkdir()
+    (repo / "docs" / "setup.md").write_text("# Setup\n")
+    watcher.notify(repo / "docs")  # A new folder is crawled
+    asyncio.run(watcher.flush(force=True))
+    assert paths(docs) == ["README.md", "docs/setup.md"]
+
+    shutil.rmtree(repo / "docs")
+    watcher.notify(repo / "docs")  # Everything under a removed folder goes
+    asyncio.run(watcher.flush(force=True))
+    assert paths(docs) == ["README.md"]
+
+    # Meilisearch down: the change is kept and retried
+    docs.index.fail = True
+    (repo / "README.md").write_text("# API v2\n")
+    watcher.notify(repo / "README.md")
+    asyncio.run(watcher.flush(force=True))
+    assert watcher.status_line(project.id) == "✗ Index not updated: Meilisearch isn't reachable"
+    docs.index.fail = False
+    clock.now += 3
+    assert asyncio.run(watcher.flush())["API"].files == 1
+    assert watcher.status_line(project.id).startswith("● Index up to date")
+
+
+def test_polling_scan(tmp_path):
+    repo, project, docs, watcher, clock = setup(tmp_path)
+    watcher.scan()  # First scan is the baseline
+    assert watcher.statuses[project.id].pending == 0
+
+    (repo / "src" / "retry.py").write_text("def retry(times):\n    pass\n\n\n")
+    (repo / "README.md").unlink()
+    (repo / "node_modules").mkdir()
+    (repo / "node_modules" / "dep.js").write_text("x")
+    watcher.scan()
+    assert sorted(p.name for p in watcher._pending) == ["README.md", "retry.py"]
+    asyncio.run(watcher.flush(force=True))
+    assert paths(docs) == ["src/retry.py"]
+
+    assert watcher.status_line("someone-else") == ""
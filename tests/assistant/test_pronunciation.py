@@ -0,0 +1,77 @@
+"""
+Tests for the pronunciation lexicon (assistant/pronunciation.py).
+
+Covers:
+- Respellings as written, IPA between slashes turned into one
+- Whole words, ignoring case, possessives kept, the longest entry first, one pass
+- A persona's entries over the shared ones
+- The YAML file: written by add/remove, hand edits picked up
+- The set_pronunciation tool
+"""
+
+import pytest
+
+from assistant import pronunciation, tools
+from assistant.pronunciation import PronunciationLexicon, respell
+
+
+@pytest.fixture
+def lexicon(tmp_path, monkeypatch):
+    lexicon = PronunciationLexicon(tmp_path / "pronunciations.yaml")
+    monkeypatch.setattr(pronunciation, "_lexicon", lexicon)
+    return lexicon
+
+
+@pytest.mark.parametrize("spoken, expected", [
+    ("shiv-AWN", "shiv-AWN"),
+    ("/ʃɪˈvɔːn/", "shi-VAWN"),
+    ("/wɪn/", "win"),
+    ("/ˈniːəv/", "NEEUHV"),
+    ("/kəˈtʃiːn.ə/", "kuh-CHEEN-uh"),
+])
+def test_respell(spoken, expected):
+    assert respell(spoken) == expected
+
+
+def test_apply(lexicon):
+    lexicon.add("Siobhan", "shiv-AWN")
+    lexicon.add("Siobhan Kelly", "shiv-AWN KEL-ee")
+    lexicon.add("k8s", "kubernetes")
+    lexicon.add("kubernetes", "koo-ber-NET-eez")
+    assert lexicon.apply("Ask siobhan; Siobhan's k8s cluster is down") == (
+        "Ask shiv-AWN; shiv-AWN's kubernetes cluster is down")  # Not respelled twice
+    assert lexicon.apply("Call Siobhan Kelly") == "Call shiv-AWN KEL-ee"
+    assert lexicon.apply("Siobhanna and pre-Siobhan") == "Siobhanna and pre-Siobhan"
+
+
+def test_persona_overrides(lexicon):
+    lexicon.add("Siobhan", "shiv-AWN")
+    lexicon.add("Nguyen", "/wɪn/", persona="GLaDOS")
+    lexicon.add("siobhan", "SHIV-on", persona="glados")  # Same persona, same word
+    assert lexicon.apply("Siobhan and Nguyen") == "shiv-AWN and Nguyen"
+    assert lexicon.apply("Siobhan and Nguyen", "GLaDOS") == "SHIV-on and win"
+    assert lexicon.entries("GLaDOS") == {"siobhan": "SHIV-on", "Nguyen": "/wɪn/"}
+    assert lexicon.remove("Nguyen", "glados")
+    assert not lexicon.remove("Nguyen", "glados")
+    assert lexicon.apply("Nguyen", "GLaDOS") == "Nguyen"
+
+
+def test_file(lexicon, tmp_path):
+    lexicon.add("Siobhan", "shiv-AWN")
+    path = tmp_path / "pronunciations.yaml"
+    assert path.read_text(encoding="utf-8").startswith("# How words are said aloud")
+    assert "Siobhan: shiv-AWN" in path.read_text(encoding="utf-8")
+    # Edited by hand
+    path.write_text("words:\n  Aoife: EE-fuh\n", encoding="utf-8")
+    lexicon._mtime = -1  # The edit may land within the same mtime tick
+    assert lexicon.apply("Aoife and Siobhan") == "EE-fuh and Siobhan"
+    path.write_text("words: [not, a, mapping", encoding="utf-8")
+    lexicon._mtime = -1
+    assert lexicon.apply("Aoife") == "Aoife"  # Broken file: nothing applied, nothing raised
+
+
+def test_tool(lexicon):
+    assert tools.set_pronunciation("Siobhan", "shiv-AWN") == "✓ 'Siobhan' will be said \"shiv-AWN\""
+    assert lexicon.apply("Siobhan") == "shiv-AWN"
+    assert tools.set_pronunciation("Siobhan", "") == "✓ 'Siobhan' is said as spelled again"
+    assert tools.set_pronunciation("Siobhan", "").startswith("✗")
@@ -0,0 +1,56 @@
+"""
+Tests for speech hints in replies (assistant/prosody.py).
+
+Covers:
+- Plain engines (Moshi): codes spelled out, pauses as punctuation, emphasis as the word
+- SSML engines: hints kept, text escaped, hints the engine lacks put into words
+- Shown text: hints removed; other tags and stray or unclosed ones handled
+- The persona's spoken prompt asks for them, unless voice.markup is off
+"""
+
+import pytest
+
+from assistant.personas.config import PersonaConfig, VoiceSettings
+from assistant.prosody import POLLY_NEURAL, PROMPT_GUIDE, SSML, render, strip
+
+REPLY = ('Your code is <say-as interpret-as="characters">XK-92</say-as>. '
+         'That is <emphasis level="strong">today</emphasis>, not tomorrow. Ready? <break time="1s"/> Go.')
+
+
+@pytest.mark.parametrize("text, spoken", [
+    (REPLY, "Your code is X K, 9 2. That is today, not tomorrow. Ready? ... Go."),
+    ("PIN <say-as interpret-as='digits'>4821</say-as>", "PIN 4 8 2 1"),
+    ("Wait<break/> then go", "Wait, then go"),
+    ("Wait <break time='800ms'/>then go", "Wait... then go"),
+    ("Done.<break strength='weak'/> Next", "Done. Next"),
+    ("<break time='2s'/>Hello", "Hello"),
+    ("<say-as interpret-as='date'>2026-10-17</say-as>", "2026-10-17"),  # Left for speakable()
+    ("a < b and <thinking>x</thinking>", "a < b and <thinking>x</thinking>"),
+])
+def test_plain(text, spoken):
+    assert render(text) == spoken
+
+
+def test_ssml():
+    assert render(REPLY, SSML) == (
+        '<speak>Your code is <say-as interpret-as="characters">XK-92</say-as>. That is '
+        '<emphasis level="strong">today</emphasis>, not tomorrow. Ready? <break time="1000ms"/> Go.</speak>')
+    assert render(REPLY, POLLY_NEURAL) == (
+        'Your code is <say-as interpret-as="characters">XK-92</say-as>. That is today, not tomorrow. '
+        'Ready? <break time="1000ms"/> Go.')
+    assert render("Tom & Jerry <b>", SSML) == "<speak>Tom &amp; Jerry &lt;b&gt;</speak>"
+    assert render('<emphasis level="loud">now', SSML) == "<speak><emphasis>now</emphasis></speak>"
+
+
+def test_strip():
+    assert strip(REPLY) == "Your code is XK-92. That is today, not tomorrow. Ready? Go."
+    assert strip("one</emphasis> two <EMPHASIS>three") == "one two three"
+    assert strip("no hints here  ") == "no hints here  "
+
+
+def test_persona_prompt():
+    persona = PersonaConfig(name="Jarvis", system_prompt="You are {NAME}.")
+    assert PROMPT_GUIDE not in persona.build_system_prompt()
+    assert persona.build_system_prompt(spoken=True).endswith(PROMPT_GUIDE)
+    quiet = PersonaConfig(name="Marvin", system_prompt="You are {NAME}.", voice=VoiceSettings(markup=False))
+    assert PROMPT_GUIDE not in quiet.build_system_prompt(spoken=True)
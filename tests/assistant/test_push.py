@@ -0,0 +1,177 @@
+"""
+Tests for ntfy/Pushover push notifications (assistant/push.py).
+
+Covers:
+- Which recorded events are pushed, and to which targets
+- The ntfy and Pushover requests
+- Per-provider rate limits
+- Quiet hours / do not disturb, and bad settings
+- Long background jobs recording the event that is pushed
+- Failed pushes retried with backoff, kept as dead letters, resent by hand
+"""
+
+import asyncio
+import time
+from types import SimpleNamespace
+
+import pytest
+
+from assistant.events import add_event_listener, record_event, remove_event_listener
+from assistant.governor import ResourceGovernor, ResourceUsage
+from assistant.notifications import NotificationPolicy
+from assistant.push import DeliveryQueue, NtfyProvider, Push, PushError, PushoverProvider, PushRouter
+from assistant.scheduler import Scheduler
+
+
+class Clock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+def make_router(routes, per_hour=None, policy=None, clock=None, queue=None):
+    posts = []
+
+    def post(url, **kwargs):
+        posts.append((url, kwargs))
+    providers = {"ntfy": NtfyProvider("https://ntfy.example.com/", token="tk_1", post=post),
+                 "pushover": PushoverProvider("app", "user", post=post)}
+    router = PushRouter(providers, routes, per_hour, policy=policy or NotificationPolicy(),
+                        run=lambda send, *args: send(*args), clock=clock or Clock(), queue=queue)
+    return router, posts
+
+
+def test_routes_events_to_targets():
+    router, posts = make_router({"error": ["ntfy:alerts", "pushover:phone"], "missed_reminder": ["ntfy:me"]})
+    add_event_listener(router.on_event)
+    try:
+        record_event("activity", "✗ Calendar sync failed", {"level": "error"})
+        record_event("activity", "Synced", {"level": "info"})
+        record_event("reminder", "Dentist at 4", {"title": "Dentist", "delivered": True})
+        record_event("reminder", "Standup at 9", {"title": "Standup", "delivered": False})
+        record_event("task", "document_indexing finished after 12 min", {"name": "document_indexing"})  # Not routed
+    finally:
+        remove_event_listener(router.on_event)
+
+    assert posts == [
+        ("https://ntfy.example.com", {"json": {"topic": "alerts", "title": "xswarm error",
+                                               "message": "Calendar sync failed", "priority": 4, "tags": ["error"]},
+                                      "headers": {"Authorization": "Bearer tk_1"}}),
+        ("https://api.pushover.net/1/messages.json", {"data": {"token": "app", "user": "user", "title": "xswarm error",
+                                                               "message": "Calendar sync failed", "priority": 1,
+                                                               "device": "phone"}}),
+        ("https://ntfy.example.com", {"json": {"topic": "me", "title": "Missed reminder", "message": "Standup at 9",
+                                               "priority": 3, "tags": ["missed_reminder"]},
+                                      "headers": {"Authorization": "Bearer tk_1"}}),
+    ]
+
+
+def test_rate_limit_per_provider():
+    clock = Clock()
+    router, posts = make_router({"error": ["ntfy:alerts", "pushover"]}, per_hour={"ntfy": 2, "pushover": 20},
+                                clock=clock)
+    assert [router.push("error", f"Failure {n}") for n in range(3)] == [2, 2, 1]
+    assert sum(url.startswith("https://ntfy") for url, _ in posts) == 2
+    clock.now += 1800  # Half an hour refills one of two
+    assert router.push("error", "Failure 4") == 2
+
+
+def test_quiet_hours_and_send_failures():
+    router, posts = make_router({"task_done": ["ntfy:tasks"], "error": ["ntfy:alerts"]},
+                                policy=NotificationPolicy(SimpleNamespace(do_not_disturb=True,
+                                                                          quiet_hours_channels={"push": "high"})))
+    assert router.push("task_done", "Backup finished") == 0
+    assert router.push("error", "Disk full") == 1
+
+    def fail(url, **kwargs):
+        raise OSError("unreachable")
+    router.providers["ntfy"].post = fail
+    assert router.push("error", "Disk full") == 1  # Queued; the failure is only logged
+    assert router.send_now("error", "Test") == {"ntfy:alerts": "unreachable"}
+    assert len(posts) == 1
+
+
+def test_from_config():
+    assert PushRouter.from_config(SimpleNamespace(push_routes={"error": []})) is None
+    config = SimpleNamespace(push_routes={"error": ["pushover"]},
+                             push_providers={"pushover": {"token": "app", "user": "u", "per_hour": 5}})
+    assert list(PushRouter.from_config(config).providers) == ["pushover"]
+
+    for routes, providers, problem in [
+        ({"typo": ["ntfy:x"]}, {"ntfy": {}}, "Unknown push event class 'typo'"),
+        ({"error": ["ntfy:x"]}, {}, "needs config.push_providers\\['ntfy'\\]"),
+        ({"error": ["ntfy"]}, {"ntfy": {}}, "need a topic"),
+        ({"error": ["pushover"]}, {"pushover": {"token": "app"}}, "user key"),
+        ({"error": ["gotify:x"]}, {"gotify": {}}, "Unknown push provider 'gotify'"),
+    ]:
+        with pytest.raises(PushError, match=problem):
+            PushRouter.from_config(SimpleNamespace(push_routes=routes, push_providers=providers))
+
+
+def test_long_jobs_record_task_event():
+    scheduler = Scheduler(governor=ResourceGovernor(sampler=lambda: ResourceUsage(), sample_interval=0),
+                          config=SimpleNamespace(jobs={}, long_job_seconds=0.01))
+    scheduler.tasks.clear()
+    scheduler.add_job("quick", lambda: None, interval=60)
+    scheduler.add_job("slow", lambda: time.sleep(0.02), interval=60)
+    seen = []
+    add_event_listener(seen.append)
+    try:
+        asyncio.run(scheduler.run_now("quick"))
+        asyncio.run(scheduler.run_now("slow"))
+    finally:
+        remove_event_listener(seen.append)
+    assert [(e.type, e.data["name"]) for e in seen] == [("task", "slow")]
+
+
+def test_failed_pushes_retry_with_backoff(tmp_path):
+    clock, dead = Clock(), []
+    queue = DeliveryQueue(tmp_path / "queue.json", max_attempts=3, backoff=30, max_backoff=45,
+                          clock=clock, on_dead=dead.append)
+    router, posts = make_router({"error": ["ntfy:alerts"]}, queue=queue)
+    ntfy = router.providers["ntfy"]
+    post = ntfy.post
+
+    def fail(url, **kwargs):
+        raise OSError("unreachable")
+    ntfy.post = fail
+    router.push("error", "Disk full")
+    [entry] = queue.pending()
+    assert (entry.target, entry.push.message, entry.attempts, entry.error) == ("ntfy:alerts", "Disk full", 1, "unreachable")
+    assert router.retry_due() == (0, 0)  # Not due for 30s
+
+    clock.now += 30
+    assert router.retry_due() == (0, 1)
+    clock.now += 44
+    assert router.retry_due() == (0, 0)  # 60s capped at 45
+    clock.now += 1
+    assert router.retry_due() == (0, 1)
+    assert [e.id for e in dead] == [entry.id] and dead[0].attempts == 3
+    assert queue.pending() == [] and queue.due() == []
+
+    # Persisted: a new session (or the CLI) sees the dead letter and resends it
+    ntfy.post = post
+    queue = DeliveryQueue(tmp_path / "queue.json", clock=clock)
+    router.queue = queue
+    assert [e.error for e in queue.dead()] == ["unreachable"]
+    assert [e.id for e in queue.retry()] == [entry.id]
+    assert router.retry_due() == (1, 0)
+    assert posts[-1][1]["json"]["message"] == "Disk full"
+    assert queue.dead() == queue.pending() == []
+
+
+def test_retries_respect_rate_limit_and_discard(tmp_path):
+    clock = Clock()
+    queue = DeliveryQueue(tmp_path / "queue.json", clock=clock)
+    router, posts = make_router({"error": ["ntfy:alerts"]}, per_hour={"ntfy": 1}, clock=clock, queue=queue)
+    for target, message in [("ntfy:alerts", "One"), ("ntfy:alerts", "Two"), ("gotify:x", "Three")]:
+        queue.failed(target, Push("xswarm error", message, 4, target.partition(":")[2], "error"), "timeout")
+    clock.now += 30
+    assert router.retry_due() == (1, 1)  # One allowed this hour; gotify isn't configured any more
+    assert len(posts) == 1 and [e.push.message for e in queue.due()] == ["Two"] and len(queue.pending()) == 2
+
+    assert queue.discard() == 0  # Only dead letters unless ids are given
+    assert queue.discard([e.id for e in queue.pending()]) == 2
+    assert not (tmp_path / "queue.json").read_text().strip("[]\n ")
@@ -0,0 +1,85 @@
+"""
+Tests for quick math and conversions (assistant/quick_math.py).
+
+Covers:
+- Arithmetic in words and symbols, percentages, powers and roots; nothing is eval'd
+- Units of the same kind convert (temperatures too); mismatched ones don't
+- Currency from cached rates, offline, with the rates' date once they're stale
+- Utterances that aren't sums or conversions (dates, reminders, lists) are left for the model
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.quick_math import RateCache, answer_quick_question, evaluate
+
+
+@pytest.mark.parametrize("text, answer", [
+    ("What's 15% of 240?", "15% of 240 is 36"),
+    ("twelve times seven", "12 times 7 is 84"),
+    ("two hundred and five divided by five", "205 divided by 5 is 41"),
+    ("calculate (3 + 4) * 2", "(3 + 4) * 2 is 14"),
+    ("what is 2 to the power of 10", "2 to the power of 10 is 1024"),
+    ("square root of 81 plus 2", "square root of 81 plus 2 is 11"),
+    ("what is 10/3", "10/3 is 3.33"),
+    ("convert 5 miles to km", "5 miles is 8.05 km"),
+    ("how many ounces in a pound", "1 pound is 16 ounces"),
+    ("72 degrees fahrenheit in celsius", "72 degrees fahrenheit is 22.22 celsius"),
+    ("1.5 hours in minutes", "1.5 hours is 90 minutes"),
+])
+def test_answers(text, answer):
+    assert answer_quick_question(text) == answer
+
+
+@pytest.mark.parametrize("text", [
+    "2026-10-16", "10/16", "remind me in 5 minutes", "add 2 eggs to the list", "what's the time in Tokyo",
+    "10 minutes to 5", "5 km to pounds", "what's 7 divided by 0", "9 ** 9 ** 9", "__import__('os')",
+])
+def test_left_for_the_model(text):
+    assert answer_quick_question(text) is None
+
+
+def test_evaluate_is_not_eval():
+    assert evaluate("2 ** 3 % 5") == 3
+    assert evaluate("(1).__class__") is None and evaluate("sqrt(sqrt)") is None
+
+
+def test_currency(tmp_path):
+    cache = RateCache(tmp_path)
+    assert "don't have exchange rates" in answer_quick_question("100 dollars in euros", cache)
+    cache.store({"USD": 1.0, "EUR": 0.9, "GBP": 0.8})
+    assert answer_quick_question("€50 to GBP", cache) == "50 EUR is 44.44 GBP"
+    assert answer_quick_question("100 pounds in euros", cache) == "100 GBP is 112.50 EUR"
+    assert answer_quick_question("100 pounds in kg", cache) == "100 pounds is 45.36 kg"  # Weight, not money
+    offline = RateCache(tmp_path)  # Read back from disk
+    offline.store(offline.rates, updated=1760616000)  # Noon UTC, October 16 2025
+    assert answer_quick_question("10 usd in eur", offline).endswith("9.00 EUR (rates from Oct 16)")
+
+
+def test_refresh(tmp_path, monkeypatch):
+    class Response:
+        def raise_for_status(self):
+            pass
+
+        def json(self):
+            return {"result": "success", "time_last_update_unix": 1760572800, "rates": {"USD": 1, "JPY": 150.5}}
+
+    class Client:
+        def __init__(self, **kwargs):
+            pass
+
+        async def __aenter__(self):
+            return self
+
+        async def __aexit__(self, *exc):
+            return False
+
+        async def get(self, url):
+            return Response()
+
+    import httpx
+    monkeypatch.setattr(httpx, "AsyncClient", Client)
+    cache = RateCache(tmp_path)
+    assert asyncio.run(cache.refresh()) == 2
+    assert RateCache(tmp_path).convert(2, "USD", "JPY") == 301.0
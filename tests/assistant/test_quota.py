@@ -0,0 +1,153 @@
+"""
+Tests for subscription quotas on phone minutes and text messages (assistant/quota.py).
+
+Covers:
+- Tier limits before the first sync, and the 80% warning once a month
+- Over-quota actions blocked with an explanation; overage when allowed
+- Syncing: usage reported, remaining counts taken from the server
+- A new month starting over, and text replies checked before sending
+"""
+
+import asyncio
+from datetime import datetime
+
+from assistant.inbox import InboxStore
+from assistant.quota import QuotaDecision, QuotaManager, set_quota_manager
+from assistant.reply_drafts import ReplyWorkflow
+
+
+class Clock:
+    def __init__(self, now):
+        self.now = now
+
+    def __call__(self):
+        return self.now
+
+
+def make_manager(tmp_path, tier="personal", allow_overage=False, now=datetime(2026, 10, 16, 9, 0)):
+    return QuotaManager(tier, tmp_path / "quota.json", allow_overage=allow_overage, clock=Clock(now))
+
+
+def test_limits_and_warning(tmp_path):
+    manager = make_manager(tmp_path)
+    assert manager.remaining("voice_minutes") == 100
+    assert manager.record_call(75 * 60 + 5) is None  # 76 minutes: Twilio bills started minutes
+    assert manager.record_call(4 * 60) == "You've used 80 of 100 phone minutes this month (resets November 1)."
+    assert manager.record_call(60) is None  # Warned once
+    assert manager.remaining("voice_minutes") == 19
+
+    # Kept on disk
+    assert make_manager(tmp_path).used("voice_minutes") == 81
+    assert make_manager(tmp_path, tier="enterprise").check("voice_minutes", 500).allowed
+
+
+def test_blocked_with_explanation(tmp_path):
+    manager = make_manager(tmp_path)
+    manager.record("sms_messages", 99)
+    assert manager.check("sms_messages").allowed
+    manager.record("sms_messages", 1)
+    decision = manager.check("sms_messages")
+    assert not decision.allowed
+    assert decision.message == ("You have no text messages left this month on the personal plan; it resets on "
+                                "November 1. Upgrade your plan, or set quota_allow_overage to go over and be billed.")
+    manager.record("voice_minutes", 81)
+    assert "You have only 19 phone minutes left" in manager.check("voice_minutes", 20).message
+
+    free = QuotaManager("free", tmp_path / "free.json")
+    assert free.check("voice_minutes").message == ("Phone minutes aren't included in the free plan - "
+                                                   "upgrade to a paid plan to use them.")
+
+    overage = make_manager(tmp_path, allow_overage=True)
+    assert overage.check("sms_messages") == QuotaDecision(
+        True, "You're past this month's text messages; this will be billed as overage.")
+
+
+class FakeResponse:
+    def __init__(self, data):
+        self.data = data
+
+    def json(self):
+        return self.data
+
+
+class FakeClient:
+    def __init__(self, identity):
+        self.identity = identity
+        self.posted = []
+
+    async def call(self, route, **kwargs):
+        return await getattr(self, route.method.lower())(route.path, **kwargs)
+
+    async def get(self, path):
+        assert path == "/api/identity"
+        return FakeResponse(self.identity)
+
+    async def post(self, path, json):
+        self.posted.append((path, json))
+        return FakeResponse({"recorded": json})
+
+
+def test_sync(tmp_path):
+    manager = make_manager(tmp_path, tier="free")
+    manager.record("sms_messages", 3)
+    client = FakeClient({"id": "admin-sam", "subscription_tier": "professional",
+                         "voice_minutes_remaining": 400, "sms_messages_remaining": 50})
+    assert asyncio.run(manager.sync(client)) == {"sms_messages": 3}
+    assert client.posted == [("/api/usage", {"user_id": "admin-sam", "sms_messages": 3})]
+    assert (manager.effective_tier, manager.remaining("sms_messages"), manager.remaining("voice_minutes")) == (
+        "professional", 47, 400)
+
+    manager.record("sms_messages", 7)  # Counted against the server's figure until the next sync
+    assert manager.remaining("sms_messages") == 40
+    assert asyncio.run(manager.sync(FakeClient({"sms_messages_remaining": None}))) == {"sms_messages": 7}
+    assert manager.check("sms_messages", 1000).allowed  # Unlimited per the server
+    assert "Last synced: 2026-10-16T09:00:00" in manager.summary()
+
+
+def test_new_month(tmp_path):
+    manager = make_manager(tmp_path)
+    manager.record("voice_minutes", 90)
+    manager.clock.now = datetime(2026, 11, 1, 0, 5)
+    assert (manager.used("voice_minutes"), manager.remaining("voice_minutes")) == (0, 100)
+    assert "90 phone minutes to report" in manager.summary()  # Not synced yet, so still owed to the server
+
+
+class FakeAI:
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        return "See you Thursday!"
+
+
+class FakeInboxManager:
+    def __init__(self, store):
+        self.store = store
+        self.sent = []
+
+    async def send_reply(self, item_id, text):
+        self.sent.append((item_id, text))
+        return True
+
+
+def test_text_replies_checked(tmp_path):
+    store = InboxStore(tmp_path)
+    store.merge_remote([{"id": "msg-1", "channel": "sms", "sender": "+15551234567", "content": "Lunch Thursday?",
+                         "received_at": "2026-10-16T08:00:00"}])
+    manager = make_manager(tmp_path)
+    asyncio.run(manager.sync(FakeClient({"sms_messages_remaining": 0})))  # Used up on another device
+    set_quota_manager(manager)
+    try:
+        workflow = ReplyWorkflow(FakeAI(), None, FakeInboxManager(store))
+        asyncio.run(workflow.start("msg-1"))
+        reply = asyncio.run(workflow.handle("send it"))
+        assert reply.startswith("I can't send that text: You have no text messages left this month")
+        assert workflow.inbox_manager.sent == [] and workflow.session.state != "sent"
+
+        asyncio.run(manager.sync(FakeClient({"sms_messages_remaining": 21})))  # More bought on the website
+        assert asyncio.run(workflow.handle("send it")) == (
+            "Sent your reply to +15551234567 by text message. "
+            "You've used 80 of 100 text messages this month (resets November 1).")
+    finally:
+        set_quota_manager(None)
+    assert workflow.inbox_manager.sent == [("msg-1", "See you Thursday!")]
@@ -0,0 +1,77 @@
+"""
+Tests for listener rate limiting and abuse protection.
+
+Covers:
+- Total and per-client connection limits
+- Oversized messages and message floods are dropped, then disconnected
+- Violations are reported once per interval
+"""
+
+from assistant.rate_limit import ClientGuard, ListenerLimits, TokenBucket
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 100.0
+
+    def __call__(self):
+        return self.now
+
+
+def _guard(**limits):
+    clock = FakeClock()
+    reports = []
+    guard = ClientGuard("Test", ListenerLimits(**limits), reports.append, clock=clock)
+    return guard, clock, reports
+
+
+class TestConnections:
+    def test_per_client_limit(self):
+        guard, _, reports = _guard(max_connections_per_client=2)
+        assert guard.admit("1.2.3.4")
+        assert guard.admit("1.2.3.4")
+        assert not guard.admit("1.2.3.4")
+        assert guard.admit("5.6.7.8")
+        assert "refused connection from 1.2.3.4" in reports[0]
+
+        guard.release("1.2.3.4")
+        assert guard.admit("1.2.3.4")
+
+    def test_total_limit(self):
+        guard, _, _ = _guard(max_connections=2)
+        assert guard.admit("a") and guard.admit("b")
+        assert not guard.admit("c")
+        assert guard.connection_count == 2
+
+
+class TestMessages:
+    def test_oversized_message(self):
+        guard, _, reports = _guard(max_message_bytes=100)
+        assert guard.check_message("a", 1, 50) is None
+        assert guard.check_message("a", 1, 500) == "drop"
+        assert "rejected 500-byte message" in reports[0]
+
+    def test_flood_is_dropped_then_disconnected(self):
+        guard, clock, _ = _guard(messages_per_second=10, burst=5, max_violations=3)
+        assert all(guard.check_message("a", 1, 10) is None for _ in range(5))
+        assert guard.check_message("a", 1, 10) == "drop"
+        assert guard.check_message("a", 1, 10) == "drop"
+        assert guard.check_message("a", 1, 10) == "disconnect"
+
+    def test_tokens_refill(self):
+        clock = FakeClock()
+        bucket = TokenBucket(rate=10, burst=2, clock=clock)
+        assert bucket.take() and bucket.take()
+        assert not bucket.take()
+        clock.now += 0.5
+        assert bucket.take()
+
+    def test_reports_are_throttled(self):
+        guard, clock, reports = _guard(messages_per_second=1, burst=1, report_interval=10)
+        for _ in range(20):
+            guard.check_message("a", 1, 10)
+        assert len(reports) == 1
+        clock.now += 11
+        guard.check_message("a", 1, 10)
+        guard.check_message("a", 1, 10)
+        assert len(reports) == 2
@@ -0,0 +1,74 @@
+"""
+Tests for redaction of sensitive data (assistant/redaction.py).
+
+Covers:
+- Masking emails, phone numbers, card numbers and credentials
+- Leaving timestamps, dates and short numbers alone
+- Strict mode hashing identifiers consistently, but never secrets
+- Custom patterns from config, and turning built-ins off
+- The logging filter and memory storage
+"""
+
+import logging
+
+import pytest
+
+from assistant.config import Config
+from assistant.memory import MemoryManager
+from assistant.redaction import RedactingFilter, Redactor, load_key, set_redactor
+
+
+def test_masks_identifiers_and_secrets():
+    redact = Redactor()
+    assert redact("call +1 (555) 123-4567 tomorrow") == "call [phone] tomorrow"
+    assert redact("mail Dana.Lee@example.com") == "mail [email]"
+    assert redact("my password is hunter22") == "my password is [secret]"
+    assert redact("GITHUB_TOKEN=ghp_abcdefghijklmnopqrstuvwx") == "GITHUB_TOKEN=[secret]"
+    assert redact("Authorization: Bearer abc.def.ghijkl") == "Authorization: Bearer [secret]"
+    assert redact("card 4111 1111 1111 1111") == "card [card]"
+
+
+def test_leaves_ordinary_text():
+    redact = Redactor()
+    for text in ["2026-10-14 15:30:00,123 - INFO - Synced 12 events",
+                 "pid 48213 used 1.5 GB", "the pinball was broken", "order 4111 1111 1111 1112 shipped"]:
+        assert redact(text) == text
+    assert Redactor(enabled=False)("dana@example.com") == "dana@example.com"
+
+
+def test_strict_hashes_identifiers():
+    redact = Redactor(strict=True, key=b"test-key")
+    first = redact("text 555-123-4567")
+    assert first.startswith("text [phone:") and "555" not in first
+    # Same number in another format, same hash - it can be followed through the log
+    assert redact("text (555) 123 4567") == first
+    assert redact("dana@example.com") == redact("Dana@Example.com") != redact("lee@example.com")
+    assert redact("password: hunter22") == "password: [secret]"
+    assert Redactor(strict=True, key=b"other-key")("text 555-123-4567") != first
+
+
+def test_key_is_kept(tmp_path):
+    path = tmp_path / "redaction.key"
+    assert load_key(path) == load_key(path)
+
+
+def test_custom_patterns_from_config():
+    redact = Redactor.from_config(Config(redaction_patterns={"employee_id": r"EMP-\d{6}", "phone": "",
+                                                             "broken": "("}))
+    assert redact("EMP-123456 called 555-123-4567") == "[employee_id] called 555-123-4567"
+
+
+def test_logging_filter():
+    set_redactor(Redactor())
+    record = logging.LogRecord("x", logging.INFO, __file__, 1, "User said: %s", ("email me at a@b.co",), None)
+    assert RedactingFilter().filter(record)
+    assert record.getMessage() == "User said: email me at [email]"
+
+
+@pytest.mark.asyncio
+async def test_memory_is_redacted():
+    set_redactor(Redactor())
+    memory = MemoryManager(server_url="http://localhost:1")
+    memory._server_available = False
+    await memory.store_message("u1", "my pin is 4211, call 555 123 4567")
+    assert memory.local_cache.get_history("u1")[0]["message"] == "my pin is [secret], call [phone]"
@@ -0,0 +1,107 @@
+"""
+Tests for reminder receipts and effectiveness (assistant/reminders.py).
+
+Covers:
+- Acknowledgements: "got it" answers the latest spoken reminder within the window, once
+- Desktop clicks, and channels a reminder never went out on
+- Delivery on the configured methods only
+- Acknowledgement rates per channel from the event history, and the spoken summary
+- Auto-adjusting methods: ignored channels are left out, the best one is always kept
+"""
+
+import asyncio
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.events import EventStore, set_event_store
+from assistant.reminders import (ChannelStats, Reminder, ReminderReceipts, adjust_methods, deliver_reminder,
+                                 describe_effectiveness, effectiveness, is_acknowledgement)
+
+NOW = datetime(2026, 10, 16, 8, 50)
+
+
+def _reminder(key="standup", title="Standup"):
+    return Reminder(key=key, title=title, start=NOW + timedelta(minutes=10), minutes=10)
+
+
+@pytest.fixture
+def store():
+    store = EventStore(":memory:")
+    set_event_store(store)
+    yield store
+    set_event_store(None)
+    store.close()
+
+
+@pytest.mark.parametrize("text, ack", [
+    ("Got it", True), ("okay, thanks!", True), ("on my way", True), ("thank you", True),
+    ("okay what's the weather", False), ("remind me later", False), ("", False),
+])
+def test_acknowledgements(text, ack):
+    assert is_acknowledgement(text) is ack
+
+
+def test_spoken_acknowledgement(store):
+    receipts = ReminderReceipts()
+    receipts.sent(_reminder("standup"), {"desktop": True, "speech": True}, NOW)
+    receipts.sent(_reminder("dentist", "Dentist"), {"desktop": True, "speech": True}, NOW + timedelta(minutes=1))
+    assert receipts.heard("what time is it", NOW + timedelta(minutes=2)) is None
+    assert receipts.heard("got it, thanks", NOW + timedelta(minutes=2)).title == "Dentist"  # The latest
+    assert receipts.heard("okay", NOW + timedelta(minutes=2)).title == "Standup"
+    assert receipts.heard("okay", NOW + timedelta(minutes=2)) is None  # Both answered
+    acks = store.query(["reminder_ack"])
+    assert [(e.data["key"], e.data["channel"], e.data["seconds"]) for e in acks] == [
+        ("dentist", "speech", 60), ("standup", "speech", 120)]
+
+
+def test_window_and_channels(store):
+    receipts = ReminderReceipts()
+    receipts.sent(_reminder(), {"desktop": True, "speech": False}, NOW)
+    assert receipts.heard("got it", NOW + timedelta(seconds=30)) is None  # Never spoken
+    assert receipts.acknowledge("standup", "desktop", NOW + timedelta(hours=1)).title == "Standup"
+    assert receipts.acknowledge("standup", "desktop") is None
+    receipts.sent(_reminder("gym", "Gym"), {"speech": True}, NOW)
+    assert receipts.heard("thanks", NOW + timedelta(minutes=4)) is None  # Too late
+
+
+def test_delivery_on_configured_methods():
+    clicks, notified = [], []
+
+    def notify(title, body, tags=None, on_click=None):
+        notified.append(body)
+        on_click()  # Clicked straight away
+        return True
+
+    delivered = asyncio.run(deliver_reminder(_reminder(), notify=notify, methods=["desktop"],
+                                             on_click=clicks.append))
+    assert delivered == {"activity": False, "desktop": True, "speech": False}
+    assert notified == ["Standup in 10 minutes"] and clicks[0].key == "standup"
+    assert asyncio.run(deliver_reminder(_reminder(), notify=notify, methods=[])) == {
+        "activity": False, "desktop": False, "speech": False}
+
+
+def test_effectiveness(store):
+    for n in range(10):
+        channels = ["desktop", "speech"] if n < 5 else ["speech"]
+        store.record("reminder", "x", {"key": f"r{n}", "channels": channels, "delivered": True})
+        if n < 9:
+            store.record("reminder_ack", "x", {"key": f"r{n}", "channel": "speech"})
+        if n < 2:
+            store.record("reminder_ack", "x", {"key": f"r{n}", "channel": "desktop"})
+    store.record("reminder", "old", {"title": "From before receipts", "delivered": True})
+    stats = effectiveness(store)
+    assert (stats["speech"].sent, stats["speech"].acknowledged, stats["desktop"].sent, stats["desktop"].acknowledged) == (
+        10, 9, 5, 2)
+    assert describe_effectiveness(stats) == (
+        "Spoken reminders get acknowledged 90% of the time, desktop notifications 40%.")
+    assert describe_effectiveness(effectiveness(store, NOW + timedelta(days=400))) == "No reminders sent yet."
+
+
+@pytest.mark.parametrize("stats, methods", [
+    ({"desktop": ChannelStats("desktop", 20, 1), "speech": ChannelStats("speech", 20, 18)}, ["speech"]),
+    ({"desktop": ChannelStats("desktop", 5, 0), "speech": ChannelStats("speech", 20, 18)}, ["desktop", "speech"]),
+    ({"desktop": ChannelStats("desktop", 20, 2), "speech": ChannelStats("speech", 20, 1)}, ["desktop"]),
+])
+def test_adjust_methods(stats, methods):
+    assert adjust_methods(["desktop", "speech"], stats, min_rate=0.2) == methods
@@ -0,0 +1,132 @@
+"""
+Tests for server-queued commands (assistant/remote_commands.py).
+
+Covers:
+- Allowed commands run and are acknowledged with their result
+- Commands the local policy doesn't allow, or doesn't know, are rejected
+- A command delivered twice (lost ack, restart mid-command) never runs twice
+- Command hints on the calendar subscription socket
+- Reloading the config file in place for refresh_config
+"""
+
+import asyncio
+import json
+
+from assistant.calendar_live import CalendarSubscription
+from assistant.config import Config
+from assistant.remote_commands import CommandFailed, CommandRunner
+
+
+class FakeResponse:
+    def __init__(self, data):
+        self.data = data
+
+    def json(self):
+        return self.data
+
+
+class FakeServer:
+    def __init__(self, commands):
+        self.commands = commands
+        self.acks = []
+
+    async def call(self, route, **kwargs):
+        return await getattr(self, route.method.lower())(route.path, idempotent=route.idempotent, **kwargs)
+
+    async def get(self, path, params=None, **kwargs):
+        assert (path, params) == ("/api/commands", None)  # The server takes the user from the token
+        return FakeResponse({"commands": self.commands})
+
+    async def post(self, path, json=None, idempotent=None, **kwargs):
+        assert idempotent and "user_id" not in json
+        self.acks.append((path.split("/")[3], json["status"], json["result"]))
+        return FakeResponse({})
+
+
+def command(id, type, key=None, **args):
+    return {"id": id, "type": type, "args": args, "idempotency_key": key or f"key-{id}"}
+
+
+def make_runner(tmp_path, server, allowed=("speak", "refresh_config")):
+    spoken = []
+
+    async def speak(args):
+        spoken.append(args["text"])
+        return "spoken"
+
+    async def refresh(args):
+        raise CommandFailed("config file unreadable")
+
+    async def call(args):
+        raise AssertionError("calls aren't allowed")
+
+    handlers = {"speak": speak, "refresh_config": refresh, "call": call}
+    return CommandRunner(server, handlers, allowed, path=tmp_path / "seen.json"), spoken
+
+
+def test_runs_rejects_and_acknowledges(tmp_path):
+    server = FakeServer([command("c1", "speak", text="Deploy finished"), command("c2", "call", message="Hi"),
+                         command("c3", "refresh_config"), command("c4", "format_disk")])
+    runner, spoken = make_runner(tmp_path, server)
+    outcomes = asyncio.run(runner.poll())
+
+    assert spoken == ["Deploy finished"]
+    assert server.acks == [
+        ("c1", "done", "spoken"),
+        ("c2", "rejected", "call isn't allowed here (config.remote_commands)"),
+        ("c3", "failed", "config file unreadable"),
+        ("c4", "rejected", "unknown command 'format_disk'"),
+    ]
+    assert [o.repeated for o in outcomes] == [False] * 4
+
+
+def test_redelivered_commands_run_once(tmp_path):
+    server = FakeServer([command("c1", "speak", text="Standup moved")])
+    runner, spoken = make_runner(tmp_path, server)
+    asyncio.run(runner.poll())
+
+    # The ack was lost: the server sends it again, to a restarted assistant
+    runner, spoken_again = make_runner(tmp_path, server)
+    [outcome] = asyncio.run(runner.poll())
+    assert spoken == ["Standup moved"] and spoken_again == []
+    assert outcome.repeated and server.acks[-1] == ("c1", "done", "spoken")
+
+    # Queued again under the same idempotency key: same answer, not run
+    server.commands = [command("c9", "speak", key="key-c1", text="Standup moved")]
+    asyncio.run(runner.poll())
+    assert spoken_again == [] and server.acks[-1] == ("c9", "done", "spoken")
+
+
+def test_interrupted_command_not_rerun(tmp_path):
+    (tmp_path / "seen.json").write_text(json.dumps({"key-c1": {"id": "c1", "type": "speak", "status": "failed",
+                                                                "result": "interrupted", "at": 4e9}}))
+    server = FakeServer([command("c1", "speak", text="Half said")])
+    runner, spoken = make_runner(tmp_path, server)
+    asyncio.run(runner.poll())
+    assert spoken == [] and server.acks == [("c1", "failed", "interrupted")]
+
+
+def test_command_hints_from_the_calendar_socket():
+    hints = []
+
+    async def on_change():
+        pass
+
+    subscription = CalendarSubscription("https://api.xswarm.ai", "u1", on_change, on_hint=hints.append)
+    for message in ('{"type": "subscribed"}', '{"type": "command", "id": "c1"}', "pong",
+                    '{"type": "calendar_change", "count": 1}'):
+        subscription.handle(message)
+    assert hints == [{"type": "command", "id": "c1"}] and subscription.changes == 1
+
+
+def test_config_reload_keeps_startup_settings(tmp_path):
+    path = tmp_path / "config.yaml"
+    path.write_text("quiet_hours_enabled: false\nvolume: 0.5\n")
+    config = Config.load_from_file(path)
+    config.text_only = True  # --text-only
+    config.anthropic_api_key = "sk-from-env"
+
+    path.write_text("quiet_hours_enabled: true\nvolume: 0.5\nremote_commands: [speak]\n")
+    assert config.reload() == ["quiet_hours_enabled", "remote_commands"]
+    assert config.quiet_hours_enabled and config.text_only and config.anthropic_api_key == "sk-from-env"
+    assert config.reload() == []
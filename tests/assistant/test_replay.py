@@ -0,0 +1,113 @@
+"""
+Tests for scenario replay (`xswarm dev replay`).
+
+Covers:
+- The example scenarios in tests/fixtures/scenarios pass end to end
+- Wake word gating and stripping
+- Held calls: prompt, then "yes" runs them; "undo that" is routed to undo
+- Failed expectations are reported, mocked tools replace real ones
+- Malformed scenarios are rejected
+"""
+
+import asyncio
+from pathlib import Path
+
+import pytest
+
+from assistant import tools
+from assistant.confirmation import get_confirmation_policy
+from assistant.conversation_window import strip_wake_word
+from assistant.replay import ReplayRunner, Scenario, ScenarioError
+
+SCENARIOS = Path(__file__).parents[1] / "fixtures" / "scenarios"
+
+
+def _replay(data):
+    return asyncio.run(ReplayRunner(Scenario.from_dict(data)).run())
+
+
+class TestExampleScenarios:
+    @pytest.mark.parametrize("name", ["book_dentist.yaml", "confirm_before_booking.yaml", "ask_for_missing_time.yaml",
+                                      "follow_up_it.yaml", "follow_up_window.yaml", "provider_outage.yaml"])
+    def test_scenario_passes(self, name):
+        result = asyncio.run(ReplayRunner(Scenario.load(SCENARIOS / name)).run())
+        assert result.passed, "\n".join(result.lines())
+
+    def test_globals_restored(self):
+        planner, policy = tools._planner_data, get_confirmation_policy()
+        asyncio.run(ReplayRunner(Scenario.load(SCENARIOS / "book_dentist.yaml")).run())
+        assert tools._planner_data is planner
+        assert get_confirmation_policy() is policy
+
+
+class TestRouting:
+    def test_wake_word(self):
+        assert strip_wake_word("Jarvis, what's next?", ["jarvis"]) == "what's next?"
+        assert strip_wake_word("ok hey boss add milk", ["hey boss"]) == "ok add milk"
+        assert strip_wake_word("what's next", ["jarvis"]) is None
+
+    def test_undo_after_booking(self):
+        result = _replay({"turns": [
+            {"say": "book standup", "ai": {"tools": [{"add_calendar_event": {
+                "title": "Standup", "day": "2026-10-20", "start_time": "09:00"}}]}},
+            {"say": "delete it", "ai": {"reply": "[TOOL: delete_calendar_event event_id=missing]"}},
+            {"say": "undo that"},
+        ]})
+        assert [t.route for t in result.turns] == ["ai", "ai", "undo"]
+        assert result.turns[1].tools[0].name == "delete_calendar_event"
+
+    def test_declined_call_not_run(self):
+        result = _replay({
+            "config": {"confirmation_levels": {"add_calendar_event": "explicit_yes"}},
+            "turns": [
+                {"say": "book lunch", "ai": {"tools": [{"add_calendar_event": {"title": "Lunch", "day": "2026-10-20"}}]}},
+                {"say": "no"},
+            ],
+        })
+        assert result.turns[0].tools[0].pending_confirmation
+        assert result.turns[1].route == "confirmation"
+        assert result.appointments == []
+
+    def test_mocked_tool(self):
+        result = _replay({
+            "mock_tools": {"add_calendar_event": "✓ (pretend) booked"},
+            "turns": [{"say": "book it", "ai": {"tools": [{"add_calendar_event": {"title": "X", "day": "today"}}]}}],
+        })
+        assert result.tools[0].result == "✓ (pretend) booked"
+        assert result.appointments == []
+        assert "(mocked)" not in tools.registry.get_tool("add_calendar_event").description
+
+
+class TestExpectations:
+    def test_failures_reported(self):
+        result = _replay({
+            "turns": [{"say": "hello", "ai": {"reply": "Hi there."}, "expect": {"spoken": ["Goodbye"]}}],
+            "expect": {"tools": ["add_task"], "appointments": [{"title": "Dentist"}]},
+        })
+        assert not result.passed
+        assert len(result.turns[0].failures) == 1
+        assert len(result.failures) == 2
+        assert result.lines()[-1] == "✗ failed (3 assertion(s))"
+
+    def test_tags_stripped_from_speech(self):
+        result = _replay({"turns": [{"say": "add milk", "ai": {"reply": "Added. [TOOL: add_task title=Milk]"}}]})
+        assert result.spoken == ["Added."]
+        assert [t["title"] for t in result.tasks] == ["Milk"]
+
+
+class TestScenarioFormat:
+    @pytest.mark.parametrize("data", [
+        {},
+        {"turns": []},
+        {"turns": [{"say": "hi", "audio": "hi.wav"}]},
+        {"turns": [{"say": "hi", "ai": {"tools": ["add_task"]}}]},
+    ])
+    def test_rejected(self, data):
+        with pytest.raises(ScenarioError):
+            Scenario.from_dict(data)
+
+    def test_tool_spec_forms(self):
+        scenario = Scenario.from_dict({"wake_word": "Jarvis", "turns": [{"say": "hi", "ai": {"tools": [
+            {"add_task": {"title": "A"}}, {"name": "add_task", "args": {"title": "B"}}]}}]})
+        assert scenario.wake_word == ["jarvis"]
+        assert [(t.name, t.args) for t in scenario.turns[0].tools] == [("add_task", {"title": "A"}), ("add_task", {"title": "B"})]
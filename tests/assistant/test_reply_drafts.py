@@ -0,0 +1,102 @@
+"""
+Tests for the voice "draft a reply" workflow.
+
+Covers:
+- Only explicit phrases confirm or cancel
+- Edit requests produce revisions instead of sending
+- Nothing is sent before confirmation
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.inbox import InboxStore
+from assistant.reply_drafts import ReplyWorkflow, classify_reply_command
+
+
+class FakeAI:
+    def __init__(self):
+        self.calls = []
+
+    def is_available(self):
+        return True
+
+    async def chat(self, messages, max_tokens=1024):
+        self.calls.append(messages)
+        return f"draft {len(self.calls)}"
+
+
+class FakeInboxManager:
+    def __init__(self, store):
+        self.store = store
+        self.sent = []
+
+    async def send_reply(self, item_id, text):
+        self.sent.append((item_id, text))
+        return True
+
+    async def draft_reply(self, item_id, instructions=None):
+        return None
+
+
+@pytest.fixture
+def workflow(tmp_path):
+    store = InboxStore(tmp_path)
+    store.merge_remote([{
+        "id": "msg-1",
+        "channel": "email",
+        "sender": "bob@example.com",
+        "subject": "Lunch?",
+        "content": "Free for lunch Thursday?",
+        "received_at": "2026-01-01T10:00:00",
+    }])
+    return ReplyWorkflow(FakeAI(), None, FakeInboxManager(store))
+
+
+class TestClassifyReplyCommand:
+    @pytest.mark.parametrize("text", ["Send it", "send it!", "go ahead", "Yes, send it."])
+    def test_confirm(self, text):
+        assert classify_reply_command(text) == "confirm"
+
+    @pytest.mark.parametrize("text", ["cancel", "Never mind", "don't send"])
+    def test_cancel(self, text):
+        assert classify_reply_command(text) == "cancel"
+
+    @pytest.mark.parametrize("text", ["yes", "send it but shorter", "make it shorter"])
+    def test_ambiguous_or_edit_is_not_confirm(self, text):
+        assert classify_reply_command(text) == "edit"
+
+
+class TestReplyWorkflow:
+    def test_edit_loop_then_send(self, workflow):
+        async def run():
+            first = await workflow.start("msg-1")
+            assert "draft 1" in first
+            assert workflow.inbox_manager.sent == []
+
+            revised = await workflow.handle("make it shorter")
+            assert "draft 2" in revised
+            assert "make it shorter" in workflow.ai.calls[-1][-1]["content"]
+            assert workflow.inbox_manager.sent == []
+
+            await workflow.handle("send it")
+            return workflow.inbox_manager.sent
+
+        assert asyncio.run(run()) == [("msg-1", "draft 2")]
+        assert workflow.session.state == "sent"
+        assert workflow.session.revisions == ["draft 1"]
+
+    def test_cancel_never_sends(self, workflow):
+        async def run():
+            await workflow.start("msg-1")
+            await workflow.handle("cancel")
+
+        asyncio.run(run())
+        assert workflow.inbox_manager.sent == []
+        assert not workflow.is_active
+
+    def test_email_guidelines_in_prompt(self, workflow):
+        asyncio.run(workflow.start("msg-1"))
+        system = workflow.ai.calls[0][0]["content"]
+        assert "email" in system and "sign-off" in system
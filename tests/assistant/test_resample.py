@@ -0,0 +1,73 @@
+"""
+Tests for the streaming resampler and per-consumer format negotiation.
+
+Covers:
+- Frame-by-frame resampling matches resampling the whole stream at once
+- Output length follows the rate ratio exactly across frames
+- Tones are preserved; content above the new Nyquist is filtered out
+- Conversions are shared between consumers at the same rate
+"""
+
+import numpy as np
+import pytest
+
+from assistant.audio_frame import AudioFrame
+from assistant.resample import AudioFormat, FormatNegotiator, StreamResampler
+
+
+def _tone(freq, rate, seconds=1.0, amplitude=0.5):
+    t = np.arange(int(rate * seconds)) / rate
+    return (amplitude * np.sin(2 * np.pi * freq * t)).astype(np.float32)
+
+
+def _rms(x):
+    return float(np.sqrt(np.mean(np.square(x))))
+
+
+def _stream(resampler, audio, frame_len):
+    return np.concatenate([resampler.process(audio[i:i + frame_len]) for i in range(0, len(audio), frame_len)])
+
+
+@pytest.mark.parametrize("src,dst", [(24000, 16000), (48000, 16000), (16000, 24000), (44100, 48000)])
+def test_streaming_matches_one_shot(src, dst):
+    audio = _tone(440, src, seconds=0.5)
+    whole = StreamResampler(src, dst).process(audio)
+    framed = _stream(StreamResampler(src, dst), audio, int(src * 0.02))
+    assert len(framed) == len(whole) == len(audio) * dst // src
+    assert np.allclose(framed, whole, atol=1e-5)
+
+
+def test_passthrough():
+    audio = _tone(440, 24000, seconds=0.02)
+    resampler = StreamResampler(24000, 24000)
+    assert resampler.passthrough
+    assert resampler.process(audio) is audio
+
+
+def test_tone_is_preserved():
+    out = StreamResampler(24000, 16000).process(_tone(440, 24000))
+    assert _rms(out[1000:]) == pytest.approx(_rms(_tone(440, 16000)), rel=0.02)
+
+
+def test_content_above_new_nyquist_is_removed():
+    out = StreamResampler(24000, 16000).process(_tone(11000, 24000))
+    assert _rms(out[1000:]) < 0.005
+
+
+class Consumer:
+    def __init__(self, rate):
+        self.input_format = AudioFormat(rate, "int16")
+
+
+def test_negotiation_shares_conversions():
+    formats = FormatNegotiator(24000)
+    frame = AudioFrame(_tone(440, 24000, seconds=0.02), sample_rate=24000)
+
+    stt, wake_word = Consumer(16000), Consumer(16000)
+    converted = formats.for_consumer(frame, stt)
+    assert converted.sample_rate == 16000
+    assert len(converted) == 320
+    assert formats.for_consumer(frame, wake_word) is converted
+
+    # Consumers without a declared format get the capture format
+    assert formats.for_consumer(frame, object()) is frame
@@ -0,0 +1,102 @@
+"""
+Tests for the data retention policy (assistant/retention.py).
+
+Covers:
+- Per-class defaults and config overrides, including keep-forever
+- Dry runs reporting without deleting
+- Deleting expired transcripts (and their index entries), recordings, logs and events
+"""
+
+import json
+import os
+from datetime import datetime, timedelta
+
+from assistant.config import Config
+from assistant.events import EventStore
+from assistant.retention import RetentionEngine, RetentionPolicy
+
+NOW = datetime(2026, 10, 16, 12, 0)
+
+
+def make_file(path, days_old, text="x"):
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(text)
+    stamp = (NOW - timedelta(days=days_old)).timestamp()
+    os.utime(path, (stamp, stamp))
+    return path
+
+
+def make_engine(tmp_path, **overrides):
+    return RetentionEngine(RetentionPolicy(overrides), root=tmp_path,
+                           tmp_logs=(tmp_path / "tmp", "xswarm_*.log*"), clock=lambda: NOW)
+
+
+def test_policy_from_config():
+    policy = RetentionPolicy.from_config(Config(event_history_days=30, retention_days={"audio": 7, "exports": 0}))
+    assert policy.days["audio"] == 7
+    assert policy.days["events"] == 30
+    assert policy.days["transcripts"] == 365
+    assert policy.cutoff("exports", NOW) is None
+    assert RetentionPolicy({"videos": 3, "logs": -1}).problems == [
+        "Unknown retention class 'videos' (use transcripts, audio, events, ai_log, logs, exports)",
+        "Retention for logs must be a number of days (0 = keep forever)",
+    ]
+
+
+def test_dry_run_keeps_everything(tmp_path):
+    old = make_file(tmp_path / "recordings" / "call.wav", 40)
+    make_file(tmp_path / "recordings" / "today.wav", 1)
+    make_file(tmp_path / "recordings" / "notes.txt", 40)  # Not audio
+    report = make_engine(tmp_path).run(apply=False)
+    assert old.exists()
+    assert report.total("audio") == 1
+    assert report.summary() == "Would delete 1 audio"
+    assert "Retention (dry run - nothing deleted)" in report.lines()
+
+
+def test_config_dry_run_applies_to_the_job(tmp_path):
+    old = make_file(tmp_path / "exports" / "week.csv", 40)
+    engine = RetentionEngine(RetentionPolicy(dry_run=True), root=tmp_path, tmp_logs=(tmp_path, "*.log"),
+                             clock=lambda: NOW)
+    assert not engine.run().applied
+    assert old.exists()
+
+
+def test_deletes_expired_files(tmp_path):
+    recording = make_file(tmp_path / "recordings" / "call.wav", 40)
+    log = make_file(tmp_path / "tmp" / "xswarm_main.log.1", 20)
+    fresh_log = make_file(tmp_path / "logs" / "voice.log", 2)
+    export = make_file(tmp_path / "exports" / "week.csv", 40)
+    report = make_engine(tmp_path, exports=0).run(apply=True)
+    assert not recording.exists() and not log.exists()
+    assert fresh_log.exists() and export.exists()  # Exports kept forever
+    assert report.summary() == "Deleted 1 audio, 1 logs"
+
+
+def test_deletes_expired_transcripts(tmp_path):
+    persona = tmp_path / "chat_history" / "jarvis"
+    index = [
+        {"session_id": "session_old", "started_at": "2025-01-01T10:00:00", "ended_at": "2025-01-01T10:30:00"},
+        {"session_id": "session_new", "started_at": "2026-10-01T10:00:00", "ended_at": None},
+    ]
+    make_file(persona / "sessions.json", 0, json.dumps(index))
+    old = make_file(persona / "session_old.json", 0)  # Index date wins over the file time
+    new = make_file(persona / "session_new.json", 0)
+    orphan = make_file(persona / "session_orphan.json", 400)
+
+    report = make_engine(tmp_path).run(apply=True)
+    assert not old.exists() and not orphan.exists() and new.exists()
+    assert [entry["session_id"] for entry in json.loads((persona / "sessions.json").read_text())] == ["session_new"]
+    assert report.total("transcripts") == 2
+
+
+def test_events(tmp_path):
+    store = EventStore(":memory:", keep_days=None)
+    store.record("activity", "old", at=NOW - timedelta(days=100))
+    store.record("activity", "new", at=NOW - timedelta(days=1))
+    engine = RetentionEngine(RetentionPolicy(), root=tmp_path, events=store, tmp_logs=(tmp_path, "*.log"),
+                             clock=lambda: NOW)
+    assert engine.run(apply=False).total("events") == 1
+    assert len(store.query()) == 2
+    engine.run(apply=True)
+    assert [event.summary for event in store.query()] == ["new"]
@@ -0,0 +1,245 @@
+"""
+Tests for the background job scheduler.
+
+Covers:
+- Cron expression parsing and next-run calculation
+- Interval and cron jobs run when due, with jitter
+- Per-job enable flags and schedule overrides from config
+- Last-run status (ok/error/skipped) persisted for `dev jobs list`
+"""
+
+import asyncio
+from datetime import datetime
+from types import SimpleNamespace
+
+import pytest
+
+from assistant.governor import ResourceGovernor, ResourceUsage
+from assistant.scheduler import CronSchedule, JobStateStore, ScheduledTask, Scheduler, format_job_table
+
+
+class FakeClock:
+    def __init__(self, start: datetime = datetime(2026, 3, 2, 8, 59, 30)):
+        self.now = start.timestamp()
+
+    def __call__(self):
+        return self.now
+
+
+def _idle_governor():
+    return ResourceGovernor(sampler=lambda: ResourceUsage(), sample_interval=0)
+
+
+def _scheduler(tmp_path=None, jobs=None, clock=None):
+    scheduler = Scheduler(
+        governor=_idle_governor(),
+        store=JobStateStore(tmp_path) if tmp_path else None,
+        config=SimpleNamespace(jobs=jobs or {}),
+        clock=clock or FakeClock(),
+    )
+    scheduler.tasks.clear()  # Only the jobs each test registers
+    return scheduler
+
+
+def _run(coro):
+    return asyncio.run(coro)
+
+
+class TestCron:
+    def test_every_fifteen_minutes(self):
+        cron = CronSchedule("*/15 * * * *")
+        assert cron.next_after(datetime(2026, 3, 2, 8, 59, 30)) == datetime(2026, 3, 2, 9, 0)
+        assert cron.next_after(datetime(2026, 3, 2, 9, 0)) == datetime(2026, 3, 2, 9, 15)
+
+    def test_weekdays_at_nine(self):
+        cron = CronSchedule("0 9 * * 1-5")
+        # Friday 2026-03-06 after 9 -> Monday 2026-03-09
+        assert cron.next_after(datetime(2026, 3, 6, 9, 0)) == datetime(2026, 3, 9, 9, 0)
+
+    def test_sunday_is_zero_or_seven(self):
+        assert CronSchedule("0 0 * * 0").weekdays == CronSchedule("0 0 * * 7").weekdays == {6}
+
+    def test_lists_and_aliases(self):
+        assert CronSchedule("0,30 * * * *").minutes == {0, 30}
+        assert CronSchedule("@hourly").next_after(datetime(2026, 3, 2, 8, 15)) == datetime(2026, 3, 2, 9, 0)
+
+    def test_month_rollover(self):
+        cron = CronSchedule("0 0 1 * *")
+        assert cron.next_after(datetime(2026, 12, 15)) == datetime(2027, 1, 1)
+
+    def test_day_of_month_or_weekday(self):
+        # Both restricted: the 13th OR any Friday
+        cron = CronSchedule("0 12 13 * 5")
+        assert cron.matches(datetime(2026, 3, 13, 12, 0))  # Friday the 13th
+        assert cron.matches(datetime(2026, 3, 6, 12, 0))   # A Friday
+        assert cron.matches(datetime(2026, 4, 13, 12, 0))  # A Monday the 13th
+
+    @pytest.mark.parametrize("expression", ["* * * *", "60 * * * *", "*/0 * * * *", "5-1 * * * *", "0 0 31 2 *"])
+    def test_invalid(self, expression):
+        with pytest.raises(ValueError):
+            CronSchedule(expression).next_after(datetime(2026, 1, 1))
+
+
+class TestRunning:
+    def test_interval_job_runs_at_start_then_every_interval(self):
+        clock = FakeClock()
+        scheduler = _scheduler(clock=clock)
+        runs = []
+        scheduler.add_job("sync", lambda: runs.append(clock.now), interval=60)
+
+        async def scenario():
+            scheduler.start()
+            await scheduler.run_due()
+            clock.now += 30
+            await scheduler.run_due()
+            clock.now += 30
+            await scheduler.run_due()
+            await scheduler.run_due()
+            scheduler.stop()
+
+        _run(scenario())
+        assert len(runs) == 2
+        assert runs[1] - runs[0] == 60
+
+    def test_run_at_start_and_async_handler(self):
+        scheduler = _scheduler()
+        runs = []
+
+        async def handler():
+            runs.append(1)
+
+        scheduler.add_job("inbox", handler, interval=60, run_at_start=True)
+
+        async def scenario():
+            scheduler.start()
+            await scheduler.run_due()
+            scheduler.stop()
+
+        _run(scenario())
+        assert runs == [1]
+        assert scheduler.tasks["inbox"].last_status == "ok"
+
+    def test_cron_job_next_run(self):
+        clock = FakeClock(datetime(2026, 3, 2, 8, 59, 30))
+        scheduler = _scheduler(clock=clock)
+        task = scheduler.add_job("digest", lambda: None, cron="0 9 * * 1")
+
+        async def scenario():
+            scheduler.start()
+            scheduler.stop()
+
+        _run(scenario())
+        assert datetime.fromtimestamp(task.next_run) == datetime(2026, 3, 2, 9, 0)
+
+    def test_jitter_bounds(self):
+        task = ScheduledTask(name="sync", interval=60, jitter=10)
+        for _ in range(50):
+            assert 1060 <= task.compute_next_run(1000) <= 1070
+
+    def test_disabled_job_does_not_run_but_run_now_does(self):
+        scheduler = _scheduler(jobs={"indexing": {"enabled": False}})
+        runs = []
+        scheduler.add_job("indexing", lambda: runs.append(1), interval=60, run_at_start=True)
+
+        async def scenario():
+            scheduler.start()
+            await scheduler.run_due()
+            await scheduler.run_now("indexing")
+            scheduler.stop()
+
+        _run(scenario())
+        assert runs == [1]
+
+    def test_config_schedule_override(self):
+        scheduler = _scheduler(jobs={"calendar_sync": {"cron": "*/30 7-22 * * *", "jitter": 0}})
+        task = scheduler.add_job("calendar_sync", lambda: None, interval=900, jitter=60)
+        assert task.schedule == "cron */30 7-22 * * *"
+        assert task.jitter == 0
+
+    def test_errors_are_recorded(self):
+        scheduler = _scheduler()
+
+        def boom():
+            raise RuntimeError("server down")
+
+        scheduler.add_job("sync", boom, interval=60)
+        task = _run(scheduler.run_now("sync"))
+        assert task.last_status == "error"
+        assert task.last_error == "server down"
+        assert task.run_count == 1
+
+    def test_thinking_task_without_engine_is_skipped(self):
+        scheduler = Scheduler(governor=_idle_governor())
+        task = _run(scheduler.run_now("memory_consolidation"))
+        assert task.last_status == "skipped"
+
+    def test_unknown_job(self):
+        with pytest.raises(KeyError):
+            _run(_scheduler().run_now("nope"))
+
+
+class TestStatus:
+    def test_status_persists_across_instances(self, tmp_path):
+        scheduler = _scheduler(tmp_path)
+        scheduler.add_job("calendar_sync", lambda: None, interval=900)
+        _run(scheduler.run_now("calendar_sync"))
+
+        again = _scheduler(tmp_path)
+        task = again.add_job("calendar_sync", lambda: None, interval=900)
+        assert task.last_status == "ok"
+        assert task.run_count == 1
+        assert again.store.get("calendar_sync")["schedule"] == "every 15m"
+
+    def test_frequent_jobs_only_persist_changes(self, tmp_path):
+        scheduler = _scheduler(tmp_path)
+        scheduler.add_job("poll", lambda: None, interval=2)
+        _run(scheduler.run_now("poll"))
+        _run(scheduler.run_now("poll"))
+        assert JobStateStore(tmp_path).get("poll")["run_count"] == 1
+
+    def test_resumes_interval_from_last_run(self, tmp_path):
+        clock = FakeClock()
+        scheduler = _scheduler(tmp_path, clock=clock)
+        scheduler.add_job("hourly", lambda: None, interval=3600)
+        _run(scheduler.run_now("hourly"))
+
+        clock.now += 600
+        again = _scheduler(tmp_path, clock=clock)
+        task = again.add_job("hourly", lambda: None, interval=3600)
+
+        async def scenario():
+            again.start()
+            again.stop()
+
+        _run(scenario())
+        assert task.next_run == clock.now - 600 + 3600
+
+    def test_run_at_start_ignores_last_run(self, tmp_path):
+        clock = FakeClock()
+        scheduler = _scheduler(tmp_path, clock=clock)
+        scheduler.add_job("inbox", lambda: None, interval=3600)
+        _run(scheduler.run_now("inbox"))
+
+        clock.now += 600
+        again = _scheduler(tmp_path, clock=clock)
+        task = again.add_job("inbox", lambda: None, interval=3600, run_at_start=True)
+
+        async def scenario():
+            again.start()
+            again.stop()
+
+        _run(scenario())
+        assert task.next_run == clock.now
+
+    def test_format_table(self):
+        now = datetime(2026, 3, 2, 9, 0).timestamp()
+        rows = [
+            {"name": "inbox_sync", "schedule": "every 1m", "enabled": True, "last_run": now - 30,
+             "last_status": "error", "last_error": "server down"},
+            {"name": "document_indexing", "schedule": "every 6h", "enabled": False, "last_run": 0,
+             "last_status": None, "last_error": None},
+        ]
+        table = format_job_table(rows, now=now).splitlines()
+        assert table[0].startswith("JOB")
+        assert "never" in table[1] and "[disabled]" in table[1]
+        assert "30s ago" in table[2] and "error (server down)" in table[2]
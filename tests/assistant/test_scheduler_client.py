@@ -0,0 +1,253 @@
+"""
+Tests for bulk calendar calls and planner -> server calendar sync.
+
+Covers:
+- Batches are chunked and per-item indexes mapped back to the caller's list
+- Recurring events are materialized and pushed in one request
+- Re-syncing only sends what changed; skipped occurrences are deleted, moved ones re-created
+- Invitations target the synced appointment and RSVPs land in the planner
+- Schedule reads cached, revalidated with ETags, dropped after changes and inbox events
+"""
+
+import asyncio
+from datetime import date, datetime
+
+from assistant.events import Event
+from assistant.planner import PlannerData
+from assistant.scheduler_client import MAX_BATCH, CalendarSync, SchedulerClient
+
+WEDNESDAY = date(2026, 10, 14)
+
+
+class FakeResponse:
+    def __init__(self, body, status_code=200, headers=None):
+        self.body = body
+        self.status_code = status_code
+        self.headers = headers or {}
+
+    def json(self):
+        return self.body
+
+
+class FakeApi:
+    """Records requests and answers like the server batch endpoints."""
+
+    def __init__(self, reject_titles=()):
+        self.requests = []
+        self.reject_titles = set(reject_titles)
+        self.next_id = 0
+
+    async def call(self, route, **kwargs):
+        return await getattr(self, route.method.lower())(route.path, **kwargs)
+
+    async def post(self, path, json=None, **kwargs):
+        self.requests.append((path, json))
+        if path.endswith("/invitations"):
+            return FakeResponse({
+                "invitations": {"sent": ["Sarah", "bob@example.com"], "skipped": [], "failed": []},
+                "participants": [{"label": "Sarah", "rsvp_status": "pending"}, {"label": "bob@example.com", "rsvp_status": "pending"}],
+            })
+        if path.endswith("/batch-delete"):
+            return FakeResponse({"deleted": list(json["ids"]), "not_found": []})
+        created, errors = [], []
+        for index, appointment in enumerate(json["appointments"]):
+            if appointment["title"] in self.reject_titles:
+                errors.append({"index": index, "error": "rejected"})
+                continue
+            self.next_id += 1
+            created.append({"id": f"apt-{self.next_id}", **appointment})
+        return FakeResponse({"created": created, "conflicts": [], "errors": errors})
+
+    async def get(self, path, **kwargs):
+        self.requests.append((path, None))
+        return FakeResponse({"participants": [
+            {"label": "Sarah", "rsvp_status": "accepted"},
+            {"label": "bob@example.com", "rsvp_status": "declined"},
+        ]})
+
+    async def put(self, path, json=None, **kwargs):
+        self.requests.append((path, json))
+        return FakeResponse({"reminders": [{"id": u["id"], "completed": u.get("completed")} for u in json["updates"]]})
+
+
+def _client(api):
+    client = SchedulerClient()
+    client.client = api
+    return client
+
+
+class TestSchedulerClient:
+    def test_create_chunks_and_offsets_indexes(self):
+        api = FakeApi(reject_titles={"bad"})
+        appointments = [{"title": "ok", "start_time": "x", "end_time": "y"} for _ in range(MAX_BATCH + 5)]
+        appointments[MAX_BATCH + 2]["title"] = "bad"
+
+        result = asyncio.run(_client(api).create_appointments("u1", appointments))
+
+        assert len(api.requests) == 2
+        assert len(result["created"]) == MAX_BATCH + 4
+        assert result["errors"] == [{"index": MAX_BATCH + 2, "error": "rejected"}]
+
+    def test_bulk_reminder_updates(self):
+        api = FakeApi()
+        updates = [{"id": f"r{i}", "completed": True} for i in range(3)]
+        reminders = asyncio.run(_client(api).update_reminders("u1", updates))
+        assert [r["id"] for r in reminders] == ["r0", "r1", "r2"]
+        assert api.requests[0][0] == "/api/calendar/reminders/batch"
+
+
+class FakeCalendarServer(FakeApi):
+    """Answers schedule reads with an ETag, and 304 when If-None-Match still matches."""
+
+    def __init__(self):
+        super().__init__()
+        self.schedule = [{"id": "apt-1", "title": "Standup"}]
+        self.version = 1
+
+    async def get(self, path, params=None, headers=None, **kwargs):
+        self.requests.append((path, dict(headers or {})))
+        etag = f'"v{self.version}"'
+        validators = {"ETag": etag, "Last-Modified": "Wed, 14 Oct 2026 09:00:00 GMT"}
+        if (headers or {}).get("If-None-Match") == etag:
+            return FakeResponse(None, 304, validators)
+        key = "schedule" if path.endswith("/today") else "appointments"
+        return FakeResponse({key: list(self.schedule)}, 200, validators)
+
+
+class Clock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+class TestCachedReads:
+    def _client(self):
+        api, clock = FakeCalendarServer(), Clock()
+        client = SchedulerClient(cache_seconds=30, clock=clock)
+        client.client = api
+        return client, api, clock
+
+    def test_fresh_then_revalidated(self):
+        client, api, clock = self._client()
+        assert asyncio.run(client.today_schedule("u1"))[0]["title"] == "Standup"
+        asyncio.run(client.today_schedule("u1"))
+        assert len(api.requests) == 1  # Answered from the cache
+
+        clock.now += 31
+        assert asyncio.run(client.today_schedule("u1"))[0]["title"] == "Standup"
+        assert api.requests[-1][1] == {"If-None-Match": '"v1"', "If-Modified-Since": "Wed, 14 Oct 2026 09:00:00 GMT"}
+        assert client.cache_stats == {"fresh": 1, "not_modified": 1, "fetched": 1}
+
+        # Changed on the server: the next revalidation gets the new body
+        api.schedule.append({"id": "apt-2", "title": "Dentist"})
+        api.version = 2
+        clock.now += 31
+        assert len(asyncio.run(client.today_schedule("u1"))) == 2
+
+    def test_queries_cached_separately(self):
+        client, api, clock = self._client()
+        asyncio.run(client.list_appointments("u1", start="2026-10-14T00:00:00"))
+        asyncio.run(client.list_appointments("u1", start="2026-10-15T00:00:00"))
+        asyncio.run(client.list_appointments("u1", start="2026-10-14T00:00:00"))
+        assert len(api.requests) == 2
+
+    def test_changes_and_inbox_events_invalidate(self):
+        client, api, clock = self._client()
+        asyncio.run(client.today_schedule("u1"))
+        asyncio.run(client.create_appointments("u1", [{"title": "Lunch", "start_time": "x", "end_time": "y"}]))
+        asyncio.run(client.today_schedule("u1"))
+        reads = [r for r in api.requests if r[0] == "/api/calendar/today"]
+        assert len(reads) == 2 and reads[1][1] == {}  # Fetched fresh, not revalidated
+
+        client.on_event(Event("activity", datetime(2026, 10, 14, 9), "Indexed docs"))
+        asyncio.run(client.today_schedule("u1"))
+        client.on_event(Event("sms", datetime(2026, 10, 14, 9), "Text from Sarah"))
+        asyncio.run(client.today_schedule("u1"))
+        assert len([r for r in api.requests if r[0] == "/api/calendar/today"]) == 3
+
+
+class TestCalendarSync:
+    def _sync(self, tmp_path, api):
+        planner = PlannerData(tmp_path / "planner")
+        return planner, CalendarSync(planner, _client(api), "u1", storage_dir=tmp_path / "sync")
+
+    def test_recurring_instances_pushed_in_one_request(self, tmp_path):
+        api = FakeApi()
+        planner, sync = self._sync(tmp_path, api)
+        planner.add_calendar_event("Standup", "2026-10-15T09:00:00", "2026-10-15T09:15:00", recurrence="daily")
+        planner.add_calendar_event("Dentist", "2026-10-20T14:00:00", "2026-10-20T15:00:00")
+
+        result = asyncio.run(sync.push(days=13, today=WEDNESDAY))
+
+        assert len(api.requests) == 1
+        titles = [a["title"] for a in api.requests[0][1]["appointments"]]
+        assert titles.count("Standup") == 13
+        assert titles.count("Dentist") == 1
+        assert result == {"created": 14, "deleted": 0, "failed": 0}
+
+    def test_resync_sends_only_changes(self, tmp_path):
+        api = FakeApi()
+        planner, sync = self._sync(tmp_path, api)
+        dentist = planner.add_calendar_event("Dentist", "2026-10-20T14:00:00", "2026-10-20T15:00:00")
+        lunch = planner.add_calendar_event("Lunch", "2026-10-21T12:00:00", "2026-10-21T13:00:00")
+        asyncio.run(sync.push(today=WEDNESDAY))
+
+        assert asyncio.run(sync.push(today=WEDNESDAY)) == {"created": 0, "deleted": 0, "failed": 0}
+
+        planner.update_calendar_event(dentist.id, title="Dentist (moved room)")
+        planner.delete_calendar_event(lunch.id)
+        result = asyncio.run(sync.push(today=WEDNESDAY))
+
+        assert result == {"created": 1, "deleted": 2, "failed": 0}
+        delete_path, delete_body = api.requests[-2]
+        assert delete_path.endswith("/batch-delete") and len(delete_body["ids"]) == 2
+
+    def test_skipped_and_moved_occurrences(self, tmp_path):
+        api = FakeApi()
+        planner, sync = self._sync(tmp_path, api)
+        standup = planner.add_calendar_event("Standup", "2026-10-15T09:00:00", "2026-10-15T09:15:00", recurrence="daily")
+        asyncio.run(sync.push(days=6, today=WEDNESDAY))
+
+        planner.delete_calendar_event(f"{standup.id}@2026-10-16")
+        planner.update_calendar_event(f"{standup.id}@2026-10-19", start_time="2026-10-19T10:00:00",
+                                      end_time="2026-10-19T10:15:00")
+        result = asyncio.run(sync.push(days=6, today=WEDNESDAY))
+
+        assert result == {"created": 1, "deleted": 2, "failed": 0}  # The skipped one only goes
+        assert api.requests[-1][1]["appointments"][0]["start_time"].startswith("2026-10-19T10:00:00")
+
+    def test_rejected_items_retried_next_sync(self, tmp_path):
+        api = FakeApi(reject_titles={"Bad"})
+        planner, sync = self._sync(tmp_path, api)
+        planner.add_calendar_event("Bad", "2026-10-20T14:00:00", "2026-10-20T15:00:00")
+
+        assert asyncio.run(sync.push(today=WEDNESDAY))["failed"] == 1
+        api.reject_titles.clear()
+        assert asyncio.run(sync.push(today=WEDNESDAY))["created"] == 1
+
+
+class TestParticipants:
+    def test_invite_syncs_first_and_rsvps_reach_planner(self, tmp_path):
+        api = FakeApi()
+        planner = PlannerData(tmp_path / "planner")
+        sync = CalendarSync(planner, _client(api), "u1", storage_dir=tmp_path / "sync")
+        event = planner.add_calendar_event(
+            "Review", "2099-01-05T10:00:00", "2099-01-05T11:00:00", attendees=["Sarah", "bob@example.com"]
+        )
+        asyncio.run(sync.push(days=1, today=date(2099, 1, 5)))
+
+        asyncio.run(sync.invite(event.id))
+        assert api.requests[-1][0] == "/api/calendar/appointments/apt-1/invitations"
+        assert planner.get_calendar_event(event.id).participant_summary() == "with Sarah …, bob@example.com …"
+
+        updated = asyncio.run(sync.refresh_rsvps(event.id))
+        assert updated.rsvp == {"Sarah": "accepted", "bob@example.com": "declined"}
+        assert updated.participant_summary() == "with Sarah ✓, bob@example.com ✗"
+
+    def test_summary_without_rsvps(self, tmp_path):
+        planner = PlannerData(tmp_path / "planner")
+        event = planner.add_calendar_event("Lunch", "2099-01-05T12:00:00", "2099-01-05T13:00:00", attendees=["Ana"])
+        assert event.participant_summary() == "with Ana"
+        assert planner.add_calendar_event("Focus", "2099-01-05T14:00:00", "2099-01-05T15:00:00").participant_summary() == ""
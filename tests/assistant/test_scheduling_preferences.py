@@ -0,0 +1,117 @@
+"""
+Tests for scheduling preferences learned from what the user says (assistant/scheduling_preferences.py).
+
+Covers:
+- "I never take meetings before 10" and the other ways of stating one; anything else is left alone
+- Stored beside the profile; a newer rule of the same kind and days replaces the old one
+- The working day a day's rules leave, and the rules a meeting breaks
+- suggest_meeting_times only offers times that fit; booking outside them warns
+- Reviewing and forgetting them, and "undo that"
+"""
+
+from datetime import date, datetime, timedelta
+
+import pytest
+
+from assistant import scheduling_preferences, tools
+from assistant.planner import PlannerData
+from assistant.scheduling_preferences import Constraint, SchedulingPreferences, parse_preference
+from assistant.undo import UndoLog, undo_last
+
+MONDAY = date.today() + timedelta(days=7 - date.today().weekday())  # Next week's, so always ahead
+
+
+@pytest.fixture
+def preferences(tmp_path, monkeypatch):
+    preferences = SchedulingPreferences(tmp_path / "profile")
+    monkeypatch.setattr(scheduling_preferences, "_preferences", preferences)
+    monkeypatch.setattr(tools, "_planner_data", PlannerData(tmp_path / "planner"))
+    monkeypatch.setattr(tools, "_undo_log", UndoLog(tmp_path / "undo"))
+    return preferences
+
+
+@pytest.mark.parametrize("text, expected", [
+    ("I never take meetings before 10", "no meetings before 10:00"),
+    ("no calls after 4pm", "no meetings after 16:00"),
+    ("I never take calls past 5", "no meetings after 17:00"),
+    ("I don't do meetings on Fridays", "no meetings on Fridays"),
+    ("Please don't book anything on weekends.", "no meetings on weekends"),
+    ("no meetings on Friday afternoons", "no meetings after 12:00 on Fridays"),
+    ("no meetings before 9:30 on mondays and wednesdays", "no meetings before 09:30 on Mondays and Wednesdays"),
+    ("keep my mornings free", "no meetings before 12:00"),
+    ("No more than three meetings a day", "at most 3 meetings a day"),
+    ("no meetings tomorrow after 3", None),  # About one day, not a rule
+    ("no meetings today", None),
+    ("what meetings do I have on friday?", None),
+])
+def test_parse_preference(text, expected):
+    constraint = parse_preference(text)
+    assert (constraint.description if constraint else None) == expected
+    if constraint:
+        assert constraint.said == text
+
+
+def test_store(preferences, tmp_path):
+    preferences.add(parse_preference("I never take meetings before 10"))
+    preferences.add(parse_preference("no meetings on Fridays"))
+    replaced = preferences.add(parse_preference("actually no meetings before 9:30"))
+    assert [c.description for c in replaced] == ["no meetings before 10:00"]
+    again = SchedulingPreferences(tmp_path / "profile")
+    assert [c.description for c in again.constraints()] == ["no meetings on Fridays", "no meetings before 09:30"]
+    removed = again.remove("friday")
+    assert [c.description for c in removed] == ["no meetings on Fridays"]
+    assert again.restore([vars(c) for c in removed]) == 1
+    assert sorted(c.kind for c in again.constraints()) == ["before", "day"]
+
+
+def test_window_and_broken_by(preferences):
+    preferences.add(Constraint("before", "10:00"))
+    preferences.add(Constraint("after", "12:00", [4]))
+    preferences.add(Constraint("per_day", "2"))
+    friday = MONDAY + timedelta(days=4)
+    assert preferences.window(MONDAY) == ("10:00", "18:00")
+    assert preferences.window(friday) == ("10:00", "12:00")
+    assert preferences.window(MONDAY, booked=2) is None
+
+    def broken(day, start, end, booked=0):
+        at = datetime.combine(day, datetime.min.time())
+        return [c.kind for c in preferences.broken_by(at + timedelta(hours=start), at + timedelta(hours=end), booked)]
+
+    assert broken(MONDAY, 9, 10) == ["before"]
+    assert broken(MONDAY, 14, 15) == []
+    assert broken(friday, 11.5, 12.5) == ["after"]
+    assert broken(MONDAY, 14, 15, booked=2) == ["per_day"]
+
+
+def test_suggestions_fit(preferences):
+    day = MONDAY.isoformat()
+    tools.get_planner_data().add_calendar_event("Review", f"{day}T10:00:00", f"{day}T11:30:00")
+    preferences.add(parse_preference("I never take meetings before 10"))
+    result = tools.suggest_meeting_times(day, 60, count=3)
+    assert result.splitlines()[0] == "Free for 60 min (keeping to no meetings before 10:00):"
+    assert [line.split(" at ")[1] for line in result.splitlines()[1:]] == ["11:30", "12:30", "13:30"]
+    preferences.add(parse_preference("no meetings on mondays"))
+    assert tools.suggest_meeting_times(day).startswith(f"✗ No free 60 min slot on {MONDAY:%a %b %d}")
+    week = tools.suggest_meeting_times("", 60, count=20)
+    assert f"{MONDAY:%a %b %d}" not in week
+
+
+def test_booking_outside_warns(preferences):
+    preferences.add(parse_preference("I never take meetings before 10"))
+    assert tools.add_calendar_event("Sync", MONDAY.isoformat(), "09:00", 30).endswith(
+        "\n⚠ You asked for no meetings before 10:00")
+    assert "⚠ You asked" not in tools.add_calendar_event("Sync", MONDAY.isoformat(), "10:00", 30)
+
+
+def test_review_forget_and_undo(preferences):
+    assert tools.list_scheduling_preferences().startswith("No scheduling preferences yet")
+    assert tools.add_scheduling_preference("no meetings on fridays") == "✓ Noted: no meetings on Fridays"
+    assert tools.add_scheduling_preference("whenever").startswith("✗")
+    listed = tools.list_scheduling_preferences()
+    assert listed.splitlines()[0] == "Scheduling preferences (1), working day 09:00-18:00:"
+    assert "no meetings on Fridays - \"no meetings on fridays\"" in listed
+    assert tools.forget_scheduling_preference("fridays").startswith("✓ Forgot 1 scheduling preference")
+    assert preferences.constraints() == []
+    assert tools.forget_scheduling_preference("fridays").startswith("✗")
+    assert undo_last(tools.get_undo_log()) == "✓ Undone: Forgot 1 scheduling preference"
+    assert [c.description for c in preferences.constraints()] == ["no meetings on Fridays"]
@@ -0,0 +1,198 @@
+"""
+Tests for Lua hook scripts (assistant/scripting.py).
+
+Covers:
+- Off unless config.scripts_enabled; *.lua files load in name order, broken ones reported
+- on_transcript: rewrite, drop (false) or leave (nil) what the user said
+- before_notification: change title/message/priority, or drop; emergencies skip the hooks
+- on_event: change the summary or drop the event before it's recorded and pushed
+- Scripts chain, each seeing the last one's changes
+- A failing or timed-out call is ignored; MAX_FAILURES in a row switch the script off
+- With lupa installed: a script's top-level code and its hooks stop at a wall-clock deadline
+(Lua itself is otherwise replaced by a fake engine calling Python functions)
+"""
+
+import time
+from datetime import datetime
+
+import pytest
+
+from assistant.config import Config
+from assistant.events import (
+    Event, add_event_hook, add_event_listener, record_event, remove_event_hook, remove_event_listener,
+)
+from assistant.notifications import (
+    Notification, add_notification_hook, apply_notification_hooks, remove_notification_hook,
+)
+from assistant.scripting import MAX_FAILURES, Script, ScriptError, ScriptHooks
+
+
+class FakeEngine:
+    """Stands in for a Lua state: hooks are Python functions, and raising ScriptError is a Lua error."""
+
+    def __init__(self, **hooks):
+        self.hooks = hooks
+        self.timeouts = []
+
+    def has(self, hook):
+        return hook in self.hooks
+
+    def call(self, hook, timeout, *args):
+        self.timeouts.append(timeout)
+        return self.hooks[hook](*args)
+
+
+def _hooks(*engines, **kwargs):
+    return ScriptHooks([Script(f"script{i}", engine) for i, engine in enumerate(engines)], **kwargs)
+
+
+def _timed_out(*args):
+    raise ScriptError("timed out")
+
+
+def test_off_unless_enabled(tmp_path):
+    (tmp_path / "a.lua").write_text("function on_transcript(t) return false end")
+    assert ScriptHooks.from_config(Config(scripts_dir=str(tmp_path)), lambda name, source: FakeEngine()).scripts == []
+
+
+def test_loads_in_name_order(tmp_path):
+    for name in ("b_second", "a_first", "broken"):
+        (tmp_path / f"{name}.lua").write_text(f"-- {name}")
+    (tmp_path / "notes.txt").write_text("not a script")
+
+    def engine(name, source):
+        if name == "broken":
+            raise ScriptError(f"{name}: syntax error near 'end'")
+        return FakeEngine(on_transcript=lambda text, source: None)
+
+    hooks = ScriptHooks.from_config(Config(scripts_enabled=True, scripts_dir=str(tmp_path), script_timeout_ms=50),
+                                    engine)
+    assert [s.name for s in hooks.scripts] == ["a_first", "b_second"]
+    assert hooks.problems == ["broken.lua: broken: syntax error near 'end'"]
+    assert hooks.scripts[0].hooks == {"on_transcript"} and hooks.timeout == 0.05
+
+
+@pytest.mark.parametrize("hook, result", [
+    (lambda text, source: text.replace("hey boss, ", ""), "remind me at 5"),
+    (lambda text, source: False, None),
+    (lambda text, source: None, "hey boss, remind me at 5"),
+    (lambda text, source: 42, "hey boss, remind me at 5"),  # Wrong type: ignored
+])
+def test_on_transcript(hook, result):
+    assert _hooks(FakeEngine(on_transcript=hook)).on_transcript("hey boss, remind me at 5") == result
+
+
+def test_transcript_source():
+    seen = []
+    _hooks(FakeEngine(on_transcript=lambda text, source: seen.append(source))).on_transcript("hi", "chat")
+    assert seen == ["chat"]
+
+
+def test_before_notification():
+    def quieter(n):
+        if n["title"].startswith("Standup"):
+            return False
+        return {"message": n["message"].upper(), "priority": "low", "channel": "sms"}
+
+    hooks = _hooks(FakeEngine(before_notification=quieter))
+    assert hooks.before_notification(Notification("desktop", "Standup", "in 5 minutes")) is None
+    changed = hooks.before_notification(Notification("speech", "", "dentist at 3", "high", ("health",)))
+    assert changed == Notification("speech", "", "DENTIST AT 3", "low", ("health",))  # Channel can't change
+
+
+def test_unknown_priority_is_ignored():
+    hooks = _hooks(FakeEngine(before_notification=lambda n: {"priority": "urgent!!"}))
+    assert hooks.before_notification(Notification("desktop", "t", "m", "high")).priority == "high"
+
+
+def test_notification_hooks_skip_emergencies():
+    drop = _hooks(FakeEngine(before_notification=lambda n: False)).before_notification
+    add_notification_hook(drop)
+    try:
+        assert apply_notification_hooks(Notification("desktop", "t", "m")) is None
+        emergency = Notification("speech", "", "Sam needs help", "emergency")
+        assert apply_notification_hooks(emergency) == emergency
+    finally:
+        remove_notification_hook(drop)
+
+
+def test_on_event():
+    def rename(e):
+        if e["type"] == "spam":
+            return False
+        return {"summary": "Done: " + e["summary"], "type": "other"}
+
+    hooks = _hooks(FakeEngine(on_event=rename))
+    event = Event("task", datetime(2026, 1, 5, 9), "backup", {"duration": 300})
+    assert hooks.on_event(event) == Event("task", event.time, "Done: backup", {"duration": 300})
+    assert hooks.on_event(Event("spam", event.time, "buy now")) is None
+
+
+def test_event_hooks_before_listeners():
+    seen = []
+    hooks = _hooks(FakeEngine(on_event=lambda e: False if e["type"] == "spam" else {"summary": "changed"}))
+    add_event_hook(hooks.on_event)
+    add_event_listener(seen.append)
+    try:
+        record_event("spam", "buy now")
+        record_event("task", "backup")
+    finally:
+        remove_event_hook(hooks.on_event)
+        remove_event_listener(seen.append)
+    assert [(e.type, e.summary) for e in seen] == [("task", "changed")]
+
+
+def test_scripts_chain():
+    first = FakeEngine(on_transcript=lambda text, source: text + " please")
+    second = FakeEngine(on_transcript=lambda text, source: text.capitalize())
+    assert _hooks(first, second).on_transcript("lights off") == "Lights off please"
+    dropped = FakeEngine(on_transcript=lambda text, source: False)
+    never = FakeEngine(on_transcript=lambda text, source: pytest.fail("ran after a drop"))
+    assert _hooks(dropped, never).on_transcript("lights off") is None
+
+
+def test_failures_are_ignored_then_switch_the_script_off():
+    told = []
+    broken = FakeEngine(on_transcript=_timed_out)
+    fine = FakeEngine(on_transcript=lambda text, source: text + "!")
+    hooks = _hooks(broken, fine, timeout_ms=20, on_disabled=told.append)
+    for _ in range(MAX_FAILURES):
+        assert hooks.on_transcript("hello") == "hello!"
+    assert hooks.scripts[0].disabled and not hooks.scripts[1].disabled
+    assert told == [f"Script script0 switched off after {MAX_FAILURES} failures: timed out"]
+    hooks.on_transcript("hello")
+    assert len(broken.timeouts) == MAX_FAILURES and broken.timeouts[0] == 0.02
+
+
+def test_a_success_resets_the_count():
+    results = iter([ScriptError("oops"), ScriptError("oops"), "ok", ScriptError("oops"), ScriptError("oops")])
+
+    def flaky(text, source):
+        result = next(results)
+        if isinstance(result, Exception):
+            raise result
+        return result
+
+    hooks = _hooks(FakeEngine(on_transcript=flaky))
+    for _ in range(5):
+        hooks.on_transcript("hi")
+    assert not hooks.scripts[0].disabled
+
+
+def test_lua_deadlines_are_wall_clock():
+    pytest.importorskip("lupa")
+    from assistant.scripting import LuaEngine
+
+    started = time.monotonic()
+    with pytest.raises(ScriptError, match="timed out"):
+        LuaEngine("spin", "while true do end", load_timeout=0.05)  # Would hang startup
+    assert time.monotonic() - started < 1
+
+    engine = LuaEngine("hooks", """
+        function on_transcript(text) while true do pcall(function() while true do end end) end end
+        function on_event(e) local n = 0 for i = 1, 100000 do n = n + i end return n end
+    """)
+    with pytest.raises(ScriptError, match="timed out"):
+        engine.call("on_transcript", 0.05, "hi", "voice")
+
+    assert engine.call("on_event", 1.0, {}) == 5000050000  # The hook is cleared after a timeout
@@ -0,0 +1,151 @@
+"""
+Tests for installing signed releases of the binary (assistant/self_update.py).
+
+Covers:
+- Manifests trusted only with a release key's signature over their canonical JSON
+- The download checked against the signed checksum and run with --version before it's swapped in
+- The swap keeping the previous binary; rollback when the restarted daemon doesn't come up, and on request
+- Installs that aren't the binary told the pip command; the dashboard's release check route
+"""
+
+import asyncio
+import base64
+import hashlib
+import types
+
+import pytest
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+
+from assistant import self_update
+from assistant.self_update import SelfUpdater, UpdateError, sign_manifest, verify_manifest
+
+NEW_BINARY = b"#!/bin/sh\necho xswarm 99.0.0\n"
+
+
+def keypair():
+    private = Ed25519PrivateKey.generate()
+    raw = private.private_bytes(serialization.Encoding.Raw, serialization.PrivateFormat.Raw,
+                                serialization.NoEncryption())
+    public = private.public_key().public_bytes(serialization.Encoding.Raw, serialization.PublicFormat.Raw)
+    return raw, base64.b64encode(public).decode()
+
+
+PRIVATE, PUBLIC = keypair()
+
+
+def manifest(binary=NEW_BINARY, platform="linux-x86_64", key=PRIVATE):
+    return sign_manifest({"version": "99.0.0", "channel": "stable", "notes": "Faster wake word",
+                          "assets": {platform: {"sha256": hashlib.sha256(binary).hexdigest()}}}, key)
+
+
+class FakeClient:
+    def __init__(self, manifest, binary=NEW_BINARY):
+        self.manifest = manifest
+        self.binary = binary
+        self.calls = []
+
+    async def call(self, route, **kwargs):
+        self.calls.append((route.key, route.path, kwargs.get("params")))
+        if route.path == "/api/releases/latest":
+            return types.SimpleNamespace(json=lambda: self.manifest)
+        return types.SimpleNamespace(content=self.binary)
+
+    async def close(self):
+        pass
+
+
+class FakeSystem:
+    """subprocess.run for `--version` and systemctl: the daemon comes up unless told otherwise."""
+
+    def __init__(self, version="99.0.0", daemon="user", starts=True):
+        self.version, self.daemon, self.starts = version, daemon, starts
+        self.commands = []
+
+    def __call__(self, command, **kwargs):
+        self.commands.append(command)
+        if command[-1] == "--version":
+            return types.SimpleNamespace(returncode=0, stdout=f"xswarm {self.version}\n")
+        scope = "user" if "--user" in command else "system"
+        if "cat" in command:
+            return types.SimpleNamespace(returncode=0 if scope == self.daemon else 1)
+        return types.SimpleNamespace(returncode=0 if self.starts or "restart" in command else 3)
+
+
+@pytest.fixture
+def binary(tmp_path):
+    path = tmp_path / "xswarm"
+    path.write_bytes(b"old")
+    return path
+
+
+def updater(client, binary, system=None):
+    return SelfUpdater(client, binary=binary, keys=(PUBLIC,), platform_id="linux-x86_64",
+                       run=system or FakeSystem(), sleep=lambda seconds: None)
+
+
+def test_signatures():
+    release = verify_manifest(manifest(), (PUBLIC,))
+    assert (release.version, release.notes) == ("99.0.0", "Faster wake word") and release.newer_than("0.32.1")
+    tampered = {**manifest(), "version": "99.0.1"}
+    other_key = manifest(key=keypair()[0])
+    unsigned = {key: value for key, value in manifest().items() if key != "signature"}
+    for bad in (tampered, other_key, unsigned, {**manifest(), "signature": "not base64!"}):
+        with pytest.raises(UpdateError, match="isn't signed"):
+            verify_manifest(bad, (PUBLIC,))
+    with pytest.raises(UpdateError, match="isn't signed"):
+        verify_manifest(manifest())  # Only the release keys count
+
+
+def test_update_swaps_and_restarts(binary):
+    system = FakeSystem()
+    client = FakeClient(manifest())
+    said = asyncio.run(updater(client, binary, system).update(say=lambda line: None))
+    assert said == "Updated to xswarm 99.0.0 and restarted the xswarm service"
+    assert binary.read_bytes() == NEW_BINARY and (binary.parent / "xswarm.previous").read_bytes() == b"old"
+    assert not (binary.parent / "xswarm.new").exists()
+    assert [call[1] for call in client.calls] == ["/api/releases/latest", "/api/releases/99.0.0/linux-x86_64"]
+    assert ["systemctl", "--user", "restart", "xswarm"] in system.commands
+
+    no_daemon = asyncio.run(updater(FakeClient(manifest()), binary, FakeSystem(daemon=None)).update(lambda line: None))
+    assert no_daemon == "Updated to xswarm 99.0.0 - restart xswarm to use it"
+
+
+@pytest.mark.parametrize("client, system, message", [
+    (FakeClient(manifest(), binary=b"tampered"), None, "doesn't match its signed checksum"),
+    (FakeClient(manifest()), FakeSystem(version="98.0.0"), "doesn't run on this machine"),
+    (FakeClient(manifest(platform="macos-aarch64")), None, "has no build for linux-x86_64"),
+])
+def test_bad_downloads_leave_the_binary(binary, client, system, message):
+    with pytest.raises(UpdateError, match=message):
+        asyncio.run(updater(client, binary, system).update(say=lambda line: None))
+    assert binary.read_bytes() == b"old" and sorted(p.name for p in binary.parent.iterdir()) == ["xswarm"]
+
+
+def test_rollback(binary):
+    system = FakeSystem(daemon="system", starts=False)
+    with pytest.raises(UpdateError, match="didn't start - rolled back"):
+        asyncio.run(updater(FakeClient(manifest()), binary, system).update(say=lambda line: None))
+    assert binary.read_bytes() == b"old" and (binary.parent / "xswarm.previous").read_bytes() == NEW_BINARY
+    assert system.commands.count(["systemctl", "restart", "xswarm"]) == 2
+
+    updater(None, binary).rollback()  # Twice undoes it
+    assert binary.read_bytes() == NEW_BINARY and (binary.parent / "xswarm.previous").read_bytes() == b"old"
+    (binary.parent / "xswarm.previous").unlink()
+    with pytest.raises(UpdateError, match="no previous version"):
+        updater(None, binary).rollback()
+
+
+def test_up_to_date_and_pip_installs(monkeypatch):
+    monkeypatch.setattr(self_update, "__version__", "99.0.0")
+    assert asyncio.run(updater(FakeClient(manifest()), None).update()) == "xswarm 99.0.0 is up to date"
+    monkeypatch.setattr(self_update, "__version__", "0.32.1")
+    client = FakeClient(manifest())
+    said = asyncio.run(updater(client, None).update())
+    assert said.endswith(f"update this install with: {self_update.PIP_COMMAND}") and len(client.calls) == 1
+
+    beta = SelfUpdater.from_config(types.SimpleNamespace(update_channel="beta"), client)
+    assert beta.binary is None  # Running from Python, not the binary
+    with pytest.raises(UpdateError):
+        asyncio.run(beta.check())  # Against the real release keys
+    assert client.calls[-1] == ("GET /api/releases/latest", "/api/releases/latest", {"channel": "beta"})
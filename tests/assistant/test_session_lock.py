@@ -0,0 +1,77 @@
+"""
+Tests for the dashboard lock (assistant/session_lock.py).
+
+Covers:
+- Unlocking with the PIN (typed or spoken) or the account password
+- Refusing to lock with nothing to unlock it with
+- Locking after the idle timeout, and activity postponing it
+- Backing off after too many wrong attempts
+"""
+
+from assistant.config import Config
+from assistant.confirmation import hash_pin
+from assistant.session_lock import LOCKOUT, MAX_ATTEMPTS, SessionLock
+
+
+class Clock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        return self.now
+
+
+def make_lock(clock=None, **config):
+    config.setdefault("confirmation_pin_hash", hash_pin("4211"))
+    return SessionLock(Config(**config), clock=clock or Clock())
+
+
+def test_pin():
+    lock = make_lock()
+    assert lock.lock() and lock.locked
+    result = lock.unlock("1234")
+    assert not result.ok and result.message == "That pin didn't match"
+    assert lock.unlock("four two one one").ok
+    assert not lock.locked
+
+
+def test_needs_a_pin():
+    lock = make_lock(confirmation_pin_hash=None)
+    assert "PIN" in lock.unavailable()
+    assert not lock.lock() and not lock.locked
+
+
+def test_account_password():
+    checked = []
+    lock = SessionLock(Config(lock_unlock_with="account"),
+                       check_password=lambda password: checked.append(password) or password == "hunter2")
+    assert lock.prompt == "Account password" and lock.unavailable() is None
+    lock.lock()
+    assert not lock.unlock("").ok and checked == []  # Nothing typed: not checked at all
+    assert not lock.unlock("hunter3").ok
+    assert lock.unlock("hunter2").ok
+
+
+def test_idle():
+    clock = Clock()
+    lock = make_lock(clock, lock_idle_minutes=5)
+    clock.now += 4 * 60
+    assert not lock.idle_expired()
+    lock.touch()
+    clock.now += 4 * 60
+    assert not lock.idle_expired()
+    clock.now += 60
+    assert lock.idle_expired()
+    assert not make_lock(clock).idle_expired()  # lock_idle_minutes=0: never
+
+
+def test_lockout():
+    clock = Clock()
+    lock = make_lock(clock)
+    lock.lock()
+    for _ in range(MAX_ATTEMPTS):
+        result = lock.unlock("0000")
+    assert result.message == f"Too many tries - wait {int(LOCKOUT)}s"
+    assert not lock.unlock("4211").ok  # Even the right PIN waits
+    clock.now += LOCKOUT
+    assert lock.unlock("4211").ok
@@ -0,0 +1,164 @@
+"""
+Tests for sharing a conversation's notes (assistant/share_notes.py).
+
+Covers:
+- "send Alice the notes" and "share the notes from X with Alice" parsed; which conversation they mean
+- Contacts from config.contacts and emergency contacts; two matches asked about
+- The conversation picked: this one, the last one, or the latest mentioning the topic
+- Notes read back, revised on request and only emailed after "send it"; plain notes without an AI
+- Templates: the built-in wording, config overrides, and broken ones left out
+"""
+
+import asyncio
+import types
+
+import pytest
+
+from assistant.memory import PersistentChatHistory
+from assistant.share_notes import (
+    ShareNotesWorkflow, ShareRequest, ShareTemplates, load_contacts, match_contacts, parse_share_request,
+    select_conversation,
+)
+
+
+class FakeAI:
+    def __init__(self, available=True):
+        self.available = available
+        self.calls = []
+
+    def is_available(self):
+        return self.available
+
+    async def chat(self, messages, max_tokens=1024):
+        self.calls.append(messages)
+        return f"notes {len(self.calls)}"
+
+
+class FakeMailer:
+    def __init__(self):
+        self.sent = []
+
+    async def send_email(self, to_email, subject, content, persona):
+        self.sent.append((to_email, subject, content))
+        return {"success": True}
+
+
+class FakePersonas:
+    def get_current_persona(self):
+        return types.SimpleNamespace(name="JARVIS")
+
+
+@pytest.fixture
+def config():
+    return types.SimpleNamespace(
+        user_name="Sam",
+        contacts={"Alice Chen": {"email": "alice@example.com"}, "Alice Park": {"email": "apark@example.com"},
+                  "Bob Stone": {"email": "bob@example.com"}},
+        emergency_contacts=[{"name": "Mom", "phone": "+15550001111"}],
+        share_templates={},
+    )
+
+
+@pytest.fixture
+def history(tmp_path):
+    history = PersistentChatHistory(storage_dir=tmp_path, persona="JARVIS")
+    history.add_message("user", "Let's go over the budget for the offsite")
+    history.add_message("assistant", "The budget is 4000 dollars, with catering the biggest item")
+    history.current_session.session_id = "session_earlier"  # Ids are by the second
+    history.end_session()
+    history.add_message("user", "Remind me what we decided about the launch date")
+    history.add_message("assistant", "ok")
+    history.add_message("assistant", "You decided to move the launch to March 3")
+    return history
+
+
+def workflow(config, history, ai=None):
+    return ShareNotesWorkflow(ai, FakePersonas(), history, FakeMailer(), config)
+
+
+@pytest.mark.parametrize("text, recipient, topic, which", [
+    ("send Alice the notes", "Alice", "", "current"),
+    ("Email Bob a summary of our last conversation.", "Bob", "our last conversation", "last"),
+    ("share the notes from the budget call with Alice Chen", "Alice Chen", "the budget call", "topic"),
+    ("could you send the recap of this conversation to Bob please", "Bob", "this conversation", "current"),
+    ("send Alice Chen the notes", "Alice Chen", "", "current"),
+])
+def test_parse_share_request(text, recipient, topic, which):
+    request = parse_share_request(text)
+    assert (request.recipient, request.topic, request.which) == (recipient, topic, which)
+
+
+@pytest.mark.parametrize("text", ["send the email", "send Alice a text", "what are the notes", "share my screen"])
+def test_not_a_share_request(text):
+    assert parse_share_request(text) is None
+
+
+def test_contacts(config):
+    contacts = load_contacts(config)
+    assert [c.name for c in contacts] == ["Alice Chen", "Alice Park", "Bob Stone", "Mom"]
+    assert contacts[-1].phone == "+15550001111" and not contacts[-1].email
+    assert [c.name for c in match_contacts("alice", contacts)] == ["Alice Chen", "Alice Park"]
+    assert [c.name for c in match_contacts("Alice Chen", contacts)] == ["Alice Chen"]
+    assert match_contacts("Carol", contacts) == []
+
+
+def test_select_conversation(history):
+    current = select_conversation(history, ShareRequest("Bob"))
+    assert "launch" in current.messages[0].content
+    assert "budget" in select_conversation(history, ShareRequest("Bob", "our last conversation")).messages[0].content
+    assert "budget" in select_conversation(history, ShareRequest("Bob", "the offsite budget")).messages[0].content
+    assert select_conversation(history, ShareRequest("Bob", "the hiring meeting")) is None
+
+
+def test_ask_which_contact_then_send(config, history):
+    flow = workflow(config, history, FakeAI())
+
+    async def run():
+        assert await flow.start(parse_share_request("send Alice the notes")) == \
+            "Which Alice: Alice Chen or Alice Park?"
+        readback = await flow.handle("Chen")
+        assert "notes 1" in readback and "alice@example.com" in readback
+        assert flow.mailer.sent == []
+        assert "notes 2" in await flow.handle("leave out the numbers")
+        assert "leave out the numbers" in flow.ai.calls[-1][-1]["content"]
+        assert flow.mailer.sent == []
+        return await flow.handle("send it")
+
+    assert asyncio.run(run()) == "Sent the notes to Alice Chen by email."
+    (to, subject, body), = flow.mailer.sent
+    assert to == "alice@example.com" and subject.startswith("Notes (")
+    assert body.startswith("Hi Alice,") and "notes 2" in body and body.endswith("Sam")
+    assert "launch" in flow.ai.calls[0][-1]["content"]
+
+
+def test_cancel_and_unknown_contacts(config, history):
+    flow = workflow(config, history, FakeAI())
+
+    async def run():
+        await flow.start(parse_share_request("send Bob the notes"))
+        assert await flow.handle("cancel") == "Okay, I won't send them."
+        assert not flow.is_active
+        assert "don't have a contact" in await flow.start(parse_share_request("send Carol the notes"))
+        assert "email address for Mom" in await flow.start(parse_share_request("send Mom the notes"))
+
+    asyncio.run(run())
+    assert flow.mailer.sent == []
+
+
+def test_plain_notes_without_ai(config, history):
+    flow = workflow(config, history, FakeAI(available=False))
+    readback = asyncio.run(flow.start(parse_share_request("send Bob the notes")))
+    assert "- Sam: Remind me what we decided about the launch date" in readback
+    assert "- JARVIS: You decided to move the launch to March 3" in readback
+    assert "JARVIS: ok" not in readback
+    assert "without the AI" in asyncio.run(flow.handle("make it shorter"))
+
+
+def test_templates():
+    context = {"summary": "- Launch moved", "recipient": "Bob", "contact": "Bob Stone", "user": "",
+               "persona": "JARVIS", "topic": "the launch", "date": "October 16"}
+    templates = ShareTemplates({"subject": "{{ persona }} notes: {{ topic }}", "body": "{{ nope }}",
+                                "footer": "x"})
+    assert templates.render("subject", context) == "JARVIS notes: the launch"
+    assert templates.render("body", context) == "Hi Bob,\n\nHere are the notes from the launch.\n\n- Launch moved"
+    assert ShareTemplates().render("subject", context) == "Notes: the launch (October 16)"
@@ -0,0 +1,279 @@
+"""
+Tests for WebAssembly skill plugins (assistant/skill_plugins.py).
+
+Covers:
+- Manifests: intents, tools and settings read from skill.yaml; bad ones (wrong shapes, intent
+  patterns that are too long or repeat a repeat) refused with the reason
+- Nothing loads until enabled; only permissions the manifest asks for can be granted
+- state.json keeps the exact hosts and folders approved; a manifest asking for more isn't loaded
+- Settings: defaults, config.plugin_settings overrides, type checks
+- Intents answer matching utterances, with named groups as args; a failing plugin says so
+- Tools go into the tool registry with a schema from the manifest; taken names are refused
+- The sandbox: http_get and read_file only reach granted hosts and folders
+- The bundled coin_flip sample is a valid plugin
+(the wasmtime runtime itself is replaced by a fake)
+"""
+
+import asyncio
+from pathlib import Path
+
+import pytest
+import yaml
+
+from assistant.capabilities import capabilities
+from assistant.skill_plugins import (
+    Grants, Manifest, PluginError, SkillHost, SkillPlugins, WasmRuntime,
+)
+from assistant.tools import ToolRegistry
+
+SAMPLE = Path(__file__).parents[2] / "packages" / "assistant" / "examples" / "plugins" / "coin_flip"
+
+
+class FakeRuntime:
+    """Stands in for wasmtime: records requests and answers with `reply`."""
+
+    def __init__(self, reply=None):
+        self.requests = []
+        self.reply = reply or {"say": "Heads."}
+
+    def call(self, request):
+        self.requests.append(request)
+        return self.reply
+
+
+def _write_plugin(directory: Path, **overrides) -> Path:
+    manifest = {
+        "name": "weather_uk",
+        "version": "1.2.0",
+        "description": "Met Office forecasts",
+        "module": "weather.wasm",
+        "intents": [{"name": "forecast", "patterns": [r"what's the weather in (?P<town>\w+)"],
+                     "examples": ["what's the weather in Leeds"]}],
+        "tools": [{"name": "uk_forecast", "description": "Forecast for a UK town",
+                   "parameters": {"town": {"type": "string"}, "days": {"type": "integer", "default": 1}}}],
+        "config": {"units": {"type": "string", "default": "metric"}, "days_ahead": "integer"},
+        "permissions": {"network": ["api.metoffice.gov.uk"]},
+        "category": "Information",
+    }
+    manifest.update(overrides)
+    folder = directory / str(manifest.get("name") or "unnamed")
+    folder.mkdir(parents=True)
+    (folder / "weather.wasm").write_bytes(b"\0asm")
+    (folder / "skill.yaml").write_text(yaml.safe_dump(manifest))
+    return folder
+
+
+def _plugins(tmp_path, runtime=None, settings=None):
+    runtime = runtime or FakeRuntime()
+    return SkillPlugins(tmp_path, settings, runtime_factory=lambda manifest, host: runtime), runtime
+
+
+def test_manifest(tmp_path):
+    manifest = Manifest.load(_write_plugin(tmp_path))
+    assert (manifest.name, manifest.version, manifest.category) == ("weather_uk", "1.2.0", "Information")
+    assert manifest.intents[0].match("What's the weather in Leeds?") == {"town": "Leeds"}
+    assert manifest.intents[0].match("tell me the weather in Leeds") is None
+    assert manifest.tools[0].parameters["days"].default == 1
+    assert manifest.config["days_ahead"].type == "integer"
+    assert manifest.permissions == {"network": ["api.metoffice.gov.uk"]}
+
+
+@pytest.mark.parametrize("overrides, reason", [
+    ({"name": "Weather-UK"}, "name must be"),
+    ({"module": "missing.wasm"}, "module must name"),
+    ({"module": "../weather.wasm"}, "module must"),
+    ({"permissions": {"camera": []}}, "unknown permission 'camera'"),
+    ({"category": "Games"}, "category must be"),
+    ({"intents": [{"name": "forecast", "patterns": ["(unclosed"]}]}, "bad intent"),
+    ({"intents": [{"name": "forecast"}]}, "needs patterns"),
+    ({"intents": [{"name": "forecast", "patterns": "weather"}]}, "patterns must be a list"),
+    ({"intents": [{"name": "forecast", "patterns": [42]}]}, "non-empty strings"),
+    ({"intents": [{"name": "forecast", "patterns": ["weather " * 30]}]}, "longer than 200"),
+    ({"intents": [{"name": "forecast", "patterns": [r"(\w+\s?)+ weather"]}]}, "repeats a repeat"),
+    ({"intents": [{"name": "forecast", "patterns": [r"(?:(?:a|b)*c)+"]}]}, "repeats a repeat"),
+    ({"intents": ["forecast"]}, "each intent must be a mapping"),
+    ({"intents": {"name": "forecast"}}, "intents must be a list"),
+    ({"tools": ["forecast"]}, "each tool must be a mapping"),
+    ({"permissions": ["network"]}, "permissions must be a mapping"),
+    ({"permissions": {"network": "api.metoffice.gov.uk"}}, "permissions.network must be a list"),
+    ({"tools": [{"name": "Forecast"}]}, "tool names"),
+    ({"config": {"units": {"type": "list"}}}, "unknown type 'list'"),
+])
+def test_bad_manifest(tmp_path, overrides, reason):
+    folder = _write_plugin(tmp_path, **overrides)
+    if overrides.get("module") == "../weather.wasm":
+        (tmp_path / "weather.wasm").write_bytes(b"\0asm")
+    with pytest.raises(PluginError, match=reason.replace("(", r"\(")):
+        Manifest.load(folder)
+
+
+def test_manifest_must_be_a_mapping(tmp_path):
+    folder = _write_plugin(tmp_path)
+    (folder / "skill.yaml").write_text("- name: weather_uk\n")
+    with pytest.raises(PluginError, match="must be a mapping of fields"):
+        Manifest.load(folder)
+
+
+def test_discover_reports_problems(tmp_path):
+    _write_plugin(tmp_path)
+    _write_plugin(tmp_path, name="broken", category="Games")
+    (tmp_path / "not_a_plugin").mkdir()
+    manifests, problems = SkillPlugins(tmp_path).discover()
+    assert [m.name for m in manifests] == ["weather_uk"]
+    assert len(problems) == 1 and problems[0].startswith("broken:")
+
+
+def test_nothing_loads_until_enabled(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, _ = _plugins(tmp_path)
+    assert plugins.load() == [] and plugins.answer("what's the weather in Leeds") is None
+
+
+def test_enable_and_disable(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, _ = _plugins(tmp_path)
+    with pytest.raises(PluginError, match="doesn't ask for files"):
+        plugins.enable("weather_uk", ["files"])
+    with pytest.raises(PluginError, match="No plugin called 'nope'"):
+        plugins.enable("nope", [])
+    plugins.enable("weather_uk", ["network"])
+    reloaded = SkillPlugins(tmp_path)  # Kept in state.json
+    assert reloaded.state.enabled("weather_uk")
+    assert reloaded.state.granted("weather_uk") == {"network": ["api.metoffice.gov.uk"]}
+    reloaded.disable("weather_uk")
+    assert not SkillPlugins(tmp_path).state.enabled("weather_uk")
+
+
+def test_manifest_asking_for_more_is_refused(tmp_path):
+    folder = _write_plugin(tmp_path)
+    plugins, _ = _plugins(tmp_path)
+    plugins.enable("weather_uk", ["network"])
+    manifest = yaml.safe_load((folder / "skill.yaml").read_text())
+    manifest["permissions"]["network"].append("evil.example.com")
+    (folder / "skill.yaml").write_text(yaml.safe_dump(manifest))
+
+    widened, _ = _plugins(tmp_path)
+    assert widened.load() == []
+    assert widened.problems == [
+        "weather_uk now asks for evil.example.com, which wasn't approved: enable it again to grant that"]
+    widened.enable("weather_uk", ["network"])
+    assert [p.host.grants.hosts for p in _plugins(tmp_path)[0].load()] == [["api.metoffice.gov.uk", "evil.example.com"]]
+
+    (tmp_path / "state.json").write_text('{"weather_uk": {"enabled": true, "grants": ["network"]}}')  # Older format
+    assert _plugins(tmp_path)[0].load() == []
+
+
+def test_settings(tmp_path):
+    manifest = Manifest.load(_write_plugin(tmp_path))
+    assert manifest.settings(None) == {"units": "metric", "days_ahead": None}
+    assert manifest.settings({"units": "imperial", "days_ahead": 3}) == {"units": "imperial", "days_ahead": 3}
+    with pytest.raises(PluginError, match="'days_ahead' should be integer"):
+        manifest.settings({"days_ahead": "3"})
+    with pytest.raises(PluginError, match="unknown setting 'colour'"):
+        manifest.settings({"colour": "red"})
+
+
+def test_bad_settings_fail_when_enabling(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, _ = _plugins(tmp_path, settings={"weather_uk": {"days_ahead": True}})
+    with pytest.raises(PluginError, match="should be integer"):
+        plugins.enable("weather_uk", [])
+
+
+def test_intent(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, runtime = _plugins(tmp_path, FakeRuntime({"say": "Rain in Leeds."}),
+                                settings={"weather_uk": {"units": "imperial"}})
+    plugins.enable("weather_uk", [])
+    plugins.load()
+    assert plugins.match("set a timer for 5 minutes") is None
+    assert plugins.answer("what's the weather in Leeds?") == "Rain in Leeds."
+    request = runtime.requests[0]
+    assert (request["kind"], request["name"], request["args"]) == ("intent", "forecast", {"town": "Leeds"})
+    assert request["config"] == {"units": "imperial", "days_ahead": None}
+    assert "Weather uk" in [c.name for c in capabilities()]
+
+
+def test_failing_plugin_says_so(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, _ = _plugins(tmp_path, FakeRuntime({"error": "Met Office is down"}))
+    plugins.enable("weather_uk", [])
+    plugins.load()
+    assert plugins.answer("what's the weather in Leeds") == "The weather uk plugin couldn't do that."
+
+
+def test_tools(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, runtime = _plugins(tmp_path, FakeRuntime({"say": "Sunny."}))
+    plugins.enable("weather_uk", [])
+    registry = ToolRegistry()
+    plugins.load(registry)
+    schema = next(s for s in registry.get_anthropic_tool_schemas() if s["name"] == "uk_forecast")
+    assert schema["description"] == "Forecast for a UK town"
+    assert schema["input_schema"]["properties"]["days"]["type"] == "integer"
+    assert schema["input_schema"]["required"] == ["town"]
+    result = asyncio.run(registry.execute_tool("uk_forecast", {"town": "York"}))
+    assert result["success"] and result["result"] == "Sunny."
+    assert runtime.requests[-1]["args"] == {"town": "York"}
+    assert not asyncio.run(registry.execute_tool("uk_forecast", {"town": "York", "days": "two"}))["success"]
+
+
+def test_taken_tool_name(tmp_path):
+    _write_plugin(tmp_path)
+    plugins, _ = _plugins(tmp_path)
+    plugins.enable("weather_uk", [])
+    registry = ToolRegistry()
+    registry.register("uk_forecast", "Built in")(lambda town: town)
+    assert plugins.load(registry) == []
+    assert plugins.problems == ["weather_uk: tool 'uk_forecast' is already taken"]
+    assert registry.list_tools()["uk_forecast"] == "Built in"
+
+
+def test_network_grant(tmp_path):
+    manifest = Manifest.load(_write_plugin(tmp_path))
+    fetched = []
+    fetch = lambda url: (fetched.append(url), (200, "ok"))[1]  # noqa: E731
+    denied = SkillHost("weather_uk", Grants.for_plugin(manifest, {}), fetch)
+    assert denied.http_get("https://api.metoffice.gov.uk/leeds") == {
+        "error": "network access to api.metoffice.gov.uk not granted"}
+    granted = SkillHost("weather_uk", Grants.for_plugin(manifest, {"network": ["api.metoffice.gov.uk"]}), fetch)
+    assert granted.http_get("https://api.metoffice.gov.uk/leeds") == {"status": 200, "body": "ok"}
+    assert "error" in granted.http_get("https://evil.example.com/")
+    assert "error" in granted.http_get("file:///etc/passwd")
+    assert fetched == ["https://api.metoffice.gov.uk/leeds"]
+
+
+def test_files_grant(tmp_path):
+    notes = tmp_path / "notes"
+    notes.mkdir()
+    (notes / "today.md").write_text("buy milk")
+    (tmp_path / "secret.txt").write_text("hunter2")
+    manifest = Manifest.load(_write_plugin(tmp_path, permissions={"files": [str(notes)]}))
+    assert "not granted" in SkillHost("weather_uk", Grants.for_plugin(manifest, {})).read_file(
+        str(notes / "today.md"))["error"]
+    host = SkillHost("weather_uk", Grants.for_plugin(manifest, {"files": [str(notes.resolve())]}))
+    assert host.read_file(str(notes / "today.md")) == {"content": "buy milk"}
+    assert "not granted" in host.read_file(str(notes / ".." / "secret.txt"))["error"]
+
+
+def test_missing_wasmtime_is_explained(tmp_path, monkeypatch):
+    import builtins
+    real_import = builtins.__import__
+
+    def no_wasmtime(name, *args, **kwargs):
+        if name == "wasmtime":
+            raise ImportError(name)
+        return real_import(name, *args, **kwargs)
+
+    monkeypatch.setattr(builtins, "__import__", no_wasmtime)
+    manifest = Manifest.load(_write_plugin(tmp_path))
+    runtime = WasmRuntime(manifest.module, SkillHost(manifest.name, Grants()))
+    with pytest.raises(PluginError, match=r"pip install voice-assistant\[plugins\]"):
+        runtime.call({"kind": "intent"})
+
+
+def test_sample_plugin(tmp_path):
+    manifest = Manifest.load(SAMPLE)
+    assert manifest.name == "coin_flip" and manifest.module.suffix == ".wat"
+    assert manifest.intents[0].match("heads or tails?") == {}
+    assert manifest.permissions == {}
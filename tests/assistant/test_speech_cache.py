@@ -0,0 +1,111 @@
+"""
+Tests for the offline speech cache (assistant/speech_cache.py).
+
+Covers:
+- Recordings round-trip through WAV and survive a restart
+- Common phrases are kept at once, other short texts once said often enough
+- Recording starts at the first sound and ends on silence; no speech or a long reply is dropped
+- A voice settings change gives a new fingerprint and retain_voice drops the old recordings
+- Least recently used recordings are evicted past the size limit
+"""
+
+import numpy as np
+import pytest
+
+from assistant.personas.config import PersonaConfig
+from assistant.speech_cache import SpeechCache, SpeechCapture, voice_fingerprint
+
+RATE = 24000
+FRAME = 1920  # 80ms, one Moshi frame
+
+
+def tone(seconds, level=0.5):
+    t = np.arange(int(seconds * RATE), dtype=np.float32) / RATE
+    return (level * np.sin(2 * np.pi * 220 * t)).astype(np.float32)
+
+
+def frames(audio):
+    return [audio[i:i + FRAME] for i in range(0, len(audio), FRAME)]
+
+
+class Clock:
+    def __init__(self):
+        self.now = 1000.0
+
+    def __call__(self):
+        self.now += 1
+        return self.now
+
+
+def test_round_trip_and_restart(tmp_path):
+    cache = SpeechCache(tmp_path)
+    assert cache.get("Jarvis", "v1", "Got it.") is None
+    assert cache.put("Jarvis", "v1", "Got it.", tone(0.5), RATE)
+
+    audio, rate = SpeechCache(tmp_path).get("Jarvis", "v1", "  Got   it. ")
+    assert rate == RATE and len(audio) == int(0.5 * RATE)
+    assert np.max(np.abs(audio - tone(0.5))) < 1e-3
+    assert SpeechCache(tmp_path).get("Jarvis", "v2", "Got it.") is None
+    assert SpeechCache(tmp_path).get("HAL", "v1", "Got it.") is None
+
+
+def test_what_gets_recorded(tmp_path):
+    cache = SpeechCache(tmp_path, record_after=2)
+    assert cache.wants("Jarvis", "v1", "Got it.")
+    assert cache.wants("Jarvis", "v1", "It's half past two.")
+    assert not cache.wants("Jarvis", "v1", "Your dentist is at four")
+    assert cache.wants("Jarvis", "v1", "Your dentist is at four")
+    assert not cache.wants("Jarvis", "v1", "word " * 50)
+
+    cache.put("Jarvis", "v1", "Got it.", tone(0.3), RATE)
+    assert not cache.wants("Jarvis", "v1", "Got it.")
+    assert "Got it." not in cache.missing("Jarvis", "v1")
+    assert "Got it." in cache.missing("Jarvis", "v2")
+
+
+def test_capture_bounds():
+    capture = SpeechCapture("Jarvis", "v1", "Done.")
+    for chunk in frames(np.zeros(RATE, dtype=np.float32)) + frames(tone(0.6)) + frames(np.zeros(RATE, dtype=np.float32)):
+        capture.feed(chunk)
+    assert capture.done and not capture.failed
+    assert abs(len(capture.audio()) - 0.6 * RATE) <= FRAME  # Leading and trailing silence trimmed
+
+    never_spoke = SpeechCapture("Jarvis", "v1", "Done.")
+    for chunk in frames(np.zeros(7 * RATE, dtype=np.float32)):
+        never_spoke.feed(chunk)
+    assert never_spoke.failed
+
+    rambled = SpeechCapture("Jarvis", "v1", "Done.")
+    for chunk in frames(tone(16)):
+        rambled.feed(chunk)
+    assert rambled.failed
+
+
+def test_voice_change_drops_recordings(tmp_path):
+    persona = PersonaConfig(name="Jarvis")
+    before = voice_fingerprint(persona)
+    persona.voice.speed = 1.3
+    after = voice_fingerprint(persona)
+    assert before != after
+
+    cache = SpeechCache(tmp_path)
+    cache.put("Jarvis", before, "Okay.", tone(0.2), RATE)
+    cache.put("HAL", before, "Okay.", tone(0.2), RATE)
+    assert cache.retain_voice("Jarvis", after) == 1
+    assert cache.get("Jarvis", before, "Okay.") is None
+    assert cache.get("HAL", before, "Okay.") is not None
+    assert len(list(tmp_path.glob("*.wav"))) == 1
+
+
+def test_size_limit_evicts_least_recently_used(tmp_path):
+    one = int(1.0 * RATE) * 2 + 44
+    cache = SpeechCache(tmp_path, max_bytes=2 * one, clock=Clock())
+    cache.put("Jarvis", "v1", "Okay.", tone(1.0), RATE)
+    cache.put("Jarvis", "v1", "Done.", tone(1.0), RATE)
+    cache.get("Jarvis", "v1", "Okay.")  # Done. is now the oldest
+    cache.put("Jarvis", "v1", "Sure.", tone(1.0), RATE)
+
+    assert cache.get("Jarvis", "v1", "Done.") is None
+    assert cache.get("Jarvis", "v1", "Okay.") is not None
+    assert cache.stats()["entries"] == 2 and cache.size() <= cache.max_bytes
+    assert not cache.put("Jarvis", "v1", "Long.", tone(3.0), RATE)
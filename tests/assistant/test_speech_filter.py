@@ -0,0 +1,76 @@
+"""
+Tests for the filters between a reply and the voice (assistant/speech_filter.py).
+
+Covers:
+- Profanity masked as "bleep", removed, or left alone
+- Phone numbers, emails and custom patterns said as "the ... on screen"; existing brackets kept
+- Long replies split at sentences (or words), with "Shall I continue?" and its answers
+- Settings layered: config, then the persona, then the channel; calls cut instead of asking
+"""
+
+import types
+
+import pytest
+
+from assistant.personas.config import PersonaConfig, SpeechFilterSettings
+from assistant.redaction import Redactor
+from assistant.speech_filter import (CONTINUE_PROMPT, CONTINUE_WINDOW, FilterSettings, SpeechFilter, mask_profanity,
+                                     parse_continue, split_for_speech, suppress_pii)
+
+LONG = "The build failed twice. The first failure was a timeout. The second was a missing key. Shall I retry it?"
+
+
+@pytest.mark.parametrize("mode, said", [
+    ("mask", "Well, bleep. That is bleep, honestly."),
+    ("remove", "Well. That is, honestly."),
+    ("off", "Well, damn. That is bullshit, honestly."),
+])
+def test_profanity(mode, said):
+    assert mask_profanity("Well, damn. That is bullshit, honestly.", mode) == said
+    assert mask_profanity("Send it to Scunthorpe", mode) == "Send it to Scunthorpe"
+
+
+def test_pii():
+    said = suppress_pii("Call +1 555 123 4567 or mail dana@example.com about [meeting]")
+    assert said == "Call the phone number on screen or mail the email address on screen about [meeting]"
+    custom = Redactor(patterns={"account_id": r"ACME-\d+"})
+    assert suppress_pii("Ticket ACME-4411 is closed", custom, "the {label}") == "Ticket the account id is closed"
+
+
+def test_split():
+    assert split_for_speech(LONG, 0) == [LONG]
+    assert split_for_speech(LONG, 10) == ["The build failed twice. The first failure was a timeout.",
+                                         "The second was a missing key. Shall I retry it?"]
+    assert split_for_speech("one two three four five", 2) == ["one two", "three four", "five"]
+
+
+def test_continue():
+    clock = types.SimpleNamespace(now=0.0)
+    speech = SpeechFilter(FilterSettings(max_words=6), clock=lambda: clock.now)
+    assert speech.apply(LONG) == f"The build failed twice. {CONTINUE_PROMPT}"
+    assert speech.apply("Okay.") == "Okay." and speech.has_more()  # Short replies and fillers keep the rest
+    assert speech.answer("what time is it") is None and not speech.has_more()
+
+    speech.apply(LONG)
+    assert speech.answer("yes, go on") is True
+    assert speech.next_part() == f"The first failure was a timeout. {CONTINUE_PROMPT}"
+    assert speech.answer("that's enough, thanks") is False and speech.next_part() == ""
+
+    speech.apply(LONG)
+    clock.now += CONTINUE_WINDOW + 1
+    assert speech.answer("yes") is None
+    assert [parse_continue(t) for t in ("Sure.", "keep going", "nope", "go on and delete it")] == [True, True, False,
+                                                                                                   None]
+
+
+def test_layers():
+    config = types.SimpleNamespace(speech_profanity="remove", speech_pii=True, speech_max_words=6,
+                                   speech_filter_channels={"call": {"max_words": 4}}, redaction_patterns={})
+    speech = SpeechFilter.from_config(config)
+    assert speech.settings() == FilterSettings("remove", True, 6)
+    persona = PersonaConfig(name="Marvin", speech_filter=SpeechFilterSettings(profanity="off", max_words=0))
+    assert speech.settings(persona) == FilterSettings("off", True, 0)
+    assert speech.settings(persona, "call") == FilterSettings("off", True, 4)
+    assert speech.apply(LONG, persona, "call") == "The build failed twice." and not speech.has_more()
+    assert SpeechFilter().settings(channel="call").max_words == 0
+    assert SpeechFilter(FilterSettings(profanity="bleep-all")).settings().profanity == "mask"
@@ -0,0 +1,74 @@
+"""
+Tests for the startup profile (assistant/startup_profile.py).
+
+Covers:
+- Stages timed from the start, with config budgets applied to stages already running
+- A stage over its budget reported once, an error noted; only a stage's first run counts
+- The breakdown: stages in start order, running ones, and the total once startup is done
+"""
+
+import types
+
+import pytest
+
+from assistant.startup_profile import StartupProfile
+
+
+@pytest.fixture
+def clock():
+    clock = types.SimpleNamespace(now=100.0)
+    clock.tick = lambda seconds: setattr(clock, "now", clock.now + seconds)
+    return clock
+
+
+def test_stages_and_budgets(clock):
+    profile = StartupProfile(clock=lambda: clock.now)
+    clock.tick(0.25)
+    with profile.stage("config"):
+        clock.tick(0.75)
+    profile.start("models")
+    profile.set_budgets({"models": 90, "config": 1})
+    clock.tick(80)
+    profile.finish("models")
+
+    config, models = profile.stages["config"], profile.stages["models"]
+    assert (config.started, config.seconds, config.budget) == (0.25, 0.75, 1)
+    assert (models.started, models.seconds, models.budget) == (1.0, 80, 90)
+    assert profile.over_budget() == []
+
+
+def test_over_budget_and_errors(clock):
+    warned = []
+    profile = StartupProfile({"audio": 1}, clock=lambda: clock.now)
+    profile.on_over = warned.append
+    with pytest.raises(OSError):
+        with profile.stage("audio"):
+            clock.tick(2)
+            raise OSError("No output device")
+    assert [stage.name for stage in warned] == ["audio"]
+    assert profile.stages["audio"].error == "OSError: No output device"
+
+    with profile.stage("audio"):  # Opened again later: the first run is the one that counts
+        clock.tick(5)
+    assert profile.stages["audio"].seconds == 2 and len(warned) == 1
+    assert profile.finish("never_started") is None
+
+
+def test_breakdown(clock):
+    profile = StartupProfile(clock=lambda: clock.now)
+    profile.start("dashboard")
+    clock.tick(0.5)
+    with profile.stage("config"):
+        clock.tick(1)
+    profile.start("microphone")
+    clock.tick(0.5)
+
+    lines = profile.lines()
+    assert lines[0] == "Startup: 2.00s so far"
+    assert [line.split()[0] for line in lines[2:]] == ["dashboard", "config", "microphone"]
+    assert lines[3].endswith("⚠ over budget") and "1.00s" in lines[3]
+    assert "running" in lines[2] and "running" in lines[4]
+
+    assert profile.done()[0] == "Startup: 2.00s to ready"
+    clock.tick(3)
+    assert profile.done()[0] == "Startup: 2.00s to ready"
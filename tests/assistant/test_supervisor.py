@@ -0,0 +1,166 @@
+"""
+Tests for the background task supervisor.
+
+Covers:
+- Crashed tasks restart with exponential backoff
+- Subsystems are marked failed after too many crashes in a row
+- Clean exits and cancellation are not restarted
+- Subsystem states reported with report_state()
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.supervisor import TaskSupervisor
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self):
+        return self.now
+
+
+def _supervisor(**kwargs):
+    crashes, failures = [], []
+    supervisor = TaskSupervisor(
+        on_crash=lambda name, error, delay: crashes.append((name, str(error), delay)),
+        on_failed=failures.append,
+        restart_delay=0.001,
+        max_restart_delay=0.004,
+        **kwargs,
+    )
+    return supervisor, crashes, failures
+
+
+class TestSupervisor:
+    def test_restarts_after_crash(self):
+        supervisor, crashes, failures = _supervisor()
+        runs = []
+
+        async def flaky():
+            runs.append(1)
+            if len(runs) < 3:
+                raise RuntimeError("queue closed")
+
+        async def scenario():
+            await supervisor.spawn("voice audio forwarder", flaky)
+
+        asyncio.run(scenario())
+        assert len(runs) == 3
+        assert [c[:2] for c in crashes] == [("voice audio forwarder", "queue closed")] * 2
+        assert failures == []
+
+    def test_backoff_doubles_up_to_cap(self):
+        supervisor, crashes, _ = _supervisor(max_restarts=4)
+
+        async def broken():
+            raise ValueError("bad frame")
+
+        async def scenario():
+            await supervisor.spawn("listener", broken)
+
+        asyncio.run(scenario())
+        assert [c[2] for c in crashes] == [0.001, 0.002, 0.004, 0.004]
+
+    def test_gives_up_after_max_restarts(self):
+        supervisor, crashes, failures = _supervisor(max_restarts=2)
+        runs = []
+
+        async def broken():
+            runs.append(1)
+            raise ValueError("bad frame")
+
+        async def scenario():
+            await supervisor.spawn("listener", broken)
+
+        asyncio.run(scenario())
+        assert len(runs) == 3  # First run + 2 restarts
+        assert len(failures) == 1
+        assert failures[0].name == "listener"
+        assert failures[0].error == "ValueError: bad frame"
+        assert [f.name for f in supervisor.failures()] == ["listener"]
+
+    def test_stable_run_resets_crash_count(self):
+        clock = FakeClock()
+        supervisor, crashes, failures = _supervisor(max_restarts=1, stable_after=60, clock=clock)
+        runs = []
+
+        async def hiccups():
+            runs.append(1)
+            if len(runs) < 4:
+                clock.now += 120  # Ran fine for a while before crashing
+                raise RuntimeError("hiccup")
+
+        async def scenario():
+            await supervisor.spawn("scheduler", hiccups)
+
+        asyncio.run(scenario())
+        assert len(runs) == 4
+        assert failures == []
+
+    def test_cancel_is_not_a_crash(self):
+        supervisor, crashes, failures = _supervisor()
+
+        async def forever():
+            await asyncio.sleep(3600)
+
+        async def scenario():
+            task = supervisor.spawn("forwarder", forever)
+            await asyncio.sleep(0)
+            task.cancel()
+            with pytest.raises(asyncio.CancelledError):
+                await task
+            return task
+
+        task = asyncio.run(scenario())
+        assert task.cancelled()
+        assert crashes == [] and failures == []
+        assert supervisor.tasks == {}
+
+    def test_respawn_clears_failure(self):
+        supervisor, _, failures = _supervisor(max_restarts=0)
+
+        async def broken():
+            raise RuntimeError("down")
+
+        async def ok():
+            return None
+
+        async def scenario():
+            await supervisor.spawn("listener", broken)
+            assert supervisor.failures()
+            await supervisor.spawn("listener", ok)
+
+        asyncio.run(scenario())
+        assert supervisor.failures() == []
+
+    def test_callback_errors_are_contained(self):
+        def bad_callback(*args):
+            raise RuntimeError("widget gone")
+
+        supervisor = TaskSupervisor(on_crash=bad_callback, on_failed=bad_callback, max_restarts=1, restart_delay=0)
+        runs = []
+
+        async def broken():
+            runs.append(1)
+            raise RuntimeError("down")
+
+        async def scenario():
+            await supervisor.spawn("listener", broken)
+
+        asyncio.run(scenario())
+        assert len(runs) == 2
+
+    def test_report_state(self):
+        clock = FakeClock()
+        reports = []
+        supervisor = TaskSupervisor(on_state=reports.append, clock=clock)
+        supervisor.report_state("conversation", "speaking", "reply_audio")
+        clock.now = 2.0
+        supervisor.report_state("conversation", "interrupted", "user_speech")
+        assert supervisor.states["conversation"].state == "interrupted"
+        assert supervisor.states["conversation"].since == 2.0
+        assert [r.state for r in reports] == ["speaking", "interrupted"]
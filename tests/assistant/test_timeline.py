@@ -0,0 +1,76 @@
+"""
+Tests for rebuilding interaction timelines from the event history (assistant/timeline.py).
+
+Covers:
+- Time ranges: a clock minute or second, "A..B" on the same day, parse_since() starts, mistakes
+- Threads seen in the range shown whole, with their model calls; other events on their own line
+- The step-by-step lines: time since the start, since the step before, and each step's own latency
+"""
+
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.ai_log import AILog
+from assistant.events import EventStore
+from assistant.timeline import build_timelines, parse_range
+
+NOW = datetime(2026, 10, 14, 15, 30)
+AT = datetime(2026, 10, 14, 15, 4, 2)
+
+
+@pytest.mark.parametrize("text, start, end", [
+    ("3:04pm", datetime(2026, 10, 14, 15, 4), datetime(2026, 10, 14, 15, 5)),
+    ("15:04:10", datetime(2026, 10, 14, 15, 4, 10), datetime(2026, 10, 14, 15, 4, 11)),
+    ("11pm", datetime(2026, 10, 13, 23, 0), datetime(2026, 10, 13, 23, 1)),  # Tonight's hasn't come yet
+    ("yesterday 9am..9:30am", datetime(2026, 10, 13, 9, 0), datetime(2026, 10, 13, 9, 30)),
+    ("2h", datetime(2026, 10, 14, 13, 30), NOW),
+])
+def test_parse_range(text, start, end):
+    assert parse_range(text, NOW) == (start, end)
+
+
+@pytest.mark.parametrize("text, message", [("3pm..2pm", "ends before"), ("13pm", "Unknown time"),
+                                           ("soonish", "Unknown time")])
+def test_parse_range_errors(text, message):
+    with pytest.raises(ValueError, match=message):
+        parse_range(text, NOW)
+
+
+def seconds(n):
+    return AT + timedelta(seconds=n)
+
+
+@pytest.fixture
+def store():
+    store = EventStore(":memory:", keep_days=None)
+    store.record("turn", "voice", {"source": "voice"}, at=seconds(0), thread="a1")
+    store.record("activity", "✓ Calendar synced", {"level": "success"}, at=seconds(1))
+    store.record("reply", "1.5s", {"latency": 1.5}, at=seconds(1.5), thread="a1")
+    store.record("tool", "add_task", {"name": "add_task"}, at=seconds(1.75), thread="a1")
+    store.record("email", "📧 Dana: Invoice", {"sender": "Dana"}, at=seconds(120), thread="b2")
+    store.record("activity", "⚠ Invoice due Friday", {"level": "warning"}, at=seconds(125), thread="b2")
+    return store
+
+
+def test_threads_in_full(store):
+    ai_log = AILog(":memory:", "metadata")
+    ai_log.record("anthropic", "claude", "chat", {"messages": []}, latency=1.2, at=seconds(1.25), thread="a1")
+    ai_log.record("ollama", "llama3", "chat", {"messages": []}, latency=4.0, at=seconds(4))
+
+    first, other, second = build_timelines(store, seconds(1), seconds(121), ai_log)  # Starts mid-thread
+    assert (first.thread, other.thread, second.thread) == ("a1", None, "b2")
+    assert [step.stage for step in first.steps] == ["heard", "ai", "answered", "action"]
+    assert first.lines() == [
+        "2026-10-14 15:04:02  a1      voice: 4 steps in 1.75s",
+        "     +0.00s    0.00s  heard        voice",
+        "     +1.25s    1.25s  ai           anthropic claude chat (1.20s)",
+        "     +1.50s    0.25s  answered     1.5s (1.50s)",
+        "     +1.75s    0.25s  action       add_task",
+    ]
+    assert other.lines() == ["2026-10-14 15:04:03  -       activity     ✓ Calendar synced"]
+    assert second.source == "email in" and second.steps[-1].level == "warning"
+
+    assert [t.thread for t in build_timelines(store, seconds(60), seconds(600))] == ["b2"]
+    assert [len(t.steps) for t in build_timelines(store, thread="a1")] == [3]
+    assert build_timelines(store, seconds(600), seconds(700)) == []
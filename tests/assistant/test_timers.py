@@ -0,0 +1,87 @@
+"""
+Tests for timers and the stopwatch (assistant/timers.py).
+
+Covers:
+- Durations in words and digits; names, unnamed timers named for their length
+- Requests: start, cancel (one, the only one, all), time left; other talk is left for the model
+- Long timers survive a restart and ones that ran out meanwhile are reported; short ones don't persist
+- Stopwatch start, lap, stop, resume, reset; footer countdowns
+"""
+
+from datetime import datetime, timedelta
+
+import pytest
+
+from assistant.timers import TimerCommand, TimerRegistry, clock_face, parse_duration, parse_timer_command
+
+NOW = datetime(2026, 10, 16, 12, 0)
+
+
+@pytest.mark.parametrize("text, seconds", [
+    ("9 minutes", 540), ("an hour and a half", 5400), ("1 hour 30 minutes", 5400), ("half an hour", 1800),
+    ("ninety seconds", 90), ("a 5-minute", 300), ("2.5 mins", 150), ("soon", None),
+])
+def test_durations(text, seconds):
+    assert parse_duration(text) == seconds
+
+
+@pytest.mark.parametrize("text, command", [
+    ("Set a pasta timer for 9 minutes", TimerCommand("start", "pasta", 540)),
+    ("hey, start a five minute tea timer", TimerCommand("start", "tea", 300)),
+    ("set a timer called boiled eggs for 6 minutes", TimerCommand("start", "boiled eggs", 360)),
+    ("timer for an hour", TimerCommand("start", "", 3600)),
+    ("how long is left on the pasta timer?", TimerCommand("remaining", "pasta")),
+    ("cancel the 10 minute timer", TimerCommand("cancel", "10 minute")),
+    ("cancel all timers", TimerCommand("cancel", "*")),
+    ("stop the stopwatch", TimerCommand("stopwatch", "stop")),
+    ("set a reminder for 9 minutes", None),
+    ("remind me to check the timer", None),
+    ("set a timer", None),
+    ("how much time is left?", None),  # No timers running: not about a timer
+])
+def test_parse(text, command):
+    assert parse_timer_command(text) == command
+
+
+def test_start_cancel_and_ask(tmp_path):
+    registry = TimerRegistry(tmp_path)
+    assert registry.apply(TimerCommand("start", "pasta", 540), NOW) == "Pasta timer set for 9 minutes."
+    assert registry.apply(TimerCommand("start", "", 600), NOW) == "10 minutes, starting now."
+    assert registry.apply(TimerCommand("start", "pasta", 600), NOW) == "Restarted the pasta timer: 10 minutes."
+    later = NOW + timedelta(seconds=78)
+    assert registry.countdown(later) == ["pasta 8:42", "10 minute 8:42"]
+    question = parse_timer_command("how much time is left?", registry)
+    assert registry.apply(question, later) == ("8 minutes and 42 seconds left on the pasta timer. "
+                                              "8 minutes and 42 seconds left on the 10 minute timer.")
+    assert registry.apply(TimerCommand("cancel", ""), later) == (
+        "Which one? You have the pasta timer and the 10 minute timer.")
+    assert registry.apply(TimerCommand("cancel", "10 minute"), later) == "Cancelled the 10 minute timer."
+    assert registry.apply(TimerCommand("cancel", ""), later) == "Cancelled the pasta timer."
+    assert registry.apply(TimerCommand("cancel", "*"), later) == "There aren't any timers running."
+
+
+def test_finishing_and_restarts(tmp_path):
+    registry = TimerRegistry(tmp_path)
+    registry.start("bread", 3600, NOW)
+    registry.start("tea", 120, NOW)  # Too short to keep across a restart
+    assert [t.name for t in registry.finished(NOW + timedelta(minutes=2))] == ["tea"]
+    restarted = TimerRegistry(tmp_path)
+    assert list(restarted.timers) == ["bread"]
+    assert restarted.finished(NOW + timedelta(minutes=59)) == []
+    late = restarted.finished(NOW + timedelta(hours=3))
+    assert [(t.name, t.end_time) for t in late] == [("bread", NOW + timedelta(hours=1))]
+    assert TimerRegistry(tmp_path).timers == {}
+
+
+def test_stopwatch(tmp_path):
+    registry = TimerRegistry(tmp_path)
+    run = lambda action, seconds: registry.apply(TimerCommand("stopwatch", action), NOW + timedelta(seconds=seconds))
+    assert run("read", 0) == "The stopwatch isn't running."
+    assert run("start", 0) == "Stopwatch started."
+    assert run("lap", 65) == "Lap 1: 1 minute and 5 seconds."
+    assert run("stop", 90) == "Stopped at 1 minute and 30 seconds."
+    assert run("start", 200) == "Stopwatch resumed."
+    assert registry.countdown(NOW + timedelta(seconds=210)) == ["stopwatch 1:40"]
+    assert run("read", 210) == "1 minute and 40 seconds."
+    assert run("reset", 220) == "Stopwatch reset." and registry.countdown(NOW) == []
+    assert clock_face(3905) == "1:05:05" and clock_face(0.2) == "0:01"
@@ -0,0 +1,160 @@
+"""
+Tests for the home timezone and travel mode (assistant/timezones.py).
+
+Covers:
+- Detecting the OS zone from TZ, /etc/timezone and the /etc/localtime link
+- Finding zones by city or IANA name
+- Converting stored home times to the travel zone and back (DST aware)
+- Dual-timezone display in calendar views
+- The travel mode tools: events entered while travelling are stored as
+  home time, listed with both times, and spoken in local time
+- The startup hint when the machine's zone differs from home
+- Server sync sends the home zone's offset
+"""
+
+from datetime import datetime, timedelta
+from types import SimpleNamespace
+
+import pytest
+
+from assistant import dates, tools
+from assistant.config import Config
+from assistant.dates import DateSettings
+from assistant.planner import PlannerData
+from assistant.scheduler_client import appointment_payload
+from assistant.timezones import (
+    find_timezone,
+    format_dual,
+    from_display,
+    set_travel_timezone,
+    system_timezone,
+    to_display,
+    travel_suggestion,
+)
+from assistant.verbalize import verbalize_datetime
+
+NEW_YORK = "America/New_York"
+LONDON = "Europe/London"
+HOME = DateSettings(clock="12h", order="mdy", timezone=NEW_YORK)
+TRAVELLING = DateSettings(clock="12h", order="mdy", timezone=NEW_YORK, travel_timezone=LONDON)
+
+
+def _planner(tmp_path, monkeypatch, settings=HOME):
+    planner = PlannerData(tmp_path / "planner")
+    monkeypatch.setattr(tools, "_planner_data", planner)
+    monkeypatch.setattr(dates, "_settings", settings)
+    return planner
+
+
+class TestDetection:
+    def test_tz_variable_wins(self, tmp_path):
+        assert system_timezone({"TZ": ":Europe/Paris"}, tmp_path / "none", tmp_path / "none") == "Europe/Paris"
+
+    def test_timezone_file(self, tmp_path):
+        (tmp_path / "timezone").write_text("Asia/Tokyo\n")
+        assert system_timezone({}, tmp_path / "none", tmp_path / "timezone") == "Asia/Tokyo"
+
+    def test_localtime_link(self, tmp_path):
+        zoneinfo = tmp_path / "usr" / "share" / "zoneinfo" / "America"
+        zoneinfo.mkdir(parents=True)
+        (zoneinfo / "Chicago").write_bytes(b"TZif")
+        (tmp_path / "localtime").symlink_to(zoneinfo / "Chicago")
+        assert system_timezone({}, tmp_path / "localtime", tmp_path / "none") == "America/Chicago"
+
+    def test_nothing_usable(self, tmp_path):
+        assert system_timezone({"TZ": "EST5EDT,M3.2.0,M11.1.0"}, tmp_path / "none", tmp_path / "none") is None
+
+    @pytest.mark.parametrize("text, name", [
+        ("Europe/London", LONDON), ("london", LONDON), ("new york", NEW_YORK), ("UTC", "UTC"),
+        ("Atlantis", None), ("", None),
+    ])
+    def test_find_timezone(self, text, name):
+        assert find_timezone(text) == name
+
+
+class TestConversion:
+    def test_no_travel_mode_is_identity(self):
+        value = datetime(2026, 10, 16, 14, 30)
+        assert to_display(value, HOME) == value
+        assert from_display(value, HOME) == value
+        assert format_dual(value, HOME) == "14:30"
+
+    def test_home_to_travel_and_back(self):
+        home_time = datetime(2026, 10, 16, 10, 0)  # EDT, UTC-4; London is on BST, UTC+1
+        assert to_display(home_time, TRAVELLING) == datetime(2026, 10, 16, 15, 0)
+        assert from_display(datetime(2026, 10, 16, 15, 0), TRAVELLING) == home_time
+
+    def test_follows_daylight_saving(self):
+        # London leaves BST on Oct 25, New York on Nov 1: the gap is 4 hours that week
+        assert to_display(datetime(2026, 10, 28, 10, 0), TRAVELLING) == datetime(2026, 10, 28, 14, 0)
+        assert to_display(datetime(2026, 12, 1, 10, 0), TRAVELLING) == datetime(2026, 12, 1, 15, 0)
+
+    def test_dual_display(self):
+        assert format_dual(datetime(2026, 10, 16, 10, 0), TRAVELLING) == "15:00 (10:00 home)"
+        same_offset = DateSettings(timezone=LONDON, travel_timezone="Europe/Lisbon")
+        assert format_dual(datetime(2026, 10, 16, 10, 0), same_offset) == "10:00"
+
+    def test_spoken_in_travel_zone(self):
+        now = datetime(2026, 10, 16, 8, 0)  # Home time
+        assert verbalize_datetime(datetime(2026, 10, 17, 10, 0), now, TRAVELLING) == "tomorrow at three o'clock"
+        assert verbalize_datetime(datetime(2026, 10, 17, 10, 0), now, HOME) == "tomorrow at ten o'clock"
+
+
+class TestTravelTools:
+    def test_event_entered_while_travelling_is_stored_as_home_time(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch, TRAVELLING)
+        result = tools.add_calendar_event("Dinner", "2026-10-20", "19:00")
+        assert result == "✓ Added: 'Dinner' on Tuesday Oct 20 at 19:00 (14:00 home)"
+        assert planner.get_calendar_events(expand_recurring=False)[0].start_time == "2026-10-20T14:00:00"
+
+    def test_listing_shows_both_times(self, tmp_path, monkeypatch):
+        planner = _planner(tmp_path, monkeypatch, TRAVELLING)
+        start = (datetime.now() + timedelta(days=1)).replace(hour=10, minute=0, second=0, microsecond=0)
+        planner.add_calendar_event("Standup", start.isoformat(), start.replace(hour=11).isoformat())
+        listing = tools.list_calendar_events(days=2)
+        shown = to_display(start, TRAVELLING)
+        assert f"{shown:%Y-%m-%d %H:%M} (10:00 home) - Standup" in listing
+
+    def test_set_and_end_travel_mode(self, tmp_path, monkeypatch):
+        monkeypatch.setenv("HOME", str(tmp_path))  # Config saves to ~/.config/xswarm/config.yaml
+        config = Config(timezone=NEW_YORK)
+        _planner(tmp_path, monkeypatch, DateSettings.from_config(config))
+
+        assert tools.set_travel_mode("Atlantis").startswith("✗ Unknown timezone 'Atlantis'")
+        result = tools.set_travel_mode("london")
+        assert result.startswith(f"✓ Travel mode on: times now in {LONDON} (")
+        assert "ahead of home" in result
+        assert dates.get_date_settings().travel_timezone == LONDON
+        assert config.travel_timezone == LONDON
+        assert f"travel_timezone: {LONDON}" in (tmp_path / ".config" / "xswarm" / "config.yaml").read_text()
+
+        assert tools.end_travel_mode() == f"✓ Travel mode off: times back in home time ({NEW_YORK})"
+        assert dates.get_date_settings().travel_timezone is None
+        assert config.travel_timezone is None
+        assert tools.end_travel_mode() == f"✓ Already on home time ({NEW_YORK})"
+
+    def test_travelling_home_ends_travel_mode(self, tmp_path, monkeypatch):
+        _planner(tmp_path, monkeypatch, TRAVELLING)
+        assert tools.set_travel_mode("New York") == f"✓ {NEW_YORK} is home - travel mode off"
+        assert dates.get_date_settings().travel_timezone is None
+
+    def test_set_travel_timezone_keeps_other_settings(self, monkeypatch):
+        monkeypatch.setattr(dates, "_settings", HOME)
+        updated = set_travel_timezone(LONDON)
+        assert (updated.order, updated.clock, updated.timezone, updated.travel_timezone) == ("mdy", "12h", NEW_YORK, LONDON)
+
+
+class TestStartupAndSync:
+    def test_suggests_travel_mode_when_machine_moved(self):
+        assert "travel mode London" in travel_suggestion(HOME, detected=LONDON)
+        assert travel_suggestion(HOME, detected=NEW_YORK) is None
+        assert travel_suggestion(TRAVELLING, detected=LONDON) is None
+        assert travel_suggestion(DateSettings(), detected=LONDON) is None  # Home is the OS zone
+
+    def test_server_gets_home_offset(self, monkeypatch):
+        monkeypatch.setattr(dates, "_settings", HOME)
+        event = SimpleNamespace(title="Standup", description="", start_time="2026-10-16T09:00:00",
+                                end_time="2026-10-16T09:15:00", location="", attendees=[], tags=[])
+        payload = appointment_payload(event)
+        assert payload["start_time"] == "2026-10-16T09:00:00-04:00"
+        assert payload["end_time"] == "2026-10-16T09:15:00-04:00"
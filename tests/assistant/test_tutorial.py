@@ -0,0 +1,89 @@
+"""
+Tests for the first-use tutorial (assistant/tutorial.py).
+
+Covers:
+- Reading practice reminders ("remind me to stretch in ten minutes", "remind me at 5 to call Mum")
+- The walkthrough: wake word, mic level, reminder read-back, calendar answer
+- Retrying a quiet microphone or a missing wake word; skipping steps; no mic step without voice
+- Progress: recorded when finished or cancelled, so it isn't offered again
+- "start the tutorial" starts it from the flow registry
+"""
+
+from datetime import datetime
+
+import pytest
+
+from assistant.flows import FlowRun, match_flow
+from assistant.tutorial import MicMeter, TutorialHooks, TutorialProgress, parse_reminder, tutorial_flow
+
+NOW = datetime(2026, 10, 16, 10, 0)
+
+
+@pytest.mark.parametrize("text, reminder", [
+    ("remind me to stretch in ten minutes", ("stretch", datetime(2026, 10, 16, 10, 10))),
+    ("Remind me to call Mum at five", ("call Mum", datetime(2026, 10, 16, 17, 0))),
+    ("remind me to check in on Dad at 5pm", ("check in on Dad", datetime(2026, 10, 16, 17, 0))),
+    ("remind me at 5 to call Mum", ("call Mum", datetime(2026, 10, 16, 17, 0))),
+    ("remind me tomorrow at 9 to buy milk", ("buy milk", datetime(2026, 10, 17, 9, 0))),
+    ("remind me to stretch", None),
+    ("what's the weather", None),
+])
+def test_parse_reminder(text, reminder):
+    assert parse_reminder(text, NOW) == reminder
+
+
+def _run(tmp_path, mic=True, wake_heard=False):
+    hooks = TutorialHooks(wake_words=["jarvis"], wake_word_heard=lambda: wake_heard,
+                          mic=MicMeter() if mic else None, calendar=lambda: "Dentist · today 16:00",
+                          progress=TutorialProgress(tmp_path / "tutorial.json"), now=lambda: NOW)
+    return FlowRun(tutorial_flow(hooks)), hooks
+
+
+def test_walkthrough(tmp_path):
+    run, hooks = _run(tmp_path)
+    assert run.start().startswith("Welcome! Here's a quick tour") and "say 'jarvis, hello'" in run.start()
+    assert "Watch the wave" in run.handle("Jarvis, hello")
+    hooks.mic.sample(0.3)
+    assert run.handle("testing one two three").startswith("Your microphone sounds good. Now let's practise")
+    reply = run.handle("remind me to stretch in ten minutes")
+    assert reply.startswith("That's all it takes - I'd remind you to stretch in ten minutes.")
+    assert reply.endswith("try 'what's on my calendar?'")
+    assert run.handle("what's on my calendar today?").startswith("Next up: Dentist · today 16:00. That's the tour!")
+    assert run.state == "done"
+    assert TutorialProgress(tmp_path / "tutorial.json").record["outcome"] == "finished"
+
+
+def test_retries_and_skips(tmp_path):
+    run, hooks = _run(tmp_path)
+    run.start()
+    assert run.handle("hello").startswith("I didn't hear 'jarvis' in that.")
+    assert "Watch the wave" in run.handle("skip")
+    hooks.mic.sample(0.01)
+    assert run.handle("testing").startswith("That was very quiet")
+    hooks.mic.sample(0.5)  # Reset by the re-ask: only this answer counts
+    assert "practise a reminder" in run.handle("testing one two three")
+    assert run.handle("remind me").startswith("Say what to be reminded about and when")
+    assert run.handle("skip").startswith("Last one")
+
+
+def test_wake_word_heard_by_voice_and_no_mic_step(tmp_path):
+    run, _ = _run(tmp_path, mic=False, wake_heard=True)
+    run.start()
+    assert run.handle("hello").startswith("Now let's practise a reminder")  # The wake word was stripped already
+
+
+def test_cancel_is_recorded(tmp_path):
+    progress = TutorialProgress(tmp_path / "tutorial.json")
+    assert progress.should_offer
+    progress.mark("started", NOW)
+    run, _ = _run(tmp_path)
+    run.start()
+    assert run.handle("cancel").startswith("Okay, tutorial stopped.")
+    saved = TutorialProgress(tmp_path / "tutorial.json")
+    assert not saved.should_offer and saved.record["outcome"] == "cancelled"
+
+
+@pytest.mark.parametrize("text", ["start the tutorial", "Run the tutorial again.", "tutorial",
+                                  "show me how to use this"])
+def test_started_by_request(text):
+    assert match_flow(text) == "tutorial"
@@ -0,0 +1,113 @@
+"""
+Tests for the undo log and soft-deleted planner items.
+
+Covers:
+- Deleted items are hidden but restorable in place within the window
+- Completing can be reverted; forgotten facts come back
+- Nothing is undoable after 5 minutes; undo goes newest first
+- "undo that" intent matching
+"""
+
+from datetime import datetime, timedelta
+
+from assistant.memory import UserProfile
+from assistant.planner import PlannerData
+from assistant.undo import UndoLog, is_undo_request, undo_last
+
+NOW = datetime(2026, 10, 14, 12, 0)
+
+
+def _setup(tmp_path):
+    return PlannerData(tmp_path / "planner"), UndoLog(tmp_path / "undo")
+
+
+class TestSoftDelete:
+    def test_deleted_event_restored_in_place(self, tmp_path):
+        planner, log = _setup(tmp_path)
+        first = planner.add_calendar_event("Standup", "2026-10-14T09:00:00", "2026-10-14T09:15:00")
+        second = planner.add_calendar_event("Dentist", "2026-10-14T15:00:00", "2026-10-14T16:00:00")
+        planner.add_calendar_event("Dinner", "2026-10-14T19:00:00", "2026-10-14T20:00:00")
+
+        assert planner.delete_calendar_event(second.id)
+        assert planner.get_calendar_event(second.id) is None
+        log.record("planner_restore", "Deleted event 'Dentist'",
+                   {"collection": "calendar_events", "id": second.id}, now=NOW)
+
+        assert undo_last(log, planner, now=NOW + timedelta(minutes=2)) == "✓ Undone: Deleted event 'Dentist'"
+        titles = [e.title for e in planner.get_calendar_events(expand_recurring=False)]
+        assert titles == ["Standup", "Dentist", "Dinner"]
+        assert planner.get_calendar_event(first.id) is not None
+
+    def test_trash_survives_reload(self, tmp_path):
+        planner, _ = _setup(tmp_path)
+        task = planner.add_task("Call Bob", auto_schedule=False)
+        planner.delete_task(task.id)
+        reloaded = PlannerData(tmp_path / "planner")
+        assert reloaded.get_task(task.id) is None
+        assert reloaded.restore_deleted("tasks", task.id)["title"] == "Call Bob"
+        assert reloaded.get_task(task.id).title == "Call Bob"
+
+
+class TestUndoLog:
+    def test_complete_is_reverted(self, tmp_path):
+        planner, log = _setup(tmp_path)
+        task = planner.add_task("Renew passport", auto_schedule=False)
+        planner.complete_task(task.id, reschedule=False)
+        log.record("planner_fields", "Completed task 'Renew passport'", {
+            "collection": "tasks", "id": task.id, "fields": {"status": "inbox", "completed_at": None},
+        }, now=NOW)
+
+        assert undo_last(log, planner, now=NOW).startswith("✓")
+        restored = planner.get_task(task.id)
+        assert restored.status == "inbox"
+        assert restored.completed_at is None
+
+    def test_window_expires(self, tmp_path):
+        planner, log = _setup(tmp_path)
+        task = planner.add_task("Old", auto_schedule=False)
+        planner.delete_task(task.id)
+        log.record("planner_restore", "Deleted task 'Old'", {"collection": "tasks", "id": task.id}, now=NOW)
+
+        result = undo_last(log, planner, now=NOW + timedelta(minutes=5))
+        assert result.startswith("✗ Nothing to undo")
+        assert planner.get_task(task.id) is None
+
+    def test_newest_first_and_only_once(self, tmp_path):
+        planner, log = _setup(tmp_path)
+        a = planner.add_task("A", auto_schedule=False)
+        b = planner.add_task("B", auto_schedule=False)
+        for task, minute in ((a, 0), (b, 1)):
+            planner.delete_task(task.id)
+            log.record("planner_restore", f"Deleted task '{task.title}'",
+                       {"collection": "tasks", "id": task.id}, now=NOW + timedelta(minutes=minute))
+
+        later = NOW + timedelta(minutes=2)
+        assert undo_last(log, planner, now=later) == "✓ Undone: Deleted task 'B'"
+        assert undo_last(log, planner, now=later) == "✓ Undone: Deleted task 'A'"
+        assert undo_last(log, planner, now=later).startswith("✗")
+        assert len(UndoLog(tmp_path / "undo").entries()) == 2
+
+    def test_forgotten_facts_come_back(self, tmp_path):
+        profile = UserProfile(tmp_path / "profile")
+        profile.add_fact("identity", "Lives in Portland")
+        profile.add_fact("preference", "Likes tea")
+        log = UndoLog(tmp_path / "undo")
+
+        removed = profile.remove_facts_matching("portland")
+        assert [f.fact for f in removed] == ["Lives in Portland"]
+        log.record("profile_facts", "Forgot 1 fact about you",
+                   {"facts": [{"category": f.category, "fact": f.fact, "added_at": f.added_at} for f in removed]},
+                   now=NOW)
+
+        assert undo_last(log, profile=profile, now=NOW).startswith("✓")
+        assert {f.fact for f in profile.facts} == {"Lives in Portland", "Likes tea"}
+
+
+class TestIntent:
+    def test_undo_phrases(self):
+        for text in ("undo that", "Undo.", "oops, undo it", "please undo the last action", "put it back"):
+            assert is_undo_request(text), text
+
+    def test_not_undo(self):
+        for text in ("how do I undo a git commit?", "undo my 3pm and book 4pm", "restore the database"):
+            assert not is_undo_request(text), text
@@ -0,0 +1,200 @@
+"""
+Tests for speaking dates and times (assistant/verbalize.py).
+
+Covers:
+- Number, ordinal and year words, and spoken lists
+- Clock times on 12h and 24h clocks; every 12h time parses back to itself
+  with dates.parse_time_expression
+- Days relative to today, in both day/month orders
+- Durations and relative times ("in twenty minutes")
+- Whole datetimes, including the configured timezone
+- speakable() rewriting tool results for text-to-speech
+- Spoken reminders and the morning briefing's calendar
+"""
+
+from datetime import date, datetime, time, timedelta, timezone
+
+import pytest
+
+from assistant import dates
+from assistant.config import Config
+from assistant.dates import DateSettings, parse_time_expression, system_clock_format
+from assistant.planner import PlannerData, PlanningSession
+from assistant.reminders import Reminder
+from assistant.verbalize import (
+    join_words,
+    number_words,
+    ordinal_words,
+    speakable,
+    verbalize_date,
+    verbalize_datetime,
+    verbalize_duration,
+    verbalize_relative,
+    verbalize_time,
+    year_words,
+)
+
+NOW = datetime(2026, 10, 16, 14, 0)  # A Friday
+US = DateSettings(order="mdy", clock="12h")
+EU = DateSettings(order="dmy", clock="24h")
+
+
+@pytest.mark.parametrize("n, words", [
+    (0, "zero"), (7, "seven"), (13, "thirteen"), (20, "twenty"), (45, "forty-five"),
+    (100, "one hundred"), (305, "three hundred five"), (2026, "two thousand twenty-six"),
+])
+def test_number_words(n, words):
+    assert number_words(n) == words
+
+
+@pytest.mark.parametrize("n, words", [
+    (1, "first"), (2, "second"), (3, "third"), (5, "fifth"), (9, "ninth"), (12, "twelfth"),
+    (20, "twentieth"), (21, "twenty-first"), (22, "twenty-second"), (30, "thirtieth"), (31, "thirty-first"),
+])
+def test_ordinal_words(n, words):
+    assert ordinal_words(n) == words
+
+
+@pytest.mark.parametrize("year, words", [
+    (2026, "twenty twenty-six"), (2000, "two thousand"), (2005, "two thousand five"),
+    (2010, "twenty ten"), (1900, "nineteen hundred"), (1905, "nineteen oh five"),
+])
+def test_year_words(year, words):
+    assert year_words(year) == words
+
+
+@pytest.mark.parametrize("items, words", [
+    (["milk"], "milk"), (["milk", "eggs"], "milk and eggs"), (["milk", "eggs", "bread"], "milk, eggs and bread"),
+])
+def test_join_words(items, words):
+    assert join_words(items) == words
+
+
+@pytest.mark.parametrize("clock_time, words", [
+    (time(0, 0), "midnight"),
+    (time(12, 0), "noon"),
+    (time(9, 0), "nine o'clock"),
+    (time(14, 30), "half past two"),
+    (time(9, 15), "quarter past nine"),
+    (time(18, 45), "quarter to seven in the evening"),
+    (time(11, 45), "quarter to noon"),
+    (time(23, 45), "quarter to midnight"),
+    (time(0, 30), "half past midnight"),
+    (time(14, 5), "two oh five"),
+    (time(4, 20), "four twenty in the morning"),
+    (time(21, 10), "nine ten in the evening"),
+])
+def test_twelve_hour_times(clock_time, words):
+    assert verbalize_time(clock_time, "12h") == words
+
+
+@pytest.mark.parametrize("clock_time, words", [
+    (time(0, 0), "midnight"),
+    (time(9, 0), "nine hundred"),
+    (time(14, 30), "fourteen thirty"),
+    (time(9, 5), "nine oh five"),
+    (time(18, 45), "eighteen forty-five"),
+])
+def test_twenty_four_hour_times(clock_time, words):
+    assert verbalize_time(clock_time, "24h") == words
+
+
+def test_every_twelve_hour_time_parses_back():
+    for minutes in range(24 * 60):
+        clock_time = time(*divmod(minutes, 60))
+        spoken = verbalize_time(clock_time, "12h")
+        assert parse_time_expression(spoken) == clock_time.strftime("%H:%M"), spoken
+
+
+def test_clock_format_follows_locale_and_config():
+    assert system_clock_format({"LANG": "en_US.UTF-8"}) == "12h"
+    assert system_clock_format({"LANG": "en_GB.UTF-8"}) == "12h"
+    assert system_clock_format({"LC_TIME": "de_DE.UTF-8", "LANG": "en_US.UTF-8"}) == "24h"
+    assert system_clock_format({}) == "12h"
+    assert DateSettings.from_config(Config(clock_format="24h")).resolved_clock() == "24h"
+    assert DateSettings(clock="nonsense").resolved_clock() in ("12h", "24h")
+
+
+@pytest.mark.parametrize("day, words", [
+    (date(2026, 10, 16), "today"),
+    (date(2026, 10, 17), "tomorrow"),
+    (date(2026, 10, 15), "yesterday"),
+    (date(2026, 10, 20), "Tuesday"),
+    (date(2026, 10, 12), "last Monday"),
+    (date(2026, 11, 5), "November fifth"),
+    (date(2027, 4, 5), "April fifth, twenty twenty-seven"),
+])
+def test_dates_month_first(day, words):
+    assert verbalize_date(day, today=NOW.date(), order="mdy") == words
+
+
+def test_dates_day_first():
+    assert verbalize_date(date(2026, 11, 5), today=NOW.date(), order="dmy") == "the fifth of November"
+    assert verbalize_date(date(2027, 4, 22), today=NOW.date(), order="dmy") == "the twenty-second of April twenty twenty-seven"
+
+
+@pytest.mark.parametrize("minutes, words", [
+    (1, "one minute"), (20, "twenty minutes"), (60, "an hour"), (90, "an hour and a half"),
+    (130, "two hours and ten minutes"), (300, "five hours"), (30 * 60, "thirty hours"),
+    (3 * 24 * 60, "three days"), (14 * 24 * 60, "two weeks"), (40 * 24 * 60, "forty days"),
+])
+def test_durations(minutes, words):
+    assert verbalize_duration(timedelta(minutes=minutes)) == words
+
+
+def test_relative():
+    assert verbalize_relative(timedelta(minutes=20)) == "in twenty minutes"
+    assert verbalize_relative(timedelta(minutes=-10)) == "ten minutes ago"
+    assert verbalize_relative(timedelta(seconds=10)) == "in less than a minute"
+    assert verbalize_relative(timedelta(seconds=-10)) == "just now"
+
+
+def test_datetimes():
+    assert verbalize_datetime(NOW + timedelta(minutes=20), NOW, US) == "in twenty minutes"
+    assert verbalize_datetime(NOW + timedelta(minutes=20), NOW, US, relative=False) == "today at two twenty"
+    assert verbalize_datetime(datetime(2026, 10, 17, 14, 30), NOW, US) == "tomorrow at half past two"
+    assert verbalize_datetime(datetime(2026, 10, 20, 9, 0), NOW, US) == "Tuesday at nine o'clock"
+    assert verbalize_datetime(datetime(2026, 11, 5, 14, 30), NOW, EU) == "the fifth of November at fourteen thirty"
+
+
+def test_aware_datetimes_use_configured_timezone():
+    settings = DateSettings(clock="12h", timezone="America/New_York")
+    start = datetime(2026, 10, 16, 18, 30, tzinfo=timezone.utc)  # 14:30 in New York (EDT)
+    now = datetime(2026, 10, 16, 12, 0, tzinfo=timezone.utc)
+    assert verbalize_datetime(start, now, settings) == "today at half past two"
+
+
+def test_unknown_timezone_falls_back_to_local():
+    assert DateSettings(timezone="Mars/Olympus_Mons").home_zone() is None
+    assert DateSettings.from_config(Config(timezone="Europe/London")).home_zone() is not None
+
+
+def test_speakable_rewrites_tool_results():
+    assert speakable("✓ Moved 'Report' to 2026-10-17", NOW, US) == "✓ Moved 'Report' to tomorrow"
+    assert speakable("Added 'Dentist' on 2026-10-20 at 14:30", NOW, US) == "Added 'Dentist' on Tuesday at half past two"
+    assert speakable("Standup on 2026-10-17T09:00:00", NOW, US) == "Standup tomorrow at nine o'clock"
+    assert speakable("Quiet 22:00-07:00", NOW, US) == "Quiet ten o'clock in the evening to seven o'clock"
+    assert speakable("Rescheduled to 14:30", NOW, EU) == "Rescheduled to fourteen thirty"
+
+
+def test_speakable_leaves_other_text_alone():
+    text = "Version 2.1, ratio 3:2:1, 2026-13-45 and 25:99, 3 tasks"
+    assert speakable(text, NOW, US) == text
+
+
+def test_spoken_reminder():
+    reminder = Reminder(key="k", title="Standup", start=NOW, minutes=10)
+    assert reminder.text == "Standup in 10 minutes"
+    assert reminder.spoken == "Reminder: Standup in ten minutes."
+    assert Reminder(key="k", title="Call", start=NOW, minutes=1).spoken == "Reminder: Call in one minute."
+
+
+def test_morning_briefing_lists_todays_calendar_in_words(tmp_path, monkeypatch):
+    monkeypatch.setattr(dates, "_settings", US)
+    planner = PlannerData(tmp_path / "planner")
+    today = date.today().isoformat()
+    planner.add_project("Launch")  # Not a new user, so the returning-user briefing is built
+    planner.add_calendar_event("Dentist", f"{today}T14:30:00", f"{today}T15:00:00", location="Main St")
+    session = PlanningSession(planner)
+    context = session._build_returning_user_morning_context(planner.get_planning_summary())
+    assert "- half past two: Dentist (Main St)" in context
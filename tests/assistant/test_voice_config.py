@@ -0,0 +1,103 @@
+"""
+Tests for changing settings by asking (assistant/voice_config.py).
+
+Covers:
+- Values checked by kind: quiet hours ranges and on/off, numbers in range, choices
+- "The British one": a persona matched by name or description, ambiguity refused
+- A change is set on the config, saved, passed to hooks and read back
+- "Undo that" puts the old values back
+- The change_setting tool
+"""
+
+import asyncio
+
+import pytest
+
+from assistant import voice_config
+from assistant.config import Config
+from assistant.tools import registry
+from assistant.undo import UndoLog, undo_last
+from assistant.voice_config import (
+    SETTINGS, ConfigChanger, SettingError, find_setting, match_persona, parse_value,
+)
+
+PERSONAS = {"JARVIS": "A polite British butler AI", "GLaDOS": "Passive-aggressive test supervisor",
+            "HAL": "Calm, polite ship computer"}
+
+
+class SavedConfig(Config):
+    saves: int = 0
+
+    def save_to_file(self, path=None):
+        self.saves += 1
+
+
+@pytest.fixture
+def changer(tmp_path):
+    return ConfigChanger(SavedConfig(), personas=lambda: PERSONAS, undo_log=UndoLog(tmp_path / "undo"))
+
+
+def test_parse_values():
+    quiet = SETTINGS["quiet_hours"]
+    assert parse_value(quiet, "from 10pm to 7am") == \
+        {"quiet_hours_enabled": True, "quiet_hours_start": "22:00", "quiet_hours_end": "07:00"}
+    assert parse_value(quiet, "11:30pm - 6am")["quiet_hours_start"] == "23:30"
+    assert parse_value(quiet, "off") == {"quiet_hours_enabled": False}
+    with pytest.raises(SettingError, match="start and an end"):
+        parse_value(quiet, "10pm")
+
+    assert parse_value(SETTINGS["follow_up_window"], "12 seconds") == {"follow_up_window": 12.0}
+    assert parse_value(SETTINGS["max_spoken_words"], "150") == {"speech_max_words": 150}
+    with pytest.raises(SettingError, match="0 to 30"):
+        parse_value(SETTINGS["follow_up_window"], "90")
+    with pytest.raises(SettingError, match="on or off"):
+        parse_value(SETTINGS["wake_word"], "sometimes")
+    with pytest.raises(SettingError, match="mask, remove, off"):
+        parse_value(SETTINGS["profanity"], "bleep")
+    assert find_setting("Quiet time") is SETTINGS["quiet_hours"]
+    assert find_setting("volume") is None
+
+
+def test_match_persona():
+    assert match_persona("glados", PERSONAS) == "GLaDOS"
+    assert match_persona("the British one", PERSONAS) == "JARVIS"
+    with pytest.raises(SettingError, match="The voices are"):
+        match_persona("the polite one", PERSONAS)
+    assert match_persona("the calm voice", PERSONAS) == "HAL"
+
+
+def test_change_saves_applies_and_reads_back(changer):
+    switched = []
+    changer.on_change("voice", lambda values: switched.append(values["default_persona"]))
+
+    assert changer.change("quiet hours", "10pm to 7am") == "✓ Quiet hours are now 10 PM to 7 AM"
+    assert (changer.config.quiet_hours_enabled, changer.config.quiet_hours_start) == (True, "22:00")
+    assert changer.change("voice", "the British one") == "✓ The voice is now JARVIS"
+    assert switched == ["JARVIS"]
+    assert changer.config.saves == 2
+
+    assert changer.change("follow_up_window", "45").startswith("✗ follow_up_window goes from 0 to 30")
+    assert changer.change("theme", "dark").startswith("✗ 'theme' can't be changed")
+    assert changer.change("quiet_hours", "10pm until 7am").startswith("✓ Already so")
+    assert changer.config.saves == 2
+    assert "Voices: JARVIS, GLaDOS, HAL" in changer.summary()
+
+
+def test_undo_restores_old_values(changer, monkeypatch):
+    monkeypatch.setattr(voice_config, "_changer", changer)
+    before = changer.config.quiet_hours_start
+    changer.change("wake_word", "on")
+    changer.change("quiet_hours", "9pm to 6am")
+
+    assert undo_last(changer.undo_log).startswith("✓ Undone: changed quiet hours")
+    assert changer.config.quiet_hours_start == before
+    assert changer.config.require_wake_word is True
+    undo_last(changer.undo_log)
+    assert changer.config.require_wake_word is False
+
+
+def test_change_setting_tool(changer, monkeypatch):
+    monkeypatch.setattr(voice_config, "_changer", changer)
+    result = asyncio.run(registry.execute_tool("change_setting", {"setting": "profanity", "value": "remove"}))
+    assert result["success"] and result["result"] == "✓ Profanity is now remove"
+    assert changer.config.speech_profanity == "remove"
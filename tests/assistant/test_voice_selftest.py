@@ -0,0 +1,74 @@
+"""
+Tests for the voice quality self-test (assistant/voice_selftest.py).
+
+Covers:
+- Word error rate ignores case and punctuation, counts substitutions, deletions and insertions
+- Corpus files: plain and per-prompt thresholds, command-line overrides, malformed corpora
+- A run scores each prompt; silence, slow starts and synthesis errors fail it; the report summarizes
+"""
+
+import asyncio
+
+import pytest
+
+from assistant.voice_selftest import (
+    DEFAULT_CORPUS, Prompt, Sample, SelftestError, VoiceSelftest, load_corpus, word_error_rate,
+)
+
+
+@pytest.mark.parametrize("reference, heard, wer", [
+    ("Good morning, you have two meetings today.", "good morning you have two meetings today", 0.0),
+    ("Don't stop the build-server!", "don't stop the build server", 0.0),
+    ("call the bank before it closes", "call the tank before closes", 2 / 6),
+    ("call the bank", "please call the bank now", 2 / 3),
+    ("call the bank", "", 1.0),
+    ("", "", 0.0),
+])
+def test_word_error_rate(reference, heard, wer):
+    assert word_error_rate(reference, heard) == pytest.approx(wer)
+
+
+def test_corpus(tmp_path):
+    assert [p.text for p in load_corpus()] == DEFAULT_CORPUS
+    corpus = tmp_path / "corpus.yaml"
+    corpus.write_text("max_latency: 2.0\nprompts:\n  - Hello there.\n  - text: Lisbon at seven.\n    max_wer: 0.4\n")
+    assert load_corpus(corpus) == [Prompt("Hello there.", 0.25, 2.0), Prompt("Lisbon at seven.", 0.4, 2.0)]
+    assert load_corpus(corpus, max_wer=0.1, max_latency=5.0)[0] == Prompt("Hello there.", 0.1, 5.0)
+    corpus.write_text("- Just a list.\n")
+    assert load_corpus(corpus)[0].text == "Just a list."
+    for bad in ("prompts: []\n", "prompts:\n  - max_wer: 0.2\n", "prompts: [unclosed\n"):
+        corpus.write_text(bad)
+        with pytest.raises(SelftestError):
+            load_corpus(corpus)
+    with pytest.raises(SelftestError):
+        load_corpus(tmp_path / "missing.yaml")
+
+
+def test_run_and_report():
+    readings = {
+        "Hello there.": Sample("hello there", 24000, 0.8),
+        "Call the bank.": Sample("call the tank", 24000, 1.0),
+        "Quiet please.": Sample("", 24000, None),
+        "Slow start.": Sample("slow start", 24000, 3.5),
+    }
+
+    async def synthesize(text):
+        if text == "Broken.":
+            raise RuntimeError("server gone")
+        return readings[text]
+
+    seen = []
+    prompts = [Prompt(text) for text in [*readings, "Broken."]]
+    report = asyncio.run(VoiceSelftest(prompts, synthesize, lambda audio, rate: audio, on_result=seen.append).run())
+    assert [r.passed for r in report.results] == [True, False, False, False, False] and not report.passed
+    assert len(seen) == 5
+    assert report.results[1].wer == pytest.approx(1 / 3)
+    lines = report.lines()
+    assert lines[0] == '✓ "Hello there.": WER 0.00, latency 0.80s'
+    assert lines[1] == '✗ "Call the bank.": WER 0.33 > 0.25, latency 1.00s\n    heard: "call the tank"'
+    assert lines[2].startswith('✗ "Quiet please.": WER 1.00 > 0.25, no speech')
+    assert "latency 3.50s > 3.0s" in lines[3]
+    assert lines[4] == '✗ "Broken.": server gone'
+    assert lines[-1] == "1/5 prompts passed (mean WER 0.33, median latency 1.00s)"
+    data = report.to_dict()
+    assert data["passed"] is False and data["results"][0]["passed"] is True and data["median_latency"] == 1.0
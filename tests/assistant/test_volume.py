@@ -0,0 +1,98 @@
+"""
+Tests for playback volume (assistant/volume.py).
+
+Covers:
+- Recognizing "speak louder" / "volume to 60%" and leaving other requests alone
+- Stepping, setting, muting and clamping levels
+- Saving changes to the config
+- Whispered speech during meetings
+- The set_volume tool
+"""
+
+import pytest
+
+from assistant import volume
+from assistant.config import Config
+from assistant.tools import set_volume
+from assistant.volume import STEP, VolumeRequest, VolumeSettings, parse_volume_request
+
+
+@pytest.mark.parametrize("text, stream, level", [
+    ("speak louder", "speech", "up"),
+    ("Could you talk quieter, please?", "speech", "down"),
+    ("I can't hear you", "speech", "up"),
+    ("you're too loud", "speech", "down"),
+    ("volume up", "master", "up"),
+    ("turn down the volume a bit", "master", "down"),
+    ("set the volume to 60%", "master", "60"),
+    ("volume 7", "master", "70"),
+    ("volume max", "master", "100"),
+    ("set the music volume to 30 percent", "media", "30"),
+    ("turn up the music", "media", "up"),
+    ("mute the chimes", "earcons", "mute"),
+    ("turn the sound effects back on", "earcons", "unmute"),
+])
+def test_parse(text, stream, level):
+    assert parse_volume_request(text) == VolumeRequest(stream, level)
+
+
+@pytest.mark.parametrize("text", [
+    "set a reminder to turn the music down",
+    "turn down the meeting with Bob",
+    "mute",
+    "what's the weather like",
+    "",
+])
+def test_not_volume(text):
+    assert parse_volume_request(text) is None
+
+
+class SavedConfig(Config):
+    saves: int = 0
+
+    def save_to_file(self, config_path=None):
+        self.saves += 1
+
+
+class TestSettings:
+    def test_from_config(self):
+        settings = VolumeSettings.from_config(Config(volume=0.5, speech_volume=3.0, earcon_volume=0.2))
+        assert (settings.master, settings.speech, settings.earcons) == (0.5, 1.5, 0.2)
+        assert settings.gain("earcons") == pytest.approx(0.1)
+
+    def test_whisper(self, monkeypatch):
+        settings = VolumeSettings(speech=0.8, whisper_level=0.5)
+        monkeypatch.setattr(volume.time, "monotonic", lambda: 100.0)
+        settings.whisper("Your call with Bob moved to three")  # 7 words: 2.8s to say, plus 2
+        assert settings.gain("speech") == pytest.approx(0.4)
+        assert settings.gain("media") == pytest.approx(0.8)
+        monkeypatch.setattr(volume.time, "monotonic", lambda: 105.0)
+        assert settings.gain("speech") == pytest.approx(0.8)
+
+    def test_change(self):
+        settings = VolumeSettings(speech=0.5)
+        assert settings.change("speech", "up") == 0.5 + STEP
+        assert settings.change("speech", "down") == 0.5
+        assert settings.change("master", "up") == 1.0  # Master stops at 100%
+        assert settings.change("media", "40%") == 0.4
+        assert settings.change("media", "mute") == 0.0
+        assert settings.change("media", "unmute") == 0.8
+        with pytest.raises(ValueError):
+            settings.change("media", "loud-ish")
+
+    def test_saves(self):
+        config = SavedConfig()
+        settings = VolumeSettings.from_config(config)
+        settings.change("speech", "up")
+        assert config.speech_volume == 1.0 + STEP
+        assert config.saves == 1
+
+
+def test_set_volume_tool(monkeypatch):
+    settings = VolumeSettings()
+    monkeypatch.setattr(volume, "_settings", settings)
+    assert set_volume("voice", "60") == "✓ Speech volume 60%"
+    assert settings.speech == 0.6
+    assert set_volume("music", "mute") == "✓ Media volume muted"
+    assert set_volume("radio", "up").startswith("✗ Unknown stream")
+    assert set_volume("master", "loud-ish").startswith("✗ Unknown level")
@@ -0,0 +1,82 @@
+"""
+Tests for voice pipeline warm-up (assistant/warmup.py).
+
+Covers:
+- Due once models load, then only after idle_after without interactions or warm-ups
+- Skipped when disabled or saving power (battery, low-power profile)
+- Steps run in order; failures and timeouts are recorded without stopping the rest
+"""
+
+import asyncio
+
+from assistant import warmup
+from assistant.power import PowerManager, PowerState
+from assistant.warmup import Warmup
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self):
+        return self.now
+
+
+def runner(**kwargs):
+    clock = FakeClock()
+    w = Warmup(idle_after=600, power_saving=lambda: None, clock=clock, **kwargs)
+    calls = []
+
+    async def step():
+        calls.append(clock.now)
+        return True
+
+    w.add_step("stt", step)
+    return w, clock, calls
+
+
+def test_due_after_load_then_after_idle():
+    w, clock, calls = runner()
+    assert w.due()
+    asyncio.run(w.maybe_run())
+    assert calls == [0.0] and not w.due()
+    clock.now = 500
+    w.touch()  # The user said something: warm now, the idle clock restarts
+    clock.now = 1000
+    assert asyncio.run(w.maybe_run()) is None
+    clock.now = 1100
+    assert [str(r) for r in asyncio.run(w.maybe_run())] == ["stt 0.0s"]
+    assert calls == [0.0, 1100]
+
+
+def test_skipped_when_disabled_or_saving_power(monkeypatch):
+    w, _, _ = runner(enabled=False)
+    assert not w.due() and w.skip_reason() == "disabled"
+    assert not Warmup(power_saving=lambda: "on battery").due()
+
+    manager = PowerManager("auto", reader=lambda: PowerState(on_battery=True))
+    monkeypatch.setattr(warmup, "get_power_manager", lambda: manager)
+    assert warmup._power_saving() is None
+    manager.sample(force=True)
+    assert warmup._power_saving() == "on battery"
+    monkeypatch.setattr(warmup, "get_power_manager", lambda: PowerManager("low_power"))
+    assert warmup._power_saving() == "low power"
+
+
+def test_failures_dont_stop_the_rest():
+    w, _, calls = runner(step_timeout=0.01)
+
+    async def broken():
+        raise RuntimeError("voice busy")
+
+    async def nothing():
+        return False
+
+    async def stuck():
+        await asyncio.sleep(1)
+
+    w.steps = {"tts": broken, "llm": nothing, "slow": stuck, **w.steps}
+    results = asyncio.run(w.run())
+    assert [str(r) for r in results] == ["tts failed (voice busy)", "llm skipped", "slow failed (timed out)",
+                                         "stt 0.0s"]
+    assert len(calls) == 1 and not w.running and w.last_run is not None
@@ -0,0 +1,126 @@
+"""
+Tests for the web search tool (assistant/web_search.py).
+
+Covers:
+- Off by default: no provider, and the tool says so instead of searching
+- Provider config: searxng needs a URL, brave a key, unknown names are rejected
+- SearxNG and Brave responses parsed into results (highlighting stripped, long snippets cut)
+- Spoken source names for citations, and the numbered context the model answers from
+"""
+
+import asyncio
+
+import pytest
+
+from assistant import tools
+from assistant.config import Config
+from assistant.web_search import (SearchResult, StaticSearch, WebSearch, WebSearchError, get_web_search,
+                                  set_web_search, source_name)
+
+
+@pytest.mark.parametrize("url, name", [
+    ("https://blog.rust-lang.org/2026/09/18/Rust-1.90.0.html", "the Rust blog"),
+    ("https://en.wikipedia.org/wiki/Rust_(programming_language)", "Wikipedia"),
+    ("https://www.bbc.co.uk/news/technology", "the BBC"),
+    ("https://news.bbc.co.uk/x", "the BBC"),
+    ("https://www.theverge.com/tech", "theverge.com"),
+    ("not a url", "the web"),
+])
+def test_source_names(url, name):
+    assert source_name(url) == name
+
+
+def test_off_by_default():
+    assert Config().web_search == "none"
+    assert WebSearch.from_config(Config()) is None
+    set_web_search(None)
+    assert asyncio.run(tools.web_search("rust news")).startswith("✗ Web search is off")
+
+
+@pytest.mark.parametrize("settings, error", [
+    ({"web_search": "searxng"}, "web_search_url"),
+    ({"web_search": "brave"}, "web_search_key"),
+    ({"web_search": "bing"}, "Unknown web_search provider"),
+])
+def test_misconfigured(settings, error):
+    with pytest.raises(WebSearchError, match=error):
+        WebSearch.from_config(Config(**settings))
+
+
+class Response:
+    def __init__(self, body, status_code=200):
+        self.body, self.status_code = body, status_code
+
+    def raise_for_status(self):
+        pass
+
+    def json(self):
+        return self.body
+
+
+def _fake_httpx(monkeypatch, body, status_code=200):
+    requests = []
+
+    class Client:
+        def __init__(self, **kwargs):
+            self.headers = kwargs.get("headers", {})
+
+        async def __aenter__(self):
+            return self
+
+        async def __aexit__(self, *exc):
+            return False
+
+        async def get(self, url, params=None):
+            requests.append((url, params, self.headers))
+            return Response(body, status_code)
+
+    import httpx
+    monkeypatch.setattr(httpx, "AsyncClient", Client)
+    return requests
+
+
+def test_searxng(monkeypatch):
+    requests = _fake_httpx(monkeypatch, {"results": [
+        {"title": "Announcing <strong>Rust</strong> 1.90", "url": "https://blog.rust-lang.org/1.90",
+         "content": "The Rust team is happy to announce " + "word " * 100, "publishedDate": "2026-09-18T00:00:00"},
+        {"title": "No link"},
+    ]})
+    search = WebSearch.from_config(Config(web_search="searxng", web_search_url="http://localhost:8888/"))
+    results = asyncio.run(search.search("  rust   1.90 "))
+    assert requests[0][:2] == ("http://localhost:8888/search", {"q": "rust 1.90", "format": "json"})
+    assert [(r.title, r.source) for r in results] == [("Announcing Rust 1.90", "the Rust blog")]
+    assert len(results[0].snippet) <= 301 and results[0].snippet.endswith("…")
+
+
+def test_searxng_without_json(monkeypatch):
+    _fake_httpx(monkeypatch, {}, status_code=403)
+    search = WebSearch.from_config(Config(web_search="searxng", web_search_url="http://localhost:8888"))
+    with pytest.raises(WebSearchError, match="search.formats"):
+        asyncio.run(search.search("anything"))
+
+
+def test_brave(monkeypatch):
+    requests = _fake_httpx(monkeypatch, {"web": {"results": [
+        {"title": "Rust (programming language)", "url": "https://en.wikipedia.org/wiki/Rust", "description": "A language"},
+    ]}})
+    search = WebSearch.from_config(Config(web_search="brave", web_search_key="secret", web_search_results=3))
+    results = asyncio.run(search.search("rust"))
+    assert requests[0][1] == {"q": "rust", "count": 3} and requests[0][2]["X-Subscription-Token"] == "secret"
+    assert results == [SearchResult("Rust (programming language)", "https://en.wikipedia.org/wiki/Rust", "A language")]
+
+
+def test_tool_gives_the_model_citable_results():
+    set_web_search(WebSearch(StaticSearch({"rust": [
+        SearchResult("Announcing Rust 1.90", "https://blog.rust-lang.org/1.90", "Faster builds.", "2026-09-18"),
+        SearchResult("Rust", "https://en.wikipedia.org/wiki/Rust"),
+    ]})))
+    try:
+        context = asyncio.run(tools.web_search("what's new in Rust"))
+        assert "[1] Announcing Rust 1.90 (the Rust blog, 2026-09-18) https://blog.rust-lang.org/1.90" in context
+        assert "    Faster builds." in context and "[2] Rust (Wikipedia)" in context
+        assert "according to the Rust blog" in context
+        assert asyncio.run(tools.web_search("cheese")) == 'No web results for "cheese".'
+        assert get_web_search() is not None
+    finally:
+        set_web_search(None)
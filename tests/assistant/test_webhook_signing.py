@@ -0,0 +1,68 @@
+"""
+Tests for the Media Streams handshake signatures (assistant/webhook_signing.py).
+
+Covers:
+- Twilio's signature with the primary or the secondary (rotating) auth token; each CallSid once
+- xSwarm signatures: verified once, stale timestamps, replays and retired secrets rejected
+- Unconfigured tokens and secrets refuse everything; both websockets process_request APIs get a 401
+"""
+
+import types
+from http import HTTPStatus
+
+from assistant.webhook_signing import WINDOW_SECONDS, WebhookVerifier, sign, twilio_signature
+
+URL = "wss://calls.example.com"
+NOW = 1_792_000_000.0
+
+
+def verifier(**kwargs):
+    kwargs.setdefault("twilio_tokens", ["primary", "secondary"])
+    kwargs.setdefault("secrets", ["new-secret", "old-secret"])
+    return WebhookVerifier(public_url=URL + "/", clock=lambda: NOW, **kwargs)
+
+
+def test_twilio():
+    check = verifier()
+    for token in ("primary", "secondary"):
+        assert check.check("/stream", {"X-Twilio-Signature": twilio_signature(token, URL + "/stream")}) == "verified"
+    stolen, elsewhere = twilio_signature("stolen", URL + "/stream"), twilio_signature("primary", URL + "/stream")
+    assert check.check("/stream", {"X-Twilio-Signature": stolen}) == "bad_signature"
+    assert check.check("/other", {"X-Twilio-Signature": elsewhere}) == "bad_signature"
+    assert check.check("/stream", {}) == "missing"
+
+    assert check.first_call("CA1") and check.first_call("CA2")
+    assert not check.first_call("CA1")
+    assert check.stats["replayed"] == 1
+
+
+def test_xswarm():
+    check = verifier()
+    headers = sign("old-secret", "/stream", now=NOW)
+    assert check.check("/stream", headers) == "verified"
+    assert check.check("/stream", headers) == "replayed"
+    assert check.check("/other", sign("new-secret", "/stream", now=NOW)) == "bad_signature"
+    assert check.check("/stream", sign("new-secret", "/stream", now=NOW - WINDOW_SECONDS - 1)) == "stale"
+    assert check.check("/stream", {"X-Xswarm-Timestamp": str(int(NOW))}) == "missing"
+
+    retired = verifier(secrets=["new-secret"])
+    assert retired.check("/stream", sign("old-secret", "/stream", now=NOW)) == "bad_signature"
+
+
+def test_unconfigured_and_process_request():
+    bare = WebhookVerifier(clock=lambda: NOW)
+    assert bare.check("/stream", {"X-Twilio-Signature": "anything"}) == "unconfigured"
+    assert bare.check("/stream", sign("new-secret", "/stream", now=NOW)) == "unconfigured"
+
+    status, _, body = bare.process_request("/stream", {})
+    assert status == HTTPStatus.UNAUTHORIZED and b"unconfigured" in body
+
+    class Connection:
+        def respond(self, status, text):
+            return status, text
+
+    request = types.SimpleNamespace(path="/stream", headers={})
+    assert verifier().process_request(Connection(), request) == (HTTPStatus.UNAUTHORIZED, "Webhook signature missing\n")
+    signed = types.SimpleNamespace(path="/stream", headers=sign("new-secret", "/stream", now=NOW))
+    assert verifier().process_request(Connection(), signed) is None
+    assert bare.stats["unconfigured"] == 3
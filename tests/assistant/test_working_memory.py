@@ -0,0 +1,88 @@
+"""
+Tests for the conversation's working memory (assistant/working_memory.py).
+
+Covers:
+- The window keeps the latest turns, fewer when they're over the token budget, and always the latest
+- Turns leaving the window are folded into the synopsis, which is built on the previous one
+- Without a working AI the synopsis lists the user's earlier requests
+- A conversation's own window overrides the config; the config is read live otherwise
+"""
+
+import asyncio
+import types
+
+from assistant.voice_config import ConfigChanger
+from assistant.working_memory import FALLBACK_HEADING, Window, WorkingMemory, window_start
+
+
+def conversation(turns, words=1):
+    messages = []
+    for n in range(turns):
+        messages.append({"role": "user", "content": f"question {n} " + "word " * words})
+        messages.append({"role": "assistant", "content": f"answer {n}"})
+    return messages
+
+
+def test_window_start():
+    messages = conversation(5)
+    assert window_start(messages, Window(turns=2, tokens=0)) == 6
+    assert window_start(messages, Window(turns=0, tokens=0)) == 0
+    assert window_start([{"role": "system", "content": "hi"}], Window(turns=1)) == 0
+
+    long = conversation(5, words=100)  # About 130 tokens a turn
+    assert window_start(long, Window(turns=5, tokens=300)) == 6
+    assert window_start(long, Window(turns=5, tokens=10)) == 8  # The latest turn stays, even over budget
+
+
+def test_synopsis_builds_on_the_previous_one():
+    calls = []
+
+    async def summarize(messages, max_tokens):
+        calls.append(messages[1]["content"])
+        return f"synopsis {len(calls)}"
+
+    memory = WorkingMemory(types.SimpleNamespace(working_memory_turns=2, working_memory_tokens=0), summarize)
+    messages = conversation(2)
+    assert not asyncio.run(memory.update(messages))
+    assert memory.visible(messages) == (messages, "")
+
+    messages += conversation(1)
+    assert asyncio.run(memory.update(messages))
+    assert calls[0] == "Older messages:\nUser: question 0 word \nAssistant: answer 0"
+    assert memory.visible(messages) == (messages[2:], "synopsis 1")
+
+    messages += conversation(1)
+    assert asyncio.run(memory.update(messages))
+    assert calls[1].startswith("Synopsis so far:\nsynopsis 1\n\nOlder messages:\nUser: question 1")
+    assert memory.folded == 4 and memory.synopsis == "synopsis 2"
+
+    memory.reset()
+    assert memory.visible(messages) == (messages, "")
+
+
+def test_fallback_lists_earlier_requests():
+    async def broken(messages, max_tokens):
+        raise RuntimeError("offline")
+
+    memory = WorkingMemory(types.SimpleNamespace(working_memory_turns=1, working_memory_tokens=0), broken)
+    messages = conversation(3)
+    asyncio.run(memory.update(messages))
+    assert memory.synopsis == f"{FALLBACK_HEADING}\n- question 0 word\n- question 1 word"
+
+    memory.summarize = None
+    messages += conversation(1)
+    asyncio.run(memory.update(messages))
+    assert memory.synopsis.count(FALLBACK_HEADING) == 1
+    assert memory.synopsis.splitlines()[1:] == ["- question 0 word", "- question 1 word", "- question 2 word"]
+
+
+def test_window_override_and_hot_change():
+    config = types.SimpleNamespace(working_memory_turns=20, working_memory_tokens=8000)
+    memory = WorkingMemory(config)
+    ConfigChanger(config, save=False).change("memory_window", "5")
+    assert config.working_memory_turns == 5 and memory.window == Window(5, 8000)
+
+    other = WorkingMemory(config)
+    assert other.set_window(turns=2) == Window(2, 8000)
+    config.working_memory_tokens = 100
+    assert other.window == Window(2, 8000) and memory.window == Window(5, 100)
@@ -0,0 +1,104 @@
+"""
+Tests for WebSocket framing and bandwidth metering (assistant/ws_framing.py).
+
+Covers:
+- Audio frames round-trip, several to a message; truncated or foreign data is refused
+- Negotiation keeps only formats both sides know; an old client's offer is empty
+- The meter's totals, savings and rate over its window
+- The companion server counts its traffic and whether deflate was negotiated
+"""
+
+import asyncio
+import json
+import types
+
+import pytest
+
+from assistant import ws_framing
+from assistant.pairing import CompanionServer, DeviceRegistry
+from assistant.ws_framing import (
+    HEADER, AudioFrame, BandwidthMeter, FrameError, FrameSession, compression, decode_frames, encode_frames,
+    negotiate,
+)
+
+
+def test_frames_round_trip():
+    frames = [AudioFrame("mulaw", 8000, 0, b"\xff" * 160), AudioFrame("opus", 48000, 1, b"\x01\x02")]
+    data = encode_frames(frames)
+    assert len(data) == 2 * HEADER.size + 162
+    assert decode_frames(data) == frames
+    assert decode_frames(b"") == []
+
+    with pytest.raises(FrameError, match="payload"):
+        decode_frames(data[:-1])
+    with pytest.raises(FrameError, match="header"):
+        decode_frames(data[:HEADER.size - 1])
+    with pytest.raises(FrameError, match="audio-v1"):
+        decode_frames(b'{"event": "media"}' + bytes(HEADER.size))
+    with pytest.raises(FrameError, match="codec"):
+        AudioFrame("flac", 44100, 0, b"").encode()
+
+
+def test_negotiate():
+    assert negotiate(["audio-v2", "audio-v1"]) == ["audio-v1"]
+    assert negotiate(None) == [] and negotiate("audio-v1") == []
+    assert compression(types.SimpleNamespace(ws_compression=False)) is None
+    assert compression(None) == "deflate"
+
+    session = FrameSession("phone", meter=BandwidthMeter())
+    assert not session.audio
+    session.accept(["audio-v1"])
+    first, second = (session.audio_message("mulaw", 8000, b"\x00" * 160) for _ in range(2))
+    assert [f.sequence for f in decode_frames(first + second)] == [0, 1]
+
+
+def test_meter():
+    now = [0.0]
+    meter = BandwidthMeter(window=10, clock=lambda: now[0])
+    deflated = types.SimpleNamespace(extensions=[types.SimpleNamespace(name="permessage-deflate")])
+    session = FrameSession("phone", deflated, meter=meter)
+    session.received("é" * 50)  # Counted in bytes, not characters
+    session.sent(b"x" * 176, instead_of=290)
+    assert meter.totals["phone"] == {"sent": 176, "received": 100, "saved": 114, "messages": 2,
+                                     "connections": 1, "deflate": 1}
+    assert meter.rate() == pytest.approx(27.6)
+    now[0] = 11
+    meter.record("companion", sent=50)
+    assert meter.rate() == 5
+    assert meter.summary()["rate"] == 5
+
+
+class FakeSocket:
+    remote_address = ("10.0.0.5", 50000)
+    extensions = []
+
+    def __init__(self, messages):
+        self.messages = messages
+        self.sent = []
+
+    async def send(self, text):
+        self.sent.append(text)
+
+    async def close(self, code=1000, reason=""):
+        pass
+
+    def __aiter__(self):
+        return self._iterate()
+
+    async def _iterate(self):
+        for message in self.messages:
+            yield message
+
+
+def test_companion_traffic_is_metered(tmp_path, monkeypatch):
+    meter = BandwidthMeter()
+    monkeypatch.setattr(ws_framing, "_meter", meter)
+    server = CompanionServer(DeviceRegistry(tmp_path / "devices.json"), lambda text, device: None, lambda: {})
+    socket = FakeSocket([json.dumps({"type": "status"}), "not json"])
+    asyncio.run(server._serve(socket))
+
+    assert [json.loads(reply)["type"] for reply in socket.sent] == ["error", "error"]
+    totals = meter.totals["companion"]
+    assert totals["received"] == len(socket.messages[0]) + len("not json")
+    assert totals["sent"] == sum(len(reply) for reply in socket.sent)
+    assert (totals["connections"], totals["deflate"]) == (1, 0)
@@ -0,0 +1,192 @@
+"""
+Tests for the xswarm-client package (packages/client).
+
+Covers:
+- ControlClient and AsyncControlClient against the assistant's own ControlServer
+- ControlError when the assistant isn't running or refuses a command
+- ServerClient queueing commands (idempotent per key) against MockServer
+- Server errors and unreachable servers raising ServerError
+- Calendar change feed paging and reset
+- Models ignoring fields they don't know
+"""
+
+import asyncio
+import sys
+import threading
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parents[2] / "packages" / "client"))
+
+from xswarm_client import (AsyncControlClient, CalendarChange, ControlClient, ControlError, ServerClient,
+                           ServerError, Status)
+from assistant.control import ControlServer
+from harness import MockServer
+
+STATUS = {"mic": "wake_word", "muted": False, "voice": True, "dnd": False, "egress": [],
+          "next": "Dentist · today 16:00", "message": "xSwarm: mic waiting for wake word"}
+
+
+class Assistant:
+    """The dashboard's control handlers, minus the dashboard."""
+
+    def __init__(self):
+        self.muted = False
+
+    def handlers(self):
+        return {
+            "status": lambda request: {**STATUS, "muted": self.muted},
+            "mute": self.mute,
+            "next": lambda request: {"message": STATUS["next"]},
+        }
+
+    def mute(self, request):
+        self.muted = not self.muted if request.get("on") is None else bool(request["on"])
+        return {"message": "✓ Mic muted" if self.muted else "✓ Mic on", "muted": self.muted}
+
+
+class RunningServer:
+    """A ControlServer on its own event loop thread, for the blocking client."""
+
+    def __init__(self, path, handlers):
+        self.loop = asyncio.new_event_loop()
+        self.server = ControlServer(handlers, path)
+        self.thread = threading.Thread(target=self.loop.run_forever, daemon=True)
+
+    def __enter__(self):
+        self.thread.start()
+        assert asyncio.run_coroutine_threadsafe(self.server.start(), self.loop).result(5)
+        return self
+
+    def __exit__(self, *exc):
+        async def stop():
+            await asyncio.sleep(0.05)  # Let the server see the last client hang up
+            self.server.close()
+
+        asyncio.run_coroutine_threadsafe(stop(), self.loop).result(5)
+        self.loop.call_soon_threadsafe(self.loop.stop)
+        self.thread.join(5)
+        self.loop.close()
+
+
+def test_control_client(tmp_path):
+    path = tmp_path / "control.sock"
+    assistant = Assistant()
+    client = ControlClient(path)
+    assert not client.running()
+
+    with RunningServer(path, assistant.handlers()):
+        assert client.running()
+        status = client.status()
+        assert isinstance(status, Status)
+        assert (status.mic, status.muted, status.next) == ("wake_word", False, "Dentist · today 16:00")
+        assert client.mute() is True and assistant.muted
+        assert client.mute(False) is False
+        assert client.next_appointment() == "Dentist · today 16:00"
+        try:
+            client.dnd(True)
+            assert False, "dnd isn't handled by this assistant"
+        except ControlError as e:
+            assert str(e) == "Unknown command 'dnd'"
+
+    try:
+        client.status()
+        assert False, "the assistant has stopped"
+    except ControlError as e:
+        assert str(e) == "xSwarm isn't running"
+
+
+def test_async_control_client(tmp_path):
+    path = tmp_path / "control.sock"
+    assistant = Assistant()
+
+    async def run():
+        server = ControlServer(assistant.handlers(), path)
+        assert await server.start()
+        try:
+            client = AsyncControlClient(path)
+            assert (await client.status()).voice
+            assert await client.mute(True) is True
+            return (await client.send("next"))["message"]
+        finally:
+            await asyncio.sleep(0.05)  # Let the server see the last client hang up
+            server.close()
+
+    assert asyncio.run(run()) == "Dentist · today 16:00"
+    assert assistant.muted
+
+
+def test_queue_commands_idempotently():
+    with MockServer() as server:
+        client = ServerClient(server.url, "token")
+        command = client.speak("Deploy finished", idempotency_key="deploy-41")
+        assert (command.type, command.args, command.pending) == ("speak", {"text": "Deploy finished",
+                                                                           "priority": "normal"}, True)
+        again, duplicate = client.queue_command("speak", {"text": "Deploy finished"}, idempotency_key="deploy-41")
+        assert duplicate and again.id == command.id
+        client.refresh_config()
+        assert [c.type for c in client.pending_commands()] == ["speak", "refresh_config"]
+
+        assert server.requests_to("/api/commands", "GET")[0].query == {"limit": "20"}  # The token names the user
+        request = server.requests_to("/api/commands", "POST")[0]
+        assert request.headers["Authorization"] == "Bearer token"
+        assert request.body["idempotency_key"] == "deploy-41"
+
+        try:
+            client.queue_command("reboot")
+            assert False, "unknown types are refused before reaching the server"
+        except ValueError:
+            pass
+
+
+def test_server_errors():
+    with MockServer() as server:
+        server.fail("POST", r"^/api/commands$", 503)
+        client = ServerClient(server.url, "token")
+        try:
+            client.speak("Hello")
+            assert False, "the server failed"
+        except ServerError as e:
+            assert (e.status, e.message) == (503, "Injected failure (503)")
+        url = server.url
+
+    try:
+        ServerClient(url, timeout=1).speak("Hello")
+        assert False, "the server is gone"
+    except ServerError as e:
+        assert e.status == 0 and "Can't reach" in e.message
+
+
+class Opener:
+    """Stands in for urlopen with canned replies, one per request."""
+
+    def __init__(self, *replies):
+        self.replies = list(replies)
+        self.urls = []
+
+    def __call__(self, request, data, timeout):
+        import io
+        import json
+        self.urls.append(request.full_url)
+        return io.BytesIO(json.dumps(self.replies.pop(0)).encode())
+
+
+def test_calendar_changes_follow_pages():
+    change = {"seq": 3, "entity": "appointment", "id": "apt-1", "op": "upsert", "data": {"title": "Dentist"}}
+    opener = Opener(
+        {"changes": [], "cursor": 0, "has_more": True, "reset": True},
+        {"changes": [change], "cursor": 3, "has_more": True},
+        {"changes": [{"seq": 5, "entity": "reminder", "id": "rem-1", "op": "delete", "data": None}],
+         "cursor": 5, "has_more": False},
+    )
+    client = ServerClient("http://test/", user_id="u1", opener=opener)
+    changes, cursor = client.calendar_changes(since=99)
+    assert cursor == 5
+    assert changes[0] == CalendarChange(3, "appointment", "apt-1", "upsert", {"title": "Dentist"})
+    assert [(c.entity, c.op) for c in changes] == [("appointment", "upsert"), ("reminder", "delete")]
+    assert [url.split("since=")[1] for url in opener.urls] == ["99", "0", "3"]
+
+
+def test_models_ignore_unknown_fields():
+    status = Status.from_dict({**STATUS, "battery": 80})
+    assert status.mic == "wake_word" and not hasattr(status, "battery")
+    assert Status.from_dict({}).mic == "off"
@@ -0,0 +1,107 @@
+"""
+Tests for xswarm_client.core (the assistant's dates and memory, for notebooks).
+
+Covers:
+- Natural-language dates and times read as the assistant reads them
+- schedule() turning "tomorrow at 4pm" into a server appointment
+- Memory search embedding the query and returning MemoryHits, also inside a running event loop
+"""
+
+import asyncio
+import io
+import json
+import sys
+from datetime import date, datetime
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).parents[2] / "packages" / "client"))
+
+from xswarm_client import ServerClient
+from xswarm_client.core import MemoryHit, MemorySearch, parse_date, parse_datetime, parse_time, schedule
+
+TODAY = date(2026, 10, 16)  # A Friday
+
+
+def test_parse_dates_and_times():
+    assert parse_date("next tuesday", TODAY) == date(2026, 10, 20)
+    assert parse_date("end of the month", TODAY) == date(2026, 10, 31)
+    assert parse_date("14/03/2027", TODAY, date_order="dmy") == date(2027, 3, 14)
+    assert parse_date("sometime", TODAY) is None
+    assert parse_time("half past three") == "15:30"
+    assert parse_datetime("at 4pm tomorrow", today=TODAY) == datetime(2026, 10, 17, 16, 0)
+
+
+class Opener:
+    """Stands in for urlopen: records each request and answers with the appointment it was sent."""
+
+    def __init__(self):
+        self.requests = []
+
+    def __call__(self, request, data, timeout):
+        body = json.loads(data)
+        self.requests.append((request.get_method(), request.full_url, body))
+        return io.BytesIO(json.dumps({"success": True, "appointment": {"id": "apt-1", **body}}).encode())
+
+
+def test_schedule_natural_language():
+    opener = Opener()
+    client = ServerClient("http://test", "token", user_id="u1", opener=opener)
+    appointment = schedule(client, "Dentist", "tomorrow at 4pm", minutes=45, today=TODAY, location="Main St")
+
+    method, url, body = opener.requests[0]
+    assert (method, url) == ("POST", "http://test/api/calendar/appointments")
+    start, end = datetime.fromisoformat(body["start_time"]), datetime.fromisoformat(body["end_time"])
+    assert start.replace(tzinfo=None) == datetime(2026, 10, 17, 16, 0)
+    assert (end - start).total_seconds() == 45 * 60
+    assert (body["user_id"], body["location"]) == ("u1", "Main St")
+    assert (appointment.id, appointment.title) == ("apt-1", "Dentist")
+
+    try:
+        schedule(client, "Dentist", "whenever", today=TODAY)
+        assert False, "not a date"
+    except ValueError:
+        pass
+
+
+class FakeEmbedder:
+    def __init__(self):
+        self.embedded = []
+
+    def get_dimension(self):
+        return 3
+
+    async def embed(self, text):
+        self.embedded.append(text)
+        return [0.1, 0.2, 0.3]
+
+
+class FakeStore:
+    def __init__(self):
+        self.searches = []
+
+    def search(self, embedding, limit, role):
+        self.searches.append((embedding, limit, role))
+        return [{"id": 7, "content": "Budget review moved to Q1", "role": "user", "session_id": "s1",
+                 "timestamp": "2026-10-01T10:00:00", "metadata": {}, "distance": 0.2, "similarity": 0.8}]
+
+    def get_recent(self, limit):
+        return [{"id": 8, "content": "Hi", "role": "user", "session_id": None, "timestamp": "", "metadata": {}}]
+
+    def count(self):
+        return 2
+
+
+def test_memory_search():
+    embedder, store = FakeEmbedder(), FakeStore()
+    memory = MemorySearch(embedder=embedder, store=store)
+    hits = memory.search("budget", limit=5, role="user")
+    assert hits == [MemoryHit(7, "Budget review moved to Q1", "user", "2026-10-01T10:00:00", 0.8, "s1", {})]
+    assert store.searches == [([0.1, 0.2, 0.3], 5, "user")]
+    assert [hit.content for hit in memory.recent()] == ["Hi"]
+    assert len(memory) == 2
+
+    async def in_notebook():
+        return memory.search("budget again")  # Jupyter cells run inside an event loop
+
+    assert asyncio.run(in_notebook())[0].id == 7
+    assert embedder.embedded == ["budget", "budget again"]
@@ -0,0 +1,45 @@
+"""
+Shared fixtures for Python tests.
+
+Integration fixtures come from the harness package (tests/harness/):
+
+- mock_server: a running MockServer; point clients at mock_server.url
+- fake_audio: a FakeAudioIO with nothing scripted yet
+- scripted_supervisor: a ScriptedSupervisor that plays into fake_audio
+- notification_policy: install a NotificationPolicy for one test
+"""
+
+import pytest
+
+from harness import FakeAudioIO, MockServer, ScriptedSupervisor
+
+
+@pytest.fixture
+def mock_server():
+    with MockServer() as server:
+        yield server
+
+
+@pytest.fixture
+def fake_audio():
+    return FakeAudioIO()
+
+
+@pytest.fixture
+def scripted_supervisor(fake_audio):
+    return ScriptedSupervisor(audio_io=fake_audio)
+
+
+@pytest.fixture
+def notification_policy():
+    """Call with a Config to install its policy; the previous policy is restored afterwards."""
+    from assistant import notifications
+
+    previous = notifications._policy
+
+    def install(config):
+        notifications.set_notification_policy(notifications.NotificationPolicy(config))
+        return notifications.get_notification_policy()
+
+    yield install
+    notifications._policy = previous
@@ -0,0 +1,130 @@
+"""
+E2E test: a calendar reminder from planner to delivery.
+
+Runs the event-reminder flow against the integration harness (mock
+server, fake audio, scripted supervisor) instead of a server, Moshi and a
+sound card:
+
+planner event -> synced to the server -> reminder due -> formatted ->
+activity line + spoken announcement (audio played) -> marked delivered
+on the server.
+"""
+
+import asyncio
+from datetime import date, datetime
+
+from assistant.api_client import ApiPolicy
+from assistant.config import Config
+from assistant.planner import PlannerData
+from assistant.reminders import deliver_reminder, due_reminders, format_reminder
+from assistant.scheduler_client import CalendarSync, SchedulerClient
+
+DAY = date(2026, 10, 14)
+USER = "u1"
+
+
+def _planner(tmp_path):
+    planner = PlannerData(tmp_path / "planner")
+    planner.add_calendar_event("Standup", "2026-10-14T09:00:00", "2026-10-14T09:15:00",
+                               reminder_minutes=15, tags=["work"])
+    planner.add_calendar_event("Dentist", "2026-10-14T16:00:00", "2026-10-14T17:00:00", reminder_minutes=30)
+    return planner
+
+
+def _events(planner):
+    return planner.get_calendar_events(DAY.isoformat(), DAY.isoformat())
+
+
+def _no_desktop(*args, **kwargs):
+    return False
+
+
+class TestReminderFlow:
+    def test_due_reminder_formatted_and_spoken(self, tmp_path, scripted_supervisor, fake_audio):
+        planner = _planner(tmp_path)
+        reminded, activity = set(), []
+
+        async def tick(now):
+            delivered = []
+            for reminder in due_reminders(_events(planner), now, reminded):
+                delivered.append(await deliver_reminder(reminder, activity=activity.append, notify=_no_desktop,
+                                                        announcer=scripted_supervisor))
+            return delivered
+
+        assert asyncio.run(tick(datetime(2026, 10, 14, 8, 30))) == []  # Too early
+        delivered = asyncio.run(tick(datetime(2026, 10, 14, 8, 50)))
+        assert delivered == [{"activity": True, "desktop": False, "speech": True}]
+        assert activity == ["⏰ Standup in 10 minutes"]
+        assert scripted_supervisor.spoken == ["Reminder: Standup in ten minutes."]
+        assert fake_audio.played_seconds() > 0
+
+        # The job runs every minute; each occurrence is reminded once
+        assert asyncio.run(tick(datetime(2026, 10, 14, 8, 51))) == []
+
+    def test_quiet_hours_hold_back_speech(self, tmp_path, scripted_supervisor, notification_policy):
+        notification_policy(Config(quiet_hours_enabled=True, quiet_hours_start="15:00", quiet_hours_end="18:00"))
+        scripted_supervisor.clock = lambda: datetime(2026, 10, 14, 15, 45)
+        planner, activity = _planner(tmp_path), []
+
+        async def scenario():
+            reminders = due_reminders(_events(planner), datetime(2026, 10, 14, 15, 45), set())
+            return [await deliver_reminder(r, activity=activity.append, notify=_no_desktop,
+                                           announcer=scripted_supervisor) for r in reminders]
+
+        assert asyncio.run(scenario()) == [{"activity": True, "desktop": False, "speech": False}]
+        assert activity == ["⏰ Dentist in 15 minutes"]
+        assert scripted_supervisor.spoken == []
+        assert scripted_supervisor.suppressed == [("Reminder: Dentist in fifteen minutes.", "quiet hours")]
+
+    def test_synced_to_server_and_marked_delivered(self, tmp_path, mock_server, scripted_supervisor):
+        planner = _planner(tmp_path)
+        client = SchedulerClient(mock_server.url, policy=ApiPolicy(backoff_base=0.01))
+        sync = CalendarSync(planner, client, USER, storage_dir=tmp_path / "sync")
+        server_reminder = mock_server.add_reminder(USER, "Standup", "2026-10-14T08:45:00")
+        # First status update hits a flaky server; the client retries
+        mock_server.fail("PUT", r"/reminders/batch$", status=503, times=1)
+
+        async def scenario():
+            try:
+                counts = await sync.push(days=1, today=DAY)
+                for reminder in due_reminders(_events(planner), datetime(2026, 10, 14, 8, 50), set()):
+                    result = await deliver_reminder(reminder, notify=_no_desktop, announcer=scripted_supervisor)
+                    if result["speech"]:
+                        await client.update_reminders(USER, [{"id": server_reminder["id"], "completed": True}])
+                return counts
+            finally:
+                await client.close()
+
+        assert asyncio.run(scenario()) == {"created": 2, "deleted": 0, "failed": 0}
+        assert sorted(a["title"] for a in mock_server.appointments.values()) == ["Dentist", "Standup"]
+        assert mock_server.reminders[server_reminder["id"]]["completed"] is True
+        assert len(mock_server.requests_to("/api/calendar/reminders/batch", "PUT")) == 2
+        assert scripted_supervisor.spoken == ["Reminder: Standup in ten minutes."]
+
+
+class TestHarness:
+    def test_fake_mic_frames(self, fake_audio):
+        frames = []
+        fake_audio.tone(440, seconds=0.16).silence(seconds=0.16)
+        fake_audio.start_input(frames.append, shared_frames=True)
+        assert fake_audio.pump() == 4
+        assert [round(f.rms(), 2) for f in frames] == [0.21, 0.21, 0.0, 0.0]
+        assert fake_audio.pending_frames() == 0
+
+    def test_fake_mic_reader_queue(self, fake_audio):
+        fake_audio.silence(seconds=0.08)
+        fake_audio.start_input()
+        fake_audio.pump()
+        assert len(fake_audio.read_frame()) == fake_audio.frame_size
+        assert fake_audio.read_frame() is None
+
+    def test_scripted_replies(self, scripted_supervisor):
+        scripted_supervisor.reply_to(r"what's next", "Standup at nine.")
+        asyncio.run(scripted_supervisor.send_text("What's next today?"))
+        asyncio.run(scripted_supervisor.send_text("Thanks"))
+        assert scripted_supervisor.replies == ["Standup at nine."]
+        assert scripted_supervisor.sent == ["What's next today?", "Thanks"]
+
+    def test_format_reminder(self):
+        assert format_reminder("Standup", 1) == "Standup in 1 minute"
+        assert format_reminder("Standup", 5) == "Standup in 5 minutes"
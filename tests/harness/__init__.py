@@ -0,0 +1,15 @@
+"""
+Integration test harness - run end-to-end flows without a server or hardware.
+
+- MockServer: the server's calendar/reminder API over real local HTTP
+- FakeAudioIO: synthetic mic frames and a recording speaker (AudioIO surface)
+- ScriptedSupervisor: a voice orchestrator that speaks and replies from a script
+
+Fixtures for each live in tests/conftest.py.
+"""
+
+from .fake_audio import FakeAudioIO
+from .mock_server import MockServer, RecordedRequest
+from .scripted import ScriptedSupervisor
+
+__all__ = ["FakeAudioIO", "MockServer", "RecordedRequest", "ScriptedSupervisor"]
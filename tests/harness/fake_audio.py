@@ -0,0 +1,143 @@
+"""
+Fake audio devices - Synthetic mic frames and a recording speaker.
+
+FakeAudioIO has the same surface as assistant.audio.AudioIO (start_input,
+start_output, play_audio, read_frame, input_format, audio_stats, stop) but
+never opens a sound device. Mic input comes from a script of synthetic
+segments, delivered frame by frame when the test calls pump(), so flows
+that need "the user spoke, then went quiet" run deterministically in CI:
+
+    mic = FakeAudioIO()
+    mic.tone(440, seconds=0.5).silence(seconds=1.0)
+    mic.start_input(orchestrator._on_audio_frame, shared_frames=True)
+    mic.pump()                      # Delivers every scripted frame
+    assert mic.played_seconds() > 0  # Whatever the assistant played back
+"""
+
+from typing import Any, Callable, Dict, List, Optional
+
+import numpy as np
+
+from assistant.audio_bus import FrameQueue
+from assistant.audio_frame import AudioFrame
+from assistant.resample import AudioFormat
+
+
+class FakeAudioIO:
+    """AudioIO stand-in: scripted synthetic input, recorded output."""
+
+    def __init__(self, sample_rate: int = 24000, frame_size: int = 1920, playback_rate: Optional[int] = None,
+                 seed: int = 0):
+        self.sample_rate = sample_rate
+        self.frame_size = frame_size
+        self.playback_rate = playback_rate or sample_rate
+        self.input_queue = FrameQueue("fake mic reader", capacity=50)
+        self.played: List[np.ndarray] = []
+        self.input_running = False
+        self.output_running = False
+        self.current_output_amplitude = 0.0
+        self._script: List[np.ndarray] = []
+        self._callback: Optional[Callable] = None
+        self._shared_frames = False
+        self._rng = np.random.default_rng(seed)
+
+    # ------------------------------------------------------------------
+    # Script building (chainable)
+    # ------------------------------------------------------------------
+
+    def _samples(self, seconds: float) -> int:
+        return int(round(seconds * self.sample_rate))
+
+    def tone(self, frequency: float, seconds: float, amplitude: float = 0.3) -> "FakeAudioIO":
+        """A sine tone - loud enough for VAD/wake word energy checks to treat it as speech."""
+        t = np.arange(self._samples(seconds)) / self.sample_rate
+        self._script.append((amplitude * np.sin(2 * np.pi * frequency * t)).astype(np.float32))
+        return self
+
+    def noise(self, seconds: float, amplitude: float = 0.05) -> "FakeAudioIO":
+        """White noise (background hiss)."""
+        self._script.append((amplitude * self._rng.standard_normal(self._samples(seconds))).astype(np.float32))
+        return self
+
+    def silence(self, seconds: float) -> "FakeAudioIO":
+        self._script.append(np.zeros(self._samples(seconds), dtype=np.float32))
+        return self
+
+    def samples(self, audio: np.ndarray) -> "FakeAudioIO":
+        """Arbitrary recorded or generated audio at sample_rate."""
+        self._script.append(np.asarray(audio, dtype=np.float32))
+        return self
+
+    # ------------------------------------------------------------------
+    # AudioIO surface
+    # ------------------------------------------------------------------
+
+    def start_input(self, callback: Optional[Callable] = None, shared_frames: bool = False):
+        self._callback = callback
+        self._shared_frames = shared_frames
+        self.input_running = True
+
+    def start_output(self):
+        self.output_running = True
+
+    @property
+    def input_format(self) -> AudioFormat:
+        return AudioFormat(self.sample_rate)
+
+    @property
+    def playback_frame_size(self) -> int:
+        return int(round(self.frame_size * self.playback_rate / self.sample_rate))
+
+    def play_audio(self, audio: np.ndarray, sample_rate: Optional[int] = None):
+        if len(audio) == 0:
+            return
+        self.played.append(np.array(audio, dtype=np.float32, copy=True))
+
+    def read_frame(self, timeout: float = 0.1) -> Optional[np.ndarray]:
+        return None if self.input_queue.empty() else self.input_queue.get_nowait()
+
+    def audio_stats(self) -> List[Dict[str, Any]]:
+        return [self.input_queue.stats()]
+
+    def stop(self):
+        self.input_running = False
+        self.output_running = False
+
+    # ------------------------------------------------------------------
+    # Driving the fake mic
+    # ------------------------------------------------------------------
+
+    def pending_frames(self) -> int:
+        return int(np.ceil(sum(len(s) for s in self._script) / self.frame_size))
+
+    def pump(self, frames: Optional[int] = None) -> int:
+        """
+        Deliver up to `frames` scripted frames (all by default) the way the
+        PortAudio callback would. The last frame is zero-padded. Returns the
+        number delivered.
+        """
+        if not self.input_running:
+            raise RuntimeError("start_input() before pump()")
+        audio = np.concatenate(self._script) if self._script else np.zeros(0, dtype=np.float32)
+        available = int(np.ceil(len(audio) / self.frame_size))
+        count = available if frames is None else min(frames, available)
+
+        for i in range(count):
+            chunk = audio[i * self.frame_size:(i + 1) * self.frame_size]
+            if len(chunk) < self.frame_size:
+                chunk = np.pad(chunk, (0, self.frame_size - len(chunk)))
+            frame = AudioFrame(chunk, self.sample_rate)
+            if self._callback is None:
+                self.input_queue.put_nowait(frame.samples)
+            else:
+                self._callback(frame if self._shared_frames else frame.samples)
+
+        rest = audio[count * self.frame_size:]
+        self._script = [rest] if len(rest) else []
+        return count
+
+    def played_audio(self) -> np.ndarray:
+        return np.concatenate(self.played) if self.played else np.zeros(0, dtype=np.float32)
+
+    def played_seconds(self) -> float:
+        return len(self.played_audio()) / self.sample_rate
@@ -0,0 +1,266 @@
+"""
+Mock server - An in-process stand-in for the Node/Workers API.
+
+Serves the calendar endpoints the assistant calls (scheduler_client.py)
+over real HTTP on 127.0.0.1, so ApiClient retries, circuit breakers and
+error classification are exercised exactly as against the real server:
+
+- POST /api/calendar/appointments/batch
+- POST /api/calendar/appointments/batch-delete
+- GET  /api/calendar/appointments/:id/participants
+- POST /api/calendar/appointments/:id/invitations
+- POST /api/calendar/reminders
+- GET  /api/calendar/reminders?user_id=&completed=
+- PUT  /api/calendar/reminders/batch
+- POST /api/commands                    (idempotent per idempotency_key)
+- GET  /api/commands
+- POST /api/commands/:id/ack
+
+State is kept in memory (`appointments`, `reminders`), every request is
+recorded in `requests`, and fail() injects error statuses for a route.
+
+    with MockServer() as server:
+        client = SchedulerClient(server.url)
+        ...
+        assert server.requests[0].path == "/api/calendar/appointments/batch"
+"""
+
+import json
+import re
+import threading
+from dataclasses import dataclass, field
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any, Callable, Dict, List, Optional, Tuple
+from urllib.parse import parse_qs, urlparse
+
+
+@dataclass
+class RecordedRequest:
+    method: str
+    path: str
+    query: Dict[str, str]
+    body: Any
+    headers: Dict[str, str] = field(default_factory=dict)
+
+
+class MockServer:
+    """Threaded HTTP server with in-memory calendar and reminder state."""
+
+    def __init__(self, host: str = "127.0.0.1", port: int = 0):
+        self.appointments: Dict[str, Dict[str, Any]] = {}
+        self.reminders: Dict[str, Dict[str, Any]] = {}
+        self.participants: Dict[str, List[Dict[str, Any]]] = {}
+        self.commands: Dict[str, Dict[str, Any]] = {}
+        self.requests: List[RecordedRequest] = []
+        self._failures: List[List[Any]] = []  # [method, path regex, status, remaining]
+        self._next_id = 0
+        self._lock = threading.Lock()
+        self._routes: List[Tuple[str, re.Pattern, Callable]] = [
+            ("POST", re.compile(r"^/api/calendar/appointments/batch$"), self._create_appointments),
+            ("POST", re.compile(r"^/api/calendar/appointments/batch-delete$"), self._delete_appointments),
+            ("GET", re.compile(r"^/api/calendar/appointments/(?P<id>[^/]+)/participants$"), self._get_participants),
+            ("POST", re.compile(r"^/api/calendar/appointments/(?P<id>[^/]+)/invitations$"), self._send_invitations),
+            ("PUT", re.compile(r"^/api/calendar/reminders/batch$"), self._update_reminders),
+            ("POST", re.compile(r"^/api/calendar/reminders$"), self._create_reminder),
+            ("GET", re.compile(r"^/api/calendar/reminders$"), self._get_reminders),
+            ("POST", re.compile(r"^/api/commands$"), self._queue_command),
+            ("GET", re.compile(r"^/api/commands$"), self._get_commands),
+            ("POST", re.compile(r"^/api/commands/(?P<id>[^/]+)/ack$"), self._ack_command),
+        ]
+        self._httpd = ThreadingHTTPServer((host, port), self._handler_class())
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def url(self) -> str:
+        host, port = self._httpd.server_address[:2]
+        return f"http://{host}:{port}"
+
+    def start(self) -> "MockServer":
+        self._thread = threading.Thread(target=self._httpd.serve_forever, daemon=True, name="MockServer")
+        self._thread.start()
+        return self
+
+    def stop(self) -> None:
+        self._httpd.shutdown()
+        self._httpd.server_close()
+        if self._thread:
+            self._thread.join(timeout=5)
+
+    def __enter__(self) -> "MockServer":
+        return self.start()
+
+    def __exit__(self, *exc) -> None:
+        self.stop()
+
+    # ------------------------------------------------------------------
+    # Test controls
+    # ------------------------------------------------------------------
+
+    def fail(self, method: str, path_pattern: str, status: int = 503, times: int = 1) -> None:
+        """Answer the next `times` matching requests with `status` (e.g. to test retries and outages)."""
+        with self._lock:
+            self._failures.append([method.upper(), re.compile(path_pattern), status, times])
+
+    def add_reminder(self, user_id: str, title: str, due_time: str, **fields) -> Dict[str, Any]:
+        """Seed a reminder as if created on another device."""
+        with self._lock:
+            return self._store_reminder({"user_id": user_id, "title": title, "due_time": due_time, **fields})
+
+    def requests_to(self, path: str, method: Optional[str] = None) -> List[RecordedRequest]:
+        return [r for r in self.requests if r.path == path and (method is None or r.method == method)]
+
+    # ------------------------------------------------------------------
+    # Routes
+    # ------------------------------------------------------------------
+
+    def _id(self, prefix: str) -> str:
+        self._next_id += 1
+        return f"{prefix}-{self._next_id}"
+
+    def _create_appointments(self, body, query, match):
+        created, errors = [], []
+        for index, appointment in enumerate(body.get("appointments", [])):
+            if not appointment.get("title") or not appointment.get("start_time"):
+                errors.append({"index": index, "error": "Missing required fields: title, start_time"})
+                continue
+            record = {"id": self._id("apt"), "user_id": body.get("user_id"), **appointment}
+            self.appointments[record["id"]] = record
+            self.participants[record["id"]] = [
+                {"label": p, "rsvp_status": "pending"} for p in appointment.get("participants", [])
+            ]
+            created.append(record)
+        return 200, {"success": True, "created": created, "conflicts": [], "errors": errors}
+
+    def _delete_appointments(self, body, query, match):
+        ids = body.get("ids", [])
+        deleted = [i for i in ids if self.appointments.pop(i, None) is not None]
+        return 200, {"success": True, "deleted": deleted, "not_found": [i for i in ids if i not in deleted]}
+
+    def _get_participants(self, body, query, match):
+        appointment_id = match.group("id")
+        if appointment_id not in self.appointments:
+            return 404, {"error": "Appointment not found"}
+        return 200, {"participants": self.participants.get(appointment_id, [])}
+
+    def _send_invitations(self, body, query, match):
+        appointment_id = match.group("id")
+        if appointment_id not in self.appointments:
+            return 404, {"error": "Appointment not found"}
+        participants = self.participants.get(appointment_id, [])
+        return 200, {
+            "invitations": {"sent": [p["label"] for p in participants], "skipped": [], "failed": []},
+            "participants": participants,
+        }
+
+    def _store_reminder(self, body) -> Dict[str, Any]:
+        record = {
+            "id": self._id("rem"),
+            "user_id": body["user_id"],
+            "title": body["title"],
+            "description": body.get("description"),
+            "due_time": body["due_time"],
+            "priority": body.get("priority", 3),
+            "notification_channels": body.get("notification_channels", ["sms", "email"]),
+            "tags": body.get("tags", []),
+            "completed": False,
+            "snoozed_until": None,
+        }
+        self.reminders[record["id"]] = record
+        return record
+
+    def _create_reminder(self, body, query, match):
+        if not body.get("user_id") or not body.get("title") or not body.get("due_time"):
+            return 400, {"error": "Missing required fields: user_id, title, due_time"}
+        return 201, {"success": True, "reminder": self._store_reminder(body)}
+
+    def _get_reminders(self, body, query, match):
+        user_id = query.get("user_id")
+        if not user_id:
+            return 400, {"error": "Missing user_id parameter"}
+        completed = query.get("completed") == "true"
+        reminders = sorted(
+            (r for r in self.reminders.values() if r["user_id"] == user_id and r["completed"] == completed),
+            key=lambda r: r["due_time"],
+        )
+        return 200, {"reminders": reminders}
+
+    def _update_reminders(self, body, query, match):
+        updated, not_found = [], []
+        for item in body.get("updates", []):
+            reminder = self.reminders.get(item.get("id"))
+            if reminder is None or reminder["user_id"] != body.get("user_id"):
+                not_found.append(item.get("id"))
+                continue
+            reminder.update({k: v for k, v in item.items() if k in ("completed", "snoozed_until", "tags")})
+            updated.append(reminder)
+        return 200, {"success": True, "reminders": updated, "not_found": not_found, "errors": []}
+
+    # ------------------------------------------------------------------
+    # HTTP plumbing
+    # ------------------------------------------------------------------
+
+    def _queue_command(self, body, query, match):
+        user_id = "mock-user"  # The real server takes it from the bearer token
+        key = body.get("idempotency_key") or self._id("key")
+        for command in self.commands.values():
+            if command["user_id"] == user_id and command["idempotency_key"] == key:
+                return 200, {"command": command, "duplicate": True}
+        command = {"id": self._id("cmd"), "user_id": user_id, "type": body.get("type"), "args": body.get("args", {}),
+                   "idempotency_key": key, "status": "pending", "result": None, "created_at": "", "expires_at": "",
+                   "acked_at": None}
+        self.commands[command["id"]] = command
+        return 201, {"command": command, "duplicate": False}
+
+    def _get_commands(self, body, query, match):
+        return 200, {"commands": [c for c in self.commands.values()
+                                  if c["user_id"] == "mock-user" and c["status"] == "pending"]}
+
+    def _ack_command(self, body, query, match):
+        command = self.commands.get(match.group("id"))
+        if command is None or command["user_id"] != "mock-user":
+            return 404, {"error": "Command not found"}
+        if command["status"] == "pending":
+            command.update(status=body.get("status"), result=body.get("result"), acked_at="now")
+        return 200, {"command": command}
+
+    def _dispatch(self, method: str, raw_path: str, headers: Dict[str, str], raw_body: bytes) -> Tuple[int, Any]:
+        parsed = urlparse(raw_path)
+        query = {k: v[-1] for k, v in parse_qs(parsed.query).items()}
+        try:
+            body = json.loads(raw_body) if raw_body else {}
+        except ValueError:
+            return 400, {"error": "Invalid JSON"}
+
+        with self._lock:
+            self.requests.append(RecordedRequest(method, parsed.path, query, body, headers))
+            for failure in self._failures:
+                fail_method, pattern, status, remaining = failure
+                if remaining > 0 and fail_method == method and pattern.search(parsed.path):
+                    failure[3] -= 1
+                    return status, {"error": f"Injected failure ({status})"}
+            for route_method, pattern, handler in self._routes:
+                match = pattern.match(parsed.path)
+                if route_method == method and match:
+                    return handler(body, query, match)
+        return 404, {"error": "Not found"}
+
+    def _handler_class(self):
+        server = self
+
+        class Handler(BaseHTTPRequestHandler):
+            def _handle(self):
+                length = int(self.headers.get("Content-Length") or 0)
+                status, payload = server._dispatch(self.command, self.path, dict(self.headers), self.rfile.read(length))
+                data = json.dumps(payload).encode()
+                self.send_response(status)
+                self.send_header("Content-Type", "application/json")
+                self.send_header("Content-Length", str(len(data)))
+                self.end_headers()
+                self.wfile.write(data)
+
+            do_GET = do_POST = do_PUT = do_DELETE = _handle
+
+            def log_message(self, format, *args):
+                pass  # Keep test output quiet
+
+        return Handler
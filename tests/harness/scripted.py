@@ -0,0 +1,88 @@
+"""
+Scripted supervisor - A voice orchestrator that follows a script.
+
+Stands in for VoiceBridgeOrchestrator wherever the dashboard or a job
+talks to voice (announce, speak_text, send_text), without Moshi, a GPU or
+a sound card. It records what it was asked to say, answers user text with
+scripted replies, and - given a FakeAudioIO - "speaks" a short synthetic
+tone per utterance so output-side plumbing sees audio.
+
+Announcements go through the real notification policy, so quiet hours
+and category preferences apply exactly as they do for the real
+orchestrator. Pass `clock` to pin the time the policy sees.
+
+    supervisor = ScriptedSupervisor()
+    supervisor.reply_to(r"what's next", "Standup at ten.")
+    await supervisor.send_text("What's next today?")
+    assert supervisor.replies == ["Standup at ten."]
+"""
+
+import re
+from datetime import datetime
+from typing import Callable, List, Optional, Pattern, Tuple
+
+import numpy as np
+
+from assistant.notifications import get_notification_policy
+
+SPEECH_SECONDS_PER_WORD = 0.05  # Length of the stand-in tone, so played audio scales with what was said
+
+
+class ScriptedSupervisor:
+    """VoiceBridgeOrchestrator stand-in driven by (pattern -> reply) rules."""
+
+    def __init__(self, audio_io=None, clock: Optional[Callable[[], datetime]] = None,
+                 on_text_output: Optional[Callable[[str, str], None]] = None):
+        self.audio_io = audio_io
+        self.clock = clock
+        self.on_text_output = on_text_output  # (speaker, text), like the real orchestrator's callback
+        self.persona: Optional[str] = None
+        self.spoken: List[str] = []       # Everything said aloud (announcements and replies)
+        self.suppressed: List[Tuple[str, str]] = []  # (text, reason) for announcements the policy held back
+        self.sent: List[str] = []         # User text received
+        self.replies: List[str] = []
+        self._rules: List[Tuple[Pattern, str]] = []
+        self.default_reply: Optional[str] = None
+
+    def reply_to(self, pattern: str, reply: str) -> "ScriptedSupervisor":
+        """Answer user text matching `pattern` (case-insensitive regex) with `reply`. First match wins."""
+        self._rules.append((re.compile(pattern, re.IGNORECASE), reply))
+        return self
+
+    # ------------------------------------------------------------------
+    # Orchestrator surface
+    # ------------------------------------------------------------------
+
+    async def announce(self, text: str, priority: str = "normal", tags=()) -> bool:
+        now = self.clock() if self.clock else None
+        decision = get_notification_policy().check("speech", priority, now=now, tags=tags)
+        if not decision.allowed:
+            self.suppressed.append((text, decision.reason))
+            return False
+        await self.speak_text(text)
+        return True
+
+    async def speak_text(self, text: str):
+        self.spoken.append(text)
+        if self.on_text_output:
+            self.on_text_output("assistant", text)
+        if self.audio_io is not None:
+            seconds = SPEECH_SECONDS_PER_WORD * max(1, len(text.split()))
+            t = np.arange(int(seconds * self.audio_io.sample_rate)) / self.audio_io.sample_rate
+            self.audio_io.play_audio((0.2 * np.sin(2 * np.pi * 220 * t)).astype(np.float32))
+
+    async def send_text(self, text: str):
+        self.sent.append(text)
+        reply = next((r for pattern, r in self._rules if pattern.search(text)), self.default_reply)
+        if reply is not None:
+            self.replies.append(reply)
+            await self.speak_text(reply)
+
+    async def set_persona(self, persona_name: str):
+        self.persona = persona_name
+
+    def get_audio_stats(self):
+        return self.audio_io.audio_stats() if self.audio_io is not None else []
+
+    def stop(self):
+        pass